@@ -92,6 +92,8 @@ fn configure_validation(builder: Builder) -> Builder {
             ("CreateCollection.optimizers_config", ""),
             ("CreateCollection.vectors_config", ""),
             ("CreateCollection.quantization_config", ""),
+            ("CreateCollection.search_threads", "custom = \"crate::grpc::validate::validate_u32_range_min_1\""),
+            ("CreateCollection.strict_mode_config", ""),
             ("UpdateCollection.collection_name", "length(min = 1, max = 255)"),
             ("UpdateCollection.optimizers_config", ""),
             ("UpdateCollection.params", ""),
@@ -99,12 +101,21 @@ fn configure_validation(builder: Builder) -> Builder {
             ("UpdateCollection.hnsw_config", ""),
             ("UpdateCollection.vectors_config", ""),
             ("UpdateCollection.quantization_config", ""),
+            ("UpdateCollection.strict_mode_config", ""),
+            ("UpdateCollection.new_vectors_config", ""),
             ("DeleteCollection.collection_name", "length(min = 1, max = 255)"),
+            ("CloneCollection.collection_name", "length(min = 1, max = 255)"),
+            ("CloneCollection.target", "length(min = 1, max = 255), custom = \"common::validation::validate_collection_name\""),
+            ("CloneCollection.hnsw_config", ""),
+            ("CloneCollection.optimizers_config", ""),
+            ("CloneCollection.quantization_config", ""),
+            ("CloneCollection.timeout", "custom = \"crate::grpc::validate::validate_u64_range_min_1\""),
             ("DeleteCollection.timeout", "custom = \"crate::grpc::validate::validate_u64_range_min_1\""),
             ("CollectionConfig.params", ""),
             ("CollectionConfig.hnsw_config", ""),
             ("CollectionConfig.optimizers_config", ""),
             ("CollectionConfig.quantization_config", ""),
+            ("CollectionConfig.strict_mode_config", ""),
             ("CollectionParams.vectors_config", ""),
             ("ChangeAliases.timeout", "custom = \"crate::grpc::validate::validate_u64_range_min_1\""),
             ("ListCollectionAliasesRequest.collection_name", "length(min = 1, max = 255)"),
@@ -112,11 +123,19 @@ fn configure_validation(builder: Builder) -> Builder {
             ("WalConfigDiff.wal_capacity_mb", "custom = \"crate::grpc::validate::validate_u64_range_min_1\""),
             ("OptimizersConfigDiff.deleted_threshold", "custom = \"crate::grpc::validate::validate_f64_range_1\""),
             ("OptimizersConfigDiff.vacuum_min_vector_number", "custom = \"crate::grpc::validate::validate_u64_range_min_100\""),
+            ("StrictModeConfig.max_query_limit", "custom = \"crate::grpc::validate::validate_u64_range_min_1\""),
+            ("StrictModeConfig.max_batch_size", "custom = \"crate::grpc::validate::validate_u64_range_min_1\""),
+            ("StrictModeConfig.max_filter_conditions", "custom = \"crate::grpc::validate::validate_u64_range_min_1\""),
+            ("StrictModeConfig.max_points_count", "custom = \"crate::grpc::validate::validate_u64_range_min_1\""),
+            ("StrictModeConfig.max_disk_usage_bytes", "custom = \"crate::grpc::validate::validate_u64_range_min_1\""),
+            ("StrictModeConfig.max_concurrent_searches", "custom = \"crate::grpc::validate::validate_u64_range_min_1\""),
             ("VectorsConfig.config", ""),
             ("VectorsConfigDiff.config", ""),
             ("VectorParams.size", "range(min = 1, max = 65536)"),
             ("VectorParams.hnsw_config", ""),
             ("VectorParams.quantization_config", ""),
+            ("VectorParams.dimensionality_reduction", ""),
+            ("DimensionalityReduction.input_size", "range(min = 1, max = 65536)"),
             ("VectorParamsMap.map", ""),
             ("VectorParamsDiff.hnsw_config", ""),
             ("VectorParamsDiff.quantization_config", ""),
@@ -126,6 +145,7 @@ fn configure_validation(builder: Builder) -> Builder {
             ("ScalarQuantization.quantile", "custom = \"crate::grpc::validate::validate_f32_range_min_0_5_max_1\""),
             ("UpdateCollectionClusterSetupRequest.timeout", "custom = \"crate::grpc::validate::validate_u64_range_min_1\""),
             ("UpdateCollectionClusterSetupRequest.operation", ""),
+            ("CancelOperationRequest.timeout", "custom = \"crate::grpc::validate::validate_u64_range_min_1\""),
         ], &[
             "ListCollectionsRequest",
             "CollectionParamsDiff",
@@ -138,6 +158,7 @@ fn configure_validation(builder: Builder) -> Builder {
             "QuantizationConfigDiff",
             "quantization_config_diff::Quantization",
             "Replica",
+            "ListOperationsRequest",
         ])
         // Service: collections_internal.proto
         .validates(&[
@@ -149,20 +170,41 @@ fn configure_validation(builder: Builder) -> Builder {
         // Service: points.proto
         .validates(&[
             ("UpsertPoints.collection_name", "length(min = 1, max = 255)"),
+            ("UpsertPoints.timeout", "custom = \"crate::grpc::validate::validate_u64_range_min_1\""),
+            ("UpsertPoints.ttl", "custom = \"crate::grpc::validate::validate_u64_range_min_1\""),
             ("DeletePoints.collection_name", "length(min = 1, max = 255)"),
+            ("DeletePoints.timeout", "custom = \"crate::grpc::validate::validate_u64_range_min_1\""),
             ("UpdatePointVectors.collection_name", "length(min = 1, max = 255)"),
+            ("UpdatePointVectors.timeout", "custom = \"crate::grpc::validate::validate_u64_range_min_1\""),
             ("UpdatePointVectors.vectors", "custom(function = \"crate::grpc::validate::validate_named_vectors_not_empty\", message = \"must specify vectors to update\")"),
             ("DeletePointVectors.collection_name", "length(min = 1, max = 255)"),
+            ("DeletePointVectors.timeout", "custom = \"crate::grpc::validate::validate_u64_range_min_1\""),
             ("DeletePointVectors.vector_names", "length(min = 1, message = \"must specify vector names to delete\")"),
             ("GetPoints.collection_name", "length(min = 1, max = 255)"),
             ("SetPayloadPoints.collection_name", "length(min = 1, max = 255)"),
+            ("SetPayloadPoints.timeout", "custom = \"crate::grpc::validate::validate_u64_range_min_1\""),
             ("DeletePayloadPoints.collection_name", "length(min = 1, max = 255)"),
+            ("DeletePayloadPoints.timeout", "custom = \"crate::grpc::validate::validate_u64_range_min_1\""),
             ("ClearPayloadPoints.collection_name", "length(min = 1, max = 255)"),
+            ("ClearPayloadPoints.timeout", "custom = \"crate::grpc::validate::validate_u64_range_min_1\""),
+            ("UndeletePoints.collection_name", "length(min = 1, max = 255)"),
+            ("UndeletePoints.timeout", "custom = \"crate::grpc::validate::validate_u64_range_min_1\""),
+            ("DeleteByFilterPoints.collection_name", "length(min = 1, max = 255)"),
+            ("DeleteByFilterPoints.filter", ""),
+            ("DeleteByFilterPoints.batch_size", "custom = \"crate::grpc::validate::validate_u64_range_min_1\""),
+            ("DeleteByFilterPoints.timeout", "custom = \"crate::grpc::validate::validate_u64_range_min_1\""),
+            ("SetPayloadByFilterPoints.collection_name", "length(min = 1, max = 255)"),
+            ("SetPayloadByFilterPoints.filter", ""),
+            ("SetPayloadByFilterPoints.batch_size", "custom = \"crate::grpc::validate::validate_u64_range_min_1\""),
+            ("SetPayloadByFilterPoints.timeout", "custom = \"crate::grpc::validate::validate_u64_range_min_1\""),
             ("UpdateBatchPoints.collection_name", "length(min = 1, max = 255)"),
+            ("UpdateBatchPoints.timeout", "custom = \"crate::grpc::validate::validate_u64_range_min_1\""),
             ("UpdateBatchPoints.operations", "length(min = 1)"),
             ("CreateFieldIndexCollection.collection_name", "length(min = 1, max = 255)"),
+            ("CreateFieldIndexCollection.timeout", "custom = \"crate::grpc::validate::validate_u64_range_min_1\""),
             ("CreateFieldIndexCollection.field_name", "length(min = 1)"),
             ("DeleteFieldIndexCollection.collection_name", "length(min = 1, max = 255)"),
+            ("DeleteFieldIndexCollection.timeout", "custom = \"crate::grpc::validate::validate_u64_range_min_1\""),
             ("DeleteFieldIndexCollection.field_name", "length(min = 1)"),
             // TODO(sparse) validate sparse vector for `SearchPoints`
             ("SearchPoints.collection_name", "length(min = 1, max = 255)"),
@@ -173,6 +215,7 @@ fn configure_validation(builder: Builder) -> Builder {
             ("SearchBatchPoints.collection_name", "length(min = 1, max = 255)"),
             ("SearchBatchPoints.search_points", ""),
             ("SearchBatchPoints.timeout", "custom = \"crate::grpc::validate::validate_u64_range_min_1\""),
+            ("SearchPointsPage.search_points", ""),
             // TODO(sparse) validate sparse vector for `SearchPointGroups`
             ("SearchPointGroups.collection_name", "length(min = 1, max = 255)"),
             ("SearchPointGroups.group_by", "length(min = 1)"),
@@ -186,6 +229,8 @@ fn configure_validation(builder: Builder) -> Builder {
             ("ScrollPoints.collection_name", "length(min = 1, max = 255)"),
             ("ScrollPoints.filter", ""),
             ("ScrollPoints.limit", "custom = \"crate::grpc::validate::validate_u32_range_min_1\""),
+            ("ScrollPoints.session_id", "length(min = 1, max = 4096)"),
+            ("ScrollPoints.session_ttl_sec", "range(min = 1, max = 3600)"),
             ("RecommendPoints.collection_name", "length(min = 1, max = 255)"),
             ("RecommendPoints.filter", ""),
             ("RecommendPoints.params", ""),
@@ -243,6 +288,7 @@ fn configure_validation(builder: Builder) -> Builder {
             ("ClearPayloadPointsInternal.clear_payload_points", ""),
             ("CreateFieldIndexCollectionInternal.create_field_index_collection", ""),
             ("DeleteFieldIndexCollectionInternal.delete_field_index_collection", ""),
+            ("UpdateBatchInternal.update_batch", ""),
             ("SearchPointsInternal.search_points", ""),
             ("SearchBatchPointsInternal.collection_name", "length(min = 1, max = 255)"),
             ("SearchBatchPointsInternal.search_points", ""),
@@ -250,6 +296,15 @@ fn configure_validation(builder: Builder) -> Builder {
             ("CoreSearchPoints.filter", ""),
             ("CoreSearchPoints.limit", "range(min = 1)"),
             ("CoreSearchPoints.params", ""),
+            ("CoreSearchPoints.prefetch", ""),
+            ("CoreSearchPoints.rescore", ""),
+            ("CoreSearchPoints.distinct_by", ""),
+            ("CorePrefetch.params", ""),
+            ("CorePrefetch.limit", "range(min = 1)"),
+            ("CoreRescore.formula", "length(min = 1)"),
+            ("CoreRescore.candidates", "range(min = 1)"),
+            ("CoreDistinct.key", "length(min = 1)"),
+            ("CoreDistinct.candidates", "range(min = 1)"),
             ("CoreSearchBatchPointsInternal.collection_name", "length(min = 1, max = 255)"),
             ("CoreSearchBatchPointsInternal.search_points", ""),
             ("RecommendPointsInternal.recommend_points", ""),
@@ -258,6 +313,7 @@ fn configure_validation(builder: Builder) -> Builder {
             ("CountPointsInternal.count_points", ""),
             ("SyncPointsInternal.sync_points", ""),
             ("SyncPoints.collection_name", "length(min = 1, max = 255)"),
+            ("SyncPoints.timeout", "custom = \"crate::grpc::validate::validate_u64_range_min_1\""),
         ], &[])
         // Service: raft_service.proto
         .validates(&[
@@ -282,6 +338,7 @@ fn configure_validation(builder: Builder) -> Builder {
             "ListFullSnapshotsRequest",
         ])
         .field_attribute("SnapshotDescription.creation_time", "#[serde(skip)]")
+        .field_attribute("RunningOperation.started_at", "#[serde(skip)]")
 }
 
 fn append_to_file(path: &str, line: &str) {
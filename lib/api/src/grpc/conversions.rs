@@ -19,14 +19,15 @@ use crate::grpc::qdrant::vectors::VectorsOptions;
 use crate::grpc::qdrant::with_payload_selector::SelectorOptions;
 use crate::grpc::qdrant::{
     shard_key, with_vectors_selector, CollectionDescription, CollectionOperationResponse,
-    Condition, Distance, FieldCondition, Filter, GeoBoundingBox, GeoPoint, GeoPolygon, GeoRadius,
-    HasIdCondition, HealthCheckReply, HnswConfigDiff, IsEmptyCondition, IsNullCondition,
-    ListCollectionsResponse, ListValue, Match, NamedVectors, NestedCondition,
-    PayloadExcludeSelector, PayloadIncludeSelector, PayloadIndexParams, PayloadSchemaInfo,
-    PayloadSchemaType, PointId, ProductQuantization, QuantizationConfig, QuantizationSearchParams,
-    QuantizationType, Range, RepeatedIntegers, RepeatedStrings, ScalarQuantization, ScoredPoint,
-    SearchParams, ShardKey, Struct, TextIndexParams, TokenizerType, Value, ValuesCount, Vector,
-    Vectors, VectorsSelector, WithPayloadSelector, WithVectorsSelector,
+    Condition, Datatype, Direction, Distance, FieldCondition, Filter, GeoBoundingBox, GeoPoint,
+    GeoPolygon, GeoRadius, HasIdCondition, HealthCheckReply, HnswConfigDiff, IsEmptyCondition,
+    IsNullCondition, ListCollectionsResponse, ListValue, Match, MultiVectorComparator,
+    MultiVectorConfig, NamedVectors, NestedCondition, OrderBy, PayloadExcludeSelector,
+    PayloadIncludeSelector, PayloadIndexParams, PayloadSchemaInfo, PayloadSchemaType, PointId,
+    ProductQuantization, QuantizationConfig, QuantizationSearchParams, QuantizationType, Range,
+    RepeatedIntegers, RepeatedStrings, ScalarQuantization, ScoredPoint, SearchParams, ShardKey,
+    Struct, TextIndexParams, TokenizerType, Value, ValuesCount, Vector, Vectors, VectorsSelector,
+    WithPayloadSelector, WithVectorsSelector,
 };
 
 pub fn payload_to_proto(payload: segment::types::Payload) -> HashMap<String, Value> {
@@ -436,31 +437,62 @@ impl From<segment::data_types::vectors::Vector> for Vector {
             segment::data_types::vectors::Vector::Dense(vector) => Self {
                 data: vector,
                 indices: None,
+                vectors_count: None,
             },
             segment::data_types::vectors::Vector::Sparse(vector) => Self {
                 data: vector.values,
                 indices: Some(SparseIndices {
                     data: vector.indices,
                 }),
+                vectors_count: None,
             },
+            segment::data_types::vectors::Vector::MultiDense(vectors) => {
+                // Allocate flattened data once, instead of growing it vector by vector
+                let mut data = Vec::with_capacity(vectors.iter().map(Vec::len).sum());
+                for vector in &vectors {
+                    data.extend_from_slice(vector);
+                }
+                Self {
+                    vectors_count: Some(vectors.len() as u32),
+                    data,
+                    indices: None,
+                }
+            }
         }
     }
 }
 
 impl From<Vector> for segment::data_types::vectors::Vector {
     fn from(vector: Vector) -> Self {
-        match vector.indices {
-            None => segment::data_types::vectors::Vector::Dense(vector.data),
-            Some(indices) => segment::data_types::vectors::Vector::Sparse(
+        match (vector.indices, vector.vectors_count) {
+            (Some(indices), _) => segment::data_types::vectors::Vector::Sparse(
                 sparse::common::sparse_vector::SparseVector {
                     values: vector.data,
                     indices: indices.data,
                 },
             ),
+            (None, Some(vectors_count)) => segment::data_types::vectors::Vector::MultiDense(
+                into_multi_dense_vector(vector.data, vectors_count),
+            ),
+            (None, None) => segment::data_types::vectors::Vector::Dense(vector.data),
         }
     }
 }
 
+/// Split the flattened data of the multi-vector into vectors of the same size
+///
+/// Data, which can't be split into `vectors_count` vectors evenly, is rejected by the validation.
+pub fn into_multi_dense_vector(
+    data: Vec<VectorElementType>,
+    vectors_count: u32,
+) -> segment::data_types::vectors::MultiDenseVector {
+    if vectors_count == 0 || data.is_empty() {
+        return Vec::new();
+    }
+    let dim = data.len().div_ceil(vectors_count as usize);
+    data.chunks(dim).map(|vector| vector.to_vec()).collect()
+}
+
 impl From<HashMap<String, segment::data_types::vectors::Vector>> for NamedVectors {
     fn from(vectors: HashMap<String, segment::data_types::vectors::Vector>) -> Self {
         Self {
@@ -482,13 +514,7 @@ impl From<segment::data_types::vectors::VectorStruct> for Vectors {
                 }
             }
             segment::data_types::vectors::VectorStruct::Multi(vectors) => Self {
-                vectors_options: Some(VectorsOptions::Vectors(NamedVectors {
-                    vectors: HashMap::from_iter(
-                        vectors
-                            .iter()
-                            .map(|(name, vector)| (name.clone(), vector.clone().into())),
-                    ),
-                })),
+                vectors_options: Some(VectorsOptions::Vectors(NamedVectors::from(vectors))),
             },
         }
     }
@@ -523,6 +549,42 @@ impl From<segment::data_types::groups::GroupId> for GroupId {
     }
 }
 
+impl From<segment::data_types::order_by::OrderBy> for OrderBy {
+    fn from(order_by: segment::data_types::order_by::OrderBy) -> Self {
+        let direction = order_by.direction.map(|direction| match direction {
+            segment::data_types::order_by::Direction::Asc => Direction::Asc,
+            segment::data_types::order_by::Direction::Desc => Direction::Desc,
+        });
+        Self {
+            key: order_by.key,
+            direction: direction.map(|direction| direction as i32),
+            start_from: order_by.start_from,
+        }
+    }
+}
+
+impl TryFrom<OrderBy> for segment::data_types::order_by::OrderBy {
+    type Error = Status;
+
+    fn try_from(order_by: OrderBy) -> Result<Self, Self::Error> {
+        let direction = order_by
+            .direction
+            .map(|direction| match Direction::from_i32(direction) {
+                Some(Direction::Asc) => Ok(segment::data_types::order_by::Direction::Asc),
+                Some(Direction::Desc) => Ok(segment::data_types::order_by::Direction::Desc),
+                None => Err(Status::invalid_argument(format!(
+                    "Unknown order direction: {direction}"
+                ))),
+            })
+            .transpose()?;
+        Ok(Self {
+            key: order_by.key,
+            direction,
+            start_from: order_by.start_from,
+        })
+    }
+}
+
 impl From<NamedVectors> for HashMap<String, segment::data_types::vectors::Vector> {
     fn from(vectors: NamedVectors) -> Self {
         vectors
@@ -1226,13 +1288,74 @@ pub fn from_grpc_dist(dist: i32) -> Result<segment::types::Distance, Status> {
     }
 }
 
+pub fn from_grpc_datatype(datatype: i32) -> Result<segment::types::VectorStorageDatatype, Status> {
+    match Datatype::from_i32(datatype) {
+        Some(Datatype::Default | Datatype::Float32) => {
+            Ok(segment::types::VectorStorageDatatype::Float32)
+        }
+        Some(Datatype::Uint8) => Ok(segment::types::VectorStorageDatatype::Uint8),
+        Some(Datatype::Float16) => Ok(segment::types::VectorStorageDatatype::Float16),
+        None => Err(Status::invalid_argument(format!(
+            "Malformed datatype parameter, unexpected value: {datatype}"
+        ))),
+    }
+}
+
+impl TryFrom<MultiVectorConfig> for segment::types::MultiVectorConfig {
+    type Error = Status;
+
+    fn try_from(value: MultiVectorConfig) -> Result<Self, Self::Error> {
+        let comparator = match MultiVectorComparator::from_i32(value.comparator) {
+            Some(MultiVectorComparator::MaxSim) => segment::types::MultiVectorComparator::MaxSim,
+            None => {
+                return Err(Status::invalid_argument(format!(
+                    "Malformed multi-vector comparator, unexpected value: {}",
+                    value.comparator,
+                )))
+            }
+        };
+        Ok(Self { comparator })
+    }
+}
+
+impl From<segment::types::MultiVectorConfig> for MultiVectorConfig {
+    fn from(value: segment::types::MultiVectorConfig) -> Self {
+        let comparator = match value.comparator {
+            segment::types::MultiVectorComparator::MaxSim => MultiVectorComparator::MaxSim,
+        };
+        Self {
+            comparator: comparator.into(),
+        }
+    }
+}
+
 pub fn into_named_vector_struct(
     vector_name: Option<String>,
     vector: Vec<VectorElementType>,
     indices: Option<SparseIndices>,
+    vectors_count: Option<u32>,
 ) -> Result<segment::data_types::vectors::NamedVectorStruct, Status> {
-    use segment::data_types::vectors::{NamedSparseVector, NamedVector, NamedVectorStruct};
+    use segment::data_types::vectors::{
+        NamedMultiDenseVector, NamedSparseVector, NamedVector, NamedVectorStruct,
+    };
     use sparse::common::sparse_vector::SparseVector;
+    if let Some(vectors_count) = vectors_count {
+        if indices.is_some() {
+            return Err(Status::invalid_argument(
+                "Multi-vector can't have sparse indices",
+            ));
+        }
+        if vectors_count == 0 || vector.len() % vectors_count as usize != 0 {
+            return Err(Status::invalid_argument(
+                "Multi-vector must consist of `vectors_count` vectors of the same size",
+            ));
+        }
+        return Ok(NamedVectorStruct::MultiDense(NamedMultiDenseVector {
+            name: vector_name
+                .ok_or_else(|| Status::invalid_argument("Multi-vector must have a name"))?,
+            vector: into_multi_dense_vector(vector, vectors_count),
+        }));
+    }
     Ok(match indices {
         Some(indices) => NamedVectorStruct::Sparse(NamedSparseVector {
             name: vector_name
@@ -21,6 +21,19 @@ pub struct VectorParams {
     /// If true - serve vectors from disk. If set to false, the vectors will be loaded in RAM.
     #[prost(bool, optional, tag = "5")]
     pub on_disk: ::core::option::Option<bool>,
+    /// If true - L2-normalize vectors on upsert and query vectors on search. Can't be changed after the collection is created.
+    #[prost(bool, optional, tag = "6")]
+    pub normalize: ::core::option::Option<bool>,
+    /// If set - vectors and query vectors of `input_size` dimensions are projected to `size` dimensions by a learned linear transform
+    #[prost(message, optional, tag = "7")]
+    #[validate]
+    pub dimensionality_reduction: ::core::option::Option<DimensionalityReduction>,
+    /// If set - each point stores a variable number of vectors of `size` dimensions under this name
+    #[prost(message, optional, tag = "8")]
+    pub multivector_config: ::core::option::Option<MultiVectorConfig>,
+    /// Type of the elements of the stored vectors. If omitted - float32 is used. Can't be changed after the collection is created.
+    #[prost(enumeration = "Datatype", optional, tag = "9")]
+    pub datatype: ::core::option::Option<i32>,
 }
 #[derive(validator::Validate)]
 #[derive(serde::Serialize)]
@@ -38,6 +51,42 @@ pub struct VectorParamsDiff {
     /// If true - serve vectors from disk. If set to false, the vectors will be loaded in RAM.
     #[prost(bool, optional, tag = "3")]
     pub on_disk: ::core::option::Option<bool>,
+    /// Set the learned transform of the vector with dimensionality reduction configured. Could only be set once.
+    #[prost(message, optional, tag = "4")]
+    pub dimensionality_reduction_transform: ::core::option::Option<LinearTransform>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct LinearTransform {
+    /// Mean of the input vectors, subtracted before the projection. Has `input_size` values.
+    #[prost(float, repeated, tag = "1")]
+    pub mean: ::prost::alloc::vec::Vec<f32>,
+    /// Projection matrix in row-major order, with a row per output dimension. Has `size * input_size` values.
+    #[prost(float, repeated, tag = "2")]
+    pub matrix: ::prost::alloc::vec::Vec<f32>,
+}
+#[derive(validator::Validate)]
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct MultiVectorConfig {
+    /// How to compare the multi-vectors
+    #[prost(enumeration = "MultiVectorComparator", tag = "1")]
+    pub comparator: i32,
+}
+#[derive(validator::Validate)]
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct DimensionalityReduction {
+    /// Size of the vectors sent by the clients, before the reduction. Must be greater than the size of the stored vectors.
+    #[prost(uint64, tag = "1")]
+    #[validate(range(min = 1, max = 65536))]
+    pub input_size: u64,
+    /// Learned transform of the vectors. If omitted - vectors can't be inserted until the transform is trained or uploaded.
+    #[prost(message, optional, tag = "2")]
+    pub transform: ::core::option::Option<LinearTransform>,
 }
 #[derive(validator::Validate)]
 #[derive(serde::Serialize)]
@@ -232,6 +281,9 @@ pub struct WalConfigDiff {
     /// Number of segments to create in advance
     #[prost(uint64, optional, tag = "2")]
     pub wal_segments_ahead: ::core::option::Option<u64>,
+    /// Compression of the new WAL entries
+    #[prost(enumeration = "WalCompression", optional, tag = "3")]
+    pub wal_compression: ::core::option::Option<i32>,
 }
 #[derive(validator::Validate)]
 #[derive(serde::Serialize)]
@@ -299,6 +351,76 @@ pub struct OptimizersConfigDiff {
     /// Max number of threads, which can be used for optimization. If 0 - `NUM_CPU - 1` will be used
     #[prost(uint64, optional, tag = "8")]
     pub max_optimization_threads: ::core::option::Option<u64>,
+    ///
+    /// Size (in kilobytes) of updates accumulated since the last flush, which triggers a flush
+    /// before `flush_interval_sec` has passed.
+    /// If not set, flush is triggered by interval only.
+    #[prost(uint64, optional, tag = "9")]
+    pub flush_dirty_threshold: ::core::option::Option<u64>,
+    ///
+    /// Number of update operations accumulated since the last flush, which triggers a flush
+    /// before `flush_interval_sec` has passed.
+    /// If not set, flush is triggered by interval only.
+    #[prost(uint64, optional, tag = "10")]
+    pub flush_dirty_operations: ::core::option::Option<u64>,
+    ///
+    /// Maximum number of segments per shard, which are rebuilt at the same time to apply changed
+    /// collection configuration, e.g. HNSW, quantization or on-disk storage parameters.
+    /// Set to 0 to abort the rollout: running rebuilds are cancelled, and no new ones are started
+    /// until the limit is raised.
+    /// If not set, all segments with outdated configuration are rebuilt at once.
+    #[prost(uint64, optional, tag = "11")]
+    pub max_config_rebuild_segments: ::core::option::Option<u64>,
+}
+#[derive(validator::Validate)]
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct StrictModeConfig {
+    /// Whether strict mode is enabled for the collection
+    #[prost(bool, optional, tag = "1")]
+    pub enabled: ::core::option::Option<bool>,
+    /// Max allowed `limit` parameter of search, recommend, discover, scroll and group requests
+    #[prost(uint64, optional, tag = "2")]
+    #[validate(custom = "crate::grpc::validate::validate_u64_range_min_1")]
+    pub max_query_limit: ::core::option::Option<u64>,
+    /// Max allowed `offset` parameter of search, recommend and discover requests
+    #[prost(uint64, optional, tag = "3")]
+    pub max_query_offset: ::core::option::Option<u64>,
+    /// Max number of requests in a batch request, or number of points in a single update
+    #[prost(uint64, optional, tag = "4")]
+    #[validate(custom = "crate::grpc::validate::validate_u64_range_min_1")]
+    pub max_batch_size: ::core::option::Option<u64>,
+    /// Max number of conditions in a filter, including conditions of nested filters
+    #[prost(uint64, optional, tag = "5")]
+    #[validate(custom = "crate::grpc::validate::validate_u64_range_min_1")]
+    pub max_filter_conditions: ::core::option::Option<u64>,
+    /// Allow filtering by payload fields without payload index
+    #[prost(bool, optional, tag = "6")]
+    pub unindexed_filtering_allowed: ::core::option::Option<bool>,
+    /// Max number of points in the collection, inserts and updates are rejected once it is reached
+    #[prost(uint64, optional, tag = "7")]
+    #[validate(custom = "crate::grpc::validate::validate_u64_range_min_1")]
+    pub max_points_count: ::core::option::Option<u64>,
+    /// Max size of the collection data on disk in bytes, inserts and updates are rejected once it is reached
+    #[prost(uint64, optional, tag = "8")]
+    #[validate(custom = "crate::grpc::validate::validate_u64_range_min_1")]
+    pub max_disk_usage_bytes: ::core::option::Option<u64>,
+    /// Max number of searches executed in the collection at the same time, further searches are queued or rejected
+    #[prost(uint64, optional, tag = "9")]
+    #[validate(custom = "crate::grpc::validate::validate_u64_range_min_1")]
+    pub max_concurrent_searches: ::core::option::Option<u64>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct FeatureFlags {
+    /// Append-log payload storage, selected by the `AppendLog` payload storage backend
+    #[prost(bool, optional, tag = "1")]
+    pub append_log_payload_storage: ::core::option::Option<bool>,
+    /// PCA dimensionality reduction of vectors, selected by `dimensionality_reduction` of the vector params
+    #[prost(bool, optional, tag = "2")]
+    pub dimensionality_reduction: ::core::option::Option<bool>,
 }
 #[derive(validator::Validate)]
 #[derive(serde::Serialize)]
@@ -390,6 +512,60 @@ pub mod quantization_config_diff {
         Binary(super::BinaryQuantization),
     }
 }
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct VectorStoragePolicy {
+    /// Placement of the original vectors, only applies to dense vectors
+    #[prost(enumeration = "StoragePlacement", optional, tag = "1")]
+    pub vectors: ::core::option::Option<i32>,
+    /// Placement of the vector index
+    #[prost(enumeration = "StoragePlacement", optional, tag = "2")]
+    pub index: ::core::option::Option<i32>,
+    /// Placement of the quantized vectors
+    #[prost(enumeration = "StoragePlacement", optional, tag = "3")]
+    pub quantization: ::core::option::Option<i32>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct StoragePolicy {
+    /// Placement of the data of all vectors, unless specified for the vector in `vectors`
+    #[prost(message, optional, tag = "1")]
+    pub all_vectors: ::core::option::Option<VectorStoragePolicy>,
+    /// Placement of the data of individual vectors, by vector name
+    #[prost(map = "string, message", tag = "2")]
+    pub vectors: ::std::collections::HashMap<
+        ::prost::alloc::string::String,
+        VectorStoragePolicy,
+    >,
+    /// Placement of the payload
+    #[prost(enumeration = "StoragePlacement", optional, tag = "3")]
+    pub payload: ::core::option::Option<i32>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct InferenceConfig {
+    /// URL of the endpoint, which converts documents into vectors
+    #[prost(string, tag = "1")]
+    pub url: ::prost::alloc::string::String,
+    /// Name of the model, passed to the endpoint
+    #[prost(string, optional, tag = "2")]
+    pub model: ::core::option::Option<::prost::alloc::string::String>,
+    /// Dense vector of the collection, which is inferred. Default: unnamed vector
+    #[prost(string, optional, tag = "3")]
+    pub vector_name: ::core::option::Option<::prost::alloc::string::String>,
+    /// Max number of documents in a single request to the endpoint
+    #[prost(uint64, optional, tag = "4")]
+    pub batch_size: ::core::option::Option<u64>,
+    /// Timeout of a single request to the endpoint in seconds
+    #[prost(uint64, optional, tag = "5")]
+    pub timeout_sec: ::core::option::Option<u64>,
+    /// Max number of retries of a failed request
+    #[prost(uint64, optional, tag = "6")]
+    pub max_retries: ::core::option::Option<u64>,
+}
 #[derive(validator::Validate)]
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -446,6 +622,35 @@ pub struct CreateCollection {
     /// Configuration for sparse vectors
     #[prost(message, optional, tag = "16")]
     pub sparse_vectors_config: ::core::option::Option<SparseVectorConfig>,
+    /// Number of threads in a dedicated search thread pool of the collection, can't exceed the number of CPUs. If not set - shared search thread pool is used
+    #[prost(uint32, optional, tag = "17")]
+    #[validate(custom = "crate::grpc::validate::validate_u32_range_min_1")]
+    pub search_threads: ::core::option::Option<u32>,
+    /// Storage engine of the on-disk payload
+    #[prost(enumeration = "PayloadStorageBackend", optional, tag = "18")]
+    pub payload_storage_backend: ::core::option::Option<i32>,
+    /// Payload field with the expiration time of the point (unix timestamp in seconds). If set - expired points are deleted automatically
+    #[prost(string, optional, tag = "19")]
+    pub expiration_field: ::core::option::Option<::prost::alloc::string::String>,
+    /// Strict mode restrictions for the requests to the collection
+    #[prost(message, optional, tag = "20")]
+    #[validate]
+    pub strict_mode_config: ::core::option::Option<StrictModeConfig>,
+    /// If set - deleted points are retained for this number of seconds and could be restored
+    #[prost(uint64, optional, tag = "21")]
+    pub soft_delete_window_sec: ::core::option::Option<u64>,
+    /// Placement of the collection data on disk or in RAM, takes precedence over the individual settings
+    #[prost(message, optional, tag = "22")]
+    pub storage_policy: ::core::option::Option<StoragePolicy>,
+    /// Payload field, which selects the shard of the point. Required for the `PayloadKey` sharding method
+    #[prost(string, optional, tag = "23")]
+    pub sharding_payload_key: ::core::option::Option<::prost::alloc::string::String>,
+    /// Name of the collection template to take the configuration and payload indexes from. Parameters of this request take precedence
+    #[prost(string, optional, tag = "24")]
+    pub from_template: ::core::option::Option<::prost::alloc::string::String>,
+    /// Flags of the experimental features, unset ones are inherited from the node configuration
+    #[prost(message, optional, tag = "25")]
+    pub feature_flags: ::core::option::Option<FeatureFlags>,
 }
 #[derive(validator::Validate)]
 #[derive(serde::Serialize)]
@@ -483,6 +688,17 @@ pub struct UpdateCollection {
     /// New sparse vector parameters
     #[prost(message, optional, tag = "8")]
     pub sparse_vectors_config: ::core::option::Option<SparseVectorConfig>,
+    /// New strict mode restrictions, unspecified fields are left unchanged
+    #[prost(message, optional, tag = "9")]
+    #[validate]
+    pub strict_mode_config: ::core::option::Option<StrictModeConfig>,
+    /// New named vectors to add to the collection
+    #[prost(message, optional, tag = "10")]
+    #[validate]
+    pub new_vectors_config: ::core::option::Option<VectorParamsMap>,
+    /// New flags of the experimental features, unspecified flags are left unchanged
+    #[prost(message, optional, tag = "11")]
+    pub feature_flags: ::core::option::Option<FeatureFlags>,
 }
 #[derive(validator::Validate)]
 #[derive(serde::Serialize)]
@@ -539,6 +755,48 @@ pub struct CollectionParams {
     /// Configuration for sparse vectors
     #[prost(message, optional, tag = "10")]
     pub sparse_vectors_config: ::core::option::Option<SparseVectorConfig>,
+    /// Number of threads in a dedicated search thread pool of the collection
+    #[prost(uint32, optional, tag = "11")]
+    pub search_threads: ::core::option::Option<u32>,
+    /// Storage engine of the on-disk payload
+    #[prost(enumeration = "PayloadStorageBackend", optional, tag = "12")]
+    pub payload_storage_backend: ::core::option::Option<i32>,
+    /// Payload field with the expiration time of the point (unix timestamp in seconds)
+    #[prost(string, optional, tag = "13")]
+    pub expiration_field: ::core::option::Option<::prost::alloc::string::String>,
+    /// Number of seconds to retain deleted points, which could be restored during this time
+    #[prost(uint64, optional, tag = "14")]
+    pub soft_delete_window_sec: ::core::option::Option<u64>,
+    /// Max number of seconds an update waits for the consensus to deactivate failed replicas
+    #[prost(uint64, optional, tag = "15")]
+    pub shard_deactivation_timeout_sec: ::core::option::Option<u64>,
+    /// Placement of the collection data on disk or in RAM
+    #[prost(message, optional, tag = "16")]
+    pub storage_policy: ::core::option::Option<StoragePolicy>,
+    /// Write ordering of the updates, which don't specify it
+    #[prost(enumeration = "WriteOrderingType", optional, tag = "17")]
+    pub default_write_ordering: ::core::option::Option<i32>,
+    /// Whether updates, which don't specify it, wait for the changes to be applied
+    #[prost(bool, optional, tag = "18")]
+    pub default_wait: ::core::option::Option<bool>,
+    /// Payload field, which selects the shard of the point
+    #[prost(string, optional, tag = "19")]
+    pub sharding_payload_key: ::core::option::Option<::prost::alloc::string::String>,
+    /// If true - reads fall back to Partial and Listener replicas, when there are not enough Active replicas
+    #[prost(bool, optional, tag = "20")]
+    pub allow_degraded_reads: ::core::option::Option<bool>,
+    /// If true - low priority reads are served by Listener replicas first
+    #[prost(bool, optional, tag = "21")]
+    pub serve_reads_from_listeners: ::core::option::Option<bool>,
+    /// If true - updates return once enough replicas applied them, slower replicas complete in background
+    #[prost(bool, optional, tag = "22")]
+    pub hedge_updates: ::core::option::Option<bool>,
+    /// Number of seconds to retain previous states of the changed points for time-travel reads
+    #[prost(uint64, optional, tag = "23")]
+    pub time_travel_window_sec: ::core::option::Option<u64>,
+    /// Endpoint, which converts documents into vectors of the collection
+    #[prost(message, optional, tag = "24")]
+    pub inference: ::core::option::Option<InferenceConfig>,
 }
 #[derive(validator::Validate)]
 #[derive(serde::Serialize)]
@@ -557,6 +815,42 @@ pub struct CollectionParamsDiff {
     /// Fan-out every read request to these many additional remote nodes (and return first available response)
     #[prost(uint32, optional, tag = "4")]
     pub read_fan_out_factor: ::core::option::Option<u32>,
+    /// Storage engine of the on-disk payload
+    #[prost(enumeration = "PayloadStorageBackend", optional, tag = "5")]
+    pub payload_storage_backend: ::core::option::Option<i32>,
+    /// Payload field with the expiration time of the point (unix timestamp in seconds)
+    #[prost(string, optional, tag = "6")]
+    pub expiration_field: ::core::option::Option<::prost::alloc::string::String>,
+    /// Number of seconds to retain deleted points, which could be restored during this time
+    #[prost(uint64, optional, tag = "7")]
+    pub soft_delete_window_sec: ::core::option::Option<u64>,
+    /// Max number of seconds an update waits for the consensus to deactivate failed replicas
+    #[prost(uint64, optional, tag = "8")]
+    pub shard_deactivation_timeout_sec: ::core::option::Option<u64>,
+    /// Placement of the collection data on disk or in RAM, replaces the current storage policy
+    #[prost(message, optional, tag = "9")]
+    pub storage_policy: ::core::option::Option<StoragePolicy>,
+    /// Write ordering of the updates, which don't specify it
+    #[prost(enumeration = "WriteOrderingType", optional, tag = "10")]
+    pub default_write_ordering: ::core::option::Option<i32>,
+    /// Whether updates, which don't specify it, wait for the changes to be applied
+    #[prost(bool, optional, tag = "11")]
+    pub default_wait: ::core::option::Option<bool>,
+    /// If true - reads fall back to Partial and Listener replicas, when there are not enough Active replicas
+    #[prost(bool, optional, tag = "12")]
+    pub allow_degraded_reads: ::core::option::Option<bool>,
+    /// If true - low priority reads are served by Listener replicas first
+    #[prost(bool, optional, tag = "13")]
+    pub serve_reads_from_listeners: ::core::option::Option<bool>,
+    /// If true - updates return once enough replicas applied them, slower replicas complete in background
+    #[prost(bool, optional, tag = "14")]
+    pub hedge_updates: ::core::option::Option<bool>,
+    /// Number of seconds to retain previous states of the changed points for time-travel reads
+    #[prost(uint64, optional, tag = "15")]
+    pub time_travel_window_sec: ::core::option::Option<u64>,
+    /// Endpoint, which converts documents into vectors of the collection, replaces the current inference config
+    #[prost(message, optional, tag = "16")]
+    pub inference: ::core::option::Option<InferenceConfig>,
 }
 #[derive(validator::Validate)]
 #[derive(serde::Serialize)]
@@ -581,6 +875,13 @@ pub struct CollectionConfig {
     #[prost(message, optional, tag = "5")]
     #[validate]
     pub quantization_config: ::core::option::Option<QuantizationConfig>,
+    /// Strict mode restrictions for the requests to the collection
+    #[prost(message, optional, tag = "6")]
+    #[validate]
+    pub strict_mode_config: ::core::option::Option<StrictModeConfig>,
+    /// Flags of the experimental features, unset ones are inherited from the node configuration
+    #[prost(message, optional, tag = "7")]
+    pub feature_flags: ::core::option::Option<FeatureFlags>,
 }
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -662,6 +963,9 @@ pub struct CollectionInfo {
     /// Approximate number of indexed vectors in the collection.
     #[prost(uint64, optional, tag = "10")]
     pub indexed_vectors_count: ::core::option::Option<u64>,
+    /// Approximate size of the collection data on disk in bytes
+    #[prost(uint64, optional, tag = "11")]
+    pub disk_usage_bytes: ::core::option::Option<u64>,
 }
 #[derive(validator::Validate)]
 #[derive(serde::Serialize)]
@@ -680,7 +984,7 @@ pub struct ChangeAliases {
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct AliasOperations {
-    #[prost(oneof = "alias_operations::Action", tags = "1, 2, 3")]
+    #[prost(oneof = "alias_operations::Action", tags = "1, 2, 3, 4")]
     pub action: ::core::option::Option<alias_operations::Action>,
 }
 /// Nested message and enum types in `AliasOperations`.
@@ -695,6 +999,8 @@ pub mod alias_operations {
         RenameAlias(super::RenameAlias),
         #[prost(message, tag = "3")]
         DeleteAlias(super::DeleteAlias),
+        #[prost(message, tag = "4")]
+        DropCollection(super::DropCollection),
     }
 }
 #[derive(serde::Serialize)]
@@ -727,6 +1033,14 @@ pub struct DeleteAlias {
     #[prost(string, tag = "1")]
     pub alias_name: ::prost::alloc::string::String,
 }
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct DropCollection {
+    /// Name of the collection to delete, after all alias changes are applied
+    #[prost(string, tag = "1")]
+    pub collection_name: ::prost::alloc::string::String,
+}
 #[derive(validator::Validate)]
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -911,6 +1225,14 @@ pub struct DeleteShardKey {
     #[prost(message, optional, tag = "1")]
     pub shard_key: ::core::option::Option<ShardKey>,
 }
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SplitShard {
+    /// Shard to split, points of the shard are distributed between two new shards
+    #[prost(uint32, tag = "1")]
+    pub shard_id: u32,
+}
 #[derive(validator::Validate)]
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -925,7 +1247,7 @@ pub struct UpdateCollectionClusterSetupRequest {
     pub timeout: ::core::option::Option<u64>,
     #[prost(
         oneof = "update_collection_cluster_setup_request::Operation",
-        tags = "2, 3, 4, 5, 7, 8"
+        tags = "2, 3, 4, 5, 7, 8, 9"
     )]
     #[validate]
     pub operation: ::core::option::Option<
@@ -950,6 +1272,8 @@ pub mod update_collection_cluster_setup_request {
         CreateShardKey(super::CreateShardKey),
         #[prost(message, tag = "8")]
         DeleteShardKey(super::DeleteShardKey),
+        #[prost(message, tag = "9")]
+        SplitShard(super::SplitShard),
     }
 }
 #[derive(serde::Serialize)]
@@ -1001,6 +1325,145 @@ pub struct DeleteShardKeyResponse {
     #[prost(bool, tag = "1")]
     pub result: bool,
 }
+#[derive(validator::Validate)]
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ListOperationsRequest {}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct OperationProgress {
+    /// Amount of work done, e.g. the number of transferred points
+    #[prost(uint64, tag = "1")]
+    pub done: u64,
+    /// Total amount of work, if known
+    #[prost(uint64, optional, tag = "2")]
+    pub total: ::core::option::Option<u64>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RunningOperation {
+    /// Id of the operation, unique within the node
+    #[prost(uint64, tag = "1")]
+    pub id: u64,
+    #[prost(enumeration = "RunningOperationKind", tag = "2")]
+    pub kind: i32,
+    /// Collection of the operation
+    #[prost(string, tag = "3")]
+    pub collection_name: ::prost::alloc::string::String,
+    /// Shard of the operation, if it is shard specific
+    #[prost(uint32, optional, tag = "4")]
+    pub shard_id: ::core::option::Option<u32>,
+    /// Human readable description of the operation
+    #[prost(string, tag = "5")]
+    pub description: ::prost::alloc::string::String,
+    /// Time the operation started at
+    #[prost(message, optional, tag = "6")]
+    #[serde(skip)]
+    pub started_at: ::core::option::Option<::prost_types::Timestamp>,
+    /// Progress of the operation, if it is tracked
+    #[prost(message, optional, tag = "7")]
+    pub progress: ::core::option::Option<OperationProgress>,
+    /// If the operation could be cancelled
+    #[prost(bool, tag = "8")]
+    pub cancellable: bool,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ListOperationsResponse {
+    #[prost(message, repeated, tag = "1")]
+    pub operations: ::prost::alloc::vec::Vec<RunningOperation>,
+    /// Time spent to process
+    #[prost(double, tag = "2")]
+    pub time: f64,
+}
+#[derive(validator::Validate)]
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CancelOperationRequest {
+    /// Id of the operation to cancel
+    #[prost(uint64, tag = "1")]
+    pub operation_id: u64,
+    /// Wait timeout for operation commit in seconds, if not specified - default value will be supplied
+    #[prost(uint64, optional, tag = "2")]
+    #[validate(custom = "crate::grpc::validate::validate_u64_range_min_1")]
+    pub timeout: ::core::option::Option<u64>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CancelOperationResponse {
+    #[prost(bool, tag = "1")]
+    pub result: bool,
+    /// Time spent to process
+    #[prost(double, tag = "2")]
+    pub time: f64,
+}
+#[derive(serde::Serialize)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum Datatype {
+    Default = 0,
+    /// 4 bytes per dimension
+    Float32 = 1,
+    /// 1 byte per dimension, values must be integers in range \[0, 255\]
+    Uint8 = 2,
+    /// 2 bytes per dimension, absolute values must not exceed 65504
+    Float16 = 3,
+}
+impl Datatype {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            Datatype::Default => "Default",
+            Datatype::Float32 => "Float32",
+            Datatype::Uint8 => "Uint8",
+            Datatype::Float16 => "Float16",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "Default" => Some(Self::Default),
+            "Float32" => Some(Self::Float32),
+            "Uint8" => Some(Self::Uint8),
+            "Float16" => Some(Self::Float16),
+            _ => None,
+        }
+    }
+}
+#[derive(serde::Serialize)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum MultiVectorComparator {
+    /// Sum of the highest similarities of each query vector to any of the point vectors
+    MaxSim = 0,
+}
+impl MultiVectorComparator {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            MultiVectorComparator::MaxSim => "MaxSim",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "MaxSim" => Some(Self::MaxSim),
+            _ => None,
+        }
+    }
+}
 #[derive(serde::Serialize)]
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
 #[repr(i32)]
@@ -1186,6 +1649,8 @@ pub enum ShardingMethod {
     Auto = 0,
     /// Shard by user-defined key
     Custom = 1,
+    /// Shard by the hash of the value of the sharding payload key
+    PayloadKey = 2,
 }
 impl ShardingMethod {
     /// String value of the enum field names used in the ProtoBuf definition.
@@ -1196,6 +1661,7 @@ impl ShardingMethod {
         match self {
             ShardingMethod::Auto => "Auto",
             ShardingMethod::Custom => "Custom",
+            ShardingMethod::PayloadKey => "PayloadKey",
         }
     }
     /// Creates an enum from field names used in the ProtoBuf definition.
@@ -1203,6 +1669,7 @@ impl ShardingMethod {
         match value {
             "Auto" => Some(Self::Auto),
             "Custom" => Some(Self::Custom),
+            "PayloadKey" => Some(Self::PayloadKey),
             _ => None,
         }
     }
@@ -1210,35 +1677,28 @@ impl ShardingMethod {
 #[derive(serde::Serialize)]
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
 #[repr(i32)]
-pub enum TokenizerType {
-    Unknown = 0,
-    Prefix = 1,
-    Whitespace = 2,
-    Word = 3,
-    Multilingual = 4,
+pub enum PayloadStorageBackend {
+    /// Payload is stored in RocksDB
+    Rocksdb = 0,
+    /// Payload is stored in memory-mapped append-only pages
+    AppendLog = 1,
 }
-impl TokenizerType {
+impl PayloadStorageBackend {
     /// String value of the enum field names used in the ProtoBuf definition.
     ///
     /// The values are not transformed in any way and thus are considered stable
     /// (if the ProtoBuf definition does not change) and safe for programmatic use.
     pub fn as_str_name(&self) -> &'static str {
         match self {
-            TokenizerType::Unknown => "Unknown",
-            TokenizerType::Prefix => "Prefix",
-            TokenizerType::Whitespace => "Whitespace",
-            TokenizerType::Word => "Word",
-            TokenizerType::Multilingual => "Multilingual",
+            PayloadStorageBackend::Rocksdb => "Rocksdb",
+            PayloadStorageBackend::AppendLog => "AppendLog",
         }
     }
     /// Creates an enum from field names used in the ProtoBuf definition.
     pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
         match value {
-            "Unknown" => Some(Self::Unknown),
-            "Prefix" => Some(Self::Prefix),
-            "Whitespace" => Some(Self::Whitespace),
-            "Word" => Some(Self::Word),
-            "Multilingual" => Some(Self::Multilingual),
+            "Rocksdb" => Some(Self::Rocksdb),
+            "AppendLog" => Some(Self::AppendLog),
             _ => None,
         }
     }
@@ -1246,44 +1706,28 @@ impl TokenizerType {
 #[derive(serde::Serialize)]
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
 #[repr(i32)]
-pub enum ReplicaState {
-    /// Active and sound
-    Active = 0,
-    /// Failed for some reason
-    Dead = 1,
-    /// The shard is partially loaded and is currently receiving data from other shards
-    Partial = 2,
-    /// Collection is being created
-    Initializing = 3,
-    /// A shard which receives data, but is not used for search; Useful for backup shards
-    Listener = 4,
-    /// Snapshot shard transfer is in progress; Updates should not be sent to (and are ignored by) the shard
-    PartialSnapshot = 5,
+pub enum StoragePlacement {
+    /// Data is stored on disk and read through mmap
+    Disk = 0,
+    /// Data is kept in RAM
+    Ram = 1,
 }
-impl ReplicaState {
+impl StoragePlacement {
     /// String value of the enum field names used in the ProtoBuf definition.
     ///
     /// The values are not transformed in any way and thus are considered stable
     /// (if the ProtoBuf definition does not change) and safe for programmatic use.
     pub fn as_str_name(&self) -> &'static str {
         match self {
-            ReplicaState::Active => "Active",
-            ReplicaState::Dead => "Dead",
-            ReplicaState::Partial => "Partial",
-            ReplicaState::Initializing => "Initializing",
-            ReplicaState::Listener => "Listener",
-            ReplicaState::PartialSnapshot => "PartialSnapshot",
+            StoragePlacement::Disk => "Disk",
+            StoragePlacement::Ram => "Ram",
         }
     }
     /// Creates an enum from field names used in the ProtoBuf definition.
     pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
         match value {
-            "Active" => Some(Self::Active),
-            "Dead" => Some(Self::Dead),
-            "Partial" => Some(Self::Partial),
-            "Initializing" => Some(Self::Initializing),
-            "Listener" => Some(Self::Listener),
-            "PartialSnapshot" => Some(Self::PartialSnapshot),
+            "Disk" => Some(Self::Disk),
+            "Ram" => Some(Self::Ram),
             _ => None,
         }
     }
@@ -1291,2151 +1735,1753 @@ impl ReplicaState {
 #[derive(serde::Serialize)]
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
 #[repr(i32)]
-pub enum ShardTransferMethod {
-    StreamRecords = 0,
-    Snapshot = 1,
-}
-impl ShardTransferMethod {
+pub enum WalCompression {
+    /// Entries are stored as is
+    None = 0,
+    /// Fast compression with moderate ratio
+    Lz4 = 1,
+    /// Better compression ratio at a higher CPU cost
+    Zstd = 2,
+}
+impl WalCompression {
     /// String value of the enum field names used in the ProtoBuf definition.
     ///
     /// The values are not transformed in any way and thus are considered stable
     /// (if the ProtoBuf definition does not change) and safe for programmatic use.
     pub fn as_str_name(&self) -> &'static str {
         match self {
-            ShardTransferMethod::StreamRecords => "StreamRecords",
-            ShardTransferMethod::Snapshot => "Snapshot",
+            WalCompression::None => "None",
+            WalCompression::Lz4 => "Lz4",
+            WalCompression::Zstd => "Zstd",
         }
     }
     /// Creates an enum from field names used in the ProtoBuf definition.
     pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
         match value {
-            "StreamRecords" => Some(Self::StreamRecords),
-            "Snapshot" => Some(Self::Snapshot),
+            "None" => Some(Self::None),
+            "Lz4" => Some(Self::Lz4),
+            "Zstd" => Some(Self::Zstd),
             _ => None,
         }
     }
 }
-/// Generated client implementations.
-pub mod collections_client {
-    #![allow(unused_variables, dead_code, missing_docs, clippy::let_unit_value)]
-    use tonic::codegen::*;
-    use tonic::codegen::http::Uri;
-    #[derive(Debug, Clone)]
-    pub struct CollectionsClient<T> {
-        inner: tonic::client::Grpc<T>,
-    }
-    impl CollectionsClient<tonic::transport::Channel> {
-        /// Attempt to create a new client by connecting to a given endpoint.
-        pub async fn connect<D>(dst: D) -> Result<Self, tonic::transport::Error>
-        where
-            D: TryInto<tonic::transport::Endpoint>,
-            D::Error: Into<StdError>,
-        {
-            let conn = tonic::transport::Endpoint::new(dst)?.connect().await?;
-            Ok(Self::new(conn))
+#[derive(serde::Serialize)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum WriteOrderingType {
+    /// Write operations may be reordered, works faster, default
+    Weak = 0,
+    /// Write operations go through dynamically selected leader, may be inconsistent for a short period of time in case of leader change
+    Medium = 1,
+    /// Write operations go through the permanent leader, consistent, but may be unavailable if leader is down
+    Strong = 2,
+}
+impl WriteOrderingType {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            WriteOrderingType::Weak => "Weak",
+            WriteOrderingType::Medium => "Medium",
+            WriteOrderingType::Strong => "Strong",
         }
     }
-    impl<T> CollectionsClient<T>
-    where
-        T: tonic::client::GrpcService<tonic::body::BoxBody>,
-        T::Error: Into<StdError>,
-        T::ResponseBody: Body<Data = Bytes> + Send + 'static,
-        <T::ResponseBody as Body>::Error: Into<StdError> + Send,
-    {
-        pub fn new(inner: T) -> Self {
-            let inner = tonic::client::Grpc::new(inner);
-            Self { inner }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "Weak" => Some(Self::Weak),
+            "Medium" => Some(Self::Medium),
+            "Strong" => Some(Self::Strong),
+            _ => None,
         }
-        pub fn with_origin(inner: T, origin: Uri) -> Self {
-            let inner = tonic::client::Grpc::with_origin(inner, origin);
-            Self { inner }
+    }
+}
+#[derive(serde::Serialize)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum TokenizerType {
+    Unknown = 0,
+    Prefix = 1,
+    Whitespace = 2,
+    Word = 3,
+    Multilingual = 4,
+}
+impl TokenizerType {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            TokenizerType::Unknown => "Unknown",
+            TokenizerType::Prefix => "Prefix",
+            TokenizerType::Whitespace => "Whitespace",
+            TokenizerType::Word => "Word",
+            TokenizerType::Multilingual => "Multilingual",
         }
-        pub fn with_interceptor<F>(
-            inner: T,
-            interceptor: F,
-        ) -> CollectionsClient<InterceptedService<T, F>>
-        where
-            F: tonic::service::Interceptor,
-            T::ResponseBody: Default,
-            T: tonic::codegen::Service<
-                http::Request<tonic::body::BoxBody>,
-                Response = http::Response<
-                    <T as tonic::client::GrpcService<tonic::body::BoxBody>>::ResponseBody,
-                >,
-            >,
-            <T as tonic::codegen::Service<
-                http::Request<tonic::body::BoxBody>,
-            >>::Error: Into<StdError> + Send + Sync,
-        {
-            CollectionsClient::new(InterceptedService::new(inner, interceptor))
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "Unknown" => Some(Self::Unknown),
+            "Prefix" => Some(Self::Prefix),
+            "Whitespace" => Some(Self::Whitespace),
+            "Word" => Some(Self::Word),
+            "Multilingual" => Some(Self::Multilingual),
+            _ => None,
         }
-        /// Compress requests with the given encoding.
-        ///
-        /// This requires the server to support it otherwise it might respond with an
-        /// error.
-        #[must_use]
-        pub fn send_compressed(mut self, encoding: CompressionEncoding) -> Self {
-            self.inner = self.inner.send_compressed(encoding);
-            self
+    }
+}
+#[derive(serde::Serialize)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum ReplicaState {
+    /// Active and sound
+    Active = 0,
+    /// Failed for some reason
+    Dead = 1,
+    /// The shard is partially loaded and is currently receiving data from other shards
+    Partial = 2,
+    /// Collection is being created
+    Initializing = 3,
+    /// A shard which receives data, but is not used for search; Useful for backup shards
+    Listener = 4,
+    /// Snapshot shard transfer is in progress; Updates should not be sent to (and are ignored by) the shard
+    PartialSnapshot = 5,
+}
+impl ReplicaState {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            ReplicaState::Active => "Active",
+            ReplicaState::Dead => "Dead",
+            ReplicaState::Partial => "Partial",
+            ReplicaState::Initializing => "Initializing",
+            ReplicaState::Listener => "Listener",
+            ReplicaState::PartialSnapshot => "PartialSnapshot",
         }
-        /// Enable decompressing responses.
-        #[must_use]
-        pub fn accept_compressed(mut self, encoding: CompressionEncoding) -> Self {
-            self.inner = self.inner.accept_compressed(encoding);
-            self
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "Active" => Some(Self::Active),
+            "Dead" => Some(Self::Dead),
+            "Partial" => Some(Self::Partial),
+            "Initializing" => Some(Self::Initializing),
+            "Listener" => Some(Self::Listener),
+            "PartialSnapshot" => Some(Self::PartialSnapshot),
+            _ => None,
         }
-        /// Limits the maximum size of a decoded message.
-        ///
-        /// Default: `4MB`
-        #[must_use]
-        pub fn max_decoding_message_size(mut self, limit: usize) -> Self {
-            self.inner = self.inner.max_decoding_message_size(limit);
-            self
+    }
+}
+#[derive(serde::Serialize)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum ShardTransferMethod {
+    StreamRecords = 0,
+    Snapshot = 1,
+    WalDelta = 2,
+}
+impl ShardTransferMethod {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            ShardTransferMethod::StreamRecords => "StreamRecords",
+            ShardTransferMethod::Snapshot => "Snapshot",
+            ShardTransferMethod::WalDelta => "WalDelta",
         }
-        /// Limits the maximum size of an encoded message.
-        ///
-        /// Default: `usize::MAX`
-        #[must_use]
-        pub fn max_encoding_message_size(mut self, limit: usize) -> Self {
-            self.inner = self.inner.max_encoding_message_size(limit);
-            self
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "StreamRecords" => Some(Self::StreamRecords),
+            "Snapshot" => Some(Self::Snapshot),
+            "WalDelta" => Some(Self::WalDelta),
+            _ => None,
         }
-        ///
-        /// Get detailed information about specified existing collection
-        pub async fn get(
-            &mut self,
-            request: impl tonic::IntoRequest<super::GetCollectionInfoRequest>,
-        ) -> std::result::Result<
-            tonic::Response<super::GetCollectionInfoResponse>,
-            tonic::Status,
-        > {
-            self.inner
-                .ready()
-                .await
-                .map_err(|e| {
-                    tonic::Status::new(
-                        tonic::Code::Unknown,
-                        format!("Service was not ready: {}", e.into()),
-                    )
-                })?;
-            let codec = tonic::codec::ProstCodec::default();
-            let path = http::uri::PathAndQuery::from_static("/qdrant.Collections/Get");
-            let mut req = request.into_request();
-            req.extensions_mut().insert(GrpcMethod::new("qdrant.Collections", "Get"));
-            self.inner.unary(req, path, codec).await
+    }
+}
+#[derive(serde::Serialize)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum RunningOperationKind {
+    UnknownOperationKind = 0,
+    /// Optimization of the segments, like merging or vacuuming
+    Optimization = 1,
+    /// Building of the vector and payload indexes of the segments
+    IndexBuild = 2,
+    /// Transfer of a shard to another peer
+    ShardTransfer = 3,
+    /// Creation of a collection snapshot
+    SnapshotCreation = 4,
+    /// Batched update of the points matching a filter
+    UpdateByFilter = 5,
+}
+impl RunningOperationKind {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            RunningOperationKind::UnknownOperationKind => "UnknownOperationKind",
+            RunningOperationKind::Optimization => "Optimization",
+            RunningOperationKind::IndexBuild => "IndexBuild",
+            RunningOperationKind::ShardTransfer => "ShardTransfer",
+            RunningOperationKind::SnapshotCreation => "SnapshotCreation",
+            RunningOperationKind::UpdateByFilter => "UpdateByFilter",
         }
-        ///
-        /// Get list name of all existing collections
-        pub async fn list(
-            &mut self,
-            request: impl tonic::IntoRequest<super::ListCollectionsRequest>,
-        ) -> std::result::Result<
-            tonic::Response<super::ListCollectionsResponse>,
-            tonic::Status,
-        > {
-            self.inner
-                .ready()
-                .await
-                .map_err(|e| {
-                    tonic::Status::new(
-                        tonic::Code::Unknown,
-                        format!("Service was not ready: {}", e.into()),
-                    )
-                })?;
-            let codec = tonic::codec::ProstCodec::default();
-            let path = http::uri::PathAndQuery::from_static("/qdrant.Collections/List");
-            let mut req = request.into_request();
-            req.extensions_mut().insert(GrpcMethod::new("qdrant.Collections", "List"));
-            self.inner.unary(req, path, codec).await
-        }
-        ///
-        /// Create new collection with given parameters
-        pub async fn create(
-            &mut self,
-            request: impl tonic::IntoRequest<super::CreateCollection>,
-        ) -> std::result::Result<
-            tonic::Response<super::CollectionOperationResponse>,
-            tonic::Status,
-        > {
-            self.inner
-                .ready()
-                .await
-                .map_err(|e| {
-                    tonic::Status::new(
-                        tonic::Code::Unknown,
-                        format!("Service was not ready: {}", e.into()),
-                    )
-                })?;
-            let codec = tonic::codec::ProstCodec::default();
-            let path = http::uri::PathAndQuery::from_static(
-                "/qdrant.Collections/Create",
-            );
-            let mut req = request.into_request();
-            req.extensions_mut().insert(GrpcMethod::new("qdrant.Collections", "Create"));
-            self.inner.unary(req, path, codec).await
-        }
-        ///
-        /// Update parameters of the existing collection
-        pub async fn update(
-            &mut self,
-            request: impl tonic::IntoRequest<super::UpdateCollection>,
-        ) -> std::result::Result<
-            tonic::Response<super::CollectionOperationResponse>,
-            tonic::Status,
-        > {
-            self.inner
-                .ready()
-                .await
-                .map_err(|e| {
-                    tonic::Status::new(
-                        tonic::Code::Unknown,
-                        format!("Service was not ready: {}", e.into()),
-                    )
-                })?;
-            let codec = tonic::codec::ProstCodec::default();
-            let path = http::uri::PathAndQuery::from_static(
-                "/qdrant.Collections/Update",
-            );
-            let mut req = request.into_request();
-            req.extensions_mut().insert(GrpcMethod::new("qdrant.Collections", "Update"));
-            self.inner.unary(req, path, codec).await
-        }
-        ///
-        /// Drop collection and all associated data
-        pub async fn delete(
-            &mut self,
-            request: impl tonic::IntoRequest<super::DeleteCollection>,
-        ) -> std::result::Result<
-            tonic::Response<super::CollectionOperationResponse>,
-            tonic::Status,
-        > {
-            self.inner
-                .ready()
-                .await
-                .map_err(|e| {
-                    tonic::Status::new(
-                        tonic::Code::Unknown,
-                        format!("Service was not ready: {}", e.into()),
-                    )
-                })?;
-            let codec = tonic::codec::ProstCodec::default();
-            let path = http::uri::PathAndQuery::from_static(
-                "/qdrant.Collections/Delete",
-            );
-            let mut req = request.into_request();
-            req.extensions_mut().insert(GrpcMethod::new("qdrant.Collections", "Delete"));
-            self.inner.unary(req, path, codec).await
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "UnknownOperationKind" => Some(Self::UnknownOperationKind),
+            "Optimization" => Some(Self::Optimization),
+            "IndexBuild" => Some(Self::IndexBuild),
+            "ShardTransfer" => Some(Self::ShardTransfer),
+            "SnapshotCreation" => Some(Self::SnapshotCreation),
+            "UpdateByFilter" => Some(Self::UpdateByFilter),
+            _ => None,
         }
-        ///
-        /// Update Aliases of the existing collection
-        pub async fn update_aliases(
-            &mut self,
-            request: impl tonic::IntoRequest<super::ChangeAliases>,
-        ) -> std::result::Result<
-            tonic::Response<super::CollectionOperationResponse>,
-            tonic::Status,
-        > {
-            self.inner
-                .ready()
-                .await
-                .map_err(|e| {
-                    tonic::Status::new(
-                        tonic::Code::Unknown,
-                        format!("Service was not ready: {}", e.into()),
-                    )
-                })?;
-            let codec = tonic::codec::ProstCodec::default();
-            let path = http::uri::PathAndQuery::from_static(
-                "/qdrant.Collections/UpdateAliases",
-            );
-            let mut req = request.into_request();
-            req.extensions_mut()
-                .insert(GrpcMethod::new("qdrant.Collections", "UpdateAliases"));
-            self.inner.unary(req, path, codec).await
+    }
+}
+/// `Struct` represents a structured data value, consisting of fields
+/// which map to dynamically typed values. In some languages, `Struct`
+/// might be supported by a native representation. For example, in
+/// scripting languages like JS a struct is represented as an
+/// object. The details of that representation are described together
+/// with the proto support for the language.
+///
+/// The JSON representation for `Struct` is a JSON object.
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Struct {
+    /// Unordered map of dynamically typed values.
+    #[prost(map = "string, message", tag = "1")]
+    pub fields: ::std::collections::HashMap<::prost::alloc::string::String, Value>,
+}
+/// `Value` represents a dynamically typed value which can be either
+/// null, a number, a string, a boolean, a recursive struct value, or a
+/// list of values. A producer of value is expected to set one of those
+/// variants, absence of any variant indicates an error.
+///
+/// The JSON representation for `Value` is a JSON value.
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Value {
+    /// The kind of value.
+    #[prost(oneof = "value::Kind", tags = "1, 2, 3, 4, 5, 6, 7")]
+    pub kind: ::core::option::Option<value::Kind>,
+}
+/// Nested message and enum types in `Value`.
+pub mod value {
+    /// The kind of value.
+    #[derive(serde::Serialize)]
+    #[allow(clippy::derive_partial_eq_without_eq)]
+    #[derive(Clone, PartialEq, ::prost::Oneof)]
+    pub enum Kind {
+        /// Represents a null value.
+        #[prost(enumeration = "super::NullValue", tag = "1")]
+        NullValue(i32),
+        /// Represents a double value.
+        #[prost(double, tag = "2")]
+        DoubleValue(f64),
+        /// Represents an integer value
+        #[prost(int64, tag = "3")]
+        IntegerValue(i64),
+        /// Represents a string value.
+        #[prost(string, tag = "4")]
+        StringValue(::prost::alloc::string::String),
+        /// Represents a boolean value.
+        #[prost(bool, tag = "5")]
+        BoolValue(bool),
+        /// Represents a structured value.
+        #[prost(message, tag = "6")]
+        StructValue(super::Struct),
+        /// Represents a repeated `Value`.
+        #[prost(message, tag = "7")]
+        ListValue(super::ListValue),
+    }
+}
+/// `ListValue` is a wrapper around a repeated field of values.
+///
+/// The JSON representation for `ListValue` is a JSON array.
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ListValue {
+    /// Repeated field of dynamically typed values.
+    #[prost(message, repeated, tag = "1")]
+    pub values: ::prost::alloc::vec::Vec<Value>,
+}
+/// `NullValue` is a singleton enumeration to represent the null value for the
+/// `Value` type union.
+///
+///   The JSON representation for `NullValue` is JSON `null`.
+#[derive(serde::Serialize)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum NullValue {
+    /// Null value.
+    NullValue = 0,
+}
+impl NullValue {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            NullValue::NullValue => "NULL_VALUE",
         }
-        ///
-        /// Get list of all aliases for a collection
-        pub async fn list_collection_aliases(
-            &mut self,
-            request: impl tonic::IntoRequest<super::ListCollectionAliasesRequest>,
-        ) -> std::result::Result<
-            tonic::Response<super::ListAliasesResponse>,
-            tonic::Status,
-        > {
-            self.inner
-                .ready()
-                .await
-                .map_err(|e| {
-                    tonic::Status::new(
-                        tonic::Code::Unknown,
-                        format!("Service was not ready: {}", e.into()),
-                    )
-                })?;
-            let codec = tonic::codec::ProstCodec::default();
-            let path = http::uri::PathAndQuery::from_static(
-                "/qdrant.Collections/ListCollectionAliases",
-            );
-            let mut req = request.into_request();
-            req.extensions_mut()
-                .insert(GrpcMethod::new("qdrant.Collections", "ListCollectionAliases"));
-            self.inner.unary(req, path, codec).await
-        }
-        ///
-        /// Get list of all aliases for all existing collections
-        pub async fn list_aliases(
-            &mut self,
-            request: impl tonic::IntoRequest<super::ListAliasesRequest>,
-        ) -> std::result::Result<
-            tonic::Response<super::ListAliasesResponse>,
-            tonic::Status,
-        > {
-            self.inner
-                .ready()
-                .await
-                .map_err(|e| {
-                    tonic::Status::new(
-                        tonic::Code::Unknown,
-                        format!("Service was not ready: {}", e.into()),
-                    )
-                })?;
-            let codec = tonic::codec::ProstCodec::default();
-            let path = http::uri::PathAndQuery::from_static(
-                "/qdrant.Collections/ListAliases",
-            );
-            let mut req = request.into_request();
-            req.extensions_mut()
-                .insert(GrpcMethod::new("qdrant.Collections", "ListAliases"));
-            self.inner.unary(req, path, codec).await
-        }
-        ///
-        /// Get cluster information for a collection
-        pub async fn collection_cluster_info(
-            &mut self,
-            request: impl tonic::IntoRequest<super::CollectionClusterInfoRequest>,
-        ) -> std::result::Result<
-            tonic::Response<super::CollectionClusterInfoResponse>,
-            tonic::Status,
-        > {
-            self.inner
-                .ready()
-                .await
-                .map_err(|e| {
-                    tonic::Status::new(
-                        tonic::Code::Unknown,
-                        format!("Service was not ready: {}", e.into()),
-                    )
-                })?;
-            let codec = tonic::codec::ProstCodec::default();
-            let path = http::uri::PathAndQuery::from_static(
-                "/qdrant.Collections/CollectionClusterInfo",
-            );
-            let mut req = request.into_request();
-            req.extensions_mut()
-                .insert(GrpcMethod::new("qdrant.Collections", "CollectionClusterInfo"));
-            self.inner.unary(req, path, codec).await
-        }
-        ///
-        /// Update cluster setup for a collection
-        pub async fn update_collection_cluster_setup(
-            &mut self,
-            request: impl tonic::IntoRequest<super::UpdateCollectionClusterSetupRequest>,
-        ) -> std::result::Result<
-            tonic::Response<super::UpdateCollectionClusterSetupResponse>,
-            tonic::Status,
-        > {
-            self.inner
-                .ready()
-                .await
-                .map_err(|e| {
-                    tonic::Status::new(
-                        tonic::Code::Unknown,
-                        format!("Service was not ready: {}", e.into()),
-                    )
-                })?;
-            let codec = tonic::codec::ProstCodec::default();
-            let path = http::uri::PathAndQuery::from_static(
-                "/qdrant.Collections/UpdateCollectionClusterSetup",
-            );
-            let mut req = request.into_request();
-            req.extensions_mut()
-                .insert(
-                    GrpcMethod::new("qdrant.Collections", "UpdateCollectionClusterSetup"),
-                );
-            self.inner.unary(req, path, codec).await
-        }
-        ///
-        /// Create shard key
-        pub async fn create_shard_key(
-            &mut self,
-            request: impl tonic::IntoRequest<super::CreateShardKeyRequest>,
-        ) -> std::result::Result<
-            tonic::Response<super::CreateShardKeyResponse>,
-            tonic::Status,
-        > {
-            self.inner
-                .ready()
-                .await
-                .map_err(|e| {
-                    tonic::Status::new(
-                        tonic::Code::Unknown,
-                        format!("Service was not ready: {}", e.into()),
-                    )
-                })?;
-            let codec = tonic::codec::ProstCodec::default();
-            let path = http::uri::PathAndQuery::from_static(
-                "/qdrant.Collections/CreateShardKey",
-            );
-            let mut req = request.into_request();
-            req.extensions_mut()
-                .insert(GrpcMethod::new("qdrant.Collections", "CreateShardKey"));
-            self.inner.unary(req, path, codec).await
-        }
-        ///
-        /// Delete shard key
-        pub async fn delete_shard_key(
-            &mut self,
-            request: impl tonic::IntoRequest<super::DeleteShardKeyRequest>,
-        ) -> std::result::Result<
-            tonic::Response<super::DeleteShardKeyResponse>,
-            tonic::Status,
-        > {
-            self.inner
-                .ready()
-                .await
-                .map_err(|e| {
-                    tonic::Status::new(
-                        tonic::Code::Unknown,
-                        format!("Service was not ready: {}", e.into()),
-                    )
-                })?;
-            let codec = tonic::codec::ProstCodec::default();
-            let path = http::uri::PathAndQuery::from_static(
-                "/qdrant.Collections/DeleteShardKey",
-            );
-            let mut req = request.into_request();
-            req.extensions_mut()
-                .insert(GrpcMethod::new("qdrant.Collections", "DeleteShardKey"));
-            self.inner.unary(req, path, codec).await
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "NULL_VALUE" => Some(Self::NullValue),
+            _ => None,
         }
     }
 }
-/// Generated server implementations.
-pub mod collections_server {
-    #![allow(unused_variables, dead_code, missing_docs, clippy::let_unit_value)]
-    use tonic::codegen::*;
-    /// Generated trait containing gRPC methods that should be implemented for use with CollectionsServer.
-    #[async_trait]
-    pub trait Collections: Send + Sync + 'static {
-        ///
-        /// Get detailed information about specified existing collection
-        async fn get(
-            &self,
-            request: tonic::Request<super::GetCollectionInfoRequest>,
-        ) -> std::result::Result<
-            tonic::Response<super::GetCollectionInfoResponse>,
-            tonic::Status,
-        >;
-        ///
-        /// Get list name of all existing collections
-        async fn list(
-            &self,
-            request: tonic::Request<super::ListCollectionsRequest>,
-        ) -> std::result::Result<
-            tonic::Response<super::ListCollectionsResponse>,
-            tonic::Status,
-        >;
-        ///
-        /// Create new collection with given parameters
-        async fn create(
-            &self,
-            request: tonic::Request<super::CreateCollection>,
-        ) -> std::result::Result<
-            tonic::Response<super::CollectionOperationResponse>,
-            tonic::Status,
-        >;
-        ///
-        /// Update parameters of the existing collection
-        async fn update(
-            &self,
-            request: tonic::Request<super::UpdateCollection>,
-        ) -> std::result::Result<
-            tonic::Response<super::CollectionOperationResponse>,
-            tonic::Status,
-        >;
-        ///
-        /// Drop collection and all associated data
-        async fn delete(
-            &self,
-            request: tonic::Request<super::DeleteCollection>,
-        ) -> std::result::Result<
-            tonic::Response<super::CollectionOperationResponse>,
-            tonic::Status,
-        >;
-        ///
-        /// Update Aliases of the existing collection
-        async fn update_aliases(
-            &self,
-            request: tonic::Request<super::ChangeAliases>,
-        ) -> std::result::Result<
-            tonic::Response<super::CollectionOperationResponse>,
-            tonic::Status,
-        >;
-        ///
-        /// Get list of all aliases for a collection
-        async fn list_collection_aliases(
-            &self,
-            request: tonic::Request<super::ListCollectionAliasesRequest>,
-        ) -> std::result::Result<
-            tonic::Response<super::ListAliasesResponse>,
-            tonic::Status,
-        >;
-        ///
-        /// Get list of all aliases for all existing collections
-        async fn list_aliases(
-            &self,
-            request: tonic::Request<super::ListAliasesRequest>,
-        ) -> std::result::Result<
-            tonic::Response<super::ListAliasesResponse>,
-            tonic::Status,
-        >;
-        ///
-        /// Get cluster information for a collection
-        async fn collection_cluster_info(
-            &self,
-            request: tonic::Request<super::CollectionClusterInfoRequest>,
-        ) -> std::result::Result<
-            tonic::Response<super::CollectionClusterInfoResponse>,
-            tonic::Status,
-        >;
-        ///
-        /// Update cluster setup for a collection
-        async fn update_collection_cluster_setup(
-            &self,
-            request: tonic::Request<super::UpdateCollectionClusterSetupRequest>,
-        ) -> std::result::Result<
-            tonic::Response<super::UpdateCollectionClusterSetupResponse>,
-            tonic::Status,
-        >;
-        ///
-        /// Create shard key
-        async fn create_shard_key(
-            &self,
-            request: tonic::Request<super::CreateShardKeyRequest>,
-        ) -> std::result::Result<
-            tonic::Response<super::CreateShardKeyResponse>,
-            tonic::Status,
-        >;
-        ///
-        /// Delete shard key
-        async fn delete_shard_key(
-            &self,
-            request: tonic::Request<super::DeleteShardKeyRequest>,
-        ) -> std::result::Result<
-            tonic::Response<super::DeleteShardKeyResponse>,
-            tonic::Status,
-        >;
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct WriteOrdering {
+    /// Write ordering guarantees
+    #[prost(enumeration = "WriteOrderingType", tag = "1")]
+    pub r#type: i32,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ReadConsistency {
+    #[prost(oneof = "read_consistency::Value", tags = "1, 2")]
+    pub value: ::core::option::Option<read_consistency::Value>,
+}
+/// Nested message and enum types in `ReadConsistency`.
+pub mod read_consistency {
+    #[derive(serde::Serialize)]
+    #[allow(clippy::derive_partial_eq_without_eq)]
+    #[derive(Clone, PartialEq, ::prost::Oneof)]
+    pub enum Value {
+        /// Common read consistency configurations
+        #[prost(enumeration = "super::ReadConsistencyType", tag = "1")]
+        Type(i32),
+        /// Send request to a specified number of nodes, and return points which are present on all of them
+        #[prost(uint64, tag = "2")]
+        Factor(u64),
     }
-    #[derive(Debug)]
-    pub struct CollectionsServer<T: Collections> {
-        inner: _Inner<T>,
-        accept_compression_encodings: EnabledCompressionEncodings,
-        send_compression_encodings: EnabledCompressionEncodings,
-        max_decoding_message_size: Option<usize>,
-        max_encoding_message_size: Option<usize>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct WriteConsistency {
+    #[prost(oneof = "write_consistency::Value", tags = "1, 2")]
+    pub value: ::core::option::Option<write_consistency::Value>,
+}
+/// Nested message and enum types in `WriteConsistency`.
+pub mod write_consistency {
+    #[derive(serde::Serialize)]
+    #[allow(clippy::derive_partial_eq_without_eq)]
+    #[derive(Clone, PartialEq, ::prost::Oneof)]
+    pub enum Value {
+        /// Common write consistency configurations
+        #[prost(enumeration = "super::WriteConsistencyType", tag = "1")]
+        Type(i32),
+        /// Update must be applied by a specified number of replicas
+        #[prost(uint64, tag = "2")]
+        Factor(u64),
     }
-    struct _Inner<T>(Arc<T>);
-    impl<T: Collections> CollectionsServer<T> {
-        pub fn new(inner: T) -> Self {
-            Self::from_arc(Arc::new(inner))
-        }
-        pub fn from_arc(inner: Arc<T>) -> Self {
-            let inner = _Inner(inner);
-            Self {
-                inner,
-                accept_compression_encodings: Default::default(),
-                send_compression_encodings: Default::default(),
-                max_decoding_message_size: None,
-                max_encoding_message_size: None,
-            }
-        }
-        pub fn with_interceptor<F>(
-            inner: T,
-            interceptor: F,
-        ) -> InterceptedService<Self, F>
-        where
-            F: tonic::service::Interceptor,
-        {
-            InterceptedService::new(Self::new(inner), interceptor)
-        }
-        /// Enable decompressing requests with the given encoding.
-        #[must_use]
-        pub fn accept_compressed(mut self, encoding: CompressionEncoding) -> Self {
-            self.accept_compression_encodings.enable(encoding);
-            self
-        }
-        /// Compress responses with the given encoding, if the client supports it.
-        #[must_use]
-        pub fn send_compressed(mut self, encoding: CompressionEncoding) -> Self {
-            self.send_compression_encodings.enable(encoding);
-            self
-        }
-        /// Limits the maximum size of a decoded message.
-        ///
-        /// Default: `4MB`
-        #[must_use]
-        pub fn max_decoding_message_size(mut self, limit: usize) -> Self {
-            self.max_decoding_message_size = Some(limit);
-            self
-        }
-        /// Limits the maximum size of an encoded message.
-        ///
-        /// Default: `usize::MAX`
-        #[must_use]
-        pub fn max_encoding_message_size(mut self, limit: usize) -> Self {
-            self.max_encoding_message_size = Some(limit);
-            self
-        }
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PointId {
+    #[prost(oneof = "point_id::PointIdOptions", tags = "1, 2")]
+    pub point_id_options: ::core::option::Option<point_id::PointIdOptions>,
+}
+/// Nested message and enum types in `PointId`.
+pub mod point_id {
+    #[derive(serde::Serialize)]
+    #[allow(clippy::derive_partial_eq_without_eq)]
+    #[derive(Clone, PartialEq, ::prost::Oneof)]
+    pub enum PointIdOptions {
+        /// Numerical ID of the point
+        #[prost(uint64, tag = "1")]
+        Num(u64),
+        /// UUID
+        #[prost(string, tag = "2")]
+        Uuid(::prost::alloc::string::String),
     }
-    impl<T, B> tonic::codegen::Service<http::Request<B>> for CollectionsServer<T>
-    where
-        T: Collections,
-        B: Body + Send + 'static,
-        B::Error: Into<StdError> + Send + 'static,
-    {
-        type Response = http::Response<tonic::body::BoxBody>;
-        type Error = std::convert::Infallible;
-        type Future = BoxFuture<Self::Response, Self::Error>;
-        fn poll_ready(
-            &mut self,
-            _cx: &mut Context<'_>,
-        ) -> Poll<std::result::Result<(), Self::Error>> {
-            Poll::Ready(Ok(()))
-        }
-        fn call(&mut self, req: http::Request<B>) -> Self::Future {
-            let inner = self.inner.clone();
-            match req.uri().path() {
-                "/qdrant.Collections/Get" => {
-                    #[allow(non_camel_case_types)]
-                    struct GetSvc<T: Collections>(pub Arc<T>);
-                    impl<
-                        T: Collections,
-                    > tonic::server::UnaryService<super::GetCollectionInfoRequest>
-                    for GetSvc<T> {
-                        type Response = super::GetCollectionInfoResponse;
-                        type Future = BoxFuture<
-                            tonic::Response<Self::Response>,
-                            tonic::Status,
-                        >;
-                        fn call(
-                            &mut self,
-                            request: tonic::Request<super::GetCollectionInfoRequest>,
-                        ) -> Self::Future {
-                            let inner = Arc::clone(&self.0);
-                            let fut = async move {
-                                <T as Collections>::get(&inner, request).await
-                            };
-                            Box::pin(fut)
-                        }
-                    }
-                    let accept_compression_encodings = self.accept_compression_encodings;
-                    let send_compression_encodings = self.send_compression_encodings;
-                    let max_decoding_message_size = self.max_decoding_message_size;
-                    let max_encoding_message_size = self.max_encoding_message_size;
-                    let inner = self.inner.clone();
-                    let fut = async move {
-                        let inner = inner.0;
-                        let method = GetSvc(inner);
-                        let codec = tonic::codec::ProstCodec::default();
-                        let mut grpc = tonic::server::Grpc::new(codec)
-                            .apply_compression_config(
-                                accept_compression_encodings,
-                                send_compression_encodings,
-                            )
-                            .apply_max_message_size_config(
-                                max_decoding_message_size,
-                                max_encoding_message_size,
-                            );
-                        let res = grpc.unary(method, req).await;
-                        Ok(res)
-                    };
-                    Box::pin(fut)
-                }
-                "/qdrant.Collections/List" => {
-                    #[allow(non_camel_case_types)]
-                    struct ListSvc<T: Collections>(pub Arc<T>);
-                    impl<
-                        T: Collections,
-                    > tonic::server::UnaryService<super::ListCollectionsRequest>
-                    for ListSvc<T> {
-                        type Response = super::ListCollectionsResponse;
-                        type Future = BoxFuture<
-                            tonic::Response<Self::Response>,
-                            tonic::Status,
-                        >;
-                        fn call(
-                            &mut self,
-                            request: tonic::Request<super::ListCollectionsRequest>,
-                        ) -> Self::Future {
-                            let inner = Arc::clone(&self.0);
-                            let fut = async move {
-                                <T as Collections>::list(&inner, request).await
-                            };
-                            Box::pin(fut)
-                        }
-                    }
-                    let accept_compression_encodings = self.accept_compression_encodings;
-                    let send_compression_encodings = self.send_compression_encodings;
-                    let max_decoding_message_size = self.max_decoding_message_size;
-                    let max_encoding_message_size = self.max_encoding_message_size;
-                    let inner = self.inner.clone();
-                    let fut = async move {
-                        let inner = inner.0;
-                        let method = ListSvc(inner);
-                        let codec = tonic::codec::ProstCodec::default();
-                        let mut grpc = tonic::server::Grpc::new(codec)
-                            .apply_compression_config(
-                                accept_compression_encodings,
-                                send_compression_encodings,
-                            )
-                            .apply_max_message_size_config(
-                                max_decoding_message_size,
-                                max_encoding_message_size,
-                            );
-                        let res = grpc.unary(method, req).await;
-                        Ok(res)
-                    };
-                    Box::pin(fut)
-                }
-                "/qdrant.Collections/Create" => {
-                    #[allow(non_camel_case_types)]
-                    struct CreateSvc<T: Collections>(pub Arc<T>);
-                    impl<
-                        T: Collections,
-                    > tonic::server::UnaryService<super::CreateCollection>
-                    for CreateSvc<T> {
-                        type Response = super::CollectionOperationResponse;
-                        type Future = BoxFuture<
-                            tonic::Response<Self::Response>,
-                            tonic::Status,
-                        >;
-                        fn call(
-                            &mut self,
-                            request: tonic::Request<super::CreateCollection>,
-                        ) -> Self::Future {
-                            let inner = Arc::clone(&self.0);
-                            let fut = async move {
-                                <T as Collections>::create(&inner, request).await
-                            };
-                            Box::pin(fut)
-                        }
-                    }
-                    let accept_compression_encodings = self.accept_compression_encodings;
-                    let send_compression_encodings = self.send_compression_encodings;
-                    let max_decoding_message_size = self.max_decoding_message_size;
-                    let max_encoding_message_size = self.max_encoding_message_size;
-                    let inner = self.inner.clone();
-                    let fut = async move {
-                        let inner = inner.0;
-                        let method = CreateSvc(inner);
-                        let codec = tonic::codec::ProstCodec::default();
-                        let mut grpc = tonic::server::Grpc::new(codec)
-                            .apply_compression_config(
-                                accept_compression_encodings,
-                                send_compression_encodings,
-                            )
-                            .apply_max_message_size_config(
-                                max_decoding_message_size,
-                                max_encoding_message_size,
-                            );
-                        let res = grpc.unary(method, req).await;
-                        Ok(res)
-                    };
-                    Box::pin(fut)
-                }
-                "/qdrant.Collections/Update" => {
-                    #[allow(non_camel_case_types)]
-                    struct UpdateSvc<T: Collections>(pub Arc<T>);
-                    impl<
-                        T: Collections,
-                    > tonic::server::UnaryService<super::UpdateCollection>
-                    for UpdateSvc<T> {
-                        type Response = super::CollectionOperationResponse;
-                        type Future = BoxFuture<
-                            tonic::Response<Self::Response>,
-                            tonic::Status,
-                        >;
-                        fn call(
-                            &mut self,
-                            request: tonic::Request<super::UpdateCollection>,
-                        ) -> Self::Future {
-                            let inner = Arc::clone(&self.0);
-                            let fut = async move {
-                                <T as Collections>::update(&inner, request).await
-                            };
-                            Box::pin(fut)
-                        }
-                    }
-                    let accept_compression_encodings = self.accept_compression_encodings;
-                    let send_compression_encodings = self.send_compression_encodings;
-                    let max_decoding_message_size = self.max_decoding_message_size;
-                    let max_encoding_message_size = self.max_encoding_message_size;
-                    let inner = self.inner.clone();
-                    let fut = async move {
-                        let inner = inner.0;
-                        let method = UpdateSvc(inner);
-                        let codec = tonic::codec::ProstCodec::default();
-                        let mut grpc = tonic::server::Grpc::new(codec)
-                            .apply_compression_config(
-                                accept_compression_encodings,
-                                send_compression_encodings,
-                            )
-                            .apply_max_message_size_config(
-                                max_decoding_message_size,
-                                max_encoding_message_size,
-                            );
-                        let res = grpc.unary(method, req).await;
-                        Ok(res)
-                    };
-                    Box::pin(fut)
-                }
-                "/qdrant.Collections/Delete" => {
-                    #[allow(non_camel_case_types)]
-                    struct DeleteSvc<T: Collections>(pub Arc<T>);
-                    impl<
-                        T: Collections,
-                    > tonic::server::UnaryService<super::DeleteCollection>
-                    for DeleteSvc<T> {
-                        type Response = super::CollectionOperationResponse;
-                        type Future = BoxFuture<
-                            tonic::Response<Self::Response>,
-                            tonic::Status,
-                        >;
-                        fn call(
-                            &mut self,
-                            request: tonic::Request<super::DeleteCollection>,
-                        ) -> Self::Future {
-                            let inner = Arc::clone(&self.0);
-                            let fut = async move {
-                                <T as Collections>::delete(&inner, request).await
-                            };
-                            Box::pin(fut)
-                        }
-                    }
-                    let accept_compression_encodings = self.accept_compression_encodings;
-                    let send_compression_encodings = self.send_compression_encodings;
-                    let max_decoding_message_size = self.max_decoding_message_size;
-                    let max_encoding_message_size = self.max_encoding_message_size;
-                    let inner = self.inner.clone();
-                    let fut = async move {
-                        let inner = inner.0;
-                        let method = DeleteSvc(inner);
-                        let codec = tonic::codec::ProstCodec::default();
-                        let mut grpc = tonic::server::Grpc::new(codec)
-                            .apply_compression_config(
-                                accept_compression_encodings,
-                                send_compression_encodings,
-                            )
-                            .apply_max_message_size_config(
-                                max_decoding_message_size,
-                                max_encoding_message_size,
-                            );
-                        let res = grpc.unary(method, req).await;
-                        Ok(res)
-                    };
-                    Box::pin(fut)
-                }
-                "/qdrant.Collections/UpdateAliases" => {
-                    #[allow(non_camel_case_types)]
-                    struct UpdateAliasesSvc<T: Collections>(pub Arc<T>);
-                    impl<
-                        T: Collections,
-                    > tonic::server::UnaryService<super::ChangeAliases>
-                    for UpdateAliasesSvc<T> {
-                        type Response = super::CollectionOperationResponse;
-                        type Future = BoxFuture<
-                            tonic::Response<Self::Response>,
-                            tonic::Status,
-                        >;
-                        fn call(
-                            &mut self,
-                            request: tonic::Request<super::ChangeAliases>,
-                        ) -> Self::Future {
-                            let inner = Arc::clone(&self.0);
-                            let fut = async move {
-                                <T as Collections>::update_aliases(&inner, request).await
-                            };
-                            Box::pin(fut)
-                        }
-                    }
-                    let accept_compression_encodings = self.accept_compression_encodings;
-                    let send_compression_encodings = self.send_compression_encodings;
-                    let max_decoding_message_size = self.max_decoding_message_size;
-                    let max_encoding_message_size = self.max_encoding_message_size;
-                    let inner = self.inner.clone();
-                    let fut = async move {
-                        let inner = inner.0;
-                        let method = UpdateAliasesSvc(inner);
-                        let codec = tonic::codec::ProstCodec::default();
-                        let mut grpc = tonic::server::Grpc::new(codec)
-                            .apply_compression_config(
-                                accept_compression_encodings,
-                                send_compression_encodings,
-                            )
-                            .apply_max_message_size_config(
-                                max_decoding_message_size,
-                                max_encoding_message_size,
-                            );
-                        let res = grpc.unary(method, req).await;
-                        Ok(res)
-                    };
-                    Box::pin(fut)
-                }
-                "/qdrant.Collections/ListCollectionAliases" => {
-                    #[allow(non_camel_case_types)]
-                    struct ListCollectionAliasesSvc<T: Collections>(pub Arc<T>);
-                    impl<
-                        T: Collections,
-                    > tonic::server::UnaryService<super::ListCollectionAliasesRequest>
-                    for ListCollectionAliasesSvc<T> {
-                        type Response = super::ListAliasesResponse;
-                        type Future = BoxFuture<
-                            tonic::Response<Self::Response>,
-                            tonic::Status,
-                        >;
-                        fn call(
-                            &mut self,
-                            request: tonic::Request<super::ListCollectionAliasesRequest>,
-                        ) -> Self::Future {
-                            let inner = Arc::clone(&self.0);
-                            let fut = async move {
-                                <T as Collections>::list_collection_aliases(&inner, request)
-                                    .await
-                            };
-                            Box::pin(fut)
-                        }
-                    }
-                    let accept_compression_encodings = self.accept_compression_encodings;
-                    let send_compression_encodings = self.send_compression_encodings;
-                    let max_decoding_message_size = self.max_decoding_message_size;
-                    let max_encoding_message_size = self.max_encoding_message_size;
-                    let inner = self.inner.clone();
-                    let fut = async move {
-                        let inner = inner.0;
-                        let method = ListCollectionAliasesSvc(inner);
-                        let codec = tonic::codec::ProstCodec::default();
-                        let mut grpc = tonic::server::Grpc::new(codec)
-                            .apply_compression_config(
-                                accept_compression_encodings,
-                                send_compression_encodings,
-                            )
-                            .apply_max_message_size_config(
-                                max_decoding_message_size,
-                                max_encoding_message_size,
-                            );
-                        let res = grpc.unary(method, req).await;
-                        Ok(res)
-                    };
-                    Box::pin(fut)
-                }
-                "/qdrant.Collections/ListAliases" => {
-                    #[allow(non_camel_case_types)]
-                    struct ListAliasesSvc<T: Collections>(pub Arc<T>);
-                    impl<
-                        T: Collections,
-                    > tonic::server::UnaryService<super::ListAliasesRequest>
-                    for ListAliasesSvc<T> {
-                        type Response = super::ListAliasesResponse;
-                        type Future = BoxFuture<
-                            tonic::Response<Self::Response>,
-                            tonic::Status,
-                        >;
-                        fn call(
-                            &mut self,
-                            request: tonic::Request<super::ListAliasesRequest>,
-                        ) -> Self::Future {
-                            let inner = Arc::clone(&self.0);
-                            let fut = async move {
-                                <T as Collections>::list_aliases(&inner, request).await
-                            };
-                            Box::pin(fut)
-                        }
-                    }
-                    let accept_compression_encodings = self.accept_compression_encodings;
-                    let send_compression_encodings = self.send_compression_encodings;
-                    let max_decoding_message_size = self.max_decoding_message_size;
-                    let max_encoding_message_size = self.max_encoding_message_size;
-                    let inner = self.inner.clone();
-                    let fut = async move {
-                        let inner = inner.0;
-                        let method = ListAliasesSvc(inner);
-                        let codec = tonic::codec::ProstCodec::default();
-                        let mut grpc = tonic::server::Grpc::new(codec)
-                            .apply_compression_config(
-                                accept_compression_encodings,
-                                send_compression_encodings,
-                            )
-                            .apply_max_message_size_config(
-                                max_decoding_message_size,
-                                max_encoding_message_size,
-                            );
-                        let res = grpc.unary(method, req).await;
-                        Ok(res)
-                    };
-                    Box::pin(fut)
-                }
-                "/qdrant.Collections/CollectionClusterInfo" => {
-                    #[allow(non_camel_case_types)]
-                    struct CollectionClusterInfoSvc<T: Collections>(pub Arc<T>);
-                    impl<
-                        T: Collections,
-                    > tonic::server::UnaryService<super::CollectionClusterInfoRequest>
-                    for CollectionClusterInfoSvc<T> {
-                        type Response = super::CollectionClusterInfoResponse;
-                        type Future = BoxFuture<
-                            tonic::Response<Self::Response>,
-                            tonic::Status,
-                        >;
-                        fn call(
-                            &mut self,
-                            request: tonic::Request<super::CollectionClusterInfoRequest>,
-                        ) -> Self::Future {
-                            let inner = Arc::clone(&self.0);
-                            let fut = async move {
-                                <T as Collections>::collection_cluster_info(&inner, request)
-                                    .await
-                            };
-                            Box::pin(fut)
-                        }
-                    }
-                    let accept_compression_encodings = self.accept_compression_encodings;
-                    let send_compression_encodings = self.send_compression_encodings;
-                    let max_decoding_message_size = self.max_decoding_message_size;
-                    let max_encoding_message_size = self.max_encoding_message_size;
-                    let inner = self.inner.clone();
-                    let fut = async move {
-                        let inner = inner.0;
-                        let method = CollectionClusterInfoSvc(inner);
-                        let codec = tonic::codec::ProstCodec::default();
-                        let mut grpc = tonic::server::Grpc::new(codec)
-                            .apply_compression_config(
-                                accept_compression_encodings,
-                                send_compression_encodings,
-                            )
-                            .apply_max_message_size_config(
-                                max_decoding_message_size,
-                                max_encoding_message_size,
-                            );
-                        let res = grpc.unary(method, req).await;
-                        Ok(res)
-                    };
-                    Box::pin(fut)
-                }
-                "/qdrant.Collections/UpdateCollectionClusterSetup" => {
-                    #[allow(non_camel_case_types)]
-                    struct UpdateCollectionClusterSetupSvc<T: Collections>(pub Arc<T>);
-                    impl<
-                        T: Collections,
-                    > tonic::server::UnaryService<
-                        super::UpdateCollectionClusterSetupRequest,
-                    > for UpdateCollectionClusterSetupSvc<T> {
-                        type Response = super::UpdateCollectionClusterSetupResponse;
-                        type Future = BoxFuture<
-                            tonic::Response<Self::Response>,
-                            tonic::Status,
-                        >;
-                        fn call(
-                            &mut self,
-                            request: tonic::Request<
-                                super::UpdateCollectionClusterSetupRequest,
-                            >,
-                        ) -> Self::Future {
-                            let inner = Arc::clone(&self.0);
-                            let fut = async move {
-                                <T as Collections>::update_collection_cluster_setup(
-                                        &inner,
-                                        request,
-                                    )
-                                    .await
-                            };
-                            Box::pin(fut)
-                        }
-                    }
-                    let accept_compression_encodings = self.accept_compression_encodings;
-                    let send_compression_encodings = self.send_compression_encodings;
-                    let max_decoding_message_size = self.max_decoding_message_size;
-                    let max_encoding_message_size = self.max_encoding_message_size;
-                    let inner = self.inner.clone();
-                    let fut = async move {
-                        let inner = inner.0;
-                        let method = UpdateCollectionClusterSetupSvc(inner);
-                        let codec = tonic::codec::ProstCodec::default();
-                        let mut grpc = tonic::server::Grpc::new(codec)
-                            .apply_compression_config(
-                                accept_compression_encodings,
-                                send_compression_encodings,
-                            )
-                            .apply_max_message_size_config(
-                                max_decoding_message_size,
-                                max_encoding_message_size,
-                            );
-                        let res = grpc.unary(method, req).await;
-                        Ok(res)
-                    };
-                    Box::pin(fut)
-                }
-                "/qdrant.Collections/CreateShardKey" => {
-                    #[allow(non_camel_case_types)]
-                    struct CreateShardKeySvc<T: Collections>(pub Arc<T>);
-                    impl<
-                        T: Collections,
-                    > tonic::server::UnaryService<super::CreateShardKeyRequest>
-                    for CreateShardKeySvc<T> {
-                        type Response = super::CreateShardKeyResponse;
-                        type Future = BoxFuture<
-                            tonic::Response<Self::Response>,
-                            tonic::Status,
-                        >;
-                        fn call(
-                            &mut self,
-                            request: tonic::Request<super::CreateShardKeyRequest>,
-                        ) -> Self::Future {
-                            let inner = Arc::clone(&self.0);
-                            let fut = async move {
-                                <T as Collections>::create_shard_key(&inner, request).await
-                            };
-                            Box::pin(fut)
-                        }
-                    }
-                    let accept_compression_encodings = self.accept_compression_encodings;
-                    let send_compression_encodings = self.send_compression_encodings;
-                    let max_decoding_message_size = self.max_decoding_message_size;
-                    let max_encoding_message_size = self.max_encoding_message_size;
-                    let inner = self.inner.clone();
-                    let fut = async move {
-                        let inner = inner.0;
-                        let method = CreateShardKeySvc(inner);
-                        let codec = tonic::codec::ProstCodec::default();
-                        let mut grpc = tonic::server::Grpc::new(codec)
-                            .apply_compression_config(
-                                accept_compression_encodings,
-                                send_compression_encodings,
-                            )
-                            .apply_max_message_size_config(
-                                max_decoding_message_size,
-                                max_encoding_message_size,
-                            );
-                        let res = grpc.unary(method, req).await;
-                        Ok(res)
-                    };
-                    Box::pin(fut)
-                }
-                "/qdrant.Collections/DeleteShardKey" => {
-                    #[allow(non_camel_case_types)]
-                    struct DeleteShardKeySvc<T: Collections>(pub Arc<T>);
-                    impl<
-                        T: Collections,
-                    > tonic::server::UnaryService<super::DeleteShardKeyRequest>
-                    for DeleteShardKeySvc<T> {
-                        type Response = super::DeleteShardKeyResponse;
-                        type Future = BoxFuture<
-                            tonic::Response<Self::Response>,
-                            tonic::Status,
-                        >;
-                        fn call(
-                            &mut self,
-                            request: tonic::Request<super::DeleteShardKeyRequest>,
-                        ) -> Self::Future {
-                            let inner = Arc::clone(&self.0);
-                            let fut = async move {
-                                <T as Collections>::delete_shard_key(&inner, request).await
-                            };
-                            Box::pin(fut)
-                        }
-                    }
-                    let accept_compression_encodings = self.accept_compression_encodings;
-                    let send_compression_encodings = self.send_compression_encodings;
-                    let max_decoding_message_size = self.max_decoding_message_size;
-                    let max_encoding_message_size = self.max_encoding_message_size;
-                    let inner = self.inner.clone();
-                    let fut = async move {
-                        let inner = inner.0;
-                        let method = DeleteShardKeySvc(inner);
-                        let codec = tonic::codec::ProstCodec::default();
-                        let mut grpc = tonic::server::Grpc::new(codec)
-                            .apply_compression_config(
-                                accept_compression_encodings,
-                                send_compression_encodings,
-                            )
-                            .apply_max_message_size_config(
-                                max_decoding_message_size,
-                                max_encoding_message_size,
-                            );
-                        let res = grpc.unary(method, req).await;
-                        Ok(res)
-                    };
-                    Box::pin(fut)
-                }
-                _ => {
-                    Box::pin(async move {
-                        Ok(
-                            http::Response::builder()
-                                .status(200)
-                                .header("grpc-status", "12")
-                                .header("content-type", "application/grpc")
-                                .body(empty_body())
-                                .unwrap(),
-                        )
-                    })
-                }
-            }
-        }
-    }
-    impl<T: Collections> Clone for CollectionsServer<T> {
-        fn clone(&self) -> Self {
-            let inner = self.inner.clone();
-            Self {
-                inner,
-                accept_compression_encodings: self.accept_compression_encodings,
-                send_compression_encodings: self.send_compression_encodings,
-                max_decoding_message_size: self.max_decoding_message_size,
-                max_encoding_message_size: self.max_encoding_message_size,
-            }
-        }
-    }
-    impl<T: Collections> Clone for _Inner<T> {
-        fn clone(&self) -> Self {
-            Self(Arc::clone(&self.0))
-        }
-    }
-    impl<T: std::fmt::Debug> std::fmt::Debug for _Inner<T> {
-        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-            write!(f, "{:?}", self.0)
-        }
-    }
-    impl<T: Collections> tonic::server::NamedService for CollectionsServer<T> {
-        const NAME: &'static str = "qdrant.Collections";
-    }
-}
-#[derive(validator::Validate)]
-#[derive(serde::Serialize)]
-#[allow(clippy::derive_partial_eq_without_eq)]
-#[derive(Clone, PartialEq, ::prost::Message)]
-pub struct GetCollectionInfoRequestInternal {
-    #[prost(message, optional, tag = "1")]
-    pub get_collection_info_request: ::core::option::Option<GetCollectionInfoRequest>,
-    #[prost(uint32, tag = "2")]
-    pub shard_id: u32,
-}
-#[derive(validator::Validate)]
-#[derive(serde::Serialize)]
-#[allow(clippy::derive_partial_eq_without_eq)]
-#[derive(Clone, PartialEq, ::prost::Message)]
-pub struct InitiateShardTransferRequest {
-    /// Name of the collection
-    #[prost(string, tag = "1")]
-    #[validate(length(min = 1, max = 255))]
-    pub collection_name: ::prost::alloc::string::String,
-    /// Id of the temporary shard
-    #[prost(uint32, tag = "2")]
-    pub shard_id: u32,
-}
-#[derive(validator::Validate)]
-#[derive(serde::Serialize)]
-#[allow(clippy::derive_partial_eq_without_eq)]
-#[derive(Clone, PartialEq, ::prost::Message)]
-pub struct WaitForShardStateRequest {
-    /// Name of the collection
-    #[prost(string, tag = "1")]
-    #[validate(length(min = 1, max = 255))]
-    pub collection_name: ::prost::alloc::string::String,
-    /// Id of the shard
-    #[prost(uint32, tag = "2")]
-    pub shard_id: u32,
-    /// Shard state to wait for
-    #[prost(enumeration = "ReplicaState", tag = "3")]
-    pub state: i32,
-    /// Timeout in seconds
-    #[prost(uint64, tag = "4")]
-    #[validate(range(min = 1))]
-    pub timeout: u64,
-}
-/// Generated client implementations.
-pub mod collections_internal_client {
-    #![allow(unused_variables, dead_code, missing_docs, clippy::let_unit_value)]
-    use tonic::codegen::*;
-    use tonic::codegen::http::Uri;
-    #[derive(Debug, Clone)]
-    pub struct CollectionsInternalClient<T> {
-        inner: tonic::client::Grpc<T>,
-    }
-    impl CollectionsInternalClient<tonic::transport::Channel> {
-        /// Attempt to create a new client by connecting to a given endpoint.
-        pub async fn connect<D>(dst: D) -> Result<Self, tonic::transport::Error>
-        where
-            D: TryInto<tonic::transport::Endpoint>,
-            D::Error: Into<StdError>,
-        {
-            let conn = tonic::transport::Endpoint::new(dst)?.connect().await?;
-            Ok(Self::new(conn))
-        }
-    }
-    impl<T> CollectionsInternalClient<T>
-    where
-        T: tonic::client::GrpcService<tonic::body::BoxBody>,
-        T::Error: Into<StdError>,
-        T::ResponseBody: Body<Data = Bytes> + Send + 'static,
-        <T::ResponseBody as Body>::Error: Into<StdError> + Send,
-    {
-        pub fn new(inner: T) -> Self {
-            let inner = tonic::client::Grpc::new(inner);
-            Self { inner }
-        }
-        pub fn with_origin(inner: T, origin: Uri) -> Self {
-            let inner = tonic::client::Grpc::with_origin(inner, origin);
-            Self { inner }
-        }
-        pub fn with_interceptor<F>(
-            inner: T,
-            interceptor: F,
-        ) -> CollectionsInternalClient<InterceptedService<T, F>>
-        where
-            F: tonic::service::Interceptor,
-            T::ResponseBody: Default,
-            T: tonic::codegen::Service<
-                http::Request<tonic::body::BoxBody>,
-                Response = http::Response<
-                    <T as tonic::client::GrpcService<tonic::body::BoxBody>>::ResponseBody,
-                >,
-            >,
-            <T as tonic::codegen::Service<
-                http::Request<tonic::body::BoxBody>,
-            >>::Error: Into<StdError> + Send + Sync,
-        {
-            CollectionsInternalClient::new(InterceptedService::new(inner, interceptor))
-        }
-        /// Compress requests with the given encoding.
-        ///
-        /// This requires the server to support it otherwise it might respond with an
-        /// error.
-        #[must_use]
-        pub fn send_compressed(mut self, encoding: CompressionEncoding) -> Self {
-            self.inner = self.inner.send_compressed(encoding);
-            self
-        }
-        /// Enable decompressing responses.
-        #[must_use]
-        pub fn accept_compressed(mut self, encoding: CompressionEncoding) -> Self {
-            self.inner = self.inner.accept_compressed(encoding);
-            self
-        }
-        /// Limits the maximum size of a decoded message.
-        ///
-        /// Default: `4MB`
-        #[must_use]
-        pub fn max_decoding_message_size(mut self, limit: usize) -> Self {
-            self.inner = self.inner.max_decoding_message_size(limit);
-            self
-        }
-        /// Limits the maximum size of an encoded message.
-        ///
-        /// Default: `usize::MAX`
-        #[must_use]
-        pub fn max_encoding_message_size(mut self, limit: usize) -> Self {
-            self.inner = self.inner.max_encoding_message_size(limit);
-            self
-        }
-        ///
-        /// Get collection info
-        pub async fn get(
-            &mut self,
-            request: impl tonic::IntoRequest<super::GetCollectionInfoRequestInternal>,
-        ) -> std::result::Result<
-            tonic::Response<super::GetCollectionInfoResponse>,
-            tonic::Status,
-        > {
-            self.inner
-                .ready()
-                .await
-                .map_err(|e| {
-                    tonic::Status::new(
-                        tonic::Code::Unknown,
-                        format!("Service was not ready: {}", e.into()),
-                    )
-                })?;
-            let codec = tonic::codec::ProstCodec::default();
-            let path = http::uri::PathAndQuery::from_static(
-                "/qdrant.CollectionsInternal/Get",
-            );
-            let mut req = request.into_request();
-            req.extensions_mut()
-                .insert(GrpcMethod::new("qdrant.CollectionsInternal", "Get"));
-            self.inner.unary(req, path, codec).await
-        }
-        ///
-        /// Initiate shard transfer
-        pub async fn initiate(
-            &mut self,
-            request: impl tonic::IntoRequest<super::InitiateShardTransferRequest>,
-        ) -> std::result::Result<
-            tonic::Response<super::CollectionOperationResponse>,
-            tonic::Status,
-        > {
-            self.inner
-                .ready()
-                .await
-                .map_err(|e| {
-                    tonic::Status::new(
-                        tonic::Code::Unknown,
-                        format!("Service was not ready: {}", e.into()),
-                    )
-                })?;
-            let codec = tonic::codec::ProstCodec::default();
-            let path = http::uri::PathAndQuery::from_static(
-                "/qdrant.CollectionsInternal/Initiate",
-            );
-            let mut req = request.into_request();
-            req.extensions_mut()
-                .insert(GrpcMethod::new("qdrant.CollectionsInternal", "Initiate"));
-            self.inner.unary(req, path, codec).await
-        }
-        /// *
-        /// Wait for a shard to get into the given state
-        pub async fn wait_for_shard_state(
-            &mut self,
-            request: impl tonic::IntoRequest<super::WaitForShardStateRequest>,
-        ) -> std::result::Result<
-            tonic::Response<super::CollectionOperationResponse>,
-            tonic::Status,
-        > {
-            self.inner
-                .ready()
-                .await
-                .map_err(|e| {
-                    tonic::Status::new(
-                        tonic::Code::Unknown,
-                        format!("Service was not ready: {}", e.into()),
-                    )
-                })?;
-            let codec = tonic::codec::ProstCodec::default();
-            let path = http::uri::PathAndQuery::from_static(
-                "/qdrant.CollectionsInternal/WaitForShardState",
-            );
-            let mut req = request.into_request();
-            req.extensions_mut()
-                .insert(
-                    GrpcMethod::new("qdrant.CollectionsInternal", "WaitForShardState"),
-                );
-            self.inner.unary(req, path, codec).await
-        }
-    }
-}
-/// Generated server implementations.
-pub mod collections_internal_server {
-    #![allow(unused_variables, dead_code, missing_docs, clippy::let_unit_value)]
-    use tonic::codegen::*;
-    /// Generated trait containing gRPC methods that should be implemented for use with CollectionsInternalServer.
-    #[async_trait]
-    pub trait CollectionsInternal: Send + Sync + 'static {
-        ///
-        /// Get collection info
-        async fn get(
-            &self,
-            request: tonic::Request<super::GetCollectionInfoRequestInternal>,
-        ) -> std::result::Result<
-            tonic::Response<super::GetCollectionInfoResponse>,
-            tonic::Status,
-        >;
-        ///
-        /// Initiate shard transfer
-        async fn initiate(
-            &self,
-            request: tonic::Request<super::InitiateShardTransferRequest>,
-        ) -> std::result::Result<
-            tonic::Response<super::CollectionOperationResponse>,
-            tonic::Status,
-        >;
-        /// *
-        /// Wait for a shard to get into the given state
-        async fn wait_for_shard_state(
-            &self,
-            request: tonic::Request<super::WaitForShardStateRequest>,
-        ) -> std::result::Result<
-            tonic::Response<super::CollectionOperationResponse>,
-            tonic::Status,
-        >;
-    }
-    #[derive(Debug)]
-    pub struct CollectionsInternalServer<T: CollectionsInternal> {
-        inner: _Inner<T>,
-        accept_compression_encodings: EnabledCompressionEncodings,
-        send_compression_encodings: EnabledCompressionEncodings,
-        max_decoding_message_size: Option<usize>,
-        max_encoding_message_size: Option<usize>,
-    }
-    struct _Inner<T>(Arc<T>);
-    impl<T: CollectionsInternal> CollectionsInternalServer<T> {
-        pub fn new(inner: T) -> Self {
-            Self::from_arc(Arc::new(inner))
-        }
-        pub fn from_arc(inner: Arc<T>) -> Self {
-            let inner = _Inner(inner);
-            Self {
-                inner,
-                accept_compression_encodings: Default::default(),
-                send_compression_encodings: Default::default(),
-                max_decoding_message_size: None,
-                max_encoding_message_size: None,
-            }
-        }
-        pub fn with_interceptor<F>(
-            inner: T,
-            interceptor: F,
-        ) -> InterceptedService<Self, F>
-        where
-            F: tonic::service::Interceptor,
-        {
-            InterceptedService::new(Self::new(inner), interceptor)
-        }
-        /// Enable decompressing requests with the given encoding.
-        #[must_use]
-        pub fn accept_compressed(mut self, encoding: CompressionEncoding) -> Self {
-            self.accept_compression_encodings.enable(encoding);
-            self
-        }
-        /// Compress responses with the given encoding, if the client supports it.
-        #[must_use]
-        pub fn send_compressed(mut self, encoding: CompressionEncoding) -> Self {
-            self.send_compression_encodings.enable(encoding);
-            self
-        }
-        /// Limits the maximum size of a decoded message.
-        ///
-        /// Default: `4MB`
-        #[must_use]
-        pub fn max_decoding_message_size(mut self, limit: usize) -> Self {
-            self.max_decoding_message_size = Some(limit);
-            self
-        }
-        /// Limits the maximum size of an encoded message.
-        ///
-        /// Default: `usize::MAX`
-        #[must_use]
-        pub fn max_encoding_message_size(mut self, limit: usize) -> Self {
-            self.max_encoding_message_size = Some(limit);
-            self
-        }
-    }
-    impl<T, B> tonic::codegen::Service<http::Request<B>> for CollectionsInternalServer<T>
-    where
-        T: CollectionsInternal,
-        B: Body + Send + 'static,
-        B::Error: Into<StdError> + Send + 'static,
-    {
-        type Response = http::Response<tonic::body::BoxBody>;
-        type Error = std::convert::Infallible;
-        type Future = BoxFuture<Self::Response, Self::Error>;
-        fn poll_ready(
-            &mut self,
-            _cx: &mut Context<'_>,
-        ) -> Poll<std::result::Result<(), Self::Error>> {
-            Poll::Ready(Ok(()))
-        }
-        fn call(&mut self, req: http::Request<B>) -> Self::Future {
-            let inner = self.inner.clone();
-            match req.uri().path() {
-                "/qdrant.CollectionsInternal/Get" => {
-                    #[allow(non_camel_case_types)]
-                    struct GetSvc<T: CollectionsInternal>(pub Arc<T>);
-                    impl<
-                        T: CollectionsInternal,
-                    > tonic::server::UnaryService<
-                        super::GetCollectionInfoRequestInternal,
-                    > for GetSvc<T> {
-                        type Response = super::GetCollectionInfoResponse;
-                        type Future = BoxFuture<
-                            tonic::Response<Self::Response>,
-                            tonic::Status,
-                        >;
-                        fn call(
-                            &mut self,
-                            request: tonic::Request<
-                                super::GetCollectionInfoRequestInternal,
-                            >,
-                        ) -> Self::Future {
-                            let inner = Arc::clone(&self.0);
-                            let fut = async move {
-                                <T as CollectionsInternal>::get(&inner, request).await
-                            };
-                            Box::pin(fut)
-                        }
-                    }
-                    let accept_compression_encodings = self.accept_compression_encodings;
-                    let send_compression_encodings = self.send_compression_encodings;
-                    let max_decoding_message_size = self.max_decoding_message_size;
-                    let max_encoding_message_size = self.max_encoding_message_size;
-                    let inner = self.inner.clone();
-                    let fut = async move {
-                        let inner = inner.0;
-                        let method = GetSvc(inner);
-                        let codec = tonic::codec::ProstCodec::default();
-                        let mut grpc = tonic::server::Grpc::new(codec)
-                            .apply_compression_config(
-                                accept_compression_encodings,
-                                send_compression_encodings,
-                            )
-                            .apply_max_message_size_config(
-                                max_decoding_message_size,
-                                max_encoding_message_size,
-                            );
-                        let res = grpc.unary(method, req).await;
-                        Ok(res)
-                    };
-                    Box::pin(fut)
-                }
-                "/qdrant.CollectionsInternal/Initiate" => {
-                    #[allow(non_camel_case_types)]
-                    struct InitiateSvc<T: CollectionsInternal>(pub Arc<T>);
-                    impl<
-                        T: CollectionsInternal,
-                    > tonic::server::UnaryService<super::InitiateShardTransferRequest>
-                    for InitiateSvc<T> {
-                        type Response = super::CollectionOperationResponse;
-                        type Future = BoxFuture<
-                            tonic::Response<Self::Response>,
-                            tonic::Status,
-                        >;
-                        fn call(
-                            &mut self,
-                            request: tonic::Request<super::InitiateShardTransferRequest>,
-                        ) -> Self::Future {
-                            let inner = Arc::clone(&self.0);
-                            let fut = async move {
-                                <T as CollectionsInternal>::initiate(&inner, request).await
-                            };
-                            Box::pin(fut)
-                        }
-                    }
-                    let accept_compression_encodings = self.accept_compression_encodings;
-                    let send_compression_encodings = self.send_compression_encodings;
-                    let max_decoding_message_size = self.max_decoding_message_size;
-                    let max_encoding_message_size = self.max_encoding_message_size;
-                    let inner = self.inner.clone();
-                    let fut = async move {
-                        let inner = inner.0;
-                        let method = InitiateSvc(inner);
-                        let codec = tonic::codec::ProstCodec::default();
-                        let mut grpc = tonic::server::Grpc::new(codec)
-                            .apply_compression_config(
-                                accept_compression_encodings,
-                                send_compression_encodings,
-                            )
-                            .apply_max_message_size_config(
-                                max_decoding_message_size,
-                                max_encoding_message_size,
-                            );
-                        let res = grpc.unary(method, req).await;
-                        Ok(res)
-                    };
-                    Box::pin(fut)
-                }
-                "/qdrant.CollectionsInternal/WaitForShardState" => {
-                    #[allow(non_camel_case_types)]
-                    struct WaitForShardStateSvc<T: CollectionsInternal>(pub Arc<T>);
-                    impl<
-                        T: CollectionsInternal,
-                    > tonic::server::UnaryService<super::WaitForShardStateRequest>
-                    for WaitForShardStateSvc<T> {
-                        type Response = super::CollectionOperationResponse;
-                        type Future = BoxFuture<
-                            tonic::Response<Self::Response>,
-                            tonic::Status,
-                        >;
-                        fn call(
-                            &mut self,
-                            request: tonic::Request<super::WaitForShardStateRequest>,
-                        ) -> Self::Future {
-                            let inner = Arc::clone(&self.0);
-                            let fut = async move {
-                                <T as CollectionsInternal>::wait_for_shard_state(
-                                        &inner,
-                                        request,
-                                    )
-                                    .await
-                            };
-                            Box::pin(fut)
-                        }
-                    }
-                    let accept_compression_encodings = self.accept_compression_encodings;
-                    let send_compression_encodings = self.send_compression_encodings;
-                    let max_decoding_message_size = self.max_decoding_message_size;
-                    let max_encoding_message_size = self.max_encoding_message_size;
-                    let inner = self.inner.clone();
-                    let fut = async move {
-                        let inner = inner.0;
-                        let method = WaitForShardStateSvc(inner);
-                        let codec = tonic::codec::ProstCodec::default();
-                        let mut grpc = tonic::server::Grpc::new(codec)
-                            .apply_compression_config(
-                                accept_compression_encodings,
-                                send_compression_encodings,
-                            )
-                            .apply_max_message_size_config(
-                                max_decoding_message_size,
-                                max_encoding_message_size,
-                            );
-                        let res = grpc.unary(method, req).await;
-                        Ok(res)
-                    };
-                    Box::pin(fut)
-                }
-                _ => {
-                    Box::pin(async move {
-                        Ok(
-                            http::Response::builder()
-                                .status(200)
-                                .header("grpc-status", "12")
-                                .header("content-type", "application/grpc")
-                                .body(empty_body())
-                                .unwrap(),
-                        )
-                    })
-                }
-            }
-        }
-    }
-    impl<T: CollectionsInternal> Clone for CollectionsInternalServer<T> {
-        fn clone(&self) -> Self {
-            let inner = self.inner.clone();
-            Self {
-                inner,
-                accept_compression_encodings: self.accept_compression_encodings,
-                send_compression_encodings: self.send_compression_encodings,
-                max_decoding_message_size: self.max_decoding_message_size,
-                max_encoding_message_size: self.max_encoding_message_size,
-            }
-        }
-    }
-    impl<T: CollectionsInternal> Clone for _Inner<T> {
-        fn clone(&self) -> Self {
-            Self(Arc::clone(&self.0))
-        }
-    }
-    impl<T: std::fmt::Debug> std::fmt::Debug for _Inner<T> {
-        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-            write!(f, "{:?}", self.0)
-        }
-    }
-    impl<T: CollectionsInternal> tonic::server::NamedService
-    for CollectionsInternalServer<T> {
-        const NAME: &'static str = "qdrant.CollectionsInternal";
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SparseIndices {
+    #[prost(uint32, repeated, tag = "1")]
+    pub data: ::prost::alloc::vec::Vec<u32>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Vector {
+    #[prost(float, repeated, tag = "1")]
+    pub data: ::prost::alloc::vec::Vec<f32>,
+    #[prost(message, optional, tag = "2")]
+    pub indices: ::core::option::Option<SparseIndices>,
+    /// Number of vectors of the multi-vector, `data` holds all of them one after another
+    #[prost(uint32, optional, tag = "3")]
+    pub vectors_count: ::core::option::Option<u32>,
+}
+/// ---------------------------------------------
+/// ----------------- ShardKeySelector ----------
+/// ---------------------------------------------
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ShardKeySelector {
+    /// List of shard keys which should be used in the request
+    #[prost(message, repeated, tag = "1")]
+    pub shard_keys: ::prost::alloc::vec::Vec<ShardKey>,
+}
+#[derive(validator::Validate)]
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct UpsertPoints {
+    /// name of the collection
+    #[prost(string, tag = "1")]
+    #[validate(length(min = 1, max = 255))]
+    pub collection_name: ::prost::alloc::string::String,
+    /// Wait until the changes have been applied?
+    #[prost(bool, optional, tag = "2")]
+    pub wait: ::core::option::Option<bool>,
+    #[prost(message, repeated, tag = "3")]
+    pub points: ::prost::alloc::vec::Vec<PointStruct>,
+    /// Write ordering guarantees
+    #[prost(message, optional, tag = "4")]
+    pub ordering: ::core::option::Option<WriteOrdering>,
+    /// Option for custom sharding to specify used shard keys
+    #[prost(message, optional, tag = "5")]
+    pub shard_key_selector: ::core::option::Option<ShardKeySelector>,
+    /// Max number of seconds to wait for the deactivation of replicas, which failed to apply the update
+    #[prost(uint64, optional, tag = "6")]
+    pub shard_deactivation_timeout: ::core::option::Option<u64>,
+    /// Number of replicas, which must apply the update for it to succeed, overrides the collection setting
+    #[prost(message, optional, tag = "7")]
+    pub write_consistency: ::core::option::Option<WriteConsistency>,
+    /// Max number of seconds to wait for the replicas to apply the update, those which didn't are reported as failed
+    #[prost(uint64, optional, tag = "8")]
+    #[validate(custom = "crate::grpc::validate::validate_u64_range_min_1")]
+    pub timeout: ::core::option::Option<u64>,
+    /// Time to live of the points in seconds, requires `expiration_field` of the collection. Points expire after this time and are deleted in background
+    #[prost(uint64, optional, tag = "9")]
+    #[validate(custom = "crate::grpc::validate::validate_u64_range_min_1")]
+    pub ttl: ::core::option::Option<u64>,
+    /// Priority of the update, interactive updates are not queued behind bulk ones
+    #[prost(enumeration = "UpdatePriority", optional, tag = "10")]
+    pub priority: ::core::option::Option<i32>,
+}
+#[derive(validator::Validate)]
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct DeletePoints {
+    /// name of the collection
+    #[prost(string, tag = "1")]
+    #[validate(length(min = 1, max = 255))]
+    pub collection_name: ::prost::alloc::string::String,
+    /// Wait until the changes have been applied?
+    #[prost(bool, optional, tag = "2")]
+    pub wait: ::core::option::Option<bool>,
+    /// Affected points
+    #[prost(message, optional, tag = "3")]
+    pub points: ::core::option::Option<PointsSelector>,
+    /// Write ordering guarantees
+    #[prost(message, optional, tag = "4")]
+    pub ordering: ::core::option::Option<WriteOrdering>,
+    /// Option for custom sharding to specify used shard keys
+    #[prost(message, optional, tag = "5")]
+    pub shard_key_selector: ::core::option::Option<ShardKeySelector>,
+    /// Max number of seconds to wait for the deactivation of replicas, which failed to apply the update
+    #[prost(uint64, optional, tag = "6")]
+    pub shard_deactivation_timeout: ::core::option::Option<u64>,
+    /// Number of replicas, which must apply the update for it to succeed, overrides the collection setting
+    #[prost(message, optional, tag = "7")]
+    pub write_consistency: ::core::option::Option<WriteConsistency>,
+    /// Max number of seconds to wait for the replicas to apply the update, those which didn't are reported as failed
+    #[prost(uint64, optional, tag = "8")]
+    #[validate(custom = "crate::grpc::validate::validate_u64_range_min_1")]
+    pub timeout: ::core::option::Option<u64>,
+    /// Priority of the update, interactive updates are not queued behind bulk ones
+    #[prost(enumeration = "UpdatePriority", optional, tag = "9")]
+    pub priority: ::core::option::Option<i32>,
+}
+#[derive(validator::Validate)]
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetPoints {
+    /// name of the collection
+    #[prost(string, tag = "1")]
+    #[validate(length(min = 1, max = 255))]
+    pub collection_name: ::prost::alloc::string::String,
+    /// List of points to retrieve
+    #[prost(message, repeated, tag = "2")]
+    pub ids: ::prost::alloc::vec::Vec<PointId>,
+    /// Options for specifying which payload to include or not
+    #[prost(message, optional, tag = "4")]
+    pub with_payload: ::core::option::Option<WithPayloadSelector>,
+    /// Options for specifying which vectors to include into response
+    #[prost(message, optional, tag = "5")]
+    pub with_vectors: ::core::option::Option<WithVectorsSelector>,
+    /// Options for specifying read consistency guarantees
+    #[prost(message, optional, tag = "6")]
+    pub read_consistency: ::core::option::Option<ReadConsistency>,
+    /// Specify in which shards to look for the points, if not specified - look in all shards
+    #[prost(message, optional, tag = "7")]
+    pub shard_key_selector: ::core::option::Option<ShardKeySelector>,
+    /// If set - return points as they were at this time (unix timestamp in seconds), within the time-travel window of the collection
+    #[prost(uint64, optional, tag = "8")]
+    pub as_of: ::core::option::Option<u64>,
+    /// Whether to return the versions of the points, which are used as `if_version` of conditional updates. Default: false
+    #[prost(bool, optional, tag = "9")]
+    pub with_version: ::core::option::Option<bool>,
+}
+#[derive(validator::Validate)]
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct UpdatePointVectors {
+    /// name of the collection
+    #[prost(string, tag = "1")]
+    #[validate(length(min = 1, max = 255))]
+    pub collection_name: ::prost::alloc::string::String,
+    /// Wait until the changes have been applied?
+    #[prost(bool, optional, tag = "2")]
+    pub wait: ::core::option::Option<bool>,
+    /// List of points and vectors to update
+    #[prost(message, repeated, tag = "3")]
+    pub points: ::prost::alloc::vec::Vec<PointVectors>,
+    /// Write ordering guarantees
+    #[prost(message, optional, tag = "4")]
+    pub ordering: ::core::option::Option<WriteOrdering>,
+    /// Option for custom sharding to specify used shard keys
+    #[prost(message, optional, tag = "5")]
+    pub shard_key_selector: ::core::option::Option<ShardKeySelector>,
+    /// Max number of seconds to wait for the deactivation of replicas, which failed to apply the update
+    #[prost(uint64, optional, tag = "6")]
+    pub shard_deactivation_timeout: ::core::option::Option<u64>,
+    /// Number of replicas, which must apply the update for it to succeed, overrides the collection setting
+    #[prost(message, optional, tag = "7")]
+    pub write_consistency: ::core::option::Option<WriteConsistency>,
+    /// Max number of seconds to wait for the replicas to apply the update, those which didn't are reported as failed
+    #[prost(uint64, optional, tag = "8")]
+    #[validate(custom = "crate::grpc::validate::validate_u64_range_min_1")]
+    pub timeout: ::core::option::Option<u64>,
+    /// Priority of the update, interactive updates are not queued behind bulk ones
+    #[prost(enumeration = "UpdatePriority", optional, tag = "9")]
+    pub priority: ::core::option::Option<i32>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PointVectors {
+    /// ID to update vectors for
+    #[prost(message, optional, tag = "1")]
+    pub id: ::core::option::Option<PointId>,
+    /// Named vectors to update, leave others intact
+    #[prost(message, optional, tag = "2")]
+    pub vectors: ::core::option::Option<Vectors>,
+}
+#[derive(validator::Validate)]
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct DeletePointVectors {
+    /// name of the collection
+    #[prost(string, tag = "1")]
+    #[validate(length(min = 1, max = 255))]
+    pub collection_name: ::prost::alloc::string::String,
+    /// Wait until the changes have been applied?
+    #[prost(bool, optional, tag = "2")]
+    pub wait: ::core::option::Option<bool>,
+    /// Affected points
+    #[prost(message, optional, tag = "3")]
+    pub points_selector: ::core::option::Option<PointsSelector>,
+    /// List of vector names to delete
+    #[prost(message, optional, tag = "4")]
+    pub vectors: ::core::option::Option<VectorsSelector>,
+    /// Write ordering guarantees
+    #[prost(message, optional, tag = "5")]
+    pub ordering: ::core::option::Option<WriteOrdering>,
+    /// Option for custom sharding to specify used shard keys
+    #[prost(message, optional, tag = "6")]
+    pub shard_key_selector: ::core::option::Option<ShardKeySelector>,
+    /// Max number of seconds to wait for the deactivation of replicas, which failed to apply the update
+    #[prost(uint64, optional, tag = "7")]
+    pub shard_deactivation_timeout: ::core::option::Option<u64>,
+    /// Number of replicas, which must apply the update for it to succeed, overrides the collection setting
+    #[prost(message, optional, tag = "8")]
+    pub write_consistency: ::core::option::Option<WriteConsistency>,
+    /// Max number of seconds to wait for the replicas to apply the update, those which didn't are reported as failed
+    #[prost(uint64, optional, tag = "9")]
+    #[validate(custom = "crate::grpc::validate::validate_u64_range_min_1")]
+    pub timeout: ::core::option::Option<u64>,
+    /// Priority of the update, interactive updates are not queued behind bulk ones
+    #[prost(enumeration = "UpdatePriority", optional, tag = "10")]
+    pub priority: ::core::option::Option<i32>,
+}
+#[derive(validator::Validate)]
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SetPayloadPoints {
+    /// name of the collection
+    #[prost(string, tag = "1")]
+    #[validate(length(min = 1, max = 255))]
+    pub collection_name: ::prost::alloc::string::String,
+    /// Wait until the changes have been applied?
+    #[prost(bool, optional, tag = "2")]
+    pub wait: ::core::option::Option<bool>,
+    /// New payload values
+    #[prost(map = "string, message", tag = "3")]
+    pub payload: ::std::collections::HashMap<::prost::alloc::string::String, Value>,
+    /// Affected points
+    #[prost(message, optional, tag = "5")]
+    pub points_selector: ::core::option::Option<PointsSelector>,
+    /// Write ordering guarantees
+    #[prost(message, optional, tag = "6")]
+    pub ordering: ::core::option::Option<WriteOrdering>,
+    /// Option for custom sharding to specify used shard keys
+    #[prost(message, optional, tag = "7")]
+    pub shard_key_selector: ::core::option::Option<ShardKeySelector>,
+    /// Max number of seconds to wait for the deactivation of replicas, which failed to apply the update
+    #[prost(uint64, optional, tag = "8")]
+    pub shard_deactivation_timeout: ::core::option::Option<u64>,
+    /// Number of replicas, which must apply the update for it to succeed, overrides the collection setting
+    #[prost(message, optional, tag = "9")]
+    pub write_consistency: ::core::option::Option<WriteConsistency>,
+    /// Max number of seconds to wait for the replicas to apply the update, those which didn't are reported as failed
+    #[prost(uint64, optional, tag = "10")]
+    #[validate(custom = "crate::grpc::validate::validate_u64_range_min_1")]
+    pub timeout: ::core::option::Option<u64>,
+    /// Only update points, which current version is equal to this one. Otherwise, the whole operation is rejected
+    #[prost(uint64, optional, tag = "11")]
+    pub if_version: ::core::option::Option<u64>,
+    /// Priority of the update, interactive updates are not queued behind bulk ones
+    #[prost(enumeration = "UpdatePriority", optional, tag = "12")]
+    pub priority: ::core::option::Option<i32>,
+}
+#[derive(validator::Validate)]
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct DeletePayloadPoints {
+    /// name of the collection
+    #[prost(string, tag = "1")]
+    #[validate(length(min = 1, max = 255))]
+    pub collection_name: ::prost::alloc::string::String,
+    /// Wait until the changes have been applied?
+    #[prost(bool, optional, tag = "2")]
+    pub wait: ::core::option::Option<bool>,
+    /// List of keys to delete
+    #[prost(string, repeated, tag = "3")]
+    pub keys: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+    /// Affected points
+    #[prost(message, optional, tag = "5")]
+    pub points_selector: ::core::option::Option<PointsSelector>,
+    /// Write ordering guarantees
+    #[prost(message, optional, tag = "6")]
+    pub ordering: ::core::option::Option<WriteOrdering>,
+    /// Option for custom sharding to specify used shard keys
+    #[prost(message, optional, tag = "7")]
+    pub shard_key_selector: ::core::option::Option<ShardKeySelector>,
+    /// Max number of seconds to wait for the deactivation of replicas, which failed to apply the update
+    #[prost(uint64, optional, tag = "8")]
+    pub shard_deactivation_timeout: ::core::option::Option<u64>,
+    /// Number of replicas, which must apply the update for it to succeed, overrides the collection setting
+    #[prost(message, optional, tag = "9")]
+    pub write_consistency: ::core::option::Option<WriteConsistency>,
+    /// Max number of seconds to wait for the replicas to apply the update, those which didn't are reported as failed
+    #[prost(uint64, optional, tag = "10")]
+    #[validate(custom = "crate::grpc::validate::validate_u64_range_min_1")]
+    pub timeout: ::core::option::Option<u64>,
+    /// Priority of the update, interactive updates are not queued behind bulk ones
+    #[prost(enumeration = "UpdatePriority", optional, tag = "11")]
+    pub priority: ::core::option::Option<i32>,
+}
+#[derive(validator::Validate)]
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ClearPayloadPoints {
+    /// name of the collection
+    #[prost(string, tag = "1")]
+    #[validate(length(min = 1, max = 255))]
+    pub collection_name: ::prost::alloc::string::String,
+    /// Wait until the changes have been applied?
+    #[prost(bool, optional, tag = "2")]
+    pub wait: ::core::option::Option<bool>,
+    /// Affected points
+    #[prost(message, optional, tag = "3")]
+    pub points: ::core::option::Option<PointsSelector>,
+    /// Write ordering guarantees
+    #[prost(message, optional, tag = "4")]
+    pub ordering: ::core::option::Option<WriteOrdering>,
+    /// Option for custom sharding to specify used shard keys
+    #[prost(message, optional, tag = "5")]
+    pub shard_key_selector: ::core::option::Option<ShardKeySelector>,
+    /// Max number of seconds to wait for the deactivation of replicas, which failed to apply the update
+    #[prost(uint64, optional, tag = "6")]
+    pub shard_deactivation_timeout: ::core::option::Option<u64>,
+    /// Number of replicas, which must apply the update for it to succeed, overrides the collection setting
+    #[prost(message, optional, tag = "7")]
+    pub write_consistency: ::core::option::Option<WriteConsistency>,
+    /// Max number of seconds to wait for the replicas to apply the update, those which didn't are reported as failed
+    #[prost(uint64, optional, tag = "8")]
+    #[validate(custom = "crate::grpc::validate::validate_u64_range_min_1")]
+    pub timeout: ::core::option::Option<u64>,
+    /// Priority of the update, interactive updates are not queued behind bulk ones
+    #[prost(enumeration = "UpdatePriority", optional, tag = "9")]
+    pub priority: ::core::option::Option<i32>,
+}
+#[derive(validator::Validate)]
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct UndeletePoints {
+    /// name of the collection
+    #[prost(string, tag = "1")]
+    #[validate(length(min = 1, max = 255))]
+    pub collection_name: ::prost::alloc::string::String,
+    /// Wait until the changes have been applied?
+    #[prost(bool, optional, tag = "2")]
+    pub wait: ::core::option::Option<bool>,
+    /// Points to restore
+    #[prost(message, repeated, tag = "3")]
+    pub ids: ::prost::alloc::vec::Vec<PointId>,
+    /// Write ordering guarantees
+    #[prost(message, optional, tag = "4")]
+    pub ordering: ::core::option::Option<WriteOrdering>,
+    /// Option for custom sharding to specify used shard keys
+    #[prost(message, optional, tag = "5")]
+    pub shard_key_selector: ::core::option::Option<ShardKeySelector>,
+    /// Max number of seconds to wait for the deactivation of replicas, which failed to apply the update
+    #[prost(uint64, optional, tag = "6")]
+    pub shard_deactivation_timeout: ::core::option::Option<u64>,
+    /// Number of replicas, which must apply the update for it to succeed, overrides the collection setting
+    #[prost(message, optional, tag = "7")]
+    pub write_consistency: ::core::option::Option<WriteConsistency>,
+    /// Max number of seconds to wait for the replicas to apply the update, those which didn't are reported as failed
+    #[prost(uint64, optional, tag = "8")]
+    #[validate(custom = "crate::grpc::validate::validate_u64_range_min_1")]
+    pub timeout: ::core::option::Option<u64>,
+    /// Priority of the update, interactive updates are not queued behind bulk ones
+    #[prost(enumeration = "UpdatePriority", optional, tag = "9")]
+    pub priority: ::core::option::Option<i32>,
+}
+#[derive(validator::Validate)]
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct DeleteByFilterPoints {
+    /// name of the collection
+    #[prost(string, tag = "1")]
+    #[validate(length(min = 1, max = 255))]
+    pub collection_name: ::prost::alloc::string::String,
+    /// Delete all points, which satisfy this filter condition
+    #[prost(message, optional, tag = "2")]
+    #[validate]
+    pub filter: ::core::option::Option<Filter>,
+    /// Option for custom sharding to specify used shard keys
+    #[prost(message, optional, tag = "3")]
+    pub shard_key_selector: ::core::option::Option<ShardKeySelector>,
+    /// Number of points deleted at once. Default: 1000
+    #[prost(uint64, optional, tag = "4")]
+    #[validate(custom = "crate::grpc::validate::validate_u64_range_min_1")]
+    pub batch_size: ::core::option::Option<u64>,
+    /// Pause between the batches in milliseconds, to limit the impact on the serving traffic. Default: no pause
+    #[prost(uint64, optional, tag = "5")]
+    pub batch_delay_ms: ::core::option::Option<u64>,
+    /// Write ordering guarantees
+    #[prost(message, optional, tag = "6")]
+    pub ordering: ::core::option::Option<WriteOrdering>,
+    /// Max number of seconds to wait for the deactivation of replicas, which failed to apply the update
+    #[prost(uint64, optional, tag = "7")]
+    pub shard_deactivation_timeout: ::core::option::Option<u64>,
+    /// Number of replicas, which must apply the update for it to succeed, overrides the collection setting
+    #[prost(message, optional, tag = "8")]
+    pub write_consistency: ::core::option::Option<WriteConsistency>,
+    /// Max number of seconds to wait for the replicas to apply each batch, those which didn't are reported as failed
+    #[prost(uint64, optional, tag = "9")]
+    #[validate(custom = "crate::grpc::validate::validate_u64_range_min_1")]
+    pub timeout: ::core::option::Option<u64>,
+    /// Priority of the update, interactive updates are not queued behind bulk ones
+    #[prost(enumeration = "UpdatePriority", optional, tag = "10")]
+    pub priority: ::core::option::Option<i32>,
+}
+#[derive(validator::Validate)]
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SetPayloadByFilterPoints {
+    /// name of the collection
+    #[prost(string, tag = "1")]
+    #[validate(length(min = 1, max = 255))]
+    pub collection_name: ::prost::alloc::string::String,
+    /// New payload values
+    #[prost(map = "string, message", tag = "2")]
+    pub payload: ::std::collections::HashMap<::prost::alloc::string::String, Value>,
+    /// Assigns payload to each point, which satisfies this filter condition
+    #[prost(message, optional, tag = "3")]
+    #[validate]
+    pub filter: ::core::option::Option<Filter>,
+    /// Option for custom sharding to specify used shard keys
+    #[prost(message, optional, tag = "4")]
+    pub shard_key_selector: ::core::option::Option<ShardKeySelector>,
+    /// Number of points updated at once. Default: 1000
+    #[prost(uint64, optional, tag = "5")]
+    #[validate(custom = "crate::grpc::validate::validate_u64_range_min_1")]
+    pub batch_size: ::core::option::Option<u64>,
+    /// Pause between the batches in milliseconds, to limit the impact on the serving traffic. Default: no pause
+    #[prost(uint64, optional, tag = "6")]
+    pub batch_delay_ms: ::core::option::Option<u64>,
+    /// Write ordering guarantees
+    #[prost(message, optional, tag = "7")]
+    pub ordering: ::core::option::Option<WriteOrdering>,
+    /// Max number of seconds to wait for the deactivation of replicas, which failed to apply the update
+    #[prost(uint64, optional, tag = "8")]
+    pub shard_deactivation_timeout: ::core::option::Option<u64>,
+    /// Number of replicas, which must apply the update for it to succeed, overrides the collection setting
+    #[prost(message, optional, tag = "9")]
+    pub write_consistency: ::core::option::Option<WriteConsistency>,
+    /// Max number of seconds to wait for the replicas to apply each batch, those which didn't are reported as failed
+    #[prost(uint64, optional, tag = "10")]
+    #[validate(custom = "crate::grpc::validate::validate_u64_range_min_1")]
+    pub timeout: ::core::option::Option<u64>,
+    /// Priority of the update, interactive updates are not queued behind bulk ones
+    #[prost(enumeration = "UpdatePriority", optional, tag = "11")]
+    pub priority: ::core::option::Option<i32>,
+}
+#[derive(validator::Validate)]
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CreateFieldIndexCollection {
+    /// name of the collection
+    #[prost(string, tag = "1")]
+    #[validate(length(min = 1, max = 255))]
+    pub collection_name: ::prost::alloc::string::String,
+    /// Wait until the changes have been applied?
+    #[prost(bool, optional, tag = "2")]
+    pub wait: ::core::option::Option<bool>,
+    /// Field name to index
+    #[prost(string, tag = "3")]
+    #[validate(length(min = 1))]
+    pub field_name: ::prost::alloc::string::String,
+    /// Field type.
+    #[prost(enumeration = "FieldType", optional, tag = "4")]
+    pub field_type: ::core::option::Option<i32>,
+    /// Payload index params.
+    #[prost(message, optional, tag = "5")]
+    pub field_index_params: ::core::option::Option<PayloadIndexParams>,
+    /// Write ordering guarantees
+    #[prost(message, optional, tag = "6")]
+    pub ordering: ::core::option::Option<WriteOrdering>,
+    /// Max number of seconds to wait for the deactivation of replicas, which failed to apply the update
+    #[prost(uint64, optional, tag = "7")]
+    pub shard_deactivation_timeout: ::core::option::Option<u64>,
+    /// Number of replicas, which must apply the update for it to succeed, overrides the collection setting
+    #[prost(message, optional, tag = "8")]
+    pub write_consistency: ::core::option::Option<WriteConsistency>,
+    /// Max number of seconds to wait for the replicas to apply the update, those which didn't are reported as failed
+    #[prost(uint64, optional, tag = "9")]
+    #[validate(custom = "crate::grpc::validate::validate_u64_range_min_1")]
+    pub timeout: ::core::option::Option<u64>,
+    /// Priority of the update, interactive updates are not queued behind bulk ones
+    #[prost(enumeration = "UpdatePriority", optional, tag = "10")]
+    pub priority: ::core::option::Option<i32>,
+}
+#[derive(validator::Validate)]
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct DeleteFieldIndexCollection {
+    /// name of the collection
+    #[prost(string, tag = "1")]
+    #[validate(length(min = 1, max = 255))]
+    pub collection_name: ::prost::alloc::string::String,
+    /// Wait until the changes have been applied?
+    #[prost(bool, optional, tag = "2")]
+    pub wait: ::core::option::Option<bool>,
+    /// Field name to delete
+    #[prost(string, tag = "3")]
+    #[validate(length(min = 1))]
+    pub field_name: ::prost::alloc::string::String,
+    /// Write ordering guarantees
+    #[prost(message, optional, tag = "4")]
+    pub ordering: ::core::option::Option<WriteOrdering>,
+    /// Max number of seconds to wait for the deactivation of replicas, which failed to apply the update
+    #[prost(uint64, optional, tag = "5")]
+    pub shard_deactivation_timeout: ::core::option::Option<u64>,
+    /// Number of replicas, which must apply the update for it to succeed, overrides the collection setting
+    #[prost(message, optional, tag = "6")]
+    pub write_consistency: ::core::option::Option<WriteConsistency>,
+    /// Max number of seconds to wait for the replicas to apply the update, those which didn't are reported as failed
+    #[prost(uint64, optional, tag = "7")]
+    #[validate(custom = "crate::grpc::validate::validate_u64_range_min_1")]
+    pub timeout: ::core::option::Option<u64>,
+    /// Priority of the update, interactive updates are not queued behind bulk ones
+    #[prost(enumeration = "UpdatePriority", optional, tag = "8")]
+    pub priority: ::core::option::Option<i32>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PayloadIncludeSelector {
+    /// List of payload keys to include into result
+    #[prost(string, repeated, tag = "1")]
+    pub fields: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PayloadExcludeSelector {
+    /// List of payload keys to exclude from the result
+    #[prost(string, repeated, tag = "1")]
+    pub fields: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct WithPayloadSelector {
+    #[prost(oneof = "with_payload_selector::SelectorOptions", tags = "1, 2, 3")]
+    pub selector_options: ::core::option::Option<with_payload_selector::SelectorOptions>,
+}
+/// Nested message and enum types in `WithPayloadSelector`.
+pub mod with_payload_selector {
+    #[derive(serde::Serialize)]
+    #[allow(clippy::derive_partial_eq_without_eq)]
+    #[derive(Clone, PartialEq, ::prost::Oneof)]
+    pub enum SelectorOptions {
+        /// If `true` - return all payload, if `false` - none
+        #[prost(bool, tag = "1")]
+        Enable(bool),
+        #[prost(message, tag = "2")]
+        Include(super::PayloadIncludeSelector),
+        #[prost(message, tag = "3")]
+        Exclude(super::PayloadExcludeSelector),
     }
 }
-/// `Struct` represents a structured data value, consisting of fields
-/// which map to dynamically typed values. In some languages, `Struct`
-/// might be supported by a native representation. For example, in
-/// scripting languages like JS a struct is represented as an
-/// object. The details of that representation are described together
-/// with the proto support for the language.
-///
-/// The JSON representation for `Struct` is a JSON object.
+#[derive(validator::Validate)]
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
-pub struct Struct {
-    /// Unordered map of dynamically typed values.
+pub struct NamedVectors {
     #[prost(map = "string, message", tag = "1")]
-    pub fields: ::std::collections::HashMap<::prost::alloc::string::String, Value>,
+    #[validate]
+    pub vectors: ::std::collections::HashMap<::prost::alloc::string::String, Vector>,
 }
-/// `Value` represents a dynamically typed value which can be either
-/// null, a number, a string, a boolean, a recursive struct value, or a
-/// list of values. A producer of value is expected to set one of those
-/// variants, absence of any variant indicates an error.
-///
-/// The JSON representation for `Value` is a JSON value.
+#[derive(validator::Validate)]
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
-pub struct Value {
-    /// The kind of value.
-    #[prost(oneof = "value::Kind", tags = "1, 2, 3, 4, 5, 6, 7")]
-    pub kind: ::core::option::Option<value::Kind>,
+pub struct Vectors {
+    #[prost(oneof = "vectors::VectorsOptions", tags = "1, 2")]
+    #[validate]
+    pub vectors_options: ::core::option::Option<vectors::VectorsOptions>,
 }
-/// Nested message and enum types in `Value`.
-pub mod value {
-    /// The kind of value.
+/// Nested message and enum types in `Vectors`.
+pub mod vectors {
     #[derive(serde::Serialize)]
     #[allow(clippy::derive_partial_eq_without_eq)]
     #[derive(Clone, PartialEq, ::prost::Oneof)]
-    pub enum Kind {
-        /// Represents a null value.
-        #[prost(enumeration = "super::NullValue", tag = "1")]
-        NullValue(i32),
-        /// Represents a double value.
-        #[prost(double, tag = "2")]
-        DoubleValue(f64),
-        /// Represents an integer value
-        #[prost(int64, tag = "3")]
-        IntegerValue(i64),
-        /// Represents a string value.
-        #[prost(string, tag = "4")]
-        StringValue(::prost::alloc::string::String),
-        /// Represents a boolean value.
-        #[prost(bool, tag = "5")]
-        BoolValue(bool),
-        /// Represents a structured value.
-        #[prost(message, tag = "6")]
-        StructValue(super::Struct),
-        /// Represents a repeated `Value`.
-        #[prost(message, tag = "7")]
-        ListValue(super::ListValue),
+    pub enum VectorsOptions {
+        #[prost(message, tag = "1")]
+        Vector(super::Vector),
+        #[prost(message, tag = "2")]
+        Vectors(super::NamedVectors),
     }
 }
-/// `ListValue` is a wrapper around a repeated field of values.
-///
-/// The JSON representation for `ListValue` is a JSON array.
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
-pub struct ListValue {
-    /// Repeated field of dynamically typed values.
-    #[prost(message, repeated, tag = "1")]
-    pub values: ::prost::alloc::vec::Vec<Value>,
+pub struct VectorsSelector {
+    /// List of vectors to include into result
+    #[prost(string, repeated, tag = "1")]
+    pub names: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
 }
-/// `NullValue` is a singleton enumeration to represent the null value for the
-/// `Value` type union.
-///
-///   The JSON representation for `NullValue` is JSON `null`.
 #[derive(serde::Serialize)]
-#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
-#[repr(i32)]
-pub enum NullValue {
-    /// Null value.
-    NullValue = 0,
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct WithVectorsSelector {
+    #[prost(oneof = "with_vectors_selector::SelectorOptions", tags = "1, 2")]
+    pub selector_options: ::core::option::Option<with_vectors_selector::SelectorOptions>,
 }
-impl NullValue {
-    /// String value of the enum field names used in the ProtoBuf definition.
-    ///
-    /// The values are not transformed in any way and thus are considered stable
-    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
-    pub fn as_str_name(&self) -> &'static str {
-        match self {
-            NullValue::NullValue => "NULL_VALUE",
-        }
-    }
-    /// Creates an enum from field names used in the ProtoBuf definition.
-    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
-        match value {
-            "NULL_VALUE" => Some(Self::NullValue),
-            _ => None,
-        }
+/// Nested message and enum types in `WithVectorsSelector`.
+pub mod with_vectors_selector {
+    #[derive(serde::Serialize)]
+    #[allow(clippy::derive_partial_eq_without_eq)]
+    #[derive(Clone, PartialEq, ::prost::Oneof)]
+    pub enum SelectorOptions {
+        /// If `true` - return all vectors, if `false` - none
+        #[prost(bool, tag = "1")]
+        Enable(bool),
+        /// List of payload keys to include into result
+        #[prost(message, tag = "2")]
+        Include(super::VectorsSelector),
     }
 }
+#[derive(validator::Validate)]
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct QuantizationSearchParams {
+    ///
+    /// If set to true, search will ignore quantized vector data
+    #[prost(bool, optional, tag = "1")]
+    pub ignore: ::core::option::Option<bool>,
+    ///
+    /// If true, use original vectors to re-score top-k results. If ignored, qdrant decides automatically does rescore enabled or not.
+    #[prost(bool, optional, tag = "2")]
+    pub rescore: ::core::option::Option<bool>,
+    ///
+    /// Oversampling factor for quantization.
+    ///
+    /// Defines how many extra vectors should be pre-selected using quantized index,
+    /// and then re-scored using original vectors.
+    ///
+    /// For example, if `oversampling` is 2.4 and `limit` is 100, then 240 vectors will be pre-selected using quantized index,
+    /// and then top-100 will be returned after re-scoring.
+    #[prost(double, optional, tag = "3")]
+    #[validate(custom = "crate::grpc::validate::validate_f64_range_min_1")]
+    pub oversampling: ::core::option::Option<f64>,
+}
+#[derive(validator::Validate)]
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SearchParams {
+    ///
+    /// Params relevant to HNSW index. Size of the beam in a beam-search.
+    /// Larger the value - more accurate the result, more time required for search.
+    #[prost(uint64, optional, tag = "1")]
+    pub hnsw_ef: ::core::option::Option<u64>,
+    ///
+    /// Search without approximation. If set to true, search may run long but with exact results.
+    #[prost(bool, optional, tag = "2")]
+    pub exact: ::core::option::Option<bool>,
+    ///
+    /// If set to true, search will ignore quantized vector data
+    #[prost(message, optional, tag = "3")]
+    #[validate]
+    pub quantization: ::core::option::Option<QuantizationSearchParams>,
+    ///
+    /// If enabled, the engine will only perform search among indexed or small segments.
+    /// Using this option prevents slow searches in case of delayed index, but does not
+    /// guarantee that all uploaded vectors will be included in search results
+    #[prost(bool, optional, tag = "4")]
+    pub indexed_only: ::core::option::Option<bool>,
+}
+#[derive(validator::Validate)]
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SearchPoints {
+    /// name of the collection
+    #[prost(string, tag = "1")]
+    #[validate(length(min = 1, max = 255))]
+    pub collection_name: ::prost::alloc::string::String,
+    /// vector
+    #[prost(float, repeated, tag = "2")]
+    pub vector: ::prost::alloc::vec::Vec<f32>,
+    /// Filter conditions - return only those points that satisfy the specified conditions
+    #[prost(message, optional, tag = "3")]
+    #[validate]
+    pub filter: ::core::option::Option<Filter>,
+    /// Max number of result
+    #[prost(uint64, tag = "4")]
+    #[validate(range(min = 1))]
+    pub limit: u64,
+    /// Options for specifying which payload to include or not
+    #[prost(message, optional, tag = "6")]
+    pub with_payload: ::core::option::Option<WithPayloadSelector>,
+    /// Search config
+    #[prost(message, optional, tag = "7")]
+    #[validate]
+    pub params: ::core::option::Option<SearchParams>,
+    /// If provided - cut off results with worse scores
+    #[prost(float, optional, tag = "8")]
+    pub score_threshold: ::core::option::Option<f32>,
+    /// Offset of the result
+    #[prost(uint64, optional, tag = "9")]
+    pub offset: ::core::option::Option<u64>,
+    /// Which vector to use for search, if not specified - use default vector
+    #[prost(string, optional, tag = "10")]
+    pub vector_name: ::core::option::Option<::prost::alloc::string::String>,
+    /// Options for specifying which vectors to include into response
+    #[prost(message, optional, tag = "11")]
+    pub with_vectors: ::core::option::Option<WithVectorsSelector>,
+    /// Options for specifying read consistency guarantees
+    #[prost(message, optional, tag = "12")]
+    pub read_consistency: ::core::option::Option<ReadConsistency>,
+    /// If set, overrides global timeout setting for this request. Unit is seconds.
+    #[prost(uint64, optional, tag = "13")]
+    #[validate(custom = "crate::grpc::validate::validate_u64_range_min_1")]
+    pub timeout: ::core::option::Option<u64>,
+    /// Specify in which shards to look for the points, if not specified - look in all shards
+    #[prost(message, optional, tag = "14")]
+    pub shard_key_selector: ::core::option::Option<ShardKeySelector>,
+    #[prost(message, optional, tag = "15")]
+    pub sparse_indices: ::core::option::Option<SparseIndices>,
+    /// Token returned by the update: only search in replicas, which have already applied it
+    #[prost(string, optional, tag = "16")]
+    pub consistency_token: ::core::option::Option<::prost::alloc::string::String>,
+    /// Number of vectors of the multi-vector, `vector` holds all of them one after another
+    #[prost(uint32, optional, tag = "17")]
+    pub vectors_count: ::core::option::Option<u32>,
+    /// If set, results of the shards which responded are returned along with the list of failed shards, instead of failing the whole request
+    #[prost(bool, optional, tag = "18")]
+    pub partial_results: ::core::option::Option<bool>,
+}
+#[derive(validator::Validate)]
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
-pub struct WriteOrdering {
-    /// Write ordering guarantees
-    #[prost(enumeration = "WriteOrderingType", tag = "1")]
-    pub r#type: i32,
+pub struct SearchBatchPoints {
+    /// Name of the collection
+    #[prost(string, tag = "1")]
+    #[validate(length(min = 1, max = 255))]
+    pub collection_name: ::prost::alloc::string::String,
+    #[prost(message, repeated, tag = "2")]
+    #[validate]
+    pub search_points: ::prost::alloc::vec::Vec<SearchPoints>,
+    /// Options for specifying read consistency guarantees
+    #[prost(message, optional, tag = "3")]
+    pub read_consistency: ::core::option::Option<ReadConsistency>,
+    /// If set, overrides global timeout setting for this request. Unit is seconds.
+    #[prost(uint64, optional, tag = "4")]
+    #[validate(custom = "crate::grpc::validate::validate_u64_range_min_1")]
+    pub timeout: ::core::option::Option<u64>,
+    /// If set, results of the shards which responded are returned along with the list of failed shards, instead of failing the whole request
+    #[prost(bool, optional, tag = "5")]
+    pub partial_results: ::core::option::Option<bool>,
 }
+#[derive(validator::Validate)]
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
-pub struct ReadConsistency {
-    #[prost(oneof = "read_consistency::Value", tags = "1, 2")]
-    pub value: ::core::option::Option<read_consistency::Value>,
-}
-/// Nested message and enum types in `ReadConsistency`.
-pub mod read_consistency {
-    #[derive(serde::Serialize)]
-    #[allow(clippy::derive_partial_eq_without_eq)]
-    #[derive(Clone, PartialEq, ::prost::Oneof)]
-    pub enum Value {
-        /// Common read consistency configurations
-        #[prost(enumeration = "super::ReadConsistencyType", tag = "1")]
-        Type(i32),
-        /// Send request to a specified number of nodes, and return points which are present on all of them
-        #[prost(uint64, tag = "2")]
-        Factor(u64),
-    }
+pub struct SearchPointsPage {
+    /// Search request, `offset` and `partial_results` are not supported
+    #[prost(message, optional, tag = "1")]
+    #[validate]
+    pub search_points: ::core::option::Option<SearchPoints>,
+    /// Cursor returned with the previous page. If not specified - the first page is returned. Cursor is only valid for the same search request, `limit` may be changed between pages
+    #[prost(string, optional, tag = "2")]
+    pub cursor: ::core::option::Option<::prost::alloc::string::String>,
 }
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
-pub struct PointId {
-    #[prost(oneof = "point_id::PointIdOptions", tags = "1, 2")]
-    pub point_id_options: ::core::option::Option<point_id::PointIdOptions>,
+pub struct WithLookup {
+    /// Name of the collection to use for points lookup
+    #[prost(string, tag = "1")]
+    pub collection: ::prost::alloc::string::String,
+    /// Options for specifying which payload to include (or not)
+    #[prost(message, optional, tag = "2")]
+    pub with_payload: ::core::option::Option<WithPayloadSelector>,
+    /// Options for specifying which vectors to include (or not)
+    #[prost(message, optional, tag = "3")]
+    pub with_vectors: ::core::option::Option<WithVectorsSelector>,
 }
-/// Nested message and enum types in `PointId`.
-pub mod point_id {
-    #[derive(serde::Serialize)]
-    #[allow(clippy::derive_partial_eq_without_eq)]
-    #[derive(Clone, PartialEq, ::prost::Oneof)]
-    pub enum PointIdOptions {
-        /// Numerical ID of the point
-        #[prost(uint64, tag = "1")]
-        Num(u64),
-        /// UUID
-        #[prost(string, tag = "2")]
-        Uuid(::prost::alloc::string::String),
-    }
+#[derive(validator::Validate)]
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SearchPointGroups {
+    /// Name of the collection
+    #[prost(string, tag = "1")]
+    #[validate(length(min = 1, max = 255))]
+    pub collection_name: ::prost::alloc::string::String,
+    /// Vector to compare against
+    #[prost(float, repeated, tag = "2")]
+    pub vector: ::prost::alloc::vec::Vec<f32>,
+    /// Filter conditions - return only those points that satisfy the specified conditions
+    #[prost(message, optional, tag = "3")]
+    #[validate]
+    pub filter: ::core::option::Option<Filter>,
+    /// Max number of result
+    #[prost(uint32, tag = "4")]
+    #[validate(range(min = 1))]
+    pub limit: u32,
+    /// Options for specifying which payload to include or not
+    #[prost(message, optional, tag = "5")]
+    pub with_payload: ::core::option::Option<WithPayloadSelector>,
+    /// Search config
+    #[prost(message, optional, tag = "6")]
+    #[validate]
+    pub params: ::core::option::Option<SearchParams>,
+    /// If provided - cut off results with worse scores
+    #[prost(float, optional, tag = "7")]
+    pub score_threshold: ::core::option::Option<f32>,
+    /// Which vector to use for search, if not specified - use default vector
+    #[prost(string, optional, tag = "8")]
+    pub vector_name: ::core::option::Option<::prost::alloc::string::String>,
+    /// Options for specifying which vectors to include into response
+    #[prost(message, optional, tag = "9")]
+    pub with_vectors: ::core::option::Option<WithVectorsSelector>,
+    /// Payload field to group by, must be a string or number field. If there are multiple values for the field, all of them will be used. One point can be in multiple groups.
+    #[prost(string, tag = "10")]
+    #[validate(length(min = 1))]
+    pub group_by: ::prost::alloc::string::String,
+    /// Maximum amount of points to return per group
+    #[prost(uint32, tag = "11")]
+    #[validate(range(min = 1))]
+    pub group_size: u32,
+    /// Options for specifying read consistency guarantees
+    #[prost(message, optional, tag = "12")]
+    pub read_consistency: ::core::option::Option<ReadConsistency>,
+    /// Options for specifying how to use the group id to lookup points in another collection
+    #[prost(message, optional, tag = "13")]
+    pub with_lookup: ::core::option::Option<WithLookup>,
+    /// If set, overrides global timeout setting for this request. Unit is seconds.
+    #[prost(uint64, optional, tag = "14")]
+    #[validate(custom = "crate::grpc::validate::validate_u64_range_min_1")]
+    pub timeout: ::core::option::Option<u64>,
+    /// Specify in which shards to look for the points, if not specified - look in all shards
+    #[prost(message, optional, tag = "15")]
+    pub shard_key_selector: ::core::option::Option<ShardKeySelector>,
+    #[prost(message, optional, tag = "16")]
+    pub sparse_indices: ::core::option::Option<SparseIndices>,
+    /// Number of vectors of the multi-vector, `vector` holds all of them one after another
+    #[prost(uint32, optional, tag = "17")]
+    pub vectors_count: ::core::option::Option<u32>,
 }
+#[derive(validator::Validate)]
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
-pub struct SparseIndices {
-    #[prost(uint32, repeated, tag = "1")]
-    pub data: ::prost::alloc::vec::Vec<u32>,
+pub struct OrderBy {
+    /// Payload key to order by, must have a numeric index
+    #[prost(string, tag = "1")]
+    pub key: ::prost::alloc::string::String,
+    /// Ascending or descending order, default is ascending
+    #[prost(enumeration = "Direction", optional, tag = "2")]
+    pub direction: ::core::option::Option<i32>,
+    /// Start from this value, inclusive
+    #[prost(double, optional, tag = "3")]
+    pub start_from: ::core::option::Option<f64>,
 }
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
-pub struct Vector {
-    #[prost(float, repeated, tag = "1")]
-    pub data: ::prost::alloc::vec::Vec<f32>,
+pub struct ScrollPoints {
+    #[prost(string, tag = "1")]
+    #[validate(length(min = 1, max = 255))]
+    pub collection_name: ::prost::alloc::string::String,
+    /// Filter conditions - return only those points that satisfy the specified conditions
     #[prost(message, optional, tag = "2")]
-    pub indices: ::core::option::Option<SparseIndices>,
+    #[validate]
+    pub filter: ::core::option::Option<Filter>,
+    /// Start with this ID
+    #[prost(message, optional, tag = "3")]
+    pub offset: ::core::option::Option<PointId>,
+    /// Max number of result
+    #[prost(uint32, optional, tag = "4")]
+    #[validate(custom = "crate::grpc::validate::validate_u32_range_min_1")]
+    pub limit: ::core::option::Option<u32>,
+    /// Options for specifying which payload to include or not
+    #[prost(message, optional, tag = "6")]
+    pub with_payload: ::core::option::Option<WithPayloadSelector>,
+    /// Options for specifying which vectors to include into response
+    #[prost(message, optional, tag = "7")]
+    pub with_vectors: ::core::option::Option<WithVectorsSelector>,
+    /// Options for specifying read consistency guarantees
+    #[prost(message, optional, tag = "8")]
+    pub read_consistency: ::core::option::Option<ReadConsistency>,
+    /// Specify in which shards to look for the points, if not specified - look in all shards
+    #[prost(message, optional, tag = "9")]
+    pub shard_key_selector: ::core::option::Option<ShardKeySelector>,
+    /// If set - return points as they were at this time (unix timestamp in seconds), within the time-travel window of the collection
+    #[prost(uint64, optional, tag = "10")]
+    pub as_of: ::core::option::Option<u64>,
+    /// If set - read all pages from the same view of the collection, as it was when the session was started by its first page. Next pages have to use `session_id` of the previous response. Sessions expire after a minute without reads by default
+    #[prost(string, optional, tag = "11")]
+    #[validate(length(min = 1, max = 4096))]
+    pub session_id: ::core::option::Option<::prost::alloc::string::String>,
+    /// Order the records by a payload field, can't be used with `offset`
+    #[prost(message, optional, tag = "12")]
+    pub order_by: ::core::option::Option<OrderBy>,
+    /// Return a sample of `limit` points instead of a page, can't be used with `offset`, `order_by` or `as_of`
+    #[prost(enumeration = "Sample", optional, tag = "13")]
+    pub sample: ::core::option::Option<i32>,
+    /// Time without reads in seconds, after which the session expires. Only used by the request, which starts the session. Default: 60
+    #[prost(uint64, optional, tag = "14")]
+    #[validate(range(min = 1, max = 3600))]
+    pub session_ttl_sec: ::core::option::Option<u64>,
+    /// Whether to return the versions of the points, which are used as `if_version` of conditional updates. Default: false
+    #[prost(bool, optional, tag = "15")]
+    pub with_version: ::core::option::Option<bool>,
 }
-/// ---------------------------------------------
-/// ----------------- ShardKeySelector ----------
-/// ---------------------------------------------
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
-pub struct ShardKeySelector {
-    /// List of shard keys which should be used in the request
-    #[prost(message, repeated, tag = "1")]
-    pub shard_keys: ::prost::alloc::vec::Vec<ShardKey>,
+pub struct LookupLocation {
+    #[prost(string, tag = "1")]
+    pub collection_name: ::prost::alloc::string::String,
+    /// Which vector to use for search, if not specified - use default vector
+    #[prost(string, optional, tag = "2")]
+    pub vector_name: ::core::option::Option<::prost::alloc::string::String>,
+    /// Specify in which shards to look for the points, if not specified - look in all shards
+    #[prost(message, optional, tag = "3")]
+    pub shard_key_selector: ::core::option::Option<ShardKeySelector>,
 }
 #[derive(validator::Validate)]
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
-pub struct UpsertPoints {
+pub struct RecommendPoints {
     /// name of the collection
     #[prost(string, tag = "1")]
     #[validate(length(min = 1, max = 255))]
     pub collection_name: ::prost::alloc::string::String,
-    /// Wait until the changes have been applied?
-    #[prost(bool, optional, tag = "2")]
-    pub wait: ::core::option::Option<bool>,
+    /// Look for vectors closest to the vectors from these points
+    #[prost(message, repeated, tag = "2")]
+    pub positive: ::prost::alloc::vec::Vec<PointId>,
+    /// Try to avoid vectors like the vector from these points
     #[prost(message, repeated, tag = "3")]
-    pub points: ::prost::alloc::vec::Vec<PointStruct>,
-    /// Write ordering guarantees
+    pub negative: ::prost::alloc::vec::Vec<PointId>,
+    /// Filter conditions - return only those points that satisfy the specified conditions
     #[prost(message, optional, tag = "4")]
-    pub ordering: ::core::option::Option<WriteOrdering>,
-    /// Option for custom sharding to specify used shard keys
-    #[prost(message, optional, tag = "5")]
+    #[validate]
+    pub filter: ::core::option::Option<Filter>,
+    /// Max number of result
+    #[prost(uint64, tag = "5")]
+    pub limit: u64,
+    /// Options for specifying which payload to include or not
+    #[prost(message, optional, tag = "7")]
+    pub with_payload: ::core::option::Option<WithPayloadSelector>,
+    /// Search config
+    #[prost(message, optional, tag = "8")]
+    #[validate]
+    pub params: ::core::option::Option<SearchParams>,
+    /// If provided - cut off results with worse scores
+    #[prost(float, optional, tag = "9")]
+    pub score_threshold: ::core::option::Option<f32>,
+    /// Offset of the result
+    #[prost(uint64, optional, tag = "10")]
+    pub offset: ::core::option::Option<u64>,
+    /// Define which vector to use for recommendation, if not specified - default vector
+    #[prost(string, optional, tag = "11")]
+    pub using: ::core::option::Option<::prost::alloc::string::String>,
+    /// Options for specifying which vectors to include into response
+    #[prost(message, optional, tag = "12")]
+    pub with_vectors: ::core::option::Option<WithVectorsSelector>,
+    /// Name of the collection to use for points lookup, if not specified - use current collection
+    #[prost(message, optional, tag = "13")]
+    pub lookup_from: ::core::option::Option<LookupLocation>,
+    /// Options for specifying read consistency guarantees
+    #[prost(message, optional, tag = "14")]
+    pub read_consistency: ::core::option::Option<ReadConsistency>,
+    /// How to use the example vectors to find the results
+    #[prost(enumeration = "RecommendStrategy", optional, tag = "16")]
+    pub strategy: ::core::option::Option<i32>,
+    /// Look for vectors closest to those
+    #[prost(message, repeated, tag = "17")]
+    #[validate]
+    pub positive_vectors: ::prost::alloc::vec::Vec<Vector>,
+    /// Try to avoid vectors like this
+    #[prost(message, repeated, tag = "18")]
+    #[validate]
+    pub negative_vectors: ::prost::alloc::vec::Vec<Vector>,
+    /// If set, overrides global timeout setting for this request. Unit is seconds.
+    #[prost(uint64, optional, tag = "19")]
+    #[validate(custom = "crate::grpc::validate::validate_u64_range_min_1")]
+    pub timeout: ::core::option::Option<u64>,
+    /// Specify in which shards to look for the points, if not specified - look in all shards
+    #[prost(message, optional, tag = "20")]
     pub shard_key_selector: ::core::option::Option<ShardKeySelector>,
 }
 #[derive(validator::Validate)]
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
-pub struct DeletePoints {
-    /// name of the collection
+pub struct RecommendBatchPoints {
+    /// Name of the collection
     #[prost(string, tag = "1")]
     #[validate(length(min = 1, max = 255))]
     pub collection_name: ::prost::alloc::string::String,
-    /// Wait until the changes have been applied?
-    #[prost(bool, optional, tag = "2")]
-    pub wait: ::core::option::Option<bool>,
-    /// Affected points
+    #[prost(message, repeated, tag = "2")]
+    #[validate]
+    pub recommend_points: ::prost::alloc::vec::Vec<RecommendPoints>,
+    /// Options for specifying read consistency guarantees
     #[prost(message, optional, tag = "3")]
-    pub points: ::core::option::Option<PointsSelector>,
-    /// Write ordering guarantees
-    #[prost(message, optional, tag = "4")]
-    pub ordering: ::core::option::Option<WriteOrdering>,
-    /// Option for custom sharding to specify used shard keys
-    #[prost(message, optional, tag = "5")]
-    pub shard_key_selector: ::core::option::Option<ShardKeySelector>,
+    pub read_consistency: ::core::option::Option<ReadConsistency>,
+    /// If set, overrides global timeout setting for this request. Unit is seconds.
+    #[prost(uint64, optional, tag = "4")]
+    #[validate(custom = "crate::grpc::validate::validate_u64_range_min_1")]
+    pub timeout: ::core::option::Option<u64>,
 }
 #[derive(validator::Validate)]
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
-pub struct GetPoints {
-    /// name of the collection
+pub struct RecommendPointGroups {
+    /// Name of the collection
     #[prost(string, tag = "1")]
     #[validate(length(min = 1, max = 255))]
     pub collection_name: ::prost::alloc::string::String,
-    /// List of points to retrieve
+    /// Look for vectors closest to the vectors from these points
     #[prost(message, repeated, tag = "2")]
-    pub ids: ::prost::alloc::vec::Vec<PointId>,
-    /// Options for specifying which payload to include or not
+    pub positive: ::prost::alloc::vec::Vec<PointId>,
+    /// Try to avoid vectors like the vector from these points
+    #[prost(message, repeated, tag = "3")]
+    pub negative: ::prost::alloc::vec::Vec<PointId>,
+    /// Filter conditions - return only those points that satisfy the specified conditions
     #[prost(message, optional, tag = "4")]
+    #[validate]
+    pub filter: ::core::option::Option<Filter>,
+    /// Max number of groups in result
+    #[prost(uint32, tag = "5")]
+    #[validate(range(min = 1))]
+    pub limit: u32,
+    /// Options for specifying which payload to include or not
+    #[prost(message, optional, tag = "6")]
     pub with_payload: ::core::option::Option<WithPayloadSelector>,
+    /// Search config
+    #[prost(message, optional, tag = "7")]
+    #[validate]
+    pub params: ::core::option::Option<SearchParams>,
+    /// If provided - cut off results with worse scores
+    #[prost(float, optional, tag = "8")]
+    pub score_threshold: ::core::option::Option<f32>,
+    /// Define which vector to use for recommendation, if not specified - default vector
+    #[prost(string, optional, tag = "9")]
+    pub using: ::core::option::Option<::prost::alloc::string::String>,
     /// Options for specifying which vectors to include into response
-    #[prost(message, optional, tag = "5")]
+    #[prost(message, optional, tag = "10")]
     pub with_vectors: ::core::option::Option<WithVectorsSelector>,
+    /// Name of the collection to use for points lookup, if not specified - use current collection
+    #[prost(message, optional, tag = "11")]
+    pub lookup_from: ::core::option::Option<LookupLocation>,
+    /// Payload field to group by, must be a string or number field. If there are multiple values for the field, all of them will be used. One point can be in multiple groups.
+    #[prost(string, tag = "12")]
+    #[validate(length(min = 1))]
+    pub group_by: ::prost::alloc::string::String,
+    /// Maximum amount of points to return per group
+    #[prost(uint32, tag = "13")]
+    #[validate(range(min = 1))]
+    pub group_size: u32,
     /// Options for specifying read consistency guarantees
-    #[prost(message, optional, tag = "6")]
+    #[prost(message, optional, tag = "14")]
     pub read_consistency: ::core::option::Option<ReadConsistency>,
+    /// Options for specifying how to use the group id to lookup points in another collection
+    #[prost(message, optional, tag = "15")]
+    pub with_lookup: ::core::option::Option<WithLookup>,
+    /// How to use the example vectors to find the results
+    #[prost(enumeration = "RecommendStrategy", optional, tag = "17")]
+    pub strategy: ::core::option::Option<i32>,
+    /// Look for vectors closest to those
+    #[prost(message, repeated, tag = "18")]
+    #[validate]
+    pub positive_vectors: ::prost::alloc::vec::Vec<Vector>,
+    /// Try to avoid vectors like this
+    #[prost(message, repeated, tag = "19")]
+    #[validate]
+    pub negative_vectors: ::prost::alloc::vec::Vec<Vector>,
+    /// If set, overrides global timeout setting for this request. Unit is seconds.
+    #[prost(uint64, optional, tag = "20")]
+    #[validate(custom = "crate::grpc::validate::validate_u64_range_min_1")]
+    pub timeout: ::core::option::Option<u64>,
     /// Specify in which shards to look for the points, if not specified - look in all shards
-    #[prost(message, optional, tag = "7")]
+    #[prost(message, optional, tag = "21")]
     pub shard_key_selector: ::core::option::Option<ShardKeySelector>,
 }
-#[derive(validator::Validate)]
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
-pub struct UpdatePointVectors {
-    /// name of the collection
-    #[prost(string, tag = "1")]
-    #[validate(length(min = 1, max = 255))]
-    pub collection_name: ::prost::alloc::string::String,
-    /// Wait until the changes have been applied?
-    #[prost(bool, optional, tag = "2")]
-    pub wait: ::core::option::Option<bool>,
-    /// List of points and vectors to update
-    #[prost(message, repeated, tag = "3")]
-    pub points: ::prost::alloc::vec::Vec<PointVectors>,
-    /// Write ordering guarantees
-    #[prost(message, optional, tag = "4")]
-    pub ordering: ::core::option::Option<WriteOrdering>,
-    /// Option for custom sharding to specify used shard keys
-    #[prost(message, optional, tag = "5")]
-    pub shard_key_selector: ::core::option::Option<ShardKeySelector>,
+pub struct TargetVector {
+    #[prost(oneof = "target_vector::Target", tags = "1")]
+    pub target: ::core::option::Option<target_vector::Target>,
+}
+/// Nested message and enum types in `TargetVector`.
+pub mod target_vector {
+    #[derive(serde::Serialize)]
+    #[allow(clippy::derive_partial_eq_without_eq)]
+    #[derive(Clone, PartialEq, ::prost::Oneof)]
+    pub enum Target {
+        #[prost(message, tag = "1")]
+        Single(super::VectorExample),
+    }
 }
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
-pub struct PointVectors {
-    /// ID to update vectors for
-    #[prost(message, optional, tag = "1")]
-    pub id: ::core::option::Option<PointId>,
-    /// Named vectors to update, leave others intact
-    #[prost(message, optional, tag = "2")]
-    pub vectors: ::core::option::Option<Vectors>,
+pub struct VectorExample {
+    #[prost(oneof = "vector_example::Example", tags = "1, 2")]
+    pub example: ::core::option::Option<vector_example::Example>,
+}
+/// Nested message and enum types in `VectorExample`.
+pub mod vector_example {
+    #[derive(serde::Serialize)]
+    #[allow(clippy::derive_partial_eq_without_eq)]
+    #[derive(Clone, PartialEq, ::prost::Oneof)]
+    pub enum Example {
+        #[prost(message, tag = "1")]
+        Id(super::PointId),
+        #[prost(message, tag = "2")]
+        Vector(super::Vector),
+    }
 }
-#[derive(validator::Validate)]
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
-pub struct DeletePointVectors {
-    /// name of the collection
-    #[prost(string, tag = "1")]
-    #[validate(length(min = 1, max = 255))]
-    pub collection_name: ::prost::alloc::string::String,
-    /// Wait until the changes have been applied?
-    #[prost(bool, optional, tag = "2")]
-    pub wait: ::core::option::Option<bool>,
-    /// Affected points
-    #[prost(message, optional, tag = "3")]
-    pub points_selector: ::core::option::Option<PointsSelector>,
-    /// List of vector names to delete
-    #[prost(message, optional, tag = "4")]
-    pub vectors: ::core::option::Option<VectorsSelector>,
-    /// Write ordering guarantees
-    #[prost(message, optional, tag = "5")]
-    pub ordering: ::core::option::Option<WriteOrdering>,
-    /// Option for custom sharding to specify used shard keys
-    #[prost(message, optional, tag = "6")]
-    pub shard_key_selector: ::core::option::Option<ShardKeySelector>,
+pub struct ContextExamplePair {
+    #[prost(message, optional, tag = "1")]
+    pub positive: ::core::option::Option<VectorExample>,
+    #[prost(message, optional, tag = "2")]
+    pub negative: ::core::option::Option<VectorExample>,
 }
 #[derive(validator::Validate)]
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
-pub struct SetPayloadPoints {
+pub struct DiscoverPoints {
     /// name of the collection
     #[prost(string, tag = "1")]
     #[validate(length(min = 1, max = 255))]
     pub collection_name: ::prost::alloc::string::String,
-    /// Wait until the changes have been applied?
-    #[prost(bool, optional, tag = "2")]
-    pub wait: ::core::option::Option<bool>,
-    /// New payload values
-    #[prost(map = "string, message", tag = "3")]
-    pub payload: ::std::collections::HashMap<::prost::alloc::string::String, Value>,
-    /// Affected points
-    #[prost(message, optional, tag = "5")]
-    pub points_selector: ::core::option::Option<PointsSelector>,
-    /// Write ordering guarantees
+    /// Use this as the primary search objective
+    #[prost(message, optional, tag = "2")]
+    pub target: ::core::option::Option<TargetVector>,
+    /// Search will be constrained by these pairs of examples
+    #[prost(message, repeated, tag = "3")]
+    pub context: ::prost::alloc::vec::Vec<ContextExamplePair>,
+    /// Filter conditions - return only those points that satisfy the specified conditions
+    #[prost(message, optional, tag = "4")]
+    #[validate]
+    pub filter: ::core::option::Option<Filter>,
+    /// Max number of result
+    #[prost(uint64, tag = "5")]
+    #[validate(range(min = 1))]
+    pub limit: u64,
+    /// Options for specifying which payload to include or not
     #[prost(message, optional, tag = "6")]
-    pub ordering: ::core::option::Option<WriteOrdering>,
-    /// Option for custom sharding to specify used shard keys
+    pub with_payload: ::core::option::Option<WithPayloadSelector>,
+    /// Search config
     #[prost(message, optional, tag = "7")]
+    #[validate]
+    pub params: ::core::option::Option<SearchParams>,
+    /// Offset of the result
+    #[prost(uint64, optional, tag = "8")]
+    pub offset: ::core::option::Option<u64>,
+    /// Define which vector to use for recommendation, if not specified - default vector
+    #[prost(string, optional, tag = "9")]
+    pub using: ::core::option::Option<::prost::alloc::string::String>,
+    /// Options for specifying which vectors to include into response
+    #[prost(message, optional, tag = "10")]
+    pub with_vectors: ::core::option::Option<WithVectorsSelector>,
+    /// Name of the collection to use for points lookup, if not specified - use current collection
+    #[prost(message, optional, tag = "11")]
+    pub lookup_from: ::core::option::Option<LookupLocation>,
+    /// Options for specifying read consistency guarantees
+    #[prost(message, optional, tag = "12")]
+    pub read_consistency: ::core::option::Option<ReadConsistency>,
+    /// If set, overrides global timeout setting for this request. Unit is seconds.
+    #[prost(uint64, optional, tag = "13")]
+    #[validate(custom = "crate::grpc::validate::validate_u64_range_min_1")]
+    pub timeout: ::core::option::Option<u64>,
+    /// Specify in which shards to look for the points, if not specified - look in all shards
+    #[prost(message, optional, tag = "14")]
     pub shard_key_selector: ::core::option::Option<ShardKeySelector>,
 }
 #[derive(validator::Validate)]
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
-pub struct DeletePayloadPoints {
-    /// name of the collection
+pub struct DiscoverBatchPoints {
+    /// Name of the collection
     #[prost(string, tag = "1")]
     #[validate(length(min = 1, max = 255))]
     pub collection_name: ::prost::alloc::string::String,
-    /// Wait until the changes have been applied?
-    #[prost(bool, optional, tag = "2")]
-    pub wait: ::core::option::Option<bool>,
-    /// List of keys to delete
-    #[prost(string, repeated, tag = "3")]
-    pub keys: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
-    /// Affected points
-    #[prost(message, optional, tag = "5")]
-    pub points_selector: ::core::option::Option<PointsSelector>,
-    /// Write ordering guarantees
-    #[prost(message, optional, tag = "6")]
-    pub ordering: ::core::option::Option<WriteOrdering>,
-    /// Option for custom sharding to specify used shard keys
-    #[prost(message, optional, tag = "7")]
-    pub shard_key_selector: ::core::option::Option<ShardKeySelector>,
+    #[prost(message, repeated, tag = "2")]
+    #[validate]
+    pub discover_points: ::prost::alloc::vec::Vec<DiscoverPoints>,
+    /// Options for specifying read consistency guarantees
+    #[prost(message, optional, tag = "3")]
+    pub read_consistency: ::core::option::Option<ReadConsistency>,
+    /// If set, overrides global timeout setting for this request. Unit is seconds.
+    #[prost(uint64, optional, tag = "4")]
+    #[validate(custom = "crate::grpc::validate::validate_u64_range_min_1")]
+    pub timeout: ::core::option::Option<u64>,
 }
 #[derive(validator::Validate)]
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
-pub struct ClearPayloadPoints {
+pub struct CountPoints {
     /// name of the collection
     #[prost(string, tag = "1")]
     #[validate(length(min = 1, max = 255))]
     pub collection_name: ::prost::alloc::string::String,
-    /// Wait until the changes have been applied?
-    #[prost(bool, optional, tag = "2")]
-    pub wait: ::core::option::Option<bool>,
-    /// Affected points
-    #[prost(message, optional, tag = "3")]
-    pub points: ::core::option::Option<PointsSelector>,
-    /// Write ordering guarantees
+    /// Filter conditions - return only those points that satisfy the specified conditions
+    #[prost(message, optional, tag = "2")]
+    #[validate]
+    pub filter: ::core::option::Option<Filter>,
+    /// If `true` - return exact count, if `false` - return approximate count
+    #[prost(bool, optional, tag = "3")]
+    pub exact: ::core::option::Option<bool>,
+    /// Options for specifying read consistency guarantees
     #[prost(message, optional, tag = "4")]
-    pub ordering: ::core::option::Option<WriteOrdering>,
-    /// Option for custom sharding to specify used shard keys
+    pub read_consistency: ::core::option::Option<ReadConsistency>,
+    /// Specify in which shards to look for the points, if not specified - look in all shards
     #[prost(message, optional, tag = "5")]
     pub shard_key_selector: ::core::option::Option<ShardKeySelector>,
 }
-#[derive(validator::Validate)]
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
-pub struct CreateFieldIndexCollection {
-    /// name of the collection
-    #[prost(string, tag = "1")]
-    #[validate(length(min = 1, max = 255))]
-    pub collection_name: ::prost::alloc::string::String,
-    /// Wait until the changes have been applied?
-    #[prost(bool, optional, tag = "2")]
-    pub wait: ::core::option::Option<bool>,
-    /// Field name to index
-    #[prost(string, tag = "3")]
-    #[validate(length(min = 1))]
-    pub field_name: ::prost::alloc::string::String,
-    /// Field type.
-    #[prost(enumeration = "FieldType", optional, tag = "4")]
-    pub field_type: ::core::option::Option<i32>,
-    /// Payload index params.
-    #[prost(message, optional, tag = "5")]
-    pub field_index_params: ::core::option::Option<PayloadIndexParams>,
-    /// Write ordering guarantees
-    #[prost(message, optional, tag = "6")]
-    pub ordering: ::core::option::Option<WriteOrdering>,
+pub struct PointsUpdateOperation {
+    #[prost(
+        oneof = "points_update_operation::Operation",
+        tags = "1, 2, 3, 4, 5, 6, 7, 8, 9, 10"
+    )]
+    pub operation: ::core::option::Option<points_update_operation::Operation>,
+}
+/// Nested message and enum types in `PointsUpdateOperation`.
+pub mod points_update_operation {
+    #[derive(serde::Serialize)]
+    #[allow(clippy::derive_partial_eq_without_eq)]
+    #[derive(Clone, PartialEq, ::prost::Message)]
+    pub struct PointStructList {
+        #[prost(message, repeated, tag = "1")]
+        pub points: ::prost::alloc::vec::Vec<super::PointStruct>,
+        /// Option for custom sharding to specify used shard keys
+        #[prost(message, optional, tag = "2")]
+        pub shard_key_selector: ::core::option::Option<super::ShardKeySelector>,
+    }
+    #[derive(serde::Serialize)]
+    #[allow(clippy::derive_partial_eq_without_eq)]
+    #[derive(Clone, PartialEq, ::prost::Message)]
+    pub struct SetPayload {
+        #[prost(map = "string, message", tag = "1")]
+        pub payload: ::std::collections::HashMap<
+            ::prost::alloc::string::String,
+            super::Value,
+        >,
+        /// Affected points
+        #[prost(message, optional, tag = "2")]
+        pub points_selector: ::core::option::Option<super::PointsSelector>,
+        /// Option for custom sharding to specify used shard keys
+        #[prost(message, optional, tag = "3")]
+        pub shard_key_selector: ::core::option::Option<super::ShardKeySelector>,
+        /// Only update points, which current version is equal to this one. Otherwise, the whole operation is rejected
+        #[prost(uint64, optional, tag = "4")]
+        pub if_version: ::core::option::Option<u64>,
+    }
+    #[derive(serde::Serialize)]
+    #[allow(clippy::derive_partial_eq_without_eq)]
+    #[derive(Clone, PartialEq, ::prost::Message)]
+    pub struct DeletePayload {
+        #[prost(string, repeated, tag = "1")]
+        pub keys: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+        /// Affected points
+        #[prost(message, optional, tag = "2")]
+        pub points_selector: ::core::option::Option<super::PointsSelector>,
+        /// Option for custom sharding to specify used shard keys
+        #[prost(message, optional, tag = "3")]
+        pub shard_key_selector: ::core::option::Option<super::ShardKeySelector>,
+    }
+    #[derive(serde::Serialize)]
+    #[allow(clippy::derive_partial_eq_without_eq)]
+    #[derive(Clone, PartialEq, ::prost::Message)]
+    pub struct UpdateVectors {
+        /// List of points and vectors to update
+        #[prost(message, repeated, tag = "1")]
+        pub points: ::prost::alloc::vec::Vec<super::PointVectors>,
+        /// Option for custom sharding to specify used shard keys
+        #[prost(message, optional, tag = "2")]
+        pub shard_key_selector: ::core::option::Option<super::ShardKeySelector>,
+    }
+    #[derive(serde::Serialize)]
+    #[allow(clippy::derive_partial_eq_without_eq)]
+    #[derive(Clone, PartialEq, ::prost::Message)]
+    pub struct DeleteVectors {
+        /// Affected points
+        #[prost(message, optional, tag = "1")]
+        pub points_selector: ::core::option::Option<super::PointsSelector>,
+        /// List of vector names to delete
+        #[prost(message, optional, tag = "2")]
+        pub vectors: ::core::option::Option<super::VectorsSelector>,
+        /// Option for custom sharding to specify used shard keys
+        #[prost(message, optional, tag = "3")]
+        pub shard_key_selector: ::core::option::Option<super::ShardKeySelector>,
+    }
+    #[derive(validator::Validate)]
+    #[derive(serde::Serialize)]
+    #[allow(clippy::derive_partial_eq_without_eq)]
+    #[derive(Clone, PartialEq, ::prost::Message)]
+    pub struct DeletePoints {
+        /// Affected points
+        #[prost(message, optional, tag = "1")]
+        pub points: ::core::option::Option<super::PointsSelector>,
+        /// Option for custom sharding to specify used shard keys
+        #[prost(message, optional, tag = "2")]
+        pub shard_key_selector: ::core::option::Option<super::ShardKeySelector>,
+    }
+    #[derive(serde::Serialize)]
+    #[allow(clippy::derive_partial_eq_without_eq)]
+    #[derive(Clone, PartialEq, ::prost::Message)]
+    pub struct ClearPayload {
+        /// Affected points
+        #[prost(message, optional, tag = "1")]
+        pub points: ::core::option::Option<super::PointsSelector>,
+        /// Option for custom sharding to specify used shard keys
+        #[prost(message, optional, tag = "2")]
+        pub shard_key_selector: ::core::option::Option<super::ShardKeySelector>,
+    }
+    #[derive(serde::Serialize)]
+    #[allow(clippy::derive_partial_eq_without_eq)]
+    #[derive(Clone, PartialEq, ::prost::Oneof)]
+    pub enum Operation {
+        #[prost(message, tag = "1")]
+        Upsert(PointStructList),
+        #[prost(message, tag = "2")]
+        DeleteDeprecated(super::PointsSelector),
+        #[prost(message, tag = "3")]
+        SetPayload(SetPayload),
+        #[prost(message, tag = "4")]
+        OverwritePayload(SetPayload),
+        #[prost(message, tag = "5")]
+        DeletePayload(DeletePayload),
+        #[prost(message, tag = "6")]
+        ClearPayloadDeprecated(super::PointsSelector),
+        #[prost(message, tag = "7")]
+        UpdateVectors(UpdateVectors),
+        #[prost(message, tag = "8")]
+        DeleteVectors(DeleteVectors),
+        #[prost(message, tag = "9")]
+        DeletePoints(DeletePoints),
+        #[prost(message, tag = "10")]
+        ClearPayload(ClearPayload),
+    }
 }
 #[derive(validator::Validate)]
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
-pub struct DeleteFieldIndexCollection {
+pub struct UpdateBatchPoints {
     /// name of the collection
     #[prost(string, tag = "1")]
     #[validate(length(min = 1, max = 255))]
@@ -3443,1488 +3489,2879 @@ pub struct DeleteFieldIndexCollection {
     /// Wait until the changes have been applied?
     #[prost(bool, optional, tag = "2")]
     pub wait: ::core::option::Option<bool>,
-    /// Field name to delete
-    #[prost(string, tag = "3")]
+    #[prost(message, repeated, tag = "3")]
     #[validate(length(min = 1))]
-    pub field_name: ::prost::alloc::string::String,
+    pub operations: ::prost::alloc::vec::Vec<PointsUpdateOperation>,
     /// Write ordering guarantees
     #[prost(message, optional, tag = "4")]
     pub ordering: ::core::option::Option<WriteOrdering>,
+    /// Max number of seconds to wait for the deactivation of replicas, which failed to apply the update
+    #[prost(uint64, optional, tag = "5")]
+    pub shard_deactivation_timeout: ::core::option::Option<u64>,
+    /// Number of replicas, which must apply the update for it to succeed, overrides the collection setting
+    #[prost(message, optional, tag = "6")]
+    pub write_consistency: ::core::option::Option<WriteConsistency>,
+    /// Max number of seconds to wait for the replicas to apply the update, those which didn't are reported as failed
+    #[prost(uint64, optional, tag = "7")]
+    #[validate(custom = "crate::grpc::validate::validate_u64_range_min_1")]
+    pub timeout: ::core::option::Option<u64>,
+    /// Apply all operations as a single update: in order, under one operation number, without other updates in between. If an operation fails, the previous ones are rolled back. Concurrent reads might see the batch partially applied. A batch can change at most 10000 points. All operations must use the same shard key
+    #[prost(bool, optional, tag = "8")]
+    pub all_or_nothing: ::core::option::Option<bool>,
+    /// Priority of the update, interactive updates are not queued behind bulk ones
+    #[prost(enumeration = "UpdatePriority", optional, tag = "9")]
+    pub priority: ::core::option::Option<i32>,
 }
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
-pub struct PayloadIncludeSelector {
-    /// List of payload keys to include into result
-    #[prost(string, repeated, tag = "1")]
-    pub fields: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+pub struct PointsOperationResponse {
+    #[prost(message, optional, tag = "1")]
+    pub result: ::core::option::Option<UpdateResult>,
+    /// Time spent to process
+    #[prost(double, tag = "2")]
+    pub time: f64,
 }
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
-pub struct PayloadExcludeSelector {
-    /// List of payload keys to exclude from the result
-    #[prost(string, repeated, tag = "1")]
-    pub fields: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+pub struct FilterUpdateResponse {
+    #[prost(message, optional, tag = "1")]
+    pub result: ::core::option::Option<FilterUpdateResult>,
+    /// Time spent to process
+    #[prost(double, tag = "2")]
+    pub time: f64,
 }
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
-pub struct WithPayloadSelector {
-    #[prost(oneof = "with_payload_selector::SelectorOptions", tags = "1, 2, 3")]
-    pub selector_options: ::core::option::Option<with_payload_selector::SelectorOptions>,
-}
-/// Nested message and enum types in `WithPayloadSelector`.
-pub mod with_payload_selector {
-    #[derive(serde::Serialize)]
-    #[allow(clippy::derive_partial_eq_without_eq)]
-    #[derive(Clone, PartialEq, ::prost::Oneof)]
-    pub enum SelectorOptions {
-        /// If `true` - return all payload, if `false` - none
-        #[prost(bool, tag = "1")]
-        Enable(bool),
-        #[prost(message, tag = "2")]
-        Include(super::PayloadIncludeSelector),
-        #[prost(message, tag = "3")]
-        Exclude(super::PayloadExcludeSelector),
-    }
+pub struct FilterUpdateResult {
+    /// Number of the updated points
+    #[prost(uint64, tag = "1")]
+    pub points: u64,
+    /// Number of the applied batches
+    #[prost(uint64, tag = "2")]
+    pub batches: u64,
 }
-#[derive(validator::Validate)]
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
-pub struct NamedVectors {
-    #[prost(map = "string, message", tag = "1")]
-    #[validate]
-    pub vectors: ::std::collections::HashMap<::prost::alloc::string::String, Vector>,
+pub struct UpdateResult {
+    /// Number of operation
+    #[prost(uint64, optional, tag = "1")]
+    pub operation_id: ::core::option::Option<u64>,
+    /// Operation status
+    #[prost(enumeration = "UpdateStatus", tag = "2")]
+    pub status: i32,
+    /// Token to pass to the subsequent searches to only read from replicas, which have applied this update
+    #[prost(string, optional, tag = "3")]
+    pub consistency_token: ::core::option::Option<::prost::alloc::string::String>,
+    /// Results of the update on each replica, which responded before the update returned
+    #[prost(message, repeated, tag = "4")]
+    pub acknowledgments: ::prost::alloc::vec::Vec<ReplicaAcknowledgment>,
 }
-#[derive(validator::Validate)]
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
-pub struct Vectors {
-    #[prost(oneof = "vectors::VectorsOptions", tags = "1, 2")]
-    #[validate]
-    pub vectors_options: ::core::option::Option<vectors::VectorsOptions>,
-}
-/// Nested message and enum types in `Vectors`.
-pub mod vectors {
-    #[derive(serde::Serialize)]
-    #[allow(clippy::derive_partial_eq_without_eq)]
-    #[derive(Clone, PartialEq, ::prost::Oneof)]
-    pub enum VectorsOptions {
-        #[prost(message, tag = "1")]
-        Vector(super::Vector),
-        #[prost(message, tag = "2")]
-        Vectors(super::NamedVectors),
-    }
+pub struct ReplicaAcknowledgment {
+    /// Shard of the replica
+    #[prost(uint32, tag = "1")]
+    pub shard_id: u32,
+    /// Peer of the replica
+    #[prost(uint64, tag = "2")]
+    pub peer_id: u64,
+    /// Update status on the replica. If not set - the replica failed to apply the update
+    #[prost(enumeration = "UpdateStatus", optional, tag = "3")]
+    pub status: ::core::option::Option<i32>,
+    /// Error, the replica failed to apply the update with
+    #[prost(string, optional, tag = "4")]
+    pub error: ::core::option::Option<::prost::alloc::string::String>,
 }
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
-pub struct VectorsSelector {
-    /// List of vectors to include into result
-    #[prost(string, repeated, tag = "1")]
-    pub names: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+pub struct ScoredPoint {
+    /// Point id
+    #[prost(message, optional, tag = "1")]
+    pub id: ::core::option::Option<PointId>,
+    /// Payload
+    #[prost(map = "string, message", tag = "2")]
+    pub payload: ::std::collections::HashMap<::prost::alloc::string::String, Value>,
+    /// Similarity score
+    #[prost(float, tag = "3")]
+    pub score: f32,
+    /// Last update operation applied to this point
+    #[prost(uint64, tag = "5")]
+    pub version: u64,
+    /// Vectors to search
+    #[prost(message, optional, tag = "6")]
+    pub vectors: ::core::option::Option<Vectors>,
+    /// Shard key
+    #[prost(message, optional, tag = "7")]
+    pub shard_key: ::core::option::Option<ShardKey>,
 }
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
-pub struct WithVectorsSelector {
-    #[prost(oneof = "with_vectors_selector::SelectorOptions", tags = "1, 2")]
-    pub selector_options: ::core::option::Option<with_vectors_selector::SelectorOptions>,
+pub struct GroupId {
+    #[prost(oneof = "group_id::Kind", tags = "1, 2, 3")]
+    pub kind: ::core::option::Option<group_id::Kind>,
 }
-/// Nested message and enum types in `WithVectorsSelector`.
-pub mod with_vectors_selector {
+/// Nested message and enum types in `GroupId`.
+pub mod group_id {
     #[derive(serde::Serialize)]
     #[allow(clippy::derive_partial_eq_without_eq)]
     #[derive(Clone, PartialEq, ::prost::Oneof)]
-    pub enum SelectorOptions {
-        /// If `true` - return all vectors, if `false` - none
-        #[prost(bool, tag = "1")]
-        Enable(bool),
-        /// List of payload keys to include into result
-        #[prost(message, tag = "2")]
-        Include(super::VectorsSelector),
+    pub enum Kind {
+        /// Represents a double value.
+        #[prost(uint64, tag = "1")]
+        UnsignedValue(u64),
+        /// Represents an integer value
+        #[prost(int64, tag = "2")]
+        IntegerValue(i64),
+        /// Represents a string value.
+        #[prost(string, tag = "3")]
+        StringValue(::prost::alloc::string::String),
     }
 }
-#[derive(validator::Validate)]
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
-pub struct QuantizationSearchParams {
-    ///
-    /// If set to true, search will ignore quantized vector data
-    #[prost(bool, optional, tag = "1")]
-    pub ignore: ::core::option::Option<bool>,
-    ///
-    /// If true, use original vectors to re-score top-k results. If ignored, qdrant decides automatically does rescore enabled or not.
-    #[prost(bool, optional, tag = "2")]
-    pub rescore: ::core::option::Option<bool>,
-    ///
-    /// Oversampling factor for quantization.
-    ///
-    /// Defines how many extra vectors should be pre-selected using quantized index,
-    /// and then re-scored using original vectors.
-    ///
-    /// For example, if `oversampling` is 2.4 and `limit` is 100, then 240 vectors will be pre-selected using quantized index,
-    /// and then top-100 will be returned after re-scoring.
-    #[prost(double, optional, tag = "3")]
-    #[validate(custom = "crate::grpc::validate::validate_f64_range_min_1")]
-    pub oversampling: ::core::option::Option<f64>,
+pub struct PointGroup {
+    /// Group id
+    #[prost(message, optional, tag = "1")]
+    pub id: ::core::option::Option<GroupId>,
+    /// Points in the group
+    #[prost(message, repeated, tag = "2")]
+    pub hits: ::prost::alloc::vec::Vec<ScoredPoint>,
+    /// Point(s) from the lookup collection that matches the group id
+    #[prost(message, optional, tag = "3")]
+    pub lookup: ::core::option::Option<RetrievedPoint>,
 }
-#[derive(validator::Validate)]
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
-pub struct SearchParams {
-    ///
-    /// Params relevant to HNSW index. Size of the beam in a beam-search.
-    /// Larger the value - more accurate the result, more time required for search.
-    #[prost(uint64, optional, tag = "1")]
-    pub hnsw_ef: ::core::option::Option<u64>,
-    ///
-    /// Search without approximation. If set to true, search may run long but with exact results.
-    #[prost(bool, optional, tag = "2")]
-    pub exact: ::core::option::Option<bool>,
-    ///
-    /// If set to true, search will ignore quantized vector data
-    #[prost(message, optional, tag = "3")]
-    #[validate]
-    pub quantization: ::core::option::Option<QuantizationSearchParams>,
-    ///
-    /// If enabled, the engine will only perform search among indexed or small segments.
-    /// Using this option prevents slow searches in case of delayed index, but does not
-    /// guarantee that all uploaded vectors will be included in search results
-    #[prost(bool, optional, tag = "4")]
-    pub indexed_only: ::core::option::Option<bool>,
+pub struct GroupsResult {
+    /// Groups
+    #[prost(message, repeated, tag = "1")]
+    pub groups: ::prost::alloc::vec::Vec<PointGroup>,
 }
-#[derive(validator::Validate)]
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
-pub struct SearchPoints {
-    /// name of the collection
-    #[prost(string, tag = "1")]
-    #[validate(length(min = 1, max = 255))]
-    pub collection_name: ::prost::alloc::string::String,
-    /// vector
-    #[prost(float, repeated, tag = "2")]
-    pub vector: ::prost::alloc::vec::Vec<f32>,
-    /// Filter conditions - return only those points that satisfy the specified conditions
-    #[prost(message, optional, tag = "3")]
-    #[validate]
-    pub filter: ::core::option::Option<Filter>,
-    /// Max number of result
-    #[prost(uint64, tag = "4")]
-    #[validate(range(min = 1))]
-    pub limit: u64,
-    /// Options for specifying which payload to include or not
-    #[prost(message, optional, tag = "6")]
-    pub with_payload: ::core::option::Option<WithPayloadSelector>,
-    /// Search config
-    #[prost(message, optional, tag = "7")]
-    #[validate]
-    pub params: ::core::option::Option<SearchParams>,
-    /// If provided - cut off results with worse scores
-    #[prost(float, optional, tag = "8")]
-    pub score_threshold: ::core::option::Option<f32>,
-    /// Offset of the result
-    #[prost(uint64, optional, tag = "9")]
-    pub offset: ::core::option::Option<u64>,
-    /// Which vector to use for search, if not specified - use default vector
-    #[prost(string, optional, tag = "10")]
-    pub vector_name: ::core::option::Option<::prost::alloc::string::String>,
-    /// Options for specifying which vectors to include into response
-    #[prost(message, optional, tag = "11")]
-    pub with_vectors: ::core::option::Option<WithVectorsSelector>,
-    /// Options for specifying read consistency guarantees
-    #[prost(message, optional, tag = "12")]
-    pub read_consistency: ::core::option::Option<ReadConsistency>,
-    /// If set, overrides global timeout setting for this request. Unit is seconds.
-    #[prost(uint64, optional, tag = "13")]
-    #[validate(custom = "crate::grpc::validate::validate_u64_range_min_1")]
-    pub timeout: ::core::option::Option<u64>,
-    /// Specify in which shards to look for the points, if not specified - look in all shards
-    #[prost(message, optional, tag = "14")]
-    pub shard_key_selector: ::core::option::Option<ShardKeySelector>,
-    #[prost(message, optional, tag = "15")]
-    pub sparse_indices: ::core::option::Option<SparseIndices>,
+pub struct ShardFailure {
+    #[prost(uint32, tag = "1")]
+    pub shard_id: u32,
+    #[prost(message, optional, tag = "2")]
+    pub shard_key: ::core::option::Option<ShardKey>,
+    /// Reason of the failure
+    #[prost(string, tag = "3")]
+    pub error: ::prost::alloc::string::String,
 }
-#[derive(validator::Validate)]
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
-pub struct SearchBatchPoints {
-    /// Name of the collection
-    #[prost(string, tag = "1")]
-    #[validate(length(min = 1, max = 255))]
-    pub collection_name: ::prost::alloc::string::String,
-    #[prost(message, repeated, tag = "2")]
-    #[validate]
-    pub search_points: ::prost::alloc::vec::Vec<SearchPoints>,
-    /// Options for specifying read consistency guarantees
-    #[prost(message, optional, tag = "3")]
-    pub read_consistency: ::core::option::Option<ReadConsistency>,
-    /// If set, overrides global timeout setting for this request. Unit is seconds.
-    #[prost(uint64, optional, tag = "4")]
-    #[validate(custom = "crate::grpc::validate::validate_u64_range_min_1")]
-    pub timeout: ::core::option::Option<u64>,
+pub struct SearchResponse {
+    #[prost(message, repeated, tag = "1")]
+    pub result: ::prost::alloc::vec::Vec<ScoredPoint>,
+    /// Time spent to process
+    #[prost(double, tag = "2")]
+    pub time: f64,
+    /// Shards, which failed to respond, if partial results are requested. Points stored in them are missing from the result
+    #[prost(message, repeated, tag = "3")]
+    pub failed_shards: ::prost::alloc::vec::Vec<ShardFailure>,
 }
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
-pub struct WithLookup {
-    /// Name of the collection to use for points lookup
-    #[prost(string, tag = "1")]
-    pub collection: ::prost::alloc::string::String,
-    /// Options for specifying which payload to include (or not)
-    #[prost(message, optional, tag = "2")]
-    pub with_payload: ::core::option::Option<WithPayloadSelector>,
-    /// Options for specifying which vectors to include (or not)
-    #[prost(message, optional, tag = "3")]
-    pub with_vectors: ::core::option::Option<WithVectorsSelector>,
+pub struct BatchResult {
+    #[prost(message, repeated, tag = "1")]
+    pub result: ::prost::alloc::vec::Vec<ScoredPoint>,
 }
-#[derive(validator::Validate)]
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
-pub struct SearchPointGroups {
-    /// Name of the collection
-    #[prost(string, tag = "1")]
-    #[validate(length(min = 1, max = 255))]
-    pub collection_name: ::prost::alloc::string::String,
-    /// Vector to compare against
-    #[prost(float, repeated, tag = "2")]
-    pub vector: ::prost::alloc::vec::Vec<f32>,
-    /// Filter conditions - return only those points that satisfy the specified conditions
-    #[prost(message, optional, tag = "3")]
-    #[validate]
-    pub filter: ::core::option::Option<Filter>,
-    /// Max number of result
-    #[prost(uint32, tag = "4")]
-    #[validate(range(min = 1))]
-    pub limit: u32,
-    /// Options for specifying which payload to include or not
-    #[prost(message, optional, tag = "5")]
-    pub with_payload: ::core::option::Option<WithPayloadSelector>,
-    /// Search config
-    #[prost(message, optional, tag = "6")]
-    #[validate]
-    pub params: ::core::option::Option<SearchParams>,
-    /// If provided - cut off results with worse scores
-    #[prost(float, optional, tag = "7")]
-    pub score_threshold: ::core::option::Option<f32>,
-    /// Which vector to use for search, if not specified - use default vector
-    #[prost(string, optional, tag = "8")]
-    pub vector_name: ::core::option::Option<::prost::alloc::string::String>,
-    /// Options for specifying which vectors to include into response
-    #[prost(message, optional, tag = "9")]
-    pub with_vectors: ::core::option::Option<WithVectorsSelector>,
-    /// Payload field to group by, must be a string or number field. If there are multiple values for the field, all of them will be used. One point can be in multiple groups.
-    #[prost(string, tag = "10")]
-    #[validate(length(min = 1))]
-    pub group_by: ::prost::alloc::string::String,
-    /// Maximum amount of points to return per group
-    #[prost(uint32, tag = "11")]
-    #[validate(range(min = 1))]
-    pub group_size: u32,
-    /// Options for specifying read consistency guarantees
-    #[prost(message, optional, tag = "12")]
-    pub read_consistency: ::core::option::Option<ReadConsistency>,
-    /// Options for specifying how to use the group id to lookup points in another collection
-    #[prost(message, optional, tag = "13")]
-    pub with_lookup: ::core::option::Option<WithLookup>,
-    /// If set, overrides global timeout setting for this request. Unit is seconds.
-    #[prost(uint64, optional, tag = "14")]
-    #[validate(custom = "crate::grpc::validate::validate_u64_range_min_1")]
-    pub timeout: ::core::option::Option<u64>,
-    /// Specify in which shards to look for the points, if not specified - look in all shards
-    #[prost(message, optional, tag = "15")]
-    pub shard_key_selector: ::core::option::Option<ShardKeySelector>,
-    #[prost(message, optional, tag = "16")]
-    pub sparse_indices: ::core::option::Option<SparseIndices>,
+pub struct SearchBatchResponse {
+    #[prost(message, repeated, tag = "1")]
+    pub result: ::prost::alloc::vec::Vec<BatchResult>,
+    /// Time spent to process
+    #[prost(double, tag = "2")]
+    pub time: f64,
+    /// Shards, which failed to respond, if partial results are requested. Points stored in them are missing from the results
+    #[prost(message, repeated, tag = "3")]
+    pub failed_shards: ::prost::alloc::vec::Vec<ShardFailure>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SearchPageResponse {
+    #[prost(message, repeated, tag = "1")]
+    pub result: ::prost::alloc::vec::Vec<ScoredPoint>,
+    /// Cursor to retrieve the next page. If not specified - there are no more results
+    #[prost(string, optional, tag = "2")]
+    pub next_page_cursor: ::core::option::Option<::prost::alloc::string::String>,
+    /// Time spent to process
+    #[prost(double, tag = "3")]
+    pub time: f64,
 }
-#[derive(validator::Validate)]
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
-pub struct ScrollPoints {
-    #[prost(string, tag = "1")]
-    #[validate(length(min = 1, max = 255))]
-    pub collection_name: ::prost::alloc::string::String,
-    /// Filter conditions - return only those points that satisfy the specified conditions
-    #[prost(message, optional, tag = "2")]
-    #[validate]
-    pub filter: ::core::option::Option<Filter>,
-    /// Start with this ID
-    #[prost(message, optional, tag = "3")]
-    pub offset: ::core::option::Option<PointId>,
-    /// Max number of result
-    #[prost(uint32, optional, tag = "4")]
-    #[validate(custom = "crate::grpc::validate::validate_u32_range_min_1")]
-    pub limit: ::core::option::Option<u32>,
-    /// Options for specifying which payload to include or not
-    #[prost(message, optional, tag = "6")]
-    pub with_payload: ::core::option::Option<WithPayloadSelector>,
-    /// Options for specifying which vectors to include into response
-    #[prost(message, optional, tag = "7")]
-    pub with_vectors: ::core::option::Option<WithVectorsSelector>,
-    /// Options for specifying read consistency guarantees
-    #[prost(message, optional, tag = "8")]
-    pub read_consistency: ::core::option::Option<ReadConsistency>,
-    /// Specify in which shards to look for the points, if not specified - look in all shards
-    #[prost(message, optional, tag = "9")]
-    pub shard_key_selector: ::core::option::Option<ShardKeySelector>,
+pub struct SearchGroupsResponse {
+    #[prost(message, optional, tag = "1")]
+    pub result: ::core::option::Option<GroupsResult>,
+    /// Time spent to process
+    #[prost(double, tag = "2")]
+    pub time: f64,
 }
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
-pub struct LookupLocation {
-    #[prost(string, tag = "1")]
-    pub collection_name: ::prost::alloc::string::String,
-    /// Which vector to use for search, if not specified - use default vector
-    #[prost(string, optional, tag = "2")]
-    pub vector_name: ::core::option::Option<::prost::alloc::string::String>,
-    /// Specify in which shards to look for the points, if not specified - look in all shards
-    #[prost(message, optional, tag = "3")]
-    pub shard_key_selector: ::core::option::Option<ShardKeySelector>,
+pub struct CountResponse {
+    #[prost(message, optional, tag = "1")]
+    pub result: ::core::option::Option<CountResult>,
+    /// Time spent to process
+    #[prost(double, tag = "2")]
+    pub time: f64,
 }
-#[derive(validator::Validate)]
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
-pub struct RecommendPoints {
-    /// name of the collection
-    #[prost(string, tag = "1")]
-    #[validate(length(min = 1, max = 255))]
-    pub collection_name: ::prost::alloc::string::String,
-    /// Look for vectors closest to the vectors from these points
+pub struct ScrollResponse {
+    /// Use this offset for the next query
+    #[prost(message, optional, tag = "1")]
+    pub next_page_offset: ::core::option::Option<PointId>,
     #[prost(message, repeated, tag = "2")]
-    pub positive: ::prost::alloc::vec::Vec<PointId>,
-    /// Try to avoid vectors like the vector from these points
-    #[prost(message, repeated, tag = "3")]
-    pub negative: ::prost::alloc::vec::Vec<PointId>,
-    /// Filter conditions - return only those points that satisfy the specified conditions
-    #[prost(message, optional, tag = "4")]
-    #[validate]
-    pub filter: ::core::option::Option<Filter>,
-    /// Max number of result
-    #[prost(uint64, tag = "5")]
-    pub limit: u64,
-    /// Options for specifying which payload to include or not
-    #[prost(message, optional, tag = "7")]
-    pub with_payload: ::core::option::Option<WithPayloadSelector>,
-    /// Search config
-    #[prost(message, optional, tag = "8")]
-    #[validate]
-    pub params: ::core::option::Option<SearchParams>,
-    /// If provided - cut off results with worse scores
-    #[prost(float, optional, tag = "9")]
-    pub score_threshold: ::core::option::Option<f32>,
-    /// Offset of the result
-    #[prost(uint64, optional, tag = "10")]
-    pub offset: ::core::option::Option<u64>,
-    /// Define which vector to use for recommendation, if not specified - default vector
-    #[prost(string, optional, tag = "11")]
-    pub using: ::core::option::Option<::prost::alloc::string::String>,
-    /// Options for specifying which vectors to include into response
-    #[prost(message, optional, tag = "12")]
-    pub with_vectors: ::core::option::Option<WithVectorsSelector>,
-    /// Name of the collection to use for points lookup, if not specified - use current collection
-    #[prost(message, optional, tag = "13")]
-    pub lookup_from: ::core::option::Option<LookupLocation>,
-    /// Options for specifying read consistency guarantees
-    #[prost(message, optional, tag = "14")]
-    pub read_consistency: ::core::option::Option<ReadConsistency>,
-    /// How to use the example vectors to find the results
-    #[prost(enumeration = "RecommendStrategy", optional, tag = "16")]
-    pub strategy: ::core::option::Option<i32>,
-    /// Look for vectors closest to those
-    #[prost(message, repeated, tag = "17")]
-    #[validate]
-    pub positive_vectors: ::prost::alloc::vec::Vec<Vector>,
-    /// Try to avoid vectors like this
-    #[prost(message, repeated, tag = "18")]
-    #[validate]
-    pub negative_vectors: ::prost::alloc::vec::Vec<Vector>,
-    /// If set, overrides global timeout setting for this request. Unit is seconds.
-    #[prost(uint64, optional, tag = "19")]
-    #[validate(custom = "crate::grpc::validate::validate_u64_range_min_1")]
-    pub timeout: ::core::option::Option<u64>,
-    /// Specify in which shards to look for the points, if not specified - look in all shards
-    #[prost(message, optional, tag = "20")]
-    pub shard_key_selector: ::core::option::Option<ShardKeySelector>,
+    pub result: ::prost::alloc::vec::Vec<RetrievedPoint>,
+    /// Time spent to process
+    #[prost(double, tag = "3")]
+    pub time: f64,
+    /// Scroll session for the next query, it routes the next pages to the replicas, which keep the session
+    #[prost(string, optional, tag = "4")]
+    pub session_id: ::core::option::Option<::prost::alloc::string::String>,
 }
-#[derive(validator::Validate)]
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
-pub struct RecommendBatchPoints {
-    /// Name of the collection
-    #[prost(string, tag = "1")]
-    #[validate(length(min = 1, max = 255))]
-    pub collection_name: ::prost::alloc::string::String,
-    #[prost(message, repeated, tag = "2")]
-    #[validate]
-    pub recommend_points: ::prost::alloc::vec::Vec<RecommendPoints>,
-    /// Options for specifying read consistency guarantees
-    #[prost(message, optional, tag = "3")]
-    pub read_consistency: ::core::option::Option<ReadConsistency>,
-    /// If set, overrides global timeout setting for this request. Unit is seconds.
-    #[prost(uint64, optional, tag = "4")]
-    #[validate(custom = "crate::grpc::validate::validate_u64_range_min_1")]
-    pub timeout: ::core::option::Option<u64>,
+pub struct CountResult {
+    #[prost(uint64, tag = "1")]
+    pub count: u64,
 }
-#[derive(validator::Validate)]
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
-pub struct RecommendPointGroups {
-    /// Name of the collection
-    #[prost(string, tag = "1")]
-    #[validate(length(min = 1, max = 255))]
-    pub collection_name: ::prost::alloc::string::String,
-    /// Look for vectors closest to the vectors from these points
-    #[prost(message, repeated, tag = "2")]
-    pub positive: ::prost::alloc::vec::Vec<PointId>,
-    /// Try to avoid vectors like the vector from these points
-    #[prost(message, repeated, tag = "3")]
-    pub negative: ::prost::alloc::vec::Vec<PointId>,
-    /// Filter conditions - return only those points that satisfy the specified conditions
+pub struct RetrievedPoint {
+    #[prost(message, optional, tag = "1")]
+    pub id: ::core::option::Option<PointId>,
+    #[prost(map = "string, message", tag = "2")]
+    pub payload: ::std::collections::HashMap<::prost::alloc::string::String, Value>,
     #[prost(message, optional, tag = "4")]
-    #[validate]
-    pub filter: ::core::option::Option<Filter>,
-    /// Max number of groups in result
-    #[prost(uint32, tag = "5")]
-    #[validate(range(min = 1))]
-    pub limit: u32,
-    /// Options for specifying which payload to include or not
-    #[prost(message, optional, tag = "6")]
-    pub with_payload: ::core::option::Option<WithPayloadSelector>,
-    /// Search config
-    #[prost(message, optional, tag = "7")]
-    #[validate]
-    pub params: ::core::option::Option<SearchParams>,
-    /// If provided - cut off results with worse scores
-    #[prost(float, optional, tag = "8")]
-    pub score_threshold: ::core::option::Option<f32>,
-    /// Define which vector to use for recommendation, if not specified - default vector
-    #[prost(string, optional, tag = "9")]
-    pub using: ::core::option::Option<::prost::alloc::string::String>,
-    /// Options for specifying which vectors to include into response
-    #[prost(message, optional, tag = "10")]
-    pub with_vectors: ::core::option::Option<WithVectorsSelector>,
-    /// Name of the collection to use for points lookup, if not specified - use current collection
-    #[prost(message, optional, tag = "11")]
-    pub lookup_from: ::core::option::Option<LookupLocation>,
-    /// Payload field to group by, must be a string or number field. If there are multiple values for the field, all of them will be used. One point can be in multiple groups.
-    #[prost(string, tag = "12")]
-    #[validate(length(min = 1))]
-    pub group_by: ::prost::alloc::string::String,
-    /// Maximum amount of points to return per group
-    #[prost(uint32, tag = "13")]
-    #[validate(range(min = 1))]
-    pub group_size: u32,
-    /// Options for specifying read consistency guarantees
-    #[prost(message, optional, tag = "14")]
-    pub read_consistency: ::core::option::Option<ReadConsistency>,
-    /// Options for specifying how to use the group id to lookup points in another collection
-    #[prost(message, optional, tag = "15")]
-    pub with_lookup: ::core::option::Option<WithLookup>,
-    /// How to use the example vectors to find the results
-    #[prost(enumeration = "RecommendStrategy", optional, tag = "17")]
-    pub strategy: ::core::option::Option<i32>,
-    /// Look for vectors closest to those
-    #[prost(message, repeated, tag = "18")]
-    #[validate]
-    pub positive_vectors: ::prost::alloc::vec::Vec<Vector>,
-    /// Try to avoid vectors like this
-    #[prost(message, repeated, tag = "19")]
-    #[validate]
-    pub negative_vectors: ::prost::alloc::vec::Vec<Vector>,
-    /// If set, overrides global timeout setting for this request. Unit is seconds.
-    #[prost(uint64, optional, tag = "20")]
-    #[validate(custom = "crate::grpc::validate::validate_u64_range_min_1")]
-    pub timeout: ::core::option::Option<u64>,
-    /// Specify in which shards to look for the points, if not specified - look in all shards
-    #[prost(message, optional, tag = "21")]
-    pub shard_key_selector: ::core::option::Option<ShardKeySelector>,
+    pub vectors: ::core::option::Option<Vectors>,
+    /// Shard key
+    #[prost(message, optional, tag = "5")]
+    pub shard_key: ::core::option::Option<ShardKey>,
+    /// Point version, the sequence number of the last update of the point. Used as `if_version` of conditional updates
+    #[prost(uint64, optional, tag = "6")]
+    pub version: ::core::option::Option<u64>,
+    /// Value of the `order_by` field, the point is ordered by
+    #[prost(double, optional, tag = "7")]
+    pub order_value: ::core::option::Option<f64>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetResponse {
+    #[prost(message, repeated, tag = "1")]
+    pub result: ::prost::alloc::vec::Vec<RetrievedPoint>,
+    /// Time spent to process
+    #[prost(double, tag = "2")]
+    pub time: f64,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RecommendResponse {
+    #[prost(message, repeated, tag = "1")]
+    pub result: ::prost::alloc::vec::Vec<ScoredPoint>,
+    /// Time spent to process
+    #[prost(double, tag = "2")]
+    pub time: f64,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RecommendBatchResponse {
+    #[prost(message, repeated, tag = "1")]
+    pub result: ::prost::alloc::vec::Vec<BatchResult>,
+    /// Time spent to process
+    #[prost(double, tag = "2")]
+    pub time: f64,
 }
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
-pub struct TargetVector {
-    #[prost(oneof = "target_vector::Target", tags = "1")]
-    pub target: ::core::option::Option<target_vector::Target>,
-}
-/// Nested message and enum types in `TargetVector`.
-pub mod target_vector {
-    #[derive(serde::Serialize)]
-    #[allow(clippy::derive_partial_eq_without_eq)]
-    #[derive(Clone, PartialEq, ::prost::Oneof)]
-    pub enum Target {
-        #[prost(message, tag = "1")]
-        Single(super::VectorExample),
-    }
+pub struct DiscoverResponse {
+    #[prost(message, repeated, tag = "1")]
+    pub result: ::prost::alloc::vec::Vec<ScoredPoint>,
+    /// Time spent to process
+    #[prost(double, tag = "2")]
+    pub time: f64,
 }
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
-pub struct VectorExample {
-    #[prost(oneof = "vector_example::Example", tags = "1, 2")]
-    pub example: ::core::option::Option<vector_example::Example>,
-}
-/// Nested message and enum types in `VectorExample`.
-pub mod vector_example {
-    #[derive(serde::Serialize)]
-    #[allow(clippy::derive_partial_eq_without_eq)]
-    #[derive(Clone, PartialEq, ::prost::Oneof)]
-    pub enum Example {
-        #[prost(message, tag = "1")]
-        Id(super::PointId),
-        #[prost(message, tag = "2")]
-        Vector(super::Vector),
-    }
+pub struct DiscoverBatchResponse {
+    #[prost(message, repeated, tag = "1")]
+    pub result: ::prost::alloc::vec::Vec<BatchResult>,
+    /// Time spent to process
+    #[prost(double, tag = "2")]
+    pub time: f64,
 }
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
-pub struct ContextExamplePair {
+pub struct RecommendGroupsResponse {
     #[prost(message, optional, tag = "1")]
-    pub positive: ::core::option::Option<VectorExample>,
-    #[prost(message, optional, tag = "2")]
-    pub negative: ::core::option::Option<VectorExample>,
+    pub result: ::core::option::Option<GroupsResult>,
+    /// Time spent to process
+    #[prost(double, tag = "2")]
+    pub time: f64,
 }
-#[derive(validator::Validate)]
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
-pub struct DiscoverPoints {
-    /// name of the collection
-    #[prost(string, tag = "1")]
-    #[validate(length(min = 1, max = 255))]
-    pub collection_name: ::prost::alloc::string::String,
-    /// Use this as the primary search objective
-    #[prost(message, optional, tag = "2")]
-    pub target: ::core::option::Option<TargetVector>,
-    /// Search will be constrained by these pairs of examples
-    #[prost(message, repeated, tag = "3")]
-    pub context: ::prost::alloc::vec::Vec<ContextExamplePair>,
-    /// Filter conditions - return only those points that satisfy the specified conditions
-    #[prost(message, optional, tag = "4")]
-    #[validate]
-    pub filter: ::core::option::Option<Filter>,
-    /// Max number of result
-    #[prost(uint64, tag = "5")]
-    #[validate(range(min = 1))]
-    pub limit: u64,
-    /// Options for specifying which payload to include or not
-    #[prost(message, optional, tag = "6")]
-    pub with_payload: ::core::option::Option<WithPayloadSelector>,
-    /// Search config
-    #[prost(message, optional, tag = "7")]
-    #[validate]
-    pub params: ::core::option::Option<SearchParams>,
-    /// Offset of the result
-    #[prost(uint64, optional, tag = "8")]
-    pub offset: ::core::option::Option<u64>,
-    /// Define which vector to use for recommendation, if not specified - default vector
-    #[prost(string, optional, tag = "9")]
-    pub using: ::core::option::Option<::prost::alloc::string::String>,
-    /// Options for specifying which vectors to include into response
-    #[prost(message, optional, tag = "10")]
-    pub with_vectors: ::core::option::Option<WithVectorsSelector>,
-    /// Name of the collection to use for points lookup, if not specified - use current collection
-    #[prost(message, optional, tag = "11")]
-    pub lookup_from: ::core::option::Option<LookupLocation>,
-    /// Options for specifying read consistency guarantees
-    #[prost(message, optional, tag = "12")]
-    pub read_consistency: ::core::option::Option<ReadConsistency>,
-    /// If set, overrides global timeout setting for this request. Unit is seconds.
-    #[prost(uint64, optional, tag = "13")]
-    #[validate(custom = "crate::grpc::validate::validate_u64_range_min_1")]
-    pub timeout: ::core::option::Option<u64>,
-    /// Specify in which shards to look for the points, if not specified - look in all shards
-    #[prost(message, optional, tag = "14")]
-    pub shard_key_selector: ::core::option::Option<ShardKeySelector>,
+pub struct UpdateBatchResponse {
+    #[prost(message, repeated, tag = "1")]
+    pub result: ::prost::alloc::vec::Vec<UpdateResult>,
+    /// Time spent to process
+    #[prost(double, tag = "2")]
+    pub time: f64,
 }
 #[derive(validator::Validate)]
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
-pub struct DiscoverBatchPoints {
-    /// Name of the collection
-    #[prost(string, tag = "1")]
-    #[validate(length(min = 1, max = 255))]
-    pub collection_name: ::prost::alloc::string::String,
+pub struct Filter {
+    /// At least one of those conditions should match
+    #[prost(message, repeated, tag = "1")]
+    #[validate]
+    pub should: ::prost::alloc::vec::Vec<Condition>,
+    /// All conditions must match
     #[prost(message, repeated, tag = "2")]
     #[validate]
-    pub discover_points: ::prost::alloc::vec::Vec<DiscoverPoints>,
-    /// Options for specifying read consistency guarantees
-    #[prost(message, optional, tag = "3")]
-    pub read_consistency: ::core::option::Option<ReadConsistency>,
-    /// If set, overrides global timeout setting for this request. Unit is seconds.
-    #[prost(uint64, optional, tag = "4")]
-    #[validate(custom = "crate::grpc::validate::validate_u64_range_min_1")]
-    pub timeout: ::core::option::Option<u64>,
+    pub must: ::prost::alloc::vec::Vec<Condition>,
+    /// All conditions must NOT match
+    #[prost(message, repeated, tag = "3")]
+    #[validate]
+    pub must_not: ::prost::alloc::vec::Vec<Condition>,
 }
 #[derive(validator::Validate)]
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
-pub struct CountPoints {
-    /// name of the collection
-    #[prost(string, tag = "1")]
-    #[validate(length(min = 1, max = 255))]
-    pub collection_name: ::prost::alloc::string::String,
-    /// Filter conditions - return only those points that satisfy the specified conditions
-    #[prost(message, optional, tag = "2")]
+pub struct Condition {
+    #[prost(oneof = "condition::ConditionOneOf", tags = "1, 2, 3, 4, 5, 6")]
     #[validate]
-    pub filter: ::core::option::Option<Filter>,
-    /// If `true` - return exact count, if `false` - return approximate count
-    #[prost(bool, optional, tag = "3")]
-    pub exact: ::core::option::Option<bool>,
-    /// Options for specifying read consistency guarantees
-    #[prost(message, optional, tag = "4")]
-    pub read_consistency: ::core::option::Option<ReadConsistency>,
-    /// Specify in which shards to look for the points, if not specified - look in all shards
-    #[prost(message, optional, tag = "5")]
-    pub shard_key_selector: ::core::option::Option<ShardKeySelector>,
-}
-#[derive(serde::Serialize)]
-#[allow(clippy::derive_partial_eq_without_eq)]
-#[derive(Clone, PartialEq, ::prost::Message)]
-pub struct PointsUpdateOperation {
-    #[prost(
-        oneof = "points_update_operation::Operation",
-        tags = "1, 2, 3, 4, 5, 6, 7, 8, 9, 10"
-    )]
-    pub operation: ::core::option::Option<points_update_operation::Operation>,
+    pub condition_one_of: ::core::option::Option<condition::ConditionOneOf>,
 }
-/// Nested message and enum types in `PointsUpdateOperation`.
-pub mod points_update_operation {
-    #[derive(serde::Serialize)]
-    #[allow(clippy::derive_partial_eq_without_eq)]
-    #[derive(Clone, PartialEq, ::prost::Message)]
-    pub struct PointStructList {
-        #[prost(message, repeated, tag = "1")]
-        pub points: ::prost::alloc::vec::Vec<super::PointStruct>,
-        /// Option for custom sharding to specify used shard keys
-        #[prost(message, optional, tag = "2")]
-        pub shard_key_selector: ::core::option::Option<super::ShardKeySelector>,
-    }
-    #[derive(serde::Serialize)]
-    #[allow(clippy::derive_partial_eq_without_eq)]
-    #[derive(Clone, PartialEq, ::prost::Message)]
-    pub struct SetPayload {
-        #[prost(map = "string, message", tag = "1")]
-        pub payload: ::std::collections::HashMap<
-            ::prost::alloc::string::String,
-            super::Value,
-        >,
-        /// Affected points
-        #[prost(message, optional, tag = "2")]
-        pub points_selector: ::core::option::Option<super::PointsSelector>,
-        /// Option for custom sharding to specify used shard keys
-        #[prost(message, optional, tag = "3")]
-        pub shard_key_selector: ::core::option::Option<super::ShardKeySelector>,
-    }
-    #[derive(serde::Serialize)]
-    #[allow(clippy::derive_partial_eq_without_eq)]
-    #[derive(Clone, PartialEq, ::prost::Message)]
-    pub struct DeletePayload {
-        #[prost(string, repeated, tag = "1")]
-        pub keys: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
-        /// Affected points
-        #[prost(message, optional, tag = "2")]
-        pub points_selector: ::core::option::Option<super::PointsSelector>,
-        /// Option for custom sharding to specify used shard keys
-        #[prost(message, optional, tag = "3")]
-        pub shard_key_selector: ::core::option::Option<super::ShardKeySelector>,
-    }
-    #[derive(serde::Serialize)]
-    #[allow(clippy::derive_partial_eq_without_eq)]
-    #[derive(Clone, PartialEq, ::prost::Message)]
-    pub struct UpdateVectors {
-        /// List of points and vectors to update
-        #[prost(message, repeated, tag = "1")]
-        pub points: ::prost::alloc::vec::Vec<super::PointVectors>,
-        /// Option for custom sharding to specify used shard keys
-        #[prost(message, optional, tag = "2")]
-        pub shard_key_selector: ::core::option::Option<super::ShardKeySelector>,
-    }
-    #[derive(serde::Serialize)]
-    #[allow(clippy::derive_partial_eq_without_eq)]
-    #[derive(Clone, PartialEq, ::prost::Message)]
-    pub struct DeleteVectors {
-        /// Affected points
-        #[prost(message, optional, tag = "1")]
-        pub points_selector: ::core::option::Option<super::PointsSelector>,
-        /// List of vector names to delete
-        #[prost(message, optional, tag = "2")]
-        pub vectors: ::core::option::Option<super::VectorsSelector>,
-        /// Option for custom sharding to specify used shard keys
-        #[prost(message, optional, tag = "3")]
-        pub shard_key_selector: ::core::option::Option<super::ShardKeySelector>,
-    }
-    #[derive(validator::Validate)]
-    #[derive(serde::Serialize)]
-    #[allow(clippy::derive_partial_eq_without_eq)]
-    #[derive(Clone, PartialEq, ::prost::Message)]
-    pub struct DeletePoints {
-        /// Affected points
-        #[prost(message, optional, tag = "1")]
-        pub points: ::core::option::Option<super::PointsSelector>,
-        /// Option for custom sharding to specify used shard keys
-        #[prost(message, optional, tag = "2")]
-        pub shard_key_selector: ::core::option::Option<super::ShardKeySelector>,
-    }
-    #[derive(serde::Serialize)]
-    #[allow(clippy::derive_partial_eq_without_eq)]
-    #[derive(Clone, PartialEq, ::prost::Message)]
-    pub struct ClearPayload {
-        /// Affected points
-        #[prost(message, optional, tag = "1")]
-        pub points: ::core::option::Option<super::PointsSelector>,
-        /// Option for custom sharding to specify used shard keys
-        #[prost(message, optional, tag = "2")]
-        pub shard_key_selector: ::core::option::Option<super::ShardKeySelector>,
-    }
+/// Nested message and enum types in `Condition`.
+pub mod condition {
     #[derive(serde::Serialize)]
     #[allow(clippy::derive_partial_eq_without_eq)]
     #[derive(Clone, PartialEq, ::prost::Oneof)]
-    pub enum Operation {
+    pub enum ConditionOneOf {
         #[prost(message, tag = "1")]
-        Upsert(PointStructList),
+        Field(super::FieldCondition),
         #[prost(message, tag = "2")]
-        DeleteDeprecated(super::PointsSelector),
+        IsEmpty(super::IsEmptyCondition),
         #[prost(message, tag = "3")]
-        SetPayload(SetPayload),
+        HasId(super::HasIdCondition),
         #[prost(message, tag = "4")]
-        OverwritePayload(SetPayload),
+        Filter(super::Filter),
         #[prost(message, tag = "5")]
-        DeletePayload(DeletePayload),
+        IsNull(super::IsNullCondition),
         #[prost(message, tag = "6")]
-        ClearPayloadDeprecated(super::PointsSelector),
-        #[prost(message, tag = "7")]
-        UpdateVectors(UpdateVectors),
-        #[prost(message, tag = "8")]
-        DeleteVectors(DeleteVectors),
-        #[prost(message, tag = "9")]
-        DeletePoints(DeletePoints),
-        #[prost(message, tag = "10")]
-        ClearPayload(ClearPayload),
+        Nested(super::NestedCondition),
     }
 }
-#[derive(validator::Validate)]
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
-pub struct UpdateBatchPoints {
-    /// name of the collection
+pub struct IsEmptyCondition {
     #[prost(string, tag = "1")]
-    #[validate(length(min = 1, max = 255))]
-    pub collection_name: ::prost::alloc::string::String,
-    /// Wait until the changes have been applied?
-    #[prost(bool, optional, tag = "2")]
-    pub wait: ::core::option::Option<bool>,
-    #[prost(message, repeated, tag = "3")]
-    #[validate(length(min = 1))]
-    pub operations: ::prost::alloc::vec::Vec<PointsUpdateOperation>,
-    /// Write ordering guarantees
-    #[prost(message, optional, tag = "4")]
-    pub ordering: ::core::option::Option<WriteOrdering>,
+    pub key: ::prost::alloc::string::String,
 }
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
-pub struct PointsOperationResponse {
-    #[prost(message, optional, tag = "1")]
-    pub result: ::core::option::Option<UpdateResult>,
-    /// Time spent to process
-    #[prost(double, tag = "2")]
-    pub time: f64,
+pub struct IsNullCondition {
+    #[prost(string, tag = "1")]
+    pub key: ::prost::alloc::string::String,
 }
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
-pub struct UpdateResult {
-    /// Number of operation
-    #[prost(uint64, optional, tag = "1")]
-    pub operation_id: ::core::option::Option<u64>,
-    /// Operation status
-    #[prost(enumeration = "UpdateStatus", tag = "2")]
-    pub status: i32,
+pub struct HasIdCondition {
+    #[prost(message, repeated, tag = "1")]
+    pub has_id: ::prost::alloc::vec::Vec<PointId>,
 }
+#[derive(validator::Validate)]
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
-pub struct ScoredPoint {
-    /// Point id
-    #[prost(message, optional, tag = "1")]
-    pub id: ::core::option::Option<PointId>,
-    /// Payload
-    #[prost(map = "string, message", tag = "2")]
-    pub payload: ::std::collections::HashMap<::prost::alloc::string::String, Value>,
-    /// Similarity score
-    #[prost(float, tag = "3")]
-    pub score: f32,
-    /// Last update operation applied to this point
-    #[prost(uint64, tag = "5")]
-    pub version: u64,
-    /// Vectors to search
+pub struct NestedCondition {
+    /// Path to nested object
+    #[prost(string, tag = "1")]
+    pub key: ::prost::alloc::string::String,
+    /// Filter condition
+    #[prost(message, optional, tag = "2")]
+    #[validate]
+    pub filter: ::core::option::Option<Filter>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct FieldCondition {
+    #[prost(string, tag = "1")]
+    pub key: ::prost::alloc::string::String,
+    /// Check if point has field with a given value
+    #[prost(message, optional, tag = "2")]
+    pub r#match: ::core::option::Option<Match>,
+    /// Check if points value lies in a given range
+    #[prost(message, optional, tag = "3")]
+    pub range: ::core::option::Option<Range>,
+    /// Check if points geolocation lies in a given area
+    #[prost(message, optional, tag = "4")]
+    pub geo_bounding_box: ::core::option::Option<GeoBoundingBox>,
+    /// Check if geo point is within a given radius
+    #[prost(message, optional, tag = "5")]
+    pub geo_radius: ::core::option::Option<GeoRadius>,
+    /// Check number of values for a specific field
     #[prost(message, optional, tag = "6")]
-    pub vectors: ::core::option::Option<Vectors>,
-    /// Shard key
+    pub values_count: ::core::option::Option<ValuesCount>,
+    /// Check if geo point is within a given polygon
     #[prost(message, optional, tag = "7")]
-    pub shard_key: ::core::option::Option<ShardKey>,
+    pub geo_polygon: ::core::option::Option<GeoPolygon>,
 }
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
-pub struct GroupId {
-    #[prost(oneof = "group_id::Kind", tags = "1, 2, 3")]
-    pub kind: ::core::option::Option<group_id::Kind>,
+pub struct Match {
+    #[prost(oneof = "r#match::MatchValue", tags = "1, 2, 3, 4, 5, 6, 7, 8")]
+    pub match_value: ::core::option::Option<r#match::MatchValue>,
 }
-/// Nested message and enum types in `GroupId`.
-pub mod group_id {
+/// Nested message and enum types in `Match`.
+pub mod r#match {
     #[derive(serde::Serialize)]
     #[allow(clippy::derive_partial_eq_without_eq)]
     #[derive(Clone, PartialEq, ::prost::Oneof)]
-    pub enum Kind {
-        /// Represents a double value.
-        #[prost(uint64, tag = "1")]
-        UnsignedValue(u64),
-        /// Represents an integer value
+    pub enum MatchValue {
+        /// Match string keyword
+        #[prost(string, tag = "1")]
+        Keyword(::prost::alloc::string::String),
+        /// Match integer
         #[prost(int64, tag = "2")]
-        IntegerValue(i64),
-        /// Represents a string value.
-        #[prost(string, tag = "3")]
-        StringValue(::prost::alloc::string::String),
+        Integer(i64),
+        /// Match boolean
+        #[prost(bool, tag = "3")]
+        Boolean(bool),
+        /// Match text
+        #[prost(string, tag = "4")]
+        Text(::prost::alloc::string::String),
+        /// Match multiple keywords
+        #[prost(message, tag = "5")]
+        Keywords(super::RepeatedStrings),
+        /// Match multiple integers
+        #[prost(message, tag = "6")]
+        Integers(super::RepeatedIntegers),
+        /// Match any other value except those integers
+        #[prost(message, tag = "7")]
+        ExceptIntegers(super::RepeatedIntegers),
+        /// Match any other value except those keywords
+        #[prost(message, tag = "8")]
+        ExceptKeywords(super::RepeatedStrings),
     }
 }
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
-pub struct PointGroup {
-    /// Group id
-    #[prost(message, optional, tag = "1")]
-    pub id: ::core::option::Option<GroupId>,
-    /// Points in the group
-    #[prost(message, repeated, tag = "2")]
-    pub hits: ::prost::alloc::vec::Vec<ScoredPoint>,
-    /// Point(s) from the lookup collection that matches the group id
-    #[prost(message, optional, tag = "3")]
-    pub lookup: ::core::option::Option<RetrievedPoint>,
+pub struct RepeatedStrings {
+    #[prost(string, repeated, tag = "1")]
+    pub strings: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
 }
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
-pub struct GroupsResult {
-    /// Groups
-    #[prost(message, repeated, tag = "1")]
-    pub groups: ::prost::alloc::vec::Vec<PointGroup>,
+pub struct RepeatedIntegers {
+    #[prost(int64, repeated, tag = "1")]
+    pub integers: ::prost::alloc::vec::Vec<i64>,
 }
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
-pub struct SearchResponse {
-    #[prost(message, repeated, tag = "1")]
-    pub result: ::prost::alloc::vec::Vec<ScoredPoint>,
-    /// Time spent to process
-    #[prost(double, tag = "2")]
-    pub time: f64,
+pub struct Range {
+    #[prost(double, optional, tag = "1")]
+    pub lt: ::core::option::Option<f64>,
+    #[prost(double, optional, tag = "2")]
+    pub gt: ::core::option::Option<f64>,
+    #[prost(double, optional, tag = "3")]
+    pub gte: ::core::option::Option<f64>,
+    #[prost(double, optional, tag = "4")]
+    pub lte: ::core::option::Option<f64>,
 }
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
-pub struct BatchResult {
-    #[prost(message, repeated, tag = "1")]
-    pub result: ::prost::alloc::vec::Vec<ScoredPoint>,
+pub struct GeoBoundingBox {
+    /// north-west corner
+    #[prost(message, optional, tag = "1")]
+    pub top_left: ::core::option::Option<GeoPoint>,
+    /// south-east corner
+    #[prost(message, optional, tag = "2")]
+    pub bottom_right: ::core::option::Option<GeoPoint>,
 }
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
-pub struct SearchBatchResponse {
+pub struct GeoRadius {
+    /// Center of the circle
+    #[prost(message, optional, tag = "1")]
+    pub center: ::core::option::Option<GeoPoint>,
+    /// In meters
+    #[prost(float, tag = "2")]
+    pub radius: f32,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GeoLineString {
+    /// Ordered sequence of GeoPoints representing the line
     #[prost(message, repeated, tag = "1")]
-    pub result: ::prost::alloc::vec::Vec<BatchResult>,
-    /// Time spent to process
-    #[prost(double, tag = "2")]
-    pub time: f64,
+    pub points: ::prost::alloc::vec::Vec<GeoPoint>,
 }
+/// For a valid GeoPolygon, both the exterior and interior GeoLineStrings must consist of a minimum of 4 points.
+/// Additionally, the first and last points of each GeoLineString must be the same.
+#[derive(validator::Validate)]
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
-pub struct SearchGroupsResponse {
+pub struct GeoPolygon {
+    /// The exterior line bounds the surface
     #[prost(message, optional, tag = "1")]
-    pub result: ::core::option::Option<GroupsResult>,
-    /// Time spent to process
-    #[prost(double, tag = "2")]
-    pub time: f64,
+    #[validate(custom = "crate::grpc::validate::validate_geo_polygon_exterior")]
+    pub exterior: ::core::option::Option<GeoLineString>,
+    /// Interior lines (if present) bound holes within the surface
+    #[prost(message, repeated, tag = "2")]
+    #[validate(custom = "crate::grpc::validate::validate_geo_polygon_interiors")]
+    pub interiors: ::prost::alloc::vec::Vec<GeoLineString>,
 }
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
-pub struct CountResponse {
-    #[prost(message, optional, tag = "1")]
-    pub result: ::core::option::Option<CountResult>,
-    /// Time spent to process
-    #[prost(double, tag = "2")]
-    pub time: f64,
+pub struct ValuesCount {
+    #[prost(uint64, optional, tag = "1")]
+    pub lt: ::core::option::Option<u64>,
+    #[prost(uint64, optional, tag = "2")]
+    pub gt: ::core::option::Option<u64>,
+    #[prost(uint64, optional, tag = "3")]
+    pub gte: ::core::option::Option<u64>,
+    #[prost(uint64, optional, tag = "4")]
+    pub lte: ::core::option::Option<u64>,
 }
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
-pub struct ScrollResponse {
-    /// Use this offset for the next query
-    #[prost(message, optional, tag = "1")]
-    pub next_page_offset: ::core::option::Option<PointId>,
-    #[prost(message, repeated, tag = "2")]
-    pub result: ::prost::alloc::vec::Vec<RetrievedPoint>,
-    /// Time spent to process
-    #[prost(double, tag = "3")]
-    pub time: f64,
+pub struct PointsSelector {
+    #[prost(oneof = "points_selector::PointsSelectorOneOf", tags = "1, 2")]
+    pub points_selector_one_of: ::core::option::Option<
+        points_selector::PointsSelectorOneOf,
+    >,
+}
+/// Nested message and enum types in `PointsSelector`.
+pub mod points_selector {
+    #[derive(serde::Serialize)]
+    #[allow(clippy::derive_partial_eq_without_eq)]
+    #[derive(Clone, PartialEq, ::prost::Oneof)]
+    pub enum PointsSelectorOneOf {
+        #[prost(message, tag = "1")]
+        Points(super::PointsIdsList),
+        #[prost(message, tag = "2")]
+        Filter(super::Filter),
+    }
 }
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
-pub struct CountResult {
-    #[prost(uint64, tag = "1")]
-    pub count: u64,
+pub struct PointsIdsList {
+    #[prost(message, repeated, tag = "1")]
+    pub ids: ::prost::alloc::vec::Vec<PointId>,
 }
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
-pub struct RetrievedPoint {
+pub struct PointStruct {
     #[prost(message, optional, tag = "1")]
     pub id: ::core::option::Option<PointId>,
-    #[prost(map = "string, message", tag = "2")]
+    #[prost(map = "string, message", tag = "3")]
     pub payload: ::std::collections::HashMap<::prost::alloc::string::String, Value>,
     #[prost(message, optional, tag = "4")]
     pub vectors: ::core::option::Option<Vectors>,
-    /// Shard key
-    #[prost(message, optional, tag = "5")]
-    pub shard_key: ::core::option::Option<ShardKey>,
+    /// Only update the point, if its current version is equal to this one. Use 0 to insert the point only if it doesn't exist yet. Otherwise, the whole operation is rejected
+    #[prost(uint64, optional, tag = "5")]
+    pub if_version: ::core::option::Option<u64>,
 }
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
-pub struct GetResponse {
-    #[prost(message, repeated, tag = "1")]
-    pub result: ::prost::alloc::vec::Vec<RetrievedPoint>,
-    /// Time spent to process
+pub struct GeoPoint {
+    #[prost(double, tag = "1")]
+    pub lon: f64,
     #[prost(double, tag = "2")]
-    pub time: f64,
+    pub lat: f64,
 }
 #[derive(serde::Serialize)]
-#[allow(clippy::derive_partial_eq_without_eq)]
-#[derive(Clone, PartialEq, ::prost::Message)]
-pub struct RecommendResponse {
-    #[prost(message, repeated, tag = "1")]
-    pub result: ::prost::alloc::vec::Vec<ScoredPoint>,
-    /// Time spent to process
-    #[prost(double, tag = "2")]
-    pub time: f64,
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum ReadConsistencyType {
+    /// Send request to all nodes and return points which are present on all of them
+    All = 0,
+    /// Send requests to all nodes and return points which are present on majority of them
+    Majority = 1,
+    /// Send requests to half + 1 nodes, return points which are present on all of them
+    Quorum = 2,
+}
+impl ReadConsistencyType {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            ReadConsistencyType::All => "All",
+            ReadConsistencyType::Majority => "Majority",
+            ReadConsistencyType::Quorum => "Quorum",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "All" => Some(Self::All),
+            "Majority" => Some(Self::Majority),
+            "Quorum" => Some(Self::Quorum),
+            _ => None,
+        }
+    }
 }
 #[derive(serde::Serialize)]
-#[allow(clippy::derive_partial_eq_without_eq)]
-#[derive(Clone, PartialEq, ::prost::Message)]
-pub struct RecommendBatchResponse {
-    #[prost(message, repeated, tag = "1")]
-    pub result: ::prost::alloc::vec::Vec<BatchResult>,
-    /// Time spent to process
-    #[prost(double, tag = "2")]
-    pub time: f64,
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum WriteConsistencyType {
+    /// Update must be applied by the majority of replicas
+    MajorityReplicas = 0,
+    /// Update must be applied by all replicas
+    AllReplicas = 1,
+}
+impl WriteConsistencyType {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            WriteConsistencyType::MajorityReplicas => "MajorityReplicas",
+            WriteConsistencyType::AllReplicas => "AllReplicas",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "MajorityReplicas" => Some(Self::MajorityReplicas),
+            "AllReplicas" => Some(Self::AllReplicas),
+            _ => None,
+        }
+    }
 }
 #[derive(serde::Serialize)]
-#[allow(clippy::derive_partial_eq_without_eq)]
-#[derive(Clone, PartialEq, ::prost::Message)]
-pub struct DiscoverResponse {
-    #[prost(message, repeated, tag = "1")]
-    pub result: ::prost::alloc::vec::Vec<ScoredPoint>,
-    /// Time spent to process
-    #[prost(double, tag = "2")]
-    pub time: f64,
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum UpdatePriority {
+    /// Update is applied ahead of the queued bulk updates, default
+    Interactive = 0,
+    /// Update of a backfill or an import, yields the update queue to interactive updates under load
+    Bulk = 1,
+}
+impl UpdatePriority {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            UpdatePriority::Interactive => "Interactive",
+            UpdatePriority::Bulk => "Bulk",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "Interactive" => Some(Self::Interactive),
+            "Bulk" => Some(Self::Bulk),
+            _ => None,
+        }
+    }
 }
 #[derive(serde::Serialize)]
-#[allow(clippy::derive_partial_eq_without_eq)]
-#[derive(Clone, PartialEq, ::prost::Message)]
-pub struct DiscoverBatchResponse {
-    #[prost(message, repeated, tag = "1")]
-    pub result: ::prost::alloc::vec::Vec<BatchResult>,
-    /// Time spent to process
-    #[prost(double, tag = "2")]
-    pub time: f64,
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum FieldType {
+    Keyword = 0,
+    Integer = 1,
+    Float = 2,
+    Geo = 3,
+    Text = 4,
+    Bool = 5,
+}
+impl FieldType {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            FieldType::Keyword => "FieldTypeKeyword",
+            FieldType::Integer => "FieldTypeInteger",
+            FieldType::Float => "FieldTypeFloat",
+            FieldType::Geo => "FieldTypeGeo",
+            FieldType::Text => "FieldTypeText",
+            FieldType::Bool => "FieldTypeBool",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "FieldTypeKeyword" => Some(Self::Keyword),
+            "FieldTypeInteger" => Some(Self::Integer),
+            "FieldTypeFloat" => Some(Self::Float),
+            "FieldTypeGeo" => Some(Self::Geo),
+            "FieldTypeText" => Some(Self::Text),
+            "FieldTypeBool" => Some(Self::Bool),
+            _ => None,
+        }
+    }
 }
 #[derive(serde::Serialize)]
-#[allow(clippy::derive_partial_eq_without_eq)]
-#[derive(Clone, PartialEq, ::prost::Message)]
-pub struct RecommendGroupsResponse {
-    #[prost(message, optional, tag = "1")]
-    pub result: ::core::option::Option<GroupsResult>,
-    /// Time spent to process
-    #[prost(double, tag = "2")]
-    pub time: f64,
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum Direction {
+    Asc = 0,
+    Desc = 1,
+}
+impl Direction {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            Direction::Asc => "Asc",
+            Direction::Desc => "Desc",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "Asc" => Some(Self::Asc),
+            "Desc" => Some(Self::Desc),
+            _ => None,
+        }
+    }
 }
 #[derive(serde::Serialize)]
-#[allow(clippy::derive_partial_eq_without_eq)]
-#[derive(Clone, PartialEq, ::prost::Message)]
-pub struct UpdateBatchResponse {
-    #[prost(message, repeated, tag = "1")]
-    pub result: ::prost::alloc::vec::Vec<UpdateResult>,
-    /// Time spent to process
-    #[prost(double, tag = "2")]
-    pub time: f64,
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum Sample {
+    /// Uniform random sample of the points, in random order
+    Random = 0,
+}
+impl Sample {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            Sample::Random => "Random",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "Random" => Some(Self::Random),
+            _ => None,
+        }
+    }
+}
+/// How to use positive and negative vectors to find the results, default is `AverageVector`:
+#[derive(serde::Serialize)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum RecommendStrategy {
+    /// Average positive and negative vectors and create a single query with the formula
+    /// `query = avg_pos + avg_pos - avg_neg`. Then performs normal search.
+    AverageVector = 0,
+    /// Uses custom search objective. Each candidate is compared against all
+    /// examples, its score is then chosen from the `max(max_pos_score, max_neg_score)`.
+    /// If the `max_neg_score` is chosen then it is squared and negated.
+    BestScore = 1,
+}
+impl RecommendStrategy {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            RecommendStrategy::AverageVector => "AverageVector",
+            RecommendStrategy::BestScore => "BestScore",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "AverageVector" => Some(Self::AverageVector),
+            "BestScore" => Some(Self::BestScore),
+            _ => None,
+        }
+    }
+}
+#[derive(serde::Serialize)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum UpdateStatus {
+    UnknownUpdateStatus = 0,
+    /// Update is received, but not processed yet
+    Acknowledged = 1,
+    /// Update is applied and ready for search
+    Completed = 2,
+}
+impl UpdateStatus {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            UpdateStatus::UnknownUpdateStatus => "UnknownUpdateStatus",
+            UpdateStatus::Acknowledged => "Acknowledged",
+            UpdateStatus::Completed => "Completed",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "UnknownUpdateStatus" => Some(Self::UnknownUpdateStatus),
+            "Acknowledged" => Some(Self::Acknowledged),
+            "Completed" => Some(Self::Completed),
+            _ => None,
+        }
+    }
 }
 #[derive(validator::Validate)]
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
-pub struct Filter {
-    /// At least one of those conditions should match
-    #[prost(message, repeated, tag = "1")]
-    #[validate]
-    pub should: ::prost::alloc::vec::Vec<Condition>,
-    /// All conditions must match
-    #[prost(message, repeated, tag = "2")]
+pub struct CloneCollection {
+    /// Name of the collection to clone
+    #[prost(string, tag = "1")]
+    #[validate(length(min = 1, max = 255))]
+    pub collection_name: ::prost::alloc::string::String,
+    /// Name of the new collection
+    #[prost(string, tag = "2")]
+    #[validate(
+        length(min = 1, max = 255),
+        custom = "common::validation::validate_collection_name"
+    )]
+    pub target: ::prost::alloc::string::String,
+    /// Copy only points, which satisfy this conditions. If not specified - all points are copied
+    #[prost(message, optional, tag = "3")]
+    pub filter: ::core::option::Option<Filter>,
+    /// Custom params for HNSW index of the new collection. If not specified - same as of the source collection
+    #[prost(message, optional, tag = "4")]
     #[validate]
-    pub must: ::prost::alloc::vec::Vec<Condition>,
-    /// All conditions must NOT match
-    #[prost(message, repeated, tag = "3")]
+    pub hnsw_config: ::core::option::Option<HnswConfigDiff>,
+    /// Custom params for Optimizers of the new collection. If not specified - same as of the source collection
+    #[prost(message, optional, tag = "5")]
     #[validate]
-    pub must_not: ::prost::alloc::vec::Vec<Condition>,
-}
-#[derive(validator::Validate)]
-#[derive(serde::Serialize)]
-#[allow(clippy::derive_partial_eq_without_eq)]
-#[derive(Clone, PartialEq, ::prost::Message)]
-pub struct Condition {
-    #[prost(oneof = "condition::ConditionOneOf", tags = "1, 2, 3, 4, 5, 6")]
+    pub optimizers_config: ::core::option::Option<OptimizersConfigDiff>,
+    /// Quantization parameters of the new collection. If not specified - same as of the source collection, if disabled - the new collection is not quantized
+    #[prost(message, optional, tag = "6")]
     #[validate]
-    pub condition_one_of: ::core::option::Option<condition::ConditionOneOf>,
+    pub quantization_config: ::core::option::Option<QuantizationConfigDiff>,
+    /// Wait timeout for operation commit in seconds, if not specified - default value will be supplied
+    #[prost(uint64, optional, tag = "7")]
+    #[validate(custom = "crate::grpc::validate::validate_u64_range_min_1")]
+    pub timeout: ::core::option::Option<u64>,
 }
-/// Nested message and enum types in `Condition`.
-pub mod condition {
-    #[derive(serde::Serialize)]
-    #[allow(clippy::derive_partial_eq_without_eq)]
-    #[derive(Clone, PartialEq, ::prost::Oneof)]
-    pub enum ConditionOneOf {
-        #[prost(message, tag = "1")]
-        Field(super::FieldCondition),
-        #[prost(message, tag = "2")]
-        IsEmpty(super::IsEmptyCondition),
-        #[prost(message, tag = "3")]
-        HasId(super::HasIdCondition),
-        #[prost(message, tag = "4")]
-        Filter(super::Filter),
-        #[prost(message, tag = "5")]
-        IsNull(super::IsNullCondition),
-        #[prost(message, tag = "6")]
-        Nested(super::NestedCondition),
+/// Generated client implementations.
+pub mod collections_client {
+    #![allow(unused_variables, dead_code, missing_docs, clippy::let_unit_value)]
+    use tonic::codegen::*;
+    use tonic::codegen::http::Uri;
+    #[derive(Debug, Clone)]
+    pub struct CollectionsClient<T> {
+        inner: tonic::client::Grpc<T>,
+    }
+    impl CollectionsClient<tonic::transport::Channel> {
+        /// Attempt to create a new client by connecting to a given endpoint.
+        pub async fn connect<D>(dst: D) -> Result<Self, tonic::transport::Error>
+        where
+            D: TryInto<tonic::transport::Endpoint>,
+            D::Error: Into<StdError>,
+        {
+            let conn = tonic::transport::Endpoint::new(dst)?.connect().await?;
+            Ok(Self::new(conn))
+        }
+    }
+    impl<T> CollectionsClient<T>
+    where
+        T: tonic::client::GrpcService<tonic::body::BoxBody>,
+        T::Error: Into<StdError>,
+        T::ResponseBody: Body<Data = Bytes> + Send + 'static,
+        <T::ResponseBody as Body>::Error: Into<StdError> + Send,
+    {
+        pub fn new(inner: T) -> Self {
+            let inner = tonic::client::Grpc::new(inner);
+            Self { inner }
+        }
+        pub fn with_origin(inner: T, origin: Uri) -> Self {
+            let inner = tonic::client::Grpc::with_origin(inner, origin);
+            Self { inner }
+        }
+        pub fn with_interceptor<F>(
+            inner: T,
+            interceptor: F,
+        ) -> CollectionsClient<InterceptedService<T, F>>
+        where
+            F: tonic::service::Interceptor,
+            T::ResponseBody: Default,
+            T: tonic::codegen::Service<
+                http::Request<tonic::body::BoxBody>,
+                Response = http::Response<
+                    <T as tonic::client::GrpcService<tonic::body::BoxBody>>::ResponseBody,
+                >,
+            >,
+            <T as tonic::codegen::Service<
+                http::Request<tonic::body::BoxBody>,
+            >>::Error: Into<StdError> + Send + Sync,
+        {
+            CollectionsClient::new(InterceptedService::new(inner, interceptor))
+        }
+        /// Compress requests with the given encoding.
+        ///
+        /// This requires the server to support it otherwise it might respond with an
+        /// error.
+        #[must_use]
+        pub fn send_compressed(mut self, encoding: CompressionEncoding) -> Self {
+            self.inner = self.inner.send_compressed(encoding);
+            self
+        }
+        /// Enable decompressing responses.
+        #[must_use]
+        pub fn accept_compressed(mut self, encoding: CompressionEncoding) -> Self {
+            self.inner = self.inner.accept_compressed(encoding);
+            self
+        }
+        /// Limits the maximum size of a decoded message.
+        ///
+        /// Default: `4MB`
+        #[must_use]
+        pub fn max_decoding_message_size(mut self, limit: usize) -> Self {
+            self.inner = self.inner.max_decoding_message_size(limit);
+            self
+        }
+        /// Limits the maximum size of an encoded message.
+        ///
+        /// Default: `usize::MAX`
+        #[must_use]
+        pub fn max_encoding_message_size(mut self, limit: usize) -> Self {
+            self.inner = self.inner.max_encoding_message_size(limit);
+            self
+        }
+        ///
+        /// Get detailed information about specified existing collection
+        pub async fn get(
+            &mut self,
+            request: impl tonic::IntoRequest<super::GetCollectionInfoRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::GetCollectionInfoResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static("/qdrant.Collections/Get");
+            let mut req = request.into_request();
+            req.extensions_mut().insert(GrpcMethod::new("qdrant.Collections", "Get"));
+            self.inner.unary(req, path, codec).await
+        }
+        ///
+        /// Get list name of all existing collections
+        pub async fn list(
+            &mut self,
+            request: impl tonic::IntoRequest<super::ListCollectionsRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::ListCollectionsResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static("/qdrant.Collections/List");
+            let mut req = request.into_request();
+            req.extensions_mut().insert(GrpcMethod::new("qdrant.Collections", "List"));
+            self.inner.unary(req, path, codec).await
+        }
+        ///
+        /// Create new collection with given parameters
+        pub async fn create(
+            &mut self,
+            request: impl tonic::IntoRequest<super::CreateCollection>,
+        ) -> std::result::Result<
+            tonic::Response<super::CollectionOperationResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/qdrant.Collections/Create",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut().insert(GrpcMethod::new("qdrant.Collections", "Create"));
+            self.inner.unary(req, path, codec).await
+        }
+        ///
+        /// Update parameters of the existing collection
+        pub async fn update(
+            &mut self,
+            request: impl tonic::IntoRequest<super::UpdateCollection>,
+        ) -> std::result::Result<
+            tonic::Response<super::CollectionOperationResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/qdrant.Collections/Update",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut().insert(GrpcMethod::new("qdrant.Collections", "Update"));
+            self.inner.unary(req, path, codec).await
+        }
+        ///
+        /// Create a new collection with the configuration of the existing one and copy its points into it
+        pub async fn clone_collection(
+            &mut self,
+            request: impl tonic::IntoRequest<super::CloneCollection>,
+        ) -> std::result::Result<
+            tonic::Response<super::CollectionOperationResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/qdrant.Collections/CloneCollection",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("qdrant.Collections", "CloneCollection"));
+            self.inner.unary(req, path, codec).await
+        }
+        ///
+        /// Drop collection and all associated data
+        pub async fn delete(
+            &mut self,
+            request: impl tonic::IntoRequest<super::DeleteCollection>,
+        ) -> std::result::Result<
+            tonic::Response<super::CollectionOperationResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/qdrant.Collections/Delete",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut().insert(GrpcMethod::new("qdrant.Collections", "Delete"));
+            self.inner.unary(req, path, codec).await
+        }
+        ///
+        /// Update Aliases of the existing collection
+        pub async fn update_aliases(
+            &mut self,
+            request: impl tonic::IntoRequest<super::ChangeAliases>,
+        ) -> std::result::Result<
+            tonic::Response<super::CollectionOperationResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/qdrant.Collections/UpdateAliases",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("qdrant.Collections", "UpdateAliases"));
+            self.inner.unary(req, path, codec).await
+        }
+        ///
+        /// Get list of all aliases for a collection
+        pub async fn list_collection_aliases(
+            &mut self,
+            request: impl tonic::IntoRequest<super::ListCollectionAliasesRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::ListAliasesResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/qdrant.Collections/ListCollectionAliases",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("qdrant.Collections", "ListCollectionAliases"));
+            self.inner.unary(req, path, codec).await
+        }
+        ///
+        /// Get list of all aliases for all existing collections
+        pub async fn list_aliases(
+            &mut self,
+            request: impl tonic::IntoRequest<super::ListAliasesRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::ListAliasesResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/qdrant.Collections/ListAliases",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("qdrant.Collections", "ListAliases"));
+            self.inner.unary(req, path, codec).await
+        }
+        ///
+        /// Get cluster information for a collection
+        pub async fn collection_cluster_info(
+            &mut self,
+            request: impl tonic::IntoRequest<super::CollectionClusterInfoRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::CollectionClusterInfoResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/qdrant.Collections/CollectionClusterInfo",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("qdrant.Collections", "CollectionClusterInfo"));
+            self.inner.unary(req, path, codec).await
+        }
+        ///
+        /// Update cluster setup for a collection
+        pub async fn update_collection_cluster_setup(
+            &mut self,
+            request: impl tonic::IntoRequest<super::UpdateCollectionClusterSetupRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::UpdateCollectionClusterSetupResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/qdrant.Collections/UpdateCollectionClusterSetup",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(
+                    GrpcMethod::new("qdrant.Collections", "UpdateCollectionClusterSetup"),
+                );
+            self.inner.unary(req, path, codec).await
+        }
+        ///
+        /// Create shard key
+        pub async fn create_shard_key(
+            &mut self,
+            request: impl tonic::IntoRequest<super::CreateShardKeyRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::CreateShardKeyResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/qdrant.Collections/CreateShardKey",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("qdrant.Collections", "CreateShardKey"));
+            self.inner.unary(req, path, codec).await
+        }
+        ///
+        /// Delete shard key
+        pub async fn delete_shard_key(
+            &mut self,
+            request: impl tonic::IntoRequest<super::DeleteShardKeyRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::DeleteShardKeyResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/qdrant.Collections/DeleteShardKey",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("qdrant.Collections", "DeleteShardKey"));
+            self.inner.unary(req, path, codec).await
+        }
+        ///
+        /// List long running operations of the collections on this node
+        pub async fn list_operations(
+            &mut self,
+            request: impl tonic::IntoRequest<super::ListOperationsRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::ListOperationsResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/qdrant.Collections/ListOperations",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("qdrant.Collections", "ListOperations"));
+            self.inner.unary(req, path, codec).await
+        }
+        ///
+        /// Cancel long running operation
+        pub async fn cancel_operation(
+            &mut self,
+            request: impl tonic::IntoRequest<super::CancelOperationRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::CancelOperationResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/qdrant.Collections/CancelOperation",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("qdrant.Collections", "CancelOperation"));
+            self.inner.unary(req, path, codec).await
+        }
     }
 }
-#[derive(serde::Serialize)]
-#[allow(clippy::derive_partial_eq_without_eq)]
-#[derive(Clone, PartialEq, ::prost::Message)]
-pub struct IsEmptyCondition {
-    #[prost(string, tag = "1")]
-    pub key: ::prost::alloc::string::String,
-}
-#[derive(serde::Serialize)]
-#[allow(clippy::derive_partial_eq_without_eq)]
-#[derive(Clone, PartialEq, ::prost::Message)]
-pub struct IsNullCondition {
-    #[prost(string, tag = "1")]
-    pub key: ::prost::alloc::string::String,
-}
-#[derive(serde::Serialize)]
-#[allow(clippy::derive_partial_eq_without_eq)]
-#[derive(Clone, PartialEq, ::prost::Message)]
-pub struct HasIdCondition {
-    #[prost(message, repeated, tag = "1")]
-    pub has_id: ::prost::alloc::vec::Vec<PointId>,
-}
-#[derive(validator::Validate)]
-#[derive(serde::Serialize)]
-#[allow(clippy::derive_partial_eq_without_eq)]
-#[derive(Clone, PartialEq, ::prost::Message)]
-pub struct NestedCondition {
-    /// Path to nested object
-    #[prost(string, tag = "1")]
-    pub key: ::prost::alloc::string::String,
-    /// Filter condition
-    #[prost(message, optional, tag = "2")]
-    #[validate]
-    pub filter: ::core::option::Option<Filter>,
-}
-#[derive(serde::Serialize)]
-#[allow(clippy::derive_partial_eq_without_eq)]
-#[derive(Clone, PartialEq, ::prost::Message)]
-pub struct FieldCondition {
-    #[prost(string, tag = "1")]
-    pub key: ::prost::alloc::string::String,
-    /// Check if point has field with a given value
-    #[prost(message, optional, tag = "2")]
-    pub r#match: ::core::option::Option<Match>,
-    /// Check if points value lies in a given range
-    #[prost(message, optional, tag = "3")]
-    pub range: ::core::option::Option<Range>,
-    /// Check if points geolocation lies in a given area
-    #[prost(message, optional, tag = "4")]
-    pub geo_bounding_box: ::core::option::Option<GeoBoundingBox>,
-    /// Check if geo point is within a given radius
-    #[prost(message, optional, tag = "5")]
-    pub geo_radius: ::core::option::Option<GeoRadius>,
-    /// Check number of values for a specific field
-    #[prost(message, optional, tag = "6")]
-    pub values_count: ::core::option::Option<ValuesCount>,
-    /// Check if geo point is within a given polygon
-    #[prost(message, optional, tag = "7")]
-    pub geo_polygon: ::core::option::Option<GeoPolygon>,
-}
-#[derive(serde::Serialize)]
-#[allow(clippy::derive_partial_eq_without_eq)]
-#[derive(Clone, PartialEq, ::prost::Message)]
-pub struct Match {
-    #[prost(oneof = "r#match::MatchValue", tags = "1, 2, 3, 4, 5, 6, 7, 8")]
-    pub match_value: ::core::option::Option<r#match::MatchValue>,
-}
-/// Nested message and enum types in `Match`.
-pub mod r#match {
-    #[derive(serde::Serialize)]
-    #[allow(clippy::derive_partial_eq_without_eq)]
-    #[derive(Clone, PartialEq, ::prost::Oneof)]
-    pub enum MatchValue {
-        /// Match string keyword
-        #[prost(string, tag = "1")]
-        Keyword(::prost::alloc::string::String),
-        /// Match integer
-        #[prost(int64, tag = "2")]
-        Integer(i64),
-        /// Match boolean
-        #[prost(bool, tag = "3")]
-        Boolean(bool),
-        /// Match text
-        #[prost(string, tag = "4")]
-        Text(::prost::alloc::string::String),
-        /// Match multiple keywords
-        #[prost(message, tag = "5")]
-        Keywords(super::RepeatedStrings),
-        /// Match multiple integers
-        #[prost(message, tag = "6")]
-        Integers(super::RepeatedIntegers),
-        /// Match any other value except those integers
-        #[prost(message, tag = "7")]
-        ExceptIntegers(super::RepeatedIntegers),
-        /// Match any other value except those keywords
-        #[prost(message, tag = "8")]
-        ExceptKeywords(super::RepeatedStrings),
+/// Generated server implementations.
+pub mod collections_server {
+    #![allow(unused_variables, dead_code, missing_docs, clippy::let_unit_value)]
+    use tonic::codegen::*;
+    /// Generated trait containing gRPC methods that should be implemented for use with CollectionsServer.
+    #[async_trait]
+    pub trait Collections: Send + Sync + 'static {
+        ///
+        /// Get detailed information about specified existing collection
+        async fn get(
+            &self,
+            request: tonic::Request<super::GetCollectionInfoRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::GetCollectionInfoResponse>,
+            tonic::Status,
+        >;
+        ///
+        /// Get list name of all existing collections
+        async fn list(
+            &self,
+            request: tonic::Request<super::ListCollectionsRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::ListCollectionsResponse>,
+            tonic::Status,
+        >;
+        ///
+        /// Create new collection with given parameters
+        async fn create(
+            &self,
+            request: tonic::Request<super::CreateCollection>,
+        ) -> std::result::Result<
+            tonic::Response<super::CollectionOperationResponse>,
+            tonic::Status,
+        >;
+        ///
+        /// Update parameters of the existing collection
+        async fn update(
+            &self,
+            request: tonic::Request<super::UpdateCollection>,
+        ) -> std::result::Result<
+            tonic::Response<super::CollectionOperationResponse>,
+            tonic::Status,
+        >;
+        ///
+        /// Create a new collection with the configuration of the existing one and copy its points into it
+        async fn clone_collection(
+            &self,
+            request: tonic::Request<super::CloneCollection>,
+        ) -> std::result::Result<
+            tonic::Response<super::CollectionOperationResponse>,
+            tonic::Status,
+        >;
+        ///
+        /// Drop collection and all associated data
+        async fn delete(
+            &self,
+            request: tonic::Request<super::DeleteCollection>,
+        ) -> std::result::Result<
+            tonic::Response<super::CollectionOperationResponse>,
+            tonic::Status,
+        >;
+        ///
+        /// Update Aliases of the existing collection
+        async fn update_aliases(
+            &self,
+            request: tonic::Request<super::ChangeAliases>,
+        ) -> std::result::Result<
+            tonic::Response<super::CollectionOperationResponse>,
+            tonic::Status,
+        >;
+        ///
+        /// Get list of all aliases for a collection
+        async fn list_collection_aliases(
+            &self,
+            request: tonic::Request<super::ListCollectionAliasesRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::ListAliasesResponse>,
+            tonic::Status,
+        >;
+        ///
+        /// Get list of all aliases for all existing collections
+        async fn list_aliases(
+            &self,
+            request: tonic::Request<super::ListAliasesRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::ListAliasesResponse>,
+            tonic::Status,
+        >;
+        ///
+        /// Get cluster information for a collection
+        async fn collection_cluster_info(
+            &self,
+            request: tonic::Request<super::CollectionClusterInfoRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::CollectionClusterInfoResponse>,
+            tonic::Status,
+        >;
+        ///
+        /// Update cluster setup for a collection
+        async fn update_collection_cluster_setup(
+            &self,
+            request: tonic::Request<super::UpdateCollectionClusterSetupRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::UpdateCollectionClusterSetupResponse>,
+            tonic::Status,
+        >;
+        ///
+        /// Create shard key
+        async fn create_shard_key(
+            &self,
+            request: tonic::Request<super::CreateShardKeyRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::CreateShardKeyResponse>,
+            tonic::Status,
+        >;
+        ///
+        /// Delete shard key
+        async fn delete_shard_key(
+            &self,
+            request: tonic::Request<super::DeleteShardKeyRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::DeleteShardKeyResponse>,
+            tonic::Status,
+        >;
+        ///
+        /// List long running operations of the collections on this node
+        async fn list_operations(
+            &self,
+            request: tonic::Request<super::ListOperationsRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::ListOperationsResponse>,
+            tonic::Status,
+        >;
+        ///
+        /// Cancel long running operation
+        async fn cancel_operation(
+            &self,
+            request: tonic::Request<super::CancelOperationRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::CancelOperationResponse>,
+            tonic::Status,
+        >;
+    }
+    #[derive(Debug)]
+    pub struct CollectionsServer<T: Collections> {
+        inner: _Inner<T>,
+        accept_compression_encodings: EnabledCompressionEncodings,
+        send_compression_encodings: EnabledCompressionEncodings,
+        max_decoding_message_size: Option<usize>,
+        max_encoding_message_size: Option<usize>,
+    }
+    struct _Inner<T>(Arc<T>);
+    impl<T: Collections> CollectionsServer<T> {
+        pub fn new(inner: T) -> Self {
+            Self::from_arc(Arc::new(inner))
+        }
+        pub fn from_arc(inner: Arc<T>) -> Self {
+            let inner = _Inner(inner);
+            Self {
+                inner,
+                accept_compression_encodings: Default::default(),
+                send_compression_encodings: Default::default(),
+                max_decoding_message_size: None,
+                max_encoding_message_size: None,
+            }
+        }
+        pub fn with_interceptor<F>(
+            inner: T,
+            interceptor: F,
+        ) -> InterceptedService<Self, F>
+        where
+            F: tonic::service::Interceptor,
+        {
+            InterceptedService::new(Self::new(inner), interceptor)
+        }
+        /// Enable decompressing requests with the given encoding.
+        #[must_use]
+        pub fn accept_compressed(mut self, encoding: CompressionEncoding) -> Self {
+            self.accept_compression_encodings.enable(encoding);
+            self
+        }
+        /// Compress responses with the given encoding, if the client supports it.
+        #[must_use]
+        pub fn send_compressed(mut self, encoding: CompressionEncoding) -> Self {
+            self.send_compression_encodings.enable(encoding);
+            self
+        }
+        /// Limits the maximum size of a decoded message.
+        ///
+        /// Default: `4MB`
+        #[must_use]
+        pub fn max_decoding_message_size(mut self, limit: usize) -> Self {
+            self.max_decoding_message_size = Some(limit);
+            self
+        }
+        /// Limits the maximum size of an encoded message.
+        ///
+        /// Default: `usize::MAX`
+        #[must_use]
+        pub fn max_encoding_message_size(mut self, limit: usize) -> Self {
+            self.max_encoding_message_size = Some(limit);
+            self
+        }
+    }
+    impl<T, B> tonic::codegen::Service<http::Request<B>> for CollectionsServer<T>
+    where
+        T: Collections,
+        B: Body + Send + 'static,
+        B::Error: Into<StdError> + Send + 'static,
+    {
+        type Response = http::Response<tonic::body::BoxBody>;
+        type Error = std::convert::Infallible;
+        type Future = BoxFuture<Self::Response, Self::Error>;
+        fn poll_ready(
+            &mut self,
+            _cx: &mut Context<'_>,
+        ) -> Poll<std::result::Result<(), Self::Error>> {
+            Poll::Ready(Ok(()))
+        }
+        fn call(&mut self, req: http::Request<B>) -> Self::Future {
+            let inner = self.inner.clone();
+            match req.uri().path() {
+                "/qdrant.Collections/Get" => {
+                    #[allow(non_camel_case_types)]
+                    struct GetSvc<T: Collections>(pub Arc<T>);
+                    impl<
+                        T: Collections,
+                    > tonic::server::UnaryService<super::GetCollectionInfoRequest>
+                    for GetSvc<T> {
+                        type Response = super::GetCollectionInfoResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::GetCollectionInfoRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as Collections>::get(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = GetSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/qdrant.Collections/List" => {
+                    #[allow(non_camel_case_types)]
+                    struct ListSvc<T: Collections>(pub Arc<T>);
+                    impl<
+                        T: Collections,
+                    > tonic::server::UnaryService<super::ListCollectionsRequest>
+                    for ListSvc<T> {
+                        type Response = super::ListCollectionsResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::ListCollectionsRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as Collections>::list(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = ListSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/qdrant.Collections/Create" => {
+                    #[allow(non_camel_case_types)]
+                    struct CreateSvc<T: Collections>(pub Arc<T>);
+                    impl<
+                        T: Collections,
+                    > tonic::server::UnaryService<super::CreateCollection>
+                    for CreateSvc<T> {
+                        type Response = super::CollectionOperationResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::CreateCollection>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as Collections>::create(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = CreateSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/qdrant.Collections/Update" => {
+                    #[allow(non_camel_case_types)]
+                    struct UpdateSvc<T: Collections>(pub Arc<T>);
+                    impl<
+                        T: Collections,
+                    > tonic::server::UnaryService<super::UpdateCollection>
+                    for UpdateSvc<T> {
+                        type Response = super::CollectionOperationResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::UpdateCollection>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as Collections>::update(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = UpdateSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/qdrant.Collections/CloneCollection" => {
+                    #[allow(non_camel_case_types)]
+                    struct CloneCollectionSvc<T: Collections>(pub Arc<T>);
+                    impl<
+                        T: Collections,
+                    > tonic::server::UnaryService<super::CloneCollection>
+                    for CloneCollectionSvc<T> {
+                        type Response = super::CollectionOperationResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::CloneCollection>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as Collections>::clone_collection(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = CloneCollectionSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/qdrant.Collections/Delete" => {
+                    #[allow(non_camel_case_types)]
+                    struct DeleteSvc<T: Collections>(pub Arc<T>);
+                    impl<
+                        T: Collections,
+                    > tonic::server::UnaryService<super::DeleteCollection>
+                    for DeleteSvc<T> {
+                        type Response = super::CollectionOperationResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::DeleteCollection>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as Collections>::delete(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = DeleteSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/qdrant.Collections/UpdateAliases" => {
+                    #[allow(non_camel_case_types)]
+                    struct UpdateAliasesSvc<T: Collections>(pub Arc<T>);
+                    impl<
+                        T: Collections,
+                    > tonic::server::UnaryService<super::ChangeAliases>
+                    for UpdateAliasesSvc<T> {
+                        type Response = super::CollectionOperationResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::ChangeAliases>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as Collections>::update_aliases(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = UpdateAliasesSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/qdrant.Collections/ListCollectionAliases" => {
+                    #[allow(non_camel_case_types)]
+                    struct ListCollectionAliasesSvc<T: Collections>(pub Arc<T>);
+                    impl<
+                        T: Collections,
+                    > tonic::server::UnaryService<super::ListCollectionAliasesRequest>
+                    for ListCollectionAliasesSvc<T> {
+                        type Response = super::ListAliasesResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::ListCollectionAliasesRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as Collections>::list_collection_aliases(&inner, request)
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = ListCollectionAliasesSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/qdrant.Collections/ListAliases" => {
+                    #[allow(non_camel_case_types)]
+                    struct ListAliasesSvc<T: Collections>(pub Arc<T>);
+                    impl<
+                        T: Collections,
+                    > tonic::server::UnaryService<super::ListAliasesRequest>
+                    for ListAliasesSvc<T> {
+                        type Response = super::ListAliasesResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::ListAliasesRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as Collections>::list_aliases(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = ListAliasesSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/qdrant.Collections/CollectionClusterInfo" => {
+                    #[allow(non_camel_case_types)]
+                    struct CollectionClusterInfoSvc<T: Collections>(pub Arc<T>);
+                    impl<
+                        T: Collections,
+                    > tonic::server::UnaryService<super::CollectionClusterInfoRequest>
+                    for CollectionClusterInfoSvc<T> {
+                        type Response = super::CollectionClusterInfoResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::CollectionClusterInfoRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as Collections>::collection_cluster_info(&inner, request)
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = CollectionClusterInfoSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/qdrant.Collections/UpdateCollectionClusterSetup" => {
+                    #[allow(non_camel_case_types)]
+                    struct UpdateCollectionClusterSetupSvc<T: Collections>(pub Arc<T>);
+                    impl<
+                        T: Collections,
+                    > tonic::server::UnaryService<
+                        super::UpdateCollectionClusterSetupRequest,
+                    > for UpdateCollectionClusterSetupSvc<T> {
+                        type Response = super::UpdateCollectionClusterSetupResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<
+                                super::UpdateCollectionClusterSetupRequest,
+                            >,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as Collections>::update_collection_cluster_setup(
+                                        &inner,
+                                        request,
+                                    )
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = UpdateCollectionClusterSetupSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/qdrant.Collections/CreateShardKey" => {
+                    #[allow(non_camel_case_types)]
+                    struct CreateShardKeySvc<T: Collections>(pub Arc<T>);
+                    impl<
+                        T: Collections,
+                    > tonic::server::UnaryService<super::CreateShardKeyRequest>
+                    for CreateShardKeySvc<T> {
+                        type Response = super::CreateShardKeyResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::CreateShardKeyRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as Collections>::create_shard_key(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = CreateShardKeySvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/qdrant.Collections/DeleteShardKey" => {
+                    #[allow(non_camel_case_types)]
+                    struct DeleteShardKeySvc<T: Collections>(pub Arc<T>);
+                    impl<
+                        T: Collections,
+                    > tonic::server::UnaryService<super::DeleteShardKeyRequest>
+                    for DeleteShardKeySvc<T> {
+                        type Response = super::DeleteShardKeyResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::DeleteShardKeyRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as Collections>::delete_shard_key(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = DeleteShardKeySvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/qdrant.Collections/ListOperations" => {
+                    #[allow(non_camel_case_types)]
+                    struct ListOperationsSvc<T: Collections>(pub Arc<T>);
+                    impl<
+                        T: Collections,
+                    > tonic::server::UnaryService<super::ListOperationsRequest>
+                    for ListOperationsSvc<T> {
+                        type Response = super::ListOperationsResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::ListOperationsRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as Collections>::list_operations(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = ListOperationsSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/qdrant.Collections/CancelOperation" => {
+                    #[allow(non_camel_case_types)]
+                    struct CancelOperationSvc<T: Collections>(pub Arc<T>);
+                    impl<
+                        T: Collections,
+                    > tonic::server::UnaryService<super::CancelOperationRequest>
+                    for CancelOperationSvc<T> {
+                        type Response = super::CancelOperationResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::CancelOperationRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as Collections>::cancel_operation(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = CancelOperationSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                _ => {
+                    Box::pin(async move {
+                        Ok(
+                            http::Response::builder()
+                                .status(200)
+                                .header("grpc-status", "12")
+                                .header("content-type", "application/grpc")
+                                .body(empty_body())
+                                .unwrap(),
+                        )
+                    })
+                }
+            }
+        }
+    }
+    impl<T: Collections> Clone for CollectionsServer<T> {
+        fn clone(&self) -> Self {
+            let inner = self.inner.clone();
+            Self {
+                inner,
+                accept_compression_encodings: self.accept_compression_encodings,
+                send_compression_encodings: self.send_compression_encodings,
+                max_decoding_message_size: self.max_decoding_message_size,
+                max_encoding_message_size: self.max_encoding_message_size,
+            }
+        }
+    }
+    impl<T: Collections> Clone for _Inner<T> {
+        fn clone(&self) -> Self {
+            Self(Arc::clone(&self.0))
+        }
+    }
+    impl<T: std::fmt::Debug> std::fmt::Debug for _Inner<T> {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "{:?}", self.0)
+        }
+    }
+    impl<T: Collections> tonic::server::NamedService for CollectionsServer<T> {
+        const NAME: &'static str = "qdrant.Collections";
     }
 }
-#[derive(serde::Serialize)]
-#[allow(clippy::derive_partial_eq_without_eq)]
-#[derive(Clone, PartialEq, ::prost::Message)]
-pub struct RepeatedStrings {
-    #[prost(string, repeated, tag = "1")]
-    pub strings: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
-}
-#[derive(serde::Serialize)]
-#[allow(clippy::derive_partial_eq_without_eq)]
-#[derive(Clone, PartialEq, ::prost::Message)]
-pub struct RepeatedIntegers {
-    #[prost(int64, repeated, tag = "1")]
-    pub integers: ::prost::alloc::vec::Vec<i64>,
-}
-#[derive(serde::Serialize)]
-#[allow(clippy::derive_partial_eq_without_eq)]
-#[derive(Clone, PartialEq, ::prost::Message)]
-pub struct Range {
-    #[prost(double, optional, tag = "1")]
-    pub lt: ::core::option::Option<f64>,
-    #[prost(double, optional, tag = "2")]
-    pub gt: ::core::option::Option<f64>,
-    #[prost(double, optional, tag = "3")]
-    pub gte: ::core::option::Option<f64>,
-    #[prost(double, optional, tag = "4")]
-    pub lte: ::core::option::Option<f64>,
-}
-#[derive(serde::Serialize)]
-#[allow(clippy::derive_partial_eq_without_eq)]
-#[derive(Clone, PartialEq, ::prost::Message)]
-pub struct GeoBoundingBox {
-    /// north-west corner
-    #[prost(message, optional, tag = "1")]
-    pub top_left: ::core::option::Option<GeoPoint>,
-    /// south-east corner
-    #[prost(message, optional, tag = "2")]
-    pub bottom_right: ::core::option::Option<GeoPoint>,
-}
-#[derive(serde::Serialize)]
-#[allow(clippy::derive_partial_eq_without_eq)]
-#[derive(Clone, PartialEq, ::prost::Message)]
-pub struct GeoRadius {
-    /// Center of the circle
-    #[prost(message, optional, tag = "1")]
-    pub center: ::core::option::Option<GeoPoint>,
-    /// In meters
-    #[prost(float, tag = "2")]
-    pub radius: f32,
-}
-#[derive(serde::Serialize)]
-#[allow(clippy::derive_partial_eq_without_eq)]
-#[derive(Clone, PartialEq, ::prost::Message)]
-pub struct GeoLineString {
-    /// Ordered sequence of GeoPoints representing the line
-    #[prost(message, repeated, tag = "1")]
-    pub points: ::prost::alloc::vec::Vec<GeoPoint>,
-}
-/// For a valid GeoPolygon, both the exterior and interior GeoLineStrings must consist of a minimum of 4 points.
-/// Additionally, the first and last points of each GeoLineString must be the same.
 #[derive(validator::Validate)]
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
-pub struct GeoPolygon {
-    /// The exterior line bounds the surface
+pub struct GetCollectionInfoRequestInternal {
     #[prost(message, optional, tag = "1")]
-    #[validate(custom = "crate::grpc::validate::validate_geo_polygon_exterior")]
-    pub exterior: ::core::option::Option<GeoLineString>,
-    /// Interior lines (if present) bound holes within the surface
-    #[prost(message, repeated, tag = "2")]
-    #[validate(custom = "crate::grpc::validate::validate_geo_polygon_interiors")]
-    pub interiors: ::prost::alloc::vec::Vec<GeoLineString>,
+    pub get_collection_info_request: ::core::option::Option<GetCollectionInfoRequest>,
+    #[prost(uint32, tag = "2")]
+    pub shard_id: u32,
 }
+#[derive(validator::Validate)]
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
-pub struct ValuesCount {
-    #[prost(uint64, optional, tag = "1")]
-    pub lt: ::core::option::Option<u64>,
-    #[prost(uint64, optional, tag = "2")]
-    pub gt: ::core::option::Option<u64>,
-    #[prost(uint64, optional, tag = "3")]
-    pub gte: ::core::option::Option<u64>,
-    #[prost(uint64, optional, tag = "4")]
-    pub lte: ::core::option::Option<u64>,
+pub struct InitiateShardTransferRequest {
+    /// Name of the collection
+    #[prost(string, tag = "1")]
+    #[validate(length(min = 1, max = 255))]
+    pub collection_name: ::prost::alloc::string::String,
+    /// Id of the temporary shard
+    #[prost(uint32, tag = "2")]
+    pub shard_id: u32,
 }
+#[derive(validator::Validate)]
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
-pub struct PointsSelector {
-    #[prost(oneof = "points_selector::PointsSelectorOneOf", tags = "1, 2")]
-    pub points_selector_one_of: ::core::option::Option<
-        points_selector::PointsSelectorOneOf,
-    >,
+pub struct WaitForShardStateRequest {
+    /// Name of the collection
+    #[prost(string, tag = "1")]
+    #[validate(length(min = 1, max = 255))]
+    pub collection_name: ::prost::alloc::string::String,
+    /// Id of the shard
+    #[prost(uint32, tag = "2")]
+    pub shard_id: u32,
+    /// Shard state to wait for
+    #[prost(enumeration = "ReplicaState", tag = "3")]
+    pub state: i32,
+    /// Timeout in seconds
+    #[prost(uint64, tag = "4")]
+    #[validate(range(min = 1))]
+    pub timeout: u64,
 }
-/// Nested message and enum types in `PointsSelector`.
-pub mod points_selector {
-    #[derive(serde::Serialize)]
-    #[allow(clippy::derive_partial_eq_without_eq)]
-    #[derive(Clone, PartialEq, ::prost::Oneof)]
-    pub enum PointsSelectorOneOf {
-        #[prost(message, tag = "1")]
-        Points(super::PointsIdsList),
-        #[prost(message, tag = "2")]
-        Filter(super::Filter),
+/// Generated client implementations.
+pub mod collections_internal_client {
+    #![allow(unused_variables, dead_code, missing_docs, clippy::let_unit_value)]
+    use tonic::codegen::*;
+    use tonic::codegen::http::Uri;
+    #[derive(Debug, Clone)]
+    pub struct CollectionsInternalClient<T> {
+        inner: tonic::client::Grpc<T>,
     }
-}
-#[derive(serde::Serialize)]
-#[allow(clippy::derive_partial_eq_without_eq)]
-#[derive(Clone, PartialEq, ::prost::Message)]
-pub struct PointsIdsList {
-    #[prost(message, repeated, tag = "1")]
-    pub ids: ::prost::alloc::vec::Vec<PointId>,
-}
-#[derive(serde::Serialize)]
-#[allow(clippy::derive_partial_eq_without_eq)]
-#[derive(Clone, PartialEq, ::prost::Message)]
-pub struct PointStruct {
-    #[prost(message, optional, tag = "1")]
-    pub id: ::core::option::Option<PointId>,
-    #[prost(map = "string, message", tag = "3")]
-    pub payload: ::std::collections::HashMap<::prost::alloc::string::String, Value>,
-    #[prost(message, optional, tag = "4")]
-    pub vectors: ::core::option::Option<Vectors>,
-}
-#[derive(serde::Serialize)]
-#[allow(clippy::derive_partial_eq_without_eq)]
-#[derive(Clone, PartialEq, ::prost::Message)]
-pub struct GeoPoint {
-    #[prost(double, tag = "1")]
-    pub lon: f64,
-    #[prost(double, tag = "2")]
-    pub lat: f64,
-}
-#[derive(serde::Serialize)]
-#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
-#[repr(i32)]
-pub enum WriteOrderingType {
-    /// Write operations may be reordered, works faster, default
-    Weak = 0,
-    /// Write operations go through dynamically selected leader, may be inconsistent for a short period of time in case of leader change
-    Medium = 1,
-    /// Write operations go through the permanent leader, consistent, but may be unavailable if leader is down
-    Strong = 2,
-}
-impl WriteOrderingType {
-    /// String value of the enum field names used in the ProtoBuf definition.
-    ///
-    /// The values are not transformed in any way and thus are considered stable
-    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
-    pub fn as_str_name(&self) -> &'static str {
-        match self {
-            WriteOrderingType::Weak => "Weak",
-            WriteOrderingType::Medium => "Medium",
-            WriteOrderingType::Strong => "Strong",
+    impl CollectionsInternalClient<tonic::transport::Channel> {
+        /// Attempt to create a new client by connecting to a given endpoint.
+        pub async fn connect<D>(dst: D) -> Result<Self, tonic::transport::Error>
+        where
+            D: TryInto<tonic::transport::Endpoint>,
+            D::Error: Into<StdError>,
+        {
+            let conn = tonic::transport::Endpoint::new(dst)?.connect().await?;
+            Ok(Self::new(conn))
+        }
+    }
+    impl<T> CollectionsInternalClient<T>
+    where
+        T: tonic::client::GrpcService<tonic::body::BoxBody>,
+        T::Error: Into<StdError>,
+        T::ResponseBody: Body<Data = Bytes> + Send + 'static,
+        <T::ResponseBody as Body>::Error: Into<StdError> + Send,
+    {
+        pub fn new(inner: T) -> Self {
+            let inner = tonic::client::Grpc::new(inner);
+            Self { inner }
+        }
+        pub fn with_origin(inner: T, origin: Uri) -> Self {
+            let inner = tonic::client::Grpc::with_origin(inner, origin);
+            Self { inner }
+        }
+        pub fn with_interceptor<F>(
+            inner: T,
+            interceptor: F,
+        ) -> CollectionsInternalClient<InterceptedService<T, F>>
+        where
+            F: tonic::service::Interceptor,
+            T::ResponseBody: Default,
+            T: tonic::codegen::Service<
+                http::Request<tonic::body::BoxBody>,
+                Response = http::Response<
+                    <T as tonic::client::GrpcService<tonic::body::BoxBody>>::ResponseBody,
+                >,
+            >,
+            <T as tonic::codegen::Service<
+                http::Request<tonic::body::BoxBody>,
+            >>::Error: Into<StdError> + Send + Sync,
+        {
+            CollectionsInternalClient::new(InterceptedService::new(inner, interceptor))
+        }
+        /// Compress requests with the given encoding.
+        ///
+        /// This requires the server to support it otherwise it might respond with an
+        /// error.
+        #[must_use]
+        pub fn send_compressed(mut self, encoding: CompressionEncoding) -> Self {
+            self.inner = self.inner.send_compressed(encoding);
+            self
         }
-    }
-    /// Creates an enum from field names used in the ProtoBuf definition.
-    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
-        match value {
-            "Weak" => Some(Self::Weak),
-            "Medium" => Some(Self::Medium),
-            "Strong" => Some(Self::Strong),
-            _ => None,
+        /// Enable decompressing responses.
+        #[must_use]
+        pub fn accept_compressed(mut self, encoding: CompressionEncoding) -> Self {
+            self.inner = self.inner.accept_compressed(encoding);
+            self
         }
-    }
-}
-#[derive(serde::Serialize)]
-#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
-#[repr(i32)]
-pub enum ReadConsistencyType {
-    /// Send request to all nodes and return points which are present on all of them
-    All = 0,
-    /// Send requests to all nodes and return points which are present on majority of them
-    Majority = 1,
-    /// Send requests to half + 1 nodes, return points which are present on all of them
-    Quorum = 2,
-}
-impl ReadConsistencyType {
-    /// String value of the enum field names used in the ProtoBuf definition.
-    ///
-    /// The values are not transformed in any way and thus are considered stable
-    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
-    pub fn as_str_name(&self) -> &'static str {
-        match self {
-            ReadConsistencyType::All => "All",
-            ReadConsistencyType::Majority => "Majority",
-            ReadConsistencyType::Quorum => "Quorum",
+        /// Limits the maximum size of a decoded message.
+        ///
+        /// Default: `4MB`
+        #[must_use]
+        pub fn max_decoding_message_size(mut self, limit: usize) -> Self {
+            self.inner = self.inner.max_decoding_message_size(limit);
+            self
         }
-    }
-    /// Creates an enum from field names used in the ProtoBuf definition.
-    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
-        match value {
-            "All" => Some(Self::All),
-            "Majority" => Some(Self::Majority),
-            "Quorum" => Some(Self::Quorum),
-            _ => None,
+        /// Limits the maximum size of an encoded message.
+        ///
+        /// Default: `usize::MAX`
+        #[must_use]
+        pub fn max_encoding_message_size(mut self, limit: usize) -> Self {
+            self.inner = self.inner.max_encoding_message_size(limit);
+            self
+        }
+        ///
+        /// Get collection info
+        pub async fn get(
+            &mut self,
+            request: impl tonic::IntoRequest<super::GetCollectionInfoRequestInternal>,
+        ) -> std::result::Result<
+            tonic::Response<super::GetCollectionInfoResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/qdrant.CollectionsInternal/Get",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("qdrant.CollectionsInternal", "Get"));
+            self.inner.unary(req, path, codec).await
+        }
+        ///
+        /// Initiate shard transfer
+        pub async fn initiate(
+            &mut self,
+            request: impl tonic::IntoRequest<super::InitiateShardTransferRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::CollectionOperationResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/qdrant.CollectionsInternal/Initiate",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("qdrant.CollectionsInternal", "Initiate"));
+            self.inner.unary(req, path, codec).await
+        }
+        /// *
+        /// Wait for a shard to get into the given state
+        pub async fn wait_for_shard_state(
+            &mut self,
+            request: impl tonic::IntoRequest<super::WaitForShardStateRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::CollectionOperationResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/qdrant.CollectionsInternal/WaitForShardState",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(
+                    GrpcMethod::new("qdrant.CollectionsInternal", "WaitForShardState"),
+                );
+            self.inner.unary(req, path, codec).await
         }
     }
 }
-#[derive(serde::Serialize)]
-#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
-#[repr(i32)]
-pub enum FieldType {
-    Keyword = 0,
-    Integer = 1,
-    Float = 2,
-    Geo = 3,
-    Text = 4,
-    Bool = 5,
-}
-impl FieldType {
-    /// String value of the enum field names used in the ProtoBuf definition.
-    ///
-    /// The values are not transformed in any way and thus are considered stable
-    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
-    pub fn as_str_name(&self) -> &'static str {
-        match self {
-            FieldType::Keyword => "FieldTypeKeyword",
-            FieldType::Integer => "FieldTypeInteger",
-            FieldType::Float => "FieldTypeFloat",
-            FieldType::Geo => "FieldTypeGeo",
-            FieldType::Text => "FieldTypeText",
-            FieldType::Bool => "FieldTypeBool",
+/// Generated server implementations.
+pub mod collections_internal_server {
+    #![allow(unused_variables, dead_code, missing_docs, clippy::let_unit_value)]
+    use tonic::codegen::*;
+    /// Generated trait containing gRPC methods that should be implemented for use with CollectionsInternalServer.
+    #[async_trait]
+    pub trait CollectionsInternal: Send + Sync + 'static {
+        ///
+        /// Get collection info
+        async fn get(
+            &self,
+            request: tonic::Request<super::GetCollectionInfoRequestInternal>,
+        ) -> std::result::Result<
+            tonic::Response<super::GetCollectionInfoResponse>,
+            tonic::Status,
+        >;
+        ///
+        /// Initiate shard transfer
+        async fn initiate(
+            &self,
+            request: tonic::Request<super::InitiateShardTransferRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::CollectionOperationResponse>,
+            tonic::Status,
+        >;
+        /// *
+        /// Wait for a shard to get into the given state
+        async fn wait_for_shard_state(
+            &self,
+            request: tonic::Request<super::WaitForShardStateRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::CollectionOperationResponse>,
+            tonic::Status,
+        >;
+    }
+    #[derive(Debug)]
+    pub struct CollectionsInternalServer<T: CollectionsInternal> {
+        inner: _Inner<T>,
+        accept_compression_encodings: EnabledCompressionEncodings,
+        send_compression_encodings: EnabledCompressionEncodings,
+        max_decoding_message_size: Option<usize>,
+        max_encoding_message_size: Option<usize>,
+    }
+    struct _Inner<T>(Arc<T>);
+    impl<T: CollectionsInternal> CollectionsInternalServer<T> {
+        pub fn new(inner: T) -> Self {
+            Self::from_arc(Arc::new(inner))
+        }
+        pub fn from_arc(inner: Arc<T>) -> Self {
+            let inner = _Inner(inner);
+            Self {
+                inner,
+                accept_compression_encodings: Default::default(),
+                send_compression_encodings: Default::default(),
+                max_decoding_message_size: None,
+                max_encoding_message_size: None,
+            }
+        }
+        pub fn with_interceptor<F>(
+            inner: T,
+            interceptor: F,
+        ) -> InterceptedService<Self, F>
+        where
+            F: tonic::service::Interceptor,
+        {
+            InterceptedService::new(Self::new(inner), interceptor)
+        }
+        /// Enable decompressing requests with the given encoding.
+        #[must_use]
+        pub fn accept_compressed(mut self, encoding: CompressionEncoding) -> Self {
+            self.accept_compression_encodings.enable(encoding);
+            self
+        }
+        /// Compress responses with the given encoding, if the client supports it.
+        #[must_use]
+        pub fn send_compressed(mut self, encoding: CompressionEncoding) -> Self {
+            self.send_compression_encodings.enable(encoding);
+            self
+        }
+        /// Limits the maximum size of a decoded message.
+        ///
+        /// Default: `4MB`
+        #[must_use]
+        pub fn max_decoding_message_size(mut self, limit: usize) -> Self {
+            self.max_decoding_message_size = Some(limit);
+            self
         }
-    }
-    /// Creates an enum from field names used in the ProtoBuf definition.
-    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
-        match value {
-            "FieldTypeKeyword" => Some(Self::Keyword),
-            "FieldTypeInteger" => Some(Self::Integer),
-            "FieldTypeFloat" => Some(Self::Float),
-            "FieldTypeGeo" => Some(Self::Geo),
-            "FieldTypeText" => Some(Self::Text),
-            "FieldTypeBool" => Some(Self::Bool),
-            _ => None,
+        /// Limits the maximum size of an encoded message.
+        ///
+        /// Default: `usize::MAX`
+        #[must_use]
+        pub fn max_encoding_message_size(mut self, limit: usize) -> Self {
+            self.max_encoding_message_size = Some(limit);
+            self
         }
     }
-}
-/// How to use positive and negative vectors to find the results, default is `AverageVector`:
-#[derive(serde::Serialize)]
-#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
-#[repr(i32)]
-pub enum RecommendStrategy {
-    /// Average positive and negative vectors and create a single query with the formula
-    /// `query = avg_pos + avg_pos - avg_neg`. Then performs normal search.
-    AverageVector = 0,
-    /// Uses custom search objective. Each candidate is compared against all
-    /// examples, its score is then chosen from the `max(max_pos_score, max_neg_score)`.
-    /// If the `max_neg_score` is chosen then it is squared and negated.
-    BestScore = 1,
-}
-impl RecommendStrategy {
-    /// String value of the enum field names used in the ProtoBuf definition.
-    ///
-    /// The values are not transformed in any way and thus are considered stable
-    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
-    pub fn as_str_name(&self) -> &'static str {
-        match self {
-            RecommendStrategy::AverageVector => "AverageVector",
-            RecommendStrategy::BestScore => "BestScore",
+    impl<T, B> tonic::codegen::Service<http::Request<B>> for CollectionsInternalServer<T>
+    where
+        T: CollectionsInternal,
+        B: Body + Send + 'static,
+        B::Error: Into<StdError> + Send + 'static,
+    {
+        type Response = http::Response<tonic::body::BoxBody>;
+        type Error = std::convert::Infallible;
+        type Future = BoxFuture<Self::Response, Self::Error>;
+        fn poll_ready(
+            &mut self,
+            _cx: &mut Context<'_>,
+        ) -> Poll<std::result::Result<(), Self::Error>> {
+            Poll::Ready(Ok(()))
         }
-    }
-    /// Creates an enum from field names used in the ProtoBuf definition.
-    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
-        match value {
-            "AverageVector" => Some(Self::AverageVector),
-            "BestScore" => Some(Self::BestScore),
-            _ => None,
+        fn call(&mut self, req: http::Request<B>) -> Self::Future {
+            let inner = self.inner.clone();
+            match req.uri().path() {
+                "/qdrant.CollectionsInternal/Get" => {
+                    #[allow(non_camel_case_types)]
+                    struct GetSvc<T: CollectionsInternal>(pub Arc<T>);
+                    impl<
+                        T: CollectionsInternal,
+                    > tonic::server::UnaryService<
+                        super::GetCollectionInfoRequestInternal,
+                    > for GetSvc<T> {
+                        type Response = super::GetCollectionInfoResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<
+                                super::GetCollectionInfoRequestInternal,
+                            >,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as CollectionsInternal>::get(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = GetSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/qdrant.CollectionsInternal/Initiate" => {
+                    #[allow(non_camel_case_types)]
+                    struct InitiateSvc<T: CollectionsInternal>(pub Arc<T>);
+                    impl<
+                        T: CollectionsInternal,
+                    > tonic::server::UnaryService<super::InitiateShardTransferRequest>
+                    for InitiateSvc<T> {
+                        type Response = super::CollectionOperationResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::InitiateShardTransferRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as CollectionsInternal>::initiate(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = InitiateSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/qdrant.CollectionsInternal/WaitForShardState" => {
+                    #[allow(non_camel_case_types)]
+                    struct WaitForShardStateSvc<T: CollectionsInternal>(pub Arc<T>);
+                    impl<
+                        T: CollectionsInternal,
+                    > tonic::server::UnaryService<super::WaitForShardStateRequest>
+                    for WaitForShardStateSvc<T> {
+                        type Response = super::CollectionOperationResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::WaitForShardStateRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as CollectionsInternal>::wait_for_shard_state(
+                                        &inner,
+                                        request,
+                                    )
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = WaitForShardStateSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                _ => {
+                    Box::pin(async move {
+                        Ok(
+                            http::Response::builder()
+                                .status(200)
+                                .header("grpc-status", "12")
+                                .header("content-type", "application/grpc")
+                                .body(empty_body())
+                                .unwrap(),
+                        )
+                    })
+                }
+            }
         }
     }
-}
-#[derive(serde::Serialize)]
-#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
-#[repr(i32)]
-pub enum UpdateStatus {
-    UnknownUpdateStatus = 0,
-    /// Update is received, but not processed yet
-    Acknowledged = 1,
-    /// Update is applied and ready for search
-    Completed = 2,
-}
-impl UpdateStatus {
-    /// String value of the enum field names used in the ProtoBuf definition.
-    ///
-    /// The values are not transformed in any way and thus are considered stable
-    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
-    pub fn as_str_name(&self) -> &'static str {
-        match self {
-            UpdateStatus::UnknownUpdateStatus => "UnknownUpdateStatus",
-            UpdateStatus::Acknowledged => "Acknowledged",
-            UpdateStatus::Completed => "Completed",
+    impl<T: CollectionsInternal> Clone for CollectionsInternalServer<T> {
+        fn clone(&self) -> Self {
+            let inner = self.inner.clone();
+            Self {
+                inner,
+                accept_compression_encodings: self.accept_compression_encodings,
+                send_compression_encodings: self.send_compression_encodings,
+                max_decoding_message_size: self.max_decoding_message_size,
+                max_encoding_message_size: self.max_encoding_message_size,
+            }
+        }
+    }
+    impl<T: CollectionsInternal> Clone for _Inner<T> {
+        fn clone(&self) -> Self {
+            Self(Arc::clone(&self.0))
         }
     }
-    /// Creates an enum from field names used in the ProtoBuf definition.
-    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
-        match value {
-            "UnknownUpdateStatus" => Some(Self::UnknownUpdateStatus),
-            "Acknowledged" => Some(Self::Acknowledged),
-            "Completed" => Some(Self::Completed),
-            _ => None,
+    impl<T: std::fmt::Debug> std::fmt::Debug for _Inner<T> {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "{:?}", self.0)
         }
     }
+    impl<T: CollectionsInternal> tonic::server::NamedService
+    for CollectionsInternalServer<T> {
+        const NAME: &'static str = "qdrant.CollectionsInternal";
+    }
 }
 /// Generated client implementations.
 pub mod points_client {
@@ -5240,6 +6677,87 @@ pub mod points_client {
             self.inner.unary(req, path, codec).await
         }
         ///
+        /// Restore soft-deleted points, which are not yet deleted permanently
+        pub async fn undelete(
+            &mut self,
+            request: impl tonic::IntoRequest<super::UndeletePoints>,
+        ) -> std::result::Result<
+            tonic::Response<super::PointsOperationResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/qdrant.Points/Undelete",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("qdrant.Points", "Undelete"));
+            self.inner.unary(req, path, codec).await
+        }
+        ///
+        /// Delete points matching the filter in batches, waiting for each batch to be applied
+        pub async fn delete_by_filter(
+            &mut self,
+            request: impl tonic::IntoRequest<super::DeleteByFilterPoints>,
+        ) -> std::result::Result<
+            tonic::Response<super::FilterUpdateResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/qdrant.Points/DeleteByFilter",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("qdrant.Points", "DeleteByFilter"));
+            self.inner.unary(req, path, codec).await
+        }
+        ///
+        /// Set payload of the points matching the filter in batches, waiting for each batch to be applied
+        pub async fn set_payload_by_filter(
+            &mut self,
+            request: impl tonic::IntoRequest<super::SetPayloadByFilterPoints>,
+        ) -> std::result::Result<
+            tonic::Response<super::FilterUpdateResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/qdrant.Points/SetPayloadByFilter",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("qdrant.Points", "SetPayloadByFilter"));
+            self.inner.unary(req, path, codec).await
+        }
+        ///
         /// Create index for field in collection
         pub async fn create_field_index(
             &mut self,
@@ -5368,6 +6886,33 @@ pub mod points_client {
             self.inner.unary(req, path, codec).await
         }
         ///
+        /// Retrieve closest points based on vector similarity and given filtering conditions, page by page using a cursor
+        pub async fn search_page(
+            &mut self,
+            request: impl tonic::IntoRequest<super::SearchPointsPage>,
+        ) -> std::result::Result<
+            tonic::Response<super::SearchPageResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/qdrant.Points/SearchPage",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("qdrant.Points", "SearchPage"));
+            self.inner.unary(req, path, codec).await
+        }
+        ///
         /// Iterate over all or filtered points
         pub async fn scroll(
             &mut self,
@@ -5666,6 +7211,33 @@ pub mod points_server {
             tonic::Status,
         >;
         ///
+        /// Restore soft-deleted points, which are not yet deleted permanently
+        async fn undelete(
+            &self,
+            request: tonic::Request<super::UndeletePoints>,
+        ) -> std::result::Result<
+            tonic::Response<super::PointsOperationResponse>,
+            tonic::Status,
+        >;
+        ///
+        /// Delete points matching the filter in batches, waiting for each batch to be applied
+        async fn delete_by_filter(
+            &self,
+            request: tonic::Request<super::DeleteByFilterPoints>,
+        ) -> std::result::Result<
+            tonic::Response<super::FilterUpdateResponse>,
+            tonic::Status,
+        >;
+        ///
+        /// Set payload of the points matching the filter in batches, waiting for each batch to be applied
+        async fn set_payload_by_filter(
+            &self,
+            request: tonic::Request<super::SetPayloadByFilterPoints>,
+        ) -> std::result::Result<
+            tonic::Response<super::FilterUpdateResponse>,
+            tonic::Status,
+        >;
+        ///
         /// Create index for field in collection
         async fn create_field_index(
             &self,
@@ -5708,6 +7280,15 @@ pub mod points_server {
             tonic::Status,
         >;
         ///
+        /// Retrieve closest points based on vector similarity and given filtering conditions, page by page using a cursor
+        async fn search_page(
+            &self,
+            request: tonic::Request<super::SearchPointsPage>,
+        ) -> std::result::Result<
+            tonic::Response<super::SearchPageResponse>,
+            tonic::Status,
+        >;
+        ///
         /// Iterate over all or filtered points
         async fn scroll(
             &self,
@@ -5923,11 +7504,147 @@ pub mod points_server {
                         >;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::DeletePoints>,
+                            request: tonic::Request<super::DeletePoints>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as Points>::delete(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = DeleteSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/qdrant.Points/Get" => {
+                    #[allow(non_camel_case_types)]
+                    struct GetSvc<T: Points>(pub Arc<T>);
+                    impl<T: Points> tonic::server::UnaryService<super::GetPoints>
+                    for GetSvc<T> {
+                        type Response = super::GetResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::GetPoints>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as Points>::get(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = GetSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/qdrant.Points/UpdateVectors" => {
+                    #[allow(non_camel_case_types)]
+                    struct UpdateVectorsSvc<T: Points>(pub Arc<T>);
+                    impl<
+                        T: Points,
+                    > tonic::server::UnaryService<super::UpdatePointVectors>
+                    for UpdateVectorsSvc<T> {
+                        type Response = super::PointsOperationResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::UpdatePointVectors>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as Points>::update_vectors(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = UpdateVectorsSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/qdrant.Points/DeleteVectors" => {
+                    #[allow(non_camel_case_types)]
+                    struct DeleteVectorsSvc<T: Points>(pub Arc<T>);
+                    impl<
+                        T: Points,
+                    > tonic::server::UnaryService<super::DeletePointVectors>
+                    for DeleteVectorsSvc<T> {
+                        type Response = super::PointsOperationResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::DeletePointVectors>,
                         ) -> Self::Future {
                             let inner = Arc::clone(&self.0);
                             let fut = async move {
-                                <T as Points>::delete(&inner, request).await
+                                <T as Points>::delete_vectors(&inner, request).await
                             };
                             Box::pin(fut)
                         }
@@ -5939,7 +7656,7 @@ pub mod points_server {
                     let inner = self.inner.clone();
                     let fut = async move {
                         let inner = inner.0;
-                        let method = DeleteSvc(inner);
+                        let method = DeleteVectorsSvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec)
                             .apply_compression_config(
@@ -5955,23 +7672,23 @@ pub mod points_server {
                     };
                     Box::pin(fut)
                 }
-                "/qdrant.Points/Get" => {
+                "/qdrant.Points/SetPayload" => {
                     #[allow(non_camel_case_types)]
-                    struct GetSvc<T: Points>(pub Arc<T>);
-                    impl<T: Points> tonic::server::UnaryService<super::GetPoints>
-                    for GetSvc<T> {
-                        type Response = super::GetResponse;
+                    struct SetPayloadSvc<T: Points>(pub Arc<T>);
+                    impl<T: Points> tonic::server::UnaryService<super::SetPayloadPoints>
+                    for SetPayloadSvc<T> {
+                        type Response = super::PointsOperationResponse;
                         type Future = BoxFuture<
                             tonic::Response<Self::Response>,
                             tonic::Status,
                         >;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::GetPoints>,
+                            request: tonic::Request<super::SetPayloadPoints>,
                         ) -> Self::Future {
                             let inner = Arc::clone(&self.0);
                             let fut = async move {
-                                <T as Points>::get(&inner, request).await
+                                <T as Points>::set_payload(&inner, request).await
                             };
                             Box::pin(fut)
                         }
@@ -5983,7 +7700,7 @@ pub mod points_server {
                     let inner = self.inner.clone();
                     let fut = async move {
                         let inner = inner.0;
-                        let method = GetSvc(inner);
+                        let method = SetPayloadSvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec)
                             .apply_compression_config(
@@ -5999,13 +7716,11 @@ pub mod points_server {
                     };
                     Box::pin(fut)
                 }
-                "/qdrant.Points/UpdateVectors" => {
+                "/qdrant.Points/OverwritePayload" => {
                     #[allow(non_camel_case_types)]
-                    struct UpdateVectorsSvc<T: Points>(pub Arc<T>);
-                    impl<
-                        T: Points,
-                    > tonic::server::UnaryService<super::UpdatePointVectors>
-                    for UpdateVectorsSvc<T> {
+                    struct OverwritePayloadSvc<T: Points>(pub Arc<T>);
+                    impl<T: Points> tonic::server::UnaryService<super::SetPayloadPoints>
+                    for OverwritePayloadSvc<T> {
                         type Response = super::PointsOperationResponse;
                         type Future = BoxFuture<
                             tonic::Response<Self::Response>,
@@ -6013,11 +7728,11 @@ pub mod points_server {
                         >;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::UpdatePointVectors>,
+                            request: tonic::Request<super::SetPayloadPoints>,
                         ) -> Self::Future {
                             let inner = Arc::clone(&self.0);
                             let fut = async move {
-                                <T as Points>::update_vectors(&inner, request).await
+                                <T as Points>::overwrite_payload(&inner, request).await
                             };
                             Box::pin(fut)
                         }
@@ -6029,7 +7744,7 @@ pub mod points_server {
                     let inner = self.inner.clone();
                     let fut = async move {
                         let inner = inner.0;
-                        let method = UpdateVectorsSvc(inner);
+                        let method = OverwritePayloadSvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec)
                             .apply_compression_config(
@@ -6045,13 +7760,13 @@ pub mod points_server {
                     };
                     Box::pin(fut)
                 }
-                "/qdrant.Points/DeleteVectors" => {
+                "/qdrant.Points/DeletePayload" => {
                     #[allow(non_camel_case_types)]
-                    struct DeleteVectorsSvc<T: Points>(pub Arc<T>);
+                    struct DeletePayloadSvc<T: Points>(pub Arc<T>);
                     impl<
                         T: Points,
-                    > tonic::server::UnaryService<super::DeletePointVectors>
-                    for DeleteVectorsSvc<T> {
+                    > tonic::server::UnaryService<super::DeletePayloadPoints>
+                    for DeletePayloadSvc<T> {
                         type Response = super::PointsOperationResponse;
                         type Future = BoxFuture<
                             tonic::Response<Self::Response>,
@@ -6059,11 +7774,11 @@ pub mod points_server {
                         >;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::DeletePointVectors>,
+                            request: tonic::Request<super::DeletePayloadPoints>,
                         ) -> Self::Future {
                             let inner = Arc::clone(&self.0);
                             let fut = async move {
-                                <T as Points>::delete_vectors(&inner, request).await
+                                <T as Points>::delete_payload(&inner, request).await
                             };
                             Box::pin(fut)
                         }
@@ -6075,7 +7790,7 @@ pub mod points_server {
                     let inner = self.inner.clone();
                     let fut = async move {
                         let inner = inner.0;
-                        let method = DeleteVectorsSvc(inner);
+                        let method = DeletePayloadSvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec)
                             .apply_compression_config(
@@ -6091,11 +7806,13 @@ pub mod points_server {
                     };
                     Box::pin(fut)
                 }
-                "/qdrant.Points/SetPayload" => {
+                "/qdrant.Points/ClearPayload" => {
                     #[allow(non_camel_case_types)]
-                    struct SetPayloadSvc<T: Points>(pub Arc<T>);
-                    impl<T: Points> tonic::server::UnaryService<super::SetPayloadPoints>
-                    for SetPayloadSvc<T> {
+                    struct ClearPayloadSvc<T: Points>(pub Arc<T>);
+                    impl<
+                        T: Points,
+                    > tonic::server::UnaryService<super::ClearPayloadPoints>
+                    for ClearPayloadSvc<T> {
                         type Response = super::PointsOperationResponse;
                         type Future = BoxFuture<
                             tonic::Response<Self::Response>,
@@ -6103,11 +7820,11 @@ pub mod points_server {
                         >;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::SetPayloadPoints>,
+                            request: tonic::Request<super::ClearPayloadPoints>,
                         ) -> Self::Future {
                             let inner = Arc::clone(&self.0);
                             let fut = async move {
-                                <T as Points>::set_payload(&inner, request).await
+                                <T as Points>::clear_payload(&inner, request).await
                             };
                             Box::pin(fut)
                         }
@@ -6119,7 +7836,7 @@ pub mod points_server {
                     let inner = self.inner.clone();
                     let fut = async move {
                         let inner = inner.0;
-                        let method = SetPayloadSvc(inner);
+                        let method = ClearPayloadSvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec)
                             .apply_compression_config(
@@ -6135,11 +7852,13 @@ pub mod points_server {
                     };
                     Box::pin(fut)
                 }
-                "/qdrant.Points/OverwritePayload" => {
+                "/qdrant.Points/Undelete" => {
                     #[allow(non_camel_case_types)]
-                    struct OverwritePayloadSvc<T: Points>(pub Arc<T>);
-                    impl<T: Points> tonic::server::UnaryService<super::SetPayloadPoints>
-                    for OverwritePayloadSvc<T> {
+                    struct UndeleteSvc<T: Points>(pub Arc<T>);
+                    impl<
+                        T: Points,
+                    > tonic::server::UnaryService<super::UndeletePoints>
+                    for UndeleteSvc<T> {
                         type Response = super::PointsOperationResponse;
                         type Future = BoxFuture<
                             tonic::Response<Self::Response>,
@@ -6147,11 +7866,11 @@ pub mod points_server {
                         >;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::SetPayloadPoints>,
+                            request: tonic::Request<super::UndeletePoints>,
                         ) -> Self::Future {
                             let inner = Arc::clone(&self.0);
                             let fut = async move {
-                                <T as Points>::overwrite_payload(&inner, request).await
+                                <T as Points>::undelete(&inner, request).await
                             };
                             Box::pin(fut)
                         }
@@ -6163,7 +7882,7 @@ pub mod points_server {
                     let inner = self.inner.clone();
                     let fut = async move {
                         let inner = inner.0;
-                        let method = OverwritePayloadSvc(inner);
+                        let method = UndeleteSvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec)
                             .apply_compression_config(
@@ -6179,25 +7898,25 @@ pub mod points_server {
                     };
                     Box::pin(fut)
                 }
-                "/qdrant.Points/DeletePayload" => {
+                "/qdrant.Points/DeleteByFilter" => {
                     #[allow(non_camel_case_types)]
-                    struct DeletePayloadSvc<T: Points>(pub Arc<T>);
+                    struct DeleteByFilterSvc<T: Points>(pub Arc<T>);
                     impl<
                         T: Points,
-                    > tonic::server::UnaryService<super::DeletePayloadPoints>
-                    for DeletePayloadSvc<T> {
-                        type Response = super::PointsOperationResponse;
+                    > tonic::server::UnaryService<super::DeleteByFilterPoints>
+                    for DeleteByFilterSvc<T> {
+                        type Response = super::FilterUpdateResponse;
                         type Future = BoxFuture<
                             tonic::Response<Self::Response>,
                             tonic::Status,
                         >;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::DeletePayloadPoints>,
+                            request: tonic::Request<super::DeleteByFilterPoints>,
                         ) -> Self::Future {
                             let inner = Arc::clone(&self.0);
                             let fut = async move {
-                                <T as Points>::delete_payload(&inner, request).await
+                                <T as Points>::delete_by_filter(&inner, request).await
                             };
                             Box::pin(fut)
                         }
@@ -6209,7 +7928,7 @@ pub mod points_server {
                     let inner = self.inner.clone();
                     let fut = async move {
                         let inner = inner.0;
-                        let method = DeletePayloadSvc(inner);
+                        let method = DeleteByFilterSvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec)
                             .apply_compression_config(
@@ -6225,25 +7944,25 @@ pub mod points_server {
                     };
                     Box::pin(fut)
                 }
-                "/qdrant.Points/ClearPayload" => {
+                "/qdrant.Points/SetPayloadByFilter" => {
                     #[allow(non_camel_case_types)]
-                    struct ClearPayloadSvc<T: Points>(pub Arc<T>);
+                    struct SetPayloadByFilterSvc<T: Points>(pub Arc<T>);
                     impl<
                         T: Points,
-                    > tonic::server::UnaryService<super::ClearPayloadPoints>
-                    for ClearPayloadSvc<T> {
-                        type Response = super::PointsOperationResponse;
+                    > tonic::server::UnaryService<super::SetPayloadByFilterPoints>
+                    for SetPayloadByFilterSvc<T> {
+                        type Response = super::FilterUpdateResponse;
                         type Future = BoxFuture<
                             tonic::Response<Self::Response>,
                             tonic::Status,
                         >;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::ClearPayloadPoints>,
+                            request: tonic::Request<super::SetPayloadByFilterPoints>,
                         ) -> Self::Future {
                             let inner = Arc::clone(&self.0);
                             let fut = async move {
-                                <T as Points>::clear_payload(&inner, request).await
+                                <T as Points>::set_payload_by_filter(&inner, request).await
                             };
                             Box::pin(fut)
                         }
@@ -6255,7 +7974,7 @@ pub mod points_server {
                     let inner = self.inner.clone();
                     let fut = async move {
                         let inner = inner.0;
-                        let method = ClearPayloadSvc(inner);
+                        let method = SetPayloadByFilterSvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec)
                             .apply_compression_config(
@@ -6495,6 +8214,50 @@ pub mod points_server {
                     };
                     Box::pin(fut)
                 }
+                "/qdrant.Points/SearchPage" => {
+                    #[allow(non_camel_case_types)]
+                    struct SearchPageSvc<T: Points>(pub Arc<T>);
+                    impl<T: Points> tonic::server::UnaryService<super::SearchPointsPage>
+                    for SearchPageSvc<T> {
+                        type Response = super::SearchPageResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::SearchPointsPage>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as Points>::search_page(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = SearchPageSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
                 "/qdrant.Points/Scroll" => {
                     #[allow(non_camel_case_types)]
                     struct ScrollSvc<T: Points>(pub Arc<T>);
@@ -6916,6 +8679,19 @@ pub struct SyncPoints {
     pub to_id: ::core::option::Option<PointId>,
     #[prost(message, optional, tag = "6")]
     pub ordering: ::core::option::Option<WriteOrdering>,
+    /// Max number of seconds to wait for the deactivation of replicas, which failed to apply the update
+    #[prost(uint64, optional, tag = "7")]
+    pub shard_deactivation_timeout: ::core::option::Option<u64>,
+    /// Number of replicas, which must apply the update for it to succeed, overrides the collection setting
+    #[prost(message, optional, tag = "8")]
+    pub write_consistency: ::core::option::Option<WriteConsistency>,
+    /// Max number of seconds to wait for the replicas to apply the update, those which didn't are reported as failed
+    #[prost(uint64, optional, tag = "9")]
+    #[validate(custom = "crate::grpc::validate::validate_u64_range_min_1")]
+    pub timeout: ::core::option::Option<u64>,
+    /// Priority of the update, interactive updates are not queued behind bulk ones
+    #[prost(enumeration = "UpdatePriority", optional, tag = "10")]
+    pub priority: ::core::option::Option<i32>,
 }
 #[derive(serde::Serialize)]
 #[derive(validator::Validate)]
@@ -7035,6 +8811,17 @@ pub struct DeleteFieldIndexCollectionInternal {
 #[derive(validator::Validate)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
+pub struct UpdateBatchInternal {
+    #[prost(message, optional, tag = "1")]
+    #[validate]
+    pub update_batch: ::core::option::Option<UpdateBatchPoints>,
+    #[prost(uint32, optional, tag = "2")]
+    pub shard_id: ::core::option::Option<u32>,
+}
+#[derive(serde::Serialize)]
+#[derive(validator::Validate)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
 pub struct SearchPointsInternal {
     #[prost(message, optional, tag = "1")]
     #[validate]
@@ -7162,6 +8949,63 @@ pub struct CoreSearchPoints {
     pub with_vectors: ::core::option::Option<WithVectorsSelector>,
     #[prost(message, optional, tag = "11")]
     pub read_consistency: ::core::option::Option<ReadConsistency>,
+    /// First stage of the search, which selects candidates for the query
+    #[prost(message, optional, tag = "12")]
+    #[validate]
+    pub prefetch: ::core::option::Option<CorePrefetch>,
+    /// Formula to rescore the best candidates in each shard with
+    #[prost(message, optional, tag = "13")]
+    #[validate]
+    pub rescore: ::core::option::Option<CoreRescore>,
+    /// Payload field to deduplicate the results by
+    #[prost(message, optional, tag = "14")]
+    #[validate]
+    pub distinct_by: ::core::option::Option<CoreDistinct>,
+}
+#[derive(serde::Serialize)]
+#[derive(validator::Validate)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CorePrefetch {
+    #[prost(message, optional, tag = "1")]
+    pub query: ::core::option::Option<QueryEnum>,
+    #[prost(string, optional, tag = "2")]
+    pub vector_name: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(message, optional, tag = "3")]
+    #[validate]
+    pub params: ::core::option::Option<SearchParams>,
+    /// Number of candidates to select in each segment
+    #[prost(uint64, tag = "4")]
+    #[validate(range(min = 1))]
+    pub limit: u64,
+}
+#[derive(serde::Serialize)]
+#[derive(validator::Validate)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CoreRescore {
+    /// Formula of the vector score and payload fields
+    #[prost(string, tag = "1")]
+    #[validate(length(min = 1))]
+    pub formula: ::prost::alloc::string::String,
+    /// Number of best candidates by the vector score, rescored in each shard
+    #[prost(uint64, tag = "2")]
+    #[validate(range(min = 1))]
+    pub candidates: u64,
+}
+#[derive(serde::Serialize)]
+#[derive(validator::Validate)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CoreDistinct {
+    /// Payload field, only the best point is kept for each of its values
+    #[prost(string, tag = "1")]
+    #[validate(length(min = 1))]
+    pub key: ::prost::alloc::string::String,
+    /// Number of best candidates, deduplicated in each shard
+    #[prost(uint64, tag = "2")]
+    #[validate(range(min = 1))]
+    pub candidates: u64,
 }
 #[derive(serde::Serialize)]
 #[derive(validator::Validate)]
@@ -7583,6 +9427,31 @@ pub mod points_internal_client {
                 .insert(GrpcMethod::new("qdrant.PointsInternal", "DeleteFieldIndex"));
             self.inner.unary(req, path, codec).await
         }
+        pub async fn update_batch(
+            &mut self,
+            request: impl tonic::IntoRequest<super::UpdateBatchInternal>,
+        ) -> std::result::Result<
+            tonic::Response<super::PointsOperationResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/qdrant.PointsInternal/UpdateBatch",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("qdrant.PointsInternal", "UpdateBatch"));
+            self.inner.unary(req, path, codec).await
+        }
         pub async fn search(
             &mut self,
             request: impl tonic::IntoRequest<super::SearchPointsInternal>,
@@ -7831,6 +9700,13 @@ pub mod points_internal_server {
             tonic::Response<super::PointsOperationResponse>,
             tonic::Status,
         >;
+        async fn update_batch(
+            &self,
+            request: tonic::Request<super::UpdateBatchInternal>,
+        ) -> std::result::Result<
+            tonic::Response<super::PointsOperationResponse>,
+            tonic::Status,
+        >;
         async fn search(
             &self,
             request: tonic::Request<super::SearchPointsInternal>,
@@ -8463,6 +10339,52 @@ pub mod points_internal_server {
                     };
                     Box::pin(fut)
                 }
+                "/qdrant.PointsInternal/UpdateBatch" => {
+                    #[allow(non_camel_case_types)]
+                    struct UpdateBatchSvc<T: PointsInternal>(pub Arc<T>);
+                    impl<
+                        T: PointsInternal,
+                    > tonic::server::UnaryService<super::UpdateBatchInternal>
+                    for UpdateBatchSvc<T> {
+                        type Response = super::PointsOperationResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::UpdateBatchInternal>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as PointsInternal>::update_batch(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = UpdateBatchSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
                 "/qdrant.PointsInternal/Search" => {
                     #[allow(non_camel_case_types)]
                     struct SearchSvc<T: PointsInternal>(pub Arc<T>);
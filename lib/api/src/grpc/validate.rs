@@ -161,11 +161,18 @@ impl Validate for crate::grpc::qdrant::condition::ConditionOneOf {
         use crate::grpc::qdrant::condition::ConditionOneOf;
         match self {
             ConditionOneOf::Field(field_condition) => field_condition.validate(),
-            ConditionOneOf::Nested(nested) => nested.validate(),
+            ConditionOneOf::Nested(nested) => {
+                segment::types::validate_payload_key(&nested.key)?;
+                nested.validate()
+            }
             ConditionOneOf::Filter(filter) => filter.validate(),
-            ConditionOneOf::IsEmpty(_) => Ok(()),
+            ConditionOneOf::IsEmpty(condition) => {
+                segment::types::validate_payload_key(&condition.key)
+            }
             ConditionOneOf::HasId(_) => Ok(()),
-            ConditionOneOf::IsNull(_) => Ok(()),
+            ConditionOneOf::IsNull(condition) => {
+                segment::types::validate_payload_key(&condition.key)
+            }
         }
     }
 }
@@ -187,17 +194,39 @@ impl Validate for crate::grpc::qdrant::FieldCondition {
             );
             Err(errors)
         } else {
-            Ok(())
+            segment::types::validate_payload_key(&self.key)
         }
     }
 }
 
 impl Validate for crate::grpc::qdrant::Vector {
     fn validate(&self) -> Result<(), ValidationErrors> {
-        if let Some(indices) = &self.indices {
-            sparse::common::sparse_vector::validate_sparse_vector_impl(&indices.data, &self.data)
-        } else {
-            Ok(())
+        match (&self.indices, self.vectors_count) {
+            (Some(indices), None) => sparse::common::sparse_vector::validate_sparse_vector_impl(
+                &indices.data,
+                &self.data,
+            ),
+            (None, Some(vectors_count)) => {
+                if vectors_count == 0 || self.data.len() % vectors_count as usize != 0 {
+                    let mut errors = ValidationErrors::new();
+                    errors.add(
+                        "vectors_count",
+                        ValidationError::new("must split data into vectors of the same size"),
+                    );
+                    Err(errors)
+                } else {
+                    Ok(())
+                }
+            }
+            (Some(_), Some(_)) => {
+                let mut errors = ValidationErrors::new();
+                errors.add(
+                    "vectors_count",
+                    ValidationError::new("must not be set for sparse vector"),
+                );
+                Err(errors)
+            }
+            (None, None) => Ok(()),
         }
     }
 }
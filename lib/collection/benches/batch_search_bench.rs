@@ -37,6 +37,7 @@ fn create_rnd_batch() -> CollectionUpdateOperations {
             id: (i as u64).into(),
             vector: vectors.into(),
             payload: Some(Payload(payload_map)),
+            if_version: None,
         };
         points.push(point);
     }
@@ -56,6 +57,7 @@ fn batch_search_bench(c: &mut Criterion) {
     let wal_config = WalConfig {
         wal_capacity_mb: 1,
         wal_segments_ahead: 0,
+        wal_compression: Default::default(),
     };
 
     let collection_params = CollectionParams {
@@ -65,6 +67,10 @@ fn batch_search_bench(c: &mut Criterion) {
             hnsw_config: None,
             quantization_config: None,
             on_disk: None,
+            normalize: None,
+            dimensionality_reduction: None,
+            multivector_config: None,
+            datatype: None,
         }
         .into(),
         ..CollectionParams::empty()
@@ -80,11 +86,16 @@ fn batch_search_bench(c: &mut Criterion) {
             memmap_threshold: Some(100_000),
             indexing_threshold: Some(50_000),
             flush_interval_sec: 30,
+            flush_dirty_threshold: None,
+            flush_dirty_operations: None,
             max_optimization_threads: 2,
+            max_config_rebuild_segments: None,
         },
         wal_config,
         hnsw_config: Default::default(),
         quantization_config: Default::default(),
+        strict_mode_config: Default::default(),
+        feature_flags: None,
     };
 
     let shared_config = Arc::new(RwLock::new(collection_config));
@@ -102,7 +113,9 @@ fn batch_search_bench(c: &mut Criterion) {
 
     let rnd_batch = create_rnd_batch();
 
-    handle.block_on(shard.update(rnd_batch, true)).unwrap();
+    handle
+        .block_on(shard.update(rnd_batch, true, None))
+        .unwrap();
 
     let mut group = c.benchmark_group("batch-search-bench");
 
@@ -0,0 +1,64 @@
+//! Point-level access tags: requests restricted to a set of tags only see points, which have at
+//! least one of them in the [`ACCESS_TAGS_KEY`] payload field.
+//!
+//! The API layer reads the tags from the metadata of the API key and passes them along with the
+//! read as [`AccessTags`]. The restriction is added to the filter of every read, so it is enforced
+//! by the segments themselves and can't be bypassed by the request.
+
+use std::sync::Arc;
+
+use segment::types::{AnyVariants, Condition, FieldCondition, Filter, Match};
+
+/// Reserved payload field, which holds the access tags of the point
+pub const ACCESS_TAGS_KEY: &str = "access_tags";
+
+/// Access tags of the request, it could only read points with any of them
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AccessTags(pub Arc<Vec<String>>);
+
+/// Restrict the filter to points, which are visible with the access tags of the request
+///
+/// Requests without access tags, like internal ones, are not restricted.
+pub(crate) fn restrict_by_access_tags(
+    filter: Option<Filter>,
+    access_tags: Option<&AccessTags>,
+) -> Option<Filter> {
+    match access_tags {
+        None => filter,
+        Some(AccessTags(tags)) => Some(restrict_by_tags(filter, tags)),
+    }
+}
+
+/// Add a condition, which matches points with any of the given tags, to the filter
+fn restrict_by_tags(filter: Option<Filter>, tags: &[String]) -> Filter {
+    let allowed = Filter::new_must(Condition::Field(FieldCondition::new_match(
+        ACCESS_TAGS_KEY,
+        Match::new_any(AnyVariants::Keywords(tags.to_vec())),
+    )));
+    match filter {
+        None => allowed,
+        Some(filter) => filter.merge(&allowed),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_restrict_by_access_tags() {
+        assert_eq!(restrict_by_access_tags(None, None), None);
+
+        let tags = AccessTags(Arc::new(vec!["team-a".to_string()]));
+        let filter = restrict_by_access_tags(None, Some(&tags));
+        assert_eq!(
+            filter,
+            Some(Filter::new_must(Condition::Field(
+                FieldCondition::new_match(
+                    ACCESS_TAGS_KEY,
+                    Match::new_any(AnyVariants::Keywords(vec!["team-a".to_string()])),
+                )
+            ))),
+        );
+    }
+}
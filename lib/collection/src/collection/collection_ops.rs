@@ -1,18 +1,96 @@
 use std::cmp;
 use std::sync::Arc;
+use std::time::Instant;
 
 use futures::{future, TryStreamExt as _};
+use merge::Merge;
 use segment::types::QuantizationConfig;
+use validator::Validate as _;
 
 use super::Collection;
+use crate::config::StrictModeConfig;
 use crate::operations::config_diff::*;
+use crate::operations::feature_flags::FeatureFlags;
 use crate::operations::shard_selector_internal::ShardSelectorInternal;
+use crate::operations::strict_mode::{
+    check_batch_size, check_quotas, check_update_operation, is_growing_operation, QuotaUsage,
+    StrictModeVerification, QUOTA_USAGE_REFRESH_INTERVAL,
+};
 use crate::operations::types::*;
+use crate::operations::CollectionUpdateOperations;
 use crate::optimizers_builder::OptimizersConfig;
 use crate::shards::replica_set::{Change, ReplicaState};
-use crate::shards::shard::PeerId;
+use crate::shards::shard::{PeerId, ShardId};
+use crate::shards::shard_holder::shard_not_found_error;
 
 impl Collection {
+    /// Checks that the configuration update is applicable to this collection and that the
+    /// resulting configuration is valid, without applying it
+    ///
+    /// Allows to reject invalid updates before they are distributed across the cluster, so that an
+    /// update is never applied partially.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn check_config_update(
+        &self,
+        params_diff: Option<&CollectionParamsDiff>,
+        hnsw_config_diff: Option<&HnswConfigDiff>,
+        optimizer_config_diff: Option<&OptimizersConfigDiff>,
+        vectors_diff: Option<&VectorsConfigDiff>,
+        sparse_vectors_diff: Option<&SparseVectorsConfig>,
+        new_vectors: Option<&NewVectorsConfig>,
+        feature_flags_diff: Option<&FeatureFlags>,
+    ) -> CollectionResult<()> {
+        let config = self.collection_config.read().await;
+
+        // Newly selected experimental features must be enabled by the updated flags
+        let mut feature_flags = feature_flags_diff.copied().unwrap_or_default();
+        if let Some(current) = config.feature_flags {
+            feature_flags.merge(current);
+        }
+        let feature_flags = FeatureFlags::resolve(
+            Some(&feature_flags),
+            &self.shared_storage_config.feature_flags,
+        );
+        if let Some(diff) = params_diff {
+            feature_flags.check_payload_storage_backend(diff.payload_storage_backend)?;
+        }
+        if let Some(new_vectors) = new_vectors {
+            feature_flags.check_vectors(new_vectors.0.values())?;
+        }
+
+        let mut params = match params_diff {
+            Some(diff) => diff.clone().update(&config.params)?,
+            None => config.params.clone(),
+        };
+        if let Some(diff) = vectors_diff {
+            diff.check_vector_names(&params)?;
+            params.update_vectors_from_diff(diff)?;
+        }
+        if let Some(diff) = sparse_vectors_diff {
+            diff.check_vector_names(&params)?;
+            params.update_sparse_vectors_from_other(diff)?;
+        }
+        if let Some(new_vectors) = new_vectors {
+            new_vectors.check_vector_names(&params)?;
+            params.add_vectors(new_vectors);
+        }
+        if let Some(storage_policy) = &params.storage_policy {
+            storage_policy.check_vector_names(&params)?;
+        }
+        if let Some(inference) = &params.inference {
+            inference.check_vector_name(&params)?;
+        }
+        params.validate()?;
+
+        if let Some(diff) = hnsw_config_diff {
+            diff.update(&config.hnsw_config)?.validate()?;
+        }
+        if let Some(diff) = optimizer_config_diff {
+            DiffConfig::update(diff.clone(), &config.optimizer_config)?.validate()?;
+        }
+        Ok(())
+    }
+
     /// Updates collection params:
     /// Saves new params on disk
     ///
@@ -83,6 +161,22 @@ impl Collection {
         Ok(())
     }
 
+    /// Adds new named vectors:
+    /// Saves new params on disk
+    ///
+    /// Existing points don't have the new vectors, they accept them on update. Existing segments
+    /// get storages for the new vectors when they are optimized.
+    ///
+    /// After this, `recreate_optimizers_blocking` must be called to create new optimizers and an
+    /// appendable segment with the new vectors.
+    pub async fn add_vectors(&self, new_vectors: &NewVectorsConfig) -> CollectionResult<()> {
+        let mut config = self.collection_config.write().await;
+        new_vectors.check_vector_names(&config.params)?;
+        config.params.add_vectors(new_vectors);
+        config.save(&self.path)?;
+        Ok(())
+    }
+
     /// Updates shard optimization params:
     /// Saves new params on disk
     ///
@@ -153,6 +247,144 @@ impl Collection {
         Ok(())
     }
 
+    /// Updates strict mode config:
+    /// Fields which are not specified in the diff are left unchanged. Saves new config on disk
+    ///
+    /// Takes effect on the next request, optimizers are not affected.
+    pub async fn update_strict_mode_config(
+        &self,
+        mut strict_mode_diff: StrictModeConfig,
+    ) -> CollectionResult<()> {
+        {
+            let mut config = self.collection_config.write().await;
+            if let Some(current) = config.strict_mode_config.take() {
+                strict_mode_diff.merge(current);
+            }
+            config.strict_mode_config = Some(strict_mode_diff);
+        }
+        self.collection_config.read().await.save(&self.path)?;
+        Ok(())
+    }
+
+    /// Updates flags of the experimental features:
+    /// Flags which are not specified in the diff are left unchanged. Saves new config on disk
+    pub async fn update_feature_flags(
+        &self,
+        mut feature_flags_diff: FeatureFlags,
+    ) -> CollectionResult<()> {
+        {
+            let mut config = self.collection_config.write().await;
+            if let Some(current) = config.feature_flags.take() {
+                feature_flags_diff.merge(current);
+            }
+            config.feature_flags = Some(feature_flags_diff);
+        }
+        self.collection_config.read().await.save(&self.path)?;
+        Ok(())
+    }
+
+    /// Flags of the experimental features of the collection, unset ones are taken from the node
+    pub async fn feature_flags(&self) -> FeatureFlags {
+        FeatureFlags::resolve(
+            self.collection_config.read().await.feature_flags.as_ref(),
+            &self.shared_storage_config.feature_flags,
+        )
+    }
+
+    pub(super) async fn enabled_strict_mode_config(&self) -> Option<StrictModeConfig> {
+        self.collection_config
+            .read()
+            .await
+            .strict_mode_config
+            .clone()
+            .filter(StrictModeConfig::is_enabled)
+    }
+
+    /// Check read requests against the strict mode restrictions of the collection, if enabled
+    ///
+    /// Multiple requests are considered a batch and are also restricted by the batch size.
+    pub async fn check_strict_mode<'a, R>(
+        &self,
+        requests: impl ExactSizeIterator<Item = &'a R>,
+    ) -> CollectionResult<()>
+    where
+        R: StrictModeVerification + 'a,
+    {
+        let Some(strict_mode_config) = self.enabled_strict_mode_config().await else {
+            return Ok(());
+        };
+        check_batch_size(requests.len(), &strict_mode_config)?;
+        let payload_index_schema = self.payload_index_schema.read();
+        for request in requests {
+            request.check_strict_mode(&strict_mode_config, &payload_index_schema.schema)?;
+        }
+        Ok(())
+    }
+
+    /// Check update operation against the strict mode restrictions of the collection, if enabled
+    ///
+    /// Operations, which might increase the amount of data, are also checked against the quotas
+    /// using the usage of the collection, estimated by [`Collection::quota_usage`].
+    pub async fn check_strict_mode_update(
+        &self,
+        operation: &CollectionUpdateOperations,
+    ) -> CollectionResult<()> {
+        let Some(strict_mode_config) = self.enabled_strict_mode_config().await else {
+            return Ok(());
+        };
+        {
+            let payload_index_schema = self.payload_index_schema.read();
+            check_update_operation(operation, &strict_mode_config, &payload_index_schema.schema)?;
+        }
+        if strict_mode_config.has_quotas() && is_growing_operation(operation) {
+            if let Some(usage) = self.quota_usage().await? {
+                check_quotas(
+                    Some(usage.points_count),
+                    Some(usage.disk_usage_bytes),
+                    &strict_mode_config,
+                )?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Usage of the whole collection, estimated from its local shards
+    ///
+    /// Remote shards are not requested on updates, each of them is assumed to hold as much data
+    /// as an average local shard. Usage is collected at most once per
+    /// [`QUOTA_USAGE_REFRESH_INTERVAL`], so quotas might be exceeded by the updates in between.
+    ///
+    /// Returns `None` if there are no local shards to estimate the usage by.
+    async fn quota_usage(&self) -> CollectionResult<Option<QuotaUsage>> {
+        if let Some((collected_at, usage)) = *self.quota_usage.lock() {
+            if collected_at.elapsed() < QUOTA_USAGE_REFRESH_INTERVAL {
+                return Ok(usage);
+            }
+        }
+
+        let shards_holder = self.shards_holder.read().await;
+        let mut local_usage = QuotaUsage::default();
+        let mut local_shards_count = 0;
+        for replica_set in shards_holder.all_shards() {
+            if !replica_set.has_local_shard().await {
+                continue;
+            }
+            let info = replica_set.info(true).await?;
+            local_usage.points_count += info.points_count.unwrap_or_default();
+            local_usage.disk_usage_bytes += info.disk_usage_bytes.unwrap_or_default();
+            local_shards_count += 1;
+        }
+        let shards_count = shards_holder.len();
+        drop(shards_holder);
+
+        let usage = (local_shards_count > 0).then(|| QuotaUsage {
+            points_count: local_usage.points_count * shards_count / local_shards_count,
+            disk_usage_bytes: local_usage.disk_usage_bytes * shards_count / local_shards_count,
+        });
+        *self.quota_usage.lock() = Some((Instant::now(), usage));
+        Ok(usage)
+    }
+
     /// Handle replica changes
     ///
     /// add and remove replicas from replica set
@@ -217,6 +449,65 @@ impl Collection {
         Ok(())
     }
 
+    /// Read local data of the collection into memory, to avoid slow first requests
+    ///
+    /// Shards are warmed up one by one, so the disk is not overloaded with concurrent reads.
+    pub async fn warm_up(&self) -> CollectionResult<WarmUpResult> {
+        let shard_holder = self.shards_holder.read().await;
+        let shards_count = shard_holder.len();
+        let mut result = WarmUpResult::default();
+        for (i, replica_set) in shard_holder.all_shards().enumerate() {
+            let shard_result = replica_set.warm_up().await?;
+            log::info!(
+                "Warmed up shard {} of collection {} ({}/{shards_count}): {} files, {} bytes",
+                replica_set.shard_id,
+                self.id,
+                i + 1,
+                shard_result.files,
+                shard_result.bytes,
+            );
+            result += shard_result;
+        }
+        Ok(result)
+    }
+
+    /// Merge small segments of the local shard on demand, e.g. after a bulk load
+    ///
+    /// The configured number of segments is not taken into account, only the maximum segment
+    /// size limits the merges.
+    pub async fn compact_shard(&self, shard_id: ShardId) -> CollectionResult<CompactionResult> {
+        let shard_holder = self.shards_holder.read().await;
+        let replica_set = shard_holder
+            .get_shard(&shard_id)
+            .ok_or_else(|| shard_not_found_error(shard_id))?;
+        replica_set.compact().await
+    }
+
+    /// Stop local shards for the node shutdown: apply queued updates, cancel optimizations and
+    /// flush all data to disk
+    ///
+    /// Shards are stopped one by one, waiting for their workers is limited by the `deadline`.
+    /// Errors are logged, so the remaining shards are still stopped.
+    pub async fn shutdown(&self, deadline: Instant) {
+        let shard_holder = self.shards_holder.read().await;
+        let shards_count = shard_holder.len();
+        for (i, replica_set) in shard_holder.all_shards().enumerate() {
+            match replica_set.shutdown(deadline).await {
+                Ok(()) => log::info!(
+                    "Stopped shard {} of collection {} ({}/{shards_count})",
+                    replica_set.shard_id,
+                    self.id,
+                    i + 1,
+                ),
+                Err(err) => log::error!(
+                    "Failed to stop shard {} of collection {}: {err}",
+                    replica_set.shard_id,
+                    self.id,
+                ),
+            }
+        }
+    }
+
     pub async fn info(
         &self,
         shard_selection: &ShardSelectorInternal,
@@ -250,6 +541,10 @@ impl Collection {
                 .zip(response.points_count)
                 .map(|(a, b)| a + b);
             info.segments_count += response.segments_count;
+            info.disk_usage_bytes = info
+                .disk_usage_bytes
+                .zip(response.disk_usage_bytes)
+                .map(|(a, b)| a + b);
 
             for (key, response_schema) in response.payload_schema {
                 info.payload_schema
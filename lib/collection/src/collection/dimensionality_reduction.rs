@@ -0,0 +1,352 @@
+//! Server-side dimensionality reduction of vectors, which have `dimensionality_reduction` in
+//! their params
+//!
+//! Vectors of the `input_size` are projected by the learned transform before the update is
+//! split between the shards, and query vectors before the search is sent to them, so shards only
+//! ever see reduced vectors. Vectors of any other size are left as they are: these are either
+//! already reduced, like the vectors looked up by the recommendations, or rejected by the usual
+//! dimension checks.
+
+use std::collections::{BTreeMap, HashMap};
+
+use segment::common::operation_error::OperationError;
+use segment::data_types::vectors::{
+    BatchVectorStruct, DenseVector, NamedMultiDenseVector, NamedQuery, NamedVector,
+    NamedVectorStruct, Vector, VectorStruct, DEFAULT_VECTOR_NAME,
+};
+use segment::vector_storage::query::TransformInto;
+
+use super::Collection;
+use crate::operations::dimensionality_reduction::{
+    DimensionalityReduction, LinearTransform, TrainDimensionalityReduction,
+};
+use crate::operations::point_ops::{PointInsertOperationsInternal, PointOperations};
+use crate::operations::types::{
+    CollectionError, CollectionResult, CoreSearchRequestBatch, QueryEnum, VectorParamsDiff,
+    VectorsConfigDiff,
+};
+use crate::operations::vector_ops::{UpdateVectorsOp, VectorOperations};
+use crate::operations::CollectionUpdateOperations;
+
+/// Dimensionality reductions by the names of the vectors
+type Reductions = HashMap<String, DimensionalityReduction>;
+
+impl Collection {
+    async fn dimensionality_reductions(&self) -> Reductions {
+        self.collection_config
+            .read()
+            .await
+            .params
+            .vectors
+            .params_iter()
+            .filter_map(|(name, params)| {
+                let reduction = params.dimensionality_reduction.clone()?;
+                Some((name.to_string(), reduction))
+            })
+            .collect()
+    }
+
+    /// Reduce inserted and updated vectors, which have `dimensionality_reduction` configured
+    pub(crate) async fn reduce_operation(
+        &self,
+        mut operation: CollectionUpdateOperations,
+    ) -> CollectionResult<CollectionUpdateOperations> {
+        let reductions = self.dimensionality_reductions().await;
+        if reductions.is_empty() {
+            return Ok(operation);
+        }
+
+        match &mut operation {
+            CollectionUpdateOperations::PointOperation(PointOperations::UpsertPoints(
+                PointInsertOperationsInternal::PointsBatch(batch),
+            )) => reduce_batch(&mut batch.vectors, &reductions)?,
+            CollectionUpdateOperations::PointOperation(PointOperations::UpsertPoints(
+                PointInsertOperationsInternal::PointsList(points),
+            )) => {
+                for point in points {
+                    reduce_vectors(&mut point.vector, &reductions)?;
+                }
+            }
+            CollectionUpdateOperations::VectorOperation(VectorOperations::UpdateVectors(
+                UpdateVectorsOp { points },
+            )) => {
+                for point in points {
+                    reduce_vectors(&mut point.vector, &reductions)?;
+                }
+            }
+            _ => {}
+        }
+
+        Ok(operation)
+    }
+
+    /// Reduce query vectors of the searches by vectors, which have `dimensionality_reduction`
+    /// configured
+    pub(crate) async fn reduce_search_batch(
+        &self,
+        request: &mut CoreSearchRequestBatch,
+    ) -> CollectionResult<()> {
+        let reductions = self.dimensionality_reductions().await;
+        if reductions.is_empty() {
+            return Ok(());
+        }
+
+        request.searches = std::mem::take(&mut request.searches)
+            .into_iter()
+            .map(|mut search| {
+                if let Some(reduction) = reductions.get(search.query.get_vector_name()) {
+                    search.query = reduce_query(search.query, reduction)?;
+                }
+                if let Some(prefetch) = &mut search.prefetch {
+                    if let Some(reduction) = reductions.get(prefetch.query.get_vector_name()) {
+                        prefetch.query = reduce_query(prefetch.query.clone(), reduction)?;
+                    }
+                }
+                Ok(search)
+            })
+            .collect::<CollectionResult<_>>()?;
+
+        Ok(())
+    }
+
+    /// Train the PCA transform of the vector on the given sample
+    ///
+    /// Returns the diff, which sets the transform in the collection config. Training is CPU
+    /// intensive, so it is done on a blocking thread.
+    pub async fn train_dimensionality_reduction(
+        &self,
+        request: TrainDimensionalityReduction,
+    ) -> CollectionResult<VectorsConfigDiff> {
+        let vector_name = request
+            .vector_name
+            .unwrap_or_else(|| DEFAULT_VECTOR_NAME.to_string());
+
+        let (input_size, size) = {
+            let config = self.collection_config.read().await;
+            let params = config
+                .params
+                .vectors
+                .get_params(&vector_name)
+                .ok_or_else(|| {
+                    CollectionError::bad_input(format!(
+                        "Vector params for {vector_name} are not specified in config"
+                    ))
+                })?;
+            let Some(reduction) = &params.dimensionality_reduction else {
+                return Err(CollectionError::bad_input(format!(
+                    "Vector {vector_name} has no dimensionality reduction configured"
+                )));
+            };
+            if reduction.transform.is_some() {
+                return Err(CollectionError::bad_input(format!(
+                    "Dimensionality reduction of vector {vector_name} is already trained"
+                )));
+            }
+            (
+                reduction.input_size.get() as usize,
+                params.size.get() as usize,
+            )
+        };
+
+        if let Some(vector) = request.sample.iter().find(|v| v.len() != input_size) {
+            return Err(CollectionError::bad_input(format!(
+                "Training sample vector dimension error: expected dim: {input_size}, got {}",
+                vector.len(),
+            )));
+        }
+
+        let sample = request.sample;
+        let transform =
+            tokio::task::spawn_blocking(move || LinearTransform::train_pca(&sample, size))
+                .await??;
+
+        Ok(VectorsConfigDiff(BTreeMap::from([(
+            vector_name,
+            VectorParamsDiff {
+                hnsw_config: None,
+                quantization_config: None,
+                on_disk: None,
+                dimensionality_reduction_transform: Some(transform),
+            },
+        )])))
+    }
+}
+
+/// Project the vector of the input size by the learned transform
+pub(crate) fn reduce(
+    vector: &mut DenseVector,
+    reduction: &DimensionalityReduction,
+) -> CollectionResult<()> {
+    if vector.len() != reduction.input_size.get() as usize {
+        return Ok(());
+    }
+    let Some(transform) = &reduction.transform else {
+        return Err(CollectionError::bad_input(
+            "Dimensionality reduction is not trained yet, vectors can't be reduced".to_string(),
+        ));
+    };
+    *vector = transform.apply(vector);
+    Ok(())
+}
+
+fn reduce_vector(vector: &mut Vector, reduction: &DimensionalityReduction) -> CollectionResult<()> {
+    match vector {
+        Vector::Dense(vector) => reduce(vector, reduction),
+        Vector::MultiDense(vectors) => vectors
+            .iter_mut()
+            .try_for_each(|vector| reduce(vector, reduction)),
+        // Only dense vectors could be reduced
+        Vector::Sparse(_) => Ok(()),
+    }
+}
+
+fn reduce_vectors(vectors: &mut VectorStruct, reductions: &Reductions) -> CollectionResult<()> {
+    match vectors {
+        VectorStruct::Single(vector) => {
+            if let Some(reduction) = reductions.get(DEFAULT_VECTOR_NAME) {
+                reduce(vector, reduction)?;
+            }
+        }
+        VectorStruct::Multi(vectors) => {
+            for (name, vector) in vectors.iter_mut() {
+                if let Some(reduction) = reductions.get(name) {
+                    reduce_vector(vector, reduction)?;
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+fn reduce_batch(vectors: &mut BatchVectorStruct, reductions: &Reductions) -> CollectionResult<()> {
+    match vectors {
+        BatchVectorStruct::Single(vectors) => {
+            if let Some(reduction) = reductions.get(DEFAULT_VECTOR_NAME) {
+                for vector in vectors {
+                    reduce(vector, reduction)?;
+                }
+            }
+        }
+        BatchVectorStruct::Multi(vectors) => {
+            for (name, vectors) in vectors.iter_mut() {
+                let Some(reduction) = reductions.get(name) else {
+                    continue;
+                };
+                for vector in vectors {
+                    reduce_vector(vector, reduction)?;
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+fn reduce_query(
+    query: QueryEnum,
+    reduction: &DimensionalityReduction,
+) -> CollectionResult<QueryEnum> {
+    let reduce_owned = |mut vector: Vector| {
+        reduce_vector(&mut vector, reduction)
+            .map(|_| vector)
+            .map_err(|err| OperationError::ValidationError {
+                description: err.to_string(),
+            })
+    };
+
+    let query = match query {
+        QueryEnum::Nearest(NamedVectorStruct::Default(mut vector)) => {
+            reduce(&mut vector, reduction)?;
+            QueryEnum::Nearest(NamedVectorStruct::Default(vector))
+        }
+        QueryEnum::Nearest(NamedVectorStruct::Dense(NamedVector { name, mut vector })) => {
+            reduce(&mut vector, reduction)?;
+            QueryEnum::Nearest(NamedVectorStruct::Dense(NamedVector { name, vector }))
+        }
+        QueryEnum::Nearest(NamedVectorStruct::MultiDense(NamedMultiDenseVector {
+            name,
+            mut vector,
+        })) => {
+            for vector in &mut vector {
+                reduce(vector, reduction)?;
+            }
+            QueryEnum::Nearest(NamedVectorStruct::MultiDense(NamedMultiDenseVector {
+                name,
+                vector,
+            }))
+        }
+        QueryEnum::Nearest(vector @ NamedVectorStruct::Sparse(_)) => QueryEnum::Nearest(vector),
+        QueryEnum::RecommendBestScore(NamedQuery { query, using }) => {
+            QueryEnum::RecommendBestScore(NamedQuery {
+                query: query.transform(reduce_owned)?,
+                using,
+            })
+        }
+        QueryEnum::Discover(NamedQuery { query, using }) => QueryEnum::Discover(NamedQuery {
+            query: query.transform(reduce_owned)?,
+            using,
+        }),
+        QueryEnum::Context(NamedQuery { query, using }) => QueryEnum::Context(NamedQuery {
+            query: query.transform(reduce_owned)?,
+            using,
+        }),
+    };
+    Ok(query)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZeroU64;
+
+    use super::*;
+
+    fn reductions(transform: Option<LinearTransform>) -> Reductions {
+        Reductions::from([(
+            "image".to_string(),
+            DimensionalityReduction {
+                input_size: NonZeroU64::new(3).unwrap(),
+                transform,
+            },
+        )])
+    }
+
+    #[test]
+    fn test_reduce_vectors() {
+        let reductions = reductions(Some(LinearTransform {
+            mean: vec![0.0; 3],
+            matrix: vec![1.0, 1.0, 0.0, 0.0, 0.0, 1.0],
+        }));
+
+        let mut vectors = VectorStruct::Multi(HashMap::from([
+            ("image".to_string(), Vector::Dense(vec![1.0, 2.0, 3.0])),
+            ("text".to_string(), Vector::Dense(vec![1.0, 2.0, 3.0])),
+        ]));
+        reduce_vectors(&mut vectors, &reductions).unwrap();
+
+        let VectorStruct::Multi(vectors) = vectors else {
+            unreachable!()
+        };
+        assert_eq!(vectors["image"], Vector::Dense(vec![3.0, 3.0]));
+        assert_eq!(vectors["text"], Vector::Dense(vec![1.0, 2.0, 3.0]));
+
+        // Already reduced vectors are left as they are
+        let mut vectors = BatchVectorStruct::Multi(HashMap::from([(
+            "image".to_string(),
+            vec![Vector::Dense(vec![1.0, 2.0])],
+        )]));
+        reduce_batch(&mut vectors, &reductions).unwrap();
+        let BatchVectorStruct::Multi(vectors) = vectors else {
+            unreachable!()
+        };
+        assert_eq!(vectors["image"], vec![Vector::Dense(vec![1.0, 2.0])]);
+    }
+
+    #[test]
+    fn test_reduce_untrained() {
+        let reductions = reductions(None);
+        let mut vectors = VectorStruct::Multi(HashMap::from([(
+            "image".to_string(),
+            Vector::Dense(vec![1.0, 2.0, 3.0]),
+        )]));
+        assert!(reduce_vectors(&mut vectors, &reductions).is_err());
+    }
+}
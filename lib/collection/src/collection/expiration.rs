@@ -0,0 +1,229 @@
+//! Background deletion of expired points
+//!
+//! Points are expired by the time in the `expiration_field` of the collection, soft-deleted points
+//! expire once they are retained for longer than `soft_delete_window_sec`.
+//!
+//! Each shard is cleaned up by a single peer - the leader of its updates. Deletions are applied
+//! through the replica set, so they are written into WAL of every replica, same as client updates.
+
+use std::sync::{Arc, Weak};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use segment::types::{
+    Condition, FieldCondition, Filter, PayloadFieldSchema, PayloadKeyType, PayloadSchemaType,
+    Range, WithPayloadInterface, WithVector,
+};
+use tokio::sync::RwLock;
+
+use super::payload_index_schema::PayloadIndexSchema;
+use super::soft_delete::SOFT_DELETED_AT_KEY;
+use super::Collection;
+use crate::config::CollectionConfig;
+use crate::operations::point_ops::{PointOperations, WriteOrdering};
+use crate::operations::types::CollectionResult;
+use crate::operations::CollectionUpdateOperations;
+use crate::save_on_disk::SaveOnDisk;
+use crate::shards::replica_set::read_priority::ReadPriority;
+use crate::shards::replica_set::ShardReplicaSet;
+use crate::shards::shard_holder::LockedShardHolder;
+
+/// Interval at which expired points are looked for
+const EXPIRATION_CHECK_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Maximal number of expired points, deleted by a single update operation
+const EXPIRATION_BATCH_SIZE: usize = 1000;
+
+/// Maximal number of deletions of a single shard per check.
+/// Expiration is not urgent, remaining points are deleted by the next checks.
+const MAX_EXPIRATION_BATCHES: usize = 10;
+
+/// Deletions are ordered with other updates by the leader of the shard
+const EXPIRATION_WRITE_ORDERING: WriteOrdering = WriteOrdering::Medium;
+
+impl Collection {
+    /// Start periodic deletion of expired points, the task stops once the collection is dropped
+    pub(super) fn spawn_expiration_task(&self) {
+        let shards_holder = Arc::downgrade(&self.shards_holder);
+        let collection_config = self.collection_config.clone();
+        let payload_index_schema = self.payload_index_schema.clone();
+        self.update_runtime.spawn(async move {
+            loop {
+                tokio::time::sleep(EXPIRATION_CHECK_INTERVAL).await;
+                let Some(shards_holder) = Weak::upgrade(&shards_holder) else {
+                    return;
+                };
+                delete_expired_points(&shards_holder, &collection_config, &payload_index_schema)
+                    .await;
+            }
+        });
+    }
+
+    /// Delete points, which are expired by now, from the shards this peer leads the updates of
+    ///
+    /// Returns number of the deleted points.
+    pub async fn delete_expired_points(&self) -> usize {
+        delete_expired_points(
+            &self.shards_holder,
+            &self.collection_config,
+            &self.payload_index_schema,
+        )
+        .await
+    }
+
+    /// Index the expiration field, so expired points are found without a full scan
+    ///
+    /// Does nothing if expiration is not configured or the field is already indexed.
+    pub async fn create_expiration_index(&self) -> CollectionResult<()> {
+        let Some(expiration_field) = self.expiration_field().await else {
+            return Ok(());
+        };
+        if self
+            .payload_index_schema
+            .read()
+            .schema
+            .contains_key(&expiration_field)
+        {
+            return Ok(());
+        }
+        self.create_payload_index(
+            expiration_field,
+            PayloadFieldSchema::FieldType(PayloadSchemaType::Float),
+        )
+        .await?;
+        Ok(())
+    }
+}
+
+async fn delete_expired_points(
+    shards_holder: &LockedShardHolder,
+    collection_config: &RwLock<CollectionConfig>,
+    payload_index_schema: &SaveOnDisk<PayloadIndexSchema>,
+) -> usize {
+    // Pairs of the field with deletion time and the delay after it, when points are deleted
+    let expiration_rules: Vec<(PayloadKeyType, u64)> = {
+        let config = collection_config.read().await;
+        config
+            .params
+            .expiration_field
+            .clone()
+            .map(|expiration_field| (expiration_field, 0))
+            .into_iter()
+            .chain(
+                config
+                    .params
+                    .soft_delete_window_sec
+                    .map(|window| (SOFT_DELETED_AT_KEY.to_string(), window)),
+            )
+            .collect()
+    };
+
+    let mut deleted = 0;
+    for (expiration_field, delay_sec) in expiration_rules {
+        // Without an index, every check would be a full scan of the collection
+        if !payload_index_schema
+            .read()
+            .schema
+            .contains_key(&expiration_field)
+        {
+            log::warn!("Points are not expired by `{expiration_field}`, the field is not indexed");
+            continue;
+        }
+
+        let shards_holder = shards_holder.read().await;
+        for replica_set in shards_holder.all_shards() {
+            if !replica_set.is_update_leader(EXPIRATION_WRITE_ORDERING) {
+                continue;
+            }
+            match delete_expired_shard_points(replica_set, &expiration_field, delay_sec).await {
+                Ok(count) => deleted += count,
+                Err(err) => log::warn!(
+                    "Failed to delete points of shard {} expired by `{expiration_field}`: {err}",
+                    replica_set.shard_id,
+                ),
+            }
+        }
+    }
+    deleted
+}
+
+/// Deletes points of the shard, time in `expiration_field` of which is older than `delay_sec`, in batches
+async fn delete_expired_shard_points(
+    replica_set: &ShardReplicaSet,
+    expiration_field: &str,
+    delay_sec: u64,
+) -> CollectionResult<usize> {
+    let threshold = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .saturating_sub(Duration::from_secs(delay_sec))
+        .as_secs_f64();
+    let filter = expiration_filter(expiration_field, threshold);
+
+    let mut deleted = 0;
+    for _ in 0..MAX_EXPIRATION_BATCHES {
+        // Leader is an active replica, its local data is up to date
+        let expired_points: Vec<_> = replica_set
+            .scroll_by(
+                None,
+                EXPIRATION_BATCH_SIZE,
+                &WithPayloadInterface::Bool(false),
+                &WithVector::Bool(false),
+                Some(&filter),
+                None,
+                None,
+                None,
+                None,
+                None,
+                ReadPriority::Normal,
+                None,
+                None,
+                true,
+            )
+            .await?
+            .into_iter()
+            .map(|record| record.id)
+            .collect();
+        if expired_points.is_empty() {
+            break;
+        }
+
+        let is_last_batch = expired_points.len() < EXPIRATION_BATCH_SIZE;
+        let batch_size = expired_points.len();
+        let operation = CollectionUpdateOperations::PointOperation(PointOperations::DeletePoints {
+            ids: expired_points,
+        });
+        // Wait for the deletion to be applied, so the same points are not found again
+        replica_set
+            .update_with_consistency(
+                operation,
+                true,
+                EXPIRATION_WRITE_ORDERING,
+                None,
+                None,
+                None,
+                None,
+            )
+            .await?;
+        deleted += batch_size;
+        log::debug!(
+            "Deleted {batch_size} points of shard {} expired by `{expiration_field}`",
+            replica_set.shard_id,
+        );
+
+        if is_last_batch {
+            break;
+        }
+    }
+    Ok(deleted)
+}
+
+/// Points, time in `expiration_field` of which is not later than `threshold`
+fn expiration_filter(expiration_field: &str, threshold: f64) -> Filter {
+    Filter::new_must(Condition::Field(FieldCondition::new_range(
+        expiration_field,
+        Range {
+            lte: Some(threshold),
+            ..Default::default()
+        },
+    )))
+}
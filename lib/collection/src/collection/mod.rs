@@ -1,40 +1,66 @@
+pub mod access_tags;
 mod collection_ops;
+mod dimensionality_reduction;
+mod expiration;
+mod normalization;
 pub mod payload_index_schema;
+mod payload_sharding;
 mod point_ops;
+pub mod read_only;
+mod running_operations;
 mod search;
+mod search_page;
+mod shard_split;
 mod shard_transfer;
 mod sharding_keys;
-mod snapshots;
+pub mod snapshots;
+pub mod soft_delete;
 mod state_management;
+pub mod stored_queries;
+mod stored_query_index;
+pub mod usage;
+mod wal_archive;
 
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::ops::Deref;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use segment::common::version::StorageVersion;
-use segment::types::ShardKey;
+use segment::types::{PayloadKeyType, ShardKey};
 use semver::Version;
 use tokio::runtime::Handle;
 use tokio::sync::{Mutex, RwLock, RwLockWriteGuard};
 
 use crate::collection::payload_index_schema::PayloadIndexSchema;
+use crate::collection::payload_sharding::PlacementLocks;
+use crate::collection::read_only::ReadOnlyState;
+use crate::collection::stored_queries::StoredQueries;
+use crate::collection::stored_query_index::StoredQueryIndex;
+use crate::collection::usage::UsageMeter;
 use crate::collection_state::{ShardInfo, State};
 use crate::common::is_ready::IsReady;
-use crate::config::CollectionConfig;
+use crate::common::search_admission::SearchAdmission;
+use crate::common::search_runtime::DedicatedSearchRuntime;
+use crate::common::stoppable_task_async::CancellableAsyncTaskHandle;
+use crate::config::{CollectionConfig, InferenceConfig};
+use crate::events::CollectionEventKind;
+use crate::operations::running_operations::OperationsRegistry;
 use crate::operations::shared_storage_config::SharedStorageConfig;
+use crate::operations::strict_mode::QuotaUsage;
 use crate::operations::types::{CollectionError, CollectionResult, NodeType};
 use crate::save_on_disk::SaveOnDisk;
 use crate::shards::channel_service::ChannelService;
 use crate::shards::collection_shard_distribution::CollectionShardDistribution;
+use crate::shards::consistency_check::ConsistencyAnomaly;
 use crate::shards::replica_set::ReplicaState::{Active, Dead, Initializing, Listener};
 use crate::shards::replica_set::{ChangePeerState, ReplicaState, ShardReplicaSet};
 use crate::shards::shard::{PeerId, ShardId};
 use crate::shards::shard_holder::{shard_not_found_error, LockedShardHolder, ShardHolder};
 use crate::shards::transfer::helpers::check_transfer_conflicts_strict;
 use crate::shards::transfer::transfer_tasks_pool::TransferTasksPool;
-use crate::shards::transfer::ShardTransfer;
+use crate::shards::transfer::{ShardTransfer, ShardTransferMethod};
 use crate::shards::{replica_set, CollectionId};
 use crate::telemetry::CollectionTelemetry;
 
@@ -44,14 +70,27 @@ pub struct Collection {
     pub(crate) shards_holder: Arc<LockedShardHolder>,
     pub(crate) collection_config: Arc<RwLock<CollectionConfig>>,
     pub(crate) shared_storage_config: Arc<SharedStorageConfig>,
-    pub(crate) payload_index_schema: SaveOnDisk<PayloadIndexSchema>,
+    pub(crate) payload_index_schema: Arc<SaveOnDisk<PayloadIndexSchema>>,
+    pub(crate) stored_queries: SaveOnDisk<StoredQueries>,
+    pub(crate) read_only: SaveOnDisk<ReadOnlyState>,
+    // Index of the stored queries, rebuilt on every change of them
+    stored_query_index: parking_lot::RwLock<Arc<StoredQueryIndex>>,
     this_peer_id: PeerId,
     path: PathBuf,
-    snapshots_path: PathBuf,
+    /// Could be changed by reloading the service configuration
+    snapshots_path: parking_lot::RwLock<PathBuf>,
     channel_service: ChannelService,
     transfer_tasks: Mutex<TransferTasksPool>,
+    split_tasks: Mutex<HashMap<ShardId, CancellableAsyncTaskHandle<bool>>>,
+    // Limit of concurrent searches of the collection, follows the strict mode config.
+    search_admission: parking_lot::Mutex<SearchAdmission>,
+    // Requests of the tenants of the collection, received by this peer
+    usage_meter: UsageMeter,
+    // Usage of the collection, checked against the strict mode quotas, and the time it was collected
+    quota_usage: parking_lot::Mutex<Option<(Instant, Option<QuotaUsage>)>>,
+    // Long running operations, which are not tracked by the shards, like snapshot creation
+    operations_registry: Arc<OperationsRegistry>,
     request_shard_transfer_cb: RequestShardTransfer,
-    #[allow(dead_code)] //Might be useful in case of repartition implementation
     notify_peer_failure_cb: ChangePeerState,
     abort_shard_transfer_cb: replica_set::AbortShardTransfer,
     init_time: Duration,
@@ -62,10 +101,14 @@ pub struct Collection {
     // Lock is acquired for read on update operation and can be acquired for write externally,
     // which will block all update operations until the lock is released.
     updates_lock: RwLock<()>,
+    // Locks of the points, which are being placed by the value of the sharding payload key
+    placement_locks: PlacementLocks,
     // Update runtime handle.
     update_runtime: Handle,
     // Search runtime handle.
     search_runtime: Handle,
+    // Own search runtime of the collection, if configured. `search_runtime` is a handle to it.
+    _dedicated_search_runtime: Option<DedicatedSearchRuntime>,
 }
 
 pub type RequestShardTransfer = Arc<dyn Fn(ShardTransfer) + Send + Sync>;
@@ -73,6 +116,9 @@ pub type RequestShardTransfer = Arc<dyn Fn(ShardTransfer) + Send + Sync>;
 pub type OnTransferFailure = Arc<dyn Fn(ShardTransfer, CollectionId, &str) + Send + Sync>;
 pub type OnTransferSuccess = Arc<dyn Fn(ShardTransfer, CollectionId) + Send + Sync>;
 
+pub type OnShardSplitCopied = Arc<dyn Fn(ShardId, CollectionId) + Send + Sync>;
+pub type OnShardSplitFailure = Arc<dyn Fn(ShardId, CollectionId, &str) + Send + Sync>;
+
 impl Collection {
     #[allow(clippy::too_many_arguments)]
     pub async fn new(
@@ -92,6 +138,12 @@ impl Collection {
     ) -> Result<Self, CollectionError> {
         let start_time = std::time::Instant::now();
 
+        let dedicated_search_runtime = Self::dedicated_search_runtime(&name, collection_config)?;
+        let search_runtime = dedicated_search_runtime
+            .as_ref()
+            .map(|runtime| runtime.handle().clone())
+            .or(search_runtime);
+
         let mut shard_holder = ShardHolder::new(path)?;
 
         let shared_collection_config = Arc::new(RwLock::new(collection_config.clone()));
@@ -126,27 +178,43 @@ impl Collection {
         collection_config.save(path)?;
 
         let payload_index_schema = Self::load_payload_index_schema(path)?;
+        let stored_queries = Self::load_stored_queries(path)?;
+        let stored_query_index = StoredQueryIndex::build(&stored_queries.read());
+        let read_only = Self::load_read_only_state(path)?;
 
-        Ok(Self {
+        let collection = Self {
             id: name.clone(),
             shards_holder: locked_shard_holder,
             collection_config: shared_collection_config,
-            payload_index_schema,
+            payload_index_schema: Arc::new(payload_index_schema),
+            stored_queries,
+            stored_query_index: parking_lot::RwLock::new(Arc::new(stored_query_index)),
+            read_only,
             shared_storage_config,
             this_peer_id,
             path: path.to_owned(),
-            snapshots_path: snapshots_path.to_owned(),
+            snapshots_path: parking_lot::RwLock::new(snapshots_path.to_owned()),
             channel_service,
             transfer_tasks: Mutex::new(TransferTasksPool::new(name.clone())),
+            split_tasks: Default::default(),
+            search_admission: Default::default(),
+            usage_meter: Default::default(),
+            quota_usage: Default::default(),
+            operations_registry: Default::default(),
             request_shard_transfer_cb: request_shard_transfer.clone(),
             notify_peer_failure_cb: on_replica_failure.clone(),
             abort_shard_transfer_cb: abort_shard_transfer,
             init_time: start_time.elapsed(),
             is_initialized: Arc::new(Default::default()),
             updates_lock: RwLock::new(()),
+            placement_locks: Default::default(),
             update_runtime: update_runtime.unwrap_or_else(Handle::current),
             search_runtime: search_runtime.unwrap_or_else(Handle::current),
-        })
+            _dedicated_search_runtime: dedicated_search_runtime,
+        };
+        collection.spawn_expiration_task();
+
+        Ok(collection)
     }
 
     #[allow(clippy::too_many_arguments)]
@@ -162,7 +230,7 @@ impl Collection {
         abort_shard_transfer: replica_set::AbortShardTransfer,
         search_runtime: Option<Handle>,
         update_runtime: Option<Handle>,
-    ) -> Self {
+    ) -> CollectionResult<Self> {
         let start_time = std::time::Instant::now();
         let stored_version = CollectionVersion::load(path)
             .expect("Can't read collection version")
@@ -197,6 +265,13 @@ impl Collection {
         });
         collection_config.validate_and_warn();
 
+        let dedicated_search_runtime =
+            Self::dedicated_search_runtime(&collection_id, &collection_config)?;
+        let search_runtime = dedicated_search_runtime
+            .as_ref()
+            .map(|runtime| runtime.handle().clone())
+            .or(search_runtime);
+
         let mut shard_holder = ShardHolder::new(path).expect("Can not create shard holder");
 
         let shared_collection_config = Arc::new(RwLock::new(collection_config.clone()));
@@ -220,27 +295,62 @@ impl Collection {
 
         let payload_index_schema = Self::load_payload_index_schema(path)
             .expect("Can't load or initialize payload index schema");
+        let stored_queries =
+            Self::load_stored_queries(path).expect("Can't load or initialize stored queries");
+        let stored_query_index = StoredQueryIndex::build(&stored_queries.read());
+        let read_only =
+            Self::load_read_only_state(path).expect("Can't load or initialize read-only state");
 
-        Self {
+        let collection = Self {
             id: collection_id.clone(),
             shards_holder: locked_shard_holder,
             collection_config: shared_collection_config,
-            payload_index_schema,
+            payload_index_schema: Arc::new(payload_index_schema),
+            stored_queries,
+            stored_query_index: parking_lot::RwLock::new(Arc::new(stored_query_index)),
+            read_only,
             shared_storage_config,
             this_peer_id,
             path: path.to_owned(),
-            snapshots_path: snapshots_path.to_owned(),
+            snapshots_path: parking_lot::RwLock::new(snapshots_path.to_owned()),
             channel_service,
             transfer_tasks: Mutex::new(TransferTasksPool::new(collection_id.clone())),
+            split_tasks: Default::default(),
+            search_admission: Default::default(),
+            usage_meter: Default::default(),
+            quota_usage: Default::default(),
+            operations_registry: Default::default(),
             request_shard_transfer_cb: request_shard_transfer.clone(),
             notify_peer_failure_cb: on_replica_failure,
             abort_shard_transfer_cb: abort_shard_transfer,
             init_time: start_time.elapsed(),
             is_initialized: Arc::new(Default::default()),
             updates_lock: RwLock::new(()),
+            placement_locks: Default::default(),
             update_runtime: update_runtime.unwrap_or_else(Handle::current),
             search_runtime: search_runtime.unwrap_or_else(Handle::current),
-        }
+            _dedicated_search_runtime: dedicated_search_runtime,
+        };
+        collection.spawn_expiration_task();
+
+        Ok(collection)
+    }
+
+    /// Create dedicated search runtime, if collection is configured to have one
+    fn dedicated_search_runtime(
+        collection_id: &CollectionId,
+        collection_config: &CollectionConfig,
+    ) -> CollectionResult<Option<DedicatedSearchRuntime>> {
+        let Some(search_threads) = collection_config.params.search_threads else {
+            return Ok(None);
+        };
+        let runtime = DedicatedSearchRuntime::new(collection_id, search_threads.get() as usize)
+            .map_err(|err| {
+                CollectionError::service_error(format!(
+                    "Can't create search runtime for collection {collection_id}: {err}"
+                ))
+            })?;
+        Ok(Some(runtime))
     }
 
     /// Check if stored version have consequent version.
@@ -280,11 +390,45 @@ impl Collection {
             .collect()
     }
 
+    /// Payload field with the time of expiration of the points, if configured
+    pub async fn expiration_field(&self) -> Option<PayloadKeyType> {
+        self.collection_config
+            .read()
+            .await
+            .params
+            .expiration_field
+            .clone()
+    }
+
+    /// Endpoint, which converts documents into vectors of the collection, if configured
+    pub async fn inference_config(&self) -> Option<InferenceConfig> {
+        self.collection_config.read().await.params.inference.clone()
+    }
+
     /// Return a list of local shards, present on this peer
     pub async fn get_local_shards(&self) -> Vec<ShardId> {
         self.shards_holder.read().await.get_local_shards().await
     }
 
+    /// Check consistency of the shards of the collection, anomalies are reported in the
+    /// optimizer status of the local shards
+    pub async fn check_consistency(
+        &self,
+        max_missed_updates: u64,
+    ) -> Vec<(ShardId, ConsistencyAnomaly)> {
+        let shard_holder = self.shards_holder.read().await;
+        let mut anomalies = Vec::new();
+        for (&shard_id, replica_set) in shard_holder.get_shards() {
+            let shard_anomalies = replica_set.check_consistency(max_missed_updates).await;
+            anomalies.extend(
+                shard_anomalies
+                    .into_iter()
+                    .map(|anomaly| (shard_id, anomaly)),
+            );
+        }
+        anomalies
+    }
+
     pub async fn contains_shard(&self, shard_id: ShardId) -> bool {
         self.shards_holder.read().await.contains_shard(&shard_id)
     }
@@ -363,6 +507,12 @@ impl Collection {
             .await?;
 
         if state == ReplicaState::Dead {
+            self.shared_storage_config.events.notify(
+                self.name(),
+                Some(shard_id),
+                CollectionEventKind::ReplicaDeactivated { peer_id },
+            );
+
             // Terminate transfer if source or target replicas are now dead
             let related_transfers = shard_holder.get_related_transfers(&shard_id, &peer_id);
             for transfer in related_transfers {
@@ -398,12 +548,15 @@ impl Collection {
                 .find(|(_, state)| state == &ReplicaState::Active)
                 .map(|(peer_id, _)| peer_id);
             if let Some(transfer_from) = transfer_from {
+                // Local shard still has the data, only the missed updates are to be replayed
+                let method =
+                    (!replica_set.is_dummy().await).then_some(ShardTransferMethod::WalDelta);
                 self.request_shard_transfer(ShardTransfer {
                     shard_id,
                     from: transfer_from,
                     to: self.this_peer_id,
                     sync: true,
-                    method: None,
+                    method,
                 })
             } else {
                 log::warn!("No alive replicas to recover shard {shard_id}");
@@ -430,6 +583,9 @@ impl Collection {
             transfers,
             shards_key_mapping: shards_holder.get_shard_key_to_ids_mapping(),
             payload_index_schema: self.payload_index_schema.read().clone(),
+            stored_queries: self.stored_queries.read().clone(),
+            read_only: self.read_only.read().clone(),
+            shard_splits: shards_holder.shard_splits(),
         }
     }
 
@@ -508,7 +664,7 @@ impl Collection {
                 continue;
             }
 
-            if self.shared_storage_config.node_type == NodeType::Listener {
+            if self.shared_storage_config.node_type.get() == NodeType::Listener {
                 if this_peer_state == Some(Active) && !is_last_active {
                     // Convert active node from active to listener
                     on_convert_to_listener(*this_peer_id, shard_id);
@@ -524,6 +680,10 @@ impl Collection {
                 continue; // All good
             }
 
+            if shard_holder.get_shard_split(shard_id).is_some() {
+                continue; // Recovered after the split, as a replica of the new shards
+            }
+
             // Try to find dead replicas with no active transfers
             let transfers = shard_holder.get_transfers(|_| true);
 
@@ -534,7 +694,7 @@ impl Collection {
                     to: *this_peer_id,
                     shard_id,
                     sync: true,
-                    method: None,
+                    method: Some(ShardTransferMethod::WalDelta),
                 };
 
                 if check_transfer_conflicts_strict(&transfer, transfers.iter()).is_some() {
@@ -580,13 +740,16 @@ impl Collection {
             }
             (shards_telemetry, shards_holder.get_shard_transfer_info())
         };
+        let transfer_rates = self.transfer_tasks.lock().await.get_rates();
 
         CollectionTelemetry {
             id: self.name(),
             init_time_ms: self.init_time.as_millis() as u64,
             config: self.collection_config.read().await.clone(),
+            feature_flags: self.feature_flags().await,
             shards: shards_telemetry,
             transfers,
+            transfer_rates,
         }
     }
 
@@ -0,0 +1,253 @@
+//! Server-side L2 normalization of vectors, which have `normalize` enabled in their params
+//!
+//! Vectors are normalized before the update is split between the shards, and query vectors
+//! before the search is sent to them, so shards only ever see normalized vectors.
+
+use std::collections::HashSet;
+
+use segment::common::operation_error::OperationResult;
+use segment::data_types::vectors::{
+    BatchVectorStruct, DenseVector, NamedMultiDenseVector, NamedQuery, NamedVector,
+    NamedVectorStruct, Vector, VectorStruct, DEFAULT_VECTOR_NAME,
+};
+use segment::types::Distance;
+use segment::vector_storage::query::TransformInto;
+
+use super::Collection;
+use crate::operations::point_ops::{PointInsertOperationsInternal, PointOperations};
+use crate::operations::types::{CollectionResult, CoreSearchRequestBatch, QueryEnum};
+use crate::operations::vector_ops::{UpdateVectorsOp, VectorOperations};
+use crate::operations::CollectionUpdateOperations;
+
+impl Collection {
+    /// Names of the vectors, which are normalized
+    async fn normalized_vectors(&self) -> HashSet<String> {
+        self.collection_config
+            .read()
+            .await
+            .params
+            .vectors
+            .params_iter()
+            .filter(|(_, params)| params.normalize.unwrap_or_default())
+            .map(|(name, _)| name.to_string())
+            .collect()
+    }
+
+    /// Normalize inserted and updated vectors, which have `normalize` enabled
+    pub(crate) async fn normalize_operation(
+        &self,
+        mut operation: CollectionUpdateOperations,
+    ) -> CollectionUpdateOperations {
+        let normalized = self.normalized_vectors().await;
+        if normalized.is_empty() {
+            return operation;
+        }
+
+        match &mut operation {
+            CollectionUpdateOperations::PointOperation(PointOperations::UpsertPoints(
+                PointInsertOperationsInternal::PointsBatch(batch),
+            )) => normalize_batch(&mut batch.vectors, &normalized),
+            CollectionUpdateOperations::PointOperation(PointOperations::UpsertPoints(
+                PointInsertOperationsInternal::PointsList(points),
+            )) => {
+                for point in points {
+                    normalize_vectors(&mut point.vector, &normalized);
+                }
+            }
+            CollectionUpdateOperations::VectorOperation(VectorOperations::UpdateVectors(
+                UpdateVectorsOp { points },
+            )) => {
+                for point in points {
+                    normalize_vectors(&mut point.vector, &normalized);
+                }
+            }
+            _ => {}
+        }
+
+        operation
+    }
+
+    /// Normalize query vectors of the searches by vectors, which have `normalize` enabled
+    pub(crate) async fn normalize_search_batch(
+        &self,
+        request: &mut CoreSearchRequestBatch,
+    ) -> CollectionResult<()> {
+        let normalized = self.normalized_vectors().await;
+        if normalized.is_empty() {
+            return Ok(());
+        }
+
+        request.searches = std::mem::take(&mut request.searches)
+            .into_iter()
+            .map(|mut search| {
+                if normalized.contains(search.query.get_vector_name()) {
+                    search.query = normalize_query(search.query)?;
+                }
+                if let Some(prefetch) = &mut search.prefetch {
+                    if normalized.contains(prefetch.query.get_vector_name()) {
+                        prefetch.query = normalize_query(prefetch.query.clone())?;
+                    }
+                }
+                Ok(search)
+            })
+            .collect::<CollectionResult<_>>()?;
+
+        Ok(())
+    }
+}
+
+/// L2-normalize the vector, the same way as `Cosine` distance does
+fn normalize(vector: &mut DenseVector) {
+    *vector = Distance::Cosine.preprocess_vector(std::mem::take(vector));
+}
+
+fn normalize_vector_mut(vector: &mut Vector) {
+    match vector {
+        Vector::Dense(vector) => normalize(vector),
+        // Each vector of the multi-vector is normalized
+        Vector::MultiDense(vectors) => vectors.iter_mut().for_each(normalize),
+        // Only dense vectors could be normalized
+        Vector::Sparse(_) => {}
+    }
+}
+
+fn normalize_vector(mut vector: Vector) -> Vector {
+    normalize_vector_mut(&mut vector);
+    vector
+}
+
+fn normalize_vectors(vectors: &mut VectorStruct, normalized: &HashSet<String>) {
+    match vectors {
+        VectorStruct::Single(vector) => {
+            if normalized.contains(DEFAULT_VECTOR_NAME) {
+                normalize(vector);
+            }
+        }
+        VectorStruct::Multi(vectors) => {
+            for (name, vector) in vectors.iter_mut() {
+                if normalized.contains(name) {
+                    normalize_vector_mut(vector);
+                }
+            }
+        }
+    }
+}
+
+fn normalize_batch(vectors: &mut BatchVectorStruct, normalized: &HashSet<String>) {
+    match vectors {
+        BatchVectorStruct::Single(vectors) => {
+            if normalized.contains(DEFAULT_VECTOR_NAME) {
+                vectors.iter_mut().for_each(normalize);
+            }
+        }
+        BatchVectorStruct::Multi(vectors) => {
+            for (name, vectors) in vectors.iter_mut() {
+                if !normalized.contains(name) {
+                    continue;
+                }
+                vectors.iter_mut().for_each(normalize_vector_mut);
+            }
+        }
+    }
+}
+
+fn normalize_query(query: QueryEnum) -> OperationResult<QueryEnum> {
+    let query = match query {
+        QueryEnum::Nearest(NamedVectorStruct::Default(mut vector)) => {
+            normalize(&mut vector);
+            QueryEnum::Nearest(NamedVectorStruct::Default(vector))
+        }
+        QueryEnum::Nearest(NamedVectorStruct::Dense(NamedVector { name, mut vector })) => {
+            normalize(&mut vector);
+            QueryEnum::Nearest(NamedVectorStruct::Dense(NamedVector { name, vector }))
+        }
+        QueryEnum::Nearest(NamedVectorStruct::MultiDense(NamedMultiDenseVector {
+            name,
+            mut vector,
+        })) => {
+            vector.iter_mut().for_each(normalize);
+            QueryEnum::Nearest(NamedVectorStruct::MultiDense(NamedMultiDenseVector {
+                name,
+                vector,
+            }))
+        }
+        QueryEnum::Nearest(vector @ NamedVectorStruct::Sparse(_)) => QueryEnum::Nearest(vector),
+        QueryEnum::RecommendBestScore(NamedQuery { query, using }) => {
+            QueryEnum::RecommendBestScore(NamedQuery {
+                query: query.transform(|vector| Ok(normalize_vector(vector)))?,
+                using,
+            })
+        }
+        QueryEnum::Discover(NamedQuery { query, using }) => QueryEnum::Discover(NamedQuery {
+            query: query.transform(|vector| Ok(normalize_vector(vector)))?,
+            using,
+        }),
+        QueryEnum::Context(NamedQuery { query, using }) => QueryEnum::Context(NamedQuery {
+            query: query.transform(|vector| Ok(normalize_vector(vector)))?,
+            using,
+        }),
+    };
+    Ok(query)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+
+    fn length(vector: &[f32]) -> f32 {
+        vector.iter().map(|x| x * x).sum::<f32>().sqrt()
+    }
+
+    #[test]
+    fn test_normalize_vectors() {
+        let normalized = HashSet::from(["image".to_string()]);
+
+        let mut vectors = VectorStruct::Multi(HashMap::from([
+            ("image".to_string(), Vector::Dense(vec![3.0, 4.0])),
+            ("text".to_string(), Vector::Dense(vec![3.0, 4.0])),
+        ]));
+        normalize_vectors(&mut vectors, &normalized);
+
+        let VectorStruct::Multi(vectors) = vectors else {
+            unreachable!()
+        };
+        assert_eq!(vectors["image"], Vector::Dense(vec![0.6, 0.8]));
+        assert_eq!(vectors["text"], Vector::Dense(vec![3.0, 4.0]));
+
+        // Default vector is not normalized
+        let mut vectors = VectorStruct::Single(vec![3.0, 4.0]);
+        normalize_vectors(&mut vectors, &normalized);
+        assert_eq!(vectors, VectorStruct::Single(vec![3.0, 4.0]));
+    }
+
+    #[test]
+    fn test_normalize_batch() {
+        let normalized = HashSet::from([DEFAULT_VECTOR_NAME.to_string()]);
+
+        let mut vectors = BatchVectorStruct::Single(vec![vec![1.0, 1.0, 1.0, 1.0], vec![0.0; 4]]);
+        normalize_batch(&mut vectors, &normalized);
+
+        let BatchVectorStruct::Single(vectors) = vectors else {
+            unreachable!()
+        };
+        assert!((length(&vectors[0]) - 1.0).abs() < 1e-6);
+        // Zero vectors are left as they are
+        assert_eq!(vectors[1], vec![0.0; 4]);
+    }
+
+    #[test]
+    fn test_normalize_query() {
+        let query = QueryEnum::Nearest(NamedVectorStruct::Dense(NamedVector {
+            name: "image".to_string(),
+            vector: vec![3.0, 4.0],
+        }));
+        let QueryEnum::Nearest(NamedVectorStruct::Dense(NamedVector { vector, .. })) =
+            normalize_query(query).unwrap()
+        else {
+            unreachable!()
+        };
+        assert_eq!(vector, vec![0.6, 0.8]);
+    }
+}
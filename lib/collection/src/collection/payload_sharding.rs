@@ -0,0 +1,400 @@
+//! Routing of the points by the value of the sharding payload key
+//!
+//! Collections with the `payload_key` sharding method place every point into the shard of its
+//! `sharding_payload_key` value, so all points of a tenant are stored in the same shard. Reads,
+//! which filter by exact values of the key, only query the shards of these values.
+//!
+//! Inserted points are placed by their payload. Other operations, which select points by ID,
+//! first locate the points, as their shards can't be derived from the ID.
+//!
+//! Placement of a point depends on where it is already stored, so updates by ID are serialized
+//! per point on the peer, which receives them: points are locked from locating till the update
+//! is applied. Otherwise concurrent upserts of the same point with different values of the key
+//! could both miss it and store it in two shards. Updates of the same point, received by
+//! different peers at the same time, are not serialized.
+
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::hash::{Hash, Hasher};
+use std::sync::Arc;
+
+use futures::future;
+use segment::types::{
+    AnyVariants, Condition, Filter, Match, MatchAny, MatchValue, Payload, PointIdType, ShardKey,
+    ValueVariants, WithPayload, WithVector,
+};
+use serde_json::Value;
+use tokio::sync::{Mutex, MutexGuard};
+
+use super::Collection;
+use crate::config::ShardingMethod;
+use crate::operations::operation_effect::{EstimateOperationEffectArea, OperationEffectArea};
+use crate::operations::payload_ops::PayloadOps;
+use crate::operations::point_ops::{PointInsertOperationsInternal, PointOperations};
+use crate::operations::shard_selector_internal::ShardSelectorInternal;
+use crate::operations::types::{CollectionError, CollectionResult, PointRequestInternal};
+use crate::operations::CollectionUpdateOperations;
+use crate::shards::replica_set::read_priority::ReadPriority;
+use crate::shards::replica_set::ShardReplicaSet;
+use crate::shards::shard::ShardId;
+use crate::shards::shard_holder::ShardHolder;
+
+/// Value of the sharding payload key, which is hashed to select the shard
+///
+/// Keywords and integers, which match the same filter value, select the same shard.
+#[derive(Debug, Hash, PartialEq, Eq)]
+enum ShardingValue<'a> {
+    Keyword(&'a str),
+    Integer(i64),
+}
+
+impl<'a> ShardingValue<'a> {
+    fn from_payload(payload: Option<&'a Payload>, sharding_key: &str) -> Option<Self> {
+        match payload?.0.get(sharding_key)? {
+            Value::String(keyword) => Some(Self::Keyword(keyword)),
+            Value::Number(number) => number.as_i64().map(Self::Integer),
+            _ => None,
+        }
+    }
+}
+
+/// Number of locks, the points are spread over by the hash of their ID
+const PLACEMENT_LOCKS: usize = 64;
+
+/// Locks of the points, which are being placed
+///
+/// Updates of the points, which share a lock, are placed one at a time.
+pub(crate) struct PlacementLocks {
+    locks: Vec<Mutex<()>>,
+}
+
+impl Default for PlacementLocks {
+    fn default() -> Self {
+        Self {
+            locks: (0..PLACEMENT_LOCKS).map(|_| Mutex::new(())).collect(),
+        }
+    }
+}
+
+impl PlacementLocks {
+    /// Lock all the points, locks are always taken in the same order to avoid deadlocks
+    async fn lock(&self, ids: &[PointIdType]) -> Vec<MutexGuard<'_, ()>> {
+        let lock_indices: BTreeSet<usize> = ids
+            .iter()
+            .map(|id| {
+                let mut hasher = DefaultHasher::new();
+                id.hash(&mut hasher);
+                hasher.finish() as usize % PLACEMENT_LOCKS
+            })
+            .collect();
+        let mut guards = Vec::with_capacity(lock_indices.len());
+        for index in lock_indices {
+            guards.push(self.locks[index].lock().await);
+        }
+        guards
+    }
+}
+
+impl Collection {
+    /// Sharding payload key, if the collection uses the `payload_key` sharding method
+    pub(crate) async fn sharding_payload_key(&self) -> Option<String> {
+        let config = self.collection_config.read().await;
+        match config.params.sharding_method.unwrap_or_default() {
+            ShardingMethod::PayloadKey => config.params.sharding_payload_key.clone(),
+            ShardingMethod::Auto | ShardingMethod::Custom => None,
+        }
+    }
+
+    /// Shards of the points, affected by the operation
+    ///
+    /// Points, which are neither inserted nor found, are left to the hash of their ID, so the
+    /// operation fails for them the same way as in other collections.
+    ///
+    /// Returned guards lock the points, they must be held until the operation is applied.
+    pub(crate) async fn payload_key_placement(
+        &self,
+        operation: &CollectionUpdateOperations,
+        sharding_key: &str,
+    ) -> CollectionResult<(HashMap<PointIdType, ShardId>, Vec<MutexGuard<'_, ()>>)> {
+        if let CollectionUpdateOperations::Batch(_) = operation {
+            return Err(CollectionError::bad_request(format!(
+                "Batches of operations are not supported in collections, sharded by the payload key `{sharding_key}`"
+            )));
+        }
+        let ids = match operation.estimate_effect_area() {
+            OperationEffectArea::Points(ids) if !ids.is_empty() => ids,
+            OperationEffectArea::Filter(_) => {
+                check_sharding_key_not_set(operation, sharding_key)?;
+                return Ok((HashMap::new(), Vec::new()));
+            }
+            OperationEffectArea::Points(_) | OperationEffectArea::Empty => {
+                return Ok((HashMap::new(), Vec::new()))
+            }
+        };
+
+        let guards = self.placement_locks.lock(&ids).await;
+        let located = self.locate_points(ids).await?;
+
+        let shard_holder = self.shards_holder.read().await;
+        let placed = match operation {
+            CollectionUpdateOperations::PointOperation(PointOperations::UpsertPoints(insert)) => {
+                inserted_points_placement(insert, sharding_key, &shard_holder)?
+            }
+            CollectionUpdateOperations::PayloadOperation(
+                PayloadOps::SetPayload(set_payload) | PayloadOps::OverwritePayload(set_payload),
+            ) => {
+                let shard_id =
+                    ShardingValue::from_payload(Some(&set_payload.payload), sharding_key)
+                        .and_then(|value| shard_holder.payload_value_to_shard(&value));
+                match shard_id {
+                    Some(shard_id) => located.keys().map(|id| (*id, shard_id)).collect(),
+                    None => {
+                        check_sharding_key_not_set(operation, sharding_key)?;
+                        HashMap::new()
+                    }
+                }
+            }
+            _ => HashMap::new(),
+        };
+
+        // Points are never moved between the shards, changing the value would lose them
+        for (id, shard_id) in &placed {
+            if located.get(id).is_some_and(|located| located != shard_id) {
+                return Err(CollectionError::bad_input(format!(
+                    "Point {id} is stored with another value of the sharding payload key `{sharding_key}`, \
+                     delete the point before changing the value",
+                )));
+            }
+        }
+
+        let mut placement = located;
+        placement.extend(placed);
+        Ok((placement, guards))
+    }
+
+    /// Shards, which store the points
+    async fn locate_points(
+        &self,
+        ids: Vec<PointIdType>,
+    ) -> CollectionResult<HashMap<PointIdType, ShardId>> {
+        let request = Arc::new(PointRequestInternal {
+            ids,
+            with_payload: None,
+            with_vector: WithVector::Bool(false),
+            as_of: None,
+            with_version: false,
+        });
+        let with_payload = WithPayload::from(false);
+
+        let shard_holder = self.shards_holder.read().await;
+        let shard_records = future::try_join_all(shard_holder.all_shards().map(|shard| async {
+            let records = shard
+                .retrieve(
+                    request.clone(),
+                    &with_payload,
+                    &request.with_vector,
+                    None,
+                    ReadPriority::Normal,
+                    None,
+                    false,
+                )
+                .await?;
+            CollectionResult::Ok((shard.shard_id, records))
+        }))
+        .await?;
+
+        Ok(shard_records
+            .into_iter()
+            .flat_map(|(shard_id, records)| {
+                records.into_iter().map(move |record| (record.id, shard_id))
+            })
+            .collect())
+    }
+}
+
+/// Shards of the inserted points, by their values of the sharding payload key
+fn inserted_points_placement(
+    insert: &PointInsertOperationsInternal,
+    sharding_key: &str,
+    shard_holder: &ShardHolder,
+) -> CollectionResult<HashMap<PointIdType, ShardId>> {
+    let points: Vec<(PointIdType, Option<&Payload>)> = match insert {
+        PointInsertOperationsInternal::PointsBatch(batch) => match &batch.payloads {
+            Some(payloads) => batch
+                .ids
+                .iter()
+                .copied()
+                .zip(payloads.iter().map(Option::as_ref))
+                .collect(),
+            None => batch.ids.iter().map(|id| (*id, None)).collect(),
+        },
+        PointInsertOperationsInternal::PointsList(points) => points
+            .iter()
+            .map(|point| (point.id, point.payload.as_ref()))
+            .collect(),
+    };
+
+    points
+        .into_iter()
+        .map(|(id, payload)| {
+            let shard_id = ShardingValue::from_payload(payload, sharding_key)
+                .and_then(|value| shard_holder.payload_value_to_shard(&value))
+                .ok_or_else(|| {
+                    CollectionError::bad_input(format!(
+                        "Point {id} must have a keyword or integer value of the sharding payload key `{sharding_key}`",
+                    ))
+                })?;
+            Ok((id, shard_id))
+        })
+        .collect()
+}
+
+/// Check that the operation doesn't set the sharding payload key of the points, selected by filter
+fn check_sharding_key_not_set(
+    operation: &CollectionUpdateOperations,
+    sharding_key: &str,
+) -> CollectionResult<()> {
+    let CollectionUpdateOperations::PayloadOperation(
+        PayloadOps::SetPayload(set_payload) | PayloadOps::OverwritePayload(set_payload),
+    ) = operation
+    else {
+        return Ok(());
+    };
+    if set_payload.payload.contains_key(sharding_key) {
+        return Err(CollectionError::bad_input(format!(
+            "The sharding payload key `{sharding_key}` could only be set to a keyword or integer \
+             value, for the points selected by ID",
+        )));
+    }
+    Ok(())
+}
+
+/// Shards, which could contain points matching the filter, `None` if it could be any shard
+fn filter_shards(
+    filter: &Filter,
+    sharding_key: &str,
+    shard_holder: &ShardHolder,
+) -> Option<HashSet<ShardId>> {
+    filter
+        .must
+        .iter()
+        .flatten()
+        .find_map(|condition| match condition {
+            Condition::Field(field) if field.key == sharding_key => {
+                let values = match field.r#match.as_ref()? {
+                    Match::Value(MatchValue {
+                        value: ValueVariants::Keyword(keyword),
+                    }) => vec![ShardingValue::Keyword(keyword)],
+                    Match::Value(MatchValue {
+                        value: ValueVariants::Integer(integer),
+                    }) => vec![ShardingValue::Integer(*integer)],
+                    Match::Any(MatchAny {
+                        any: AnyVariants::Keywords(keywords),
+                    }) => keywords
+                        .iter()
+                        .map(|keyword| ShardingValue::Keyword(keyword))
+                        .collect(),
+                    Match::Any(MatchAny {
+                        any: AnyVariants::Integers(integers),
+                    }) => integers
+                        .iter()
+                        .map(|integer| ShardingValue::Integer(*integer))
+                        .collect(),
+                    _ => return None,
+                };
+                values
+                    .iter()
+                    .map(|value| shard_holder.payload_value_to_shard(value))
+                    .collect()
+            }
+            Condition::Filter(filter) => filter_shards(filter, sharding_key, shard_holder),
+            _ => None,
+        })
+}
+
+/// Shards of the selection, which could contain points matching any of the filters
+///
+/// Filters by exact values of the sharding payload key only need the shards of these values.
+pub(super) fn select_shards_by_filters<'a, 'b>(
+    shard_holder: &'a ShardHolder,
+    shard_selection: &'a ShardSelectorInternal,
+    sharding_key: Option<&str>,
+    mut filters: impl Iterator<Item = Option<&'b Filter>>,
+) -> CollectionResult<Vec<(&'a ShardReplicaSet, Option<&'a ShardKey>)>> {
+    let shards = shard_holder.select_shards(shard_selection)?;
+    let Some(sharding_key) = sharding_key else {
+        return Ok(shards);
+    };
+    // Requests forwarded to a specific shard are already routed
+    if !matches!(shard_selection, ShardSelectorInternal::All) {
+        return Ok(shards);
+    }
+
+    let mut filtered_shards = HashSet::new();
+    let all_filtered = filters.all(|filter| {
+        match filter.and_then(|filter| filter_shards(filter, sharding_key, shard_holder)) {
+            Some(shard_ids) => {
+                filtered_shards.extend(shard_ids);
+                true
+            }
+            None => false,
+        }
+    });
+    if !all_filtered {
+        return Ok(shards);
+    }
+
+    Ok(shards
+        .into_iter()
+        .filter(|(shard, _)| filtered_shards.contains(&shard.shard_id))
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use segment::types::FieldCondition;
+    use serde_json::json;
+
+    use super::*;
+    use crate::operations::payload_ops::SetPayloadOp;
+
+    #[test]
+    fn test_sharding_value_from_payload() {
+        let payload: Payload =
+            json!({"tenant": "acme", "group": 7, "score": 0.5, "tags": ["a"]}).into();
+
+        assert_eq!(
+            ShardingValue::from_payload(Some(&payload), "tenant"),
+            Some(ShardingValue::Keyword("acme")),
+        );
+        assert_eq!(
+            ShardingValue::from_payload(Some(&payload), "group"),
+            Some(ShardingValue::Integer(7)),
+        );
+        assert_eq!(ShardingValue::from_payload(Some(&payload), "score"), None);
+        assert_eq!(ShardingValue::from_payload(Some(&payload), "tags"), None);
+        assert_eq!(ShardingValue::from_payload(Some(&payload), "missing"), None);
+        assert_eq!(ShardingValue::from_payload(None, "tenant"), None);
+    }
+
+    #[test]
+    fn test_check_sharding_key_not_set() {
+        let set_payload = |payload: Value| {
+            CollectionUpdateOperations::PayloadOperation(PayloadOps::SetPayload(SetPayloadOp {
+                payload: payload.into(),
+                points: None,
+                filter: Some(Filter::new_must(Condition::Field(
+                    FieldCondition::new_match("color".to_string(), "red".to_string().into()),
+                ))),
+                if_version: None,
+            }))
+        };
+
+        assert!(
+            check_sharding_key_not_set(&set_payload(json!({"color": "blue"})), "tenant").is_ok()
+        );
+        assert!(
+            check_sharding_key_not_set(&set_payload(json!({"tenant": "acme"})), "tenant").is_err()
+        );
+    }
+}
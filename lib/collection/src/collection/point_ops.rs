@@ -1,19 +1,53 @@
+use std::collections::HashMap;
+use std::iter;
+use std::str::FromStr;
 use std::sync::Arc;
+use std::time::Duration;
 
 use futures::{future, TryFutureExt, TryStreamExt as _};
 use itertools::Itertools as _;
-use segment::types::{ShardKey, WithPayload, WithPayloadInterface};
+use rand::seq::SliceRandom;
+use segment::types::{Filter, ShardKey, WithPayload, WithPayloadInterface, WithVector};
+use uuid::Uuid;
 use validator::Validate as _;
 
-use super::Collection;
-use crate::operations::consistency_params::ReadConsistency;
+use super::access_tags::{restrict_by_access_tags, AccessTags};
+use super::payload_sharding::select_shards_by_filters;
+use super::{soft_delete, Collection};
+use crate::common::sampling::proportional_sample_sizes;
+use crate::operations::consistency_params::{ReadConsistency, WriteConsistency};
+use crate::operations::consistency_token::ConsistencyToken;
 use crate::operations::point_ops::WriteOrdering;
 use crate::operations::shard_selector_internal::ShardSelectorInternal;
 use crate::operations::types::*;
+use crate::operations::update_priority::UpdatePriority;
 use crate::operations::CollectionUpdateOperations;
+use crate::shards::replica_set::read_priority::ReadPriority;
+use crate::shards::replica_set::stale_reads::StaleReads;
+use crate::shards::scroll_session::{SessionRef, SessionToken, SCROLL_SESSION_TIMEOUT};
 use crate::shards::shard::ShardId;
+use crate::shards::time_travel;
 
 impl Collection {
+    /// Resolve `wait` and `ordering` of the client update, falling back to the collection defaults
+    /// for the ones not specified in the request
+    pub async fn resolve_update_params(
+        &self,
+        wait: Option<bool>,
+        ordering: Option<WriteOrdering>,
+    ) -> (bool, WriteOrdering) {
+        if let (Some(wait), Some(ordering)) = (wait, ordering) {
+            return (wait, ordering);
+        }
+        let config = self.collection_config.read().await;
+        (
+            wait.or(config.params.default_wait).unwrap_or(false),
+            ordering
+                .or(config.params.default_write_ordering)
+                .unwrap_or_default(),
+        )
+    }
+
     /// Apply collection update operation to all local shards.
     /// Return None if there are no local shards
     pub async fn update_all_local(
@@ -26,7 +60,7 @@ impl Collection {
 
         let res: Vec<_> = shard_holder_guard
             .all_shards()
-            .map(|shard| shard.update_local(operation.clone(), wait))
+            .map(|shard| shard.update_local(operation.clone(), wait, None))
             .collect();
 
         let results: Vec<_> = future::try_join_all(res).await?;
@@ -45,6 +79,10 @@ impl Collection {
         shard_selection: ShardId,
         wait: bool,
         ordering: WriteOrdering,
+        shard_deactivation_timeout: Option<Duration>,
+        write_consistency: Option<WriteConsistency>,
+        timeout: Option<Duration>,
+        priority: Option<UpdatePriority>,
     ) -> CollectionResult<UpdateResult> {
         let _update_lock = self.updates_lock.read().await;
         let shard_holder_guard = self.shards_holder.read().await;
@@ -52,10 +90,18 @@ impl Collection {
         let res = match shard_holder_guard.get_shard(&shard_selection) {
             None => None,
             Some(target_shard) => match ordering {
-                WriteOrdering::Weak => target_shard.update_local(operation, wait).await?,
+                WriteOrdering::Weak => target_shard.update_local(operation, wait, priority).await?,
                 WriteOrdering::Medium | WriteOrdering::Strong => Some(
                     target_shard
-                        .update_with_consistency(operation, wait, ordering)
+                        .update_with_consistency(
+                            operation,
+                            wait,
+                            ordering,
+                            shard_deactivation_timeout,
+                            write_consistency,
+                            timeout,
+                            priority,
+                        )
                         .await?,
                 ),
             },
@@ -76,23 +122,66 @@ impl Collection {
         wait: bool,
         ordering: WriteOrdering,
     ) -> CollectionResult<UpdateResult> {
-        self.update_from_client(operation, wait, ordering, None)
+        self.update_from_client(operation, wait, ordering, None, None, None, None, None)
             .await
     }
 
+    /// Apply the collection settings, which change the stored data, to the operation
+    async fn prepare_operation(
+        &self,
+        operation: CollectionUpdateOperations,
+    ) -> CollectionResult<CollectionUpdateOperations> {
+        if let CollectionUpdateOperations::Batch(_) = operation {
+            return Err(CollectionError::bad_request(
+                "Nested batches of operations are not supported".to_string(),
+            ));
+        }
+        let operation = self.soft_delete_operation(operation).await;
+        let operation = self.reduce_operation(operation).await?;
+        Ok(self.normalize_operation(operation).await)
+    }
+
     pub async fn update_from_client(
         &self,
         operation: CollectionUpdateOperations,
         wait: bool,
         ordering: WriteOrdering,
         shard_keys_selection: Option<ShardKey>,
+        shard_deactivation_timeout: Option<Duration>,
+        write_consistency: Option<WriteConsistency>,
+        timeout: Option<Duration>,
+        priority: Option<UpdatePriority>,
     ) -> CollectionResult<UpdateResult> {
         operation.validate()?;
+        let is_write_operation = operation.is_write_operation();
+        self.read_only.read().check_collection(is_write_operation)?;
+        let operation = match operation {
+            CollectionUpdateOperations::Batch(operations) => {
+                let mut prepared = Vec::with_capacity(operations.len());
+                for operation in operations {
+                    prepared.push(self.prepare_operation(operation).await?);
+                }
+                CollectionUpdateOperations::Batch(prepared)
+            }
+            operation => self.prepare_operation(operation).await?,
+        };
+        let stored_query_candidates = self.stored_query_candidates(&operation);
+        let (placement, placement_guards) = match self.sharding_payload_key().await {
+            Some(sharding_key) => {
+                self.payload_key_placement(&operation, &sharding_key)
+                    .await?
+            }
+            None => (HashMap::new(), Vec::new()),
+        };
+        // Next updates of the locked points are placed by the result of this one, so it must be
+        // applied before the points are unlocked
+        let wait = wait || !placement_guards.is_empty();
         let _update_lock = self.updates_lock.read().await;
 
         let mut results = {
             let shards_holder = self.shards_holder.read().await;
-            let shard_to_op = shards_holder.split_by_shard(operation, &shard_keys_selection)?;
+            let shard_to_op =
+                shards_holder.split_by_shard(operation, &shard_keys_selection, placement)?;
 
             if shard_to_op.is_empty() {
                 return Err(CollectionError::bad_request(
@@ -100,10 +189,26 @@ impl Collection {
                 ));
             }
 
+            // Update is rejected as a whole, so it is not applied partially
+            {
+                let read_only = self.read_only.read();
+                for (replica_set, _) in &shard_to_op {
+                    read_only.check_shard(replica_set.shard_id, is_write_operation)?;
+                }
+            }
+
             let shard_requests = shard_to_op
                 .into_iter()
                 .map(move |(replica_set, operation)| {
-                    replica_set.update_with_consistency(operation, wait, ordering)
+                    replica_set.update_with_consistency(
+                        operation,
+                        wait,
+                        ordering,
+                        shard_deactivation_timeout,
+                        write_consistency,
+                        timeout,
+                        priority,
+                    )
                 });
             future::join_all(shard_requests).await
         };
@@ -131,9 +236,55 @@ impl Collection {
                 first_err
             }
         } else {
+            let consistency_token = ConsistencyToken::merge_all(results.iter().map(|result| {
+                result
+                    .as_ref()
+                    .ok()
+                    .and_then(|res| res.consistency_token.as_deref())
+            }))?;
+            let acknowledgments = results
+                .iter_mut()
+                .filter_map(|result| result.as_mut().ok()?.acknowledgments.take())
+                .reduce(|mut acknowledgments, shard_acknowledgments| {
+                    acknowledgments.extend(shard_acknowledgments);
+                    acknowledgments
+                });
             // At least one result is always present.
-            results.pop().unwrap()
+            let mut result = results.pop().unwrap()?;
+            // Reads should expect the update in all of the affected shards
+            result.consistency_token = consistency_token.map(|token| token.to_string());
+            result.acknowledgments = acknowledgments;
+            if let Some(points) = stored_query_candidates {
+                self.notify_stored_queries(&points).await;
+            }
+            Ok(result)
+        }
+    }
+
+    /// Check, that points could be read as they were at the given time
+    async fn check_time_travel(&self, as_of: Option<u64>) -> CollectionResult<()> {
+        let Some(as_of) = as_of else {
+            return Ok(());
+        };
+        let Some(window_sec) = self
+            .collection_config
+            .read()
+            .await
+            .params
+            .time_travel_window_sec
+        else {
+            return Err(CollectionError::bad_request(format!(
+                "Time-travel reads are not enabled for collection {}, set `time_travel_window_sec` to enable them",
+                self.id,
+            )));
+        };
+        let window_start = (time_travel::now_millis() / 1000).saturating_sub(window_sec);
+        if as_of < window_start {
+            return Err(CollectionError::bad_request(format!(
+                "Requested time {as_of} is outside of the time-travel window of {window_sec} seconds",
+            )));
         }
+        Ok(())
     }
 
     pub async fn scroll_by(
@@ -141,8 +292,24 @@ impl Collection {
         request: ScrollRequestInternal,
         read_consistency: Option<ReadConsistency>,
         shard_selection: &ShardSelectorInternal,
+        access_tags: Option<&AccessTags>,
+        read_priority: ReadPriority,
+        stale_reads: Option<&StaleReads>,
     ) -> CollectionResult<ScrollResult> {
+        self.check_time_travel(request.as_of).await?;
+        let as_of = request.as_of;
+        let mut session_token = request
+            .session_id
+            .as_deref()
+            .map(SessionToken::from_str)
+            .transpose()?;
+        let session = session_token.as_ref().map(|token| SessionRef {
+            id: token.id.clone(),
+            ttl: request.session_ttl_sec.map(Duration::from_secs),
+        });
         let default_request = ScrollRequestInternal::default();
+        let filter =
+            restrict_by_access_tags(self.exclude_soft_deleted(request.filter).await, access_tags);
 
         let offset = request.offset;
         let limit = request
@@ -153,6 +320,10 @@ impl Collection {
             .clone()
             .unwrap_or_else(|| default_request.with_payload.clone().unwrap());
         let with_vector = request.with_vector;
+        let order_by = request.order_by;
+        let sample = request.sample;
+        // Versions are dropped by the first node, peers always return them
+        let with_version = request.with_version || shard_selection.is_shard_id();
 
         if limit == 0 {
             return Err(CollectionError::BadRequest {
@@ -160,11 +331,76 @@ impl Collection {
             });
         }
 
+        if order_by.is_some() && offset.is_some() {
+            return Err(CollectionError::bad_request(
+                "Offset can't be used with `order_by`, use `order_by.start_from` instead"
+                    .to_string(),
+            ));
+        }
+        if order_by.is_some() && as_of.is_some() {
+            return Err(CollectionError::bad_request(
+                "`order_by` is not supported for time-travel reads".to_string(),
+            ));
+        }
+        if sample.is_some() && (offset.is_some() || order_by.is_some() || as_of.is_some()) {
+            return Err(CollectionError::bad_request(
+                "`sample` can't be combined with `offset`, `order_by` or `as_of`".to_string(),
+            ));
+        }
+        if session.is_some() && (as_of.is_some() || order_by.is_some() || sample.is_some()) {
+            return Err(CollectionError::bad_request(
+                "`session_id` can't be combined with `as_of`, `order_by` or `sample`".to_string(),
+            ));
+        }
+
+        if let Some(sample) = sample {
+            let mut points = self
+                .sample_points(
+                    sample,
+                    limit,
+                    &with_payload_interface,
+                    &with_vector,
+                    filter,
+                    read_consistency,
+                    read_priority,
+                    stale_reads,
+                    shard_selection,
+                )
+                .await?;
+            prepare_records(&mut points, with_version);
+            return Ok(ScrollResult {
+                points,
+                next_page_offset: None,
+                session_id: None,
+            });
+        }
+
         // Needed to return next page offset.
         let limit = limit + 1;
-        let retrieved_points: Vec<_> = {
+        let sharding_key = self.sharding_payload_key().await;
+        let mut retrieved_points: Vec<_> = {
             let shards_holder = self.shards_holder.read().await;
-            let target_shards = shards_holder.select_shards(shard_selection)?;
+            let target_shards = select_shards_by_filters(
+                &shards_holder,
+                shard_selection,
+                sharding_key.as_deref(),
+                iter::once(filter.as_ref()),
+            )?;
+
+            // Pages of the session are read from the replicas, which keep the session
+            let mut shard_tokens = HashMap::new();
+            if let Some(session_token) = &mut session_token {
+                if !shard_selection.is_shard_id() {
+                    for (shard, _) in &target_shards {
+                        if let Some(token) =
+                            session_token.shard_token(shard, offset.is_none()).await?
+                        {
+                            shard_tokens.insert(shard.shard_id, token);
+                        }
+                    }
+                }
+            }
+
             let scroll_futures = target_shards.into_iter().map(|(shard, shard_key)| {
                 let shard_key = shard_key.cloned();
                 shard
@@ -173,8 +409,15 @@ impl Collection {
                         limit,
                         &with_payload_interface,
                         &with_vector,
-                        request.filter.as_ref(),
+                        filter.as_ref(),
+                        order_by.as_ref(),
+                        None,
+                        as_of,
+                        session.as_ref(),
                         read_consistency,
+                        read_priority,
+                        stale_reads,
+                        shard_tokens.get(&shard.shard_id),
                         shard_selection.is_shard_id(),
                     )
                     .and_then(move |mut records| async move {
@@ -190,6 +433,31 @@ impl Collection {
 
             future::try_join_all(scroll_futures).await?
         };
+        for points in &mut retrieved_points {
+            prepare_records(points, with_version);
+        }
+        if let Some(order_by) = &order_by {
+            // Next page is requested with `order_by.start_from`, so the offset is not returned
+            let points = retrieved_points
+                .into_iter()
+                .flatten()
+                .sorted_by(|a, b| {
+                    order_by
+                        .compare(
+                            a.order_value.unwrap_or_default(),
+                            b.order_value.unwrap_or_default(),
+                        )
+                        .then(a.id.cmp(&b.id))
+                })
+                .take(limit - 1)
+                .collect();
+            return Ok(ScrollResult {
+                points,
+                next_page_offset: None,
+                session_id: None,
+            });
+        }
+
         let mut points: Vec<_> = retrieved_points
             .into_iter()
             .flatten()
@@ -207,17 +475,176 @@ impl Collection {
         Ok(ScrollResult {
             points,
             next_page_offset,
+            session_id: session_token.map(|token| token.to_string()),
         })
     }
 
+    /// Open a scroll session on all selected shards of the collection
+    ///
+    /// Shards start sessions by their first page, so each of them is opened by reading a single
+    /// point within the new session. Only one replica of each shard keeps the session, the
+    /// returned session ID routes the scrolls to it.
+    pub async fn open_scroll_session(
+        &self,
+        request: OpenScrollSession,
+        read_consistency: Option<ReadConsistency>,
+        shard_selection: &ShardSelectorInternal,
+    ) -> CollectionResult<ScrollSessionInfo> {
+        let ttl = request
+            .ttl_sec
+            .map_or(SCROLL_SESSION_TIMEOUT, Duration::from_secs);
+        let mut session_token = SessionToken::new(Uuid::new_v4().to_string());
+        let session = SessionRef {
+            id: session_token.id.clone(),
+            ttl: Some(ttl),
+        };
+
+        {
+            let shards_holder = self.shards_holder.read().await;
+            let target_shards = shards_holder.select_shards(shard_selection)?;
+
+            // Each shard keeps the session on a single replica, which serves all of its pages
+            let mut shard_tokens = HashMap::new();
+            if !shard_selection.is_shard_id() {
+                for (shard, _) in &target_shards {
+                    if let Some(token) = session_token.shard_token(shard, true).await? {
+                        shard_tokens.insert(shard.shard_id, token);
+                    }
+                }
+            }
+
+            let open_futures = target_shards.into_iter().map(|(shard, _shard_key)| {
+                shard.scroll_by(
+                    None,
+                    1,
+                    &WithPayloadInterface::Bool(false),
+                    &WithVector::Bool(false),
+                    None,
+                    None,
+                    None,
+                    None,
+                    Some(&session),
+                    read_consistency,
+                    ReadPriority::Normal,
+                    None,
+                    shard_tokens.get(&shard.shard_id),
+                    shard_selection.is_shard_id(),
+                )
+            });
+            future::try_join_all(open_futures).await?;
+        }
+
+        Ok(ScrollSessionInfo {
+            session_id: session_token.to_string(),
+            ttl_sec: ttl.as_secs(),
+        })
+    }
+
+    /// Sample of the points, which match the filter
+    ///
+    /// Each shard returns a sample of `limit` points, but only a part of it is used, proportional
+    /// to the approximate number of the matching points in the shard. So the sample is uniform
+    /// over the points of all shards.
+    #[allow(clippy::too_many_arguments)]
+    async fn sample_points(
+        &self,
+        sample: Sample,
+        limit: usize,
+        with_payload_interface: &WithPayloadInterface,
+        with_vector: &WithVector,
+        filter: Option<Filter>,
+        read_consistency: Option<ReadConsistency>,
+        read_priority: ReadPriority,
+        stale_reads: Option<&StaleReads>,
+        shard_selection: &ShardSelectorInternal,
+    ) -> CollectionResult<Vec<Record>> {
+        let sharding_key = self.sharding_payload_key().await;
+        let count_request = Arc::new(CountRequestInternal {
+            filter: filter.clone(),
+            exact: false,
+        });
+        let shard_samples = {
+            let shards_holder = self.shards_holder.read().await;
+            let target_shards = select_shards_by_filters(
+                &shards_holder,
+                shard_selection,
+                sharding_key.as_deref(),
+                iter::once(filter.as_ref()),
+            )?;
+            let sample_futures = target_shards.into_iter().map(|(shard, shard_key)| {
+                let shard_key = shard_key.cloned();
+                let count = shard.count(
+                    count_request.clone(),
+                    read_consistency,
+                    read_priority,
+                    stale_reads,
+                    shard_selection.is_shard_id(),
+                );
+                let points = shard.scroll_by(
+                    None,
+                    limit,
+                    with_payload_interface,
+                    with_vector,
+                    filter.as_ref(),
+                    None,
+                    Some(sample),
+                    None,
+                    None,
+                    read_consistency,
+                    read_priority,
+                    stale_reads,
+                    None,
+                    shard_selection.is_shard_id(),
+                );
+                future::try_join(count, points).map_ok(move |(count, mut points)| {
+                    if shard_key.is_some() {
+                        for point in &mut points {
+                            point.shard_key = shard_key.clone();
+                        }
+                    }
+                    (count.count, points)
+                })
+            });
+            future::try_join_all(sample_futures).await?
+        };
+
+        let sizes: Vec<_> = shard_samples
+            .iter()
+            .map(|(count, points)| (*count).max(points.len()))
+            .collect();
+        let mut points: Vec<_> = shard_samples
+            .into_iter()
+            .zip(proportional_sample_sizes(&sizes, limit))
+            .flat_map(|((_, points), sample_size)| points.into_iter().take(sample_size))
+            .collect();
+        points.shuffle(&mut rand::thread_rng());
+        Ok(points)
+    }
+
     pub async fn count(
         &self,
         request: CountRequestInternal,
         read_consistency: Option<ReadConsistency>,
         shard_selection: &ShardSelectorInternal,
+        access_tags: Option<&AccessTags>,
+        read_priority: ReadPriority,
+        stale_reads: Option<&StaleReads>,
     ) -> CollectionResult<CountResult> {
+        let request = CountRequestInternal {
+            filter: restrict_by_access_tags(
+                self.exclude_soft_deleted(request.filter).await,
+                access_tags,
+            ),
+            ..request
+        };
+        let sharding_key = self.sharding_payload_key().await;
         let shards_holder = self.shards_holder.read().await;
-        let shards = shards_holder.select_shards(shard_selection)?;
+        let shards = select_shards_by_filters(
+            &shards_holder,
+            shard_selection,
+            sharding_key.as_deref(),
+            iter::once(request.filter.as_ref()),
+        )?;
 
         let request = Arc::new(request);
         let mut requests: futures::stream::FuturesUnordered<_> = shards
@@ -227,6 +654,8 @@ impl Collection {
                 shard.count(
                     request.clone(),
                     read_consistency,
+                    read_priority,
+                    stale_reads,
                     shard_selection.is_shard_id(),
                 )
             })
@@ -246,7 +675,23 @@ impl Collection {
         request: PointRequestInternal,
         read_consistency: Option<ReadConsistency>,
         shard_selection: &ShardSelectorInternal,
+        access_tags: Option<&AccessTags>,
+        read_priority: ReadPriority,
+        stale_reads: Option<&StaleReads>,
     ) -> CollectionResult<Vec<Record>> {
+        self.check_time_travel(request.as_of).await?;
+        if self.is_soft_delete_enabled().await || access_tags.is_some() {
+            return self
+                .retrieve_by_scroll(
+                    &request,
+                    read_consistency,
+                    shard_selection,
+                    access_tags,
+                    read_priority,
+                    stale_reads,
+                )
+                .await;
+        }
         let with_payload_interface = request
             .with_payload
             .as_ref()
@@ -264,6 +709,8 @@ impl Collection {
                         &with_payload,
                         &request.with_vector,
                         read_consistency,
+                        read_priority,
+                        stale_reads,
                         shard_selection.is_shard_id(),
                     )
                     .and_then(move |mut records| async move {
@@ -278,7 +725,23 @@ impl Collection {
             });
             future::try_join_all(retrieve_futures).await?
         };
-        let points = all_shard_collection_results.into_iter().flatten().collect();
+        let mut points: Vec<_> = all_shard_collection_results.into_iter().flatten().collect();
+        prepare_records(
+            &mut points,
+            request.with_version || shard_selection.is_shard_id(),
+        );
         Ok(points)
     }
 }
+
+/// Prepare the records, read from the shards, to be returned by the collection
+///
+/// Removes the soft delete marker from the payload, and the versions, if they are not requested.
+fn prepare_records(records: &mut [Record], with_version: bool) {
+    for record in records {
+        soft_delete::hide_soft_delete_marker(&mut record.payload);
+        if !with_version {
+            record.version = None;
+        }
+    }
+}
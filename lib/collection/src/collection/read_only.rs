@@ -0,0 +1,199 @@
+//! Read-only freeze of the collection or of its shards
+//!
+//! Frozen collection or shard rejects updates from clients with [`CollectionError::ReadOnly`],
+//! while reads are served as usual. A freeze may still accept deletes, so that space can be freed. It is used to keep the data unchanged during migrations or
+//! forensic analysis, and by the disk watchdog, when free space of the peer runs out.
+//!
+//! The state is changed by consensus operations, so all peers agree on it. It is a state of the
+//! cluster, not of the data, so it is not stored in snapshots.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+use crate::collection::Collection;
+use crate::operations::types::{CollectionError, CollectionResult};
+use crate::save_on_disk::SaveOnDisk;
+use crate::shards::shard::ShardId;
+
+pub const READ_ONLY_FILE: &str = "read_only.json";
+
+/// Freeze of the collection or of a shard
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, Default, PartialEq, Eq, Hash)]
+pub struct Freeze {
+    /// Why updates are rejected, reported in the errors of the updates
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+    /// Deletes are still accepted, so that disk space can be freed
+    #[serde(default)]
+    pub allow_deletes: bool,
+}
+
+impl Freeze {
+    fn rejects(&self, is_write_operation: bool) -> bool {
+        is_write_operation || !self.allow_deletes
+    }
+}
+
+/// Frozen collection and shards
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, Default, PartialEq, Eq)]
+pub struct ReadOnlyState {
+    /// Set if the whole collection is frozen
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub collection: Option<Freeze>,
+    /// Frozen shards of the collection
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub shards: BTreeMap<ShardId, Freeze>,
+}
+
+/// Request to freeze or unfreeze the collection, or one of its shards
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone)]
+pub struct SetReadOnlyRequest {
+    /// Reject updates if true, accept them again if false
+    pub read_only: bool,
+    /// Freeze only this shard. If not set - the whole collection
+    #[serde(default)]
+    pub shard_id: Option<ShardId>,
+    /// Why updates are rejected, reported in the errors of the updates
+    #[serde(default)]
+    #[validate(length(max = 1024))]
+    pub reason: Option<String>,
+}
+
+impl SetReadOnlyRequest {
+    pub fn freeze(self) -> Option<Freeze> {
+        self.read_only.then_some(Freeze {
+            reason: self.reason,
+            allow_deletes: false,
+        })
+    }
+}
+
+impl ReadOnlyState {
+    /// Freeze or unfreeze the collection, or only the given shard
+    pub fn set(&mut self, shard_id: Option<ShardId>, freeze: Option<Freeze>) {
+        match (shard_id, freeze) {
+            (None, freeze) => self.collection = freeze,
+            (Some(shard_id), Some(freeze)) => {
+                self.shards.insert(shard_id, freeze);
+            }
+            (Some(shard_id), None) => {
+                self.shards.remove(&shard_id);
+            }
+        }
+    }
+
+    /// Error of the update of the collection, if it is frozen
+    ///
+    /// See [`crate::operations::CollectionUpdateOperations::is_write_operation`] for updates,
+    /// which are deletes.
+    pub fn check_collection(&self, is_write_operation: bool) -> CollectionResult<()> {
+        match &self.collection {
+            Some(freeze) if freeze.rejects(is_write_operation) => {
+                Err(read_only_error("Collection", freeze))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Error of the update of the shard, if it is frozen
+    pub fn check_shard(&self, shard_id: ShardId, is_write_operation: bool) -> CollectionResult<()> {
+        match self.shards.get(&shard_id) {
+            Some(freeze) if freeze.rejects(is_write_operation) => {
+                Err(read_only_error(&format!("Shard {shard_id}"), freeze))
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+fn read_only_error(what: &str, freeze: &Freeze) -> CollectionError {
+    let description = match &freeze.reason {
+        Some(reason) => format!("{what} is frozen: {reason}"),
+        None => format!("{what} is frozen"),
+    };
+    CollectionError::read_only(description)
+}
+
+impl Collection {
+    pub(crate) fn read_only_file(collection_path: &Path) -> PathBuf {
+        collection_path.join(READ_ONLY_FILE)
+    }
+
+    pub(crate) fn load_read_only_state(
+        collection_path: &Path,
+    ) -> CollectionResult<SaveOnDisk<ReadOnlyState>> {
+        let read_only_file = Self::read_only_file(collection_path);
+        let read_only: SaveOnDisk<ReadOnlyState> = SaveOnDisk::load_or_init(read_only_file)?;
+        Ok(read_only)
+    }
+
+    pub fn read_only_state(&self) -> ReadOnlyState {
+        self.read_only.read().clone()
+    }
+
+    /// Freeze or unfreeze the collection, or only the given shard of it
+    pub async fn set_read_only(
+        &self,
+        shard_id: Option<ShardId>,
+        freeze: Option<Freeze>,
+    ) -> CollectionResult<()> {
+        if let Some(shard_id) = shard_id {
+            let shards_holder = self.shards_holder.read().await;
+            // Unfreeze of a removed shard is allowed, so its freeze can always be cleaned up
+            if freeze.is_some() && shards_holder.get_shard(&shard_id).is_none() {
+                return Err(CollectionError::NotFound {
+                    what: format!("Shard {shard_id}"),
+                });
+            }
+        }
+        self.read_only.write(|state| state.set(shard_id, freeze))?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_only_state() {
+        let mut state = ReadOnlyState::default();
+        assert!(state.check_collection(true).is_ok());
+
+        let freeze = Freeze {
+            reason: Some("migration".to_string()),
+            allow_deletes: false,
+        };
+        state.set(Some(1), Some(freeze));
+        assert!(state.check_collection(true).is_ok());
+        assert!(state.check_shard(0, true).is_ok());
+        let err = state.check_shard(1, true).unwrap_err();
+        assert!(matches!(err, CollectionError::ReadOnly { .. }));
+        assert_eq!(err.to_string(), "Read-only: Shard 1 is frozen: migration");
+        assert!(state.check_shard(1, false).is_err());
+
+        state.set(None, Some(Freeze::default()));
+        assert_eq!(
+            state.check_collection(true).unwrap_err().to_string(),
+            "Read-only: Collection is frozen",
+        );
+        assert!(state.check_collection(false).is_err());
+
+        // Deletes pass the freeze, which allows them
+        let freeze = Freeze {
+            reason: Some("disk is full".to_string()),
+            allow_deletes: true,
+        };
+        state.set(Some(2), Some(freeze));
+        assert!(state.check_shard(2, true).is_err());
+        assert!(state.check_shard(2, false).is_ok());
+
+        state.set(None, None);
+        state.set(Some(1), None);
+        assert_eq!(state, ReadOnlyState::default());
+    }
+}
@@ -0,0 +1,78 @@
+use chrono::Utc;
+
+use super::Collection;
+use crate::operations::running_operations::{
+    next_operation_id, RunningOperation, RunningOperationGuard, RunningOperationKind,
+};
+use crate::shards::transfer::ShardTransfer;
+
+/// Name of the optimizer, which builds indexes of the segments
+const INDEXING_OPTIMIZER: &str = "indexing";
+
+impl Collection {
+    /// Long running operations of the collection on this node, ordered by their start
+    pub async fn running_operations(&self) -> Vec<RunningOperation> {
+        let mut operations = Vec::new();
+
+        {
+            let shards_holder = self.shards_holder.read().await;
+            for (shard_id, replica_set) in shards_holder.get_shards() {
+                for tracker in replica_set.running_optimizations().await {
+                    let kind = if tracker.name == INDEXING_OPTIMIZER {
+                        RunningOperationKind::IndexBuild
+                    } else {
+                        RunningOperationKind::Optimization
+                    };
+                    operations.push(RunningOperation {
+                        id: tracker.id,
+                        kind,
+                        collection_name: self.id.clone(),
+                        shard_id: Some(*shard_id),
+                        description: format!(
+                            "{} optimization of segments {:?}",
+                            tracker.name, tracker.segment_ids,
+                        ),
+                        started_at: tracker.start_at,
+                        progress: None,
+                        cancellable: false,
+                    });
+                }
+            }
+        }
+
+        operations.extend(self.transfer_tasks.lock().await.running_operations());
+        operations.extend(self.operations_registry.list());
+
+        operations.sort_by_key(|operation| operation.id);
+        operations
+    }
+
+    /// Register a long running operation of the collection, which is executed outside of it
+    ///
+    /// Operation is listed until the returned guard is dropped, the guard doesn't keep the
+    /// collection alive.
+    pub fn register_operation(
+        &self,
+        kind: RunningOperationKind,
+        description: String,
+    ) -> RunningOperationGuard {
+        self.operations_registry.register(RunningOperation {
+            id: next_operation_id(),
+            kind,
+            collection_name: self.id.clone(),
+            shard_id: None,
+            description,
+            started_at: Utc::now(),
+            progress: None,
+            cancellable: false,
+        })
+    }
+
+    /// Shard transfer, running on this node as the operation with the given id
+    pub async fn running_transfer(&self, operation_id: u64) -> Option<ShardTransfer> {
+        self.transfer_tasks
+            .lock()
+            .await
+            .find_by_operation_id(operation_id)
+    }
+}
@@ -6,10 +6,18 @@ use futures::{future, TryFutureExt};
 use segment::spaces::tools;
 use segment::types::{ExtendedPointId, Order, ScoredPoint, WithPayloadInterface, WithVector};
 
-use super::Collection;
+use super::access_tags::{restrict_by_access_tags, AccessTags};
+use super::payload_sharding::select_shards_by_filters;
+use super::{soft_delete, Collection};
+use crate::common::search_admission::SearchPermit;
+use crate::config::CollectionParams;
+use crate::distinct::{distinct_points, restore_payload};
 use crate::operations::consistency_params::ReadConsistency;
+use crate::operations::consistency_token::ConsistencyToken;
 use crate::operations::shard_selector_internal::ShardSelectorInternal;
 use crate::operations::types::*;
+use crate::shards::replica_set::read_priority::ReadPriority;
+use crate::shards::replica_set::stale_reads::StaleReads;
 
 impl Collection {
     pub async fn search(
@@ -18,6 +26,9 @@ impl Collection {
         read_consistency: Option<ReadConsistency>,
         shard_selection: &ShardSelectorInternal,
         timeout: Option<Duration>,
+        access_tags: Option<&AccessTags>,
+        read_priority: ReadPriority,
+        stale_reads: Option<&StaleReads>,
     ) -> CollectionResult<Vec<ScoredPoint>> {
         if request.limit == 0 {
             return Ok(vec![]);
@@ -27,7 +38,16 @@ impl Collection {
             searches: vec![request],
         };
         let results = self
-            .do_core_search_batch(request_batch, read_consistency, shard_selection, timeout)
+            .do_core_search_batch(
+                request_batch,
+                read_consistency,
+                None,
+                shard_selection,
+                timeout,
+                access_tags,
+                read_priority,
+                stale_reads,
+            )
             .await?;
         Ok(results.into_iter().next().unwrap())
     }
@@ -36,8 +56,12 @@ impl Collection {
         &self,
         request: CoreSearchRequestBatch,
         read_consistency: Option<ReadConsistency>,
+        consistency_token: Option<&ConsistencyToken>,
         shard_selection: ShardSelectorInternal,
         timeout: Option<Duration>,
+        access_tags: Option<&AccessTags>,
+        read_priority: ReadPriority,
+        stale_reads: Option<&StaleReads>,
     ) -> CollectionResult<Vec<Vec<ScoredPoint>>> {
         // shortcuts batch if all requests with limit=0
         if request.searches.iter().all(|s| s.limit == 0) {
@@ -73,7 +97,8 @@ impl Collection {
         let is_required_transfer_large_enough =
             require_transfers > used_transfers * PAYLOAD_TRANSFERS_FACTOR_THRESHOLD;
 
-        if metadata_required && is_required_transfer_large_enough {
+        // Payload retrieval might go to a replica which didn't apply the operations of the token
+        if metadata_required && is_required_transfer_large_enough && consistency_token.is_none() {
             // If there is a significant offset, we need to retrieve the whole result
             // set without payload first and then retrieve the payload.
             // It is required to do this because the payload might be too large to send over the
@@ -92,50 +117,186 @@ impl Collection {
                 .do_core_search_batch(
                     without_payload_batch,
                     read_consistency,
+                    None,
                     &shard_selection,
                     timeout,
+                    access_tags,
+                    read_priority,
+                    stale_reads,
                 )
                 .await?;
             let filled_results = without_payload_results
                 .into_iter()
-                .zip(request.clone().searches.into_iter())
+                .zip(request.searches)
                 .map(|(without_payload_result, req)| {
                     self.fill_search_result_with_payload(
                         without_payload_result,
-                        req.with_payload.clone(),
+                        req.with_payload,
                         req.with_vector.unwrap_or_default(),
                         read_consistency,
                         &shard_selection,
+                        access_tags,
+                        read_priority,
+                        stale_reads,
                     )
                 });
             future::try_join_all(filled_results).await
         } else {
             let result = self
-                .do_core_search_batch(request, read_consistency, &shard_selection, timeout)
+                .do_core_search_batch(
+                    request,
+                    read_consistency,
+                    consistency_token,
+                    &shard_selection,
+                    timeout,
+                    access_tags,
+                    read_priority,
+                    stale_reads,
+                )
                 .await?;
             Ok(result)
         }
     }
 
+    /// Same as [`Collection::core_search_batch`], but failures of individual shards are reported
+    /// in the result instead of failing the whole request.
+    ///
+    /// Fails only if none of the selected shards could process the request.
+    pub async fn core_search_batch_partial(
+        &self,
+        request: CoreSearchRequestBatch,
+        read_consistency: Option<ReadConsistency>,
+        consistency_token: Option<&ConsistencyToken>,
+        shard_selection: ShardSelectorInternal,
+        timeout: Option<Duration>,
+        access_tags: Option<&AccessTags>,
+        read_priority: ReadPriority,
+        stale_reads: Option<&StaleReads>,
+    ) -> CollectionResult<PartialSearchBatchResult> {
+        // shortcuts batch if all requests with limit=0
+        if request.searches.iter().all(|s| s.limit == 0) {
+            return Ok(PartialSearchBatchResult::default());
+        }
+        // Payload is always retrieved together with the search, 2-step search would require
+        // all shards to be available for the retrieval
+        self.do_core_search_batch_shards(
+            request,
+            read_consistency,
+            consistency_token,
+            &shard_selection,
+            timeout,
+            access_tags,
+            read_priority,
+            stale_reads,
+            true,
+        )
+        .await
+    }
+
     async fn do_core_search_batch(
         &self,
         request: CoreSearchRequestBatch,
         read_consistency: Option<ReadConsistency>,
+        consistency_token: Option<&ConsistencyToken>,
         shard_selection: &ShardSelectorInternal,
         timeout: Option<Duration>,
+        access_tags: Option<&AccessTags>,
+        read_priority: ReadPriority,
+        stale_reads: Option<&StaleReads>,
     ) -> CollectionResult<Vec<Vec<ScoredPoint>>> {
+        let result = self
+            .do_core_search_batch_shards(
+                request,
+                read_consistency,
+                consistency_token,
+                shard_selection,
+                timeout,
+                access_tags,
+                read_priority,
+                stale_reads,
+                false,
+            )
+            .await?;
+        debug_assert!(result.failed_shards.is_empty());
+        Ok(result.results)
+    }
+
+    /// Wait until the search could be executed within the concurrency limits
+    ///
+    /// The limit of the collection is checked first, so searches queued for a busy collection do
+    /// not hold permits of the node.
+    async fn admit_search(&self) -> CollectionResult<(SearchPermit, SearchPermit)> {
+        let max_concurrency = self
+            .enabled_strict_mode_config()
+            .await
+            .and_then(|config| config.max_concurrent_searches);
+
+        let collection_admission = {
+            let mut admission = self.search_admission.lock();
+            if admission.max_concurrency() != max_concurrency {
+                *admission = self
+                    .shared_storage_config
+                    .search_admission
+                    .with_max_concurrency(max_concurrency);
+            }
+            admission.clone()
+        };
+
+        let collection_permit = collection_admission.admit().await?;
+        let node_permit = self.shared_storage_config.search_admission.admit().await?;
+        Ok((collection_permit, node_permit))
+    }
+
+    /// Search in selected shards and merge their results
+    ///
+    /// If `allow_partial` is set, shards which failed are skipped and reported in the result,
+    /// unless all of them failed.
+    async fn do_core_search_batch_shards(
+        &self,
+        mut request: CoreSearchRequestBatch,
+        read_consistency: Option<ReadConsistency>,
+        consistency_token: Option<&ConsistencyToken>,
+        shard_selection: &ShardSelectorInternal,
+        timeout: Option<Duration>,
+        access_tags: Option<&AccessTags>,
+        read_priority: ReadPriority,
+        stale_reads: Option<&StaleReads>,
+        allow_partial: bool,
+    ) -> CollectionResult<PartialSearchBatchResult> {
+        let _permits = self.admit_search().await?;
+
+        if self.is_soft_delete_enabled().await {
+            for search in &mut request.searches {
+                search.filter = Some(soft_delete::exclude_soft_deleted(search.filter.take()));
+            }
+        }
+        for search in &mut request.searches {
+            search.filter = restrict_by_access_tags(search.filter.take(), access_tags);
+        }
+        self.reduce_search_batch(&mut request).await?;
+        self.normalize_search_batch(&mut request).await?;
         let request = Arc::new(request);
+        let mut failed_shards = Vec::new();
+        let sharding_key = self.sharding_payload_key().await;
 
         // query all shards concurrently
         let all_searches_res = {
             let shard_holder = self.shards_holder.read().await;
-            let target_shards = shard_holder.select_shards(shard_selection)?;
+            let target_shards = select_shards_by_filters(
+                &shard_holder,
+                shard_selection,
+                sharding_key.as_deref(),
+                request.searches.iter().map(|search| search.filter.as_ref()),
+            )?;
             let all_searches = target_shards.iter().map(|(shard, shard_key)| {
                 let shard_key = shard_key.cloned();
                 shard
                     .core_search(
                         Arc::clone(&request),
                         read_consistency,
+                        read_priority,
+                        stale_reads,
+                        consistency_token,
                         shard_selection.is_shard_id(),
                         timeout,
                     )
@@ -151,11 +312,45 @@ impl Collection {
                         Ok(records)
                     })
             });
-            future::try_join_all(all_searches).await?
+
+            if allow_partial {
+                let shard_results = future::join_all(all_searches).await;
+                let mut searches_res = Vec::with_capacity(shard_results.len());
+                let mut first_error = None;
+                for ((shard, shard_key), shard_result) in target_shards.iter().zip(shard_results) {
+                    match shard_result {
+                        Ok(records) => searches_res.push(records),
+                        Err(err) => {
+                            log::warn!("Search in shard {} failed: {err}", shard.shard_id);
+                            failed_shards.push(ShardFailure {
+                                shard_id: shard.shard_id,
+                                shard_key: shard_key.cloned(),
+                                error: err.to_string(),
+                            });
+                            first_error.get_or_insert(err);
+                        }
+                    }
+                }
+                match first_error {
+                    // Nothing to return, if none of the shards responded
+                    Some(err) if searches_res.is_empty() => return Err(err),
+                    _ => searches_res,
+                }
+            } else {
+                future::try_join_all(all_searches).await?
+            }
         };
 
-        self.merge_from_shards(all_searches_res, request, !shard_selection.is_shard_id())
-            .await
+        let mut results = self
+            .merge_from_shards(all_searches_res, request, !shard_selection.is_shard_id())
+            .await?;
+        for point in results.iter_mut().flatten() {
+            soft_delete::hide_soft_delete_marker(&mut point.payload);
+        }
+        Ok(PartialSearchBatchResult {
+            results,
+            failed_shards,
+        })
     }
 
     pub(crate) async fn fill_search_result_with_payload(
@@ -165,6 +360,9 @@ impl Collection {
         with_vector: WithVector,
         read_consistency: Option<ReadConsistency>,
         shard_selection: &ShardSelectorInternal,
+        access_tags: Option<&AccessTags>,
+        read_priority: ReadPriority,
+        stale_reads: Option<&StaleReads>,
     ) -> CollectionResult<Vec<ScoredPoint>> {
         // short-circuit if not needed
         if let (&Some(WithPayloadInterface::Bool(false)), &WithVector::Bool(false)) =
@@ -184,9 +382,18 @@ impl Collection {
             ids: search_result.iter().map(|x| x.id).collect(),
             with_payload,
             with_vector,
+            as_of: None,
+            with_version: false,
         };
         let retrieved_records = self
-            .retrieve(retrieve_request, read_consistency, shard_selection)
+            .retrieve(
+                retrieve_request,
+                read_consistency,
+                shard_selection,
+                access_tags,
+                read_priority,
+                stale_reads,
+            )
             .await?;
         let mut records_map: HashMap<ExtendedPointId, Record> = retrieved_records
             .into_iter()
@@ -228,26 +435,30 @@ impl Collection {
             .into_iter()
             .zip(request.searches.iter())
             .map(|(res, request)| {
-                let order = match &request.query {
-                    QueryEnum::Nearest(_) => collection_params
-                        .get_distance(request.query.get_vector_name())?
-                        .distance_order(),
-
-                    // Score comes from special handling of the distances in a way that it doesn't
-                    // directly represent distance anymore, so the order is always `LargeBetter`
-                    QueryEnum::Discover(_)
-                    | QueryEnum::Context(_)
-                    | QueryEnum::RecommendBestScore(_) => Order::LargeBetter,
+                // Rescored results are always ordered by the formula, larger is better
+                let order = if request.rescore.is_some() {
+                    Order::LargeBetter
+                } else {
+                    query_order(&request.query, &collection_params)?
                 };
 
+                // Deduplicated results are ordered all, as any of them could be a duplicate
+                let top = match &request.distinct_by {
+                    Some(_) => res.len(),
+                    None => request.limit + request.offset,
+                };
                 let mut top_res = match order {
-                    Order::LargeBetter => {
-                        tools::peek_top_largest_iterable(res, request.limit + request.offset)
-                    }
-                    Order::SmallBetter => {
-                        tools::peek_top_smallest_iterable(res, request.limit + request.offset)
-                    }
+                    Order::LargeBetter => tools::peek_top_largest_iterable(res, top),
+                    Order::SmallBetter => tools::peek_top_smallest_iterable(res, top),
                 };
+                if let Some(distinct) = &request.distinct_by {
+                    top_res =
+                        distinct_points(top_res, &distinct.key, request.limit + request.offset);
+                    // Values of the field are kept for the merge on the peer, which sent the request
+                    if is_client_request {
+                        restore_payload(&mut top_res, request.with_payload.as_ref());
+                    }
+                }
                 // Remove `offset` from top result only for client requests
                 // to avoid applying `offset` twice in distributed mode.
                 if is_client_request && request.offset > 0 {
@@ -264,4 +475,28 @@ impl Collection {
 
         Ok(top_results)
     }
+
+    /// Order of the scores in the search results for the given query
+    pub async fn query_order(&self, query: &QueryEnum) -> CollectionResult<Order> {
+        query_order(query, &self.collection_config.read().await.params)
+    }
+}
+
+/// Order of the scores in the search results for the given query
+pub(super) fn query_order(
+    query: &QueryEnum,
+    collection_params: &CollectionParams,
+) -> CollectionResult<Order> {
+    let order = match query {
+        QueryEnum::Nearest(_) => collection_params
+            .get_distance(query.get_vector_name())?
+            .distance_order(),
+
+        // Score comes from special handling of the distances in a way that it doesn't
+        // directly represent distance anymore, so the order is always `LargeBetter`
+        QueryEnum::Discover(_) | QueryEnum::Context(_) | QueryEnum::RecommendBestScore(_) => {
+            Order::LargeBetter
+        }
+    };
+    Ok(order)
 }
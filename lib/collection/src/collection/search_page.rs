@@ -0,0 +1,409 @@
+//! Cursor pagination of the search results
+//!
+//! Instead of re-scoring `offset + limit` results in every shard for every page, the cursor
+//! remembers how many results of each shard were already returned, and the position of the last
+//! returned point in the results order. Next page only requests `consumed + limit` results from
+//! each shard and skips everything up to the remembered position.
+//!
+//! The storage can't pin a snapshot of the data, so the cursor pins a version of each shard
+//! instead. The first page remembers the local replica of each shard and the last operation it
+//! has applied, all pages of the shard are then read from that replica only. Points with a later
+//! version were written after the cursor was created: they are skipped, so inserted points don't
+//! appear in the middle of the pagination and updated ones are never repeated.
+//!
+//! Shards without an active local replica on the peer, which served the first page, can't be
+//! pinned. For those only the position in the results order is used: points which are inserted
+//! or updated before the position are not shown, points which are already shown are not repeated.
+//!
+//! If the pinned replica becomes unavailable, next pages fail and pagination has to be restarted.
+
+use std::cmp::Ordering;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::sync::Arc;
+use std::time::Duration;
+
+use futures::future;
+use segment::types::{ExtendedPointId, Order, ScoreType, ScoredPoint, SeqNumberType, ShardKey};
+use serde::{Deserialize, Serialize};
+
+use super::access_tags::{restrict_by_access_tags, AccessTags};
+use super::search::query_order;
+use super::{soft_delete, Collection};
+use crate::operations::consistency_params::ReadConsistency;
+use crate::operations::consistency_token::ConsistencyToken;
+use crate::operations::shard_selector_internal::ShardSelectorInternal;
+use crate::operations::types::*;
+use crate::shards::replica_set::read_priority::ReadPriority;
+use crate::shards::replica_set::stale_reads::StaleReads;
+use crate::shards::replica_set::ShardReplicaSet;
+use crate::shards::shard::{PeerId, ShardId};
+
+/// Version of the cursor encoding, cursors of other versions are rejected
+const CURSOR_VERSION: u32 = 2;
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+struct SearchCursor {
+    version: u32,
+    /// Hash of the request parameters, which affect the results order
+    fingerprint: u64,
+    /// Number of already returned results of each shard
+    positions: HashMap<ShardId, usize>,
+    /// Score and id of the last returned point
+    last: Option<(ScoreType, ExtendedPointId)>,
+    /// Replica, which serves the pages of each shard, and the last operation it has applied
+    /// before the first page
+    pins: HashMap<ShardId, (PeerId, SeqNumberType)>,
+}
+
+impl SearchCursor {
+    fn new(fingerprint: u64) -> Self {
+        Self {
+            version: CURSOR_VERSION,
+            fingerprint,
+            positions: HashMap::new(),
+            last: None,
+            pins: HashMap::new(),
+        }
+    }
+
+    fn encode(&self) -> String {
+        // Serialization of the plain struct into JSON never fails
+        let json = serde_json::to_vec(self).unwrap();
+        json.iter().map(|byte| format!("{byte:02x}")).collect()
+    }
+
+    fn decode(cursor: &str, fingerprint: u64) -> CollectionResult<Self> {
+        let invalid = || CollectionError::bad_request("Invalid search cursor".to_string());
+        if cursor.len() % 2 != 0 || !cursor.is_ascii() {
+            return Err(invalid());
+        }
+        let json = (0..cursor.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&cursor[i..i + 2], 16))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|_| invalid())?;
+        let cursor: Self = serde_json::from_slice(&json).map_err(|_| invalid())?;
+        if cursor.version != CURSOR_VERSION {
+            return Err(invalid());
+        }
+        if cursor.fingerprint != fingerprint {
+            return Err(CollectionError::bad_request(
+                "Search cursor was created for a different request".to_string(),
+            ));
+        }
+        Ok(cursor)
+    }
+
+    fn position(&self, shard_id: ShardId) -> usize {
+        self.positions.get(&shard_id).copied().unwrap_or(0)
+    }
+
+    /// Whether the point was written before the cursor was created
+    fn is_pinned(&self, shard_id: ShardId, point: &ScoredPoint) -> bool {
+        self.pins
+            .get(&shard_id)
+            .map_or(true, |&(_, operation_id)| point.version <= operation_id)
+    }
+
+    /// Read requirement, which routes the shard to its pinned replica
+    ///
+    /// Unpinned shards are read according to the token of the request.
+    fn shard_token(
+        &self,
+        shard_id: ShardId,
+        consistency_token: Option<&ConsistencyToken>,
+    ) -> Option<ConsistencyToken> {
+        match self.pins.get(&shard_id) {
+            Some(&pin) => Some(ConsistencyToken::new(shard_id, [pin])),
+            None => consistency_token.cloned(),
+        }
+    }
+
+    /// Whether the point goes after the last returned one
+    fn is_after(&self, point: &ScoredPoint, order: Order) -> bool {
+        match self.last {
+            None => true,
+            Some(last) => compare_points(order, last, (point.score, point.id)) == Ordering::Less,
+        }
+    }
+}
+
+/// Total order of the search results: by score, ties are resolved by point id
+fn compare_points(
+    order: Order,
+    (a_score, a_id): (ScoreType, ExtendedPointId),
+    (b_score, b_id): (ScoreType, ExtendedPointId),
+) -> Ordering {
+    let by_score = match order {
+        Order::LargeBetter => b_score.total_cmp(&a_score),
+        Order::SmallBetter => a_score.total_cmp(&b_score),
+    };
+    by_score.then_with(|| a_id.cmp(&b_id))
+}
+
+/// Hash of the request parameters, which define the results and their order
+fn request_fingerprint(request: &CoreSearchRequest) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    format!(
+        "{:?}{:?}{:?}{:?}",
+        request.query, request.filter, request.params, request.score_threshold,
+    )
+    .hash(&mut hasher);
+    hasher.finish()
+}
+
+impl Collection {
+    /// Search with cursor pagination
+    ///
+    /// `offset` of the request is not allowed, `cursor` from the previous page should be used
+    /// instead.
+    pub async fn search_page(
+        &self,
+        mut request: CoreSearchRequest,
+        cursor: Option<&str>,
+        read_consistency: Option<ReadConsistency>,
+        consistency_token: Option<&ConsistencyToken>,
+        shard_selection: &ShardSelectorInternal,
+        timeout: Option<Duration>,
+        access_tags: Option<&AccessTags>,
+        read_priority: ReadPriority,
+        stale_reads: Option<&StaleReads>,
+    ) -> CollectionResult<SearchPageResult> {
+        if request.offset > 0 {
+            return Err(CollectionError::bad_request(
+                "`offset` can't be used with cursor pagination, use `cursor` instead".to_string(),
+            ));
+        }
+
+        let fingerprint = request_fingerprint(&request);
+        let is_first_page = cursor.is_none();
+        let mut cursor = match cursor {
+            Some(cursor) => SearchCursor::decode(cursor, fingerprint)?,
+            None => SearchCursor::new(fingerprint),
+        };
+
+        if request.limit == 0 {
+            return Ok(SearchPageResult::default());
+        }
+
+        let order = query_order(&request.query, &self.collection_config.read().await.params)?;
+
+        if self.is_soft_delete_enabled().await {
+            request.filter = Some(soft_delete::exclude_soft_deleted(request.filter.take()));
+        }
+        request.filter = restrict_by_access_tags(request.filter.take(), access_tags);
+
+        let shards_results = {
+            let shard_holder = self.shards_holder.read().await;
+            let target_shards = shard_holder.select_shards(shard_selection)?;
+            for (shard, _) in &target_shards {
+                if is_first_page {
+                    if let Some(pin) = pin_local_replica(shard, consistency_token).await {
+                        cursor.pins.insert(shard.shard_id, pin);
+                    }
+                } else if let Some(&(peer_id, _)) = cursor.pins.get(&shard.shard_id) {
+                    // Local reads ignore the token, versions of other replicas are not comparable
+                    if shard_selection.is_shard_id() && peer_id != shard.this_peer_id() {
+                        return Err(CollectionError::bad_request(format!(
+                            "Search cursor of shard {} is pinned to peer {peer_id}",
+                            shard.shard_id,
+                        )));
+                    }
+                }
+            }
+            let shard_searches = target_shards.iter().map(|(shard, shard_key)| {
+                search_shard_after(
+                    shard,
+                    shard_key.cloned(),
+                    &request,
+                    &cursor,
+                    order,
+                    read_consistency,
+                    read_priority,
+                    stale_reads,
+                    cursor.shard_token(shard.shard_id, consistency_token),
+                    shard_selection.is_shard_id(),
+                    timeout,
+                )
+            });
+            future::try_join_all(shard_searches).await?
+        };
+
+        let mut points: Vec<_> = shards_results.into_iter().flatten().collect();
+        points.sort_unstable_by(|(_, a), (_, b)| {
+            compare_points(order, (a.score, a.id), (b.score, b.id))
+        });
+        points.truncate(request.limit);
+
+        for (shard_id, point) in &points {
+            *cursor.positions.entry(*shard_id).or_default() += 1;
+            cursor.last = Some((point.score, point.id));
+        }
+
+        // Shards return all of their remaining results, if there are less than `limit` of them
+        let next_page_cursor = (points.len() == request.limit).then(|| cursor.encode());
+
+        Ok(SearchPageResult {
+            points: points.into_iter().map(|(_, point)| point).collect(),
+            next_page_cursor,
+        })
+    }
+}
+
+/// Pin the shard to its local replica, if there is an active one
+///
+/// If the request token requires an operation from the local replica, the pinned version
+/// includes it, otherwise the read would not see the writes of the token.
+async fn pin_local_replica(
+    shard: &ShardReplicaSet,
+    consistency_token: Option<&ConsistencyToken>,
+) -> Option<(PeerId, SeqNumberType)> {
+    let this_peer_id = shard.this_peer_id();
+    let applied_operation = shard.local_applied_operation().await?;
+    let required_operation =
+        match consistency_token.and_then(|token| token.shard_replicas(shard.shard_id)) {
+            // Local replica is not allowed by the token, shard is read from the required replicas
+            Some(replicas) => *replicas.get(&this_peer_id)?,
+            None => 0,
+        };
+    Some((this_peer_id, applied_operation.max(required_operation)))
+}
+
+/// Search the shard for at least `limit` points, which go after the cursor
+///
+/// Shard is first asked for the number of its points returned so far plus `limit`. If the shard
+/// was updated since the previous page, that might not be enough to reach past the cursor, then
+/// the search is repeated with a larger limit.
+#[allow(clippy::too_many_arguments)]
+async fn search_shard_after(
+    shard: &ShardReplicaSet,
+    shard_key: Option<ShardKey>,
+    request: &CoreSearchRequest,
+    cursor: &SearchCursor,
+    order: Order,
+    read_consistency: Option<ReadConsistency>,
+    read_priority: ReadPriority,
+    stale_reads: Option<&StaleReads>,
+    consistency_token: Option<ConsistencyToken>,
+    local_only: bool,
+    timeout: Option<Duration>,
+) -> CollectionResult<Vec<(ShardId, ScoredPoint)>> {
+    let mut shard_limit = cursor.position(shard.shard_id) + request.limit;
+    loop {
+        let shard_request = CoreSearchRequest {
+            limit: shard_limit,
+            offset: 0,
+            ..request.clone()
+        };
+        let batch = Arc::new(CoreSearchRequestBatch {
+            searches: vec![shard_request],
+        });
+        let points = shard
+            .core_search(
+                batch,
+                read_consistency,
+                read_priority,
+                stale_reads,
+                consistency_token.as_ref(),
+                local_only,
+                timeout,
+            )
+            .await?
+            .into_iter()
+            .next()
+            .unwrap_or_default();
+
+        let is_exhausted = points.len() < shard_limit;
+        let after_cursor: Vec<_> = points
+            .into_iter()
+            .filter(|point| {
+                cursor.is_pinned(shard.shard_id, point) && cursor.is_after(point, order)
+            })
+            .map(|mut point| {
+                soft_delete::hide_soft_delete_marker(&mut point.payload);
+                point.shard_key = shard_key.clone();
+                (shard.shard_id, point)
+            })
+            .collect();
+
+        if is_exhausted || after_cursor.len() >= request.limit {
+            return Ok(after_cursor);
+        }
+        shard_limit *= 2;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cursor_encoding() {
+        let mut cursor = SearchCursor::new(42);
+        cursor.positions.insert(0, 10);
+        cursor.positions.insert(3, 7);
+        cursor.last = Some((0.5, ExtendedPointId::NumId(12)));
+        cursor.pins.insert(0, (1, 100));
+
+        let encoded = cursor.encode();
+        assert_eq!(SearchCursor::decode(&encoded, 42).unwrap(), cursor);
+
+        assert!(SearchCursor::decode(&encoded, 43).is_err());
+        assert!(SearchCursor::decode("not a cursor", 42).is_err());
+        assert!(SearchCursor::decode(&encoded[1..], 42).is_err());
+    }
+
+    #[test]
+    fn test_cursor_order() {
+        let mut cursor = SearchCursor::new(0);
+        cursor.last = Some((0.5, ExtendedPointId::NumId(12)));
+
+        let point = |score, id| ScoredPoint {
+            id: ExtendedPointId::NumId(id),
+            version: 0,
+            score,
+            payload: None,
+            vector: None,
+            shard_key: None,
+        };
+
+        assert!(cursor.is_after(&point(0.4, 1), Order::LargeBetter));
+        assert!(cursor.is_after(&point(0.5, 13), Order::LargeBetter));
+        assert!(!cursor.is_after(&point(0.5, 12), Order::LargeBetter));
+        assert!(!cursor.is_after(&point(0.5, 11), Order::LargeBetter));
+        assert!(!cursor.is_after(&point(0.6, 100), Order::LargeBetter));
+
+        assert!(cursor.is_after(&point(0.6, 1), Order::SmallBetter));
+        assert!(!cursor.is_after(&point(0.4, 100), Order::SmallBetter));
+    }
+
+    #[test]
+    fn test_cursor_pins() {
+        let mut cursor = SearchCursor::new(0);
+        cursor.pins.insert(0, (1, 100));
+
+        let point = |version| ScoredPoint {
+            id: ExtendedPointId::NumId(1),
+            version,
+            score: 0.5,
+            payload: None,
+            vector: None,
+            shard_key: None,
+        };
+
+        assert!(cursor.is_pinned(0, &point(100)));
+        assert!(!cursor.is_pinned(0, &point(101)));
+        // Unpinned shards are not filtered by version
+        assert!(cursor.is_pinned(1, &point(101)));
+
+        let token = ConsistencyToken::new(1, [(2, 5)]);
+        assert_eq!(
+            cursor.shard_token(0, Some(&token)),
+            Some(ConsistencyToken::new(0, [(1, 100)])),
+        );
+        assert_eq!(cursor.shard_token(1, Some(&token)), Some(token));
+        assert_eq!(cursor.shard_token(1, None), None);
+    }
+}
@@ -0,0 +1,408 @@
+use std::collections::HashMap;
+use std::future::Future;
+use std::num::NonZeroU32;
+
+use super::{Collection, OnShardSplitCopied, OnShardSplitFailure};
+use crate::config::ShardingMethod;
+use crate::operations::types::{CollectionError, CollectionResult};
+use crate::shards::replica_set::{ReplicaState, ShardReplicaSet};
+use crate::shards::shard::{PeerId, ShardId};
+use crate::shards::shard_holder::shard_not_found_error;
+use crate::shards::shard_split::{spawn_split_task, ShardSplit};
+use crate::shards::shard_versioning::versioned_shard_path;
+
+impl Collection {
+    pub async fn get_shard_splits(&self) -> Vec<ShardSplit> {
+        self.shards_holder.read().await.shard_splits()
+    }
+
+    /// Start splitting the shard into two new shards
+    ///
+    /// The split is registered on every peer. Peers, which have an active replica of the shard,
+    /// also start copying its points into their replicas of the new shards.
+    ///
+    /// Returns `false`, if the split is already registered.
+    pub async fn start_shard_split<T, F>(
+        &self,
+        source: ShardId,
+        on_finish: T,
+        on_error: F,
+    ) -> CollectionResult<bool>
+    where
+        T: Future<Output = ()> + Send + 'static,
+        F: Future<Output = ()> + Send + 'static,
+    {
+        let sharding_method = self
+            .collection_config
+            .read()
+            .await
+            .params
+            .sharding_method
+            .unwrap_or_default();
+        if sharding_method != ShardingMethod::Auto {
+            return Err(CollectionError::bad_request(format!(
+                "Shards cannot be split with {sharding_method:?} sharding method",
+            )));
+        }
+
+        let split = {
+            let shard_holder = self.shards_holder.read().await;
+            let replica_set = shard_holder
+                .get_shard(&source)
+                .ok_or_else(|| shard_not_found_error(source))?;
+
+            let shard_splits = shard_holder.shard_splits();
+            if let Some(split) = shard_splits.iter().find(|split| !split.is_done()) {
+                if split.source == source {
+                    return Ok(false);
+                }
+                return Err(CollectionError::bad_request(format!(
+                    "Shard {} is being split already, only one split at a time is supported",
+                    split.source,
+                )));
+            }
+
+            if !shard_holder
+                .get_transfers(|transfer| transfer.shard_id == source)
+                .is_empty()
+            {
+                return Err(CollectionError::bad_request(format!(
+                    "Shard {source} cannot be split while it is being transferred",
+                )));
+            }
+
+            if let Some((peer_id, state)) = replica_set
+                .peers()
+                .into_iter()
+                .find(|(_, state)| *state != ReplicaState::Active)
+            {
+                return Err(CollectionError::bad_request(format!(
+                    "Shard {source} cannot be split while its replica on peer {peer_id} is {state:?}",
+                )));
+            }
+
+            // New shards get IDs, which were never used in the collection
+            let max_shard_id = shard_holder
+                .get_shards()
+                .map(|(shard_id, _)| *shard_id)
+                .chain(shard_splits.iter().flat_map(|split| split.targets))
+                .max()
+                .unwrap_or(0);
+            let split = ShardSplit::new(source, [max_shard_id + 1, max_shard_id + 2]);
+
+            shard_holder.register_start_shard_split(split.clone())?;
+            split
+        };
+
+        log::info!(
+            "Splitting shard {}:{source} into shards {:?}",
+            self.id,
+            split.targets,
+        );
+
+        let is_active_here = {
+            let shard_holder = self.shards_holder.read().await;
+            match shard_holder.get_shard(&source) {
+                Some(replica_set) => {
+                    replica_set.peer_state(&self.this_peer_id) == Some(ReplicaState::Active)
+                }
+                None => false,
+            }
+        };
+
+        if !is_active_here {
+            return Ok(true);
+        }
+
+        match self.prepare_split_targets(&split).await {
+            Ok(()) => {
+                let split_task = spawn_split_task(
+                    self.shards_holder.clone(),
+                    self.id.clone(),
+                    source,
+                    on_finish,
+                    on_error,
+                );
+                if let Some(old_task) = self.split_tasks.lock().await.insert(source, split_task) {
+                    old_task.ask_to_cancel();
+                }
+            }
+            Err(err) => {
+                log::error!(
+                    "Failed to prepare split of shard {}:{source}: {err}",
+                    self.id,
+                );
+                tokio::spawn(on_error);
+            }
+        }
+
+        Ok(true)
+    }
+
+    /// Build local replicas of the split targets and route updates of the source shard into them
+    async fn prepare_split_targets(&self, split: &ShardSplit) -> CollectionResult<()> {
+        let mut targets = Vec::with_capacity(split.targets.len());
+        for target in split.targets {
+            self.remove_shard_dir(target).await?;
+            let replica_set = ShardReplicaSet::build(
+                target,
+                self.name(),
+                self.this_peer_id,
+                true,
+                Default::default(),
+                self.notify_peer_failure_cb.clone(),
+                self.abort_shard_transfer_cb.clone(),
+                &self.path,
+                self.collection_config.clone(),
+                self.shared_storage_config.clone(),
+                self.channel_service.clone(),
+                self.update_runtime.clone(),
+                self.search_runtime.clone(),
+                Some(ReplicaState::Partial),
+            )
+            .await?;
+            targets.push(replica_set);
+        }
+
+        let shard_holder = self.shards_holder.read().await;
+        let replica_set = shard_holder
+            .get_shard(&split.source)
+            .ok_or_else(|| shard_not_found_error(split.source))?;
+        replica_set.split_proxify_local(targets, split.ring()).await
+    }
+
+    /// Record that the peer has filled its replicas of the split targets
+    ///
+    /// Once all active replicas of the source shard are copied, the split is finished.
+    pub async fn shard_split_copied(
+        &self,
+        source: ShardId,
+        peer_id: PeerId,
+    ) -> CollectionResult<()> {
+        let (split, source_peers) = {
+            let shard_holder = self.shards_holder.read().await;
+            let Some(split) = shard_holder.register_split_copied(source, peer_id)? else {
+                log::debug!(
+                    "Shard {}:{source} is not being split, ignoring copied report of peer {peer_id}",
+                    self.id,
+                );
+                return Ok(());
+            };
+            let replica_set = shard_holder
+                .get_shard(&source)
+                .ok_or_else(|| shard_not_found_error(source))?;
+            (split, replica_set.peers())
+        };
+
+        let mut active_peers = source_peers
+            .iter()
+            .filter(|(_, state)| **state == ReplicaState::Active)
+            .map(|(peer_id, _)| peer_id)
+            .peekable();
+        let is_copied = active_peers.peek().is_some()
+            && active_peers.all(|peer_id| split.copied_peers.contains(peer_id));
+
+        if is_copied {
+            self.finish_shard_split(&split).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Replace the source shard of the split with the targets
+    pub(super) async fn finish_shard_split(&self, split: &ShardSplit) -> CollectionResult<()> {
+        self.stop_split_task(split.source).await;
+
+        let mut shard_holder = self.shards_holder.write().await;
+
+        let (replicas, mut local_targets) = {
+            let source_replica_set = shard_holder
+                .get_shard(&split.source)
+                .ok_or_else(|| shard_not_found_error(split.source))?;
+
+            // Only the copied replicas of the targets are up to date, others are recovered by
+            // transfers
+            let replicas: HashMap<_, _> = source_replica_set
+                .peers()
+                .into_iter()
+                .map(|(peer_id, state)| {
+                    let is_copied =
+                        state == ReplicaState::Active && split.copied_peers.contains(&peer_id);
+                    let state = if is_copied {
+                        ReplicaState::Active
+                    } else {
+                        ReplicaState::Dead
+                    };
+                    (peer_id, state)
+                })
+                .collect();
+
+            (replicas, source_replica_set.un_split_proxify_local().await)
+        };
+
+        for target in split.targets {
+            let local_target = local_targets
+                .iter()
+                .position(|replica_set| replica_set.shard_id == target)
+                .map(|index| local_targets.swap_remove(index));
+
+            let replica_set = match local_target {
+                Some(replica_set) => replica_set,
+                None => {
+                    self.remove_shard_dir(target).await?;
+                    self.create_replica_set(target, &[]).await?
+                }
+            };
+
+            // Creates an empty local replica and reports it as failed, if this peer is expected
+            // to have a copied replica, but has lost it
+            replica_set.apply_state(replicas.clone()).await?;
+            shard_holder.add_shard(target, replica_set, None)?;
+        }
+
+        shard_holder.finish_shard_split(split.source).await?;
+
+        {
+            let mut config = self.collection_config.write().await;
+            let shard_number = config.params.shard_number.get() + 1;
+            config.params.shard_number = NonZeroU32::new(shard_number).unwrap();
+            config.save(&self.path)?;
+        }
+
+        log::info!(
+            "Shard {}:{} is replaced with shards {:?}",
+            self.id,
+            split.source,
+            split.targets,
+        );
+
+        Ok(())
+    }
+
+    /// Abort the split of the shard, the source shard stays in place
+    pub async fn abort_shard_split(&self, source: ShardId, reason: &str) -> CollectionResult<()> {
+        let Some(split) = self.shards_holder.read().await.get_shard_split(source) else {
+            log::debug!(
+                "Shard {}:{source} is not being split, ignoring abort",
+                self.id,
+            );
+            return Ok(());
+        };
+
+        log::warn!("Aborting split of shard {}:{source}: {reason}", self.id);
+
+        self.stop_split_task(source).await;
+
+        let shard_holder = self.shards_holder.read().await;
+        if let Some(replica_set) = shard_holder.get_shard(&source) {
+            let local_targets = replica_set.un_split_proxify_local().await;
+            drop(local_targets);
+        }
+        for target in split.targets {
+            self.remove_shard_dir(target).await?;
+        }
+
+        shard_holder.register_abort_shard_split(source)?;
+        Ok(())
+    }
+
+    /// Apply splits of the consensus state
+    ///
+    /// Splits, which were finished or aborted in the meantime, are finished or aborted locally.
+    pub(super) async fn apply_shard_splits(
+        &self,
+        shard_splits: Vec<ShardSplit>,
+    ) -> CollectionResult<()> {
+        let local_splits = self.shards_holder.read().await.shard_splits();
+        for local_split in local_splits.iter().filter(|split| !split.is_done()) {
+            match shard_splits
+                .iter()
+                .find(|split| split.source == local_split.source)
+            {
+                Some(split) if split.is_done() => self.finish_shard_split(split).await?,
+                Some(_) => {}
+                None => {
+                    self.abort_shard_split(local_split.source, "split is not in the state")
+                        .await?
+                }
+            }
+        }
+
+        self.shards_holder
+            .write()
+            .await
+            .apply_shard_splits(shard_splits)
+    }
+
+    /// Report unreported results of the local split tasks
+    pub async fn sync_shard_splits(
+        &self,
+        on_split_copied: OnShardSplitCopied,
+        on_split_failure: OnShardSplitFailure,
+    ) -> CollectionResult<()> {
+        let shard_holder = self.shards_holder.read().await;
+
+        for split in shard_holder.shard_splits() {
+            if split.is_done() || split.copied_peers.contains(&self.this_peer_id) {
+                continue;
+            }
+
+            let Some(replica_set) = shard_holder.get_shard(&split.source) else {
+                continue;
+            };
+            if replica_set.peer_state(&self.this_peer_id) != Some(ReplicaState::Active) {
+                continue;
+            }
+
+            let split_tasks = self.split_tasks.lock().await;
+            match split_tasks.get(&split.source) {
+                Some(task) if !task.is_finished() => {}
+                Some(task) => {
+                    if task.get_result() == Some(true) {
+                        log::debug!(
+                            "Split of shard {}:{} is copied, but not reported. Reporting now.",
+                            self.id,
+                            split.source,
+                        );
+                        on_split_copied(split.source, self.name());
+                    } else {
+                        log::debug!(
+                            "Split of shard {}:{} is failed, but not reported as failed. Reporting now.",
+                            self.id,
+                            split.source,
+                        );
+                        on_split_failure(split.source, self.name(), "split failed");
+                    }
+                }
+                None => {
+                    log::debug!(
+                        "Split of shard {}:{} does not exist, but not reported as cancelled. Reporting now.",
+                        self.id,
+                        split.source,
+                    );
+                    on_split_failure(split.source, self.name(), "split task does not exist");
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn stop_split_task(&self, source: ShardId) {
+        let Some(task) = self.split_tasks.lock().await.remove(&source) else {
+            return;
+        };
+        if let Err(err) = task.cancel().await {
+            log::warn!("Split task for shard {}:{source} failed: {err}", self.id,);
+        }
+    }
+
+    /// Remove leftovers of the split target, which was not registered in the shard holder
+    async fn remove_shard_dir(&self, shard_id: ShardId) -> CollectionResult<()> {
+        let shard_path = versioned_shard_path(&self.path, shard_id, 0);
+        if shard_path.exists() {
+            tokio::fs::remove_dir_all(shard_path).await?;
+        }
+        Ok(())
+    }
+}
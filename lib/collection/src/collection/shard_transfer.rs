@@ -1,16 +1,19 @@
 use std::future::Future;
 use std::path::PathBuf;
+use std::sync::Arc;
 use std::time::Duration;
 
 use common::defaults;
 
 use super::Collection;
+use crate::events::CollectionEventKind;
 use crate::operations::types::{CollectionError, CollectionResult};
 use crate::shards::local_shard::LocalShard;
 use crate::shards::replica_set::ReplicaState;
 use crate::shards::shard::{PeerId, ShardId};
 use crate::shards::shard_holder::ShardHolder;
 use crate::shards::transfer;
+use crate::shards::transfer::throttle::TransferRate;
 use crate::shards::transfer::transfer_tasks_pool::TaskResult;
 use crate::shards::transfer::{
     ShardTransfer, ShardTransferConsensus, ShardTransferKey, ShardTransferMethod,
@@ -73,7 +76,9 @@ impl Collection {
             let is_sender = replica_set.this_peer_id() == shard_transfer.from;
 
             let initial_state = match shard_transfer.method.unwrap_or_default() {
-                ShardTransferMethod::StreamRecords => ReplicaState::Partial,
+                ShardTransferMethod::StreamRecords | ShardTransferMethod::WalDelta => {
+                    ReplicaState::Partial
+                }
                 ShardTransferMethod::Snapshot => ReplicaState::PartialSnapshot,
             };
 
@@ -129,6 +134,7 @@ impl Collection {
         let shard_holder = self.shards_holder.clone();
         let collection_id = self.id.clone();
         let channel_service = self.channel_service.clone();
+        let rate = Arc::new(TransferRate::default());
 
         let transfer_task = transfer::driver::spawn_transfer_task(
             shard_holder,
@@ -136,14 +142,16 @@ impl Collection {
             consensus,
             collection_id,
             channel_service,
-            self.snapshots_path.clone(),
+            self.snapshots_path(),
             self.name(),
             temp_dir,
+            self.shared_storage_config.shard_transfer,
+            rate.clone(),
             on_finish,
             on_error,
         );
 
-        active_transfer_tasks.add_task(&transfer, transfer_task);
+        active_transfer_tasks.add_task(&transfer, transfer_task, rate);
     }
 
     /// Handles finishing of the shard transfer.
@@ -205,6 +213,19 @@ impl Collection {
         let finish_was_registered =
             shards_holder_guard.register_finish_transfer(&transfer.key())?;
         log::debug!("finish_was_registered: {}", finish_was_registered);
+
+        if finish_was_registered {
+            self.shared_storage_config.events.notify(
+                self.name(),
+                Some(transfer.shard_id),
+                CollectionEventKind::ShardTransferFinished {
+                    from: transfer.from,
+                    to: transfer.to,
+                    sync: transfer.sync,
+                },
+            );
+        }
+
         Ok(())
     }
 
@@ -49,10 +49,9 @@ impl Collection {
     ) -> Result<(), CollectionError> {
         let state = self.state().await;
         match state.config.params.sharding_method.unwrap_or_default() {
-            ShardingMethod::Auto => {
+            sharding_method @ (ShardingMethod::Auto | ShardingMethod::PayloadKey) => {
                 return Err(CollectionError::bad_request(format!(
-                    "Shard Key {} cannot be created with Auto sharding method",
-                    shard_key
+                    "Shard Key {shard_key} cannot be created with {sharding_method:?} sharding method",
                 )));
             }
             ShardingMethod::Custom => {}
@@ -105,7 +104,9 @@ impl Collection {
                     }),
                 );
 
-                replica_set.update_local(create_index_op, true).await?;
+                replica_set
+                    .update_local(create_index_op, true, None)
+                    .await?;
             }
 
             self.shards_holder.write().await.add_shard(
@@ -121,10 +122,9 @@ impl Collection {
         let state = self.state().await;
 
         match state.config.params.sharding_method.unwrap_or_default() {
-            ShardingMethod::Auto => {
+            sharding_method @ (ShardingMethod::Auto | ShardingMethod::PayloadKey) => {
                 return Err(CollectionError::bad_request(format!(
-                    "Shard Key {} cannot be removed with Auto sharding method",
-                    shard_key
+                    "Shard Key {shard_key} cannot be removed with {sharding_method:?} sharding method",
                 )));
             }
             ShardingMethod::Custom => {}
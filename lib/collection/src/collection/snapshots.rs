@@ -1,15 +1,25 @@
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
+use std::ffi::OsStr;
+use std::io::Read;
 use std::path::{Path, PathBuf};
 
-use io::file_operations::read_json;
+use chrono::Utc;
+use io::file_operations::{atomic_save_json, read_json};
 use segment::common::version::StorageVersion as _;
-use tempfile::TempPath;
+use segment::types::SeqNumberType;
+use serde::{Deserialize, Serialize};
 use tokio::fs;
 
 use super::Collection;
 use crate::collection::CollectionVersion;
+use crate::common::io_budget::ThrottledWriter;
+use crate::common::snapshot_storage::SnapshotStorage;
 use crate::config::{CollectionConfig, ShardingMethod};
-use crate::operations::snapshot_ops::{self, SnapshotDescription};
+use crate::events::CollectionEventKind;
+use crate::operations::running_operations::{
+    next_operation_id, OperationProgress, RunningOperation, RunningOperationKind,
+};
+use crate::operations::snapshot_ops::{SnapshotCreationPriority, SnapshotDescription};
 use crate::operations::types::{CollectionError, CollectionResult, NodeType};
 use crate::shards::local_shard::LocalShard;
 use crate::shards::remote_shard::RemoteShard;
@@ -17,30 +27,108 @@ use crate::shards::replica_set::ShardReplicaSet;
 use crate::shards::shard::{PeerId, ShardId};
 use crate::shards::shard_config::{self, ShardConfig};
 use crate::shards::shard_holder::{ShardKeyMapping, SHARD_KEY_MAPPING_FILE};
+use crate::shards::shard_split::{self, ShardSplit, SHARD_SPLITS_FILE};
 use crate::shards::shard_versioning;
 
+/// Stored as the first entry of each collection snapshot archive
+const SNAPSHOT_MANIFEST_FILE: &str = "snapshot_manifest.json";
+
+/// Ending of the names of incremental snapshots
+const INCREMENTAL_SNAPSHOT_SUFFIX: &str = "-incremental.snapshot";
+
+/// Ending of the names of snapshots, created by the snapshot scheduler
+pub const SCHEDULED_SNAPSHOT_SUFFIX: &str = "-scheduled.snapshot";
+
+/// Describes the content of a collection snapshot, required to create and restore incremental
+/// snapshots
+#[derive(Debug, Default, Deserialize, Serialize)]
+struct SnapshotManifest {
+    /// Name of the snapshot, which this snapshot is an increment of. `None` for full snapshots.
+    #[serde(default)]
+    base: Option<String>,
+    /// Versions of all segments of the local shards at the moment of the snapshot, including
+    /// segments which are not stored in an incremental snapshot as they are unchanged
+    #[serde(default)]
+    shards: HashMap<ShardId, HashMap<String, SeqNumberType>>,
+}
+
+impl SnapshotManifest {
+    /// Load manifest of the unpacked snapshot, snapshots without manifest are full ones
+    fn load(snapshot_dir: &Path) -> CollectionResult<Self> {
+        let path = snapshot_dir.join(SNAPSHOT_MANIFEST_FILE);
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        Ok(read_json(&path)?)
+    }
+
+    /// Read manifest from the snapshot archive without unpacking it
+    ///
+    /// Manifest is the first entry of the archive, so the rest of the archive is not read.
+    fn read_from_archive(archive: impl Read, snapshot_path: &Path) -> CollectionResult<Self> {
+        let mut archive = tar::Archive::new(archive);
+        for entry in archive.entries()? {
+            let entry = entry?;
+            if entry.path()?.as_ref() == Path::new(SNAPSHOT_MANIFEST_FILE) {
+                return serde_json::from_reader(entry).map_err(|err| {
+                    CollectionError::service_error(format!(
+                        "Can't read manifest of snapshot {}: {err}",
+                        snapshot_path.display(),
+                    ))
+                });
+            }
+        }
+        Err(CollectionError::bad_input(format!(
+            "Snapshot {} has no manifest, it was created by an older version and can't be a base of an incremental snapshot",
+            snapshot_path.display(),
+        )))
+    }
+}
+
 impl Collection {
+    /// Storage of the collection snapshots, local or object storage
+    pub fn snapshot_storage(&self) -> &SnapshotStorage {
+        &self.shared_storage_config.snapshot_storage
+    }
+
+    /// Directory of the collection snapshots
+    pub fn snapshots_path(&self) -> PathBuf {
+        self.snapshots_path.read().clone()
+    }
+
+    /// Store new snapshots in another directory, existing snapshots are not moved
+    pub fn set_snapshots_path(&self, snapshots_path: PathBuf) {
+        *self.snapshots_path.write() = snapshots_path;
+    }
+
     pub async fn list_snapshots(&self) -> CollectionResult<Vec<SnapshotDescription>> {
-        snapshot_ops::list_snapshots_in_directory(&self.snapshots_path).await
+        self.snapshot_storage().list(&self.snapshots_path()).await
+    }
+
+    pub async fn delete_snapshot(&self, snapshot_name: &str) -> CollectionResult<()> {
+        let snapshot_path = self.get_snapshot_path(snapshot_name).await?;
+        log::info!("Deleting collection snapshot {:?}", snapshot_path);
+        self.snapshot_storage().delete(&snapshot_path).await
     }
 
     /// Creates a snapshot of the collection.
     ///
-    /// The snapshot is created in three steps:
+    /// The snapshot is created in two steps:
     /// 1. Create a temporary directory and create a snapshot of each shard in it.
-    /// 2. Archive the temporary directory into a single file.
-    /// 3. Move the archive to the final location.
+    /// 2. Archive the temporary directory, streaming the archive into the snapshot storage.
     ///
     /// # Arguments
     ///
     /// * `global_temp_dir`: directory used to host snapshots while they are being created
     /// * `this_peer_id`: current peer id
+    /// * `priority`: low priority snapshots take disk throughput from the shared budget
     ///
     /// returns: Result<SnapshotDescription, CollectionError>
     pub async fn create_snapshot(
         &self,
         global_temp_dir: &Path,
         this_peer_id: PeerId,
+        priority: SnapshotCreationPriority,
     ) -> CollectionResult<SnapshotDescription> {
         let snapshot_name = format!(
             "{}-{}-{}.snapshot",
@@ -48,15 +136,132 @@ impl Collection {
             this_peer_id,
             chrono::Utc::now().format("%Y-%m-%d-%H-%M-%S")
         );
+        self.create_snapshot_with_base(snapshot_name, global_temp_dir, this_peer_id, priority, None)
+            .await
+    }
+
+    /// Creates an incremental snapshot of the collection on top of `base_snapshot`.
+    ///
+    /// Only segments, which were changed since the base snapshot, are stored, along with the WAL
+    /// and configuration. The base snapshot may be incremental itself. To restore, the whole
+    /// chain of snapshots, starting from a full one, is required,
+    /// see [`Self::restore_snapshot_chain`].
+    pub async fn create_incremental_snapshot(
+        &self,
+        global_temp_dir: &Path,
+        this_peer_id: PeerId,
+        priority: SnapshotCreationPriority,
+        base_snapshot: &str,
+    ) -> CollectionResult<SnapshotDescription> {
+        let base_snapshot_path = self.get_snapshot_path(base_snapshot).await?;
+        let base_reader = self.snapshot_storage().reader(&base_snapshot_path).await?;
+        let base_manifest = tokio::task::spawn_blocking(move || {
+            SnapshotManifest::read_from_archive(base_reader, &base_snapshot_path)
+        })
+        .await??;
+
+        let snapshot_name = format!(
+            "{}-{}-{}{INCREMENTAL_SNAPSHOT_SUFFIX}",
+            self.name(),
+            this_peer_id,
+            chrono::Utc::now().format("%Y-%m-%d-%H-%M-%S-%3f")
+        );
+        self.create_snapshot_with_base(
+            snapshot_name,
+            global_temp_dir,
+            this_peer_id,
+            priority,
+            Some((base_snapshot, base_manifest)),
+        )
+        .await
+    }
+
+    /// Creates a low priority snapshot of the collection on behalf of the snapshot scheduler
+    ///
+    /// Its name ends with [`SCHEDULED_SNAPSHOT_SUFFIX`], so the scheduler can tell its own
+    /// snapshots from the ones created by users.
+    pub async fn create_scheduled_snapshot(
+        &self,
+        global_temp_dir: &Path,
+        this_peer_id: PeerId,
+    ) -> CollectionResult<SnapshotDescription> {
+        let snapshot_name = format!(
+            "{}-{}-{}{SCHEDULED_SNAPSHOT_SUFFIX}",
+            self.name(),
+            this_peer_id,
+            chrono::Utc::now().format("%Y-%m-%d-%H-%M-%S")
+        );
+        self.create_snapshot_with_base(
+            snapshot_name,
+            global_temp_dir,
+            this_peer_id,
+            SnapshotCreationPriority::Low,
+            None,
+        )
+        .await
+    }
+
+    /// Names of the base snapshots of the incremental snapshots of the collection, by the names
+    /// of the incremental snapshots
+    pub async fn incremental_snapshot_bases(&self) -> CollectionResult<HashMap<String, String>> {
+        let mut bases = HashMap::new();
+        for snapshot in self.list_snapshots().await? {
+            if !snapshot.name.ends_with(INCREMENTAL_SNAPSHOT_SUFFIX) {
+                continue;
+            }
+            let snapshot_path = self.get_snapshot_path(&snapshot.name).await?;
+            let reader = self.snapshot_storage().reader(&snapshot_path).await?;
+            let manifest = tokio::task::spawn_blocking(move || {
+                SnapshotManifest::read_from_archive(reader, &snapshot_path)
+            })
+            .await??;
+            if let Some(base) = manifest.base {
+                bases.insert(snapshot.name, base);
+            }
+        }
+        Ok(bases)
+    }
+
+    async fn create_snapshot_with_base(
+        &self,
+        snapshot_name: String,
+        global_temp_dir: &Path,
+        this_peer_id: PeerId,
+        priority: SnapshotCreationPriority,
+        base: Option<(&str, SnapshotManifest)>,
+    ) -> CollectionResult<SnapshotDescription> {
+        let (base_name, base_manifest) = base.unzip();
+        let mut manifest = SnapshotManifest {
+            base: base_name.map(str::to_string),
+            shards: HashMap::new(),
+        };
 
         // Final location of snapshot
-        let snapshot_path = self.snapshots_path.join(&snapshot_name);
+        let snapshot_path = self.snapshots_path().join(&snapshot_name);
         log::info!(
             "Creating collection snapshot {} into {:?}",
             snapshot_name,
             snapshot_path
         );
 
+        let operation = self.operations_registry.register(RunningOperation {
+            id: next_operation_id(),
+            kind: RunningOperationKind::SnapshotCreation,
+            collection_name: self.id.clone(),
+            shard_id: None,
+            description: format!("Creation of collection snapshot {snapshot_name}"),
+            started_at: Utc::now(),
+            progress: None,
+            cancellable: false,
+        });
+
+        let io_budget = match priority {
+            SnapshotCreationPriority::Low => {
+                Some(self.shared_storage_config.low_priority_io_budget.clone())
+            }
+            SnapshotCreationPriority::Normal => None,
+        };
+
         // Dedicated temporary directory for this snapshot (deleted on drop)
         let snapshot_temp_target_dir = tempfile::Builder::new()
             .prefix(&format!("{snapshot_name}-target-"))
@@ -69,8 +274,13 @@ impl Collection {
                 .prefix(&format!("{snapshot_name}-temp-"))
                 .tempdir_in(global_temp_dir)?;
             let shards_holder = self.shards_holder.read().await;
+            let shards_count = shards_holder.get_shards().count() as u64;
             // Create snapshot of each shard
-            for (shard_id, replica_set) in shards_holder.get_shards() {
+            for (done, (shard_id, replica_set)) in shards_holder.get_shards().enumerate() {
+                operation.set_progress(OperationProgress {
+                    done: done as u64,
+                    total: Some(shards_count),
+                });
                 let shard_snapshot_path = shard_versioning::versioned_shard_path(
                     &snapshot_temp_target_dir_path,
                     *shard_id,
@@ -78,7 +288,7 @@ impl Collection {
                 );
                 fs::create_dir_all(&shard_snapshot_path).await?;
                 // If node is listener, we can save whatever currently is in the storage
-                let save_wal = self.shared_storage_config.node_type != NodeType::Listener;
+                let save_wal = self.shared_storage_config.node_type.get() != NodeType::Listener;
                 replica_set
                     .create_snapshot(
                         snapshot_temp_temp_dir.path(),
@@ -86,6 +296,39 @@ impl Collection {
                         save_wal,
                     )
                     .await?;
+
+                let segment_versions_path = LocalShard::segment_versions_path(&shard_snapshot_path);
+                if !segment_versions_path.exists() {
+                    // No local data
+                    continue;
+                }
+
+                if let Some(io_budget) = &io_budget {
+                    // Segments are copied under the lock of the shard segments, so the copy is
+                    // not slowed down. Instead, it is paid off before the next shard.
+                    let segments_path = LocalShard::segments_path(&shard_snapshot_path);
+                    io_budget.acquire(files_size(&segments_path).await?).await;
+                }
+
+                let segment_versions: HashMap<String, SeqNumberType> =
+                    read_json(&segment_versions_path)?;
+                fs::remove_file(&segment_versions_path).await?;
+
+                if let Some(base_segments) = base_manifest
+                    .as_ref()
+                    .and_then(|base_manifest| base_manifest.shards.get(shard_id))
+                {
+                    let segments_path = LocalShard::segments_path(&shard_snapshot_path);
+                    for (segment_id, version) in &segment_versions {
+                        if base_segments.get(segment_id) == Some(version) {
+                            // Unchanged since the base snapshot
+                            fs::remove_file(segments_path.join(format!("{segment_id}.tar")))
+                                .await?;
+                        }
+                    }
+                }
+
+                manifest.shards.insert(*shard_id, segment_versions);
             }
         }
 
@@ -96,51 +339,65 @@ impl Collection {
             .await
             .save(&snapshot_temp_target_dir_path)?;
 
-        self.shards_holder
-            .read()
-            .await
-            .save_key_mapping_to_dir(&snapshot_temp_target_dir_path)?;
+        {
+            let shards_holder = self.shards_holder.read().await;
+            shards_holder.save_key_mapping_to_dir(&snapshot_temp_target_dir_path)?;
+            shards_holder.save_shard_splits_to_dir(&snapshot_temp_target_dir_path)?;
+        }
 
         let payload_index_schema_tmp_path =
             Self::payload_index_file(&snapshot_temp_target_dir_path);
         self.payload_index_schema
             .save_to(&payload_index_schema_tmp_path)?;
+        self.stored_queries
+            .save_to(Self::stored_queries_file(&snapshot_temp_target_dir_path))?;
 
-        // Dedicated temporary file for archiving this snapshot (deleted on drop)
-        let mut snapshot_temp_arc_file = tempfile::Builder::new()
-            .prefix(&format!("{snapshot_name}-arc-"))
+        // Manifest is stored outside of the snapshot folder, to be the first entry of the archive
+        let manifest_temp_file = tempfile::Builder::new()
+            .prefix(&format!("{snapshot_name}-manifest-"))
             .tempfile_in(global_temp_dir)?;
+        atomic_save_json(manifest_temp_file.path(), &manifest)?;
 
-        // Archive snapshot folder into a single file
+        // Archive snapshot folder right into the snapshot storage, the snapshot only appears
+        // there once the archive is complete
         log::debug!("Archiving snapshot {:?}", &snapshot_temp_target_dir_path);
+        let snapshot_size = dir_size(&snapshot_temp_target_dir_path).await?;
+        let snapshot_writer = self
+            .snapshot_storage()
+            .writer(&snapshot_path, snapshot_size)
+            .await?;
         let archiving = tokio::task::spawn_blocking(move || -> CollectionResult<_> {
-            let mut builder = tar::Builder::new(snapshot_temp_arc_file.as_file_mut());
+            let snapshot_writer =
+                ThrottledWriter::new(snapshot_writer, io_budget.unwrap_or_default());
+            let mut builder = tar::Builder::new(snapshot_writer);
+            builder.append_path_with_name(manifest_temp_file.path(), SNAPSHOT_MANIFEST_FILE)?;
             // archive recursively collection directory `snapshot_path_with_arc_extension` into `snapshot_path`
             builder.append_dir_all(".", &snapshot_temp_target_dir_path)?;
-            builder.finish()?;
-            drop(builder);
-            // return ownership of the file
-            Ok(snapshot_temp_arc_file)
+            builder.into_inner()?.into_inner().finish()
         });
-        snapshot_temp_arc_file = archiving.await??;
-
-        // Move snapshot to permanent location.
-        // We can't move right away, because snapshot folder can be on another mounting point.
-        // We can't copy to the target location directly, because copy is not atomic.
-        // So we copy to the final location with a temporary name and then rename atomically.
-        let snapshot_path_tmp_move = snapshot_path.with_extension("tmp");
-
-        // Ensure that the temporary file is deleted on error
-        let _temp_path = TempPath::from_path(&snapshot_path_tmp_move);
-        fs::copy(&snapshot_temp_arc_file.path(), &snapshot_path_tmp_move).await?;
-        fs::rename(&snapshot_path_tmp_move, &snapshot_path).await?;
-
-        log::info!(
-            "Collection snapshot {} completed into {:?}",
-            snapshot_name,
-            snapshot_path
+        archiving.await??;
+
+        match &manifest.base {
+            Some(base_name) => log::info!(
+                "Incremental collection snapshot {} on top of {} completed into {:?}",
+                snapshot_name,
+                base_name,
+                snapshot_path
+            ),
+            None => log::info!(
+                "Collection snapshot {} completed into {:?}",
+                snapshot_name,
+                snapshot_path
+            ),
+        }
+        self.shared_storage_config.events.notify(
+            self.name(),
+            None,
+            CollectionEventKind::SnapshotCompleted {
+                snapshot_name: snapshot_name.clone(),
+            },
         );
-        snapshot_ops::get_snapshot_description(&snapshot_path).await
+        self.snapshot_storage().describe(&snapshot_path).await
     }
 
     /// Restore collection from snapshot
@@ -152,17 +409,172 @@ impl Collection {
         this_peer_id: PeerId,
         is_distributed: bool,
     ) -> CollectionResult<()> {
-        // decompress archive
         let archive_file = std::fs::File::open(snapshot_path)?;
-        let mut ar = tar::Archive::new(archive_file);
+        Self::restore_snapshot_from_reader(
+            archive_file,
+            &snapshot_path.display().to_string(),
+            target_dir,
+            this_peer_id,
+            is_distributed,
+        )
+    }
+
+    /// Restore collection from snapshot archive, which is read from `archive`, e.g. streamed
+    /// from the object storage
+    ///
+    /// This method performs blocking IO.
+    pub fn restore_snapshot_from_reader(
+        archive: impl Read,
+        snapshot_name: &str,
+        target_dir: &Path,
+        this_peer_id: PeerId,
+        is_distributed: bool,
+    ) -> CollectionResult<()> {
+        // decompress archive
+        let mut ar = tar::Archive::new(archive);
         ar.unpack(target_dir)?;
 
+        if let Some(base) = SnapshotManifest::load(target_dir)?.base {
+            return Err(CollectionError::bad_input(format!(
+                "Snapshot {snapshot_name} is incremental on top of {base}, it can only be restored together with its base snapshots",
+            )));
+        }
+
+        Self::restore_unpacked_snapshot(target_dir, this_peer_id, is_distributed)
+    }
+
+    /// Restore collection from a chain of snapshots: a full snapshot, followed by incremental
+    /// snapshots, each made on top of the previous one
+    ///
+    /// This method performs blocking IO.
+    pub fn restore_snapshot_chain(
+        snapshot_paths: &[PathBuf],
+        target_dir: &Path,
+        this_peer_id: PeerId,
+        is_distributed: bool,
+    ) -> CollectionResult<()> {
+        let Some((full_snapshot_path, increment_paths)) = snapshot_paths.split_first() else {
+            return Err(CollectionError::bad_input(
+                "Snapshot chain is empty".to_string(),
+            ));
+        };
+
+        let archive_file = std::fs::File::open(full_snapshot_path)?;
+        tar::Archive::new(archive_file).unpack(target_dir)?;
+        let manifest = SnapshotManifest::load(target_dir)?;
+        if manifest.base.is_some() {
+            return Err(CollectionError::bad_input(format!(
+                "Snapshot chain must start with a full snapshot, but {} is incremental",
+                full_snapshot_path.display(),
+            )));
+        }
+
+        let mut previous_path = full_snapshot_path;
+        for increment_path in increment_paths {
+            Self::apply_snapshot_increment(increment_path, previous_path, target_dir)?;
+            previous_path = increment_path;
+        }
+
+        Self::restore_unpacked_snapshot(target_dir, this_peer_id, is_distributed)
+    }
+
+    /// Unpack incremental snapshot on top of the unpacked snapshot in `target_dir`
+    ///
+    /// Segments, which are not changed since the base snapshot, are taken from `target_dir`,
+    /// everything else is replaced with the content of the increment.
+    fn apply_snapshot_increment(
+        increment_path: &Path,
+        base_path: &Path,
+        target_dir: &Path,
+    ) -> CollectionResult<()> {
+        let base_name = base_path.file_name().and_then(|name| name.to_str());
+
+        let increment_dir = tempfile::Builder::new()
+            .prefix("snapshot-increment-")
+            .tempdir_in(
+                target_dir
+                    .parent()
+                    .filter(|parent| !parent.as_os_str().is_empty())
+                    .unwrap_or_else(|| Path::new(".")),
+            )?;
+        let archive_file = std::fs::File::open(increment_path)?;
+        tar::Archive::new(archive_file).unpack(increment_dir.path())?;
+
+        let manifest = SnapshotManifest::load(increment_dir.path())?;
+        if manifest.base.is_none() || manifest.base.as_deref() != base_name {
+            return Err(CollectionError::bad_input(format!(
+                "Snapshot {} is not an increment of {}",
+                increment_path.display(),
+                base_path.display(),
+            )));
+        }
+
+        for (shard_id, segment_versions) in &manifest.shards {
+            let base_segments_path = LocalShard::segments_path(
+                &shard_versioning::versioned_shard_path(target_dir, *shard_id, 0),
+            );
+            let increment_segments_path = LocalShard::segments_path(
+                &shard_versioning::versioned_shard_path(increment_dir.path(), *shard_id, 0),
+            );
+            std::fs::create_dir_all(&increment_segments_path)?;
+
+            for segment_id in segment_versions.keys() {
+                let segment_file = format!("{segment_id}.tar");
+                let increment_segment_path = increment_segments_path.join(&segment_file);
+                if increment_segment_path.exists() {
+                    continue;
+                }
+                let base_segment_path = base_segments_path.join(&segment_file);
+                if !base_segment_path.exists() {
+                    return Err(CollectionError::bad_input(format!(
+                        "Segment {segment_id} of shard {shard_id} is missing in the base snapshot {}",
+                        base_path.display(),
+                    )));
+                }
+                std::fs::rename(base_segment_path, increment_segment_path)?;
+            }
+        }
+
+        std::fs::remove_dir_all(target_dir)?;
+        std::fs::rename(increment_dir.into_path(), target_dir)?;
+
+        log::debug!(
+            "Applied incremental snapshot {} on top of {}",
+            increment_path.display(),
+            base_path.display(),
+        );
+        Ok(())
+    }
+
+    /// Restore collection from the snapshot, unpacked into `target_dir`
+    fn restore_unpacked_snapshot(
+        target_dir: &Path,
+        this_peer_id: PeerId,
+        is_distributed: bool,
+    ) -> CollectionResult<()> {
+        let manifest_path = target_dir.join(SNAPSHOT_MANIFEST_FILE);
+        if manifest_path.exists() {
+            std::fs::remove_file(manifest_path)?;
+        }
+
         let config = CollectionConfig::load(target_dir)?;
         config.validate_and_warn();
         let configured_shards = config.params.shard_number.get();
 
         let shard_ids_list: Vec<_> = match config.params.sharding_method.unwrap_or_default() {
-            ShardingMethod::Auto => (0..configured_shards).collect(),
+            ShardingMethod::Auto | ShardingMethod::PayloadKey => {
+                // Snapshot stores only the finished splits
+                let splits_path = target_dir.join(SHARD_SPLITS_FILE);
+                let shard_splits: Vec<ShardSplit> = if splits_path.exists() {
+                    read_json(&splits_path)?
+                } else {
+                    Vec::new()
+                };
+                shard_split::live_shard_ids(
+                    configured_shards - shard_splits.len() as u32,
+                    &shard_splits,
+                )
+            }
             ShardingMethod::Custom => {
                 // Load shard mapping from disk
                 let mapping_path = target_dir.join(SHARD_KEY_MAPPING_FILE);
@@ -241,8 +653,25 @@ impl Collection {
             .await
     }
 
+    /// Path of the snapshot in the snapshots directory
+    ///
+    /// If snapshots are stored in the object storage, the path is only used to address the
+    /// snapshot in [`Self::snapshot_storage`].
     pub async fn get_snapshot_path(&self, snapshot_name: &str) -> CollectionResult<PathBuf> {
-        let snapshot_path = self.snapshots_path.join(snapshot_name);
+        let snapshots_path = self.snapshots_path();
+        let snapshot_path = snapshots_path.join(snapshot_name);
+
+        if !self.snapshot_storage().is_local() {
+            // Only plain names are allowed, so snapshots of other collections can't be addressed
+            let is_plain_name =
+                Path::new(snapshot_name).file_name() == Some(OsStr::new(snapshot_name));
+            if !is_plain_name || !self.snapshot_storage().exists(&snapshot_path).await? {
+                return Err(CollectionError::NotFound {
+                    what: format!("Snapshot {snapshot_name}"),
+                });
+            }
+            return Ok(snapshot_path);
+        }
 
         let absolute_snapshot_path =
             snapshot_path
@@ -252,10 +681,10 @@ impl Collection {
                 })?;
 
         let absolute_snapshot_dir =
-            self.snapshots_path
+            snapshots_path
                 .canonicalize()
                 .map_err(|_| CollectionError::NotFound {
-                    what: format!("Snapshot directory: {}", self.snapshots_path.display()),
+                    what: format!("Snapshot directory: {}", snapshots_path.display()),
                 })?;
 
         if !absolute_snapshot_path.starts_with(absolute_snapshot_dir) {
@@ -279,7 +708,7 @@ impl Collection {
         self.shards_holder
             .read()
             .await
-            .list_shard_snapshots(&self.snapshots_path, shard_id)
+            .list_shard_snapshots(&self.snapshots_path(), shard_id)
             .await
     }
 
@@ -291,7 +720,7 @@ impl Collection {
         self.shards_holder
             .read()
             .await
-            .create_shard_snapshot(&self.snapshots_path, &self.name(), shard_id, temp_dir)
+            .create_shard_snapshot(&self.snapshots_path(), &self.name(), shard_id, temp_dir)
             .await
     }
 
@@ -344,7 +773,31 @@ impl Collection {
         self.shards_holder
             .read()
             .await
-            .get_shard_snapshot_path(&self.snapshots_path, shard_id, snapshot_file_name)
+            .get_shard_snapshot_path(&self.snapshots_path(), shard_id, snapshot_file_name)
             .await
     }
 }
+
+/// Total size of the files in the directory and its subdirectories
+async fn dir_size(dir: &Path) -> CollectionResult<u64> {
+    let dir = dir.to_path_buf();
+    tokio::task::spawn_blocking(move || {
+        fs_extra::dir::get_size(&dir).map_err(|err| {
+            CollectionError::service_error(format!("Failed to get size of {dir:?}: {err}"))
+        })
+    })
+    .await?
+}
+
+/// Total size of the files in the directory, not recursive
+async fn files_size(dir: &Path) -> CollectionResult<usize> {
+    let mut size = 0;
+    let mut entries = fs::read_dir(dir).await?;
+    while let Some(entry) = entries.next_entry().await? {
+        let metadata = entry.metadata().await?;
+        if metadata.is_file() {
+            size += metadata.len() as usize;
+        }
+    }
+    Ok(size)
+}
@@ -0,0 +1,234 @@
+//! Soft delete: deleted points are retained for a configured window, hidden from all reads,
+//! and could be restored until the expiration worker deletes them permanently.
+
+use std::collections::HashSet;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use segment::types::{
+    Condition, Filter, HasIdCondition, IsEmptyCondition, Payload, PayloadField, PayloadFieldSchema,
+    PayloadSchemaType, PointIdType, WithPayloadInterface,
+};
+use serde_json::json;
+
+use super::access_tags::AccessTags;
+use super::Collection;
+use crate::operations::consistency_params::ReadConsistency;
+use crate::operations::payload_ops::{DeletePayloadOp, PayloadOps, SetPayloadOp};
+use crate::operations::point_ops::PointOperations;
+use crate::operations::shard_selector_internal::ShardSelectorInternal;
+use crate::operations::types::*;
+use crate::operations::CollectionUpdateOperations;
+use crate::shards::replica_set::read_priority::ReadPriority;
+use crate::shards::replica_set::stale_reads::StaleReads;
+
+/// Reserved payload field, which holds the deletion time of a soft-deleted point as unix timestamp in seconds
+pub const SOFT_DELETED_AT_KEY: &str = "__qdrant_deleted_at";
+
+impl Collection {
+    pub(crate) async fn is_soft_delete_enabled(&self) -> bool {
+        self.collection_config
+            .read()
+            .await
+            .params
+            .soft_delete_window_sec
+            .is_some()
+    }
+
+    /// Restrict the filter to points, which are not soft-deleted, if soft delete is enabled
+    pub(crate) async fn exclude_soft_deleted(&self, filter: Option<Filter>) -> Option<Filter> {
+        if self.is_soft_delete_enabled().await {
+            Some(exclude_soft_deleted(filter))
+        } else {
+            filter
+        }
+    }
+
+    /// Replace deletion of points with marking them as deleted, if soft delete is enabled
+    ///
+    /// Points, which are already soft-deleted, keep their original deletion time.
+    pub(crate) async fn soft_delete_operation(
+        &self,
+        operation: CollectionUpdateOperations,
+    ) -> CollectionUpdateOperations {
+        if !self.is_soft_delete_enabled().await {
+            return operation;
+        }
+
+        let filter = match operation {
+            CollectionUpdateOperations::PointOperation(PointOperations::DeletePoints { ids }) => {
+                // Marking by filter, as unlike deletion, setting payload fails on missing points
+                let ids: HashSet<_> = ids.into_iter().collect();
+                Filter::new_must(Condition::HasId(HasIdCondition::from(ids)))
+            }
+            CollectionUpdateOperations::PointOperation(PointOperations::DeletePointsByFilter(
+                filter,
+            )) => filter,
+            operation => return operation,
+        };
+
+        let deleted_at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+        let payload: Payload = json!({ SOFT_DELETED_AT_KEY: deleted_at }).into();
+
+        CollectionUpdateOperations::PayloadOperation(PayloadOps::SetPayload(SetPayloadOp {
+            payload,
+            points: None,
+            filter: Some(exclude_soft_deleted(Some(filter))),
+            if_version: None,
+        }))
+    }
+
+    /// Retrieve points by ids, skipping soft-deleted ones
+    ///
+    /// Points are selected with a filter, so soft-deleted points and points hidden by access tags
+    /// could be excluded the same way as in other reads.
+    pub(crate) async fn retrieve_by_scroll(
+        &self,
+        request: &PointRequestInternal,
+        read_consistency: Option<ReadConsistency>,
+        shard_selection: &ShardSelectorInternal,
+        access_tags: Option<&AccessTags>,
+        read_priority: ReadPriority,
+        stale_reads: Option<&StaleReads>,
+    ) -> CollectionResult<Vec<Record>> {
+        let ids: HashSet<_> = request.ids.iter().copied().collect();
+        if ids.is_empty() {
+            return Ok(vec![]);
+        }
+        let scroll_request = ScrollRequestInternal {
+            offset: None,
+            limit: Some(ids.len()),
+            filter: Some(Filter::new_must(Condition::HasId(HasIdCondition::from(
+                ids,
+            )))),
+            with_payload: Some(
+                request
+                    .with_payload
+                    .clone()
+                    .unwrap_or(WithPayloadInterface::Bool(false)),
+            ),
+            with_vector: request.with_vector.clone(),
+            as_of: request.as_of,
+            session_id: None,
+            session_ttl_sec: None,
+            order_by: None,
+            sample: None,
+            with_version: request.with_version,
+        };
+        let result = self
+            .scroll_by(
+                scroll_request,
+                read_consistency,
+                shard_selection,
+                access_tags,
+                read_priority,
+                stale_reads,
+            )
+            .await?;
+        Ok(result.points)
+    }
+
+    /// Index the deletion time field, so soft-deleted points are excluded from reads efficiently
+    ///
+    /// Does nothing if soft delete is disabled or the field is already indexed.
+    pub async fn create_soft_delete_index(&self) -> CollectionResult<()> {
+        if !self.is_soft_delete_enabled().await
+            || self
+                .payload_index_schema
+                .read()
+                .schema
+                .contains_key(SOFT_DELETED_AT_KEY)
+        {
+            return Ok(());
+        }
+        self.create_payload_index(
+            SOFT_DELETED_AT_KEY.to_string(),
+            PayloadFieldSchema::FieldType(PayloadSchemaType::Integer),
+        )
+        .await?;
+        Ok(())
+    }
+}
+
+/// Add a condition, which excludes soft-deleted points, to the filter
+pub fn exclude_soft_deleted(filter: Option<Filter>) -> Filter {
+    let not_deleted = Filter::new_must(Condition::IsEmpty(IsEmptyCondition {
+        is_empty: PayloadField {
+            key: SOFT_DELETED_AT_KEY.to_string(),
+        },
+    }));
+    match filter {
+        None => not_deleted,
+        Some(filter) => filter.merge(&not_deleted),
+    }
+}
+
+/// Remove the soft delete marker from the payload of a point, which is returned to the client
+///
+/// Soft-deleted points are excluded from reads, but points, which were soft-deleted before
+/// the soft delete was disabled, remain visible with the marker.
+pub(crate) fn hide_soft_delete_marker(payload: &mut Option<Payload>) {
+    if let Some(payload) = payload {
+        payload.0.remove(SOFT_DELETED_AT_KEY);
+    }
+}
+
+/// Operation, which restores soft-deleted points
+pub fn undelete_operation(ids: Vec<PointIdType>) -> CollectionUpdateOperations {
+    CollectionUpdateOperations::PayloadOperation(PayloadOps::DeletePayload(DeletePayloadOp {
+        keys: vec![SOFT_DELETED_AT_KEY.to_string()],
+        points: Some(ids),
+        filter: None,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use segment::types::{FieldCondition, Match, ValueVariants};
+
+    use super::*;
+
+    #[test]
+    fn test_exclude_soft_deleted() {
+        let is_not_deleted = Condition::IsEmpty(IsEmptyCondition {
+            is_empty: PayloadField {
+                key: SOFT_DELETED_AT_KEY.to_string(),
+            },
+        });
+        assert_eq!(
+            exclude_soft_deleted(None),
+            Filter::new_must(is_not_deleted.clone()),
+        );
+
+        let condition = Condition::Field(FieldCondition::new_match(
+            "color",
+            Match::new_value(ValueVariants::Keyword("red".to_string())),
+        ));
+        let filter = Filter {
+            should: Some(vec![condition.clone()]),
+            must: Some(vec![condition.clone()]),
+            must_not: None,
+        };
+        assert_eq!(
+            exclude_soft_deleted(Some(filter)),
+            Filter {
+                should: Some(vec![condition.clone()]),
+                must: Some(vec![condition, is_not_deleted]),
+                must_not: None,
+            },
+        );
+    }
+
+    #[test]
+    fn test_hide_soft_delete_marker() {
+        let mut payload = Some(Payload::from(json!({ "a": 1, SOFT_DELETED_AT_KEY: 100 })));
+        hide_soft_delete_marker(&mut payload);
+        assert_eq!(payload, Some(Payload::from(json!({ "a": 1 }))));
+
+        let mut payload = None;
+        hide_soft_delete_marker(&mut payload);
+        assert_eq!(payload, None);
+    }
+}
@@ -20,10 +20,15 @@ impl Collection {
         self.apply_config(state.config).await?;
         self.apply_shard_transfers(state.transfers, this_peer_id, abort_transfer)
             .await?;
+        // Splits define the ring of the applied shards
+        self.apply_shard_splits(state.shard_splits).await?;
         self.apply_shard_info(state.shards, state.shards_key_mapping)
             .await?;
         self.apply_payload_index_schema(state.payload_index_schema)
             .await?;
+        self.update_stored_queries(|stored_queries| *stored_queries = state.stored_queries)?;
+        self.read_only
+            .write(|read_only| *read_only = state.read_only)?;
         Ok(())
     }
 
@@ -65,6 +70,11 @@ impl Collection {
             let mut config = self.collection_config.write().await;
             config.params.replication_factor = new_config.params.replication_factor;
             config.params.write_consistency_factor = new_config.params.write_consistency_factor;
+            // Changed by shard splits
+            if config.params.shard_number != new_config.params.shard_number {
+                config.params.shard_number = new_config.params.shard_number;
+                config.save(&self.path)?;
+            }
         }
 
         self.recreate_optimizers_blocking().await?;
@@ -0,0 +1,361 @@
+//! Stored queries, evaluated against the points of incoming updates
+//!
+//! A stored query is a filter, a query vector with a score threshold, or both. Points of each
+//! upsert are checked against the stored queries of the collection, and every match is emitted
+//! into the collection event stream as a `stored_query_matched` event. This allows to be notified
+//! about new similar content without polling the collection with searches.
+//!
+//! Queries are evaluated once per client update, on the peer which received it.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use schemars::JsonSchema;
+use segment::data_types::vectors::{DenseVector, VectorRef, DEFAULT_VECTOR_NAME};
+use segment::index::field_index::FieldIndex;
+use segment::payload_storage::query_checker::{
+    check_field_condition, check_filter, check_is_empty_condition, check_is_null_condition,
+    check_payload,
+};
+use segment::types::{
+    Condition, Distance, Filter, OwnedPayloadRef, Payload, PayloadKeyType, ScoreType,
+};
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+use super::dimensionality_reduction::reduce;
+use super::stored_query_index::StoredQueryIndex;
+use crate::collection::Collection;
+use crate::events::CollectionEventKind;
+use crate::operations::point_ops::{PointInsertOperationsInternal, PointOperations, PointStruct};
+use crate::operations::types::{CollectionError, CollectionResult};
+use crate::operations::CollectionUpdateOperations;
+use crate::save_on_disk::SaveOnDisk;
+
+pub const STORED_QUERIES_FILE: &str = "stored_queries.json";
+
+/// Query, which is checked against every upserted point of the collection
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub struct StoredQuery {
+    /// Points must satisfy these conditions to match. If not set - payload is not checked
+    #[serde(default)]
+    #[validate]
+    pub filter: Option<Filter>,
+    /// Points must be similar to this vector to match. If not set - only the filter is checked
+    #[serde(default)]
+    pub vector: Option<DenseVector>,
+    /// Name of the vector to compare with. If not set - the default vector is used
+    #[serde(default)]
+    pub using: Option<String>,
+    /// Points must score better than this threshold to match. Required if `vector` is set
+    #[serde(default)]
+    pub score_threshold: Option<ScoreType>,
+}
+
+/// Stored queries of the collection by their names
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, Default, PartialEq)]
+pub struct StoredQueries {
+    pub queries: HashMap<String, StoredQuery>,
+}
+
+impl StoredQuery {
+    fn vector_name(&self) -> &str {
+        self.using.as_deref().unwrap_or(DEFAULT_VECTOR_NAME)
+    }
+
+    fn check_payload(&self, point: &PointStruct) -> bool {
+        let Some(filter) = &self.filter else {
+            return true;
+        };
+
+        let empty_payload = Payload::default();
+        let payload = point.payload.as_ref().unwrap_or(&empty_payload);
+        let no_indexes = HashMap::<PayloadKeyType, Vec<FieldIndex>>::new();
+
+        let checker = |condition: &Condition| match condition {
+            Condition::Field(field_condition) => {
+                check_field_condition(field_condition, payload, &no_indexes)
+            }
+            Condition::IsEmpty(is_empty) => check_is_empty_condition(is_empty, payload),
+            Condition::IsNull(is_null) => check_is_null_condition(is_null, payload),
+            Condition::HasId(has_id) => has_id.has_id.contains(&point.id),
+            Condition::Nested(_) => check_payload(
+                Box::new(|| OwnedPayloadRef::from(payload)),
+                None,
+                &Filter::new_must(condition.clone()),
+                0,
+                &no_indexes,
+            ),
+            // Nested filters are unfolded by `check_filter`
+            Condition::Filter(_) => unreachable!(),
+        };
+
+        check_filter(&checker, filter)
+    }
+
+    /// Score of the point against the query vector, `None` if the point has no such vector
+    fn score(&self, query: &[f32], distance: Distance, point: &PointStruct) -> Option<ScoreType> {
+        let Some(VectorRef::Dense(vector)) = point.vector.get(self.vector_name()) else {
+            return None;
+        };
+        if vector.len() != query.len() {
+            return None;
+        }
+        let vector = distance.preprocess_vector(vector.to_vec());
+        Some(distance.postprocess_score(distance.similarity(query, &vector)))
+    }
+}
+
+impl Collection {
+    pub(crate) fn stored_queries_file(collection_path: &Path) -> PathBuf {
+        collection_path.join(STORED_QUERIES_FILE)
+    }
+
+    pub(crate) fn load_stored_queries(
+        collection_path: &Path,
+    ) -> CollectionResult<SaveOnDisk<StoredQueries>> {
+        let stored_queries_file = Self::stored_queries_file(collection_path);
+        let stored_queries: SaveOnDisk<StoredQueries> =
+            SaveOnDisk::load_or_init(stored_queries_file)?;
+        Ok(stored_queries)
+    }
+
+    pub fn stored_queries(&self) -> StoredQueries {
+        self.stored_queries.read().clone()
+    }
+
+    /// Check that the query is applicable to the collection, before it is stored
+    pub async fn check_stored_query(&self, query: &StoredQuery) -> CollectionResult<()> {
+        let Some(vector) = &query.vector else {
+            if query.filter.is_none() {
+                return Err(CollectionError::bad_request(
+                    "Stored query must have a filter or a vector".to_string(),
+                ));
+            }
+            if query.using.is_some() || query.score_threshold.is_some() {
+                return Err(CollectionError::bad_request(
+                    "`using` and `score_threshold` of a stored query require a vector".to_string(),
+                ));
+            }
+            return Ok(());
+        };
+
+        if query.score_threshold.is_none() {
+            return Err(CollectionError::bad_request(
+                "Stored query with a vector must have a `score_threshold`".to_string(),
+            ));
+        }
+
+        let config = self.collection_config.read().await;
+        let vector_name = query.vector_name();
+        let params = config
+            .params
+            .vectors
+            .get_params(vector_name)
+            .ok_or_else(|| {
+                CollectionError::bad_request(format!(
+                    "Stored query vector {vector_name} is not a dense vector of the collection",
+                ))
+            })?;
+        let expected = params.size.get() as usize;
+        // Query vectors of the input size are reduced, the same way as the stored vectors
+        let input_size = params
+            .dimensionality_reduction
+            .as_ref()
+            .map(|reduction| reduction.input_size.get() as usize);
+        if vector.len() != expected && Some(vector.len()) != input_size {
+            return Err(CollectionError::bad_input(format!(
+                "Stored query vector dimension error: expected dim: {expected}, got {}",
+                vector.len(),
+            )));
+        }
+
+        Ok(())
+    }
+
+    pub fn set_stored_query(&self, name: String, query: StoredQuery) -> CollectionResult<()> {
+        self.update_stored_queries(|stored_queries| {
+            stored_queries.queries.insert(name, query);
+        })
+    }
+
+    pub fn delete_stored_query(&self, name: &str) -> CollectionResult<()> {
+        self.update_stored_queries(|stored_queries| {
+            stored_queries.queries.remove(name);
+        })
+    }
+
+    /// Change the stored queries and rebuild their index
+    pub(crate) fn update_stored_queries(
+        &self,
+        update: impl FnOnce(&mut StoredQueries),
+    ) -> CollectionResult<()> {
+        // Index lock is held during the update, so concurrent updates can't swap stale indexes
+        let mut index = self.stored_query_index.write();
+        self.stored_queries.write(update)?;
+        *index = Arc::new(StoredQueryIndex::build(&self.stored_queries.read()));
+        Ok(())
+    }
+
+    /// Points of the update, which have to be checked against the stored queries
+    ///
+    /// Returns `None` if there is nothing to check, or nobody listens to the matches.
+    pub(crate) fn stored_query_candidates(
+        &self,
+        operation: &CollectionUpdateOperations,
+    ) -> Option<Vec<PointStruct>> {
+        if !self.shared_storage_config.events.is_enabled()
+            || self.stored_queries.read().queries.is_empty()
+        {
+            return None;
+        }
+
+        match operation {
+            CollectionUpdateOperations::PointOperation(PointOperations::UpsertPoints(
+                PointInsertOperationsInternal::PointsList(points),
+            )) => Some(points.clone()),
+            CollectionUpdateOperations::PointOperation(PointOperations::UpsertPoints(
+                PointInsertOperationsInternal::PointsBatch(batch),
+            )) => Some(batch.clone().into_points()),
+            CollectionUpdateOperations::Batch(operations) => {
+                let points: Vec<_> = operations
+                    .iter()
+                    .filter_map(|operation| self.stored_query_candidates(operation))
+                    .flatten()
+                    .collect();
+                (!points.is_empty()).then_some(points)
+            }
+            _ => None,
+        }
+    }
+
+    /// Check upserted points against the stored queries and emit an event for every match
+    ///
+    /// Each point is only checked against the queries, which the index selects for its payload.
+    pub(crate) async fn notify_stored_queries(&self, points: &[PointStruct]) {
+        let config = self.collection_config.read().await;
+        let index = self.stored_query_index.read().clone();
+        let stored_queries = self.stored_queries.read();
+
+        // Query vectors are preprocessed once per update, the same way the collection stores
+        // vectors, and only for the queries, which are selected for any of the points
+        let mut query_vectors: HashMap<&str, Option<(DenseVector, Distance)>> = HashMap::new();
+        let preprocess = |query: &StoredQuery| {
+            let vector_name = query.vector_name();
+            let distance = config.params.get_distance(vector_name).ok()?;
+            let mut vector = query.vector.clone()?;
+            let reduction = config
+                .params
+                .vectors
+                .get_params(vector_name)
+                .and_then(|params| params.dimensionality_reduction.as_ref());
+            if let Some(reduction) = reduction {
+                // Not matched until the reduction is trained
+                reduce(&mut vector, reduction).ok()?;
+            }
+            Some((distance.preprocess_vector(vector), distance))
+        };
+
+        for point in points {
+            for name in index.candidates(point.payload.as_ref()) {
+                let Some((name, query)) = stored_queries.queries.get_key_value(name) else {
+                    continue;
+                };
+                if !query.check_payload(point) {
+                    continue;
+                }
+
+                let score = if query.vector.is_some() {
+                    let vector = query_vectors
+                        .entry(name.as_str())
+                        .or_insert_with(|| preprocess(query));
+                    // Query vector is not applicable to the collection anymore
+                    let Some((vector, distance)) = vector else {
+                        continue;
+                    };
+                    let Some(score) = query.score(vector, *distance, point) else {
+                        continue;
+                    };
+                    let threshold = query.score_threshold.unwrap_or_default();
+                    if !distance.check_threshold(score, threshold) {
+                        continue;
+                    }
+                    Some(score)
+                } else {
+                    None
+                };
+
+                self.shared_storage_config.events.notify(
+                    self.name(),
+                    None,
+                    CollectionEventKind::StoredQueryMatched {
+                        query: name.to_string(),
+                        point_id: point.id,
+                        score,
+                    },
+                );
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use segment::data_types::vectors::VectorStruct;
+    use serde_json::json;
+
+    use super::*;
+
+    fn point(payload: serde_json::Value) -> PointStruct {
+        PointStruct {
+            id: 1.into(),
+            vector: vec![1.0, 0.0].into(),
+            payload: Some(serde_json::from_value(payload).unwrap()),
+            if_version: None,
+        }
+    }
+
+    #[test]
+    fn test_check_payload() {
+        let query: StoredQuery = serde_json::from_value(json!({
+            "filter": {
+                "must": [{ "key": "lang", "match": { "value": "en" } }],
+                "must_not": [{ "has_id": [2] }],
+            }
+        }))
+        .unwrap();
+
+        assert!(query.check_payload(&point(json!({ "lang": "en" }))));
+        assert!(!query.check_payload(&point(json!({ "lang": "de" }))));
+
+        let mut excluded = point(json!({ "lang": "en" }));
+        excluded.id = 2.into();
+        assert!(!query.check_payload(&excluded));
+    }
+
+    #[test]
+    fn test_score() {
+        let query = StoredQuery {
+            filter: None,
+            vector: Some(vec![1.0, 1.0]),
+            using: None,
+            score_threshold: Some(0.5),
+        };
+        let distance = Distance::Cosine;
+        let vector = distance.preprocess_vector(vec![1.0, 1.0]);
+
+        let score = query.score(&vector, distance, &point(json!({}))).unwrap();
+        assert!((score - std::f32::consts::FRAC_1_SQRT_2).abs() < 1e-5);
+        assert!(distance.check_threshold(score, 0.5));
+
+        // Points without the vector never match
+        let mut named = point(json!({}));
+        named.vector = VectorStruct::Multi(HashMap::from([(
+            "image".to_string(),
+            vec![1.0, 1.0].into(),
+        )]));
+        assert_eq!(query.score(&vector, distance, &named), None);
+    }
+}
@@ -0,0 +1,217 @@
+//! Index of the stored queries by the payload values, which points must have to match them
+//!
+//! Evaluating every stored query against every upserted point is linear in the number of
+//! queries. Instead, filters of the queries are decomposed into their `must` conditions, and one
+//! exact match condition of a keyword or an integer is picked as the anchor of the query. A point
+//! can only match the query if its payload has one of the anchor values, so only queries anchored
+//! by the values of the point, and the queries without an anchor, are evaluated.
+
+use std::collections::{HashMap, HashSet};
+
+use segment::types::{
+    AnyVariants, Condition, FieldCondition, Filter, IntPayloadType, Match, MatchAny, MatchValue,
+    Payload, PayloadContainer, PayloadKeyType, ValueVariants,
+};
+use serde_json::Value;
+
+use super::stored_queries::StoredQueries;
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum AnchorValue {
+    Keyword(String),
+    Integer(IntPayloadType),
+}
+
+impl AnchorValue {
+    /// Values of the payload, which could be matched by an anchor
+    ///
+    /// Arrays are flattened one level deep, the same way as conditions are checked.
+    fn from_payload(value: &Value, values: &mut Vec<AnchorValue>) {
+        match value {
+            Value::String(keyword) => values.push(AnchorValue::Keyword(keyword.clone())),
+            Value::Number(number) => {
+                if let Some(integer) = number.as_i64() {
+                    values.push(AnchorValue::Integer(integer));
+                }
+            }
+            Value::Array(array) => {
+                for value in array {
+                    if !value.is_array() {
+                        AnchorValue::from_payload(value, values);
+                    }
+                }
+            }
+            Value::Null | Value::Bool(_) | Value::Object(_) => {}
+        }
+    }
+}
+
+/// Exact match condition, which every matching point satisfies
+struct Anchor<'a> {
+    key: &'a PayloadKeyType,
+    values: Vec<AnchorValue>,
+}
+
+#[derive(Debug, Default)]
+pub(crate) struct StoredQueryIndex {
+    /// Names of the anchored queries, by the anchor field and value
+    anchored: HashMap<PayloadKeyType, HashMap<AnchorValue, Vec<String>>>,
+    /// Names of the queries without an anchor, evaluated for every point
+    unanchored: Vec<String>,
+}
+
+impl StoredQueryIndex {
+    pub fn build(stored_queries: &StoredQueries) -> Self {
+        let mut index = Self::default();
+        for (name, query) in &stored_queries.queries {
+            let Some(anchor) = query.filter.as_ref().and_then(find_anchor) else {
+                index.unanchored.push(name.clone());
+                continue;
+            };
+            let by_value = index.anchored.entry(anchor.key.clone()).or_default();
+            for value in anchor.values {
+                by_value.entry(value).or_default().push(name.clone());
+            }
+        }
+        index
+    }
+
+    /// Names of the queries, which the point with the given payload might match
+    pub fn candidates(&self, payload: Option<&Payload>) -> HashSet<&str> {
+        let mut candidates: HashSet<&str> = self.unanchored.iter().map(String::as_str).collect();
+        let Some(payload) = payload else {
+            return candidates;
+        };
+
+        let mut values = Vec::new();
+        for (key, by_value) in &self.anchored {
+            values.clear();
+            for value in payload.get_value(key) {
+                AnchorValue::from_payload(value, &mut values);
+            }
+            for value in &values {
+                if let Some(names) = by_value.get(value) {
+                    candidates.extend(names.iter().map(String::as_str));
+                }
+            }
+        }
+        candidates
+    }
+}
+
+/// The most selective exact match condition among the required conditions of the filter
+fn find_anchor(filter: &Filter) -> Option<Anchor> {
+    filter
+        .must
+        .iter()
+        .flatten()
+        .filter_map(|condition| match condition {
+            Condition::Field(field_condition) => anchor_of_condition(field_condition),
+            // Conditions of a nested filter in `must` are required as well
+            Condition::Filter(filter) => find_anchor(filter),
+            _ => None,
+        })
+        .min_by_key(|anchor| anchor.values.len())
+}
+
+fn anchor_of_condition(condition: &FieldCondition) -> Option<Anchor> {
+    // Field condition is satisfied by any of its checks, so only a lone match is required
+    let FieldCondition {
+        key,
+        r#match: Some(r#match),
+        range: None,
+        geo_bounding_box: None,
+        geo_radius: None,
+        geo_polygon: None,
+        values_count: None,
+    } = condition
+    else {
+        return None;
+    };
+
+    let values = match r#match {
+        Match::Value(MatchValue {
+            value: ValueVariants::Keyword(keyword),
+        }) => vec![AnchorValue::Keyword(keyword.clone())],
+        Match::Value(MatchValue {
+            value: ValueVariants::Integer(integer),
+        }) => vec![AnchorValue::Integer(*integer)],
+        Match::Any(MatchAny {
+            any: AnyVariants::Keywords(keywords),
+        }) => keywords.iter().cloned().map(AnchorValue::Keyword).collect(),
+        Match::Any(MatchAny {
+            any: AnyVariants::Integers(integers),
+        }) => integers.iter().copied().map(AnchorValue::Integer).collect(),
+        // Booleans are not selective, other matches are not exact
+        Match::Value(MatchValue {
+            value: ValueVariants::Bool(_),
+        })
+        | Match::Text(_)
+        | Match::Except(_) => return None,
+    };
+    Some(Anchor { key, values })
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn index(queries: serde_json::Value) -> StoredQueryIndex {
+        StoredQueryIndex::build(&serde_json::from_value(json!({ "queries": queries })).unwrap())
+    }
+
+    fn candidates(index: &StoredQueryIndex, payload: serde_json::Value) -> Vec<&str> {
+        let payload: Payload = serde_json::from_value(payload).unwrap();
+        let mut candidates: Vec<_> = index.candidates(Some(&payload)).into_iter().collect();
+        candidates.sort();
+        candidates
+    }
+
+    #[test]
+    fn test_stored_query_index() {
+        let index = index(json!({
+            "en": { "filter": { "must": [{ "key": "lang", "match": { "value": "en" } }] } },
+            "de_or_fr": {
+                "filter": { "must": [{ "key": "lang", "match": { "any": ["de", "fr"] } }] }
+            },
+            // The single value is more selective than the list
+            "user": {
+                "filter": {
+                    "must": [
+                        { "key": "lang", "match": { "any": ["en", "de"] } },
+                        { "must": [{ "key": "user", "match": { "value": 42 } }] },
+                    ]
+                }
+            },
+            // Only `should` conditions, not anchored
+            "any": { "filter": { "should": [{ "key": "lang", "match": { "value": "en" } }] } },
+            // Matches by range as well, not anchored
+            "range": {
+                "filter": {
+                    "must": [{ "key": "lang", "match": { "value": "en" }, "range": { "gt": 1 } }]
+                }
+            },
+            "vector": { "vector": [1.0, 0.0], "score_threshold": 0.5 },
+        }));
+
+        assert_eq!(
+            candidates(&index, json!({ "lang": "en" })),
+            vec!["any", "en", "range", "vector"],
+        );
+        assert_eq!(
+            candidates(&index, json!({ "lang": ["fr", "de"], "user": 42 })),
+            vec!["any", "de_or_fr", "range", "user", "vector"],
+        );
+        assert_eq!(
+            candidates(&index, json!({ "user": [[42]] })),
+            vec!["any", "range", "vector"],
+        );
+        assert_eq!(
+            index.candidates(None).len(),
+            3,
+            "points without payload are only checked against unanchored queries",
+        );
+    }
+}
@@ -0,0 +1,331 @@
+//! Usage metering of the tenants of the collection
+//!
+//! Tenants of a shared collection are identified by their shard keys. Requests are metered by the
+//! shard keys they select: their number and the time spent serving them, which is the resource
+//! usage of the tenant. Time of a request, which selects several shard keys, is split evenly
+//! between them. Requests without a shard key, or to all of them, are metered without a tenant.
+//!
+//! Metering is local to the peer: requests are metered by the peer, which received them from the
+//! client, and storage usage is reported for the local shards only. Counters are kept in memory,
+//! periodic rollups close the current period and keep the usage of the last periods.
+
+use std::collections::{HashMap, VecDeque};
+use std::time::{Duration, Instant};
+
+use parking_lot::Mutex;
+use schemars::JsonSchema;
+use segment::types::ShardKey;
+use serde::{Deserialize, Serialize};
+
+use crate::collection::Collection;
+use crate::operations::shard_selector_internal::ShardSelectorInternal;
+use crate::operations::types::CollectionResult;
+use crate::shards::time_travel::now_millis;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RequestKind {
+    /// Searches, recommendations, discovery and grouping
+    Search,
+    /// Retrieval, scroll and count of points
+    Read,
+    /// Changes of points, vectors and payloads
+    Update,
+}
+
+/// Number of requests and the time spent serving them
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RequestCounters {
+    pub count: u64,
+    pub duration_micros: u64,
+}
+
+impl RequestCounters {
+    fn add(&mut self, other: RequestCounters) {
+        self.count += other.count;
+        self.duration_micros += other.duration_micros;
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RequestUsage {
+    pub search: RequestCounters,
+    pub read: RequestCounters,
+    pub update: RequestCounters,
+}
+
+impl RequestUsage {
+    fn counters_mut(&mut self, kind: RequestKind) -> &mut RequestCounters {
+        match kind {
+            RequestKind::Search => &mut self.search,
+            RequestKind::Read => &mut self.read,
+            RequestKind::Update => &mut self.update,
+        }
+    }
+}
+
+/// Usage of a tenant of the collection on this peer
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, Default, PartialEq)]
+pub struct TenantUsage {
+    /// Shard key of the tenant. Not set for the requests and shards without a shard key
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub shard_key: Option<ShardKey>,
+    /// Number of points in the local shards of the tenant
+    pub points_count: usize,
+    /// Approximate size of the local shards of the tenant on disk, in bytes
+    pub disk_usage_bytes: usize,
+    /// Requests of the tenant, received by this peer during the period
+    pub requests: RequestUsage,
+}
+
+/// Usage of the tenants during a closed period
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq)]
+pub struct UsageRollup {
+    /// Start of the period, unix timestamp in seconds
+    pub start: u64,
+    /// End of the period, unix timestamp in seconds. Storage usage is measured at the end
+    pub end: u64,
+    pub tenants: Vec<TenantUsage>,
+}
+
+/// Usage of the tenants of the collection on this peer
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq)]
+pub struct CollectionUsage {
+    /// Start of the current period, unix timestamp in seconds
+    pub period_start: u64,
+    /// Usage of the tenants during the current period, storage usage is measured now
+    pub tenants: Vec<TenantUsage>,
+    /// Usage during the last closed periods, oldest first
+    pub rollups: Vec<UsageRollup>,
+}
+
+#[derive(Debug)]
+struct MeterState {
+    period_start: u64,
+    requests: HashMap<Option<ShardKey>, RequestUsage>,
+    rollups: VecDeque<UsageRollup>,
+}
+
+#[derive(Debug)]
+pub struct UsageMeter {
+    state: Mutex<MeterState>,
+}
+
+impl Default for UsageMeter {
+    fn default() -> Self {
+        Self {
+            state: Mutex::new(MeterState {
+                period_start: now_millis() / 1000,
+                requests: HashMap::new(),
+                rollups: VecDeque::new(),
+            }),
+        }
+    }
+}
+
+impl UsageMeter {
+    /// Record one request for each of the tenants, with the time split between them
+    fn record(&self, kind: RequestKind, tenants: &[Option<ShardKey>], duration: Duration) {
+        if tenants.is_empty() {
+            return;
+        }
+        let duration_micros = duration.as_micros() as u64 / tenants.len() as u64;
+        let mut state = self.state.lock();
+        for tenant in tenants {
+            state
+                .requests
+                .entry(tenant.clone())
+                .or_default()
+                .counters_mut(kind)
+                .add(RequestCounters {
+                    count: 1,
+                    duration_micros,
+                });
+        }
+    }
+
+    /// Requests of the current period, joined with the storage usage of the tenants
+    fn current(&self, storage: HashMap<Option<ShardKey>, (usize, usize)>) -> CollectionUsage {
+        let state = self.state.lock();
+        CollectionUsage {
+            period_start: state.period_start,
+            tenants: tenant_usage(state.requests.clone(), storage),
+            rollups: state.rollups.iter().cloned().collect(),
+        }
+    }
+
+    /// Close the current period, only the last `rollups_to_keep` periods are kept
+    fn rollup(&self, storage: HashMap<Option<ShardKey>, (usize, usize)>, rollups_to_keep: usize) {
+        let mut state = self.state.lock();
+        let end = now_millis() / 1000;
+        let requests = std::mem::take(&mut state.requests);
+        let rollup = UsageRollup {
+            start: state.period_start,
+            end,
+            tenants: tenant_usage(requests, storage),
+        };
+        state.period_start = end;
+        state.rollups.push_back(rollup);
+        while state.rollups.len() > rollups_to_keep {
+            state.rollups.pop_front();
+        }
+    }
+}
+
+/// Usage of each tenant, which has either requests or local shards, ordered by shard key
+fn tenant_usage(
+    requests: HashMap<Option<ShardKey>, RequestUsage>,
+    mut storage: HashMap<Option<ShardKey>, (usize, usize)>,
+) -> Vec<TenantUsage> {
+    let mut tenants: Vec<_> = requests
+        .into_iter()
+        .map(|(shard_key, requests)| {
+            let (points_count, disk_usage_bytes) = storage.remove(&shard_key).unwrap_or_default();
+            TenantUsage {
+                shard_key,
+                points_count,
+                disk_usage_bytes,
+                requests,
+            }
+        })
+        .collect();
+    tenants.extend(
+        storage.into_iter().map(
+            |(shard_key, (points_count, disk_usage_bytes))| TenantUsage {
+                shard_key,
+                points_count,
+                disk_usage_bytes,
+                requests: RequestUsage::default(),
+            },
+        ),
+    );
+    tenants.sort_by_cached_key(|tenant| tenant.shard_key.as_ref().map(ToString::to_string));
+    tenants
+}
+
+/// Records the time of the metered requests, once they are served
+pub struct UsageMeasurer<'a> {
+    meter: &'a UsageMeter,
+    kind: RequestKind,
+    tenants: Vec<Option<ShardKey>>,
+    start: Instant,
+}
+
+impl Drop for UsageMeasurer<'_> {
+    fn drop(&mut self) {
+        self.meter
+            .record(self.kind, &self.tenants, self.start.elapsed());
+    }
+}
+
+/// Tenants of the request, internal requests to a shard of this peer are not metered
+fn tenants_of(shard_selection: &ShardSelectorInternal) -> Vec<Option<ShardKey>> {
+    match shard_selection {
+        ShardSelectorInternal::Empty | ShardSelectorInternal::All => vec![None],
+        ShardSelectorInternal::ShardKey(shard_key) => vec![Some(shard_key.clone())],
+        ShardSelectorInternal::ShardKeys(shard_keys) => {
+            shard_keys.iter().cloned().map(Some).collect()
+        }
+        ShardSelectorInternal::ShardId(_) => vec![],
+    }
+}
+
+impl Collection {
+    /// Meter the requests with the given shard selections, until the returned measurer is dropped
+    ///
+    /// Each selection is a separate request of a batch.
+    pub fn meter_requests<'a>(
+        &self,
+        kind: RequestKind,
+        shard_selections: impl IntoIterator<Item = &'a ShardSelectorInternal>,
+    ) -> UsageMeasurer<'_> {
+        UsageMeasurer {
+            meter: &self.usage_meter,
+            kind,
+            tenants: shard_selections.into_iter().flat_map(tenants_of).collect(),
+            start: Instant::now(),
+        }
+    }
+
+    /// Number of points and disk usage of the local shards, by shard key
+    async fn local_storage_usage(
+        &self,
+    ) -> CollectionResult<HashMap<Option<ShardKey>, (usize, usize)>> {
+        let shards_holder = self.shards_holder.read().await;
+        let shard_keys = shards_holder.get_shard_id_to_key_mapping();
+        let mut storage: HashMap<Option<ShardKey>, (usize, usize)> = HashMap::new();
+        for (shard_id, replica_set) in shards_holder.get_shards() {
+            if !replica_set.has_local_shard().await {
+                continue;
+            }
+            let info = replica_set.info(true).await?;
+            let usage = storage
+                .entry(shard_keys.get(shard_id).cloned())
+                .or_default();
+            usage.0 += info.points_count.unwrap_or_default();
+            usage.1 += info.disk_usage_bytes.unwrap_or_default();
+        }
+        Ok(storage)
+    }
+
+    pub async fn usage(&self) -> CollectionResult<CollectionUsage> {
+        let storage = self.local_storage_usage().await?;
+        Ok(self.usage_meter.current(storage))
+    }
+
+    /// Close the current metering period, keeping the last `rollups_to_keep` periods
+    pub async fn rollup_usage(&self, rollups_to_keep: usize) -> CollectionResult<()> {
+        let storage = self.local_storage_usage().await?;
+        self.usage_meter.rollup(storage, rollups_to_keep);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_usage_meter() {
+        let meter = UsageMeter::default();
+        let tenant = |key: &str| Some(ShardKey::from(key));
+
+        let batch = [
+            ShardSelectorInternal::ShardKey("a".into()),
+            ShardSelectorInternal::ShardKeys(vec!["a".into(), "b".into()]),
+            ShardSelectorInternal::ShardId(0),
+        ];
+        let tenants: Vec<_> = batch.iter().flat_map(tenants_of).collect();
+        assert_eq!(tenants, vec![tenant("a"), tenant("a"), tenant("b")]);
+        meter.record(RequestKind::Search, &tenants, Duration::from_micros(300));
+        meter.record(RequestKind::Update, &[None], Duration::from_micros(50));
+
+        let storage = HashMap::from([(tenant("a"), (10, 1000)), (tenant("c"), (5, 500))]);
+        let usage = meter.current(storage.clone());
+        let tenants: Vec<_> = usage
+            .tenants
+            .iter()
+            .map(|tenant| tenant.shard_key.clone())
+            .collect();
+        assert_eq!(tenants, vec![None, tenant("a"), tenant("b"), tenant("c")]);
+        assert_eq!(usage.tenants[0].requests.update.count, 1);
+        assert_eq!(
+            usage.tenants[1].requests.search,
+            RequestCounters {
+                count: 2,
+                duration_micros: 200,
+            },
+        );
+        assert_eq!(usage.tenants[1].points_count, 10);
+        assert_eq!(usage.tenants[3].requests, RequestUsage::default());
+
+        meter.rollup(storage.clone(), 1);
+        meter.rollup(storage, 1);
+        let usage = meter.current(HashMap::new());
+        assert!(usage.tenants.is_empty());
+        assert_eq!(usage.rollups.len(), 1);
+        assert!(usage.rollups[0]
+            .tenants
+            .iter()
+            .all(|tenant| tenant.requests == RequestUsage::default()));
+    }
+}
@@ -0,0 +1,72 @@
+use std::collections::HashMap;
+use std::path::Path;
+
+use segment::types::SeqNumberType;
+
+use super::Collection;
+use crate::operations::types::CollectionResult;
+use crate::shards::shard::ShardId;
+use crate::shards::wal_archive::WalArchiveResult;
+
+impl Collection {
+    /// Archive operations of the local shards, which were written since the previous call
+    ///
+    /// Operations of each shard are stored in a separate `archive_path/{shard_id}` directory.
+    /// The first call enables archiving: WAL of the shards is not truncated past the archived
+    /// operations, until [`Self::stop_wal_archiving`] is called.
+    pub async fn archive_wal(&self, archive_path: &Path) -> CollectionResult<WalArchiveResult> {
+        let shard_holder = self.shards_holder.read().await;
+        let mut result = WalArchiveResult::default();
+        for replica_set in shard_holder.all_shards() {
+            let shard_archive_path = archive_path.join(replica_set.shard_id.to_string());
+            result += replica_set.archive_wal(&shard_archive_path).await?;
+        }
+        log::debug!(
+            "Archived {} WAL operations of collection {}",
+            result.operations,
+            self.id,
+        );
+        Ok(result)
+    }
+
+    pub async fn stop_wal_archiving(&self) -> CollectionResult<()> {
+        let shard_holder = self.shards_holder.read().await;
+        for replica_set in shard_holder.all_shards() {
+            replica_set.stop_wal_archiving().await?;
+        }
+        Ok(())
+    }
+
+    /// Apply archived operations on top of the local shards, e.g. right after the collection is
+    /// recovered from a snapshot
+    ///
+    /// Operations, which are already in the shards, are skipped. Operations are numbered by each
+    /// shard independently, so the point in time to recover to is given per shard in
+    /// `until_versions`: operations after it are not applied. Shards, which are not listed, are
+    /// recovered up to the last archived operation.
+    ///
+    /// Returns number of the applied operations.
+    pub async fn replay_wal_archive(
+        &self,
+        archive_path: &Path,
+        until_versions: &HashMap<ShardId, SeqNumberType>,
+    ) -> CollectionResult<usize> {
+        let shard_holder = self.shards_holder.read().await;
+        let mut replayed = 0;
+        for replica_set in shard_holder.all_shards() {
+            let shard_archive_path = archive_path.join(replica_set.shard_id.to_string());
+            if !shard_archive_path.exists() {
+                continue;
+            }
+            let until_version = until_versions.get(&replica_set.shard_id).copied();
+            replayed += replica_set
+                .replay_wal_archive(&shard_archive_path, until_version)
+                .await?;
+        }
+        log::info!(
+            "Replayed {replayed} archived WAL operations of collection {}",
+            self.id,
+        );
+        Ok(replayed)
+    }
+}
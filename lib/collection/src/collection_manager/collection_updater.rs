@@ -49,7 +49,40 @@ impl CollectionUpdater {
     ) -> CollectionResult<usize> {
         // Allow only one update at a time, ensure no data races between segments.
         // let _lock = self.update_lock.lock().unwrap();
-        let operation_result = match operation {
+        let operation_result = Self::process_operation(segments, op_num, operation);
+
+        CollectionUpdater::handle_update_result(segments, op_num, &operation_result);
+        // Declined operations are applied as well, they don't change the data
+        segments
+            .read()
+            .update_tracker()
+            .set_applied_operation(op_num);
+
+        operation_result
+    }
+
+    fn process_operation(
+        segments: &RwLock<SegmentHolder>,
+        op_num: SeqNumberType,
+        operation: CollectionUpdateOperations,
+    ) -> CollectionResult<usize> {
+        match operation {
+            CollectionUpdateOperations::Batch(operations) => {
+                // Segments can't be swapped by the optimizer while the batch is applied,
+                // so the backup taken before the batch is restored into the same segments
+                let segments = segments.upgradable_read();
+                Self::process_batch(&segments, op_num, operations)
+            }
+            operation => Self::process_single_operation(&segments.read(), op_num, operation),
+        }
+    }
+
+    fn process_single_operation(
+        segments: &SegmentHolder,
+        op_num: SeqNumberType,
+        operation: CollectionUpdateOperations,
+    ) -> CollectionResult<usize> {
+        match operation {
             CollectionUpdateOperations::PointOperation(point_operation) => {
                 process_point_operation(segments, op_num, point_operation)
             }
@@ -62,18 +95,47 @@ impl CollectionUpdater {
             CollectionUpdateOperations::FieldIndexOperation(index_operation) => {
                 process_field_index_operation(segments, op_num, &index_operation)
             }
-        };
-
-        CollectionUpdater::handle_update_result(segments, op_num, &operation_result);
+            CollectionUpdateOperations::Batch(operations) => {
+                Self::process_batch(segments, op_num, operations)
+            }
+        }
+    }
 
-        operation_result
+    /// Batch is applied as a whole, points are restored if any of its operations fails
+    ///
+    /// Reads are not blocked by the batch, so they might see it partially applied.
+    fn process_batch(
+        segments: &SegmentHolder,
+        op_num: SeqNumberType,
+        operations: Vec<CollectionUpdateOperations>,
+    ) -> CollectionResult<usize> {
+        let backup = PointsBackup::new(segments, op_num, &operations)?;
+
+        // All operations of the batch share the operation number
+        segments.apply_batch(backup.applied_points().clone(), || {
+            let mut updated = 0;
+            for operation in operations {
+                match Self::process_single_operation(segments, op_num, operation) {
+                    Ok(count) => updated += count,
+                    Err(err) => {
+                        if let Err(restore_err) = backup.restore(segments, op_num) {
+                            log::error!(
+                                "Failed to roll back batch operation {op_num} after error: {err}, restore error: {restore_err}",
+                            );
+                        }
+                        return Err(err);
+                    }
+                }
+            }
+            Ok(updated)
+        })
     }
 }
 
 #[cfg(test)]
 mod tests {
     use segment::data_types::vectors::{only_default_vector, DEFAULT_VECTOR_NAME};
-    use segment::types::{Payload, WithPayload};
+    use segment::types::{Condition, FieldCondition, Filter, Payload, WithPayload};
     use serde_json::json;
     use tempfile::Builder;
 
@@ -82,7 +144,10 @@ mod tests {
     use crate::collection_manager::segments_searcher::SegmentsSearcher;
     use crate::collection_manager::segments_updater::upsert_points;
     use crate::operations::payload_ops::{DeletePayloadOp, PayloadOps, SetPayloadOp};
-    use crate::operations::point_ops::{PointOperations, PointStruct};
+    use crate::operations::point_ops::{
+        PointInsertOperationsInternal, PointOperations, PointStruct,
+    };
+    use crate::operations::types::CollectionError;
 
     #[test]
     fn test_sync_ops() {
@@ -99,26 +164,31 @@ mod tests {
                 id: 11.into(),
                 vector: vec11.into(),
                 payload: None,
+                if_version: None,
             },
             PointStruct {
                 id: 12.into(),
                 vector: vec12.into(),
                 payload: None,
+                if_version: None,
             },
             PointStruct {
                 id: 13.into(),
                 vector: vec13.into(),
                 payload: Some(json!({ "color": "red" }).into()),
+                if_version: None,
             },
             PointStruct {
                 id: 14.into(),
                 vector: vec![0., 0., 0., 0.].into(),
                 payload: None,
+                if_version: None,
             },
             PointStruct {
                 id: 500.into(),
                 vector: vec![2., 0., 2., 0.].into(),
                 payload: None,
+                if_version: None,
             },
         ];
 
@@ -141,11 +211,13 @@ mod tests {
                 id: 1.into(),
                 vector: vec![2., 2., 2., 2.].into(),
                 payload: None,
+                if_version: None,
             },
             PointStruct {
                 id: 500.into(),
                 vector: vec![2., 0., 2., 0.].into(),
                 payload: None,
+                if_version: None,
             },
         ];
 
@@ -176,7 +248,7 @@ mod tests {
         }
 
         process_point_operation(
-            &segments,
+            &segments.read(),
             101,
             PointOperations::DeletePoints {
                 ids: vec![500.into()],
@@ -198,6 +270,171 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_batch_ops() {
+        let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+        let segments = build_test_holder(dir.path());
+
+        let set_payload = |payload: serde_json::Value| {
+            CollectionUpdateOperations::PayloadOperation(PayloadOps::SetPayload(SetPayloadOp {
+                payload: payload.into(),
+                points: Some(vec![1.into()]),
+                filter: None,
+                if_version: None,
+            }))
+        };
+
+        // Operations of the batch share the operation number, and all of them are applied
+        let batch = CollectionUpdateOperations::Batch(vec![
+            set_payload(json!({ "color": "red" })),
+            set_payload(json!({ "size": "big" })),
+        ]);
+        CollectionUpdater::update(&segments, 100, batch).unwrap();
+
+        let res = SegmentsSearcher::retrieve(
+            &segments,
+            &[1.into()],
+            &WithPayload::from(true),
+            &false.into(),
+        )
+        .unwrap();
+        let payload = res[0].payload.as_ref().unwrap();
+        assert!(payload.contains_key("color"));
+        assert!(payload.contains_key("size"));
+        assert_eq!(res[0].version, Some(100));
+    }
+
+    #[test]
+    fn test_failed_batch_is_rolled_back() {
+        let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+        let segments = build_test_holder(dir.path());
+
+        let ids = [1.into(), 2.into(), 3.into(), 600.into()];
+        let retrieve_all = || {
+            let mut records =
+                SegmentsSearcher::retrieve(&segments, &ids, &WithPayload::from(true), &true.into())
+                    .unwrap();
+            records.sort_by_key(|record| record.id);
+            records
+        };
+        let before = retrieve_all();
+        assert_eq!(before.len(), 3);
+
+        let set_payload = |id: u64| {
+            CollectionUpdateOperations::PayloadOperation(PayloadOps::SetPayload(SetPayloadOp {
+                payload: json!({ "size": "big" }).into(),
+                points: Some(vec![id.into()]),
+                filter: None,
+                if_version: None,
+            }))
+        };
+        let upsert = CollectionUpdateOperations::PointOperation(PointOperations::UpsertPoints(
+            PointInsertOperationsInternal::PointsList(vec![
+                PointStruct {
+                    id: 2.into(),
+                    vector: vec![0., 0., 0., 0.].into(),
+                    payload: None,
+                    if_version: None,
+                },
+                PointStruct {
+                    id: 600.into(),
+                    vector: vec![1., 1., 1., 1.].into(),
+                    payload: None,
+                    if_version: None,
+                },
+            ]),
+        ));
+        let clear_blue = CollectionUpdateOperations::PayloadOperation(
+            PayloadOps::ClearPayloadByFilter(Filter::new_must(Condition::Field(
+                FieldCondition::new_match("color".to_string(), "blue".to_string().into()),
+            ))),
+        );
+        let delete = CollectionUpdateOperations::PointOperation(PointOperations::DeletePoints {
+            ids: vec![3.into()],
+        });
+
+        // Last operation fails, as the point doesn't exist
+        let batch = CollectionUpdateOperations::Batch(vec![
+            set_payload(1),
+            upsert,
+            clear_blue,
+            delete,
+            set_payload(1000),
+        ]);
+        let err = CollectionUpdater::update(&segments, 100, batch).unwrap_err();
+        assert!(
+            matches!(err, CollectionError::PointNotFound { .. }),
+            "{err}"
+        );
+
+        // Points are the same as before the batch, new point is not inserted
+        assert_eq!(retrieve_all(), before);
+    }
+
+    #[test]
+    fn test_batch_is_not_reapplied() {
+        let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+        let segments = build_test_holder(dir.path());
+
+        let overwrite_payload = |payload: serde_json::Value| {
+            CollectionUpdateOperations::PayloadOperation(PayloadOps::OverwritePayload(
+                SetPayloadOp {
+                    payload: payload.into(),
+                    points: Some(vec![1.into()]),
+                    filter: None,
+                    if_version: None,
+                },
+            ))
+        };
+
+        let batch = CollectionUpdateOperations::Batch(vec![
+            overwrite_payload(json!({ "color": "red" })),
+            overwrite_payload(json!({ "size": "big" })),
+        ]);
+        CollectionUpdater::update(&segments, 100, batch).unwrap();
+
+        // Batch with the same operation number, e.g. on WAL recovery, is already applied
+        let batch =
+            CollectionUpdateOperations::Batch(vec![overwrite_payload(json!({ "color": "blue" }))]);
+        CollectionUpdater::update(&segments, 100, batch).unwrap();
+
+        let res = SegmentsSearcher::retrieve(
+            &segments,
+            &[1.into()],
+            &WithPayload::from(true),
+            &false.into(),
+        )
+        .unwrap();
+        let expected: Payload = json!({ "size": "big" }).into();
+        assert_eq!(res[0].payload.as_ref(), Some(&expected));
+    }
+
+    #[test]
+    fn test_batch_too_large() {
+        let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+        let segments = build_test_holder(dir.path());
+
+        let delete = CollectionUpdateOperations::PointOperation(PointOperations::DeletePoints {
+            ids: (0..=10_000).map(|id: u64| id.into()).collect(),
+        });
+        let batch = CollectionUpdateOperations::Batch(vec![delete]);
+        let err = CollectionUpdater::update(&segments, 100, batch).unwrap_err();
+        assert!(
+            matches!(err, CollectionError::BatchTooLarge { max_points: 10_000 }),
+            "{err}"
+        );
+
+        // Nothing is deleted
+        let res = SegmentsSearcher::retrieve(
+            &segments,
+            &[1.into()],
+            &WithPayload::from(false),
+            &false.into(),
+        )
+        .unwrap();
+        assert_eq!(res.len(), 1);
+    }
+
     #[test]
     fn test_payload_ops() {
         let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
@@ -208,12 +445,13 @@ mod tests {
         let points = vec![1.into(), 2.into(), 3.into()];
 
         process_payload_operation(
-            &segments,
+            &segments.read(),
             100,
             PayloadOps::SetPayload(SetPayloadOp {
                 payload,
                 points: Some(points.clone()),
                 filter: None,
+                if_version: None,
             }),
         )
         .unwrap();
@@ -236,7 +474,7 @@ mod tests {
 
         // Test payload delete
         process_payload_operation(
-            &segments,
+            &segments.read(),
             101,
             PayloadOps::DeletePayload(DeletePayloadOp {
                 points: Some(vec![3.into()]),
@@ -269,7 +507,7 @@ mod tests {
         assert!(res[0].payload.as_ref().unwrap().contains_key("color"));
 
         process_payload_operation(
-            &segments,
+            &segments.read(),
             102,
             PayloadOps::ClearPayload {
                 points: vec![2.into()],
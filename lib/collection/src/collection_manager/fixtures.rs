@@ -19,6 +19,7 @@ use serde_json::json;
 use tempfile::Builder;
 
 use crate::collection_manager::holders::segment_holder::{LockedSegment, SegmentHolder};
+use crate::collection_manager::optimizers::config_mismatch_optimizer::ConfigMismatchOptimizer;
 use crate::collection_manager::optimizers::indexing_optimizer::IndexingOptimizer;
 use crate::collection_manager::optimizers::merge_optimizer::MergeOptimizer;
 use crate::collection_manager::optimizers::segment_optimizer::{
@@ -218,6 +219,10 @@ pub(crate) fn get_merge_optimizer(
                 hnsw_config: None,
                 quantization_config: None,
                 on_disk: None,
+                normalize: None,
+                dimensionality_reduction: None,
+                multivector_config: None,
+                datatype: None,
             }),
             ..CollectionParams::empty()
         },
@@ -246,6 +251,10 @@ pub(crate) fn get_indexing_optimizer(
                 hnsw_config: None,
                 quantization_config: None,
                 on_disk: None,
+                normalize: None,
+                dimensionality_reduction: None,
+                multivector_config: None,
+                datatype: None,
             }),
             ..CollectionParams::empty()
         },
@@ -254,6 +263,43 @@ pub(crate) fn get_indexing_optimizer(
     )
 }
 
+/// Config mismatch optimizer, which moves payload of all segments built by [`random_segment`]
+/// to disk
+pub(crate) fn get_config_mismatch_optimizer(
+    segment_path: &Path,
+    collection_temp_dir: &Path,
+    dim: usize,
+    max_rebuild_segments: Option<usize>,
+) -> ConfigMismatchOptimizer {
+    ConfigMismatchOptimizer::new(
+        max_rebuild_segments,
+        OptimizerThresholds {
+            max_segment_size: 100_000,
+            memmap_threshold: 1000000,
+            indexing_threshold: 1000000,
+        },
+        segment_path.to_owned(),
+        collection_temp_dir.to_owned(),
+        CollectionParams {
+            vectors: VectorsConfig::Single(VectorParams {
+                size: NonZeroU64::new(dim as u64).unwrap(),
+                distance: Distance::Dot,
+                hnsw_config: None,
+                quantization_config: None,
+                on_disk: None,
+                normalize: None,
+                dimensionality_reduction: None,
+                multivector_config: None,
+                datatype: None,
+            }),
+            on_disk_payload: true,
+            ..CollectionParams::empty()
+        },
+        Default::default(),
+        Default::default(),
+    )
+}
+
 pub fn optimize_segment(segment: Segment) -> LockedSegment {
     let dir = Builder::new().prefix("segment_dir_tmp").tempdir().unwrap();
 
@@ -7,17 +7,19 @@ use std::sync::Arc;
 use parking_lot::{RwLock, RwLockUpgradableReadGuard};
 use segment::common::operation_error::{OperationResult, SegmentFailedState};
 use segment::data_types::named_vectors::NamedVectors;
+use segment::data_types::order_by::OrderBy;
 use segment::data_types::vectors::{QueryVector, Vector};
 use segment::entry::entry_point::SegmentEntry;
 use segment::index::field_index::CardinalityEstimation;
 use segment::telemetry::SegmentTelemetry;
 use segment::types::{
-    Condition, Filter, Payload, PayloadFieldSchema, PayloadKeyType, PayloadKeyTypeRef, PointIdType,
-    ScoredPoint, SearchParams, SegmentConfig, SegmentInfo, SegmentType, SeqNumberType, WithPayload,
-    WithVector,
+    Condition, Filter, FloatPayloadType, Payload, PayloadFieldSchema, PayloadKeyType,
+    PayloadKeyTypeRef, PointIdType, RetrievedPoint, ScoredPoint, SearchParams, SegmentConfig,
+    SegmentInfo, SegmentType, SeqNumberType, WithPayload, WithVector,
 };
 
 use crate::collection_manager::holders::segment_holder::LockedSegment;
+use crate::common::sampling::proportional_sample_sizes;
 
 type LockedRmSet = Arc<RwLock<HashSet<PointIdType>>>;
 type LockedFieldsSet = Arc<RwLock<HashSet<PayloadKeyType>>>;
@@ -469,6 +471,43 @@ impl SegmentEntry for ProxySegment {
         };
     }
 
+    fn payload_batch(&self, point_ids: &[PointIdType]) -> OperationResult<Vec<Payload>> {
+        // Points of a proxy segment may live in either of the underlying segments,
+        // so the batch is resolved point by point.
+        point_ids
+            .iter()
+            .map(|&point_id| self.payload(point_id))
+            .collect()
+    }
+
+    fn retrieve(
+        &self,
+        point_ids: &[PointIdType],
+        with_payload: &WithPayload,
+        with_vector: &WithVector,
+    ) -> OperationResult<Vec<RetrievedPoint>> {
+        let write_segment = self.write_segment.get();
+        let write_segment_guard = write_segment.read();
+
+        // Split points by the segment, which holds the actual version of each point
+        let (write_points, wrapped_points): (Vec<_>, Vec<_>) = {
+            let deleted_points = self.deleted_points.read();
+            point_ids.iter().copied().partition(|point_id| {
+                deleted_points.contains(point_id) || write_segment_guard.has_point(*point_id)
+            })
+        };
+
+        let mut result = write_segment_guard.retrieve(&write_points, with_payload, with_vector)?;
+        if !wrapped_points.is_empty() {
+            result.extend(self.wrapped_segment.get().read().retrieve(
+                &wrapped_points,
+                with_payload,
+                with_vector,
+            )?);
+        }
+        Ok(result)
+    }
+
     /// Not implemented for proxy
     fn iter_points(&self) -> Box<dyn Iterator<Item = PointIdType> + '_> {
         // iter_points is not available for Proxy implementation
@@ -506,6 +545,68 @@ impl SegmentEntry for ProxySegment {
         read_points
     }
 
+    fn read_ordered_filtered<'a>(
+        &'a self,
+        limit: Option<usize>,
+        filter: Option<&'a Filter>,
+        order_by: &'a OrderBy,
+    ) -> OperationResult<Vec<(FloatPayloadType, PointIdType)>> {
+        let deleted_points = self.deleted_points.read();
+        let mut read_points = if deleted_points.is_empty() {
+            self.wrapped_segment
+                .get()
+                .read()
+                .read_ordered_filtered(limit, filter, order_by)?
+        } else {
+            let wrapped_filter =
+                self.add_deleted_points_condition_to_filter(filter, &deleted_points);
+            self.wrapped_segment.get().read().read_ordered_filtered(
+                limit,
+                Some(&wrapped_filter),
+                order_by,
+            )?
+        };
+        let mut write_segment_points = self
+            .write_segment
+            .get()
+            .read()
+            .read_ordered_filtered(limit, filter, order_by)?;
+        read_points.append(&mut write_segment_points);
+        read_points.sort_unstable_by(|(value_a, id_a), (value_b, id_b)| {
+            order_by.compare(*value_a, *value_b).then(id_a.cmp(id_b))
+        });
+        read_points.truncate(limit.unwrap_or(usize::MAX));
+        Ok(read_points)
+    }
+
+    fn read_random_filtered<'a>(
+        &'a self,
+        limit: usize,
+        filter: Option<&'a Filter>,
+    ) -> Vec<PointIdType> {
+        let deleted_points = self.deleted_points.read();
+        let wrapped_filter = (!deleted_points.is_empty())
+            .then(|| self.add_deleted_points_condition_to_filter(filter, &deleted_points));
+        let wrapped_filter = wrapped_filter.as_ref().or(filter);
+
+        let wrapped_segment = self.wrapped_segment.get();
+        let wrapped_segment = wrapped_segment.read();
+        let write_segment = self.write_segment.get();
+        let write_segment = write_segment.read();
+
+        // Both segments are sampled in proportion to the number of their points
+        let sample_sizes = proportional_sample_sizes(
+            &[
+                wrapped_segment.estimate_point_count(wrapped_filter).exp,
+                write_segment.estimate_point_count(filter).exp,
+            ],
+            limit,
+        );
+        let mut read_points = wrapped_segment.read_random_filtered(sample_sizes[0], wrapped_filter);
+        read_points.extend(write_segment.read_random_filtered(sample_sizes[1], filter));
+        read_points
+    }
+
     /// Read points in [from; to) range
     fn read_range(&self, from: Option<PointIdType>, to: Option<PointIdType>) -> Vec<PointIdType> {
         let deleted_points = self.deleted_points.read();
@@ -634,6 +735,15 @@ impl SegmentEntry for ProxySegment {
         true
     }
 
+    fn is_dirty(&self) -> bool {
+        // Changes held by the proxy itself are persisted only once the optimization is finished
+        !self.deleted_points.read().is_empty()
+            || !self.deleted_indexes.read().is_empty()
+            || !self.created_indexes.read().is_empty()
+            || self.wrapped_segment.get().read().is_dirty()
+            || self.write_segment.get().read().is_dirty()
+    }
+
     fn flush(&self, sync: bool) -> OperationResult<SeqNumberType> {
         let deleted_points_guard = self.deleted_points.read();
         let deleted_indexes_guard = self.deleted_indexes.read();
@@ -1331,6 +1441,8 @@ mod tests {
                         storage_type: VectorStorageType::Memory,
                         index: Indexes::Plain {},
                         quantization_config: None,
+                        multivector_config: None,
+                        datatype: None,
                     },
                 ),
                 (
@@ -1341,6 +1453,8 @@ mod tests {
                         storage_type: VectorStorageType::Memory,
                         index: Indexes::Plain {},
                         quantization_config: None,
+                        multivector_config: None,
+                        datatype: None,
                     },
                 ),
             ]),
@@ -1,4 +1,4 @@
-use std::cmp::{max, min};
+use std::cmp::{max, min, Ordering, Reverse};
 use std::collections::{BTreeMap, BTreeSet, BinaryHeap, HashMap, HashSet};
 use std::ops::Deref;
 use std::path::Path;
@@ -7,15 +7,16 @@ use std::thread::sleep;
 use std::time::Duration;
 
 use itertools::Itertools;
-use parking_lot::{RwLock, RwLockReadGuard, RwLockUpgradableReadGuard, RwLockWriteGuard};
+use parking_lot::{Mutex, RwLock, RwLockReadGuard, RwLockUpgradableReadGuard, RwLockWriteGuard};
 use rand::seq::SliceRandom;
 use rand::{thread_rng, Rng};
 use segment::common::operation_error::{OperationError, OperationResult};
 use segment::entry::entry_point::SegmentEntry;
 use segment::segment::Segment;
-use segment::types::{PointIdType, SeqNumberType};
+use segment::types::{Filter, PointIdType, SeqNumberType};
 
 use crate::collection_manager::holders::proxy_segment::ProxySegment;
+use crate::common::sampling::proportional_sample_sizes;
 use crate::operations::types::CollectionError;
 use crate::shards::update_tracker::UpdateTracker;
 
@@ -50,6 +51,14 @@ impl PartialOrd for DedupPoint {
     }
 }
 
+/// Check if the segment has storages for all of the given vectors
+fn has_vectors(segment: &dyn SegmentEntry, vector_names: &[String]) -> bool {
+    let config = segment.config();
+    vector_names
+        .iter()
+        .all(|vector_name| config.has_vector(vector_name))
+}
+
 fn try_unwrap_with_timeout<T>(
     mut arc: Arc<T>,
     spin: Duration,
@@ -148,6 +157,10 @@ pub struct SegmentHolder {
 
     /// Holds the first uncorrected error happened with optimizer
     pub optimizer_errors: Option<CollectionError>,
+
+    /// Points, to which the batch of operations being applied was already applied before,
+    /// e.g. before the restart. If there is no batch in progress - None
+    batch_applied_points: Mutex<Option<HashSet<PointIdType>>>,
 }
 
 pub type LockedSegmentHolder = Arc<RwLock<SegmentHolder>>;
@@ -249,13 +262,57 @@ impl<'s> SegmentHolder {
             .collect()
     }
 
+    /// Appendable segments, which have storages for all of the given vectors
+    ///
+    /// Vectors might be added to the collection after some segments were created, those segments
+    /// can't accept points with the new vectors.
+    pub fn appendable_segments_with_vectors(&self, vector_names: &[String]) -> Vec<SegmentId> {
+        self.segments
+            .iter()
+            .filter(|(_idx, seg)| {
+                let segment = seg.get();
+                let segment = segment.read();
+                segment.is_appendable() && has_vectors(segment.deref(), vector_names)
+            })
+            .map(|(idx, _seg)| *idx)
+            .collect()
+    }
+
     pub fn random_appendable_segment(&self) -> Option<LockedSegment> {
-        let segment_ids: Vec<_> = self.appendable_segments();
+        self.random_appendable_segment_with_vectors(&[])
+    }
+
+    pub fn random_appendable_segment_with_vectors(
+        &self,
+        vector_names: &[String],
+    ) -> Option<LockedSegment> {
+        let segment_ids: Vec<_> = self.appendable_segments_with_vectors(vector_names);
         segment_ids
             .choose(&mut rand::thread_rng())
             .and_then(|idx| self.segments.get(idx).cloned())
     }
 
+    /// Segments to sample the points from, with the number of points to sample from each
+    ///
+    /// Segments are sampled in proportion to the estimated number of their points, which match
+    /// the filter. So the sample is uniform across the segments, without reading all points.
+    pub fn sample_segments(
+        &self,
+        filter: Option<&Filter>,
+        limit: usize,
+    ) -> Vec<(LockedSegment, usize)> {
+        let segments: Vec<_> = self.segments.values().cloned().collect();
+        let sizes: Vec<_> = segments
+            .iter()
+            .map(|segment| segment.get().read().estimate_point_count(filter).exp)
+            .collect();
+        segments
+            .into_iter()
+            .zip(proportional_sample_sizes(&sizes, limit))
+            .filter(|(_, sample_size)| *sample_size > 0)
+            .collect()
+    }
+
     /// Selects point ids, which is stored in this segment
     fn segment_points(&self, ids: &[PointIdType], segment: &dyn SegmentEntry) -> Vec<PointIdType> {
         ids.iter()
@@ -378,11 +435,31 @@ impl<'s> SegmentHolder {
 
     /// Update function wrapper, which ensures that updates are not applied written to un-appendable segment.
     /// In case of such attempt, this function will move data into a mutable segment and remove data from un-appendable.
+    ///
+    /// Points are grouped by the segment they are stored in, so each segment is write-locked
+    /// only once per call. Points of an un-appendable segment are moved together, under a
+    /// single lock of the target appendable segment.
+    ///
     /// Returns: Set of point ids which were successfully(already) applied to segments
     pub fn apply_points_to_appendable<F>(
         &self,
         op_num: SeqNumberType,
         ids: &[PointIdType],
+        f: F,
+    ) -> OperationResult<HashSet<PointIdType>>
+    where
+        F: FnMut(PointIdType, &mut RwLockWriteGuard<dyn SegmentEntry>) -> OperationResult<bool>,
+    {
+        self.apply_points_with_vectors_to_appendable(op_num, ids, &[], f)
+    }
+
+    /// Same as [`SegmentHolder::apply_points_to_appendable`], but the update writes the given
+    /// vectors, so points are also moved out of appendable segments, which don't have them.
+    pub fn apply_points_with_vectors_to_appendable<F>(
+        &self,
+        op_num: SeqNumberType,
+        ids: &[PointIdType],
+        vector_names: &[String],
         mut f: F,
     ) -> OperationResult<HashSet<PointIdType>>
     where
@@ -391,42 +468,98 @@ impl<'s> SegmentHolder {
         let _update_guard = self.update_tracker.update();
 
         // Choose random appendable segment from this
-        let appendable_segments = self.appendable_segments();
+        let appendable_segments = self.appendable_segments_with_vectors(vector_names);
 
         let mut applied_points: HashSet<PointIdType> = Default::default();
 
-        let _applied_points_count = self.apply_points(ids, |point_id, _idx, write_segment| {
-            if let Some(point_version) = write_segment.point_version(point_id) {
-                if point_version >= op_num {
-                    applied_points.insert(point_id);
-                    return Ok(false);
-                }
+        for segment in self.segments.values() {
+            // Collect affected points first, we want to lock segment for writing as rare as possible
+            let segment_arc = segment.get();
+            let segment_lock = segment_arc.upgradable_read();
+            let segment_points: Vec<_> = self
+                .segment_points(ids, segment_lock.deref())
+                .into_iter()
+                .filter(|&point_id| match segment_lock.point_version(point_id) {
+                    // Operation is already applied to this point
+                    Some(point_version) if self.is_applied(op_num, point_id, point_version) => {
+                        applied_points.insert(point_id);
+                        false
+                    }
+                    _ => true,
+                })
+                .collect();
+            if segment_points.is_empty() {
+                continue;
             }
 
-            let is_applied = if write_segment.is_appendable() {
-                f(point_id, write_segment)?
+            let mut write_segment = RwLockUpgradableReadGuard::upgrade(segment_lock);
+            if write_segment.is_appendable() && has_vectors(write_segment.deref(), vector_names) {
+                for &point_id in &segment_points {
+                    f(point_id, &mut write_segment)?;
+                }
             } else {
                 self.aloha_random_write(
                     &appendable_segments,
                     |_appendable_idx, appendable_write_segment| {
-                        let all_vectors = write_segment.all_vectors(point_id)?;
-                        let payload = write_segment.payload(point_id)?;
+                        for &point_id in &segment_points {
+                            let all_vectors = write_segment.all_vectors(point_id)?;
+                            let payload = write_segment.payload(point_id)?;
 
-                        appendable_write_segment.upsert_point(op_num, point_id, all_vectors)?;
-                        appendable_write_segment.set_full_payload(op_num, point_id, &payload)?;
+                            appendable_write_segment.upsert_point(op_num, point_id, all_vectors)?;
+                            appendable_write_segment
+                                .set_full_payload(op_num, point_id, &payload)?;
 
-                        write_segment.delete_point(op_num, point_id)?;
+                            write_segment.delete_point(op_num, point_id)?;
 
-                        f(point_id, appendable_write_segment)
+                            f(point_id, appendable_write_segment)?;
+                        }
+                        Ok(true)
                     },
-                )?
-            };
-            applied_points.insert(point_id);
-            Ok(is_applied)
-        })?;
+                )?;
+            }
+            applied_points.extend(segment_points);
+        }
         Ok(applied_points)
     }
 
+    /// Apply a batch of operations, which share the same operation number
+    ///
+    /// Points of this version are updated by every operation of the batch, except for the
+    /// `applied_points`, which had this version before the batch.
+    pub fn apply_batch<T>(&self, applied_points: HashSet<PointIdType>, f: impl FnOnce() -> T) -> T {
+        let is_outer_batch = {
+            let mut batch_applied_points = self.batch_applied_points.lock();
+            let is_outer_batch = batch_applied_points.is_none();
+            if is_outer_batch {
+                *batch_applied_points = Some(applied_points);
+            }
+            is_outer_batch
+        };
+        let result = f();
+        if is_outer_batch {
+            *self.batch_applied_points.lock() = None;
+        }
+        result
+    }
+
+    /// Check if the operation `op_num` is already applied to the point of the `point_version`
+    pub fn is_applied(
+        &self,
+        op_num: SeqNumberType,
+        point_id: PointIdType,
+        point_version: SeqNumberType,
+    ) -> bool {
+        match point_version.cmp(&op_num) {
+            Ordering::Less => false,
+            Ordering::Equal => self
+                .batch_applied_points
+                .lock()
+                .as_ref()
+                .map_or(true, |applied_points| applied_points.contains(&point_id)),
+            Ordering::Greater => true,
+        }
+    }
+
     pub fn read_points<F>(&self, ids: &[PointIdType], mut f: F) -> OperationResult<usize>
     where
         F: FnMut(PointIdType, &RwLockReadGuard<dyn SegmentEntry>) -> OperationResult<bool>,
@@ -443,13 +576,46 @@ impl<'s> SegmentHolder {
         Ok(read_points)
     }
 
+    /// Same as [`Self::read_points`], but `f` is called once per segment with all of the
+    /// requested points, which are present in that segment.
+    ///
+    /// Allows segments to read data of multiple points in a single batch.
+    pub fn read_points_per_segment<F>(&self, ids: &[PointIdType], mut f: F) -> OperationResult<()>
+    where
+        F: FnMut(&[PointIdType], &RwLockReadGuard<dyn SegmentEntry>) -> OperationResult<()>,
+    {
+        for segment in self.segments.values() {
+            let segment_arc = segment.get();
+            let read_segment = segment_arc.read();
+            let segment_points: Vec<_> = ids
+                .iter()
+                .copied()
+                .filter(|id| read_segment.has_point(*id))
+                .collect();
+            if segment_points.is_empty() {
+                continue;
+            }
+            f(&segment_points, &read_segment)?;
+        }
+        Ok(())
+    }
+
     /// Defines flush ordering for segments.
     ///
     /// Flush appendable segments first, then non-appendable.
     /// This is done to ensure that all data, transferred from non-appendable segments to appendable segments
     /// is persisted, before marking records in non-appendable segments as removed.
+    ///
+    /// For the same reason, appendable segments with more vectors are flushed first: points are
+    /// moved into them from appendable segments, which were created before vectors were added.
     fn segment_flush_ordering(&self) -> impl Iterator<Item = SegmentId> {
-        let appendable_segments = self.appendable_segments();
+        let mut appendable_segments = self.appendable_segments();
+        appendable_segments.sort_by_cached_key(|idx| {
+            let segment = self.segments[idx].get();
+            let segment = segment.read();
+            let config = segment.config();
+            Reverse(config.vector_data.len() + config.sparse_vector_data.len())
+        });
         let non_appendable_segments = self.non_appendable_segments();
 
         appendable_segments
@@ -514,6 +680,13 @@ impl<'s> SegmentHolder {
         // Flush and release each segment
         for read_segment in segment_reads {
             let segment_version = read_segment.version();
+
+            // Clean segments are left untouched, all of their updates are persisted already
+            if !read_segment.is_dirty() {
+                max_persisted_version = max(max_persisted_version, segment_version);
+                continue;
+            }
+
             let segment_persisted_version = read_segment.flush(sync)?;
 
             if segment_version > segment_persisted_version {
@@ -555,17 +728,31 @@ impl<'s> SegmentHolder {
     /// Take a snapshot of all segments into `snapshot_dir_path`
     ///
     /// Shortcuts at the first failing segment snapshot
+    /// Take snapshots of all segments into `snapshot_dir_path`
+    ///
+    /// Returns versions of the snapshotted segments by their IDs, read under the same lock as
+    /// the snapshot is taken. Proxy segments are being optimized and are not reported.
     pub fn snapshot_all_segments(
         &self,
         temp_dir: &Path,
         snapshot_dir_path: &Path,
-    ) -> OperationResult<()> {
+    ) -> OperationResult<HashMap<String, SeqNumberType>> {
+        let mut versions = HashMap::new();
         for segment in self.segments.values() {
-            let segment_lock = segment.get();
-            let read_segment = segment_lock.read();
-            read_segment.take_snapshot(temp_dir, snapshot_dir_path)?;
+            match segment {
+                LockedSegment::Original(segment) => {
+                    let read_segment = segment.read();
+                    let archive_path = read_segment.take_snapshot(temp_dir, snapshot_dir_path)?;
+                    if let Some(segment_id) = archive_path.file_stem().and_then(|s| s.to_str()) {
+                        versions.insert(segment_id.to_string(), read_segment.version());
+                    }
+                }
+                LockedSegment::Proxy(segment) => {
+                    segment.read().take_snapshot(temp_dir, snapshot_dir_path)?;
+                }
+            }
         }
-        Ok(())
+        Ok(versions)
     }
 
     pub fn report_optimizer_error<E: Into<CollectionError>>(&mut self, error: E) {
@@ -688,6 +875,7 @@ impl<'s> SegmentHolder {
 mod tests {
     use std::fs::read_dir;
 
+    use segment::segment::SEGMENT_STATE_FILE;
     use segment::segment_constructor::simple_segment_constructor::build_simple_segment;
     use segment::types::Distance;
     use serde_json::json;
@@ -758,6 +946,28 @@ mod tests {
         assert!(read_segment_1.has_point(12.into()));
     }
 
+    #[test]
+    fn test_apply_to_appendable_skips_applied() {
+        let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+
+        let segment1 = build_segment_1(dir.path());
+
+        let mut holder = SegmentHolder::default();
+        holder.add(segment1);
+
+        // Points of `build_segment_1` are stored with versions up to 6
+        let mut processed_points: Vec<PointIdType> = vec![];
+        let applied = holder
+            .apply_points_to_appendable(5, &[1.into(), 2.into(), 3.into()], |point_id, _| {
+                processed_points.push(point_id);
+                Ok(true)
+            })
+            .unwrap();
+
+        assert!(processed_points.is_empty());
+        assert_eq!(3, applied.len());
+    }
+
     #[test]
     fn test_points_deduplication() {
         let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
@@ -813,12 +1023,51 @@ mod tests {
 
         let temp_dir = Builder::new().prefix("temp_dir").tempdir().unwrap();
         let snapshot_dir = Builder::new().prefix("snapshot_dir").tempdir().unwrap();
-        holder
+        let versions = holder
             .snapshot_all_segments(temp_dir.path(), snapshot_dir.path())
             .unwrap();
 
         let archive_count = read_dir(&snapshot_dir).unwrap().count();
         // one archive produced per concrete segment in the SegmentHolder
         assert_eq!(archive_count, 2);
+        assert_eq!(versions.len(), 2);
+    }
+
+    #[test]
+    fn test_flush_only_dirty_segments() {
+        let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+        let segment1 = build_segment_1(dir.path());
+        let segment2 = build_segment_2(dir.path());
+
+        let mut holder = SegmentHolder::default();
+
+        let sid1 = holder.add(segment1);
+        let sid2 = holder.add(segment2);
+
+        holder.flush_all(true).unwrap();
+        let segment1 = holder.get(sid1).unwrap().get();
+        let segment2 = holder.get(sid2).unwrap().get();
+        assert!(!segment1.read().is_dirty());
+        assert!(!segment2.read().is_dirty());
+
+        let state_modified = |segment: &Arc<RwLock<dyn SegmentEntry>>| {
+            let state_path = segment.read().data_path().join(SEGMENT_STATE_FILE);
+            std::fs::metadata(state_path).unwrap().modified().unwrap()
+        };
+        let clean_state_modified = state_modified(&segment2);
+
+        segment1
+            .write()
+            .set_payload(100, 1.into(), &json!({ "color": "green" }).into())
+            .unwrap();
+        assert!(segment1.read().is_dirty());
+        assert!(!segment2.read().is_dirty());
+
+        let flushed_version = holder.flush_all(true).unwrap();
+        assert_eq!(flushed_version, 100);
+        assert!(!segment1.read().is_dirty());
+
+        // Clean segment is not flushed again
+        assert_eq!(state_modified(&segment2), clean_state_modified);
     }
 }
@@ -12,7 +12,7 @@ use segment::types::{HnswConfig, Indexes, QuantizationConfig, SegmentType, VECTO
 
 use crate::collection_manager::holders::segment_holder::{LockedSegmentHolder, SegmentId};
 use crate::collection_manager::optimizers::segment_optimizer::{
-    OptimizerThresholds, SegmentOptimizer,
+    ConcurrencyLimit, OptimizerThresholds, SegmentOptimizer,
 };
 use crate::config::CollectionParams;
 use crate::operations::config_diff::DiffConfig;
@@ -22,7 +22,12 @@ use crate::operations::config_diff::DiffConfig;
 /// For example, a user may change the HNSW parameters for a collection. A segment that was already
 /// indexed with different parameters now has a mismatch. This segment should be optimized (and
 /// indexed) again in order to update the effective configuration.
+///
+/// Number of segments, which are rebuilt at the same time, may be limited to roll out configuration
+/// changes gradually. The limit applies to each shard separately, but it is a part of the collection
+/// config, so a zero limit aborts rebuilding on all peers of the cluster.
 pub struct ConfigMismatchOptimizer {
+    rebuild_limit: Option<ConcurrencyLimit>,
     thresholds_config: OptimizerThresholds,
     segments_path: PathBuf,
     collection_temp_dir: PathBuf,
@@ -34,6 +39,7 @@ pub struct ConfigMismatchOptimizer {
 
 impl ConfigMismatchOptimizer {
     pub fn new(
+        max_rebuild_segments: Option<usize>,
         thresholds_config: OptimizerThresholds,
         segments_path: PathBuf,
         collection_temp_dir: PathBuf,
@@ -42,6 +48,7 @@ impl ConfigMismatchOptimizer {
         quantization_config: Option<QuantizationConfig>,
     ) -> Self {
         ConfigMismatchOptimizer {
+            rebuild_limit: max_rebuild_segments.map(ConcurrencyLimit::new),
             thresholds_config,
             segments_path,
             collection_temp_dir,
@@ -54,27 +61,19 @@ impl ConfigMismatchOptimizer {
 
     /// Check if current configuration requires vectors to be stored on disk
     fn check_if_vectors_on_disk(&self, vector_name: &str) -> Option<bool> {
-        self.collection_params
-            .vectors
-            .get_params(vector_name)
-            .and_then(|vector_params| vector_params.on_disk)
+        self.collection_params.vectors_on_disk(vector_name)
     }
 
     /// Check if current configuration requires sparse vectors index to be stored on disk
-    #[allow(dead_code)]
     fn check_if_sparse_vectors_index_on_disk(&self, vector_name: &str) -> Option<bool> {
-        self.collection_params
-            .sparse_vectors
-            .as_ref()
-            .and_then(|vector_params| vector_params.get(vector_name))
-            .and_then(|params| params.index)
-            .and_then(|index| index.on_disk)
+        self.collection_params.sparse_index_on_disk(vector_name)
     }
 
     /// Calculates and HNSW config that should be used for a given vector
     /// with current configuration.
     ///
     /// Takes vector-specific HNSW config (if any) and merges it with the collection-wide config.
+    /// Placement of the index is defined by the storage policy, if it is specified.
     fn get_required_hnsw_config(&self, vector_name: &str) -> Cow<HnswConfig> {
         let target_hnsw_collection = &self.hnsw_config;
         // Select vector specific target HNSW config
@@ -93,19 +92,25 @@ impl ConfigMismatchOptimizer {
                     None
                 }
             });
-        match target_hnsw_vector {
+        let mut target_hnsw = match target_hnsw_vector {
             Some(target_hnsw) => Cow::Owned(target_hnsw),
             None => Cow::Borrowed(target_hnsw_collection),
+        };
+        if self.collection_params.hnsw_on_disk(vector_name).is_some() {
+            self.collection_params
+                .place_hnsw_config(vector_name, target_hnsw.to_mut());
         }
+        target_hnsw
     }
 
-    fn worst_segment(
+    /// Find segments with configuration mismatch, along with the size of their vector data
+    fn mismatched_segments(
         &self,
-        segments: LockedSegmentHolder,
+        segments: &LockedSegmentHolder,
         excluded_ids: &HashSet<SegmentId>,
-    ) -> Vec<SegmentId> {
+    ) -> Vec<(SegmentId, usize)> {
         let segments_read_guard = segments.read();
-        let candidates: Vec<_> = segments_read_guard
+        let candidates = segments_read_guard
             .iter()
             // Excluded externally, might already be scheduled for optimization
             .filter(|(idx, _)| !excluded_ids.contains(idx))
@@ -128,8 +133,8 @@ impl ConfigMismatchOptimizer {
                     return None; // Never optimize already optimized segment
                 }
 
-                if self.collection_params.on_disk_payload
-                    != segment_config.payload_storage_type.is_on_disk()
+                if self.collection_params.payload_storage_type()
+                    != segment_config.payload_storage_type
                 {
                     return Some((*idx, vector_size)); // Skip segments with payload mismatch
                 }
@@ -169,9 +174,13 @@ impl ConfigMismatchOptimizer {
                                 .and_then(|vector_params| {
                                     vector_params.quantization_config.clone()
                                 });
-                            let target_quantization = target_quantization_vector
-                                .as_ref()
-                                .or(target_quantization_collection);
+                            let mut target_quantization = target_quantization_vector
+                                .or_else(|| target_quantization_collection.cloned());
+                            if let Some(target_quantization) = &mut target_quantization {
+                                self.collection_params
+                                    .place_quantization_config(vector_name, target_quantization);
+                            }
+                            let target_quantization = target_quantization.as_ref();
                             let quantization_mismatch = vector_data
                                 .quantization_config
                                 .as_ref()
@@ -209,6 +218,15 @@ impl ConfigMismatchOptimizer {
                 (sparse_has_mismatch || dense_has_mismatch).then_some((*idx, vector_size))
             })
             .collect();
+        candidates
+    }
+
+    fn worst_segment(
+        &self,
+        segments: LockedSegmentHolder,
+        excluded_ids: &HashSet<SegmentId>,
+    ) -> Vec<SegmentId> {
+        let candidates = self.mismatched_segments(&segments, excluded_ids);
 
         // Select segment with largest vector size
         candidates
@@ -257,6 +275,14 @@ impl SegmentOptimizer for ConfigMismatchOptimizer {
         self.worst_segment(segments, excluded_ids)
     }
 
+    fn concurrency_limit(&self) -> Option<&ConcurrencyLimit> {
+        self.rebuild_limit.as_ref()
+    }
+
+    fn pending_segments(&self, segments: LockedSegmentHolder) -> Option<usize> {
+        Some(self.mismatched_segments(&segments, &HashSet::new()).len())
+    }
+
     fn get_telemetry_data(&self) -> OperationDurationStatistics {
         self.get_telemetry_counter().lock().get_statistics()
     }
@@ -274,8 +300,8 @@ mod tests {
     use parking_lot::RwLock;
     use segment::entry::entry_point::SegmentEntry;
     use segment::types::{
-        CompressionRatio, Distance, ProductQuantization, ProductQuantizationConfig,
-        ScalarQuantizationConfig, ScalarType,
+        CompressionRatio, Distance, PayloadStorageType, ProductQuantization,
+        ProductQuantizationConfig, ScalarQuantizationConfig, ScalarType,
     };
     use tempfile::Builder;
 
@@ -283,6 +309,9 @@ mod tests {
     use crate::collection_manager::fixtures::{random_multi_vec_segment, random_segment};
     use crate::collection_manager::holders::segment_holder::{LockedSegment, SegmentHolder};
     use crate::collection_manager::optimizers::indexing_optimizer::IndexingOptimizer;
+    use crate::config::{
+        PayloadStorageBackend, StoragePlacement, StoragePolicy, VectorStoragePolicy,
+    };
     use crate::operations::config_diff::HnswConfigDiff;
     use crate::operations::types::{VectorParams, VectorsConfig};
 
@@ -316,6 +345,10 @@ mod tests {
                 hnsw_config: None,
                 quantization_config: None,
                 on_disk: None,
+                normalize: None,
+                dimensionality_reduction: None,
+                multivector_config: None,
+                datatype: None,
             }),
             ..CollectionParams::empty()
         };
@@ -349,6 +382,7 @@ mod tests {
             Default::default(),
         );
         let mut config_mismatch_optimizer = ConfigMismatchOptimizer::new(
+            None,
             thresholds_config,
             dir.path().to_owned(),
             temp_dir.path().to_owned(),
@@ -383,10 +417,18 @@ mod tests {
         let suggested_to_optimize =
             config_mismatch_optimizer.check_condition(locked_holder.clone(), &Default::default());
         assert_eq!(suggested_to_optimize.len(), 1);
+        assert_eq!(
+            config_mismatch_optimizer.pending_segments(locked_holder.clone()),
+            Some(1),
+        );
         let changed = config_mismatch_optimizer
             .optimize(locked_holder.clone(), suggested_to_optimize, &false.into())
             .unwrap();
         assert!(changed, "optimizer should have rebuilt this segment");
+        assert_eq!(
+            config_mismatch_optimizer.pending_segments(locked_holder.clone()),
+            Some(0),
+        );
 
         // Ensure new segment has changed HNSW config
         locked_holder
@@ -448,6 +490,10 @@ mod tests {
                         hnsw_config: Some(hnsw_config_vector1),
                         quantization_config: None,
                         on_disk: None,
+                        normalize: None,
+                        dimensionality_reduction: None,
+                        multivector_config: None,
+                        datatype: None,
                     },
                 ),
                 (
@@ -458,6 +504,10 @@ mod tests {
                         hnsw_config: None,
                         quantization_config: None,
                         on_disk: None,
+                        normalize: None,
+                        dimensionality_reduction: None,
+                        multivector_config: None,
+                        datatype: None,
                     },
                 ),
             ])),
@@ -499,6 +549,7 @@ mod tests {
             Default::default(),
         );
         let mut config_mismatch_optimizer = ConfigMismatchOptimizer::new(
+            None,
             thresholds_config,
             dir.path().to_owned(),
             temp_dir.path().to_owned(),
@@ -613,6 +664,10 @@ mod tests {
                         hnsw_config: None,
                         quantization_config: Some(quantization_config_vector1.clone()),
                         on_disk: None,
+                        normalize: None,
+                        dimensionality_reduction: None,
+                        multivector_config: None,
+                        datatype: None,
                     },
                 ),
                 (
@@ -623,6 +678,10 @@ mod tests {
                         hnsw_config: None,
                         quantization_config: None,
                         on_disk: None,
+                        normalize: None,
+                        dimensionality_reduction: None,
+                        multivector_config: None,
+                        datatype: None,
                     },
                 ),
             ])),
@@ -664,6 +723,7 @@ mod tests {
             Some(quantization_config_collection.clone()),
         );
         let mut config_mismatch_optimizer = ConfigMismatchOptimizer::new(
+            None,
             thresholds_config,
             dir.path().to_owned(),
             temp_dir.path().to_owned(),
@@ -736,4 +796,199 @@ mod tests {
                 );
             });
     }
+
+    /// This tests the config mismatch optimizer for a changed payload storage backend
+    ///
+    /// It tests whether:
+    /// - the condition check for payload storage mismatches works
+    /// - optimized segments use the new payload storage and keep all payloads
+    #[test]
+    fn test_payload_storage_mismatch() {
+        let (point_count, dim) = (100, 10);
+        let thresholds_config = OptimizerThresholds {
+            max_segment_size: std::usize::MAX,
+            memmap_threshold: std::usize::MAX,
+            indexing_threshold: std::usize::MAX,
+        };
+        let collection_params = CollectionParams {
+            vectors: VectorsConfig::Single(VectorParams {
+                size: dim.try_into().unwrap(),
+                distance: Distance::Dot,
+                hnsw_config: None,
+                quantization_config: None,
+                on_disk: None,
+                normalize: None,
+                dimensionality_reduction: None,
+                multivector_config: None,
+                datatype: None,
+            }),
+            ..CollectionParams::empty()
+        };
+
+        let temp_dir = Builder::new().prefix("segment_temp_dir").tempdir().unwrap();
+        let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+        let mut holder = SegmentHolder::default();
+
+        let segment = random_segment(dir.path(), 100, point_count, dim as usize);
+        let payloads: Vec<_> = segment
+            .iter_points()
+            .map(|point_id| (point_id, segment.payload(point_id).unwrap()))
+            .collect();
+
+        holder.add(segment);
+        let locked_holder: Arc<RwLock<_>> = Arc::new(RwLock::new(holder));
+
+        let mut config_mismatch_optimizer = ConfigMismatchOptimizer::new(
+            None,
+            thresholds_config,
+            dir.path().to_owned(),
+            temp_dir.path().to_owned(),
+            collection_params,
+            HnswConfig {
+                m: 16,
+                ef_construct: 100,
+                full_scan_threshold: 10,
+                max_indexing_threads: 0,
+                on_disk: None,
+                payload_m: None,
+            },
+            Default::default(),
+        );
+
+        let suggested_to_optimize =
+            config_mismatch_optimizer.check_condition(locked_holder.clone(), &Default::default());
+        assert_eq!(suggested_to_optimize.len(), 0);
+
+        // Move payload to the append log storage
+        config_mismatch_optimizer.collection_params.on_disk_payload = true;
+        config_mismatch_optimizer
+            .collection_params
+            .payload_storage_backend = Some(PayloadStorageBackend::AppendLog);
+
+        let suggested_to_optimize =
+            config_mismatch_optimizer.check_condition(locked_holder.clone(), &Default::default());
+        assert_eq!(suggested_to_optimize.len(), 1);
+        let changed = config_mismatch_optimizer
+            .optimize(locked_holder.clone(), suggested_to_optimize, &false.into())
+            .unwrap();
+        assert!(changed, "optimizer should have rebuilt this segment");
+
+        let suggested_to_optimize =
+            config_mismatch_optimizer.check_condition(locked_holder.clone(), &Default::default());
+        assert_eq!(suggested_to_optimize.len(), 0);
+
+        locked_holder
+            .read()
+            .iter()
+            .map(|(_, segment)| match segment {
+                LockedSegment::Original(s) => s.read(),
+                LockedSegment::Proxy(_) => unreachable!(),
+            })
+            .for_each(|segment| {
+                assert_eq!(
+                    segment.config().payload_storage_type,
+                    PayloadStorageType::AppendLog,
+                );
+                if segment.total_point_count() > 0 {
+                    for (point_id, payload) in &payloads {
+                        assert_eq!(&segment.payload(*point_id).unwrap(), payload);
+                    }
+                }
+            });
+    }
+
+    /// This tests the config mismatch optimizer for a changed storage policy
+    ///
+    /// It tests whether:
+    /// - the condition check for placement mismatches works
+    /// - optimized segments store vectors and payload according to the policy
+    #[test]
+    fn test_storage_policy_mismatch() {
+        let (point_count, dim) = (100, 10);
+        let thresholds_config = OptimizerThresholds {
+            max_segment_size: std::usize::MAX,
+            memmap_threshold: std::usize::MAX,
+            indexing_threshold: std::usize::MAX,
+        };
+        let collection_params = CollectionParams {
+            vectors: VectorsConfig::Single(VectorParams {
+                size: dim.try_into().unwrap(),
+                distance: Distance::Dot,
+                hnsw_config: None,
+                quantization_config: None,
+                on_disk: Some(false),
+                normalize: None,
+                dimensionality_reduction: None,
+                multivector_config: None,
+                datatype: None,
+            }),
+            ..CollectionParams::empty()
+        };
+
+        let temp_dir = Builder::new().prefix("segment_temp_dir").tempdir().unwrap();
+        let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+        let mut holder = SegmentHolder::default();
+
+        holder.add(random_segment(dir.path(), 100, point_count, dim as usize));
+        let locked_holder: Arc<RwLock<_>> = Arc::new(RwLock::new(holder));
+
+        let mut config_mismatch_optimizer = ConfigMismatchOptimizer::new(
+            None,
+            thresholds_config,
+            dir.path().to_owned(),
+            temp_dir.path().to_owned(),
+            collection_params,
+            HnswConfig {
+                m: 16,
+                ef_construct: 100,
+                full_scan_threshold: 10,
+                max_indexing_threads: 0,
+                on_disk: None,
+                payload_m: None,
+            },
+            Default::default(),
+        );
+
+        let suggested_to_optimize =
+            config_mismatch_optimizer.check_condition(locked_holder.clone(), &Default::default());
+        assert_eq!(suggested_to_optimize.len(), 0);
+
+        // Policy takes precedence over `on_disk` of the vector params
+        config_mismatch_optimizer.collection_params.storage_policy = Some(StoragePolicy {
+            all_vectors: Some(VectorStoragePolicy {
+                vectors: Some(StoragePlacement::Disk),
+                ..Default::default()
+            }),
+            vectors: Default::default(),
+            payload: Some(StoragePlacement::Disk),
+        });
+
+        let suggested_to_optimize =
+            config_mismatch_optimizer.check_condition(locked_holder.clone(), &Default::default());
+        assert_eq!(suggested_to_optimize.len(), 1);
+        let changed = config_mismatch_optimizer
+            .optimize(locked_holder.clone(), suggested_to_optimize, &false.into())
+            .unwrap();
+        assert!(changed, "optimizer should have rebuilt this segment");
+
+        let suggested_to_optimize =
+            config_mismatch_optimizer.check_condition(locked_holder.clone(), &Default::default());
+        assert_eq!(suggested_to_optimize.len(), 0);
+
+        locked_holder
+            .read()
+            .iter()
+            .map(|(_, segment)| match segment {
+                LockedSegment::Original(s) => s.read(),
+                LockedSegment::Proxy(_) => unreachable!(),
+            })
+            .for_each(|segment| {
+                let config = segment.config();
+                assert_eq!(config.payload_storage_type, PayloadStorageType::OnDisk);
+                assert!(config
+                    .vector_data
+                    .values()
+                    .all(|vector_data| vector_data.storage_type.is_on_disk()));
+            });
+    }
 }
@@ -131,7 +131,7 @@ impl IndexingOptimizer {
                     .saturating_mul(BYTES_IN_KB);
                 let mut require_optimization = false;
 
-                for (vector_name, vector_config) in self.collection_params.vectors.params_iter() {
+                for (vector_name, _) in self.collection_params.vectors.params_iter() {
                     if let Some(vector_data) = segment_config.vector_data.get(vector_name) {
                         let is_indexed = vector_data.index.is_indexed();
                         let is_on_disk = vector_data.storage_type.is_on_disk();
@@ -141,7 +141,8 @@ impl IndexingOptimizer {
                         let is_big_for_mmap = storage_size >= mmap_threshold_kb;
 
                         let optimize_for_index = is_big_for_index && !is_indexed;
-                        let optimize_for_mmap = if let Some(on_disk_config) = vector_config.on_disk
+                        let optimize_for_mmap = if let Some(on_disk_config) =
+                            self.collection_params.vectors_on_disk(vector_name)
                         {
                             on_disk_config && !is_on_disk
                         } else {
@@ -285,7 +286,6 @@ impl SegmentOptimizer for IndexingOptimizer {
 mod tests {
     use std::collections::BTreeMap;
     use std::num::NonZeroU64;
-    use std::ops::Deref;
     use std::sync::atomic::AtomicBool;
     use std::sync::Arc;
 
@@ -349,6 +349,10 @@ mod tests {
                         hnsw_config: None,
                         quantization_config: None,
                         on_disk: None,
+                        normalize: None,
+                        dimensionality_reduction: None,
+                        multivector_config: None,
+                        datatype: None,
                     },
                 )
             })
@@ -464,6 +468,10 @@ mod tests {
                     hnsw_config: None,
                     quantization_config: None,
                     on_disk: None,
+                    normalize: None,
+                    dimensionality_reduction: None,
+                    multivector_config: None,
+                    datatype: None,
                 }),
                 ..CollectionParams::empty()
             },
@@ -507,7 +515,7 @@ mod tests {
 
         // ----- CREATE AN INDEXED FIELD ------
         process_field_index_operation(
-            locked_holder.deref(),
+            &locked_holder.read(),
             opnum.next().unwrap(),
             &FieldIndexOperations::CreateIndex(CreateIndex {
                 field_name: payload_field.to_owned(),
@@ -617,7 +625,7 @@ mod tests {
             .num_vectors;
 
         process_point_operation(
-            locked_holder.deref(),
+            &locked_holder.read(),
             opnum.next().unwrap(),
             insert_point_ops,
         )
@@ -680,7 +688,7 @@ mod tests {
         .into();
 
         process_point_operation(
-            locked_holder.deref(),
+            &locked_holder.read(),
             opnum.next().unwrap(),
             insert_point_ops,
         )
@@ -720,6 +728,10 @@ mod tests {
                 hnsw_config: None,
                 quantization_config: None,
                 on_disk: Some(false),
+                normalize: None,
+                dimensionality_reduction: None,
+                multivector_config: None,
+                datatype: None,
             }),
             ..CollectionParams::empty()
         };
@@ -754,6 +766,7 @@ mod tests {
                 Default::default(),
             );
             let config_mismatch_optimizer = ConfigMismatchOptimizer::new(
+                None,
                 thresholds_config.clone(),
                 dir.path().to_owned(),
                 temp_dir.path().to_owned(),
@@ -4,9 +4,12 @@ use std::sync::Arc;
 use chrono::{DateTime, Utc};
 use parking_lot::Mutex;
 use schemars::JsonSchema;
+use segment::types::SegmentInfo;
+use segment::utils::resource_usage::{self, ThreadUsage};
 use serde::{Deserialize, Serialize};
 
 use super::holders::segment_holder::SegmentId;
+use crate::operations::running_operations::next_operation_id;
 
 pub mod config_mismatch_optimizer;
 pub mod indexing_optimizer;
@@ -57,6 +60,15 @@ impl TrackerLog {
         });
     }
 
+    /// Optimizations, which are still running
+    pub fn running(&self) -> Vec<Tracker> {
+        self.descriptions
+            .iter()
+            .filter(|tracker| tracker.state.lock().status == TrackerStatus::Optimizing)
+            .cloned()
+            .collect()
+    }
+
     /// Convert log into list of objects usable in telemetry
     pub fn to_telemetry(&self) -> Vec<TrackerTelemetry> {
         self.descriptions
@@ -71,6 +83,8 @@ impl TrackerLog {
 /// Tracks the state of an optimizer
 #[derive(Clone, Debug)]
 pub struct Tracker {
+    /// Id of the optimization, see [`next_operation_id`]
+    pub id: u64,
     /// Name of the optimizer
     pub name: String,
     /// Segment IDs being optimized
@@ -85,6 +99,7 @@ impl Tracker {
     /// Start a new optimizer tracker
     pub fn start(name: impl Into<String>, segment_ids: Vec<SegmentId>) -> Self {
         Self {
+            id: next_operation_id(),
             name: name.into(),
             segment_ids,
             state: Default::default(),
@@ -106,6 +121,7 @@ impl Tracker {
             status: state.status.clone(),
             start_at: self.start_at,
             end_at: state.end_at,
+            resources: state.resources.clone(),
         }
     }
 }
@@ -123,6 +139,33 @@ pub struct TrackerTelemetry {
     pub start_at: DateTime<Utc>,
     /// End time of the optimizer
     pub end_at: Option<DateTime<Utc>>,
+    /// Resources used by the optimization
+    #[serde(default)]
+    pub resources: TrackerResources,
+}
+
+/// Resources used by an optimization, reported once it is finished
+#[derive(Serialize, Deserialize, Clone, Debug, Default, JsonSchema, PartialEq)]
+pub struct TrackerResources {
+    /// CPU time of the optimization in seconds, including the threads building the index
+    pub cpu_sec: f64,
+    /// Number of bytes read from the storage
+    pub read_bytes: u64,
+    /// Number of bytes written to the storage
+    pub written_bytes: u64,
+    /// Estimate of the peak resident memory of the process, sampled at the stages of the
+    /// optimization. Includes the memory used by the rest of the process.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub peak_rss_bytes: Option<u64>,
+    /// Number of points in the optimized segment
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub output_points: Option<usize>,
+    /// Size of the optimized segment on disk
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub output_disk_bytes: Option<usize>,
+    /// Size of the optimized segment in RAM
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub output_ram_bytes: Option<usize>,
 }
 
 /// Handle to an optimizer tracker, allows updating its state
@@ -132,9 +175,39 @@ pub struct TrackerHandle {
 }
 
 impl TrackerHandle {
+    /// Handle of an optimization, which is not registered in any log
+    pub fn untracked() -> Self {
+        Arc::new(Mutex::new(TrackerState::default())).into()
+    }
+
     pub fn update(&self, status: TrackerStatus) {
         self.handle.lock().update(status);
     }
+
+    /// Sample resident memory of the process for the peak memory estimate
+    pub fn sample_rss(&self) {
+        let Some(rss_bytes) = resource_usage::process_rss_bytes() else {
+            return;
+        };
+        let resources = &mut self.handle.lock().resources;
+        resources.peak_rss_bytes = resources.peak_rss_bytes.max(Some(rss_bytes));
+    }
+
+    /// Record CPU time and IO of the optimization
+    pub fn record_usage(&self, usage: ThreadUsage) {
+        let resources = &mut self.handle.lock().resources;
+        resources.cpu_sec = usage.cpu_time.as_secs_f64();
+        resources.read_bytes = usage.read_bytes;
+        resources.written_bytes = usage.write_bytes;
+    }
+
+    /// Record sizes of the optimized segment
+    pub fn record_output_segment(&self, info: &SegmentInfo) {
+        let resources = &mut self.handle.lock().resources;
+        resources.output_points = Some(info.num_points);
+        resources.output_disk_bytes = Some(info.disk_usage_bytes);
+        resources.output_ram_bytes = Some(info.ram_usage_bytes);
+    }
 }
 
 impl From<Arc<Mutex<TrackerState>>> for TrackerHandle {
@@ -144,10 +217,11 @@ impl From<Arc<Mutex<TrackerState>>> for TrackerHandle {
 }
 
 /// Mutable state of an optimizer tracker
-#[derive(Debug, Default, Clone, PartialEq, Eq)]
+#[derive(Debug, Default, Clone, PartialEq)]
 pub struct TrackerState {
     pub status: TrackerStatus,
     pub end_at: Option<DateTime<Utc>>,
+    pub resources: TrackerResources,
 }
 
 impl TrackerState {
@@ -1,6 +1,6 @@
 use std::collections::{HashMap, HashSet};
 use std::path::Path;
-use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 use std::sync::Arc;
 
 use itertools::Itertools;
@@ -16,14 +16,15 @@ use segment::segment::{Segment, SegmentVersion};
 use segment::segment_constructor::build_segment;
 use segment::segment_constructor::segment_builder::SegmentBuilder;
 use segment::types::{
-    HnswConfig, Indexes, PayloadFieldSchema, PayloadKeyType, PayloadStorageType, PointIdType,
-    QuantizationConfig, SegmentConfig, VectorStorageType, VECTOR_ELEMENT_SIZE,
+    HnswConfig, Indexes, PayloadFieldSchema, PayloadKeyType, PointIdType, QuantizationConfig,
+    SegmentConfig, VectorStorageType, VECTOR_ELEMENT_SIZE,
 };
 
 use crate::collection_manager::holders::proxy_segment::ProxySegment;
 use crate::collection_manager::holders::segment_holder::{
     LockedSegment, LockedSegmentHolder, SegmentId,
 };
+use crate::collection_manager::optimizers::TrackerHandle;
 use crate::config::CollectionParams;
 use crate::operations::config_diff::DiffConfig;
 use crate::operations::types::{CollectionError, CollectionResult};
@@ -37,6 +38,53 @@ pub struct OptimizerThresholds {
     pub indexing_threshold: usize,
 }
 
+/// Limits the number of optimizations of a single optimizer, which run at the same time
+#[derive(Debug)]
+pub struct ConcurrencyLimit {
+    max: usize,
+    running: Arc<AtomicUsize>,
+}
+
+impl ConcurrencyLimit {
+    pub fn new(max: usize) -> Self {
+        Self {
+            max,
+            running: Default::default(),
+        }
+    }
+
+    pub fn max(&self) -> usize {
+        self.max
+    }
+
+    /// Take a permit for one more optimization, if the limit is not reached yet
+    ///
+    /// Permit is held by the optimization task, so it is released once the task is finished
+    /// or dropped without being started.
+    pub fn try_acquire(&self) -> Option<ConcurrencyPermit> {
+        self.running
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |running| {
+                (running < self.max).then_some(running + 1)
+            })
+            .ok()?;
+        Some(ConcurrencyPermit {
+            running: self.running.clone(),
+        })
+    }
+}
+
+/// Permit of a running optimization, see [`ConcurrencyLimit::try_acquire`]
+#[derive(Debug)]
+pub struct ConcurrencyPermit {
+    running: Arc<AtomicUsize>,
+}
+
+impl Drop for ConcurrencyPermit {
+    fn drop(&mut self) {
+        self.running.fetch_sub(1, Ordering::AcqRel);
+    }
+}
+
 /// SegmentOptimizer - trait implementing common functionality of the optimizers
 ///
 /// It provides functions which allow to re-build specified segments into a new, better one.
@@ -78,17 +126,28 @@ pub trait SegmentOptimizer {
 
     fn get_telemetry_counter(&self) -> Arc<Mutex<OperationDurationsAggregator>>;
 
+    /// Limit of optimizations of this optimizer, which may run at the same time
+    ///
+    /// Not limited by default
+    fn concurrency_limit(&self) -> Option<&ConcurrencyLimit> {
+        None
+    }
+
+    /// Number of segments, which require optimization by this optimizer, including the ones
+    /// being optimized right now
+    ///
+    /// Only reported by optimizers, which track their progress
+    fn pending_segments(&self, _segments: LockedSegmentHolder) -> Option<usize> {
+        None
+    }
+
     /// Build temp segment
     fn temp_segment(&self, save_version: bool) -> CollectionResult<LockedSegment> {
         let collection_params = self.collection_params();
         let config = SegmentConfig {
             vector_data: collection_params.into_base_vector_data()?,
             sparse_vector_data: collection_params.into_sparse_vector_data()?,
-            payload_storage_type: if collection_params.on_disk_payload {
-                PayloadStorageType::OnDisk
-            } else {
-                PayloadStorageType::InMemory
-            },
+            payload_storage_type: collection_params.payload_storage_type(),
         };
         Ok(LockedSegment::new(build_segment(
             self.collection_path(),
@@ -168,9 +227,10 @@ pub trait SegmentOptimizer {
                     .vectors
                     .get_params(vector_name)
                     .and_then(|params| params.hnsw_config);
-                let vector_hnsw = param_hnsw
+                let mut vector_hnsw = param_hnsw
                     .and_then(|c| c.update(collection_hnsw).ok())
                     .unwrap_or_else(|| collection_hnsw.clone());
+                collection_params.place_hnsw_config(vector_name, &mut vector_hnsw);
                 config.index = Indexes::Hnsw(vector_hnsw);
 
                 // Assign quantization config
@@ -178,9 +238,12 @@ pub trait SegmentOptimizer {
                     .vectors
                     .get_params(vector_name)
                     .and_then(|params| params.quantization_config.as_ref());
-                let vector_quantization = param_quantization
+                let mut vector_quantization = param_quantization
                     .or(collection_quantization.as_ref())
                     .cloned();
+                if let Some(vector_quantization) = &mut vector_quantization {
+                    collection_params.place_quantization_config(vector_name, vector_quantization);
+                }
                 config.quantization_config = vector_quantization;
             });
         }
@@ -189,10 +252,7 @@ pub trait SegmentOptimizer {
         if threshold_is_on_disk {
             vector_data.iter_mut().for_each(|(vector_name, config)| {
                 // Check whether on_disk is explicitly configured, if not, set it to true
-                let config_on_disk = collection_params
-                    .vectors
-                    .get_params(vector_name)
-                    .and_then(|config| config.on_disk);
+                let config_on_disk = collection_params.vectors_on_disk(vector_name);
 
                 match config_on_disk {
                     Some(true) => config.storage_type = VectorStorageType::Mmap, // Both agree, but prefer mmap storage type
@@ -215,10 +275,9 @@ pub trait SegmentOptimizer {
             .for_each(|(vector_name, config)| {
                 // Assign sparse index on disk
                 if let Some(sparse_config) = &collection_params.sparse_vectors {
-                    if let Some(params) = sparse_config.get(vector_name) {
-                        let config_on_disk = params
-                            .index
-                            .and_then(|index_params| index_params.on_disk)
+                    if sparse_config.contains_key(vector_name) {
+                        let config_on_disk = collection_params
+                            .sparse_index_on_disk(vector_name)
                             .unwrap_or(threshold_is_on_disk);
 
                         // If mmap OR index is exceeded
@@ -238,11 +297,7 @@ pub trait SegmentOptimizer {
         let optimized_config = SegmentConfig {
             vector_data,
             sparse_vector_data,
-            payload_storage_type: if collection_params.on_disk_payload {
-                PayloadStorageType::OnDisk
-            } else {
-                PayloadStorageType::InMemory
-            },
+            payload_storage_type: collection_params.payload_storage_type(),
         };
 
         Ok(SegmentBuilder::new(
@@ -430,6 +485,18 @@ pub trait SegmentOptimizer {
         segments: LockedSegmentHolder,
         ids: Vec<SegmentId>,
         stopped: &AtomicBool,
+    ) -> CollectionResult<bool> {
+        self.optimize_tracked(segments, ids, stopped, &TrackerHandle::untracked())
+    }
+
+    /// Same as [`SegmentOptimizer::optimize`], but reports the memory usage and the optimized
+    /// segment to the `tracker`
+    fn optimize_tracked(
+        &self,
+        segments: LockedSegmentHolder,
+        ids: Vec<SegmentId>,
+        stopped: &AtomicBool,
+        tracker: &TrackerHandle,
     ) -> CollectionResult<bool> {
         check_process_stopped(stopped)?;
 
@@ -537,6 +604,9 @@ pub trait SegmentOptimizer {
             }
         };
 
+        tracker.sample_rss();
+        tracker.record_output_segment(&optimized_segment.info());
+
         // Avoid unnecessary point removing in the critical section:
         // - save already removed points while avoiding long read locks
         // - exclude already removed points from post-optimization removing
@@ -310,6 +310,10 @@ mod tests {
                     hnsw_config: None,
                     quantization_config: None,
                     on_disk: None,
+                    normalize: None,
+                    dimensionality_reduction: None,
+                    multivector_config: None,
+                    datatype: None,
                 }),
                 ..CollectionParams::empty()
             },
@@ -400,6 +404,10 @@ mod tests {
                         hnsw_config: None,
                         quantization_config: None,
                         on_disk: None,
+                        normalize: None,
+                        dimensionality_reduction: None,
+                        multivector_config: None,
+                        datatype: None,
                     },
                 ),
                 (
@@ -410,6 +418,10 @@ mod tests {
                         hnsw_config: None,
                         quantization_config: None,
                         on_disk: None,
+                        normalize: None,
+                        dimensionality_reduction: None,
+                        multivector_config: None,
+                        datatype: None,
                     },
                 ),
             ])),
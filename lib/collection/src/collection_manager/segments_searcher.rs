@@ -1,4 +1,5 @@
-use std::collections::HashMap;
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, HashSet};
 use std::ops::Deref;
 use std::sync::atomic::AtomicBool;
 use std::sync::Arc;
@@ -10,21 +11,22 @@ use ordered_float::Float;
 use parking_lot::RwLock;
 use segment::common::operation_error::OperationError;
 use segment::common::BYTES_IN_KB;
-use segment::data_types::named_vectors::NamedVectors;
 use segment::data_types::vectors::QueryVector;
 use segment::entry::entry_point::SegmentEntry;
 use segment::types::{
-    Filter, Indexes, PointIdType, ScoredPoint, SearchParams, SegmentConfig, SeqNumberType,
-    WithPayload, WithPayloadInterface, WithVector, VECTOR_ELEMENT_SIZE,
+    Condition, Filter, Indexes, PointIdType, QuantizationSearchParams, ScoredPoint, SearchParams,
+    SegmentConfig, SeqNumberType, WithPayload, WithPayloadInterface, WithVector,
+    VECTOR_ELEMENT_SIZE,
 };
 use tokio::runtime::Handle;
 use tokio::task::JoinHandle;
 
-use crate::collection_manager::holders::segment_holder::{LockedSegment, SegmentHolder};
+use crate::collection_manager::holders::segment_holder::{LockedSegment, SegmentHolder, SegmentId};
 use crate::collection_manager::probabilistic_segment_search_sampling::find_search_sampling_over_point_distribution;
 use crate::collection_manager::search_result_aggregator::BatchResultAggregator;
 use crate::operations::types::{
-    CollectionError, CollectionResult, CoreSearchRequestBatch, QueryEnum, Record,
+    CollectionError, CollectionResult, CorePrefetch, CoreSearchRequest, CoreSearchRequestBatch,
+    QueryEnum, Record,
 };
 
 type BatchOffset = usize;
@@ -38,27 +40,41 @@ type BatchSearchResult = Vec<SegmentBatchSearchResult>;
 // Result of batch search in one segment
 type SegmentSearchExecutedResult = CollectionResult<(SegmentBatchSearchResult, Vec<bool>)>;
 
+// Results of batch searches in the segments, searched sequentially by one task
+type SearchTaskResult = Vec<(SegmentOffset, SegmentSearchExecutedResult)>;
+
+/// Minimal number of points to search in one task.
+/// Segments smaller than that are searched together, so on fragmented shards
+/// scheduling overhead doesn't outweigh the search itself.
+const MIN_POINTS_PER_SEARCH_TASK: usize = 10_000;
+
 /// Simple implementation of segment manager
 ///  - rebuild segment for memory optimization purposes
 #[derive(Default)]
 pub struct SegmentsSearcher {}
 
 impl SegmentsSearcher {
+    /// Wait for the search tasks and collect results of their segment searches
+    ///
+    /// The resulting Vec is in the order of segment offsets, `0..searches_count`.
     async fn execute_searches(
-        searches: Vec<JoinHandle<SegmentSearchExecutedResult>>,
+        searches_count: usize,
+        tasks: Vec<JoinHandle<SearchTaskResult>>,
     ) -> CollectionResult<(BatchSearchResult, Vec<Vec<bool>>)> {
-        let searches = try_join_all(searches);
-        let search_results_per_segment_res = searches.await?;
+        let tasks_results = try_join_all(tasks).await?;
 
-        let mut search_results_per_segment = vec![];
-        let mut further_searches_per_segment = vec![];
-        for search_result in search_results_per_segment_res {
+        let mut results: Vec<Option<(SegmentBatchSearchResult, Vec<bool>)>> =
+            (0..searches_count).map(|_| None).collect();
+        for (segment_idx, search_result) in tasks_results.into_iter().flatten() {
             let (search_results, further_searches) = search_result?;
             debug_assert!(search_results.len() == further_searches.len());
-            search_results_per_segment.push(search_results);
-            further_searches_per_segment.push(further_searches);
+            results[segment_idx] = Some((search_results, further_searches));
         }
-        Ok((search_results_per_segment, further_searches_per_segment))
+
+        Ok(results
+            .into_iter()
+            .map(|result| result.expect("all segments are searched"))
+            .unzip())
     }
 
     /// Processes search result of [segment_size x batch_size]
@@ -151,6 +167,9 @@ impl SegmentsSearcher {
         (result_aggregator, searches_to_rerun)
     }
 
+    /// Search all segments of the shard
+    ///
+    /// Segments are searched by up to `max_search_tasks` concurrent tasks, see [`plan_search_tasks`].
     pub async fn search(
         segments: Arc<RwLock<SegmentHolder>>,
         batch_request: Arc<CoreSearchRequestBatch>,
@@ -158,6 +177,7 @@ impl SegmentsSearcher {
         sampling_enabled: bool,
         is_stopped: Arc<AtomicBool>,
         search_optimized_threshold_kb: usize,
+        max_search_tasks: usize,
     ) -> CollectionResult<Vec<Vec<ScoredPoint>>> {
         // Do blocking calls in a blocking task: `segment.get().read()` calls might block async runtime
         let task = {
@@ -167,21 +187,28 @@ impl SegmentsSearcher {
                 let segments = segments.read();
 
                 if !segments.is_empty() {
-                    let available_point_count = segments
+                    let segment_sizes: HashMap<SegmentId, usize> = segments
                         .iter()
-                        .map(|(_, segment)| segment.get().read().available_point_count())
-                        .sum();
+                        .map(|(&id, segment)| (id, segment.get().read().available_point_count()))
+                        .collect();
 
-                    Some(available_point_count)
+                    Some(segment_sizes)
                 } else {
                     None
                 }
             })
         };
 
-        let Some(available_point_count) = task.await? else {
-            return Ok(Vec::new());
+        let Some(segment_sizes) = task.await? else {
+            return Ok(vec![]);
         };
+        let available_point_count = segment_sizes.values().sum();
+
+        let limits: Vec<_> = batch_request
+            .searches
+            .iter()
+            .map(|request| request.limit + request.offset)
+            .collect();
 
         // Using block to ensure `segments` variable is dropped in the end of it
         let (locked_segments, searches): (Vec<_>, Vec<_>) = {
@@ -198,41 +225,56 @@ impl SegmentsSearcher {
             // - segments are not empty
             let use_sampling = sampling_enabled && segments.len() > 1 && available_point_count > 0;
 
-            segments
+            let locked_segments: Vec<_> = segments
                 .iter()
-                .map(|(_id, segment)| {
-                    let search = runtime_handle.spawn_blocking({
-                        let (segment, batch_request) = (segment.clone(), batch_request.clone());
-                        let is_stopped_clone = is_stopped.clone();
-                        move || {
-                            search_in_segment(
-                                segment,
-                                batch_request,
-                                available_point_count,
-                                use_sampling,
-                                &is_stopped_clone,
-                                search_optimized_threshold_kb,
-                            )
-                        }
-                    });
-                    (segment.clone(), search)
+                .map(|(_id, segment)| segment.clone())
+                .collect();
+            // Segments, which were swapped by the optimizer since their sizes were read,
+            // are considered empty, it only affects the balance of the tasks
+            let sizes: Vec<_> = segments
+                .iter()
+                .map(|(id, _segment)| segment_sizes.get(id).copied().unwrap_or(0))
+                .collect();
+
+            let searches = plan_search_tasks(&sizes, max_search_tasks)
+                .into_iter()
+                .map(|segment_offsets| {
+                    let task_segments: Vec<_> = segment_offsets
+                        .into_iter()
+                        .map(|offset| (offset, locked_segments[offset].clone()))
+                        .collect();
+                    let batch_request = batch_request.clone();
+                    let is_stopped_clone = is_stopped.clone();
+                    runtime_handle.spawn_blocking(move || {
+                        task_segments
+                            .into_iter()
+                            .map(|(offset, segment)| {
+                                let result = search_in_segment(
+                                    segment,
+                                    batch_request.clone(),
+                                    available_point_count,
+                                    use_sampling,
+                                    &is_stopped_clone,
+                                    search_optimized_threshold_kb,
+                                );
+                                (offset, result)
+                            })
+                            .collect::<SearchTaskResult>()
+                    })
                 })
-                .unzip()
+                .collect();
+            (locked_segments, searches)
         };
 
         // perform search on all segments concurrently
-        // the resulting Vec is in the same order as the segment searches were provided.
+        // the resulting Vec is in the same order as the segments were provided.
         let (all_search_results_per_segment, further_results) =
-            Self::execute_searches(searches).await?;
+            Self::execute_searches(locked_segments.len(), searches).await?;
         debug_assert!(all_search_results_per_segment.len() == locked_segments.len());
 
         let (mut result_aggregator, searches_to_rerun) = Self::process_search_result_step1(
             all_search_results_per_segment,
-            batch_request
-                .searches
-                .iter()
-                .map(|request| request.limit + request.offset)
-                .collect(),
+            limits,
             further_results,
         );
         // The second step of the search is to re-run the search without sampling on some segments
@@ -245,7 +287,7 @@ impl SegmentsSearcher {
 
             let secondary_searches: Vec<_> = {
                 let mut res = vec![];
-                for (segment_id, batch_ids) in searches_to_rerun.iter() {
+                for (rerun_idx, (segment_id, batch_ids)) in searches_to_rerun.iter().enumerate() {
                     let segment = locked_segments[*segment_id].clone();
                     let partial_batch_request = Arc::new(CoreSearchRequestBatch {
                         searches: batch_ids
@@ -255,21 +297,22 @@ impl SegmentsSearcher {
                     });
                     let is_stopped_clone = is_stopped.clone();
                     res.push(runtime_handle.spawn_blocking(move || {
-                        search_in_segment(
+                        let result = search_in_segment(
                             segment,
                             partial_batch_request,
                             0,
                             false,
                             &is_stopped_clone,
                             search_optimized_threshold_kb,
-                        )
+                        );
+                        vec![(rerun_idx, result)]
                     }))
                 }
                 res
             };
 
             let (secondary_search_results_per_segment, _) =
-                Self::execute_searches(secondary_searches).await?;
+                Self::execute_searches(searches_to_rerun.len(), secondary_searches).await?;
 
             result_aggregator.update_point_versions(&secondary_search_results_per_segment);
 
@@ -299,45 +342,42 @@ impl SegmentsSearcher {
         let mut point_version: HashMap<PointIdType, SeqNumberType> = Default::default();
         let mut point_records: HashMap<PointIdType, Record> = Default::default();
 
-        segments.read().read_points(points, |id, segment| {
-            let version = segment.point_version(id).ok_or_else(|| {
-                OperationError::service_error(format!("No version for point {id}"))
-            })?;
-            // If this point was not found yet or this segment have later version
-            if !point_version.contains_key(&id) || point_version[&id] < version {
-                point_records.insert(
-                    id,
-                    Record {
-                        id,
-                        payload: if with_payload.enable {
-                            if let Some(selector) = &with_payload.payload_selector {
-                                Some(selector.process(segment.payload(id)?))
-                            } else {
-                                Some(segment.payload(id)?)
-                            }
-                        } else {
-                            None
-                        },
-                        vector: match with_vector {
-                            WithVector::Bool(true) => Some(segment.all_vectors(id)?.into()),
-                            WithVector::Bool(false) => None,
-                            WithVector::Selector(vector_names) => {
-                                let mut selected_vectors = NamedVectors::default();
-                                for vector_name in vector_names {
-                                    if let Some(vector) = segment.vector(vector_name, id)? {
-                                        selected_vectors.insert(vector_name.into(), vector);
-                                    }
-                                }
-                                Some(selected_vectors.into())
-                            }
+        segments
+            .read()
+            .read_points_per_segment(points, |segment_points, segment| {
+                // Points, for which this segment has the latest version seen so far
+                let mut newer_points: HashMap<PointIdType, SeqNumberType> = Default::default();
+                for &id in segment_points {
+                    let version = segment.point_version(id).ok_or_else(|| {
+                        OperationError::service_error(format!("No version for point {id}"))
+                    })?;
+                    if point_version
+                        .get(&id)
+                        .map_or(true, |&known| known < version)
+                    {
+                        newer_points.insert(id, version);
+                    }
+                }
+
+                let ids: Vec<_> = newer_points.keys().copied().collect();
+                let with_vector = segment_vectors_selector(segment.deref(), with_vector);
+                for point in segment.retrieve(&ids, with_payload, &with_vector)? {
+                    let version = newer_points[&point.id];
+                    point_records.insert(
+                        point.id,
+                        Record {
+                            id: point.id,
+                            version: Some(version),
+                            payload: point.payload,
+                            vector: point.vector,
+                            shard_key: None,
+                            order_value: None,
                         },
-                        shard_key: None,
-                    },
-                );
-                point_version.insert(id, version);
-            }
-            Ok(true)
-        })?;
+                    );
+                    point_version.insert(point.id, version);
+                }
+                Ok(())
+            })?;
         Ok(point_records.into_values().collect())
     }
 }
@@ -373,6 +413,36 @@ struct BatchSearchParams<'a> {
     pub params: Option<&'a SearchParams>,
 }
 
+/// Group segments into search tasks, each of which searches its segments one by one
+///
+/// The number of tasks is limited by `max_tasks`, and by the total number of points, so that
+/// each task searches at least [`MIN_POINTS_PER_SEARCH_TASK`] points. Segments are assigned to the
+/// least loaded task, starting from the largest one, to balance the number of points per task.
+///
+/// Returns offsets of the segments in `segment_sizes`, grouped by task.
+fn plan_search_tasks(segment_sizes: &[usize], max_tasks: usize) -> Vec<Vec<SegmentOffset>> {
+    if segment_sizes.is_empty() {
+        return vec![];
+    }
+    let total_points: usize = segment_sizes.iter().sum();
+    let tasks_count = segment_sizes
+        .len()
+        .min(max_tasks)
+        .min(total_points.div_ceil(MIN_POINTS_PER_SEARCH_TASK))
+        .max(1);
+
+    let mut tasks = vec![vec![]; tasks_count];
+    let mut task_loads: BinaryHeap<_> = (0..tasks_count).map(|task| Reverse((0, task))).collect();
+    let largest_first =
+        (0..segment_sizes.len()).sorted_by_key(|&offset| Reverse(segment_sizes[offset]));
+    for offset in largest_first {
+        let Reverse((load, task)) = task_loads.pop().expect("at least one task");
+        tasks[task].push(offset);
+        task_loads.push(Reverse((load + segment_sizes[offset], task)));
+    }
+    tasks
+}
+
 /// Returns suggested search sampling size for a given number of points and required limit.
 fn sampling_limit(
     limit: usize,
@@ -432,6 +502,38 @@ fn search_in_segment(
     let mut prev_params = BatchSearchParams::default();
 
     for search_query in &request.searches {
+        if let Some(prefetch) = &search_query.prefetch {
+            // Searches with a prefetch stage are not batched,
+            // execute what has been batched so far
+            if !vectors_batch.is_empty() {
+                let (mut res, mut further) = execute_batch_search(
+                    &segment,
+                    &vectors_batch,
+                    &prev_params,
+                    use_sampling,
+                    total_points,
+                    is_stopped,
+                    search_optimized_threshold_kb,
+                )?;
+                further_results.append(&mut further);
+                result.append(&mut res);
+                vectors_batch.clear();
+            }
+            let (res, further) = execute_prefetch_search(
+                &segment,
+                search_query,
+                prefetch,
+                use_sampling,
+                total_points,
+                is_stopped,
+                search_optimized_threshold_kb,
+            )?;
+            further_results.push(further);
+            result.push(res);
+            prev_params = BatchSearchParams::default();
+            continue;
+        }
+
         let with_payload_interface = search_query
             .with_payload
             .as_ref()
@@ -467,7 +569,7 @@ fn search_in_segment(
                 )?;
                 further_results.append(&mut further);
                 result.append(&mut res);
-                vectors_batch.clear()
+                vectors_batch.clear();
             }
             // start new batch for current search query
             vectors_batch.push(query);
@@ -493,6 +595,82 @@ fn search_in_segment(
     Ok((result, further_results))
 }
 
+/// Select candidates with the prefetch query, and score only them with the query of the request
+///
+/// Candidates are scored exactly, with the original vectors. Both stages are executed on the same
+/// segment, so only the best `limit + offset` points of the segment are returned.
+///
+/// # Returns
+///
+/// * Scored points of the request
+/// * Whether the first stage has further candidates, which were not selected due to sampling
+fn execute_prefetch_search(
+    segment: &LockedSegment,
+    request: &CoreSearchRequest,
+    prefetch: &CorePrefetch,
+    use_sampling: bool,
+    total_points: usize,
+    is_stopped: &AtomicBool,
+    search_optimized_threshold_kb: usize,
+) -> CollectionResult<(Vec<ScoredPoint>, bool)> {
+    let prefetch_params = BatchSearchParams {
+        search_type: prefetch.query.as_ref().into(),
+        vector_name: prefetch.query.get_vector_name(),
+        filter: request.filter.as_ref(),
+        with_payload: WithPayload::from(false),
+        with_vector: WithVector::Bool(false),
+        top: prefetch.limit,
+        params: prefetch.params.as_ref(),
+    };
+    let (candidates, further) = execute_batch_search(
+        segment,
+        &vec![prefetch.query.clone().into()],
+        &prefetch_params,
+        use_sampling,
+        total_points,
+        is_stopped,
+        search_optimized_threshold_kb,
+    )?;
+    let further = further.into_iter().any(|further| further);
+    let candidates: HashSet<PointIdType> = candidates.into_iter().flatten().map(|p| p.id).collect();
+    if candidates.is_empty() {
+        return Ok((vec![], further));
+    }
+
+    let candidates_filter = Filter::new_must(Condition::HasId(candidates.into()));
+    let rescore_params = SearchParams {
+        exact: true,
+        quantization: Some(QuantizationSearchParams {
+            ignore: true,
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
+    let params = BatchSearchParams {
+        search_type: request.query.as_ref().into(),
+        vector_name: request.query.get_vector_name(),
+        filter: Some(&candidates_filter),
+        with_payload: request
+            .with_payload
+            .as_ref()
+            .map(WithPayload::from)
+            .unwrap_or_default(),
+        with_vector: request.with_vector.clone().unwrap_or_default(),
+        top: request.limit + request.offset,
+        params: Some(&rescore_params),
+    };
+    let (result, _) = execute_batch_search(
+        segment,
+        &vec![request.query.clone().into()],
+        &params,
+        false,
+        total_points,
+        is_stopped,
+        search_optimized_threshold_kb,
+    )?;
+    Ok((result.into_iter().flatten().collect(), further))
+}
+
 fn execute_batch_search(
     segment: &LockedSegment,
     vectors_batch: &Vec<QueryVector>,
@@ -508,6 +686,12 @@ fn execute_batch_search(
     let segment_points = read_segment.available_point_count();
     let segment_config = read_segment.config();
 
+    // Segment was created before the vector was added to the collection, none of its points has it
+    if !segment_config.has_vector(search_params.vector_name) {
+        let batch_len = vectors_batch.len();
+        return Ok((vec![vec![]; batch_len], vec![false; batch_len]));
+    }
+
     let top = if use_sampling {
         let ef_limit = search_params
             .params
@@ -533,11 +717,12 @@ fn execute_batch_search(
         return Ok((vec![vec![]; batch_len], vec![false; batch_len]));
     }
     let vectors_batch = &vectors_batch.iter().collect_vec();
+    let with_vector = segment_vectors_selector(read_segment.deref(), &search_params.with_vector);
     let res = read_segment.search_batch(
         search_params.vector_name,
         vectors_batch,
         &search_params.with_payload,
-        &search_params.with_vector,
+        &with_vector,
         search_params.filter,
         top,
         search_params.params,
@@ -552,6 +737,22 @@ fn execute_batch_search(
     Ok((res, further_results))
 }
 
+/// Select only vectors, which the segment has
+///
+/// Segments, created before a vector was added to the collection, don't have it.
+fn segment_vectors_selector(segment: &dyn SegmentEntry, with_vector: &WithVector) -> WithVector {
+    match with_vector {
+        WithVector::Bool(_) => with_vector.clone(),
+        WithVector::Selector(vector_names) => WithVector::Selector(
+            vector_names
+                .iter()
+                .filter(|vector_name| segment.config().has_vector(vector_name))
+                .cloned()
+                .collect(),
+        ),
+    }
+}
+
 /// Check if the segment is indexed enough to be searched with `indexed_only` parameter
 fn is_search_optimized(
     segment: &dyn SegmentEntry,
@@ -622,9 +823,9 @@ mod tests {
 
     use super::*;
     use crate::collection_manager::fixtures::{
-        build_test_holder, optimize_segment, random_segment,
+        build_segment_1, build_test_holder, optimize_segment, random_segment,
     };
-    use crate::operations::types::{CoreSearchRequest, SearchRequestInternal};
+    use crate::operations::types::SearchRequestInternal;
     use crate::optimizers_builder::DEFAULT_INDEXING_THRESHOLD_KB;
 
     #[test]
@@ -673,6 +874,9 @@ mod tests {
             limit: 5,
             score_threshold: None,
             offset: 0,
+            prefetch: None,
+            rescore: None,
+            distinct_by: None,
         };
 
         let batch_request = CoreSearchRequestBatch {
@@ -686,6 +890,7 @@ mod tests {
             true,
             Arc::new(AtomicBool::new(false)),
             DEFAULT_INDEXING_THRESHOLD_KB,
+            4,
         )
         .await
         .unwrap()
@@ -701,6 +906,100 @@ mod tests {
         assert!(result[1].id == 3.into() || result[1].id == 11.into());
     }
 
+    #[test]
+    fn test_prefetch_search() {
+        let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+        let segment = LockedSegment::new(build_segment_1(dir.path()));
+
+        let request = CoreSearchRequest {
+            query: vec![0.0, 1.0, 0.0, 0.0].into(),
+            with_payload: None,
+            with_vector: None,
+            filter: None,
+            params: None,
+            limit: 5,
+            score_threshold: None,
+            offset: 0,
+            prefetch: Some(CorePrefetch {
+                query: vec![0.0, 0.0, 0.0, 1.0].into(),
+                params: None,
+                limit: 3,
+            }),
+            rescore: None,
+            distinct_by: None,
+        };
+        let batch = Arc::new(CoreSearchRequestBatch {
+            searches: vec![request.clone(), request],
+        });
+
+        let (result, further) = search_in_segment(
+            segment,
+            batch,
+            5,
+            false,
+            None,
+            &AtomicBool::new(false),
+            DEFAULT_INDEXING_THRESHOLD_KB,
+        )
+        .unwrap();
+        assert_eq!(further, vec![true, true]);
+        for points in result {
+            // Only the candidates of the prefetch: 1, 3 and 4 have the last component
+            let ids: Vec<_> = points.iter().map(|point| point.id).collect();
+            assert_eq!(ids.len(), 3);
+            assert!(ids[..2].contains(&3.into()) && ids[..2].contains(&4.into()));
+            assert_eq!(ids[2], 1.into());
+            assert_eq!(points[0].score, 1.0);
+            assert_eq!(points[2].score, 0.0);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_segments_search_with_payload_and_vector() {
+        let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+
+        let segment_holder = build_test_holder(dir.path());
+
+        let req = CoreSearchRequest {
+            query: vec![1.0, 1.0, 1.0, 1.0].into(),
+            with_payload: Some(WithPayloadInterface::Bool(true)),
+            with_vector: Some(WithVector::Bool(true)),
+            filter: None,
+            params: None,
+            limit: 5,
+            score_threshold: None,
+            offset: 0,
+            prefetch: None,
+            rescore: None,
+            distinct_by: None,
+        };
+
+        let batch_request = CoreSearchRequestBatch {
+            searches: vec![req],
+        };
+
+        let result = SegmentsSearcher::search(
+            Arc::new(segment_holder),
+            Arc::new(batch_request),
+            &Handle::current(),
+            true,
+            Arc::new(AtomicBool::new(false)),
+            DEFAULT_INDEXING_THRESHOLD_KB,
+            4,
+        )
+        .await
+        .unwrap()
+        .into_iter()
+        .next()
+        .unwrap();
+
+        assert_eq!(result.len(), 5);
+        for point in &result {
+            assert!(point.payload.is_some());
+            assert!(point.vector.is_some());
+        }
+    }
+
     #[tokio::test]
     async fn test_segments_search_sampling() {
         let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
@@ -752,6 +1051,7 @@ mod tests {
                 false,
                 Arc::new(false.into()),
                 DEFAULT_INDEXING_THRESHOLD_KB,
+                4,
             )
             .await
             .unwrap();
@@ -765,6 +1065,7 @@ mod tests {
                 true,
                 Arc::new(false.into()),
                 DEFAULT_INDEXING_THRESHOLD_KB,
+                4,
             )
             .await
             .unwrap();
@@ -797,6 +1098,28 @@ mod tests {
         assert_eq!(records.len(), 3);
     }
 
+    #[test]
+    fn test_plan_search_tasks() {
+        assert!(plan_search_tasks(&[], 4).is_empty());
+
+        // Small segments of a fragmented shard are searched by a single task
+        let tasks = plan_search_tasks(&[100, 200, 300, 0], 4);
+        assert_eq!(tasks, vec![vec![2, 1, 0, 3]]);
+
+        // Large segments are searched in parallel, up to the limit
+        let tasks = plan_search_tasks(&[50_000, 40_000, 30_000, 20_000, 10_000], 3);
+        assert_eq!(tasks, vec![vec![0], vec![1, 4], vec![2, 3]]);
+
+        let tasks = plan_search_tasks(&[50_000, 50_000], 1);
+        assert_eq!(tasks, vec![vec![0, 1]]);
+
+        // Each segment is searched exactly once
+        let sizes = [15_000, 3, 70_000, 8_000, 0, 25_000, 900];
+        let mut offsets = plan_search_tasks(&sizes, 8).concat();
+        offsets.sort_unstable();
+        assert_eq!(offsets, (0..sizes.len()).collect_vec());
+    }
+
     #[test]
     fn test_sampling_limit() {
         assert_eq!(sampling_limit(1000, None, 464530, 35103551), 30);
@@ -2,21 +2,24 @@
 
 use std::collections::{HashMap, HashSet};
 
-use parking_lot::{RwLock, RwLockWriteGuard};
+use itertools::Itertools as _;
+use parking_lot::RwLockWriteGuard;
 use segment::common::operation_error::{OperationError, OperationResult};
 use segment::data_types::named_vectors::NamedVectors;
+use segment::data_types::vectors::VectorStruct;
 use segment::entry::entry_point::SegmentEntry;
 use segment::types::{
     Filter, Payload, PayloadFieldSchema, PayloadKeyType, PayloadKeyTypeRef, PointIdType,
-    SeqNumberType,
+    SeqNumberType, WithPayload, WithVector,
 };
 
 use crate::collection_manager::holders::segment_holder::SegmentHolder;
+use crate::operations::operation_effect::{EstimateOperationEffectArea, OperationEffectArea};
 use crate::operations::payload_ops::PayloadOps;
 use crate::operations::point_ops::{PointInsertOperationsInternal, PointOperations, PointStruct};
 use crate::operations::types::{CollectionError, CollectionResult};
 use crate::operations::vector_ops::{PointVectors, VectorOperations};
-use crate::operations::FieldIndexOperations;
+use crate::operations::{CollectionUpdateOperations, FieldIndexOperations};
 
 pub(crate) fn check_unprocessed_points(
     points: &[PointIdType],
@@ -30,6 +33,58 @@ pub(crate) fn check_unprocessed_points(
     }
 }
 
+/// Rejects the whole update, if the version of any point differs from its `if_version`
+///
+/// The version of the point is the number of the last operation, which updated it, as tracked by
+/// the segments. Points, which don't exist, have version 0. Versions are only looked up for the
+/// points with `if_version`, unconditional updates don't read anything.
+///
+/// Points, already updated by this operation, are never conflicting, so the operation could be
+/// re-applied during WAL recovery. Checked before any point is updated, so the update is either
+/// applied to all points or to none of them.
+fn check_versions(
+    segments: &SegmentHolder,
+    op_num: SeqNumberType,
+    points: &[PointIdType],
+    if_version: impl Fn(PointIdType) -> Option<SeqNumberType>,
+) -> CollectionResult<()> {
+    let conditional_points: Vec<_> = points
+        .iter()
+        .copied()
+        .filter(|&id| if_version(id).is_some())
+        .collect();
+    if conditional_points.is_empty() {
+        return Ok(());
+    }
+
+    // Point might be present in multiple segments, the latest version is the actual one
+    let mut versions: HashMap<PointIdType, SeqNumberType> = HashMap::new();
+    segments.read_points(&conditional_points, |id, segment| {
+        if let Some(version) = segment.point_version(id) {
+            let latest = versions.entry(id).or_insert(version);
+            *latest = (*latest).max(version);
+        }
+        Ok(true)
+    })?;
+
+    let conflicts = conditional_points
+        .into_iter()
+        .filter(|&id| match (versions.get(&id).copied(), if_version(id)) {
+            (Some(version), _) if segments.is_applied(op_num, id, version) => false,
+            // Version 0 requires the point not to exist
+            (version, Some(0)) => version.is_some(),
+            (version, expected) => version != expected,
+        })
+        .sorted()
+        .collect_vec();
+    if conflicts.is_empty() {
+        return Ok(());
+    }
+    Err(CollectionError::bad_request(format!(
+        "Version conflict, current versions of points {conflicts:?} differ from `if_version`, no points were updated",
+    )))
+}
+
 /// Tries to delete points from all segments, returns number of actually deleted points
 pub(crate) fn delete_points(
     segments: &SegmentHolder,
@@ -52,12 +107,17 @@ pub(crate) fn update_vectors(
     let points_map: HashMap<PointIdType, &PointVectors> =
         points.iter().map(|p| (p.id, p)).collect();
     let ids: Vec<PointIdType> = points_map.keys().copied().collect();
+    let vector_names = vector_names(points.iter().map(|p| &p.vector));
 
-    let updated_points =
-        segments.apply_points_to_appendable(op_num, &ids, |id, write_segment| {
+    let updated_points = segments.apply_points_with_vectors_to_appendable(
+        op_num,
+        &ids,
+        &vector_names,
+        |id, write_segment| {
             let vectors = points_map[&id].vector.clone().into_all_vectors();
             write_segment.update_vectors(op_num, id, vectors)
-        })?;
+        },
+    )?;
     check_unprocessed_points(&ids, &updated_points)?;
     Ok(updated_points.len())
 }
@@ -73,6 +133,10 @@ pub(crate) fn delete_vectors(
         .apply_points(points, |id, _idx, write_segment| {
             let mut res = true;
             for name in vector_names {
+                // Segment, created before the vector was added to the collection, has no such vector
+                if !write_segment.config().has_vector(name) {
+                    continue;
+                }
                 res &= write_segment.delete_vector(op_num, id, name)?;
             }
             Ok(res)
@@ -96,7 +160,10 @@ pub(crate) fn overwrite_payload(
     op_num: SeqNumberType,
     payload: &Payload,
     points: &[PointIdType],
+    if_version: Option<SeqNumberType>,
 ) -> CollectionResult<usize> {
+    check_versions(segments, op_num, points, |_| if_version)?;
+
     let updated_points =
         segments.apply_points_to_appendable(op_num, points, |id, write_segment| {
             write_segment.set_full_payload(op_num, id, payload)
@@ -111,9 +178,10 @@ pub(crate) fn overwrite_payload_by_filter(
     op_num: SeqNumberType,
     payload: &Payload,
     filter: &Filter,
+    if_version: Option<SeqNumberType>,
 ) -> CollectionResult<usize> {
     let affected_points = points_by_filter(segments, filter)?;
-    overwrite_payload(segments, op_num, payload, &affected_points)
+    overwrite_payload(segments, op_num, payload, &affected_points, if_version)
 }
 
 pub(crate) fn set_payload(
@@ -121,7 +189,10 @@ pub(crate) fn set_payload(
     op_num: SeqNumberType,
     payload: &Payload,
     points: &[PointIdType],
+    if_version: Option<SeqNumberType>,
 ) -> CollectionResult<usize> {
+    check_versions(segments, op_num, points, |_| if_version)?;
+
     let updated_points =
         segments.apply_points_to_appendable(op_num, points, |id, write_segment| {
             write_segment.set_payload(op_num, id, payload)
@@ -149,9 +220,10 @@ pub(crate) fn set_payload_by_filter(
     op_num: SeqNumberType,
     payload: &Payload,
     filter: &Filter,
+    if_version: Option<SeqNumberType>,
 ) -> CollectionResult<usize> {
     let affected_points = points_by_filter(segments, filter)?;
-    set_payload(segments, op_num, payload, &affected_points)
+    set_payload(segments, op_num, payload, &affected_points, if_version)
 }
 
 pub(crate) fn delete_payload(
@@ -333,8 +405,18 @@ pub(crate) fn sync_points(
     Ok((deleted, num_new, num_updated))
 }
 
+/// Names of all vectors, written by the update
+fn vector_names<'a>(vectors: impl Iterator<Item = &'a VectorStruct>) -> Vec<String> {
+    vectors
+        .flat_map(VectorStruct::names)
+        .unique()
+        .map(str::to_owned)
+        .collect()
+}
+
 /// Checks point id in each segment, update point if found.
 /// All not found points are inserted into random segment.
+/// If `if_version` of any point differs from its current version, no points are updated.
 /// Returns: number of updated points.
 pub(crate) fn upsert_points<'a, T>(
     segments: &SegmentHolder,
@@ -348,9 +430,25 @@ where
         points.into_iter().map(|p| (p.id, p)).collect();
     let ids: Vec<PointIdType> = points_map.keys().copied().collect();
 
+    check_versions(segments, op_num, &ids, |id| points_map[&id].if_version)?;
+    write_points(segments, op_num, &points_map)
+}
+
+/// Upserts points, new points are inserted into random segment
+fn write_points(
+    segments: &SegmentHolder,
+    op_num: SeqNumberType,
+    points_map: &HashMap<PointIdType, &PointStruct>,
+) -> CollectionResult<usize> {
+    let ids: Vec<PointIdType> = points_map.keys().copied().collect();
+    let vector_names = vector_names(points_map.values().map(|p| &p.vector));
+
     // Update points in writable segments
-    let updated_points =
-        segments.apply_points_to_appendable(op_num, &ids, |id, write_segment| {
+    let updated_points = segments.apply_points_with_vectors_to_appendable(
+        op_num,
+        &ids,
+        &vector_names,
+        |id, write_segment| {
             let point = points_map[&id];
             upsert_with_payload(
                 write_segment,
@@ -359,7 +457,8 @@ where
                 point.get_vectors(),
                 point.payload.as_ref(),
             )
-        })?;
+        },
+    )?;
 
     let mut res = updated_points.len();
     // Insert new points, which was not updated or existed
@@ -369,9 +468,13 @@ where
         .filter(|x| !(updated_points.contains(x)));
 
     {
-        let default_write_segment = segments.random_appendable_segment().ok_or_else(|| {
-            CollectionError::service_error("No segments exists, expected at least one".to_string())
-        })?;
+        let default_write_segment = segments
+            .random_appendable_segment_with_vectors(&vector_names)
+            .ok_or_else(|| {
+                CollectionError::service_error(format!(
+                    "No appendable segments with vectors {vector_names:?} exist, expected at least one",
+                ))
+            })?;
 
         let segment_arc = default_write_segment.get();
         let mut write_segment = segment_arc.write();
@@ -391,13 +494,136 @@ where
     Ok(res)
 }
 
+/// Maximal number of points, which a batch of operations might change
+///
+/// Points are backed up in memory before the batch is applied, to roll it back on failure.
+const MAX_BATCH_POINTS: usize = 10_000;
+
+/// Points, affected by the update operations, as they were before the operations are applied
+///
+/// Allows to roll back a batch of operations, which failed partway.
+pub(crate) struct PointsBackup {
+    /// Points, which existed before the update, with all their vectors and payload
+    points: Vec<PointStruct>,
+    /// Points, which didn't exist before the update
+    missing: Vec<PointIdType>,
+    /// Points, to which the operation was already applied, e.g. before the restart
+    applied: HashSet<PointIdType>,
+}
+
+impl PointsBackup {
+    pub(crate) fn new(
+        segments: &SegmentHolder,
+        op_num: SeqNumberType,
+        operations: &[CollectionUpdateOperations],
+    ) -> CollectionResult<Self> {
+        let mut ids = HashSet::new();
+        affected_points(segments, operations, &mut ids)?;
+        let ids: Vec<_> = ids.into_iter().collect();
+
+        // Point might be present in multiple segments, the one updated by the latest operation
+        // is the actual one
+        let mut latest: HashMap<PointIdType, (SeqNumberType, PointStruct)> = HashMap::new();
+        segments.read_points_per_segment(&ids, |segment_points, segment| {
+            for point in segment.retrieve(
+                segment_points,
+                &WithPayload::from(true),
+                &WithVector::Bool(true),
+            )? {
+                let Some(op_version) = segment.point_version(point.id) else {
+                    continue;
+                };
+                if latest
+                    .get(&point.id)
+                    .map_or(true, |(known, _)| *known < op_version)
+                {
+                    let point = PointStruct {
+                        id: point.id,
+                        vector: point
+                            .vector
+                            .unwrap_or_else(|| VectorStruct::Multi(HashMap::new())),
+                        payload: Some(point.payload.unwrap_or_default()),
+                        if_version: None,
+                    };
+                    latest.insert(point.id, (op_version, point));
+                }
+            }
+            Ok(())
+        })?;
+
+        let missing = ids
+            .into_iter()
+            .filter(|id| !latest.contains_key(id))
+            .collect();
+        let applied = latest
+            .iter()
+            .filter(|(_, (op_version, _))| *op_version >= op_num)
+            .map(|(&id, _)| id)
+            .collect();
+        let points = latest.into_values().map(|(_, point)| point).collect();
+        Ok(Self {
+            points,
+            missing,
+            applied,
+        })
+    }
+
+    /// Points, to which the operation was already applied, e.g. before the restart
+    pub(crate) fn applied_points(&self) -> &HashSet<PointIdType> {
+        &self.applied
+    }
+
+    /// Write the points back as they were, under the number of the rolled back operation
+    pub(crate) fn restore(
+        &self,
+        segments: &SegmentHolder,
+        op_num: SeqNumberType,
+    ) -> CollectionResult<()> {
+        delete_points(segments, op_num, &self.missing)?;
+        if !self.points.is_empty() {
+            let points_map = self.points.iter().map(|p| (p.id, p)).collect();
+            write_points(segments, op_num, &points_map)?;
+        }
+        Ok(())
+    }
+}
+
+/// Collect points, which might be changed by the `operations`
+///
+/// Points, selected by filters, are resolved before any of the operations is applied. Points,
+/// which match the filters only after the previous operations, are changed by these operations,
+/// so they are already collected.
+fn affected_points(
+    segments: &SegmentHolder,
+    operations: &[CollectionUpdateOperations],
+    ids: &mut HashSet<PointIdType>,
+) -> CollectionResult<()> {
+    for operation in operations {
+        if let CollectionUpdateOperations::Batch(operations) = operation {
+            affected_points(segments, operations, ids)?;
+            continue;
+        }
+        match operation.estimate_effect_area() {
+            OperationEffectArea::Empty => {}
+            OperationEffectArea::Points(points) => ids.extend(points),
+            OperationEffectArea::Filter(filter) => ids.extend(points_by_filter(segments, &filter)?),
+        }
+        if ids.len() > MAX_BATCH_POINTS {
+            return Err(CollectionError::BatchTooLarge {
+                max_points: MAX_BATCH_POINTS,
+            });
+        }
+    }
+    Ok(())
+}
+
 pub(crate) fn process_point_operation(
-    segments: &RwLock<SegmentHolder>,
+    segments: &SegmentHolder,
     op_num: SeqNumberType,
     point_operation: PointOperations,
 ) -> CollectionResult<usize> {
     match point_operation {
-        PointOperations::DeletePoints { ids, .. } => delete_points(&segments.read(), op_num, &ids),
+        PointOperations::DeletePoints { ids, .. } => delete_points(segments, op_num, &ids),
         PointOperations::UpsertPoints(operation) => {
             let points: Vec<_> = match operation {
                 PointInsertOperationsInternal::PointsBatch(batch) => {
@@ -409,6 +635,7 @@ pub(crate) fn process_point_operation(
                                 id,
                                 vector: vectors.into(),
                                 payload: None,
+                                if_version: None,
                             })
                             .collect(),
                         Some(payloads) => vectors_iter
@@ -417,21 +644,22 @@ pub(crate) fn process_point_operation(
                                 id,
                                 vector: vectors.into(),
                                 payload,
+                                if_version: None,
                             })
                             .collect(),
                     }
                 }
                 PointInsertOperationsInternal::PointsList(points) => points,
             };
-            let res = upsert_points(&segments.read(), op_num, points.iter())?;
+            let res = upsert_points(segments, op_num, points.iter())?;
             Ok(res)
         }
         PointOperations::DeletePointsByFilter(filter) => {
-            delete_points_by_filter(&segments.read(), op_num, &filter)
+            delete_points_by_filter(segments, op_num, &filter)
         }
         PointOperations::SyncPoints(operation) => {
             let (deleted, new, updated) = sync_points(
-                &segments.read(),
+                segments,
                 op_num,
                 operation.from_id,
                 operation.to_id,
@@ -443,25 +671,25 @@ pub(crate) fn process_point_operation(
 }
 
 pub(crate) fn process_vector_operation(
-    segments: &RwLock<SegmentHolder>,
+    segments: &SegmentHolder,
     op_num: SeqNumberType,
     vector_operation: VectorOperations,
 ) -> CollectionResult<usize> {
     match vector_operation {
         VectorOperations::UpdateVectors(operation) => {
-            update_vectors(&segments.read(), op_num, &operation.points)
+            update_vectors(segments, op_num, &operation.points)
         }
         VectorOperations::DeleteVectors(ids, vector_names) => {
-            delete_vectors(&segments.read(), op_num, &ids.points, &vector_names)
+            delete_vectors(segments, op_num, &ids.points, &vector_names)
         }
         VectorOperations::DeleteVectorsByFilter(filter, vector_names) => {
-            delete_vectors_by_filter(&segments.read(), op_num, &filter, &vector_names)
+            delete_vectors_by_filter(segments, op_num, &filter, &vector_names)
         }
     }
 }
 
 pub(crate) fn process_payload_operation(
-    segments: &RwLock<SegmentHolder>,
+    segments: &SegmentHolder,
     op_num: SeqNumberType,
     payload_operation: PayloadOps,
 ) -> CollectionResult<usize> {
@@ -469,9 +697,9 @@ pub(crate) fn process_payload_operation(
         PayloadOps::SetPayload(sp) => {
             let payload: Payload = sp.payload;
             if let Some(points) = sp.points {
-                set_payload(&segments.read(), op_num, &payload, &points)
+                set_payload(segments, op_num, &payload, &points, sp.if_version)
             } else if let Some(filter) = sp.filter {
-                set_payload_by_filter(&segments.read(), op_num, &payload, &filter)
+                set_payload_by_filter(segments, op_num, &payload, &filter, sp.if_version)
             } else {
                 Err(CollectionError::BadRequest {
                     description: "No points or filter specified".to_string(),
@@ -480,27 +708,25 @@ pub(crate) fn process_payload_operation(
         }
         PayloadOps::DeletePayload(dp) => {
             if let Some(points) = dp.points {
-                delete_payload(&segments.read(), op_num, &points, &dp.keys)
+                delete_payload(segments, op_num, &points, &dp.keys)
             } else if let Some(filter) = dp.filter {
-                delete_payload_by_filter(&segments.read(), op_num, &filter, &dp.keys)
+                delete_payload_by_filter(segments, op_num, &filter, &dp.keys)
             } else {
                 Err(CollectionError::BadRequest {
                     description: "No points or filter specified".to_string(),
                 })
             }
         }
-        PayloadOps::ClearPayload { ref points, .. } => {
-            clear_payload(&segments.read(), op_num, points)
-        }
+        PayloadOps::ClearPayload { ref points, .. } => clear_payload(segments, op_num, points),
         PayloadOps::ClearPayloadByFilter(ref filter) => {
-            clear_payload_by_filter(&segments.read(), op_num, filter)
+            clear_payload_by_filter(segments, op_num, filter)
         }
         PayloadOps::OverwritePayload(sp) => {
             let payload: Payload = sp.payload;
             if let Some(points) = sp.points {
-                overwrite_payload(&segments.read(), op_num, &payload, &points)
+                overwrite_payload(segments, op_num, &payload, &points, sp.if_version)
             } else if let Some(filter) = sp.filter {
-                overwrite_payload_by_filter(&segments.read(), op_num, &payload, &filter)
+                overwrite_payload_by_filter(segments, op_num, &payload, &filter, sp.if_version)
             } else {
                 Err(CollectionError::BadRequest {
                     description: "No points or filter specified".to_string(),
@@ -511,19 +737,19 @@ pub(crate) fn process_payload_operation(
 }
 
 pub(crate) fn process_field_index_operation(
-    segments: &RwLock<SegmentHolder>,
+    segments: &SegmentHolder,
     op_num: SeqNumberType,
     field_index_operation: &FieldIndexOperations,
 ) -> CollectionResult<usize> {
     match field_index_operation {
         FieldIndexOperations::CreateIndex(index_data) => create_field_index(
-            &segments.read(),
+            segments,
             op_num,
             &index_data.field_name,
             index_data.field_schema.as_ref(),
         ),
         FieldIndexOperations::DeleteIndex(field_name) => {
-            delete_field_index(&segments.read(), op_num, field_name)
+            delete_field_index(segments, op_num, field_name)
         }
     }
 }
@@ -6,9 +6,10 @@ use std::sync::Arc;
 
 use itertools::Itertools;
 use parking_lot::RwLock;
-use segment::data_types::vectors::only_default_vector;
+use segment::data_types::vectors::{only_default_vector, VectorStruct, DEFAULT_VECTOR_NAME};
 use segment::entry::entry_point::SegmentEntry;
-use segment::types::{PayloadFieldSchema, PayloadKeyType, PointIdType};
+use segment::segment_constructor::build_segment;
+use segment::types::{PayloadFieldSchema, PayloadKeyType, PointIdType, WithPayload};
 use tempfile::Builder;
 
 use crate::collection_manager::fixtures::{build_segment_1, build_segment_2, empty_segment};
@@ -16,6 +17,7 @@ use crate::collection_manager::holders::proxy_segment::ProxySegment;
 use crate::collection_manager::holders::segment_holder::{
     LockedSegment, LockedSegmentHolder, SegmentHolder, SegmentId,
 };
+use crate::collection_manager::segments_searcher::SegmentsSearcher;
 use crate::collection_manager::segments_updater::upsert_points;
 use crate::operations::point_ops::PointStruct;
 
@@ -71,11 +73,13 @@ fn test_update_proxy_segments() {
                 id: (100 * i + 1).into(),
                 vector: vectors[0].clone().into(),
                 payload: None,
+                if_version: None,
             },
             PointStruct {
                 id: (100 * i + 2).into(),
                 vector: vectors[1].clone().into(),
                 payload: None,
+                if_version: None,
             },
         ];
         upsert_points(&segments.read(), 1000 + i, &points).unwrap();
@@ -115,11 +119,13 @@ fn test_move_points_to_copy_on_write() {
             id: 1.into(),
             vector: vec![0.0, 0.0, 0.0, 0.0].into(),
             payload: None,
+            if_version: None,
         },
         PointStruct {
             id: 2.into(),
             vector: vec![0.0, 0.0, 0.0, 0.0].into(),
             payload: None,
+            if_version: None,
         },
     ];
 
@@ -130,11 +136,13 @@ fn test_move_points_to_copy_on_write() {
             id: 2.into(),
             vector: vec![0.0, 0.0, 0.0, 0.0].into(),
             payload: None,
+            if_version: None,
         },
         PointStruct {
             id: 3.into(),
             vector: vec![0.0, 0.0, 0.0, 0.0].into(),
             payload: None,
+            if_version: None,
         },
     ];
 
@@ -177,3 +185,115 @@ fn test_move_points_to_copy_on_write() {
         eprintln!("{idx} -> {external}");
     }
 }
+
+#[test]
+fn test_upsert_points_with_added_vector() {
+    let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+
+    let mut old_segment = empty_segment(dir.path());
+    old_segment
+        .upsert_point(1, 1.into(), only_default_vector(&[1.0, 0.0, 0.0, 0.0]))
+        .unwrap();
+
+    // Segment, created after the "new" vector was added to the collection
+    let mut new_config = old_segment.config().clone();
+    let new_vector_config = new_config.vector_data[DEFAULT_VECTOR_NAME].clone();
+    new_config
+        .vector_data
+        .insert("new".to_string(), new_vector_config);
+    let new_segment = build_segment(dir.path(), &new_config, true).unwrap();
+
+    let mut holder = SegmentHolder::default();
+    let old_sid = holder.add(old_segment);
+    let new_sid = holder.add(new_segment);
+
+    let points: Vec<_> = [1, 2]
+        .into_iter()
+        .map(|id| PointStruct {
+            id: id.into(),
+            vector: VectorStruct::Multi(HashMap::from([
+                (
+                    DEFAULT_VECTOR_NAME.to_string(),
+                    vec![0.0, 1.0, 0.0, 0.0].into(),
+                ),
+                ("new".to_string(), vec![0.0, 0.0, 1.0, 0.0].into()),
+            ])),
+            payload: None,
+            if_version: None,
+        })
+        .collect();
+
+    upsert_points(&holder, 2, &points).unwrap();
+
+    let old_segment = holder.get(old_sid).unwrap().get();
+    let new_segment = holder.get(new_sid).unwrap().get();
+    assert!(!old_segment.read().has_point(1.into()));
+    for id in [1, 2] {
+        let vector = new_segment.read().vector("new", id.into()).unwrap();
+        assert_eq!(vector, Some(vec![0.0, 0.0, 1.0, 0.0].into()));
+    }
+}
+
+#[test]
+fn test_upsert_points_if_version() {
+    let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+
+    let mut holder = SegmentHolder::default();
+    let sid = holder.add(empty_segment(dir.path()));
+
+    let point = |id: u64, value: f32, if_version: Option<u64>| PointStruct {
+        id: id.into(),
+        vector: VectorStruct::Single(vec![value, 0.0, 0.0, 0.0]),
+        payload: None,
+        if_version,
+    };
+
+    let segment = holder.get(sid).unwrap().get();
+
+    upsert_points(&holder, 10, &[point(1, 1.0, None)]).unwrap();
+
+    // Point 1 has version 10, point 2 doesn't exist yet
+    let err = upsert_points(
+        &holder,
+        11,
+        &[point(1, 2.0, Some(9)), point(2, 2.0, Some(0))],
+    )
+    .unwrap_err();
+    assert!(err.to_string().contains("Version conflict"), "{err}");
+
+    // Whole operation is rejected, none of the points is written
+    assert_eq!(segment.read().point_version(1.into()), Some(10));
+    assert_eq!(segment.read().point_version(2.into()), None);
+
+    upsert_points(
+        &holder,
+        12,
+        &[point(1, 3.0, Some(10)), point(2, 3.0, Some(0))],
+    )
+    .unwrap();
+    assert_eq!(segment.read().point_version(1.into()), Some(12));
+    assert_eq!(segment.read().point_version(2.into()), Some(12));
+
+    // Re-applying the operation, e.g. during WAL recovery, is not a conflict
+    upsert_points(
+        &holder,
+        12,
+        &[point(1, 3.0, Some(10)), point(2, 3.0, Some(0))],
+    )
+    .unwrap();
+
+    // Existing point can't be inserted with version 0
+    let err = upsert_points(&holder, 13, &[point(2, 4.0, Some(0))]).unwrap_err();
+    assert!(err.to_string().contains("Version conflict"), "{err}");
+
+    // Version is reported with the record
+    let holder = RwLock::new(holder);
+    let records = SegmentsSearcher::retrieve(
+        &holder,
+        &[1.into()],
+        &WithPayload::from(true),
+        &false.into(),
+    )
+    .unwrap();
+    assert_eq!(records[0].version, Some(12));
+}
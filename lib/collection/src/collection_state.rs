@@ -4,10 +4,13 @@ use serde::{Deserialize, Serialize};
 use validator::Validate;
 
 use crate::collection::payload_index_schema::PayloadIndexSchema;
+use crate::collection::read_only::ReadOnlyState;
+use crate::collection::stored_queries::StoredQueries;
 use crate::config::CollectionConfig;
 use crate::shards::replica_set::ReplicaState;
 use crate::shards::shard::{PeerId, ShardId};
 use crate::shards::shard_holder::ShardKeyMapping;
+use crate::shards::shard_split::ShardSplit;
 use crate::shards::transfer::ShardTransfer;
 
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
@@ -26,6 +29,12 @@ pub struct State {
     pub shards_key_mapping: ShardKeyMapping,
     #[serde(default)]
     pub payload_index_schema: PayloadIndexSchema,
+    #[serde(default)]
+    pub shard_splits: Vec<ShardSplit>,
+    #[serde(default)]
+    pub stored_queries: StoredQueries,
+    #[serde(default)]
+    pub read_only: ReadOnlyState,
 }
 
 impl State {
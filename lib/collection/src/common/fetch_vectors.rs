@@ -6,6 +6,7 @@ use segment::data_types::vectors::{Vector, VectorRef};
 use segment::types::{PointIdType, WithPayloadInterface, WithVector};
 use tokio::sync::RwLockReadGuard;
 
+use crate::collection::access_tags::AccessTags;
 use crate::collection::Collection;
 use crate::common::batching::batch_requests;
 use crate::common::retrieve_request_trait::RetrieveRequest;
@@ -15,6 +16,8 @@ use crate::operations::shard_selector_internal::ShardSelectorInternal;
 use crate::operations::types::{
     CollectionError, CollectionResult, PointRequestInternal, RecommendExample, Record,
 };
+use crate::shards::replica_set::read_priority::ReadPriority;
+use crate::shards::replica_set::stale_reads::StaleReads;
 
 pub async fn retrieve_points(
     collection: &Collection,
@@ -22,6 +25,9 @@ pub async fn retrieve_points(
     vector_names: Vec<String>,
     read_consistency: Option<ReadConsistency>,
     shard_selector: &ShardSelectorInternal,
+    access_tags: Option<&AccessTags>,
+    read_priority: ReadPriority,
+    stale_reads: Option<&StaleReads>,
 ) -> CollectionResult<Vec<Record>> {
     collection
         .retrieve(
@@ -29,9 +35,14 @@ pub async fn retrieve_points(
                 ids,
                 with_payload: Some(WithPayloadInterface::Bool(false)),
                 with_vector: WithVector::Selector(vector_names),
+                as_of: None,
+                with_version: false,
             },
             read_consistency,
             shard_selector,
+            access_tags,
+            read_priority,
+            stale_reads,
         )
         .await
 }
@@ -47,6 +58,9 @@ pub async fn retrieve_points_with_locked_collection(
     vector_names: Vec<String>,
     read_consistency: Option<ReadConsistency>,
     shard_selector: &ShardSelectorInternal,
+    access_tags: Option<&AccessTags>,
+    read_priority: ReadPriority,
+    stale_reads: Option<&StaleReads>,
 ) -> CollectionResult<Vec<Record>> {
     match collection_holder {
         CollectionRefHolder::Ref(collection) => {
@@ -56,11 +70,24 @@ pub async fn retrieve_points_with_locked_collection(
                 vector_names,
                 read_consistency,
                 shard_selector,
+                access_tags,
+                read_priority,
+                stale_reads,
             )
             .await
         }
         CollectionRefHolder::Guard(guard) => {
-            retrieve_points(&guard, ids, vector_names, read_consistency, shard_selector).await
+            retrieve_points(
+                &guard,
+                ids,
+                vector_names,
+                read_consistency,
+                shard_selector,
+                access_tags,
+                read_priority,
+                stale_reads,
+            )
+            .await
         }
     }
 }
@@ -159,6 +186,9 @@ impl<'coll_name> ReferencedPoints<'coll_name> {
         read_consistency: Option<ReadConsistency>,
         collection_by_name: &F,
         shard_selector: ShardSelectorInternal,
+        access_tags: Option<&AccessTags>,
+        read_priority: ReadPriority,
+        stale_reads: Option<&StaleReads>,
     ) -> CollectionResult<ReferencedVectors>
     where
         F: Fn(String) -> Fut,
@@ -184,6 +214,9 @@ impl<'coll_name> ReferencedPoints<'coll_name> {
                     vector_names,
                     read_consistency,
                     &shard_selector,
+                    access_tags,
+                    read_priority,
+                    stale_reads,
                 )),
                 Some(name) => {
                     let other_collection = collection_by_name(name.to_string()).await;
@@ -195,6 +228,9 @@ impl<'coll_name> ReferencedPoints<'coll_name> {
                                 vector_names,
                                 read_consistency,
                                 &shard_selector,
+                                access_tags,
+                                read_priority,
+                                stale_reads,
                             ))
                         }
                         None => {
@@ -234,6 +270,7 @@ pub fn convert_to_vectors_owned(
         .into_iter()
         .filter_map(|example| match example {
             RecommendExample::Dense(vector) => Some(vector.into()),
+            RecommendExample::MultiDense(vector) => Some(vector.into()),
             RecommendExample::Sparse(vector) => Some(vector.into()),
             RecommendExample::PointId(vid) => {
                 let rec = all_vectors_records_map.get(&collection_name, vid).unwrap();
@@ -251,6 +288,7 @@ pub fn convert_to_vectors<'a>(
 ) -> impl Iterator<Item = VectorRef<'a>> + 'a {
     examples.filter_map(move |example| match example {
         RecommendExample::Dense(vector) => Some(vector.into()),
+        RecommendExample::MultiDense(vector) => Some(vector.into()),
         RecommendExample::Sparse(vector) => Some(vector.into()),
         RecommendExample::PointId(vid) => {
             let rec = all_vectors_records_map.get(&collection_name, *vid).unwrap();
@@ -264,6 +302,9 @@ pub async fn resolve_referenced_vectors_batch<'a, 'b, F, Fut, Req: RetrieveReque
     collection: &Collection,
     collection_by_name: F,
     read_consistency: Option<ReadConsistency>,
+    access_tags: Option<&AccessTags>,
+    read_priority: ReadPriority,
+    stale_reads: Option<&StaleReads>,
 ) -> CollectionResult<ReferencedVectors>
 where
     F: Fn(String) -> Fut,
@@ -302,6 +343,9 @@ where
                 read_consistency,
                 &collection_by_name,
                 shard_selector,
+                access_tags,
+                read_priority,
+                stale_reads,
             );
             requests.push(fetch);
             Ok(())
@@ -0,0 +1,257 @@
+//! Client of the external inference endpoint, which converts documents into vectors
+//!
+//! Documents are sent in batches of `batch_size` as POST requests with the JSON body:
+//! `{"model": "<model>", "inputs": [{"text": "..."}, {"image": "..."}]}`.
+//! The endpoint responds with a vector per input, in the same order:
+//! `{"embeddings": [[0.1, 0.2, ...], ...]}`.
+//!
+//! Requests, failed because of connection errors, timeouts, `429` or `5xx` responses, are retried
+//! with exponential backoff. Other failures are returned right away.
+
+use std::sync::OnceLock;
+use std::time::Duration;
+
+use reqwest::StatusCode;
+use segment::data_types::vectors::DenseVector;
+use serde::{Deserialize, Serialize};
+
+use crate::config::InferenceConfig;
+use crate::operations::document_ops::Document;
+use crate::operations::types::{CollectionError, CollectionResult};
+
+const DEFAULT_BATCH_SIZE: usize = 32;
+
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(30);
+
+const DEFAULT_MAX_RETRIES: usize = 3;
+
+/// Delay before the first retry, doubled with each following one
+const INITIAL_RETRY_DELAY: Duration = Duration::from_millis(100);
+
+#[derive(Serialize)]
+struct InferenceRequest<'a> {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    model: Option<&'a str>,
+    inputs: &'a [Document],
+}
+
+#[derive(Deserialize)]
+struct InferenceResponse {
+    embeddings: Vec<DenseVector>,
+}
+
+/// HTTP client is shared by all collections, so connections to the endpoints are reused
+fn http_client() -> &'static reqwest::Client {
+    static CLIENT: OnceLock<reqwest::Client> = OnceLock::new();
+    CLIENT.get_or_init(reqwest::Client::new)
+}
+
+/// Convert documents into vectors with the inference endpoint, a vector per document
+pub async fn infer(
+    config: &InferenceConfig,
+    documents: &[Document],
+) -> CollectionResult<Vec<DenseVector>> {
+    let batch_size = config.batch_size.unwrap_or(DEFAULT_BATCH_SIZE).max(1);
+    let mut vectors = Vec::with_capacity(documents.len());
+    for batch in documents.chunks(batch_size) {
+        vectors.extend(infer_batch(config, batch).await?);
+    }
+    Ok(vectors)
+}
+
+async fn infer_batch(
+    config: &InferenceConfig,
+    documents: &[Document],
+) -> CollectionResult<Vec<DenseVector>> {
+    let max_retries = config.max_retries.unwrap_or(DEFAULT_MAX_RETRIES);
+    let mut delay = INITIAL_RETRY_DELAY;
+    let mut attempt = 0;
+    loop {
+        match send(config, documents).await {
+            Ok(vectors) => return Ok(vectors),
+            Err(InferenceError::Retryable(err)) if attempt < max_retries => {
+                log::debug!(
+                    "Inference request to {} failed, retrying: {err}",
+                    config.url
+                );
+                tokio::time::sleep(delay).await;
+                delay *= 2;
+                attempt += 1;
+            }
+            Err(InferenceError::Retryable(err) | InferenceError::Fatal(err)) => {
+                return Err(CollectionError::service_error(format!(
+                    "Inference request to {} failed: {err}",
+                    config.url,
+                )));
+            }
+        }
+    }
+}
+
+enum InferenceError {
+    Retryable(String),
+    Fatal(String),
+}
+
+async fn send(
+    config: &InferenceConfig,
+    documents: &[Document],
+) -> Result<Vec<DenseVector>, InferenceError> {
+    let body = serde_json::to_vec(&InferenceRequest {
+        model: config.model.as_deref(),
+        inputs: documents,
+    })
+    .map_err(|err| InferenceError::Fatal(err.to_string()))?;
+
+    let response = http_client()
+        .post(config.url.clone())
+        .timeout(
+            config
+                .timeout_sec
+                .map_or(DEFAULT_TIMEOUT, Duration::from_secs),
+        )
+        .header(reqwest::header::CONTENT_TYPE, "application/json")
+        .body(body)
+        .send()
+        .await
+        .map_err(|err| InferenceError::Retryable(err.to_string()))?;
+
+    let status = response.status();
+    let body = response
+        .bytes()
+        .await
+        .map_err(|err| InferenceError::Retryable(err.to_string()))?;
+    if !status.is_success() {
+        let err = format!("{status}: {}", String::from_utf8_lossy(&body));
+        return if status.is_server_error() || status == StatusCode::TOO_MANY_REQUESTS {
+            Err(InferenceError::Retryable(err))
+        } else {
+            Err(InferenceError::Fatal(err))
+        };
+    }
+
+    let InferenceResponse { embeddings } = serde_json::from_slice(&body)
+        .map_err(|err| InferenceError::Fatal(format!("malformed response: {err}")))?;
+    if embeddings.len() != documents.len() {
+        return Err(InferenceError::Fatal(format!(
+            "{} vectors returned for {} documents",
+            embeddings.len(),
+            documents.len(),
+        )));
+    }
+    Ok(embeddings)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use parking_lot::Mutex;
+    use serde_json::{json, Value};
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+    use url::Url;
+
+    use super::*;
+
+    /// Minimal HTTP endpoint, which returns a vector `[i]` for the `i`-th input of each request,
+    /// after failing the given number of requests with `503`
+    async fn serve(failures: usize) -> (Url, Arc<Mutex<Vec<Value>>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = Url::parse(&format!("http://{}/embed", listener.local_addr().unwrap())).unwrap();
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        tokio::spawn({
+            let requests = requests.clone();
+            async move {
+                let mut failures = failures;
+                loop {
+                    let (mut stream, _) = listener.accept().await.unwrap();
+                    let mut data = Vec::new();
+                    let mut buffer = [0; 4096];
+                    let body = loop {
+                        let read = stream.read(&mut buffer).await.unwrap();
+                        data.extend_from_slice(&buffer[..read]);
+                        let text = String::from_utf8_lossy(&data).to_string();
+                        let Some((head, body)) = text.split_once("\r\n\r\n") else {
+                            continue;
+                        };
+                        let length: usize = head
+                            .lines()
+                            .find_map(|line| {
+                                line.to_lowercase()
+                                    .strip_prefix("content-length: ")
+                                    .map(|length| length.trim().parse().unwrap())
+                            })
+                            .unwrap_or(0);
+                        if body.len() >= length {
+                            break body.to_string();
+                        }
+                    };
+
+                    let request: Value = serde_json::from_str(&body).unwrap();
+                    let inputs = request["inputs"].as_array().unwrap().len();
+                    requests.lock().push(request);
+                    let (status, body) = if failures > 0 {
+                        failures -= 1;
+                        ("503 Service Unavailable", "overloaded".to_string())
+                    } else {
+                        let embeddings: Vec<_> = (0..inputs).map(|i| vec![i as f32]).collect();
+                        ("200 OK", json!({ "embeddings": embeddings }).to_string())
+                    };
+                    let response = format!(
+                        "HTTP/1.1 {status}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                        body.len(),
+                    );
+                    stream.write_all(response.as_bytes()).await.unwrap();
+                }
+            }
+        });
+        (url, requests)
+    }
+
+    fn config(url: Url) -> InferenceConfig {
+        InferenceConfig {
+            url,
+            model: Some("test-model".to_string()),
+            vector_name: None,
+            batch_size: Some(2),
+            timeout_sec: None,
+            max_retries: Some(1),
+        }
+    }
+
+    fn text(text: &str) -> Document {
+        Document::Text {
+            text: text.to_string(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_infer_in_batches() {
+        let (url, requests) = serve(0).await;
+        let documents = [text("a"), text("b"), text("c")];
+
+        let vectors = infer(&config(url), &documents).await.unwrap();
+        assert_eq!(vectors, vec![vec![0.0], vec![1.0], vec![0.0]]);
+
+        let requests = requests.lock();
+        assert_eq!(requests.len(), 2);
+        assert_eq!(
+            requests[0],
+            json!({"model": "test-model", "inputs": [{"text": "a"}, {"text": "b"}]}),
+        );
+    }
+
+    #[tokio::test]
+    async fn test_infer_retries() {
+        let (url, requests) = serve(1).await;
+        let vectors = infer(&config(url), &[text("a")]).await.unwrap();
+        assert_eq!(vectors, vec![vec![0.0]]);
+        assert_eq!(requests.lock().len(), 2);
+
+        // Retries are exhausted
+        let (url, requests) = serve(2).await;
+        assert!(infer(&config(url), &[text("a")]).await.is_err());
+        assert_eq!(requests.lock().len(), 2);
+    }
+}
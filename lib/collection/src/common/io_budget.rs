@@ -0,0 +1,154 @@
+//! Shared budget of disk throughput for low priority background tasks
+//!
+//! All low priority tasks of the node, e.g. snapshots created with `snapshot_priority=low`, take
+//! their permits from the same budget. So together they never exceed the configured throughput,
+//! and leave the rest of the disk to the serving traffic.
+
+use std::io::Write;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use parking_lot::Mutex;
+
+const MB: usize = 1024 * 1024;
+
+/// Budget of bytes per second, shared between clones
+#[derive(Clone, Debug, Default)]
+pub struct IoBudget {
+    /// `None` if throughput is not limited
+    bucket: Option<Arc<Mutex<TokenBucket>>>,
+}
+
+impl IoBudget {
+    /// Budget of `limit_mb_per_sec` megabytes per second, `0` means no limit
+    pub fn new(limit_mb_per_sec: usize) -> Self {
+        let bucket = (limit_mb_per_sec > 0)
+            .then(|| Arc::new(Mutex::new(TokenBucket::new(limit_mb_per_sec * MB))));
+        Self { bucket }
+    }
+
+    /// Time to wait before `bytes` could be used
+    ///
+    /// The bytes are taken from the budget right away, so concurrent tasks queue up behind each
+    /// other.
+    fn take(&self, bytes: usize) -> Duration {
+        match &self.bucket {
+            Some(bucket) => bucket.lock().take(bytes, Instant::now()),
+            None => Duration::ZERO,
+        }
+    }
+
+    /// Wait until `bytes` could be used, blocking the thread
+    pub fn acquire_blocking(&self, bytes: usize) {
+        let delay = self.take(bytes);
+        if !delay.is_zero() {
+            std::thread::sleep(delay);
+        }
+    }
+
+    /// Wait until `bytes` could be used
+    pub async fn acquire(&self, bytes: usize) {
+        let delay = self.take(bytes);
+        if !delay.is_zero() {
+            tokio::time::sleep(delay).await;
+        }
+    }
+}
+
+/// Units per second, e.g. bytes, which could be taken in advance and paid off with time
+#[derive(Debug)]
+pub(crate) struct TokenBucket {
+    /// Units per second, also the largest burst
+    rate: f64,
+    /// Units, which could be used right away, negative if they are already owed
+    available: f64,
+    updated: Instant,
+}
+
+impl TokenBucket {
+    pub(crate) fn new(rate: usize) -> Self {
+        Self {
+            rate: rate as f64,
+            available: rate as f64,
+            updated: Instant::now(),
+        }
+    }
+
+    /// Take `units` from the bucket, returns the time to wait until they are paid off
+    pub(crate) fn take(&mut self, units: usize, now: Instant) -> Duration {
+        let elapsed = now.saturating_duration_since(self.updated).as_secs_f64();
+        self.available = (self.available + elapsed * self.rate).min(self.rate);
+        self.updated = now;
+
+        self.available -= units as f64;
+        if self.available >= 0.0 {
+            Duration::ZERO
+        } else {
+            Duration::from_secs_f64(-self.available / self.rate)
+        }
+    }
+}
+
+/// Writer, which takes the written bytes from the [`IoBudget`]
+pub struct ThrottledWriter<W> {
+    writer: W,
+    budget: IoBudget,
+}
+
+impl<W: Write> ThrottledWriter<W> {
+    pub fn new(writer: W, budget: IoBudget) -> Self {
+        Self { writer, budget }
+    }
+
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+impl<W: Write> Write for ThrottledWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        let written = self.writer.write(buf)?;
+        self.budget.acquire_blocking(written);
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.writer.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_token_bucket() {
+        let start = Instant::now();
+        let mut bucket = TokenBucket {
+            rate: 100.0,
+            available: 100.0,
+            updated: start,
+        };
+
+        // Burst of one second is available right away
+        assert_eq!(bucket.take(100, start), Duration::ZERO);
+        // Further bytes are owed
+        assert_eq!(bucket.take(50, start), Duration::from_millis(500));
+        assert_eq!(bucket.take(50, start), Duration::from_secs(1));
+
+        // Debt is paid off with time
+        let later = start + Duration::from_secs(2);
+        assert_eq!(bucket.take(0, later), Duration::ZERO);
+
+        // Unused budget is not accumulated beyond one second
+        let much_later = later + Duration::from_secs(10);
+        assert_eq!(bucket.take(100, much_later), Duration::ZERO);
+        assert_eq!(bucket.take(10, much_later), Duration::from_millis(100));
+    }
+
+    #[test]
+    fn test_unlimited_budget() {
+        let budget = IoBudget::new(0);
+        assert_eq!(budget.take(usize::MAX), Duration::ZERO);
+    }
+}
@@ -1,8 +1,15 @@
 pub mod batching;
 pub mod fetch_vectors;
 pub mod file_utils;
+pub mod inference;
+pub mod io_budget;
 pub mod is_ready;
 pub mod retrieve_request_trait;
+pub mod s3;
+pub mod sampling;
+pub mod search_admission;
+pub mod search_runtime;
+pub mod snapshot_storage;
 pub mod stoppable_task;
 pub mod stoppable_task_async;
 pub mod stopping_guard;
@@ -0,0 +1,53 @@
+use rand::distributions::{Distribution, WeightedIndex};
+
+/// Number of points to sample from each of the sources, e.g. shards or segments, of the given sizes
+///
+/// Each point of the sample is drawn from a source with the probability, proportional to the number
+/// of its points, which are not drawn yet. So the sample is uniform over all points of the sources,
+/// and no source is asked for more points, than it has.
+pub fn proportional_sample_sizes(sizes: &[usize], limit: usize) -> Vec<usize> {
+    let mut sample_sizes = vec![0; sizes.len()];
+    if sizes.iter().sum::<usize>() <= limit {
+        return sizes.to_vec();
+    }
+
+    let mut remaining = sizes.to_vec();
+    let Ok(mut distribution) = WeightedIndex::new(&remaining) else {
+        return sample_sizes;
+    };
+    let mut rng = rand::thread_rng();
+    for _ in 0..limit {
+        let source = distribution.sample(&mut rng);
+        sample_sizes[source] += 1;
+        remaining[source] -= 1;
+        if distribution
+            .update_weights(&[(source, &remaining[source])])
+            .is_err()
+        {
+            break;
+        }
+    }
+    sample_sizes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_proportional_sample_sizes() {
+        assert_eq!(proportional_sample_sizes(&[3, 0, 2], 10), vec![3, 0, 2]);
+        assert_eq!(proportional_sample_sizes(&[], 10), Vec::<usize>::new());
+
+        let sizes = [1000, 10, 0, 5000];
+        let sample_sizes = proportional_sample_sizes(&sizes, 600);
+        assert_eq!(sample_sizes.iter().sum::<usize>(), 600);
+        assert!(sample_sizes
+            .iter()
+            .zip(sizes)
+            .all(|(sampled, size)| *sampled <= size));
+        assert_eq!(sample_sizes[2], 0);
+        // Expected 500 points from the largest source
+        assert!(sample_sizes[3] > sample_sizes[0] * 2);
+    }
+}
@@ -0,0 +1,197 @@
+//! Admission control of concurrently executing searches
+//!
+//! Every search takes a permit before it is executed. Once all permits are taken, further
+//! searches wait in a bounded queue for at most the queue timeout. Searches, which do not fit
+//! into the queue or wait for too long, are rejected with an overload error right away, so a
+//! burst of requests is shed early instead of piling up and timing out everywhere.
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+use crate::operations::types::{CollectionError, CollectionResult};
+
+const DEFAULT_QUEUE_SIZE: usize = 128;
+const DEFAULT_QUEUE_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Limit of concurrent searches, shared between clones
+#[derive(Clone, Debug)]
+pub struct SearchAdmission {
+    /// Max number of searches waiting for a permit
+    queue_size: usize,
+    /// Max time to wait for a permit
+    queue_timeout: Duration,
+    /// `None` if concurrency is not limited
+    limiter: Option<Arc<Limiter>>,
+}
+
+#[derive(Debug)]
+struct Limiter {
+    semaphore: Arc<Semaphore>,
+    max_concurrency: usize,
+    /// Number of searches waiting for a permit
+    queued: AtomicUsize,
+}
+
+impl Default for SearchAdmission {
+    fn default() -> Self {
+        Self::new(None, DEFAULT_QUEUE_SIZE, DEFAULT_QUEUE_TIMEOUT)
+    }
+}
+
+impl SearchAdmission {
+    /// Admission of `max_concurrency` searches at a time, `None` means no limit
+    pub fn new(max_concurrency: Option<usize>, queue_size: usize, queue_timeout: Duration) -> Self {
+        let limiter = max_concurrency.map(|max_concurrency| {
+            Arc::new(Limiter {
+                semaphore: Arc::new(Semaphore::new(max_concurrency)),
+                max_concurrency,
+                queued: AtomicUsize::new(0),
+            })
+        });
+        Self {
+            queue_size,
+            queue_timeout,
+            limiter,
+        }
+    }
+
+    pub fn max_concurrency(&self) -> Option<usize> {
+        self.limiter.as_ref().map(|limiter| limiter.max_concurrency)
+    }
+
+    /// New admission with another concurrency limit, but the same queue parameters
+    ///
+    /// Permits of the new admission are not shared with this one.
+    pub fn with_max_concurrency(&self, max_concurrency: Option<usize>) -> Self {
+        Self::new(max_concurrency, self.queue_size, self.queue_timeout)
+    }
+
+    /// Wait for a permit to execute a search
+    ///
+    /// Returns [`CollectionError::Overloaded`] if the queue is full, or the permit is not
+    /// acquired within the queue timeout.
+    pub async fn admit(&self) -> CollectionResult<SearchPermit> {
+        let Some(limiter) = &self.limiter else {
+            return Ok(SearchPermit { _permit: None });
+        };
+
+        if let Ok(permit) = limiter.semaphore.clone().try_acquire_owned() {
+            return Ok(SearchPermit {
+                _permit: Some(permit),
+            });
+        }
+
+        let _queue_place =
+            QueuePlace::reserve(&limiter.queued, self.queue_size).ok_or_else(|| {
+                CollectionError::overloaded(format!(
+                    "too many concurrent searches, limit is {} with queue of {}",
+                    limiter.max_concurrency, self.queue_size,
+                ))
+            })?;
+
+        let permit = tokio::time::timeout(
+            self.queue_timeout,
+            limiter.semaphore.clone().acquire_owned(),
+        )
+        .await
+        .map_err(|_| {
+            CollectionError::overloaded(format!(
+                "search waited in the queue for more than {:?}",
+                self.queue_timeout,
+            ))
+        })?
+        .map_err(|_| CollectionError::service_error("Search admission is closed"))?;
+
+        Ok(SearchPermit {
+            _permit: Some(permit),
+        })
+    }
+}
+
+/// Permit to execute a search, released on drop
+#[derive(Debug)]
+pub struct SearchPermit {
+    _permit: Option<OwnedSemaphorePermit>,
+}
+
+/// Place in the admission queue, released on drop
+struct QueuePlace<'a> {
+    queued: &'a AtomicUsize,
+}
+
+impl<'a> QueuePlace<'a> {
+    fn reserve(queued: &'a AtomicUsize, queue_size: usize) -> Option<Self> {
+        queued
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |queued| {
+                (queued < queue_size).then_some(queued + 1)
+            })
+            .ok()
+            .map(|_| Self { queued })
+    }
+}
+
+impl Drop for QueuePlace<'_> {
+    fn drop(&mut self) {
+        self.queued.fetch_sub(1, Ordering::AcqRel);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn queued(admission: &SearchAdmission) -> usize {
+        admission
+            .limiter
+            .as_ref()
+            .unwrap()
+            .queued
+            .load(Ordering::Acquire)
+    }
+
+    #[tokio::test]
+    async fn test_unlimited_admission() {
+        let admission = SearchAdmission::new(None, 0, Duration::ZERO);
+        let _permits: Vec<_> = futures::future::try_join_all((0..100).map(|_| admission.admit()))
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_queue_is_bounded() {
+        let admission = SearchAdmission::new(Some(1), 1, Duration::from_secs(10));
+
+        let permit = admission.admit().await.unwrap();
+
+        // Second search waits in the queue, third does not fit into it
+        let queued_search = tokio::spawn({
+            let admission = admission.clone();
+            async move { admission.admit().await }
+        });
+        while queued(&admission) == 0 {
+            tokio::task::yield_now().await;
+        }
+        let rejected = admission.admit().await.unwrap_err();
+        assert!(matches!(rejected, CollectionError::Overloaded { .. }));
+
+        // Queued search is admitted, once the permit is released
+        drop(permit);
+        assert!(queued_search.await.unwrap().is_ok());
+        assert!(admission.admit().await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_queue_timeout() {
+        let admission = SearchAdmission::new(Some(1), 10, Duration::from_millis(10));
+
+        let _permit = admission.admit().await.unwrap();
+        let rejected = admission.admit().await.unwrap_err();
+        assert!(matches!(rejected, CollectionError::Overloaded { .. }));
+
+        // Timed out search leaves the queue
+        assert_eq!(queued(&admission), 0);
+    }
+}
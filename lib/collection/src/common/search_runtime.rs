@@ -0,0 +1,66 @@
+use std::io;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use segment::common::cpu::get_num_cpus;
+use tokio::runtime::{self, Handle, Runtime};
+
+/// Search runtime, dedicated to a single collection.
+///
+/// Searches of the collection are executed in this runtime instead of the shared one,
+/// so a collection under heavy search load can't starve searches of other collections.
+pub struct DedicatedSearchRuntime {
+    // Always `Some` until dropped
+    runtime: Option<Runtime>,
+}
+
+impl DedicatedSearchRuntime {
+    /// Number of threads is capped to the number of CPUs, as the collection config may come from
+    /// a peer with more CPUs.
+    pub fn new(collection_name: &str, search_threads: usize) -> io::Result<Self> {
+        let search_threads = search_threads.min(get_num_cpus());
+        let thread_prefix = format!("search-{collection_name}");
+        let runtime = runtime::Builder::new_multi_thread()
+            .worker_threads(search_threads)
+            .max_blocking_threads(search_threads)
+            .enable_all()
+            .thread_name_fn(move || {
+                static ATOMIC_ID: AtomicUsize = AtomicUsize::new(0);
+                let id = ATOMIC_ID.fetch_add(1, Ordering::SeqCst);
+                format!("{thread_prefix}-{id}")
+            })
+            .build()?;
+        Ok(Self {
+            runtime: Some(runtime),
+        })
+    }
+
+    pub fn handle(&self) -> &Handle {
+        self.runtime
+            .as_ref()
+            .expect("runtime is only taken on drop")
+            .handle()
+    }
+}
+
+impl Drop for DedicatedSearchRuntime {
+    fn drop(&mut self) {
+        // Collection may be dropped from within async context, where blocking runtime shutdown panics
+        if let Some(runtime) = self.runtime.take() {
+            runtime.shutdown_background();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_drop_in_async_context() {
+        let runtime = DedicatedSearchRuntime::new("test", 2).unwrap();
+        let result = runtime.handle().spawn(async { 1 + 1 }).await.unwrap();
+        assert_eq!(result, 2);
+        // Must not panic, even though we are inside of another runtime
+        drop(runtime);
+    }
+}
@@ -0,0 +1,354 @@
+//! Storage of collection snapshots: local directory or S3-compatible object storage
+//!
+//! Snapshots are streamed to the storage while they are archived, and streamed from it while
+//! they are unpacked, so large snapshots don't take disk space twice.
+//!
+//! Objects in the object storage are named after the relative path of the snapshot in the local
+//! snapshots directory: `{collection_name}/{snapshot_name}`.
+
+use std::fs::File;
+use std::io::{BufWriter, Read, Write};
+use std::path::{Path, PathBuf};
+
+use reqwest::Response;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use tempfile::TempPath;
+use url::Url;
+
+use crate::common::s3::{ObjectInfo, S3Client, S3Config, S3Download, S3Upload};
+use crate::operations::snapshot_ops::{self, SnapshotDescription};
+use crate::operations::types::{CollectionError, CollectionResult};
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum SnapshotsStorageType {
+    #[default]
+    Local,
+    S3,
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq, Eq, Default)]
+pub struct SnapshotsConfig {
+    /// Where collection snapshots are stored
+    #[serde(default)]
+    pub snapshots_storage: SnapshotsStorageType,
+    /// Required for the `s3` storage
+    #[serde(default)]
+    pub s3_config: Option<S3Config>,
+}
+
+#[derive(Clone, Debug, Default)]
+pub enum SnapshotStorage {
+    #[default]
+    Local,
+    S3 {
+        client: S3Client,
+        /// Local snapshots directory, paths of snapshots are relative to it in the bucket
+        snapshots_root: PathBuf,
+    },
+}
+
+impl SnapshotStorage {
+    pub fn new(config: &SnapshotsConfig, snapshots_root: &Path) -> CollectionResult<Self> {
+        match config.snapshots_storage {
+            SnapshotsStorageType::Local => Ok(Self::Local),
+            SnapshotsStorageType::S3 => {
+                let s3_config = config.s3_config.as_ref().ok_or_else(|| {
+                    CollectionError::bad_input(
+                        "`s3_config` is required for the `s3` snapshots storage".to_string(),
+                    )
+                })?;
+                Ok(Self::S3 {
+                    client: S3Client::new(s3_config)?,
+                    snapshots_root: snapshots_root.to_path_buf(),
+                })
+            }
+        }
+    }
+
+    pub fn is_local(&self) -> bool {
+        matches!(self, Self::Local)
+    }
+
+    /// Open a writer of the snapshot at `snapshot_path`
+    ///
+    /// The snapshot only appears in the storage after [`SnapshotWriter::finish`]. The writer is
+    /// blocking, so it must be used outside of async context.
+    ///
+    /// `expected_size` is an estimate of the snapshot size, used to split uploads into parts.
+    pub async fn writer(
+        &self,
+        snapshot_path: &Path,
+        expected_size: u64,
+    ) -> CollectionResult<SnapshotWriter> {
+        match self {
+            Self::Local => {
+                // Written under a temporary name and renamed on finish, so unfinished snapshots
+                // are never listed
+                let temp_path = TempPath::from_path(snapshot_path.with_extension("tmp"));
+                let file = File::create(&temp_path)?;
+                Ok(SnapshotWriter::Local {
+                    file: BufWriter::new(file),
+                    temp_path,
+                    snapshot_path: snapshot_path.to_path_buf(),
+                })
+            }
+            Self::S3 { client, .. } => {
+                let key = self.object_key(snapshot_path);
+                let upload = S3Upload::start(client.clone(), key, expected_size).await?;
+                Ok(SnapshotWriter::S3(upload))
+            }
+        }
+    }
+
+    /// Open a reader of the snapshot at `snapshot_path`
+    ///
+    /// The reader is blocking, so it must be used outside of async context.
+    pub async fn reader(&self, snapshot_path: &Path) -> CollectionResult<Box<dyn Read + Send>> {
+        match self {
+            Self::Local => Ok(Box::new(File::open(snapshot_path)?)),
+            Self::S3 { client, .. } => {
+                let key = self.object_key(snapshot_path);
+                Ok(Box::new(S3Download::start(client, &key).await?))
+            }
+        }
+    }
+
+    /// Open a reader of the object with `key`, e.g. given in the recovery location
+    pub async fn object_reader(&self, key: &str) -> CollectionResult<Box<dyn Read + Send>> {
+        match self {
+            Self::Local => Err(CollectionError::bad_input(
+                "Snapshots object storage is not configured".to_string(),
+            )),
+            Self::S3 { client, .. } => Ok(Box::new(S3Download::start(client, key).await?)),
+        }
+    }
+
+    /// Response with the body of the snapshot object, to stream it to the client
+    ///
+    /// Local snapshots are served as files instead.
+    pub async fn object_response(&self, snapshot_path: &Path) -> CollectionResult<Response> {
+        match self {
+            Self::Local => Err(CollectionError::service_error(
+                "Local snapshots are not stored as objects",
+            )),
+            Self::S3 { client, .. } => client.get_object(&self.object_key(snapshot_path)).await,
+        }
+    }
+
+    /// Key of the object given by the `s3://{bucket}/{key}` recovery location
+    ///
+    /// Only objects in the configured bucket can be read.
+    pub fn location_key(&self, location: &Url) -> CollectionResult<String> {
+        let Self::S3 { client, .. } = self else {
+            return Err(CollectionError::bad_input(
+                "Snapshots object storage is not configured".to_string(),
+            ));
+        };
+        if location.host_str() != Some(client.bucket()) {
+            return Err(CollectionError::bad_input(format!(
+                "Snapshot {location} is not in the configured bucket {}",
+                client.bucket(),
+            )));
+        }
+        let key = location.path().trim_start_matches('/');
+        if key.is_empty() {
+            return Err(CollectionError::bad_input(format!(
+                "Snapshot location {location} has no object key",
+            )));
+        }
+        Ok(key.to_string())
+    }
+
+    pub async fn exists(&self, snapshot_path: &Path) -> CollectionResult<bool> {
+        match self {
+            Self::Local => Ok(snapshot_path.exists()),
+            Self::S3 { client, .. } => Ok(client
+                .head_object(&self.object_key(snapshot_path))
+                .await?
+                .is_some()),
+        }
+    }
+
+    pub async fn describe(&self, snapshot_path: &Path) -> CollectionResult<SnapshotDescription> {
+        match self {
+            Self::Local => snapshot_ops::get_snapshot_description(snapshot_path).await,
+            Self::S3 { client, .. } => {
+                let key = self.object_key(snapshot_path);
+                let object =
+                    client
+                        .head_object(&key)
+                        .await?
+                        .ok_or_else(|| CollectionError::NotFound {
+                            what: format!("Snapshot {key}"),
+                        })?;
+                Ok(object_description(object))
+            }
+        }
+    }
+
+    pub async fn list(&self, snapshots_dir: &Path) -> CollectionResult<Vec<SnapshotDescription>> {
+        match self {
+            Self::Local => snapshot_ops::list_snapshots_in_directory(snapshots_dir).await,
+            Self::S3 { client, .. } => {
+                let prefix = format!("{}/", self.object_key(snapshots_dir));
+                let objects = client.list_objects(&prefix).await?;
+                Ok(objects
+                    .into_iter()
+                    .filter(|object| {
+                        // Only direct children of the directory
+                        object.key[prefix.len()..].ends_with(".snapshot")
+                            && !object.key[prefix.len()..].contains('/')
+                    })
+                    .map(object_description)
+                    .collect())
+            }
+        }
+    }
+
+    pub async fn delete(&self, snapshot_path: &Path) -> CollectionResult<()> {
+        match self {
+            Self::Local => Ok(tokio::fs::remove_file(snapshot_path).await?),
+            Self::S3 { client, .. } => client.delete_object(&self.object_key(snapshot_path)).await,
+        }
+    }
+
+    /// Name of the object, which stores the snapshot at `snapshot_path`
+    fn object_key(&self, snapshot_path: &Path) -> String {
+        let relative_path = match self {
+            Self::S3 { snapshots_root, .. } => snapshot_path
+                .strip_prefix(snapshots_root)
+                .unwrap_or(snapshot_path),
+            Self::Local => snapshot_path,
+        };
+        relative_path
+            .components()
+            .filter_map(|component| match component {
+                std::path::Component::Normal(name) => name.to_str(),
+                _ => None,
+            })
+            .collect::<Vec<_>>()
+            .join("/")
+    }
+}
+
+fn object_description(object: ObjectInfo) -> SnapshotDescription {
+    let name = object
+        .key
+        .rsplit('/')
+        .next()
+        .unwrap_or(&object.key)
+        .to_string();
+    SnapshotDescription {
+        name,
+        creation_time: object.last_modified,
+        size: object.size,
+    }
+}
+
+/// Blocking writer of the snapshot into the [`SnapshotStorage`]
+pub enum SnapshotWriter {
+    Local {
+        file: BufWriter<File>,
+        temp_path: TempPath,
+        snapshot_path: PathBuf,
+    },
+    S3(S3Upload),
+}
+
+impl SnapshotWriter {
+    /// Make the written snapshot available in the storage
+    pub fn finish(self) -> CollectionResult<()> {
+        match self {
+            Self::Local {
+                file,
+                temp_path,
+                snapshot_path,
+            } => {
+                let file = file.into_inner().map_err(|err| err.into_error())?;
+                file.sync_all()?;
+                temp_path.persist(&snapshot_path).map_err(|err| err.error)?;
+                Ok(())
+            }
+            Self::S3(upload) => upload.finish(),
+        }
+    }
+}
+
+impl Write for SnapshotWriter {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        match self {
+            Self::Local { file, .. } => file.write(buf),
+            Self::S3(upload) => upload.write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        match self {
+            Self::Local { file, .. } => file.flush(),
+            Self::S3(upload) => upload.flush(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn s3_storage() -> SnapshotStorage {
+        let config = SnapshotsConfig {
+            snapshots_storage: SnapshotsStorageType::S3,
+            s3_config: Some(S3Config {
+                bucket: "snapshots".to_string(),
+                region: None,
+                access_key: Some("access".to_string()),
+                secret_key: Some("secret".to_string()),
+                endpoint_url: Some("http://localhost:9000".to_string()),
+            }),
+        };
+        SnapshotStorage::new(&config, Path::new("/qdrant/snapshots")).unwrap()
+    }
+
+    #[test]
+    fn test_object_key() {
+        let storage = s3_storage();
+        assert_eq!(
+            storage.object_key(Path::new("/qdrant/snapshots/test/test-1.snapshot")),
+            "test/test-1.snapshot",
+        );
+        assert_eq!(
+            storage.object_key(Path::new("/qdrant/snapshots/test")),
+            "test"
+        );
+    }
+
+    #[test]
+    fn test_location_key() {
+        let storage = s3_storage();
+
+        let location = Url::parse("s3://snapshots/test/test-1.snapshot").unwrap();
+        assert_eq!(
+            storage.location_key(&location).unwrap(),
+            "test/test-1.snapshot",
+        );
+
+        let location = Url::parse("s3://other/test/test-1.snapshot").unwrap();
+        assert!(storage.location_key(&location).is_err());
+
+        let location = Url::parse("s3://snapshots/").unwrap();
+        assert!(storage.location_key(&location).is_err());
+
+        let location = Url::parse("s3://snapshots/test/test-1.snapshot").unwrap();
+        assert!(SnapshotStorage::Local.location_key(&location).is_err());
+    }
+
+    #[test]
+    fn test_s3_config_is_required() {
+        let config = SnapshotsConfig {
+            snapshots_storage: SnapshotsStorageType::S3,
+            s3_config: None,
+        };
+        assert!(SnapshotStorage::new(&config, Path::new("/qdrant/snapshots")).is_err());
+    }
+}
@@ -6,22 +6,26 @@ use std::path::Path;
 
 use atomicwrites::AtomicFile;
 use atomicwrites::OverwriteBehavior::AllowOverwrite;
+use merge::Merge;
 use schemars::JsonSchema;
 use segment::common::anonymize::Anonymize;
 use segment::data_types::vectors::DEFAULT_VECTOR_NAME;
 use segment::index::sparse_index::sparse_index_config::{SparseIndexConfig, SparseIndexType};
 use segment::types::{
-    Distance, HnswConfig, Indexes, QuantizationConfig, SparseVectorDataConfig, VectorDataConfig,
-    VectorStorageType,
+    Distance, HnswConfig, Indexes, PayloadKeyType, PayloadStorageType, QuantizationConfig,
+    SparseVectorDataConfig, VectorDataConfig, VectorStorageType,
 };
 use serde::{Deserialize, Serialize};
+use url::Url;
 use validator::Validate;
 use wal::WalOptions;
 
 use crate::operations::config_diff::{DiffConfig, QuantizationConfigDiff};
+use crate::operations::feature_flags::FeatureFlags;
+use crate::operations::point_ops::WriteOrdering;
 use crate::operations::types::{
-    CollectionError, CollectionResult, SparseVectorParams, SparseVectorsConfig, VectorParams,
-    VectorParamsDiff, VectorsConfig, VectorsConfigDiff,
+    CollectionError, CollectionResult, NewVectorsConfig, SparseVectorParams, SparseVectorsConfig,
+    VectorParams, VectorParamsDiff, VectorsConfig, VectorsConfigDiff,
 };
 use crate::operations::validation;
 use crate::optimizers_builder::OptimizersConfig;
@@ -35,6 +39,23 @@ pub struct WalConfig {
     pub wal_capacity_mb: usize,
     /// Number of WAL segments to create ahead of actually used ones
     pub wal_segments_ahead: usize,
+    /// Compression of the new WAL entries. Entries are decompressed transparently on read,
+    /// regardless of this setting.
+    #[serde(default)]
+    pub wal_compression: WalCompression,
+}
+
+/// Compression of the WAL entries
+#[derive(Debug, Deserialize, Serialize, JsonSchema, PartialEq, Eq, Hash, Clone, Copy, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum WalCompression {
+    /// Entries are stored as is
+    #[default]
+    None,
+    /// Fast compression with moderate ratio
+    Lz4,
+    /// Better compression ratio at a higher CPU cost
+    Zstd,
 }
 
 impl From<&WalConfig> for WalOptions {
@@ -51,6 +72,7 @@ impl Default for WalConfig {
         WalConfig {
             wal_capacity_mb: 32,
             wal_segments_ahead: 0,
+            wal_compression: WalCompression::default(),
         }
     }
 }
@@ -61,6 +83,165 @@ pub enum ShardingMethod {
     #[default]
     Auto,
     Custom,
+    PayloadKey,
+}
+
+/// Storage engine of the on-disk payload
+#[derive(Debug, Deserialize, Serialize, JsonSchema, PartialEq, Eq, Hash, Clone, Copy, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum PayloadStorageBackend {
+    /// Payload is stored in RocksDB
+    #[default]
+    Rocksdb,
+    /// Payload is stored in memory-mapped append-only pages.
+    /// Avoids latency spikes caused by RocksDB compactions.
+    AppendLog,
+}
+
+/// Placement of the stored data
+#[derive(Debug, Deserialize, Serialize, JsonSchema, PartialEq, Eq, Hash, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+pub enum StoragePlacement {
+    /// Data is stored on disk and read through mmap, only the pages in use are cached in RAM
+    Disk,
+    /// Data is kept in RAM
+    Ram,
+}
+
+impl StoragePlacement {
+    pub fn is_on_disk(self) -> bool {
+        self == StoragePlacement::Disk
+    }
+}
+
+/// Placement of the data of a vector
+#[derive(Debug, Default, Deserialize, Serialize, JsonSchema, PartialEq, Eq, Hash, Clone, Copy)]
+pub struct VectorStoragePolicy {
+    /// Placement of the original vectors. Only applies to dense vectors.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub vectors: Option<StoragePlacement>,
+    /// Placement of the vector index: HNSW graph of dense vectors or inverted index of sparse vectors
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub index: Option<StoragePlacement>,
+    /// Placement of the quantized vectors. Only applies if quantization is enabled.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub quantization: Option<StoragePlacement>,
+}
+
+impl VectorStoragePolicy {
+    /// Placements of `self`, with not specified ones taken from `other`
+    fn or(self, other: Self) -> Self {
+        VectorStoragePolicy {
+            vectors: self.vectors.or(other.vectors),
+            index: self.index.or(other.index),
+            quantization: self.quantization.or(other.quantization),
+        }
+    }
+}
+
+/// Placement of the collection data on disk or in RAM, in one place
+///
+/// Takes precedence over the individual settings: `on_disk` of the vectors, HNSW index and sparse
+/// index, `always_ram` of quantization, `on_disk_payload` and `memmap_threshold` of the optimizers.
+/// Data without specified placement is stored according to the individual settings.
+///
+/// Existing segments are migrated to the new placement by the optimizer, once it is changed.
+#[derive(Debug, Default, Deserialize, Serialize, JsonSchema, PartialEq, Eq, Hash, Clone)]
+pub struct StoragePolicy {
+    /// Placement of the data of all vectors, unless specified for the vector in `vectors`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub all_vectors: Option<VectorStoragePolicy>,
+    /// Placement of the data of individual vectors, by vector name
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub vectors: BTreeMap<String, VectorStoragePolicy>,
+    /// Placement of the payload
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub payload: Option<StoragePlacement>,
+}
+
+impl StoragePolicy {
+    pub fn vector_policy(&self, vector_name: &str) -> VectorStoragePolicy {
+        let all_vectors = self.all_vectors.unwrap_or_default();
+        match self.vectors.get(vector_name) {
+            Some(policy) => policy.or(all_vectors),
+            None => all_vectors,
+        }
+    }
+
+    /// Check that the policy only refers to the vectors of the collection
+    pub fn check_vector_names(&self, params: &CollectionParams) -> CollectionResult<()> {
+        for vector_name in self.vectors.keys() {
+            let is_dense = params.vectors.get_params(vector_name).is_some();
+            let is_sparse = params
+                .sparse_vectors
+                .as_ref()
+                .is_some_and(|sparse_vectors| sparse_vectors.contains_key(vector_name));
+            if !is_dense && !is_sparse {
+                return Err(CollectionError::bad_input(format!(
+                    "Storage policy refers to vector {vector_name}, which is not in the collection"
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Anonymize for StoragePolicy {
+    fn anonymize(&self) -> Self {
+        StoragePolicy {
+            all_vectors: self.all_vectors,
+            vectors: self
+                .vectors
+                .iter()
+                .map(|(vector_name, policy)| (vector_name.anonymize(), *policy))
+                .collect(),
+            payload: self.payload,
+        }
+    }
+}
+
+/// External endpoint, which converts documents into vectors of the collection
+///
+/// Upserts and searches could then submit raw documents instead of vectors.
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone, PartialEq, Eq, Hash)]
+pub struct InferenceConfig {
+    /// URL of the inference endpoint, documents are sent to it in POST requests
+    pub url: Url,
+    /// Name of the model, passed to the endpoint. If not set - the endpoint uses its default model
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+    /// Name of the dense vector, which is inferred from documents. If not set - the default vector
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub vector_name: Option<String>,
+    /// Max number of documents in a single request to the endpoint. Default is 32
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate(range(min = 1))]
+    pub batch_size: Option<usize>,
+    /// Timeout of a single request to the endpoint in seconds. Default is 30
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate(range(min = 1))]
+    pub timeout_sec: Option<u64>,
+    /// Number of retries of the requests, failed because of network errors or overload of the
+    /// endpoint. Default is 3
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_retries: Option<usize>,
+}
+
+impl InferenceConfig {
+    pub fn vector_name(&self) -> &str {
+        self.vector_name.as_deref().unwrap_or(DEFAULT_VECTOR_NAME)
+    }
+
+    /// Check, that documents are inferred into a dense vector of the collection
+    pub fn check_vector_name(&self, params: &CollectionParams) -> CollectionResult<()> {
+        if params.vectors.get_params(self.vector_name()).is_none() {
+            return Err(CollectionError::bad_input(format!(
+                "Inference config refers to dense vector `{}`, which is not in the collection",
+                self.vector_name(),
+            )));
+        }
+        Ok(())
+    }
 }
 
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone, PartialEq, Eq)]
@@ -76,9 +257,14 @@ pub struct CollectionParams {
     /// Sharding method
     /// Default is Auto - points are distributed across all available shards
     /// Custom - points are distributed across shards according to shard key
+    /// PayloadKey - points are distributed across shards according to the value of `sharding_payload_key`
     #[serde(default)]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub sharding_method: Option<ShardingMethod>,
+    /// Top-level payload field, which selects the shard of the point, for the `payload_key` sharding method.
+    /// Searches, which filter by a value of this field, only query the shard of this value.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sharding_payload_key: Option<PayloadKeyType>,
     /// Number of replicas for each shard
     #[serde(default = "default_replication_factor")]
     pub replication_factor: NonZeroU32,
@@ -94,16 +280,86 @@ pub struct CollectionParams {
     /// Having more than 0 might be useful to smooth latency spikes of individual nodes.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub read_fan_out_factor: Option<u32>,
+    /// If true - reads fall back to `Partial` and `Listener` replicas, when a shard doesn't have
+    /// enough `Active` replicas. Such replicas may miss recent updates, so responses of these reads
+    /// are marked as stale.
+    /// Default is false.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub allow_degraded_reads: Option<bool>,
+    /// If true - low priority reads are served by `Listener` replicas first, to offload `Active`
+    /// ones. Listeners may miss recent updates, so responses of these reads are marked as stale.
+    /// Reads are marked as low priority with the `qdrant-read-priority: low` request header.
+    /// Default is false.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub serve_reads_from_listeners: Option<bool>,
+    /// If true - updates with `wait=true` return once `write_consistency_factor` replicas applied
+    /// them, remote replicas which are slower complete the update in background. Replicas failing
+    /// the update in background are deactivated and recovered later. Until then, reads from them
+    /// may miss the update. The next update with `medium` or `strong` ordering waits until the
+    /// previous one is completed on all replicas.
+    /// Default is false.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hedge_updates: Option<bool>,
+    /// If set - previous states of the changed points are retained for this number of seconds,
+    /// so points could be retrieved and scrolled as they were at a recent point in time.
+    /// Retained states are kept in memory and are lost on restart.
+    /// If not set - time-travel reads are not available.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub time_travel_window_sec: Option<u64>,
+    /// Max number of seconds an update waits for the consensus to deactivate replicas, which failed
+    /// to apply it. Could be overridden per request.
+    /// Default is 30 seconds.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub shard_deactivation_timeout_sec: Option<u64>,
     /// If true - point's payload will not be stored in memory.
     /// It will be read from the disk every time it is requested.
     /// This setting saves RAM by (slightly) increasing the response time.
     /// Note: those payload values that are involved in filtering and are indexed - remain in RAM.
     #[serde(default = "default_on_disk_payload")]
     pub on_disk_payload: bool,
+    /// Storage engine of the on-disk payload. Only used if `on_disk_payload` is enabled.
+    /// Default is RocksDB.
+    /// Existing segments are migrated to the new engine by the optimizer, once it is changed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub payload_storage_backend: Option<PayloadStorageBackend>,
     /// Configuration of the sparse vector storage
     #[serde(default, skip_serializing_if = "Option::is_none")]
     #[validate]
     pub sparse_vectors: Option<BTreeMap<String, SparseVectorParams>>,
+    /// If set - search requests to this collection are executed in a dedicated thread pool of this size,
+    /// isolated from other collections. The pool is capped to the number of CPUs of the node.
+    /// If not set - the shared search thread pool of the service is used.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub search_threads: Option<NonZeroU32>,
+    /// Name of the payload field, which holds the expiration time of the point as unix timestamp in seconds.
+    /// If set - expired points are deleted automatically in background.
+    /// The field is indexed automatically, points are not expired while it has no index.
+    /// Points without this field never expire.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expiration_field: Option<PayloadKeyType>,
+    /// If set - deleted points are retained for this number of seconds, hidden from all reads,
+    /// and could be restored with the `undelete` operation. After this time they are deleted permanently.
+    /// If not set - points are deleted immediately.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub soft_delete_window_sec: Option<u64>,
+    /// Placement of the collection data on disk or in RAM.
+    /// Takes precedence over the individual `on_disk`, `always_ram` and `on_disk_payload` settings.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub storage_policy: Option<StoragePolicy>,
+    /// Write ordering of the updates, which don't specify it. Could be overridden per request.
+    /// Default is `weak`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default_write_ordering: Option<WriteOrdering>,
+    /// Whether updates, which don't specify it, wait for the changes to be applied.
+    /// Could be overridden per request.
+    /// Default is false.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default_wait: Option<bool>,
+    /// External endpoint, which converts documents into vectors of the collection.
+    /// If not set - only vectors are accepted.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate]
+    pub inference: Option<InferenceConfig>,
 }
 
 impl Anonymize for CollectionParams {
@@ -112,11 +368,25 @@ impl Anonymize for CollectionParams {
             vectors: self.vectors.anonymize(),
             shard_number: self.shard_number,
             sharding_method: self.sharding_method,
+            sharding_payload_key: self.sharding_payload_key.clone(),
             replication_factor: self.replication_factor,
             write_consistency_factor: self.write_consistency_factor,
             read_fan_out_factor: self.read_fan_out_factor,
+            allow_degraded_reads: self.allow_degraded_reads,
+            serve_reads_from_listeners: self.serve_reads_from_listeners,
+            hedge_updates: self.hedge_updates,
+            time_travel_window_sec: self.time_travel_window_sec,
+            shard_deactivation_timeout_sec: self.shard_deactivation_timeout_sec,
             on_disk_payload: self.on_disk_payload,
+            payload_storage_backend: self.payload_storage_backend,
             sparse_vectors: self.sparse_vectors.anonymize(),
+            search_threads: self.search_threads,
+            expiration_field: self.expiration_field.clone(),
+            soft_delete_window_sec: self.soft_delete_window_sec,
+            storage_policy: self.storage_policy.anonymize(),
+            default_write_ordering: self.default_write_ordering,
+            default_wait: self.default_wait,
+            inference: self.inference.clone(),
         }
     }
 }
@@ -149,6 +419,77 @@ pub struct CollectionConfig {
     pub wal_config: WalConfig,
     #[serde(default)]
     pub quantization_config: Option<QuantizationConfig>,
+    #[serde(default)]
+    #[validate]
+    pub strict_mode_config: Option<StrictModeConfig>,
+    /// Flags of the experimental features, unset ones are inherited from the node configuration
+    #[serde(default)]
+    pub feature_flags: Option<FeatureFlags>,
+}
+
+/// Restrictions for the requests to the collection.
+///
+/// Protects the cluster from accidental heavy requests, which might affect other users.
+/// Requests violating the restrictions are rejected before execution.
+#[derive(
+    Debug, Default, Deserialize, Serialize, JsonSchema, Validate, Clone, Merge, PartialEq, Eq, Hash,
+)]
+pub struct StrictModeConfig {
+    /// Whether strict mode is enabled for the collection. Default: false
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub enabled: Option<bool>,
+    /// Max allowed `limit` parameter of search, recommend, discover, scroll and group requests
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate(range(min = 1))]
+    pub max_query_limit: Option<usize>,
+    /// Max allowed `offset` parameter of search, recommend and discover requests
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_query_offset: Option<usize>,
+    /// Max number of requests in a batch request, or number of points in a single update
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate(range(min = 1))]
+    pub max_batch_size: Option<usize>,
+    /// Max number of conditions in a filter, including conditions of nested filters
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate(range(min = 1))]
+    pub max_filter_conditions: Option<usize>,
+    /// Allow filtering by payload fields without payload index. Default: false
+    ///
+    /// Filtering by unindexed fields requires full scan of the payload storage.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub unindexed_filtering_allowed: Option<bool>,
+    /// Max number of points in the collection
+    ///
+    /// Inserts and updates are rejected once the collection reaches the quota, deletes are always
+    /// allowed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate(range(min = 1))]
+    pub max_points_count: Option<usize>,
+    /// Max size of the collection data on disk, in bytes
+    ///
+    /// Inserts and updates are rejected once the collection reaches the quota, deletes are always
+    /// allowed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate(range(min = 1))]
+    pub max_disk_usage_bytes: Option<usize>,
+    /// Max number of searches executed in the collection at the same time
+    ///
+    /// Further searches wait in the search queue of the node, and are rejected with an overload
+    /// error if the queue is full or the wait is too long.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate(range(min = 1))]
+    pub max_concurrent_searches: Option<usize>,
+}
+
+impl StrictModeConfig {
+    pub fn is_enabled(&self) -> bool {
+        self.enabled.unwrap_or(false)
+    }
+
+    /// Whether any quota on the amount of collection data is configured
+    pub fn has_quotas(&self) -> bool {
+        self.max_points_count.is_some() || self.max_disk_usage_bytes.is_some()
+    }
 }
 
 impl CollectionConfig {
@@ -189,11 +530,103 @@ impl CollectionParams {
             vectors: Default::default(),
             shard_number: default_shard_number(),
             sharding_method: None,
+            sharding_payload_key: None,
             replication_factor: default_replication_factor(),
             write_consistency_factor: default_write_consistency_factor(),
             read_fan_out_factor: None,
+            allow_degraded_reads: None,
+            serve_reads_from_listeners: None,
+            hedge_updates: None,
+            time_travel_window_sec: None,
+            shard_deactivation_timeout_sec: None,
             on_disk_payload: default_on_disk_payload(),
+            payload_storage_backend: None,
             sparse_vectors: None,
+            search_threads: None,
+            expiration_field: None,
+            soft_delete_window_sec: None,
+            storage_policy: None,
+            default_write_ordering: None,
+            default_wait: None,
+            inference: None,
+        }
+    }
+
+    /// Payload storage type of the segments, according to the collection params
+    pub fn payload_storage_type(&self) -> PayloadStorageType {
+        let on_disk_payload = self
+            .storage_policy
+            .as_ref()
+            .and_then(|policy| policy.payload)
+            .map_or(self.on_disk_payload, StoragePlacement::is_on_disk);
+        if !on_disk_payload {
+            return PayloadStorageType::InMemory;
+        }
+        match self.payload_storage_backend.unwrap_or_default() {
+            PayloadStorageBackend::Rocksdb => PayloadStorageType::OnDisk,
+            PayloadStorageBackend::AppendLog => PayloadStorageType::AppendLog,
+        }
+    }
+
+    fn vector_storage_policy(&self, vector_name: &str) -> VectorStoragePolicy {
+        self.storage_policy
+            .as_ref()
+            .map(|policy| policy.vector_policy(vector_name))
+            .unwrap_or_default()
+    }
+
+    /// Whether dense vectors are required to be stored on disk
+    ///
+    /// `None` if not configured, then the placement is defined by the `memmap_threshold`.
+    pub fn vectors_on_disk(&self, vector_name: &str) -> Option<bool> {
+        self.vector_storage_policy(vector_name)
+            .vectors
+            .map(StoragePlacement::is_on_disk)
+            .or_else(|| {
+                self.vectors
+                    .get_params(vector_name)
+                    .and_then(|params| params.on_disk)
+            })
+    }
+
+    /// Whether sparse vectors index is required to be stored on disk
+    pub fn sparse_index_on_disk(&self, vector_name: &str) -> Option<bool> {
+        self.vector_storage_policy(vector_name)
+            .index
+            .map(StoragePlacement::is_on_disk)
+            .or_else(|| {
+                self.sparse_vectors
+                    .as_ref()
+                    .and_then(|sparse_vectors| sparse_vectors.get(vector_name))
+                    .and_then(|params| params.index)
+                    .and_then(|index| index.on_disk)
+            })
+    }
+
+    /// Whether HNSW index is required to be stored on disk by the storage policy
+    ///
+    /// `None` if not configured, then the placement is defined by the HNSW config.
+    pub fn hnsw_on_disk(&self, vector_name: &str) -> Option<bool> {
+        self.vector_storage_policy(vector_name)
+            .index
+            .map(StoragePlacement::is_on_disk)
+    }
+
+    /// Apply the storage policy to the HNSW config of the vector
+    pub fn place_hnsw_config(&self, vector_name: &str, hnsw_config: &mut HnswConfig) {
+        if let Some(on_disk) = self.hnsw_on_disk(vector_name) {
+            hnsw_config.on_disk = Some(on_disk);
+        }
+    }
+
+    /// Apply the storage policy to the quantization config of the vector
+    pub fn place_quantization_config(
+        &self,
+        vector_name: &str,
+        quantization_config: &mut QuantizationConfig,
+    ) {
+        if let Some(placement) = self.vector_storage_policy(vector_name).quantization {
+            quantization_config.set_always_ram(!placement.is_on_disk());
         }
     }
 
@@ -261,6 +694,7 @@ impl CollectionParams {
                 hnsw_config,
                 quantization_config,
                 on_disk,
+                dimensionality_reduction_transform,
             } = update_params.clone();
 
             if let Some(hnsw_diff) = hnsw_config {
@@ -289,6 +723,23 @@ impl CollectionParams {
             if let Some(on_disk) = on_disk {
                 vector_params.on_disk = Some(on_disk);
             }
+
+            if let Some(transform) = dimensionality_reduction_transform {
+                let size = vector_params.size.get() as usize;
+                let Some(reduction) = &mut vector_params.dimensionality_reduction else {
+                    return Err(CollectionError::bad_input(format!(
+                        "Vector {vector_name} has no dimensionality reduction configured"
+                    )));
+                };
+                // Stored vectors are already transformed, they can't be transformed again
+                if reduction.transform.is_some() {
+                    return Err(CollectionError::bad_input(format!(
+                        "Dimensionality reduction of vector {vector_name} is already trained"
+                    )));
+                }
+                transform.check_dimensions(reduction.input_size.get() as usize, size)?;
+                reduction.transform = Some(transform);
+            }
         }
         Ok(())
     }
@@ -313,6 +764,15 @@ impl CollectionParams {
         Ok(())
     }
 
+    /// Add new named vectors to the collection
+    ///
+    /// Use [`NewVectorsConfig::check_vector_names`] to check that the vectors can be added.
+    pub fn add_vectors(&mut self, new_vectors: &NewVectorsConfig) {
+        if let VectorsConfig::Multi(vectors) = &mut self.vectors {
+            vectors.extend(new_vectors.0.clone());
+        }
+    }
+
     /// Convert into unoptimized named vector data configs
     ///
     /// It is the job of the segment optimizer to change this configuration with optimized settings
@@ -332,11 +792,13 @@ impl CollectionParams {
                         // Disabled quantization
                         quantization_config: None,
                         // Default to in memory storage
-                        storage_type: if params.on_disk.unwrap_or_default() {
+                        storage_type: if self.vectors_on_disk(name).unwrap_or_default() {
                             VectorStorageType::ChunkedMmap
                         } else {
                             VectorStorageType::Memory
                         },
+                        multivector_config: params.multivector_config,
+                        datatype: params.datatype,
                     },
                 )
             })
@@ -8,6 +8,7 @@ use segment::vector_storage::query::context_query::{ContextPair, ContextQuery};
 use segment::vector_storage::query::discovery_query::DiscoveryQuery;
 use tokio::sync::RwLockReadGuard;
 
+use crate::collection::access_tags::AccessTags;
 use crate::collection::Collection;
 use crate::common::batching::batch_requests;
 use crate::common::fetch_vectors::{
@@ -20,6 +21,8 @@ use crate::operations::types::{
     CollectionError, CollectionResult, CoreSearchRequest, CoreSearchRequestBatch,
     DiscoverRequestInternal, QueryEnum,
 };
+use crate::shards::replica_set::read_priority::ReadPriority;
+use crate::shards::replica_set::stale_reads::StaleReads;
 
 fn discovery_into_core_search(
     request: DiscoverRequestInternal,
@@ -111,6 +114,9 @@ fn discovery_into_core_search(
         with_payload: request.with_payload,
         with_vector: request.with_vector,
         score_threshold: None,
+        prefetch: None,
+        rescore: None,
+        distinct_by: None,
     };
 
     Ok(core_search)
@@ -123,6 +129,9 @@ pub async fn discover<'a, F, Fut>(
     read_consistency: Option<ReadConsistency>,
     shard_selector: ShardSelectorInternal,
     timeout: Option<Duration>,
+    access_tags: Option<&AccessTags>,
+    read_priority: ReadPriority,
+    stale_reads: Option<&StaleReads>,
 ) -> CollectionResult<Vec<ScoredPoint>>
 where
     F: Fn(String) -> Fut,
@@ -140,6 +149,9 @@ where
         collection_by_name,
         read_consistency,
         timeout,
+        access_tags,
+        read_priority,
+        stale_reads,
     )
     .await?;
     Ok(results.into_iter().next().unwrap())
@@ -151,6 +163,9 @@ pub async fn discover_batch<'a, F, Fut>(
     collection_by_name: F,
     read_consistency: Option<ReadConsistency>,
     timeout: Option<Duration>,
+    access_tags: Option<&AccessTags>,
+    read_priority: ReadPriority,
+    stale_reads: Option<&StaleReads>,
 ) -> CollectionResult<Vec<Vec<ScoredPoint>>>
 where
     F: Fn(String) -> Fut,
@@ -185,6 +200,9 @@ where
         collection,
         collection_by_name,
         read_consistency,
+        access_tags,
+        read_priority,
+        stale_reads,
     )
     .await?;
 
@@ -213,8 +231,12 @@ where
             requests.push(collection.core_search_batch(
                 core_search_batch_request,
                 read_consistency,
+                None,
                 shard_selector,
                 timeout,
+                access_tags,
+                read_priority,
+                stale_reads,
             ));
 
             Ok(())
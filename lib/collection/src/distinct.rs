@@ -0,0 +1,171 @@
+//! Deduplication of the search results by a payload field
+//!
+//! Only the best scored point is kept for each value of the field, e.g. one chunk per source
+//! document. It is a lighter alternative to the grouping, when only the best point of each group
+//! is needed. Each shard deduplicates its own candidates, with the values read locally, and keeps
+//! the values in the payload of its results. So the results of the shards are deduplicated again
+//! while they are merged, and only then the payload is cut back to the requested one.
+//!
+//! Points without the field are never deduplicated. Arrays are equal only if all of their values
+//! are equal, in the same order.
+
+use std::collections::HashSet;
+
+use schemars::JsonSchema;
+use segment::types::{
+    Payload, PayloadContainer, PayloadKeyType, ScoredPoint, WithPayload, WithPayloadInterface,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use validator::Validate;
+
+/// By default, each shard deduplicates this many times more candidates, than it returns
+const DEFAULT_CANDIDATES_FACTOR: usize = 4;
+
+/// Deduplication of the search results by a payload field
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone)]
+#[serde(rename_all = "snake_case")]
+pub struct DistinctBy {
+    /// Payload field, only the best scored point is returned for each of its values.
+    /// Nested fields are referenced by the path: `meta.document_id`
+    #[validate(length(min = 1))]
+    pub key: PayloadKeyType,
+    /// Number of best candidates, deduplicated in each shard. Larger values return more
+    /// results, if many points share the same value. Default: `4 * (offset + limit)`
+    #[validate(range(min = 1))]
+    pub candidates: Option<usize>,
+}
+
+/// Deduplication of the core search, executed by the shards and by the merge of their results
+#[derive(Debug, Clone)]
+pub struct CoreDistinct {
+    pub key: PayloadKeyType,
+    /// Number of best candidates, deduplicated in each shard
+    pub candidates: usize,
+}
+
+impl CoreDistinct {
+    pub fn new(distinct: DistinctBy, offset: usize, limit: usize) -> Self {
+        Self {
+            key: distinct.key,
+            candidates: distinct
+                .candidates
+                .unwrap_or((offset + limit) * DEFAULT_CANDIDATES_FACTOR),
+        }
+    }
+
+    /// Top-level key of the payload, which contains the field
+    pub fn payload_key(&self) -> &str {
+        top_level_key(&self.key)
+    }
+}
+
+fn top_level_key(key: &str) -> &str {
+    key.split(['.', '[']).next().unwrap_or(key)
+}
+
+/// Value of the field to compare the points by, `None` if the point has no such field
+fn distinct_value(payload: &Payload, key: &str) -> Option<String> {
+    let values: Vec<&Value> = payload.get_value(key).into_iter().collect();
+    if values.is_empty() {
+        return None;
+    }
+    serde_json::to_string(&values).ok()
+}
+
+/// Keep only the first point for each value of the field, at most `limit` points
+///
+/// Points are expected to be ordered from the best to the worst.
+pub fn distinct_points(
+    points: impl IntoIterator<Item = ScoredPoint>,
+    key: &str,
+    limit: usize,
+) -> Vec<ScoredPoint> {
+    let mut seen = HashSet::new();
+    points
+        .into_iter()
+        .filter(|point| {
+            match point
+                .payload
+                .as_ref()
+                .and_then(|payload| distinct_value(payload, key))
+            {
+                Some(value) => seen.insert(value),
+                None => true,
+            }
+        })
+        .take(limit)
+        .collect()
+}
+
+/// Cut the payload of the deduplicated points back to the requested one
+pub fn restore_payload(points: &mut [ScoredPoint], with_payload: Option<&WithPayloadInterface>) {
+    let with_payload = with_payload.map(WithPayload::from).unwrap_or_default();
+    for point in points {
+        point.payload = match (with_payload.enable, &with_payload.payload_selector) {
+            (false, _) => None,
+            (true, Some(selector)) => point
+                .payload
+                .take()
+                .map(|payload| selector.process(payload)),
+            (true, None) => point.payload.take(),
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use segment::types::{PayloadSelector, PayloadSelectorInclude};
+    use serde_json::json;
+
+    use super::*;
+
+    fn point(id: u64, payload: Value) -> ScoredPoint {
+        ScoredPoint {
+            id: id.into(),
+            version: 0,
+            score: 0.0,
+            payload: Some(serde_json::from_value(payload).unwrap()),
+            vector: None,
+            shard_key: None,
+        }
+    }
+
+    #[test]
+    fn test_distinct_points() {
+        let points = vec![
+            point(1, json!({ "doc": { "id": 1 } })),
+            point(2, json!({ "doc": { "id": 2 } })),
+            point(3, json!({ "doc": { "id": 1 } })),
+            point(4, json!({})),
+            point(5, json!({})),
+            point(6, json!({ "doc": { "id": [2] } })),
+            point(7, json!({ "doc": { "id": 3 } })),
+        ];
+
+        let ids = |points: Vec<ScoredPoint>| points.iter().map(|p| p.id).collect::<Vec<_>>();
+        assert_eq!(
+            ids(distinct_points(points.clone(), "doc.id", 10)),
+            vec![1.into(), 2.into(), 4.into(), 5.into(), 6.into(), 7.into()],
+        );
+        assert_eq!(
+            ids(distinct_points(points, "doc.id", 2)),
+            vec![1.into(), 2.into()],
+        );
+    }
+
+    #[test]
+    fn test_restore_payload() {
+        let mut points = vec![point(1, json!({ "doc": 1, "text": "a" }))];
+
+        let selector = PayloadSelector::Include(PayloadSelectorInclude::new(vec!["text".into()]));
+        restore_payload(&mut points, Some(&WithPayloadInterface::Selector(selector)));
+        assert_eq!(
+            points[0].payload,
+            Some(serde_json::from_value(json!({ "text": "a" })).unwrap()),
+        );
+
+        restore_payload(&mut points, None);
+        assert_eq!(points[0].payload, None);
+    }
+}
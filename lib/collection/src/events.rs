@@ -0,0 +1,176 @@
+use chrono::{DateTime, Utc};
+use schemars::JsonSchema;
+use segment::types::{PointIdType, ScoreType};
+use serde::{Deserialize, Serialize};
+use tokio::sync::broadcast;
+
+use crate::shards::shard::{PeerId, ShardId};
+use crate::shards::CollectionId;
+
+/// Type of the collection lifecycle event, used to filter events on the subscriber side
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum CollectionEventType {
+    OptimizationFinished,
+    ReplicaDeactivated,
+    SnapshotCompleted,
+    ShardTransferFinished,
+    StoredQueryMatched,
+}
+
+#[derive(Debug, Serialize, Clone, PartialEq)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum CollectionEventKind {
+    /// Optimization of the shard segments is finished
+    OptimizationFinished,
+    /// Replica of the shard was marked as dead
+    ReplicaDeactivated { peer_id: PeerId },
+    /// Collection snapshot is created and moved into the snapshots directory
+    SnapshotCompleted { snapshot_name: String },
+    /// Shard transfer is finished and registered on this peer
+    ShardTransferFinished {
+        from: PeerId,
+        to: PeerId,
+        sync: bool,
+    },
+    /// Upserted point matches the stored query
+    StoredQueryMatched {
+        query: String,
+        point_id: PointIdType,
+        /// Score of the point, if the query has a vector
+        #[serde(skip_serializing_if = "Option::is_none")]
+        score: Option<ScoreType>,
+    },
+}
+
+impl CollectionEventKind {
+    pub fn event_type(&self) -> CollectionEventType {
+        match self {
+            CollectionEventKind::OptimizationFinished => CollectionEventType::OptimizationFinished,
+            CollectionEventKind::ReplicaDeactivated { .. } => {
+                CollectionEventType::ReplicaDeactivated
+            }
+            CollectionEventKind::SnapshotCompleted { .. } => CollectionEventType::SnapshotCompleted,
+            CollectionEventKind::ShardTransferFinished { .. } => {
+                CollectionEventType::ShardTransferFinished
+            }
+            CollectionEventKind::StoredQueryMatched { .. } => {
+                CollectionEventType::StoredQueryMatched
+            }
+        }
+    }
+}
+
+/// Lifecycle event of a collection, delivered to subscribers of the event stream
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct CollectionEvent {
+    pub collection_name: CollectionId,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub shard_id: Option<ShardId>,
+    #[serde(flatten)]
+    pub kind: CollectionEventKind,
+    pub timestamp: DateTime<Utc>,
+}
+
+/// Sender of collection lifecycle events, shared by all collections of the node
+///
+/// Default notifier has no channel, so nobody is interested in events and they are dropped
+/// right away.
+#[derive(Clone, Debug, Default)]
+pub struct EventNotifier {
+    sender: Option<broadcast::Sender<CollectionEvent>>,
+}
+
+impl EventNotifier {
+    /// All events are broadcasted to the receivers of the channel of `sender`
+    pub fn new(sender: broadcast::Sender<CollectionEvent>) -> Self {
+        Self {
+            sender: Some(sender),
+        }
+    }
+
+    /// Whether events are delivered anywhere, so it is worth to produce them
+    pub fn is_enabled(&self) -> bool {
+        self.sender.is_some()
+    }
+
+    /// Emit collection event into the event stream, if there is one.
+    ///
+    /// This function never blocks: if there are no subscribers, the event is dropped.
+    pub fn notify(
+        &self,
+        collection_name: CollectionId,
+        shard_id: Option<ShardId>,
+        kind: CollectionEventKind,
+    ) {
+        let Some(sender) = &self.sender else {
+            return;
+        };
+
+        let event = CollectionEvent {
+            collection_name,
+            shard_id,
+            kind,
+            timestamp: Utc::now(),
+        };
+
+        if sender.send(event).is_err() {
+            log::trace!("No subscribers for collection events, event dropped");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_event_serialization() {
+        let event = CollectionEvent {
+            collection_name: "test".to_string(),
+            shard_id: Some(1),
+            kind: CollectionEventKind::ShardTransferFinished {
+                from: 1,
+                to: 2,
+                sync: true,
+            },
+            timestamp: Utc::now(),
+        };
+
+        let json = serde_json::to_value(&event).unwrap();
+        assert_eq!(json["event"], "shard_transfer_finished");
+        assert_eq!(json["collection_name"], "test");
+        assert_eq!(json["shard_id"], 1);
+        assert_eq!(json["to"], 2);
+        assert_eq!(
+            event.kind.event_type(),
+            CollectionEventType::ShardTransferFinished
+        );
+    }
+
+    #[test]
+    fn test_notifiers_are_independent() {
+        let (sender, mut receiver) = broadcast::channel(4);
+        let notifier = EventNotifier::new(sender);
+        let disabled = EventNotifier::default();
+        assert!(notifier.is_enabled());
+        assert!(!disabled.is_enabled());
+
+        disabled.notify(
+            "other".to_string(),
+            None,
+            CollectionEventKind::OptimizationFinished,
+        );
+        notifier.clone().notify(
+            "test".to_string(),
+            Some(0),
+            CollectionEventKind::OptimizationFinished,
+        );
+
+        let event = receiver.try_recv().unwrap();
+        assert_eq!(event.collection_name, "test");
+        assert_eq!(event.shard_id, Some(0));
+        // Events of other notifiers are not delivered into this channel
+        assert!(receiver.try_recv().is_err());
+    }
+}
@@ -5,12 +5,15 @@ use itertools::Itertools;
 use tokio::sync::RwLockReadGuard;
 
 use super::group_by::{group_by, GroupRequest};
+use crate::collection::access_tags::AccessTags;
 use crate::collection::Collection;
 use crate::lookup::lookup_ids;
 use crate::lookup::types::PseudoId;
 use crate::operations::consistency_params::ReadConsistency;
 use crate::operations::shard_selector_internal::ShardSelectorInternal;
 use crate::operations::types::{CollectionError, CollectionResult, PointGroup};
+use crate::shards::replica_set::read_priority::ReadPriority;
+use crate::shards::replica_set::stale_reads::StaleReads;
 
 /// Builds on top of the group_by function to add lookup and possibly other features
 pub struct GroupBy<'a, F, Fut>
@@ -25,6 +28,9 @@ where
     read_consistency: Option<ReadConsistency>,
     shard_selection: ShardSelectorInternal,
     timeout: Option<Duration>,
+    access_tags: Option<AccessTags>,
+    read_priority: ReadPriority,
+    stale_reads: Option<StaleReads>,
 }
 
 impl<'a, F, Fut> GroupBy<'a, F, Fut>
@@ -41,6 +47,9 @@ where
             read_consistency: None,
             shard_selection: ShardSelectorInternal::All,
             timeout: None,
+            access_tags: None,
+            read_priority: ReadPriority::Normal,
+            stale_reads: None,
         }
     }
 
@@ -59,6 +68,21 @@ where
         self
     }
 
+    pub fn set_access_tags(mut self, access_tags: Option<AccessTags>) -> Self {
+        self.access_tags = access_tags;
+        self
+    }
+
+    pub fn set_read_priority(mut self, read_priority: ReadPriority) -> Self {
+        self.read_priority = read_priority;
+        self
+    }
+
+    pub fn set_stale_reads(mut self, stale_reads: Option<StaleReads>) -> Self {
+        self.stale_reads = stale_reads;
+        self
+    }
+
     /// Runs the group by operation, optionally with a timeout.
     pub async fn execute(self) -> CollectionResult<Vec<PointGroup>> {
         if let Some(timeout) = self.timeout {
@@ -84,6 +108,9 @@ where
                 self.collection_by_name.clone(),
                 self.read_consistency,
                 self.shard_selection.clone(),
+                self.access_tags.as_ref(),
+                self.read_priority,
+                self.stale_reads.as_ref(),
             )
             .await?;
 
@@ -93,6 +120,9 @@ where
             self.read_consistency,
             self.shard_selection.clone(),
             self.timeout,
+            self.access_tags.as_ref(),
+            self.read_priority,
+            self.stale_reads.as_ref(),
         )
         .await?;
 
@@ -110,6 +140,9 @@ where
                     self.collection_by_name,
                     self.read_consistency,
                     &self.shard_selection,
+                    self.access_tags.as_ref(),
+                    self.read_priority,
+                    self.stale_reads.as_ref(),
                 )
                 .await?
             };
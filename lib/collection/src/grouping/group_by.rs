@@ -11,6 +11,7 @@ use tokio::sync::RwLockReadGuard;
 
 use super::aggregator::GroupsAggregator;
 use super::types::CoreGroupRequest;
+use crate::collection::access_tags::AccessTags;
 use crate::collection::Collection;
 use crate::common::fetch_vectors;
 use crate::lookup::WithLookup;
@@ -21,6 +22,8 @@ use crate::operations::types::{
     RecommendRequestInternal, SearchGroupsRequestInternal, SearchRequestInternal,
 };
 use crate::recommendations::recommend_into_core_search;
+use crate::shards::replica_set::read_priority::ReadPriority;
+use crate::shards::replica_set::stale_reads::StaleReads;
 
 const MAX_GET_GROUPS_REQUESTS: usize = 5;
 const MAX_GROUP_FILLING_REQUESTS: usize = 5;
@@ -74,6 +77,9 @@ impl GroupRequest {
         collection_by_name: F,
         read_consistency: Option<ReadConsistency>,
         shard_selection: ShardSelectorInternal,
+        access_tags: Option<&AccessTags>,
+        read_priority: ReadPriority,
+        stale_reads: Option<&StaleReads>,
     ) -> CollectionResult<CoreGroupRequest>
     where
         F: Fn(String) -> Fut,
@@ -87,6 +93,9 @@ impl GroupRequest {
                     collection,
                     collection_by_name,
                     read_consistency,
+                    access_tags,
+                    read_priority,
+                    stale_reads,
                 )
                 .await?;
 
@@ -117,6 +126,9 @@ impl CoreGroupRequest {
         read_consistency: Option<ReadConsistency>,
         shard_selection: ShardSelectorInternal,
         timeout: Option<Duration>,
+        access_tags: Option<&AccessTags>,
+        read_priority: ReadPriority,
+        stale_reads: Option<&StaleReads>,
     ) -> CollectionResult<Vec<ScoredPoint>> {
         let mut request = self.source.clone();
 
@@ -132,7 +144,15 @@ impl CoreGroupRequest {
         request.with_vector = None;
 
         collection
-            .search(request, read_consistency, &shard_selection, timeout)
+            .search(
+                request,
+                read_consistency,
+                &shard_selection,
+                timeout,
+                access_tags,
+                read_priority,
+                stale_reads,
+            )
             .await
     }
 }
@@ -230,6 +250,9 @@ pub async fn group_by(
     read_consistency: Option<ReadConsistency>,
     shard_selection: ShardSelectorInternal,
     timeout: Option<Duration>,
+    access_tags: Option<&AccessTags>,
+    read_priority: ReadPriority,
+    stale_reads: Option<&StaleReads>,
 ) -> CollectionResult<Vec<PointGroup>> {
     let score_ordering = {
         let vector_name = request.source.query.get_vector_name();
@@ -291,6 +314,9 @@ pub async fn group_by(
                 read_consistency,
                 shard_selection.clone(),
                 timeout,
+                access_tags,
+                read_priority,
+                stale_reads,
             )
             .await?;
 
@@ -351,6 +377,9 @@ pub async fn group_by(
                     read_consistency,
                     shard_selection.clone(),
                     timeout,
+                    access_tags,
+                    read_priority,
+                    stale_reads,
                 )
                 .await?;
 
@@ -384,6 +413,9 @@ pub async fn group_by(
             request.source.with_vector.unwrap_or_default(),
             read_consistency,
             &shard_selection,
+            access_tags,
+            read_priority,
+            stale_reads,
         )
         .await?
         .into_iter()
@@ -0,0 +1,264 @@
+//! Search by a dense and a sparse vector at once, with the results fused into a single list
+//!
+//! Both vectors are searched in their own vector spaces of the collection, each search returns
+//! its best candidates across all shards. Scores of dense and sparse vectors are not comparable,
+//! so candidates are fused by their ranks or by their normalized scores, before the final cut.
+
+use std::collections::HashMap;
+
+use schemars::JsonSchema;
+use segment::data_types::vectors::{NamedSparseVector, NamedVectorStruct};
+use segment::types::{
+    ExtendedPointId, Filter, Order, ScoreType, ScoredPoint, SearchParams, WithPayloadInterface,
+    WithVector,
+};
+use serde::{Deserialize, Serialize};
+use validator::{Validate, ValidationError};
+
+use crate::multi_search::min_max_normalize;
+use crate::operations::shard_key_selector::ShardKeySelector;
+use crate::operations::types::{CoreSearchRequest, QueryEnum};
+
+const DEFAULT_RRF_K: usize = 60;
+
+fn default_rrf_k() -> usize {
+    DEFAULT_RRF_K
+}
+
+/// Search by a dense and a sparse vector, with the results fused into one list
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone)]
+#[serde(rename_all = "snake_case")]
+pub struct HybridSearchRequest {
+    /// Dense vector to search with: unnamed or with the name of the dense vector
+    #[validate(custom = "validate_dense_vector")]
+    pub dense: NamedVectorStruct,
+    /// Sparse vector to search with
+    #[validate]
+    pub sparse: NamedSparseVector,
+    /// How results of the dense and the sparse search are fused. Default: `rrf`
+    #[serde(default)]
+    #[validate(custom = "validate_fusion")]
+    pub fusion: Fusion,
+    /// Number of candidates, found by each of the vectors before the fusion.
+    /// Default: `offset + limit`
+    #[validate(range(min = 1))]
+    pub prefetch_limit: Option<usize>,
+    /// Look only for points which satisfies this conditions
+    #[validate]
+    pub filter: Option<Filter>,
+    /// Additional search params, applied to both searches
+    #[validate]
+    pub params: Option<SearchParams>,
+    /// Max number of result to return
+    #[validate(range(min = 1))]
+    pub limit: usize,
+    /// Offset of the first result to return, applied to the fused results
+    pub offset: Option<usize>,
+    /// Select which payload to return with the response. Default: None
+    pub with_payload: Option<WithPayloadInterface>,
+    /// Whether to return the point vector with the result?
+    #[serde(default, alias = "with_vectors")]
+    pub with_vector: Option<WithVector>,
+    /// Specify in which shards to look for the points, if not specified - look in all shards
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub shard_key: Option<ShardKeySelector>,
+    /// Token returned by the update: only search in replicas, which have already applied it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub consistency_token: Option<String>,
+}
+
+impl HybridSearchRequest {
+    /// Dense and sparse searches of the candidates
+    ///
+    /// Each search returns `prefetch_limit` best points, the offset is applied to the fused results.
+    pub fn searches(&self) -> [CoreSearchRequest; 2] {
+        let candidates = self
+            .prefetch_limit
+            .unwrap_or(0)
+            .max(self.offset.unwrap_or(0) + self.limit);
+        let search = |vector: NamedVectorStruct| CoreSearchRequest {
+            query: QueryEnum::Nearest(vector),
+            filter: self.filter.clone(),
+            params: self.params,
+            limit: candidates,
+            offset: 0,
+            with_payload: self.with_payload.clone(),
+            with_vector: self.with_vector.clone(),
+            score_threshold: None,
+            prefetch: None,
+            rescore: None,
+            distinct_by: None,
+        };
+        [
+            search(self.dense.clone()),
+            search(NamedVectorStruct::Sparse(self.sparse.clone())),
+        ]
+    }
+}
+
+/// Method of fusion of the dense and the sparse search results
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum Fusion {
+    /// Reciprocal rank fusion: score of the point is the sum of `1 / (k + rank)` over the results
+    /// it is found in, where rank starts from 1
+    Rrf {
+        /// Larger values reduce the advantage of the top ranked points. Default: 60
+        #[serde(default = "default_rrf_k")]
+        k: usize,
+    },
+    /// Weighted sum of the scores, scores of each search are min-max normalized into `[0, 1]`
+    /// first. Points, which are not found by one of the searches, get 0 for it
+    WeightedSum {
+        dense_weight: ScoreType,
+        sparse_weight: ScoreType,
+    },
+}
+
+impl Default for Fusion {
+    fn default() -> Self {
+        Fusion::Rrf { k: DEFAULT_RRF_K }
+    }
+}
+
+fn validate_dense_vector(vector: &NamedVectorStruct) -> Result<(), ValidationError> {
+    match vector {
+        NamedVectorStruct::Default(_) | NamedVectorStruct::Dense(_) => Ok(()),
+        NamedVectorStruct::Sparse(_) | NamedVectorStruct::MultiDense(_) => {
+            Err(ValidationError::new("must be a dense vector"))
+        }
+    }
+}
+
+fn validate_fusion(fusion: &Fusion) -> Result<(), ValidationError> {
+    match fusion {
+        Fusion::Rrf { .. } => Ok(()),
+        Fusion::WeightedSum {
+            dense_weight,
+            sparse_weight,
+        } => {
+            let is_valid = |weight: &ScoreType| weight.is_finite() && *weight >= 0.0;
+            if is_valid(dense_weight) && is_valid(sparse_weight) {
+                Ok(())
+            } else {
+                Err(ValidationError::new("weights must be non-negative numbers"))
+            }
+        }
+    }
+}
+
+/// Results of one of the searches with the order of their scores
+pub struct SearchCandidates {
+    pub order: Order,
+    pub points: Vec<ScoredPoint>,
+}
+
+/// Fuse results of the dense and the sparse search into one list, ordered by the fused score
+///
+/// Each point keeps the payload and vector of the first search it is found in. The fused list
+/// skips `offset` best points and keeps at most `limit`.
+pub fn fuse_results(
+    dense: SearchCandidates,
+    sparse: SearchCandidates,
+    fusion: Fusion,
+    offset: usize,
+    limit: usize,
+) -> Vec<ScoredPoint> {
+    let mut fused: Vec<ScoredPoint> = Vec::new();
+    let mut positions: HashMap<ExtendedPointId, usize> = HashMap::new();
+
+    for (candidates, is_dense) in [(dense, true), (sparse, false)] {
+        let scores = candidates.points.iter().map(|point| point.score);
+        let min = scores.clone().fold(ScoreType::INFINITY, ScoreType::min);
+        let max = scores.fold(ScoreType::NEG_INFINITY, ScoreType::max);
+
+        for (rank, mut point) in candidates.points.into_iter().enumerate() {
+            let score = match fusion {
+                Fusion::Rrf { k } => 1.0 / (k + rank + 1) as ScoreType,
+                Fusion::WeightedSum {
+                    dense_weight,
+                    sparse_weight,
+                } => {
+                    let weight = if is_dense {
+                        dense_weight
+                    } else {
+                        sparse_weight
+                    };
+                    weight * min_max_normalize(point.score, min, max, candidates.order)
+                }
+            };
+            match positions.get(&point.id) {
+                Some(&position) => fused[position].score += score,
+                None => {
+                    positions.insert(point.id, fused.len());
+                    point.score = score;
+                    fused.push(point);
+                }
+            }
+        }
+    }
+
+    // Stable sort, so points with equal scores keep the order of the dense results
+    fused.sort_by(|a, b| b.score.total_cmp(&a.score));
+    fused.into_iter().skip(offset).take(limit).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn candidates(order: Order, points: &[(u64, ScoreType)]) -> SearchCandidates {
+        SearchCandidates {
+            order,
+            points: points
+                .iter()
+                .map(|&(id, score)| ScoredPoint {
+                    id: id.into(),
+                    version: 0,
+                    score,
+                    payload: None,
+                    vector: None,
+                    shard_key: None,
+                })
+                .collect(),
+        }
+    }
+
+    fn ids(points: &[ScoredPoint]) -> Vec<ExtendedPointId> {
+        points.iter().map(|point| point.id).collect()
+    }
+
+    #[test]
+    fn test_fuse_rrf() {
+        let dense = candidates(Order::LargeBetter, &[(1, 0.9), (2, 0.8), (3, 0.7)]);
+        let sparse = candidates(Order::LargeBetter, &[(3, 12.0), (4, 10.0), (1, 5.0)]);
+
+        let fused = fuse_results(dense, sparse, Fusion::Rrf { k: 1 }, 0, 10);
+        // 1: 1/2 + 1/4, 3: 1/4 + 1/2, 2: 1/3, 4: 1/3
+        assert_eq!(ids(&fused), vec![1.into(), 3.into(), 2.into(), 4.into()]);
+        assert!((fused[0].score - 0.75).abs() < 1e-6);
+        assert!((fused[2].score - 1.0 / 3.0).abs() < 1e-6);
+
+        let dense = candidates(Order::LargeBetter, &[(1, 0.9), (2, 0.8), (3, 0.7)]);
+        let sparse = candidates(Order::LargeBetter, &[(3, 12.0), (4, 10.0), (1, 5.0)]);
+        let fused = fuse_results(dense, sparse, Fusion::Rrf { k: 1 }, 1, 2);
+        assert_eq!(ids(&fused), vec![3.into(), 2.into()]);
+    }
+
+    #[test]
+    fn test_fuse_weighted_sum() {
+        // Distances of the dense vectors: smaller is better
+        let dense = candidates(Order::SmallBetter, &[(1, 1.0), (2, 2.0), (3, 3.0)]);
+        let sparse = candidates(Order::LargeBetter, &[(3, 20.0), (2, 15.0), (4, 10.0)]);
+
+        let fusion = Fusion::WeightedSum {
+            dense_weight: 0.3,
+            sparse_weight: 0.7,
+        };
+        let fused = fuse_results(dense, sparse, fusion, 0, 10);
+        // 1: 0.3, 2: 0.15 + 0.35, 3: 0 + 0.7, 4: 0
+        assert_eq!(ids(&fused), vec![3.into(), 2.into(), 1.into(), 4.into()]);
+        assert!((fused[1].score - 0.5).abs() < 1e-6);
+        assert_eq!(fused[3].score, 0.0);
+    }
+}
@@ -4,13 +4,20 @@ pub mod collection_state;
 pub mod common;
 pub mod config;
 pub mod discovery;
+pub mod distinct;
+pub mod events;
 pub mod grouping;
 pub mod hash_ring;
+pub mod hybrid_search;
 pub mod lookup;
+pub mod multi_search;
 pub mod operations;
 pub mod optimizers_builder;
 pub mod recommendations;
+pub mod rescore_search;
 pub mod save_on_disk;
+pub mod score_formula;
+pub mod search_matrix;
 pub mod shards;
 pub mod telemetry;
 mod update_handler;
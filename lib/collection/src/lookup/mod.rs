@@ -10,10 +10,13 @@ use serde::{Deserialize, Serialize};
 use tokio::sync::RwLockReadGuard;
 use types::PseudoId;
 
+use crate::collection::access_tags::AccessTags;
 use crate::collection::Collection;
 use crate::operations::consistency_params::ReadConsistency;
 use crate::operations::shard_selector_internal::ShardSelectorInternal;
 use crate::operations::types::{CollectionError, CollectionResult, PointRequestInternal, Record};
+use crate::shards::replica_set::read_priority::ReadPriority;
+use crate::shards::replica_set::stale_reads::StaleReads;
 
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct WithLookup {
@@ -41,6 +44,9 @@ pub async fn lookup_ids<'a, F, Fut>(
     collection_by_name: F,
     read_consistency: Option<ReadConsistency>,
     shard_selection: &ShardSelectorInternal,
+    access_tags: Option<&AccessTags>,
+    read_priority: ReadPriority,
+    stale_reads: Option<&StaleReads>,
 ) -> CollectionResult<HashMap<PseudoId, Record>>
 where
     F: FnOnce(String) -> Fut,
@@ -65,10 +71,19 @@ where
         ids,
         with_payload: request.with_payload,
         with_vector: request.with_vectors.unwrap_or_default(),
+        as_of: None,
+        with_version: false,
     };
 
     let result = collection
-        .retrieve(point_request, read_consistency, shard_selection)
+        .retrieve(
+            point_request,
+            read_consistency,
+            shard_selection,
+            access_tags,
+            read_priority,
+            stale_reads,
+        )
         .await?
         .into_iter()
         .map(|point| (PseudoId::from(point.id), point))
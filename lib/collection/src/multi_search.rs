@@ -0,0 +1,246 @@
+//! Search of the same queries in several collections, with the results merged together
+//!
+//! Each collection is searched on its own, then the results of all collections are merged into
+//! a single list per query. Scores of different collections are not always comparable, e.g. if
+//! the collections use different distances or models, so they could be normalized per collection
+//! before the merge.
+
+use std::collections::HashSet;
+
+use schemars::JsonSchema;
+use segment::types::{Order, ScoreType, ScoredPoint};
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+use crate::operations::types::{CollectionError, CollectionResult, SearchRequestInternal};
+
+/// Search in several collections at once
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone)]
+#[serde(rename_all = "snake_case")]
+pub struct MultiCollectionSearchRequest {
+    /// Collections to search in
+    #[validate(length(min = 1))]
+    pub collections: Vec<String>,
+    /// Searches to run in each of the collections. Results of each search are merged separately
+    #[validate]
+    pub searches: Vec<SearchRequestInternal>,
+    /// How scores of each collection are normalized before the merge. Default: `none`
+    #[serde(default)]
+    pub score_normalization: ScoreNormalization,
+}
+
+impl MultiCollectionSearchRequest {
+    /// Check that every collection is listed only once
+    pub fn check_collections(&self) -> CollectionResult<()> {
+        let mut seen = HashSet::new();
+        for collection in &self.collections {
+            if !seen.insert(collection) {
+                return Err(CollectionError::bad_request(format!(
+                    "Collection {collection} is listed more than once",
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum ScoreNormalization {
+    /// Scores are merged as is, all collections must order scores the same way
+    #[default]
+    None,
+    /// Scores of each collection are scaled to `[0, 1]`, where 1 is the best result of the
+    /// collection and 0 is the worst
+    MinMax,
+}
+
+/// Point found in one of the searched collections
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone)]
+pub struct MultiCollectionScoredPoint {
+    /// Collection the point is found in
+    pub collection: String,
+    /// Score of the point in its collection, before normalization
+    pub raw_score: ScoreType,
+    #[serde(flatten)]
+    pub point: ScoredPoint,
+}
+
+/// Results of a single search in one of the collections
+pub struct CollectionSearchResult {
+    pub collection: String,
+    /// Order of the scores in the collection
+    pub order: Order,
+    pub points: Vec<ScoredPoint>,
+}
+
+/// Merge results of a search in several collections into one list
+///
+/// Each collection is expected to return `offset + limit` best points. The merged list skips
+/// `offset` best points and keeps at most `limit`.
+pub fn merge_results(
+    results: Vec<CollectionSearchResult>,
+    normalization: ScoreNormalization,
+    offset: usize,
+    limit: usize,
+) -> CollectionResult<Vec<MultiCollectionScoredPoint>> {
+    let order = match normalization {
+        ScoreNormalization::None => {
+            let mut orders = results.iter().map(|result| result.order);
+            let order = orders.next().unwrap_or(Order::LargeBetter);
+            if orders.any(|other| other != order) {
+                return Err(CollectionError::bad_request(
+                    "Collections order scores differently, use score normalization to merge them"
+                        .to_string(),
+                ));
+            }
+            order
+        }
+        // Best normalized score is always the largest
+        ScoreNormalization::MinMax => Order::LargeBetter,
+    };
+
+    let mut merged: Vec<_> = results
+        .into_iter()
+        .flat_map(|result| {
+            let scores = result.points.iter().map(|point| point.score);
+            let normalize = match normalization {
+                ScoreNormalization::None => None,
+                ScoreNormalization::MinMax => {
+                    let min = scores.clone().fold(ScoreType::INFINITY, ScoreType::min);
+                    let max = scores.fold(ScoreType::NEG_INFINITY, ScoreType::max);
+                    Some((min, max, result.order))
+                }
+            };
+            let collection = result.collection;
+            result.points.into_iter().map(move |mut point| {
+                let raw_score = point.score;
+                if let Some((min, max, order)) = normalize {
+                    point.score = min_max_normalize(raw_score, min, max, order);
+                }
+                MultiCollectionScoredPoint {
+                    collection: collection.clone(),
+                    raw_score,
+                    point,
+                }
+            })
+        })
+        .collect();
+
+    // Stable sort, so points with equal scores keep the order of the collections
+    merged.sort_by(|a, b| {
+        let ordering = a.point.score.total_cmp(&b.point.score);
+        match order {
+            Order::LargeBetter => ordering.reverse(),
+            Order::SmallBetter => ordering,
+        }
+    });
+
+    Ok(merged.into_iter().skip(offset).take(limit).collect())
+}
+
+pub(crate) fn min_max_normalize(
+    score: ScoreType,
+    min: ScoreType,
+    max: ScoreType,
+    order: Order,
+) -> ScoreType {
+    let range = max - min;
+    if range.is_nan() || range <= 0.0 {
+        // All points of the collection are equally good
+        return 1.0;
+    }
+    match order {
+        Order::LargeBetter => (score - min) / range,
+        Order::SmallBetter => (max - score) / range,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scored(id: u64, score: ScoreType) -> ScoredPoint {
+        ScoredPoint {
+            id: id.into(),
+            version: 0,
+            score,
+            payload: None,
+            vector: None,
+            shard_key: None,
+        }
+    }
+
+    fn result(collection: &str, order: Order, scores: &[ScoreType]) -> CollectionSearchResult {
+        CollectionSearchResult {
+            collection: collection.to_string(),
+            order,
+            points: scores
+                .iter()
+                .enumerate()
+                .map(|(id, score)| scored(id as u64, *score))
+                .collect(),
+        }
+    }
+
+    fn labels(merged: &[MultiCollectionScoredPoint]) -> Vec<(&str, ScoreType)> {
+        merged
+            .iter()
+            .map(|point| (point.collection.as_str(), point.point.score))
+            .collect()
+    }
+
+    #[test]
+    fn test_merge_raw_scores() {
+        let merged = merge_results(
+            vec![
+                result("en", Order::LargeBetter, &[0.9, 0.5]),
+                result("de", Order::LargeBetter, &[0.7, 0.6]),
+            ],
+            ScoreNormalization::None,
+            1,
+            2,
+        )
+        .unwrap();
+        assert_eq!(labels(&merged), vec![("de", 0.7), ("de", 0.6)]);
+        assert_eq!(merged[0].raw_score, 0.7);
+
+        // Raw scores of different orders are not comparable
+        let err = merge_results(
+            vec![
+                result("en", Order::LargeBetter, &[0.9]),
+                result("de", Order::SmallBetter, &[0.1]),
+            ],
+            ScoreNormalization::None,
+            0,
+            10,
+        );
+        assert!(err.is_err());
+    }
+
+    #[test]
+    fn test_merge_min_max_normalized() {
+        let merged = merge_results(
+            vec![
+                result("en", Order::LargeBetter, &[0.9, 0.8, 0.7]),
+                result("de", Order::SmallBetter, &[10.0, 30.0]),
+                result("fr", Order::LargeBetter, &[0.3]),
+            ],
+            ScoreNormalization::MinMax,
+            0,
+            10,
+        )
+        .unwrap();
+
+        let labels = labels(&merged);
+        assert_eq!(labels.len(), 6);
+        // Best point of each collection scores 1, worst 0, stable for equal scores
+        assert_eq!(&labels[..3], &[("en", 1.0), ("de", 1.0), ("fr", 1.0)]);
+        assert_eq!(labels[3].0, "en");
+        assert!((labels[3].1 - 0.5).abs() < 1e-5);
+        assert_eq!(&labels[4..], &[("en", 0.0), ("de", 0.0)]);
+
+        // Raw scores are kept
+        assert_eq!(merged[1].raw_score, 10.0);
+    }
+}
@@ -24,6 +24,8 @@ pub enum ClusterOperations {
     CreateShardingKey(CreateShardingKeyOperation),
     /// Drop a custom shard partition for a given key
     DropShardingKey(DropShardingKeyOperation),
+    /// Split shard into two new shards
+    SplitShard(SplitShardOperation),
 }
 
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone)]
@@ -60,6 +62,19 @@ pub struct DropShardingKey {
     pub shard_key: ShardKey,
 }
 
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone)]
+#[serde(rename_all = "snake_case")]
+pub struct SplitShardOperation {
+    pub split_shard: SplitShard,
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone)]
+#[serde(rename_all = "snake_case")]
+pub struct SplitShard {
+    /// Shard to split, points of the shard are distributed between two new shards
+    pub shard_id: ShardId,
+}
+
 impl Validate for ClusterOperations {
     fn validate(&self) -> Result<(), validator::ValidationErrors> {
         match self {
@@ -69,6 +84,7 @@ impl Validate for ClusterOperations {
             ClusterOperations::DropReplica(op) => op.validate(),
             ClusterOperations::CreateShardingKey(op) => op.validate(),
             ClusterOperations::DropShardingKey(op) => op.validate(),
+            ClusterOperations::SplitShard(op) => op.validate(),
         }
     }
 }
@@ -2,13 +2,20 @@ use std::num::NonZeroU32;
 
 use merge::Merge;
 use schemars::JsonSchema;
-use segment::types::{BinaryQuantization, HnswConfig, ProductQuantization, ScalarQuantization};
+use segment::types::{
+    BinaryQuantization, HnswConfig, PayloadKeyType, ProductQuantization, QuantizationConfig,
+    ScalarQuantization,
+};
 use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use validator::{Validate, ValidationErrors};
 
-use crate::config::{CollectionParams, WalConfig};
+use crate::config::{
+    CollectionParams, InferenceConfig, PayloadStorageBackend, StoragePolicy, WalCompression,
+    WalConfig,
+};
+use crate::operations::point_ops::WriteOrdering;
 use crate::operations::types::CollectionResult;
 use crate::optimizers_builder::OptimizersConfig;
 
@@ -91,6 +98,8 @@ pub struct WalConfigDiff {
     pub wal_capacity_mb: Option<usize>,
     /// Number of WAL segments to create ahead of actually used ones
     pub wal_segments_ahead: Option<usize>,
+    /// Compression of the new WAL entries
+    pub wal_compression: Option<WalCompression>,
 }
 
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, Merge, PartialEq, Eq, Hash)]
@@ -101,12 +110,51 @@ pub struct CollectionParamsDiff {
     pub write_consistency_factor: Option<NonZeroU32>,
     /// Fan-out every read request to these many additional remote nodes (and return first available response)
     pub read_fan_out_factor: Option<u32>,
+    /// If true - reads fall back to `Partial` and `Listener` replicas, when there are not enough `Active` replicas
+    #[serde(default)]
+    pub allow_degraded_reads: Option<bool>,
+    /// If true - low priority reads are served by `Listener` replicas first
+    #[serde(default)]
+    pub serve_reads_from_listeners: Option<bool>,
+    /// If true - updates return once enough replicas applied them, slower replicas complete in background
+    #[serde(default)]
+    pub hedge_updates: Option<bool>,
+    /// Number of seconds to retain previous states of the changed points for time-travel reads
+    #[serde(default)]
+    pub time_travel_window_sec: Option<u64>,
+    /// Max number of seconds an update waits for the consensus to deactivate failed replicas
+    #[serde(default)]
+    pub shard_deactivation_timeout_sec: Option<u64>,
     /// If true - point's payload will not be stored in memory.
     /// It will be read from the disk every time it is requested.
     /// This setting saves RAM by (slightly) increasing the response time.
     /// Note: those payload values that are involved in filtering and are indexed - remain in RAM.
     #[serde(default)]
     pub on_disk_payload: Option<bool>,
+    /// Storage engine of the on-disk payload.
+    /// Existing segments are migrated to the new engine by the optimizer.
+    #[serde(default)]
+    pub payload_storage_backend: Option<PayloadStorageBackend>,
+    /// Name of the payload field, which holds the expiration time of the point as unix timestamp in seconds.
+    #[serde(default)]
+    pub expiration_field: Option<PayloadKeyType>,
+    /// Number of seconds to retain deleted points, which could be restored during this time.
+    #[serde(default)]
+    pub soft_delete_window_sec: Option<u64>,
+    /// Placement of the collection data on disk or in RAM. Replaces the current storage policy.
+    /// Existing segments are migrated to the new placement by the optimizer.
+    #[serde(default)]
+    pub storage_policy: Option<StoragePolicy>,
+    /// Write ordering of the updates, which don't specify it
+    #[serde(default)]
+    pub default_write_ordering: Option<WriteOrdering>,
+    /// Whether updates, which don't specify it, wait for the changes to be applied
+    #[serde(default)]
+    pub default_wait: Option<bool>,
+    /// External endpoint, which converts documents into vectors of the collection.
+    /// Replaces the current inference config.
+    #[serde(default)]
+    pub inference: Option<InferenceConfig>,
 }
 
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone, Merge)]
@@ -154,8 +202,23 @@ pub struct OptimizersConfigDiff {
     pub indexing_threshold: Option<usize>,
     /// Minimum interval between forced flushes.
     pub flush_interval_sec: Option<u64>,
+    /// Size (in kilobytes) of updates accumulated since the last flush, which triggers a flush
+    /// before `flush_interval_sec` has passed.
+    #[serde(alias = "flush_dirty_threshold_kb")]
+    pub flush_dirty_threshold: Option<usize>,
+    /// Number of update operations accumulated since the last flush, which triggers a flush
+    /// before `flush_interval_sec` has passed.
+    pub flush_dirty_operations: Option<usize>,
     /// Maximum available threads for optimization workers
     pub max_optimization_threads: Option<usize>,
+    /// Maximum number of segments per shard, which are rebuilt at the same time to apply changed
+    /// collection configuration, e.g. HNSW, quantization or on-disk storage parameters.
+    /// Allows to roll out configuration changes gradually, without rebuilding all segments at once.
+    ///
+    /// Set to `0` to abort the rollout: running rebuilds are cancelled, and no new ones are started
+    /// until the limit is raised.
+    /// If not set, all segments with outdated configuration are rebuilt at once.
+    pub max_config_rebuild_segments: Option<usize>,
 }
 
 impl std::hash::Hash for OptimizersConfigDiff {
@@ -167,7 +230,10 @@ impl std::hash::Hash for OptimizersConfigDiff {
         self.memmap_threshold.hash(state);
         self.indexing_threshold.hash(state);
         self.flush_interval_sec.hash(state);
+        self.flush_dirty_threshold.hash(state);
+        self.flush_dirty_operations.hash(state);
         self.max_optimization_threads.hash(state);
+        self.max_config_rebuild_segments.hash(state);
     }
 }
 
@@ -181,7 +247,10 @@ impl PartialEq for OptimizersConfigDiff {
             && self.memmap_threshold == other.memmap_threshold
             && self.indexing_threshold == other.indexing_threshold
             && self.flush_interval_sec == other.flush_interval_sec
+            && self.flush_dirty_threshold == other.flush_dirty_threshold
+            && self.flush_dirty_operations == other.flush_dirty_operations
             && self.max_optimization_threads == other.max_optimization_threads
+            && self.max_config_rebuild_segments == other.max_config_rebuild_segments
     }
 }
 
@@ -307,6 +376,16 @@ impl QuantizationConfigDiff {
     pub fn new_disabled() -> Self {
         QuantizationConfigDiff::Disabled(Disabled::Disabled)
     }
+
+    /// Quantization config, set by this diff. None if quantization is disabled.
+    pub fn into_config(self) -> Option<QuantizationConfig> {
+        match self {
+            QuantizationConfigDiff::Scalar(scalar) => Some(QuantizationConfig::Scalar(scalar)),
+            QuantizationConfigDiff::Product(product) => Some(QuantizationConfig::Product(product)),
+            QuantizationConfigDiff::Binary(binary) => Some(QuantizationConfig::Binary(binary)),
+            QuantizationConfigDiff::Disabled(_) => None,
+        }
+    }
 }
 
 impl Validate for QuantizationConfigDiff {
@@ -339,6 +418,10 @@ mod tests {
                 hnsw_config: None,
                 quantization_config: None,
                 on_disk: None,
+                normalize: None,
+                dimensionality_reduction: None,
+                multivector_config: None,
+                datatype: None,
             }
             .into(),
             ..CollectionParams::empty()
@@ -348,7 +431,19 @@ mod tests {
             replication_factor: None,
             write_consistency_factor: Some(NonZeroU32::new(2).unwrap()),
             read_fan_out_factor: None,
+            allow_degraded_reads: None,
+            serve_reads_from_listeners: None,
+            hedge_updates: None,
+            time_travel_window_sec: None,
+            shard_deactivation_timeout_sec: None,
             on_disk_payload: None,
+            payload_storage_backend: None,
+            expiration_field: None,
+            soft_delete_window_sec: None,
+            storage_policy: None,
+            default_write_ordering: None,
+            default_wait: None,
+            inference: None,
         };
 
         let new_params = diff.update(&params).unwrap();
@@ -376,7 +471,10 @@ mod tests {
             memmap_threshold: None,
             indexing_threshold: Some(50_000),
             flush_interval_sec: 30,
+            flush_dirty_threshold: None,
+            flush_dirty_operations: None,
             max_optimization_threads: 1,
+            max_config_rebuild_segments: None,
         };
         let update: OptimizersConfigDiff =
             serde_json::from_str(r#"{ "indexing_threshold": 10000 }"#).unwrap();
@@ -1,8 +1,9 @@
 use std::borrow::Cow;
 
 use api::grpc::qdrant::{
-    read_consistency, ReadConsistency as ReadConsistencyGrpc,
-    ReadConsistencyType as ReadConsistencyTypeGrpc,
+    read_consistency, write_consistency, ReadConsistency as ReadConsistencyGrpc,
+    ReadConsistencyType as ReadConsistencyTypeGrpc, WriteConsistency as WriteConsistencyGrpc,
+    WriteConsistencyType as WriteConsistencyTypeGrpc,
 };
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
@@ -14,9 +15,9 @@ use validator::{Validate, ValidationError as ValidatorError, ValidationErrors};
 ///
 /// * `N` - send N random request and return points, which present on all of them
 ///
-/// * `majority` - send N/2+1 random request and return points, which present on all of them
+/// * `majority` - send requests to all nodes and return points which present on majority of them
 ///
-/// * `quorum` - send requests to all nodes and return points which present on majority of them
+/// * `quorum` - send N/2+1 random request and return points, which present on all of them
 ///
 /// * `all` - send requests to all nodes and return points which present on all of them
 ///
@@ -127,30 +128,28 @@ where
 
     let factor = factor.map_err(|err| {
         serde::de::Error::custom(format!(
-            "failed to deserialize read consistency factor value: {err}"
+            "failed to deserialize consistency factor value: {err}"
         ))
     })?;
 
     if factor > 0 {
         Ok(factor)
     } else {
-        Err(serde::de::Error::custom(
-            "read consistency factor can't be zero",
-        ))
+        Err(serde::de::Error::custom("consistency factor can't be zero"))
     }
 }
 
-/// * `majority` - send N/2+1 random request and return points, which present on all of them
+/// * `majority` - send requests to all nodes and return points which present on majority of nodes
 ///
-/// * `quorum` - send requests to all nodes and return points which present on majority of nodes
+/// * `quorum` - send N/2+1 random request and return points, which present on all of them
 ///
 /// * `all` - send requests to all nodes and return points which present on all nodes
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Copy, Clone, PartialEq, Eq)]
 #[serde(rename_all = "snake_case")]
 pub enum ReadConsistencyType {
-    // send N/2+1 random request and return points, which present on all of them
-    Majority,
     // send requests to all nodes and return points which present on majority of nodes
+    Majority,
+    // send N/2+1 random request and return points, which present on all of them
     Quorum,
     // send requests to all nodes and return points which present on all nodes
     All,
@@ -196,6 +195,151 @@ impl From<ReadConsistencyType> for ReadConsistencyTypeGrpc {
     }
 }
 
+/// Write consistency parameter
+///
+/// Defines how many replicas must apply the update for it to succeed, overrides
+/// `write_consistency_factor` of the collection
+///
+/// * `N` - update must be applied by N replicas
+///
+/// * `majority` - update must be applied by the majority of replicas
+///
+/// * `all` - update must be applied by all replicas
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Deserialize, Serialize, JsonSchema)]
+#[serde(untagged)]
+pub enum WriteConsistency {
+    // update must be applied by N replicas
+    Factor(#[serde(deserialize_with = "deserialize_factor")] usize),
+    Type(WriteConsistencyType),
+}
+
+impl Validate for WriteConsistency {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        match self {
+            WriteConsistency::Factor(factor) if *factor == 0 => {
+                let mut errors = ValidationErrors::new();
+                errors.add("factor", {
+                    let mut error = ValidatorError::new("range");
+                    error.add_param(Cow::from("value"), factor);
+                    error.add_param(Cow::from("min"), &1);
+                    error
+                });
+                Err(errors)
+            }
+            WriteConsistency::Factor(_) | WriteConsistency::Type(_) => Ok(()),
+        }
+    }
+}
+
+impl WriteConsistency {
+    /// Number of replicas, which must apply the update, out of the given number of updated ones
+    pub fn minimal_success_count(&self, replicas: usize) -> usize {
+        match self {
+            WriteConsistency::Factor(factor) => *factor,
+            WriteConsistency::Type(WriteConsistencyType::Majority) => replicas / 2 + 1,
+            WriteConsistency::Type(WriteConsistencyType::All) => replicas,
+        }
+    }
+
+    pub fn try_from_optional(
+        consistency: Option<WriteConsistencyGrpc>,
+    ) -> Result<Option<Self>, tonic::Status> {
+        consistency.map(TryFrom::try_from).transpose()
+    }
+}
+
+impl TryFrom<WriteConsistencyGrpc> for WriteConsistency {
+    type Error = tonic::Status;
+
+    fn try_from(consistency: WriteConsistencyGrpc) -> Result<Self, Self::Error> {
+        let value = consistency.value.ok_or_else(|| {
+            tonic::Status::invalid_argument(
+                "invalid write consistency message: `WriteConsistency::value` field is `None`",
+            )
+        })?;
+
+        let consistency = match value {
+            write_consistency::Value::Factor(0) => {
+                return Err(tonic::Status::invalid_argument(
+                    "write consistency factor can't be zero",
+                ))
+            }
+            write_consistency::Value::Factor(factor) => Self::Factor(
+                usize::try_from(factor)
+                    .map_err(|err| tonic::Status::invalid_argument(err.to_string()))?,
+            ),
+            write_consistency::Value::Type(consistency) => Self::Type(consistency.try_into()?),
+        };
+
+        Ok(consistency)
+    }
+}
+
+impl From<WriteConsistency> for WriteConsistencyGrpc {
+    fn from(consistency: WriteConsistency) -> Self {
+        let value = match consistency {
+            WriteConsistency::Factor(factor) => {
+                write_consistency::Value::Factor(factor.try_into().unwrap())
+            }
+            WriteConsistency::Type(consistency) => {
+                write_consistency::Value::Type(consistency.into())
+            }
+        };
+
+        WriteConsistencyGrpc { value: Some(value) }
+    }
+}
+
+/// * `majority` - update must be applied by the majority of replicas
+///
+/// * `all` - update must be applied by all replicas
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Copy, Clone, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum WriteConsistencyType {
+    // update must be applied by the majority of replicas
+    Majority,
+    // update must be applied by all replicas
+    All,
+}
+
+impl TryFrom<i32> for WriteConsistencyType {
+    type Error = tonic::Status;
+
+    fn try_from(consistency: i32) -> Result<Self, Self::Error> {
+        let consistency = WriteConsistencyTypeGrpc::from_i32(consistency).ok_or_else(|| {
+            tonic::Status::invalid_argument(format!(
+                "invalid write consistency type value {consistency}",
+            ))
+        })?;
+
+        Ok(consistency.into())
+    }
+}
+
+impl From<WriteConsistencyTypeGrpc> for WriteConsistencyType {
+    fn from(consistency: WriteConsistencyTypeGrpc) -> Self {
+        match consistency {
+            WriteConsistencyTypeGrpc::MajorityReplicas => Self::Majority,
+            WriteConsistencyTypeGrpc::AllReplicas => Self::All,
+        }
+    }
+}
+
+impl From<WriteConsistencyType> for i32 {
+    fn from(consistency: WriteConsistencyType) -> Self {
+        WriteConsistencyTypeGrpc::from(consistency) as _
+    }
+}
+
+impl From<WriteConsistencyType> for WriteConsistencyTypeGrpc {
+    fn from(consistency: WriteConsistencyType) -> Self {
+        match consistency {
+            WriteConsistencyType::Majority => WriteConsistencyTypeGrpc::MajorityReplicas,
+            WriteConsistencyType::All => WriteConsistencyTypeGrpc::AllReplicas,
+        }
+    }
+}
+
 #[derive(Copy, Clone, Debug, thiserror::Error)]
 #[error("Read consistency factor cannot be less than 1")]
 pub struct ValidationError;
@@ -243,4 +387,22 @@ mod tests {
         let schema_str = serde_json::to_string_pretty(&schema).unwrap();
         println!("{schema_str}")
     }
+
+    #[test]
+    fn test_write_consistency() {
+        let consistency: WriteConsistency = serde_json::from_str("\"majority\"").unwrap();
+        assert_eq!(consistency.minimal_success_count(3), 2);
+        assert_eq!(consistency.minimal_success_count(4), 3);
+
+        let consistency: WriteConsistency = serde_json::from_str("\"all\"").unwrap();
+        assert_eq!(consistency.minimal_success_count(3), 3);
+
+        // Query parameters are deserialized from strings
+        let consistency: WriteConsistency = serde_json::from_str("\"2\"").unwrap();
+        assert_eq!(consistency, WriteConsistency::Factor(2));
+        assert_eq!(consistency.minimal_success_count(3), 2);
+
+        let consistency: Result<WriteConsistency, _> = serde_json::from_str("0");
+        assert!(consistency.is_err());
+    }
 }
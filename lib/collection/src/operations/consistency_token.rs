@@ -0,0 +1,153 @@
+//! Read-your-writes consistency token
+//!
+//! Operation numbers are assigned by the WAL of each replica independently, so the token
+//! remembers the number of the operation on every replica which has applied the update.
+//! Reads, which provide the token, are only routed to those replicas, and only after they have
+//! applied at least the remembered operation.
+//!
+//! Token is encoded as a comma separated list of `shard_id:peer_id:operation_id` entries.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::str::FromStr;
+
+use segment::types::SeqNumberType;
+
+use crate::operations::types::CollectionError;
+use crate::shards::shard::{PeerId, ShardId};
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ConsistencyToken {
+    /// Operation number on each replica, which has applied the update, per shard
+    shards: BTreeMap<ShardId, BTreeMap<PeerId, SeqNumberType>>,
+}
+
+impl ConsistencyToken {
+    pub fn new(
+        shard_id: ShardId,
+        replicas: impl IntoIterator<Item = (PeerId, SeqNumberType)>,
+    ) -> Self {
+        let replicas: BTreeMap<_, _> = replicas.into_iter().collect();
+        let mut shards = BTreeMap::new();
+        if !replicas.is_empty() {
+            shards.insert(shard_id, replicas);
+        }
+        Self { shards }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.shards.is_empty()
+    }
+
+    /// Replicas of the shard, which are allowed to serve the read, with the operation number
+    /// they have to apply first
+    ///
+    /// `None` if the token has no requirements for the shard.
+    pub fn shard_replicas(&self, shard_id: ShardId) -> Option<&BTreeMap<PeerId, SeqNumberType>> {
+        self.shards.get(&shard_id)
+    }
+
+    /// Combine tokens of several updates, the result is satisfied only if all of them are
+    ///
+    /// Only replicas, which are present in both tokens, remain allowed for the shard.
+    pub fn merge(&mut self, other: &Self) {
+        for (shard_id, other_replicas) in &other.shards {
+            match self.shards.get_mut(shard_id) {
+                Some(replicas) => {
+                    replicas.retain(|peer_id, _| other_replicas.contains_key(peer_id));
+                    for (peer_id, operation_id) in replicas.iter_mut() {
+                        *operation_id = (*operation_id).max(other_replicas[peer_id]);
+                    }
+                }
+                None => {
+                    self.shards.insert(*shard_id, other_replicas.clone());
+                }
+            }
+        }
+    }
+
+    /// Parse and merge optional tokens of the requests, which are executed together
+    pub fn merge_all<'a>(
+        tokens: impl IntoIterator<Item = Option<&'a str>>,
+    ) -> Result<Option<Self>, CollectionError> {
+        let mut merged: Option<Self> = None;
+        for token in tokens.into_iter().flatten() {
+            let token: Self = token.parse()?;
+            match &mut merged {
+                Some(merged) => merged.merge(&token),
+                None => merged = Some(token),
+            }
+        }
+        Ok(merged)
+    }
+}
+
+impl fmt::Display for ConsistencyToken {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut separator = "";
+        for (shard_id, replicas) in &self.shards {
+            for (peer_id, operation_id) in replicas {
+                write!(f, "{separator}{shard_id}:{peer_id}:{operation_id}")?;
+                separator = ",";
+            }
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for ConsistencyToken {
+    type Err = CollectionError;
+
+    fn from_str(token: &str) -> Result<Self, Self::Err> {
+        let invalid = || CollectionError::bad_input(format!("Invalid consistency token {token}"));
+
+        let mut shards: BTreeMap<ShardId, BTreeMap<PeerId, SeqNumberType>> = BTreeMap::new();
+        for entry in token.split(',').filter(|entry| !entry.is_empty()) {
+            let mut parts = entry.split(':');
+            let (Some(shard_id), Some(peer_id), Some(operation_id), None) =
+                (parts.next(), parts.next(), parts.next(), parts.next())
+            else {
+                return Err(invalid());
+            };
+            shards
+                .entry(shard_id.parse().map_err(|_| invalid())?)
+                .or_default()
+                .insert(
+                    peer_id.parse().map_err(|_| invalid())?,
+                    operation_id.parse().map_err(|_| invalid())?,
+                );
+        }
+        Ok(Self { shards })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_consistency_token_encoding() {
+        let mut token = ConsistencyToken::new(0, [(10, 5), (20, 7)]);
+        token.merge(&ConsistencyToken::new(3, [(20, 1)]));
+
+        let encoded = token.to_string();
+        assert_eq!(encoded, "0:10:5,0:20:7,3:20:1");
+        assert_eq!(encoded.parse::<ConsistencyToken>().unwrap(), token);
+
+        assert!("".parse::<ConsistencyToken>().unwrap().is_empty());
+        assert!("0:10".parse::<ConsistencyToken>().is_err());
+        assert!("0:10:5:1".parse::<ConsistencyToken>().is_err());
+        assert!("a:10:5".parse::<ConsistencyToken>().is_err());
+    }
+
+    #[test]
+    fn test_consistency_token_merge() {
+        let mut token = ConsistencyToken::new(0, [(10, 5), (20, 7)]);
+        token.merge(&ConsistencyToken::new(0, [(20, 9), (30, 1)]));
+        token.merge(&ConsistencyToken::new(1, [(10, 2)]));
+
+        assert_eq!(token.shard_replicas(0), Some(&BTreeMap::from([(20, 9)])),);
+        assert_eq!(token.shard_replicas(1), Some(&BTreeMap::from([(10, 2)])),);
+        assert_eq!(token.shard_replicas(2), None);
+    }
+}
@@ -4,7 +4,7 @@ use std::time::Duration;
 
 use api::grpc::conversions::{
     convert_shard_key_from_grpc, convert_shard_key_from_grpc_opt, convert_shard_key_to_grpc,
-    from_grpc_dist, payload_to_proto, proto_to_payloads,
+    from_grpc_datatype, from_grpc_dist, payload_to_proto, proto_to_payloads,
 };
 use api::grpc::qdrant::quantization_config_diff::Quantization;
 use api::grpc::qdrant::update_collection_cluster_setup_request::{
@@ -14,7 +14,7 @@ use api::grpc::qdrant::{CreateShardKey, SearchPoints};
 use common::types::ScoreType;
 use itertools::Itertools;
 use segment::data_types::vectors::{Named, NamedQuery, Vector, VectorStruct, DEFAULT_VECTOR_NAME};
-use segment::types::{Distance, QuantizationConfig};
+use segment::types::{Distance, QuantizationConfig, VectorStorageDatatype};
 use segment::vector_storage::query::context_query::{ContextPair, ContextQuery};
 use segment::vector_storage::query::discovery_query::DiscoveryQuery;
 use segment::vector_storage::query::reco_query::RecoQuery;
@@ -22,26 +22,32 @@ use tonic::Status;
 
 use super::consistency_params::ReadConsistency;
 use super::types::{
-    BaseGroupRequest, ContextExamplePair, CoreSearchRequest, DiscoverRequestInternal, GroupsResult,
-    PointGroup, QueryEnum, RecommendExample, RecommendGroupsRequestInternal, RecommendStrategy,
-    SearchGroupsRequestInternal, SparseIndexParams, SparseVectorParams, VectorParamsDiff,
-    VectorsConfigDiff,
+    BaseGroupRequest, ContextExamplePair, CorePrefetch, CoreSearchRequest, DiscoverRequestInternal,
+    GroupsResult, NewVectorsConfig, PointGroup, QueryEnum, RecommendExample,
+    RecommendGroupsRequestInternal, RecommendStrategy, Sample, SearchGroupsRequestInternal,
+    SparseIndexParams, SparseVectorParams, VectorParamsDiff, VectorsConfigDiff,
 };
 use crate::config::{
     default_replication_factor, default_write_consistency_factor, CollectionConfig,
-    CollectionParams, ShardingMethod, WalConfig,
+    CollectionParams, InferenceConfig, PayloadStorageBackend, ShardingMethod, StoragePlacement,
+    StoragePolicy, StrictModeConfig, VectorStoragePolicy, WalCompression, WalConfig,
 };
+use crate::distinct::CoreDistinct;
 use crate::lookup::types::WithLookupInterface;
 use crate::lookup::WithLookup;
 use crate::operations::cluster_ops::{
     AbortTransferOperation, ClusterOperations, CreateShardingKey, CreateShardingKeyOperation,
     DropReplicaOperation, DropShardingKey, DropShardingKeyOperation, MoveShard, MoveShardOperation,
-    Replica, ReplicateShardOperation,
+    Replica, ReplicateShardOperation, SplitShard, SplitShardOperation,
 };
 use crate::operations::config_diff::{
     CollectionParamsDiff, HnswConfigDiff, OptimizersConfigDiff, QuantizationConfigDiff,
     WalConfigDiff,
 };
+use crate::operations::dimensionality_reduction::{
+    validate_dimensionality_reduction, DimensionalityReduction, LinearTransform,
+};
+use crate::operations::feature_flags::FeatureFlags;
 use crate::operations::point_ops::PointsSelector::PointIdsSelector;
 use crate::operations::point_ops::{
     Batch, FilterSelector, PointIdsList, PointStruct, PointsSelector, WriteOrdering,
@@ -51,10 +57,12 @@ use crate::operations::shard_selector_internal::ShardSelectorInternal;
 use crate::operations::types::{
     AliasDescription, CollectionClusterInfo, CollectionInfo, CollectionStatus, CountResult,
     LocalShardInfo, LookupLocation, OptimizersStatus, RecommendRequestInternal, Record,
-    RemoteShardInfo, SearchRequestInternal, ShardTransferInfo, UpdateResult, UpdateStatus,
-    VectorParams, VectorsConfig,
+    RemoteShardInfo, ReplicaAcknowledgment, SearchRequestInternal, ShardFailure, ShardTransferInfo,
+    UpdateResult, UpdateStatus, VectorParams, VectorsConfig,
 };
+use crate::operations::update_priority::UpdatePriority;
 use crate::optimizers_builder::OptimizersConfig;
+use crate::score_formula::{CoreRescore, ScoreFormula};
 use crate::shards::remote_shard::{CollectionCoreSearchRequest, CollectionSearchRequest};
 use crate::shards::replica_set::ReplicaState;
 use crate::shards::transfer::ShardTransferMethod;
@@ -63,6 +71,7 @@ pub fn sharding_method_to_proto(sharding_method: ShardingMethod) -> i32 {
     match sharding_method {
         ShardingMethod::Auto => api::grpc::qdrant::ShardingMethod::Auto as i32,
         ShardingMethod::Custom => api::grpc::qdrant::ShardingMethod::Custom as i32,
+        ShardingMethod::PayloadKey => api::grpc::qdrant::ShardingMethod::PayloadKey as i32,
     }
 }
 
@@ -70,6 +79,9 @@ pub fn sharding_method_from_proto(sharding_method: i32) -> Result<ShardingMethod
     match sharding_method {
         x if x == api::grpc::qdrant::ShardingMethod::Auto as i32 => Ok(ShardingMethod::Auto),
         x if x == api::grpc::qdrant::ShardingMethod::Custom as i32 => Ok(ShardingMethod::Custom),
+        x if x == api::grpc::qdrant::ShardingMethod::PayloadKey as i32 => {
+            Ok(ShardingMethod::PayloadKey)
+        }
         _ => Err(Status::invalid_argument(format!(
             "Cannot convert sharding method: {}",
             sharding_method
@@ -77,39 +89,84 @@ pub fn sharding_method_from_proto(sharding_method: i32) -> Result<ShardingMethod
     }
 }
 
+pub fn payload_storage_backend_to_proto(backend: PayloadStorageBackend) -> i32 {
+    match backend {
+        PayloadStorageBackend::Rocksdb => api::grpc::qdrant::PayloadStorageBackend::Rocksdb as i32,
+        PayloadStorageBackend::AppendLog => {
+            api::grpc::qdrant::PayloadStorageBackend::AppendLog as i32
+        }
+    }
+}
+
+pub fn payload_storage_backend_from_proto(backend: i32) -> Result<PayloadStorageBackend, Status> {
+    match backend {
+        x if x == api::grpc::qdrant::PayloadStorageBackend::Rocksdb as i32 => {
+            Ok(PayloadStorageBackend::Rocksdb)
+        }
+        x if x == api::grpc::qdrant::PayloadStorageBackend::AppendLog as i32 => {
+            Ok(PayloadStorageBackend::AppendLog)
+        }
+        _ => Err(Status::invalid_argument(format!(
+            "Cannot convert payload storage backend: {backend}",
+        ))),
+    }
+}
+
+pub fn write_ordering_type_to_proto(ordering: WriteOrdering) -> i32 {
+    match ordering {
+        WriteOrdering::Weak => api::grpc::qdrant::WriteOrderingType::Weak as i32,
+        WriteOrdering::Medium => api::grpc::qdrant::WriteOrderingType::Medium as i32,
+        WriteOrdering::Strong => api::grpc::qdrant::WriteOrderingType::Strong as i32,
+    }
+}
+
+pub fn write_ordering_type_from_proto(ordering: i32) -> Result<WriteOrdering, Status> {
+    match api::grpc::qdrant::WriteOrderingType::from_i32(ordering) {
+        None => Err(Status::invalid_argument(format!(
+            "cannot convert ordering: {ordering}"
+        ))),
+        Some(api::grpc::qdrant::WriteOrderingType::Weak) => Ok(WriteOrdering::Weak),
+        Some(api::grpc::qdrant::WriteOrderingType::Medium) => Ok(WriteOrdering::Medium),
+        Some(api::grpc::qdrant::WriteOrderingType::Strong) => Ok(WriteOrdering::Strong),
+    }
+}
+
 pub fn write_ordering_to_proto(ordering: WriteOrdering) -> api::grpc::qdrant::WriteOrdering {
     api::grpc::qdrant::WriteOrdering {
-        r#type: match ordering {
-            WriteOrdering::Weak => api::grpc::qdrant::WriteOrderingType::Weak as i32,
-            WriteOrdering::Medium => api::grpc::qdrant::WriteOrderingType::Medium as i32,
-            WriteOrdering::Strong => api::grpc::qdrant::WriteOrderingType::Strong as i32,
-        },
+        r#type: write_ordering_type_to_proto(ordering),
     }
 }
 
+/// Ordering of the request, `None` if it is not specified and the collection default applies
 pub fn write_ordering_from_proto(
     ordering: Option<api::grpc::qdrant::WriteOrdering>,
-) -> Result<WriteOrdering, Status> {
-    let ordering_parsed = match ordering {
-        None => api::grpc::qdrant::WriteOrderingType::Weak,
-        Some(write_ordering) => {
-            match api::grpc::qdrant::WriteOrderingType::from_i32(write_ordering.r#type) {
-                None => {
-                    return Err(Status::invalid_argument(format!(
-                        "cannot convert ordering: {}",
-                        write_ordering.r#type
-                    )))
-                }
-                Some(res) => res,
-            }
-        }
-    };
+) -> Result<Option<WriteOrdering>, Status> {
+    ordering
+        .map(|ordering| write_ordering_type_from_proto(ordering.r#type))
+        .transpose()
+}
 
-    Ok(match ordering_parsed {
-        api::grpc::qdrant::WriteOrderingType::Weak => WriteOrdering::Weak,
-        api::grpc::qdrant::WriteOrderingType::Medium => WriteOrdering::Medium,
-        api::grpc::qdrant::WriteOrderingType::Strong => WriteOrdering::Strong,
-    })
+pub fn update_priority_to_proto(priority: UpdatePriority) -> i32 {
+    match priority {
+        UpdatePriority::Interactive => api::grpc::qdrant::UpdatePriority::Interactive as i32,
+        UpdatePriority::Bulk => api::grpc::qdrant::UpdatePriority::Bulk as i32,
+    }
+}
+
+/// Priority of the update, `None` if it is not specified and the update is interactive
+pub fn update_priority_from_proto(priority: Option<i32>) -> Result<Option<UpdatePriority>, Status> {
+    let Some(priority) = priority else {
+        return Ok(None);
+    };
+    match api::grpc::qdrant::UpdatePriority::from_i32(priority) {
+        None => Err(Status::invalid_argument(format!(
+            "cannot convert update priority: {priority}"
+        ))),
+        Some(api::grpc::qdrant::UpdatePriority::Interactive) => {
+            Ok(Some(UpdatePriority::Interactive))
+        }
+        Some(api::grpc::qdrant::UpdatePriority::Bulk) => Ok(Some(UpdatePriority::Bulk)),
+    }
 }
 
 pub fn try_record_from_grpc(
@@ -135,9 +192,11 @@ pub fn try_record_from_grpc(
 
     Ok(Record {
         id,
+        version: point.version,
         payload,
         vector,
         shard_key: convert_shard_key_from_grpc_opt(point.shard_key),
+        order_value: point.order_value,
     })
 }
 
@@ -247,11 +306,219 @@ impl From<HnswConfigDiff> for api::grpc::qdrant::HnswConfigDiff {
     }
 }
 
-impl From<api::grpc::qdrant::WalConfigDiff> for WalConfigDiff {
-    fn from(value: api::grpc::qdrant::WalConfigDiff) -> Self {
-        Self {
+impl TryFrom<api::grpc::qdrant::WalConfigDiff> for WalConfigDiff {
+    type Error = Status;
+
+    fn try_from(value: api::grpc::qdrant::WalConfigDiff) -> Result<Self, Self::Error> {
+        Ok(Self {
             wal_capacity_mb: value.wal_capacity_mb.map(|v| v as usize),
             wal_segments_ahead: value.wal_segments_ahead.map(|v| v as usize),
+            wal_compression: value
+                .wal_compression
+                .map(wal_compression_from_proto)
+                .transpose()?,
+        })
+    }
+}
+
+pub fn wal_compression_to_proto(compression: WalCompression) -> i32 {
+    match compression {
+        WalCompression::None => api::grpc::qdrant::WalCompression::None as i32,
+        WalCompression::Lz4 => api::grpc::qdrant::WalCompression::Lz4 as i32,
+        WalCompression::Zstd => api::grpc::qdrant::WalCompression::Zstd as i32,
+    }
+}
+
+pub fn wal_compression_from_proto(compression: i32) -> Result<WalCompression, Status> {
+    match compression {
+        x if x == api::grpc::qdrant::WalCompression::None as i32 => Ok(WalCompression::None),
+        x if x == api::grpc::qdrant::WalCompression::Lz4 as i32 => Ok(WalCompression::Lz4),
+        x if x == api::grpc::qdrant::WalCompression::Zstd as i32 => Ok(WalCompression::Zstd),
+        _ => Err(Status::invalid_argument(format!(
+            "Cannot convert WAL compression: {compression}",
+        ))),
+    }
+}
+
+pub fn storage_placement_to_proto(placement: StoragePlacement) -> i32 {
+    match placement {
+        StoragePlacement::Disk => api::grpc::qdrant::StoragePlacement::Disk as i32,
+        StoragePlacement::Ram => api::grpc::qdrant::StoragePlacement::Ram as i32,
+    }
+}
+
+pub fn storage_placement_from_proto(placement: i32) -> Result<StoragePlacement, Status> {
+    match placement {
+        x if x == api::grpc::qdrant::StoragePlacement::Disk as i32 => Ok(StoragePlacement::Disk),
+        x if x == api::grpc::qdrant::StoragePlacement::Ram as i32 => Ok(StoragePlacement::Ram),
+        _ => Err(Status::invalid_argument(format!(
+            "Cannot convert storage placement: {placement}",
+        ))),
+    }
+}
+
+impl From<VectorStoragePolicy> for api::grpc::qdrant::VectorStoragePolicy {
+    fn from(value: VectorStoragePolicy) -> Self {
+        let VectorStoragePolicy {
+            vectors,
+            index,
+            quantization,
+        } = value;
+        Self {
+            vectors: vectors.map(storage_placement_to_proto),
+            index: index.map(storage_placement_to_proto),
+            quantization: quantization.map(storage_placement_to_proto),
+        }
+    }
+}
+
+impl TryFrom<api::grpc::qdrant::VectorStoragePolicy> for VectorStoragePolicy {
+    type Error = Status;
+
+    fn try_from(value: api::grpc::qdrant::VectorStoragePolicy) -> Result<Self, Self::Error> {
+        let api::grpc::qdrant::VectorStoragePolicy {
+            vectors,
+            index,
+            quantization,
+        } = value;
+        Ok(Self {
+            vectors: vectors.map(storage_placement_from_proto).transpose()?,
+            index: index.map(storage_placement_from_proto).transpose()?,
+            quantization: quantization.map(storage_placement_from_proto).transpose()?,
+        })
+    }
+}
+
+impl From<StoragePolicy> for api::grpc::qdrant::StoragePolicy {
+    fn from(value: StoragePolicy) -> Self {
+        let StoragePolicy {
+            all_vectors,
+            vectors,
+            payload,
+        } = value;
+        Self {
+            all_vectors: all_vectors.map(Into::into),
+            vectors: vectors
+                .into_iter()
+                .map(|(vector_name, policy)| (vector_name, policy.into()))
+                .collect(),
+            payload: payload.map(storage_placement_to_proto),
+        }
+    }
+}
+
+impl TryFrom<api::grpc::qdrant::StoragePolicy> for StoragePolicy {
+    type Error = Status;
+
+    fn try_from(value: api::grpc::qdrant::StoragePolicy) -> Result<Self, Self::Error> {
+        let api::grpc::qdrant::StoragePolicy {
+            all_vectors,
+            vectors,
+            payload,
+        } = value;
+        Ok(Self {
+            all_vectors: all_vectors.map(TryInto::try_into).transpose()?,
+            vectors: vectors
+                .into_iter()
+                .map(|(vector_name, policy)| Ok((vector_name, policy.try_into()?)))
+                .collect::<Result<_, Status>>()?,
+            payload: payload.map(storage_placement_from_proto).transpose()?,
+        })
+    }
+}
+
+impl From<InferenceConfig> for api::grpc::qdrant::InferenceConfig {
+    fn from(value: InferenceConfig) -> Self {
+        let InferenceConfig {
+            url,
+            model,
+            vector_name,
+            batch_size,
+            timeout_sec,
+            max_retries,
+        } = value;
+        Self {
+            url: url.to_string(),
+            model,
+            vector_name,
+            batch_size: batch_size.map(|size| size as u64),
+            timeout_sec,
+            max_retries: max_retries.map(|retries| retries as u64),
+        }
+    }
+}
+
+impl TryFrom<api::grpc::qdrant::InferenceConfig> for InferenceConfig {
+    type Error = Status;
+
+    fn try_from(value: api::grpc::qdrant::InferenceConfig) -> Result<Self, Self::Error> {
+        let api::grpc::qdrant::InferenceConfig {
+            url,
+            model,
+            vector_name,
+            batch_size,
+            timeout_sec,
+            max_retries,
+        } = value;
+        Ok(Self {
+            url: url.parse().map_err(|err| {
+                Status::invalid_argument(format!("Invalid inference endpoint url {url}: {err}"))
+            })?,
+            model,
+            vector_name,
+            batch_size: batch_size.map(|size| size as usize),
+            timeout_sec,
+            max_retries: max_retries.map(|retries| retries as usize),
+        })
+    }
+}
+
+impl From<api::grpc::qdrant::StrictModeConfig> for StrictModeConfig {
+    fn from(value: api::grpc::qdrant::StrictModeConfig) -> Self {
+        Self {
+            enabled: value.enabled,
+            max_query_limit: value.max_query_limit.map(|v| v as usize),
+            max_query_offset: value.max_query_offset.map(|v| v as usize),
+            max_batch_size: value.max_batch_size.map(|v| v as usize),
+            max_filter_conditions: value.max_filter_conditions.map(|v| v as usize),
+            unindexed_filtering_allowed: value.unindexed_filtering_allowed,
+            max_points_count: value.max_points_count.map(|v| v as usize),
+            max_disk_usage_bytes: value.max_disk_usage_bytes.map(|v| v as usize),
+            max_concurrent_searches: value.max_concurrent_searches.map(|v| v as usize),
+        }
+    }
+}
+
+impl From<StrictModeConfig> for api::grpc::qdrant::StrictModeConfig {
+    fn from(value: StrictModeConfig) -> Self {
+        Self {
+            enabled: value.enabled,
+            max_query_limit: value.max_query_limit.map(|v| v as u64),
+            max_query_offset: value.max_query_offset.map(|v| v as u64),
+            max_batch_size: value.max_batch_size.map(|v| v as u64),
+            max_filter_conditions: value.max_filter_conditions.map(|v| v as u64),
+            unindexed_filtering_allowed: value.unindexed_filtering_allowed,
+            max_points_count: value.max_points_count.map(|v| v as u64),
+            max_disk_usage_bytes: value.max_disk_usage_bytes.map(|v| v as u64),
+            max_concurrent_searches: value.max_concurrent_searches.map(|v| v as u64),
+        }
+    }
+}
+
+impl From<api::grpc::qdrant::FeatureFlags> for FeatureFlags {
+    fn from(value: api::grpc::qdrant::FeatureFlags) -> Self {
+        Self {
+            append_log_payload_storage: value.append_log_payload_storage,
+            dimensionality_reduction: value.dimensionality_reduction,
+        }
+    }
+}
+
+impl From<FeatureFlags> for api::grpc::qdrant::FeatureFlags {
+    fn from(value: FeatureFlags) -> Self {
+        Self {
+            append_log_payload_storage: value.append_log_payload_storage,
+            dimensionality_reduction: value.dimensionality_reduction,
         }
     }
 }
@@ -277,7 +544,25 @@ impl TryFrom<api::grpc::qdrant::CollectionParamsDiff> for CollectionParamsDiff {
                 })
                 .transpose()?,
             read_fan_out_factor: value.read_fan_out_factor,
+            allow_degraded_reads: value.allow_degraded_reads,
+            serve_reads_from_listeners: value.serve_reads_from_listeners,
+            hedge_updates: value.hedge_updates,
+            time_travel_window_sec: value.time_travel_window_sec,
+            inference: value.inference.map(TryInto::try_into).transpose()?,
+            shard_deactivation_timeout_sec: value.shard_deactivation_timeout_sec,
             on_disk_payload: value.on_disk_payload,
+            payload_storage_backend: value
+                .payload_storage_backend
+                .map(payload_storage_backend_from_proto)
+                .transpose()?,
+            expiration_field: value.expiration_field,
+            soft_delete_window_sec: value.soft_delete_window_sec,
+            storage_policy: value.storage_policy.map(TryInto::try_into).transpose()?,
+            default_write_ordering: value
+                .default_write_ordering
+                .map(write_ordering_type_from_proto)
+                .transpose()?,
+            default_wait: value.default_wait,
         })
     }
 }
@@ -292,7 +577,10 @@ impl From<api::grpc::qdrant::OptimizersConfigDiff> for OptimizersConfigDiff {
             memmap_threshold: value.memmap_threshold.map(|v| v as usize),
             indexing_threshold: value.indexing_threshold.map(|v| v as usize),
             flush_interval_sec: value.flush_interval_sec,
+            flush_dirty_threshold: value.flush_dirty_threshold.map(|v| v as usize),
+            flush_dirty_operations: value.flush_dirty_operations.map(|v| v as usize),
             max_optimization_threads: value.max_optimization_threads.map(|v| v as usize),
+            max_config_rebuild_segments: value.max_config_rebuild_segments.map(|v| v as usize),
         }
     }
 }
@@ -324,6 +612,7 @@ impl From<CollectionInfo> for api::grpc::qdrant::CollectionInfo {
             indexed_vectors_count,
             points_count,
             segments_count,
+            disk_usage_bytes,
             config,
             payload_schema,
         } = value;
@@ -348,6 +637,7 @@ impl From<CollectionInfo> for api::grpc::qdrant::CollectionInfo {
             indexed_vectors_count: indexed_vectors_count.map(|count| count as u64),
             points_count: points_count.map(|count| count as u64),
             segments_count: segments_count as u64,
+            disk_usage_bytes: disk_usage_bytes.map(|size| size as u64),
             config: Some(api::grpc::qdrant::CollectionConfig {
                 params: Some(api::grpc::qdrant::CollectionParams {
                     vectors_config: {
@@ -377,6 +667,12 @@ impl From<CollectionInfo> for api::grpc::qdrant::CollectionInfo {
                     on_disk_payload: config.params.on_disk_payload,
                     write_consistency_factor: Some(config.params.write_consistency_factor.get()),
                     read_fan_out_factor: config.params.read_fan_out_factor,
+                    allow_degraded_reads: config.params.allow_degraded_reads,
+                    serve_reads_from_listeners: config.params.serve_reads_from_listeners,
+                    hedge_updates: config.params.hedge_updates,
+                    time_travel_window_sec: config.params.time_travel_window_sec,
+                    inference: config.params.inference.map(Into::into),
+                    shard_deactivation_timeout_sec: config.params.shard_deactivation_timeout_sec,
                     sharding_method: config.params.sharding_method.map(sharding_method_to_proto),
                     sparse_vectors_config: config.params.sparse_vectors.map(|sparse_vectors| {
                         api::grpc::qdrant::SparseVectorConfig {
@@ -388,6 +684,20 @@ impl From<CollectionInfo> for api::grpc::qdrant::CollectionInfo {
                                 .collect(),
                         }
                     }),
+                    search_threads: config.params.search_threads.map(|threads| threads.get()),
+                    payload_storage_backend: config
+                        .params
+                        .payload_storage_backend
+                        .map(payload_storage_backend_to_proto),
+                    expiration_field: config.params.expiration_field,
+                    soft_delete_window_sec: config.params.soft_delete_window_sec,
+                    storage_policy: config.params.storage_policy.map(Into::into),
+                    default_write_ordering: config
+                        .params
+                        .default_write_ordering
+                        .map(write_ordering_type_to_proto),
+                    default_wait: config.params.default_wait,
+                    sharding_payload_key: config.params.sharding_payload_key,
                 }),
                 hnsw_config: Some(api::grpc::qdrant::HnswConfigDiff {
                     m: Some(config.hnsw_config.m as u64),
@@ -412,15 +722,32 @@ impl From<CollectionInfo> for api::grpc::qdrant::CollectionInfo {
                         .indexing_threshold
                         .map(|x| x as u64),
                     flush_interval_sec: Some(config.optimizer_config.flush_interval_sec),
+                    flush_dirty_threshold: config
+                        .optimizer_config
+                        .flush_dirty_threshold
+                        .map(|x| x as u64),
+                    flush_dirty_operations: config
+                        .optimizer_config
+                        .flush_dirty_operations
+                        .map(|x| x as u64),
                     max_optimization_threads: Some(
                         config.optimizer_config.max_optimization_threads as u64,
                     ),
+                    max_config_rebuild_segments: config
+                        .optimizer_config
+                        .max_config_rebuild_segments
+                        .map(|x| x as u64),
                 }),
                 wal_config: Some(api::grpc::qdrant::WalConfigDiff {
                     wal_capacity_mb: Some(config.wal_config.wal_capacity_mb as u64),
                     wal_segments_ahead: Some(config.wal_config.wal_segments_ahead as u64),
+                    wal_compression: Some(wal_compression_to_proto(
+                        config.wal_config.wal_compression,
+                    )),
                 }),
                 quantization_config: config.quantization_config.map(|x| x.into()),
+                strict_mode_config: config.strict_mode_config.map(|x| x.into()),
+                feature_flags: config.feature_flags.map(|x| x.into()),
             }),
             payload_schema: payload_schema
                 .into_iter()
@@ -439,6 +766,8 @@ impl From<Record> for api::grpc::qdrant::RetrievedPoint {
             payload: record.payload.map(payload_to_proto).unwrap_or_default(),
             vectors,
             shard_key: record.shard_key.map(convert_shard_key_to_grpc),
+            version: record.version,
+            order_value: record.order_value,
         }
     }
 }
@@ -469,19 +798,31 @@ impl From<api::grpc::qdrant::OptimizersConfigDiff> for OptimizersConfig {
             memmap_threshold: optimizer_config.memmap_threshold.map(|x| x as usize),
             indexing_threshold: optimizer_config.indexing_threshold.map(|x| x as usize),
             flush_interval_sec: optimizer_config.flush_interval_sec.unwrap_or_default(),
+            flush_dirty_threshold: optimizer_config.flush_dirty_threshold.map(|x| x as usize),
+            flush_dirty_operations: optimizer_config.flush_dirty_operations.map(|x| x as usize),
             max_optimization_threads: optimizer_config
                 .max_optimization_threads
                 .unwrap_or_default() as usize,
+            max_config_rebuild_segments: optimizer_config
+                .max_config_rebuild_segments
+                .map(|x| x as usize),
         }
     }
 }
 
-impl From<api::grpc::qdrant::WalConfigDiff> for WalConfig {
-    fn from(wal_config: api::grpc::qdrant::WalConfigDiff) -> Self {
-        Self {
+impl TryFrom<api::grpc::qdrant::WalConfigDiff> for WalConfig {
+    type Error = Status;
+
+    fn try_from(wal_config: api::grpc::qdrant::WalConfigDiff) -> Result<Self, Self::Error> {
+        Ok(Self {
             wal_capacity_mb: wal_config.wal_capacity_mb.unwrap_or_default() as usize,
             wal_segments_ahead: wal_config.wal_segments_ahead.unwrap_or_default() as usize,
-        }
+            wal_compression: wal_config
+                .wal_compression
+                .map(wal_compression_from_proto)
+                .transpose()?
+                .unwrap_or_default(),
+        })
     }
 }
 
@@ -530,7 +871,7 @@ impl TryFrom<api::grpc::qdrant::VectorParams> for VectorParams {
     type Error = Status;
 
     fn try_from(vector_params: api::grpc::qdrant::VectorParams) -> Result<Self, Self::Error> {
-        Ok(Self {
+        let params = Self {
             size: NonZeroU64::new(vector_params.size).ok_or_else(|| {
                 Status::invalid_argument("VectorParams size must be greater than zero")
             })?,
@@ -541,10 +882,84 @@ impl TryFrom<api::grpc::qdrant::VectorParams> for VectorParams {
                 .map(grpc_to_segment_quantization_config)
                 .transpose()?,
             on_disk: vector_params.on_disk,
+            normalize: vector_params.normalize,
+            dimensionality_reduction: vector_params
+                .dimensionality_reduction
+                .map(TryInto::try_into)
+                .transpose()?,
+            multivector_config: vector_params
+                .multivector_config
+                .map(TryInto::try_into)
+                .transpose()?,
+            datatype: vector_params.datatype.map(from_grpc_datatype).transpose()?,
+        };
+        if params.multivector_config.is_some() && params.quantization_config.is_some() {
+            return Err(Status::invalid_argument("Multi-vectors can't be quantized"));
+        }
+        if let Some(conflict) = params.datatype_conflict() {
+            return Err(Status::invalid_argument(conflict));
+        }
+        if let Some(reduction) = &params.dimensionality_reduction {
+            validate_dimensionality_reduction(params.size, reduction).map_err(|err| {
+                Status::invalid_argument(format!("Invalid dimensionality reduction: {err}"))
+            })?;
+        }
+        Ok(params)
+    }
+}
+
+impl From<api::grpc::qdrant::LinearTransform> for LinearTransform {
+    fn from(value: api::grpc::qdrant::LinearTransform) -> Self {
+        let api::grpc::qdrant::LinearTransform { mean, matrix } = value;
+        Self { mean, matrix }
+    }
+}
+
+impl From<LinearTransform> for api::grpc::qdrant::LinearTransform {
+    fn from(value: LinearTransform) -> Self {
+        let LinearTransform { mean, matrix } = value;
+        Self { mean, matrix }
+    }
+}
+
+impl TryFrom<api::grpc::qdrant::DimensionalityReduction> for DimensionalityReduction {
+    type Error = Status;
+
+    fn try_from(value: api::grpc::qdrant::DimensionalityReduction) -> Result<Self, Self::Error> {
+        Ok(Self {
+            input_size: NonZeroU64::new(value.input_size).ok_or_else(|| {
+                Status::invalid_argument(
+                    "DimensionalityReduction input_size must be greater than zero",
+                )
+            })?,
+            transform: value.transform.map(Into::into),
         })
     }
 }
 
+impl From<DimensionalityReduction> for api::grpc::qdrant::DimensionalityReduction {
+    fn from(value: DimensionalityReduction) -> Self {
+        Self {
+            input_size: value.input_size.get(),
+            transform: value.transform.map(Into::into),
+        }
+    }
+}
+
+impl TryFrom<api::grpc::qdrant::VectorParamsMap> for NewVectorsConfig {
+    type Error = Status;
+
+    fn try_from(vectors_map: api::grpc::qdrant::VectorParamsMap) -> Result<Self, Self::Error> {
+        Ok(Self(
+            vectors_map
+                .map
+                .into_iter()
+                .map(|(name, params)| Ok((name, params.try_into()?)))
+                .collect::<Result<_, Status>>()?,
+        ))
+    }
+}
+
 impl TryFrom<api::grpc::qdrant::VectorParamsDiff> for VectorParamsDiff {
     type Error = Status;
 
@@ -556,6 +971,9 @@ impl TryFrom<api::grpc::qdrant::VectorParamsDiff> for VectorParamsDiff {
                 .map(TryInto::try_into)
                 .transpose()?,
             on_disk: vector_params.on_disk,
+            dimensionality_reduction_transform: vector_params
+                .dimensionality_reduction_transform
+                .map(Into::into),
         })
     }
 }
@@ -667,10 +1085,30 @@ impl TryFrom<api::grpc::qdrant::CollectionConfig> for CollectionConfig {
                     })?,
 
                     read_fan_out_factor: params.read_fan_out_factor,
+                    allow_degraded_reads: params.allow_degraded_reads,
+                    serve_reads_from_listeners: params.serve_reads_from_listeners,
+                    hedge_updates: params.hedge_updates,
+                    time_travel_window_sec: params.time_travel_window_sec,
+                    inference: params.inference.map(TryInto::try_into).transpose()?,
+                    shard_deactivation_timeout_sec: params.shard_deactivation_timeout_sec,
                     sharding_method: params
                         .sharding_method
                         .map(sharding_method_from_proto)
                         .transpose()?,
+                    sharding_payload_key: params.sharding_payload_key,
+                    search_threads: params.search_threads.and_then(NonZeroU32::new),
+                    payload_storage_backend: params
+                        .payload_storage_backend
+                        .map(payload_storage_backend_from_proto)
+                        .transpose()?,
+                    expiration_field: params.expiration_field,
+                    soft_delete_window_sec: params.soft_delete_window_sec,
+                    storage_policy: params.storage_policy.map(TryInto::try_into).transpose()?,
+                    default_write_ordering: params
+                        .default_write_ordering
+                        .map(write_ordering_type_from_proto)
+                        .transpose()?,
+                    default_wait: params.default_wait,
                 },
             },
             hnsw_config: match config.hnsw_config {
@@ -683,7 +1121,7 @@ impl TryFrom<api::grpc::qdrant::CollectionConfig> for CollectionConfig {
             },
             wal_config: match config.wal_config {
                 None => return Err(Status::invalid_argument("Malformed WalConfig type")),
-                Some(wal_config) => wal_config.into(),
+                Some(wal_config) => wal_config.try_into()?,
             },
             quantization_config: {
                 if let Some(config) = config.quantization_config {
@@ -692,6 +1130,8 @@ impl TryFrom<api::grpc::qdrant::CollectionConfig> for CollectionConfig {
                     None
                 }
             },
+            strict_mode_config: config.strict_mode_config.map(Into::into),
+            feature_flags: config.feature_flags.map(Into::into),
         })
     }
 }
@@ -726,6 +1166,9 @@ impl TryFrom<api::grpc::qdrant::GetCollectionInfoResponse> for CollectionInfo {
                     .points_count
                     .map(|count| count as usize),
                 segments_count: collection_info_response.segments_count as usize,
+                disk_usage_bytes: collection_info_response
+                    .disk_usage_bytes
+                    .map(|size| size as usize),
                 config: match collection_info_response.config {
                     None => {
                         return Err(Status::invalid_argument("Malformed CollectionConfig type"))
@@ -750,6 +1193,7 @@ impl TryFrom<api::grpc::qdrant::PointStruct> for PointStruct {
             id,
             vectors,
             payload,
+            if_version,
         } = value;
 
         let converted_payload = proto_to_payloads(payload)?;
@@ -765,6 +1209,7 @@ impl TryFrom<api::grpc::qdrant::PointStruct> for PointStruct {
                 .try_into()?,
             vector: vector_struct,
             payload: Some(converted_payload),
+            if_version,
         })
     }
 }
@@ -777,6 +1222,7 @@ impl TryFrom<PointStruct> for api::grpc::qdrant::PointStruct {
 
         let id = value.id;
         let payload = value.payload;
+        let if_version = value.if_version;
 
         let converted_payload = match payload {
             None => HashMap::new(),
@@ -787,6 +1233,7 @@ impl TryFrom<PointStruct> for api::grpc::qdrant::PointStruct {
             id: Some(id.into()),
             vectors: Some(vectors),
             payload: converted_payload,
+            if_version,
         })
     }
 }
@@ -812,6 +1259,7 @@ impl TryFrom<Batch> for Vec<api::grpc::qdrant::PointStruct> {
                 id,
                 vectors: vectors.map(|v| v.into()),
                 payload: payload.unwrap_or_default(),
+                if_version: None,
             };
             points.push(point);
         }
@@ -845,14 +1293,75 @@ pub fn try_points_selector_from_grpc(
     }
 }
 
+impl From<UpdateStatus> for api::grpc::qdrant::UpdateStatus {
+    fn from(value: UpdateStatus) -> Self {
+        match value {
+            UpdateStatus::Acknowledged => api::grpc::qdrant::UpdateStatus::Acknowledged,
+            UpdateStatus::Completed => api::grpc::qdrant::UpdateStatus::Completed,
+        }
+    }
+}
+
+fn update_status_from_grpc(status: i32) -> Result<UpdateStatus, Status> {
+    match status {
+        status if status == api::grpc::qdrant::UpdateStatus::Acknowledged as i32 => {
+            Ok(UpdateStatus::Acknowledged)
+        }
+        status if status == api::grpc::qdrant::UpdateStatus::Completed as i32 => {
+            Ok(UpdateStatus::Completed)
+        }
+        _ => Err(Status::invalid_argument("Malformed UpdateStatus type")),
+    }
+}
+
+impl From<ReplicaAcknowledgment> for api::grpc::qdrant::ReplicaAcknowledgment {
+    fn from(value: ReplicaAcknowledgment) -> Self {
+        let ReplicaAcknowledgment {
+            shard_id,
+            peer_id,
+            status,
+            error,
+        } = value;
+        Self {
+            shard_id,
+            peer_id,
+            status: status.map(|status| api::grpc::qdrant::UpdateStatus::from(status) as i32),
+            error,
+        }
+    }
+}
+
+impl TryFrom<api::grpc::qdrant::ReplicaAcknowledgment> for ReplicaAcknowledgment {
+    type Error = Status;
+
+    fn try_from(value: api::grpc::qdrant::ReplicaAcknowledgment) -> Result<Self, Self::Error> {
+        let api::grpc::qdrant::ReplicaAcknowledgment {
+            shard_id,
+            peer_id,
+            status,
+            error,
+        } = value;
+        Ok(Self {
+            shard_id,
+            peer_id,
+            status: status.map(update_status_from_grpc).transpose()?,
+            error,
+        })
+    }
+}
+
 impl From<UpdateResult> for api::grpc::qdrant::UpdateResult {
     fn from(value: UpdateResult) -> Self {
         Self {
             operation_id: value.operation_id,
-            status: match value.status {
-                UpdateStatus::Acknowledged => api::grpc::qdrant::UpdateStatus::Acknowledged as i32,
-                UpdateStatus::Completed => api::grpc::qdrant::UpdateStatus::Completed as i32,
-            },
+            status: api::grpc::qdrant::UpdateStatus::from(value.status) as i32,
+            consistency_token: value.consistency_token,
+            acknowledgments: value
+                .acknowledgments
+                .unwrap_or_default()
+                .into_iter()
+                .map(Into::into)
+                .collect(),
         }
     }
 }
@@ -861,17 +1370,23 @@ impl TryFrom<api::grpc::qdrant::UpdateResult> for UpdateResult {
     type Error = Status;
 
     fn try_from(value: api::grpc::qdrant::UpdateResult) -> Result<Self, Self::Error> {
+        // Results without acknowledgments, e.g. of a single replica, have none of them
+        let acknowledgments = if value.acknowledgments.is_empty() {
+            None
+        } else {
+            Some(
+                value
+                    .acknowledgments
+                    .into_iter()
+                    .map(TryInto::try_into)
+                    .collect::<Result<_, _>>()?,
+            )
+        };
         Ok(Self {
             operation_id: value.operation_id,
-            status: match value.status {
-                status if status == api::grpc::qdrant::UpdateStatus::Acknowledged as i32 => {
-                    UpdateStatus::Acknowledged
-                }
-                status if status == api::grpc::qdrant::UpdateStatus::Completed as i32 => {
-                    UpdateStatus::Completed
-                }
-                _ => return Err(Status::invalid_argument("Malformed UpdateStatus type")),
-            },
+            status: update_status_from_grpc(value.status)?,
+            consistency_token: value.consistency_token,
+            acknowledgments,
         })
     }
 }
@@ -910,10 +1425,17 @@ impl TryFrom<api::grpc::qdrant::SearchPoints> for CoreSearchRequest {
             timeout: _,
             shard_key_selector: _,
             sparse_indices,
+            consistency_token: _,
+            vectors_count,
+            partial_results: _,
         } = value;
 
-        let vector_struct =
-            api::grpc::conversions::into_named_vector_struct(vector_name, vector, sparse_indices)?;
+        let vector_struct = api::grpc::conversions::into_named_vector_struct(
+            vector_name,
+            vector,
+            sparse_indices,
+            vectors_count,
+        )?;
 
         Ok(Self {
             query: QueryEnum::Nearest(vector_struct),
@@ -924,6 +1446,9 @@ impl TryFrom<api::grpc::qdrant::SearchPoints> for CoreSearchRequest {
             with_payload: with_payload.map(TryInto::try_into).transpose()?,
             with_vector: with_vectors.map(Into::into),
             score_threshold: score_threshold.map(|s| s as ScoreType),
+            prefetch: None,
+            rescore: None,
+            distinct_by: None,
         })
     }
 }
@@ -932,15 +1457,11 @@ impl TryFrom<api::grpc::qdrant::SearchPoints> for CoreSearchRequest {
 impl<'a> From<CollectionSearchRequest<'a>> for api::grpc::qdrant::SearchPoints {
     fn from(value: CollectionSearchRequest<'a>) -> Self {
         let (collection_id, request) = value.0;
-        let (vector, sparse_indices) = match request.vector.get_vector().to_owned() {
-            Vector::Dense(vector) => (vector, None),
-            Vector::Sparse(vector) => (
-                vector.values,
-                Some(api::grpc::qdrant::SparseIndices {
-                    data: vector.indices,
-                }),
-            ),
-        };
+        let api::grpc::qdrant::Vector {
+            data: vector,
+            indices: sparse_indices,
+            vectors_count,
+        } = request.vector.get_vector().to_owned().into();
         Self {
             collection_name: collection_id,
             vector,
@@ -959,6 +1480,9 @@ impl<'a> From<CollectionSearchRequest<'a>> for api::grpc::qdrant::SearchPoints {
             timeout: None,
             shard_key_selector: None,
             sparse_indices,
+            consistency_token: None,
+            vectors_count,
+            partial_results: None,
         }
     }
 }
@@ -1030,6 +1554,28 @@ impl<'a> From<CollectionCoreSearchRequest<'a>> for api::grpc::qdrant::CoreSearch
             offset: Some(request.offset as u64),
             vector_name: Some(request.query.get_vector_name().to_owned()),
             read_consistency: None,
+            prefetch: request
+                .prefetch
+                .clone()
+                .map(|prefetch| api::grpc::qdrant::CorePrefetch {
+                    vector_name: Some(prefetch.query.get_vector_name().to_owned()),
+                    query: Some(prefetch.query.into()),
+                    params: prefetch.params.map(|sp| sp.into()),
+                    limit: prefetch.limit as u64,
+                }),
+            rescore: request
+                .rescore
+                .as_ref()
+                .map(|rescore| api::grpc::qdrant::CoreRescore {
+                    formula: rescore.formula.to_string(),
+                    candidates: rescore.candidates as u64,
+                }),
+            distinct_by: request.distinct_by.as_ref().map(|distinct| {
+                api::grpc::qdrant::CoreDistinct {
+                    key: distinct.key.clone(),
+                    candidates: distinct.candidates as u64,
+                }
+            }),
         }
     }
 }
@@ -1089,66 +1635,94 @@ fn try_context_pair_from_grpc(
     }
 }
 
+fn try_query_from_grpc(
+    query: Option<api::grpc::qdrant::QueryEnum>,
+    vector_name: Option<String>,
+) -> Result<QueryEnum, Status> {
+    query
+        .and_then(|query| query.query)
+        .map(|query| {
+            Ok(match query {
+                api::grpc::qdrant::query_enum::Query::NearestNeighbors(vector) => {
+                    QueryEnum::Nearest(api::grpc::conversions::into_named_vector_struct(
+                        vector_name,
+                        vector.data,
+                        vector.indices.clone(),
+                    )?)
+                }
+                api::grpc::qdrant::query_enum::Query::RecommendBestScore(query) => {
+                    QueryEnum::RecommendBestScore(NamedQuery {
+                        query: RecoQuery::new(
+                            query.positives.into_iter().map(|v| v.into()).collect(),
+                            query.negatives.into_iter().map(|v| v.into()).collect(),
+                        ),
+                        using: vector_name,
+                    })
+                }
+                api::grpc::qdrant::query_enum::Query::Discover(query) => {
+                    let target = match query.target {
+                        Some(target) => target,
+                        None => return Err(Status::invalid_argument("Target is not specified")),
+                    };
+
+                    let pairs = query
+                        .context
+                        .into_iter()
+                        .map(try_context_pair_from_grpc)
+                        .try_collect()?;
+
+                    QueryEnum::Discover(NamedQuery {
+                        query: DiscoveryQuery::new(target.into(), pairs),
+                        using: vector_name,
+                    })
+                }
+                api::grpc::qdrant::query_enum::Query::Context(query) => {
+                    let pairs = query
+                        .context
+                        .into_iter()
+                        .map(try_context_pair_from_grpc)
+                        .try_collect()?;
+
+                    QueryEnum::Context(NamedQuery {
+                        query: ContextQuery::new(pairs),
+                        using: vector_name,
+                    })
+                }
+            })
+        })
+        .transpose()?
+        .ok_or(Status::invalid_argument("Query is not specified"))
+}
+
 impl TryFrom<api::grpc::qdrant::CoreSearchPoints> for CoreSearchRequest {
     type Error = Status;
 
     fn try_from(value: api::grpc::qdrant::CoreSearchPoints) -> Result<Self, Self::Error> {
-        let query = value
-            .query
-            .and_then(|query| query.query)
-            .map(|query| {
-                Ok(match query {
-                    api::grpc::qdrant::query_enum::Query::NearestNeighbors(vector) => {
-                        QueryEnum::Nearest(api::grpc::conversions::into_named_vector_struct(
-                            value.vector_name,
-                            vector.data,
-                            vector.indices.clone(),
-                        )?)
-                    }
-                    api::grpc::qdrant::query_enum::Query::RecommendBestScore(query) => {
-                        QueryEnum::RecommendBestScore(NamedQuery {
-                            query: RecoQuery::new(
-                                query.positives.into_iter().map(|v| v.into()).collect(),
-                                query.negatives.into_iter().map(|v| v.into()).collect(),
-                            ),
-                            using: value.vector_name,
-                        })
-                    }
-                    api::grpc::qdrant::query_enum::Query::Discover(query) => {
-                        let target = match query.target {
-                            Some(target) => target,
-                            None => {
-                                return Err(Status::invalid_argument("Target is not specified"))
-                            }
-                        };
-
-                        let pairs = query
-                            .context
-                            .into_iter()
-                            .map(try_context_pair_from_grpc)
-                            .try_collect()?;
-
-                        QueryEnum::Discover(NamedQuery {
-                            query: DiscoveryQuery::new(target.into(), pairs),
-                            using: value.vector_name,
-                        })
-                    }
-                    api::grpc::qdrant::query_enum::Query::Context(query) => {
-                        let pairs = query
-                            .context
-                            .into_iter()
-                            .map(try_context_pair_from_grpc)
-                            .try_collect()?;
-
-                        QueryEnum::Context(NamedQuery {
-                            query: ContextQuery::new(pairs),
-                            using: value.vector_name,
-                        })
-                    }
+        let query = try_query_from_grpc(value.query, value.vector_name)?;
+        let prefetch = value
+            .prefetch
+            .map(|prefetch| {
+                Ok::<_, Status>(CorePrefetch {
+                    query: try_query_from_grpc(prefetch.query, prefetch.vector_name)?,
+                    params: prefetch.params.map(|p| p.into()),
+                    limit: prefetch.limit as usize,
+                })
+            })
+            .transpose()?;
+        let rescore = value
+            .rescore
+            .map(|rescore| {
+                Ok::<_, Status>(CoreRescore {
+                    formula: ScoreFormula::parse(&rescore.formula)
+                        .map_err(Status::invalid_argument)?,
+                    candidates: rescore.candidates as usize,
                 })
             })
-            .transpose()?
-            .ok_or(Status::invalid_argument("Query is not specified"))?;
+            .transpose()?;
+        let distinct_by = value.distinct_by.map(|distinct| CoreDistinct {
+            key: distinct.key,
+            candidates: distinct.candidates as usize,
+        });
 
         Ok(Self {
             query,
@@ -1164,6 +1738,9 @@ impl TryFrom<api::grpc::qdrant::CoreSearchPoints> for CoreSearchRequest {
                     .unwrap_or_default(),
             ),
             score_threshold: value.score_threshold,
+            prefetch,
+            rescore,
+            distinct_by,
         })
     }
 }
@@ -1177,6 +1754,7 @@ impl TryFrom<api::grpc::qdrant::SearchPoints> for SearchRequestInternal {
                 value.vector_name,
                 value.vector,
                 value.sparse_indices,
+                value.vectors_count,
             )?,
             filter: value.filter.map(|f| f.try_into()).transpose()?,
             params: value.params.map(|p| p.into()),
@@ -1213,6 +1791,9 @@ impl TryFrom<api::grpc::qdrant::SearchPointGroups> for SearchGroupsRequestIntern
             timeout: None,
             shard_key_selector: None,
             sparse_indices: value.sparse_indices,
+            consistency_token: None,
+            vectors_count: value.vectors_count,
+            partial_results: None,
         };
 
         let SearchRequestInternal {
@@ -1319,6 +1900,26 @@ impl TryFrom<i32> for RecommendStrategy {
     }
 }
 
+impl TryFrom<i32> for Sample {
+    type Error = Status;
+
+    fn try_from(value: i32) -> Result<Self, Self::Error> {
+        let sample = api::grpc::qdrant::Sample::from_i32(value)
+            .ok_or_else(|| Status::invalid_argument(format!("Unknown sample: {}", value)))?;
+        Ok(match sample {
+            api::grpc::qdrant::Sample::Random => Sample::Random,
+        })
+    }
+}
+
+impl From<Sample> for api::grpc::qdrant::Sample {
+    fn from(value: Sample) -> Self {
+        match value {
+            Sample::Random => Self::Random,
+        }
+    }
+}
+
 impl TryFrom<api::grpc::qdrant::PointId> for RecommendExample {
     type Error = Status;
 
@@ -1332,6 +1933,7 @@ impl From<api::grpc::qdrant::Vector> for RecommendExample {
         let vector: Vector = value.into();
         match vector {
             Vector::Dense(vector) => Self::Dense(vector),
+            Vector::MultiDense(vector) => Self::MultiDense(vector),
             Vector::Sparse(vector) => Self::Sparse(vector),
         }
     }
@@ -1484,6 +2086,17 @@ impl From<VectorParams> for api::grpc::qdrant::VectorParams {
             hnsw_config: value.hnsw_config.map(Into::into),
             quantization_config: value.quantization_config.map(Into::into),
             on_disk: value.on_disk,
+            normalize: value.normalize,
+            dimensionality_reduction: value.dimensionality_reduction.map(Into::into),
+            multivector_config: value.multivector_config.map(Into::into),
+            datatype: value.datatype.map(|datatype| {
+                match datatype {
+                    VectorStorageDatatype::Float32 => api::grpc::qdrant::Datatype::Float32,
+                    VectorStorageDatatype::Uint8 => api::grpc::qdrant::Datatype::Uint8,
+                    VectorStorageDatatype::Float16 => api::grpc::qdrant::Datatype::Float16,
+                }
+                .into()
+            }),
         }
     }
 }
@@ -1519,6 +2132,16 @@ impl From<RemoteShardInfo> for api::grpc::qdrant::RemoteShardInfo {
     }
 }
 
+impl From<ShardFailure> for api::grpc::qdrant::ShardFailure {
+    fn from(value: ShardFailure) -> Self {
+        Self {
+            shard_id: value.shard_id,
+            shard_key: value.shard_key.map(convert_shard_key_to_grpc),
+            error: value.error,
+        }
+    }
+}
+
 impl From<ShardTransferInfo> for api::grpc::qdrant::ShardTransferInfo {
     fn from(value: ShardTransferInfo) -> Self {
         Self {
@@ -1587,6 +2210,7 @@ impl From<api::grpc::qdrant::ShardTransferMethod> for ShardTransferMethod {
                 ShardTransferMethod::StreamRecords
             }
             api::grpc::qdrant::ShardTransferMethod::Snapshot => ShardTransferMethod::Snapshot,
+            api::grpc::qdrant::ShardTransferMethod::WalDelta => ShardTransferMethod::WalDelta,
         }
     }
 }
@@ -1675,6 +2299,11 @@ impl TryFrom<ClusterOperationsPb> for ClusterOperations {
                     drop_sharding_key: op.try_into()?,
                 })
             }
+            Operation::SplitShard(op) => ClusterOperations::SplitShard(SplitShardOperation {
+                split_shard: SplitShard {
+                    shard_id: op.shard_id,
+                },
+            }),
         })
     }
 }
@@ -0,0 +1,321 @@
+//! Dimensionality reduction of vectors on ingest
+//!
+//! Vectors with `dimensionality_reduction` configured are sent by the clients with `input_size`
+//! dimensions. They are projected by a learned linear transform, like PCA or OPQ, to the `size`
+//! of the vector before they are stored, and so are the query vectors.
+
+use std::hash::{Hash, Hasher};
+use std::num::NonZeroU64;
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use schemars::JsonSchema;
+use segment::data_types::vectors::DenseVector;
+use serde::{Deserialize, Serialize};
+use validator::{Validate, ValidationError};
+
+use crate::operations::types::{
+    validate_nonzerou64_range_min_1_max_65536, CollectionError, CollectionResult,
+};
+
+/// Number of iterations of the subspace iteration, used to find the principal components
+const PCA_ITERATIONS: usize = 32;
+
+/// Seed of the initial subspace, so that training on the same sample gives the same transform
+const PCA_SEED: u64 = 42;
+
+/// Reduction of the dimensionality of the vectors on ingest
+#[derive(Debug, Hash, Deserialize, Serialize, JsonSchema, Validate, Clone, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub struct DimensionalityReduction {
+    /// Size of the vectors sent by the clients, before the reduction.
+    /// Must be greater than the size of the stored vectors.
+    #[validate(custom = "validate_nonzerou64_range_min_1_max_65536")]
+    pub input_size: NonZeroU64,
+    /// Learned transform of the vectors.
+    /// If none - vectors can't be inserted until the transform is trained or uploaded.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub transform: Option<LinearTransform>,
+}
+
+/// Linear transform of the vectors: `matrix * (vector - mean)`
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub struct LinearTransform {
+    /// Mean of the input vectors, subtracted before the projection. Has `input_size` values.
+    pub mean: Vec<f32>,
+    /// Projection matrix in row-major order, with a row per output dimension.
+    /// Has `size * input_size` values.
+    pub matrix: Vec<f32>,
+}
+
+impl Hash for LinearTransform {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        for value in self.mean.iter().chain(&self.matrix) {
+            value.to_le_bytes().hash(state);
+        }
+    }
+}
+
+// Values of the transform are always finite, see `check_dimensions`
+impl Eq for LinearTransform {}
+
+impl LinearTransform {
+    /// Check that the transform maps vectors of `input_size` to vectors of `output_size`
+    pub fn check_dimensions(&self, input_size: usize, output_size: usize) -> CollectionResult<()> {
+        if self.mean.len() != input_size {
+            return Err(CollectionError::bad_input(format!(
+                "Transform mean has {} values, expected {input_size}",
+                self.mean.len(),
+            )));
+        }
+        if self.matrix.len() != input_size * output_size {
+            return Err(CollectionError::bad_input(format!(
+                "Transform matrix has {} values, expected {output_size} x {input_size}",
+                self.matrix.len(),
+            )));
+        }
+        if !self.mean.iter().chain(&self.matrix).all(|x| x.is_finite()) {
+            return Err(CollectionError::bad_input(
+                "Transform values must be finite".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    pub fn input_size(&self) -> usize {
+        self.mean.len()
+    }
+
+    pub fn output_size(&self) -> usize {
+        self.matrix.len().checked_div(self.mean.len()).unwrap_or(0)
+    }
+
+    pub fn apply(&self, vector: &[f32]) -> DenseVector {
+        debug_assert_eq!(vector.len(), self.input_size());
+        self.matrix
+            .chunks_exact(self.input_size())
+            .map(|row| {
+                row.iter()
+                    .zip(vector.iter().zip(&self.mean))
+                    .map(|(weight, (value, mean))| weight * (value - mean))
+                    .sum()
+            })
+            .collect()
+    }
+
+    /// Train the PCA transform, which keeps `output_size` principal components of the sample
+    ///
+    /// Principal components are found by the subspace iteration on the centered sample, without
+    /// materializing the covariance matrix, which would be too large for big vectors.
+    pub fn train_pca(sample: &[DenseVector], output_size: usize) -> CollectionResult<Self> {
+        let Some(input_size) = sample.first().map(Vec::len) else {
+            return Err(CollectionError::bad_input(
+                "Training sample is empty".to_string(),
+            ));
+        };
+        if sample.iter().any(|vector| vector.len() != input_size) {
+            return Err(CollectionError::bad_input(format!(
+                "All vectors of the training sample must have {input_size} dimensions",
+            )));
+        }
+        if output_size == 0 || output_size >= input_size {
+            return Err(CollectionError::bad_input(format!(
+                "Reduced size {output_size} must be in range [1, {input_size})",
+            )));
+        }
+        if sample.len() <= output_size {
+            return Err(CollectionError::bad_input(format!(
+                "Training sample must have more than {output_size} vectors, got {}",
+                sample.len(),
+            )));
+        }
+        if !sample.iter().flatten().all(|x| x.is_finite()) {
+            return Err(CollectionError::bad_input(
+                "Training sample values must be finite".to_string(),
+            ));
+        }
+
+        let mut mean = vec![0.0f64; input_size];
+        for vector in sample {
+            for (mean, value) in mean.iter_mut().zip(vector) {
+                *mean += f64::from(*value);
+            }
+        }
+        mean.iter_mut().for_each(|x| *x /= sample.len() as f64);
+
+        let centered: Vec<Vec<f64>> = sample
+            .iter()
+            .map(|vector| {
+                vector
+                    .iter()
+                    .zip(&mean)
+                    .map(|(value, mean)| f64::from(*value) - mean)
+                    .collect()
+            })
+            .collect();
+
+        // Basis of the subspace, a row per component
+        let mut rng = StdRng::seed_from_u64(PCA_SEED);
+        let mut basis: Vec<Vec<f64>> = (0..output_size)
+            .map(|_| (0..input_size).map(|_| rng.gen_range(-1.0..1.0)).collect())
+            .collect();
+        orthonormalize(&mut basis);
+
+        for _ in 0..PCA_ITERATIONS {
+            // basis = X^T * (X * basis), which is the covariance matrix times the basis
+            let mut next = vec![vec![0.0f64; input_size]; output_size];
+            for row in &centered {
+                for (component, next) in basis.iter().zip(next.iter_mut()) {
+                    let projection = dot(row, component);
+                    for (next, value) in next.iter_mut().zip(row) {
+                        *next += projection * value;
+                    }
+                }
+            }
+            orthonormalize(&mut next);
+            basis = next;
+        }
+
+        Ok(Self {
+            mean: mean.into_iter().map(|x| x as f32).collect(),
+            matrix: basis.into_iter().flatten().map(|x| x as f32).collect(),
+        })
+    }
+}
+
+fn dot(a: &[f64], b: &[f64]) -> f64 {
+    a.iter().zip(b).map(|(a, b)| a * b).sum()
+}
+
+/// Modified Gram-Schmidt orthonormalization of the rows
+///
+/// Degenerate rows, e.g. if the sample has fewer independent directions than components, are
+/// left zero, so they don't contribute to the reduced vectors.
+fn orthonormalize(rows: &mut [Vec<f64>]) {
+    for i in 0..rows.len() {
+        let (done, rest) = rows.split_at_mut(i);
+        let row = &mut rest[0];
+        for other in done.iter() {
+            let projection = dot(row, other);
+            for (value, other) in row.iter_mut().zip(other) {
+                *value -= projection * other;
+            }
+        }
+        let norm = dot(row, row).sqrt();
+        if norm > f64::EPSILON {
+            row.iter_mut().for_each(|x| *x /= norm);
+        } else {
+            row.iter_mut().for_each(|x| *x = 0.0);
+        }
+    }
+}
+
+/// Train the dimensionality reduction of the vector on the given sample
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone)]
+#[serde(rename_all = "snake_case")]
+pub struct TrainDimensionalityReduction {
+    /// Name of the vector to train the reduction for. If none - the default vector is used.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub vector_name: Option<String>,
+    /// Sample of the vectors, with `input_size` dimensions each.
+    /// Must have more vectors than the size of the reduced vectors.
+    #[validate(length(min = 2))]
+    pub sample: Vec<DenseVector>,
+}
+
+/// Validate that the reduction lowers the dimensionality of the vector, and the transform fits it
+pub fn validate_dimensionality_reduction(
+    size: NonZeroU64,
+    reduction: &DimensionalityReduction,
+) -> Result<(), ValidationError> {
+    if reduction.input_size <= size {
+        let mut error = ValidationError::new("input_size");
+        error.message = Some("input_size must be greater than the vector size".into());
+        return Err(error);
+    }
+    if let Some(transform) = &reduction.transform {
+        let check =
+            transform.check_dimensions(reduction.input_size.get() as usize, size.get() as usize);
+        if let Err(err) = check {
+            let mut error = ValidationError::new("transform");
+            error.message = Some(err.to_string().into());
+            return Err(error);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_apply_transform() {
+        let transform = LinearTransform {
+            mean: vec![1.0, 1.0, 1.0],
+            matrix: vec![1.0, 0.0, 0.0, 0.0, 0.5, 0.5],
+        };
+        transform.check_dimensions(3, 2).unwrap();
+        assert!(transform.check_dimensions(3, 3).is_err());
+        assert_eq!(transform.output_size(), 2);
+
+        assert_eq!(transform.apply(&[2.0, 3.0, 5.0]), vec![1.0, 3.0]);
+    }
+
+    #[test]
+    fn test_train_pca() {
+        // Points on a line in 3D, with a bit of noise across it
+        let sample: Vec<DenseVector> = (0..100)
+            .map(|i| {
+                let t = i as f32 / 10.0;
+                let noise = if i % 2 == 0 { 0.01 } else { -0.01 };
+                vec![t + 1.0, 2.0 * t + noise, -t + 3.0]
+            })
+            .collect();
+
+        let transform = LinearTransform::train_pca(&sample, 1).unwrap();
+        transform.check_dimensions(3, 1).unwrap();
+
+        // Principal component is the direction of the line
+        let norm = 6.0f32.sqrt();
+        let expected = [1.0 / norm, 2.0 / norm, -1.0 / norm];
+        let sign = transform.matrix[0].signum();
+        for (value, expected) in transform.matrix.iter().zip(expected) {
+            assert!((value * sign - expected).abs() < 1e-3, "{transform:?}");
+        }
+
+        // Distances along the line are preserved
+        let a = transform.apply(&sample[10]);
+        let b = transform.apply(&sample[20]);
+        assert!(((a[0] - b[0]).abs() - norm).abs() < 1e-2);
+
+        assert!(LinearTransform::train_pca(&sample[..1], 1).is_err());
+        assert!(LinearTransform::train_pca(&sample, 3).is_err());
+    }
+
+    #[test]
+    fn test_validate_dimensionality_reduction() {
+        let size = NonZeroU64::new(2).unwrap();
+        let reduction = DimensionalityReduction {
+            input_size: NonZeroU64::new(3).unwrap(),
+            transform: None,
+        };
+        assert!(validate_dimensionality_reduction(size, &reduction).is_ok());
+
+        let reduction = DimensionalityReduction {
+            input_size: NonZeroU64::new(2).unwrap(),
+            transform: None,
+        };
+        assert!(validate_dimensionality_reduction(size, &reduction).is_err());
+
+        let reduction = DimensionalityReduction {
+            input_size: NonZeroU64::new(3).unwrap(),
+            transform: Some(LinearTransform {
+                mean: vec![0.0; 3],
+                matrix: vec![0.0; 3],
+            }),
+        };
+        assert!(validate_dimensionality_reduction(size, &reduction).is_err());
+    }
+}
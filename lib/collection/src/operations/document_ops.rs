@@ -0,0 +1,156 @@
+//! Requests with raw documents instead of vectors
+//!
+//! Documents are converted into vectors by the inference endpoint of the collection, see
+//! [`crate::common::inference`], and then executed as regular upserts and searches.
+
+use std::collections::HashMap;
+
+use schemars::JsonSchema;
+use segment::data_types::vectors::{
+    DenseVector, NamedVector, NamedVectorStruct, Vector, VectorStruct, DEFAULT_VECTOR_NAME,
+};
+use segment::types::{
+    Filter, Payload, PointIdType, ScoreType, SearchParams, WithPayloadInterface, WithVector,
+};
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+use super::point_ops::{Deduplication, PointStruct, PointsList};
+use super::shard_key_selector::ShardKeySelector;
+use super::types::{SearchRequest, SearchRequestInternal};
+
+/// Raw input of the inference endpoint
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq, Eq, Hash)]
+#[serde(untagged)]
+pub enum Document {
+    /// Text to embed
+    Text { text: String },
+    /// Reference to the image to embed: URL or data URI, as accepted by the endpoint
+    Image { image: String },
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone)]
+pub struct PointDocument {
+    /// Point id
+    pub id: PointIdType,
+    /// Document, which is converted into the vector of the point
+    pub document: Document,
+    /// Payload values (optional)
+    pub payload: Option<Payload>,
+}
+
+/// Upsert of points, vectors of which are inferred from documents
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, Validate)]
+pub struct PointsDocuments {
+    #[validate(length(min = 1))]
+    pub points: Vec<PointDocument>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub shard_key: Option<ShardKeySelector>,
+    /// Detect and handle points, which are near-duplicates of the existing points. Default: none
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate]
+    pub deduplication: Option<Deduplication>,
+    /// Time to live of the points in seconds.
+    /// Requires `expiration_field` of the collection, which is set to the time of expiration of the points.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate(range(min = 1))]
+    pub ttl: Option<u64>,
+}
+
+impl PointsDocuments {
+    pub fn documents(&self) -> Vec<Document> {
+        self.points
+            .iter()
+            .map(|point| point.document.clone())
+            .collect()
+    }
+
+    /// Regular points with the inferred vectors, a vector per document
+    pub fn into_points_list(self, vector_name: &str, vectors: Vec<DenseVector>) -> PointsList {
+        let points = self
+            .points
+            .into_iter()
+            .zip(vectors)
+            .map(|(point, vector)| PointStruct {
+                id: point.id,
+                vector: if vector_name == DEFAULT_VECTOR_NAME {
+                    VectorStruct::Single(vector)
+                } else {
+                    VectorStruct::Multi(HashMap::from([(
+                        vector_name.to_string(),
+                        Vector::Dense(vector),
+                    )]))
+                },
+                payload: point.payload,
+                if_version: None,
+            })
+            .collect();
+        PointsList {
+            points,
+            shard_key: self.shard_key,
+            deduplication: self.deduplication,
+            ttl: self.ttl,
+        }
+    }
+}
+
+/// Search of the points, closest to the vector inferred from the document
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone)]
+pub struct SearchDocumentRequest {
+    /// Look for vectors closest to the vector of this document
+    pub document: Document,
+    /// Look only for points which satisfies this conditions
+    #[validate]
+    pub filter: Option<Filter>,
+    /// Additional search params
+    #[validate]
+    pub params: Option<SearchParams>,
+    /// Max number of result to return
+    #[validate(range(min = 1))]
+    pub limit: usize,
+    /// Offset of the first result to return.
+    pub offset: Option<usize>,
+    /// Select which payload to return with the response. Default: None
+    pub with_payload: Option<WithPayloadInterface>,
+    /// Whether to return the point vector with the result?
+    #[serde(default, alias = "with_vectors")]
+    pub with_vector: Option<WithVector>,
+    /// Define a minimal score threshold for the result.
+    pub score_threshold: Option<ScoreType>,
+    /// Specify in which shards to look for the points, if not specified - look in all shards
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub shard_key: Option<ShardKeySelector>,
+    /// Token returned by the update: only search in replicas, which have already applied it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub consistency_token: Option<String>,
+}
+
+impl SearchDocumentRequest {
+    /// Regular search request with the inferred vector
+    pub fn into_search_request(self, vector_name: &str, vector: DenseVector) -> SearchRequest {
+        let vector = if vector_name == DEFAULT_VECTOR_NAME {
+            NamedVectorStruct::Default(vector)
+        } else {
+            NamedVectorStruct::Dense(NamedVector {
+                name: vector_name.to_string(),
+                vector,
+            })
+        };
+        SearchRequest {
+            search_request: SearchRequestInternal {
+                vector,
+                filter: self.filter,
+                params: self.params,
+                limit: self.limit,
+                offset: self.offset,
+                with_payload: self.with_payload,
+                with_vector: self.with_vector,
+                score_threshold: self.score_threshold,
+            },
+            shard_key: self.shard_key,
+            consistency_token: self.consistency_token,
+            rescore: None,
+            distinct_by: None,
+        }
+    }
+}
@@ -0,0 +1,154 @@
+//! Runtime flags of the experimental features
+//!
+//! Each flag is set in the `storage.feature_flags` section of the node configuration, which is the
+//! default for all collections of the node, and could be overridden in the configuration of each
+//! collection. Flags are checked when a collection is created or updated: configuration, which
+//! selects a disabled feature, is rejected. Collections, which already use the feature, keep
+//! working, if it is disabled later.
+//!
+//! Flags are runtime configuration rather than cargo features, so a single build could enable an
+//! experimental feature for some collections or deployments only. A new flag is a variant of
+//! [`FeatureFlag`] with its default, a field of [`FeatureFlags`], and a check of the configuration,
+//! which selects the feature.
+
+use std::fmt;
+
+use merge::Merge;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::config::PayloadStorageBackend;
+use crate::operations::types::{CollectionError, CollectionResult, VectorParams};
+
+/// Experimental feature, which could be enabled or disabled by a flag
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FeatureFlag {
+    AppendLogPayloadStorage,
+    DimensionalityReduction,
+}
+
+impl FeatureFlag {
+    /// Whether the feature is enabled, if the flag is set neither for the node nor for the collection
+    ///
+    /// Features, which were available before the flags, are enabled by default. New experimental
+    /// features should be disabled by default, until they are stable enough.
+    pub fn default_enabled(self) -> bool {
+        match self {
+            FeatureFlag::AppendLogPayloadStorage => true,
+            FeatureFlag::DimensionalityReduction => true,
+        }
+    }
+}
+
+impl fmt::Display for FeatureFlag {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FeatureFlag::AppendLogPayloadStorage => write!(f, "append_log_payload_storage"),
+            FeatureFlag::DimensionalityReduction => write!(f, "dimensionality_reduction"),
+        }
+    }
+}
+
+/// Flags of the experimental features. Unset flags are inherited from the node configuration.
+#[derive(
+    Debug, Default, Deserialize, Serialize, JsonSchema, Clone, Copy, Merge, PartialEq, Eq, Hash,
+)]
+pub struct FeatureFlags {
+    /// Append-log payload storage, selected by `payload_storage_backend: append_log`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub append_log_payload_storage: Option<bool>,
+    /// PCA dimensionality reduction of vectors, selected by `dimensionality_reduction` of the
+    /// vector params
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dimensionality_reduction: Option<bool>,
+}
+
+impl FeatureFlags {
+    fn get(&self, flag: FeatureFlag) -> Option<bool> {
+        match flag {
+            FeatureFlag::AppendLogPayloadStorage => self.append_log_payload_storage,
+            FeatureFlag::DimensionalityReduction => self.dimensionality_reduction,
+        }
+    }
+
+    pub fn is_enabled(&self, flag: FeatureFlag) -> bool {
+        self.get(flag).unwrap_or(flag.default_enabled())
+    }
+
+    /// Flags of the collection, with unset ones taken from the node configuration and the defaults
+    pub fn resolve(collection: Option<&FeatureFlags>, node: &FeatureFlags) -> FeatureFlags {
+        let mut flags = collection.copied().unwrap_or_default();
+        flags.merge(*node);
+        FeatureFlags {
+            append_log_payload_storage: Some(
+                flags.is_enabled(FeatureFlag::AppendLogPayloadStorage),
+            ),
+            dimensionality_reduction: Some(flags.is_enabled(FeatureFlag::DimensionalityReduction)),
+        }
+    }
+
+    pub fn check(&self, flag: FeatureFlag) -> CollectionResult<()> {
+        if self.is_enabled(flag) {
+            Ok(())
+        } else {
+            Err(CollectionError::bad_input(format!(
+                "Experimental feature `{flag}` is disabled, enable it with the `{flag}` feature flag of the collection or of the node",
+            )))
+        }
+    }
+
+    /// Check that the selected payload storage backend is enabled
+    pub fn check_payload_storage_backend(
+        &self,
+        backend: Option<PayloadStorageBackend>,
+    ) -> CollectionResult<()> {
+        match backend {
+            Some(PayloadStorageBackend::AppendLog) => {
+                self.check(FeatureFlag::AppendLogPayloadStorage)
+            }
+            Some(PayloadStorageBackend::Rocksdb) | None => Ok(()),
+        }
+    }
+
+    /// Check that the features, selected by the params of the vectors, are enabled
+    pub fn check_vectors<'a>(
+        &self,
+        mut vectors: impl Iterator<Item = &'a VectorParams>,
+    ) -> CollectionResult<()> {
+        if vectors.any(|params| params.dimensionality_reduction.is_some()) {
+            self.check(FeatureFlag::DimensionalityReduction)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_feature_flags() {
+        let node = FeatureFlags {
+            append_log_payload_storage: Some(false),
+            dimensionality_reduction: None,
+        };
+        let resolved = FeatureFlags::resolve(None, &node);
+        assert!(!resolved.is_enabled(FeatureFlag::AppendLogPayloadStorage));
+        assert!(resolved
+            .check(FeatureFlag::AppendLogPayloadStorage)
+            .is_err());
+        assert_eq!(
+            resolved.dimensionality_reduction,
+            Some(FeatureFlag::DimensionalityReduction.default_enabled()),
+        );
+
+        // Flags of the collection take precedence over the node ones
+        let collection = FeatureFlags {
+            append_log_payload_storage: Some(true),
+            dimensionality_reduction: Some(false),
+        };
+        let resolved = FeatureFlags::resolve(Some(&collection), &node);
+        assert!(resolved.check(FeatureFlag::AppendLogPayloadStorage).is_ok());
+        assert!(!resolved.is_enabled(FeatureFlag::DimensionalityReduction));
+    }
+}
@@ -1,27 +1,39 @@
 pub mod cluster_ops;
 pub mod config_diff;
 pub mod consistency_params;
+pub mod consistency_token;
 pub mod conversions;
+pub mod dimensionality_reduction;
+pub mod document_ops;
+pub mod feature_flags;
 pub mod operation_effect;
 pub mod payload_ops;
 pub mod point_ops;
+pub mod running_operations;
 pub mod shard_key_selector;
 pub mod shard_selector_internal;
 pub mod shared_storage_config;
 pub mod snapshot_ops;
+pub mod strict_mode;
 pub mod types;
+pub mod update_priority;
 mod utils;
 pub mod validation;
 pub mod vector_ops;
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
-use segment::types::{ExtendedPointId, PayloadFieldSchema};
+use segment::types::{
+    is_reserved_payload_key, ExtendedPointId, Payload, PayloadFieldSchema,
+    RESERVED_PAYLOAD_KEY_PREFIX,
+};
 use serde::{Deserialize, Serialize};
 use validator::Validate;
 
 use crate::hash_ring::HashRing;
+use crate::operations::types::{CollectionError, CollectionResult};
 use crate::shards::shard::ShardId;
+use crate::shards::shard_split::resolve_split;
 
 #[derive(Debug, Deserialize, Serialize, Validate, Default, Clone)]
 #[serde(rename_all = "snake_case")]
@@ -47,6 +59,12 @@ pub enum CollectionUpdateOperations {
     VectorOperation(vector_ops::VectorOperations),
     PayloadOperation(payload_ops::PayloadOps),
     FieldIndexOperation(FieldIndexOperations),
+    /// Operations, which are applied in order under a single operation number and WAL record
+    ///
+    /// The batch is replicated and recovered as a whole, other updates of the shard are never
+    /// applied in between its operations. If an operation fails, the following ones are not
+    /// applied, and the points changed by the previous ones are restored.
+    Batch(Vec<CollectionUpdateOperations>),
 }
 
 /// A mapping of operation to shard.
@@ -54,6 +72,8 @@ pub enum CollectionUpdateOperations {
 pub enum OperationToShard<O> {
     ByShard(Vec<(ShardId, O)>),
     ToAll(O),
+    /// Operations for the listed shards, and an operation for all other shards
+    ByShardWithRest(Vec<(ShardId, O)>, O),
 }
 
 impl<O> OperationToShard<O> {
@@ -78,6 +98,15 @@ impl<O> OperationToShard<O> {
                     .collect(),
             ),
             OperationToShard::ToAll(to_all) => OperationToShard::ToAll(f(to_all)),
+            OperationToShard::ByShardWithRest(operation_to_shard, rest) => {
+                OperationToShard::ByShardWithRest(
+                    operation_to_shard
+                        .into_iter()
+                        .map(|(id, operation)| (id, f(operation)))
+                        .collect(),
+                    f(rest),
+                )
+            }
         }
     }
 }
@@ -107,21 +136,68 @@ impl Validate for CollectionUpdateOperations {
             CollectionUpdateOperations::VectorOperation(operation) => operation.validate(),
             CollectionUpdateOperations::PayloadOperation(operation) => operation.validate(),
             CollectionUpdateOperations::FieldIndexOperation(operation) => operation.validate(),
+            CollectionUpdateOperations::Batch(operations) => operations
+                .iter()
+                .try_for_each(|operation| operation.validate()),
+        }
+    }
+}
+
+/// Selects the shard of a point
+///
+/// Points are placed by the hash of their ID, unless their shards are resolved beforehand,
+/// e.g. by the value of the sharding payload key.
+pub struct ShardRouter<'a> {
+    ring: &'a HashRing<ShardId>,
+    split_rings: Option<&'a HashMap<ShardId, HashRing<ShardId>>>,
+    placement: HashMap<ExtendedPointId, ShardId>,
+}
+
+impl<'a> ShardRouter<'a> {
+    /// All points are placed by the hash of their ID
+    pub fn new(ring: &'a HashRing<ShardId>) -> Self {
+        Self {
+            ring,
+            split_rings: None,
+            placement: HashMap::new(),
+        }
+    }
+
+    /// Points from `placement` go to the given shards, the rest is placed by the hash of their ID
+    ///
+    /// Points of the split shards are routed further with `split_rings`.
+    pub fn with_placement(
+        ring: &'a HashRing<ShardId>,
+        split_rings: &'a HashMap<ShardId, HashRing<ShardId>>,
+        placement: HashMap<ExtendedPointId, ShardId>,
+    ) -> Self {
+        Self {
+            ring,
+            split_rings: Some(split_rings),
+            placement,
         }
     }
 }
 
-fn point_to_shard(point_id: ExtendedPointId, ring: &HashRing<ShardId>) -> ShardId {
-    *ring
+fn point_to_shard(point_id: ExtendedPointId, router: &ShardRouter) -> ShardId {
+    if let Some(shard_id) = router.placement.get(&point_id) {
+        return *shard_id;
+    }
+    let shard_id = *router
+        .ring
         .get(&point_id)
-        .expect("Hash ring is guaranteed to be non-empty")
+        .expect("Hash ring is guaranteed to be non-empty");
+    match router.split_rings {
+        Some(split_rings) => resolve_split(split_rings, shard_id, &point_id),
+        None => shard_id,
+    }
 }
 
 /// Split iterator of items that have point ids by shard
 fn split_iter_by_shard<I, F, O>(
     iter: I,
     id_extractor: F,
-    ring: &HashRing<ShardId>,
+    router: &ShardRouter,
 ) -> OperationToShard<Vec<O>>
 where
     I: IntoIterator<Item = O>,
@@ -129,7 +205,7 @@ where
 {
     let mut op_vec_by_shard: HashMap<ShardId, Vec<O>> = HashMap::new();
     for operation in iter {
-        let shard_id = point_to_shard(id_extractor(&operation), ring);
+        let shard_id = point_to_shard(id_extractor(&operation), router);
         op_vec_by_shard.entry(shard_id).or_default().push(operation);
     }
     OperationToShard::by_shard(op_vec_by_shard)
@@ -137,30 +213,87 @@ where
 
 /// Trait for Operation enums to split them by shard.
 pub trait SplitByShard {
-    fn split_by_shard(self, ring: &HashRing<ShardId>) -> OperationToShard<Self>
+    fn split_by_shard(self, router: &ShardRouter) -> OperationToShard<Self>
     where
         Self: Sized;
 }
 
 impl SplitByShard for CollectionUpdateOperations {
-    fn split_by_shard(self, ring: &HashRing<ShardId>) -> OperationToShard<Self> {
+    fn split_by_shard(self, router: &ShardRouter) -> OperationToShard<Self> {
         match self {
             CollectionUpdateOperations::PointOperation(operation) => operation
-                .split_by_shard(ring)
+                .split_by_shard(router)
                 .map(CollectionUpdateOperations::PointOperation),
             CollectionUpdateOperations::VectorOperation(operation) => operation
-                .split_by_shard(ring)
+                .split_by_shard(router)
                 .map(CollectionUpdateOperations::VectorOperation),
             CollectionUpdateOperations::PayloadOperation(operation) => operation
-                .split_by_shard(ring)
+                .split_by_shard(router)
                 .map(CollectionUpdateOperations::PayloadOperation),
             operation @ CollectionUpdateOperations::FieldIndexOperation(_) => {
                 OperationToShard::to_all(operation)
             }
+            CollectionUpdateOperations::Batch(operations) => {
+                split_batch_by_shard(operations, router)
+            }
         }
     }
 }
 
+/// Split batch of operations by shard, keeping the order of the operations within each shard
+///
+/// Every shard gets a single batch, so it is still applied as one operation.
+fn split_batch_by_shard(
+    operations: Vec<CollectionUpdateOperations>,
+    router: &ShardRouter,
+) -> OperationToShard<CollectionUpdateOperations> {
+    let mut by_shard: HashMap<ShardId, Vec<CollectionUpdateOperations>> = HashMap::new();
+    // Parts of the operations, which go to the shards not listed in `by_shard`
+    let mut rest = Vec::new();
+
+    for operation in operations {
+        let (shard_operations, rest_operation) = match operation.split_by_shard(router) {
+            OperationToShard::ByShard(shard_operations) => (shard_operations, None),
+            OperationToShard::ToAll(operation) => (Vec::new(), Some(operation)),
+            OperationToShard::ByShardWithRest(shard_operations, operation) => {
+                (shard_operations, Some(operation))
+            }
+        };
+
+        let mut affected = HashSet::with_capacity(shard_operations.len());
+        for (shard_id, operation) in shard_operations {
+            // Shard, which is met for the first time, gets all previous operations as the rest
+            by_shard
+                .entry(shard_id)
+                .or_insert_with(|| rest.clone())
+                .push(operation);
+            affected.insert(shard_id);
+        }
+
+        if let Some(rest_operation) = rest_operation {
+            for (shard_id, operations) in by_shard.iter_mut() {
+                if !affected.contains(shard_id) {
+                    operations.push(rest_operation.clone());
+                }
+            }
+            rest.push(rest_operation);
+        }
+    }
+
+    let by_shard = by_shard
+        .into_iter()
+        .map(|(shard_id, operations)| (shard_id, CollectionUpdateOperations::Batch(operations)));
+
+    match (by_shard.len(), rest.is_empty()) {
+        (_, true) => OperationToShard::by_shard(by_shard),
+        (0, false) => OperationToShard::to_all(CollectionUpdateOperations::Batch(rest)),
+        (_, false) => OperationToShard::ByShardWithRest(
+            by_shard.collect(),
+            CollectionUpdateOperations::Batch(rest),
+        ),
+    }
+}
+
 impl CollectionUpdateOperations {
     pub fn is_write_operation(&self) -> bool {
         match self {
@@ -174,8 +307,77 @@ impl CollectionUpdateOperations {
             CollectionUpdateOperations::FieldIndexOperation(operation) => {
                 operation.is_write_operation()
             }
+            CollectionUpdateOperations::Batch(operations) => operations
+                .iter()
+                .any(CollectionUpdateOperations::is_write_operation),
         }
     }
+
+    /// Rejects updates from clients, which write, delete or index reserved payload fields,
+    /// like the soft delete marker
+    ///
+    /// Updates from peers are not checked, they copy the stored values of the reserved fields.
+    pub fn check_reserved_payload(&self) -> CollectionResult<()> {
+        let has_reserved_key =
+            |payload: &Payload| payload.0.keys().any(|key| is_reserved_payload_key(key));
+
+        let uses_reserved_key = match self {
+            CollectionUpdateOperations::PointOperation(operation) => match operation {
+                point_ops::PointOperations::UpsertPoints(insert) => match insert {
+                    point_ops::PointInsertOperationsInternal::PointsBatch(batch) => batch
+                        .payloads
+                        .iter()
+                        .flatten()
+                        .flatten()
+                        .any(has_reserved_key),
+                    point_ops::PointInsertOperationsInternal::PointsList(points) => points
+                        .iter()
+                        .filter_map(|point| point.payload.as_ref())
+                        .any(has_reserved_key),
+                },
+                point_ops::PointOperations::SyncPoints(sync) => sync
+                    .points
+                    .iter()
+                    .filter_map(|point| point.payload.as_ref())
+                    .any(has_reserved_key),
+                point_ops::PointOperations::DeletePoints { .. }
+                | point_ops::PointOperations::DeletePointsByFilter(_) => false,
+            },
+            CollectionUpdateOperations::VectorOperation(_) => false,
+            CollectionUpdateOperations::PayloadOperation(operation) => match operation {
+                payload_ops::PayloadOps::SetPayload(set_payload)
+                | payload_ops::PayloadOps::OverwritePayload(set_payload) => {
+                    has_reserved_key(&set_payload.payload)
+                }
+                payload_ops::PayloadOps::DeletePayload(delete_payload) => delete_payload
+                    .keys
+                    .iter()
+                    .any(|key| is_reserved_payload_key(key)),
+                payload_ops::PayloadOps::ClearPayload { .. }
+                | payload_ops::PayloadOps::ClearPayloadByFilter(_) => false,
+            },
+            CollectionUpdateOperations::FieldIndexOperation(operation) => match operation {
+                FieldIndexOperations::CreateIndex(create_index) => {
+                    is_reserved_payload_key(&create_index.field_name)
+                }
+                FieldIndexOperations::DeleteIndex(field_name) => {
+                    is_reserved_payload_key(field_name)
+                }
+            },
+            CollectionUpdateOperations::Batch(operations) => {
+                return operations
+                    .iter()
+                    .try_for_each(CollectionUpdateOperations::check_reserved_payload);
+            }
+        };
+
+        if uses_reserved_key {
+            return Err(CollectionError::bad_request(format!(
+                "Payload fields starting with `{RESERVED_PAYLOAD_KEY_PREFIX}` are reserved",
+            )));
+        }
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -194,4 +396,89 @@ mod tests {
         let json = serde_json::to_string_pretty(&op).unwrap();
         println!("{json}")
     }
+
+    #[test]
+    fn test_split_batch_by_shard() {
+        let mut ring = HashRing::fair(100);
+        for shard_id in 0..3 {
+            ring.add(shard_id);
+        }
+        let router = ShardRouter::new(&ring);
+
+        let first: ExtendedPointId = 1.into();
+        let first_shard = *ring.get(&first).unwrap();
+        let second = (2..)
+            .map(ExtendedPointId::from)
+            .find(|id| *ring.get(id).unwrap() != first_shard)
+            .unwrap();
+        let second_shard = *ring.get(&second).unwrap();
+
+        let delete = |id| {
+            CollectionUpdateOperations::PointOperation(point_ops::PointOperations::DeletePoints {
+                ids: vec![id],
+            })
+        };
+        let clear = CollectionUpdateOperations::PayloadOperation(
+            payload_ops::PayloadOps::ClearPayloadByFilter(Default::default()),
+        );
+        let batch = CollectionUpdateOperations::Batch(vec![delete(first), clear, delete(second)]);
+
+        let OperationToShard::ByShardWithRest(by_shard, rest) = batch.split_by_shard(&router)
+        else {
+            panic!("Batch must be split by shard with the rest");
+        };
+
+        let batch_len = |operation: &CollectionUpdateOperations| match operation {
+            CollectionUpdateOperations::Batch(operations) => operations.len(),
+            _ => panic!("Batch is expected"),
+        };
+        let by_shard: HashMap<_, _> = by_shard.into_iter().collect();
+        assert_eq!(by_shard.len(), 2);
+        // Deletion of the own point and clearing of the payload
+        assert_eq!(batch_len(&by_shard[&first_shard]), 2);
+        assert_eq!(batch_len(&by_shard[&second_shard]), 2);
+        // Other shards only clear the payload
+        assert_eq!(batch_len(&rest), 1);
+    }
+
+    #[test]
+    fn test_check_reserved_payload() {
+        let set_payload = |payload: serde_json::Value| {
+            CollectionUpdateOperations::PayloadOperation(payload_ops::PayloadOps::SetPayload(
+                payload_ops::SetPayloadOp {
+                    payload: payload.into(),
+                    points: Some(vec![1.into()]),
+                    filter: None,
+                    if_version: None,
+                },
+            ))
+        };
+        let delete_payload = |key: &str| {
+            CollectionUpdateOperations::PayloadOperation(payload_ops::PayloadOps::DeletePayload(
+                payload_ops::DeletePayloadOp {
+                    keys: vec![key.to_string()],
+                    points: Some(vec![1.into()]),
+                    filter: None,
+                },
+            ))
+        };
+
+        assert!(set_payload(serde_json::json!({ "version": 5 }))
+            .check_reserved_payload()
+            .is_ok());
+        assert!(delete_payload("a.b").check_reserved_payload().is_ok());
+        assert!(delete_payload("__qdrant_deleted_at.a")
+            .check_reserved_payload()
+            .is_err());
+        assert!(set_payload(serde_json::json!({ "__qdrant_deleted_at": 5 }))
+            .check_reserved_payload()
+            .is_err());
+
+        // Operations of a batch are checked one by one
+        let batch = CollectionUpdateOperations::Batch(vec![
+            delete_payload("a"),
+            set_payload(serde_json::json!({ "__qdrant_deleted_at": 5 })),
+        ]);
+        assert!(batch.check_reserved_payload().is_err());
+    }
 }
@@ -1,4 +1,6 @@
-use segment::types::{Filter, PointIdType};
+use std::collections::HashSet;
+
+use segment::types::{Condition, Filter, HasIdCondition, PointIdType};
 
 use super::vector_ops;
 use crate::operations::payload_ops::PayloadOps;
@@ -38,6 +40,35 @@ impl EstimateOperationEffectArea for CollectionUpdateOperations {
                 payload_operation.estimate_effect_area()
             }
             CollectionUpdateOperations::FieldIndexOperation(_) => OperationEffectArea::Empty,
+            CollectionUpdateOperations::Batch(operations) => {
+                let mut ids = Vec::new();
+                let mut filters = Vec::new();
+                for operation in operations {
+                    match operation.estimate_effect_area() {
+                        OperationEffectArea::Empty => {}
+                        OperationEffectArea::Points(points) => ids.extend(points),
+                        OperationEffectArea::Filter(filter) => filters.push(filter),
+                    }
+                }
+                if filters.is_empty() {
+                    return if ids.is_empty() {
+                        OperationEffectArea::Empty
+                    } else {
+                        OperationEffectArea::Points(ids)
+                    };
+                }
+                // Points, affected by any of the operations
+                let mut should: Vec<_> = filters.into_iter().map(Condition::Filter).collect();
+                if !ids.is_empty() {
+                    let ids: HashSet<_> = ids.into_iter().collect();
+                    should.push(Condition::HasId(HasIdCondition::from(ids)));
+                }
+                OperationEffectArea::Filter(Filter {
+                    should: Some(should),
+                    must: None,
+                    must_not: None,
+                })
+            }
         }
     }
 }
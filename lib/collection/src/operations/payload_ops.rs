@@ -4,10 +4,8 @@ use serde;
 use serde::{Deserialize, Serialize};
 use validator::Validate;
 
-use super::{split_iter_by_shard, OperationToShard, SplitByShard};
-use crate::hash_ring::HashRing;
+use super::{split_iter_by_shard, OperationToShard, ShardRouter, SplitByShard};
 use crate::operations::shard_key_selector::ShardKeySelector;
-use crate::shards::shard::ShardId;
 
 /// This data structure is used in API interface and applied across multiple shards
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone)]
@@ -20,6 +18,9 @@ pub struct SetPayload {
     pub filter: Option<Filter>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub shard_key: Option<ShardKeySelector>,
+    /// Only update points, which current version is equal to this one. Otherwise, the whole operation is rejected
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub if_version: Option<u64>,
 }
 
 /// This data structure is used inside shard operations queue
@@ -34,6 +35,9 @@ pub struct SetPayloadOp {
     pub points: Option<Vec<PointIdType>>,
     /// Assigns payload to each point that satisfy this filter condition
     pub filter: Option<Filter>,
+    /// Only update points, which current version is equal to this one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub if_version: Option<u64>,
 }
 
 #[derive(Deserialize)]
@@ -42,6 +46,8 @@ struct SetPayloadShadow {
     pub points: Option<Vec<PointIdType>>,
     pub filter: Option<Filter>,
     pub shard_key: Option<ShardKeySelector>,
+    #[serde(default)]
+    pub if_version: Option<u64>,
 }
 
 pub struct PointsSelectorValidationError;
@@ -65,6 +71,7 @@ impl TryFrom<SetPayloadShadow> for SetPayload {
                 points: value.points,
                 filter: value.filter,
                 shard_key: value.shard_key,
+                if_version: value.if_version,
             })
         } else {
             Err(PointsSelectorValidationError)
@@ -167,29 +174,29 @@ impl Validate for PayloadOps {
 }
 
 impl SplitByShard for PayloadOps {
-    fn split_by_shard(self, ring: &HashRing<ShardId>) -> OperationToShard<Self> {
+    fn split_by_shard(self, router: &ShardRouter) -> OperationToShard<Self> {
         match self {
             PayloadOps::SetPayload(operation) => {
-                operation.split_by_shard(ring).map(PayloadOps::SetPayload)
+                operation.split_by_shard(router).map(PayloadOps::SetPayload)
             }
             PayloadOps::DeletePayload(operation) => operation
-                .split_by_shard(ring)
+                .split_by_shard(router)
                 .map(PayloadOps::DeletePayload),
-            PayloadOps::ClearPayload { points } => split_iter_by_shard(points, |id| *id, ring)
+            PayloadOps::ClearPayload { points } => split_iter_by_shard(points, |id| *id, router)
                 .map(|points| PayloadOps::ClearPayload { points }),
             operation @ PayloadOps::ClearPayloadByFilter(_) => OperationToShard::to_all(operation),
             PayloadOps::OverwritePayload(operation) => operation
-                .split_by_shard(ring)
+                .split_by_shard(router)
                 .map(PayloadOps::OverwritePayload),
         }
     }
 }
 
 impl SplitByShard for DeletePayloadOp {
-    fn split_by_shard(self, ring: &HashRing<ShardId>) -> OperationToShard<Self> {
+    fn split_by_shard(self, router: &ShardRouter) -> OperationToShard<Self> {
         match (&self.points, &self.filter) {
             (Some(_), _) => {
-                split_iter_by_shard(self.points.unwrap(), |id| *id, ring).map(|points| {
+                split_iter_by_shard(self.points.unwrap(), |id| *id, router).map(|points| {
                     DeletePayloadOp {
                         points: Some(points),
                         keys: self.keys.clone(),
@@ -204,14 +211,15 @@ impl SplitByShard for DeletePayloadOp {
 }
 
 impl SplitByShard for SetPayloadOp {
-    fn split_by_shard(self, ring: &HashRing<ShardId>) -> OperationToShard<Self> {
+    fn split_by_shard(self, router: &ShardRouter) -> OperationToShard<Self> {
         match (&self.points, &self.filter) {
             (Some(_), _) => {
-                split_iter_by_shard(self.points.unwrap(), |id| *id, ring).map(|points| {
+                split_iter_by_shard(self.points.unwrap(), |id| *id, router).map(|points| {
                     SetPayloadOp {
                         points: Some(points),
                         payload: self.payload.clone(),
                         filter: self.filter.clone(),
+                        if_version: self.if_version,
                     }
                 })
             }
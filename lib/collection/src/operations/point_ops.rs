@@ -6,12 +6,11 @@ use schemars::JsonSchema;
 use segment::common::utils::transpose_map_into_named_vector;
 use segment::data_types::named_vectors::NamedVectors;
 use segment::data_types::vectors::{BatchVectorStruct, Vector, VectorStruct, DEFAULT_VECTOR_NAME};
-use segment::types::{Filter, Payload, PointIdType};
+use segment::types::{Filter, Payload, PointIdType, ScoreType};
 use serde::{Deserialize, Serialize};
 use validator::Validate;
 
-use super::{point_to_shard, split_iter_by_shard, OperationToShard, SplitByShard};
-use crate::hash_ring::HashRing;
+use super::{point_to_shard, split_iter_by_shard, OperationToShard, ShardRouter, SplitByShard};
 use crate::operations::shard_key_selector::ShardKeySelector;
 use crate::operations::types::Record;
 use crate::shards::shard::ShardId;
@@ -24,7 +23,7 @@ use crate::shards::shard::ShardId;
 ///
 /// * `strong` - Write operations go through the permanent leader, consistent, but may be unavailable if leader is down
 ///
-#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, Copy, Default)]
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, Copy, Default, PartialEq, Eq, Hash)]
 #[serde(rename_all = "snake_case")]
 pub enum WriteOrdering {
     #[default]
@@ -44,6 +43,10 @@ pub struct PointStruct {
     pub vector: VectorStruct,
     /// Payload values (optional)
     pub payload: Option<Payload>,
+    /// Only update the point, if its current version is equal to this one. Use 0 to insert the
+    /// point only if it doesn't exist yet. Otherwise, the whole operation is rejected
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub if_version: Option<u64>,
 }
 
 /// Warn: panics if the vector is empty
@@ -53,9 +56,11 @@ impl TryFrom<Record> for PointStruct {
     fn try_from(record: Record) -> Result<Self, Self::Error> {
         let Record {
             id,
+            version: _,
             payload,
             vector,
             shard_key: _,
+            order_value: _,
         } = record;
 
         if vector.is_none() {
@@ -66,6 +71,7 @@ impl TryFrom<Record> for PointStruct {
             id,
             payload,
             vector: vector.unwrap(),
+            if_version: None,
         })
     }
 }
@@ -94,6 +100,38 @@ impl Batch {
             payloads: None,
         }
     }
+
+    /// Split the batch into separate points
+    pub fn into_points(self) -> Vec<PointStruct> {
+        let Batch {
+            ids,
+            vectors,
+            payloads,
+        } = self;
+        let vectors: Vec<VectorStruct> = match vectors {
+            BatchVectorStruct::Single(vectors) => {
+                vectors.into_iter().map(VectorStruct::Single).collect()
+            }
+            BatchVectorStruct::Multi(named_vectors) if named_vectors.is_empty() => {
+                vec![VectorStruct::Multi(HashMap::new()); ids.len()]
+            }
+            BatchVectorStruct::Multi(named_vectors) => {
+                transpose_map_into_named_vector(named_vectors)
+                    .into_iter()
+                    .map(|vectors| VectorStruct::Multi(vectors.into_owned_map()))
+                    .collect()
+            }
+        };
+        let payloads = payloads.unwrap_or_else(|| vec![None; ids.len()]);
+        izip!(ids, vectors, payloads)
+            .map(|(id, vector, payload)| PointStruct {
+                id,
+                vector,
+                payload,
+                if_version: None,
+            })
+            .collect()
+    }
 }
 
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone)]
@@ -148,12 +186,49 @@ pub struct PointSyncOperation {
     pub points: Vec<PointStruct>,
 }
 
+/// What to do with an inserted point, which is a near-duplicate of an existing point
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum DuplicateAction {
+    /// Don't insert the point
+    Skip,
+    /// Insert the point under the ID of the existing point, replacing it
+    Overwrite,
+    /// Insert the point with the ID of the existing point in the `duplicate_of` payload field
+    Tag,
+}
+
+/// Detection of inserted points, which are near-duplicates of the points already in the collection
+///
+/// Each inserted point is compared with its nearest existing point only, using the approximate
+/// search. Inserted points are not compared with each other.
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone)]
+pub struct Deduplication {
+    /// Existing point is a duplicate, if its score is better than this threshold.
+    /// Same semantics as `score_threshold` of the search, e.g. for `Cosine` distance it is the
+    /// minimal similarity
+    pub threshold: ScoreType,
+    /// Name of the vector to compare. Default: the default vector
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub using: Option<String>,
+    pub action: DuplicateAction,
+}
+
 #[derive(Debug, Deserialize, Serialize, Clone, Validate, JsonSchema)]
 pub struct PointsBatch {
     #[validate]
     pub batch: Batch,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub shard_key: Option<ShardKeySelector>,
+    /// Detect and handle points, which are near-duplicates of the existing points. Default: none
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate]
+    pub deduplication: Option<Deduplication>,
+    /// Time to live of the points in seconds. Points expire after this time and are deleted in background.
+    /// Requires `expiration_field` of the collection, which is set to the time of expiration of the points.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate(range(min = 1))]
+    pub ttl: Option<u64>,
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone, JsonSchema, Validate)]
@@ -162,6 +237,15 @@ pub struct PointsList {
     pub points: Vec<PointStruct>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub shard_key: Option<ShardKeySelector>,
+    /// Detect and handle points, which are near-duplicates of the existing points. Default: none
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate]
+    pub deduplication: Option<Deduplication>,
+    /// Time to live of the points in seconds. Points expire after this time and are deleted in background.
+    /// Requires `expiration_field` of the collection, which is set to the time of expiration of the points.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate(range(min = 1))]
+    pub ttl: Option<u64>,
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone, JsonSchema)]
@@ -189,6 +273,20 @@ impl PointInsertOperations {
             PointInsertOperations::PointsList(list) => (list.shard_key, list.points.into()),
         }
     }
+
+    pub fn deduplication(&self) -> Option<&Deduplication> {
+        match self {
+            PointInsertOperations::PointsBatch(batch) => batch.deduplication.as_ref(),
+            PointInsertOperations::PointsList(list) => list.deduplication.as_ref(),
+        }
+    }
+
+    pub fn ttl(&self) -> Option<u64> {
+        match self {
+            PointInsertOperations::PointsBatch(batch) => batch.ttl,
+            PointInsertOperations::PointsList(list) => list.ttl,
+        }
+    }
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone)]
@@ -211,6 +309,28 @@ impl Validate for PointInsertOperationsInternal {
     }
 }
 
+impl PointInsertOperationsInternal {
+    /// Set the time of expiration of the points, unix timestamp in seconds, into their payload
+    pub fn set_expiration(&mut self, expiration_field: &str, expire_at: u64) {
+        let set_expiration = |payload: &mut Option<Payload>| {
+            payload
+                .get_or_insert_with(Payload::default)
+                .0
+                .insert(expiration_field.to_string(), expire_at.into());
+        };
+        match self {
+            PointInsertOperationsInternal::PointsBatch(batch) => batch
+                .payloads
+                .get_or_insert_with(|| vec![None; batch.ids.len()])
+                .iter_mut()
+                .for_each(set_expiration),
+            PointInsertOperationsInternal::PointsList(points) => points
+                .iter_mut()
+                .for_each(|point| set_expiration(&mut point.payload)),
+        }
+    }
+}
+
 impl Validate for Batch {
     fn validate(&self) -> Result<(), validator::ValidationErrors> {
         let batch = self;
@@ -263,13 +383,13 @@ impl Validate for Batch {
 }
 
 impl SplitByShard for PointInsertOperationsInternal {
-    fn split_by_shard(self, ring: &HashRing<ShardId>) -> OperationToShard<Self> {
+    fn split_by_shard(self, router: &ShardRouter) -> OperationToShard<Self> {
         match self {
             PointInsertOperationsInternal::PointsBatch(batch) => batch
-                .split_by_shard(ring)
+                .split_by_shard(router)
                 .map(PointInsertOperationsInternal::PointsBatch),
             PointInsertOperationsInternal::PointsList(list) => list
-                .split_by_shard(ring)
+                .split_by_shard(router)
                 .map(PointInsertOperationsInternal::PointsList),
         }
     }
@@ -280,6 +400,8 @@ impl From<Batch> for PointInsertOperations {
         PointInsertOperations::PointsBatch(PointsBatch {
             batch,
             shard_key: None,
+            deduplication: None,
+            ttl: None,
         })
     }
 }
@@ -289,6 +411,8 @@ impl From<Vec<PointStruct>> for PointInsertOperations {
         PointInsertOperations::PointsList(PointsList {
             points,
             shard_key: None,
+            deduplication: None,
+            ttl: None,
         })
     }
 }
@@ -341,7 +465,7 @@ impl Validate for PointOperations {
 }
 
 impl SplitByShard for Batch {
-    fn split_by_shard(self, ring: &HashRing<ShardId>) -> OperationToShard<Self> {
+    fn split_by_shard(self, router: &ShardRouter) -> OperationToShard<Self> {
         let batch = self;
         let mut batch_by_shard: HashMap<ShardId, Batch> = HashMap::new();
         let Batch {
@@ -354,7 +478,7 @@ impl SplitByShard for Batch {
             match vectors {
                 BatchVectorStruct::Single(vectors) => {
                     for (id, vector, payload) in izip!(ids, vectors, payloads) {
-                        let shard_id = point_to_shard(id, ring);
+                        let shard_id = point_to_shard(id, router);
                         let batch = batch_by_shard.entry(shard_id).or_insert_with(|| Batch {
                             ids: vec![],
                             vectors: BatchVectorStruct::Single(vec![]),
@@ -375,7 +499,7 @@ impl SplitByShard for Batch {
                         vec![NamedVectors::default(); ids.len()]
                     };
                     for (id, named_vector, payload) in izip!(ids, named_vectors_list, payloads) {
-                        let shard_id = point_to_shard(id, ring);
+                        let shard_id = point_to_shard(id, router);
                         let batch = batch_by_shard.entry(shard_id).or_insert_with(|| Batch {
                             ids: vec![],
                             vectors: BatchVectorStruct::Multi(HashMap::new()),
@@ -400,7 +524,7 @@ impl SplitByShard for Batch {
             match vectors {
                 BatchVectorStruct::Single(vectors) => {
                     for (id, vector) in izip!(ids, vectors) {
-                        let shard_id = point_to_shard(id, ring);
+                        let shard_id = point_to_shard(id, router);
                         let batch = batch_by_shard.entry(shard_id).or_insert_with(|| Batch {
                             ids: vec![],
                             vectors: BatchVectorStruct::Single(vec![]),
@@ -420,7 +544,7 @@ impl SplitByShard for Batch {
                         vec![NamedVectors::default(); ids.len()]
                     };
                     for (id, named_vector) in izip!(ids, named_vectors_list) {
-                        let shard_id = point_to_shard(id, ring);
+                        let shard_id = point_to_shard(id, router);
                         let batch = batch_by_shard.entry(shard_id).or_insert_with(|| Batch {
                             ids: vec![],
                             vectors: BatchVectorStruct::Multi(HashMap::new()),
@@ -447,18 +571,18 @@ impl SplitByShard for Batch {
 }
 
 impl SplitByShard for Vec<PointStruct> {
-    fn split_by_shard(self, ring: &HashRing<ShardId>) -> OperationToShard<Self> {
-        split_iter_by_shard(self, |point| point.id, ring)
+    fn split_by_shard(self, router: &ShardRouter) -> OperationToShard<Self> {
+        split_iter_by_shard(self, |point| point.id, router)
     }
 }
 
 impl SplitByShard for PointOperations {
-    fn split_by_shard(self, ring: &HashRing<ShardId>) -> OperationToShard<Self> {
+    fn split_by_shard(self, router: &ShardRouter) -> OperationToShard<Self> {
         match self {
             PointOperations::UpsertPoints(upsert_points) => upsert_points
-                .split_by_shard(ring)
+                .split_by_shard(router)
                 .map(PointOperations::UpsertPoints),
-            PointOperations::DeletePoints { ids } => split_iter_by_shard(ids, |id| *id, ring)
+            PointOperations::DeletePoints { ids } => split_iter_by_shard(ids, |id| *id, router)
                 .map(|ids| PointOperations::DeletePoints { ids }),
             by_filter @ PointOperations::DeletePointsByFilter(_) => {
                 OperationToShard::to_all(by_filter)
@@ -532,4 +656,34 @@ mod tests {
         .into();
         assert!(batch.validate().is_err());
     }
+
+    #[test]
+    fn test_set_expiration() {
+        let mut batch: PointInsertOperationsInternal = Batch {
+            ids: vec![PointIdType::NumId(0), PointIdType::NumId(1)],
+            vectors: vec![vec![0.1], vec![0.2]].into(),
+            payloads: None,
+        }
+        .into();
+        batch.set_expiration("expire_at", 100);
+        let PointInsertOperationsInternal::PointsBatch(batch) = batch else {
+            unreachable!()
+        };
+        let expected: Payload = serde_json::json!({ "expire_at": 100 }).into();
+        assert_eq!(batch.payloads, Some(vec![Some(expected.clone()); 2]));
+
+        let mut points: PointInsertOperationsInternal = vec![PointStruct {
+            id: PointIdType::NumId(0),
+            vector: vec![0.1].into(),
+            payload: Some(serde_json::json!({ "expire_at": 10, "a": 1 }).into()),
+            if_version: None,
+        }]
+        .into();
+        points.set_expiration("expire_at", 100);
+        let PointInsertOperationsInternal::PointsList(points) = points else {
+            unreachable!()
+        };
+        let expected: Payload = serde_json::json!({ "expire_at": 100, "a": 1 }).into();
+        assert_eq!(points[0].payload, Some(expected));
+    }
 }
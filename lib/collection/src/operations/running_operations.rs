@@ -0,0 +1,189 @@
+//! Long running operations of the collections, like optimizations and shard transfers
+//!
+//! Operations get ids, unique within the process, so they could be looked up and cancelled.
+//! Optimizations and shard transfers are tracked by the components running them, other operations
+//! are registered in the [`OperationsRegistry`] of the collection while they are running.
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+use api::grpc::conversions::date_time_to_proto;
+use chrono::{DateTime, Utc};
+use parking_lot::Mutex;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::shards::shard::ShardId;
+
+static NEXT_OPERATION_ID: AtomicU64 = AtomicU64::new(1);
+
+/// Id of a new long running operation
+pub fn next_operation_id() -> u64 {
+    NEXT_OPERATION_ID.fetch_add(1, Ordering::Relaxed)
+}
+
+/// * `optimization` - optimization of the segments, like merging or vacuuming
+///
+/// * `index_build` - building of the vector and payload indexes of the segments
+///
+/// * `shard_transfer` - transfer of a shard to another peer
+///
+/// * `snapshot_creation` - creation of a collection snapshot
+///
+/// * `update_by_filter` - batched update of the points matching a filter
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum RunningOperationKind {
+    Optimization,
+    IndexBuild,
+    ShardTransfer,
+    SnapshotCreation,
+    UpdateByFilter,
+}
+
+/// Progress of the operation, units depend on the kind of the operation
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub struct OperationProgress {
+    /// Amount of work done, e.g. the number of transferred points
+    pub done: u64,
+    /// Total amount of work, if known
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub total: Option<u64>,
+}
+
+/// Long running operation of a collection on this node
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub struct RunningOperation {
+    /// Id of the operation, unique within the node
+    pub id: u64,
+    pub kind: RunningOperationKind,
+    /// Collection of the operation
+    pub collection_name: String,
+    /// Shard of the operation, if it is shard specific
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub shard_id: Option<ShardId>,
+    /// Human readable description of the operation
+    pub description: String,
+    /// Time the operation started at
+    pub started_at: DateTime<Utc>,
+    /// Progress of the operation, if it is tracked
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub progress: Option<OperationProgress>,
+    /// If the operation could be cancelled
+    pub cancellable: bool,
+}
+
+impl From<RunningOperationKind> for api::grpc::qdrant::RunningOperationKind {
+    fn from(value: RunningOperationKind) -> Self {
+        match value {
+            RunningOperationKind::Optimization => Self::Optimization,
+            RunningOperationKind::IndexBuild => Self::IndexBuild,
+            RunningOperationKind::ShardTransfer => Self::ShardTransfer,
+            RunningOperationKind::SnapshotCreation => Self::SnapshotCreation,
+            RunningOperationKind::UpdateByFilter => Self::UpdateByFilter,
+        }
+    }
+}
+
+impl From<OperationProgress> for api::grpc::qdrant::OperationProgress {
+    fn from(value: OperationProgress) -> Self {
+        Self {
+            done: value.done,
+            total: value.total,
+        }
+    }
+}
+
+impl From<RunningOperation> for api::grpc::qdrant::RunningOperation {
+    fn from(value: RunningOperation) -> Self {
+        Self {
+            id: value.id,
+            kind: api::grpc::qdrant::RunningOperationKind::from(value.kind) as i32,
+            collection_name: value.collection_name,
+            shard_id: value.shard_id,
+            description: value.description,
+            started_at: Some(date_time_to_proto(value.started_at.naive_utc())),
+            progress: value.progress.map(From::from),
+            cancellable: value.cancellable,
+        }
+    }
+}
+
+/// Registry of the running operations, which are not tracked by the components running them
+#[derive(Debug, Default)]
+pub struct OperationsRegistry {
+    operations: Mutex<HashMap<u64, RunningOperation>>,
+}
+
+impl OperationsRegistry {
+    /// Register the operation, it is listed until the returned guard is dropped
+    pub fn register(self: &Arc<Self>, operation: RunningOperation) -> RunningOperationGuard {
+        let id = operation.id;
+        self.operations.lock().insert(id, operation);
+        RunningOperationGuard {
+            registry: self.clone(),
+            id,
+        }
+    }
+
+    pub fn list(&self) -> Vec<RunningOperation> {
+        self.operations.lock().values().cloned().collect()
+    }
+}
+
+/// Unregisters the operation from the registry on drop
+pub struct RunningOperationGuard {
+    registry: Arc<OperationsRegistry>,
+    id: u64,
+}
+
+impl RunningOperationGuard {
+    pub fn set_progress(&self, progress: OperationProgress) {
+        if let Some(operation) = self.registry.operations.lock().get_mut(&self.id) {
+            operation.progress = Some(progress);
+        }
+    }
+}
+
+impl Drop for RunningOperationGuard {
+    fn drop(&mut self) {
+        self.registry.operations.lock().remove(&self.id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_operations_registry() {
+        let registry = Arc::new(OperationsRegistry::default());
+        let operation = RunningOperation {
+            id: next_operation_id(),
+            kind: RunningOperationKind::SnapshotCreation,
+            collection_name: "test".to_string(),
+            shard_id: None,
+            description: "Creating snapshot".to_string(),
+            started_at: Utc::now(),
+            progress: None,
+            cancellable: false,
+        };
+
+        let guard = registry.register(operation.clone());
+        assert_eq!(registry.list(), vec![operation.clone()]);
+        assert_ne!(next_operation_id(), operation.id);
+
+        let progress = OperationProgress {
+            done: 1,
+            total: Some(2),
+        };
+        guard.set_progress(progress.clone());
+        assert_eq!(registry.list()[0].progress, Some(progress));
+
+        drop(guard);
+        assert!(registry.list().is_empty());
+    }
+}
@@ -1,7 +1,17 @@
 use std::num::NonZeroUsize;
+use std::sync::Arc;
 use std::time::Duration;
 
-use crate::operations::types::NodeType;
+use parking_lot::RwLock;
+
+use crate::common::io_budget::IoBudget;
+use crate::common::search_admission::SearchAdmission;
+use crate::common::snapshot_storage::SnapshotStorage;
+use crate::events::EventNotifier;
+use crate::operations::feature_flags::FeatureFlags;
+use crate::operations::types::{NodeType, ReadLoadBalancing};
+use crate::shards::scrubber::ScrubberConfig;
+use crate::shards::transfer::throttle::ShardTransferConfig;
 
 /// Default timeout for search requests.
 /// In cluster mode, this should be aligned with collection timeout.
@@ -15,12 +25,27 @@ const DEFAULT_UPDATE_QUEUE_SIZE_LISTENER: usize = 10_000;
 #[derive(Clone, Debug)]
 pub struct SharedStorageConfig {
     pub update_queue_size: usize,
-    pub node_type: NodeType,
+    pub node_type: NodeRole,
     pub handle_collection_load_errors: bool,
     pub recovery_mode: Option<String>,
     pub search_timeout: Duration,
     pub update_concurrency: Option<NonZeroUsize>,
     pub is_distributed: bool,
+    pub read_load_balancing: ReadLoadBalancing,
+    /// Where collection snapshots are stored
+    pub snapshot_storage: SnapshotStorage,
+    /// Disk throughput budget of low priority tasks, shared between all collections
+    pub low_priority_io_budget: IoBudget,
+    /// Limit of concurrent searches, shared between all collections
+    pub search_admission: SearchAdmission,
+    /// Throughput limits of each outgoing shard transfer
+    pub shard_transfer: ShardTransferConfig,
+    /// Periodic verification of the checksums of the storage
+    pub scrubber: ScrubberConfig,
+    /// Flags of the experimental features, default for all collections of the node
+    pub feature_flags: FeatureFlags,
+    /// Sender of the lifecycle events of all collections
+    pub events: EventNotifier,
 }
 
 impl Default for SharedStorageConfig {
@@ -33,11 +58,20 @@ impl Default for SharedStorageConfig {
             search_timeout: DEFAULT_SEARCH_TIMEOUT,
             update_concurrency: None,
             is_distributed: false,
+            read_load_balancing: Default::default(),
+            snapshot_storage: Default::default(),
+            low_priority_io_budget: Default::default(),
+            search_admission: Default::default(),
+            shard_transfer: Default::default(),
+            scrubber: Default::default(),
+            feature_flags: Default::default(),
+            events: Default::default(),
         }
     }
 }
 
 impl SharedStorageConfig {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         update_queue_size: Option<usize>,
         node_type: NodeType,
@@ -46,6 +80,14 @@ impl SharedStorageConfig {
         search_timeout: Option<Duration>,
         update_concurrency: Option<NonZeroUsize>,
         is_distributed: bool,
+        read_load_balancing: ReadLoadBalancing,
+        snapshot_storage: SnapshotStorage,
+        low_priority_io_budget: IoBudget,
+        search_admission: SearchAdmission,
+        shard_transfer: ShardTransferConfig,
+        scrubber: ScrubberConfig,
+        feature_flags: FeatureFlags,
+        events: EventNotifier,
     ) -> Self {
         let update_queue_size = update_queue_size.unwrap_or(match node_type {
             NodeType::Normal => DEFAULT_UPDATE_QUEUE_SIZE,
@@ -53,12 +95,42 @@ impl SharedStorageConfig {
         });
         Self {
             update_queue_size,
-            node_type,
+            node_type: node_type.into(),
             handle_collection_load_errors,
             recovery_mode,
             search_timeout: search_timeout.unwrap_or(DEFAULT_SEARCH_TIMEOUT),
             update_concurrency,
             is_distributed,
+            read_load_balancing,
+            snapshot_storage,
+            low_priority_io_budget,
+            search_admission,
+            shard_transfer,
+            scrubber,
+            feature_flags,
+            events,
         }
     }
 }
+
+/// Role of this node, which could be changed at runtime
+///
+/// Clones share the role, so the change is visible to all collections.
+#[derive(Clone, Debug, Default)]
+pub struct NodeRole(Arc<RwLock<NodeType>>);
+
+impl NodeRole {
+    pub fn get(&self) -> NodeType {
+        *self.0.read()
+    }
+
+    pub fn set(&self, node_type: NodeType) {
+        *self.0.write() = node_type;
+    }
+}
+
+impl From<NodeType> for NodeRole {
+    fn from(node_type: NodeType) -> Self {
+        Self(Arc::new(RwLock::new(node_type)))
+    }
+}
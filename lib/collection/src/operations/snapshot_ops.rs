@@ -26,6 +26,18 @@ pub enum SnapshotPriority {
     ShardTransfer,
 }
 
+/// Priority of the snapshot creation:
+/// `Normal` means - create the snapshot as fast as possible.
+/// `Low` means - limit disk throughput of the snapshot creation by the shared budget of low
+/// priority tasks, so it doesn't compete with the serving traffic.
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Default, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum SnapshotCreationPriority {
+    Low,
+    #[default]
+    Normal,
+}
+
 impl TryFrom<i32> for SnapshotPriority {
     type Error = tonic::Status;
 
@@ -63,6 +75,7 @@ pub struct SnapshotRecover {
     /// Examples:
     /// - URL `http://localhost:8080/collections/my_collection/snapshots/my_snapshot`
     /// - Local path `file:///qdrant/snapshots/test_collection-2022-08-04-10-49-10.snapshot`
+    /// - Object `s3://my_bucket/my_collection/my_snapshot` in the configured snapshots object storage
     pub location: Url,
 
     /// Defines which data should be used as a source of truth if there are other replicas in the cluster.
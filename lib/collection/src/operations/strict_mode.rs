@@ -0,0 +1,428 @@
+//! Verification of the requests against the strict mode configuration of the collection
+
+use std::collections::HashMap;
+use std::time::Duration;
+
+use segment::common::utils::JsonPathPayload;
+use segment::types::{Condition, Filter, PayloadFieldSchema, PayloadKeyType};
+
+use crate::config::StrictModeConfig;
+use crate::grouping::group_by::{GroupRequest, SourceRequest};
+use crate::operations::payload_ops::PayloadOps;
+use crate::operations::point_ops::{PointInsertOperationsInternal, PointOperations};
+use crate::operations::types::{
+    CollectionError, CollectionResult, CoreSearchRequest, CountRequestInternal,
+    DiscoverRequestInternal, RecommendRequestInternal, ScrollRequestInternal,
+};
+use crate::operations::vector_ops::VectorOperations;
+use crate::operations::CollectionUpdateOperations;
+
+/// Usage of the collection is collected for the quotas at most once per this interval,
+/// updates in between are checked against the last collected usage
+pub const QUOTA_USAGE_REFRESH_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Amount of data in the collection, restricted by the quotas of the strict mode
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct QuotaUsage {
+    pub points_count: usize,
+    pub disk_usage_bytes: usize,
+}
+
+/// Request, which could be restricted by the strict mode of the collection
+pub trait StrictModeVerification {
+    /// Max number of results, requested by the request
+    fn query_limit(&self) -> Option<usize>;
+
+    /// Number of results to skip
+    fn query_offset(&self) -> Option<usize>;
+
+    /// Filter to apply to the request
+    fn query_filter(&self) -> Option<&Filter>;
+
+    fn check_strict_mode(
+        &self,
+        strict_mode_config: &StrictModeConfig,
+        payload_schema: &HashMap<PayloadKeyType, PayloadFieldSchema>,
+    ) -> CollectionResult<()> {
+        if let (Some(limit), Some(max_limit)) =
+            (self.query_limit(), strict_mode_config.max_query_limit)
+        {
+            check_limit("limit", limit, max_limit)?;
+        }
+        if let (Some(offset), Some(max_offset)) =
+            (self.query_offset(), strict_mode_config.max_query_offset)
+        {
+            check_limit("offset", offset, max_offset)?;
+        }
+        if let Some(filter) = self.query_filter() {
+            check_filter(filter, strict_mode_config, payload_schema)?;
+        }
+        Ok(())
+    }
+}
+
+fn check_limit(parameter: &str, value: usize, max_value: usize) -> CollectionResult<()> {
+    if value > max_value {
+        return Err(CollectionError::bad_request(format!(
+            "Strict mode: `{parameter}` {value} exceeds the maximum of {max_value}"
+        )));
+    }
+    Ok(())
+}
+
+/// Check number of requests in a batch, or number of points in an update
+pub fn check_batch_size(
+    batch_size: usize,
+    strict_mode_config: &StrictModeConfig,
+) -> CollectionResult<()> {
+    if let Some(max_batch_size) = strict_mode_config.max_batch_size {
+        check_limit("batch size", batch_size, max_batch_size)?;
+    }
+    Ok(())
+}
+
+/// Check complexity of the filter and that all filtered fields are indexed, if required
+pub fn check_filter(
+    filter: &Filter,
+    strict_mode_config: &StrictModeConfig,
+    payload_schema: &HashMap<PayloadKeyType, PayloadFieldSchema>,
+) -> CollectionResult<()> {
+    if let Some(max_conditions) = strict_mode_config.max_filter_conditions {
+        check_limit(
+            "filter conditions",
+            count_filter_conditions(filter),
+            max_conditions,
+        )?;
+    }
+
+    if !strict_mode_config
+        .unindexed_filtering_allowed
+        .unwrap_or(false)
+    {
+        let mut filtered_keys = Vec::new();
+        collect_filtered_keys(filter, None, &mut filtered_keys);
+        if let Some(key) = filtered_keys
+            .into_iter()
+            .find(|key| !payload_schema.contains_key(key))
+        {
+            return Err(CollectionError::bad_request(format!(
+                "Strict mode: filtering by unindexed field `{key}` is forbidden, create a payload index for it"
+            )));
+        }
+    }
+    Ok(())
+}
+
+fn filter_conditions(filter: &Filter) -> impl Iterator<Item = &Condition> {
+    [&filter.must, &filter.should, &filter.must_not]
+        .into_iter()
+        .flatten()
+        .flatten()
+}
+
+fn count_filter_conditions(filter: &Filter) -> usize {
+    filter_conditions(filter)
+        .map(|condition| match condition {
+            Condition::Filter(filter) => count_filter_conditions(filter),
+            Condition::Nested(nested) => count_filter_conditions(nested.filter()).max(1),
+            Condition::Field(_)
+            | Condition::IsEmpty(_)
+            | Condition::IsNull(_)
+            | Condition::HasId(_) => 1,
+        })
+        .sum()
+}
+
+/// Collect full paths of all payload fields, used in the filter
+fn collect_filtered_keys(
+    filter: &Filter,
+    nested_path: Option<&JsonPathPayload>,
+    keys: &mut Vec<PayloadKeyType>,
+) {
+    for condition in filter_conditions(filter) {
+        let key = match condition {
+            Condition::Filter(filter) => {
+                collect_filtered_keys(filter, nested_path, keys);
+                continue;
+            }
+            Condition::Nested(nested) => {
+                let full_path = JsonPathPayload::extend_or_new(nested_path, &nested.array_key());
+                collect_filtered_keys(nested.filter(), Some(&full_path), keys);
+                continue;
+            }
+            Condition::HasId(_) => continue,
+            Condition::Field(field) => &field.key,
+            Condition::IsEmpty(is_empty) => &is_empty.is_empty.key,
+            Condition::IsNull(is_null) => &is_null.is_null.key,
+        };
+        keys.push(JsonPathPayload::extend_or_new(nested_path, key).path);
+    }
+}
+
+/// Check an update operation, received from the user
+pub fn check_update_operation(
+    operation: &CollectionUpdateOperations,
+    strict_mode_config: &StrictModeConfig,
+    payload_schema: &HashMap<PayloadKeyType, PayloadFieldSchema>,
+) -> CollectionResult<()> {
+    let operation = match operation {
+        CollectionUpdateOperations::PointOperation(operation) => operation,
+        CollectionUpdateOperations::Batch(operations) => {
+            return operations.iter().try_for_each(|operation| {
+                check_update_operation(operation, strict_mode_config, payload_schema)
+            });
+        }
+        _ => return Ok(()),
+    };
+    match operation {
+        PointOperations::UpsertPoints(PointInsertOperationsInternal::PointsBatch(batch)) => {
+            check_batch_size(batch.ids.len(), strict_mode_config)
+        }
+        PointOperations::UpsertPoints(PointInsertOperationsInternal::PointsList(points)) => {
+            check_batch_size(points.len(), strict_mode_config)
+        }
+        PointOperations::DeletePoints { ids } => check_batch_size(ids.len(), strict_mode_config),
+        PointOperations::DeletePointsByFilter(filter) => {
+            check_filter(filter, strict_mode_config, payload_schema)
+        }
+        PointOperations::SyncPoints(_) => Ok(()),
+    }
+}
+
+/// Whether the operation might increase the amount of data in the collection
+///
+/// Only such operations are restricted by the quotas, so that the data could always be cleaned up.
+pub fn is_growing_operation(operation: &CollectionUpdateOperations) -> bool {
+    match operation {
+        CollectionUpdateOperations::PointOperation(operation) => match operation {
+            PointOperations::UpsertPoints(_) | PointOperations::SyncPoints(_) => true,
+            PointOperations::DeletePoints { .. } | PointOperations::DeletePointsByFilter(_) => {
+                false
+            }
+        },
+        CollectionUpdateOperations::VectorOperation(operation) => match operation {
+            VectorOperations::UpdateVectors(_) => true,
+            VectorOperations::DeleteVectors(..) | VectorOperations::DeleteVectorsByFilter(..) => {
+                false
+            }
+        },
+        CollectionUpdateOperations::PayloadOperation(operation) => match operation {
+            PayloadOps::SetPayload(_) | PayloadOps::OverwritePayload(_) => true,
+            PayloadOps::DeletePayload(_)
+            | PayloadOps::ClearPayload { .. }
+            | PayloadOps::ClearPayloadByFilter(_) => false,
+        },
+        CollectionUpdateOperations::FieldIndexOperation(_) => false,
+        CollectionUpdateOperations::Batch(operations) => {
+            operations.iter().any(is_growing_operation)
+        }
+    }
+}
+
+/// Check current usage of the collection against the quotas of the strict mode
+///
+/// Usage, which is unknown (e.g. reported by a peer of an older version), is not restricted.
+pub fn check_quotas(
+    points_count: Option<usize>,
+    disk_usage_bytes: Option<usize>,
+    strict_mode_config: &StrictModeConfig,
+) -> CollectionResult<()> {
+    if let (Some(points_count), Some(max_points_count)) =
+        (points_count, strict_mode_config.max_points_count)
+    {
+        check_quota("points count", points_count, max_points_count)?;
+    }
+    if let (Some(disk_usage_bytes), Some(max_disk_usage_bytes)) =
+        (disk_usage_bytes, strict_mode_config.max_disk_usage_bytes)
+    {
+        check_quota("disk usage bytes", disk_usage_bytes, max_disk_usage_bytes)?;
+    }
+    Ok(())
+}
+
+fn check_quota(quota: &str, usage: usize, max_usage: usize) -> CollectionResult<()> {
+    if usage >= max_usage {
+        return Err(CollectionError::bad_request(format!(
+            "Quota exceeded: collection {quota} is {usage}, the quota is {max_usage}. \
+             Delete some data or ask the administrator to increase the quota"
+        )));
+    }
+    Ok(())
+}
+
+impl StrictModeVerification for CoreSearchRequest {
+    fn query_limit(&self) -> Option<usize> {
+        Some(self.limit)
+    }
+
+    fn query_offset(&self) -> Option<usize> {
+        Some(self.offset)
+    }
+
+    fn query_filter(&self) -> Option<&Filter> {
+        self.filter.as_ref()
+    }
+}
+
+impl StrictModeVerification for RecommendRequestInternal {
+    fn query_limit(&self) -> Option<usize> {
+        Some(self.limit)
+    }
+
+    fn query_offset(&self) -> Option<usize> {
+        self.offset
+    }
+
+    fn query_filter(&self) -> Option<&Filter> {
+        self.filter.as_ref()
+    }
+}
+
+impl StrictModeVerification for DiscoverRequestInternal {
+    fn query_limit(&self) -> Option<usize> {
+        Some(self.limit)
+    }
+
+    fn query_offset(&self) -> Option<usize> {
+        self.offset
+    }
+
+    fn query_filter(&self) -> Option<&Filter> {
+        self.filter.as_ref()
+    }
+}
+
+impl StrictModeVerification for ScrollRequestInternal {
+    fn query_limit(&self) -> Option<usize> {
+        self.limit
+    }
+
+    fn query_offset(&self) -> Option<usize> {
+        // Scroll offset is a point id, it does not affect the cost of the request
+        None
+    }
+
+    fn query_filter(&self) -> Option<&Filter> {
+        self.filter.as_ref()
+    }
+}
+
+impl StrictModeVerification for CountRequestInternal {
+    fn query_limit(&self) -> Option<usize> {
+        None
+    }
+
+    fn query_offset(&self) -> Option<usize> {
+        None
+    }
+
+    fn query_filter(&self) -> Option<&Filter> {
+        self.filter.as_ref()
+    }
+}
+
+impl StrictModeVerification for GroupRequest {
+    fn query_limit(&self) -> Option<usize> {
+        Some(self.limit)
+    }
+
+    fn query_offset(&self) -> Option<usize> {
+        None
+    }
+
+    fn query_filter(&self) -> Option<&Filter> {
+        match &self.source {
+            SourceRequest::Search(request) => request.filter.as_ref(),
+            SourceRequest::Recommend(request) => request.filter.as_ref(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use segment::types::{FieldCondition, Match, PayloadSchemaType, ValueVariants};
+
+    use super::*;
+
+    fn field_condition(key: &str) -> Condition {
+        Condition::Field(FieldCondition::new_match(
+            key,
+            Match::new_value(ValueVariants::Integer(1)),
+        ))
+    }
+
+    #[test]
+    fn test_check_filter() {
+        let strict_mode_config = StrictModeConfig {
+            enabled: Some(true),
+            max_filter_conditions: Some(3),
+            ..Default::default()
+        };
+        let payload_schema = HashMap::from([
+            (
+                "indexed".to_string(),
+                PayloadFieldSchema::FieldType(PayloadSchemaType::Integer),
+            ),
+            (
+                "nested[].indexed".to_string(),
+                PayloadFieldSchema::FieldType(PayloadSchemaType::Integer),
+            ),
+        ]);
+
+        let filter = Filter {
+            must: Some(vec![
+                field_condition("indexed"),
+                Condition::new_nested("nested", Filter::new_must(field_condition("indexed"))),
+            ]),
+            ..Default::default()
+        };
+        assert!(check_filter(&filter, &strict_mode_config, &payload_schema).is_ok());
+
+        let filter = Filter::new_must_not(field_condition("unindexed"));
+        assert!(check_filter(&filter, &strict_mode_config, &payload_schema).is_err());
+
+        let unindexed_allowed = StrictModeConfig {
+            unindexed_filtering_allowed: Some(true),
+            ..strict_mode_config.clone()
+        };
+        assert!(check_filter(&filter, &unindexed_allowed, &payload_schema).is_ok());
+
+        let filter = Filter {
+            should: Some(vec![field_condition("indexed"); 2]),
+            must: Some(vec![Condition::Filter(Filter::new_must(field_condition(
+                "indexed",
+            )))]),
+            ..Default::default()
+        };
+        assert_eq!(count_filter_conditions(&filter), 3);
+        assert!(check_filter(&filter, &strict_mode_config, &payload_schema).is_ok());
+
+        let filter = Filter {
+            must_not: Some(vec![field_condition("indexed")]),
+            ..filter
+        };
+        assert!(check_filter(&filter, &strict_mode_config, &payload_schema).is_err());
+    }
+
+    #[test]
+    fn test_check_quotas() {
+        let strict_mode_config = StrictModeConfig {
+            enabled: Some(true),
+            max_points_count: Some(100),
+            max_disk_usage_bytes: Some(1024),
+            ..Default::default()
+        };
+        assert!(check_quotas(Some(99), Some(0), &strict_mode_config).is_ok());
+        assert!(check_quotas(Some(100), Some(0), &strict_mode_config).is_err());
+        assert!(check_quotas(Some(0), Some(2048), &strict_mode_config).is_err());
+
+        // Usage reported by peers of older versions is unknown
+        assert!(check_quotas(None, None, &strict_mode_config).is_ok());
+
+        let unlimited = StrictModeConfig {
+            enabled: Some(true),
+            ..Default::default()
+        };
+        assert!(check_quotas(Some(100), Some(2048), &unlimited).is_ok());
+    }
+}
@@ -15,13 +15,16 @@ use schemars::JsonSchema;
 use segment::common::anonymize::Anonymize;
 use segment::common::operation_error::OperationError;
 use segment::data_types::groups::GroupId;
+use segment::data_types::order_by::OrderBy;
 use segment::data_types::vectors::{
-    DenseVector, Named, NamedQuery, NamedVectorStruct, QueryVector, Vector, VectorElementType,
-    VectorRef, VectorStruct, DEFAULT_VECTOR_NAME,
+    validate_multi_dense_vector, DenseVector, MultiDenseVector, Named, NamedQuery,
+    NamedVectorStruct, QueryVector, Vector, VectorElementType, VectorRef, VectorStruct,
+    DEFAULT_VECTOR_NAME,
 };
 use segment::types::{
-    Distance, Filter, Payload, PayloadIndexInfo, PayloadKeyType, PointIdType, QuantizationConfig,
-    ScoredPoint, SearchParams, SeqNumberType, ShardKey, WithPayloadInterface, WithVector,
+    Distance, Filter, FloatPayloadType, MultiVectorConfig, Payload, PayloadIndexInfo,
+    PayloadKeyType, PointIdType, QuantizationConfig, ScoredPoint, SearchParams, SeqNumberType,
+    ShardKey, VectorStorageDatatype, WithPayloadInterface, WithVector,
 };
 use segment::vector_storage::query::context_query::ContextQuery;
 use segment::vector_storage::query::discovery_query::DiscoveryQuery;
@@ -39,10 +42,15 @@ use validator::{Validate, ValidationError, ValidationErrors};
 
 use super::config_diff::{self};
 use crate::config::{CollectionConfig, CollectionParams};
+use crate::distinct::{CoreDistinct, DistinctBy};
 use crate::lookup::types::WithLookupInterface;
 use crate::operations::config_diff::{HnswConfigDiff, QuantizationConfigDiff};
+use crate::operations::dimensionality_reduction::{
+    validate_dimensionality_reduction, DimensionalityReduction, LinearTransform,
+};
 use crate::operations::shard_key_selector::ShardKeySelector;
 use crate::save_on_disk;
+use crate::score_formula::{CoreRescore, Rescore};
 use crate::shards::replica_set::ReplicaState;
 use crate::shards::shard::{PeerId, ShardId};
 use crate::shards::transfer::ShardTransferMethod;
@@ -83,6 +91,10 @@ pub enum OptimizersStatus {
 pub struct Record {
     /// Id of the point
     pub id: PointIdType,
+    /// Point version, the sequence number of the last update of the point. Used as `if_version`
+    /// of conditional updates
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<SeqNumberType>,
     /// Payload - values assigned to the point
     pub payload: Option<Payload>,
     /// Vector of the point
@@ -90,6 +102,9 @@ pub struct Record {
     /// Shard Key
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub shard_key: Option<ShardKey>,
+    /// Value of the `order_by` field, the point is ordered by in the scroll
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub order_value: Option<FloatPayloadType>,
 }
 
 /// Current statistics and configuration of the collection
@@ -114,6 +129,9 @@ pub struct CollectionInfo {
     /// Number of segments in collection.
     /// Each segment has independent vector as payload indexes
     pub segments_count: usize,
+    /// Approximate size of the collection data on disk, in bytes.
+    /// Compared against the `max_disk_usage_bytes` quota of the strict mode.
+    pub disk_usage_bytes: Option<usize>,
     /// Collection settings
     #[validate]
     pub config: CollectionConfig,
@@ -130,6 +148,7 @@ impl CollectionInfo {
             indexed_vectors_count: Some(0),
             points_count: Some(0),
             segments_count: 0,
+            disk_usage_bytes: Some(0),
             config: collection_config,
             payload_schema: HashMap::new(),
         }
@@ -145,6 +164,7 @@ impl From<CollectionInfoInternal> for CollectionInfo {
             indexed_vectors_count: Some(info.indexed_vectors_count),
             points_count: Some(info.points_count),
             segments_count: info.segments_count,
+            disk_usage_bytes: Some(info.disk_usage_bytes),
             config: info.config,
             payload_schema: info.payload_schema,
         }
@@ -173,12 +193,41 @@ pub struct CollectionInfoInternal {
     /// Number of segments in collection.
     /// Each segment has independent vector as payload indexes
     pub segments_count: usize,
+    /// Approximate size of the collection data on disk, in bytes
+    pub disk_usage_bytes: usize,
     /// Collection settings
     pub config: CollectionConfig,
     /// Types of stored payload
     pub payload_schema: HashMap<PayloadKeyType, PayloadIndexInfo>,
 }
 
+/// Amount of collection data, read into memory by the warm-up
+#[derive(Debug, Default, Deserialize, Serialize, JsonSchema, Clone, Copy, PartialEq, Eq)]
+pub struct WarmUpResult {
+    /// Number of memory-mapped files, read into memory
+    pub files: usize,
+    /// Total size of the read files in bytes
+    pub bytes: usize,
+}
+
+impl std::ops::AddAssign for WarmUpResult {
+    fn add_assign(&mut self, other: Self) {
+        self.files += other.files;
+        self.bytes += other.bytes;
+    }
+}
+
+/// Outcome of the manual compaction of a shard
+#[derive(Debug, Default, Deserialize, Serialize, JsonSchema, Clone, Copy, PartialEq, Eq)]
+pub struct CompactionResult {
+    /// Number of finished merges of segments
+    pub merges: usize,
+    /// Number of segments before the compaction
+    pub segments_before: usize,
+    /// Number of segments after the compaction
+    pub segments_after: usize,
+}
+
 /// Current clustering distribution for the collection
 #[derive(Debug, Deserialize, Serialize, JsonSchema)]
 pub struct CollectionClusterInfo {
@@ -236,14 +285,14 @@ pub struct RemoteShardInfo {
 
 /// `Acknowledged` - Request is saved to WAL and will be process in a queue.
 /// `Completed` - Request is completed, changes are actual.
-#[derive(Debug, Deserialize, Serialize, JsonSchema, PartialEq, Eq)]
+#[derive(Debug, Deserialize, Serialize, JsonSchema, PartialEq, Eq, Clone, Copy)]
 #[serde(rename_all = "snake_case")]
 pub enum UpdateStatus {
     Acknowledged,
     Completed,
 }
 
-#[derive(Debug, Deserialize, Serialize, JsonSchema)]
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone)]
 #[serde(rename_all = "snake_case")]
 pub struct UpdateResult {
     /// Sequential number of the operation
@@ -251,6 +300,30 @@ pub struct UpdateResult {
     pub operation_id: Option<SeqNumberType>,
     /// Update status
     pub status: UpdateStatus,
+    /// Pass this token to the subsequent searches to only read from replicas, which have
+    /// applied this update. Only returned if the update was completed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub consistency_token: Option<String>,
+    /// Results of the update on each replica, which responded before the update returned.
+    /// Replicas still applying the update in background, e.g. with hedged updates, are not listed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub acknowledgments: Option<Vec<ReplicaAcknowledgment>>,
+}
+
+/// Result of the update on a single replica
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub struct ReplicaAcknowledgment {
+    /// Shard of the replica
+    pub shard_id: ShardId,
+    /// Peer of the replica
+    pub peer_id: PeerId,
+    /// Update status on the replica. If none - the replica failed to apply the update
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status: Option<UpdateStatus>,
+    /// Error, the replica failed to apply the update with
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
 }
 
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone)]
@@ -281,6 +354,44 @@ pub struct ScrollRequestInternal {
     /// Whether to return the point vector with the result?
     #[serde(default, alias = "with_vectors")]
     pub with_vector: WithVector,
+    /// If set - return points as they were at this time (unix timestamp in seconds).
+    /// Only available within the time-travel window of the collection.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub as_of: Option<u64>,
+    /// If set - read all pages of the scroll from the same view of the collection, as it was when
+    /// the session was started by its first page or opened explicitly. Any unique string up to
+    /// 128 characters without `~` could be used as the ID of the session, next pages have to use
+    /// `session_id` returned by the previous one. Sessions expire after a minute without reads
+    /// by default.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate(length(min = 1, max = 4096))]
+    pub session_id: Option<String>,
+    /// Time without reads in seconds, after which the session expires. Only used by the request,
+    /// which starts the session. Default: 60
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate(range(min = 1, max = 3600))]
+    pub session_ttl_sec: Option<u64>,
+    /// Order the points by the values of the payload field, instead of point ids.
+    /// Requires a numeric index on the field. Can't be combined with `offset`,
+    /// use `order_by.start_from` to paginate.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub order_by: Option<OrderBy>,
+    /// Return a sample of `limit` points, which match the filter, instead of a page.
+    /// Can't be combined with `offset`, `order_by` or `as_of`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sample: Option<Sample>,
+    /// Whether to return the versions of the points, which are used as `if_version` of
+    /// conditional updates. Default: false
+    #[serde(default)]
+    pub with_version: bool,
+}
+
+/// Method of sampling of the points
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum Sample {
+    /// Uniform random sample of the points, in random order
+    Random,
 }
 
 impl Default for ScrollRequestInternal {
@@ -291,6 +402,12 @@ impl Default for ScrollRequestInternal {
             filter: None,
             with_payload: Some(WithPayloadInterface::Bool(true)),
             with_vector: WithVector::Bool(false),
+            as_of: None,
+            session_id: None,
+            session_ttl_sec: None,
+            order_by: None,
+            sample: None,
+            with_version: false,
         }
     }
 }
@@ -303,6 +420,32 @@ pub struct ScrollResult {
     pub points: Vec<Record>,
     /// Offset which should be used to retrieve a next page result
     pub next_page_offset: Option<PointIdType>,
+    /// Scroll session, which should be used to retrieve a next page result. It routes the next
+    /// pages to the replicas, which keep the session.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub session_id: Option<String>,
+}
+
+/// Open a scroll session: read snapshot of the collection, which scroll requests could reference.
+/// Sessions expire an hour after opening at the latest.
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone, Default)]
+#[serde(rename_all = "snake_case")]
+pub struct OpenScrollSession {
+    /// Time without reads in seconds, after which the session expires. Default: 60
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate(range(min = 1, max = 3600))]
+    pub ttl_sec: Option<u64>,
+}
+
+/// Opened scroll session
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone)]
+#[serde(rename_all = "snake_case")]
+pub struct ScrollSessionInfo {
+    /// ID of the session, to be used as `session_id` of the scroll requests. It also holds the
+    /// replicas, which keep the session of each shard.
+    pub session_id: String,
+    /// Time without reads in seconds, after which the session expires
+    pub ttl_sec: u64,
 }
 
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone)]
@@ -314,6 +457,19 @@ pub struct SearchRequest {
     /// Specify in which shards to look for the points, if not specified - look in all shards
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub shard_key: Option<ShardKeySelector>,
+    /// Token returned by the update: only search in replicas, which have already applied it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub consistency_token: Option<String>,
+    /// Rescore the best candidates in each shard by a formula of the vector score and payload
+    /// values. Results are ordered by the formula, larger is better
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate]
+    pub rescore: Option<Rescore>,
+    /// Return only the best scored point for each value of the payload field,
+    /// e.g. one chunk per source document
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate]
+    pub distinct_by: Option<DistinctBy>,
 }
 
 /// Search request.
@@ -358,6 +514,55 @@ pub struct SearchRequestBatch {
     pub searches: Vec<SearchRequest>,
 }
 
+/// Shard, which failed to process the request
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq)]
+pub struct ShardFailure {
+    pub shard_id: ShardId,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub shard_key: Option<ShardKey>,
+    /// Reason of the failure
+    pub error: String,
+}
+
+/// Search results, collected from the shards which responded
+#[derive(Debug, Default, Deserialize, Serialize, JsonSchema, Clone)]
+pub struct PartialSearchResult {
+    pub points: Vec<ScoredPoint>,
+    /// Shards, which failed to respond. Points stored in them are missing from the result
+    pub failed_shards: Vec<ShardFailure>,
+}
+
+/// Batch search results, collected from the shards which responded
+#[derive(Debug, Default, Deserialize, Serialize, JsonSchema, Clone)]
+pub struct PartialSearchBatchResult {
+    pub results: Vec<Vec<ScoredPoint>>,
+    /// Shards, which failed to respond. Points stored in them are missing from the results
+    pub failed_shards: Vec<ShardFailure>,
+}
+
+/// Search request, paginated with a cursor instead of `offset`
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone)]
+#[serde(rename_all = "snake_case")]
+pub struct SearchPageRequest {
+    #[serde(flatten)]
+    #[validate]
+    pub search_request: SearchRequest,
+    /// Cursor returned with the previous page. If not specified - the first page is returned.
+    /// Cursor is only valid for the same search request, `limit` may be changed between pages.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cursor: Option<String>,
+}
+
+/// Page of the search results
+#[derive(Debug, Default, Deserialize, Serialize, JsonSchema, Clone)]
+#[serde(rename_all = "snake_case")]
+pub struct SearchPageResult {
+    pub points: Vec<ScoredPoint>,
+    /// Cursor which should be used to retrieve a next page result.
+    /// If not specified - there are no more results
+    pub next_page_cursor: Option<String>,
+}
+
 #[derive(Debug, Clone)]
 pub enum QueryEnum {
     Nearest(NamedVectorStruct),
@@ -414,6 +619,26 @@ pub struct CoreSearchRequest {
     /// Whether to return the point vector with the result?
     pub with_vector: Option<WithVector>,
     pub score_threshold: Option<ScoreType>,
+    /// Cheap first stage of the search: only its candidates are scored by the `query`, exactly.
+    /// Both stages are executed in each segment, so candidates never leave the shard.
+    /// `params` are ignored, if the prefetch is set
+    pub prefetch: Option<CorePrefetch>,
+    /// Formula to rescore the best candidates in each shard with, larger scores are better.
+    /// `score_threshold` is applied to the vector scores
+    pub rescore: Option<CoreRescore>,
+    /// Payload field to deduplicate the results by, in each shard and while merging their results
+    pub distinct_by: Option<CoreDistinct>,
+}
+
+/// First stage of the search, which selects candidates for the final scoring
+#[derive(Debug, Clone)]
+pub struct CorePrefetch {
+    /// Query of the first stage, e.g. by a sparse or a smaller named vector
+    pub query: QueryEnum,
+    /// Search params of the first stage, e.g. to search with quantized vectors only
+    pub params: Option<SearchParams>,
+    /// Number of candidates to select in each segment
+    pub limit: usize,
 }
 
 #[derive(Debug, Clone)]
@@ -483,6 +708,14 @@ pub struct PointRequestInternal {
     /// Whether to return the point vector with the result?
     #[serde(default, alias = "with_vectors")]
     pub with_vector: WithVector,
+    /// If set - return points as they were at this time (unix timestamp in seconds).
+    /// Only available within the time-travel window of the collection.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub as_of: Option<u64>,
+    /// Whether to return the versions of the points, which are used as `if_version` of
+    /// conditional updates. Default: false
+    #[serde(default)]
+    pub with_version: bool,
 }
 
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq)]
@@ -490,6 +723,8 @@ pub struct PointRequestInternal {
 pub enum RecommendExample {
     PointId(PointIdType),
     Dense(DenseVector),
+    // Goes before the sparse vector, same as in `Vector`
+    MultiDense(MultiDenseVector),
     Sparse(SparseVector),
 }
 
@@ -507,6 +742,7 @@ impl Validate for RecommendExample {
         match self {
             RecommendExample::PointId(_) => Ok(()),
             RecommendExample::Dense(_) => Ok(()),
+            RecommendExample::MultiDense(multi) => validate_multi_dense_vector(multi),
             RecommendExample::Sparse(sparse) => sparse.validate(),
         }
     }
@@ -883,6 +1119,12 @@ pub enum CollectionError {
     OutOfMemory { description: String, free: u64 },
     #[error("Timeout error: {description}")]
     Timeout { description: String },
+    #[error("Service overloaded: {description}")]
+    Overloaded { description: String },
+    #[error("Read-only: {description}")]
+    ReadOnly { description: String },
+    #[error("Batch changes more than {max_points} points, split it into smaller ones")]
+    BatchTooLarge { max_points: usize },
 }
 
 impl CollectionError {
@@ -910,6 +1152,14 @@ impl CollectionError {
         CollectionError::BadRequest { description }
     }
 
+    pub fn overloaded(description: String) -> CollectionError {
+        CollectionError::Overloaded { description }
+    }
+
+    pub fn read_only(description: String) -> CollectionError {
+        CollectionError::ReadOnly { description }
+    }
+
     pub fn bad_shard_selection(description: String) -> CollectionError {
         CollectionError::BadShardSelection { description }
     }
@@ -948,6 +1198,7 @@ impl CollectionError {
             Self::Timeout { .. } => true,
             Self::Cancelled { .. } => true,
             Self::OutOfMemory { .. } => true,
+            Self::Overloaded { .. } => true,
             // Not transient
             Self::BadInput { .. } => false,
             Self::NotFound { .. } => false,
@@ -956,6 +1207,8 @@ impl CollectionError {
             Self::BadShardSelection { .. } => false,
             Self::InconsistentShardFailure { .. } => false,
             Self::ForwardProxyError { .. } => false,
+            Self::ReadOnly { .. } => false,
+            Self::BatchTooLarge { .. } => false,
         }
     }
 }
@@ -1020,6 +1273,10 @@ impl From<OperationError> for CollectionError {
             OperationError::WrongSparse => Self::BadInput {
                 description: "Conversion between sparse and regular vectors failed".to_string(),
             },
+            OperationError::WrongMulti => Self::BadInput {
+                description: "Conversion between multi-vectors and regular vectors failed"
+                    .to_string(),
+            },
         }
     }
 }
@@ -1118,6 +1375,12 @@ impl From<tonic::Status> for CollectionError {
             tonic::Code::Cancelled => CollectionError::Cancelled {
                 description: format!("{err}"),
             },
+            tonic::Code::ResourceExhausted => CollectionError::Overloaded {
+                description: format!("{err}"),
+            },
+            tonic::Code::FailedPrecondition => CollectionError::ReadOnly {
+                description: format!("{err}"),
+            },
             _other => CollectionError::ServiceError {
                 error: format!("Tonic status error: {err}"),
                 backtrace: Some(Backtrace::force_capture().to_string()),
@@ -1220,6 +1483,7 @@ impl Record {
 
 /// Params of single vector data storage
 #[derive(Debug, Hash, Deserialize, Serialize, JsonSchema, Validate, Clone, PartialEq, Eq)]
+#[validate(schema(function = "validate_vector_params"))]
 #[serde(rename_all = "snake_case")]
 pub struct VectorParams {
     /// Size of a vectors used
@@ -1243,6 +1507,81 @@ pub struct VectorParams {
     /// Default: false
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub on_disk: Option<bool>,
+    /// If true, vectors are L2-normalized on upsert, and so are the query vectors.
+    /// Allows to use `Dot` distance with vectors, which are not normalized by the client.
+    /// Can't be changed after the collection is created.
+    /// Default: false
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub normalize: Option<bool>,
+    /// If set, vectors and query vectors of `input_size` dimensions are projected to `size`
+    /// dimensions by a learned linear transform before they are used.
+    /// Can't be changed after the collection is created, except for setting the transform.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate]
+    pub dimensionality_reduction: Option<DimensionalityReduction>,
+    /// If set, each point stores a variable number of vectors of `size` dimensions under this
+    /// name, e.g. token embeddings of a late-interaction model. Multi-vectors are kept in memory
+    /// and are not quantized.
+    /// Can't be changed after the collection is created.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub multivector_config: Option<MultiVectorConfig>,
+    /// Type of the elements of the stored vectors. Vectors of the `uint8` datatype take 1 byte
+    /// per dimension, e.g. embeddings quantized by the client. Their values must be in range
+    /// `[0, 255]`, they are not quantized, normalized or reduced.
+    /// Vectors of the `float16` datatype take 2 bytes per dimension. Their absolute values must
+    /// not exceed 65504, they are not quantized.
+    /// Both are stored in memory or on disk, same as `float32` vectors.
+    /// Can't be changed after the collection is created.
+    /// Default: float32
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub datatype: Option<VectorStorageDatatype>,
+}
+
+impl VectorParams {
+    /// Reason why the options of the vector params can't be combined with its datatype, if any
+    pub fn datatype_conflict(&self) -> Option<&'static str> {
+        match self.datatype {
+            Some(VectorStorageDatatype::Uint8) => {}
+            Some(VectorStorageDatatype::Float16) => {
+                return if self.quantization_config.is_some() {
+                    Some("vectors of the float16 datatype can't be quantized")
+                } else if self.multivector_config.is_some() {
+                    Some("multi-vectors of the float16 datatype are not supported")
+                } else {
+                    None
+                };
+            }
+            Some(VectorStorageDatatype::Float32) | None => return None,
+        }
+        if self.quantization_config.is_some() {
+            Some("vectors of the uint8 datatype can't be quantized")
+        } else if self.multivector_config.is_some() {
+            Some("multi-vectors of the uint8 datatype are not supported")
+        } else if self.normalize == Some(true) {
+            Some("vectors of the uint8 datatype can't be normalized")
+        } else if self.dimensionality_reduction.is_some() {
+            Some("vectors of the uint8 datatype can't be reduced")
+        } else {
+            None
+        }
+    }
+}
+
+fn validate_vector_params(params: &VectorParams) -> Result<(), ValidationError> {
+    if params.multivector_config.is_some() && params.quantization_config.is_some() {
+        let mut error = ValidationError::new("multivector_quantization");
+        error.message = Some("multi-vectors can't be quantized".into());
+        return Err(error);
+    }
+    if let Some(conflict) = params.datatype_conflict() {
+        let mut error = ValidationError::new("datatype_conflict");
+        error.message = Some(conflict.into());
+        return Err(error);
+    }
+    match &params.dimensionality_reduction {
+        Some(reduction) => validate_dimensionality_reduction(params.size, reduction),
+        None => Ok(()),
+    }
 }
 
 /// Validate the value is in `[1, 65536]` or `None`.
@@ -1404,6 +1743,41 @@ impl VectorsConfig {
             };
 
             VectorParamsBase::from(this).check_compatibility(&other.into(), vector_name)?;
+
+            // Normalized and raw vectors can't be mixed
+            let (this_normalize, other_normalize) = (
+                this.normalize.unwrap_or_default(),
+                other.normalize.unwrap_or_default(),
+            );
+            if this_normalize != other_normalize {
+                return Err(CollectionError::BadInput {
+                    description: format!(
+                        "Vectors configuration is not compatible: \
+                         origin vector {vector_name} normalize: {this_normalize}, \
+                         while other vector normalize: {other_normalize}",
+                    ),
+                });
+            }
+
+            // Vectors of different input sizes can't be mixed
+            let (this_input_size, other_input_size) = (
+                this.dimensionality_reduction
+                    .as_ref()
+                    .map(|reduction| reduction.input_size),
+                other
+                    .dimensionality_reduction
+                    .as_ref()
+                    .map(|reduction| reduction.input_size),
+            );
+            if this_input_size != other_input_size {
+                return Err(CollectionError::BadInput {
+                    description: format!(
+                        "Vectors configuration is not compatible: \
+                         origin vector {vector_name} reduced from: {this_input_size:?}, \
+                         while other vector reduced from: {other_input_size:?}",
+                    ),
+                });
+            }
         }
 
         Ok(())
@@ -1432,6 +1806,29 @@ impl VectorsConfig {
 
         Ok(())
     }
+
+    /// Check that all vectors of the segment are known to the collection and compatible with it
+    ///
+    /// Unlike [`VectorsConfig::check_compatible_with_segment_config`], the segment is allowed to
+    /// miss vectors, which were added to the collection after the segment was created.
+    pub fn check_segment_vectors_known(
+        &self,
+        other: &HashMap<String, segment::types::VectorDataConfig>,
+    ) -> CollectionResult<()> {
+        for (vector_name, segment_config) in other {
+            let Some(this) = self.get_params(vector_name) else {
+                return Err(incompatible_vectors_error(
+                    self.params_iter().map(|(name, _)| name),
+                    other.keys().map(String::as_str),
+                ));
+            };
+
+            VectorParamsBase::from(this)
+                .check_compatibility(&segment_config.into(), vector_name)?;
+        }
+
+        Ok(())
+    }
 }
 
 // TODO(sparse): Further unify `check_compatible` and `check_compatible_with_segment_config`?
@@ -1592,6 +1989,10 @@ pub struct VectorParamsDiff {
     /// If true, vectors are served from disk, improving RAM usage at the cost of latency
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub on_disk: Option<bool>,
+    /// Set the learned transform of the vector with dimensionality reduction configured.
+    /// Could only be set once, if the vector has no transform yet.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dimensionality_reduction_transform: Option<LinearTransform>,
 }
 
 /// Vector update params for multiple vectors
@@ -1661,6 +2062,54 @@ impl Validate for SparseVectorsConfig {
     }
 }
 
+/// Params of named vectors, which are added to an existing collection
+///
+/// {
+///     "new_vector_name": {
+///         "size": 128,
+///         "distance": "Cosine"
+///     }
+/// }
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq, Hash, Eq)]
+pub struct NewVectorsConfig(pub BTreeMap<String, VectorParams>);
+
+impl NewVectorsConfig {
+    /// Check that the vectors in this config could be added to the given collection.
+    ///
+    /// Returns an error if the collection has a single unnamed vector, or if any of the names
+    /// is already taken.
+    pub fn check_vector_names(&self, collection: &CollectionParams) -> CollectionResult<()> {
+        if let VectorsConfig::Single(_) = collection.vectors {
+            return Err(CollectionError::bad_input(
+                "Can't add named vectors to collection with single unnamed vector".to_string(),
+            ));
+        }
+        for vector_name in self.0.keys() {
+            if vector_name.is_empty() {
+                return Err(CollectionError::bad_input(
+                    "Name of the added vector can't be empty".to_string(),
+                ));
+            }
+            let is_sparse = collection
+                .sparse_vectors
+                .as_ref()
+                .is_some_and(|sparse_vectors| sparse_vectors.contains_key(vector_name));
+            if collection.vectors.get_params(vector_name).is_some() || is_sparse {
+                return Err(CollectionError::bad_input(format!(
+                    "Vector {vector_name} already exists in collection"
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Validate for NewVectorsConfig {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        common::validation::validate_iter(self.0.values())
+    }
+}
+
 #[derive(Debug, Deserialize, Serialize, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub struct AliasDescription {
@@ -1674,17 +2123,32 @@ pub struct CollectionsAliasesResponse {
     pub aliases: Vec<AliasDescription>,
 }
 
-#[derive(Clone, Debug, Deserialize, Default, Copy, PartialEq)]
+/// Role of the node in the cluster
+#[derive(Clone, Debug, Deserialize, Serialize, JsonSchema, Default, Copy, PartialEq, Eq, Hash)]
 pub enum NodeType {
     /// Regular node, participates in the cluster
     #[default]
     Normal,
     /// Node that does only receive data, but is not used for search/read operations
     /// This is useful for nodes that are only used for writing data
-    /// and backup purposes
+    /// and backup purposes.
+    /// New collections place a listener replica of every shard on such nodes, while the active
+    /// replicas are placed on the regular nodes.
     Listener,
 }
 
+/// Strategy of choosing remote replicas for reads
+#[derive(Clone, Debug, Deserialize, Serialize, Default, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum ReadLoadBalancing {
+    /// Replicas are chosen at random
+    #[default]
+    Random,
+    /// Replicas with lower latency and error rate are preferred,
+    /// outliers are only read if there are not enough other replicas
+    Latency,
+}
+
 #[derive(Validate, Serialize, Deserialize, JsonSchema, Debug, Clone)]
 pub struct BaseGroupRequest {
     /// Payload field to group by, must be a string or number field.
@@ -1716,6 +2180,9 @@ impl From<SearchRequestInternal> for CoreSearchRequest {
             with_payload: request.with_payload,
             with_vector: request.with_vector,
             score_threshold: request.score_threshold,
+            prefetch: None,
+            rescore: None,
+            distinct_by: None,
         }
     }
 }
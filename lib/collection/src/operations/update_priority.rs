@@ -0,0 +1,41 @@
+//! Priority lanes of the updates: interactive updates are not queued behind bulk ones
+//!
+//! Each shard applies updates in the order of its WAL, so once an update is written to the WAL it
+//! can't be overtaken anymore. Instead, bulk updates, e.g. of a backfill, have to take a slot of
+//! the bulk lane of the shard before they are written to the WAL and queued. The lane takes at most
+//! half of the update queue, the slot is released once the update is applied. Interactive updates
+//! don't need a slot, so under a backfill they wait for a bounded number of bulk updates only, and
+//! bulk updates wait for their turn before they take the WAL.
+//!
+//! The priority is passed along with the update from the API layer down to the shards, and is
+//! forwarded to the remote replicas together with the update.
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// Priority of the update
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum UpdatePriority {
+    /// Update is applied ahead of the queued bulk updates
+    #[default]
+    Interactive,
+    /// Update of a backfill or an import, yields the update queue to interactive updates under load
+    Bulk,
+}
+
+/// Number of the bulk updates, which could be queued by a shard at once
+pub fn bulk_lane_size(update_queue_size: usize) -> usize {
+    (update_queue_size / 2).max(1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bulk_lane_size() {
+        assert_eq!(bulk_lane_size(100), 50);
+        assert_eq!(bulk_lane_size(1), 1);
+    }
+}
@@ -8,10 +8,8 @@ use serde::{Deserialize, Serialize};
 use validator::{Validate, ValidationError, ValidationErrors};
 
 use super::point_ops::PointIdsList;
-use super::{point_to_shard, split_iter_by_shard, OperationToShard, SplitByShard};
-use crate::hash_ring::HashRing;
+use super::{point_to_shard, split_iter_by_shard, OperationToShard, ShardRouter, SplitByShard};
 use crate::operations::shard_key_selector::ShardKeySelector;
-use crate::shards::shard::ShardId;
 
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone)]
 pub struct UpdateVectors {
@@ -101,20 +99,20 @@ impl Validate for VectorOperations {
 }
 
 impl SplitByShard for Vec<PointVectors> {
-    fn split_by_shard(self, ring: &HashRing<ShardId>) -> OperationToShard<Self> {
-        split_iter_by_shard(self, |point| point.id, ring)
+    fn split_by_shard(self, router: &ShardRouter) -> OperationToShard<Self> {
+        split_iter_by_shard(self, |point| point.id, router)
     }
 }
 
 impl SplitByShard for VectorOperations {
-    fn split_by_shard(self, ring: &HashRing<ShardId>) -> OperationToShard<Self> {
+    fn split_by_shard(self, router: &ShardRouter) -> OperationToShard<Self> {
         match self {
             VectorOperations::UpdateVectors(update_vectors) => {
                 let shard_points = update_vectors
                     .points
                     .into_iter()
                     .map(|point| {
-                        let shard_id = point_to_shard(point.id, ring);
+                        let shard_id = point_to_shard(point.id, router);
                         (shard_id, point)
                     })
                     .fold(
@@ -133,7 +131,7 @@ impl SplitByShard for VectorOperations {
                 OperationToShard::by_shard(shard_ops)
             }
             VectorOperations::DeleteVectors(ids, vector_names) => {
-                split_iter_by_shard(ids.points, |id| *id, ring)
+                split_iter_by_shard(ids.points, |id| *id, router)
                     .map(|ids| VectorOperations::DeleteVectors(ids.into(), vector_names.clone()))
             }
             by_filter @ VectorOperations::DeleteVectorsByFilter(..) => {
@@ -71,8 +71,30 @@ pub struct OptimizersConfig {
     pub indexing_threshold: Option<usize>,
     /// Minimum interval between forced flushes.
     pub flush_interval_sec: u64,
+    /// Size (in kilobytes) of updates accumulated since the last flush, which triggers a flush
+    /// before `flush_interval_sec` has passed.
+    ///
+    /// If not set, flush is triggered by interval only.
+    #[serde(alias = "flush_dirty_threshold_kb")]
+    #[serde(default)]
+    pub flush_dirty_threshold: Option<usize>,
+    /// Number of update operations accumulated since the last flush, which triggers a flush
+    /// before `flush_interval_sec` has passed.
+    ///
+    /// If not set, flush is triggered by interval only.
+    #[serde(default)]
+    pub flush_dirty_operations: Option<usize>,
     /// Maximum available threads for optimization workers
     pub max_optimization_threads: usize,
+    /// Maximum number of segments per shard, which are rebuilt at the same time to apply changed
+    /// collection configuration, e.g. HNSW, quantization or on-disk storage parameters.
+    /// Allows to roll out configuration changes gradually, without rebuilding all segments at once.
+    ///
+    /// Set to `0` to abort the rollout: running rebuilds are cancelled, and no new ones are started
+    /// until the limit is raised.
+    /// If not set, all segments with outdated configuration are rebuilt at once.
+    #[serde(default)]
+    pub max_config_rebuild_segments: Option<usize>,
 }
 
 impl OptimizersConfig {
@@ -86,7 +108,10 @@ impl OptimizersConfig {
             memmap_threshold: None,
             indexing_threshold: Some(100_000),
             flush_interval_sec: 60,
+            flush_dirty_threshold: None,
+            flush_dirty_operations: None,
             max_optimization_threads: 0,
+            max_config_rebuild_segments: None,
         }
     }
 
@@ -134,23 +159,7 @@ pub fn build_optimizers(
 ) -> Arc<Vec<Arc<Optimizer>>> {
     let segments_path = shard_path.join(SEGMENTS_PATH);
     let temp_segments_path = shard_path.join(TEMP_SEGMENTS_PATH);
-
-    let indexing_threshold = match optimizers_config.indexing_threshold {
-        None => DEFAULT_INDEXING_THRESHOLD_KB, // default value
-        Some(0) => usize::MAX,                 // disable vector index
-        Some(custom) => custom,
-    };
-
-    let memmap_threshold = match optimizers_config.memmap_threshold {
-        None | Some(0) => usize::MAX, // default | disable memmap
-        Some(custom) => custom,
-    };
-
-    let threshold_config = OptimizerThresholds {
-        memmap_threshold,
-        indexing_threshold,
-        max_segment_size: optimizers_config.get_max_segment_size(),
-    };
+    let threshold_config = optimizer_thresholds(optimizers_config);
 
     Arc::new(vec![
         Arc::new(MergeOptimizer::new(
@@ -181,6 +190,7 @@ pub fn build_optimizers(
             quantization_config.clone(),
         )),
         Arc::new(ConfigMismatchOptimizer::new(
+            optimizers_config.max_config_rebuild_segments,
             threshold_config,
             segments_path,
             temp_segments_path,
@@ -190,3 +200,44 @@ pub fn build_optimizers(
         )),
     ])
 }
+
+/// Optimizer for the manual compaction of a shard
+///
+/// Merges the smallest segments regardless of the configured number of segments, as long as the
+/// merged segment doesn't exceed the maximum segment size.
+pub fn build_compaction_optimizer(
+    shard_path: &Path,
+    collection_params: &CollectionParams,
+    optimizers_config: &OptimizersConfig,
+    hnsw_config: &HnswConfig,
+    quantization_config: &Option<QuantizationConfig>,
+) -> Arc<Optimizer> {
+    Arc::new(MergeOptimizer::new(
+        1,
+        optimizer_thresholds(optimizers_config),
+        shard_path.join(SEGMENTS_PATH),
+        shard_path.join(TEMP_SEGMENTS_PATH),
+        collection_params.clone(),
+        hnsw_config.clone(),
+        quantization_config.clone(),
+    ))
+}
+
+fn optimizer_thresholds(optimizers_config: &OptimizersConfig) -> OptimizerThresholds {
+    let indexing_threshold = match optimizers_config.indexing_threshold {
+        None => DEFAULT_INDEXING_THRESHOLD_KB, // default value
+        Some(0) => usize::MAX,                 // disable vector index
+        Some(custom) => custom,
+    };
+
+    let memmap_threshold = match optimizers_config.memmap_threshold {
+        None | Some(0) => usize::MAX, // default | disable memmap
+        Some(custom) => custom,
+    };
+
+    OptimizerThresholds {
+        memmap_threshold,
+        indexing_threshold,
+        max_segment_size: optimizers_config.get_max_segment_size(),
+    }
+}
@@ -13,6 +13,7 @@ use segment::vector_storage::query::reco_query::RecoQuery;
 use sparse::common::sparse_vector::SparseVector;
 use tokio::sync::RwLockReadGuard;
 
+use crate::collection::access_tags::AccessTags;
 use crate::collection::Collection;
 use crate::common::batching::batch_requests;
 use crate::common::fetch_vectors::{
@@ -26,6 +27,8 @@ use crate::operations::types::{
     CollectionError, CollectionResult, CoreSearchRequest, CoreSearchRequestBatch, QueryEnum,
     RecommendRequestInternal, RecommendStrategy, UsingVector,
 };
+use crate::shards::replica_set::read_priority::ReadPriority;
+use crate::shards::replica_set::stale_reads::StaleReads;
 
 fn avg_vectors<'a>(vectors: impl Iterator<Item = VectorRef<'a>>) -> CollectionResult<Vector> {
     let mut avg_dense = DenseVector::default();
@@ -48,6 +51,11 @@ fn avg_vectors<'a>(vectors: impl Iterator<Item = VectorRef<'a>>) -> CollectionRe
                 sparse_count += 1;
                 avg_sparse = vector.combine_aggregate(&avg_sparse, |v1, v2| v1 + v2);
             }
+            VectorRef::MultiDense(_) => {
+                return Err(CollectionError::bad_input(
+                    "Multi-vectors can't be averaged, use `best_score` strategy instead".to_owned(),
+                ));
+            }
         }
     }
 
@@ -102,6 +110,9 @@ pub async fn recommend_by<'a, F, Fut>(
     read_consistency: Option<ReadConsistency>,
     shard_selector: ShardSelectorInternal,
     timeout: Option<Duration>,
+    access_tags: Option<&AccessTags>,
+    read_priority: ReadPriority,
+    stale_reads: Option<&StaleReads>,
 ) -> CollectionResult<Vec<ScoredPoint>>
 where
     F: Fn(String) -> Fut,
@@ -118,6 +129,9 @@ where
         collection_by_name,
         read_consistency,
         timeout,
+        access_tags,
+        read_priority,
+        stale_reads,
     )
     .await?;
     Ok(results.into_iter().next().unwrap())
@@ -181,6 +195,9 @@ pub async fn recommend_batch_by<'a, F, Fut>(
     collection_by_name: F,
     read_consistency: Option<ReadConsistency>,
     timeout: Option<Duration>,
+    access_tags: Option<&AccessTags>,
+    read_priority: ReadPriority,
+    stale_reads: Option<&StaleReads>,
 ) -> CollectionResult<Vec<Vec<ScoredPoint>>>
 where
     F: Fn(String) -> Fut,
@@ -219,6 +236,9 @@ where
         collection,
         collection_by_name,
         read_consistency,
+        access_tags,
+        read_priority,
+        stale_reads,
     )
     .await?;
 
@@ -247,8 +267,12 @@ where
             requests.push(collection.core_search_batch(
                 core_search_batch_request,
                 read_consistency,
+                None,
                 shard_selector,
                 timeout,
+                access_tags,
+                read_priority,
+                stale_reads,
             ));
 
             Ok(())
@@ -331,6 +355,9 @@ fn recommend_by_avg_vector(
         limit,
         score_threshold,
         offset: offset.unwrap_or_default(),
+        prefetch: None,
+        rescore: None,
+        distinct_by: None,
     })
 }
 
@@ -394,6 +421,9 @@ fn recommend_by_best_score(
         with_payload,
         with_vector,
         score_threshold,
+        prefetch: None,
+        rescore: None,
+        distinct_by: None,
     }
 }
 
@@ -0,0 +1,105 @@
+//! Search in two stages: a cheap prefetch selects candidates, which are rescored by another vector
+//!
+//! The prefetch is usually a search with quantized vectors only, by a sparse vector, or by a
+//! smaller named vector, with a large limit. Only its candidates are scored by the query vector,
+//! exactly and with the original vectors. Both stages are executed in each segment, so the
+//! candidates are never sent between shards, only the final results are.
+//!
+//! The prefetch limit applies to each segment, so the number of rescored candidates grows with
+//! the number of segments. Candidates are rescored by an exact search, restricted to their IDs,
+//! so the cost of the second stage is linear in the prefetch limit. Searches with a prefetch are
+//! not batched with the other searches of the batch.
+
+use schemars::JsonSchema;
+use segment::data_types::vectors::NamedVectorStruct;
+use segment::types::{Filter, ScoreType, SearchParams, WithPayloadInterface, WithVector};
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+use crate::operations::shard_key_selector::ShardKeySelector;
+use crate::operations::types::{CorePrefetch, CoreSearchRequest, QueryEnum};
+
+/// Search of the candidates, selected by the prefetch, with the vector of the request
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone)]
+#[serde(rename_all = "snake_case")]
+pub struct RescoreSearchRequest {
+    /// First stage of the search, which selects the candidates
+    #[validate]
+    pub prefetch: Prefetch,
+    /// Vector to score the candidates with
+    #[validate]
+    pub vector: NamedVectorStruct,
+    /// Look only for points which satisfies this conditions, applied to the prefetch
+    #[validate]
+    pub filter: Option<Filter>,
+    /// Max number of result to return
+    #[validate(range(min = 1))]
+    pub limit: usize,
+    /// Offset of the first result to return.
+    pub offset: Option<usize>,
+    /// Select which payload to return with the response. Default: None
+    pub with_payload: Option<WithPayloadInterface>,
+    /// Whether to return the point vector with the result?
+    #[serde(default, alias = "with_vectors")]
+    pub with_vector: Option<WithVector>,
+    /// Define a minimal score threshold for the result.
+    pub score_threshold: Option<ScoreType>,
+    /// Specify in which shards to look for the points, if not specified - look in all shards
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub shard_key: Option<ShardKeySelector>,
+    /// Token returned by the update: only search in replicas, which have already applied it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub consistency_token: Option<String>,
+}
+
+/// Cheap search, which selects candidates for the rescoring
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone)]
+#[serde(rename_all = "snake_case")]
+pub struct Prefetch {
+    /// Vector to select the candidates with, e.g. a sparse vector or a smaller named vector
+    #[validate]
+    pub vector: NamedVectorStruct,
+    /// Search params of the prefetch, e.g. to search with quantized vectors only
+    #[validate]
+    pub params: Option<SearchParams>,
+    /// Number of candidates to select in each segment
+    #[validate(range(min = 1))]
+    pub limit: usize,
+}
+
+/// Core search with the prefetch stage, `shard_key` and `consistency_token` are ignored
+impl From<RescoreSearchRequest> for CoreSearchRequest {
+    fn from(request: RescoreSearchRequest) -> Self {
+        let RescoreSearchRequest {
+            prefetch,
+            vector,
+            filter,
+            limit,
+            offset,
+            with_payload,
+            with_vector,
+            score_threshold,
+            shard_key: _,
+            consistency_token: _,
+        } = request;
+
+        CoreSearchRequest {
+            query: QueryEnum::Nearest(vector),
+            filter,
+            // Candidates are always scored exactly
+            params: None,
+            limit,
+            offset: offset.unwrap_or_default(),
+            with_payload,
+            with_vector,
+            score_threshold,
+            prefetch: Some(CorePrefetch {
+                query: QueryEnum::Nearest(prefetch.vector),
+                params: prefetch.params,
+                limit: prefetch.limit,
+            }),
+            rescore: None,
+            distinct_by: None,
+        }
+    }
+}
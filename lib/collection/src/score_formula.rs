@@ -0,0 +1,553 @@
+//! Rescoring of the search results by a formula of the vector score and payload values
+//!
+//! The formula is evaluated in each shard for its best candidates by the vector score, with the
+//! payload values of the candidates read locally. The candidates are re-ordered by the formula
+//! value, larger is better, before the results of the shards are merged. So the final order is
+//! known without fetching the payloads and re-sorting on the client.
+//!
+//! Formula is an arithmetic expression of numbers, `score`, payload fields and functions, e.g.
+//! `score * 0.8 + log(popularity) * 0.2`. Fields are referenced by their key, nested ones by
+//! the path: `meta.rating`. Missing and non-numeric values of the fields are 0, booleans are 0 or
+//! 1, the first number is used for arrays.
+
+use std::collections::HashMap;
+use std::fmt;
+
+use schemars::JsonSchema;
+use segment::types::{
+    Payload, PayloadContainer, PayloadKeyType, PointIdType, ScoreType, ScoredPoint,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use validator::{Validate, ValidationError};
+
+use crate::operations::types::{
+    CollectionError, CollectionResult, CoreSearchRequest, SearchRequestInternal,
+};
+
+/// Name of the variable with the vector score of the candidate
+const SCORE_VARIABLE: &str = "score";
+
+/// Rescoring of the search results by a formula of the vector score and payload values
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone)]
+#[serde(rename_all = "snake_case")]
+pub struct Rescore {
+    /// Formula of the new score of the candidates, larger is better.
+    /// E.g. `score * 0.8 + log(popularity) * 0.2`.
+    /// Supports `+`, `-`, `*`, `/`, parentheses and functions `abs`, `exp`, `log`, `sqrt`,
+    /// `pow`, `min`, `max`. `score` is the vector score, other names are payload fields
+    #[validate(custom = "validate_formula")]
+    pub formula: String,
+    /// Number of best candidates by the vector score, rescored in each shard.
+    /// Default: `offset + limit`
+    #[validate(range(min = 1))]
+    pub candidates: Option<usize>,
+}
+
+fn validate_formula(formula: &str) -> Result<(), ValidationError> {
+    ScoreFormula::parse(formula).map(|_| ()).map_err(|err| {
+        let mut error = ValidationError::new("invalid_formula");
+        error.message = Some(err.into());
+        error
+    })
+}
+
+/// Rescoring of the core search, executed by the shards
+#[derive(Debug, Clone)]
+pub struct CoreRescore {
+    pub formula: ScoreFormula,
+    /// Number of best candidates by the vector score, rescored in each shard
+    pub candidates: usize,
+}
+
+impl CoreRescore {
+    /// Parse the formula of the request, `offset + limit` is the default number of candidates
+    pub fn new(rescore: Rescore, offset: usize, limit: usize) -> Result<Self, String> {
+        Ok(Self {
+            formula: ScoreFormula::parse(&rescore.formula)?,
+            candidates: rescore.candidates.unwrap_or(offset + limit),
+        })
+    }
+}
+
+/// Core search of the request, rescored by the formula if it is set
+pub fn rescored_core_search(
+    request: SearchRequestInternal,
+    rescore: Option<Rescore>,
+) -> CollectionResult<CoreSearchRequest> {
+    let mut core_request = CoreSearchRequest::from(request);
+    core_request.rescore = rescore
+        .map(|rescore| CoreRescore::new(rescore, core_request.offset, core_request.limit))
+        .transpose()
+        .map_err(CollectionError::bad_request)?;
+    Ok(core_request)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Function {
+    Abs,
+    Exp,
+    Log,
+    Sqrt,
+    Pow,
+    Min,
+    Max,
+}
+
+impl Function {
+    fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "abs" => Some(Self::Abs),
+            "exp" => Some(Self::Exp),
+            "log" => Some(Self::Log),
+            "sqrt" => Some(Self::Sqrt),
+            "pow" => Some(Self::Pow),
+            "min" => Some(Self::Min),
+            "max" => Some(Self::Max),
+            _ => None,
+        }
+    }
+
+    fn arity(self) -> usize {
+        match self {
+            Self::Abs | Self::Exp | Self::Log | Self::Sqrt => 1,
+            Self::Pow | Self::Min | Self::Max => 2,
+        }
+    }
+
+    fn apply(self, args: &[ScoreType]) -> ScoreType {
+        match self {
+            Self::Abs => args[0].abs(),
+            Self::Exp => args[0].exp(),
+            Self::Log => args[0].ln(),
+            Self::Sqrt => args[0].sqrt(),
+            Self::Pow => args[0].powf(args[1]),
+            Self::Min => args[0].min(args[1]),
+            Self::Max => args[0].max(args[1]),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Expression {
+    Constant(ScoreType),
+    Score,
+    Field(PayloadKeyType),
+    Negate(Box<Expression>),
+    Add(Box<Expression>, Box<Expression>),
+    Subtract(Box<Expression>, Box<Expression>),
+    Multiply(Box<Expression>, Box<Expression>),
+    Divide(Box<Expression>, Box<Expression>),
+    Call(Function, Vec<Expression>),
+}
+
+impl Expression {
+    fn evaluate(&self, score: ScoreType, payload: Option<&Payload>) -> ScoreType {
+        match self {
+            Expression::Constant(value) => *value,
+            Expression::Score => score,
+            Expression::Field(key) => payload.map_or(0.0, |payload| field_value(payload, key)),
+            Expression::Negate(expr) => -expr.evaluate(score, payload),
+            Expression::Add(left, right) => {
+                left.evaluate(score, payload) + right.evaluate(score, payload)
+            }
+            Expression::Subtract(left, right) => {
+                left.evaluate(score, payload) - right.evaluate(score, payload)
+            }
+            Expression::Multiply(left, right) => {
+                left.evaluate(score, payload) * right.evaluate(score, payload)
+            }
+            Expression::Divide(left, right) => {
+                left.evaluate(score, payload) / right.evaluate(score, payload)
+            }
+            Expression::Call(function, args) => {
+                let args: Vec<_> = args
+                    .iter()
+                    .map(|arg| arg.evaluate(score, payload))
+                    .collect();
+                function.apply(&args)
+            }
+        }
+    }
+
+    fn collect_fields(&self, fields: &mut Vec<PayloadKeyType>) {
+        match self {
+            Expression::Constant(_) | Expression::Score => {}
+            Expression::Field(key) => {
+                if !fields.contains(key) {
+                    fields.push(key.clone());
+                }
+            }
+            Expression::Negate(expr) => expr.collect_fields(fields),
+            Expression::Add(left, right)
+            | Expression::Subtract(left, right)
+            | Expression::Multiply(left, right)
+            | Expression::Divide(left, right) => {
+                left.collect_fields(fields);
+                right.collect_fields(fields);
+            }
+            Expression::Call(_, args) => args.iter().for_each(|arg| arg.collect_fields(fields)),
+        }
+    }
+}
+
+/// Numeric value of the payload field, 0 if it is missing or not a number
+fn field_value(payload: &Payload, key: &PayloadKeyType) -> ScoreType {
+    let to_number = |value: &Value| match value {
+        Value::Number(number) => number.as_f64().map(|number| number as ScoreType),
+        Value::Bool(flag) => Some(if *flag { 1.0 } else { 0.0 }),
+        _ => None,
+    };
+    payload
+        .get_value(key)
+        .into_iter()
+        .find_map(|value| match value {
+            Value::Array(values) => values.iter().find_map(to_number),
+            value => to_number(value),
+        })
+        .unwrap_or(0.0)
+}
+
+/// Parsed formula of the score
+#[derive(Debug, Clone)]
+pub struct ScoreFormula {
+    source: String,
+    expression: Expression,
+    /// Payload fields, referenced by the formula
+    fields: Vec<PayloadKeyType>,
+}
+
+impl fmt::Display for ScoreFormula {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.source)
+    }
+}
+
+impl ScoreFormula {
+    pub fn parse(source: &str) -> Result<Self, String> {
+        let mut parser = Parser {
+            tokens: tokenize(source)?,
+            position: 0,
+        };
+        let expression = parser.expression()?;
+        if let Some(token) = parser.peek() {
+            return Err(format!("unexpected `{token}` in the formula"));
+        }
+        let mut fields = Vec::new();
+        expression.collect_fields(&mut fields);
+        Ok(Self {
+            source: source.to_string(),
+            expression,
+            fields,
+        })
+    }
+
+    pub fn fields(&self) -> &[PayloadKeyType] {
+        &self.fields
+    }
+
+    /// New score of the candidate, non-finite values are ranked last
+    pub fn evaluate(&self, score: ScoreType, payload: Option<&Payload>) -> ScoreType {
+        let value = self.expression.evaluate(score, payload);
+        if value.is_finite() {
+            value
+        } else {
+            ScoreType::MIN
+        }
+    }
+
+    /// Rescore the candidates with their payloads, and keep the best `limit` of them
+    pub fn rescore(
+        &self,
+        candidates: Vec<ScoredPoint>,
+        payloads: &HashMap<PointIdType, Payload>,
+        limit: usize,
+    ) -> Vec<ScoredPoint> {
+        let mut rescored: Vec<_> = candidates
+            .into_iter()
+            .map(|mut point| {
+                point.score = self.evaluate(point.score, payloads.get(&point.id));
+                point
+            })
+            .collect();
+        // Stable sort, so candidates with equal scores keep the order of the vector scores
+        rescored.sort_by(|a, b| b.score.total_cmp(&a.score));
+        rescored.truncate(limit);
+        rescored
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Number(ScoreType),
+    Name(String),
+    Plus,
+    Minus,
+    Star,
+    Slash,
+    Comma,
+    Open,
+    Close,
+}
+
+impl fmt::Display for Token {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Token::Number(number) => write!(f, "{number}"),
+            Token::Name(name) => f.write_str(name),
+            Token::Plus => f.write_str("+"),
+            Token::Minus => f.write_str("-"),
+            Token::Star => f.write_str("*"),
+            Token::Slash => f.write_str("/"),
+            Token::Comma => f.write_str(","),
+            Token::Open => f.write_str("("),
+            Token::Close => f.write_str(")"),
+        }
+    }
+}
+
+fn tokenize(source: &str) -> Result<Vec<Token>, String> {
+    let mut tokens = Vec::new();
+    let mut chars = source.char_indices().peekable();
+    while let Some((start, c)) = chars.next() {
+        let token = match c {
+            c if c.is_whitespace() => continue,
+            '+' => Token::Plus,
+            '-' => Token::Minus,
+            '*' => Token::Star,
+            '/' => Token::Slash,
+            ',' => Token::Comma,
+            '(' => Token::Open,
+            ')' => Token::Close,
+            c if c.is_ascii_digit() || c == '.' => {
+                let mut end = start + c.len_utf8();
+                while let Some(&(index, c)) = chars.peek() {
+                    if !(c.is_ascii_digit() || c == '.') {
+                        break;
+                    }
+                    end = index + c.len_utf8();
+                    chars.next();
+                }
+                let number = &source[start..end];
+                Token::Number(
+                    number
+                        .parse()
+                        .map_err(|_| format!("invalid number `{number}` in the formula"))?,
+                )
+            }
+            c if c.is_alphabetic() || c == '_' => {
+                let mut end = start + c.len_utf8();
+                while let Some(&(index, c)) = chars.peek() {
+                    if !(c.is_alphanumeric() || c == '_' || c == '.') {
+                        break;
+                    }
+                    end = index + c.len_utf8();
+                    chars.next();
+                }
+                Token::Name(source[start..end].to_string())
+            }
+            c => return Err(format!("unexpected `{c}` in the formula")),
+        };
+        tokens.push(token);
+    }
+    Ok(tokens)
+}
+
+/// Recursive descent parser of the formula, `*` and `/` bind tighter than `+` and `-`
+struct Parser {
+    tokens: Vec<Token>,
+    position: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.position)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.position).cloned();
+        self.position += 1;
+        token
+    }
+
+    fn expect(&mut self, expected: Token) -> Result<(), String> {
+        match self.next() {
+            Some(token) if token == expected => Ok(()),
+            Some(token) => Err(format!(
+                "expected `{expected}`, found `{token}` in the formula"
+            )),
+            None => Err(format!("expected `{expected}` at the end of the formula")),
+        }
+    }
+
+    fn expression(&mut self) -> Result<Expression, String> {
+        let mut left = self.term()?;
+        loop {
+            left = match self.peek() {
+                Some(Token::Plus) => {
+                    self.next();
+                    Expression::Add(Box::new(left), Box::new(self.term()?))
+                }
+                Some(Token::Minus) => {
+                    self.next();
+                    Expression::Subtract(Box::new(left), Box::new(self.term()?))
+                }
+                _ => return Ok(left),
+            };
+        }
+    }
+
+    fn term(&mut self) -> Result<Expression, String> {
+        let mut left = self.factor()?;
+        loop {
+            left = match self.peek() {
+                Some(Token::Star) => {
+                    self.next();
+                    Expression::Multiply(Box::new(left), Box::new(self.factor()?))
+                }
+                Some(Token::Slash) => {
+                    self.next();
+                    Expression::Divide(Box::new(left), Box::new(self.factor()?))
+                }
+                _ => return Ok(left),
+            };
+        }
+    }
+
+    fn factor(&mut self) -> Result<Expression, String> {
+        match self.next() {
+            Some(Token::Number(number)) => Ok(Expression::Constant(number)),
+            Some(Token::Minus) => Ok(Expression::Negate(Box::new(self.factor()?))),
+            Some(Token::Open) => {
+                let expression = self.expression()?;
+                self.expect(Token::Close)?;
+                Ok(expression)
+            }
+            Some(Token::Name(name)) if self.peek() == Some(&Token::Open) => {
+                let function = Function::from_name(&name)
+                    .ok_or_else(|| format!("unknown function `{name}` in the formula"))?;
+                self.next();
+                let mut args = vec![self.expression()?];
+                while self.peek() == Some(&Token::Comma) {
+                    self.next();
+                    args.push(self.expression()?);
+                }
+                self.expect(Token::Close)?;
+                if args.len() != function.arity() {
+                    return Err(format!(
+                        "function `{name}` takes {} arguments, {} given",
+                        function.arity(),
+                        args.len(),
+                    ));
+                }
+                Ok(Expression::Call(function, args))
+            }
+            Some(Token::Name(name)) if name == SCORE_VARIABLE => Ok(Expression::Score),
+            Some(Token::Name(name)) => Ok(Expression::Field(name)),
+            Some(token) => Err(format!("unexpected `{token}` in the formula")),
+            None => Err("unexpected end of the formula".to_string()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn evaluate(formula: &str, score: ScoreType, payload: Value) -> ScoreType {
+        let payload: Payload = serde_json::from_value(payload).unwrap();
+        ScoreFormula::parse(formula)
+            .unwrap()
+            .evaluate(score, Some(&payload))
+    }
+
+    #[test]
+    fn test_evaluate_formula() {
+        let value = evaluate(
+            "score * 0.8 + log(popularity) * 0.2",
+            0.5,
+            json!({ "popularity": std::f32::consts::E }),
+        );
+        assert!((value - 0.6).abs() < 1e-6);
+
+        assert_eq!(evaluate("-(score - 1) * 2 / 4", 3.0, json!({})), -1.0);
+        assert_eq!(
+            evaluate(
+                "max(score, meta.rating)",
+                1.0,
+                json!({ "meta": { "rating": 4 } })
+            ),
+            4.0
+        );
+        assert_eq!(
+            evaluate("pow(boost, 2) + abs(-1)", 0.0, json!({ "boost": [3, 1] })),
+            10.0
+        );
+        // Missing and non-numeric fields are 0, booleans are 0 or 1
+        assert_eq!(
+            evaluate(
+                "missing + name + premium",
+                0.0,
+                json!({ "name": "x", "premium": true })
+            ),
+            1.0
+        );
+        // Non-finite values are ranked last
+        assert_eq!(evaluate("log(missing)", 1.0, json!({})), ScoreType::MIN);
+    }
+
+    #[test]
+    fn test_parse_formula() {
+        let formula = ScoreFormula::parse("score * price + sqrt(price) - rating").unwrap();
+        assert_eq!(
+            formula.fields(),
+            ["price".to_string(), "rating".to_string()]
+        );
+
+        for invalid in [
+            "",
+            "score +",
+            "score * (1 + price",
+            "score price",
+            "unknown(score)",
+            "min(score)",
+            "score % 2",
+            "1.2.3",
+        ] {
+            assert!(ScoreFormula::parse(invalid).is_err(), "{invalid}");
+        }
+    }
+
+    #[test]
+    fn test_rescore() {
+        let point = |id: u64, score| ScoredPoint {
+            id: id.into(),
+            version: 0,
+            score,
+            payload: None,
+            vector: None,
+            shard_key: None,
+        };
+        let payloads = HashMap::from([
+            (
+                2.into(),
+                serde_json::from_value(json!({ "boost": 1.0 })).unwrap(),
+            ),
+            (
+                3.into(),
+                serde_json::from_value(json!({ "boost": 0.5 })).unwrap(),
+            ),
+        ]);
+        let formula = ScoreFormula::parse("score + boost").unwrap();
+        let rescored = formula.rescore(
+            vec![point(1, 0.9), point(2, 0.8), point(3, 0.7)],
+            &payloads,
+            2,
+        );
+        let ids: Vec<_> = rescored.iter().map(|point| point.id).collect();
+        assert_eq!(ids, vec![2.into(), 3.into()]);
+        assert!((rescored[0].score - 1.8).abs() < 1e-6);
+    }
+}
@@ -0,0 +1,240 @@
+//! Sparse matrix of the pairwise similarities of the selected points
+//!
+//! Points are selected by their ids or as a random sample of the points, which match the filter.
+//! Each selected point is searched with its own vector among the other selected points, in a single
+//! batch, so only its `limit` nearest neighbors are kept in the row of the matrix.
+//!
+//! The matrix is built by two regular collection requests: a scroll, which selects the points with
+//! their vectors, and a batch search, restricted to the selected IDs. Both are checked by the
+//! strict mode, which could limit the number of selected points. The requests are not atomic:
+//! points are searched with the vectors they had at the scroll, but neighbors are scored with
+//! their current vectors, and points deleted in between are not among the neighbors.
+
+use schemars::JsonSchema;
+use segment::data_types::vectors::{NamedVectorStruct, DEFAULT_VECTOR_NAME};
+use segment::types::{
+    Condition, Filter, HasIdCondition, PointIdType, ScoreType, ScoredPoint, SearchParams,
+    WithPayloadInterface, WithVector,
+};
+use serde::{Deserialize, Serialize};
+use validator::{Validate, ValidationError};
+
+use crate::operations::shard_key_selector::ShardKeySelector;
+use crate::operations::types::{
+    CoreSearchRequest, CoreSearchRequestBatch, QueryEnum, Record, Sample, ScrollRequestInternal,
+    UsingVector,
+};
+
+const DEFAULT_SAMPLE: usize = 10;
+const DEFAULT_LIMIT: usize = 3;
+
+fn default_limit() -> usize {
+    DEFAULT_LIMIT
+}
+
+/// Nearest neighbors of each of the selected points among the other selected points
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone)]
+#[validate(schema(function = "validate_search_matrix_request"))]
+#[serde(rename_all = "snake_case")]
+pub struct SearchMatrixRequest {
+    /// Points to compute the matrix of. Can't be combined with `sample`
+    #[validate(length(min = 1))]
+    pub ids: Option<Vec<PointIdType>>,
+    /// Number of points to select randomly, if `ids` are not specified. Default: 10
+    #[validate(range(min = 1))]
+    pub sample: Option<usize>,
+    /// Select only points which satisfies this conditions, both as rows and as neighbors
+    #[validate]
+    pub filter: Option<Filter>,
+    /// Name of the vector to compare the points by. Default: the unnamed vector
+    pub using: Option<UsingVector>,
+    /// Additional search params
+    #[validate]
+    pub params: Option<SearchParams>,
+    /// Max number of neighbors of each point. Default: 3
+    #[serde(default = "default_limit")]
+    #[validate(range(min = 1))]
+    pub limit: usize,
+    /// Specify in which shards to look for the points, if not specified - look in all shards
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub shard_key: Option<ShardKeySelector>,
+    /// Token returned by the update: only search in replicas, which have already applied it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub consistency_token: Option<String>,
+}
+
+/// Row of the matrix: the nearest neighbors of a point
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub struct SearchMatrixRow {
+    /// Id of the selected point
+    pub id: PointIdType,
+    /// Nearest neighbors of the point, from the most similar one
+    pub neighbors: Vec<SearchMatrixNeighbor>,
+}
+
+/// Similarity of a point to its neighbor
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub struct SearchMatrixNeighbor {
+    /// Id of the neighbor
+    pub id: PointIdType,
+    /// Similarity score of the points
+    pub score: ScoreType,
+}
+
+fn validate_search_matrix_request(request: &SearchMatrixRequest) -> Result<(), ValidationError> {
+    if request.ids.is_some() && request.sample.is_some() {
+        return Err(ValidationError::new(
+            "only one of `ids` and `sample` can be specified",
+        ));
+    }
+    Ok(())
+}
+
+impl SearchMatrixRequest {
+    fn vector_name(&self) -> String {
+        match &self.using {
+            None => DEFAULT_VECTOR_NAME.to_string(),
+            Some(UsingVector::Name(name)) => name.clone(),
+        }
+    }
+
+    /// Scroll of the selected points, together with the vectors to compare them by
+    pub fn select_request(&self) -> ScrollRequestInternal {
+        let with_vector = WithVector::Selector(vec![self.vector_name()]);
+        match &self.ids {
+            Some(ids) => ScrollRequestInternal {
+                limit: Some(ids.len()),
+                filter: Some(self.filter_with_ids(ids.clone())),
+                with_payload: Some(WithPayloadInterface::Bool(false)),
+                with_vector,
+                ..Default::default()
+            },
+            None => ScrollRequestInternal {
+                limit: Some(self.sample.unwrap_or(DEFAULT_SAMPLE)),
+                filter: self.filter.clone(),
+                with_payload: Some(WithPayloadInterface::Bool(false)),
+                with_vector,
+                sample: Some(Sample::Random),
+                ..Default::default()
+            },
+        }
+    }
+
+    /// User filter, restricted to the given points
+    fn filter_with_ids(&self, ids: impl IntoIterator<Item = PointIdType>) -> Filter {
+        Filter {
+            should: None,
+            must: Some(
+                self.filter
+                    .iter()
+                    .cloned()
+                    .map(Condition::Filter)
+                    .chain([Condition::HasId(HasIdCondition {
+                        has_id: ids.into_iter().collect(),
+                    })])
+                    .collect(),
+            ),
+            must_not: None,
+        }
+    }
+
+    /// Search of the neighbors of each selected point, in the order of the points
+    ///
+    /// Points without the vector are skipped. Each search is looking for one extra neighbor,
+    /// because the point itself is usually its own nearest neighbor.
+    pub fn searches(&self, points: &[Record]) -> (Vec<PointIdType>, CoreSearchRequestBatch) {
+        let vector_name = self.vector_name();
+        let vectors: Vec<_> = points
+            .iter()
+            .filter_map(|point| {
+                let vector = point.vector.as_ref()?.get(&vector_name)?.to_owned();
+                Some((point.id, vector))
+            })
+            .collect();
+        let filter = self.filter_with_ids(vectors.iter().map(|(id, _)| *id));
+
+        let ids = vectors.iter().map(|(id, _)| *id).collect();
+        let searches = vectors
+            .into_iter()
+            .map(|(_, vector)| CoreSearchRequest {
+                query: QueryEnum::Nearest(NamedVectorStruct::new_from_vector(
+                    vector,
+                    vector_name.clone(),
+                )),
+                filter: Some(filter.clone()),
+                params: self.params,
+                limit: self.limit + 1,
+                offset: 0,
+                with_payload: Some(WithPayloadInterface::Bool(false)),
+                with_vector: None,
+                score_threshold: None,
+                prefetch: None,
+                rescore: None,
+                distinct_by: None,
+            })
+            .collect();
+        (ids, CoreSearchRequestBatch { searches })
+    }
+}
+
+/// Rows of the matrix from the results of the searches, without the points themselves
+pub fn matrix_rows(
+    ids: Vec<PointIdType>,
+    results: Vec<Vec<ScoredPoint>>,
+    limit: usize,
+) -> Vec<SearchMatrixRow> {
+    ids.into_iter()
+        .zip(results)
+        .map(|(id, points)| SearchMatrixRow {
+            id,
+            neighbors: points
+                .into_iter()
+                .filter(|point| point.id != id)
+                .take(limit)
+                .map(|point| SearchMatrixNeighbor {
+                    id: point.id,
+                    score: point.score,
+                })
+                .collect(),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn point(id: u64, score: ScoreType) -> ScoredPoint {
+        ScoredPoint {
+            id: id.into(),
+            version: 0,
+            score,
+            payload: None,
+            vector: None,
+            shard_key: None,
+        }
+    }
+
+    #[test]
+    fn test_matrix_rows() {
+        let results = vec![
+            vec![point(1, 1.0), point(2, 0.9), point(3, 0.5)],
+            // Duplicate of the point could be scored above the point itself
+            vec![point(3, 1.0), point(2, 1.0), point(1, 0.9)],
+            vec![point(3, 1.0)],
+        ];
+
+        let rows = matrix_rows(vec![1.into(), 2.into(), 3.into()], results, 2);
+        let neighbors = |row: &SearchMatrixRow| {
+            row.neighbors
+                .iter()
+                .map(|neighbor| (neighbor.id, neighbor.score))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(neighbors(&rows[0]), vec![(2.into(), 0.9), (3.into(), 0.5)]);
+        assert_eq!(neighbors(&rows[1]), vec![(3.into(), 1.0), (1.into(), 0.9)]);
+        assert!(rows[2].neighbors.is_empty());
+    }
+}
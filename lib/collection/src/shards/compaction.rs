@@ -0,0 +1,116 @@
+//! Manual compaction of the shard segments
+//!
+//! After a bulk load a shard may end up with many small segments, which the optimizers keep as
+//! long as their number doesn't exceed the configured one. Compaction merges the smallest
+//! segments on demand, so operators can compact the shard before the traffic ramps up.
+//!
+//! Merges are executed as regular optimizations: they share the limit of optimization threads
+//! of the shard, are reported in the optimizer telemetry and are cancelled on shutdown.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use tokio::sync::mpsc;
+
+use crate::operations::types::{CollectionResult, CompactionResult};
+use crate::optimizers_builder::build_compaction_optimizer;
+use crate::shards::local_shard::LocalShard;
+use crate::update_handler::UpdateHandler;
+
+/// Interval of checking for a free optimization thread, if all of them are busy
+const FREE_THREAD_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+impl LocalShard {
+    /// Merge the smallest segments of the shard, until no more segments can be merged without
+    /// exceeding the maximum segment size
+    ///
+    /// Unlike the merge optimizer, the configured number of segments is ignored. Each round of
+    /// merges waits for a free optimization thread, and the result is returned once all merges
+    /// are finished.
+    pub async fn compact(&self) -> CollectionResult<CompactionResult> {
+        let optimizer = {
+            let config = self.collection_config.read().await;
+            build_compaction_optimizer(
+                &self.path,
+                &config.params,
+                &config.optimizer_config,
+                &config.hnsw_config,
+                &config.quantization_config,
+            )
+        };
+        let (optimization_handles, max_optimization_threads) = {
+            let update_handler = self.update_handler.lock().await;
+            (
+                update_handler.optimization_handles.clone(),
+                update_handler.max_optimization_threads.max(1),
+            )
+        };
+
+        let mut result = CompactionResult {
+            segments_before: self.segments.read().len(),
+            ..Default::default()
+        };
+
+        loop {
+            // Wait for a free optimization thread
+            loop {
+                let running = optimization_handles
+                    .lock()
+                    .await
+                    .iter()
+                    .filter(|handle| !handle.is_finished())
+                    .count();
+                if running < max_optimization_threads {
+                    break;
+                }
+                tokio::time::sleep(FREE_THREAD_POLL_INTERVAL).await;
+            }
+
+            // Each merge holds a sender, so the channel is closed once all merges are finished,
+            // either completed or cancelled
+            let (finished_tx, mut finished_rx) = mpsc::unbounded_channel();
+            let mut handles = UpdateHandler::launch_optimization(
+                Arc::new(vec![optimizer.clone()]),
+                self.optimizers_log.clone(),
+                self.segments.clone(),
+                move |optimized| {
+                    let _ = finished_tx.send(optimized);
+                },
+            );
+            if handles.is_empty() {
+                break;
+            }
+            let launched = handles.len();
+            optimization_handles.lock().await.append(&mut handles);
+
+            let mut merged = 0;
+            while let Some(optimized) = finished_rx.recv().await {
+                if optimized {
+                    merged += 1;
+                }
+            }
+            result.merges += merged;
+
+            log::debug!(
+                "Compaction of shard {}: {merged}/{launched} merges finished",
+                self.path.display(),
+            );
+
+            // Some merges were cancelled, retrying them would likely fail again
+            if merged < launched {
+                break;
+            }
+        }
+
+        result.segments_after = self.segments.read().len();
+        log::info!(
+            "Compacted shard {}: {} merges, {} -> {} segments",
+            self.path.display(),
+            result.merges,
+            result.segments_before,
+            result.segments_after,
+        );
+
+        Ok(result)
+    }
+}
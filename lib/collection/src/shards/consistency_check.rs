@@ -0,0 +1,206 @@
+//! Background check of the consistency of the shard state
+//!
+//! Operations are written to the WAL before they are applied to the segments, and the WAL is
+//! truncated only up to the version, which is persisted by the segments. If any of these
+//! invariants is broken, e.g. by a bug or by a lost write of the disk, operations might be lost
+//! on the next restart, without any error. The check periodically compares the versions of the
+//! segments with the WAL, and the updates, acknowledged by the replicas of the shard, so such
+//! problems are found before they become a data loss visible to users.
+//!
+//! Anomalies are reported in the log and in the optimizer status of the shard, which turns the
+//! status of the collection red. They are not fixed automatically.
+
+use std::fmt;
+use std::path::PathBuf;
+
+use segment::entry::entry_point::SegmentEntry as _;
+use segment::types::SeqNumberType;
+
+use crate::collection_manager::holders::segment_holder::{LockedSegment, LockedSegmentHolder};
+use crate::shards::local_shard::LockedWal;
+use crate::shards::shard::PeerId;
+
+/// Inconsistency of the shard state, found by the check
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConsistencyAnomaly {
+    /// Segment has applied an operation, which is not in the WAL
+    SegmentAheadOfWal {
+        segment_path: PathBuf,
+        segment_version: SeqNumberType,
+        wal_last_version: SeqNumberType,
+    },
+    /// WAL is truncated past the persisted version of a segment with not yet persisted changes
+    WalTruncatedPastSegment {
+        segment_path: PathBuf,
+        persisted_version: SeqNumberType,
+        wal_first_version: SeqNumberType,
+    },
+    /// Active replica failed the latest updates, but it is not deactivated
+    ReplicaMissedUpdates {
+        peer_id: PeerId,
+        missed_updates: u64,
+    },
+}
+
+impl fmt::Display for ConsistencyAnomaly {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConsistencyAnomaly::SegmentAheadOfWal {
+                segment_path,
+                segment_version,
+                wal_last_version,
+            } => write!(
+                f,
+                "segment {} has applied operation {segment_version}, which is not in the WAL, \
+                 last operation of the WAL is {wal_last_version}",
+                segment_path.display(),
+            ),
+            ConsistencyAnomaly::WalTruncatedPastSegment {
+                segment_path,
+                persisted_version,
+                wal_first_version,
+            } => write!(
+                f,
+                "WAL is truncated up to operation {wal_first_version}, but segment {} has \
+                 persisted operations up to {persisted_version} only, later ones are lost on \
+                 restart",
+                segment_path.display(),
+            ),
+            ConsistencyAnomaly::ReplicaMissedUpdates {
+                peer_id,
+                missed_updates,
+            } => write!(
+                f,
+                "active replica on peer {peer_id} failed the last {missed_updates} updates",
+            ),
+        }
+    }
+}
+
+/// Versions of a single segment
+#[derive(Debug, Clone)]
+struct SegmentVersions {
+    path: PathBuf,
+    version: SeqNumberType,
+    /// `None` if the segment was never flushed
+    persisted_version: Option<SeqNumberType>,
+}
+
+/// Compare versions of the segments of the shard with its WAL
+///
+/// Segments, which are being optimized, are skipped.
+pub fn check_wal_consistency(
+    segments: &LockedSegmentHolder,
+    wal: &LockedWal,
+) -> Vec<ConsistencyAnomaly> {
+    // Persisted versions only grow and the WAL is truncated after the segments are flushed,
+    // so the first version of the WAL is read before the segments
+    let wal_first_version = wal.lock().first_index();
+
+    let to_check: Vec<_> = segments
+        .read()
+        .iter()
+        .filter_map(|(_, segment)| match segment {
+            LockedSegment::Original(segment) => Some(segment.clone()),
+            LockedSegment::Proxy(_) => None,
+        })
+        .collect();
+    let versions: Vec<_> = to_check
+        .iter()
+        .map(|segment| {
+            let segment = segment.read();
+            SegmentVersions {
+                path: segment.current_path.clone(),
+                version: segment.version(),
+                persisted_version: *segment.persisted_version.lock(),
+            }
+        })
+        .collect();
+
+    // Operations are written to the WAL before they are applied, so it is read after the segments
+    let wal_last_version = wal.lock().last_index();
+
+    check_versions(wal_first_version, wal_last_version, versions)
+}
+
+fn check_versions(
+    wal_first_version: SeqNumberType,
+    wal_last_version: SeqNumberType,
+    segments: Vec<SegmentVersions>,
+) -> Vec<ConsistencyAnomaly> {
+    let mut anomalies = Vec::new();
+    for segment in segments {
+        if segment.version > wal_last_version {
+            anomalies.push(ConsistencyAnomaly::SegmentAheadOfWal {
+                segment_path: segment.path.clone(),
+                segment_version: segment.version,
+                wal_last_version,
+            });
+        }
+        // Operations after the persisted version have to be recovered from the WAL
+        let Some(persisted_version) = segment.persisted_version else {
+            continue;
+        };
+        if segment.version > persisted_version && wal_first_version > persisted_version + 1 {
+            anomalies.push(ConsistencyAnomaly::WalTruncatedPastSegment {
+                segment_path: segment.path,
+                persisted_version,
+                wal_first_version,
+            });
+        }
+    }
+    anomalies
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn segment(
+        name: &str,
+        version: SeqNumberType,
+        persisted: Option<SeqNumberType>,
+    ) -> SegmentVersions {
+        SegmentVersions {
+            path: PathBuf::from(name),
+            version,
+            persisted_version: persisted,
+        }
+    }
+
+    #[test]
+    fn test_check_versions() {
+        let consistent = vec![
+            segment("flushed", 50, Some(50)),
+            segment("unsaved", 100, Some(60)),
+            segment("new", 100, None),
+        ];
+        assert!(check_versions(40, 100, consistent.clone()).is_empty());
+        // WAL is truncated up to the persisted version
+        assert!(check_versions(61, 100, consistent).is_empty());
+
+        let anomalies = check_versions(
+            80,
+            90,
+            vec![
+                segment("flushed", 50, Some(50)),
+                segment("unsaved", 100, Some(60)),
+            ],
+        );
+        assert_eq!(
+            anomalies,
+            vec![
+                ConsistencyAnomaly::SegmentAheadOfWal {
+                    segment_path: PathBuf::from("unsaved"),
+                    segment_version: 100,
+                    wal_last_version: 90,
+                },
+                ConsistencyAnomaly::WalTruncatedPastSegment {
+                    segment_path: PathBuf::from("unsaved"),
+                    persisted_version: 60,
+                    wal_first_version: 80,
+                },
+            ],
+        );
+    }
+}
@@ -1,25 +1,31 @@
+use std::time::Duration;
+
 use api::grpc::conversions::{convert_shard_key_from_grpc_opt, payload_to_proto};
 use api::grpc::qdrant::points_selector::PointsSelectorOneOf;
+use api::grpc::qdrant::points_update_operation::{ClearPayload, Operation, PointStructList};
 use api::grpc::qdrant::{
-    ClearPayloadPoints, ClearPayloadPointsInternal, CreateFieldIndexCollection,
-    CreateFieldIndexCollectionInternal, DeleteFieldIndexCollection,
+    points_update_operation, ClearPayloadPoints, ClearPayloadPointsInternal,
+    CreateFieldIndexCollection, CreateFieldIndexCollectionInternal, DeleteFieldIndexCollection,
     DeleteFieldIndexCollectionInternal, DeletePayloadPoints, DeletePayloadPointsInternal,
     DeletePointVectors, DeletePoints, DeletePointsInternal, DeleteVectorsInternal, PointVectors,
-    PointsIdsList, PointsSelector, SetPayloadPoints, SetPayloadPointsInternal, SyncPoints,
-    SyncPointsInternal, UpdatePointVectors, UpdateVectorsInternal, UpsertPoints,
+    PointsIdsList, PointsSelector, PointsUpdateOperation, SetPayloadPoints,
+    SetPayloadPointsInternal, SyncPoints, SyncPointsInternal, UpdateBatchInternal,
+    UpdateBatchPoints, UpdatePointVectors, UpdateVectorsInternal, UpsertPoints,
     UpsertPointsInternal, VectorsSelector,
 };
 use segment::types::{Filter, PayloadFieldSchema, PayloadSchemaParams, PointIdType, ScoredPoint};
 use tonic::Status;
 
-use crate::operations::conversions::write_ordering_to_proto;
-use crate::operations::payload_ops::{DeletePayloadOp, SetPayloadOp};
+use crate::operations::consistency_params::WriteConsistency;
+use crate::operations::conversions::{update_priority_to_proto, write_ordering_to_proto};
+use crate::operations::payload_ops::{DeletePayloadOp, PayloadOps, SetPayloadOp};
 use crate::operations::point_ops::{
-    PointInsertOperationsInternal, PointSyncOperation, WriteOrdering,
+    PointInsertOperationsInternal, PointOperations, PointSyncOperation, WriteOrdering,
 };
-use crate::operations::types::CollectionResult;
-use crate::operations::vector_ops::UpdateVectorsOp;
-use crate::operations::CreateIndex;
+use crate::operations::types::{CollectionError, CollectionResult};
+use crate::operations::update_priority::UpdatePriority;
+use crate::operations::vector_ops::{UpdateVectorsOp, VectorOperations};
+use crate::operations::{CollectionUpdateOperations, CreateIndex};
 use crate::shards::shard::ShardId;
 
 pub fn internal_sync_points(
@@ -28,6 +34,10 @@ pub fn internal_sync_points(
     points_sync_operation: PointSyncOperation,
     wait: bool,
     ordering: Option<WriteOrdering>,
+    shard_deactivation_timeout: Option<Duration>,
+    write_consistency: Option<WriteConsistency>,
+    timeout: Option<Duration>,
+    priority: Option<UpdatePriority>,
 ) -> CollectionResult<SyncPointsInternal> {
     Ok(SyncPointsInternal {
         shard_id,
@@ -42,6 +52,10 @@ pub fn internal_sync_points(
             from_id: points_sync_operation.from_id.map(|x| x.into()),
             to_id: points_sync_operation.to_id.map(|x| x.into()),
             ordering: ordering.map(write_ordering_to_proto),
+            shard_deactivation_timeout: shard_deactivation_timeout.map(|timeout| timeout.as_secs()),
+            write_consistency: write_consistency.map(Into::into),
+            timeout: timeout.map(|timeout| timeout.as_secs()),
+            priority: priority.map(update_priority_to_proto),
         }),
     })
 }
@@ -52,6 +66,10 @@ pub fn internal_upsert_points(
     point_insert_operations: PointInsertOperationsInternal,
     wait: bool,
     ordering: Option<WriteOrdering>,
+    shard_deactivation_timeout: Option<Duration>,
+    write_consistency: Option<WriteConsistency>,
+    timeout: Option<Duration>,
+    priority: Option<UpdatePriority>,
 ) -> CollectionResult<UpsertPointsInternal> {
     Ok(UpsertPointsInternal {
         shard_id,
@@ -66,7 +84,12 @@ pub fn internal_upsert_points(
                     .collect::<Result<Vec<_>, Status>>()?,
             },
             ordering: ordering.map(write_ordering_to_proto),
+            shard_deactivation_timeout: shard_deactivation_timeout.map(|timeout| timeout.as_secs()),
+            write_consistency: write_consistency.map(Into::into),
+            timeout: timeout.map(|timeout| timeout.as_secs()),
+            priority: priority.map(update_priority_to_proto),
             shard_key_selector: None,
+            ttl: None,
         }),
     })
 }
@@ -77,6 +100,10 @@ pub fn internal_delete_points(
     ids: Vec<PointIdType>,
     wait: bool,
     ordering: Option<WriteOrdering>,
+    shard_deactivation_timeout: Option<Duration>,
+    write_consistency: Option<WriteConsistency>,
+    timeout: Option<Duration>,
+    priority: Option<UpdatePriority>,
 ) -> DeletePointsInternal {
     DeletePointsInternal {
         shard_id,
@@ -89,6 +116,10 @@ pub fn internal_delete_points(
                 })),
             }),
             ordering: ordering.map(write_ordering_to_proto),
+            shard_deactivation_timeout: shard_deactivation_timeout.map(|timeout| timeout.as_secs()),
+            write_consistency: write_consistency.map(Into::into),
+            timeout: timeout.map(|timeout| timeout.as_secs()),
+            priority: priority.map(update_priority_to_proto),
             shard_key_selector: None,
         }),
     }
@@ -100,6 +131,10 @@ pub fn internal_delete_points_by_filter(
     filter: Filter,
     wait: bool,
     ordering: Option<WriteOrdering>,
+    shard_deactivation_timeout: Option<Duration>,
+    write_consistency: Option<WriteConsistency>,
+    timeout: Option<Duration>,
+    priority: Option<UpdatePriority>,
 ) -> DeletePointsInternal {
     DeletePointsInternal {
         shard_id,
@@ -110,6 +145,10 @@ pub fn internal_delete_points_by_filter(
                 points_selector_one_of: Some(PointsSelectorOneOf::Filter(filter.into())),
             }),
             ordering: ordering.map(write_ordering_to_proto),
+            shard_deactivation_timeout: shard_deactivation_timeout.map(|timeout| timeout.as_secs()),
+            write_consistency: write_consistency.map(Into::into),
+            timeout: timeout.map(|timeout| timeout.as_secs()),
+            priority: priority.map(update_priority_to_proto),
             shard_key_selector: None,
         }),
     }
@@ -121,6 +160,10 @@ pub fn internal_update_vectors(
     update_vectors: UpdateVectorsOp,
     wait: bool,
     ordering: Option<WriteOrdering>,
+    shard_deactivation_timeout: Option<Duration>,
+    write_consistency: Option<WriteConsistency>,
+    timeout: Option<Duration>,
+    priority: Option<UpdatePriority>,
 ) -> UpdateVectorsInternal {
     UpdateVectorsInternal {
         shard_id,
@@ -136,6 +179,10 @@ pub fn internal_update_vectors(
                 })
                 .collect(),
             ordering: ordering.map(write_ordering_to_proto),
+            shard_deactivation_timeout: shard_deactivation_timeout.map(|timeout| timeout.as_secs()),
+            write_consistency: write_consistency.map(Into::into),
+            timeout: timeout.map(|timeout| timeout.as_secs()),
+            priority: priority.map(update_priority_to_proto),
             shard_key_selector: None,
         }),
     }
@@ -148,6 +195,10 @@ pub fn internal_delete_vectors(
     vector_names: Vec<String>,
     wait: bool,
     ordering: Option<WriteOrdering>,
+    shard_deactivation_timeout: Option<Duration>,
+    write_consistency: Option<WriteConsistency>,
+    timeout: Option<Duration>,
+    priority: Option<UpdatePriority>,
 ) -> DeleteVectorsInternal {
     DeleteVectorsInternal {
         shard_id,
@@ -163,6 +214,10 @@ pub fn internal_delete_vectors(
                 names: vector_names,
             }),
             ordering: ordering.map(write_ordering_to_proto),
+            shard_deactivation_timeout: shard_deactivation_timeout.map(|timeout| timeout.as_secs()),
+            write_consistency: write_consistency.map(Into::into),
+            timeout: timeout.map(|timeout| timeout.as_secs()),
+            priority: priority.map(update_priority_to_proto),
             shard_key_selector: None,
         }),
     }
@@ -175,6 +230,10 @@ pub fn internal_delete_vectors_by_filter(
     vector_names: Vec<String>,
     wait: bool,
     ordering: Option<WriteOrdering>,
+    shard_deactivation_timeout: Option<Duration>,
+    write_consistency: Option<WriteConsistency>,
+    timeout: Option<Duration>,
+    priority: Option<UpdatePriority>,
 ) -> DeleteVectorsInternal {
     DeleteVectorsInternal {
         shard_id,
@@ -188,6 +247,10 @@ pub fn internal_delete_vectors_by_filter(
                 names: vector_names,
             }),
             ordering: ordering.map(write_ordering_to_proto),
+            shard_deactivation_timeout: shard_deactivation_timeout.map(|timeout| timeout.as_secs()),
+            write_consistency: write_consistency.map(Into::into),
+            timeout: timeout.map(|timeout| timeout.as_secs()),
+            priority: priority.map(update_priority_to_proto),
             shard_key_selector: None,
         }),
     }
@@ -199,6 +262,10 @@ pub fn internal_set_payload(
     set_payload: SetPayloadOp,
     wait: bool,
     ordering: Option<WriteOrdering>,
+    shard_deactivation_timeout: Option<Duration>,
+    write_consistency: Option<WriteConsistency>,
+    timeout: Option<Duration>,
+    priority: Option<UpdatePriority>,
 ) -> SetPayloadPointsInternal {
     let points_selector = if let Some(points) = set_payload.points {
         Some(PointsSelector {
@@ -220,7 +287,12 @@ pub fn internal_set_payload(
             payload: payload_to_proto(set_payload.payload),
             points_selector,
             ordering: ordering.map(write_ordering_to_proto),
+            shard_deactivation_timeout: shard_deactivation_timeout.map(|timeout| timeout.as_secs()),
+            write_consistency: write_consistency.map(Into::into),
+            timeout: timeout.map(|timeout| timeout.as_secs()),
+            priority: priority.map(update_priority_to_proto),
             shard_key_selector: None,
+            if_version: set_payload.if_version,
         }),
     }
 }
@@ -231,6 +303,10 @@ pub fn internal_delete_payload(
     delete_payload: DeletePayloadOp,
     wait: bool,
     ordering: Option<WriteOrdering>,
+    shard_deactivation_timeout: Option<Duration>,
+    write_consistency: Option<WriteConsistency>,
+    timeout: Option<Duration>,
+    priority: Option<UpdatePriority>,
 ) -> DeletePayloadPointsInternal {
     let points_selector = if let Some(points) = delete_payload.points {
         Some(PointsSelector {
@@ -252,6 +328,10 @@ pub fn internal_delete_payload(
             keys: delete_payload.keys,
             points_selector,
             ordering: ordering.map(write_ordering_to_proto),
+            shard_deactivation_timeout: shard_deactivation_timeout.map(|timeout| timeout.as_secs()),
+            write_consistency: write_consistency.map(Into::into),
+            timeout: timeout.map(|timeout| timeout.as_secs()),
+            priority: priority.map(update_priority_to_proto),
             shard_key_selector: None,
         }),
     }
@@ -263,6 +343,10 @@ pub fn internal_clear_payload(
     points: Vec<PointIdType>,
     wait: bool,
     ordering: Option<WriteOrdering>,
+    shard_deactivation_timeout: Option<Duration>,
+    write_consistency: Option<WriteConsistency>,
+    timeout: Option<Duration>,
+    priority: Option<UpdatePriority>,
 ) -> ClearPayloadPointsInternal {
     ClearPayloadPointsInternal {
         shard_id,
@@ -275,6 +359,10 @@ pub fn internal_clear_payload(
                 })),
             }),
             ordering: ordering.map(write_ordering_to_proto),
+            shard_deactivation_timeout: shard_deactivation_timeout.map(|timeout| timeout.as_secs()),
+            write_consistency: write_consistency.map(Into::into),
+            timeout: timeout.map(|timeout| timeout.as_secs()),
+            priority: priority.map(update_priority_to_proto),
             shard_key_selector: None,
         }),
     }
@@ -286,6 +374,10 @@ pub fn internal_clear_payload_by_filter(
     filter: Filter,
     wait: bool,
     ordering: Option<WriteOrdering>,
+    shard_deactivation_timeout: Option<Duration>,
+    write_consistency: Option<WriteConsistency>,
+    timeout: Option<Duration>,
+    priority: Option<UpdatePriority>,
 ) -> ClearPayloadPointsInternal {
     ClearPayloadPointsInternal {
         shard_id,
@@ -296,6 +388,10 @@ pub fn internal_clear_payload_by_filter(
                 points_selector_one_of: Some(PointsSelectorOneOf::Filter(filter.into())),
             }),
             ordering: ordering.map(write_ordering_to_proto),
+            shard_deactivation_timeout: shard_deactivation_timeout.map(|timeout| timeout.as_secs()),
+            write_consistency: write_consistency.map(Into::into),
+            timeout: timeout.map(|timeout| timeout.as_secs()),
+            priority: priority.map(update_priority_to_proto),
             shard_key_selector: None,
         }),
     }
@@ -307,6 +403,10 @@ pub fn internal_create_index(
     create_index: CreateIndex,
     wait: bool,
     ordering: Option<WriteOrdering>,
+    shard_deactivation_timeout: Option<Duration>,
+    write_consistency: Option<WriteConsistency>,
+    timeout: Option<Duration>,
+    priority: Option<UpdatePriority>,
 ) -> CreateFieldIndexCollectionInternal {
     let (field_type, field_index_params) = create_index
         .field_schema
@@ -353,6 +453,10 @@ pub fn internal_create_index(
             field_type,
             field_index_params,
             ordering: ordering.map(write_ordering_to_proto),
+            shard_deactivation_timeout: shard_deactivation_timeout.map(|timeout| timeout.as_secs()),
+            write_consistency: write_consistency.map(Into::into),
+            timeout: timeout.map(|timeout| timeout.as_secs()),
+            priority: priority.map(update_priority_to_proto),
         }),
     }
 }
@@ -363,6 +467,10 @@ pub fn internal_delete_index(
     delete_index: String,
     wait: bool,
     ordering: Option<WriteOrdering>,
+    shard_deactivation_timeout: Option<Duration>,
+    write_consistency: Option<WriteConsistency>,
+    timeout: Option<Duration>,
+    priority: Option<UpdatePriority>,
 ) -> DeleteFieldIndexCollectionInternal {
     DeleteFieldIndexCollectionInternal {
         shard_id,
@@ -371,10 +479,183 @@ pub fn internal_delete_index(
             wait: Some(wait),
             field_name: delete_index,
             ordering: ordering.map(write_ordering_to_proto),
+            shard_deactivation_timeout: shard_deactivation_timeout.map(|timeout| timeout.as_secs()),
+            write_consistency: write_consistency.map(Into::into),
+            timeout: timeout.map(|timeout| timeout.as_secs()),
+            priority: priority.map(update_priority_to_proto),
         }),
     }
 }
 
+pub fn internal_update_batch(
+    shard_id: Option<ShardId>,
+    collection_name: String,
+    operations: Vec<CollectionUpdateOperations>,
+    wait: bool,
+    ordering: Option<WriteOrdering>,
+    shard_deactivation_timeout: Option<Duration>,
+    write_consistency: Option<WriteConsistency>,
+    timeout: Option<Duration>,
+    priority: Option<UpdatePriority>,
+) -> CollectionResult<UpdateBatchInternal> {
+    Ok(UpdateBatchInternal {
+        shard_id,
+        update_batch: Some(UpdateBatchPoints {
+            collection_name,
+            wait: Some(wait),
+            operations: operations
+                .into_iter()
+                .map(points_update_operation)
+                .collect::<CollectionResult<_>>()?,
+            ordering: ordering.map(write_ordering_to_proto),
+            shard_deactivation_timeout: shard_deactivation_timeout.map(|timeout| timeout.as_secs()),
+            write_consistency: write_consistency.map(Into::into),
+            timeout: timeout.map(|timeout| timeout.as_secs()),
+            priority: priority.map(update_priority_to_proto),
+            all_or_nothing: Some(true),
+        }),
+    })
+}
+
+fn ids_selector(ids: Vec<PointIdType>) -> PointsSelector {
+    PointsSelector {
+        points_selector_one_of: Some(PointsSelectorOneOf::Points(PointsIdsList {
+            ids: ids.into_iter().map(|id| id.into()).collect(),
+        })),
+    }
+}
+
+fn filter_selector(filter: Filter) -> PointsSelector {
+    PointsSelector {
+        points_selector_one_of: Some(PointsSelectorOneOf::Filter(filter.into())),
+    }
+}
+
+/// Convert the operation of a batch into the operation of the batch update API
+fn points_update_operation(
+    operation: CollectionUpdateOperations,
+) -> CollectionResult<PointsUpdateOperation> {
+    let operation = match operation {
+        CollectionUpdateOperations::PointOperation(point_operation) => match point_operation {
+            PointOperations::UpsertPoints(point_insert_operations) => {
+                Operation::Upsert(PointStructList {
+                    points: match point_insert_operations {
+                        PointInsertOperationsInternal::PointsBatch(batch) => batch.try_into()?,
+                        PointInsertOperationsInternal::PointsList(list) => list
+                            .into_iter()
+                            .map(|point| point.try_into())
+                            .collect::<Result<Vec<_>, Status>>()?,
+                    },
+                    shard_key_selector: None,
+                })
+            }
+            PointOperations::DeletePoints { ids } => {
+                Operation::DeletePoints(points_update_operation::DeletePoints {
+                    points: Some(ids_selector(ids)),
+                    shard_key_selector: None,
+                })
+            }
+            PointOperations::DeletePointsByFilter(filter) => {
+                Operation::DeletePoints(points_update_operation::DeletePoints {
+                    points: Some(filter_selector(filter)),
+                    shard_key_selector: None,
+                })
+            }
+            PointOperations::SyncPoints(_) => {
+                return Err(CollectionError::service_error(
+                    "Sync of points can't be a part of a batch",
+                ))
+            }
+        },
+        CollectionUpdateOperations::VectorOperation(vector_operation) => match vector_operation {
+            VectorOperations::UpdateVectors(update_vectors) => {
+                Operation::UpdateVectors(points_update_operation::UpdateVectors {
+                    points: update_vectors
+                        .points
+                        .into_iter()
+                        .map(|point| PointVectors {
+                            id: Some(point.id.into()),
+                            vectors: Some(point.vector.into()),
+                        })
+                        .collect(),
+                    shard_key_selector: None,
+                })
+            }
+            VectorOperations::DeleteVectors(ids, vector_names) => {
+                Operation::DeleteVectors(points_update_operation::DeleteVectors {
+                    points_selector: Some(ids_selector(ids.points)),
+                    vectors: Some(VectorsSelector {
+                        names: vector_names,
+                    }),
+                    shard_key_selector: None,
+                })
+            }
+            VectorOperations::DeleteVectorsByFilter(filter, vector_names) => {
+                Operation::DeleteVectors(points_update_operation::DeleteVectors {
+                    points_selector: Some(filter_selector(filter)),
+                    vectors: Some(VectorsSelector {
+                        names: vector_names,
+                    }),
+                    shard_key_selector: None,
+                })
+            }
+        },
+        CollectionUpdateOperations::PayloadOperation(payload_operation) => {
+            match payload_operation {
+                PayloadOps::SetPayload(set_payload) => {
+                    Operation::SetPayload(set_payload_operation(set_payload))
+                }
+                PayloadOps::OverwritePayload(set_payload) => {
+                    Operation::OverwritePayload(set_payload_operation(set_payload))
+                }
+                PayloadOps::DeletePayload(delete_payload) => {
+                    Operation::DeletePayload(points_update_operation::DeletePayload {
+                        points_selector: match delete_payload.points {
+                            Some(points) => Some(ids_selector(points)),
+                            None => delete_payload.filter.map(filter_selector),
+                        },
+                        keys: delete_payload.keys,
+                        shard_key_selector: None,
+                    })
+                }
+                PayloadOps::ClearPayload { points } => Operation::ClearPayload(ClearPayload {
+                    points: Some(ids_selector(points)),
+                    shard_key_selector: None,
+                }),
+                PayloadOps::ClearPayloadByFilter(filter) => Operation::ClearPayload(ClearPayload {
+                    points: Some(filter_selector(filter)),
+                    shard_key_selector: None,
+                }),
+            }
+        }
+        CollectionUpdateOperations::FieldIndexOperation(_) => {
+            return Err(CollectionError::service_error(
+                "Field index operation can't be a part of a batch",
+            ))
+        }
+        CollectionUpdateOperations::Batch(_) => {
+            return Err(CollectionError::service_error(
+                "Nested batches of operations are not supported",
+            ))
+        }
+    };
+    Ok(PointsUpdateOperation {
+        operation: Some(operation),
+    })
+}
+
+fn set_payload_operation(set_payload: SetPayloadOp) -> points_update_operation::SetPayload {
+    points_update_operation::SetPayload {
+        payload: payload_to_proto(set_payload.payload),
+        points_selector: match set_payload.points {
+            Some(points) => Some(ids_selector(points)),
+            None => set_payload.filter.map(filter_selector),
+        },
+        shard_key_selector: None,
+        if_version: set_payload.if_version,
+    }
+}
+
 pub fn try_scored_point_from_grpc(
     point: api::grpc::qdrant::ScoredPoint,
     with_payload: bool,
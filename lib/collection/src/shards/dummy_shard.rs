@@ -1,20 +1,26 @@
 use std::path::Path;
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use async_trait::async_trait;
+use segment::data_types::order_by::OrderBy;
 use segment::types::{
     ExtendedPointId, Filter, ScoredPoint, WithPayload, WithPayloadInterface, WithVector,
 };
 use tokio::runtime::Handle;
 
+use crate::collection_manager::optimizers::Tracker;
 use crate::operations::types::{
-    CollectionError, CollectionInfo, CollectionResult, CoreSearchRequestBatch,
-    CountRequestInternal, CountResult, PointRequestInternal, Record, UpdateResult,
+    CollectionError, CollectionInfo, CollectionResult, CompactionResult, CoreSearchRequestBatch,
+    CountRequestInternal, CountResult, PointRequestInternal, Record, Sample, UpdateResult,
+    WarmUpResult,
 };
+use crate::operations::update_priority::UpdatePriority;
 use crate::operations::CollectionUpdateOperations;
+use crate::shards::scroll_session::SessionRef;
 use crate::shards::shard_trait::ShardOperation;
 use crate::shards::telemetry::LocalShardTelemetry;
+use crate::shards::wal_archive::WalArchiveResult;
 
 #[derive(Clone, Debug)]
 pub struct DummyShard {
@@ -41,6 +47,26 @@ impl DummyShard {
         self.dummy()
     }
 
+    pub async fn warm_up(&self) -> CollectionResult<WarmUpResult> {
+        self.dummy()
+    }
+
+    pub async fn shutdown(&self, _deadline: Instant) -> CollectionResult<()> {
+        Ok(())
+    }
+
+    pub async fn compact(&self) -> CollectionResult<CompactionResult> {
+        self.dummy()
+    }
+
+    pub async fn archive_wal(&self, _archive_path: &Path) -> CollectionResult<WalArchiveResult> {
+        self.dummy()
+    }
+
+    pub async fn stop_wal_archiving(&self) -> CollectionResult<()> {
+        self.dummy()
+    }
+
     pub fn get_telemetry_data(&self) -> LocalShardTelemetry {
         LocalShardTelemetry {
             variant_name: Some("dummy shard".into()),
@@ -49,6 +75,10 @@ impl DummyShard {
         }
     }
 
+    pub fn running_optimizations(&self) -> Vec<Tracker> {
+        vec![]
+    }
+
     fn dummy<T>(&self) -> CollectionResult<T> {
         Err(CollectionError::service_error(self.message.to_string()))
     }
@@ -60,6 +90,7 @@ impl ShardOperation for DummyShard {
         &self,
         _: CollectionUpdateOperations,
         _: bool,
+        _: Option<UpdatePriority>,
     ) -> CollectionResult<UpdateResult> {
         self.dummy()
     }
@@ -72,6 +103,10 @@ impl ShardOperation for DummyShard {
         _: &WithPayloadInterface,
         _: &WithVector,
         _: Option<&Filter>,
+        _: Option<&OrderBy>,
+        _: Option<Sample>,
+        _: Option<u64>,
+        _: Option<&SessionRef>,
         _: &Handle,
     ) -> CollectionResult<Vec<Record>> {
         self.dummy()
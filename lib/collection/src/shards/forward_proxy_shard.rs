@@ -1,8 +1,9 @@
 use std::path::Path;
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use async_trait::async_trait;
+use segment::data_types::order_by::OrderBy;
 use segment::types::{
     ExtendedPointId, Filter, PointIdType, ScoredPoint, WithPayload, WithPayloadInterface,
     WithVector,
@@ -11,16 +12,22 @@ use tokio::runtime::Handle;
 use tokio::sync::Mutex;
 
 use super::update_tracker::UpdateTracker;
+use crate::collection_manager::optimizers::Tracker;
 use crate::operations::point_ops::{PointOperations, PointStruct, PointSyncOperation};
 use crate::operations::types::{
-    CollectionError, CollectionInfo, CollectionResult, CoreSearchRequestBatch,
-    CountRequestInternal, CountResult, PointRequestInternal, Record, UpdateResult,
+    CollectionError, CollectionInfo, CollectionResult, CompactionResult, CoreSearchRequestBatch,
+    CountRequestInternal, CountResult, PointRequestInternal, Record, Sample, UpdateResult,
+    WarmUpResult,
 };
+use crate::operations::update_priority::UpdatePriority;
 use crate::operations::{CollectionUpdateOperations, CreateIndex, FieldIndexOperations};
 use crate::shards::local_shard::LocalShard;
 use crate::shards::remote_shard::RemoteShard;
+use crate::shards::scroll_session::SessionRef;
 use crate::shards::shard_trait::ShardOperation;
 use crate::shards::telemetry::LocalShardTelemetry;
+use crate::shards::transfer::throttle::BatchSize;
+use crate::shards::wal_archive::WalArchiveResult;
 
 /// ForwardProxyShard
 ///
@@ -63,6 +70,7 @@ impl ForwardProxyShard {
                         }),
                     ),
                     false,
+                    None,
                 )
                 .await?;
         }
@@ -70,7 +78,7 @@ impl ForwardProxyShard {
     }
 
     /// Move batch of points to the remote shard.
-    /// Returns an offset of the next batch to be transferred.
+    /// Returns an offset of the next batch to be transferred, and the size of the moved batch.
     ///
     /// # Cancel safety
     ///
@@ -80,7 +88,7 @@ impl ForwardProxyShard {
         offset: Option<PointIdType>,
         batch_size: usize,
         runtime_handle: &Handle,
-    ) -> CollectionResult<Option<PointIdType>> {
+    ) -> CollectionResult<(Option<PointIdType>, BatchSize)> {
         debug_assert!(batch_size > 0);
         let limit = batch_size + 1;
         let _update_lock = self.update_lock.lock().await;
@@ -92,6 +100,10 @@ impl ForwardProxyShard {
                 &WithPayloadInterface::Bool(true),
                 &true.into(),
                 None,
+                None,
+                None,
+                None,
+                None,
                 runtime_handle,
             )
             .await?;
@@ -107,6 +119,7 @@ impl ForwardProxyShard {
             batch.into_iter().map(|point| point.try_into()).collect();
 
         let points = points?;
+        let transferred = BatchSize::of(&points);
 
         // Use sync API to leverage potentially existing points
         let insert_points_operation = {
@@ -124,10 +137,10 @@ impl ForwardProxyShard {
 
         // TODO: Is cancelling `RemoteShard::update` safe for *receiver*?
         self.remote_shard
-            .update(insert_points_operation, wait)
+            .update(insert_points_operation, wait, None)
             .await?;
 
-        Ok(next_page_offset)
+        Ok((next_page_offset, transferred))
     }
 
     pub fn deconstruct(self) -> (LocalShard, RemoteShard) {
@@ -150,10 +163,34 @@ impl ForwardProxyShard {
         self.wrapped_shard.on_optimizer_config_update().await
     }
 
+    pub async fn warm_up(&self) -> CollectionResult<WarmUpResult> {
+        self.wrapped_shard.warm_up().await
+    }
+
+    pub async fn shutdown(&self, deadline: Instant) -> CollectionResult<()> {
+        self.wrapped_shard.shutdown(deadline).await
+    }
+
+    pub async fn compact(&self) -> CollectionResult<CompactionResult> {
+        self.wrapped_shard.compact().await
+    }
+
+    pub async fn archive_wal(&self, archive_path: &Path) -> CollectionResult<WalArchiveResult> {
+        self.wrapped_shard.archive_wal(archive_path).await
+    }
+
+    pub async fn stop_wal_archiving(&self) -> CollectionResult<()> {
+        self.wrapped_shard.stop_wal_archiving().await
+    }
+
     pub fn get_telemetry_data(&self) -> LocalShardTelemetry {
         self.wrapped_shard.get_telemetry_data()
     }
 
+    pub fn running_optimizations(&self) -> Vec<Tracker> {
+        self.wrapped_shard.running_optimizations()
+    }
+
     pub fn update_tracker(&self) -> &UpdateTracker {
         self.wrapped_shard.update_tracker()
     }
@@ -166,15 +203,18 @@ impl ShardOperation for ForwardProxyShard {
         &self,
         operation: CollectionUpdateOperations,
         wait: bool,
+        priority: Option<UpdatePriority>,
     ) -> CollectionResult<UpdateResult> {
         let _update_lock = self.update_lock.lock().await;
         let local_shard = &self.wrapped_shard;
         // Shard update is within a write lock scope, because we need a way to block the shard updates
         // during the transfer restart and finalization.
-        local_shard.update(operation.clone(), wait).await?;
+        local_shard
+            .update(operation.clone(), wait, priority)
+            .await?;
 
         self.remote_shard
-            .update(operation, false)
+            .update(operation, false, priority)
             .await
             .map_err(|err| CollectionError::forward_proxy_error(self.remote_shard.peer_id, err))
     }
@@ -187,6 +227,10 @@ impl ShardOperation for ForwardProxyShard {
         with_payload_interface: &WithPayloadInterface,
         with_vector: &WithVector,
         filter: Option<&Filter>,
+        order_by: Option<&OrderBy>,
+        sample: Option<Sample>,
+        as_of: Option<u64>,
+        session: Option<&SessionRef>,
         search_runtime_handle: &Handle,
     ) -> CollectionResult<Vec<Record>> {
         let local_shard = &self.wrapped_shard;
@@ -197,6 +241,10 @@ impl ShardOperation for ForwardProxyShard {
                 with_payload_interface,
                 with_vector,
                 filter,
+                order_by,
+                sample,
+                as_of,
+                session,
                 search_runtime_handle,
             )
             .await
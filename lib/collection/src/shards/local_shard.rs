@@ -2,12 +2,15 @@ use std::collections::{BTreeSet, HashMap};
 use std::mem::size_of;
 use std::ops::Deref;
 use std::path::{Path, PathBuf};
+use std::sync::atomic::Ordering;
 use std::sync::Arc;
 use std::thread;
+use std::time::Instant;
 
 use arc_swap::ArcSwap;
 use common::panic;
 use indicatif::{ProgressBar, ProgressStyle};
+use io::file_operations::atomic_save_json;
 use itertools::Itertools;
 use parking_lot::{Mutex as ParkingMutex, RwLock};
 use segment::data_types::vectors::VectorElementType;
@@ -16,38 +19,43 @@ use segment::index::field_index::CardinalityEstimation;
 use segment::segment::Segment;
 use segment::segment_constructor::{build_segment, load_segment};
 use segment::types::{
-    CompressionRatio, Filter, PayloadIndexInfo, PayloadKeyType, PayloadStorageType, PointIdType,
-    QuantizationConfig, SegmentConfig, SegmentType,
+    CompressionRatio, Filter, PayloadIndexInfo, PayloadKeyType, PointIdType, QuantizationConfig,
+    SegmentConfig, SegmentType,
 };
 use segment::utils::mem::Mem;
 use tokio::fs::{copy, create_dir_all, remove_dir_all};
 use tokio::runtime::Handle;
 use tokio::sync::mpsc::Sender;
-use tokio::sync::{mpsc, oneshot, Mutex, RwLock as TokioRwLock};
+use tokio::sync::{mpsc, oneshot, Mutex, RwLock as TokioRwLock, Semaphore};
 use wal::{Wal, WalOptions};
 
 use super::update_tracker::UpdateTracker;
 use crate::collection_manager::collection_updater::CollectionUpdater;
 use crate::collection_manager::holders::segment_holder::{LockedSegment, SegmentHolder};
-use crate::collection_manager::optimizers::TrackerLog;
+use crate::collection_manager::optimizers::{Tracker, TrackerLog};
 use crate::common::file_utils::move_dir;
-use crate::config::CollectionConfig;
+use crate::config::{CollectionConfig, CollectionParams};
 use crate::operations::shared_storage_config::SharedStorageConfig;
 use crate::operations::types::{
     check_sparse_compatible_with_segment_config, CollectionError, CollectionInfoInternal,
-    CollectionResult, CollectionStatus, OptimizersStatus,
+    CollectionResult, CollectionStatus, OptimizersStatus, WarmUpResult,
 };
+use crate::operations::update_priority::bulk_lane_size;
 use crate::operations::CollectionUpdateOperations;
 use crate::optimizers_builder::{build_optimizers, clear_temp_segments};
+use crate::shards::scroll_session::ScrollSessions;
 use crate::shards::shard::ShardId;
 use crate::shards::shard_config::{ShardConfig, SHARD_CONFIG_FILE};
 use crate::shards::telemetry::{LocalShardTelemetry, OptimizerTelemetry};
-use crate::shards::CollectionId;
+use crate::shards::time_travel::PointHistory;
+use crate::shards::{wal_archive, CollectionId};
 use crate::update_handler::{Optimizer, UpdateHandler, UpdateSignal};
 use crate::wal::SerdeWal;
 
 pub type LockedWal = Arc<ParkingMutex<SerdeWal<CollectionUpdateOperations>>>;
 
+const SEGMENT_VERSIONS_FILE: &str = "segment_versions.json";
+
 /// LocalShard
 ///
 /// LocalShard is an entity that can be moved between peers and contains some part of one collections data.
@@ -64,6 +72,10 @@ pub struct LocalShard {
     pub(super) path: PathBuf,
     pub(super) optimizers: Arc<Vec<Arc<Optimizer>>>,
     pub(super) optimizers_log: Arc<ParkingMutex<TrackerLog>>,
+    pub(super) point_history: Arc<ParkingMutex<PointHistory>>,
+    pub(super) scroll_sessions: Arc<ParkingMutex<ScrollSessions>>,
+    /// Slots of the update queue, which bulk updates could take
+    pub(super) bulk_lane: Arc<Semaphore>,
     update_runtime: Handle,
 }
 
@@ -106,7 +118,10 @@ impl LocalShard {
         Ok(())
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub async fn new(
+        id: ShardId,
+        collection_id: CollectionId,
         segment_holder: SegmentHolder,
         collection_config: Arc<TokioRwLock<CollectionConfig>>,
         shared_storage_config: Arc<SharedStorageConfig>,
@@ -119,23 +134,45 @@ impl LocalShard {
         let config = collection_config.read().await;
         let locked_wal = Arc::new(ParkingMutex::new(wal));
         let optimizers_log = Arc::new(ParkingMutex::new(Default::default()));
+        let point_history = Arc::new(ParkingMutex::new(PointHistory::default()));
+        let scroll_sessions = Arc::new(ParkingMutex::new(ScrollSessions::default()));
 
         let mut update_handler = UpdateHandler::new(
+            collection_id,
+            id,
             shared_storage_config.clone(),
+            collection_config.clone(),
+            point_history.clone(),
+            scroll_sessions.clone(),
             optimizers.clone(),
             optimizers_log.clone(),
             update_runtime.clone(),
             segment_holder.clone(),
             locked_wal.clone(),
             config.optimizer_config.flush_interval_sec,
+            config.optimizer_config.flush_dirty_threshold,
+            config.optimizer_config.flush_dirty_operations,
             config.optimizer_config.max_optimization_threads,
         );
 
+        // Keep operations, which are not archived yet, in WAL
+        let wal_archived_version = wal_archive::load_wal_archived_version(shard_path)
+            .unwrap_or_else(|err| {
+                log::error!("Can't load WAL archive state, WAL is not truncated: {err}");
+                0
+            });
+        update_handler
+            .wal_archived_version
+            .store(wal_archived_version, Ordering::Relaxed);
+
         let (update_sender, update_receiver) =
             mpsc::channel(shared_storage_config.update_queue_size);
-        update_handler.run_workers(update_receiver);
+        update_handler.run_workers(update_sender.clone(), update_receiver);
 
         let update_tracker = segment_holder.read().update_tracker();
+        let bulk_lane = Arc::new(Semaphore::new(bulk_lane_size(
+            shared_storage_config.update_queue_size,
+        )));
 
         drop(config); // release `shared_config` from borrow checker
 
@@ -151,6 +188,9 @@ impl LocalShard {
             update_runtime,
             optimizers,
             optimizers_log,
+            point_history,
+            scroll_sessions,
+            bulk_lane,
         }
     }
 
@@ -177,7 +217,8 @@ impl LocalShard {
             wal_path.to_str().unwrap(),
             (&collection_config_read.wal_config).into(),
         )
-        .map_err(|e| CollectionError::service_error(format!("Wal error: {e}")))?;
+        .map_err(|e| CollectionError::service_error(format!("Wal error: {e}")))?
+        .with_compression(collection_config_read.wal_config.wal_compression);
 
         let segment_dirs = std::fs::read_dir(&segments_path).map_err(|err| {
             CollectionError::service_error(format!(
@@ -227,7 +268,7 @@ impl LocalShard {
             collection_config_read
                 .params
                 .vectors
-                .check_compatible_with_segment_config(&segment.config().vector_data, true)?;
+                .check_segment_vectors_known(&segment.config().vector_data)?;
             collection_config_read
                 .params
                 .sparse_vectors
@@ -249,6 +290,12 @@ impl LocalShard {
             log::debug!("Deduplicated {} points", res);
         }
 
+        Self::ensure_appendable_segment_with_vectors(
+            &segments_path,
+            &mut segment_holder,
+            &collection_config_read.params,
+        )?;
+
         clear_temp_segments(shard_path);
         let optimizers = build_optimizers(
             shard_path,
@@ -261,6 +308,8 @@ impl LocalShard {
         drop(collection_config_read); // release `shared_config` from borrow checker
 
         let collection = LocalShard::new(
+            id,
+            collection_id.clone(),
             segment_holder,
             collection_config,
             shared_storage_config,
@@ -309,6 +358,11 @@ impl LocalShard {
         shard_path.join("segments")
     }
 
+    /// Versions of the segments in the shard snapshot, used to make incremental snapshots
+    pub fn segment_versions_path(shard_path: &Path) -> PathBuf {
+        shard_path.join(SEGMENT_VERSIONS_FILE)
+    }
+
     pub async fn build_local(
         id: ShardId,
         collection_id: CollectionId,
@@ -371,11 +425,7 @@ impl LocalShard {
             let segment_config = SegmentConfig {
                 vector_data: vector_params.clone(),
                 sparse_vector_data: sparse_vector_params.clone(),
-                payload_storage_type: if config.params.on_disk_payload {
-                    PayloadStorageType::OnDisk
-                } else {
-                    PayloadStorageType::InMemory
-                },
+                payload_storage_type: config.params.payload_storage_type(),
             };
             let segment = thread::Builder::new()
                 .name(format!("shard-build-{collection_id}-{id}"))
@@ -403,7 +453,8 @@ impl LocalShard {
         }
 
         let wal: SerdeWal<CollectionUpdateOperations> =
-            SerdeWal::new(wal_path.to_str().unwrap(), (&config.wal_config).into())?;
+            SerdeWal::new(wal_path.to_str().unwrap(), (&config.wal_config).into())?
+                .with_compression(config.wal_config.wal_compression);
 
         let optimizers = build_optimizers(
             shard_path,
@@ -416,6 +467,8 @@ impl LocalShard {
         drop(config); // release `shared_config` from borrow checker
 
         let collection = LocalShard::new(
+            id,
+            collection_id,
             segment_holder,
             collection_config,
             shared_storage_config,
@@ -498,6 +551,9 @@ impl LocalShard {
         self.segments.read().flush_all(true)?;
         bar.finish();
 
+        // Operations, which were truncated from WAL before the restart, are applied as well
+        self.update_tracker.set_applied_operation(wal.last_index());
+
         Ok(())
     }
 
@@ -508,7 +564,7 @@ impl LocalShard {
         let (update_sender, update_receiver) =
             mpsc::channel(self.shared_storage_config.update_queue_size);
         // makes sure that the Stop signal is the last one in this channel
-        let old_sender = self.update_sender.swap(Arc::new(update_sender));
+        let old_sender = self.update_sender.swap(Arc::new(update_sender.clone()));
         old_sender.send(UpdateSignal::Stop).await?;
         update_handler.stop_flush_worker();
 
@@ -521,17 +577,135 @@ impl LocalShard {
             &config.quantization_config,
         );
         update_handler.optimizers = new_optimizers;
+        Self::ensure_appendable_segment_with_vectors(
+            &Self::segments_path(&self.path),
+            &mut self.segments.write(),
+            &config.params,
+        )?;
         update_handler.flush_interval_sec = config.optimizer_config.flush_interval_sec;
-        update_handler.run_workers(update_receiver);
+        update_handler.flush_dirty_threshold = config.optimizer_config.flush_dirty_threshold;
+        update_handler.flush_dirty_operations = config.optimizer_config.flush_dirty_operations;
+        update_handler.run_workers(update_sender, update_receiver);
         self.update_sender.load().send(UpdateSignal::Nop).await?;
 
         Ok(())
     }
 
+    /// Make sure there is an appendable segment with all vectors of the collection
+    ///
+    /// Segments, created before vectors were added to the collection, don't have them. Points,
+    /// which get the new vectors, are moved into a segment which has them.
+    fn ensure_appendable_segment_with_vectors(
+        segments_path: &Path,
+        segment_holder: &mut SegmentHolder,
+        params: &CollectionParams,
+    ) -> CollectionResult<()> {
+        let vector_names: Vec<_> = params
+            .vectors
+            .params_iter()
+            .map(|(name, _)| name.to_string())
+            .chain(
+                params
+                    .sparse_vectors
+                    .iter()
+                    .flatten()
+                    .map(|(name, _)| name.clone()),
+            )
+            .collect();
+        if !segment_holder
+            .appendable_segments_with_vectors(&vector_names)
+            .is_empty()
+        {
+            return Ok(());
+        }
+
+        log::debug!("Creating appendable segment with vectors {vector_names:?}");
+        let segment_config = SegmentConfig {
+            vector_data: params.into_base_vector_data()?,
+            sparse_vector_data: params.into_sparse_vector_data()?,
+            payload_storage_type: params.payload_storage_type(),
+        };
+        let segment = build_segment(segments_path, &segment_config, true)?;
+        segment_holder.add(segment);
+        Ok(())
+    }
+
+    /// Read memory-mapped vector storages and HNSW graphs of all shard segments into page cache.
+    ///
+    /// Payload indexes are kept in RAM and do not require warming up.
+    pub async fn warm_up(&self) -> CollectionResult<WarmUpResult> {
+        let tasks: Vec<_> = self
+            .segments
+            .read()
+            .iter()
+            .filter_map(|(_, segment)| match segment {
+                LockedSegment::Original(segment) => {
+                    Some(segment.read().prefault_mmap_pages_tasks())
+                }
+                // Proxy segments only exist during optimization, result of which is prefaulted anyway
+                LockedSegment::Proxy(_) => None,
+            })
+            .flatten()
+            .collect();
+
+        let shard_path = self.path.clone();
+        let result = tokio::task::spawn_blocking(move || {
+            let total = tasks.len();
+            let mut result = WarmUpResult::default();
+            for (i, task) in tasks.into_iter().enumerate() {
+                task.exec();
+                result.files += 1;
+                result.bytes += task.size();
+                log::debug!(
+                    "Warm-up of shard {}: {}/{total} files, {} bytes read",
+                    shard_path.display(),
+                    i + 1,
+                    result.bytes,
+                );
+            }
+            result
+        })
+        .await?;
+
+        Ok(result)
+    }
+
+    /// Stop the shard for the node shutdown
+    ///
+    /// Updates, which are already queued, are applied, optimizations are cancelled, and all data
+    /// is flushed to disk. Waiting for the workers is limited by the `deadline`.
+    pub async fn shutdown(&self, deadline: Instant) -> CollectionResult<()> {
+        // Apply queued updates, they are already written to WAL
+        let (tx, rx) = oneshot::channel();
+        let update_sender = self.update_sender.load();
+        if update_sender.send(UpdateSignal::Plunger(tx)).await.is_ok()
+            && tokio::time::timeout_at(deadline.into(), rx).await.is_err()
+        {
+            log::warn!(
+                "Queued updates of shard {} were not applied before the shutdown deadline, \
+                 they will be recovered from WAL",
+                self.path.display(),
+            );
+        }
+        if let Err(err) = update_sender.send(UpdateSignal::Stop).await {
+            log::debug!("Update worker is already stopped: {err}");
+        }
+
+        self.update_handler
+            .lock()
+            .await
+            .shutdown_workers(deadline)
+            .await
+    }
+
     /// Finishes ongoing update tasks
     pub async fn stop_gracefully(&self) {
-        if let Err(err) = self.update_sender.load().send(UpdateSignal::Stop).await {
-            log::warn!("Error sending stop signal to update handler: {}", err);
+        let update_sender = self.update_sender.load();
+        // Update worker is already stopped, if the shard was shut down
+        if !update_sender.is_closed() {
+            if let Err(err) = update_sender.send(UpdateSignal::Stop).await {
+                log::warn!("Error sending stop signal to update handler: {}", err);
+            }
         }
 
         self.stop_flush_worker().await;
@@ -561,6 +735,11 @@ impl LocalShard {
                 std::fs::remove_file(&entry_path)?;
             }
         }
+
+        let segment_versions_path = Self::segment_versions_path(snapshot_path);
+        if segment_versions_path.exists() {
+            std::fs::remove_file(segment_versions_path)?;
+        }
         Ok(())
     }
 
@@ -597,7 +776,12 @@ impl LocalShard {
             let segments_read = segments.read();
 
             // Do not change segments while snapshotting
-            segments_read.snapshot_all_segments(&temp_path, &snapshot_segments_shard_path)?;
+            let segment_versions =
+                segments_read.snapshot_all_segments(&temp_path, &snapshot_segments_shard_path)?;
+            atomic_save_json(
+                &Self::segment_versions_path(&snapshot_shard_path_owned),
+                &segment_versions,
+            )?;
 
             if save_wal {
                 // snapshot all shard's WAL
@@ -704,6 +888,11 @@ impl LocalShard {
         Ok(all_points)
     }
 
+    /// Optimizations of the shard, which are still running
+    pub fn running_optimizations(&self) -> Vec<Tracker> {
+        self.optimizers_log.lock().running()
+    }
+
     pub fn get_telemetry_data(&self) -> LocalShardTelemetry {
         let segments_read_guard = self.segments.read();
         let segments: Vec<_> = segments_read_guard
@@ -721,6 +910,14 @@ impl LocalShard {
             .iter()
             .map(|optimizer| optimizer.get_telemetry_data())
             .fold(Default::default(), |acc, x| acc + x);
+        let pending_segments = self
+            .optimizers
+            .iter()
+            .filter_map(|optimizer| {
+                let pending = optimizer.pending_segments(self.segments.clone())?;
+                Some((optimizer.name().to_string(), pending))
+            })
+            .collect();
 
         LocalShardTelemetry {
             variant_name: None,
@@ -729,6 +926,7 @@ impl LocalShard {
                 status: optimizer_status,
                 optimizations,
                 log: self.optimizers_log.lock().to_telemetry(),
+                pending_segments,
             },
         }
     }
@@ -777,6 +975,7 @@ impl LocalShard {
         let mut indexed_vectors_count = 0;
         let mut points_count = 0;
         let mut segments_count = 0;
+        let mut disk_usage_bytes = 0;
         let mut status = CollectionStatus::Green;
         let mut schema: HashMap<PayloadKeyType, PayloadIndexInfo> = Default::default();
         for (_idx, segment) in segments.iter() {
@@ -790,6 +989,7 @@ impl LocalShard {
             vectors_count += segment_info.num_vectors;
             indexed_vectors_count += segment_info.num_indexed_vectors;
             points_count += segment_info.num_points;
+            disk_usage_bytes += segment_info.disk_usage_bytes;
             for (key, val) in segment_info.index_schema {
                 schema
                     .entry(key)
@@ -813,6 +1013,7 @@ impl LocalShard {
             indexed_vectors_count,
             points_count,
             segments_count,
+            disk_usage_bytes,
             config: collection_config,
             payload_schema: schema,
         }
@@ -1,26 +1,38 @@
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 use std::time::Duration;
 
 use async_trait::async_trait;
 use futures::future::try_join_all;
 use itertools::Itertools;
+use parking_lot::RwLock;
+use rand::seq::SliceRandom;
+use segment::common::cpu::get_num_cpus;
+use segment::data_types::order_by::OrderBy;
 use segment::types::{
-    ExtendedPointId, Filter, ScoredPoint, WithPayload, WithPayloadInterface, WithVector,
+    Condition, ExtendedPointId, Filter, HasIdCondition, PayloadSelector, PayloadSelectorInclude,
+    ScoredPoint, WithPayload, WithPayloadInterface, WithVector,
 };
 use tokio::runtime::Handle;
 use tokio::sync::oneshot;
 
+use crate::collection_manager::holders::segment_holder::SegmentHolder;
 use crate::collection_manager::segments_searcher::SegmentsSearcher;
 use crate::common::stopping_guard::StoppingGuard;
+use crate::distinct::{distinct_points, CoreDistinct};
 use crate::operations::types::{
     CollectionError, CollectionInfo, CollectionResult, CoreSearchRequestBatch,
-    CountRequestInternal, CountResult, PointRequestInternal, QueryEnum, Record, UpdateResult,
-    UpdateStatus,
+    CountRequestInternal, CountResult, PointRequestInternal, QueryEnum, Record, Sample,
+    UpdateResult, UpdateStatus,
 };
+use crate::operations::update_priority::UpdatePriority;
 use crate::operations::CollectionUpdateOperations;
 use crate::optimizers_builder::DEFAULT_INDEXING_THRESHOLD_KB;
+use crate::score_formula::ScoreFormula;
 use crate::shards::local_shard::LocalShard;
+use crate::shards::scroll_session::SessionRef;
 use crate::shards::shard_trait::ShardOperation;
+use crate::shards::time_travel;
 use crate::update_handler::{OperationData, UpdateSignal};
 
 impl LocalShard {
@@ -45,17 +57,54 @@ impl LocalShard {
         // check vector names existing
         for req in &core_request.searches {
             collection_params.get_distance(req.query.get_vector_name())?;
+            if let Some(prefetch) = &req.prefetch {
+                collection_params.get_distance(prefetch.query.get_vector_name())?;
+            }
         }
 
+        // Segments are not searched by more tasks, than threads of the search runtime
+        let max_search_tasks = collection_params
+            .search_threads
+            .map_or_else(get_num_cpus, |threads| threads.get() as usize);
+
         let is_stopped = StoppingGuard::new();
 
+        // Searches with rescoring or deduplication select at least as many candidates, as they
+        // rescore or deduplicate
+        let segments_request = if core_request
+            .searches
+            .iter()
+            .any(|req| req.rescore.is_some() || req.distinct_by.is_some())
+        {
+            let searches = core_request
+                .searches
+                .iter()
+                .map(|req| {
+                    let mut req = req.clone();
+                    if let Some(rescore) = &req.rescore {
+                        req.limit = req.limit.max(rescore.candidates.saturating_sub(req.offset));
+                    }
+                    if let Some(distinct) = &req.distinct_by {
+                        req.limit = req
+                            .limit
+                            .max(distinct.candidates.saturating_sub(req.offset));
+                    }
+                    req
+                })
+                .collect();
+            Arc::new(CoreSearchRequestBatch { searches })
+        } else {
+            Arc::clone(&core_request)
+        };
+
         let search_request = SegmentsSearcher::search(
             Arc::clone(&self.segments),
-            Arc::clone(&core_request),
+            segments_request,
             search_runtime_handle,
             true,
             is_stopped.get_is_stopped(),
             indexing_threshold_kb.max(full_scan_threshold_kb),
+            max_search_tasks,
         );
 
         let timeout = timeout.unwrap_or(self.shared_storage_config.search_timeout);
@@ -68,7 +117,7 @@ impl LocalShard {
                 CollectionError::timeout(timeout.as_secs() as usize, "Search")
             })??;
 
-        let top_results = res
+        let top_results: Vec<Vec<ScoredPoint>> = res
             .into_iter()
             .zip(core_request.searches.iter())
             .map(|(vector_res, req)| {
@@ -97,9 +146,205 @@ impl LocalShard {
                     processed_res.collect()
                 }
             })
-            .collect();
+            .zip(core_request.searches.iter())
+            .map(|(candidates, req)| {
+                // All rescored candidates are kept for the deduplication
+                let candidates = match &req.rescore {
+                    Some(rescore) => {
+                        let limit = if req.distinct_by.is_some() {
+                            candidates.len()
+                        } else {
+                            req.offset + req.limit
+                        };
+                        self.rescore(candidates, &rescore.formula, limit)?
+                    }
+                    None => candidates,
+                };
+                match &req.distinct_by {
+                    Some(distinct) => self.distinct(candidates, distinct, req.offset + req.limit),
+                    None => Ok(candidates),
+                }
+            })
+            .collect::<CollectionResult<_>>()?;
         Ok(top_results)
     }
+
+    /// Rescore the candidates by the formula, with the payload fields it references
+    fn rescore(
+        &self,
+        candidates: Vec<ScoredPoint>,
+        formula: &ScoreFormula,
+        limit: usize,
+    ) -> CollectionResult<Vec<ScoredPoint>> {
+        let payloads = if formula.fields().is_empty() {
+            HashMap::new()
+        } else {
+            // Only top-level keys could be selected, nested fields are read from them
+            let keys = formula
+                .fields()
+                .iter()
+                .map(|field| field.split(['.', '[']).next().unwrap_or(field).to_string())
+                .unique()
+                .collect();
+            let with_payload = WithPayload {
+                enable: true,
+                payload_selector: Some(PayloadSelector::Include(PayloadSelectorInclude::new(keys))),
+            };
+            let ids: Vec<_> = candidates.iter().map(|point| point.id).collect();
+            SegmentsSearcher::retrieve(
+                self.segments(),
+                &ids,
+                &with_payload,
+                &WithVector::Bool(false),
+            )?
+            .into_iter()
+            .filter_map(|record| Some((record.id, record.payload?)))
+            .collect()
+        };
+        Ok(formula.rescore(candidates, &payloads, limit))
+    }
+
+    /// Keep the best candidate for each value of the field
+    ///
+    /// The values are put into the payload of the results, so the results of the shards could be
+    /// deduplicated while merging. The payload is cut back to the requested one after the merge.
+    fn distinct(
+        &self,
+        mut candidates: Vec<ScoredPoint>,
+        distinct: &CoreDistinct,
+        limit: usize,
+    ) -> CollectionResult<Vec<ScoredPoint>> {
+        let key = distinct.payload_key();
+        let with_payload = WithPayload {
+            enable: true,
+            payload_selector: Some(PayloadSelector::Include(PayloadSelectorInclude::new(vec![
+                key.to_string(),
+            ]))),
+        };
+        let ids: Vec<_> = candidates.iter().map(|point| point.id).collect();
+        let mut values: HashMap<_, _> = SegmentsSearcher::retrieve(
+            self.segments(),
+            &ids,
+            &with_payload,
+            &WithVector::Bool(false),
+        )?
+        .into_iter()
+        .filter_map(|record| Some((record.id, record.payload?.0.remove(key)?)))
+        .collect();
+        for point in &mut candidates {
+            if let Some(value) = values.remove(&point.id) {
+                point
+                    .payload
+                    .get_or_insert_with(Default::default)
+                    .0
+                    .insert(key.to_string(), value);
+            }
+        }
+        Ok(distinct_points(candidates, &distinct.key, limit))
+    }
+
+    /// Read points ordered by the values of the `order_by` field, merged across segments
+    #[allow(clippy::too_many_arguments)]
+    async fn scroll_ordered(
+        &self,
+        segments: &RwLock<SegmentHolder>,
+        limit: usize,
+        with_payload: &WithPayload,
+        with_vector: &WithVector,
+        filter: Option<&Filter>,
+        order_by: &OrderBy,
+        search_runtime_handle: &Handle,
+    ) -> CollectionResult<Vec<Record>> {
+        let read_handles: Vec<_> = {
+            let segments_guard = segments.read();
+            segments_guard
+                .iter()
+                .map(|(_, segment)| {
+                    let segment = segment.clone();
+                    let filter = filter.cloned();
+                    let order_by = order_by.clone();
+                    search_runtime_handle.spawn_blocking(move || {
+                        segment.get().read().read_ordered_filtered(
+                            Some(limit),
+                            filter.as_ref(),
+                            &order_by,
+                        )
+                    })
+                })
+                .collect()
+        };
+        let all_points: Vec<_> = try_join_all(read_handles)
+            .await?
+            .into_iter()
+            .collect::<Result<_, _>>()?;
+
+        // A point could be present in several segments during optimization, keep its first value
+        let mut seen = HashSet::new();
+        let order_values: HashMap<_, _> = all_points
+            .into_iter()
+            .flatten()
+            .sorted_by(|(value_a, id_a), (value_b, id_b)| {
+                order_by.compare(*value_a, *value_b).then(id_a.cmp(id_b))
+            })
+            .filter(|(_, id)| seen.insert(*id))
+            .take(limit)
+            .map(|(value, id)| (id, value))
+            .collect();
+
+        let point_ids = order_values.keys().copied().collect_vec();
+        let mut points =
+            SegmentsSearcher::retrieve(segments, &point_ids, with_payload, with_vector)?;
+        for point in &mut points {
+            point.order_value = order_values.get(&point.id).copied();
+        }
+        points.sort_by(|a, b| {
+            order_by
+                .compare(
+                    a.order_value.unwrap_or_default(),
+                    b.order_value.unwrap_or_default(),
+                )
+                .then(a.id.cmp(&b.id))
+        });
+        Ok(points)
+    }
+
+    /// Read a uniform random sample of the points, segments are sampled in proportion to their size
+    async fn scroll_randomly(
+        &self,
+        segments: &RwLock<SegmentHolder>,
+        limit: usize,
+        with_payload: &WithPayload,
+        with_vector: &WithVector,
+        filter: Option<&Filter>,
+        search_runtime_handle: &Handle,
+    ) -> CollectionResult<Vec<Record>> {
+        let read_handles: Vec<_> = {
+            let segments_guard = segments.read();
+            segments_guard
+                .sample_segments(filter, limit)
+                .into_iter()
+                .map(|(segment, sample_size)| {
+                    let filter = filter.cloned();
+                    search_runtime_handle.spawn_blocking(move || {
+                        segment
+                            .get()
+                            .read()
+                            .read_random_filtered(sample_size, filter.as_ref())
+                    })
+                })
+                .collect()
+        };
+        let all_points = try_join_all(read_handles).await?;
+
+        // A point could be present in several segments during optimization
+        let point_ids = all_points.into_iter().flatten().unique().collect_vec();
+
+        let mut points =
+            SegmentsSearcher::retrieve(segments, &point_ids, with_payload, with_vector)?;
+        points.shuffle(&mut rand::thread_rng());
+        points.truncate(limit);
+        Ok(points)
+    }
 }
 #[async_trait]
 impl ShardOperation for LocalShard {
@@ -110,6 +355,7 @@ impl ShardOperation for LocalShard {
         &self,
         operation: CollectionUpdateOperations,
         wait: bool,
+        priority: Option<UpdatePriority>,
     ) -> CollectionResult<UpdateResult> {
         let (callback_sender, callback_receiver) = if wait {
             let (tx, rx) = oneshot::channel();
@@ -118,16 +364,27 @@ impl ShardOperation for LocalShard {
             (None, None)
         };
 
+        // Bulk updates wait for a slot of the bulk lane before they take the WAL
+        let bulk_permit = match priority {
+            Some(UpdatePriority::Bulk) => {
+                let permit = self.bulk_lane.clone().acquire_owned().await;
+                Some(permit.map_err(|_| CollectionError::service_error("Bulk lane is closed"))?)
+            }
+            Some(UpdatePriority::Interactive) | None => None,
+        };
+
         let operation_id = {
             let update_sender = self.update_sender.load();
             let channel_permit = update_sender.reserve().await?;
             let mut wal_lock = self.wal.lock();
-            let operation_id = wal_lock.write(&operation)?;
+            let (operation_id, size) = wal_lock.write_sized(&operation)?;
             channel_permit.send(UpdateSignal::Operation(OperationData {
                 op_num: operation_id,
                 operation,
+                size,
                 sender: callback_sender,
                 wait,
+                bulk_permit,
             }));
             operation_id
         };
@@ -137,11 +394,15 @@ impl ShardOperation for LocalShard {
             Ok(UpdateResult {
                 operation_id: Some(operation_id),
                 status: UpdateStatus::Completed,
+                consistency_token: None,
+                acknowledgments: None,
             })
         } else {
             Ok(UpdateResult {
                 operation_id: Some(operation_id),
                 status: UpdateStatus::Acknowledged,
+                consistency_token: None,
+                acknowledgments: None,
             })
         }
     }
@@ -153,17 +414,99 @@ impl ShardOperation for LocalShard {
         with_payload_interface: &WithPayloadInterface,
         with_vector: &WithVector,
         filter: Option<&Filter>,
+        order_by: Option<&OrderBy>,
+        sample: Option<Sample>,
+        as_of: Option<u64>,
+        session: Option<&SessionRef>,
         search_runtime_handle: &Handle,
     ) -> CollectionResult<Vec<Record>> {
-        // ToDo: Make faster points selection with a set
+        let with_payload = WithPayload::from(with_payload_interface);
+
+        // Scroll sessions read the shard as it was at the time of their first page
+        let opened_at = session
+            .map(|session| {
+                self.scroll_sessions
+                    .lock()
+                    .opened_at(session, offset.is_none())
+            })
+            .transpose()?;
+
+        // Points, changed since the requested time or since the start of the scroll session,
+        // are restored from the history, instead of reading them from the segments
+        let mut restored_points: Vec<Record> = vec![];
+        let mut filter = filter.cloned();
+        let restore_since = opened_at.or_else(|| as_of.map(|as_of| as_of.saturating_mul(1000)));
+        if let Some(restore_since) = restore_since {
+            let history = self.point_history.lock();
+            match (session, as_of) {
+                (Some(session), _) if !history.is_recorded_since(restore_since) => {
+                    return Err(CollectionError::bad_request(format!(
+                        "Changes since the start of scroll session {} are lost, start a new session",
+                        session.id,
+                    )));
+                }
+                (None, Some(as_of)) => history.check_available(as_of)?,
+                _ => {}
+            }
+            let changed_points = history.changed_since_millis(restore_since);
+            restored_points = changed_points
+                .values()
+                .flatten()
+                .filter(|point| offset.map_or(true, |offset| point.id >= offset))
+                .filter(|point| {
+                    filter
+                        .as_ref()
+                        .map_or(true, |filter| time_travel::check_record(point, filter))
+                })
+                .map(|point| time_travel::select_record(point, &with_payload, with_vector))
+                .collect();
+            if !changed_points.is_empty() {
+                let changed_ids: HashSet<_> = changed_points.into_keys().collect();
+                let unchanged = Filter::new_must_not(Condition::HasId(changed_ids.into()));
+                filter = Some(match filter {
+                    None => unchanged,
+                    Some(filter) => filter.merge(&unchanged),
+                });
+            }
+        }
+
         let segments = self.segments();
+
+        if let Some(order_by) = order_by {
+            return self
+                .scroll_ordered(
+                    segments,
+                    limit,
+                    &with_payload,
+                    with_vector,
+                    filter.as_ref(),
+                    order_by,
+                    search_runtime_handle,
+                )
+                .await;
+        }
+
+        if let Some(Sample::Random) = sample {
+            return self
+                .scroll_randomly(
+                    segments,
+                    limit,
+                    &with_payload,
+                    with_vector,
+                    filter.as_ref(),
+                    search_runtime_handle,
+                )
+                .await;
+        }
+
+        // ToDo: Make faster points selection with a set
         let read_handles: Vec<_> = {
             let segments_guard = segments.read();
             segments_guard
                 .iter()
                 .map(|(_, segment)| {
                     let segment = segment.clone();
-                    let filter = filter.cloned();
+                    let filter = filter.clone();
                     search_runtime_handle.spawn_blocking(move || {
                         segment
                             .get()
@@ -183,10 +526,11 @@ impl ShardOperation for LocalShard {
             .take(limit)
             .collect_vec();
 
-        let with_payload = WithPayload::from(with_payload_interface);
         let mut points =
             SegmentsSearcher::retrieve(segments, &point_ids, &with_payload, with_vector)?;
+        points.extend(restored_points);
         points.sort_by_key(|point| point.id);
+        points.truncate(limit);
 
         Ok(points)
     }
@@ -222,6 +566,24 @@ impl ShardOperation for LocalShard {
         with_payload: &WithPayload,
         with_vector: &WithVector,
     ) -> CollectionResult<Vec<Record>> {
-        SegmentsSearcher::retrieve(self.segments(), &request.ids, with_payload, with_vector)
+        let mut points =
+            SegmentsSearcher::retrieve(self.segments(), &request.ids, with_payload, with_vector)?;
+
+        let Some(as_of) = request.as_of else {
+            return Ok(points);
+        };
+        let history = self.point_history.lock();
+        history.check_available(as_of)?;
+        // Points, changed since the requested time, are replaced with their restored states
+        points.retain(|point| history.state_at(point.id, as_of).is_none());
+        points.extend(
+            request
+                .ids
+                .iter()
+                .unique()
+                .filter_map(|&id| history.state_at(id, as_of).flatten())
+                .map(|point| time_travel::select_record(point, with_payload, with_vector)),
+        );
+        Ok(points)
     }
 }
@@ -1,5 +1,7 @@
 pub mod channel_service;
 pub mod collection_shard_distribution;
+pub mod compaction;
+pub mod consistency_check;
 mod conversions;
 pub mod dummy_shard;
 pub mod forward_proxy_shard;
@@ -11,14 +13,20 @@ pub mod remote_shard;
 #[allow(dead_code)]
 pub mod replica_set;
 pub mod resolve;
+pub mod scroll_session;
+pub mod scrubber;
 pub mod shard;
 pub mod shard_config;
 pub mod shard_holder;
+pub mod shard_split;
 pub mod shard_trait;
 pub mod shard_versioning;
+pub mod split_proxy_shard;
 pub mod telemetry;
+pub mod time_travel;
 pub mod transfer;
 pub mod update_tracker;
+pub mod wal_archive;
 
 use std::path::{Path, PathBuf};
 
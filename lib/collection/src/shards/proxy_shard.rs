@@ -2,9 +2,10 @@ use std::collections::HashSet;
 use std::path::Path;
 use std::sync::atomic::AtomicBool;
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use async_trait::async_trait;
+use segment::data_types::order_by::OrderBy;
 use segment::types::{
     ExtendedPointId, Filter, PointIdType, ScoredPoint, WithPayload, WithPayloadInterface,
     WithVector,
@@ -14,17 +15,22 @@ use tokio::sync::{oneshot, RwLock};
 use tokio::time::timeout;
 
 use super::update_tracker::UpdateTracker;
+use crate::collection_manager::optimizers::Tracker;
 use crate::operations::operation_effect::{
     EstimateOperationEffectArea, OperationEffectArea, PointsOperationEffect,
 };
 use crate::operations::types::{
-    CollectionError, CollectionInfo, CollectionResult, CoreSearchRequestBatch,
-    CountRequestInternal, CountResult, PointRequestInternal, Record, UpdateResult,
+    CollectionError, CollectionInfo, CollectionResult, CompactionResult, CoreSearchRequestBatch,
+    CountRequestInternal, CountResult, PointRequestInternal, Record, Sample, UpdateResult,
+    WarmUpResult,
 };
+use crate::operations::update_priority::UpdatePriority;
 use crate::operations::CollectionUpdateOperations;
 use crate::shards::local_shard::LocalShard;
+use crate::shards::scroll_session::SessionRef;
 use crate::shards::shard_trait::ShardOperation;
 use crate::shards::telemetry::LocalShardTelemetry;
+use crate::shards::wal_archive::WalArchiveResult;
 use crate::update_handler::UpdateSignal;
 
 type ChangedPointsSet = Arc<RwLock<HashSet<PointIdType>>>;
@@ -78,6 +84,26 @@ impl ProxyShard {
         self.wrapped_shard.on_optimizer_config_update().await
     }
 
+    pub async fn warm_up(&self) -> CollectionResult<WarmUpResult> {
+        self.wrapped_shard.warm_up().await
+    }
+
+    pub async fn shutdown(&self, deadline: Instant) -> CollectionResult<()> {
+        self.wrapped_shard.shutdown(deadline).await
+    }
+
+    pub async fn compact(&self) -> CollectionResult<CompactionResult> {
+        self.wrapped_shard.compact().await
+    }
+
+    pub async fn archive_wal(&self, archive_path: &Path) -> CollectionResult<WalArchiveResult> {
+        self.wrapped_shard.archive_wal(archive_path).await
+    }
+
+    pub async fn stop_wal_archiving(&self) -> CollectionResult<()> {
+        self.wrapped_shard.stop_wal_archiving().await
+    }
+
     pub async fn reinit_changelog(&self) -> CollectionResult<()> {
         // Blocks updates in the wrapped shard.
         let mut changed_points_guard = self.changed_points.write().await;
@@ -121,6 +147,10 @@ impl ProxyShard {
         self.wrapped_shard.get_telemetry_data()
     }
 
+    pub fn running_optimizations(&self) -> Vec<Tracker> {
+        self.wrapped_shard.running_optimizations()
+    }
+
     pub fn update_tracker(&self) -> &UpdateTracker {
         self.wrapped_shard.update_tracker()
     }
@@ -133,6 +163,7 @@ impl ShardOperation for ProxyShard {
         &self,
         operation: CollectionUpdateOperations,
         wait: bool,
+        priority: Option<UpdatePriority>,
     ) -> CollectionResult<UpdateResult> {
         let local_shard = &self.wrapped_shard;
         let estimate_effect = operation.estimate_effect_area();
@@ -168,7 +199,7 @@ impl ShardOperation for ProxyShard {
             }
             // Shard update is within a write lock scope, because we need a way to block the shard updates
             // during the transfer restart and finalization.
-            local_shard.update(operation, wait).await
+            local_shard.update(operation, wait, priority).await
         }
     }
 
@@ -180,6 +211,10 @@ impl ShardOperation for ProxyShard {
         with_payload_interface: &WithPayloadInterface,
         with_vector: &WithVector,
         filter: Option<&Filter>,
+        order_by: Option<&OrderBy>,
+        sample: Option<Sample>,
+        as_of: Option<u64>,
+        session: Option<&SessionRef>,
         search_runtime_handle: &Handle,
     ) -> CollectionResult<Vec<Record>> {
         let local_shard = &self.wrapped_shard;
@@ -190,6 +225,10 @@ impl ShardOperation for ProxyShard {
                 with_payload_interface,
                 with_vector,
                 filter,
+                order_by,
+                sample,
+                as_of,
+                session,
                 search_runtime_handle,
             )
             .await
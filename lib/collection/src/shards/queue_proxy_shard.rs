@@ -1,9 +1,10 @@
 use std::path::Path;
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use async_trait::async_trait;
+use segment::data_types::order_by::OrderBy;
 use segment::types::{
     ExtendedPointId, Filter, ScoredPoint, WithPayload, WithPayloadInterface, WithVector,
 };
@@ -13,15 +14,20 @@ use tokio::sync::Mutex;
 use super::remote_shard::RemoteShard;
 use super::transfer::driver::MAX_RETRY_COUNT;
 use super::update_tracker::UpdateTracker;
+use crate::collection_manager::optimizers::Tracker;
 use crate::operations::point_ops::WriteOrdering;
 use crate::operations::types::{
-    CollectionInfo, CollectionResult, CoreSearchRequestBatch, CountRequestInternal, CountResult,
-    PointRequestInternal, Record, UpdateResult,
+    CollectionInfo, CollectionResult, CompactionResult, CoreSearchRequestBatch,
+    CountRequestInternal, CountResult, PointRequestInternal, Record, Sample, UpdateResult,
+    WarmUpResult,
 };
+use crate::operations::update_priority::UpdatePriority;
 use crate::operations::CollectionUpdateOperations;
 use crate::shards::local_shard::LocalShard;
+use crate::shards::scroll_session::SessionRef;
 use crate::shards::shard_trait::ShardOperation;
 use crate::shards::telemetry::LocalShardTelemetry;
+use crate::shards::wal_archive::WalArchiveResult;
 
 /// Number of operations in batch when syncing
 const BATCH_SIZE: usize = 100;
@@ -51,13 +57,20 @@ pub struct QueueProxyShard {
 }
 
 impl QueueProxyShard {
+    /// Queue updates after `from_version` of the WAL, or after creation of the proxy if not set
     pub fn new(
         wrapped_shard: LocalShard,
         remote_shard: RemoteShard,
         max_ack_version: Arc<AtomicU64>,
+        from_version: Option<u64>,
     ) -> Self {
         Self {
-            inner: Some(Inner::new(wrapped_shard, remote_shard, max_ack_version)),
+            inner: Some(Inner::new(
+                wrapped_shard,
+                remote_shard,
+                max_ack_version,
+                from_version,
+            )),
         }
     }
 
@@ -103,6 +116,51 @@ impl QueueProxyShard {
             .await
     }
 
+    pub async fn warm_up(&self) -> CollectionResult<WarmUpResult> {
+        self.inner
+            .as_ref()
+            .expect("Queue proxy has been finalized")
+            .wrapped_shard
+            .warm_up()
+            .await
+    }
+
+    pub async fn shutdown(&self, deadline: Instant) -> CollectionResult<()> {
+        self.inner
+            .as_ref()
+            .expect("Queue proxy has been finalized")
+            .wrapped_shard
+            .shutdown(deadline)
+            .await
+    }
+
+    pub async fn compact(&self) -> CollectionResult<CompactionResult> {
+        self.inner
+            .as_ref()
+            .expect("Queue proxy has been finalized")
+            .wrapped_shard
+            .compact()
+            .await
+    }
+
+    pub async fn archive_wal(&self, archive_path: &Path) -> CollectionResult<WalArchiveResult> {
+        self.inner
+            .as_ref()
+            .expect("Queue proxy has been finalized")
+            .wrapped_shard
+            .archive_wal(archive_path)
+            .await
+    }
+
+    pub async fn stop_wal_archiving(&self) -> CollectionResult<()> {
+        self.inner
+            .as_ref()
+            .expect("Queue proxy has been finalized")
+            .wrapped_shard
+            .stop_wal_archiving()
+            .await
+    }
+
     pub fn get_telemetry_data(&self) -> LocalShardTelemetry {
         self.inner
             .as_ref()
@@ -111,6 +169,14 @@ impl QueueProxyShard {
             .get_telemetry_data()
     }
 
+    pub fn running_optimizations(&self) -> Vec<Tracker> {
+        self.inner
+            .as_ref()
+            .expect("Queue proxy has been finalized")
+            .wrapped_shard
+            .running_optimizations()
+    }
+
     pub fn update_tracker(&self) -> &UpdateTracker {
         self.inner
             .as_ref()
@@ -156,11 +222,12 @@ impl ShardOperation for QueueProxyShard {
         &self,
         operation: CollectionUpdateOperations,
         wait: bool,
+        priority: Option<UpdatePriority>,
     ) -> CollectionResult<UpdateResult> {
         self.inner
             .as_ref()
             .expect("Queue proxy has been finalized")
-            .update(operation, wait)
+            .update(operation, wait, priority)
             .await
     }
 
@@ -172,6 +239,10 @@ impl ShardOperation for QueueProxyShard {
         with_payload_interface: &WithPayloadInterface,
         with_vector: &WithVector,
         filter: Option<&Filter>,
+        order_by: Option<&OrderBy>,
+        sample: Option<Sample>,
+        as_of: Option<u64>,
+        session: Option<&SessionRef>,
         search_runtime_handle: &Handle,
     ) -> CollectionResult<Vec<Record>> {
         self.inner
@@ -183,6 +254,10 @@ impl ShardOperation for QueueProxyShard {
                 with_payload_interface,
                 with_vector,
                 filter,
+                order_by,
+                sample,
+                as_of,
+                session,
                 search_runtime_handle,
             )
             .await
@@ -264,8 +339,9 @@ impl Inner {
         wrapped_shard: LocalShard,
         remote_shard: RemoteShard,
         max_ack_version: Arc<AtomicU64>,
+        from_version: Option<u64>,
     ) -> Self {
-        let last_idx = wrapped_shard.wal.lock().last_index();
+        let last_idx = from_version.unwrap_or_else(|| wrapped_shard.wal.lock().last_index());
 
         let shard = Self {
             wrapped_shard,
@@ -385,12 +461,13 @@ impl ShardOperation for Inner {
         &self,
         operation: CollectionUpdateOperations,
         wait: bool,
+        priority: Option<UpdatePriority>,
     ) -> CollectionResult<UpdateResult> {
         let _update_lock = self.update_lock.lock().await;
         let local_shard = &self.wrapped_shard;
         // Shard update is within a write lock scope, because we need a way to block the shard updates
         // during the transfer restart and finalization.
-        local_shard.update(operation.clone(), wait).await
+        local_shard.update(operation.clone(), wait, priority).await
     }
 
     /// Forward read-only `scroll_by` to `wrapped_shard`
@@ -401,6 +478,10 @@ impl ShardOperation for Inner {
         with_payload_interface: &WithPayloadInterface,
         with_vector: &WithVector,
         filter: Option<&Filter>,
+        order_by: Option<&OrderBy>,
+        sample: Option<Sample>,
+        as_of: Option<u64>,
+        session: Option<&SessionRef>,
         search_runtime_handle: &Handle,
     ) -> CollectionResult<Vec<Record>> {
         let local_shard = &self.wrapped_shard;
@@ -411,6 +492,10 @@ impl ShardOperation for Inner {
                 with_payload_interface,
                 with_vector,
                 filter,
+                order_by,
+                sample,
+                as_of,
+                session,
                 search_runtime_handle,
             )
             .await
@@ -469,7 +554,15 @@ async fn transfer_operations_batch(
     // TODO: naive transfer approach, transfer batch of points instead
     for (_idx, operation) in batch {
         remote_shard
-            .forward_update(operation.clone(), true, WriteOrdering::Weak)
+            .forward_update(
+                operation.clone(),
+                true,
+                WriteOrdering::Weak,
+                None,
+                None,
+                None,
+                None,
+            )
             .await?;
     }
     Ok(())
@@ -21,6 +21,7 @@ use parking_lot::Mutex;
 use segment::common::operation_time_statistics::{
     OperationDurationsAggregator, ScopeDurationMeasurer,
 };
+use segment::data_types::order_by::OrderBy;
 use segment::types::{
     ExtendedPointId, Filter, ScoredPoint, WithPayload, WithPayloadInterface, WithVector,
 };
@@ -34,15 +35,17 @@ use super::conversions::{
     internal_delete_vectors, internal_delete_vectors_by_filter, internal_update_vectors,
 };
 use super::replica_set::ReplicaState;
+use crate::operations::consistency_params::WriteConsistency;
 use crate::operations::conversions::try_record_from_grpc;
 use crate::operations::payload_ops::PayloadOps;
 use crate::operations::point_ops::{PointOperations, WriteOrdering};
 use crate::operations::snapshot_ops::SnapshotPriority;
 use crate::operations::types::{
     CollectionError, CollectionInfo, CollectionResult, CoreSearchRequest, CoreSearchRequestBatch,
-    CountRequestInternal, CountResult, PointRequestInternal, Record, SearchRequestInternal,
+    CountRequestInternal, CountResult, PointRequestInternal, Record, Sample, SearchRequestInternal,
     UpdateResult,
 };
+use crate::operations::update_priority::UpdatePriority;
 use crate::operations::vector_ops::VectorOperations;
 use crate::operations::{CollectionUpdateOperations, FieldIndexOperations};
 use crate::shards::channel_service::ChannelService;
@@ -50,8 +53,9 @@ use crate::shards::conversions::{
     internal_clear_payload, internal_clear_payload_by_filter, internal_create_index,
     internal_delete_index, internal_delete_payload, internal_delete_points,
     internal_delete_points_by_filter, internal_set_payload, internal_sync_points,
-    internal_upsert_points, try_scored_point_from_grpc,
+    internal_update_batch, internal_upsert_points, try_scored_point_from_grpc,
 };
+use crate::shards::scroll_session::SessionRef;
 use crate::shards::shard::{PeerId, ShardId};
 use crate::shards::shard_trait::ShardOperation;
 use crate::shards::telemetry::RemoteShardTelemetry;
@@ -206,6 +210,10 @@ impl RemoteShard {
         operation: CollectionUpdateOperations,
         wait: bool,
         ordering: WriteOrdering,
+        shard_deactivation_timeout: Option<Duration>,
+        write_consistency: Option<WriteConsistency>,
+        timeout: Option<Duration>,
+        priority: Option<UpdatePriority>,
     ) -> CollectionResult<UpdateResult> {
         self.execute_update_operation(
             Some(self.id),
@@ -213,6 +221,10 @@ impl RemoteShard {
             operation,
             wait,
             Some(ordering),
+            shard_deactivation_timeout,
+            write_consistency,
+            timeout,
+            priority,
         )
         .await
     }
@@ -224,6 +236,10 @@ impl RemoteShard {
         operation: CollectionUpdateOperations,
         wait: bool,
         ordering: Option<WriteOrdering>,
+        shard_deactivation_timeout: Option<Duration>,
+        write_consistency: Option<WriteConsistency>,
+        timeout: Option<Duration>,
+        priority: Option<UpdatePriority>,
     ) -> CollectionResult<UpdateResult> {
         let mut timer = ScopeDurationMeasurer::new(&self.telemetry_update_durations);
         timer.set_success(false);
@@ -237,6 +253,10 @@ impl RemoteShard {
                         point_insert_operations,
                         wait,
                         ordering,
+                        shard_deactivation_timeout,
+                        write_consistency,
+                        timeout,
+                        priority,
                     )?;
                     self.with_points_client(|mut client| async move {
                         client.upsert(tonic::Request::new(request.clone())).await
@@ -245,8 +265,17 @@ impl RemoteShard {
                     .into_inner()
                 }
                 PointOperations::DeletePoints { ids } => {
-                    let request =
-                        &internal_delete_points(shard_id, collection_name, ids, wait, ordering);
+                    let request = &internal_delete_points(
+                        shard_id,
+                        collection_name,
+                        ids,
+                        wait,
+                        ordering,
+                        shard_deactivation_timeout,
+                        write_consistency,
+                        timeout,
+                        priority,
+                    );
                     self.with_points_client(|mut client| async move {
                         client.delete(tonic::Request::new(request.clone())).await
                     })
@@ -260,6 +289,10 @@ impl RemoteShard {
                         filter,
                         wait,
                         ordering,
+                        shard_deactivation_timeout,
+                        write_consistency,
+                        timeout,
+                        priority,
                     );
                     self.with_points_client(|mut client| async move {
                         client.delete(tonic::Request::new(request.clone())).await
@@ -274,6 +307,10 @@ impl RemoteShard {
                         operation,
                         wait,
                         ordering,
+                        shard_deactivation_timeout,
+                        write_consistency,
+                        timeout,
+                        priority,
                     )?;
                     self.with_points_client(|mut client| async move {
                         client.sync(tonic::Request::new(request.clone())).await
@@ -290,6 +327,10 @@ impl RemoteShard {
                         update_operation,
                         wait,
                         ordering,
+                        shard_deactivation_timeout,
+                        write_consistency,
+                        timeout,
+                        priority,
                     );
                     self.with_points_client(|mut client| async move {
                         client
@@ -307,6 +348,10 @@ impl RemoteShard {
                         vector_names.clone(),
                         wait,
                         ordering,
+                        shard_deactivation_timeout,
+                        write_consistency,
+                        timeout,
+                        priority,
                     );
                     self.with_points_client(|mut client| async move {
                         client
@@ -324,6 +369,10 @@ impl RemoteShard {
                         vector_names.clone(),
                         wait,
                         ordering,
+                        shard_deactivation_timeout,
+                        write_consistency,
+                        timeout,
+                        priority,
                     );
                     self.with_points_client(|mut client| async move {
                         client
@@ -342,6 +391,10 @@ impl RemoteShard {
                         set_payload,
                         wait,
                         ordering,
+                        shard_deactivation_timeout,
+                        write_consistency,
+                        timeout,
+                        priority,
                     );
                     self.with_points_client(|mut client| async move {
                         client
@@ -358,6 +411,10 @@ impl RemoteShard {
                         delete_payload,
                         wait,
                         ordering,
+                        shard_deactivation_timeout,
+                        write_consistency,
+                        timeout,
+                        priority,
                     );
                     self.with_points_client(|mut client| async move {
                         client
@@ -368,8 +425,17 @@ impl RemoteShard {
                     .into_inner()
                 }
                 PayloadOps::ClearPayload { points } => {
-                    let request =
-                        &internal_clear_payload(shard_id, collection_name, points, wait, ordering);
+                    let request = &internal_clear_payload(
+                        shard_id,
+                        collection_name,
+                        points,
+                        wait,
+                        ordering,
+                        shard_deactivation_timeout,
+                        write_consistency,
+                        timeout,
+                        priority,
+                    );
                     self.with_points_client(|mut client| async move {
                         client
                             .clear_payload(tonic::Request::new(request.clone()))
@@ -385,6 +451,10 @@ impl RemoteShard {
                         filter,
                         wait,
                         ordering,
+                        shard_deactivation_timeout,
+                        write_consistency,
+                        timeout,
+                        priority,
                     );
                     self.with_points_client(|mut client| async move {
                         client
@@ -401,6 +471,10 @@ impl RemoteShard {
                         set_payload,
                         wait,
                         ordering,
+                        shard_deactivation_timeout,
+                        write_consistency,
+                        timeout,
+                        priority,
                     );
                     self.with_points_client(|mut client| async move {
                         client
@@ -420,6 +494,10 @@ impl RemoteShard {
                         create_index,
                         wait,
                         ordering,
+                        shard_deactivation_timeout,
+                        write_consistency,
+                        timeout,
+                        priority,
                     );
                     self.with_points_client(|mut client| async move {
                         client
@@ -436,6 +514,10 @@ impl RemoteShard {
                         delete_index,
                         wait,
                         ordering,
+                        shard_deactivation_timeout,
+                        write_consistency,
+                        timeout,
+                        priority,
                     );
                     self.with_points_client(|mut client| async move {
                         client
@@ -446,6 +528,26 @@ impl RemoteShard {
                     .into_inner()
                 }
             },
+            CollectionUpdateOperations::Batch(operations) => {
+                let request = &internal_update_batch(
+                    shard_id,
+                    collection_name,
+                    operations,
+                    wait,
+                    ordering,
+                    shard_deactivation_timeout,
+                    write_consistency,
+                    timeout,
+                    priority,
+                )?;
+                self.with_points_client(|mut client| async move {
+                    client
+                        .update_batch(tonic::Request::new(request.clone()))
+                        .await
+                })
+                .await?
+                .into_inner()
+            }
         };
         match point_operation_response.result {
             None => Err(CollectionError::service_error(
@@ -542,11 +644,22 @@ impl ShardOperation for RemoteShard {
         &self,
         operation: CollectionUpdateOperations,
         wait: bool,
+        priority: Option<UpdatePriority>,
     ) -> CollectionResult<UpdateResult> {
         // targets the shard explicitly
         let shard_id = Some(self.id);
-        self.execute_update_operation(shard_id, self.collection_id.clone(), operation, wait, None)
-            .await
+        self.execute_update_operation(
+            shard_id,
+            self.collection_id.clone(),
+            operation,
+            wait,
+            None,
+            None,
+            None,
+            None,
+            priority,
+        )
+        .await
     }
 
     async fn scroll_by(
@@ -556,6 +669,10 @@ impl ShardOperation for RemoteShard {
         with_payload_interface: &WithPayloadInterface,
         with_vector: &WithVector,
         filter: Option<&Filter>,
+        order_by: Option<&OrderBy>,
+        sample: Option<Sample>,
+        as_of: Option<u64>,
+        session: Option<&SessionRef>,
         search_runtime_handle: &Handle,
     ) -> CollectionResult<Vec<Record>> {
         let scroll_points = ScrollPoints {
@@ -567,6 +684,15 @@ impl ShardOperation for RemoteShard {
             with_vectors: Some(with_vector.clone().into()),
             read_consistency: None,
             shard_key_selector: None,
+            as_of,
+            session_id: session.map(|session| session.id.clone()),
+            order_by: order_by.cloned().map(Into::into),
+            sample: sample.map(|sample| api::grpc::qdrant::Sample::from(sample) as i32),
+            session_ttl_sec: session
+                .and_then(|session| session.ttl)
+                .map(|ttl| ttl.as_secs()),
+            // Peers always return the versions of the points
+            with_version: None,
         };
         let request = &ScrollPointsInternal {
             scroll_points: Some(scroll_points),
@@ -707,6 +833,9 @@ impl ShardOperation for RemoteShard {
             with_vectors: Some(with_vector.clone().into()),
             read_consistency: None,
             shard_key_selector: None,
+            as_of: request.as_of,
+            // Peers always return the versions of the points
+            with_version: None,
         };
         let request = &GetPointsInternal {
             get_points: Some(get_points),
@@ -1,17 +1,23 @@
+use std::collections::BTreeMap;
 use std::fmt::Write as _;
 use std::ops::Deref as _;
+use std::time::Instant;
 
 use futures::future::{self, BoxFuture};
 use futures::stream::FuturesUnordered;
 use futures::{FutureExt as _, StreamExt as _};
 use rand::seq::SliceRandom as _;
+use segment::types::SeqNumberType;
 
+use super::read_priority::ReadPriority;
+use super::stale_reads::StaleReads;
 use super::ShardReplicaSet;
 use crate::operations::consistency_params::{ReadConsistency, ReadConsistencyType};
-use crate::operations::types::{CollectionError, CollectionResult};
+use crate::operations::consistency_token::ConsistencyToken;
+use crate::operations::types::{CollectionError, CollectionResult, ReadLoadBalancing};
 use crate::shards::remote_shard::RemoteShard;
 use crate::shards::resolve::{Resolve, ResolveCondition};
-use crate::shards::shard::Shard;
+use crate::shards::shard::{PeerId, Shard};
 use crate::shards::shard_trait::ShardOperation;
 
 impl ShardReplicaSet {
@@ -19,6 +25,8 @@ impl ShardReplicaSet {
     /// 1 - Prefer local replica
     /// 2 - Otherwise uses `read_fan_out_ratio` to compute list of active remote shards.
     /// 3 - Fallbacks to all remaining shards if the optimisations fails.
+    /// 4 - If there are no active replicas and the collection allows degraded reads, reads
+    ///     `Partial` and `Listener` replicas instead.
     /// It does not report failing peer_ids to the consensus.
     pub async fn execute_read_operation<Res, F>(
         &self,
@@ -32,8 +40,26 @@ impl ShardReplicaSet {
             return self.execute_local_read_operation(read_operation).await;
         }
 
+        let active_count = usize::from(self.peer_is_active(&self.this_peer_id()))
+            + self
+                .remotes
+                .read()
+                .await
+                .iter()
+                .filter(|remote| self.peer_is_active(&remote.peer_id))
+                .count();
+        let degraded = self.use_degraded_replicas(active_count, 1).await;
+
         let mut responses = self
-            .execute_cluster_read_operation(read_operation, 1, None)
+            .execute_cluster_read_operation(
+                read_operation,
+                1,
+                None,
+                None,
+                degraded,
+                ReadPriority::Normal,
+                None,
+            )
             .await?;
 
         Ok(responses.pop().unwrap())
@@ -43,6 +69,34 @@ impl ShardReplicaSet {
         &self,
         read_operation: F,
         read_consistency: Option<ReadConsistency>,
+        read_priority: ReadPriority,
+        stale_reads: Option<&StaleReads>,
+        local_only: bool,
+    ) -> CollectionResult<Res>
+    where
+        F: Fn(&(dyn ShardOperation + Send + Sync)) -> BoxFuture<'_, CollectionResult<Res>>,
+        Res: Resolve,
+    {
+        self.execute_and_resolve_read_operation_with_token(
+            read_operation,
+            read_consistency,
+            read_priority,
+            stale_reads,
+            None,
+            local_only,
+        )
+        .await
+    }
+
+    /// Same as [`ShardReplicaSet::execute_and_resolve_read_operation`], but if the consistency
+    /// token is provided, only replicas which have applied the operations of the token are read
+    pub async fn execute_and_resolve_read_operation_with_token<Res, F>(
+        &self,
+        read_operation: F,
+        read_consistency: Option<ReadConsistency>,
+        read_priority: ReadPriority,
+        stale_reads: Option<&StaleReads>,
+        consistency_token: Option<&ConsistencyToken>,
         local_only: bool,
     ) -> CollectionResult<Res>
     where
@@ -55,21 +109,42 @@ impl ShardReplicaSet {
 
         let read_consistency = read_consistency.unwrap_or_default();
 
-        let local_count = usize::from(self.peer_state(&self.this_peer_id()).is_some());
-        let active_local_count = usize::from(self.peer_is_active(&self.this_peer_id()));
+        let required_replicas =
+            consistency_token.and_then(|token| token.shard_replicas(self.shard_id));
+        let local_is_allowed = self.local_is_allowed(required_replicas).await;
+        let is_allowed = |peer_id: &PeerId| {
+            required_replicas.map_or(true, |replicas| replicas.contains_key(peer_id))
+        };
+
+        let local_count = usize::from(
+            self.peer_state(&self.this_peer_id()).is_some() && is_allowed(&self.this_peer_id()),
+        );
+        let active_local_count =
+            usize::from(self.peer_is_active(&self.this_peer_id()) && local_is_allowed);
 
         let remotes = self.remotes.read().await;
 
-        let remotes_count = remotes.len();
+        let remotes_count = remotes
+            .iter()
+            .filter(|remote| is_allowed(&remote.peer_id))
+            .count();
 
         let active_remotes_count = remotes
             .iter()
-            .filter(|remote| self.peer_is_active(&remote.peer_id))
+            .filter(|remote| self.peer_is_active(&remote.peer_id) && is_allowed(&remote.peer_id))
             .count();
 
         let total_count = local_count + remotes_count;
         let active_count = active_local_count + active_remotes_count;
 
+        let degraded_local_count =
+            usize::from(self.peer_is_degraded(&self.this_peer_id()) && local_is_allowed);
+
+        let degraded_remotes_count = remotes
+            .iter()
+            .filter(|remote| self.peer_is_degraded(&remote.peer_id) && is_allowed(&remote.peer_id))
+            .count();
+
         let (required_successful_results, condition) = match read_consistency {
             ReadConsistency::Type(ReadConsistencyType::All) => (total_count, ResolveCondition::All),
 
@@ -86,9 +161,24 @@ impl ShardReplicaSet {
             }
         };
 
-        if active_count < required_successful_results {
+        let degraded = self
+            .use_degraded_replicas(active_count, required_successful_results)
+            .await;
+
+        let readable_count = if degraded {
+            active_count + degraded_local_count + degraded_remotes_count
+        } else {
+            active_count
+        };
+
+        if readable_count < required_successful_results {
+            let reason = if required_replicas.is_some() {
+                ", which have applied the operations of the consistency token"
+            } else {
+                ""
+            };
             return Err(CollectionError::service_error(format!(
-                "The replica set for shard {} on peer {} does not have enough active replicas",
+                "The replica set for shard {} on peer {} does not have enough active replicas{reason}",
                 self.shard_id,
                 self.this_peer_id(),
             )));
@@ -99,6 +189,10 @@ impl ShardReplicaSet {
                 read_operation,
                 required_successful_results,
                 Some(remotes),
+                required_replicas,
+                degraded,
+                read_priority,
+                stale_reads,
             )
             .await?;
 
@@ -125,15 +219,94 @@ impl ShardReplicaSet {
         read_operation(local.get()).await
     }
 
+    /// Check if the local replica has applied the operation, which is required from it
+    ///
+    /// Without requirements any local replica is allowed.
+    async fn local_is_allowed(
+        &self,
+        required_replicas: Option<&BTreeMap<PeerId, SeqNumberType>>,
+    ) -> bool {
+        let Some(required_replicas) = required_replicas else {
+            return true;
+        };
+        let Some(&operation_id) = required_replicas.get(&self.this_peer_id()) else {
+            return false;
+        };
+        self.local
+            .read()
+            .await
+            .as_ref()
+            .and_then(Shard::applied_operation)
+            .map_or(false, |applied| applied >= operation_id)
+    }
+
+    /// Check if reads should fall back to degraded replicas, because there are less than
+    /// `required` active replicas, and the collection allows degraded reads
+    async fn use_degraded_replicas(&self, active_count: usize, required: usize) -> bool {
+        active_count < required
+            && self
+                .collection_config
+                .read()
+                .await
+                .params
+                .allow_degraded_reads
+                .unwrap_or_default()
+    }
+
+    /// Track latency and errors of the remote peer, which are used to prefer faster replicas
+    fn report_read_result<Res>(
+        &self,
+        peer_id: PeerId,
+        start: Instant,
+        result: &CollectionResult<Res>,
+    ) {
+        if self.shared_storage_config.read_load_balancing != ReadLoadBalancing::Latency {
+            return;
+        }
+        let mut peer_latencies = self.peer_latencies.lock();
+        match result {
+            Ok(_) => peer_latencies.report_success(peer_id, start.elapsed()),
+            // Only transient errors are caused by the peer, others are caused by the request
+            Err(err) if err.is_transient() => peer_latencies.report_failure(peer_id),
+            Err(_) => {}
+        }
+    }
+
+    /// Execute read operation on active replicas
+    ///
+    /// If `required_replicas` are specified, only those replicas are read, and only after they
+    /// have applied the specified operation.
+    ///
+    /// If `degraded` is set, `Partial` and `Listener` replicas are read as well, after the active
+    /// ones. Responses of such replicas are marked in `stale_reads`.
+    ///
+    /// Low priority reads of a single replica are served by `Listener` replicas first, if the
+    /// collection allows it.
     async fn execute_cluster_read_operation<Res, F>(
         &self,
         read_operation: F,
         required_successful_results: usize,
         remotes: Option<tokio::sync::RwLockReadGuard<'_, Vec<RemoteShard>>>,
+        required_replicas: Option<&BTreeMap<PeerId, SeqNumberType>>,
+        degraded: bool,
+        read_priority: ReadPriority,
+        stale_reads: Option<&StaleReads>,
     ) -> CollectionResult<Vec<Res>>
     where
         F: Fn(&(dyn ShardOperation + Send + Sync)) -> BoxFuture<'_, CollectionResult<Res>>,
     {
+        let local_is_allowed = self.local_is_allowed(required_replicas).await;
+
+        let prefer_listeners = required_successful_results == 1
+            && read_priority == ReadPriority::Low
+            && self
+                .collection_config
+                .read()
+                .await
+                .params
+                .serve_reads_from_listeners
+                .unwrap_or_default();
+
         let remotes = match remotes {
             Some(remotes) => remotes,
             None => self.remotes.read().await,
@@ -158,9 +331,16 @@ impl ShardReplicaSet {
             Err(_) => (self.local.read().right_future(), false, None),
         };
 
-        let local_is_active = self.peer_is_active(&self.this_peer_id());
+        let is_readable = |peer_id: &PeerId| {
+            self.peer_is_active(peer_id)
+                || (degraded && self.peer_is_degraded(peer_id))
+                || (prefer_listeners && self.peer_is_listener(peer_id))
+        };
 
-        let local_operation = if local_is_active {
+        let local_is_readable = is_readable(&self.this_peer_id()) && local_is_allowed;
+        let local_is_degraded = !self.peer_is_active(&self.this_peer_id());
+
+        let local_operation = if local_is_readable {
             let local_operation = async {
                 let local = local.await;
 
@@ -174,25 +354,70 @@ impl ShardReplicaSet {
                 read_operation(local.get()).await
             };
 
-            Some(local_operation.map(|result| (result, true)).left_future())
+            Some(
+                local_operation
+                    .map(move |result| (result, true, local_is_degraded))
+                    .left_future(),
+            )
         } else {
             None
         };
 
-        let mut active_remotes: Vec<_> = remotes
+        let mut readable_remotes: Vec<_> = remotes
             .iter()
-            .filter(|remote| self.peer_is_active(&remote.peer_id))
+            .filter(|remote| is_readable(&remote.peer_id))
+            .filter(|remote| {
+                required_replicas.map_or(true, |replicas| replicas.contains_key(&remote.peer_id))
+            })
             .collect();
 
-        active_remotes.shuffle(&mut rand::thread_rng());
+        match self.shared_storage_config.read_load_balancing {
+            ReadLoadBalancing::Random => readable_remotes.shuffle(&mut rand::thread_rng()),
+            ReadLoadBalancing::Latency => self
+                .peer_latencies
+                .lock()
+                .sort_by_preference(&mut readable_remotes, |remote| remote.peer_id),
+        }
+
+        if degraded {
+            // Degraded replicas are only read if active ones are not enough
+            readable_remotes.sort_by_key(|remote| !self.peer_is_active(&remote.peer_id));
+        }
+
+        // Listeners are read before other replicas, including the local one, to offload them
+        let listeners_first = prefer_listeners && !self.peer_is_listener(&self.this_peer_id());
+        let listener_count = if listeners_first {
+            readable_remotes.sort_by_key(|remote| !self.peer_is_listener(&remote.peer_id));
+            readable_remotes
+                .iter()
+                .filter(|remote| self.peer_is_listener(&remote.peer_id))
+                .count()
+        } else {
+            0
+        };
 
-        let remote_operations = active_remotes.into_iter().map(|remote| {
-            read_operation(remote)
-                .map(|result| (result, false))
-                .right_future()
+        let read_operation = &read_operation;
+        let remote_operations = readable_remotes.into_iter().map(|remote| {
+            let is_degraded = !self.peer_is_active(&remote.peer_id);
+            async move {
+                let start = Instant::now();
+                let result = read_operation(remote).await;
+                self.report_read_result(remote.peer_id, start, &result);
+                (result, false, is_degraded)
+            }
+            .right_future()
         });
 
-        let mut operations = local_operation.into_iter().chain(remote_operations);
+        let has_local_operation = local_operation.is_some();
+        let mut operations: Vec<_> = local_operation
+            .into_iter()
+            .chain(remote_operations)
+            .collect();
+        if has_local_operation {
+            // Local replica goes right after the listeners
+            operations[..=listener_count].rotate_left(1);
+        }
+        let mut operations = operations.into_iter();
 
         // Possible scenarios:
         //
@@ -200,7 +425,7 @@ impl ShardReplicaSet {
         // - Local is not available: default fan-out is 1
         // - There is no local: default fan-out is 1
 
-        let default_fan_out = if is_local_ready && local_is_active {
+        let default_fan_out = if is_local_ready && local_is_readable {
             0
         } else {
             1
@@ -248,7 +473,14 @@ impl ShardReplicaSet {
                         break;
                     };
 
-                    let (operation_result, is_local_operation) = operation_result;
+                    let (operation_result, is_local_operation, is_degraded_operation) =
+                        operation_result;
+
+                    if is_degraded_operation && operation_result.is_ok() {
+                        if let Some(stale_reads) = stale_reads {
+                            stale_reads.mark();
+                        }
+                    }
 
                     result = operation_result;
 
@@ -257,7 +489,7 @@ impl ShardReplicaSet {
                     }
                 }
 
-                _ = &mut update_watcher, if local_is_active && !is_local_operation_resolved => {
+                _ = &mut update_watcher, if local_is_readable && !is_local_operation_resolved => {
                     pending_operations.extend(operations.next());
                     continue;
                 }
@@ -1,17 +1,24 @@
 mod execute_read_operation;
 mod locally_disabled_peers;
+mod peer_latencies;
 mod read_ops;
+pub mod read_priority;
+mod recovery_points;
+mod replica_lag;
+mod shard_split;
 mod shard_transfer;
 mod snapshots;
+pub mod stale_reads;
 mod update;
 
 use std::collections::{HashMap, HashSet};
 use std::ops::Deref as _;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use schemars::JsonSchema;
+use segment::types::SeqNumberType;
 use serde::{Deserialize, Serialize};
 use tokio::runtime::Handle;
 use tokio::sync::{Mutex, RwLock};
@@ -20,15 +27,18 @@ use super::local_shard::LocalShard;
 use super::remote_shard::RemoteShard;
 use super::transfer::ShardTransfer;
 use super::CollectionId;
+use crate::collection_manager::optimizers::Tracker;
 use crate::config::CollectionConfig;
 use crate::operations::shared_storage_config::SharedStorageConfig;
-use crate::operations::types::{CollectionError, CollectionResult};
+use crate::operations::types::{CollectionError, CollectionResult, CompactionResult, WarmUpResult};
 use crate::save_on_disk::SaveOnDisk;
 use crate::shards::channel_service::ChannelService;
+use crate::shards::consistency_check::{self, ConsistencyAnomaly};
 use crate::shards::dummy_shard::DummyShard;
 use crate::shards::shard::{PeerId, Shard, ShardId};
 use crate::shards::shard_config::ShardConfig;
 use crate::shards::telemetry::ReplicaSetTelemetry;
+use crate::shards::wal_archive::WalArchiveResult;
 
 //    │    Collection Created
 //    │
@@ -80,6 +90,12 @@ pub struct ShardReplicaSet {
     /// If the state of the peer is changed in the consensus, it is removed from the list.
     /// Update and read operations are not performed on the peers marked as dead.
     locally_disabled_peers: parking_lot::RwLock<locally_disabled_peers::Registry>,
+    /// Latency and error rate of the reads from remote peers, used to prefer faster replicas
+    peer_latencies: parking_lot::Mutex<peer_latencies::PeerLatencies>,
+    /// Points in the local WAL, from which dead remote replicas could be recovered
+    recovery_points: parking_lot::Mutex<recovery_points::RecoveryPoints>,
+    /// Updates, which the replicas failed since the last one they applied
+    replica_lag: Arc<parking_lot::Mutex<replica_lag::ReplicaLag>>,
     pub(crate) shard_path: PathBuf,
     pub(crate) shard_id: ShardId,
     notify_peer_failure_cb: ChangePeerState,
@@ -91,7 +107,8 @@ pub struct ShardReplicaSet {
     update_runtime: Handle,
     search_runtime: Handle,
     /// Lock to serialized write operations on the replicaset when a write ordering is used.
-    write_ordering_lock: Mutex<()>,
+    /// Held until all replicas complete the update, including hedged ones in background.
+    write_ordering_lock: Arc<Mutex<()>>,
 }
 
 pub type AbortShardTransfer = Arc<dyn Fn(ShardTransfer, &str) + Send + Sync>;
@@ -155,6 +172,14 @@ impl ShardReplicaSet {
             &channel_service,
         );
 
+        let recovery_points = recovery_points::RecoveryPoints::new(
+            replica_state
+                .read()
+                .peers()
+                .into_iter()
+                .filter(|(peer_id, _)| *peer_id != this_peer_id),
+        );
+
         // Save shard config as the last step, to ensure that the file state is consistent
         // Presence of shard config indicates that the shard is ready to be used
         let replica_set_shard_config = ShardConfig::new_replica_set();
@@ -166,6 +191,9 @@ impl ShardReplicaSet {
             remotes: RwLock::new(remote_shards),
             replica_state: replica_state.into(),
             locally_disabled_peers: Default::default(),
+            peer_latencies: Default::default(),
+            recovery_points: parking_lot::Mutex::new(recovery_points),
+            replica_lag: Default::default(),
             shard_path,
             abort_shard_transfer_cb: abort_shard_transfer,
             notify_peer_failure_cb: on_peer_failure,
@@ -175,7 +203,7 @@ impl ShardReplicaSet {
             shared_storage_config,
             update_runtime,
             search_runtime,
-            write_ordering_lock: Mutex::new(()),
+            write_ordering_lock: Default::default(),
         })
     }
 
@@ -224,6 +252,14 @@ impl ShardReplicaSet {
             &channel_service,
         );
 
+        let recovery_points = recovery_points::RecoveryPoints::new(
+            replica_state
+                .read()
+                .peers()
+                .into_iter()
+                .filter(|(peer_id, _)| *peer_id != this_peer_id),
+        );
+
         let mut local_load_failure = false;
         let local = if replica_state.read().is_local {
             let shard = if let Some(recovery_reason) = &shared_storage_config.recovery_mode {
@@ -273,6 +309,9 @@ impl ShardReplicaSet {
             replica_state: replica_state.into(),
             // TODO: move to collection config
             locally_disabled_peers: Default::default(),
+            peer_latencies: Default::default(),
+            recovery_points: parking_lot::Mutex::new(recovery_points),
+            replica_lag: Default::default(),
             shard_path: shard_path.to_path_buf(),
             notify_peer_failure_cb: on_peer_failure,
             abort_shard_transfer_cb: abort_shard_transfer,
@@ -282,7 +321,7 @@ impl ShardReplicaSet {
             shared_storage_config,
             update_runtime,
             search_runtime,
-            write_ordering_lock: Mutex::new(()),
+            write_ordering_lock: Default::default(),
         };
 
         if local_load_failure && replica_set.active_remote_shards().await.is_empty() {
@@ -303,6 +342,28 @@ impl ShardReplicaSet {
         self.local.read().await.is_some()
     }
 
+    /// Number of the latest operation, applied by the local replica, if it is active
+    pub async fn local_applied_operation(&self) -> Option<SeqNumberType> {
+        if !self.peer_is_active(&self.this_peer_id()) {
+            return None;
+        }
+        self.local
+            .read()
+            .await
+            .as_ref()
+            .and_then(Shard::applied_operation)
+    }
+
+    /// Replica, which keeps a new scroll session of the shard
+    ///
+    /// Active local replica is preferred, it saves the network round trips for all pages.
+    pub async fn scroll_session_replica(&self) -> Option<PeerId> {
+        if self.local_applied_operation().await.is_some() {
+            return Some(self.this_peer_id());
+        }
+        self.active_remote_shards().await.into_iter().min()
+    }
+
     pub async fn is_local(&self) -> bool {
         let local_read = self.local.read().await;
         matches!(*local_read, Some(Shard::Local(_) | Shard::Dummy(_)))
@@ -336,6 +397,58 @@ impl ShardReplicaSet {
             .collect()
     }
 
+    /// Check the local shard against its WAL, and that active replicas don't miss updates,
+    /// see [`consistency_check`] for details
+    ///
+    /// Found anomalies are reported in the optimizer status of the local shard.
+    pub async fn check_consistency(&self, max_missed_updates: u64) -> Vec<ConsistencyAnomaly> {
+        let local_state = match self.local.read().await.deref() {
+            Some(Shard::Local(local_shard)) => {
+                Some((local_shard.segments.clone(), local_shard.wal.clone()))
+            }
+            // Proxies are temporary, shards are checked once transfers are finished
+            _ => None,
+        };
+
+        let mut anomalies = Vec::new();
+        if let Some((segments, wal)) = local_state.clone() {
+            let result = tokio::task::spawn_blocking(move || {
+                consistency_check::check_wal_consistency(&segments, &wal)
+            })
+            .await;
+            match result {
+                Ok(wal_anomalies) => anomalies.extend(wal_anomalies),
+                Err(err) => log::error!("Failed to check consistency of the WAL: {err}"),
+            }
+        }
+
+        let replica_lag = self.replica_lag.lock();
+        anomalies.extend(
+            self.peers()
+                .into_iter()
+                .filter(|(_, state)| *state == ReplicaState::Active)
+                .filter_map(|(peer_id, _)| {
+                    let missed_updates = replica_lag.missed_updates(peer_id);
+                    (missed_updates > max_missed_updates).then_some(
+                        ConsistencyAnomaly::ReplicaMissedUpdates {
+                            peer_id,
+                            missed_updates,
+                        },
+                    )
+                }),
+        );
+        drop(replica_lag);
+
+        if let (Some((segments, _)), Some(anomaly)) = (local_state, anomalies.first()) {
+            segments
+                .write()
+                .report_optimizer_error(CollectionError::service_error(format!(
+                    "Consistency check failed: {anomaly}"
+                )));
+        }
+        anomalies
+    }
+
     /// Wait for a local shard to be initialized.
     ///
     /// Uses a blocking thread internally.
@@ -502,6 +615,8 @@ impl ShardReplicaSet {
         })?;
 
         self.update_locally_disabled(peer_id);
+        self.recovery_points.lock().set_peer_state(peer_id, state);
+        self.replica_lag.lock().set_peer_state(peer_id, state);
 
         let mut remotes = self.remotes.write().await;
 
@@ -526,6 +641,8 @@ impl ShardReplicaSet {
         })?;
 
         self.update_locally_disabled(peer_id);
+        self.recovery_points.lock().remove_peer(peer_id);
+        self.replica_lag.lock().remove_peer(peer_id);
 
         let mut remotes = self.remotes.write().await;
         remotes.retain(|remote| remote.peer_id != peer_id);
@@ -563,6 +680,10 @@ impl ShardReplicaSet {
             rs.set_peer_state(*peer_id, state);
         })?;
         self.update_locally_disabled(*peer_id);
+        self.replica_lag.lock().set_peer_state(*peer_id, state);
+        if *peer_id != self.this_peer_id() {
+            self.recovery_points.lock().set_peer_state(*peer_id, state);
+        }
         Ok(())
     }
 
@@ -672,6 +793,74 @@ impl ShardReplicaSet {
         }
     }
 
+    /// Read local shard data into page cache, if there is a local shard
+    pub(crate) async fn warm_up(&self) -> CollectionResult<WarmUpResult> {
+        let read_local = self.local.read().await;
+        if let Some(shard) = &*read_local {
+            shard.warm_up().await
+        } else {
+            Ok(WarmUpResult::default())
+        }
+    }
+
+    /// Merge small segments of the local shard
+    pub(crate) async fn compact(&self) -> CollectionResult<CompactionResult> {
+        let read_local = self.local.read().await;
+        match &*read_local {
+            Some(shard) => shard.compact().await,
+            None => Err(CollectionError::bad_request(format!(
+                "Shard {} is not stored on this peer",
+                self.shard_id,
+            ))),
+        }
+    }
+
+    /// Stop the local shard for the node shutdown, if there is one
+    pub(crate) async fn shutdown(&self, deadline: Instant) -> CollectionResult<()> {
+        let read_local = self.local.read().await;
+        if let Some(shard) = &*read_local {
+            shard.shutdown(deadline).await
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Archive WAL of the local shard, if there is one
+    pub(crate) async fn archive_wal(
+        &self,
+        archive_path: &Path,
+    ) -> CollectionResult<WalArchiveResult> {
+        let read_local = self.local.read().await;
+        if let Some(shard) = &*read_local {
+            shard.archive_wal(archive_path).await
+        } else {
+            Ok(WalArchiveResult::default())
+        }
+    }
+
+    pub(crate) async fn stop_wal_archiving(&self) -> CollectionResult<()> {
+        let read_local = self.local.read().await;
+        if let Some(shard) = &*read_local {
+            shard.stop_wal_archiving().await
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Apply archived operations to the local shard, if there is one
+    pub(crate) async fn replay_wal_archive(
+        &self,
+        archive_path: &Path,
+        until_version: Option<SeqNumberType>,
+    ) -> CollectionResult<usize> {
+        let read_local = self.local.read().await;
+        if let Some(shard) = &*read_local {
+            shard.replay_wal_archive(archive_path, until_version).await
+        } else {
+            Ok(0)
+        }
+    }
+
     /// Check if the are any locally disabled peers
     /// And if so, report them to the consensus
     pub fn sync_local_state<F>(&self, get_shard_transfers: F) -> CollectionResult<()>
@@ -721,6 +910,16 @@ impl ShardReplicaSet {
         }
     }
 
+    /// Optimizations of the local replica, which are still running
+    pub(crate) async fn running_optimizations(&self) -> Vec<Tracker> {
+        self.local
+            .read()
+            .await
+            .as_ref()
+            .map(Shard::running_optimizations)
+            .unwrap_or_default()
+    }
+
     pub(crate) async fn health_check(&self, peer_id: PeerId) -> CollectionResult<()> {
         let remotes = self.remotes.read().await;
 
@@ -762,6 +961,21 @@ impl ShardReplicaSet {
         self.peer_state(peer_id) == Some(ReplicaState::Active) && !self.is_locally_disabled(peer_id)
     }
 
+    /// Check whether a peer could serve degraded reads, which may miss recent updates.
+    /// Only `Partial` and `Listener` peers are degraded, `Active` ones are not.
+    fn peer_is_degraded(&self, peer_id: &PeerId) -> bool {
+        matches!(
+            self.peer_state(peer_id),
+            Some(ReplicaState::Partial | ReplicaState::Listener)
+        ) && !self.is_locally_disabled(peer_id)
+    }
+
+    /// Check whether a peer is a `Listener`, which could serve low priority reads
+    fn peer_is_listener(&self, peer_id: &PeerId) -> bool {
+        self.peer_state(peer_id) == Some(ReplicaState::Listener)
+            && !self.is_locally_disabled(peer_id)
+    }
+
     fn is_locally_disabled(&self, peer_id: &PeerId) -> bool {
         self.locally_disabled_peers.read().is_disabled(*peer_id)
     }
@@ -0,0 +1,189 @@
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use rand::Rng;
+
+use crate::shards::shard::PeerId;
+
+/// Weight of the latest observation in the moving averages
+const EWMA_ALPHA: f64 = 0.3;
+
+/// Peer is ejected, if more than this fraction of its recent reads failed
+const ERROR_RATE_EJECTION_THRESHOLD: f64 = 0.5;
+
+/// Peer is ejected, if its latency is this many times higher than the median latency of the peers
+const LATENCY_OUTLIER_FACTOR: f64 = 3.0;
+
+/// Ejected peers are only read, if there are not enough other peers.
+/// After this period the statistics of the peer are reset, and it is read as usual.
+const EJECTION_PERIOD: Duration = Duration::from_secs(10);
+
+/// Latency and error rate of the reads from remote peers
+///
+/// Used to order the replicas for a read, so that faster and more reliable peers are preferred.
+#[derive(Clone, Debug, Default)]
+pub struct PeerLatencies {
+    peers: HashMap<PeerId, PeerStats>,
+}
+
+#[derive(Copy, Clone, Debug, Default)]
+struct PeerStats {
+    /// Moving average of the read latency, in seconds
+    latency: Option<f64>,
+    /// Moving average of the fraction of failed reads
+    error_rate: f64,
+    ejected_at: Option<Instant>,
+}
+
+impl PeerStats {
+    fn is_ejected(&self) -> bool {
+        self.ejected_at
+            .is_some_and(|ejected_at| ejected_at.elapsed() < EJECTION_PERIOD)
+    }
+
+    /// Relative preference of the peer, higher is better
+    fn weight(&self, default_latency: f64) -> f64 {
+        let latency = self.latency.unwrap_or(default_latency).max(f64::EPSILON);
+        (1.0 - self.error_rate) / latency
+    }
+}
+
+impl PeerLatencies {
+    pub fn report_success(&mut self, peer_id: PeerId, latency: Duration) {
+        let latency = latency.as_secs_f64();
+        let stats = self.stats_mut(peer_id);
+        stats.latency = Some(match stats.latency {
+            Some(average) => average + EWMA_ALPHA * (latency - average),
+            None => latency,
+        });
+        stats.error_rate -= EWMA_ALPHA * stats.error_rate;
+
+        let is_outlier = self
+            .median_latency()
+            .is_some_and(|median| latency > median * LATENCY_OUTLIER_FACTOR);
+        if is_outlier {
+            self.eject(peer_id);
+        }
+    }
+
+    pub fn report_failure(&mut self, peer_id: PeerId) {
+        let stats = self.stats_mut(peer_id);
+        stats.error_rate += EWMA_ALPHA * (1.0 - stats.error_rate);
+
+        if stats.error_rate > ERROR_RATE_EJECTION_THRESHOLD {
+            self.eject(peer_id);
+        }
+    }
+
+    /// Order the peers for a read: random, but biased towards the peers with lower latency and
+    /// error rate. Ejected peers go last.
+    ///
+    /// Peers without statistics are weighted as the median peer, so they are tried out early on.
+    pub fn sort_by_preference<T>(&mut self, peers: &mut [T], peer_id: impl Fn(&T) -> PeerId) {
+        let default_latency = self.median_latency().unwrap_or(1.0);
+        let mut rng = rand::thread_rng();
+
+        // Weighted random order: key of each peer is `u ^ (1 / weight)`, where `u` is uniform
+        // in (0, 1], peers with larger keys go first
+        let mut keys: HashMap<PeerId, (bool, f64)> = HashMap::with_capacity(peers.len());
+        for peer in peers.iter() {
+            let peer_id = peer_id(peer);
+            let stats = self.stats(peer_id);
+            let weight = stats.weight(default_latency).max(f64::EPSILON);
+            let uniform: f64 = 1.0 - rng.gen::<f64>();
+            keys.insert(peer_id, (stats.is_ejected(), uniform.powf(1.0 / weight)));
+        }
+
+        peers.sort_by(|a, b| {
+            let (a_ejected, a_key) = keys[&peer_id(a)];
+            let (b_ejected, b_key) = keys[&peer_id(b)];
+            a_ejected
+                .cmp(&b_ejected)
+                .then_with(|| b_key.total_cmp(&a_key))
+        });
+    }
+
+    /// Statistics of the peer, statistics of the peers with expired ejection are reset
+    fn stats(&mut self, peer_id: PeerId) -> PeerStats {
+        let is_ejection_expired = self.peers.get(&peer_id).is_some_and(|stats| {
+            stats
+                .ejected_at
+                .is_some_and(|ejected_at| ejected_at.elapsed() >= EJECTION_PERIOD)
+        });
+        if is_ejection_expired {
+            self.peers.remove(&peer_id);
+        }
+        self.peers.get(&peer_id).copied().unwrap_or_default()
+    }
+
+    fn stats_mut(&mut self, peer_id: PeerId) -> &mut PeerStats {
+        let _ = self.stats(peer_id);
+        self.peers.entry(peer_id).or_default()
+    }
+
+    fn eject(&mut self, peer_id: PeerId) {
+        let stats = self.stats_mut(peer_id);
+        if !stats.is_ejected() {
+            log::debug!("Reads from peer {peer_id} are deprioritized, as it is an outlier");
+            stats.ejected_at = Some(Instant::now());
+        }
+    }
+
+    fn median_latency(&self) -> Option<f64> {
+        let mut latencies: Vec<_> = self
+            .peers
+            .values()
+            .filter(|stats| !stats.is_ejected())
+            .filter_map(|stats| stats.latency)
+            .collect();
+        if latencies.is_empty() {
+            return None;
+        }
+        latencies.sort_unstable_by(f64::total_cmp);
+        Some(latencies[latencies.len() / 2])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_slow_and_failing_peers_go_last() {
+        let mut latencies = PeerLatencies::default();
+        for _ in 0..10 {
+            latencies.report_success(1, Duration::from_millis(10));
+            latencies.report_success(2, Duration::from_millis(12));
+            latencies.report_success(3, Duration::from_millis(11));
+        }
+        latencies.report_success(2, Duration::from_millis(500));
+        for _ in 0..3 {
+            latencies.report_failure(3);
+        }
+
+        for _ in 0..10 {
+            let mut peers = vec![3, 2, 1];
+            latencies.sort_by_preference(&mut peers, |peer_id| *peer_id);
+            assert_eq!(peers[0], 1);
+        }
+    }
+
+    #[test]
+    fn test_faster_peers_are_preferred() {
+        let mut latencies = PeerLatencies::default();
+        for _ in 0..10 {
+            latencies.report_success(1, Duration::from_millis(10));
+            latencies.report_success(2, Duration::from_millis(20));
+        }
+
+        let first_is_fast = (0..1000)
+            .filter(|_| {
+                let mut peers = vec![1, 2];
+                latencies.sort_by_preference(&mut peers, |peer_id| *peer_id);
+                peers[0] == 1
+            })
+            .count();
+        // Peer 1 has twice the weight of peer 2, so it goes first in 2/3 of the cases
+        assert!((550..800).contains(&first_is_fast), "{first_is_fast}");
+    }
+}
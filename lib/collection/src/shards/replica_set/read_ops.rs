@@ -2,11 +2,16 @@ use std::sync::Arc;
 use std::time::Duration;
 
 use futures::FutureExt as _;
+use segment::data_types::order_by::OrderBy;
 use segment::types::*;
 
+use super::read_priority::ReadPriority;
+use super::stale_reads::StaleReads;
 use super::ShardReplicaSet;
 use crate::operations::consistency_params::ReadConsistency;
+use crate::operations::consistency_token::ConsistencyToken;
 use crate::operations::types::*;
+use crate::shards::scroll_session::SessionRef;
 
 impl ShardReplicaSet {
     #[allow(clippy::too_many_arguments)]
@@ -17,18 +22,29 @@ impl ShardReplicaSet {
         with_payload_interface: &WithPayloadInterface,
         with_vector: &WithVector,
         filter: Option<&Filter>,
+        order_by: Option<&OrderBy>,
+        sample: Option<Sample>,
+        as_of: Option<u64>,
+        session: Option<&SessionRef>,
         read_consistency: Option<ReadConsistency>,
+        read_priority: ReadPriority,
+        stale_reads: Option<&StaleReads>,
+        consistency_token: Option<&ConsistencyToken>,
         local_only: bool,
     ) -> CollectionResult<Vec<Record>> {
         let with_payload_interface = Arc::new(with_payload_interface.clone());
         let with_vector = Arc::new(with_vector.clone());
         let filter = filter.map(|filter| Arc::new(filter.clone()));
+        let order_by = order_by.map(|order_by| Arc::new(order_by.clone()));
+        let session = session.map(|session| Arc::new(session.clone()));
 
-        self.execute_and_resolve_read_operation(
+        self.execute_and_resolve_read_operation_with_token(
             |shard| {
                 let with_payload_interface = with_payload_interface.clone();
                 let with_vector = with_vector.clone();
                 let filter = filter.clone();
+                let order_by = order_by.clone();
+                let session = session.clone();
                 let search_runtime = self.search_runtime.clone();
 
                 async move {
@@ -39,6 +55,10 @@ impl ShardReplicaSet {
                             &with_payload_interface,
                             &with_vector,
                             filter.as_deref(),
+                            order_by.as_deref(),
+                            sample,
+                            as_of,
+                            session.as_deref(),
                             &search_runtime,
                         )
                         .await
@@ -46,18 +66,25 @@ impl ShardReplicaSet {
                 .boxed()
             },
             read_consistency,
+            read_priority,
+            stale_reads,
+            consistency_token,
             local_only,
         )
         .await
     }
+
     pub async fn core_search(
         &self,
         request: Arc<CoreSearchRequestBatch>,
         read_consistency: Option<ReadConsistency>,
+        read_priority: ReadPriority,
+        stale_reads: Option<&StaleReads>,
+        consistency_token: Option<&ConsistencyToken>,
         local_only: bool,
         timeout: Option<Duration>,
     ) -> CollectionResult<Vec<Vec<ScoredPoint>>> {
-        self.execute_and_resolve_read_operation(
+        self.execute_and_resolve_read_operation_with_token(
             |shard| {
                 let request = Arc::clone(&request);
                 let search_runtime = self.search_runtime.clone();
@@ -65,6 +92,9 @@ impl ShardReplicaSet {
                 async move { shard.core_search(request, &search_runtime, timeout).await }.boxed()
             },
             read_consistency,
+            read_priority,
+            stale_reads,
+            consistency_token,
             local_only,
         )
         .await
@@ -74,6 +104,8 @@ impl ShardReplicaSet {
         &self,
         request: Arc<CountRequestInternal>,
         read_consistency: Option<ReadConsistency>,
+        read_priority: ReadPriority,
+        stale_reads: Option<&StaleReads>,
         local_only: bool,
     ) -> CollectionResult<CountResult> {
         self.execute_and_resolve_read_operation(
@@ -82,6 +114,8 @@ impl ShardReplicaSet {
                 async move { shard.count(request).await }.boxed()
             },
             read_consistency,
+            read_priority,
+            stale_reads,
             local_only,
         )
         .await
@@ -93,6 +127,8 @@ impl ShardReplicaSet {
         with_payload: &WithPayload,
         with_vector: &WithVector,
         read_consistency: Option<ReadConsistency>,
+        read_priority: ReadPriority,
+        stale_reads: Option<&StaleReads>,
         local_only: bool,
     ) -> CollectionResult<Vec<Record>> {
         let with_payload = Arc::new(with_payload.clone());
@@ -107,6 +143,8 @@ impl ShardReplicaSet {
                 async move { shard.retrieve(request, &with_payload, &with_vector).await }.boxed()
             },
             read_consistency,
+            read_priority,
+            stale_reads,
             local_only,
         )
         .await
@@ -0,0 +1,38 @@
+//! Priority of the reads, which selects the replicas serving them
+//!
+//! If `serve_reads_from_listeners` is enabled for the collection, low priority reads, like
+//! analytics ones, are served by `Listener` replicas first, to offload `Active` replicas. The API
+//! layer reads the priority from the [`READ_PRIORITY_HEADER`] and passes it along with the reads.
+
+/// Request header, which sets the priority of the reads of the request
+pub const READ_PRIORITY_HEADER: &str = "qdrant-read-priority";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ReadPriority {
+    #[default]
+    Normal,
+    Low,
+}
+
+impl ReadPriority {
+    /// Parse the value of the priority header, unknown values are of normal priority
+    pub fn from_header(value: &[u8]) -> Self {
+        if value.eq_ignore_ascii_case(b"low") {
+            ReadPriority::Low
+        } else {
+            ReadPriority::Normal
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_priority() {
+        assert_eq!(ReadPriority::default(), ReadPriority::Normal);
+        assert_eq!(ReadPriority::from_header(b"LOW"), ReadPriority::Low);
+        assert_eq!(ReadPriority::from_header(b"high"), ReadPriority::Normal);
+    }
+}
@@ -0,0 +1,159 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::time::{Duration, Instant};
+
+use segment::types::SeqNumberType;
+
+use super::ReplicaState;
+use crate::shards::shard::PeerId;
+
+/// Min time between two recorded checkpoints of the local WAL
+const CHECKPOINT_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Max number of recorded checkpoints, about an hour of continuous updates
+const MAX_CHECKPOINTS: usize = 3600;
+
+/// Updates, which deactivate a replica, could be in flight for up to the shard deactivation
+/// timeout before the replica is marked dead. Operations applied within this period before
+/// the replica is marked dead are replayed as well.
+const RECOVERY_LOOKBACK: Duration = Duration::from_secs(60);
+
+/// Points in the local WAL, from which dead remote replicas could be recovered
+///
+/// Versions of the local WAL are recorded with the time they were applied. Once a remote replica
+/// in sync is marked dead, it is known to have all operations applied long enough before that.
+/// These operations are skipped by a `wal_delta` transfer, all later ones are replayed from the
+/// WAL.
+#[derive(Clone, Debug, Default)]
+pub struct RecoveryPoints {
+    /// Versions of the local WAL with the time they were applied, oldest first
+    checkpoints: VecDeque<(Instant, SeqNumberType)>,
+    /// Time since which the remote replicas are dead, after being in sync
+    dead_since: HashMap<PeerId, Instant>,
+    /// Remote replicas, which may not have all data, e.g. were not in sync since they were added
+    partial: HashSet<PeerId>,
+}
+
+impl RecoveryPoints {
+    /// Recovery points of the remote replicas in the given states
+    ///
+    /// It is not known since when the dead replicas are dead, so they are considered partial.
+    pub fn new(peers: impl IntoIterator<Item = (PeerId, ReplicaState)>) -> Self {
+        let mut recovery_points = Self::default();
+        for (peer_id, state) in peers {
+            let state = match state {
+                ReplicaState::Dead => ReplicaState::Partial,
+                state => state,
+            };
+            recovery_points.set_peer_state(peer_id, state);
+        }
+        recovery_points
+    }
+
+    pub fn record_version(&mut self, version: SeqNumberType) {
+        self.record_version_at(version, Instant::now());
+    }
+
+    fn record_version_at(&mut self, version: SeqNumberType, now: Instant) {
+        let is_recent = self
+            .checkpoints
+            .back()
+            .is_some_and(|(time, _)| now.saturating_duration_since(*time) < CHECKPOINT_INTERVAL);
+        if is_recent {
+            return;
+        }
+
+        self.checkpoints.push_back((now, version));
+        if self.checkpoints.len() > MAX_CHECKPOINTS {
+            self.checkpoints.pop_front();
+        }
+    }
+
+    pub fn set_peer_state(&mut self, peer_id: PeerId, state: ReplicaState) {
+        self.set_peer_state_at(peer_id, state, Instant::now());
+    }
+
+    fn set_peer_state_at(&mut self, peer_id: PeerId, state: ReplicaState, now: Instant) {
+        match state {
+            ReplicaState::Dead => {
+                if !self.partial.contains(&peer_id) {
+                    self.dead_since.entry(peer_id).or_insert(now);
+                }
+            }
+            // Dead replica is being recovered, keep its recovery point until it is done
+            ReplicaState::Partial | ReplicaState::PartialSnapshot | ReplicaState::Initializing => {
+                if !self.dead_since.contains_key(&peer_id) {
+                    self.partial.insert(peer_id);
+                }
+            }
+            ReplicaState::Active | ReplicaState::Listener => {
+                self.dead_since.remove(&peer_id);
+                self.partial.remove(&peer_id);
+            }
+        }
+    }
+
+    pub fn remove_peer(&mut self, peer_id: PeerId) {
+        self.dead_since.remove(&peer_id);
+        self.partial.remove(&peer_id);
+    }
+
+    /// Last version of the local WAL, which the dead replica is known to have
+    ///
+    /// Returns `None` if the replica is not dead, or there are no checkpoints old enough.
+    pub fn recovery_point(&self, peer_id: PeerId) -> Option<SeqNumberType> {
+        let dead_since = self.dead_since.get(&peer_id)?;
+        let applied_before = dead_since.checked_sub(RECOVERY_LOOKBACK)?;
+        self.checkpoints
+            .iter()
+            .rev()
+            .find(|(time, _)| *time <= applied_before)
+            .map(|(_, version)| *version)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_recovery_point() {
+        let start = Instant::now();
+        let at = |secs: u64| start + Duration::from_secs(secs);
+
+        let mut points = RecoveryPoints::default();
+        points.record_version_at(10, at(0));
+        // Checkpoints are not recorded more often than the interval
+        points.record_version_at(11, at(0));
+        points.record_version_at(20, at(30));
+        points.record_version_at(30, at(100));
+
+        // Unknown for replicas, which are not dead
+        assert_eq!(points.recovery_point(1), None);
+
+        // Latest checkpoint before the lookback period
+        points.set_peer_state_at(1, ReplicaState::Dead, at(95));
+        assert_eq!(points.recovery_point(1), Some(20));
+
+        // Replica is dead since the first time it is marked dead
+        points.set_peer_state_at(1, ReplicaState::Dead, at(200));
+        points.set_peer_state_at(1, ReplicaState::Partial, at(200));
+        assert_eq!(points.recovery_point(1), Some(20));
+
+        // Unknown, if there are no checkpoints old enough
+        points.set_peer_state_at(2, ReplicaState::Dead, at(50));
+        assert_eq!(points.recovery_point(2), None);
+
+        // Forgotten, once the replica is recovered
+        points.set_peer_state_at(1, ReplicaState::Active, at(210));
+        assert_eq!(points.recovery_point(1), None);
+
+        // Replicas are not recovered from WAL, until they are in sync
+        points.set_peer_state_at(3, ReplicaState::Partial, at(0));
+        points.set_peer_state_at(3, ReplicaState::Dead, at(200));
+        assert_eq!(points.recovery_point(3), None);
+
+        let points = RecoveryPoints::new([(1, ReplicaState::Active), (2, ReplicaState::Dead)]);
+        assert!(points.partial.contains(&2));
+        assert!(!points.partial.contains(&1));
+    }
+}
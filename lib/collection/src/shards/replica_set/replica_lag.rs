@@ -0,0 +1,74 @@
+use std::collections::HashMap;
+
+use super::ReplicaState;
+use crate::shards::shard::PeerId;
+
+/// Updates, which the replicas failed since the last one they applied
+///
+/// Replicas, which fail an update, are reported to the consensus to be deactivated and recovered.
+/// Replicas, which keep failing updates but remain active, e.g. because the deactivation never
+/// reached the consensus, serve incomplete data. They are reported by the consistency check.
+#[derive(Clone, Debug, Default)]
+pub struct ReplicaLag {
+    missed_updates: HashMap<PeerId, u64>,
+}
+
+impl ReplicaLag {
+    pub fn record_applied(&mut self, peer_id: PeerId) {
+        self.missed_updates.remove(&peer_id);
+    }
+
+    pub fn record_failed(&mut self, peer_id: PeerId) {
+        *self.missed_updates.entry(peer_id).or_default() += 1;
+    }
+
+    /// Missed updates are forgotten, once the replica is deactivated, it is recovered anyway
+    pub fn set_peer_state(&mut self, peer_id: PeerId, state: ReplicaState) {
+        match state {
+            ReplicaState::Active | ReplicaState::Listener => {}
+            ReplicaState::Dead
+            | ReplicaState::Partial
+            | ReplicaState::PartialSnapshot
+            | ReplicaState::Initializing => self.remove_peer(peer_id),
+        }
+    }
+
+    pub fn remove_peer(&mut self, peer_id: PeerId) {
+        self.missed_updates.remove(&peer_id);
+    }
+
+    /// Number of the latest updates, which the replica failed
+    pub fn missed_updates(&self, peer_id: PeerId) -> u64 {
+        self.missed_updates
+            .get(&peer_id)
+            .copied()
+            .unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_replica_lag() {
+        let mut lag = ReplicaLag::default();
+        lag.record_failed(1);
+        lag.record_failed(1);
+        lag.record_failed(2);
+        assert_eq!(lag.missed_updates(1), 2);
+        assert_eq!(lag.missed_updates(3), 0);
+
+        // Replica has caught up
+        lag.record_applied(1);
+        assert_eq!(lag.missed_updates(1), 0);
+
+        // Still active
+        lag.set_peer_state(2, ReplicaState::Active);
+        assert_eq!(lag.missed_updates(2), 1);
+
+        // Deactivated replica is recovered anyway
+        lag.set_peer_state(2, ReplicaState::Dead);
+        assert_eq!(lag.missed_updates(2), 0);
+    }
+}
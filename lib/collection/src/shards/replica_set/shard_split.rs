@@ -0,0 +1,132 @@
+use std::ops::Deref as _;
+
+use segment::types::PointIdType;
+
+use super::ShardReplicaSet;
+use crate::hash_ring::HashRing;
+use crate::operations::types::{CollectionError, CollectionResult};
+use crate::shards::shard::{Shard, ShardId};
+use crate::shards::split_proxy_shard::SplitProxyShard;
+
+impl ShardReplicaSet {
+    pub async fn is_split_proxy(&self) -> bool {
+        let local_read = self.local.read().await;
+        matches!(*local_read, Some(Shard::SplitProxy(_)))
+    }
+
+    /// Wrap local shard into a split proxy, which also updates the local replicas of the targets
+    ///
+    /// # Cancel safety
+    ///
+    /// This method is cancel safe.
+    pub async fn split_proxify_local(
+        &self,
+        targets: Vec<ShardReplicaSet>,
+        ring: HashRing<ShardId>,
+    ) -> CollectionResult<()> {
+        let mut local = self.local.write().await;
+
+        match local.deref() {
+            // Expected state, continue
+            Some(Shard::Local(_)) => {}
+
+            // Unexpected states, error
+            Some(shard) => {
+                return Err(CollectionError::service_error(format!(
+                    "Cannot split local shard {} because it has unexpected type - {}",
+                    self.shard_id,
+                    shard.variant_name(),
+                )));
+            }
+            None => {
+                return Err(CollectionError::service_error(format!(
+                    "Cannot split local shard {} on peer {} because it is not active",
+                    self.shard_id,
+                    self.this_peer_id()
+                )));
+            }
+        };
+
+        // Explicit `match` instead of `if-let` to catch `unreachable` condition if top `match` is
+        // changed
+        let local_shard = match local.take() {
+            Some(Shard::Local(local_shard)) => local_shard,
+            _ => unreachable!(),
+        };
+
+        let proxy_shard = SplitProxyShard::new(local_shard, targets, ring);
+        let _ = local.insert(Shard::SplitProxy(proxy_shard));
+
+        Ok(())
+    }
+
+    /// Un-wrap local shard from the split proxy, returns the local replicas of the targets
+    ///
+    /// Returns nothing, if the local shard is not a split proxy.
+    ///
+    /// # Cancel safety
+    ///
+    /// This method is cancel safe.
+    pub async fn un_split_proxify_local(&self) -> Vec<ShardReplicaSet> {
+        let mut local = self.local.write().await;
+
+        if !matches!(local.deref(), Some(Shard::SplitProxy(_))) {
+            return Vec::new();
+        }
+
+        let Some(Shard::SplitProxy(proxy)) = local.take() else {
+            unreachable!();
+        };
+
+        let (local_shard, targets) = proxy.deconstruct();
+        let _ = local.insert(Shard::Local(local_shard));
+
+        targets
+    }
+
+    /// # Cancel safety
+    ///
+    /// This method is cancel safe.
+    pub async fn split_transfer_batch(
+        &self,
+        offset: Option<PointIdType>,
+        batch_size: usize,
+    ) -> CollectionResult<Option<PointIdType>> {
+        let local = self.local.read().await;
+
+        let Some(Shard::SplitProxy(proxy)) = local.deref() else {
+            return Err(CollectionError::service_error(format!(
+                "Cannot copy batch from shard {} because it is not being split",
+                self.shard_id
+            )));
+        };
+
+        proxy
+            .transfer_batch(offset, batch_size, &self.search_runtime)
+            .await
+    }
+
+    /// Create payload indexes of the local shard in the targets of the split
+    ///
+    /// # Cancel safety
+    ///
+    /// This method is cancel safe.
+    pub async fn split_transfer_indexes(&self) -> CollectionResult<()> {
+        let local = self.local.read().await;
+
+        let Some(Shard::SplitProxy(proxy)) = local.deref() else {
+            return Err(CollectionError::service_error(format!(
+                "Cannot copy indexes from shard {} because it is not being split",
+                self.shard_id,
+            )));
+        };
+
+        log::trace!(
+            "Copying indexes of shard {} to shards {:?}",
+            self.shard_id,
+            proxy.target_ids(),
+        );
+
+        proxy.transfer_indexes().await
+    }
+}
@@ -1,13 +1,14 @@
 use std::ops::Deref as _;
 
-use segment::types::PointIdType;
+use segment::types::{PointIdType, SeqNumberType};
 
 use super::ShardReplicaSet;
 use crate::operations::types::{CollectionError, CollectionResult};
 use crate::shards::forward_proxy_shard::ForwardProxyShard;
 use crate::shards::queue_proxy_shard::QueueProxyShard;
 use crate::shards::remote_shard::RemoteShard;
-use crate::shards::shard::Shard;
+use crate::shards::shard::{PeerId, Shard};
+use crate::shards::transfer::throttle::BatchSize;
 
 impl ShardReplicaSet {
     /// # Cancel safety
@@ -40,6 +41,12 @@ impl ShardReplicaSet {
                     self.shard_id, remote_shard.peer_id,
                 )));
             }
+            Some(Shard::SplitProxy(_)) => {
+                return Err(CollectionError::service_error(format!(
+                    "Cannot proxify local shard {} to peer {} because it is being split",
+                    self.shard_id, remote_shard.peer_id,
+                )));
+            }
             Some(Shard::Proxy(_)) => {
                 return Err(CollectionError::service_error(format!(
                     "Cannot queue proxify local shard {} to peer {} because it already is a proxy",
@@ -74,10 +81,20 @@ impl ShardReplicaSet {
         Ok(())
     }
 
+    /// Queue updates after `from_version` of the WAL, or after this call if not set
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the WAL does not hold all updates after `from_version` anymore.
+    ///
     /// # Cancel safety
     ///
     /// This method is cancel safe.
-    pub async fn queue_proxify_local(&self, remote_shard: RemoteShard) -> CollectionResult<()> {
+    pub async fn queue_proxify_local(
+        &self,
+        remote_shard: RemoteShard,
+        from_version: Option<SeqNumberType>,
+    ) -> CollectionResult<()> {
         let mut local = self.local.write().await;
 
         match local.deref() {
@@ -95,6 +112,12 @@ impl ShardReplicaSet {
                     self.shard_id, remote_shard.peer_id,
                 )));
             }
+            Some(Shard::SplitProxy(_)) => {
+                return Err(CollectionError::service_error(format!(
+                    "Cannot queue proxify local shard {} to peer {} because it is being split",
+                    self.shard_id, remote_shard.peer_id,
+                )));
+            }
             Some(Shard::ForwardProxy(proxy)) => {
                 return Err(CollectionError::service_error(format!(
                     "Cannot queue proxify local shard {} to peer {} because it is already proxified to peer {}",
@@ -136,6 +159,17 @@ impl ShardReplicaSet {
             .max_ack_version
             .clone();
 
+        if let Some(from_version) = from_version {
+            let first_index = local_shard.wal.lock().first_index();
+            if first_index > from_version + 1 {
+                return Err(CollectionError::service_error(format!(
+                    "Cannot queue proxify local shard {} from version {from_version}, \
+                     WAL is truncated up to version {first_index}",
+                    self.shard_id,
+                )));
+            }
+        }
+
         // Proxify local shard
         //
         // Making `await` calls between `local.take()` and `local.insert(...)` is *not* cancel safe!
@@ -145,7 +179,8 @@ impl ShardReplicaSet {
             _ => unreachable!(),
         };
 
-        let proxy_shard = QueueProxyShard::new(local_shard, remote_shard, max_ack_version);
+        let proxy_shard =
+            QueueProxyShard::new(local_shard, remote_shard, max_ack_version, from_version);
         let _ = local.insert(Shard::QueueProxy(proxy_shard));
 
         Ok(())
@@ -165,7 +200,7 @@ impl ShardReplicaSet {
             Some(Shard::ForwardProxy(_) | Shard::QueueProxy(_)) => {}
 
             // Unexpected states, error
-            Some(shard @ (Shard::Proxy(_) | Shard::Dummy(_))) => {
+            Some(shard @ (Shard::Proxy(_) | Shard::SplitProxy(_) | Shard::Dummy(_))) => {
                 return Err(CollectionError::service_error(format!(
                     "Cannot un-proxify local shard {} because it has unexpected type - {}",
                     self.shard_id,
@@ -277,6 +312,8 @@ impl ShardReplicaSet {
 
     /// Custom operation for transferring data from one shard to another during transfer
     ///
+    /// Returns an offset of the next batch, and the size of the transferred one.
+    ///
     /// # Cancel safety
     ///
     /// This method is cancel safe.
@@ -284,7 +321,7 @@ impl ShardReplicaSet {
         &self,
         offset: Option<PointIdType>,
         batch_size: usize,
-    ) -> CollectionResult<Option<PointIdType>> {
+    ) -> CollectionResult<(Option<PointIdType>, BatchSize)> {
         let local = self.local.read().await;
 
         let Some(Shard::ForwardProxy(proxy)) = local.deref() else {
@@ -385,4 +422,20 @@ impl ShardReplicaSet {
 
         Ok(())
     }
+
+    /// Last version of the local WAL, which the dead remote replica is known to have
+    ///
+    /// Returns `None` if it is not known, or the local WAL does not hold all later updates anymore.
+    pub async fn wal_delta_recovery_point(&self, peer_id: PeerId) -> Option<SeqNumberType> {
+        let recovery_point = self.recovery_points.lock().recovery_point(peer_id)?;
+
+        let local = self.local.read().await;
+        let first_index = match local.deref() {
+            Some(Shard::Local(local_shard)) => local_shard.wal.lock().first_index(),
+            Some(Shard::ForwardProxy(proxy)) => proxy.wrapped_shard.wal.lock().first_index(),
+            _ => return None,
+        };
+
+        (first_index <= recovery_point + 1).then_some(recovery_point)
+    }
 }
@@ -0,0 +1,42 @@
+//! Tracking of the reads, which are served by degraded replicas
+//!
+//! If `allow_degraded_reads` is enabled and a shard doesn't have enough `Active` replicas, reads
+//! fall back to `Partial` and `Listener` replicas, which may miss recent updates. The API layer
+//! passes [`StaleReads`] of the request along with its reads and marks the response as stale.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+/// Response header, which marks the responses served by degraded replicas
+pub const STALE_READ_HEADER: &str = "qdrant-stale-read";
+
+/// Whether any of the reads of the request were served by degraded replicas
+///
+/// Clones share the same state, so the reads could mark it, while the API layer keeps a clone.
+#[derive(Debug, Clone, Default)]
+pub struct StaleReads(Arc<AtomicBool>);
+
+impl StaleReads {
+    /// Mark the request as served by degraded replicas
+    pub fn mark(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_stale(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stale_reads() {
+        let stale_reads = StaleReads::default();
+        assert!(!stale_reads.is_stale());
+
+        stale_reads.clone().mark();
+        assert!(stale_reads.is_stale());
+    }
+}
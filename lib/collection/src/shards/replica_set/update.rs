@@ -1,33 +1,47 @@
+use std::future::Future;
 use std::ops::Deref as _;
 use std::time::Duration;
 
 use futures::stream::FuturesUnordered;
 use futures::{FutureExt as _, StreamExt as _};
 use itertools::Itertools as _;
+use tokio::sync::OwnedMutexGuard;
 
 use super::{ReplicaSetState, ReplicaState, ShardReplicaSet};
+use crate::operations::consistency_params::WriteConsistency;
+use crate::operations::consistency_token::ConsistencyToken;
 use crate::operations::point_ops::WriteOrdering;
-use crate::operations::types::{CollectionError, CollectionResult, UpdateResult};
+use crate::operations::types::{
+    CollectionError, CollectionResult, ReplicaAcknowledgment, UpdateResult, UpdateStatus,
+};
+use crate::operations::update_priority::UpdatePriority;
 use crate::operations::CollectionUpdateOperations;
 use crate::shards::shard::PeerId;
 use crate::shards::shard_trait::ShardOperation as _;
 
 const DEFAULT_SHARD_DEACTIVATION_TIMEOUT: Duration = Duration::from_secs(30);
 
+type ReplicaUpdateResult = Result<(PeerId, UpdateResult), (PeerId, CollectionError)>;
+
 impl ShardReplicaSet {
     /// Update local shard if any without forwarding to remote shards
     pub async fn update_local(
         &self,
         operation: CollectionUpdateOperations,
         wait: bool,
+        priority: Option<UpdatePriority>,
     ) -> CollectionResult<Option<UpdateResult>> {
         if let Some(local_shard) = &*self.local.read().await {
             match self.peer_state(&self.this_peer_id()) {
                 Some(ReplicaState::Active | ReplicaState::Partial | ReplicaState::Initializing) => {
-                    Ok(Some(local_shard.get().update(operation, wait).await?))
+                    let result = local_shard.get().update(operation, wait, priority).await?;
+                    self.record_local_update(&result);
+                    Ok(Some(result))
                 }
                 Some(ReplicaState::Listener) => {
-                    Ok(Some(local_shard.get().update(operation, false).await?))
+                    let result = local_shard.get().update(operation, false, priority).await?;
+                    self.record_local_update(&result);
+                    Ok(Some(result))
                 }
                 Some(ReplicaState::PartialSnapshot | ReplicaState::Dead) | None => Ok(None),
             }
@@ -36,11 +50,29 @@ impl ShardReplicaSet {
         }
     }
 
+    /// Update the shard with the given write ordering
+    ///
+    /// `shard_deactivation_timeout` limits the time to wait for the consensus to deactivate
+    /// replicas, which failed to apply the update. If not specified, the collection setting or
+    /// the default of 30 seconds is used.
+    ///
+    /// `write_consistency` overrides the number of replicas, which must apply the update, set by
+    /// `write_consistency_factor` of the collection.
+    ///
+    /// `timeout` limits the time to wait for each replica to apply the update. Replicas, which
+    /// didn't apply it in time, are reported as failed with a transient error.
+    ///
+    /// `priority` is forwarded to the replicas, bulk updates yield their update queues to the
+    /// interactive ones.
     pub async fn update_with_consistency(
         &self,
         operation: CollectionUpdateOperations,
         wait: bool,
         ordering: WriteOrdering,
+        shard_deactivation_timeout: Option<Duration>,
+        write_consistency: Option<WriteConsistency>,
+        timeout: Option<Duration>,
+        priority: Option<UpdatePriority>,
     ) -> CollectionResult<UpdateResult> {
         match self.leader_peer_for_update(ordering) {
             None => Err(CollectionError::service_error(format!(
@@ -51,14 +83,33 @@ impl ShardReplicaSet {
                 // If we are the leader, run the update from this replica set
                 if leader_peer == self.this_peer_id() {
                     // lock updates if ordering is medium or strong
-                    let _guard = match ordering {
+                    let ordering_guard = match ordering {
                         WriteOrdering::Weak => None, // no locking required
-                        WriteOrdering::Medium | WriteOrdering::Strong => Some(self.write_ordering_lock.lock().await), // one request at a time
+                        WriteOrdering::Medium | WriteOrdering::Strong => Some(self.write_ordering_lock.clone().lock_owned().await), // one request at a time
                     };
-                    self.update(operation, wait).await
+                    self.update(
+                        operation,
+                        wait,
+                        ordering_guard,
+                        shard_deactivation_timeout,
+                        write_consistency,
+                        timeout,
+                        priority,
+                    )
+                    .await
                 } else {
                     // forward the update to the designated leader
-                    self.forward_update(leader_peer, operation, wait, ordering)
+                    let forward_update = self.forward_update(
+                        leader_peer,
+                        operation,
+                        wait,
+                        ordering,
+                        shard_deactivation_timeout,
+                        write_consistency,
+                        timeout,
+                        priority,
+                    );
+                    with_timeout(forward_update, timeout, "forward update to leader")
                         .await
                         .map_err(|err| {
                             if err.is_transient() {
@@ -78,6 +129,12 @@ impl ShardReplicaSet {
         }
     }
 
+    /// This peer is the designated leader of the updates with `ordering`,
+    /// other peers forward such updates to it
+    pub fn is_update_leader(&self, ordering: WriteOrdering) -> bool {
+        self.leader_peer_for_update(ordering) == Some(self.this_peer_id())
+    }
+
     /// Designated a leader replica for the update based on the WriteOrdering
     fn leader_peer_for_update(&self, ordering: WriteOrdering) -> Option<PeerId> {
         match ordering {
@@ -102,12 +159,36 @@ impl ShardReplicaSet {
         self.replica_state.read().peers.keys().max().cloned()
     }
 
+    /// `ordering_guard` is held until all replicas complete the update, so the next ordered update
+    /// can't overtake this one on a slow replica, even if this one returns earlier
     async fn update(
         &self,
         operation: CollectionUpdateOperations,
         wait: bool,
+        ordering_guard: Option<OwnedMutexGuard<()>>,
+        shard_deactivation_timeout: Option<Duration>,
+        write_consistency: Option<WriteConsistency>,
+        timeout: Option<Duration>,
+        priority: Option<UpdatePriority>,
     ) -> CollectionResult<UpdateResult> {
-        let all_res: Vec<Result<_, _>> = {
+        let (write_consistency_factor, deactivation_timeout, hedge_updates) = {
+            let collection_config = self.collection_config.read().await;
+            let params = &collection_config.params;
+            let deactivation_timeout = shard_deactivation_timeout
+                .or_else(|| {
+                    params
+                        .shard_deactivation_timeout_sec
+                        .map(Duration::from_secs)
+                })
+                .unwrap_or(DEFAULT_SHARD_DEACTIVATION_TIMEOUT);
+            (
+                params.write_consistency_factor.get() as usize,
+                deactivation_timeout,
+                wait && params.hedge_updates.unwrap_or(false),
+            )
+        };
+
+        let (all_res, minimal_success_count) = {
             let remotes = self.remotes.read().await;
             let local = self.local.read().await;
             let this_peer_id = self.this_peer_id();
@@ -129,7 +210,7 @@ impl ShardReplicaSet {
                 )));
             }
 
-            let mut update_futures = Vec::with_capacity(active_remote_shards.len() + 1);
+            let mut local_update = None;
 
             if let Some(local) = local.deref() {
                 if self.peer_is_active_or_pending(&this_peer_id) {
@@ -142,59 +223,121 @@ impl ShardReplicaSet {
 
                     let operation = operation.clone();
 
-                    let local_update = async move {
-                        local
-                            .get()
-                            .update(operation, local_wait)
+                    local_update = Some(async move {
+                        let update = local.get().update(operation, local_wait, priority);
+                        with_timeout(update, timeout, "local shard update")
                             .await
-                            .map(|ok| (this_peer_id, ok))
+                            .map(|ok| {
+                                self.record_local_update(&ok);
+                                (this_peer_id, ok)
+                            })
                             .map_err(|err| (this_peer_id, err))
-                    };
-
-                    update_futures.push(local_update.left_future());
+                    });
                 }
             }
 
-            for remote in active_remote_shards {
-                let operation = operation.clone();
-
-                let remote_update = async move {
-                    remote
-                        .update(operation, wait)
-                        .await
-                        .map(|ok| (remote.peer_id, ok))
-                        .map_err(|err| (remote.peer_id, err))
-                };
+            let total_results = usize::from(local_update.is_some()) + active_remote_shards.len();
 
-                update_futures.push(remote_update.right_future());
+            let minimal_success_count = match write_consistency {
+                Some(write_consistency) => write_consistency.minimal_success_count(total_results),
+                None => write_consistency_factor,
             }
+            .min(total_results);
+
+            if hedge_updates {
+                let remote_updates = active_remote_shards
+                    .iter()
+                    .map(|remote| {
+                        let remote = (*remote).clone();
+                        let peer_id = remote.peer_id;
+                        let operation = operation.clone();
+                        let remote_update = async move {
+                            let update = remote.update(operation, wait, priority);
+                            with_timeout(update, timeout, "remote shard update")
+                                .await
+                                .map(|ok| (peer_id, ok))
+                                .map_err(|err| (peer_id, err))
+                        };
+                        (peer_id, remote_update)
+                    })
+                    .collect();
+
+                let all_res = self
+                    .update_hedged(
+                        local_update,
+                        remote_updates,
+                        ordering_guard,
+                        minimal_success_count,
+                    )
+                    .await;
 
-            match self.shared_storage_config.update_concurrency {
-                Some(concurrency) => {
-                    futures::stream::iter(update_futures)
-                        .buffer_unordered(concurrency.get())
-                        .collect()
-                        .await
+                (all_res, minimal_success_count)
+            } else {
+                let mut update_futures = Vec::with_capacity(total_results);
+
+                if let Some(local_update) = local_update {
+                    update_futures.push(local_update.left_future());
                 }
 
-                None => FuturesUnordered::from_iter(update_futures).collect().await,
-            }
-        };
+                for remote in active_remote_shards {
+                    let operation = operation.clone();
+
+                    let remote_update = async move {
+                        let update = remote.update(operation, wait, priority);
+                        with_timeout(update, timeout, "remote shard update")
+                            .await
+                            .map(|ok| (remote.peer_id, ok))
+                            .map_err(|err| (remote.peer_id, err))
+                    };
 
-        let total_results = all_res.len();
+                    update_futures.push(remote_update.right_future());
+                }
 
-        let write_consistency_factor = self
-            .collection_config
-            .read()
-            .await
-            .params
-            .write_consistency_factor
-            .get() as usize;
+                let all_res: Vec<Result<_, _>> = match self.shared_storage_config.update_concurrency
+                {
+                    Some(concurrency) => {
+                        futures::stream::iter(update_futures)
+                            .buffer_unordered(concurrency.get())
+                            .collect()
+                            .await
+                    }
+
+                    None => FuturesUnordered::from_iter(update_futures).collect().await,
+                };
 
-        let minimal_success_count = write_consistency_factor.min(total_results);
+                (all_res, minimal_success_count)
+            }
+        };
 
         let (successes, failures): (Vec<_>, Vec<_>) = all_res.into_iter().partition_result();
 
+        {
+            let mut replica_lag = self.replica_lag.lock();
+            for (peer_id, _) in &successes {
+                replica_lag.record_applied(*peer_id);
+            }
+            for (peer_id, _) in &failures {
+                replica_lag.record_failed(*peer_id);
+            }
+        }
+
+        // Let the client know, which replicas actually applied the update
+        let acknowledgments = successes
+            .iter()
+            .map(|(peer_id, res)| ReplicaAcknowledgment {
+                shard_id: self.shard_id,
+                peer_id: *peer_id,
+                status: Some(res.status),
+                error: None,
+            })
+            .chain(failures.iter().map(|(peer_id, err)| ReplicaAcknowledgment {
+                shard_id: self.shard_id,
+                peer_id: *peer_id,
+                status: None,
+                error: Some(err.to_string()),
+            }))
+            .collect();
+
         // Notify consensus about failures if:
         // 1. There is at least one success, otherwise it might be a problem of sending node
         // 2. ???
@@ -211,8 +354,7 @@ impl ShardReplicaSet {
 
             // report all failing peers to consensus
             if wait && wait_for_deactivation && !failures.is_empty() {
-                // ToDo: allow timeout configuration in API
-                let timeout = DEFAULT_SHARD_DEACTIVATION_TIMEOUT;
+                let timeout = deactivation_timeout;
 
                 let replica_state = self.replica_state.clone();
                 let peer_ids: Vec<_> = failures.iter().map(|(peer_id, _)| *peer_id).collect();
@@ -228,7 +370,7 @@ impl ShardReplicaSet {
                                     .unwrap_or(true) // not found means that peer is dead
                             })
                         },
-                        DEFAULT_SHARD_DEACTIVATION_TIMEOUT,
+                        timeout,
                     )
                 })
                 .await?;
@@ -259,12 +401,26 @@ impl ShardReplicaSet {
             )));
         }
 
+        // Replicas, which have completed the operation, can serve reads expecting it
+        let consistency_token = ConsistencyToken::new(
+            self.shard_id,
+            successes
+                .iter()
+                .filter(|(_, res)| res.status == UpdateStatus::Completed)
+                .filter_map(|(peer_id, res)| Some((*peer_id, res.operation_id?))),
+        );
+
         // there are enough successes, return the first one
-        let (_, res) = successes
+        let (_, mut res) = successes
             .into_iter()
             .next()
             .expect("successes is not empty");
 
+        if !consistency_token.is_empty() {
+            res.consistency_token = Some(consistency_token.to_string());
+        }
+        res.acknowledgments = Some(acknowledgments);
+
         Ok(res)
     }
 
@@ -332,6 +488,10 @@ impl ShardReplicaSet {
         operation: CollectionUpdateOperations,
         wait: bool,
         ordering: WriteOrdering,
+        shard_deactivation_timeout: Option<Duration>,
+        write_consistency: Option<WriteConsistency>,
+        timeout: Option<Duration>,
+        priority: Option<UpdatePriority>,
     ) -> CollectionResult<UpdateResult> {
         let remotes_guard = self.remotes.read().await;
         let remote_leader = remotes_guard.iter().find(|r| r.peer_id == leader_peer);
@@ -339,7 +499,15 @@ impl ShardReplicaSet {
         match remote_leader {
             Some(remote_leader) => {
                 remote_leader
-                    .forward_update(operation, wait, ordering)
+                    .forward_update(
+                        operation,
+                        wait,
+                        ordering,
+                        shard_deactivation_timeout,
+                        write_consistency,
+                        timeout,
+                        priority,
+                    )
                     .await
             }
             None => Err(CollectionError::service_error(format!(
@@ -348,6 +516,114 @@ impl ShardReplicaSet {
             ))),
         }
     }
+
+    /// Update the shard, returning once enough replicas applied the update
+    ///
+    /// The local replica is always awaited. Remote replicas are updated in background tasks, those
+    /// which are still running once `minimal_success_count` replicas succeeded complete the update
+    /// after the response is returned. Active replicas failing it in background are reported to
+    /// the consensus to be deactivated, so they are recovered later.
+    ///
+    /// `ordering_guard` is released once all remote replicas are completed, in background if needed.
+    ///
+    /// Returns results of the replicas completed before the response.
+    async fn update_hedged(
+        &self,
+        local_update: Option<impl Future<Output = ReplicaUpdateResult>>,
+        remote_updates: Vec<(
+            PeerId,
+            impl Future<Output = ReplicaUpdateResult> + Send + 'static,
+        )>,
+        ordering_guard: Option<OwnedMutexGuard<()>>,
+        minimal_success_count: usize,
+    ) -> Vec<ReplicaUpdateResult> {
+        let mut results = Vec::with_capacity(remote_updates.len() + 1);
+
+        let mut remote_updates: FuturesUnordered<_> = remote_updates
+            .into_iter()
+            .map(|(peer_id, remote_update)| {
+                self.update_runtime
+                    .spawn(remote_update)
+                    .map(move |res| res.unwrap_or_else(|err| Err((peer_id, err.into()))))
+            })
+            .collect();
+
+        if let Some(local_update) = local_update {
+            results.push(local_update.await);
+        }
+
+        let mut successes = results.iter().filter(|res| res.is_ok()).count();
+
+        while successes < minimal_success_count {
+            let Some(result) = remote_updates.next().await else {
+                break;
+            };
+            successes += usize::from(result.is_ok());
+            results.push(result);
+        }
+
+        if !remote_updates.is_empty() {
+            let collection_id = self.collection_id.clone();
+            let shard_id = self.shard_id;
+            let replica_state = self.replica_state.clone();
+            let notify_peer_failure = self.notify_peer_failure_cb.clone();
+            let replica_lag = self.replica_lag.clone();
+
+            self.update_runtime.spawn(async move {
+                // Next ordered update waits for the stragglers
+                let _ordering_guard = ordering_guard;
+
+                while let Some(result) = remote_updates.next().await {
+                    let (peer_id, err) = match result {
+                        Ok((peer_id, _)) => {
+                            replica_lag.lock().record_applied(peer_id);
+                            continue;
+                        }
+                        Err(failure) => failure,
+                    };
+                    replica_lag.lock().record_failed(peer_id);
+
+                    log::warn!(
+                        "Failed to update shard {collection_id}:{shard_id} on peer {peer_id} in background, error: {err}"
+                    );
+
+                    // Active replica missed the update, it has to be recovered
+                    if replica_state.read().get_peer_state(&peer_id) == Some(&ReplicaState::Active)
+                    {
+                        notify_peer_failure.deref()(peer_id, shard_id);
+                    }
+                }
+            });
+        }
+
+        results
+    }
+
+    /// Remember the version of the applied local update, to recover dead replicas from it
+    fn record_local_update(&self, result: &UpdateResult) {
+        if let Some(version) = result.operation_id {
+            self.recovery_points.lock().record_version(version);
+        }
+    }
+}
+
+/// Fail the update with a timeout error, if it isn't completed within the `timeout`
+async fn with_timeout<T>(
+    update: impl Future<Output = CollectionResult<T>>,
+    timeout: Option<Duration>,
+    operation: &str,
+) -> CollectionResult<T> {
+    let Some(timeout) = timeout else {
+        return update.await;
+    };
+    tokio::time::timeout(timeout, update)
+        .await
+        .unwrap_or_else(|_| {
+            Err(CollectionError::timeout(
+                timeout.as_secs() as usize,
+                operation,
+            ))
+        })
 }
 
 #[cfg(test)]
@@ -385,6 +661,53 @@ mod tests {
         assert_eq!(rs.highest_alive_replica_peer_id(), Some(4));
     }
 
+    #[tokio::test]
+    async fn test_hedged_update_holds_ordering_lock() {
+        let collection_dir = Builder::new().prefix("test_collection").tempdir().unwrap();
+        let rs = new_shard_replica_set(&collection_dir).await;
+
+        let applied = Arc::new(parking_lot::Mutex::new(Vec::new()));
+
+        let local_update = async { completed_update(1) };
+        let slow_remote_update = {
+            let applied = applied.clone();
+            async move {
+                tokio::time::sleep(Duration::from_millis(100)).await;
+                applied.lock().push(2);
+                completed_update(2)
+            }
+        };
+
+        let ordering_guard = rs.write_ordering_lock.clone().lock_owned().await;
+        let results = rs
+            .update_hedged(
+                Some(local_update),
+                vec![(2, slow_remote_update)],
+                Some(ordering_guard),
+                1,
+            )
+            .await;
+
+        // Returned with the local replica only, the slow one still applies the update
+        assert_eq!(results.len(), 1);
+        assert!(matches!(results[0], Ok((1, _))));
+        assert!(applied.lock().is_empty());
+
+        // Next ordered update can't start before the slow replica applied the previous one
+        let _ordering_guard = rs.write_ordering_lock.clone().lock_owned().await;
+        assert_eq!(*applied.lock(), vec![2]);
+    }
+
+    fn completed_update(peer_id: PeerId) -> ReplicaUpdateResult {
+        let result = UpdateResult {
+            operation_id: Some(1),
+            status: UpdateStatus::Completed,
+            consistency_token: None,
+            acknowledgments: None,
+        };
+        Ok((peer_id, result))
+    }
+
     const TEST_OPTIMIZERS_CONFIG: OptimizersConfig = OptimizersConfig {
         deleted_threshold: 0.9,
         vacuum_min_vector_number: 1000,
@@ -393,7 +716,10 @@ mod tests {
         memmap_threshold: None,
         indexing_threshold: Some(50_000),
         flush_interval_sec: 30,
+        flush_dirty_threshold: None,
+        flush_dirty_operations: None,
         max_optimization_threads: 2,
+        max_config_rebuild_segments: None,
     };
 
     async fn new_shard_replica_set(collection_dir: &TempDir) -> ShardReplicaSet {
@@ -403,6 +729,7 @@ mod tests {
         let wal_config = WalConfig {
             wal_capacity_mb: 1,
             wal_segments_ahead: 0,
+            wal_compression: Default::default(),
         };
 
         let collection_params = CollectionParams {
@@ -412,6 +739,10 @@ mod tests {
                 hnsw_config: None,
                 quantization_config: None,
                 on_disk: None,
+                normalize: None,
+                dimensionality_reduction: None,
+                multivector_config: None,
+                datatype: None,
             }),
             shard_number: NonZeroU32::new(4).unwrap(),
             replication_factor: NonZeroU32::new(3).unwrap(),
@@ -425,6 +756,8 @@ mod tests {
             wal_config,
             hnsw_config: Default::default(),
             quantization_config: None,
+            strict_mode_config: None,
+            feature_flags: None,
         };
 
         let shared_config = Arc::new(RwLock::new(config.clone()));
@@ -0,0 +1,345 @@
+//! Scroll sessions: consistent view of the shard for the whole scroll, not just for one page
+//!
+//! The first page of the session remembers the time of its opening. Pages are read from the current
+//! segments of the shard, as points are scrolled in the order of their IDs, moving the points
+//! between segments by updates and optimizations doesn't change the pages. Changes of the points
+//! are hidden the same way as for time-travel reads: while sessions are open, the update worker
+//! records the states of the points before they are changed, and the session restores the points,
+//! changed since it was opened. So all pages of all scrolls within the session see the shard as
+//! it was when the session was opened.
+//!
+//! Sessions don't hold the segments, so optimizers are not blocked by them. The history of the
+//! points is kept in memory for the oldest open session, so the sessions expire after their TTL
+//! without reads, [`SCROLL_SESSION_TIMEOUT`] by default, and at most [`MAX_SCROLL_SESSION_LIFETIME`]
+//! after their opening.
+//!
+//! Each shard keeps the session only on the replica, which served its first page. The
+//! [`SessionToken`], returned to the client, remembers that replica of every shard, so all
+//! following pages of the shard are read from it.
+//!
+//! The session is passed to the shards as an argument of the scroll, as [`SessionRef`].
+
+use std::collections::hash_map::Entry;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+
+use crate::operations::consistency_token::ConsistencyToken;
+use crate::operations::types::{CollectionError, CollectionResult};
+use crate::shards::replica_set::ShardReplicaSet;
+use crate::shards::shard::{PeerId, ShardId};
+use crate::shards::time_travel;
+
+/// Default time without reads, after which the scroll session is closed
+pub const SCROLL_SESSION_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Time since the opening, after which the scroll session is closed, even if it is still read
+pub const MAX_SCROLL_SESSION_LIFETIME: Duration = Duration::from_secs(60 * 60);
+
+/// Max number of open scroll sessions of the shard
+pub const MAX_SCROLL_SESSIONS: usize = 128;
+
+/// Max length of the session ID, chosen by the client
+pub const MAX_SESSION_ID_LENGTH: usize = 128;
+
+/// Scroll session of the collection, as referenced by the client
+///
+/// Encoded as the ID of the session, followed by `~shard_id:peer_id,...` with the replicas, which
+/// serve the pages of the shards. Sessions, started by the client with a plain ID, get their
+/// replicas assigned by the first page.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SessionToken {
+    /// ID of the session in the shards
+    pub id: String,
+    /// Replica, which keeps the session of each shard
+    pub replicas: BTreeMap<ShardId, PeerId>,
+}
+
+impl SessionToken {
+    pub fn new(id: String) -> Self {
+        Self {
+            id,
+            replicas: BTreeMap::new(),
+        }
+    }
+
+    /// Read requirement, which routes the shard to the replica with the session
+    ///
+    /// The first page pins the shard to one of its replicas, following pages of the shards, which
+    /// were not pinned by the first page, are read as usual.
+    pub async fn shard_token(
+        &mut self,
+        shard: &ShardReplicaSet,
+        is_first_page: bool,
+    ) -> CollectionResult<Option<ConsistencyToken>> {
+        let peer_id = match self.replicas.get(&shard.shard_id) {
+            Some(&peer_id) => peer_id,
+            None if is_first_page => {
+                let peer_id = shard.scroll_session_replica().await.ok_or_else(|| {
+                    CollectionError::service_error(format!(
+                        "No active replica of shard {} to start scroll session {}",
+                        shard.shard_id, self.id,
+                    ))
+                })?;
+                self.replicas.insert(shard.shard_id, peer_id);
+                peer_id
+            }
+            None => return Ok(None),
+        };
+        // Any operation number is fine, the session is the same view of the shard anyway
+        Ok(Some(ConsistencyToken::new(shard.shard_id, [(peer_id, 0)])))
+    }
+}
+
+impl fmt::Display for SessionToken {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.id)?;
+        let mut separator = "~";
+        for (shard_id, peer_id) in &self.replicas {
+            write!(f, "{separator}{shard_id}:{peer_id}")?;
+            separator = ",";
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for SessionToken {
+    type Err = CollectionError;
+
+    fn from_str(token: &str) -> Result<Self, Self::Err> {
+        let invalid = || CollectionError::bad_request(format!("Invalid scroll session {token}"));
+
+        let (id, replicas) = token.split_once('~').unwrap_or((token, ""));
+        if id.is_empty() || id.len() > MAX_SESSION_ID_LENGTH {
+            return Err(invalid());
+        }
+
+        let mut session = Self::new(id.to_string());
+        for entry in replicas.split(',').filter(|entry| !entry.is_empty()) {
+            let (shard_id, peer_id) = entry.split_once(':').ok_or_else(invalid)?;
+            session.replicas.insert(
+                shard_id.parse().map_err(|_| invalid())?,
+                peer_id.parse().map_err(|_| invalid())?,
+            );
+        }
+        Ok(session)
+    }
+}
+
+/// Scroll session, referenced by the scroll request
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SessionRef {
+    pub id: String,
+    /// Time without reads, after which the session expires. Only used if the session is started
+    /// by the request.
+    pub ttl: Option<Duration>,
+}
+
+struct ScrollSession {
+    /// Time of the opening in milliseconds, the session sees the points as they were at that time
+    opened_at: u64,
+    opened: Instant,
+    ttl: Duration,
+    last_read: Instant,
+}
+
+impl ScrollSession {
+    fn is_expired(&self, now: Instant) -> bool {
+        now.duration_since(self.last_read) >= self.ttl
+            || now.duration_since(self.opened) >= MAX_SCROLL_SESSION_LIFETIME
+    }
+}
+
+/// Open scroll sessions of the shard
+#[derive(Default)]
+pub struct ScrollSessions {
+    sessions: HashMap<String, ScrollSession>,
+}
+
+impl ScrollSessions {
+    /// Time of the opening of the scroll session in milliseconds
+    ///
+    /// The session is started by its first page, continuation of the unknown session is an error.
+    pub fn opened_at(
+        &mut self,
+        session: &SessionRef,
+        is_first_page: bool,
+    ) -> CollectionResult<u64> {
+        let now = Instant::now();
+        self.remove_expired(now);
+
+        let session_id = &session.id;
+        let open_sessions = self.sessions.len();
+        let session = match self.sessions.entry(session_id.clone()) {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(_) if !is_first_page => {
+                return Err(CollectionError::bad_request(format!(
+                    "Scroll session {session_id} is expired or not started, scroll from the first page"
+                )));
+            }
+            Entry::Vacant(_) if open_sessions >= MAX_SCROLL_SESSIONS => {
+                return Err(CollectionError::bad_request(format!(
+                    "Too many open scroll sessions, max {MAX_SCROLL_SESSIONS}"
+                )));
+            }
+            Entry::Vacant(entry) => entry.insert(ScrollSession {
+                opened_at: time_travel::now_millis(),
+                opened: now,
+                ttl: session.ttl.unwrap_or(SCROLL_SESSION_TIMEOUT),
+                last_read: now,
+            }),
+        };
+        session.last_read = now;
+        Ok(session.opened_at)
+    }
+
+    /// Close the sessions, which were not read for their TTL or are open for too long
+    pub fn remove_expired(&mut self, now: Instant) {
+        self.sessions.retain(|_, session| !session.is_expired(now));
+    }
+
+    /// Time of the opening of the oldest open session in milliseconds
+    ///
+    /// States of the points, changed since then, have to be recorded for the sessions.
+    pub fn oldest_opened_at(&self) -> Option<u64> {
+        self.sessions
+            .values()
+            .map(|session| session.opened_at)
+            .min()
+    }
+
+    pub fn len(&self) -> usize {
+        self.sessions.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.sessions.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::AtomicBool;
+    use std::sync::Arc;
+
+    use parking_lot::RwLock;
+    use tempfile::Builder;
+
+    use super::*;
+    use crate::collection_manager::fixtures::{get_merge_optimizer, random_segment};
+    use crate::collection_manager::holders::segment_holder::SegmentHolder;
+    use crate::collection_manager::optimizers::segment_optimizer::SegmentOptimizer;
+
+    fn session(id: &str, ttl: Option<Duration>) -> SessionRef {
+        SessionRef {
+            id: id.to_string(),
+            ttl,
+        }
+    }
+
+    #[test]
+    fn test_scroll_sessions() {
+        let mut sessions = ScrollSessions::default();
+        let (a, b) = (session("a", None), session("b", None));
+
+        // Session can't be continued before it is started
+        assert!(sessions.opened_at(&a, false).is_err());
+
+        let opened_at = sessions.opened_at(&a, true).unwrap();
+        assert_eq!(sessions.opened_at(&a, false).unwrap(), opened_at);
+        // Starting page of the open session doesn't reopen it
+        assert_eq!(sessions.opened_at(&a, true).unwrap(), opened_at);
+
+        assert!(sessions.opened_at(&b, true).unwrap() >= opened_at);
+        assert_eq!(sessions.len(), 2);
+        assert_eq!(sessions.oldest_opened_at(), Some(opened_at));
+
+        sessions.remove_expired(Instant::now() + SCROLL_SESSION_TIMEOUT);
+        assert!(sessions.is_empty());
+        assert!(sessions.opened_at(&a, false).is_err());
+    }
+
+    #[test]
+    fn test_session_token() {
+        let token: SessionToken = "session".parse().unwrap();
+        assert_eq!(token, SessionToken::new("session".to_string()));
+        assert_eq!(token.to_string(), "session");
+
+        let token = SessionToken {
+            id: "session".to_string(),
+            replicas: BTreeMap::from([(0, 10), (2, 11)]),
+        };
+        assert_eq!(token.to_string(), "session~0:10,2:11");
+        assert_eq!(token.to_string().parse::<SessionToken>().unwrap(), token);
+
+        assert!("".parse::<SessionToken>().is_err());
+        assert!("session~0".parse::<SessionToken>().is_err());
+        assert!("session~0:peer".parse::<SessionToken>().is_err());
+    }
+
+    #[test]
+    fn test_scroll_session_ttl() {
+        let mut sessions = ScrollSessions::default();
+
+        let ttl = SCROLL_SESSION_TIMEOUT * 10;
+        sessions
+            .opened_at(&session("long", Some(ttl)), true)
+            .unwrap();
+        sessions.opened_at(&session("short", None), true).unwrap();
+
+        sessions.remove_expired(Instant::now() + SCROLL_SESSION_TIMEOUT);
+        assert_eq!(sessions.len(), 1);
+        // TTL of the session is set by its first page only
+        assert!(sessions.opened_at(&session("long", None), false).is_ok());
+
+        sessions.remove_expired(Instant::now() + ttl);
+        assert!(sessions.is_empty());
+        assert_eq!(sessions.oldest_opened_at(), None);
+    }
+
+    #[test]
+    fn test_scroll_session_lifetime() {
+        let mut sessions = ScrollSessions::default();
+
+        // Session is closed after its max lifetime, even if it is read all the time
+        let ttl = MAX_SCROLL_SESSION_LIFETIME * 2;
+        sessions.opened_at(&session("a", Some(ttl)), true).unwrap();
+        sessions.remove_expired(Instant::now() + MAX_SCROLL_SESSION_LIFETIME / 2);
+        assert_eq!(sessions.len(), 1);
+        sessions.remove_expired(Instant::now() + MAX_SCROLL_SESSION_LIFETIME);
+        assert!(sessions.is_empty());
+    }
+
+    #[test]
+    fn test_optimization_with_open_session() {
+        let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+        let temp_dir = Builder::new().prefix("segment_temp_dir").tempdir().unwrap();
+        let dim = 4;
+
+        let mut holder = SegmentHolder::default();
+        let segment_ids = vec![
+            holder.add(random_segment(dir.path(), 100, 3, dim)),
+            holder.add(random_segment(dir.path(), 100, 3, dim)),
+        ];
+        let segments = Arc::new(RwLock::new(holder));
+
+        let mut sessions = ScrollSessions::default();
+        let a = session("a", None);
+        let opened_at = sessions.opened_at(&a, true).unwrap();
+
+        // Optimized segments are dropped right away, the session doesn't hold them
+        let merge_optimizer = get_merge_optimizer(dir.path(), temp_dir.path(), dim);
+        merge_optimizer
+            .optimize(segments.clone(), segment_ids, &AtomicBool::new(false))
+            .unwrap();
+
+        let segments = segments.read();
+        assert_eq!(segments.len(), 1);
+        let (_, segment) = segments.iter().next().unwrap();
+        assert_eq!(segment.get().read().available_point_count(), 6);
+
+        // Session is still open, its pages are read from the optimized segment
+        assert_eq!(sessions.opened_at(&a, false).unwrap(), opened_at);
+    }
+}
@@ -0,0 +1,269 @@
+//! Background scrubbing of the shard storage
+//!
+//! Files of the built segments are never changed, so they are protected by block checksums,
+//! written once the segment is built. Payloads of the append-log payload storage are checksummed on
+//! each write. The scrubber periodically reads all of them with low priority IO and compares
+//! them with their checksums, so silent corruption of the disk is detected before it is served.
+//!
+//! Only the data, which can't be restored from the segment itself, is covered: vector storages and
+//! payloads. HNSW graphs, quantized vectors and payload indexes are derived from them and are not
+//! verified. Segments, built before checksums were introduced, are not verified either, until
+//! they are optimized: checksums, written for the files as they are now, would accept the damage
+//! they might already have.
+//!
+//! Corruption is reported in the log and in the optimizer status of the shard. Optionally,
+//! corrupted segments are quarantined: removed from the shard and moved into the
+//! [`QUARANTINE_DIR`] next to the segments directory, so they are not loaded on restart either.
+//! Points of the quarantined segments have to be recovered from other replicas or snapshots.
+
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use parking_lot::RwLock;
+use segment::common::checksums;
+use segment::segment::Segment;
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+use crate::collection_manager::holders::segment_holder::{
+    LockedSegment, LockedSegmentHolder, SegmentId,
+};
+use crate::common::io_budget::IoBudget;
+use crate::operations::types::{CollectionError, CollectionResult};
+
+/// Directory of the quarantined segments, next to the segments directory of the shard
+pub const QUARANTINE_DIR: &str = "quarantine";
+
+/// Periodic verification of the checksums of the storage
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq, Default, Validate)]
+pub struct ScrubberConfig {
+    /// Interval between the scrubs of each shard. If not set - storage is not scrubbed
+    #[serde(default)]
+    #[validate(range(min = 1))]
+    pub scrub_interval_sec: Option<u64>,
+    /// Remove corrupted segments from the shard, instead of only reporting them
+    #[serde(default)]
+    pub quarantine: bool,
+}
+
+/// Corruption, found in a single segment
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SegmentCorruption {
+    pub segment_path: PathBuf,
+    /// Corrupted files with the ids of their corrupted blocks
+    pub corrupted_files: Vec<(PathBuf, Vec<usize>)>,
+    /// Internal ids of the points with corrupted payload
+    pub corrupted_payloads: Vec<usize>,
+}
+
+impl fmt::Display for SegmentCorruption {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "segment {} is corrupted:", self.segment_path.display())?;
+        for (path, blocks) in &self.corrupted_files {
+            write!(f, " {} blocks of {},", blocks.len(), path.display())?;
+        }
+        write!(f, " {} payloads", self.corrupted_payloads.len())
+    }
+}
+
+/// Verify checksums of all segments of the shard, report and optionally quarantine corrupted ones
+///
+/// Segments, which are being optimized, are skipped, they are verified once optimized.
+/// `is_stopped` is checked between the segments.
+pub fn scrub_segments(
+    segments: &LockedSegmentHolder,
+    io_budget: &IoBudget,
+    quarantine: bool,
+    is_stopped: impl Fn() -> bool,
+) -> Vec<SegmentCorruption> {
+    let to_scrub: Vec<_> = segments
+        .read()
+        .iter()
+        .filter_map(|(&segment_id, segment)| match segment {
+            LockedSegment::Original(segment) => Some((segment_id, segment.clone())),
+            LockedSegment::Proxy(_) => None,
+        })
+        .collect();
+
+    let mut corruptions = Vec::new();
+    for (segment_id, segment) in to_scrub {
+        if is_stopped() {
+            break;
+        }
+        let corruption = match scrub_segment(&segment, io_budget) {
+            Ok(Some(corruption)) => corruption,
+            Ok(None) => continue,
+            Err(err) => {
+                // Segment might be removed by an optimization in the meantime
+                log::warn!("Failed to scrub segment: {err}");
+                continue;
+            }
+        };
+
+        log::error!("Scrubber found corruption: {corruption}");
+        segments
+            .write()
+            .report_optimizer_error(CollectionError::service_error(corruption.to_string()));
+        if quarantine {
+            if let Err(err) = quarantine_segment(segments, segment_id, &segment) {
+                log::error!("Failed to quarantine corrupted segment: {err}");
+            }
+        }
+        corruptions.push(corruption);
+    }
+    corruptions
+}
+
+/// Compare files and payloads of the segment with their checksums
+///
+/// Files of older segments, which don't have checksums, are skipped.
+fn scrub_segment(
+    segment: &RwLock<Segment>,
+    io_budget: &IoBudget,
+) -> CollectionResult<Option<SegmentCorruption>> {
+    let (segment_path, files) = {
+        let segment = segment.read();
+        (segment.current_path.clone(), segment.checksummed_files())
+    };
+
+    // Files are immutable, so they are read without blocking the segment
+    let mut corrupted_files = Vec::new();
+    for path in files {
+        if !checksums::has_checksums(&path) {
+            continue;
+        }
+        let verification =
+            checksums::verify_checksums(&path, &mut |bytes| io_budget.acquire_blocking(bytes))?;
+        if !verification.is_valid() {
+            corrupted_files.push((path, verification.corrupted));
+        }
+    }
+
+    let verification = segment.read().verify_payload_checksums()?;
+    // Payloads are read under the segment lock, so the budget is paid off after the fact
+    io_budget.acquire_blocking(verification.bytes_read);
+
+    if corrupted_files.is_empty() && verification.is_valid() {
+        return Ok(None);
+    }
+    Ok(Some(SegmentCorruption {
+        segment_path,
+        corrupted_files,
+        corrupted_payloads: verification.corrupted,
+    }))
+}
+
+/// Remove the segment from the shard and move its files into the quarantine directory
+fn quarantine_segment(
+    segments: &LockedSegmentHolder,
+    segment_id: SegmentId,
+    segment: &Arc<RwLock<Segment>>,
+) -> CollectionResult<()> {
+    let mut segments = segments.write();
+    // Segment might be replaced by an optimization since it was scrubbed
+    let is_same_segment = matches!(
+        segments.get(segment_id),
+        Some(LockedSegment::Original(current)) if Arc::ptr_eq(current, segment),
+    );
+    if !is_same_segment {
+        return Ok(());
+    }
+    segments.remove(&[segment_id]);
+    drop(segments);
+
+    let segment_path = segment.read().current_path.clone();
+    let quarantine_path = quarantine_path(&segment_path).ok_or_else(|| {
+        CollectionError::service_error(format!(
+            "Can't quarantine segment {}",
+            segment_path.display(),
+        ))
+    })?;
+    std::fs::create_dir_all(quarantine_path.parent().unwrap())?;
+    std::fs::rename(&segment_path, &quarantine_path)?;
+    log::warn!(
+        "Corrupted segment is quarantined to {}",
+        quarantine_path.display(),
+    );
+    Ok(())
+}
+
+/// `<shard>/segments/<segment>` -> `<shard>/quarantine/<segment>`
+fn quarantine_path(segment_path: &Path) -> Option<PathBuf> {
+    let segment_name = segment_path.file_name()?;
+    let shard_path = segment_path.parent()?.parent()?;
+    Some(shard_path.join(QUARANTINE_DIR).join(segment_name))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::OpenOptions;
+    use std::io::{Seek, SeekFrom, Write};
+
+    use tempfile::Builder;
+
+    use super::*;
+    use crate::collection_manager::fixtures::{optimize_segment, random_segment};
+    use crate::collection_manager::holders::segment_holder::SegmentHolder;
+
+    #[test]
+    fn test_scrub_segments() {
+        let dir = Builder::new().prefix("shard_dir").tempdir().unwrap();
+        let segments_dir = dir.path().join("segments");
+        std::fs::create_dir_all(&segments_dir).unwrap();
+
+        // Large enough to be optimized into a segment with mmap vector storage
+        let segment = optimize_segment(random_segment(&segments_dir, 100, 2000, 16));
+        let LockedSegment::Original(original) = &segment else {
+            panic!("optimized segment must not be a proxy");
+        };
+        let original = original.clone();
+        let files = original.read().checksummed_files();
+        assert!(!files.is_empty());
+        assert!(files.iter().all(|path| checksums::has_checksums(path)));
+
+        let mut holder = SegmentHolder::default();
+        holder.add(segment);
+        let segments: LockedSegmentHolder = Arc::new(RwLock::new(holder));
+        let io_budget = IoBudget::default();
+
+        assert!(scrub_segments(&segments, &io_budget, true, || false).is_empty());
+
+        // Files without checksums are not verified, and don't get checksums from the scrubber
+        let checksums_path = checksums::checksums_path(&files[0]);
+        let stored_checksums = std::fs::read(&checksums_path).unwrap();
+        std::fs::remove_file(&checksums_path).unwrap();
+        assert!(scrub_segments(&segments, &io_budget, true, || false).is_empty());
+        assert!(!checksums::has_checksums(&files[0]));
+        std::fs::write(&checksums_path, stored_checksums).unwrap();
+
+        // Flip a bit in the middle of the vectors, file is mapped, so it is not rewritten
+        let byte = std::fs::read(&files[0]).unwrap()[1000];
+        let mut file = OpenOptions::new().write(true).open(&files[0]).unwrap();
+        file.seek(SeekFrom::Start(1000)).unwrap();
+        file.write_all(&[byte ^ 1]).unwrap();
+        drop(file);
+
+        // Reported only
+        let corruptions = scrub_segments(&segments, &io_budget, false, || false);
+        assert_eq!(corruptions.len(), 1);
+        assert_eq!(
+            corruptions[0].corrupted_files,
+            vec![(files[0].clone(), vec![0])]
+        );
+        assert_eq!(segments.read().len(), 1);
+        assert!(segments.read().optimizer_errors.is_some());
+
+        // Quarantined
+        let segment_path = original.read().current_path.clone();
+        let corruptions = scrub_segments(&segments, &io_budget, true, || false);
+        assert_eq!(corruptions.len(), 1);
+        assert!(segments.read().is_empty());
+        assert!(!segment_path.exists());
+        assert!(dir
+            .path()
+            .join(QUARANTINE_DIR)
+            .join(segment_path.file_name().unwrap())
+            .exists());
+    }
+}
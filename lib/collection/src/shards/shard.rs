@@ -1,16 +1,22 @@
 use core::marker::{Send, Sync};
 use std::future::{self, Future};
 use std::path::Path;
+use std::time::Instant;
+
+use segment::types::SeqNumberType;
 
 use super::update_tracker::UpdateTracker;
-use crate::operations::types::CollectionResult;
+use crate::collection_manager::optimizers::Tracker;
+use crate::operations::types::{CollectionError, CollectionResult, CompactionResult, WarmUpResult};
 use crate::shards::dummy_shard::DummyShard;
 use crate::shards::forward_proxy_shard::ForwardProxyShard;
 use crate::shards::local_shard::LocalShard;
 use crate::shards::proxy_shard::ProxyShard;
 use crate::shards::queue_proxy_shard::QueueProxyShard;
 use crate::shards::shard_trait::ShardOperation;
+use crate::shards::split_proxy_shard::SplitProxyShard;
 use crate::shards::telemetry::LocalShardTelemetry;
+use crate::shards::wal_archive::WalArchiveResult;
 
 pub type ShardId = u32;
 
@@ -37,6 +43,7 @@ pub enum Shard {
     Local(LocalShard),
     Proxy(ProxyShard),
     ForwardProxy(ForwardProxyShard),
+    SplitProxy(SplitProxyShard),
     QueueProxy(QueueProxyShard),
     Dummy(DummyShard),
 }
@@ -47,6 +54,7 @@ impl Shard {
             Shard::Local(_) => "local shard",
             Shard::Proxy(_) => "proxy shard",
             Shard::ForwardProxy(_) => "forward proxy shard",
+            Shard::SplitProxy(_) => "split proxy shard",
             Shard::QueueProxy(_) => "queue proxy shard",
             Shard::Dummy(_) => "dummy shard",
         }
@@ -57,6 +65,7 @@ impl Shard {
             Shard::Local(local_shard) => local_shard,
             Shard::Proxy(proxy_shard) => proxy_shard,
             Shard::ForwardProxy(proxy_shard) => proxy_shard,
+            Shard::SplitProxy(proxy_shard) => proxy_shard,
             Shard::QueueProxy(proxy_shard) => proxy_shard,
             Shard::Dummy(dummy_shard) => dummy_shard,
         }
@@ -67,6 +76,7 @@ impl Shard {
             Shard::Local(local_shard) => local_shard.get_telemetry_data(),
             Shard::Proxy(proxy_shard) => proxy_shard.get_telemetry_data(),
             Shard::ForwardProxy(proxy_shard) => proxy_shard.get_telemetry_data(),
+            Shard::SplitProxy(proxy_shard) => proxy_shard.get_telemetry_data(),
             Shard::QueueProxy(proxy_shard) => proxy_shard.get_telemetry_data(),
             Shard::Dummy(dummy_shard) => dummy_shard.get_telemetry_data(),
         };
@@ -74,6 +84,17 @@ impl Shard {
         telemetry
     }
 
+    pub fn running_optimizations(&self) -> Vec<Tracker> {
+        match self {
+            Shard::Local(local_shard) => local_shard.running_optimizations(),
+            Shard::Proxy(proxy_shard) => proxy_shard.running_optimizations(),
+            Shard::ForwardProxy(proxy_shard) => proxy_shard.running_optimizations(),
+            Shard::SplitProxy(proxy_shard) => proxy_shard.running_optimizations(),
+            Shard::QueueProxy(proxy_shard) => proxy_shard.running_optimizations(),
+            Shard::Dummy(dummy_shard) => dummy_shard.running_optimizations(),
+        }
+    }
+
     pub async fn create_snapshot(
         &self,
         temp_path: &Path,
@@ -96,6 +117,11 @@ impl Shard {
                     .create_snapshot(temp_path, target_path, save_wal)
                     .await
             }
+            Shard::SplitProxy(proxy_shard) => {
+                proxy_shard
+                    .create_snapshot(temp_path, target_path, save_wal)
+                    .await
+            }
             Shard::QueueProxy(proxy_shard) => {
                 proxy_shard
                     .create_snapshot(temp_path, target_path, save_wal)
@@ -114,11 +140,86 @@ impl Shard {
             Shard::Local(local_shard) => local_shard.on_optimizer_config_update().await,
             Shard::Proxy(proxy_shard) => proxy_shard.on_optimizer_config_update().await,
             Shard::ForwardProxy(proxy_shard) => proxy_shard.on_optimizer_config_update().await,
+            Shard::SplitProxy(proxy_shard) => proxy_shard.on_optimizer_config_update().await,
             Shard::QueueProxy(proxy_shard) => proxy_shard.on_optimizer_config_update().await,
             Shard::Dummy(dummy_shard) => dummy_shard.on_optimizer_config_update().await,
         }
     }
 
+    pub async fn warm_up(&self) -> CollectionResult<WarmUpResult> {
+        match self {
+            Shard::Local(local_shard) => local_shard.warm_up().await,
+            Shard::Proxy(proxy_shard) => proxy_shard.warm_up().await,
+            Shard::ForwardProxy(proxy_shard) => proxy_shard.warm_up().await,
+            Shard::SplitProxy(proxy_shard) => proxy_shard.warm_up().await,
+            Shard::QueueProxy(proxy_shard) => proxy_shard.warm_up().await,
+            Shard::Dummy(dummy_shard) => dummy_shard.warm_up().await,
+        }
+    }
+
+    pub async fn shutdown(&self, deadline: Instant) -> CollectionResult<()> {
+        match self {
+            Shard::Local(local_shard) => local_shard.shutdown(deadline).await,
+            Shard::Proxy(proxy_shard) => proxy_shard.shutdown(deadline).await,
+            Shard::ForwardProxy(proxy_shard) => proxy_shard.shutdown(deadline).await,
+            Shard::SplitProxy(proxy_shard) => proxy_shard.shutdown(deadline).await,
+            Shard::QueueProxy(proxy_shard) => proxy_shard.shutdown(deadline).await,
+            Shard::Dummy(dummy_shard) => dummy_shard.shutdown(deadline).await,
+        }
+    }
+
+    pub async fn compact(&self) -> CollectionResult<CompactionResult> {
+        match self {
+            Shard::Local(local_shard) => local_shard.compact().await,
+            Shard::Proxy(proxy_shard) => proxy_shard.compact().await,
+            Shard::ForwardProxy(proxy_shard) => proxy_shard.compact().await,
+            Shard::SplitProxy(proxy_shard) => proxy_shard.compact().await,
+            Shard::QueueProxy(proxy_shard) => proxy_shard.compact().await,
+            Shard::Dummy(dummy_shard) => dummy_shard.compact().await,
+        }
+    }
+
+    pub async fn archive_wal(&self, archive_path: &Path) -> CollectionResult<WalArchiveResult> {
+        match self {
+            Shard::Local(local_shard) => local_shard.archive_wal(archive_path).await,
+            Shard::Proxy(proxy_shard) => proxy_shard.archive_wal(archive_path).await,
+            Shard::ForwardProxy(proxy_shard) => proxy_shard.archive_wal(archive_path).await,
+            Shard::SplitProxy(proxy_shard) => proxy_shard.archive_wal(archive_path).await,
+            Shard::QueueProxy(proxy_shard) => proxy_shard.archive_wal(archive_path).await,
+            Shard::Dummy(dummy_shard) => dummy_shard.archive_wal(archive_path).await,
+        }
+    }
+
+    pub async fn stop_wal_archiving(&self) -> CollectionResult<()> {
+        match self {
+            Shard::Local(local_shard) => local_shard.stop_wal_archiving().await,
+            Shard::Proxy(proxy_shard) => proxy_shard.stop_wal_archiving().await,
+            Shard::ForwardProxy(proxy_shard) => proxy_shard.stop_wal_archiving().await,
+            Shard::SplitProxy(proxy_shard) => proxy_shard.stop_wal_archiving().await,
+            Shard::QueueProxy(proxy_shard) => proxy_shard.stop_wal_archiving().await,
+            Shard::Dummy(dummy_shard) => dummy_shard.stop_wal_archiving().await,
+        }
+    }
+
+    /// Only local shards could be replayed, proxies would bypass the shard transfer
+    pub async fn replay_wal_archive(
+        &self,
+        archive_path: &Path,
+        until_version: Option<SeqNumberType>,
+    ) -> CollectionResult<usize> {
+        match self {
+            Shard::Local(local_shard) => {
+                local_shard
+                    .replay_wal_archive(archive_path, until_version)
+                    .await
+            }
+            _ => Err(CollectionError::bad_request(format!(
+                "Can't replay WAL archive into {}",
+                self.variant_name(),
+            ))),
+        }
+    }
+
     pub fn is_update_in_progress(&self) -> bool {
         self.update_tracker()
             .map_or(false, UpdateTracker::is_update_in_progress)
@@ -135,11 +236,17 @@ impl Shard {
         }
     }
 
+    /// Number of the latest operation, applied by the local shard
+    pub fn applied_operation(&self) -> Option<SeqNumberType> {
+        self.update_tracker().map(UpdateTracker::applied_operation)
+    }
+
     fn update_tracker(&self) -> Option<&UpdateTracker> {
         let update_tracker = match self {
             Self::Local(local_shard) => local_shard.update_tracker(),
             Self::Proxy(proxy_shard) => proxy_shard.update_tracker(),
             Self::ForwardProxy(proxy_shard) => proxy_shard.update_tracker(),
+            Self::SplitProxy(proxy_shard) => proxy_shard.update_tracker(),
             Self::QueueProxy(proxy_shard) => proxy_shard.update_tracker(),
             Self::Dummy(_) => return None,
         };
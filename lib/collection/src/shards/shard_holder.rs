@@ -1,10 +1,12 @@
 use std::collections::{HashMap, HashSet};
+use std::hash::Hash;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
+use io::file_operations::atomic_save_json;
 use itertools::Itertools;
 // TODO rename ReplicaShard to ReplicaSetShard
-use segment::types::ShardKey;
+use segment::types::{PointIdType, ShardKey};
 use tar::Builder as TarBuilder;
 use tokio::runtime::Handle;
 use tokio::sync::RwLock;
@@ -19,18 +21,19 @@ use crate::operations::snapshot_ops::{
     get_snapshot_description, list_snapshots_in_directory, SnapshotDescription,
 };
 use crate::operations::types::{CollectionError, CollectionResult, ShardTransferInfo};
-use crate::operations::{OperationToShard, SplitByShard};
+use crate::operations::{OperationToShard, ShardRouter, SplitByShard};
 use crate::save_on_disk::SaveOnDisk;
 use crate::shards::channel_service::ChannelService;
 use crate::shards::local_shard::LocalShard;
 use crate::shards::replica_set::{ChangePeerState, ReplicaState, ShardReplicaSet}; // TODO rename ReplicaShard to ReplicaSetShard
 use crate::shards::shard::{PeerId, ShardId};
 use crate::shards::shard_config::{ShardConfig, ShardType};
+use crate::shards::shard_split::{self, ShardSplit, ShardSplitState, SHARD_SPLITS_FILE};
 use crate::shards::shard_versioning::latest_shard_paths;
 use crate::shards::transfer::{ShardTransfer, ShardTransferKey};
 use crate::shards::CollectionId;
 
-const HASH_RING_SHARD_SCALE: u32 = 100;
+pub(crate) const HASH_RING_SHARD_SCALE: u32 = 100;
 
 const SHARD_TRANSFERS_FILE: &str = "shard_transfers";
 pub const SHARD_KEY_MAPPING_FILE: &str = "shard_key_mapping.json";
@@ -45,6 +48,9 @@ pub struct ShardHolder {
     // Duplicates the information from `key_mapping` for faster access
     // Do not require locking
     shard_id_to_key_mapping: HashMap<ShardId, ShardKey>,
+    shard_splits: SaveOnDisk<Vec<ShardSplit>>,
+    // Rings of the finished splits, by the source shard, derived from `shard_splits`
+    split_rings: HashMap<ShardId, HashRing<ShardId>>,
 }
 
 pub type LockedShardHolder = RwLock<ShardHolder>;
@@ -56,6 +62,9 @@ impl ShardHolder {
         let shard_transfers = SaveOnDisk::load_or_init(collection_path.join(SHARD_TRANSFERS_FILE))?;
         let key_mapping: SaveOnDisk<ShardKeyMapping> =
             SaveOnDisk::load_or_init(collection_path.join(SHARD_KEY_MAPPING_FILE))?;
+        let shard_splits: SaveOnDisk<Vec<ShardSplit>> =
+            SaveOnDisk::load_or_init(collection_path.join(SHARD_SPLITS_FILE))?;
+        let split_rings = shard_split::split_rings(&shard_splits.read());
         let mut shard_id_to_key_mapping = HashMap::new();
 
         for (shard_key, shard_ids) in key_mapping.read().iter() {
@@ -70,6 +79,8 @@ impl ShardHolder {
             rings,
             key_mapping,
             shard_id_to_key_mapping,
+            shard_splits,
+            split_rings,
         })
     }
 
@@ -79,6 +90,19 @@ impl ShardHolder {
         Ok(())
     }
 
+    /// Save finished splits, the unfinished ones are not part of the snapshot
+    pub fn save_shard_splits_to_dir(&self, dir: &Path) -> CollectionResult<()> {
+        let finished_splits: Vec<_> = self
+            .shard_splits
+            .read()
+            .iter()
+            .filter(|split| split.is_done())
+            .cloned()
+            .collect();
+        atomic_save_json(&dir.join(SHARD_SPLITS_FILE), &finished_splits)?;
+        Ok(())
+    }
+
     pub fn get_shard_id_to_key_mapping(&self) -> &HashMap<ShardId, ShardKey> {
         &self.shard_id_to_key_mapping
     }
@@ -103,10 +127,13 @@ impl ShardHolder {
         shard_key: Option<ShardKey>,
     ) -> Result<(), CollectionError> {
         self.shards.insert(shard_id, shard);
-        self.rings
-            .entry(shard_key.clone())
-            .or_insert_with(|| HashRing::fair(HASH_RING_SHARD_SCALE))
-            .add(shard_id);
+        // Targets of the splits are reached through the ring of the source shard
+        if !self.is_split_target(shard_id) {
+            self.rings
+                .entry(shard_key.clone())
+                .or_insert_with(|| HashRing::fair(HASH_RING_SHARD_SCALE))
+                .add(shard_id);
+        }
 
         if let Some(shard_key) = shard_key {
             self.key_mapping.write_optional(|key_mapping| {
@@ -157,14 +184,27 @@ impl ShardHolder {
         let mut rings = HashMap::new();
         rings.insert(None, HashRing::fair(HASH_RING_SHARD_SCALE));
         let ids_to_key = self.get_shard_id_to_key_mapping();
-        for shard_id in self.shards.keys() {
-            let shard_key = ids_to_key.get(shard_id).cloned();
+        for (shard_id, shard_key) in ids_to_key {
             rings
-                .entry(shard_key)
+                .entry(Some(shard_key.clone()))
                 .or_insert_with(|| HashRing::fair(HASH_RING_SHARD_SCALE))
                 .add(*shard_id);
         }
 
+        // Only shards without a key could be split
+        let shard_splits = self.shard_splits.read();
+        let unkeyed_shard_ids = self
+            .shards
+            .keys()
+            .filter(|shard_id| !ids_to_key.contains_key(shard_id))
+            .copied();
+        let default_ring = rings.get_mut(&None).unwrap();
+        for shard_id in shard_split::ring_shard_ids(unkeyed_shard_ids, &shard_splits) {
+            default_ring.add(shard_id);
+        }
+
+        self.split_rings = shard_split::split_rings(&shard_splits);
+        drop(shard_splits);
         self.rings = rings;
     }
 
@@ -215,10 +255,24 @@ impl ShardHolder {
         self.shards.values()
     }
 
+    /// Shard of the value of the sharding payload key, for the `payload_key` sharding method
+    pub fn payload_value_to_shard(&self, value: &impl Hash) -> Option<ShardId> {
+        let shard_id = *self.rings.get(&None)?.get(value)?;
+        Some(shard_split::resolve_split(
+            &self.split_rings,
+            shard_id,
+            value,
+        ))
+    }
+
+    /// Split operation between the shards
+    ///
+    /// Points from `placement` go to the given shards, the rest is placed by the hash of their ID.
     pub fn split_by_shard<O: SplitByShard + Clone>(
         &self,
         operation: O,
         shard_keys_selection: &Option<ShardKey>,
+        placement: HashMap<PointIdType, ShardId>,
     ) -> CollectionResult<Vec<(&ShardReplicaSet, O)>> {
         let Some(hashring) = self.rings.get(shard_keys_selection) else {
             if let Some(shard_key) = shard_keys_selection {
@@ -238,34 +292,50 @@ impl ShardHolder {
             ));
         }
 
-        let operation_to_shard = operation.split_by_shard(hashring);
+        let operation_to_shard = operation.split_by_shard(&ShardRouter::with_placement(
+            hashring,
+            &self.split_rings,
+            placement,
+        ));
         let shard_ops: Vec<_> = match operation_to_shard {
             OperationToShard::ByShard(by_shard) => by_shard
                 .into_iter()
                 .map(|(shard_id, operation)| (self.shards.get(&shard_id).unwrap(), operation))
                 .collect(),
-            OperationToShard::ToAll(operation) => {
-                if let Some(shard_key) = shard_keys_selection {
-                    let shard_ids = self
-                        .key_mapping
-                        .read()
-                        .get(shard_key)
-                        .cloned()
-                        .unwrap_or_default();
-                    shard_ids
-                        .into_iter()
-                        .map(|shard_id| (self.shards.get(&shard_id).unwrap(), operation.clone()))
-                        .collect()
-                } else {
-                    self.all_shards()
-                        .map(|shard| (shard, operation.clone()))
-                        .collect()
-                }
+            OperationToShard::ToAll(operation) => self
+                .target_shard_ids(shard_keys_selection)
+                .into_iter()
+                .map(|shard_id| (self.shards.get(&shard_id).unwrap(), operation.clone()))
+                .collect(),
+            OperationToShard::ByShardWithRest(by_shard, rest) => {
+                let mut by_shard: HashMap<_, _> = by_shard.into_iter().collect();
+                self.target_shard_ids(shard_keys_selection)
+                    .into_iter()
+                    .map(|shard_id| {
+                        let operation = by_shard.remove(&shard_id).unwrap_or_else(|| rest.clone());
+                        (self.shards.get(&shard_id).unwrap(), operation)
+                    })
+                    .collect()
             }
         };
         Ok(shard_ops)
     }
 
+    /// Shards, which are updated by the operations addressed to all shards
+    fn target_shard_ids(&self, shard_keys_selection: &Option<ShardKey>) -> Vec<ShardId> {
+        match shard_keys_selection {
+            Some(shard_key) => self
+                .key_mapping
+                .read()
+                .get(shard_key)
+                .cloned()
+                .unwrap_or_default()
+                .into_iter()
+                .collect(),
+            None => self.shards.keys().copied().collect(),
+        }
+    }
+
     pub fn register_start_shard_transfer(&self, transfer: ShardTransfer) -> CollectionResult<bool> {
         Ok(self
             .shard_transfers
@@ -314,6 +384,83 @@ impl ShardHolder {
             .collect()
     }
 
+    pub fn shard_splits(&self) -> Vec<ShardSplit> {
+        self.shard_splits.read().clone()
+    }
+
+    /// Unfinished split of the shard
+    pub fn get_shard_split(&self, source: ShardId) -> Option<ShardSplit> {
+        self.shard_splits
+            .read()
+            .iter()
+            .find(|split| split.source == source && !split.is_done())
+            .cloned()
+    }
+
+    fn is_split_target(&self, shard_id: ShardId) -> bool {
+        self.shard_splits
+            .read()
+            .iter()
+            .any(|split| split.targets.contains(&shard_id))
+    }
+
+    pub fn register_start_shard_split(&self, split: ShardSplit) -> CollectionResult<bool> {
+        Ok(self.shard_splits.write(|splits| {
+            if splits.contains(&split) {
+                return false;
+            }
+            splits.push(split);
+            true
+        })?)
+    }
+
+    /// Record that the peer has filled its replicas of the split targets
+    ///
+    /// Returns the updated split, `None` if the shard is not being split.
+    pub fn register_split_copied(
+        &self,
+        source: ShardId,
+        peer_id: PeerId,
+    ) -> CollectionResult<Option<ShardSplit>> {
+        Ok(self.shard_splits.write(|splits| {
+            let split = splits
+                .iter_mut()
+                .find(|split| split.source == source && !split.is_done())?;
+            split.copied_peers.insert(peer_id);
+            Some(split.clone())
+        })?)
+    }
+
+    /// Returns `true` if the unfinished split was removed
+    pub fn register_abort_shard_split(&self, source: ShardId) -> CollectionResult<bool> {
+        Ok(self.shard_splits.write(|splits| {
+            let before_remove = splits.len();
+            splits.retain(|split| split.source != source || split.is_done());
+            before_remove != splits.len()
+        })?)
+    }
+
+    /// Replace the source shard of the split with the targets, which must be added already
+    pub async fn finish_shard_split(&mut self, source: ShardId) -> CollectionResult<()> {
+        self.shard_splits.write(|splits| {
+            for split in splits.iter_mut() {
+                if split.source == source {
+                    split.state = ShardSplitState::Done;
+                }
+            }
+        })?;
+        self.drop_and_remove_shard(source).await?;
+        self.rebuild_rings();
+        Ok(())
+    }
+
+    /// Apply splits from the consensus state, rings are rebuilt with the shards
+    pub fn apply_shard_splits(&mut self, shard_splits: Vec<ShardSplit>) -> CollectionResult<()> {
+        self.shard_splits
+            .write_optional(|splits| (*splits != shard_splits).then_some(shard_splits))?;
+        Ok(())
+    }
+
     fn get_shard_ids_by_key(&self, shard_key: &ShardKey) -> CollectionResult<HashSet<ShardId>> {
         match self.key_mapping.read().get(shard_key).cloned() {
             None => Err(CollectionError::bad_request(format!(
@@ -418,8 +565,14 @@ impl ShardHolder {
             .sharding_method
             .unwrap_or_default()
         {
-            ShardingMethod::Auto => {
-                let ids_list = (0..shard_number).collect::<Vec<_>>();
+            ShardingMethod::Auto | ShardingMethod::PayloadKey => {
+                // Every finished split replaces one shard with two
+                let shard_splits = self.shard_splits.read();
+                let finished_splits = shard_splits.iter().filter(|split| split.is_done()).count();
+                let ids_list = shard_split::live_shard_ids(
+                    shard_number - finished_splits as u32,
+                    &shard_splits,
+                );
                 let shard_id_to_key_mapping = HashMap::new();
                 (ids_list, shard_id_to_key_mapping)
             }
@@ -529,6 +682,9 @@ impl ShardHolder {
                 self.add_shard(shard_id, replica_set, shard_key).unwrap();
             }
         }
+
+        // Sources of the finished splits are not loaded, but they stay on the ring
+        self.rebuild_rings();
     }
 
     pub async fn assert_shard_exists(&self, shard_id: ShardId) -> CollectionResult<()> {
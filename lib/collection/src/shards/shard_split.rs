@@ -0,0 +1,313 @@
+//! Online splitting of a shard into two new shards
+//!
+//! A split is coordinated through consensus. When it starts, every peer with a local replica of
+//! the source shard builds local replicas of both target shards and wraps its source replica
+//! into a [`SplitProxyShard`], which applies all incoming updates to the targets as well. Points
+//! of the source shard are then streamed into the targets in batches, each point into the target
+//! its ID hashes to. Until the split is done, the targets are not visible to the requests, which
+//! are served by the source shard.
+//!
+//! Once all active replicas of the source shard report that their targets are filled, every peer
+//! replaces the source shard with the targets. The source stays on the hash ring of the
+//! collection, points which hash to it are routed further to one of the targets, so points of
+//! the other shards keep their placement.
+//!
+//! Peers switch to the targets as they apply the consensus operation. An update, which is
+//! forwarded in between to a peer which is not switched yet, fails and deactivates the replica,
+//! which is then recovered by a regular shard transfer.
+//!
+//! Splits are limited to keep the coordination simple: only collections with `Auto` sharding
+//! can be split, only one shard of the collection is split at a time, and the source must have
+//! all replicas `Active` and no shard transfers. Targets get shard IDs, which were never used in
+//! the collection, so they can't be confused with stale data of removed shards.
+//!
+//! Copying is retried like shard transfers. If it still fails on any peer, the split is aborted
+//! through consensus: every peer drops its targets and unwraps the source, which stays in place.
+//!
+//! [`SplitProxyShard`]: crate::shards::split_proxy_shard::SplitProxyShard
+
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::future::Future;
+use std::hash::Hash;
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+use tokio::time::sleep;
+
+use crate::common::stoppable_task_async::{spawn_async_cancellable, CancellableAsyncTaskHandle};
+use crate::hash_ring::HashRing;
+use crate::operations::types::{CollectionError, CollectionResult};
+use crate::shards::shard::{PeerId, ShardId};
+use crate::shards::shard_holder::{LockedShardHolder, HASH_RING_SHARD_SCALE};
+use crate::shards::transfer::driver::{MAX_RETRY_COUNT, RETRY_DELAY};
+use crate::shards::CollectionId;
+
+pub const SHARD_SPLITS_FILE: &str = "shard_splits.json";
+
+const SPLIT_BATCH_SIZE: usize = 100;
+
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum ShardSplitState {
+    /// Points of the source shard are being copied into the targets
+    Splitting,
+    /// Source shard is replaced with the targets
+    Done,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Eq, Hash)]
+pub struct ShardSplit {
+    pub source: ShardId,
+    pub targets: [ShardId; 2],
+    /// Peers, which have filled their local replicas of the targets
+    #[serde(default)]
+    pub copied_peers: BTreeSet<PeerId>,
+    pub state: ShardSplitState,
+}
+
+impl ShardSplit {
+    pub fn new(source: ShardId, targets: [ShardId; 2]) -> Self {
+        Self {
+            source,
+            targets,
+            copied_peers: BTreeSet::new(),
+            state: ShardSplitState::Splitting,
+        }
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.state == ShardSplitState::Done
+    }
+
+    /// Whether the shard is the source or one of the targets of the split
+    pub fn involves(&self, shard_id: ShardId) -> bool {
+        self.source == shard_id || self.targets.contains(&shard_id)
+    }
+
+    /// Ring, which distributes the points of the source shard between the targets
+    pub fn ring(&self) -> HashRing<ShardId> {
+        let mut ring = HashRing::fair(HASH_RING_SHARD_SCALE);
+        for target in self.targets {
+            ring.add(target);
+        }
+        ring
+    }
+}
+
+/// Shards of a collection with the `auto` sharding method, after the finished splits
+pub fn live_shard_ids(initial_shard_number: u32, splits: &[ShardSplit]) -> Vec<ShardId> {
+    let mut shard_ids: Vec<_> = (0..initial_shard_number).collect();
+    for split in splits.iter().filter(|split| split.is_done()) {
+        shard_ids.retain(|shard_id| *shard_id != split.source);
+        shard_ids.extend(split.targets);
+    }
+    shard_ids
+}
+
+/// Shards, which are placed on the hash ring of the collection
+///
+/// Sources of the finished splits stay on the ring instead of their targets.
+pub fn ring_shard_ids(
+    shard_ids: impl IntoIterator<Item = ShardId>,
+    splits: &[ShardSplit],
+) -> HashSet<ShardId> {
+    let mut ring_shard_ids: HashSet<_> = shard_ids.into_iter().collect();
+    for split in splits {
+        for target in split.targets {
+            ring_shard_ids.remove(&target);
+        }
+    }
+    for split in splits.iter().filter(|split| split.is_done()) {
+        if !splits
+            .iter()
+            .any(|other| other.is_done() && other.targets.contains(&split.source))
+        {
+            ring_shard_ids.insert(split.source);
+        }
+    }
+    ring_shard_ids
+}
+
+/// Rings of the finished splits, by their source shards
+pub fn split_rings(splits: &[ShardSplit]) -> HashMap<ShardId, HashRing<ShardId>> {
+    splits
+        .iter()
+        .filter(|split| split.is_done())
+        .map(|split| (split.source, split.ring()))
+        .collect()
+}
+
+/// Follow the finished splits of the shard to the shard, which stores the key now
+pub fn resolve_split<U: Hash>(
+    split_rings: &HashMap<ShardId, HashRing<ShardId>>,
+    mut shard_id: ShardId,
+    key: &U,
+) -> ShardId {
+    while let Some(ring) = split_rings.get(&shard_id) {
+        shard_id = *ring
+            .get(key)
+            .expect("Split ring is guaranteed to be non-empty");
+    }
+    shard_id
+}
+
+/// Copy all points of the local replica of the source shard into the local replicas of the targets
+///
+/// Updates to the local replica are applied to the targets concurrently.
+///
+/// # Cancel safety
+///
+/// This function is cancel safe.
+async fn copy_split_points(
+    shard_holder: Arc<LockedShardHolder>,
+    source: ShardId,
+) -> CollectionResult<()> {
+    log::debug!("Starting split of shard {source}");
+
+    {
+        let shard_holder = shard_holder.read().await;
+
+        let Some(replica_set) = shard_holder.get_shard(&source) else {
+            return Err(CollectionError::service_error(format!(
+                "Shard {source} cannot be split because it does not exist"
+            )));
+        };
+
+        replica_set.split_transfer_indexes().await?;
+    }
+
+    let mut offset = None;
+
+    loop {
+        let shard_holder = shard_holder.read().await;
+
+        let Some(replica_set) = shard_holder.get_shard(&source) else {
+            // Split proxy gone?!
+            // That would be a programming error.
+            return Err(CollectionError::service_error(format!(
+                "Shard {source} is not found"
+            )));
+        };
+
+        offset = replica_set
+            .split_transfer_batch(offset, SPLIT_BATCH_SIZE)
+            .await?;
+
+        if offset.is_none() {
+            // That was the last batch, all look good
+            break;
+        }
+    }
+
+    log::debug!("Points of shard {source} are copied into the split targets");
+
+    Ok(())
+}
+
+pub fn spawn_split_task<T, F>(
+    shards_holder: Arc<LockedShardHolder>,
+    collection_id: CollectionId,
+    source: ShardId,
+    on_finish: T,
+    on_error: F,
+) -> CancellableAsyncTaskHandle<bool>
+where
+    T: Future<Output = ()> + Send + 'static,
+    F: Future<Output = ()> + Send + 'static,
+{
+    spawn_async_cancellable(move |cancel| async move {
+        let mut result = Err(cancel::Error::Cancelled);
+
+        for attempt in 0..MAX_RETRY_COUNT {
+            let future = async {
+                if attempt > 0 {
+                    sleep(RETRY_DELAY * attempt as u32).await;
+
+                    log::warn!(
+                        "Retrying split of shard {collection_id}:{source} (retry {attempt})"
+                    );
+                }
+
+                copy_split_points(shards_holder.clone(), source).await
+            };
+
+            result = cancel::future::cancel_on_token(cancel.clone(), future).await;
+
+            if let Ok(Err(err)) = &result {
+                log::error!("Failed to split shard {collection_id}:{source}: {err}");
+            }
+
+            let is_ok = matches!(result, Ok(Ok(())));
+            let is_cancelled = result.is_err();
+
+            if is_ok || is_cancelled {
+                break;
+            }
+        }
+
+        match &result {
+            Ok(Ok(())) => on_finish.await,
+            Ok(Err(_)) => on_error.await,
+            Err(_) => (), // do nothing, if task was cancelled
+        }
+
+        let is_ok = matches!(result, Ok(Ok(())));
+        is_ok
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn done_split(source: ShardId, targets: [ShardId; 2]) -> ShardSplit {
+        ShardSplit {
+            state: ShardSplitState::Done,
+            ..ShardSplit::new(source, targets)
+        }
+    }
+
+    #[test]
+    fn test_split_shard_ids() {
+        let splits = vec![
+            done_split(1, [3, 4]),
+            done_split(3, [5, 6]),
+            ShardSplit::new(0, [7, 8]),
+        ];
+
+        let live = live_shard_ids(3, &splits);
+        assert_eq!(live, vec![0, 2, 4, 5, 6]);
+
+        // Shards of the unfinished split are not on the ring yet, sources of the finished are
+        let ring: HashSet<_> = ring_shard_ids(live.iter().copied().chain([7, 8]), &splits);
+        assert_eq!(ring, HashSet::from([0, 1, 2]));
+    }
+
+    #[test]
+    fn test_resolve_split() {
+        let splits = vec![done_split(1, [3, 4]), done_split(3, [5, 6])];
+        let split_rings = split_rings(&splits);
+
+        let mut ring = HashRing::fair(HASH_RING_SHARD_SCALE);
+        for shard_id in ring_shard_ids(live_shard_ids(3, &splits), &splits) {
+            ring.add(shard_id);
+        }
+
+        let mut moved_shards = HashSet::new();
+        for key in 0..1000u64 {
+            let shard_id = *ring.get(&key).unwrap();
+            let resolved = resolve_split(&split_rings, shard_id, &key);
+            match shard_id {
+                // Points of the other shards keep their placement
+                0 | 2 => assert_eq!(resolved, shard_id),
+                1 => {
+                    assert!([4, 5, 6].contains(&resolved));
+                    moved_shards.insert(resolved);
+                }
+                _ => unreachable!(),
+            }
+        }
+        // Split points are distributed between all targets
+        assert_eq!(moved_shards, HashSet::from([4, 5, 6]));
+    }
+}
@@ -0,0 +1,355 @@
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+use segment::data_types::order_by::OrderBy;
+use segment::types::{
+    ExtendedPointId, Filter, PointIdType, ScoredPoint, WithPayload, WithPayloadInterface,
+    WithVector,
+};
+use tokio::runtime::Handle;
+use tokio::sync::Mutex;
+
+use super::update_tracker::UpdateTracker;
+use crate::collection_manager::optimizers::Tracker;
+use crate::hash_ring::HashRing;
+use crate::operations::point_ops::{PointOperations, PointStruct, PointSyncOperation};
+use crate::operations::types::{
+    CollectionError, CollectionInfo, CollectionResult, CompactionResult, CoreSearchRequestBatch,
+    CountRequestInternal, CountResult, PointRequestInternal, Record, Sample, UpdateResult,
+    WarmUpResult,
+};
+use crate::operations::update_priority::UpdatePriority;
+use crate::operations::{
+    CollectionUpdateOperations, CreateIndex, FieldIndexOperations, OperationToShard, ShardRouter,
+    SplitByShard as _,
+};
+use crate::shards::local_shard::LocalShard;
+use crate::shards::replica_set::ShardReplicaSet;
+use crate::shards::scroll_session::SessionRef;
+use crate::shards::shard::ShardId;
+use crate::shards::shard_trait::ShardOperation;
+use crate::shards::telemetry::LocalShardTelemetry;
+use crate::shards::wal_archive::WalArchiveResult;
+
+/// SplitProxyShard
+///
+/// SplitProxyShard is a wrapper type for a LocalShard, which is being split into new shards.
+///
+/// It serves all read operations from the wrapped shard. Updates are applied to the wrapped shard
+/// and to the local replicas of the new shards, each point goes to the shard it is routed to.
+pub struct SplitProxyShard {
+    pub(crate) wrapped_shard: LocalShard,
+    targets: Vec<ShardReplicaSet>,
+    ring: HashRing<ShardId>,
+    /// Lock required to protect split-in-progress updates.
+    /// It should block data updating operations while the batch is being copied.
+    update_lock: Mutex<()>,
+}
+
+impl SplitProxyShard {
+    pub fn new(
+        wrapped_shard: LocalShard,
+        targets: Vec<ShardReplicaSet>,
+        ring: HashRing<ShardId>,
+    ) -> Self {
+        Self {
+            wrapped_shard,
+            targets,
+            ring,
+            update_lock: Mutex::new(()),
+        }
+    }
+
+    pub fn target_ids(&self) -> Vec<ShardId> {
+        self.targets.iter().map(|target| target.shard_id).collect()
+    }
+
+    /// Create payload indexes in the target shards same as in the wrapped shard.
+    ///
+    /// # Cancel safety
+    ///
+    /// This method is cancel safe.
+    pub async fn transfer_indexes(&self) -> CollectionResult<()> {
+        let _update_lock = self.update_lock.lock().await;
+        for (index_key, index_type) in self.wrapped_shard.info().await?.payload_schema {
+            let operation = CollectionUpdateOperations::FieldIndexOperation(
+                FieldIndexOperations::CreateIndex(CreateIndex {
+                    field_name: index_key,
+                    field_schema: Some(index_type.try_into()?),
+                }),
+            );
+            for target in &self.targets {
+                target.update_local(operation.clone(), true, None).await?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Copy batch of points into the target shards.
+    /// Returns an offset of the next batch to be copied.
+    ///
+    /// # Cancel safety
+    ///
+    /// This method is cancel safe.
+    pub async fn transfer_batch(
+        &self,
+        offset: Option<PointIdType>,
+        batch_size: usize,
+        runtime_handle: &Handle,
+    ) -> CollectionResult<Option<PointIdType>> {
+        debug_assert!(batch_size > 0);
+        let limit = batch_size + 1;
+        let _update_lock = self.update_lock.lock().await;
+        let mut batch = self
+            .wrapped_shard
+            .scroll_by(
+                offset,
+                limit,
+                &WithPayloadInterface::Bool(true),
+                &true.into(),
+                None,
+                None,
+                None,
+                None,
+                None,
+                runtime_handle,
+            )
+            .await?;
+        let next_page_offset = if batch.len() < limit {
+            // This was the last page
+            None
+        } else {
+            // remove extra point, it would be a first point of the next page
+            Some(batch.pop().unwrap().id)
+        };
+
+        let mut points_by_target: HashMap<ShardId, Vec<PointStruct>> = HashMap::new();
+        for point in batch {
+            let point: PointStruct = point.try_into()?;
+            let shard_id = *self
+                .ring
+                .get(&point.id)
+                .expect("Hash ring is guaranteed to be non-empty");
+            points_by_target.entry(shard_id).or_default().push(point);
+        }
+
+        // We only need to wait for the last batch.
+        let wait = next_page_offset.is_none();
+
+        for target in &self.targets {
+            // Use sync API to leverage potentially existing points, the range is synced in every
+            // target, even if none of the points of the batch go there
+            let insert_points_operation = CollectionUpdateOperations::PointOperation(
+                PointOperations::SyncPoints(PointSyncOperation {
+                    from_id: offset,
+                    to_id: next_page_offset,
+                    points: points_by_target
+                        .remove(&target.shard_id)
+                        .unwrap_or_default(),
+                }),
+            );
+            target
+                .update_local(insert_points_operation, wait, None)
+                .await?;
+        }
+
+        Ok(next_page_offset)
+    }
+
+    pub fn deconstruct(self) -> (LocalShard, Vec<ShardReplicaSet>) {
+        (self.wrapped_shard, self.targets)
+    }
+
+    /// Forward `create_snapshot` to `wrapped_shard`
+    pub async fn create_snapshot(
+        &self,
+        temp_path: &Path,
+        target_path: &Path,
+        save_wal: bool,
+    ) -> CollectionResult<()> {
+        self.wrapped_shard
+            .create_snapshot(temp_path, target_path, save_wal)
+            .await
+    }
+
+    pub async fn on_optimizer_config_update(&self) -> CollectionResult<()> {
+        for target in &self.targets {
+            target.on_optimizer_config_update().await?;
+        }
+        self.wrapped_shard.on_optimizer_config_update().await
+    }
+
+    pub async fn warm_up(&self) -> CollectionResult<WarmUpResult> {
+        self.wrapped_shard.warm_up().await
+    }
+
+    pub async fn shutdown(&self, deadline: Instant) -> CollectionResult<()> {
+        for target in &self.targets {
+            target.shutdown(deadline).await?;
+        }
+        self.wrapped_shard.shutdown(deadline).await
+    }
+
+    pub async fn compact(&self) -> CollectionResult<CompactionResult> {
+        self.wrapped_shard.compact().await
+    }
+
+    pub async fn archive_wal(&self, archive_path: &Path) -> CollectionResult<WalArchiveResult> {
+        self.wrapped_shard.archive_wal(archive_path).await
+    }
+
+    pub async fn stop_wal_archiving(&self) -> CollectionResult<()> {
+        self.wrapped_shard.stop_wal_archiving().await
+    }
+
+    pub fn get_telemetry_data(&self) -> LocalShardTelemetry {
+        self.wrapped_shard.get_telemetry_data()
+    }
+
+    pub fn running_optimizations(&self) -> Vec<Tracker> {
+        self.wrapped_shard.running_optimizations()
+    }
+
+    pub fn update_tracker(&self) -> &UpdateTracker {
+        self.wrapped_shard.update_tracker()
+    }
+
+    async fn update_target(
+        &self,
+        shard_id: ShardId,
+        operation: CollectionUpdateOperations,
+        wait: bool,
+        priority: Option<UpdatePriority>,
+    ) -> CollectionResult<()> {
+        let Some(target) = self
+            .targets
+            .iter()
+            .find(|target| target.shard_id == shard_id)
+        else {
+            return Err(CollectionError::service_error(format!(
+                "Shard {shard_id} is not a target of the split"
+            )));
+        };
+        target
+            .update_local(operation, wait, priority)
+            .await
+            .map_err(|err| {
+                CollectionError::service_error(format!(
+                    "Failed to apply update to shard {shard_id} of the split: {err}"
+                ))
+            })?;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl ShardOperation for SplitProxyShard {
+    /// Update `wrapped_shard` and the target shards of the updated points
+    async fn update(
+        &self,
+        operation: CollectionUpdateOperations,
+        wait: bool,
+        priority: Option<UpdatePriority>,
+    ) -> CollectionResult<UpdateResult> {
+        let _update_lock = self.update_lock.lock().await;
+        // Shard update is within a write lock scope, because we need a way to block the shard updates
+        // while the batch is being copied.
+        let result = self
+            .wrapped_shard
+            .update(operation.clone(), wait, priority)
+            .await?;
+
+        match operation.split_by_shard(&ShardRouter::new(&self.ring)) {
+            OperationToShard::ByShard(by_shard) => {
+                for (shard_id, operation) in by_shard {
+                    self.update_target(shard_id, operation, wait, priority)
+                        .await?;
+                }
+            }
+            OperationToShard::ToAll(operation) => {
+                for shard_id in self.target_ids() {
+                    self.update_target(shard_id, operation.clone(), wait, priority)
+                        .await?;
+                }
+            }
+            OperationToShard::ByShardWithRest(by_shard, rest) => {
+                let mut by_shard: HashMap<_, _> = by_shard.into_iter().collect();
+                for shard_id in self.target_ids() {
+                    let operation = by_shard.remove(&shard_id).unwrap_or_else(|| rest.clone());
+                    self.update_target(shard_id, operation, wait, priority)
+                        .await?;
+                }
+            }
+        }
+
+        Ok(result)
+    }
+
+    /// Forward read-only `scroll_by` to `wrapped_shard`
+    async fn scroll_by(
+        &self,
+        offset: Option<ExtendedPointId>,
+        limit: usize,
+        with_payload_interface: &WithPayloadInterface,
+        with_vector: &WithVector,
+        filter: Option<&Filter>,
+        order_by: Option<&OrderBy>,
+        sample: Option<Sample>,
+        as_of: Option<u64>,
+        session: Option<&SessionRef>,
+        search_runtime_handle: &Handle,
+    ) -> CollectionResult<Vec<Record>> {
+        let local_shard = &self.wrapped_shard;
+        local_shard
+            .scroll_by(
+                offset,
+                limit,
+                with_payload_interface,
+                with_vector,
+                filter,
+                order_by,
+                sample,
+                as_of,
+                session,
+                search_runtime_handle,
+            )
+            .await
+    }
+
+    async fn info(&self) -> CollectionResult<CollectionInfo> {
+        let local_shard = &self.wrapped_shard;
+        local_shard.info().await
+    }
+
+    async fn core_search(
+        &self,
+        request: Arc<CoreSearchRequestBatch>,
+        search_runtime_handle: &Handle,
+        timeout: Option<Duration>,
+    ) -> CollectionResult<Vec<Vec<ScoredPoint>>> {
+        let local_shard = &self.wrapped_shard;
+        local_shard
+            .core_search(request, search_runtime_handle, timeout)
+            .await
+    }
+
+    async fn count(&self, request: Arc<CountRequestInternal>) -> CollectionResult<CountResult> {
+        let local_shard = &self.wrapped_shard;
+        local_shard.count(request).await
+    }
+
+    async fn retrieve(
+        &self,
+        request: Arc<PointRequestInternal>,
+        with_payload: &WithPayload,
+        with_vector: &WithVector,
+    ) -> CollectionResult<Vec<Record>> {
+        let local_shard = &self.wrapped_shard;
+        local_shard
+            .retrieve(request, with_payload, with_vector)
+            .await
+    }
+}
@@ -39,6 +39,10 @@ pub struct OptimizerTelemetry {
     pub status: OptimizersStatus,
     pub optimizations: OperationDurationStatistics,
     pub log: Vec<TrackerTelemetry>,
+    /// Number of segments, which still require optimization, by optimizer name.
+    /// Only reported by optimizers, which track their progress
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub pending_segments: HashMap<String, usize>,
 }
 
 impl Anonymize for OptimizerTelemetry {
@@ -47,6 +51,7 @@ impl Anonymize for OptimizerTelemetry {
             status: self.status.clone(),
             optimizations: self.optimizations.anonymize(),
             log: self.log.anonymize(),
+            pending_segments: self.pending_segments.clone(),
         }
     }
 }
@@ -69,6 +74,7 @@ impl Anonymize for TrackerTelemetry {
             status: self.status.clone(),
             start_at: self.start_at.anonymize(),
             end_at: self.end_at.anonymize(),
+            resources: self.resources.clone(),
         }
     }
 }
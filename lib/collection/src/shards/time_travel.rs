@@ -0,0 +1,371 @@
+//! Time-travel reads: points could be retrieved and scrolled as they were at a recent time
+//!
+//! If `time_travel_window_sec` is configured, the update worker records the state of the points
+//! right before each operation changes them. Points, which were not changed since the requested
+//! time, are read from the segments as usual. The others are restored from the earliest state
+//! recorded after that time, which keeps its original point version.
+//!
+//! The requested time is passed to the shards along with the read, as `as_of` of the retrieve
+//! request or as an argument of the scroll. Recorded states are kept in memory, so after a restart
+//! of the shard the history starts over.
+
+use std::collections::{BTreeSet, HashMap, VecDeque};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use itertools::Itertools;
+use parking_lot::RwLock;
+use segment::data_types::vectors::{VectorStruct, DEFAULT_VECTOR_NAME};
+use segment::index::field_index::FieldIndex;
+use segment::payload_storage::query_checker::{
+    check_field_condition, check_filter, check_is_empty_condition, check_is_null_condition,
+    check_payload,
+};
+use segment::types::{
+    Condition, Filter, OwnedPayloadRef, Payload, PayloadContainer, PayloadKeyType, PointIdType,
+    WithPayload, WithVector,
+};
+
+use crate::collection_manager::holders::segment_holder::SegmentHolder;
+use crate::collection_manager::segments_searcher::SegmentsSearcher;
+use crate::operations::operation_effect::{EstimateOperationEffectArea, OperationEffectArea};
+use crate::operations::types::{CollectionError, CollectionResult, Record};
+use crate::operations::CollectionUpdateOperations;
+
+pub fn now_millis() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as u64
+}
+
+/// State of the point before one of its changes
+#[derive(Debug)]
+struct PointChange {
+    /// Time of the change in milliseconds
+    changed_at: u64,
+    /// State of the point before the change, `None` if the point didn't exist
+    previous: Option<Record>,
+}
+
+/// Previous states of the points, changed within the time-travel window
+#[derive(Debug)]
+pub struct PointHistory {
+    /// Time in milliseconds, since which all changes are recorded. Changes made before it are
+    /// unknown, so earlier states of the points could not be restored.
+    recorded_since: u64,
+    /// Changes of each point in chronological order
+    changes: HashMap<PointIdType, VecDeque<PointChange>>,
+    /// Changed points in chronological order, to drop the changes which are out of the window
+    changes_order: VecDeque<(u64, PointIdType)>,
+}
+
+impl Default for PointHistory {
+    fn default() -> Self {
+        Self::new(now_millis())
+    }
+}
+
+impl PointHistory {
+    pub fn new(recorded_since: u64) -> Self {
+        Self {
+            recorded_since,
+            changes: HashMap::new(),
+            changes_order: VecDeque::new(),
+        }
+    }
+
+    /// Record states of the points right before they are changed at `now` (in milliseconds)
+    ///
+    /// Changes older than `window_sec` are dropped. If the window is not set, the whole history
+    /// is dropped and starts over.
+    pub fn record(
+        &mut self,
+        now: u64,
+        window_sec: Option<u64>,
+        previous: impl IntoIterator<Item = (PointIdType, Option<Record>)>,
+    ) {
+        let Some(window_sec) = window_sec else {
+            if !self.changes_order.is_empty() {
+                self.changes.clear();
+                self.changes_order.clear();
+            }
+            self.recorded_since = now;
+            return;
+        };
+
+        let window_start = now.saturating_sub(window_sec.saturating_mul(1000));
+        while let Some(&(changed_at, id)) = self.changes_order.front() {
+            if changed_at >= window_start {
+                break;
+            }
+            self.changes_order.pop_front();
+            if let Some(point_changes) = self.changes.get_mut(&id) {
+                point_changes.pop_front();
+                if point_changes.is_empty() {
+                    self.changes.remove(&id);
+                }
+            }
+        }
+        self.recorded_since = self.recorded_since.max(window_start);
+
+        for (id, previous) in previous {
+            self.changes.entry(id).or_default().push_back(PointChange {
+                changed_at: now,
+                previous,
+            });
+            self.changes_order.push_back((now, id));
+        }
+    }
+
+    /// Check, that states of the points at the given time (in seconds) could be restored
+    pub fn check_available(&self, as_of: u64) -> CollectionResult<()> {
+        if as_of.saturating_mul(1000) < self.recorded_since {
+            return Err(CollectionError::bad_request(format!(
+                "Points history is only available since {}, requested time {as_of} is too old",
+                (self.recorded_since + 999) / 1000,
+            )));
+        }
+        Ok(())
+    }
+
+    /// State of the point at the given time (in seconds)
+    ///
+    /// Returns `None` if the point was not changed since then, so its current state is the same.
+    pub fn state_at(&self, id: PointIdType, as_of: u64) -> Option<Option<&Record>> {
+        self.state_at_millis(id, as_of.saturating_mul(1000))
+    }
+
+    fn state_at_millis(&self, id: PointIdType, as_of: u64) -> Option<Option<&Record>> {
+        self.changes
+            .get(&id)?
+            .iter()
+            .find(|change| change.changed_at >= as_of)
+            .map(|change| change.previous.as_ref())
+    }
+
+    /// Points, changed since the given time (in seconds), with their states at that time
+    pub fn changed_since(&self, as_of: u64) -> HashMap<PointIdType, Option<&Record>> {
+        self.changed_since_millis(as_of.saturating_mul(1000))
+    }
+
+    /// Points, changed since the given time (in milliseconds), with their states at that time
+    pub fn changed_since_millis(&self, as_of: u64) -> HashMap<PointIdType, Option<&Record>> {
+        self.changes
+            .keys()
+            .filter_map(|&id| Some((id, self.state_at_millis(id, as_of)?)))
+            .collect()
+    }
+
+    /// Whether all changes since the given time (in milliseconds) are recorded
+    pub fn is_recorded_since(&self, since: u64) -> bool {
+        self.recorded_since <= since
+    }
+}
+
+/// Read current states of the points, which are going to be changed by the operation
+pub fn affected_points(
+    segments: &RwLock<SegmentHolder>,
+    operation: &CollectionUpdateOperations,
+) -> CollectionResult<Vec<(PointIdType, Option<Record>)>> {
+    let mut ids = BTreeSet::new();
+    collect_affected_ids(segments, operation, &mut ids);
+    if ids.is_empty() {
+        return Ok(vec![]);
+    }
+
+    let ids = ids.into_iter().collect_vec();
+    let with_payload = WithPayload {
+        enable: true,
+        payload_selector: None,
+    };
+    let mut records: HashMap<_, _> =
+        SegmentsSearcher::retrieve(segments, &ids, &with_payload, &WithVector::Bool(true))?
+            .into_iter()
+            .map(|record| (record.id, record))
+            .collect();
+
+    Ok(ids
+        .into_iter()
+        .map(|id| (id, records.remove(&id)))
+        .collect())
+}
+
+fn collect_affected_ids(
+    segments: &RwLock<SegmentHolder>,
+    operation: &CollectionUpdateOperations,
+    ids: &mut BTreeSet<PointIdType>,
+) {
+    // Operations of a batch are listed one by one, as points created by them are not found
+    // by the filter of the whole batch
+    if let CollectionUpdateOperations::Batch(operations) = operation {
+        for operation in operations {
+            collect_affected_ids(segments, operation, ids);
+        }
+        return;
+    }
+
+    match operation.estimate_effect_area() {
+        OperationEffectArea::Empty => {}
+        OperationEffectArea::Points(points) => ids.extend(points),
+        OperationEffectArea::Filter(filter) => {
+            let segments = segments.read();
+            for (_, segment) in segments.iter() {
+                ids.extend(
+                    segment
+                        .get()
+                        .read()
+                        .read_filtered(None, None, Some(&filter)),
+                );
+            }
+        }
+    }
+}
+
+/// Check the filter against the restored state of the point
+pub fn check_record(record: &Record, filter: &Filter) -> bool {
+    let empty_payload = Payload::default();
+    let payload = record.payload.as_ref().unwrap_or(&empty_payload);
+    let no_indexes: HashMap<PayloadKeyType, Vec<FieldIndex>> = HashMap::new();
+
+    let checker = |condition: &Condition| match condition {
+        Condition::Field(field_condition) => {
+            check_field_condition(field_condition, payload, &no_indexes)
+        }
+        Condition::IsEmpty(is_empty) => check_is_empty_condition(is_empty, payload),
+        Condition::IsNull(is_null) => check_is_null_condition(is_null, payload),
+        Condition::HasId(has_id) => has_id.has_id.contains(&record.id),
+        Condition::Nested(nested) => payload
+            .get_value(&nested.array_key())
+            .values()
+            .iter()
+            .filter_map(|value| value.as_object())
+            .any(|object| {
+                check_payload(
+                    Box::new(|| OwnedPayloadRef::from(object)),
+                    None,
+                    &nested.nested.filter,
+                    0,
+                    &no_indexes,
+                )
+            }),
+        Condition::Filter(_) => unreachable!(),
+    };
+
+    check_filter(&checker, filter)
+}
+
+/// Select the requested payload and vectors of the restored state of the point
+pub fn select_record(
+    record: &Record,
+    with_payload: &WithPayload,
+    with_vector: &WithVector,
+) -> Record {
+    let payload = if with_payload.enable {
+        let payload = record.payload.clone().unwrap_or_default();
+        Some(match &with_payload.payload_selector {
+            Some(selector) => selector.process(payload),
+            None => payload,
+        })
+    } else {
+        None
+    };
+
+    let vector = match (with_vector, &record.vector) {
+        (WithVector::Bool(false), _) | (_, None) => None,
+        (WithVector::Bool(true), Some(vector)) => Some(vector.clone()),
+        (WithVector::Selector(names), Some(VectorStruct::Multi(vectors))) => {
+            Some(VectorStruct::Multi(
+                vectors
+                    .iter()
+                    .filter(|(name, _)| names.contains(name))
+                    .map(|(name, vector)| (name.clone(), vector.clone()))
+                    .collect(),
+            ))
+        }
+        (WithVector::Selector(names), Some(vector)) => names
+            .iter()
+            .any(|name| name == DEFAULT_VECTOR_NAME)
+            .then(|| vector.clone()),
+    };
+
+    Record {
+        id: record.id,
+        version: record.version,
+        payload,
+        vector,
+        shard_key: None,
+        order_value: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn record(id: u64, version: u64, value: &str) -> Record {
+        Record {
+            id: id.into(),
+            version: Some(version),
+            payload: Some(json!({ "value": value }).into()),
+            vector: None,
+            shard_key: None,
+            order_value: None,
+        }
+    }
+
+    #[test]
+    fn test_point_history() {
+        let mut history = PointHistory::new(10_000);
+        let window = Some(100);
+
+        // Point 1 is changed twice, point 2 is created
+        history.record(20_000, window, [(1.into(), Some(record(1, 1, "a")))]);
+        history.record(
+            30_500,
+            window,
+            [(1.into(), Some(record(1, 5, "b"))), (2.into(), None)],
+        );
+
+        assert!(history.check_available(9).is_err());
+        assert!(history.check_available(10).is_ok());
+
+        let state = history.state_at(1.into(), 15).unwrap().unwrap();
+        assert_eq!(state.version, Some(1));
+        let state = history.state_at(1.into(), 25).unwrap().unwrap();
+        assert_eq!(state.version, Some(5));
+        assert!(history.state_at(1.into(), 31).is_none());
+
+        // Point 2 didn't exist before its creation
+        assert_eq!(history.state_at(2.into(), 30).unwrap(), None);
+        assert_eq!(history.changed_since(25).len(), 2);
+        assert_eq!(history.changed_since(31).len(), 0);
+        assert_eq!(history.changed_since_millis(30_500).len(), 2);
+        assert_eq!(history.changed_since_millis(30_501).len(), 0);
+        assert!(history.is_recorded_since(10_000));
+        assert!(!history.is_recorded_since(9_999));
+
+        // The first change is out of the window now
+        history.record(125_000, window, []);
+        assert!(history.check_available(24).is_err());
+        assert!(history.check_available(25).is_ok());
+        assert_eq!(history.changed_since(25).len(), 2);
+
+        // Disabling the window drops the history
+        history.record(130_000, None, []);
+        assert!(history.changed_since(0).is_empty());
+        assert!(history.check_available(129).is_err());
+    }
+
+    #[test]
+    fn test_check_record() {
+        let point = record(1, 1, "a");
+        let filter = Filter::new_must(Condition::Field(segment::types::FieldCondition::new_match(
+            "value",
+            "a".to_string().into(),
+        )));
+        assert!(check_record(&point, &filter));
+        assert!(!check_record(&record(1, 1, "b"), &filter));
+    }
+}
@@ -7,6 +7,8 @@ use tokio::time::sleep;
 
 use super::snapshot::transfer_snapshot;
 use super::stream_records::transfer_stream_records;
+use super::throttle::{ShardTransferConfig, TransferRate};
+use super::wal_delta::transfer_wal_delta;
 use super::{ShardTransfer, ShardTransferConsensus, ShardTransferMethod};
 use crate::common::stoppable_task_async::{spawn_async_cancellable, CancellableAsyncTaskHandle};
 use crate::operations::types::CollectionResult;
@@ -17,7 +19,7 @@ use crate::shards::shard::{PeerId, ShardId};
 use crate::shards::shard_holder::{LockedShardHolder, ShardHolder};
 use crate::shards::CollectionId;
 
-const RETRY_DELAY: Duration = Duration::from_secs(1);
+pub(crate) const RETRY_DELAY: Duration = Duration::from_secs(1);
 pub(crate) const MAX_RETRY_COUNT: usize = 3;
 
 /// # Cancel safety
@@ -33,6 +35,8 @@ pub async fn transfer_shard(
     channel_service: ChannelService,
     snapshots_path: &Path,
     temp_dir: &Path,
+    config: ShardTransferConfig,
+    rate: &TransferRate,
 ) -> CollectionResult<()> {
     let shard_id = transfer_config.shard_id;
 
@@ -50,7 +54,8 @@ pub async fn transfer_shard(
     match transfer_config.method.unwrap_or_default() {
         // Transfer shard record in batches
         ShardTransferMethod::StreamRecords => {
-            transfer_stream_records(shard_holder.clone(), shard_id, remote_shard).await?;
+            transfer_stream_records(shard_holder.clone(), shard_id, remote_shard, config, rate)
+                .await?;
         }
 
         // Transfer shard as snapshot
@@ -68,6 +73,20 @@ pub async fn transfer_shard(
             )
             .await?;
         }
+
+        // Replay missed operations from WAL, transfer shard records if not possible
+        ShardTransferMethod::WalDelta => {
+            let recovered =
+                transfer_wal_delta(shard_holder.clone(), shard_id, remote_shard.clone()).await?;
+            if !recovered {
+                log::info!(
+                    "Shard {shard_id} cannot be recovered on peer {} from WAL, transferring all records instead",
+                    transfer_config.to,
+                );
+                transfer_stream_records(shard_holder.clone(), shard_id, remote_shard, config, rate)
+                    .await?;
+            }
+        }
     }
 
     Ok(())
@@ -182,6 +201,8 @@ pub fn spawn_transfer_task<T, F>(
     snapshots_path: PathBuf,
     collection_name: String,
     temp_dir: PathBuf,
+    config: ShardTransferConfig,
+    rate: Arc<TransferRate>,
     on_finish: T,
     on_error: F,
 ) -> CancellableAsyncTaskHandle<bool>
@@ -213,6 +234,8 @@ where
                     channel_service.clone(),
                     &snapshots_path,
                     &temp_dir,
+                    config,
+                    &rate,
                 )
                 .await
             };
@@ -17,7 +17,9 @@ pub mod driver;
 pub mod helpers;
 pub mod snapshot;
 pub mod stream_records;
+pub mod throttle;
 pub mod transfer_tasks_pool;
+pub mod wal_delta;
 
 /// Number of retries for confirming a consensus operation.
 const CONSENSUS_CONFIRM_RETRIES: usize = 3;
@@ -74,6 +76,9 @@ pub enum ShardTransferMethod {
     StreamRecords,
     /// Snapshot the shard, transfer and restore it on the receiver.
     Snapshot,
+    /// Replay operations, which the receiver missed while it was dead, from the WAL of the sender.
+    /// Falls back to `stream_records`, if the WAL does not hold all of them anymore.
+    WalDelta,
 }
 
 /// Interface to consensus for shard transfer operations.
@@ -4,6 +4,7 @@ use crate::operations::types::{CollectionError, CollectionResult};
 use crate::shards::remote_shard::RemoteShard;
 use crate::shards::shard::ShardId;
 use crate::shards::shard_holder::LockedShardHolder;
+use crate::shards::transfer::throttle::{ShardTransferConfig, TransferRate, TransferThrottle};
 
 const TRANSFER_BATCH_SIZE: usize = 100;
 
@@ -15,6 +16,8 @@ const TRANSFER_BATCH_SIZE: usize = 100;
 /// This first transfers configured indices. Then it transfers all point records in batches.
 /// Updates to the local shard are forwarded to the remote concurrently.
 ///
+/// Batches are throttled to the limits of `config`, and recorded in `rate`.
+///
 /// # Cancel safety
 ///
 /// This function is cancel safe.
@@ -22,6 +25,8 @@ pub(super) async fn transfer_stream_records(
     shard_holder: Arc<LockedShardHolder>,
     shard_id: ShardId,
     remote_shard: RemoteShard,
+    config: ShardTransferConfig,
+    rate: &TransferRate,
 ) -> CollectionResult<()> {
     let remote_peer_id = remote_shard.peer_id;
 
@@ -46,26 +51,36 @@ pub(super) async fn transfer_stream_records(
     log::trace!("Transferring points to shard {shard_id} by streaming records");
 
     let mut offset = None;
+    let mut throttle = TransferThrottle::new(config);
 
     loop {
-        let shard_holder = shard_holder.read().await;
-
-        let Some(replica_set) = shard_holder.get_shard(&shard_id) else {
-            // Forward proxy gone?!
-            // That would be a programming error.
-            return Err(CollectionError::service_error(format!(
-                "Shard {shard_id} is not found"
-            )));
+        let batch = {
+            let shard_holder = shard_holder.read().await;
+
+            let Some(replica_set) = shard_holder.get_shard(&shard_id) else {
+                // Forward proxy gone?!
+                // That would be a programming error.
+                return Err(CollectionError::service_error(format!(
+                    "Shard {shard_id} is not found"
+                )));
+            };
+
+            let (next_offset, batch) = replica_set
+                .transfer_batch(offset, TRANSFER_BATCH_SIZE)
+                .await?;
+            offset = next_offset;
+            batch
         };
 
-        offset = replica_set
-            .transfer_batch(offset, TRANSFER_BATCH_SIZE)
-            .await?;
+        rate.record(batch);
 
         if offset.is_none() {
             // That was the last batch, all look good
             break;
         }
+
+        // Wait without holding the shard holder lock
+        throttle.throttle(batch).await;
     }
 
     log::debug!("Ending shard {shard_id} transfer to peer {remote_peer_id} by streaming records");
@@ -0,0 +1,229 @@
+//! Rate limiting of shard transfers
+//!
+//! A transfer by streaming records sends the points in batches. After each batch, the sender
+//! waits until the batch fits into the configured throughput, so a transfer leaves the rest of
+//! the network to the serving traffic. The limits apply to each transfer separately.
+
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+
+use parking_lot::Mutex;
+use schemars::JsonSchema;
+use segment::data_types::vectors::{Vector, VectorStruct};
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+use crate::common::io_budget::TokenBucket;
+use crate::operations::point_ops::PointStruct;
+use crate::shards::shard::{PeerId, ShardId};
+
+const MB: usize = 1024 * 1024;
+
+/// Period, over which the current rate of a transfer is measured
+const RATE_WINDOW: Duration = Duration::from_secs(10);
+
+/// Throughput limits of a single shard transfer, and WAL retention for recovery transfers
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq, Default, Validate)]
+pub struct ShardTransferConfig {
+    /// Max throughput of a transfer in megabytes per second. If not set - no limit
+    #[serde(default)]
+    #[validate(range(min = 1))]
+    pub max_mb_per_sec: Option<usize>,
+    /// Max throughput of a transfer in points per second. If not set - no limit
+    #[serde(default)]
+    #[validate(range(min = 1))]
+    pub max_points_per_sec: Option<usize>,
+    /// Number of the latest operations kept in the WAL of each shard, even if they are flushed.
+    /// Dead replicas, which missed fewer operations, are recovered by replaying them instead of
+    /// a full transfer. If not set - only the operations of not yet truncated WAL segments are kept
+    #[serde(default)]
+    pub wal_delta_retention: Option<usize>,
+}
+
+/// Approximate size of a transferred batch of points
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct BatchSize {
+    pub points: usize,
+    pub bytes: usize,
+}
+
+impl BatchSize {
+    pub fn of(points: &[PointStruct]) -> Self {
+        let bytes = points.iter().map(point_size).sum();
+        Self {
+            points: points.len(),
+            bytes,
+        }
+    }
+}
+
+/// Size of the vectors and the JSON payload of the point
+fn point_size(point: &PointStruct) -> usize {
+    let vector_size = |vector: &Vector| match vector {
+        Vector::Dense(vector) => std::mem::size_of_val(vector.as_slice()),
+        Vector::MultiDense(vectors) => vectors
+            .iter()
+            .map(|vector| std::mem::size_of_val(vector.as_slice()))
+            .sum(),
+        Vector::Sparse(vector) => {
+            std::mem::size_of_val(vector.indices.as_slice())
+                + std::mem::size_of_val(vector.values.as_slice())
+        }
+    };
+    let vectors_size = match &point.vector {
+        VectorStruct::Single(vector) => std::mem::size_of_val(vector.as_slice()),
+        VectorStruct::Multi(vectors) => vectors
+            .iter()
+            .map(|(name, vector)| name.len() + vector_size(vector))
+            .sum(),
+    };
+    let payload_size = point
+        .payload
+        .as_ref()
+        .and_then(|payload| serde_json::to_vec(payload).ok())
+        .map_or(0, |payload| payload.len());
+    std::mem::size_of_val(&point.id) + vectors_size + payload_size
+}
+
+/// Throughput limits of a single running transfer
+pub struct TransferThrottle {
+    bytes: Option<TokenBucket>,
+    points: Option<TokenBucket>,
+}
+
+impl TransferThrottle {
+    pub fn new(config: ShardTransferConfig) -> Self {
+        Self {
+            bytes: config.max_mb_per_sec.map(|mb| TokenBucket::new(mb * MB)),
+            points: config.max_points_per_sec.map(TokenBucket::new),
+        }
+    }
+
+    /// Time to wait after the batch is transferred, so it fits into the limits
+    pub fn delay(&mut self, batch: BatchSize) -> Duration {
+        let now = Instant::now();
+        let bytes_delay = self
+            .bytes
+            .as_mut()
+            .map_or(Duration::ZERO, |bucket| bucket.take(batch.bytes, now));
+        let points_delay = self
+            .points
+            .as_mut()
+            .map_or(Duration::ZERO, |bucket| bucket.take(batch.points, now));
+        bytes_delay.max(points_delay)
+    }
+
+    /// Wait until the transferred batch fits into the limits
+    pub async fn throttle(&mut self, batch: BatchSize) {
+        let delay = self.delay(batch);
+        if !delay.is_zero() {
+            tokio::time::sleep(delay).await;
+        }
+    }
+}
+
+/// Current throughput of a transfer, measured over the recent batches
+#[derive(Debug)]
+pub struct TransferRate {
+    started: Instant,
+    batches: Mutex<VecDeque<(Instant, BatchSize)>>,
+    /// Number of points transferred since the start
+    transferred_points: AtomicU64,
+}
+
+impl Default for TransferRate {
+    fn default() -> Self {
+        Self {
+            started: Instant::now(),
+            batches: Default::default(),
+            transferred_points: AtomicU64::new(0),
+        }
+    }
+}
+
+impl TransferRate {
+    pub fn record(&self, batch: BatchSize) {
+        self.transferred_points
+            .fetch_add(batch.points as u64, Ordering::Relaxed);
+        let now = Instant::now();
+        let mut batches = self.batches.lock();
+        batches.push_back((now, batch));
+        Self::evict(&mut batches, now);
+    }
+
+    pub fn transferred_points(&self) -> u64 {
+        self.transferred_points.load(Ordering::Relaxed)
+    }
+
+    /// Points and megabytes per second over the recent batches
+    pub fn current(&self) -> (f64, f64) {
+        let now = Instant::now();
+        let mut batches = self.batches.lock();
+        Self::evict(&mut batches, now);
+
+        let window = now
+            .saturating_duration_since(self.started)
+            .clamp(Duration::from_secs(1), RATE_WINDOW)
+            .as_secs_f64();
+        let (points, bytes) = batches.iter().fold((0, 0), |(points, bytes), (_, batch)| {
+            (points + batch.points, bytes + batch.bytes)
+        });
+        (points as f64 / window, bytes as f64 / MB as f64 / window)
+    }
+
+    fn evict(batches: &mut VecDeque<(Instant, BatchSize)>, now: Instant) {
+        while let Some((time, _)) = batches.front() {
+            if now.saturating_duration_since(*time) <= RATE_WINDOW {
+                break;
+            }
+            batches.pop_front();
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, JsonSchema)]
+pub struct ShardTransferRateTelemetry {
+    pub shard_id: ShardId,
+    pub to: PeerId,
+    /// Transferred points per second, over the last 10 seconds
+    pub points_per_sec: f64,
+    /// Transferred megabytes per second, over the last 10 seconds
+    pub mb_per_sec: f64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_throttle_delay() {
+        let mut throttle = TransferThrottle::new(ShardTransferConfig {
+            max_mb_per_sec: Some(1),
+            max_points_per_sec: Some(100),
+            wal_delta_retention: None,
+        });
+
+        // First second of the budget is available right away
+        let batch = BatchSize {
+            points: 100,
+            bytes: MB / 2,
+        };
+        assert_eq!(throttle.delay(batch), Duration::ZERO);
+
+        // The slowest of the limits applies
+        let delay = throttle.delay(batch);
+        assert!(delay > Duration::from_millis(900), "{delay:?}");
+        assert!(delay <= Duration::from_secs(1), "{delay:?}");
+    }
+
+    #[test]
+    fn test_unlimited_throttle() {
+        let mut throttle = TransferThrottle::new(ShardTransferConfig::default());
+        let batch = BatchSize {
+            points: usize::MAX,
+            bytes: usize::MAX,
+        };
+        assert_eq!(throttle.delay(batch), Duration::ZERO);
+    }
+}
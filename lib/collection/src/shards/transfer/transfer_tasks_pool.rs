@@ -1,12 +1,28 @@
 use std::collections::HashMap;
+use std::sync::Arc;
+
+use chrono::{DateTime, Utc};
 
 use crate::common::stoppable_task_async::CancellableAsyncTaskHandle;
+use crate::operations::running_operations::{
+    next_operation_id, OperationProgress, RunningOperation, RunningOperationKind,
+};
+use crate::shards::transfer::throttle::{ShardTransferRateTelemetry, TransferRate};
 use crate::shards::transfer::{ShardTransfer, ShardTransferKey};
 use crate::shards::CollectionId;
 
 pub struct TransferTasksPool {
     collection_id: CollectionId,
-    tasks: HashMap<ShardTransferKey, CancellableAsyncTaskHandle<bool>>,
+    tasks: HashMap<ShardTransferKey, TransferTaskItem>,
+}
+
+struct TransferTaskItem {
+    /// Id of the transfer in the running operations
+    operation_id: u64,
+    transfer: ShardTransfer,
+    started_at: DateTime<Utc>,
+    task: CancellableAsyncTaskHandle<bool>,
+    rate: Arc<TransferRate>,
 }
 
 #[derive(Debug, PartialEq, Eq, Hash, Clone)]
@@ -33,8 +49,8 @@ impl TransferTasksPool {
 
     /// Returns true if transfer task is still running
     pub fn check_if_still_running(&self, transfer_key: &ShardTransferKey) -> bool {
-        if let Some(task) = self.tasks.get(transfer_key) {
-            !task.is_finished()
+        if let Some(item) = self.tasks.get(transfer_key) {
+            !item.task.is_finished()
         } else {
             false
         }
@@ -44,8 +60,8 @@ impl TransferTasksPool {
     /// Return false if task failed or stopped
     /// Return None if task not found or not finished
     pub fn get_task_result(&self, transfer_key: &ShardTransferKey) -> Option<bool> {
-        if let Some(task) = self.tasks.get(transfer_key) {
-            task.get_result()
+        if let Some(item) = self.tasks.get(transfer_key) {
+            item.task.get_result()
         } else {
             None
         }
@@ -54,8 +70,8 @@ impl TransferTasksPool {
     /// Returns true if the task was actually stopped
     /// Returns false if the task was not found
     pub async fn stop_if_exists(&mut self, transfer_key: &ShardTransferKey) -> TaskResult {
-        if let Some(task) = self.tasks.remove(transfer_key) {
-            match task.cancel().await {
+        if let Some(item) = self.tasks.remove(transfer_key) {
+            match item.task.cancel().await {
                 Ok(res) => {
                     if res {
                         log::info!(
@@ -95,7 +111,79 @@ impl TransferTasksPool {
         &mut self,
         shard_transfer: &ShardTransfer,
         task: CancellableAsyncTaskHandle<bool>,
+        rate: Arc<TransferRate>,
     ) {
-        self.tasks.insert(shard_transfer.key(), task);
+        self.tasks.insert(
+            shard_transfer.key(),
+            TransferTaskItem {
+                operation_id: next_operation_id(),
+                transfer: shard_transfer.clone(),
+                started_at: Utc::now(),
+                task,
+                rate,
+            },
+        );
+    }
+
+    /// Transfers, which are still running, as running operations
+    pub fn running_operations(&self) -> Vec<RunningOperation> {
+        self.tasks
+            .values()
+            .filter(|item| !item.task.is_finished())
+            .map(|item| {
+                let ShardTransfer {
+                    shard_id,
+                    from,
+                    to,
+                    method,
+                    ..
+                } = &item.transfer;
+                RunningOperation {
+                    id: item.operation_id,
+                    kind: RunningOperationKind::ShardTransfer,
+                    collection_name: self.collection_id.clone(),
+                    shard_id: Some(*shard_id),
+                    description: format!(
+                        "Transfer of shard {shard_id} from peer {from} to peer {to} \
+                         with {:?} method",
+                        method.unwrap_or_default(),
+                    ),
+                    started_at: item.started_at,
+                    progress: Some(OperationProgress {
+                        done: item.rate.transferred_points(),
+                        total: None,
+                    }),
+                    cancellable: true,
+                }
+            })
+            .collect()
+    }
+
+    /// Running transfer with the given operation id
+    pub fn find_by_operation_id(&self, operation_id: u64) -> Option<ShardTransfer> {
+        self.tasks
+            .values()
+            .find(|item| item.operation_id == operation_id && !item.task.is_finished())
+            .map(|item| item.transfer.clone())
+    }
+
+    /// Current rates of the running transfers
+    pub fn get_rates(&self) -> Vec<ShardTransferRateTelemetry> {
+        let mut rates: Vec<_> = self
+            .tasks
+            .iter()
+            .filter(|(_, item)| !item.task.is_finished())
+            .map(|(key, item)| {
+                let (points_per_sec, mb_per_sec) = item.rate.current();
+                ShardTransferRateTelemetry {
+                    shard_id: key.shard_id,
+                    to: key.to,
+                    points_per_sec,
+                    mb_per_sec,
+                }
+            })
+            .collect();
+        rates.sort_by_key(|rate| (rate.shard_id, rate.to));
+        rates
     }
 }
@@ -0,0 +1,68 @@
+use std::sync::Arc;
+
+use crate::operations::types::{CollectionError, CollectionResult};
+use crate::shards::remote_shard::RemoteShard;
+use crate::shards::shard::ShardId;
+use crate::shards::shard_holder::LockedShardHolder;
+
+/// Orchestrate shard transfer by replaying the WAL delta
+///
+/// This is called on the sender to recover a dead replica, which still has most of the shard
+/// data. Only operations after the recovery point of the replica are transferred.
+///
+/// - Queue proxy local shard from the recovery point
+///   This keeps the WAL from being truncated past the recovery point. All operations after it,
+///   including new ones, are queued for the remote.
+/// - Transfer queued updates to remote, transform into forward proxy
+///   Updates are replayed in order, the same way the queue of a snapshot transfer is. Operations
+///   the remote already has are applied again, which is fine as operations are idempotent.
+///
+/// Returns `false` without transferring anything, if the recovery point of the remote is not
+/// known, or the local WAL does not hold all operations after it anymore. The shard must be
+/// transferred in full in this case.
+///
+/// # Cancel safety
+///
+/// This function is cancel safe.
+///
+/// If cancelled - none, some or all operations may be transmitted to the remote, and the local
+/// shard may be left as a queue proxy. It is reverted before the transfer is retried.
+pub(super) async fn transfer_wal_delta(
+    shard_holder: Arc<LockedShardHolder>,
+    shard_id: ShardId,
+    remote_shard: RemoteShard,
+) -> CollectionResult<bool> {
+    let remote_peer_id = remote_shard.peer_id;
+
+    let shard_holder = shard_holder.read().await;
+
+    let Some(replica_set) = shard_holder.get_shard(&shard_id) else {
+        return Err(CollectionError::service_error(format!(
+            "Shard {shard_id} cannot be queue proxied because it does not exist"
+        )));
+    };
+
+    let Some(recovery_point) = replica_set.wal_delta_recovery_point(remote_peer_id).await else {
+        log::debug!(
+            "WAL of shard {shard_id} does not hold all updates missed by peer {remote_peer_id}"
+        );
+        return Ok(false);
+    };
+
+    log::debug!(
+        "Starting shard {shard_id} transfer to peer {remote_peer_id} by replaying WAL delta after version {recovery_point}"
+    );
+
+    // Queue proxy local shard from the recovery point
+    replica_set
+        .queue_proxify_local(remote_shard, Some(recovery_point))
+        .await?;
+
+    // Transfer queued updates to remote, transform into forward proxy
+    log::trace!("Transfer all queue proxy updates and transform into forward proxy");
+    replica_set.queue_proxy_into_forward_proxy().await?;
+
+    log::debug!("Ending shard {shard_id} transfer to peer {remote_peer_id} by replaying WAL delta");
+
+    Ok(true)
+}
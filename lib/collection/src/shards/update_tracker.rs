@@ -1,13 +1,16 @@
 use std::future::{self, Future};
-use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
 use std::sync::Arc;
 
+use segment::types::SeqNumberType;
 use tokio::sync::watch;
 
 #[derive(Clone, Debug)]
 pub struct UpdateTracker {
     update_operations: Arc<AtomicUsize>,
     update_notifier: Arc<watch::Sender<()>>,
+    /// Number of the latest operation, applied to the segments
+    applied_operation: Arc<AtomicU64>,
 }
 
 impl Default for UpdateTracker {
@@ -17,6 +20,7 @@ impl Default for UpdateTracker {
         Self {
             update_operations: Default::default(),
             update_notifier: Arc::new(update_notifier),
+            applied_operation: Default::default(),
         }
     }
 }
@@ -37,6 +41,15 @@ impl UpdateTracker {
         }
     }
 
+    pub fn applied_operation(&self) -> SeqNumberType {
+        self.applied_operation.load(Ordering::Acquire)
+    }
+
+    /// Operations are applied in order, but the number never goes back, e.g. on WAL recovery
+    pub fn set_applied_operation(&self, op_num: SeqNumberType) {
+        self.applied_operation.fetch_max(op_num, Ordering::AcqRel);
+    }
+
     pub fn update(&self) -> UpdateGuard {
         if self.update_operations.fetch_add(1, Ordering::Relaxed) == 0 {
             self.update_notifier.send_replace(());
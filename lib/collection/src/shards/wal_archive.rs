@@ -0,0 +1,268 @@
+//! Archiving of the shard WAL for point-in-time recovery
+//!
+//! Operations of the shard WAL are copied into archive files in a user-provided directory.
+//! Once archiving is enabled, WAL is not truncated past the last archived operation, so no
+//! operation is lost between two archive calls.
+//!
+//! To recover, the shard is restored from a snapshot, and the archived operations, which are
+//! newer than the snapshot, are applied on top of it, optionally only up to a given operation.
+//!
+//! Each archive file contains a CBOR encoded list of consecutive operations with their numbers,
+//! and is named after the numbers of the first and the last of them: `{first}-{last}.wal`.
+
+use std::io::BufWriter;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::Ordering;
+
+use atomicwrites::{AtomicFile, OverwriteBehavior};
+use io::file_operations::{atomic_save_json, read_json};
+use segment::types::SeqNumberType;
+use serde::{Deserialize, Serialize};
+use tokio::sync::oneshot;
+
+use crate::operations::types::{CollectionError, CollectionResult};
+use crate::operations::CollectionUpdateOperations;
+use crate::shards::local_shard::{LocalShard, LockedWal};
+use crate::shards::shard_trait::ShardOperation;
+use crate::update_handler::UpdateSignal;
+
+const WAL_ARCHIVE_STATE_FILE: &str = "wal_archive.json";
+
+const ARCHIVE_FILE_EXTENSION: &str = "wal";
+
+type ArchivedOperation = (SeqNumberType, CollectionUpdateOperations);
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct WalArchiveResult {
+    /// Number of archived operations
+    pub operations: usize,
+}
+
+impl std::ops::AddAssign for WalArchiveResult {
+    fn add_assign(&mut self, other: Self) {
+        self.operations += other.operations;
+    }
+}
+
+/// Archiving progress of the shard, exists only if archiving is enabled
+#[derive(Debug, Clone, Copy, Deserialize, Serialize)]
+struct WalArchiveState {
+    /// Number of the next operation to archive
+    next_version: SeqNumberType,
+}
+
+impl WalArchiveState {
+    fn path(shard_path: &Path) -> PathBuf {
+        shard_path.join(WAL_ARCHIVE_STATE_FILE)
+    }
+
+    fn load(shard_path: &Path) -> CollectionResult<Option<Self>> {
+        let path = Self::path(shard_path);
+        if !path.exists() {
+            return Ok(None);
+        }
+        Ok(Some(read_json(&path)?))
+    }
+
+    fn save(&self, shard_path: &Path) -> CollectionResult<()> {
+        Ok(atomic_save_json(&Self::path(shard_path), self)?)
+    }
+
+    /// Last operation, which can be truncated from WAL
+    fn archived_version(&self) -> SeqNumberType {
+        self.next_version.saturating_sub(1)
+    }
+}
+
+/// Version up to which WAL could be truncated, `u64::MAX` if archiving is not enabled
+pub(super) fn load_wal_archived_version(shard_path: &Path) -> CollectionResult<u64> {
+    let state = WalArchiveState::load(shard_path)?;
+    Ok(state.map_or(u64::MAX, |state| state.archived_version()))
+}
+
+impl LocalShard {
+    /// Copy operations, which were written to WAL since the previous call, into the archive
+    ///
+    /// The first call enables archiving, from then on WAL is not truncated past the last archived
+    /// operation. So archiving should be done periodically, until it is stopped with
+    /// [`Self::stop_wal_archiving`].
+    pub async fn archive_wal(&self, archive_path: &Path) -> CollectionResult<WalArchiveResult> {
+        tokio::fs::create_dir_all(archive_path).await?;
+
+        let wal = self.wal.clone();
+        let shard_path = self.path.clone();
+        let archive_path = archive_path.to_owned();
+        let (result, state) = tokio::task::spawn_blocking(move || {
+            archive_wal_operations(&wal, &shard_path, &archive_path)
+        })
+        .await??;
+
+        self.update_handler
+            .lock()
+            .await
+            .wal_archived_version
+            .store(state.archived_version(), Ordering::Relaxed);
+
+        Ok(result)
+    }
+
+    /// Stop archiving of the WAL, so it is truncated as usual
+    pub async fn stop_wal_archiving(&self) -> CollectionResult<()> {
+        self.update_handler
+            .lock()
+            .await
+            .wal_archived_version
+            .store(u64::MAX, Ordering::Relaxed);
+
+        let state_path = WalArchiveState::path(&self.path);
+        if state_path.exists() {
+            tokio::fs::remove_file(state_path).await?;
+        }
+        Ok(())
+    }
+
+    /// Apply archived operations, which are newer than the shard data
+    ///
+    /// If `until_version` is given, operations after it are not applied, which allows to
+    /// recover the shard to a point in time.
+    ///
+    /// Returns number of the applied operations.
+    pub async fn replay_wal_archive(
+        &self,
+        archive_path: &Path,
+        until_version: Option<SeqNumberType>,
+    ) -> CollectionResult<usize> {
+        let is_replayed =
+            |op_num: SeqNumberType| until_version.map_or(true, |until| op_num <= until);
+
+        let mut next_version = self.wal.lock().next_index();
+        let mut replayed = 0;
+
+        for (first, last, path) in archive_files(archive_path)? {
+            if last < next_version {
+                continue;
+            }
+            if !is_replayed(first) {
+                break;
+            }
+
+            let data = tokio::fs::read(&path).await?;
+            let operations: Vec<ArchivedOperation> =
+                serde_cbor::from_slice(&data).map_err(|err| {
+                    CollectionError::bad_input(format!(
+                        "Can't read WAL archive file {}: {err}",
+                        path.display(),
+                    ))
+                })?;
+
+            for (op_num, operation) in operations {
+                if op_num < next_version {
+                    continue;
+                }
+                if !is_replayed(op_num) {
+                    break;
+                }
+                if op_num > next_version {
+                    return Err(CollectionError::bad_input(format!(
+                        "Operations {next_version}..{op_num} are missing in the WAL archive",
+                    )));
+                }
+
+                self.update(operation, false, None).await?;
+                next_version = op_num + 1;
+                replayed += 1;
+            }
+        }
+
+        // Wait until all replayed operations are applied
+        let (tx, rx) = oneshot::channel();
+        self.update_sender
+            .load()
+            .send(UpdateSignal::Plunger(tx))
+            .await?;
+        rx.await?;
+
+        Ok(replayed)
+    }
+}
+
+fn archive_wal_operations(
+    wal: &LockedWal,
+    shard_path: &Path,
+    archive_path: &Path,
+) -> CollectionResult<(WalArchiveResult, WalArchiveState)> {
+    let state = WalArchiveState::load(shard_path)?;
+
+    let (from, operations): (_, Vec<ArchivedOperation>) = {
+        let mut wal = wal.lock();
+        wal.flush()?;
+
+        let first_index = wal.first_index();
+        let from = match state {
+            Some(state) if state.next_version < first_index => {
+                log::warn!(
+                    "Operations {}..{first_index} were truncated from WAL before they were archived",
+                    state.next_version,
+                );
+                first_index
+            }
+            Some(state) => state.next_version,
+            None => first_index,
+        };
+        (from, wal.read(from).collect())
+    };
+
+    let next_version = match (operations.first(), operations.last()) {
+        (Some((first, _)), Some((last, _))) => {
+            let file_path =
+                archive_path.join(format!("{first:020}-{last:020}.{ARCHIVE_FILE_EXTENSION}"));
+            AtomicFile::new(&file_path, OverwriteBehavior::AllowOverwrite)
+                .write(|file| serde_cbor::to_writer(BufWriter::new(file), &operations))
+                .map_err(|err| {
+                    CollectionError::service_error(format!(
+                        "Can't write WAL archive file {}: {err}",
+                        file_path.display(),
+                    ))
+                })?;
+            last + 1
+        }
+        _ => from,
+    };
+
+    let state = WalArchiveState { next_version };
+    state.save(shard_path)?;
+
+    let result = WalArchiveResult {
+        operations: operations.len(),
+    };
+    Ok((result, state))
+}
+
+/// Archive files in the directory, ordered by the operations they contain
+fn archive_files(archive_path: &Path) -> CollectionResult<Vec<(u64, u64, PathBuf)>> {
+    let mut files = Vec::new();
+    for entry in std::fs::read_dir(archive_path)? {
+        let path = entry?.path();
+        if path
+            .extension()
+            .map_or(true, |ext| ext != ARCHIVE_FILE_EXTENSION)
+        {
+            continue;
+        }
+        let range = path
+            .file_stem()
+            .and_then(|stem| stem.to_str())
+            .and_then(parse_archive_file_name);
+        match range {
+            Some((first, last)) => files.push((first, last, path)),
+            None => log::warn!("Skipping unknown file {} in WAL archive", path.display()),
+        }
+    }
+    files.sort_unstable();
+    Ok(files)
+}
+
+fn parse_archive_file_name(stem: &str) -> Option<(u64, u64)> {
+    let (first, last) = stem.split_once('-')?;
+    Some((first.parse().ok()?, last.parse().ok()?))
+}
@@ -3,16 +3,23 @@ use segment::common::anonymize::Anonymize;
 use serde::{Deserialize, Serialize};
 
 use crate::config::CollectionConfig;
+use crate::operations::feature_flags::FeatureFlags;
 use crate::operations::types::ShardTransferInfo;
 use crate::shards::telemetry::ReplicaSetTelemetry;
+use crate::shards::transfer::throttle::ShardTransferRateTelemetry;
 
 #[derive(Serialize, Deserialize, Clone, Debug, JsonSchema)]
 pub struct CollectionTelemetry {
     pub id: String,
     pub init_time_ms: u64,
     pub config: CollectionConfig,
+    /// Flags of the experimental features, with unset ones taken from the node configuration
+    pub feature_flags: FeatureFlags,
     pub shards: Vec<ReplicaSetTelemetry>,
     pub transfers: Vec<ShardTransferInfo>,
+    /// Current rates of the outgoing transfers of this peer
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub transfer_rates: Vec<ShardTransferRateTelemetry>,
 }
 
 impl CollectionTelemetry {
@@ -31,9 +38,11 @@ impl Anonymize for CollectionTelemetry {
         Self {
             id: self.id.anonymize(),
             config: self.config.anonymize(),
+            feature_flags: self.feature_flags,
             init_time_ms: self.init_time_ms,
             shards: self.shards.anonymize(),
             transfers: vec![],
+            transfer_rates: vec![],
         }
     }
 }
@@ -46,6 +55,8 @@ impl Anonymize for CollectionConfig {
             optimizer_config: self.optimizer_config.clone(),
             wal_config: self.wal_config.clone(),
             quantization_config: self.quantization_config.clone(),
+            strict_mode_config: self.strict_mode_config.clone(),
+            feature_flags: self.feature_flags,
         }
     }
 }
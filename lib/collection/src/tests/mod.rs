@@ -1,5 +1,6 @@
 mod snapshot_test;
 mod sparse_vectors_validation_tests;
+mod wal_archive_test;
 mod wal_recovery_test;
 
 use std::sync::Arc;
@@ -8,12 +9,14 @@ use std::time::Duration;
 use futures::future::join_all;
 use itertools::Itertools;
 use parking_lot::{Mutex, RwLock};
+use segment::entry::entry_point::SegmentEntry;
+use segment::types::PayloadStorageType;
 use tempfile::Builder;
 use tokio::time::{sleep, Instant};
 
 use crate::collection::Collection;
 use crate::collection_manager::fixtures::{
-    get_indexing_optimizer, get_merge_optimizer, random_segment,
+    get_config_mismatch_optimizer, get_indexing_optimizer, get_merge_optimizer, random_segment,
 };
 use crate::collection_manager::holders::segment_holder::{LockedSegment, SegmentHolder, SegmentId};
 use crate::collection_manager::optimizers::TrackerStatus;
@@ -68,6 +71,12 @@ async fn test_optimization_process() {
         assert_eq!(log[0].status, TrackerStatus::Done);
         assert!(["indexing", "merge"].contains(&log[1].name.as_str()));
         assert_eq!(log[1].status, TrackerStatus::Done);
+
+        // Resources are reported for finished optimizations
+        for tracker in &log {
+            assert!(tracker.resources.output_points.is_some());
+            assert!(tracker.resources.output_disk_bytes.is_some());
+        }
     }
 
     let handles_2 = UpdateHandler::launch_optimization(
@@ -153,6 +162,82 @@ async fn test_cancel_optimization() {
     }
 }
 
+#[tokio::test]
+async fn test_config_rebuild_limit() {
+    let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+    let temp_dir = Builder::new().prefix("segment_temp_dir").tempdir().unwrap();
+
+    let dim = 4;
+    let mut holder = SegmentHolder::default();
+    for _ in 0..3 {
+        holder.add(random_segment(dir.path(), 100, 10, dim));
+    }
+
+    let optimizers_log = Arc::new(Mutex::new(Default::default()));
+    let segments: Arc<RwLock<_>> = Arc::new(RwLock::new(holder));
+
+    // Aborted rollout doesn't rebuild anything
+    let aborted_optimizer: Arc<Optimizer> = Arc::new(get_config_mismatch_optimizer(
+        dir.path(),
+        temp_dir.path(),
+        dim,
+        Some(0),
+    ));
+    let handles = UpdateHandler::launch_optimization(
+        Arc::new(vec![aborted_optimizer]),
+        optimizers_log.clone(),
+        segments.clone(),
+        |_| {},
+    );
+    assert!(handles.is_empty());
+
+    // Segments are rebuilt one by one
+    let config_mismatch_optimizer: Arc<Optimizer> = Arc::new(get_config_mismatch_optimizer(
+        dir.path(),
+        temp_dir.path(),
+        dim,
+        Some(1),
+    ));
+    let optimizers = Arc::new(vec![config_mismatch_optimizer]);
+    for _ in 0..3 {
+        let handles = UpdateHandler::launch_optimization(
+            optimizers.clone(),
+            optimizers_log.clone(),
+            segments.clone(),
+            |_| {},
+        );
+        assert_eq!(handles.len(), 1);
+
+        // Nothing else is started, until the running rebuild is finished
+        let deferred_handles = UpdateHandler::launch_optimization(
+            optimizers.clone(),
+            optimizers_log.clone(),
+            segments.clone(),
+            |_| {},
+        );
+        assert!(deferred_handles.is_empty());
+
+        for handle in handles {
+            assert_eq!(handle.join_handle.await.unwrap(), Some(true));
+        }
+    }
+
+    let handles = UpdateHandler::launch_optimization(
+        optimizers.clone(),
+        optimizers_log.clone(),
+        segments.clone(),
+        |_| {},
+    );
+    assert!(handles.is_empty());
+
+    for (_idx, segment) in segments.read().iter() {
+        assert_eq!(
+            segment.get().read().config().payload_storage_type,
+            PayloadStorageType::OnDisk,
+        );
+    }
+}
+
 #[test]
 fn check_version_upgrade() {
     assert!(!Collection::can_upgrade_storage(
@@ -1,5 +1,6 @@
 use std::collections::{HashMap, HashSet};
 use std::num::{NonZeroU32, NonZeroU64};
+use std::path::Path;
 use std::sync::Arc;
 
 use segment::types::Distance;
@@ -7,11 +8,16 @@ use tempfile::Builder;
 
 use crate::collection::{Collection, RequestShardTransfer};
 use crate::config::{CollectionConfig, CollectionParams, WalConfig};
+use crate::operations::point_ops::{PointStruct, WriteOrdering};
+use crate::operations::shard_selector_internal::ShardSelectorInternal;
 use crate::operations::shared_storage_config::SharedStorageConfig;
-use crate::operations::types::{NodeType, VectorParams, VectorsConfig};
+use crate::operations::snapshot_ops::SnapshotCreationPriority;
+use crate::operations::types::{CountRequestInternal, NodeType, VectorParams, VectorsConfig};
+use crate::operations::CollectionUpdateOperations;
 use crate::optimizers_builder::OptimizersConfig;
 use crate::shards::channel_service::ChannelService;
 use crate::shards::collection_shard_distribution::CollectionShardDistribution;
+use crate::shards::replica_set::read_priority::ReadPriority;
 use crate::shards::replica_set::{AbortShardTransfer, ChangePeerState};
 
 pub const TEST_OPTIMIZERS_CONFIG: OptimizersConfig = OptimizersConfig {
@@ -22,7 +28,10 @@ pub const TEST_OPTIMIZERS_CONFIG: OptimizersConfig = OptimizersConfig {
     memmap_threshold: None,
     indexing_threshold: Some(50_000),
     flush_interval_sec: 30,
+    flush_dirty_threshold: None,
+    flush_dirty_operations: None,
     max_optimization_threads: 2,
+    max_config_rebuild_segments: None,
 };
 
 pub fn dummy_on_replica_failure() -> ChangePeerState {
@@ -45,6 +54,7 @@ async fn _test_snapshot_collection(node_type: NodeType) {
     let wal_config = WalConfig {
         wal_capacity_mb: 1,
         wal_segments_ahead: 0,
+        wal_compression: Default::default(),
     };
 
     let collection_params = CollectionParams {
@@ -54,6 +64,10 @@ async fn _test_snapshot_collection(node_type: NodeType) {
             hnsw_config: None,
             quantization_config: None,
             on_disk: None,
+            normalize: None,
+            dimensionality_reduction: None,
+            multivector_config: None,
+            datatype: None,
         }),
         shard_number: NonZeroU32::new(4).unwrap(),
         replication_factor: NonZeroU32::new(3).unwrap(),
@@ -67,6 +81,8 @@ async fn _test_snapshot_collection(node_type: NodeType) {
         wal_config,
         hnsw_config: Default::default(),
         quantization_config: Default::default(),
+        strict_mode_config: Default::default(),
+        feature_flags: None,
     };
 
     let snapshots_path = Builder::new().prefix("test_snapshots").tempdir().unwrap();
@@ -84,7 +100,7 @@ async fn _test_snapshot_collection(node_type: NodeType) {
     shards.insert(3, HashSet::from([1, 20_000, 30_000]));
 
     let storage_config: SharedStorageConfig = SharedStorageConfig {
-        node_type,
+        node_type: node_type.into(),
         ..Default::default()
     };
 
@@ -108,7 +124,11 @@ async fn _test_snapshot_collection(node_type: NodeType) {
 
     let snapshots_temp_dir = Builder::new().prefix("temp_dir").tempdir().unwrap();
     let snapshot_description = collection
-        .create_snapshot(snapshots_temp_dir.path(), 0)
+        .create_snapshot(
+            snapshots_temp_dir.path(),
+            0,
+            SnapshotCreationPriority::Normal,
+        )
         .await
         .unwrap();
 
@@ -143,7 +163,8 @@ async fn _test_snapshot_collection(node_type: NodeType) {
         None,
         None,
     )
-    .await;
+    .await
+    .unwrap();
 
     {
         let shards_holder = &recovered_collection.shards_holder.read().await;
@@ -174,3 +195,212 @@ async fn test_snapshot_collection_listener() {
     init_logger();
     _test_snapshot_collection(NodeType::Listener).await;
 }
+
+fn upsert_points_operation(ids: std::ops::Range<u64>) -> CollectionUpdateOperations {
+    CollectionUpdateOperations::PointOperation(
+        ids.map(|id| PointStruct {
+            id: id.into(),
+            vector: vec![id as f32, 2.0, 3.0, 4.0].into(),
+            payload: None,
+            if_version: None,
+        })
+        .collect::<Vec<_>>()
+        .into(),
+    )
+}
+
+/// Number of segment archives in the collection snapshot
+fn count_snapshot_segments(snapshot_path: &Path) -> usize {
+    let archive_file = std::fs::File::open(snapshot_path).unwrap();
+    tar::Archive::new(archive_file)
+        .entries()
+        .unwrap()
+        .filter(|entry| {
+            let entry = entry.as_ref().unwrap();
+            entry
+                .path()
+                .unwrap()
+                .extension()
+                .is_some_and(|ext| ext == "tar")
+        })
+        .count()
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_incremental_snapshot_collection() {
+    init_logger();
+
+    let config = CollectionConfig {
+        params: CollectionParams {
+            vectors: VectorsConfig::Single(VectorParams {
+                size: NonZeroU64::new(4).unwrap(),
+                distance: Distance::Dot,
+                hnsw_config: None,
+                quantization_config: None,
+                on_disk: None,
+                normalize: None,
+                dimensionality_reduction: None,
+                multivector_config: None,
+                datatype: None,
+            }),
+            shard_number: NonZeroU32::new(2).unwrap(),
+            ..CollectionParams::empty()
+        },
+        optimizer_config: TEST_OPTIMIZERS_CONFIG.clone(),
+        wal_config: WalConfig {
+            wal_capacity_mb: 1,
+            wal_segments_ahead: 0,
+            wal_compression: Default::default(),
+        },
+        hnsw_config: Default::default(),
+        quantization_config: Default::default(),
+        strict_mode_config: Default::default(),
+        feature_flags: None,
+    };
+
+    let snapshots_path = Builder::new().prefix("test_snapshots").tempdir().unwrap();
+    let collection_dir = Builder::new().prefix("test_collection").tempdir().unwrap();
+    let recover_dir = Builder::new()
+        .prefix("test_collection_rec")
+        .tempdir()
+        .unwrap();
+    let snapshots_temp_dir = Builder::new().prefix("temp_dir").tempdir().unwrap();
+
+    let shards = HashMap::from([(0, HashSet::from([1])), (1, HashSet::from([1]))]);
+    let collection = Collection::new(
+        "test".to_string(),
+        1,
+        collection_dir.path(),
+        snapshots_path.path(),
+        &config,
+        Default::default(),
+        CollectionShardDistribution { shards },
+        ChannelService::default(),
+        dummy_on_replica_failure(),
+        dummy_request_shard_transfer(),
+        dummy_abort_shard_transfer(),
+        None,
+        None,
+    )
+    .await
+    .unwrap();
+
+    collection
+        .update_from_client_simple(
+            upsert_points_operation(0..10),
+            true,
+            WriteOrdering::default(),
+        )
+        .await
+        .unwrap();
+    let full = collection
+        .create_snapshot(
+            snapshots_temp_dir.path(),
+            1,
+            SnapshotCreationPriority::Normal,
+        )
+        .await
+        .unwrap();
+    let full_segments = count_snapshot_segments(&snapshots_path.path().join(&full.name));
+    // 2 shards with 2 segments each
+    assert_eq!(full_segments, 4);
+
+    collection
+        .update_from_client_simple(
+            upsert_points_operation(10..20),
+            true,
+            WriteOrdering::default(),
+        )
+        .await
+        .unwrap();
+    let increment = collection
+        .create_incremental_snapshot(
+            snapshots_temp_dir.path(),
+            1,
+            SnapshotCreationPriority::Normal,
+            &full.name,
+        )
+        .await
+        .unwrap();
+    let increment_segments = count_snapshot_segments(&snapshots_path.path().join(&increment.name));
+    assert!(increment_segments > 0);
+    assert!(increment_segments <= full_segments);
+
+    // Nothing changed since the previous snapshot
+    let empty_increment = collection
+        .create_incremental_snapshot(
+            snapshots_temp_dir.path(),
+            1,
+            SnapshotCreationPriority::Low,
+            &increment.name,
+        )
+        .await
+        .unwrap();
+    assert_eq!(
+        count_snapshot_segments(&snapshots_path.path().join(&empty_increment.name)),
+        0,
+    );
+
+    let chain: Vec<_> = [&full, &increment, &empty_increment]
+        .iter()
+        .map(|snapshot| snapshots_path.path().join(&snapshot.name))
+        .collect();
+
+    // Incremental snapshots can't be restored without their base
+    assert!(Collection::restore_snapshot(&chain[1], recover_dir.path(), 1, false).is_err());
+    let recover_dir = Builder::new()
+        .prefix("test_collection_rec")
+        .tempdir()
+        .unwrap();
+    assert!(Collection::restore_snapshot_chain(&chain[1..], recover_dir.path(), 1, false).is_err());
+    let recover_dir = Builder::new()
+        .prefix("test_collection_rec")
+        .tempdir()
+        .unwrap();
+    // Increments must be applied in order
+    assert!(Collection::restore_snapshot_chain(
+        &[chain[0].clone(), chain[2].clone()],
+        recover_dir.path(),
+        1,
+        false,
+    )
+    .is_err());
+
+    let recover_dir = Builder::new()
+        .prefix("test_collection_rec")
+        .tempdir()
+        .unwrap();
+    Collection::restore_snapshot_chain(&chain, recover_dir.path(), 1, false).unwrap();
+
+    let recovered_collection = Collection::load(
+        "test_rec".to_string(),
+        1,
+        recover_dir.path(),
+        snapshots_path.path(),
+        Default::default(),
+        ChannelService::default(),
+        dummy_on_replica_failure(),
+        dummy_request_shard_transfer(),
+        dummy_abort_shard_transfer(),
+        None,
+        None,
+    )
+    .await
+    .unwrap();
+
+    let count = recovered_collection
+        .count(
+            CountRequestInternal {
+                filter: None,
+                exact: true,
+            },
+            None,
+            &ShardSelectorInternal::All,
+            None,
+            ReadPriority::Normal,
+            None,
+        )
+        .await
+        .unwrap();
+    assert_eq!(count.count, 20);
+}
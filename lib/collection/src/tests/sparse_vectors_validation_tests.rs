@@ -32,6 +32,7 @@ fn wrong_point_struct() -> PointStruct {
         id: 0.into(),
         vector: VectorStruct::Multi(vector_data),
         payload: None,
+        if_version: None,
     }
 }
 
@@ -63,6 +64,8 @@ fn validate_error_sparse_vector_points_batch() {
             payloads: None,
         },
         shard_key: None,
+        deduplication: None,
+        ttl: None,
     });
 }
 
@@ -71,6 +74,8 @@ fn validate_error_sparse_vector_points_list() {
     check_validation_error(PointsList {
         points: vec![wrong_point_struct()],
         shard_key: None,
+        deduplication: None,
+        ttl: None,
     });
 }
 
@@ -0,0 +1,92 @@
+use std::sync::Arc;
+
+use tempfile::Builder;
+use tokio::runtime::Handle;
+use tokio::sync::RwLock;
+
+use crate::config::CollectionConfig;
+use crate::operations::point_ops::PointStruct;
+use crate::operations::CollectionUpdateOperations;
+use crate::shards::local_shard::LocalShard;
+use crate::shards::shard_trait::ShardOperation;
+use crate::tests::wal_recovery_test::create_collection_config;
+
+fn upsert_point_operation(id: u64) -> CollectionUpdateOperations {
+    CollectionUpdateOperations::PointOperation(
+        vec![PointStruct {
+            id: id.into(),
+            vector: vec![id as f32, 2.0, 3.0, 4.0].into(),
+            payload: None,
+            if_version: None,
+        }]
+        .into(),
+    )
+}
+
+async fn build_shard(config: &CollectionConfig) -> (LocalShard, tempfile::TempDir) {
+    let collection_dir = Builder::new().prefix("test_collection").tempdir().unwrap();
+    let shard = LocalShard::build(
+        0,
+        "test".to_string(),
+        collection_dir.path(),
+        Arc::new(RwLock::new(config.clone())),
+        Arc::new(Default::default()),
+        Handle::current(),
+    )
+    .await
+    .unwrap();
+    (shard, collection_dir)
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_archive_and_replay_wal() {
+    let archive_dir = Builder::new().prefix("wal_archive").tempdir().unwrap();
+    let config = create_collection_config();
+
+    let (shard, _shard_dir) = build_shard(&config).await;
+
+    // Enables archiving
+    let result = shard.archive_wal(archive_dir.path()).await.unwrap();
+    assert_eq!(result.operations, 0);
+
+    for id in 1..=3 {
+        shard
+            .update(upsert_point_operation(id), true, None)
+            .await
+            .unwrap();
+    }
+    let result = shard.archive_wal(archive_dir.path()).await.unwrap();
+    assert_eq!(result.operations, 3);
+
+    for id in 4..=5 {
+        shard
+            .update(upsert_point_operation(id), true, None)
+            .await
+            .unwrap();
+    }
+    let result = shard.archive_wal(archive_dir.path()).await.unwrap();
+    assert_eq!(result.operations, 2);
+
+    // Nothing new to archive
+    let result = shard.archive_wal(archive_dir.path()).await.unwrap();
+    assert_eq!(result.operations, 0);
+
+    shard.stop_wal_archiving().await.unwrap();
+
+    // Recover to the point in time after the 4th operation
+    let (recovered, _recovered_dir) = build_shard(&config).await;
+    let replayed = recovered
+        .replay_wal_archive(archive_dir.path(), Some(3))
+        .await
+        .unwrap();
+    assert_eq!(replayed, 4);
+    assert_eq!(recovered.info().await.unwrap().points_count, Some(4));
+
+    // Already applied operations are skipped
+    let replayed = recovered
+        .replay_wal_archive(archive_dir.path(), None)
+        .await
+        .unwrap();
+    assert_eq!(replayed, 1);
+    assert_eq!(recovered.info().await.unwrap().points_count, Some(5));
+}
@@ -6,7 +6,7 @@ use tempfile::Builder;
 use tokio::runtime::Handle;
 use tokio::sync::RwLock;
 
-use crate::config::{CollectionConfig, CollectionParams, WalConfig};
+use crate::config::{CollectionConfig, CollectionParams, WalCompression, WalConfig};
 use crate::operations::point_ops::{PointOperations, PointStruct};
 use crate::operations::types::{VectorParams, VectorsConfig};
 use crate::operations::{CollectionUpdateOperations, CreateIndex, FieldIndexOperations};
@@ -14,10 +14,11 @@ use crate::shards::local_shard::LocalShard;
 use crate::shards::shard_trait::ShardOperation;
 use crate::tests::snapshot_test::TEST_OPTIMIZERS_CONFIG;
 
-fn create_collection_config() -> CollectionConfig {
+pub(super) fn create_collection_config() -> CollectionConfig {
     let wal_config = WalConfig {
         wal_capacity_mb: 1,
         wal_segments_ahead: 0,
+        wal_compression: Default::default(),
     };
 
     let collection_params = CollectionParams {
@@ -27,6 +28,10 @@ fn create_collection_config() -> CollectionConfig {
             hnsw_config: None,
             quantization_config: None,
             on_disk: None,
+            normalize: None,
+            dimensionality_reduction: None,
+            multivector_config: None,
+            datatype: None,
         }),
         ..CollectionParams::empty()
     };
@@ -42,6 +47,8 @@ fn create_collection_config() -> CollectionConfig {
         wal_config,
         hnsw_config: Default::default(),
         quantization_config: Default::default(),
+        strict_mode_config: Default::default(),
+        feature_flags: None,
     }
 }
 
@@ -54,6 +61,7 @@ fn upsert_operation() -> CollectionUpdateOperations {
                 payload: Some(
                     serde_json::from_str(r#"{ "location": { "lat": 10.12, "lon": 32.12  } }"#).unwrap(),
                 ),
+                if_version: None,
             },
             PointStruct {
                 id: 2.into(),
@@ -61,6 +69,7 @@ fn upsert_operation() -> CollectionUpdateOperations {
                 payload: Some(
                     serde_json::from_str(r#"{ "location": { "lat": 11.12, "lon": 34.82  } }"#).unwrap(),
                 ),
+                if_version: None,
             },
             PointStruct {
                 id: 3.into(),
@@ -68,6 +77,7 @@ fn upsert_operation() -> CollectionUpdateOperations {
                 payload: Some(
                     serde_json::from_str(r#"{ "location": [ { "lat": 12.12, "lon": 34.82  }, { "lat": 12.2, "lon": 12.82  }] }"#).unwrap(),
                 ),
+                if_version: None,
             },
             PointStruct {
                 id: 4.into(),
@@ -75,6 +85,7 @@ fn upsert_operation() -> CollectionUpdateOperations {
                 payload: Some(
                     serde_json::from_str(r#"{ "location": { "lat": 13.12, "lon": 34.82  } }"#).unwrap(),
                 ),
+                if_version: None,
             },
             PointStruct {
                 id: 5.into(),
@@ -82,6 +93,7 @@ fn upsert_operation() -> CollectionUpdateOperations {
                 payload: Some(
                     serde_json::from_str(r#"{ "location": { "lat": 14.12, "lon": 32.12  } }"#).unwrap(),
                 ),
+                if_version: None,
             },
 
         ]
@@ -127,14 +139,14 @@ async fn test_delete_from_indexed_payload() {
 
     let upsert_ops = upsert_operation();
 
-    shard.update(upsert_ops, true).await.unwrap();
+    shard.update(upsert_ops, true, None).await.unwrap();
 
     let index_op = create_payload_index_operation();
 
-    shard.update(index_op, true).await.unwrap();
+    shard.update(index_op, true, None).await.unwrap();
 
     let delete_point_op = delete_point_operation(4);
-    shard.update(delete_point_op, true).await.unwrap();
+    shard.update(delete_point_op, true, None).await.unwrap();
 
     let info = shard.info().await.unwrap();
     eprintln!("info = {:#?}", info.payload_schema);
@@ -157,7 +169,7 @@ async fn test_delete_from_indexed_payload() {
 
     eprintln!("dropping point 5");
     let delete_point_op = delete_point_operation(5);
-    shard.update(delete_point_op, true).await.unwrap();
+    shard.update(delete_point_op, true, None).await.unwrap();
 
     drop(shard);
 
@@ -180,3 +192,68 @@ async fn test_delete_from_indexed_payload() {
     assert_eq!(number_of_indexed_points, 4);
     assert_eq!(number_of_indexed_points_after_load, 3);
 }
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_recover_from_compressed_wal() {
+    let collection_dir = Builder::new().prefix("test_collection").tempdir().unwrap();
+
+    let mut config = create_collection_config();
+    config.wal_config.wal_compression = WalCompression::Zstd;
+
+    let collection_name = "test".to_string();
+
+    let current_runtime: Handle = Handle::current();
+
+    let shard = LocalShard::build(
+        0,
+        collection_name.clone(),
+        collection_dir.path(),
+        Arc::new(RwLock::new(config.clone())),
+        Arc::new(Default::default()),
+        current_runtime.clone(),
+    )
+    .await
+    .unwrap();
+
+    shard.update(upsert_operation(), true, None).await.unwrap();
+
+    drop(shard);
+
+    // Entries of different compression methods are mixed in the same WAL
+    config.wal_config.wal_compression = WalCompression::Lz4;
+
+    let shard = LocalShard::load(
+        0,
+        collection_name.clone(),
+        collection_dir.path(),
+        Arc::new(RwLock::new(config.clone())),
+        Arc::new(Default::default()),
+        current_runtime.clone(),
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(shard.info().await.unwrap().points_count, Some(5));
+
+    shard
+        .update(delete_point_operation(4), true, None)
+        .await
+        .unwrap();
+
+    drop(shard);
+
+    config.wal_config.wal_compression = WalCompression::None;
+
+    let shard = LocalShard::load(
+        0,
+        collection_name,
+        collection_dir.path(),
+        Arc::new(RwLock::new(config)),
+        Arc::new(Default::default()),
+        current_runtime,
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(shard.info().await.unwrap().points_count, Some(4));
+}
@@ -1,7 +1,8 @@
 use std::cmp::min;
 use std::collections::HashSet;
-use std::sync::atomic::AtomicU64;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
 use std::sync::Arc;
+use std::time::Instant;
 
 use common::panic;
 use itertools::Itertools;
@@ -9,22 +10,31 @@ use log::{debug, error, info, trace, warn};
 use parking_lot::Mutex;
 use segment::common::operation_error::OperationResult;
 use segment::types::SeqNumberType;
+use segment::utils::resource_usage;
 use tokio::runtime::Handle;
 use tokio::sync::mpsc::{self, Receiver, Sender};
-use tokio::sync::{oneshot, Mutex as TokioMutex};
+use tokio::sync::{
+    oneshot, Mutex as TokioMutex, Notify, OwnedSemaphorePermit, RwLock as TokioRwLock,
+};
 use tokio::task::JoinHandle;
 use tokio::time::error::Elapsed;
-use tokio::time::{timeout, Duration};
+use tokio::time::{timeout, timeout_at, Duration};
 
 use crate::collection_manager::collection_updater::CollectionUpdater;
 use crate::collection_manager::holders::segment_holder::LockedSegmentHolder;
 use crate::collection_manager::optimizers::segment_optimizer::SegmentOptimizer;
 use crate::collection_manager::optimizers::{Tracker, TrackerLog, TrackerStatus};
 use crate::common::stoppable_task::{spawn_stoppable, StoppableTaskHandle};
+use crate::config::CollectionConfig;
+use crate::events::{CollectionEventKind, EventNotifier};
 use crate::operations::shared_storage_config::SharedStorageConfig;
 use crate::operations::types::{CollectionError, CollectionResult};
 use crate::operations::CollectionUpdateOperations;
 use crate::shards::local_shard::LockedWal;
+use crate::shards::scroll_session::ScrollSessions;
+use crate::shards::shard::ShardId;
+use crate::shards::time_travel::{self, PointHistory};
+use crate::shards::{scrubber, CollectionId};
 use crate::wal::WalError;
 
 /// Interval at which the optimizer worker cleans up old optimization handles
@@ -41,10 +51,14 @@ pub struct OperationData {
     pub op_num: SeqNumberType,
     /// Operation
     pub operation: CollectionUpdateOperations,
+    /// Size of the operation, as stored in WAL, in bytes
+    pub size: usize,
     /// If operation was requested to wait for result
     pub wait: bool,
     /// Callback notification channel
     pub sender: Option<oneshot::Sender<CollectionResult<usize>>>,
+    /// Slot of the bulk lane of the shard, released once the operation is applied
+    pub bulk_permit: Option<OwnedSemaphorePermit>,
 }
 
 /// Signal, used to inform Updater process
@@ -71,8 +85,65 @@ pub enum OptimizerSignal {
     Nop,
 }
 
+/// Tracks updates applied to segments since the last flush.
+///
+/// Wakes up the flush worker ahead of the flush interval,
+/// once accumulated updates exceed the configured thresholds.
+struct DirtyTracker {
+    max_operations: Option<usize>,
+    max_bytes: Option<usize>,
+    operations: AtomicUsize,
+    bytes: AtomicUsize,
+    notify: Notify,
+}
+
+impl DirtyTracker {
+    fn new(max_operations: Option<usize>, max_bytes: Option<usize>) -> Self {
+        Self {
+            max_operations,
+            max_bytes,
+            // Operations recovered from WAL on load are not tracked, so start dirty
+            operations: AtomicUsize::new(1),
+            bytes: AtomicUsize::new(0),
+            notify: Notify::new(),
+        }
+    }
+
+    fn record(&self, size: usize) {
+        self.operations.fetch_add(1, Ordering::Relaxed);
+        self.bytes.fetch_add(size, Ordering::Relaxed);
+        if self.is_exceeded() {
+            self.notify.notify_one();
+        }
+    }
+
+    fn is_exceeded(&self) -> bool {
+        let operations = self.operations.load(Ordering::Relaxed);
+        let bytes = self.bytes.load(Ordering::Relaxed);
+        self.max_operations.is_some_and(|max| operations >= max)
+            || self.max_bytes.is_some_and(|max| bytes >= max)
+    }
+
+    fn is_clean(&self) -> bool {
+        self.operations.load(Ordering::Relaxed) == 0
+    }
+
+    /// Reset counters before the flush, updates applied during the flush are tracked for the next one
+    fn reset(&self) {
+        self.operations.store(0, Ordering::Relaxed);
+        self.bytes.store(0, Ordering::Relaxed);
+    }
+
+    /// Make sure the next flush is not skipped, if the current one has failed
+    fn mark_dirty(&self) {
+        self.operations.fetch_max(1, Ordering::Relaxed);
+    }
+}
+
 /// Structure, which holds object, required for processing updates of the collection
 pub struct UpdateHandler {
+    collection_id: CollectionId,
+    shard_id: ShardId,
     shared_storage_config: Arc<SharedStorageConfig>,
     /// List of used optimizers
     pub optimizers: Arc<Vec<Arc<Optimizer>>>,
@@ -80,6 +151,10 @@ pub struct UpdateHandler {
     optimizers_log: Arc<Mutex<TrackerLog>>,
     /// How frequent can we flush data
     pub flush_interval_sec: u64,
+    /// Size (in kilobytes) of updates, which triggers flush before the interval has passed
+    pub flush_dirty_threshold: Option<usize>,
+    /// Number of updates, which triggers flush before the interval has passed
+    pub flush_dirty_operations: Option<usize>,
     segments: LockedSegmentHolder,
     /// Process, that listens updates signals and perform updates
     update_worker: Option<JoinHandle<()>>,
@@ -89,6 +164,13 @@ pub struct UpdateHandler {
     flush_worker: Option<JoinHandle<()>>,
     /// Sender to stop flush worker
     flush_stop: Option<oneshot::Sender<()>>,
+    /// Process that periodically verifies checksums of the storage, if scrubbing is configured
+    scrub_worker: Option<JoinHandle<()>>,
+    collection_config: Arc<TokioRwLock<CollectionConfig>>,
+    /// Previous states of the changed points for time-travel reads
+    point_history: Arc<Mutex<PointHistory>>,
+    /// Scroll sessions, which pin segments. Expired ones are closed by the optimization worker
+    scroll_sessions: Arc<Mutex<ScrollSessions>>,
     runtime_handle: Handle,
     /// WAL, required for operations
     wal: LockedWal,
@@ -97,23 +179,36 @@ pub struct UpdateHandler {
     /// shard.
     /// Defaults to `u64::MAX` to allow acknowledging all confirmed versions.
     pub(super) max_ack_version: Arc<AtomicU64>,
-    optimization_handles: Arc<TokioMutex<Vec<StoppableTaskHandle<bool>>>>,
-    max_optimization_threads: usize,
+    /// Last operation, which is copied to the WAL archive. WAL is not truncated past it, so
+    /// operations are not lost between two archive calls.
+    /// `u64::MAX`, if WAL archiving is not enabled.
+    pub(super) wal_archived_version: Arc<AtomicU64>,
+    pub(super) optimization_handles: Arc<TokioMutex<Vec<StoppableTaskHandle<bool>>>>,
+    pub(super) max_optimization_threads: usize,
 }
 
 impl UpdateHandler {
     #[allow(clippy::too_many_arguments)]
     pub fn new(
+        collection_id: CollectionId,
+        shard_id: ShardId,
         shared_storage_config: Arc<SharedStorageConfig>,
+        collection_config: Arc<TokioRwLock<CollectionConfig>>,
+        point_history: Arc<Mutex<PointHistory>>,
+        scroll_sessions: Arc<Mutex<ScrollSessions>>,
         optimizers: Arc<Vec<Arc<Optimizer>>>,
         optimizers_log: Arc<Mutex<TrackerLog>>,
         runtime_handle: Handle,
         segments: LockedSegmentHolder,
         wal: LockedWal,
         flush_interval_sec: u64,
+        flush_dirty_threshold: Option<usize>,
+        flush_dirty_operations: Option<usize>,
         max_optimization_threads: usize,
     ) -> UpdateHandler {
         UpdateHandler {
+            collection_id,
+            shard_id,
             shared_storage_config,
             optimizers,
             segments,
@@ -122,18 +217,37 @@ impl UpdateHandler {
             optimizers_log,
             flush_worker: None,
             flush_stop: None,
+            scrub_worker: None,
+            collection_config,
+            point_history,
+            scroll_sessions,
             runtime_handle,
             wal,
             max_ack_version: Arc::new(u64::MAX.into()),
+            wal_archived_version: Arc::new(u64::MAX.into()),
             flush_interval_sec,
+            flush_dirty_threshold,
+            flush_dirty_operations,
             optimization_handles: Arc::new(TokioMutex::new(vec![])),
             max_optimization_threads,
         }
     }
 
-    pub fn run_workers(&mut self, update_receiver: Receiver<UpdateSignal>) {
+    pub fn run_workers(
+        &mut self,
+        update_sender: Sender<UpdateSignal>,
+        update_receiver: Receiver<UpdateSignal>,
+    ) {
+        let dirty_tracker = Arc::new(DirtyTracker::new(
+            self.flush_dirty_operations,
+            self.flush_dirty_threshold
+                .map(|threshold_kb| threshold_kb.saturating_mul(1024)),
+        ));
         let (tx, rx) = mpsc::channel(self.shared_storage_config.update_queue_size);
         self.optimizer_worker = Some(self.runtime_handle.spawn(Self::optimization_worker_fn(
+            self.collection_id.clone(),
+            self.shard_id,
+            self.shared_storage_config.events.clone(),
             self.optimizers.clone(),
             tx.clone(),
             rx,
@@ -141,6 +255,7 @@ impl UpdateHandler {
             self.wal.clone(),
             self.optimization_handles.clone(),
             self.optimizers_log.clone(),
+            self.scroll_sessions.clone(),
             self.max_optimization_threads,
         )));
         self.update_worker = Some(self.runtime_handle.spawn(Self::update_worker_fn(
@@ -148,16 +263,31 @@ impl UpdateHandler {
             tx,
             self.wal.clone(),
             self.segments.clone(),
+            self.collection_config.clone(),
+            self.point_history.clone(),
+            self.scroll_sessions.clone(),
+            dirty_tracker.clone(),
         )));
         let (flush_tx, flush_rx) = oneshot::channel();
         self.flush_worker = Some(self.runtime_handle.spawn(Self::flush_worker(
             self.segments.clone(),
             self.wal.clone(),
             self.max_ack_version.clone(),
+            self.wal_archived_version.clone(),
+            self.wal_delta_retention(),
             self.flush_interval_sec,
+            dirty_tracker,
             flush_rx,
         )));
         self.flush_stop = Some(flush_tx);
+        if let Some(scrub_interval_sec) = self.shared_storage_config.scrubber.scrub_interval_sec {
+            self.scrub_worker = Some(self.runtime_handle.spawn(Self::scrub_worker(
+                Duration::from_secs(scrub_interval_sec),
+                self.shared_storage_config.clone(),
+                self.segments.clone(),
+                update_sender,
+            )));
+        }
     }
 
     pub fn stop_flush_worker(&mut self) {
@@ -183,6 +313,10 @@ impl UpdateHandler {
         if let Some(handle) = maybe_handle {
             handle.await?;
         }
+        let maybe_handle = self.scrub_worker.take();
+        if let Some(handle) = maybe_handle {
+            handle.await?;
+        }
 
         let mut opt_handles_guard = self.optimization_handles.lock().await;
         let opt_handles = std::mem::take(&mut *opt_handles_guard);
@@ -198,6 +332,58 @@ impl UpdateHandler {
         Ok(())
     }
 
+    /// Stop workers for the node shutdown
+    ///
+    /// Unlike [`Self::wait_workers_stops`], running optimizations are cancelled right away, and
+    /// workers are not awaited past the `deadline`. Applied updates are flushed to disk in any
+    /// case, so they don't have to be recovered from WAL on the next start.
+    pub async fn shutdown_workers(&mut self, deadline: Instant) -> CollectionResult<()> {
+        let running_optimizations = {
+            let handles = self.optimization_handles.lock().await;
+            for handle in handles.iter() {
+                handle.ask_to_stop();
+            }
+            handles.len()
+        };
+        if running_optimizations > 0 {
+            info!(
+                "Cancelling {running_optimizations} optimizations of collection {} shard {}",
+                self.collection_id, self.shard_id,
+            );
+        }
+
+        self.stop_flush_worker();
+        if timeout_at(deadline.into(), self.wait_workers_stops())
+            .await
+            .is_err()
+        {
+            warn!(
+                "Workers of collection {} shard {} did not stop before the shutdown deadline, \
+                 proceeding without them",
+                self.collection_id, self.shard_id,
+            );
+        }
+
+        self.wal.lock().flush()?;
+
+        let segments = self.segments.clone();
+        let confirmed_version =
+            tokio::task::spawn_blocking(move || Self::flush_segments(segments, true)).await??;
+        let ack = Self::ack_version(
+            confirmed_version,
+            &self.max_ack_version,
+            &self.wal_archived_version,
+            self.wal_delta_retention(),
+        );
+        self.wal.lock().ack(ack)?;
+
+        debug!(
+            "Flushed collection {} shard {} up to version {confirmed_version}",
+            self.collection_id, self.shard_id,
+        );
+        Ok(())
+    }
+
     /// Checks if there are any failed operations.
     /// If so - attempts to re-apply all failed operations.
     async fn try_recover(segments: LockedSegmentHolder, wal: LockedWal) -> CollectionResult<usize> {
@@ -233,6 +419,22 @@ impl UpdateHandler {
         let mut handles = vec![];
         for optimizer in optimizers.iter() {
             loop {
+                let permit = match optimizer.concurrency_limit() {
+                    Some(limit) => match limit.try_acquire() {
+                        Some(permit) => Some(permit),
+                        None => {
+                            debug!(
+                                "Optimizer `{}` reached the limit of {} concurrent optimizations, \
+                                 further optimizations are deferred",
+                                optimizer.name(),
+                                limit.max(),
+                            );
+                            break;
+                        }
+                    },
+                    None => None,
+                };
+
                 let nonoptimal_segment_ids =
                     optimizer.check_condition(segments.clone(), &scheduled_segment_ids);
                 if nonoptimal_segment_ids.is_empty() {
@@ -251,13 +453,30 @@ impl UpdateHandler {
                     {
                         let segments = segments.clone();
                         move |stopped| {
+                            // Counts towards the limit until the optimization is finished
+                            let _permit = permit;
+
                             // Track optimizer status
                             let tracker = Tracker::start(optimizer.as_ref().name(), nsi.clone());
                             let tracker_handle = tracker.handle();
                             optimizers_log.lock().register(tracker);
 
-                            // Optimize and handle result
-                            match optimizer.as_ref().optimize(segments.clone(), nsi, stopped) {
+                            // Optimize and account used resources
+                            let usage_at_start = resource_usage::current_usage_with_helpers();
+                            tracker_handle.sample_rss();
+                            let result = optimizer.as_ref().optimize_tracked(
+                                segments.clone(),
+                                nsi,
+                                stopped,
+                                &tracker_handle,
+                            );
+                            tracker_handle.record_usage(
+                                resource_usage::current_usage_with_helpers().since(usage_at_start),
+                            );
+                            tracker_handle.sample_rss();
+
+                            // Handle result
+                            match result {
                                 // Perform some actions when optimization if finished
                                 Ok(result) => {
                                     tracker_handle.update(TrackerStatus::Done);
@@ -313,7 +532,11 @@ impl UpdateHandler {
         handles
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub(crate) async fn process_optimization(
+        collection_id: CollectionId,
+        shard_id: ShardId,
+        events: EventNotifier,
         optimizers: Arc<Vec<Arc<Optimizer>>>,
         segments: LockedSegmentHolder,
         optimization_handles: Arc<TokioMutex<Vec<StoppableTaskHandle<bool>>>>,
@@ -324,7 +547,14 @@ impl UpdateHandler {
             optimizers.clone(),
             optimizers_log,
             segments.clone(),
-            move |_optimization_result| {
+            move |optimization_result| {
+                if optimization_result {
+                    events.notify(
+                        collection_id,
+                        Some(shard_id),
+                        CollectionEventKind::OptimizationFinished,
+                    );
+                }
                 // After optimization is finished, we still need to check if there are
                 // some further optimizations possible.
                 // If receiver is already dead - we do not care.
@@ -365,6 +595,9 @@ impl UpdateHandler {
 
     #[allow(clippy::too_many_arguments)]
     async fn optimization_worker_fn(
+        collection_id: CollectionId,
+        shard_id: ShardId,
+        events: EventNotifier,
         optimizers: Arc<Vec<Arc<Optimizer>>>,
         sender: Sender<OptimizerSignal>,
         mut receiver: Receiver<OptimizerSignal>,
@@ -372,6 +605,7 @@ impl UpdateHandler {
         wal: LockedWal,
         optimization_handles: Arc<TokioMutex<Vec<StoppableTaskHandle<bool>>>>,
         optimizers_log: Arc<Mutex<TrackerLog>>,
+        scroll_sessions: Arc<Mutex<ScrollSessions>>,
         max_handles: usize,
     ) {
         loop {
@@ -380,6 +614,8 @@ impl UpdateHandler {
 
             // Always clean up on any signal
             Self::cleanup_optimization_handles(optimization_handles.clone()).await;
+            // Expired scroll sessions don't need the history of the points anymore
+            scroll_sessions.lock().remove_expired(Instant::now());
 
             match result {
                 // Channel closed or stop signal
@@ -402,6 +638,9 @@ impl UpdateHandler {
                         continue;
                     }
                     Self::process_optimization(
+                        collection_id.clone(),
+                        shard_id,
+                        events.clone(),
                         optimizers.clone(),
                         segments.clone(),
                         optimization_handles.clone(),
@@ -414,19 +653,60 @@ impl UpdateHandler {
         }
     }
 
+    /// Record states of the points right before the operation changes them, if time-travel reads
+    /// are enabled or scroll sessions are open
+    ///
+    /// If the states can't be read, the history starts over, so reads never see incomplete history.
+    fn record_point_history(
+        point_history: &Mutex<PointHistory>,
+        scroll_sessions: &Mutex<ScrollSessions>,
+        segments: &LockedSegmentHolder,
+        time_travel_window: Option<u64>,
+        operation: &CollectionUpdateOperations,
+    ) {
+        // Time is taken before the sessions are checked, so the change is recorded for sessions,
+        // which are opened concurrently
+        let now = time_travel::now_millis();
+        // Scroll sessions read the points as they were at the start of the session
+        let sessions_window = scroll_sessions
+            .lock()
+            .oldest_opened_at()
+            .map(|opened_at| now.saturating_sub(opened_at) / 1000 + 1);
+        let window = time_travel_window.max(sessions_window);
+
+        let previous = match window {
+            None => vec![],
+            Some(_) => match time_travel::affected_points(segments, operation) {
+                Ok(previous) => previous,
+                Err(err) => {
+                    warn!("Can't record history of the points, it starts over: {err}");
+                    point_history.lock().record(now, None, []);
+                    return;
+                }
+            },
+        };
+        point_history.lock().record(now, window, previous);
+    }
+
     async fn update_worker_fn(
         mut receiver: Receiver<UpdateSignal>,
         optimize_sender: Sender<OptimizerSignal>,
         wal: LockedWal,
         segments: LockedSegmentHolder,
+        collection_config: Arc<TokioRwLock<CollectionConfig>>,
+        point_history: Arc<Mutex<PointHistory>>,
+        scroll_sessions: Arc<Mutex<ScrollSessions>>,
+        dirty_tracker: Arc<DirtyTracker>,
     ) {
         while let Some(signal) = receiver.recv().await {
             match signal {
                 UpdateSignal::Operation(OperationData {
                     op_num,
                     operation,
+                    size,
                     sender,
                     wait,
+                    bulk_permit,
                 }) => {
                     let flush_res = if wait {
                         wal.lock().flush().map_err(|err| {
@@ -439,8 +719,23 @@ impl UpdateHandler {
                         Ok(())
                     };
 
-                    let operation_result = flush_res
-                        .and_then(|_| CollectionUpdater::update(&segments, op_num, operation));
+                    let time_travel_window =
+                        collection_config.read().await.params.time_travel_window_sec;
+
+                    let operation_result = flush_res.and_then(|_| {
+                        Self::record_point_history(
+                            &point_history,
+                            &scroll_sessions,
+                            &segments,
+                            time_travel_window,
+                            &operation,
+                        );
+                        CollectionUpdater::update(&segments, op_num, operation)
+                    });
+
+                    if operation_result.is_ok() {
+                        dirty_tracker.record(size);
+                    }
 
                     let res = match operation_result {
                         Ok(update_res) => optimize_sender
@@ -451,6 +746,9 @@ impl UpdateHandler {
                         Err(err) => Err(err),
                     };
 
+                    // Next bulk update could be queued
+                    drop(bulk_permit);
+
                     if let Some(feedback) = sender {
                         feedback.send(res).unwrap_or_else(|_| {
                             info!(
@@ -493,69 +791,206 @@ impl UpdateHandler {
         segments: LockedSegmentHolder,
         wal: LockedWal,
         max_ack: Arc<AtomicU64>,
+        wal_archived_version: Arc<AtomicU64>,
+        wal_delta_retention: u64,
         flush_interval_sec: u64,
+        dirty_tracker: Arc<DirtyTracker>,
         mut stop_receiver: oneshot::Receiver<()>,
     ) {
+        let mut last_confirmed_version = None;
         loop {
             // Stop flush worker on signal or if sender was dropped
             // Even if timer did not finish
             tokio::select! {
                 _ = tokio::time::sleep(Duration::from_secs(flush_interval_sec)) => {},
+                _ = dirty_tracker.notify.notified() => {
+                    // Notification might be left from updates, which were already flushed
+                    if !dirty_tracker.is_exceeded() {
+                        continue;
+                    }
+                    trace!("Dirty updates threshold exceeded, flushing ahead of interval");
+                },
                 _ = &mut stop_receiver => {
                     debug!("Stopping flush worker.");
                     return;
                 }
             };
 
-            trace!("Attempting flushing");
-            let wal_flash_job = wal.lock().flush_async();
-
-            if let Err(err) = wal_flash_job.join() {
-                error!("Failed to flush wal: {:?}", err);
-                segments
-                    .write()
-                    .report_optimizer_error(WalError::WriteWalError(format!(
-                        "WAL flush error: {err:?}"
-                    )));
-                continue;
-            }
-
-            let confirmed_version = Self::flush_segments(segments.clone());
-            let confirmed_version = match confirmed_version {
-                Ok(version) => version,
-                Err(err) => {
-                    error!("Failed to flush: {err}");
-                    segments.write().report_optimizer_error(err);
+            let confirmed_version = if dirty_tracker.is_clean() {
+                // Nothing to flush, but WAL might still be truncated further,
+                // if the maximum acknowledged version was raised since the last flush
+                let Some(confirmed_version) = last_confirmed_version else {
+                    continue;
+                };
+                confirmed_version
+            } else {
+                dirty_tracker.reset();
+
+                trace!("Attempting flushing");
+                let wal_flash_job = wal.lock().flush_async();
+
+                if let Err(err) = wal_flash_job.join() {
+                    error!("Failed to flush wal: {:?}", err);
+                    segments
+                        .write()
+                        .report_optimizer_error(WalError::WriteWalError(format!(
+                            "WAL flush error: {err:?}"
+                        )));
+                    dirty_tracker.mark_dirty();
                     continue;
                 }
-            };
-
-            // Acknowledge confirmed version in WAL, but don't exceed specified maximum
-            // This is to prevent truncating WAL entries that may still be used by other things
-            // such as the queue proxy shard.
-            // Default maximum ack version is `u64::MAX` to allow acknowledging all confirmed.
-            let max_ack = max_ack.load(std::sync::atomic::Ordering::Relaxed);
-            if confirmed_version > max_ack {
-                trace!("Acknowledging message {max_ack} in WAL, {confirmed_version} is already confirmed but max_ack_version is set");
-            }
-            let ack = confirmed_version.min(max_ack);
 
+                let confirmed_version = Self::flush_segments(segments.clone(), false);
+                match confirmed_version {
+                    Ok(version) => version,
+                    Err(err) => {
+                        error!("Failed to flush: {err}");
+                        segments.write().report_optimizer_error(err);
+                        dirty_tracker.mark_dirty();
+                        continue;
+                    }
+                }
+            };
+            last_confirmed_version = Some(confirmed_version);
+
+            let ack = Self::ack_version(
+                confirmed_version,
+                &max_ack,
+                &wal_archived_version,
+                wal_delta_retention,
+            );
             if let Err(err) = wal.lock().ack(ack) {
                 segments.write().report_optimizer_error(err);
             }
         }
     }
 
+    /// Periodically verifies checksums of the segments with the low priority IO budget,
+    /// see [`scrubber`] for details
+    ///
+    /// Stops once the update worker is stopped, between the segments of a running scrub.
+    async fn scrub_worker(
+        scrub_interval: Duration,
+        shared_storage_config: Arc<SharedStorageConfig>,
+        segments: LockedSegmentHolder,
+        update_sender: Sender<UpdateSignal>,
+    ) {
+        loop {
+            tokio::select! {
+                _ = tokio::time::sleep(scrub_interval) => {},
+                _ = update_sender.closed() => {
+                    debug!("Stopping scrub worker.");
+                    return;
+                }
+            };
+
+            let result = tokio::task::spawn_blocking({
+                let segments = segments.clone();
+                let update_sender = update_sender.clone();
+                let shared_storage_config = shared_storage_config.clone();
+                move || {
+                    scrubber::scrub_segments(
+                        &segments,
+                        &shared_storage_config.low_priority_io_budget,
+                        shared_storage_config.scrubber.quarantine,
+                        || update_sender.is_closed(),
+                    )
+                }
+            })
+            .await;
+            match result {
+                Ok(corruptions) if corruptions.is_empty() => {
+                    debug!("Scrubbed segments, no corruption found")
+                }
+                Ok(corruptions) => warn!("Scrubber found {} corrupted segments", corruptions.len()),
+                Err(err) => error!("Failed to scrub segments: {err}"),
+            }
+        }
+    }
+
     /// Returns confirmed version after flush of all segments
     ///
     /// # Errors
     /// Returns an error on flush failure
-    fn flush_segments(segments: LockedSegmentHolder) -> OperationResult<SeqNumberType> {
+    /// Version to acknowledge in WAL, once segments are flushed up to `confirmed_version`
+    fn ack_version(
+        confirmed_version: SeqNumberType,
+        max_ack: &AtomicU64,
+        wal_archived_version: &AtomicU64,
+        wal_delta_retention: u64,
+    ) -> SeqNumberType {
+        // Acknowledge confirmed version in WAL, but don't exceed specified maximum
+        // This is to prevent truncating WAL entries that may still be used by other things
+        // such as the queue proxy shard.
+        // Default maximum ack version is `u64::MAX` to allow acknowledging all confirmed.
+        let max_ack = max_ack.load(Ordering::Relaxed);
+        if confirmed_version > max_ack {
+            trace!("Acknowledging message {max_ack} in WAL, {confirmed_version} is already confirmed but max_ack_version is set");
+        }
+        let ack = confirmed_version.min(max_ack);
+
+        // Operations, which are not archived yet, are kept in WAL
+        let ack = ack.min(wal_archived_version.load(Ordering::Relaxed));
+
+        // Latest operations are kept to recover dead replicas from
+        ack.min(confirmed_version.saturating_sub(wal_delta_retention))
+    }
+
+    fn wal_delta_retention(&self) -> u64 {
+        self.shared_storage_config
+            .shard_transfer
+            .wal_delta_retention
+            .unwrap_or(0) as u64
+    }
+
+    fn flush_segments(segments: LockedSegmentHolder, sync: bool) -> OperationResult<SeqNumberType> {
         let read_segments = segments.read();
-        let flushed_version = read_segments.flush_all(false)?;
+        let flushed_version = read_segments.flush_all(sync)?;
         Ok(match read_segments.failed_operation.iter().cloned().min() {
             None => flushed_version,
             Some(failed_operation) => min(failed_operation, flushed_version),
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dirty_tracker() {
+        let tracker = DirtyTracker::new(Some(3), Some(1000));
+        // Starts dirty, as updates recovered from WAL are not tracked
+        assert!(!tracker.is_clean());
+        assert!(!tracker.is_exceeded());
+
+        tracker.reset();
+        assert!(tracker.is_clean());
+
+        tracker.record(10);
+        tracker.record(10);
+        assert!(!tracker.is_clean());
+        assert!(!tracker.is_exceeded());
+
+        // Operations threshold
+        tracker.record(10);
+        assert!(tracker.is_exceeded());
+
+        // Bytes threshold
+        tracker.reset();
+        tracker.record(1000);
+        assert!(tracker.is_exceeded());
+
+        // Failed flush keeps the tracker dirty
+        tracker.reset();
+        tracker.mark_dirty();
+        assert!(!tracker.is_clean());
+        assert!(!tracker.is_exceeded());
+
+        let unlimited = DirtyTracker::new(None, None);
+        for _ in 0..100 {
+            unlimited.record(1_000_000);
+        }
+        assert!(!unlimited.is_exceeded());
+    }
+}
@@ -1,3 +1,4 @@
+use std::borrow::Cow;
 use std::fmt::Debug;
 use std::marker::PhantomData;
 use std::path::Path;
@@ -10,6 +11,8 @@ use serde::{Deserialize, Serialize};
 use thiserror::Error;
 use wal::{Wal, WalOptions};
 
+use crate::config::WalCompression;
+
 #[allow(clippy::enum_variant_names)]
 #[derive(Error, Debug)]
 #[error("{0}")]
@@ -67,10 +70,57 @@ pub struct SerdeWal<R> {
     wal: Wal,
     options: WalOptions,
     first_index: Option<u64>,
+    compression: WalCompression,
 }
 
 const FIRST_INDEX_FILE: &str = "first-index";
 
+/// First byte of the compressed records, followed by the byte of the compression method.
+///
+/// It is the CBOR "break" stop code, which can't start an encoded record, so compressed and
+/// uncompressed records can be mixed in the same WAL. Note that it is a valid first byte of a
+/// MessagePack record (negative fixint), so the marker has to change if records are encoded
+/// with MessagePack again.
+const COMPRESSED_RECORD_MARKER: u8 = 0xFF;
+const LZ4_RECORD: u8 = 1;
+const ZSTD_RECORD: u8 = 2;
+
+/// Default compression level of zstd
+const ZSTD_LEVEL: i32 = 0;
+
+fn compress_record(record: Vec<u8>, compression: WalCompression) -> Vec<u8> {
+    let (method, compressed) = match compression {
+        WalCompression::None => return record,
+        WalCompression::Lz4 => (LZ4_RECORD, lz4_flex::compress_prepend_size(&record)),
+        // Compression of in-memory buffer never fails
+        WalCompression::Zstd => (
+            ZSTD_RECORD,
+            zstd::encode_all(record.as_slice(), ZSTD_LEVEL).unwrap(),
+        ),
+    };
+    let mut result = Vec::with_capacity(compressed.len() + 2);
+    result.extend_from_slice(&[COMPRESSED_RECORD_MARKER, method]);
+    result.extend_from_slice(&compressed);
+    result
+}
+
+fn decompress_record(record: &[u8]) -> result::Result<Cow<[u8]>, String> {
+    match record {
+        [COMPRESSED_RECORD_MARKER, LZ4_RECORD, compressed @ ..] => {
+            lz4_flex::decompress_size_prepended(compressed)
+                .map(Cow::Owned)
+                .map_err(|err| err.to_string())
+        }
+        [COMPRESSED_RECORD_MARKER, ZSTD_RECORD, compressed @ ..] => zstd::decode_all(compressed)
+            .map(Cow::Owned)
+            .map_err(|err| err.to_string()),
+        [COMPRESSED_RECORD_MARKER, method, ..] => {
+            Err(format!("unknown compression method {method}"))
+        }
+        _ => Ok(Cow::Borrowed(record)),
+    }
+}
+
 impl<'s, R: DeserializeOwned + Serialize + Debug> SerdeWal<R> {
     pub fn new(dir: &str, wal_options: WalOptions) -> Result<SerdeWal<R>> {
         let wal = Wal::with_options(dir, &wal_options)
@@ -97,16 +147,35 @@ impl<'s, R: DeserializeOwned + Serialize + Debug> SerdeWal<R> {
             wal,
             options: wal_options,
             first_index,
+            compression: WalCompression::None,
         })
     }
 
-    /// Write a record to the WAL but does guarantee durability.
+    /// Compress new records with the given method
+    pub fn with_compression(mut self, compression: WalCompression) -> Self {
+        self.compression = compression;
+        self
+    }
+
+    /// Write a record to the WAL but does not guarantee durability.
+    ///
+    /// Records are appended to the memory-mapped segment without fsync, durability is provided
+    /// by [`Self::flush`] and [`Self::flush_async`], which are called periodically by the flush
+    /// worker for all records written since the previous flush at once.
     pub fn write(&mut self, entity: &R) -> Result<u64> {
+        self.write_sized(entity).map(|(index, _size)| index)
+    }
+
+    /// Same as [`Self::write`], but also returns size of the stored record in bytes.
+    pub fn write_sized(&mut self, entity: &R) -> Result<(u64, usize)> {
         // ToDo: Replace back to faster rmp, once this https://github.com/serde-rs/serde/issues/2055 solved
         let binary_entity = serde_cbor::to_vec(&entity).unwrap();
-        self.wal
+        let binary_entity = compress_record(binary_entity, self.compression);
+        let index = self
+            .wal
             .append(&binary_entity)
-            .map_err(|err| WalError::WriteWalError(format!("{err:?}")))
+            .map_err(|err| WalError::WriteWalError(format!("{err:?}")))?;
+        Ok((index, binary_entity.len()))
     }
 
     pub fn read_all(&'s self) -> impl Iterator<Item = (u64, R)> + 's {
@@ -149,6 +218,8 @@ impl<'s, R: DeserializeOwned + Serialize + Debug> SerdeWal<R> {
 
         (start_from..(first_index + len)).map(move |idx| {
             let record_bin = self.wal.entry(idx).expect("Can't read entry from WAL");
+            let record_bin = decompress_record(&record_bin)
+                .unwrap_or_else(|err| panic!("Can't decompress entry from WAL: {err}"));
             let record: R = serde_cbor::from_slice(&record_bin)
                 .or_else(|_err| rmp_serde::from_slice(&record_bin))
                 .expect("Can't deserialize entry, probably corrupted WAL on version mismatch");
@@ -225,6 +296,11 @@ impl<'s, R: DeserializeOwned + Serialize + Debug> SerdeWal<R> {
         self.wal.last_index()
     }
 
+    /// Sequential number, which will be assigned to the next written record
+    pub fn next_index(&self) -> u64 {
+        self.wal.first_index() + self.wal.num_entries()
+    }
+
     pub fn segment_capacity(&self) -> usize {
         self.options.segment_capacity
     }
@@ -293,4 +369,23 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn test_compressed_records() {
+        let record = serde_cbor::to_vec(&TestInternalStruct2 { a: 12, b: 13 }).unwrap();
+
+        for compression in [
+            WalCompression::None,
+            WalCompression::Lz4,
+            WalCompression::Zstd,
+        ] {
+            let stored = compress_record(record.clone(), compression);
+            assert_eq!(
+                decompress_record(&stored).unwrap().as_ref(),
+                record.as_slice()
+            );
+        }
+
+        assert!(decompress_record(&[COMPRESSED_RECORD_MARKER, 42, 0]).is_err());
+    }
 }
@@ -4,6 +4,7 @@ use collection::operations::point_ops::{
 use collection::operations::shard_selector_internal::ShardSelectorInternal;
 use collection::operations::types::ScrollRequestInternal;
 use collection::operations::CollectionUpdateOperations;
+use collection::shards::replica_set::read_priority::ReadPriority;
 use itertools::Itertools;
 use segment::types::{PayloadContainer, PayloadSelectorExclude, WithPayloadInterface};
 use serde_json::Value;
@@ -98,9 +99,18 @@ async fn test_collection_payload_reloading_with_shards(shard_number: u32) {
                 filter: None,
                 with_payload: Some(WithPayloadInterface::Bool(true)),
                 with_vector: true.into(),
+                as_of: None,
+                session_id: None,
+                session_ttl_sec: None,
+                order_by: None,
+                sample: None,
+                with_version: false,
             },
             None,
             &ShardSelectorInternal::All,
+            None,
+            ReadPriority::Normal,
+            None,
         )
         .await
         .unwrap();
@@ -169,9 +179,18 @@ async fn test_collection_payload_custom_payload_with_shards(shard_number: u32) {
                 filter: None,
                 with_payload: Some(WithPayloadInterface::Fields(vec![String::from("k2")])),
                 with_vector: true.into(),
+                as_of: None,
+                session_id: None,
+                session_ttl_sec: None,
+                order_by: None,
+                sample: None,
+                with_version: false,
             },
             None,
             &ShardSelectorInternal::All,
+            None,
+            ReadPriority::Normal,
+            None,
         )
         .await
         .unwrap();
@@ -203,9 +222,18 @@ async fn test_collection_payload_custom_payload_with_shards(shard_number: u32) {
                 filter: None,
                 with_payload: Some(PayloadSelectorExclude::new(vec!["k1".to_string()]).into()),
                 with_vector: false.into(),
+                as_of: None,
+                session_id: None,
+                session_ttl_sec: None,
+                order_by: None,
+                sample: None,
+                with_version: false,
             },
             None,
             &ShardSelectorInternal::All,
+            None,
+            ReadPriority::Normal,
+            None,
         )
         .await
         .unwrap();
@@ -1,15 +1,20 @@
 use std::collections::HashSet;
 use std::fs::File;
 
+use collection::config::StrictModeConfig;
+use collection::operations::config_diff::{
+    CollectionParamsDiff, HnswConfigDiff, OptimizersConfigDiff,
+};
 use collection::operations::payload_ops::{PayloadOps, SetPayloadOp};
 use collection::operations::point_ops::{Batch, PointOperations, PointStruct, WriteOrdering};
 use collection::operations::shard_selector_internal::ShardSelectorInternal;
 use collection::operations::types::{
     CountRequestInternal, PointRequestInternal, RecommendRequestInternal, ScrollRequestInternal,
-    SearchRequestInternal, UpdateStatus,
+    SearchRequestInternal, UpdateStatus, VectorsConfigDiff,
 };
 use collection::operations::CollectionUpdateOperations;
 use collection::recommendations::recommend_by;
+use collection::shards::replica_set::read_priority::ReadPriority;
 use collection::shards::replica_set::{ReplicaSetState, ReplicaState};
 use itertools::Itertools;
 use segment::data_types::vectors::VectorStruct;
@@ -78,6 +83,9 @@ async fn test_collection_updater_with_shards(shard_number: u32) {
             None,
             &ShardSelectorInternal::All,
             None,
+            None,
+            ReadPriority::Normal,
+            None,
         )
         .await;
 
@@ -142,6 +150,9 @@ async fn test_collection_search_with_payload_and_vector_with_shards(shard_number
             None,
             &ShardSelectorInternal::All,
             None,
+            None,
+            ReadPriority::Normal,
+            None,
         )
         .await;
 
@@ -173,7 +184,14 @@ async fn test_collection_search_with_payload_and_vector_with_shards(shard_number
     };
 
     let count_res = collection
-        .count(count_request, None, &ShardSelectorInternal::All)
+        .count(
+            count_request,
+            None,
+            &ShardSelectorInternal::All,
+            None,
+            ReadPriority::Normal,
+            None,
+        )
         .await
         .unwrap();
     assert_eq!(count_res.count, 1);
@@ -222,6 +240,7 @@ async fn test_collection_loading_with_shards(shard_number: u32) {
                 payload,
                 points: Some(vec![2.into(), 3.into()]),
                 filter: None,
+                if_version: None,
             }));
 
         collection
@@ -241,9 +260,18 @@ async fn test_collection_loading_with_shards(shard_number: u32) {
         ids: vec![1.into(), 2.into()],
         with_payload: Some(WithPayloadInterface::Bool(true)),
         with_vector: true.into(),
+        as_of: None,
+        with_version: false,
     };
     let retrieved = loaded_collection
-        .retrieve(request, None, &ShardSelectorInternal::All)
+        .retrieve(
+            request,
+            None,
+            &ShardSelectorInternal::All,
+            None,
+            ReadPriority::Normal,
+            None,
+        )
         .await
         .unwrap();
 
@@ -286,11 +314,13 @@ fn test_deserialization2() {
                 id: 0.into(),
                 vector: vec![1.0, 0.0, 1.0, 1.0].into(),
                 payload: None,
+                if_version: None,
             },
             PointStruct {
                 id: 1.into(),
                 vector: vec![1.0, 0.0, 1.0, 0.0].into(),
                 payload: None,
+                if_version: None,
             },
         ]
         .into(),
@@ -355,6 +385,9 @@ async fn test_recommendation_api_with_shards(shard_number: u32) {
         None,
         ShardSelectorInternal::All,
         None,
+        None,
+        ReadPriority::Normal,
+        None,
     )
     .await
     .unwrap();
@@ -410,9 +443,18 @@ async fn test_read_api_with_shards(shard_number: u32) {
                 filter: None,
                 with_payload: Some(WithPayloadInterface::Bool(true)),
                 with_vector: false.into(),
+                as_of: None,
+                session_id: None,
+                session_ttl_sec: None,
+                order_by: None,
+                sample: None,
+                with_version: false,
             },
             None,
             &ShardSelectorInternal::All,
+            None,
+            ReadPriority::Normal,
+            None,
         )
         .await
         .unwrap();
@@ -493,9 +535,18 @@ async fn test_collection_delete_points_by_filter_with_shards(shard_number: u32)
                 filter: None,
                 with_payload: Some(WithPayloadInterface::Bool(false)),
                 with_vector: false.into(),
+                as_of: None,
+                session_id: None,
+                session_ttl_sec: None,
+                order_by: None,
+                sample: None,
+                with_version: false,
             },
             None,
             &ShardSelectorInternal::All,
+            None,
+            ReadPriority::Normal,
+            None,
         )
         .await
         .unwrap();
@@ -548,3 +599,230 @@ async fn test_collection_local_load_initializing_not_stuck() {
         }
     }
 }
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_collection_delete_expired_points() {
+    test_collection_delete_expired_points_with_shards(1).await;
+    test_collection_delete_expired_points_with_shards(N_SHARDS).await;
+}
+
+async fn test_collection_delete_expired_points_with_shards(shard_number: u32) {
+    let collection_dir = Builder::new().prefix("collection").tempdir().unwrap();
+
+    let collection = simple_collection_fixture(collection_dir.path(), shard_number).await;
+
+    let params_diff: CollectionParamsDiff =
+        serde_json::from_value(serde_json::json!({ "expiration_field": "expire_at" })).unwrap();
+    collection
+        .update_params_from_diff(params_diff)
+        .await
+        .unwrap();
+
+    // Points 0 and 3 are expired long ago, others expire in the far future
+    let payloads = (0..5)
+        .map(|id| {
+            let expire_at: u64 = if id == 0 || id == 3 {
+                100
+            } else {
+                4_000_000_000
+            };
+            Some(Payload::from(serde_json::json!({ "expire_at": expire_at })))
+        })
+        .collect();
+    let insert_points = CollectionUpdateOperations::PointOperation(
+        Batch {
+            ids: (0..5).map(|x: u64| x.into()).collect_vec(),
+            vectors: vec![
+                vec![1.0, 0.0, 1.0, 1.0],
+                vec![1.0, 0.0, 1.0, 0.0],
+                vec![1.0, 1.0, 1.0, 1.0],
+                vec![1.0, 1.0, 0.0, 1.0],
+                vec![1.0, 0.0, 0.0, 0.0],
+            ]
+            .into(),
+            payloads: Some(payloads),
+        }
+        .into(),
+    );
+    collection
+        .update_from_client_simple(insert_points, true, WriteOrdering::default())
+        .await
+        .unwrap();
+
+    // Points are not expired by a field without an index
+    assert_eq!(collection.delete_expired_points().await, 0);
+
+    collection.create_expiration_index().await.unwrap();
+    assert_eq!(collection.delete_expired_points().await, 2);
+    assert_eq!(collection.delete_expired_points().await, 0);
+
+    let result = collection
+        .scroll_by(
+            ScrollRequestInternal {
+                offset: None,
+                limit: Some(10),
+                filter: None,
+                with_payload: Some(WithPayloadInterface::Bool(false)),
+                with_vector: false.into(),
+                as_of: None,
+                session_id: None,
+                session_ttl_sec: None,
+                order_by: None,
+                sample: None,
+                with_version: false,
+            },
+            None,
+            &ShardSelectorInternal::All,
+            None,
+            ReadPriority::Normal,
+            None,
+        )
+        .await
+        .unwrap();
+
+    let ids = result.points.iter().map(|point| point.id).collect_vec();
+    assert_eq!(ids, vec![1.into(), 2.into(), 4.into()]);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_check_config_update() {
+    let collection_dir = Builder::new().prefix("collection").tempdir().unwrap();
+
+    let collection = simple_collection_fixture(collection_dir.path(), 1).await;
+
+    let hnsw_diff: HnswConfigDiff = serde_json::from_value(serde_json::json!({ "m": 32 })).unwrap();
+    let optimizers_diff: OptimizersConfigDiff =
+        serde_json::from_value(serde_json::json!({ "max_config_rebuild_segments": 1 })).unwrap();
+    let vectors_diff: VectorsConfigDiff =
+        serde_json::from_value(serde_json::json!({ "": { "on_disk": true } })).unwrap();
+    collection
+        .check_config_update(
+            None,
+            Some(&hnsw_diff),
+            Some(&optimizers_diff),
+            Some(&vectors_diff),
+            None,
+        )
+        .await
+        .unwrap();
+
+    // Resulting HNSW config is invalid
+    let hnsw_diff: HnswConfigDiff =
+        serde_json::from_value(serde_json::json!({ "ef_construct": 1 })).unwrap();
+    assert!(collection
+        .check_config_update(None, Some(&hnsw_diff), None, None, None)
+        .await
+        .is_err());
+
+    // Resulting optimizers config is invalid
+    let optimizers_diff: OptimizersConfigDiff =
+        serde_json::from_value(serde_json::json!({ "deleted_threshold": 1.5 })).unwrap();
+    assert!(collection
+        .check_config_update(None, None, Some(&optimizers_diff), None, None)
+        .await
+        .is_err());
+
+    // Vector is not a part of the collection
+    let vectors_diff: VectorsConfigDiff =
+        serde_json::from_value(serde_json::json!({ "image": { "on_disk": true } })).unwrap();
+    assert!(collection
+        .check_config_update(None, None, None, Some(&vectors_diff), None)
+        .await
+        .is_err());
+
+    // Nothing is applied by the check
+    let config = collection
+        .info(&ShardSelectorInternal::All)
+        .await
+        .unwrap()
+        .config;
+    assert_eq!(config.hnsw_config, Default::default());
+    assert_eq!(config.optimizer_config.max_config_rebuild_segments, None);
+    assert_eq!(config.params.vectors.get_params("").unwrap().on_disk, None);
+}
+
+fn insert_points_operation(ids: std::ops::Range<u64>) -> CollectionUpdateOperations {
+    CollectionUpdateOperations::PointOperation(
+        Batch {
+            ids: ids.clone().map(|x| x.into()).collect_vec(),
+            vectors: ids
+                .map(|x| vec![x as f32, 0.0, 1.0, 1.0])
+                .collect_vec()
+                .into(),
+            payloads: None,
+        }
+        .into(),
+    )
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_collection_quotas() {
+    test_collection_quotas_with_shards(1).await;
+    test_collection_quotas_with_shards(N_SHARDS).await;
+}
+
+async fn test_collection_quotas_with_shards(shard_number: u32) {
+    let collection_dir = Builder::new().prefix("collection").tempdir().unwrap();
+
+    let collection = simple_collection_fixture(collection_dir.path(), shard_number).await;
+    collection
+        .update_from_client_simple(
+            insert_points_operation(0..10),
+            true,
+            WriteOrdering::default(),
+        )
+        .await
+        .unwrap();
+
+    collection
+        .update_strict_mode_config(StrictModeConfig {
+            enabled: Some(true),
+            max_points_count: Some(100),
+            max_disk_usage_bytes: Some(1),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+
+    // Stored points take more than a byte on disk
+    let result = collection
+        .check_strict_mode_update(&insert_points_operation(10..20))
+        .await;
+    assert!(result.is_err());
+
+    // Deletes are always allowed
+    let delete_points = CollectionUpdateOperations::PointOperation(PointOperations::DeletePoints {
+        ids: vec![0.into()],
+    });
+    assert!(collection
+        .check_strict_mode_update(&delete_points)
+        .await
+        .is_ok());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_collection_usage() {
+    test_collection_usage_with_shards(1).await;
+    test_collection_usage_with_shards(N_SHARDS).await;
+}
+
+async fn test_collection_usage_with_shards(shard_number: u32) {
+    let collection_dir = Builder::new().prefix("collection").tempdir().unwrap();
+
+    let collection = simple_collection_fixture(collection_dir.path(), shard_number).await;
+    collection
+        .update_from_client_simple(
+            insert_points_operation(0..10),
+            true,
+            WriteOrdering::default(),
+        )
+        .await
+        .unwrap();
+
+    // Shards without a shard key are reported as a single tenant
+    let usage = collection.usage().await.unwrap();
+    assert_eq!(usage.tenants.len(), 1);
+    assert_eq!(usage.tenants[0].shard_key, None);
+    assert_eq!(usage.tenants[0].points_count, 10);
+    assert!(usage.tenants[0].disk_usage_bytes > 0);
+}
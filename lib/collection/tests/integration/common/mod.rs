@@ -30,7 +30,10 @@ pub const TEST_OPTIMIZERS_CONFIG: OptimizersConfig = OptimizersConfig {
     memmap_threshold: None,
     indexing_threshold: Some(50_000),
     flush_interval_sec: 30,
+    flush_dirty_threshold: None,
+    flush_dirty_operations: None,
     max_optimization_threads: 2,
+    max_config_rebuild_segments: None,
 };
 
 #[cfg(test)]
@@ -39,6 +42,7 @@ pub async fn simple_collection_fixture(collection_path: &Path, shard_number: u32
     let wal_config = WalConfig {
         wal_capacity_mb: 1,
         wal_segments_ahead: 0,
+        wal_compression: Default::default(),
     };
 
     let collection_params = CollectionParams {
@@ -48,6 +52,10 @@ pub async fn simple_collection_fixture(collection_path: &Path, shard_number: u32
             hnsw_config: None,
             quantization_config: None,
             on_disk: None,
+            normalize: None,
+            dimensionality_reduction: None,
+            multivector_config: None,
+            datatype: None,
         }
         .into(),
         shard_number: NonZeroU32::new(shard_number).expect("Shard number can not be zero"),
@@ -60,6 +68,8 @@ pub async fn simple_collection_fixture(collection_path: &Path, shard_number: u32
         wal_config,
         hnsw_config: Default::default(),
         quantization_config: Default::default(),
+        strict_mode_config: Default::default(),
+        feature_flags: None,
     };
 
     let snapshot_path = collection_path.join("snapshots");
@@ -144,4 +154,5 @@ pub async fn load_local_collection(
         None,
     )
     .await
+    .unwrap()
 }
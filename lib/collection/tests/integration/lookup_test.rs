@@ -4,6 +4,7 @@ use collection::lookup::{lookup_ids, WithLookup};
 use collection::operations::consistency_params::ReadConsistency;
 use collection::operations::point_ops::{Batch, WriteOrdering};
 use collection::operations::shard_selector_internal::ShardSelectorInternal;
+use collection::shards::replica_set::read_priority::ReadPriority;
 use collection::shards::shard::ShardId;
 use itertools::Itertools;
 use rand::rngs::SmallRng;
@@ -117,6 +118,9 @@ async fn happy_lookup_ids() {
         collection_by_name,
         read_consistency,
         &shard_selection,
+        None,
+        ReadPriority::Normal,
+        None,
     )
     .await;
 
@@ -205,6 +209,9 @@ async fn nonexistent_lookup_ids_are_ignored(#[case] value: impl Into<PseudoId>)
         collection_by_name,
         read_consistency,
         &shard_selection,
+        None,
+        ReadPriority::Normal,
+        None,
     )
     .await;
 
@@ -237,6 +244,9 @@ async fn err_when_collection_by_name_returns_none() {
         collection_by_name,
         read_consistency,
         &shard_selection,
+        None,
+        ReadPriority::Normal,
+        None,
     )
     .await;
 
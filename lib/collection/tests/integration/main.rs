@@ -13,4 +13,8 @@ pub mod multi_vec_test;
 #[cfg(test)]
 pub mod pagination_test;
 #[cfg(test)]
+pub mod partial_results_test;
+#[cfg(test)]
+pub mod payload_sharding_test;
+#[cfg(test)]
 pub mod snapshot_recovery_test;
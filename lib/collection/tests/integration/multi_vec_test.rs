@@ -14,6 +14,7 @@ use collection::operations::types::{
 };
 use collection::operations::CollectionUpdateOperations;
 use collection::recommendations::recommend_by;
+use collection::shards::replica_set::read_priority::ReadPriority;
 use segment::data_types::named_vectors::NamedVectors;
 use segment::data_types::vectors::{NamedVector, VectorStruct};
 use segment::types::{Distance, WithPayloadInterface, WithVector};
@@ -35,6 +36,7 @@ pub async fn multi_vec_collection_fixture(collection_path: &Path, shard_number:
     let wal_config = WalConfig {
         wal_capacity_mb: 1,
         wal_segments_ahead: 0,
+        wal_compression: Default::default(),
     };
 
     let vector_params1 = VectorParams {
@@ -43,6 +45,10 @@ pub async fn multi_vec_collection_fixture(collection_path: &Path, shard_number:
         hnsw_config: None,
         quantization_config: None,
         on_disk: None,
+        normalize: None,
+        dimensionality_reduction: None,
+        multivector_config: None,
+        datatype: None,
     };
     let vector_params2 = VectorParams {
         size: NonZeroU64::new(4).unwrap(),
@@ -50,6 +56,10 @@ pub async fn multi_vec_collection_fixture(collection_path: &Path, shard_number:
         hnsw_config: None,
         quantization_config: None,
         on_disk: None,
+        normalize: None,
+        dimensionality_reduction: None,
+        multivector_config: None,
+        datatype: None,
     };
 
     let mut vectors_config = BTreeMap::new();
@@ -69,6 +79,8 @@ pub async fn multi_vec_collection_fixture(collection_path: &Path, shard_number:
         wal_config,
         hnsw_config: Default::default(),
         quantization_config: Default::default(),
+        strict_mode_config: Default::default(),
+        feature_flags: None,
     };
 
     let snapshot_path = collection_path.join("snapshots");
@@ -103,6 +115,7 @@ async fn test_multi_vec_with_shards(shard_number: u32) {
             id: i.into(),
             vector: vectors.into(),
             payload: Some(serde_json::from_str(r#"{"number": "John Doe"}"#).unwrap()),
+            if_version: None,
         });
     }
     let insert_points = CollectionUpdateOperations::PointOperation(PointOperations::UpsertPoints(
@@ -136,6 +149,9 @@ async fn test_multi_vec_with_shards(shard_number: u32) {
             None,
             &ShardSelectorInternal::All,
             None,
+            None,
+            ReadPriority::Normal,
+            None,
         )
         .await
         .unwrap();
@@ -169,6 +185,9 @@ async fn test_multi_vec_with_shards(shard_number: u32) {
             None,
             &ShardSelectorInternal::All,
             None,
+            None,
+            ReadPriority::Normal,
+            None,
         )
         .await;
 
@@ -198,6 +217,9 @@ async fn test_multi_vec_with_shards(shard_number: u32) {
             None,
             &ShardSelectorInternal::All,
             None,
+            None,
+            ReadPriority::Normal,
+            None,
         )
         .await
         .unwrap();
@@ -218,9 +240,14 @@ async fn test_multi_vec_with_shards(shard_number: u32) {
                 ids: vec![6.into()],
                 with_payload: Some(WithPayloadInterface::Bool(false)),
                 with_vector: WithVector::Selector(vec![VEC_NAME1.to_string()]),
+                as_of: None,
+                with_version: false,
             },
             None,
             &ShardSelectorInternal::All,
+            None,
+            ReadPriority::Normal,
+            None,
         )
         .await
         .unwrap();
@@ -247,6 +274,9 @@ async fn test_multi_vec_with_shards(shard_number: u32) {
         None,
         ShardSelectorInternal::All,
         None,
+        None,
+        ReadPriority::Normal,
+        None,
     )
     .await;
 
@@ -273,6 +303,9 @@ async fn test_multi_vec_with_shards(shard_number: u32) {
         None,
         ShardSelectorInternal::All,
         None,
+        None,
+        ReadPriority::Normal,
+        None,
     )
     .await
     .unwrap();
@@ -1,10 +1,13 @@
+use std::collections::HashSet;
+
 use collection::operations::point_ops::{
     PointInsertOperationsInternal, PointOperations, PointStruct, WriteOrdering,
 };
 use collection::operations::shard_selector_internal::ShardSelectorInternal;
-use collection::operations::types::SearchRequestInternal;
+use collection::operations::types::{CoreSearchRequest, SearchRequestInternal};
 use collection::operations::CollectionUpdateOperations;
-use segment::types::WithPayloadInterface;
+use collection::shards::replica_set::read_priority::ReadPriority;
+use segment::types::{ExtendedPointId, WithPayloadInterface};
 use tempfile::Builder;
 
 use crate::common::{simple_collection_fixture, N_SHARDS};
@@ -30,6 +33,7 @@ async fn test_collection_paginated_search_with_shards(shard_number: u32) {
             id: i.into(),
             vector: vec![i as f32, 0.0, 0.0, 0.0].into(),
             payload: Some(serde_json::from_str(r#"{"number": "John Doe"}"#).unwrap()),
+            if_version: None,
         });
     }
     let insert_points = CollectionUpdateOperations::PointOperation(PointOperations::UpsertPoints(
@@ -59,6 +63,9 @@ async fn test_collection_paginated_search_with_shards(shard_number: u32) {
             None,
             &ShardSelectorInternal::All,
             None,
+            None,
+            ReadPriority::Normal,
+            None,
         )
         .await
         .unwrap();
@@ -85,6 +92,9 @@ async fn test_collection_paginated_search_with_shards(shard_number: u32) {
             None,
             &ShardSelectorInternal::All,
             None,
+            None,
+            ReadPriority::Normal,
+            None,
         )
         .await
         .unwrap();
@@ -112,6 +122,9 @@ async fn test_collection_paginated_search_with_shards(shard_number: u32) {
             None,
             &ShardSelectorInternal::All,
             None,
+            None,
+            ReadPriority::Normal,
+            None,
         )
         .await
         .unwrap();
@@ -122,3 +135,155 @@ async fn test_collection_paginated_search_with_shards(shard_number: u32) {
         assert_eq!(page_9_result[i], reference_result[page_size * 9 + i]);
     }
 }
+
+fn upsert_operation(points: impl IntoIterator<Item = (u64, f32)>) -> CollectionUpdateOperations {
+    let points = points
+        .into_iter()
+        .map(|(id, score)| PointStruct {
+            id: id.into(),
+            vector: vec![score, 0.0, 0.0, 0.0].into(),
+            payload: None,
+            if_version: None,
+        })
+        .collect();
+    CollectionUpdateOperations::PointOperation(PointOperations::UpsertPoints(
+        PointInsertOperationsInternal::PointsList(points),
+    ))
+}
+
+fn page_request(limit: usize) -> CoreSearchRequest {
+    SearchRequestInternal {
+        vector: vec![1.0, 0.0, 0.0, 0.0].into(),
+        filter: None,
+        limit,
+        offset: None,
+        with_payload: Some(WithPayloadInterface::Bool(false)),
+        with_vector: None,
+        params: None,
+        score_threshold: None,
+    }
+    .into()
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_collection_search_page_with_updates() {
+    test_collection_search_page_with_updates_with_shards(1).await;
+    test_collection_search_page_with_updates_with_shards(N_SHARDS).await;
+}
+
+async fn test_collection_search_page_with_updates_with_shards(shard_number: u32) {
+    let collection_dir = Builder::new()
+        .prefix("test_collection_search_page")
+        .tempdir()
+        .unwrap();
+
+    let collection = simple_collection_fixture(collection_dir.path(), shard_number).await;
+
+    // Score of each point is equal to its id
+    collection
+        .update_from_client_simple(
+            upsert_operation((0..100).map(|i| (i, i as f32))),
+            true,
+            WriteOrdering::default(),
+        )
+        .await
+        .unwrap();
+
+    let first_page = collection
+        .search_page(
+            page_request(10),
+            None,
+            None,
+            None,
+            &ShardSelectorInternal::All,
+            None,
+            None,
+            ReadPriority::Normal,
+            None,
+        )
+        .await
+        .unwrap();
+    let mut returned_ids: Vec<_> = first_page.points.iter().map(|point| point.id).collect();
+    let expected_first_page: Vec<ExtendedPointId> = (90..100u64).rev().map(Into::into).collect();
+    assert_eq!(returned_ids, expected_first_page);
+
+    // Points written after the first page: a new best point, a new point at the end of the
+    // results, and an already returned point which is moved after the cursor
+    collection
+        .update_from_client_simple(
+            upsert_operation([(1000, 1000.0), (1001, 0.5), (95, 45.5)]),
+            true,
+            WriteOrdering::default(),
+        )
+        .await
+        .unwrap();
+
+    let mut cursor = first_page.next_page_cursor;
+    while let Some(page_cursor) = cursor {
+        // Limit may be changed between pages
+        let page = collection
+            .search_page(
+                page_request(7),
+                Some(&page_cursor),
+                None,
+                None,
+                &ShardSelectorInternal::All,
+                None,
+                None,
+                ReadPriority::Normal,
+                None,
+            )
+            .await
+            .unwrap();
+        returned_ids.extend(page.points.iter().map(|point| point.id));
+        cursor = page.next_page_cursor;
+
+        // Keep writing between the pages
+        collection
+            .update_from_client_simple(
+                upsert_operation([(2000 + returned_ids.len() as u64, 0.1)]),
+                true,
+                WriteOrdering::default(),
+            )
+            .await
+            .unwrap();
+    }
+
+    // Every point, which existed before the first page, is returned exactly once and in order
+    let unique_ids: HashSet<_> = returned_ids.iter().collect();
+    assert_eq!(unique_ids.len(), returned_ids.len());
+    let expected_ids: Vec<ExtendedPointId> = (0..100u64).rev().map(Into::into).collect();
+    assert_eq!(returned_ids, expected_ids);
+
+    // Cursor of another request is rejected
+    let page = collection
+        .search_page(
+            page_request(10),
+            None,
+            None,
+            None,
+            &ShardSelectorInternal::All,
+            None,
+            None,
+            ReadPriority::Normal,
+            None,
+        )
+        .await
+        .unwrap();
+    let mut other_request = page_request(10);
+    other_request.score_threshold = Some(50.0);
+    let result = collection
+        .search_page(
+            other_request,
+            page.next_page_cursor.as_deref(),
+            None,
+            None,
+            &ShardSelectorInternal::All,
+            None,
+            None,
+            ReadPriority::Normal,
+            None,
+        )
+        .await;
+    assert!(result.is_err());
+}
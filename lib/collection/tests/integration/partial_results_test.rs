@@ -0,0 +1,207 @@
+use std::collections::{HashMap, HashSet};
+use std::num::{NonZeroU32, NonZeroU64};
+
+use collection::collection::Collection;
+use collection::config::{CollectionConfig, CollectionParams, WalConfig};
+use collection::operations::point_ops::{
+    PointInsertOperationsInternal, PointOperations, PointStruct, WriteOrdering,
+};
+use collection::operations::shard_selector_internal::ShardSelectorInternal;
+use collection::operations::types::{CoreSearchRequestBatch, SearchRequestInternal, VectorParams};
+use collection::operations::CollectionUpdateOperations;
+use collection::shards::channel_service::ChannelService;
+use collection::shards::collection_shard_distribution::CollectionShardDistribution;
+use collection::shards::replica_set::read_priority::ReadPriority;
+use collection::shards::replica_set::ReplicaState;
+use segment::types::{Distance, WithPayloadInterface};
+use tempfile::Builder;
+
+use crate::common::{
+    dummy_abort_shard_transfer, dummy_on_replica_failure, dummy_request_shard_transfer, REST_PORT,
+    TEST_OPTIMIZERS_CONFIG,
+};
+
+const THIS_PEER_ID: u64 = 0;
+/// Peer, which is not known to the channel service, so requests to it always fail
+const UNREACHABLE_PEER_ID: u64 = 1;
+
+fn search_request() -> CoreSearchRequestBatch {
+    CoreSearchRequestBatch {
+        searches: vec![SearchRequestInternal {
+            vector: vec![1.0, 0.0, 0.0, 0.0].into(),
+            filter: None,
+            limit: 100,
+            offset: None,
+            with_payload: Some(WithPayloadInterface::Bool(true)),
+            with_vector: None,
+            params: None,
+            score_threshold: None,
+        }
+        .into()],
+    }
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_partial_search_with_failed_shard() {
+    let collection_dir = Builder::new()
+        .prefix("test_partial_search")
+        .tempdir()
+        .unwrap();
+    let snapshots_dir = Builder::new()
+        .prefix("test_partial_search_snapshots")
+        .tempdir()
+        .unwrap();
+
+    let config = CollectionConfig {
+        params: CollectionParams {
+            vectors: VectorParams {
+                size: NonZeroU64::new(4).unwrap(),
+                distance: Distance::Dot,
+                hnsw_config: None,
+                quantization_config: None,
+                on_disk: None,
+                normalize: None,
+                dimensionality_reduction: None,
+                multivector_config: None,
+                datatype: None,
+            }
+            .into(),
+            shard_number: NonZeroU32::new(2).unwrap(),
+            ..CollectionParams::empty()
+        },
+        optimizer_config: TEST_OPTIMIZERS_CONFIG.clone(),
+        wal_config: WalConfig {
+            wal_capacity_mb: 1,
+            wal_segments_ahead: 0,
+            wal_compression: Default::default(),
+        },
+        hnsw_config: Default::default(),
+        quantization_config: Default::default(),
+        strict_mode_config: Default::default(),
+        feature_flags: None,
+    };
+
+    // Shard 0 is local, shard 1 is only stored on a peer, which can't be reached
+    let shard_distribution = CollectionShardDistribution {
+        shards: HashMap::from([
+            (0, HashSet::from([THIS_PEER_ID])),
+            (1, HashSet::from([UNREACHABLE_PEER_ID])),
+        ]),
+    };
+
+    let collection = Collection::new(
+        "test".to_string(),
+        THIS_PEER_ID,
+        collection_dir.path(),
+        snapshots_dir.path(),
+        &config,
+        Default::default(),
+        shard_distribution,
+        ChannelService::new(REST_PORT),
+        dummy_on_replica_failure(),
+        dummy_request_shard_transfer(),
+        dummy_abort_shard_transfer(),
+        None,
+        None,
+    )
+    .await
+    .unwrap();
+    collection
+        .set_shard_replica_state(0, THIS_PEER_ID, ReplicaState::Active, None)
+        .await
+        .unwrap();
+
+    let points = (0..10)
+        .map(|i| PointStruct {
+            id: i.into(),
+            vector: vec![i as f32, 0.0, 0.0, 0.0].into(),
+            payload: None,
+            if_version: None,
+        })
+        .collect();
+    let insert_points = CollectionUpdateOperations::PointOperation(PointOperations::UpsertPoints(
+        PointInsertOperationsInternal::PointsList(points),
+    ));
+    collection
+        .update_from_peer(
+            insert_points,
+            0,
+            true,
+            WriteOrdering::Weak,
+            None,
+            None,
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+
+    // Failure of a single shard fails the whole request by default
+    let result = collection
+        .core_search_batch(
+            search_request(),
+            None,
+            None,
+            ShardSelectorInternal::All,
+            None,
+            None,
+            ReadPriority::Normal,
+            None,
+        )
+        .await;
+    assert!(result.is_err());
+
+    // Points of the healthy shard are returned, failed shard is reported
+    let result = collection
+        .core_search_batch_partial(
+            search_request(),
+            None,
+            None,
+            ShardSelectorInternal::All,
+            None,
+            None,
+            ReadPriority::Normal,
+            None,
+        )
+        .await
+        .unwrap();
+    assert_eq!(result.results.len(), 1);
+    assert_eq!(result.results[0].len(), 10);
+    assert_eq!(result.results[0][0].id, 9.into());
+    assert_eq!(result.failed_shards.len(), 1);
+    assert_eq!(result.failed_shards[0].shard_id, 1);
+    assert_eq!(result.failed_shards[0].shard_key, None);
+    assert!(!result.failed_shards[0].error.is_empty());
+
+    // Nothing is reported, if selected shards are healthy
+    let result = collection
+        .core_search_batch_partial(
+            search_request(),
+            None,
+            None,
+            ShardSelectorInternal::ShardId(0),
+            None,
+            None,
+            ReadPriority::Normal,
+            None,
+        )
+        .await
+        .unwrap();
+    assert_eq!(result.results[0].len(), 10);
+    assert!(result.failed_shards.is_empty());
+
+    // Request fails, if none of the selected shards responded
+    let result = collection
+        .core_search_batch_partial(
+            search_request(),
+            None,
+            None,
+            ShardSelectorInternal::ShardId(1),
+            None,
+            None,
+            ReadPriority::Normal,
+            None,
+        )
+        .await;
+    assert!(result.is_err());
+}
@@ -0,0 +1,117 @@
+use std::num::{NonZeroU32, NonZeroU64};
+use std::sync::Arc;
+
+use collection::config::{CollectionConfig, CollectionParams, ShardingMethod, WalConfig};
+use collection::operations::point_ops::{
+    PointInsertOperationsInternal, PointOperations, PointStruct, WriteOrdering,
+};
+use collection::operations::shard_selector_internal::ShardSelectorInternal;
+use collection::operations::types::{CountRequestInternal, VectorParams};
+use collection::operations::CollectionUpdateOperations;
+use collection::shards::replica_set::read_priority::ReadPriority;
+use segment::types::Distance;
+use serde_json::json;
+use tempfile::Builder;
+
+use crate::common::{new_local_collection, N_SHARDS, TEST_OPTIMIZERS_CONFIG};
+
+const TENANTS: usize = 16;
+const POINTS: u64 = 10;
+
+fn tenant_points(tenant: usize) -> CollectionUpdateOperations {
+    let points = (0..POINTS)
+        .map(|i| PointStruct {
+            id: i.into(),
+            vector: vec![i as f32, 0.0, 0.0, 0.0].into(),
+            payload: Some(json!({ "tenant": format!("tenant-{tenant}") }).into()),
+            if_version: None,
+        })
+        .collect();
+    CollectionUpdateOperations::PointOperation(PointOperations::UpsertPoints(
+        PointInsertOperationsInternal::PointsList(points),
+    ))
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_concurrent_upserts_by_payload_key() {
+    let collection_dir = Builder::new()
+        .prefix("test_payload_sharding")
+        .tempdir()
+        .unwrap();
+
+    let config = CollectionConfig {
+        params: CollectionParams {
+            vectors: VectorParams {
+                size: NonZeroU64::new(4).unwrap(),
+                distance: Distance::Dot,
+                hnsw_config: None,
+                quantization_config: None,
+                on_disk: None,
+                normalize: None,
+                dimensionality_reduction: None,
+                multivector_config: None,
+                datatype: None,
+            }
+            .into(),
+            shard_number: NonZeroU32::new(N_SHARDS).unwrap(),
+            sharding_method: Some(ShardingMethod::PayloadKey),
+            sharding_payload_key: Some("tenant".to_string()),
+            ..CollectionParams::empty()
+        },
+        optimizer_config: TEST_OPTIMIZERS_CONFIG.clone(),
+        wal_config: WalConfig {
+            wal_capacity_mb: 1,
+            wal_segments_ahead: 0,
+            wal_compression: Default::default(),
+        },
+        hnsw_config: Default::default(),
+        quantization_config: Default::default(),
+        strict_mode_config: Default::default(),
+        feature_flags: None,
+    };
+    let collection = Arc::new(
+        new_local_collection(
+            "test".to_string(),
+            collection_dir.path(),
+            &collection_dir.path().join("snapshots"),
+            &config,
+        )
+        .await
+        .unwrap(),
+    );
+
+    // Tenants upsert the same points at the same time, their values are hashed into different
+    // shards, so only the first upsert may place the points
+    let upserts = (0..TENANTS).map(|tenant| {
+        let collection = collection.clone();
+        tokio::spawn(async move {
+            collection
+                .update_from_client_simple(tenant_points(tenant), false, WriteOrdering::default())
+                .await
+        })
+    });
+    let results = futures::future::join_all(upserts).await;
+    let succeeded = results
+        .into_iter()
+        .map(|result| result.unwrap())
+        .filter(Result::is_ok)
+        .count();
+    assert!(succeeded >= 1);
+
+    // Every point is stored in a single shard
+    let count = collection
+        .count(
+            CountRequestInternal {
+                filter: None,
+                exact: true,
+            },
+            None,
+            &ShardSelectorInternal::All,
+            None,
+            ReadPriority::Normal,
+            None,
+        )
+        .await
+        .unwrap();
+    assert_eq!(count.count, POINTS as usize);
+}
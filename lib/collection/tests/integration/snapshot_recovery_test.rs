@@ -8,10 +8,12 @@ use collection::operations::point_ops::{
 };
 use collection::operations::shard_selector_internal::ShardSelectorInternal;
 use collection::operations::shared_storage_config::SharedStorageConfig;
+use collection::operations::snapshot_ops::SnapshotCreationPriority;
 use collection::operations::types::{NodeType, SearchRequestInternal, VectorParams, VectorsConfig};
 use collection::operations::CollectionUpdateOperations;
 use collection::shards::channel_service::ChannelService;
 use collection::shards::collection_shard_distribution::CollectionShardDistribution;
+use collection::shards::replica_set::read_priority::ReadPriority;
 use collection::shards::replica_set::ReplicaState;
 use segment::types::{Distance, WithPayloadInterface, WithVector};
 use tempfile::Builder;
@@ -25,6 +27,7 @@ async fn _test_snapshot_and_recover_collection(node_type: NodeType) {
     let wal_config = WalConfig {
         wal_capacity_mb: 1,
         wal_segments_ahead: 0,
+        wal_compression: Default::default(),
     };
 
     let collection_params = CollectionParams {
@@ -34,6 +37,10 @@ async fn _test_snapshot_and_recover_collection(node_type: NodeType) {
             hnsw_config: None,
             quantization_config: None,
             on_disk: None,
+            normalize: None,
+            dimensionality_reduction: None,
+            multivector_config: None,
+            datatype: None,
         }),
         ..CollectionParams::empty()
     };
@@ -44,6 +51,8 @@ async fn _test_snapshot_and_recover_collection(node_type: NodeType) {
         wal_config,
         hnsw_config: Default::default(),
         quantization_config: Default::default(),
+        strict_mode_config: Default::default(),
+        feature_flags: None,
     };
 
     let snapshots_path = Builder::new().prefix("test_snapshots").tempdir().unwrap();
@@ -56,7 +65,7 @@ async fn _test_snapshot_and_recover_collection(node_type: NodeType) {
     let collection_name_rec = "test_rec".to_string();
 
     let storage_config: SharedStorageConfig = SharedStorageConfig {
-        node_type,
+        node_type: node_type.into(),
         ..Default::default()
     };
 
@@ -99,6 +108,7 @@ async fn _test_snapshot_and_recover_collection(node_type: NodeType) {
             id: i.into(),
             vector: vec![i as f32, 0.0, 0.0, 0.0].into(),
             payload: Some(serde_json::from_str(r#"{"number": "John Doe"}"#).unwrap()),
+            if_version: None,
         });
     }
     let insert_points = CollectionUpdateOperations::PointOperation(PointOperations::UpsertPoints(
@@ -112,7 +122,11 @@ async fn _test_snapshot_and_recover_collection(node_type: NodeType) {
     // Take a snapshot
     let snapshots_temp_dir = Builder::new().prefix("temp_dir").tempdir().unwrap();
     let snapshot_description = collection
-        .create_snapshot(snapshots_temp_dir.path(), 0)
+        .create_snapshot(
+            snapshots_temp_dir.path(),
+            0,
+            SnapshotCreationPriority::Normal,
+        )
         .await
         .unwrap();
 
@@ -138,7 +152,8 @@ async fn _test_snapshot_and_recover_collection(node_type: NodeType) {
         None,
         None,
     )
-    .await;
+    .await
+    .unwrap();
 
     let query_vector = vec![1.0, 0.0, 0.0, 0.0];
 
@@ -159,6 +174,9 @@ async fn _test_snapshot_and_recover_collection(node_type: NodeType) {
             None,
             &ShardSelectorInternal::All,
             None,
+            None,
+            ReadPriority::Normal,
+            None,
         )
         .await
         .unwrap();
@@ -169,6 +187,9 @@ async fn _test_snapshot_and_recover_collection(node_type: NodeType) {
             None,
             &ShardSelectorInternal::All,
             None,
+            None,
+            ReadPriority::Normal,
+            None,
         )
         .await
         .unwrap();
@@ -65,6 +65,11 @@ impl PrefaultMmapPages {
     pub fn exec(&self) {
         prefault_mmap_pages(self.mmap.as_ref(), self.path.as_deref());
     }
+
+    /// Size of the mmap in bytes
+    pub fn size(&self) -> usize {
+        self.mmap.len()
+    }
 }
 
 fn prefault_mmap_pages<T>(mmap: &T, path: Option<&Path>)
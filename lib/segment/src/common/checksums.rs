@@ -0,0 +1,170 @@
+//! Checksums of immutable storage files, which detect silent corruption of the data on disk
+//!
+//! The file is split into blocks of [`CHECKSUM_BLOCK_SIZE`], checksums of the blocks are stored
+//! next to the file. Verification reports the blocks, which don't match their checksums,
+//! so the extent of the damage is known.
+
+use std::fs::File;
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+
+use io::file_operations::{atomic_save_json, read_json};
+use serde::{Deserialize, Serialize};
+
+use crate::common::operation_error::{OperationError, OperationResult};
+
+pub const CHECKSUM_BLOCK_SIZE: usize = 1024 * 1024; // 1Mb
+
+const CHECKSUMS_SUFFIX: &str = ".checksums";
+
+#[derive(Debug, Deserialize, Serialize)]
+struct FileChecksums {
+    block_size: usize,
+    blocks: Vec<u64>,
+}
+
+/// Outcome of a checksum verification
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Verification {
+    /// Blocks of a file or values of a storage, which don't match their checksums
+    pub corrupted: Vec<usize>,
+    /// Number of bytes read to verify the checksums
+    pub bytes_read: usize,
+}
+
+impl Verification {
+    pub fn is_valid(&self) -> bool {
+        self.corrupted.is_empty()
+    }
+}
+
+/// Checksum of a single block or value
+pub fn checksum(data: &[u8]) -> u64 {
+    seahash::hash(data)
+}
+
+/// Path of the file with checksums of the given file
+pub fn checksums_path(path: &Path) -> PathBuf {
+    let mut file_name = path.file_name().unwrap_or_default().to_owned();
+    file_name.push(CHECKSUMS_SUFFIX);
+    path.with_file_name(file_name)
+}
+
+pub fn has_checksums(path: &Path) -> bool {
+    checksums_path(path).exists()
+}
+
+/// Calculate checksums of the blocks of the file and store them next to it
+///
+/// Must only be called for files, which are not changed anymore.
+pub fn write_checksums(path: &Path) -> OperationResult<()> {
+    let mut blocks = Vec::new();
+    read_blocks(path, &mut |_| {}, |block| blocks.push(checksum(block)))?;
+    let checksums = FileChecksums {
+        block_size: CHECKSUM_BLOCK_SIZE,
+        blocks,
+    };
+    atomic_save_json(&checksums_path(path), &checksums)?;
+    Ok(())
+}
+
+/// Compare blocks of the file with their stored checksums
+///
+/// `throttle` is called with the size of each block before it is read.
+/// Missing or extra blocks, e.g. if the file is truncated, are reported as corrupted.
+pub fn verify_checksums(
+    path: &Path,
+    throttle: &mut dyn FnMut(usize),
+) -> OperationResult<Verification> {
+    let checksums: FileChecksums = read_json(&checksums_path(path))?;
+    if checksums.block_size != CHECKSUM_BLOCK_SIZE {
+        return Err(OperationError::service_error(format!(
+            "Unsupported checksum block size {} of {}",
+            checksums.block_size,
+            path.display(),
+        )));
+    }
+
+    let mut verification = Verification::default();
+    let mut block_id = 0;
+    read_blocks(path, throttle, |block| {
+        if checksums.blocks.get(block_id) != Some(&checksum(block)) {
+            verification.corrupted.push(block_id);
+        }
+        verification.bytes_read += block.len();
+        block_id += 1;
+    })?;
+    verification
+        .corrupted
+        .extend(block_id..checksums.blocks.len());
+    Ok(verification)
+}
+
+fn read_blocks(
+    path: &Path,
+    throttle: &mut dyn FnMut(usize),
+    mut callback: impl FnMut(&[u8]),
+) -> io::Result<()> {
+    let mut file = File::open(path)?;
+    let mut buffer = vec![0; CHECKSUM_BLOCK_SIZE];
+    loop {
+        throttle(CHECKSUM_BLOCK_SIZE);
+        let mut len = 0;
+        while len < buffer.len() {
+            match file.read(&mut buffer[len..])? {
+                0 => break,
+                read => len += read,
+            }
+        }
+        if len == 0 {
+            return Ok(());
+        }
+        callback(&buffer[..len]);
+        if len < buffer.len() {
+            return Ok(());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::OpenOptions;
+    use std::io::{Seek, SeekFrom, Write};
+
+    use tempfile::Builder;
+
+    use super::*;
+
+    #[test]
+    fn test_verify_checksums() {
+        let dir = Builder::new().prefix("checksums").tempdir().unwrap();
+        let path = dir.path().join("data.dat");
+        let data: Vec<u8> = (0..CHECKSUM_BLOCK_SIZE * 5 / 2).map(|i| i as u8).collect();
+        std::fs::write(&path, &data).unwrap();
+
+        assert!(!has_checksums(&path));
+        write_checksums(&path).unwrap();
+        assert!(has_checksums(&path));
+
+        let mut throttled = 0;
+        let verification = verify_checksums(&path, &mut |bytes| throttled += bytes).unwrap();
+        assert!(verification.is_valid());
+        assert_eq!(verification.bytes_read, data.len());
+        assert!(throttled >= data.len());
+
+        // Flip a bit in the second block
+        let mut file = OpenOptions::new().write(true).open(&path).unwrap();
+        file.seek(SeekFrom::Start(CHECKSUM_BLOCK_SIZE as u64 + 10))
+            .unwrap();
+        file.write_all(&[data[CHECKSUM_BLOCK_SIZE + 10] ^ 1])
+            .unwrap();
+        drop(file);
+        let verification = verify_checksums(&path, &mut |_| {}).unwrap();
+        assert_eq!(verification.corrupted, vec![1]);
+
+        // Truncated blocks are corrupted as well
+        std::fs::write(&path, &data[..CHECKSUM_BLOCK_SIZE]).unwrap();
+        let verification = verify_checksums(&path, &mut |_| {}).unwrap();
+        assert_eq!(verification.corrupted, vec![1, 2]);
+    }
+}
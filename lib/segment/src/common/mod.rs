@@ -1,5 +1,6 @@
 pub mod anonymize;
 pub mod arc_atomic_ref_cell_iterator;
+pub mod checksums;
 pub mod cpu;
 pub mod error_logging;
 pub mod mmap_type;
@@ -15,7 +16,8 @@ use std::sync::atomic::AtomicBool;
 
 use crate::common::operation_error::{OperationError, OperationResult};
 use crate::data_types::named_vectors::NamedVectors;
-use crate::data_types::vectors::{QueryVector, VectorRef};
+use crate::data_types::primitive::FLOAT16_MAX;
+use crate::data_types::vectors::{QueryVector, VectorElementType, VectorRef};
 use crate::types::{SegmentConfig, SparseVectorDataConfig, VectorDataConfig};
 
 pub type Flusher = Box<dyn FnOnce() -> OperationResult<()> + Send>;
@@ -173,19 +175,49 @@ fn check_vector_against_config(
     vector: VectorRef,
     vector_config: &VectorDataConfig,
 ) -> OperationResult<()> {
-    match vector {
-        VectorRef::Dense(vector) => {
-            // Check dimensionality
-            let dim = vector_config.size;
-            if vector.len() != dim {
-                return Err(OperationError::WrongVector {
-                    expected_dim: dim,
-                    received_dim: vector.len(),
+    let check_dim = |vector: &[VectorElementType]| {
+        // Check dimensionality
+        let dim = vector_config.size;
+        if vector.len() != dim {
+            return Err(OperationError::WrongVector {
+                expected_dim: dim,
+                received_dim: vector.len(),
+            });
+        }
+        // Values of the uint8 datatype are rounded to the nearest byte
+        if vector_config.is_byte_datatype()
+            && vector.iter().any(|value| !(-0.5..255.5).contains(value))
+        {
+            return Err(OperationError::ValidationError {
+                description: "vector of the uint8 datatype must have values in range [0, 255]"
+                    .to_string(),
+            });
+        }
+        // Values of the float16 datatype must fit into its range
+        if vector_config.is_half_datatype() && vector.iter().any(|value| value.abs() > FLOAT16_MAX)
+        {
+            return Err(OperationError::ValidationError {
+                description: format!(
+                    "vector of the float16 datatype must have values in range [-{FLOAT16_MAX}, {FLOAT16_MAX}]"
+                ),
+            });
+        }
+        Ok(())
+    };
+    match (vector, &vector_config.multivector_config) {
+        (VectorRef::Dense(vector), None) => check_dim(vector),
+        (VectorRef::MultiDense(vectors), Some(_)) => {
+            if vectors.is_empty() {
+                return Err(OperationError::ValidationError {
+                    description: "multi-vector must not be empty".to_string(),
                 });
             }
-            Ok(())
+            vectors.iter().try_for_each(|vector| check_dim(vector))
+        }
+        (VectorRef::Dense(_), Some(_)) | (VectorRef::MultiDense(_), None) => {
+            Err(OperationError::WrongMulti)
         }
-        VectorRef::Sparse(_) => Err(OperationError::WrongSparse),
+        (VectorRef::Sparse(_), _) => Err(OperationError::WrongSparse),
     }
 }
 
@@ -194,7 +226,7 @@ fn check_sparse_vector_against_config(
     _vector_config: &SparseVectorDataConfig,
 ) -> OperationResult<()> {
     match vector {
-        VectorRef::Dense(_) => Err(OperationError::WrongSparse),
+        VectorRef::Dense(_) | VectorRef::MultiDense(_) => Err(OperationError::WrongSparse),
         VectorRef::Sparse(_vector) => Ok(()), // TODO(sparse) check vector by config
     }
 }
@@ -3,7 +3,7 @@ use std::sync::Arc;
 
 use parking_lot::RwLock;
 //use atomic_refcell::{AtomicRef, AtomicRefCell};
-use rocksdb::{ColumnFamily, DBRecoveryMode, LogLevel, Options, WriteOptions, DB};
+use rocksdb::{ColumnFamily, DBRecoveryMode, LogLevel, Options, ReadOptions, WriteOptions, DB};
 
 //use crate::common::arc_rwlock_iterator::ArcRwLockIterator;
 use crate::common::operation_error::{OperationError, OperationResult};
@@ -16,6 +16,7 @@ const DB_DELETE_OBSOLETE_FILES_PERIOD: u64 = 3 * 60 * 1_000_000; // 3 minutes in
 
 pub const DB_VECTOR_CF: &str = "vector";
 pub const DB_PAYLOAD_CF: &str = "payload";
+pub const DB_PAYLOAD_DELTA_CF: &str = "payload_delta";
 pub const DB_MAPPING_CF: &str = "mapping";
 pub const DB_VERSIONS_CF: &str = "version";
 
@@ -60,7 +61,12 @@ pub fn open_db<T: AsRef<str>>(
     path: &Path,
     vector_paths: &[T],
 ) -> Result<Arc<RwLock<DB>>, rocksdb::Error> {
-    let mut column_families = vec![DB_PAYLOAD_CF, DB_MAPPING_CF, DB_VERSIONS_CF];
+    let mut column_families = vec![
+        DB_PAYLOAD_CF,
+        DB_PAYLOAD_DELTA_CF,
+        DB_MAPPING_CF,
+        DB_VERSIONS_CF,
+    ];
     for vector_path in vector_paths {
         column_families.push(vector_path.as_ref());
     }
@@ -151,6 +157,32 @@ impl DatabaseColumnWrapper {
         Ok(result)
     }
 
+    /// Read multiple keys in a single batch, values are returned in the order of `keys`.
+    ///
+    /// Keys are looked up with a single `MultiGet` call, which amortizes the lookup overhead
+    /// over the batch. With `rocksdb-io-uring` feature, RocksDB reads blocks of the batch
+    /// in parallel with io_uring, otherwise reads are synchronous.
+    pub fn multi_get_pinned<K, T, F>(&self, keys: &[K], mut f: F) -> OperationResult<Vec<Option<T>>>
+    where
+        K: AsRef<[u8]>,
+        F: FnMut(&[u8]) -> T,
+    {
+        let db = self.database.read();
+        let cf_handle = self.get_column_family(&db)?;
+        db.batched_multi_get_cf_opt(cf_handle, keys, false, &Self::get_multi_read_options())
+            .into_iter()
+            .map(|value| {
+                value
+                    .map(|value| value.map(|value| f(&value)))
+                    .map_err(|err| {
+                        OperationError::service_error(format!(
+                            "RocksDB batched_multi_get_cf error: {err}"
+                        ))
+                    })
+            })
+            .collect()
+    }
+
     pub fn remove<K>(&self, key: K) -> OperationResult<()>
     where
         K: AsRef<[u8]>,
@@ -232,6 +264,14 @@ impl DatabaseColumnWrapper {
         write_options
     }
 
+    fn get_multi_read_options() -> ReadOptions {
+        #[allow(unused_mut)]
+        let mut read_options = ReadOptions::default();
+        #[cfg(feature = "rocksdb-io-uring")]
+        read_options.set_async_io(true);
+        read_options
+    }
+
     fn get_column_family<'a>(
         &self,
         db: &'a parking_lot::RwLockReadGuard<'_, DB>,
@@ -284,3 +324,51 @@ impl<'a> Iterator for DatabaseColumnIterator<'a> {
         Some(item)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use tempfile::Builder;
+
+    use super::*;
+
+    fn decode(raw: &[u8]) -> u32 {
+        u32::from_be_bytes(raw.try_into().unwrap())
+    }
+
+    #[test]
+    fn test_multi_get_pinned() {
+        let dir = Builder::new().prefix("db_dir").tempdir().unwrap();
+        let db = open_db(dir.path(), &[DB_VECTOR_CF]).unwrap();
+        let wrapper = DatabaseColumnWrapper::new(db, DB_PAYLOAD_CF);
+        for key in 0u32..1000 {
+            wrapper
+                .put(key.to_be_bytes(), (key * 10).to_be_bytes())
+                .unwrap();
+        }
+
+        // Values are returned in the order of unsorted keys, including missing and repeated ones
+        let keys = [42u32, 7, 5000, 999, 0, 7, 1000, 13];
+        let raw_keys: Vec<_> = keys.iter().map(|key| key.to_be_bytes()).collect();
+        let values = wrapper.multi_get_pinned(&raw_keys, decode).unwrap();
+        let expected: Vec<_> = keys
+            .iter()
+            .map(|&key| (key < 1000).then_some(key * 10))
+            .collect();
+        assert_eq!(values, expected);
+
+        // Whole column in a single batch, in reverse order
+        let raw_keys: Vec<_> = (0u32..1000).rev().map(|key| key.to_be_bytes()).collect();
+        let values = wrapper.multi_get_pinned(&raw_keys, decode).unwrap();
+        assert_eq!(values.len(), 1000);
+        assert!(values
+            .iter()
+            .zip((0u32..1000).rev())
+            .all(|(value, key)| *value == Some(key * 10)));
+
+        let no_keys: [[u8; 4]; 0] = [];
+        assert!(wrapper
+            .multi_get_pinned(&no_keys, decode)
+            .unwrap()
+            .is_empty());
+    }
+}
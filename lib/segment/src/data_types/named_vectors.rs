@@ -4,7 +4,7 @@ use std::collections::HashMap;
 use sparse::common::sparse_vector::SparseVector;
 
 use super::tiny_map;
-use super::vectors::{DenseVector, Vector, VectorElementType, VectorRef};
+use super::vectors::{DenseVector, MultiDenseVector, Vector, VectorElementType, VectorRef};
 use crate::common::operation_error::OperationError;
 use crate::types::Distance;
 
@@ -14,6 +14,7 @@ type CowKey<'a> = Cow<'a, str>;
 pub enum CowVector<'a> {
     Dense(Cow<'a, [VectorElementType]>),
     Sparse(Cow<'a, SparseVector>),
+    MultiDense(Cow<'a, [DenseVector]>),
 }
 
 impl<'a> Default for CowVector<'a> {
@@ -34,6 +35,7 @@ impl<'a> CowVector<'a> {
         match self {
             CowVector::Dense(v) => Vector::Dense(v.into_owned()),
             CowVector::Sparse(v) => Vector::Sparse(v.into_owned()),
+            CowVector::MultiDense(v) => Vector::MultiDense(v.into_owned()),
         }
     }
 
@@ -41,6 +43,7 @@ impl<'a> CowVector<'a> {
         match self {
             CowVector::Dense(v) => VectorRef::Dense(v.as_ref()),
             CowVector::Sparse(v) => VectorRef::Sparse(v.as_ref()),
+            CowVector::MultiDense(v) => VectorRef::MultiDense(v.as_ref()),
         }
     }
 }
@@ -50,6 +53,7 @@ impl<'a> From<Vector> for CowVector<'a> {
         match v {
             Vector::Dense(v) => CowVector::Dense(Cow::Owned(v)),
             Vector::Sparse(v) => CowVector::Sparse(Cow::Owned(v)),
+            Vector::MultiDense(v) => CowVector::MultiDense(Cow::Owned(v)),
         }
     }
 }
@@ -72,6 +76,18 @@ impl<'a> From<&'a SparseVector> for CowVector<'a> {
     }
 }
 
+impl<'a> From<MultiDenseVector> for CowVector<'a> {
+    fn from(v: MultiDenseVector) -> Self {
+        CowVector::MultiDense(Cow::Owned(v))
+    }
+}
+
+impl<'a> From<&'a [DenseVector]> for CowVector<'a> {
+    fn from(v: &'a [DenseVector]) -> Self {
+        CowVector::MultiDense(Cow::Borrowed(v))
+    }
+}
+
 impl<'a> From<&'a [VectorElementType]> for CowVector<'a> {
     fn from(v: &'a [VectorElementType]) -> Self {
         CowVector::Dense(Cow::Owned(v.into()))
@@ -85,6 +101,7 @@ impl<'a> TryFrom<CowVector<'a>> for SparseVector {
         match value {
             CowVector::Dense(_) => Err(OperationError::WrongSparse),
             CowVector::Sparse(v) => Ok(v.into_owned()),
+            CowVector::MultiDense(_) => Err(OperationError::WrongSparse),
         }
     }
 }
@@ -96,6 +113,7 @@ impl<'a> TryFrom<CowVector<'a>> for DenseVector {
         match value {
             CowVector::Dense(v) => Ok(v.into_owned()),
             CowVector::Sparse(_) => Err(OperationError::WrongSparse),
+            CowVector::MultiDense(_) => Err(OperationError::WrongMulti),
         }
     }
 }
@@ -105,6 +123,7 @@ impl<'a> From<VectorRef<'a>> for CowVector<'a> {
         match v {
             VectorRef::Dense(v) => CowVector::Dense(Cow::Borrowed(v)),
             VectorRef::Sparse(v) => CowVector::Sparse(Cow::Borrowed(v)),
+            VectorRef::MultiDense(v) => CowVector::MultiDense(Cow::Borrowed(v)),
         }
     }
 }
@@ -112,13 +131,7 @@ impl<'a> From<VectorRef<'a>> for CowVector<'a> {
 impl<'a> NamedVectors<'a> {
     pub fn from_ref(key: &'a str, value: VectorRef<'a>) -> Self {
         let mut map = TinyMap::new();
-        map.insert(
-            Cow::Borrowed(key),
-            match value {
-                VectorRef::Dense(v) => CowVector::Dense(Cow::Borrowed(v)),
-                VectorRef::Sparse(v) => CowVector::Sparse(Cow::Borrowed(v)),
-            },
-        );
+        map.insert(Cow::Borrowed(key), CowVector::from(value));
         Self { map }
     }
 
@@ -150,23 +163,13 @@ impl<'a> NamedVectors<'a> {
     }
 
     pub fn insert(&mut self, name: String, vector: Vector) {
-        self.map.insert(
-            CowKey::Owned(name),
-            match vector {
-                Vector::Dense(v) => CowVector::Dense(Cow::Owned(v)),
-                Vector::Sparse(v) => CowVector::Sparse(Cow::Owned(v)),
-            },
-        );
+        self.map
+            .insert(CowKey::Owned(name), CowVector::from(vector));
     }
 
     pub fn insert_ref(&mut self, name: &'a str, vector: VectorRef<'a>) {
-        self.map.insert(
-            CowKey::Borrowed(name),
-            match vector {
-                VectorRef::Dense(v) => CowVector::Dense(Cow::Borrowed(v)),
-                VectorRef::Sparse(v) => CowVector::Sparse(Cow::Borrowed(v)),
-            },
-        );
+        self.map
+            .insert(CowKey::Borrowed(name), CowVector::from(vector));
     }
 
     pub fn contains_key(&self, key: &str) -> bool {
@@ -200,21 +203,32 @@ impl<'a> NamedVectors<'a> {
         self.map.get(key).map(|v| v.as_vec_ref())
     }
 
+    /// Preprocess the vectors according to the distance of each vector name
+    ///
+    /// Dense vectors, for which `distance_map` returns `None`, are stored as is.
     pub fn preprocess<F>(&mut self, distance_map: F)
     where
-        F: Fn(&str) -> Distance,
+        F: Fn(&str) -> Option<Distance>,
     {
         for (name, vector) in self.map.iter_mut() {
             let distance = distance_map(name);
-            match vector {
-                CowVector::Dense(v) => {
+            match (&mut *vector, distance) {
+                (CowVector::Dense(v), Some(distance)) => {
                     let preprocessed_vector = distance.preprocess_vector(v.to_vec());
                     *vector = CowVector::Dense(Cow::Owned(preprocessed_vector))
                 }
-                CowVector::Sparse(v) => {
+                (CowVector::Sparse(v), _) => {
                     // sort by indices to enable faster dot product and overlap checks
                     v.to_mut().sort_by_indices();
                 }
+                (CowVector::MultiDense(v), Some(distance)) => {
+                    let preprocessed_vector = v
+                        .iter()
+                        .map(|v| distance.preprocess_vector(v.clone()))
+                        .collect();
+                    *vector = CowVector::MultiDense(Cow::Owned(preprocessed_vector))
+                }
+                (CowVector::Dense(_) | CowVector::MultiDense(_), None) => {}
             }
         }
     }
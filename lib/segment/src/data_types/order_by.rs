@@ -0,0 +1,56 @@
+use std::cmp::Ordering;
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::types::{FloatPayloadType, Range};
+
+/// Direction of the ordering
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum Direction {
+    #[default]
+    Asc,
+    Desc,
+}
+
+/// Order points by the values of the payload field, instead of point ids
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub struct OrderBy {
+    /// Payload key to order by, must have a numeric (integer or float) index
+    pub key: String,
+    /// Direction of ordering: `asc` or `desc`. Default is ascending.
+    pub direction: Option<Direction>,
+    /// Value of the payload key to start from, inclusive.
+    /// Use the value of the last point of the previous page to continue scrolling.
+    pub start_from: Option<FloatPayloadType>,
+}
+
+impl OrderBy {
+    pub fn direction(&self) -> Direction {
+        self.direction.unwrap_or_default()
+    }
+
+    /// Range of the values to read, starting from `start_from` in the direction of ordering
+    pub fn value_range(&self) -> Range {
+        match self.direction() {
+            Direction::Asc => Range {
+                gte: self.start_from,
+                ..Default::default()
+            },
+            Direction::Desc => Range {
+                lte: self.start_from,
+                ..Default::default()
+            },
+        }
+    }
+
+    /// Compare values in the direction of ordering
+    pub fn compare(&self, a: FloatPayloadType, b: FloatPayloadType) -> Ordering {
+        match self.direction() {
+            Direction::Asc => a.total_cmp(&b),
+            Direction::Desc => b.total_cmp(&a),
+        }
+    }
+}
@@ -0,0 +1,56 @@
+use std::borrow::Cow;
+
+use half::f16;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use crate::data_types::vectors::{dense_vector_to_bytes, VectorElementType, VectorElementTypeByte};
+
+/// Largest finite value of the `float16` datatype, [`f16::MAX`]
+pub const FLOAT16_MAX: VectorElementType = 65504.0;
+
+/// Type of the elements of the stored dense vectors
+///
+/// Vectors are converted from and to [`VectorElementType`] when they are stored and read.
+pub trait PrimitiveVectorElement:
+    Copy + Clone + Default + Serialize + DeserializeOwned + Send + Sync + 'static
+{
+    fn slice_from_float_cow(vector: Cow<[VectorElementType]>) -> Cow<[Self]>;
+
+    fn slice_to_float_cow(vector: Cow<[Self]>) -> Cow<[VectorElementType]>;
+}
+
+impl PrimitiveVectorElement for VectorElementType {
+    fn slice_from_float_cow(vector: Cow<[VectorElementType]>) -> Cow<[Self]> {
+        vector
+    }
+
+    fn slice_to_float_cow(vector: Cow<[Self]>) -> Cow<[VectorElementType]> {
+        vector
+    }
+}
+
+impl PrimitiveVectorElement for VectorElementTypeByte {
+    fn slice_from_float_cow(vector: Cow<[VectorElementType]>) -> Cow<[Self]> {
+        Cow::Owned(dense_vector_to_bytes(&vector))
+    }
+
+    fn slice_to_float_cow(vector: Cow<[Self]>) -> Cow<[VectorElementType]> {
+        Cow::Owned(
+            vector
+                .iter()
+                .map(|&value| VectorElementType::from(value))
+                .collect(),
+        )
+    }
+}
+
+impl PrimitiveVectorElement for f16 {
+    fn slice_from_float_cow(vector: Cow<[VectorElementType]>) -> Cow<[Self]> {
+        Cow::Owned(vector.iter().map(|&value| f16::from_f32(value)).collect())
+    }
+
+    fn slice_to_float_cow(vector: Cow<[Self]>) -> Cow<[VectorElementType]> {
+        Cow::Owned(vector.iter().map(|value| value.to_f32()).collect())
+    }
+}
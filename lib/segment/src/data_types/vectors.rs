@@ -16,6 +16,8 @@ use crate::vector_storage::query::reco_query::RecoQuery;
 #[serde(untagged, rename_all = "snake_case")]
 pub enum Vector {
     Dense(DenseVector),
+    // Goes before the sparse vector, a sparse vector could be deserialized from a list of two lists
+    MultiDense(MultiDenseVector),
     Sparse(SparseVector),
 }
 
@@ -23,6 +25,7 @@ pub enum Vector {
 pub enum VectorRef<'a> {
     Dense(&'a [VectorElementType]),
     Sparse(&'a SparseVector),
+    MultiDense(&'a [DenseVector]),
 }
 
 impl Vector {
@@ -30,6 +33,7 @@ impl Vector {
         match self {
             Vector::Dense(v) => VectorRef::Dense(v.as_slice()),
             Vector::Sparse(v) => VectorRef::Sparse(v),
+            Vector::MultiDense(v) => VectorRef::MultiDense(v.as_slice()),
         }
     }
 }
@@ -39,15 +43,46 @@ impl Validate for Vector {
         match self {
             Vector::Dense(_) => Ok(()),
             Vector::Sparse(v) => v.validate(),
+            Vector::MultiDense(v) => validate_multi_dense_vector(v),
         }
     }
 }
 
+/// Multi-vector must have at least one vector, all of the same dimensionality
+pub fn validate_multi_dense_vector(
+    multi_vector: &[DenseVector],
+) -> Result<(), validator::ValidationErrors> {
+    let mut errors = validator::ValidationErrors::default();
+    match multi_vector.first() {
+        None => errors.add(
+            "vector",
+            validator::ValidationError::new("must not be empty"),
+        ),
+        Some(first)
+            if multi_vector
+                .iter()
+                .any(|vector| vector.len() != first.len()) =>
+        {
+            errors.add(
+                "vector",
+                validator::ValidationError::new("all vectors must have the same size"),
+            )
+        }
+        Some(_) => {}
+    }
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
 impl<'a> VectorRef<'a> {
     pub fn to_vec(self) -> Vector {
         match self {
             VectorRef::Dense(v) => Vector::Dense(v.to_vec()),
             VectorRef::Sparse(v) => Vector::Sparse(v.clone()),
+            VectorRef::MultiDense(v) => Vector::MultiDense(v.to_vec()),
         }
     }
 }
@@ -59,6 +94,7 @@ impl<'a> TryFrom<VectorRef<'a>> for &'a [VectorElementType] {
         match value {
             VectorRef::Dense(v) => Ok(v),
             VectorRef::Sparse(_) => Err(OperationError::WrongSparse),
+            VectorRef::MultiDense(_) => Err(OperationError::WrongMulti),
         }
     }
 }
@@ -70,6 +106,19 @@ impl<'a> TryFrom<VectorRef<'a>> for &'a SparseVector {
         match value {
             VectorRef::Dense(_) => Err(OperationError::WrongSparse),
             VectorRef::Sparse(v) => Ok(v),
+            VectorRef::MultiDense(_) => Err(OperationError::WrongSparse),
+        }
+    }
+}
+
+impl<'a> TryFrom<VectorRef<'a>> for &'a [DenseVector] {
+    type Error = OperationError;
+
+    fn try_from(value: VectorRef<'a>) -> Result<Self, Self::Error> {
+        match value {
+            VectorRef::Dense(_) => Err(OperationError::WrongMulti),
+            VectorRef::Sparse(_) => Err(OperationError::WrongSparse),
+            VectorRef::MultiDense(v) => Ok(v),
         }
     }
 }
@@ -80,6 +129,7 @@ impl From<NamedVectorStruct> for Vector {
             NamedVectorStruct::Default(v) => Vector::Dense(v),
             NamedVectorStruct::Dense(v) => Vector::Dense(v.vector),
             NamedVectorStruct::Sparse(v) => Vector::Sparse(v.vector),
+            NamedVectorStruct::MultiDense(v) => Vector::MultiDense(v.vector),
         }
     }
 }
@@ -91,6 +141,7 @@ impl TryFrom<Vector> for DenseVector {
         match value {
             Vector::Dense(v) => Ok(v),
             Vector::Sparse(_) => Err(OperationError::WrongSparse),
+            Vector::MultiDense(_) => Err(OperationError::WrongMulti),
         }
     }
 }
@@ -102,6 +153,19 @@ impl TryFrom<Vector> for SparseVector {
         match value {
             Vector::Dense(_) => Err(OperationError::WrongSparse),
             Vector::Sparse(v) => Ok(v),
+            Vector::MultiDense(_) => Err(OperationError::WrongSparse),
+        }
+    }
+}
+
+impl TryFrom<Vector> for MultiDenseVector {
+    type Error = OperationError;
+
+    fn try_from(value: Vector) -> Result<Self, Self::Error> {
+        match value {
+            Vector::Dense(_) => Err(OperationError::WrongMulti),
+            Vector::Sparse(_) => Err(OperationError::WrongSparse),
+            Vector::MultiDense(v) => Ok(v),
         }
     }
 }
@@ -124,6 +188,18 @@ impl<'a> From<&'a SparseVector> for VectorRef<'a> {
     }
 }
 
+impl<'a> From<&'a [DenseVector]> for VectorRef<'a> {
+    fn from(val: &'a [DenseVector]) -> Self {
+        VectorRef::MultiDense(val)
+    }
+}
+
+impl<'a> From<&'a MultiDenseVector> for VectorRef<'a> {
+    fn from(val: &'a MultiDenseVector) -> Self {
+        VectorRef::MultiDense(val.as_slice())
+    }
+}
+
 impl From<DenseVector> for Vector {
     fn from(val: DenseVector) -> Self {
         Vector::Dense(val)
@@ -136,36 +212,68 @@ impl From<SparseVector> for Vector {
     }
 }
 
+impl From<MultiDenseVector> for Vector {
+    fn from(val: MultiDenseVector) -> Self {
+        Vector::MultiDense(val)
+    }
+}
+
 impl<'a> From<&'a Vector> for VectorRef<'a> {
     fn from(val: &'a Vector) -> Self {
-        match val {
-            Vector::Dense(v) => VectorRef::Dense(v.as_slice()),
-            Vector::Sparse(v) => VectorRef::Sparse(v),
-        }
+        val.to_vec_ref()
     }
 }
 
 /// Type of vector element.
 pub type VectorElementType = f32;
 
+/// Element of the vectors of the `uint8` datatype
+pub type VectorElementTypeByte = u8;
+
+/// Element of the vectors of the `float16` datatype
+pub type VectorElementTypeHalf = half::f16;
+
+/// Convert the vector to the `uint8` datatype, values are rounded to the nearest integer
+pub fn dense_vector_to_bytes(vector: &[VectorElementType]) -> Vec<VectorElementTypeByte> {
+    vector
+        .iter()
+        .map(|&value| value.round() as VectorElementTypeByte)
+        .collect()
+}
+
+/// Convert the vector to the `float16` datatype, values are rounded to the nearest representable
+pub fn dense_vector_to_half(vector: &[VectorElementType]) -> Vec<VectorElementTypeHalf> {
+    vector
+        .iter()
+        .map(|&value| VectorElementTypeHalf::from_f32(value))
+        .collect()
+}
+
 pub const DEFAULT_VECTOR_NAME: &str = "";
 
 /// Type for dense vector
 pub type DenseVector = Vec<VectorElementType>;
 
+/// Type for multi-vector: variable number of dense vectors of the same size, stored under a
+/// single vector name of the point
+pub type MultiDenseVector = Vec<DenseVector>;
+
 impl<'a> VectorRef<'a> {
     // Cannot use `ToOwned` trait because of `Borrow` implementation for `Vector`
     pub fn to_owned(self) -> Vector {
         match self {
             VectorRef::Dense(v) => Vector::Dense(v.to_vec()),
             VectorRef::Sparse(v) => Vector::Sparse(v.clone()),
+            VectorRef::MultiDense(v) => Vector::MultiDense(v.to_vec()),
         }
     }
 
+    /// Number of elements of the vector, multi-vector counts the elements of all its vectors
     pub fn len(&self) -> usize {
         match self {
             VectorRef::Dense(v) => v.len(),
             VectorRef::Sparse(v) => v.indices.len(),
+            VectorRef::MultiDense(v) => v.iter().map(Vec::len).sum(),
         }
     }
 
@@ -181,6 +289,7 @@ impl<'a> TryInto<&'a [VectorElementType]> for &'a Vector {
         match self {
             Vector::Dense(v) => Ok(v),
             Vector::Sparse(_) => Err(OperationError::WrongSparse),
+            Vector::MultiDense(_) => Err(OperationError::WrongMulti),
         }
     }
 }
@@ -192,6 +301,7 @@ impl<'a> TryInto<&'a SparseVector> for &'a Vector {
         match self {
             Vector::Dense(_) => Err(OperationError::WrongSparse),
             Vector::Sparse(v) => Ok(v),
+            Vector::MultiDense(_) => Err(OperationError::WrongSparse),
         }
     }
 }
@@ -220,9 +330,18 @@ impl VectorStruct {
             VectorStruct::Multi(vectors) => vectors.values().all(|v| match v {
                 Vector::Dense(vector) => vector.is_empty(),
                 Vector::Sparse(vector) => vector.indices.is_empty(),
+                Vector::MultiDense(vector) => vector.is_empty(),
             }),
         }
     }
+
+    /// Names of the vectors in this vector struct
+    pub fn names(&self) -> Vec<&str> {
+        match self {
+            VectorStruct::Single(_) => vec![DEFAULT_VECTOR_NAME],
+            VectorStruct::Multi(vectors) => vectors.keys().map(String::as_str).collect(),
+        }
+    }
 }
 
 impl Validate for VectorStruct {
@@ -248,11 +367,15 @@ impl From<&[VectorElementType]> for VectorStruct {
 
 impl<'a> From<NamedVectors<'a>> for VectorStruct {
     fn from(v: NamedVectors) -> Self {
-        if v.len() == 1 && v.contains_key(DEFAULT_VECTOR_NAME) {
-            let vector: &[_] = v.get(DEFAULT_VECTOR_NAME).unwrap().try_into().unwrap();
-            VectorStruct::Single(vector.to_owned())
-        } else {
-            VectorStruct::Multi(v.into_owned_map())
+        // Owned vectors are moved into the struct, only borrowed ones are copied
+        let mut vectors = v.into_owned_map();
+        match vectors.remove(DEFAULT_VECTOR_NAME) {
+            Some(Vector::Dense(vector)) if vectors.is_empty() => VectorStruct::Single(vector),
+            Some(vector) => {
+                vectors.insert(DEFAULT_VECTOR_NAME.to_string(), vector);
+                VectorStruct::Multi(vectors)
+            }
+            None => VectorStruct::Multi(vectors),
         }
     }
 }
@@ -294,6 +417,16 @@ pub struct NamedSparseVector {
     pub vector: SparseVector,
 }
 
+/// Multi-vector data with name
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone)]
+#[serde(rename_all = "snake_case")]
+pub struct NamedMultiDenseVector {
+    /// Name of vector data
+    pub name: String,
+    /// Vector data
+    pub vector: MultiDenseVector,
+}
+
 /// Vector data separator for named and unnamed modes
 /// Unnamed mode:
 ///
@@ -315,6 +448,8 @@ pub struct NamedSparseVector {
 pub enum NamedVectorStruct {
     Default(DenseVector),
     Dense(NamedVector),
+    // Goes before the sparse vector, same as in `Vector`
+    MultiDense(NamedMultiDenseVector),
     Sparse(NamedSparseVector),
 }
 
@@ -336,6 +471,12 @@ impl From<NamedSparseVector> for NamedVectorStruct {
     }
 }
 
+impl From<NamedMultiDenseVector> for NamedVectorStruct {
+    fn from(v: NamedMultiDenseVector) -> Self {
+        NamedVectorStruct::MultiDense(v)
+    }
+}
+
 pub trait Named {
     fn get_name(&self) -> &str;
 }
@@ -346,6 +487,7 @@ impl Named for NamedVectorStruct {
             NamedVectorStruct::Default(_) => DEFAULT_VECTOR_NAME,
             NamedVectorStruct::Dense(v) => &v.name,
             NamedVectorStruct::Sparse(v) => &v.name,
+            NamedVectorStruct::MultiDense(v) => &v.name,
         }
     }
 }
@@ -355,6 +497,9 @@ impl NamedVectorStruct {
         match vector {
             Vector::Dense(vector) => NamedVectorStruct::Dense(NamedVector { name, vector }),
             Vector::Sparse(vector) => NamedVectorStruct::Sparse(NamedSparseVector { name, vector }),
+            Vector::MultiDense(vector) => {
+                NamedVectorStruct::MultiDense(NamedMultiDenseVector { name, vector })
+            }
         }
     }
 
@@ -363,6 +508,7 @@ impl NamedVectorStruct {
             NamedVectorStruct::Default(v) => v.as_slice().into(),
             NamedVectorStruct::Dense(v) => v.vector.as_slice().into(),
             NamedVectorStruct::Sparse(v) => (&v.vector).into(),
+            NamedVectorStruct::MultiDense(v) => v.vector.as_slice().into(),
         }
     }
 
@@ -371,6 +517,7 @@ impl NamedVectorStruct {
             NamedVectorStruct::Default(v) => v.into(),
             NamedVectorStruct::Dense(v) => v.vector.into(),
             NamedVectorStruct::Sparse(v) => v.vector.into(),
+            NamedVectorStruct::MultiDense(v) => v.vector.into(),
         }
     }
 }
@@ -381,6 +528,7 @@ impl Validate for NamedVectorStruct {
             NamedVectorStruct::Default(_) => Ok(()),
             NamedVectorStruct::Dense(_) => Ok(()),
             NamedVectorStruct::Sparse(v) => v.validate(),
+            NamedVectorStruct::MultiDense(v) => validate_multi_dense_vector(&v.vector),
         }
     }
 }
@@ -4,13 +4,14 @@ use std::sync::atomic::AtomicBool;
 
 use crate::common::operation_error::{OperationResult, SegmentFailedState};
 use crate::data_types::named_vectors::NamedVectors;
+use crate::data_types::order_by::OrderBy;
 use crate::data_types::vectors::{QueryVector, Vector};
 use crate::index::field_index::CardinalityEstimation;
 use crate::telemetry::SegmentTelemetry;
 use crate::types::{
-    Filter, Payload, PayloadFieldSchema, PayloadKeyType, PayloadKeyTypeRef, PointIdType,
-    ScoredPoint, SearchParams, SegmentConfig, SegmentInfo, SegmentType, SeqNumberType, WithPayload,
-    WithVector,
+    Filter, FloatPayloadType, Payload, PayloadFieldSchema, PayloadKeyType, PayloadKeyTypeRef,
+    PointIdType, RetrievedPoint, ScoredPoint, SearchParams, SegmentConfig, SegmentInfo,
+    SegmentType, SeqNumberType, WithPayload, WithVector,
 };
 
 /// Define all operations which can be performed with Segment or Segment-like entity.
@@ -110,6 +111,23 @@ pub trait SegmentEntry {
 
     fn payload(&self, point_id: PointIdType) -> OperationResult<Payload>;
 
+    /// Read payloads of multiple points at once, in the order of `point_ids`.
+    ///
+    /// All points are expected to be present in the segment.
+    fn payload_batch(&self, point_ids: &[PointIdType]) -> OperationResult<Vec<Payload>>;
+
+    /// Retrieve multiple points at once, reading payload and vectors selected by
+    /// `with_payload` and `with_vector`.
+    ///
+    /// All points are expected to be present in the segment.
+    /// Order of the result is not guaranteed to match the order of `point_ids`.
+    fn retrieve(
+        &self,
+        point_ids: &[PointIdType],
+        with_payload: &WithPayload,
+        with_vector: &WithVector,
+    ) -> OperationResult<Vec<RetrievedPoint>>;
+
     /// Iterator over all points in segment in ascending order.
     fn iter_points(&self) -> Box<dyn Iterator<Item = PointIdType> + '_>;
 
@@ -121,6 +139,26 @@ pub trait SegmentEntry {
         filter: Option<&'a Filter>,
     ) -> Vec<PointIdType>;
 
+    /// Read points which satisfy filtering condition, ordered by the values of the `order_by` field
+    ///
+    /// Returns the value, the point is ordered by, with each point.
+    /// Requires a numeric index on the field.
+    fn read_ordered_filtered<'a>(
+        &'a self,
+        limit: Option<usize>,
+        filter: Option<&'a Filter>,
+        order_by: &'a OrderBy,
+    ) -> OperationResult<Vec<(FloatPayloadType, PointIdType)>>;
+
+    /// Read up to `limit` points which satisfy filtering condition, sampled uniformly at random
+    ///
+    /// Order of the result is not random.
+    fn read_random_filtered<'a>(
+        &'a self,
+        limit: usize,
+        filter: Option<&'a Filter>,
+    ) -> Vec<PointIdType>;
+
     /// Read points in [from; to) range
     fn read_range(&self, from: Option<PointIdType>, to: Option<PointIdType>) -> Vec<PointIdType>;
 
@@ -160,6 +198,9 @@ pub trait SegmentEntry {
     /// Returns maximum version number which is guaranteed to be persisted.
     fn flush(&self, sync: bool) -> OperationResult<SeqNumberType>;
 
+    /// Segment has updates, which are not persisted by a flush yet
+    fn is_dirty(&self) -> bool;
+
     /// Removes all persisted data and forces to destroy segment
     fn drop_data(self) -> OperationResult<()>;
 
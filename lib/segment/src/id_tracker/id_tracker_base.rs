@@ -1,3 +1,5 @@
+use std::path::PathBuf;
+
 use bitvec::prelude::BitSlice;
 use common::types::PointOffsetType;
 use rand::rngs::StdRng;
@@ -111,6 +113,11 @@ pub trait IdTracker {
     /// Check whether the given point is soft deleted
     fn is_deleted_point(&self, internal_id: PointOffsetType) -> bool;
 
+    /// Files, which are used by the tracker besides the segment database
+    fn files(&self) -> Vec<PathBuf> {
+        vec![]
+    }
+
     /// Iterator over `n` random IDs which are not deleted
     ///
     /// A [`BitSlice`] of deleted vectors may optionally be given to also consider deleted named
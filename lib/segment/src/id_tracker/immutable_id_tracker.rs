@@ -0,0 +1,458 @@
+use std::fs::File;
+use std::io::Write;
+use std::mem::size_of;
+use std::path::{Path, PathBuf};
+
+use bitvec::prelude::{BitSlice, BitVec};
+use common::types::PointOffsetType;
+use io::file_operations::{atomic_save_json, read_json};
+use memmap2::MmapMut;
+use memory::mmap_ops;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::common::error_logging::LogError;
+use crate::common::mmap_type::{MmapBitSlice, MmapSlice};
+use crate::common::operation_error::{OperationError, OperationResult};
+use crate::common::Flusher;
+use crate::id_tracker::{IdTracker, IdTrackerSS};
+use crate::types::{PointIdType, SeqNumberType};
+
+const CONFIG_FILE: &str = "id_tracker.json";
+const EXTERNAL_IDS_FILE: &str = "id_tracker.external_ids";
+const NUM_IDS_ORDER_FILE: &str = "id_tracker.num_ids";
+const UUIDS_ORDER_FILE: &str = "id_tracker.uuids";
+const VERSIONS_FILE: &str = "id_tracker.versions";
+const DELETED_FILE: &str = "id_tracker.deleted";
+
+const NUM_ID_KIND: u64 = 0;
+const UUID_KIND: u64 = 1;
+
+/// Sizes of the mapped arrays.
+///
+/// Files are never empty, because empty files can't be memory-mapped,
+/// so the actual number of elements is stored separately.
+#[derive(Debug, Deserialize, Serialize)]
+struct ImmutableIdTrackerConfig {
+    total_point_count: usize,
+    num_ids_count: usize,
+    uuids_count: usize,
+}
+
+/// Fixed-size representation of the external point id
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+struct StoredExternalId {
+    kind: u64,
+    value: [u64; 2],
+}
+
+impl From<PointIdType> for StoredExternalId {
+    fn from(point_id: PointIdType) -> Self {
+        match point_id {
+            PointIdType::NumId(num) => Self {
+                kind: NUM_ID_KIND,
+                value: [num, 0],
+            },
+            PointIdType::Uuid(uuid) => {
+                let value = uuid.as_u128();
+                Self {
+                    kind: UUID_KIND,
+                    value: [(value >> 64) as u64, value as u64],
+                }
+            }
+        }
+    }
+}
+
+impl From<StoredExternalId> for PointIdType {
+    fn from(stored: StoredExternalId) -> Self {
+        match stored.kind {
+            UUID_KIND => PointIdType::Uuid(Uuid::from_u128(
+                (u128::from(stored.value[0]) << 64) | u128::from(stored.value[1]),
+            )),
+            _ => PointIdType::NumId(stored.value[0]),
+        }
+    }
+}
+
+/// Id tracker of immutable segments, which keeps the mappings and versions in memory-mapped files.
+///
+/// Points can't be added, but can be deleted and their versions can be updated.
+/// Lookups by external id use binary search over internal ids, sorted by external id,
+/// so only the pages which are actually accessed are kept in RAM.
+pub struct ImmutableIdTracker {
+    path: PathBuf,
+    config: ImmutableIdTrackerConfig,
+    /// Internal id -> external id
+    external_ids: MmapSlice<StoredExternalId>,
+    /// Internal ids of points with numeric external ids, sorted by external id
+    num_ids_order: MmapSlice<PointOffsetType>,
+    /// Internal ids of points with UUID external ids, sorted by external id
+    uuids_order: MmapSlice<PointOffsetType>,
+    /// Internal id -> version
+    versions: MmapSlice<SeqNumberType>,
+    deleted: MmapBitSlice,
+    deleted_count: usize,
+}
+
+impl ImmutableIdTracker {
+    /// Check if the segment directory contains an immutable id tracker
+    pub fn exists(segment_path: &Path) -> bool {
+        segment_path.join(CONFIG_FILE).exists()
+    }
+
+    /// Persist the state of the given id tracker into the immutable id tracker files
+    pub fn build(source: &IdTrackerSS, segment_path: &Path) -> OperationResult<()> {
+        let total_point_count = source.total_point_count();
+
+        let mut external_ids = Vec::with_capacity(total_point_count);
+        let mut versions = Vec::with_capacity(total_point_count);
+        let mut deleted = BitVec::<usize>::repeat(true, total_point_count.max(1));
+        for internal_id in 0..total_point_count as PointOffsetType {
+            let external_id = source.external_id(internal_id);
+            // Same placeholder for deleted points as in the simple id tracker
+            external_ids.push(external_id.unwrap_or(PointIdType::NumId(u64::MAX)).into());
+            versions.push(source.internal_version(internal_id).unwrap_or(0));
+            deleted.set(internal_id as usize, external_id.is_none());
+        }
+
+        // Iterates over numeric ids first, both parts sorted
+        let (num_ids_order, uuids_order): (Vec<_>, Vec<_>) = source
+            .iter_from(None)
+            .partition(|(external_id, _)| matches!(external_id, PointIdType::NumId(_)));
+        let num_ids_order: Vec<_> = num_ids_order.into_iter().map(|(_, id)| id).collect();
+        let uuids_order: Vec<_> = uuids_order.into_iter().map(|(_, id)| id).collect();
+
+        write_slice(&segment_path.join(EXTERNAL_IDS_FILE), &external_ids)?;
+        write_slice(&segment_path.join(NUM_IDS_ORDER_FILE), &num_ids_order)?;
+        write_slice(&segment_path.join(UUIDS_ORDER_FILE), &uuids_order)?;
+        write_slice(&segment_path.join(VERSIONS_FILE), &versions)?;
+        write_slice(&segment_path.join(DELETED_FILE), deleted.as_raw_slice())?;
+
+        // Config is saved last, it marks the tracker as completely written
+        let config = ImmutableIdTrackerConfig {
+            total_point_count,
+            num_ids_count: num_ids_order.len(),
+            uuids_count: uuids_order.len(),
+        };
+        atomic_save_json(&segment_path.join(CONFIG_FILE), &config)?;
+        Ok(())
+    }
+
+    pub fn open(segment_path: &Path) -> OperationResult<Self> {
+        let config: ImmutableIdTrackerConfig = read_json(&segment_path.join(CONFIG_FILE))?;
+
+        let external_ids =
+            unsafe { MmapSlice::try_from(open_mmap(segment_path, EXTERNAL_IDS_FILE)?)? };
+        let num_ids_order =
+            unsafe { MmapSlice::try_from(open_mmap(segment_path, NUM_IDS_ORDER_FILE)?)? };
+        let uuids_order =
+            unsafe { MmapSlice::try_from(open_mmap(segment_path, UUIDS_ORDER_FILE)?)? };
+        let versions = unsafe { MmapSlice::try_from(open_mmap(segment_path, VERSIONS_FILE)?)? };
+        let deleted = MmapBitSlice::try_from(open_mmap(segment_path, DELETED_FILE)?, 0)?;
+
+        if external_ids.len() < config.total_point_count
+            || versions.len() < config.total_point_count
+            || deleted.len() < config.total_point_count
+            || num_ids_order.len() < config.num_ids_count
+            || uuids_order.len() < config.uuids_count
+        {
+            return Err(OperationError::service_error(format!(
+                "Immutable id tracker files are corrupted: {}",
+                segment_path.display(),
+            )));
+        }
+
+        let deleted_count = deleted[..config.total_point_count].count_ones();
+
+        Ok(Self {
+            path: segment_path.to_owned(),
+            config,
+            external_ids,
+            num_ids_order,
+            uuids_order,
+            versions,
+            deleted,
+            deleted_count,
+        })
+    }
+
+    fn stored_external_id(&self, internal_id: PointOffsetType) -> PointIdType {
+        self.external_ids[internal_id as usize].into()
+    }
+
+    fn num_ids_order(&self) -> &[PointOffsetType] {
+        &self.num_ids_order[..self.config.num_ids_count]
+    }
+
+    fn uuids_order(&self) -> &[PointOffsetType] {
+        &self.uuids_order[..self.config.uuids_count]
+    }
+
+    /// Position of the first point in `order`, which external id is not less than `external_id`
+    fn lower_bound(&self, order: &[PointOffsetType], external_id: PointIdType) -> usize {
+        order.partition_point(|&internal_id| self.stored_external_id(internal_id) < external_id)
+    }
+
+    /// Iterate over not deleted points of `order`, starting from the given position
+    fn iter_order<'a>(
+        &'a self,
+        order: &'a [PointOffsetType],
+        start: usize,
+    ) -> impl Iterator<Item = (PointIdType, PointOffsetType)> + 'a {
+        order[start..]
+            .iter()
+            .filter(|&&internal_id| !self.is_deleted_point(internal_id))
+            .map(|&internal_id| (self.stored_external_id(internal_id), internal_id))
+    }
+}
+
+impl IdTracker for ImmutableIdTracker {
+    fn internal_version(&self, internal_id: PointOffsetType) -> Option<SeqNumberType> {
+        if internal_id as usize >= self.config.total_point_count {
+            return None;
+        }
+        self.versions.get(internal_id as usize).copied()
+    }
+
+    fn set_internal_version(
+        &mut self,
+        internal_id: PointOffsetType,
+        version: SeqNumberType,
+    ) -> OperationResult<()> {
+        if !self.is_deleted_point(internal_id) {
+            self.versions[internal_id as usize] = version;
+        }
+        Ok(())
+    }
+
+    fn internal_id(&self, external_id: PointIdType) -> Option<PointOffsetType> {
+        let order = match external_id {
+            PointIdType::NumId(_) => self.num_ids_order(),
+            PointIdType::Uuid(_) => self.uuids_order(),
+        };
+        let position = self.lower_bound(order, external_id);
+        let internal_id = *order.get(position)?;
+        (self.stored_external_id(internal_id) == external_id && !self.is_deleted_point(internal_id))
+            .then_some(internal_id)
+    }
+
+    fn external_id(&self, internal_id: PointOffsetType) -> Option<PointIdType> {
+        if self.is_deleted_point(internal_id) {
+            return None;
+        }
+        Some(self.stored_external_id(internal_id))
+    }
+
+    fn set_link(
+        &mut self,
+        external_id: PointIdType,
+        _internal_id: PointOffsetType,
+    ) -> OperationResult<()> {
+        Err(OperationError::service_error(format!(
+            "Can't add point {external_id} to immutable id tracker",
+        )))
+    }
+
+    fn drop(&mut self, external_id: PointIdType) -> OperationResult<()> {
+        if let Some(internal_id) = self.internal_id(external_id) {
+            if !self.deleted.replace(internal_id as usize, true) {
+                self.deleted_count += 1;
+            }
+        }
+        Ok(())
+    }
+
+    fn iter_external(&self) -> Box<dyn Iterator<Item = PointIdType> + '_> {
+        Box::new(self.iter_from(None).map(|(external_id, _)| external_id))
+    }
+
+    fn iter_internal(&self) -> Box<dyn Iterator<Item = PointOffsetType> + '_> {
+        Box::new(
+            (0..self.config.total_point_count as PointOffsetType)
+                .filter(move |i| !self.deleted[*i as usize]),
+        )
+    }
+
+    fn iter_from(
+        &self,
+        external_id: Option<PointIdType>,
+    ) -> Box<dyn Iterator<Item = (PointIdType, PointOffsetType)> + '_> {
+        let num_ids_order = self.num_ids_order();
+        let uuids_order = self.uuids_order();
+        match external_id {
+            // order is important here, we want to iterate over the u64 ids first
+            None => Box::new(
+                self.iter_order(num_ids_order, 0)
+                    .chain(self.iter_order(uuids_order, 0)),
+            ),
+            // u64 keys are less than uuid keys, so all uuids are included
+            Some(offset @ PointIdType::NumId(_)) => Box::new(
+                self.iter_order(num_ids_order, self.lower_bound(num_ids_order, offset))
+                    .chain(self.iter_order(uuids_order, 0)),
+            ),
+            Some(offset @ PointIdType::Uuid(_)) => {
+                Box::new(self.iter_order(uuids_order, self.lower_bound(uuids_order, offset)))
+            }
+        }
+    }
+
+    fn iter_ids(&self) -> Box<dyn Iterator<Item = PointOffsetType> + '_> {
+        self.iter_internal()
+    }
+
+    fn mapping_flusher(&self) -> Flusher {
+        self.deleted.flusher()
+    }
+
+    fn versions_flusher(&self) -> Flusher {
+        self.versions.flusher()
+    }
+
+    fn total_point_count(&self) -> usize {
+        self.config.total_point_count
+    }
+
+    fn deleted_point_count(&self) -> usize {
+        self.deleted_count
+    }
+
+    fn deleted_point_bitslice(&self) -> &BitSlice {
+        &self.deleted[..self.config.total_point_count]
+    }
+
+    fn is_deleted_point(&self, internal_id: PointOffsetType) -> bool {
+        let internal_id = internal_id as usize;
+        if internal_id >= self.config.total_point_count {
+            return true;
+        }
+        self.deleted[internal_id]
+    }
+
+    fn files(&self) -> Vec<PathBuf> {
+        [
+            CONFIG_FILE,
+            EXTERNAL_IDS_FILE,
+            NUM_IDS_ORDER_FILE,
+            UUIDS_ORDER_FILE,
+            VERSIONS_FILE,
+            DELETED_FILE,
+        ]
+        .into_iter()
+        .map(|file| self.path.join(file))
+        .collect()
+    }
+}
+
+fn open_mmap(segment_path: &Path, file: &str) -> OperationResult<MmapMut> {
+    Ok(mmap_ops::open_write_mmap(&segment_path.join(file))
+        .describe("Open immutable id tracker mmap")?)
+}
+
+/// Write values into a file, which can be memory-mapped as a slice of `T`
+fn write_slice<T: Copy>(path: &Path, data: &[T]) -> OperationResult<()> {
+    let mut file = File::create(path)?;
+    file.write_all(mmap_ops::transmute_to_u8_slice(data))?;
+    if data.is_empty() {
+        // Empty files can't be memory-mapped
+        file.write_all(&vec![0; size_of::<T>()])?;
+    }
+    file.sync_all()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use itertools::Itertools;
+    use tempfile::Builder;
+
+    use super::*;
+    use crate::common::rocksdb_wrapper::{open_db, DB_VECTOR_CF};
+    use crate::id_tracker::simple_id_tracker::SimpleIdTracker;
+
+    #[test]
+    fn test_same_as_simple_id_tracker() {
+        let dir = Builder::new().prefix("storage_dir").tempdir().unwrap();
+        let db = open_db(dir.path(), &[DB_VECTOR_CF]).unwrap();
+
+        let mut simple = SimpleIdTracker::open(db).unwrap();
+        let values: Vec<PointIdType> = vec![
+            100.into(),
+            PointIdType::Uuid(Uuid::from_u128(123_u128)),
+            PointIdType::Uuid(Uuid::from_u128(u128::MAX - 5)),
+            150.into(),
+            120.into(),
+            PointIdType::Uuid(Uuid::from_u128(12_u128)),
+            180.into(),
+            u64::MAX.into(),
+        ];
+        for (internal_id, external_id) in values.iter().enumerate() {
+            let internal_id = internal_id as PointOffsetType;
+            simple.set_link(*external_id, internal_id).unwrap();
+            simple
+                .set_internal_version(internal_id, internal_id as SeqNumberType * 10)
+                .unwrap();
+        }
+        simple.drop(150.into()).unwrap();
+
+        ImmutableIdTracker::build(&simple, dir.path()).unwrap();
+        let mut immutable = ImmutableIdTracker::open(dir.path()).unwrap();
+
+        assert_eq!(immutable.total_point_count(), simple.total_point_count());
+        assert_eq!(immutable.available_point_count(), 7);
+        assert_eq!(
+            immutable.iter_from(None).collect_vec(),
+            simple.iter_from(None).collect_vec(),
+        );
+        assert_eq!(
+            immutable.iter_from(Some(120.into())).collect_vec(),
+            simple.iter_from(Some(120.into())).collect_vec(),
+        );
+        assert_eq!(
+            immutable.iter_internal().collect_vec(),
+            simple.iter_internal().collect_vec(),
+        );
+        for external_id in &values {
+            let internal_id = simple.internal_id(*external_id);
+            assert_eq!(immutable.internal_id(*external_id), internal_id);
+            if let Some(internal_id) = internal_id {
+                assert_eq!(immutable.external_id(internal_id), Some(*external_id));
+                assert_eq!(
+                    immutable.internal_version(internal_id),
+                    simple.internal_version(internal_id),
+                );
+            }
+        }
+        assert_eq!(immutable.internal_id(150.into()), None);
+        assert_eq!(immutable.internal_id(999.into()), None);
+
+        // Deletions and version updates are persisted
+        immutable.drop(100.into()).unwrap();
+        immutable.set_internal_version(6, 1000).unwrap();
+        immutable.mapping_flusher()().unwrap();
+        immutable.versions_flusher()().unwrap();
+        assert!(immutable.set_link(999.into(), 8).is_err());
+        drop(immutable);
+
+        let immutable = ImmutableIdTracker::open(dir.path()).unwrap();
+        assert_eq!(immutable.available_point_count(), 6);
+        assert_eq!(immutable.internal_id(100.into()), None);
+        assert_eq!(immutable.internal_version(6), Some(1000));
+    }
+
+    #[test]
+    fn test_empty() {
+        let dir = Builder::new().prefix("storage_dir").tempdir().unwrap();
+        let db = open_db(dir.path(), &[DB_VECTOR_CF]).unwrap();
+        let simple = SimpleIdTracker::open(db).unwrap();
+
+        ImmutableIdTracker::build(&simple, dir.path()).unwrap();
+        let immutable = ImmutableIdTracker::open(dir.path()).unwrap();
+
+        assert_eq!(immutable.total_point_count(), 0);
+        assert_eq!(immutable.iter_from(None).count(), 0);
+        assert_eq!(immutable.internal_id(1.into()), None);
+        assert!(immutable.is_deleted_point(0));
+    }
+}
@@ -13,7 +13,7 @@ use crate::index::field_index::numeric_index::NumericIndex;
 use crate::index::field_index::{CardinalityEstimation, PayloadBlockCondition};
 use crate::telemetry::PayloadIndexTelemetry;
 use crate::types::{
-    FieldCondition, FloatPayloadType, IntPayloadType, Match, MatchText, PayloadKeyType,
+    FieldCondition, FloatPayloadType, IntPayloadType, Match, MatchText, PayloadKeyType, Range,
 };
 
 pub trait PayloadFieldIndex {
@@ -160,6 +160,28 @@ impl FieldIndex {
         }
     }
 
+    /// Values of the numeric index in the range with their points, sorted by value
+    ///
+    /// Returns `None` if the index is not numeric
+    pub fn ordered_values(
+        &self,
+        range: &Range,
+    ) -> Option<Box<dyn DoubleEndedIterator<Item = (FloatPayloadType, PointOffsetType)> + '_>> {
+        match self {
+            FieldIndex::IntIndex(index) => Some(Box::new(
+                index
+                    .ordered_values(range)
+                    .map(|(value, idx)| (value as FloatPayloadType, idx)),
+            )),
+            FieldIndex::FloatIndex(index) => Some(index.ordered_values(range)),
+            FieldIndex::IntMapIndex(_)
+            | FieldIndex::KeywordIndex(_)
+            | FieldIndex::GeoIndex(_)
+            | FieldIndex::FullTextIndex(_)
+            | FieldIndex::BinaryIndex(_) => None,
+        }
+    }
+
     fn get_payload_field_index(&self) -> &dyn PayloadFieldIndex {
         match self {
             FieldIndex::IntIndex(payload_field_index) => payload_field_index,
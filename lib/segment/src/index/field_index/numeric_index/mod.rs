@@ -246,6 +246,70 @@ impl<T: Encodable + Numericable> NumericIndex<T> {
             .map(|x| x.is_empty())
             .unwrap_or(true)
     }
+
+    /// Values in the range with their points, sorted by value
+    ///
+    /// Points with multiple values in the range are returned once per value.
+    pub fn ordered_values(
+        &self,
+        range: &Range,
+    ) -> Box<dyn DoubleEndedIterator<Item = (T, PointOffsetType)> + '_> {
+        let (start_bound, end_bound) = Self::range_bounds(range);
+
+        if !check_boundaries(&start_bound, &end_bound) {
+            return Box::new(std::iter::empty());
+        }
+
+        match self {
+            NumericIndex::Mutable(index) => {
+                let (start_bound, end_bound) = Self::encode_bounds(start_bound, end_bound);
+                Box::new(index.ordered_values_range(start_bound, end_bound))
+            }
+            NumericIndex::Immutable(index) => {
+                Box::new(index.ordered_values_range(start_bound, end_bound))
+            }
+        }
+    }
+
+    fn range_bounds(range: &Range) -> (Bound<NumericIndexKey<T>>, Bound<NumericIndexKey<T>>) {
+        let start_bound = match range {
+            Range { gt: Some(gt), .. } => {
+                let v: T = T::from_f64(*gt);
+                Excluded(NumericIndexKey::new(v, PointOffsetType::MAX))
+            }
+            Range { gte: Some(gte), .. } => {
+                let v: T = T::from_f64(*gte);
+                Included(NumericIndexKey::new(v, PointOffsetType::MIN))
+            }
+            _ => Unbounded,
+        };
+
+        let end_bound = match range {
+            Range { lt: Some(lt), .. } => {
+                let v: T = T::from_f64(*lt);
+                Excluded(NumericIndexKey::new(v, PointOffsetType::MIN))
+            }
+            Range { lte: Some(lte), .. } => {
+                let v: T = T::from_f64(*lte);
+                Included(NumericIndexKey::new(v, PointOffsetType::MAX))
+            }
+            _ => Unbounded,
+        };
+
+        (start_bound, end_bound)
+    }
+
+    fn encode_bounds(
+        start_bound: Bound<NumericIndexKey<T>>,
+        end_bound: Bound<NumericIndexKey<T>>,
+    ) -> (Bound<Vec<u8>>, Bound<Vec<u8>>) {
+        let encode = |bound: Bound<NumericIndexKey<T>>| match bound {
+            Included(k) => Included(k.encode()),
+            Excluded(k) => Excluded(k.encode()),
+            Unbounded => Unbounded,
+        };
+        (encode(start_bound), encode(end_bound))
+    }
 }
 
 impl<T: Encodable + Numericable> PayloadFieldIndex for NumericIndex<T> {
@@ -274,29 +338,7 @@ impl<T: Encodable + Numericable> PayloadFieldIndex for NumericIndex<T> {
             .as_ref()
             .ok_or_else(|| OperationError::service_error("failed to get condition range"))?;
 
-        let start_bound = match cond_range {
-            Range { gt: Some(gt), .. } => {
-                let v: T = T::from_f64(*gt);
-                Excluded(NumericIndexKey::new(v, PointOffsetType::MAX))
-            }
-            Range { gte: Some(gte), .. } => {
-                let v: T = T::from_f64(*gte);
-                Included(NumericIndexKey::new(v, PointOffsetType::MIN))
-            }
-            _ => Unbounded,
-        };
-
-        let end_bound = match cond_range {
-            Range { lt: Some(lt), .. } => {
-                let v: T = T::from_f64(*lt);
-                Excluded(NumericIndexKey::new(v, PointOffsetType::MIN))
-            }
-            Range { lte: Some(lte), .. } => {
-                let v: T = T::from_f64(*lte);
-                Included(NumericIndexKey::new(v, PointOffsetType::MAX))
-            }
-            _ => Unbounded,
-        };
+        let (start_bound, end_bound) = Self::range_bounds(cond_range);
 
         // map.range
         // Panics if range start > end. Panics if range start == end and both bounds are Excluded.
@@ -306,16 +348,7 @@ impl<T: Encodable + Numericable> PayloadFieldIndex for NumericIndex<T> {
 
         Ok(match self {
             NumericIndex::Mutable(index) => {
-                let start_bound = match start_bound {
-                    Included(k) => Included(k.encode()),
-                    Excluded(k) => Excluded(k.encode()),
-                    Unbounded => Unbounded,
-                };
-                let end_bound = match end_bound {
-                    Included(k) => Included(k.encode()),
-                    Excluded(k) => Excluded(k.encode()),
-                    Unbounded => Unbounded,
-                };
+                let (start_bound, end_bound) = Self::encode_bounds(start_bound, end_bound);
                 Box::new(index.values_range(start_bound, end_bound))
             }
             NumericIndex::Immutable(index) => Box::new(index.values_range(start_bound, end_bound)),
@@ -55,6 +55,16 @@ impl<T: Encodable + Numericable> MutableNumericIndex<T> {
         self.map.range((start_bound, end_bound)).map(|(_, v)| *v)
     }
 
+    pub fn ordered_values_range(
+        &self,
+        start_bound: Bound<Vec<u8>>,
+        end_bound: Bound<Vec<u8>>,
+    ) -> impl DoubleEndedIterator<Item = (T, PointOffsetType)> + '_ {
+        self.map
+            .range((start_bound, end_bound))
+            .map(|(key, idx)| (T::decode_key(key).1, *idx))
+    }
+
     fn add_value(&mut self, id: PointOffsetType, value: T) -> OperationResult<()> {
         let key = value.encode_key(id);
         self.db_wrapper.put(&key, id.to_be_bytes())?;
@@ -423,3 +423,34 @@ fn test_empty_cardinality(#[case] immutable: bool) {
         },
     );
 }
+
+#[rstest]
+#[case(true)]
+#[case(false)]
+fn test_ordered_values(#[case] immutable: bool) {
+    let (_temp_dir, index) = random_index(100, 2, immutable);
+
+    let range = Range {
+        gt: None,
+        gte: Some(20.0),
+        lt: None,
+        lte: Some(80.0),
+    };
+
+    let ascending = index.ordered_values(&range).collect_vec();
+    assert!(!ascending.is_empty());
+    assert!(ascending.windows(2).all(|pair| pair[0].0 <= pair[1].0));
+    assert!(ascending.iter().all(|(value, _)| range.check_range(*value)));
+    for (value, idx) in &ascending {
+        assert!(index.get_values(*idx).unwrap().contains(value));
+    }
+
+    let expected_count = (0..100)
+        .flat_map(|idx| index.get_values(idx).unwrap().to_vec())
+        .filter(|value| range.check_range(*value))
+        .count();
+    assert_eq!(ascending.len(), expected_count);
+
+    let descending = index.ordered_values(&range).rev().collect_vec();
+    assert_eq!(descending, ascending.into_iter().rev().collect_vec(),);
+}
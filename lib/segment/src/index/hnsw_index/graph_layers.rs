@@ -16,6 +16,7 @@ use crate::index::hnsw_index::entry_points::EntryPoints;
 use crate::index::hnsw_index::graph_links::GraphLinksConverter;
 use crate::index::hnsw_index::point_scorer::FilteredScorer;
 use crate::index::hnsw_index::search_context::SearchContext;
+use crate::index::query_arena::{QueryArena, QueryArenaPool};
 use crate::index::visited_pool::{VisitedListHandle, VisitedPool};
 
 pub type LinkContainer = Vec<PointOffsetType>;
@@ -47,6 +48,9 @@ pub struct GraphLayers<TGraphLinks: GraphLinks> {
 
     #[serde(skip)]
     pub(super) visited_pool: VisitedPool,
+
+    #[serde(skip)]
+    pub(super) query_arena_pool: QueryArenaPool,
 }
 
 pub trait GraphLayersBase {
@@ -68,7 +72,8 @@ pub trait GraphLayersBase {
         points_scorer: &mut FilteredScorer,
     ) {
         let limit = self.get_m(level);
-        let mut points_ids: Vec<PointOffsetType> = Vec::with_capacity(2 * limit);
+        let mut points_ids = std::mem::take(&mut searcher.links);
+        points_ids.reserve(2 * limit);
 
         while let Some(candidate) = searcher.candidates.pop() {
             if candidate.score < searcher.lower_bound() {
@@ -88,6 +93,8 @@ pub trait GraphLayersBase {
                 visited_list.check_and_update_visited(score_point.idx);
             });
         }
+
+        searcher.links = points_ids;
     }
 
     fn search_on_level(
@@ -96,13 +103,31 @@ pub trait GraphLayersBase {
         level: usize,
         ef: usize,
         points_scorer: &mut FilteredScorer,
+    ) -> FixedLengthPriorityQueue<ScoredPointOffset> {
+        self.search_on_level_in(
+            level_entry,
+            level,
+            ef,
+            points_scorer,
+            &mut QueryArena::default(),
+        )
+    }
+
+    /// Same as [`Self::search_on_level`], but temporary buffers are taken from the `query_arena`
+    fn search_on_level_in(
+        &self,
+        level_entry: ScoredPointOffset,
+        level: usize,
+        ef: usize,
+        points_scorer: &mut FilteredScorer,
+        query_arena: &mut QueryArena,
     ) -> FixedLengthPriorityQueue<ScoredPointOffset> {
         let mut visited_list = self.get_visited_list_from_pool();
         visited_list.check_and_update_visited(level_entry.idx);
-        let mut search_context = SearchContext::new(level_entry, ef);
+        let mut search_context = SearchContext::new_in(level_entry, ef, query_arena);
 
         self._search_on_level(&mut search_context, level, &mut visited_list, points_scorer);
-        search_context.nearest
+        search_context.release_into(query_arena)
     }
 
     /// Greedy searches for entry point of level `target_level`.
@@ -212,13 +237,23 @@ impl<TGraphLinks: GraphLinks> GraphLayers<TGraphLinks> {
             return Vec::default();
         };
 
+        let mut query_arena = self.query_arena_pool.get();
+        points_scorer = points_scorer.with_buffer(QueryArena::take(&mut query_arena.scores));
+
         let zero_level_entry = self.search_entry(
             entry_point.point_id,
             entry_point.level,
             0,
             &mut points_scorer,
         );
-        let nearest = self.search_on_level(zero_level_entry, 0, max(top, ef), &mut points_scorer);
+        let nearest = self.search_on_level_in(
+            zero_level_entry,
+            0,
+            max(top, ef),
+            &mut points_scorer,
+            &mut query_arena,
+        );
+        query_arena.scores = points_scorer.into_buffer();
         nearest.into_iter().take(top).collect_vec()
     }
 
@@ -270,6 +305,7 @@ where
                         links,
                         entry_points: legacy.entry_points,
                         visited_pool: VisitedPool::new(),
+                        query_arena_pool: QueryArenaPool::new(),
                     };
                     slf.save(graph_path)?;
                     Ok(slf)
@@ -346,6 +382,7 @@ mod tests {
             links: GraphLinksRam::default(),
             entry_points: EntryPoints::new(entry_points_num),
             visited_pool: VisitedPool::new(),
+            query_arena_pool: QueryArenaPool::new(),
         };
 
         let mut graph_links = vec![vec![Vec::new()]; num_vectors];
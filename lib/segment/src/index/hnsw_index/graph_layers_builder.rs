@@ -13,10 +13,13 @@ use rand::Rng;
 use super::graph_links::GraphLinks;
 use crate::common::operation_error::OperationResult;
 use crate::index::hnsw_index::entry_points::EntryPoints;
-use crate::index::hnsw_index::graph_layers::{GraphLayers, GraphLayersBase, LinkContainer};
+use crate::index::hnsw_index::graph_layers::{
+    GraphLayers, GraphLayersBase, LayersContainer, LinkContainer,
+};
 use crate::index::hnsw_index::graph_links::GraphLinksConverter;
 use crate::index::hnsw_index::point_scorer::FilteredScorer;
 use crate::index::hnsw_index::search_context::SearchContext;
+use crate::index::query_arena::QueryArenaPool;
 use crate::index::visited_pool::{VisitedListHandle, VisitedPool};
 
 pub type LockedLinkContainer = RwLock<LinkContainer>;
@@ -98,6 +101,7 @@ impl GraphLayersBuilder {
             links,
             entry_points: self.entry_points.into_inner(),
             visited_pool: self.visited_pool,
+            query_arena_pool: QueryArenaPool::new(),
         })
     }
 
@@ -452,6 +456,22 @@ impl GraphLayersBuilder {
         self.ready_list.write().set(point_id as usize, true);
     }
 
+    /// Insert point with already known links, e.g. transferred from another graph
+    ///
+    /// Levels of the point must be set with [`Self::set_levels`] beforehand.
+    pub fn add_point_with_links(&self, point_id: PointOffsetType, links: LayersContainer) {
+        let level = self.get_point_level(point_id);
+        debug_assert_eq!(level + 1, links.len());
+
+        for (level_links, links) in self.links_layers[point_id as usize].iter().zip(links) {
+            *level_links.write() = links;
+        }
+        self.entry_points
+            .lock()
+            .new_point(point_id, level, |_point_id| true);
+        self.ready_list.write().set(point_id as usize, true);
+    }
+
     /// This function returns average number of links per node in HNSW graph
     /// on specified level.
     ///
@@ -5,6 +5,7 @@ use std::sync::atomic::AtomicBool;
 use std::sync::Arc;
 
 use atomic_refcell::AtomicRefCell;
+use bitvec::prelude::BitVec;
 use common::types::{PointOffsetType, ScoredPointOffset};
 use log::debug;
 use memory::mmap_ops;
@@ -27,17 +28,20 @@ use crate::index::hnsw_index::graph_layers::GraphLayers;
 use crate::index::hnsw_index::graph_layers_builder::GraphLayersBuilder;
 use crate::index::hnsw_index::max_rayon_threads;
 use crate::index::hnsw_index::point_scorer::FilteredScorer;
+use crate::index::hnsw_index::reused_graph::ReusedGraph;
 use crate::index::query_estimator::adjust_to_available_vectors;
 use crate::index::sample_estimation::sample_check_cardinality;
 use crate::index::struct_payload_index::StructPayloadIndex;
 use crate::index::visited_pool::{VisitedListHandle, VisitedPool};
 use crate::index::{PayloadIndex, VectorIndex};
+use crate::payload_storage::FilterContext;
 use crate::telemetry::VectorIndexSearchesTelemetry;
 use crate::types::Condition::Field;
 use crate::types::{
     default_quantization_ignore_value, default_quantization_oversampling_value, FieldCondition,
     Filter, HnswConfig, QuantizationSearchParams, SearchParams, VECTOR_ELEMENT_SIZE,
 };
+use crate::utils::resource_usage;
 use crate::vector_storage::quantized::quantized_vectors::QuantizedVectors;
 use crate::vector_storage::query::discovery_query::DiscoveryQuery;
 use crate::vector_storage::{
@@ -158,6 +162,234 @@ impl<TGraphLinks: GraphLinks> HNSWIndex<TGraphLinks> {
         Ok(())
     }
 
+    /// Build the index, reusing links of the `reused_graph` if possible
+    ///
+    /// Only points, which are not in the reused graph, are searched and linked.
+    /// Graph is built from scratch, if it has other parameters or lost too many links.
+    pub fn build_index_with_reused_graph(
+        &mut self,
+        reused_graph: Option<ReusedGraph>,
+        stopped: &AtomicBool,
+    ) -> OperationResult<()> {
+        // Build main index graph
+        let id_tracker = self.id_tracker.borrow();
+        let vector_storage = self.vector_storage.borrow();
+        let mut rng = thread_rng();
+
+        let total_vector_count = vector_storage.total_vector_count();
+        let deleted_bitslice = vector_storage.deleted_vector_bitslice();
+
+        debug!("building HNSW for {} vectors", total_vector_count);
+        let indexing_threshold = self.config.full_scan_threshold;
+        let mut graph_layers_builder = GraphLayersBuilder::new(
+            total_vector_count,
+            self.config.m,
+            self.config.m0,
+            self.config.ef_construct,
+            (total_vector_count
+                .checked_div(indexing_threshold)
+                .unwrap_or(0)
+                * 10)
+                .max(1),
+            HNSW_USE_HEURISTIC,
+        );
+
+        let pool = rayon::ThreadPoolBuilder::new()
+            .thread_name(|idx| format!("hnsw-build-{idx}"))
+            .num_threads(max_rayon_threads(self.config.max_indexing_threads))
+            .build()?;
+
+        let is_reused = self.add_reused_graph(reused_graph, &mut graph_layers_builder, stopped)?;
+
+        for vector_id in id_tracker.iter_ids_excluding(deleted_bitslice) {
+            check_process_stopped(stopped)?;
+            if is_reused[vector_id as usize] {
+                continue;
+            }
+            let level = graph_layers_builder.get_random_layer(&mut rng);
+            graph_layers_builder.set_levels(vector_id, level);
+        }
+
+        let mut indexed_vectors = 0;
+
+        if self.config.m > 0 {
+            let ids: Vec<_> = id_tracker
+                .iter_ids_excluding(deleted_bitslice)
+                .filter(|&vector_id| !is_reused[vector_id as usize])
+                .collect();
+
+            indexed_vectors = is_reused.count_ones() + ids.len();
+
+            self.link_points(&pool, stopped, &graph_layers_builder, ids, None)?;
+
+            debug!("finish main graph");
+        } else {
+            debug!("skip building main HNSW graph");
+        }
+
+        let payload_m = self.config.payload_m.unwrap_or(self.config.m);
+
+        if payload_m > 0 {
+            let indexed_payload_vectors =
+                self.build_payload_blocks(&pool, stopped, &mut graph_layers_builder, payload_m)?;
+
+            debug_assert!(indexed_vectors >= indexed_payload_vectors || self.config.m == 0);
+            indexed_vectors = indexed_vectors.max(indexed_payload_vectors);
+            debug_assert!(indexed_payload_vectors <= total_vector_count);
+        } else {
+            debug!("skip building additional HNSW links");
+        }
+
+        // Pool threads are dedicated to this build, account them to the building thread
+        resource_usage::add_helpers_usage(resource_usage::pool_usage(&pool));
+
+        self.config.indexed_vector_count.replace(indexed_vectors);
+
+        let graph_links_path = GraphLayers::<TGraphLinks>::get_links_path(&self.path);
+        self.graph = Some(graph_layers_builder.into_graph_layers(Some(&graph_links_path))?);
+
+        #[cfg(debug_assertions)]
+        {
+            let graph = self.graph.as_ref().unwrap();
+            for (idx, deleted) in deleted_bitslice.iter().enumerate() {
+                if *deleted {
+                    debug_assert!(graph.links.links(idx as PointOffsetType, 0).is_empty());
+                }
+            }
+        }
+
+        debug!("finish additional payload field indexing");
+        self.save()
+    }
+
+    /// Insert points of the `reused_graph` into the builder, keeping their links
+    ///
+    /// Returns mask of the reused points, which don't need to be linked again.
+    /// Nothing is reused, if the graph has other parameters or lost too many links.
+    fn add_reused_graph(
+        &self,
+        reused_graph: Option<ReusedGraph>,
+        graph_layers_builder: &mut GraphLayersBuilder,
+        stopped: &AtomicBool,
+    ) -> OperationResult<BitVec> {
+        let id_tracker = self.id_tracker.borrow();
+        let vector_storage = self.vector_storage.borrow();
+
+        let total_vector_count = vector_storage.total_vector_count();
+        let deleted_bitslice = vector_storage.deleted_vector_bitslice();
+
+        let mut is_reused = BitVec::repeat(false, total_vector_count);
+
+        let reused_graph = reused_graph
+            .filter(|_| self.config.m > 0)
+            .and_then(|mut graph| {
+                if !graph.is_compatible(&self.config) {
+                    debug!("HNSW parameters are changed, building graph from scratch");
+                    return None;
+                }
+                let mut available = BitVec::repeat(false, total_vector_count);
+                for vector_id in id_tracker.iter_ids_excluding(deleted_bitslice) {
+                    available.set(vector_id as usize, true);
+                }
+                graph.retain_available(&available);
+                if graph.is_degraded() {
+                    debug!("reused HNSW graph is degraded, building graph from scratch");
+                    return None;
+                }
+                Some(graph)
+            });
+
+        if let Some(reused_graph) = reused_graph {
+            debug!("reusing HNSW graph of {} points", reused_graph.num_points());
+            for (vector_id, links) in reused_graph.into_points() {
+                check_process_stopped(stopped)?;
+                graph_layers_builder.set_levels(vector_id, links.len() - 1);
+                // Point without links on the level 0 is unreachable, link it as a new one
+                if !links[0].is_empty() {
+                    graph_layers_builder.add_point_with_links(vector_id, links);
+                    is_reused.set(vector_id as usize, true);
+                }
+            }
+        }
+
+        Ok(is_reused)
+    }
+
+    /// Build additional graphs for payload blocks of indexed fields and merge them into the builder
+    ///
+    /// Returns number of points indexed in payload blocks.
+    fn build_payload_blocks(
+        &self,
+        pool: &ThreadPool,
+        stopped: &AtomicBool,
+        graph_layers_builder: &mut GraphLayersBuilder,
+        payload_m: usize,
+    ) -> OperationResult<usize> {
+        let total_vector_count = self.vector_storage.borrow().total_vector_count();
+        let payload_index = self.payload_index.borrow();
+
+        let visited_pool = VisitedPool::new();
+        let mut block_filter_list = visited_pool.get(total_vector_count);
+        let visits_iteration = block_filter_list.get_current_iteration_id();
+
+        // Calculate true average number of links per vertex in the HNSW graph
+        // to better estimate percolation threshold
+        let average_links_per_0_level = graph_layers_builder.get_average_connectivity_on_level(0);
+        let average_links_per_0_level_int = (average_links_per_0_level as usize).max(1);
+
+        for (field, _) in payload_index.indexed_fields() {
+            debug!("building additional index for field {}", &field);
+
+            // It is expected, that graph will become disconnected less than
+            // $1/m$ points left.
+            // So blocks larger than $1/m$ are not needed.
+            // We add multiplier for the extra safety.
+            let percolation_multiplier = 4;
+            let max_block_size = if self.config.m > 0 {
+                total_vector_count / average_links_per_0_level_int * percolation_multiplier
+            } else {
+                usize::MAX
+            };
+            let min_block_size = self.config.full_scan_threshold;
+
+            for payload_block in payload_index.payload_blocks(&field, min_block_size) {
+                check_process_stopped(stopped)?;
+                if payload_block.cardinality > max_block_size {
+                    continue;
+                }
+                // ToDo: reuse graph layer for same payload
+                let mut additional_graph = GraphLayersBuilder::new_with_params(
+                    total_vector_count,
+                    payload_m,
+                    self.config.payload_m0.unwrap_or(self.config.m0),
+                    self.config.ef_construct,
+                    1,
+                    HNSW_USE_HEURISTIC,
+                    false,
+                );
+                self.build_filtered_graph(
+                    pool,
+                    stopped,
+                    &mut additional_graph,
+                    payload_block.condition,
+                    &mut block_filter_list,
+                )?;
+                graph_layers_builder.merge_from_other(additional_graph);
+            }
+        }
+
+        Ok(block_filter_list.count_visits_since(visits_iteration))
+    }
+
+    /// Graph of this index, remapped into ids of another segment
+    ///
+    /// See [`ReusedGraph::new`] for the meaning of `old_to_new`.
+    pub fn reusable_graph(&self, old_to_new: &[Option<PointOffsetType>]) -> Option<ReusedGraph> {
+        self.graph
+            .as_ref()
+            .map(|graph| ReusedGraph::new(graph, old_to_new))
+    }
+
     pub fn build_filtered_graph(
         &self,
         pool: &ThreadPool,
@@ -170,10 +402,8 @@ impl<TGraphLinks: GraphLinks> HNSWIndex<TGraphLinks> {
 
         let filter = Filter::new_must(Field(condition));
 
-        let id_tracker = self.id_tracker.borrow();
         let payload_index = self.payload_index.borrow();
         let vector_storage = self.vector_storage.borrow();
-        let quantized_vectors = self.quantized_vectors.borrow();
 
         let deleted_bitslice = vector_storage.deleted_vector_bitslice();
 
@@ -200,50 +430,77 @@ impl<TGraphLinks: GraphLinks> HNSWIndex<TGraphLinks> {
             }
         }
 
-        let insert_points = |block_point_id| {
+        self.link_points(
+            pool,
+            stopped,
+            graph_layers_builder,
+            points_to_index,
+            Some(block_filter_list),
+        )
+    }
+
+    /// Search neighbours of the `points` and link them into the graph
+    ///
+    /// With `block_filter_list`, points are only linked to the points visited in it.
+    fn link_points(
+        &self,
+        pool: &ThreadPool,
+        stopped: &AtomicBool,
+        graph_layers_builder: &GraphLayersBuilder,
+        points: Vec<PointOffsetType>,
+        block_filter_list: Option<&VisitedListHandle>,
+    ) -> OperationResult<()> {
+        let id_tracker = self.id_tracker.borrow();
+        let vector_storage = self.vector_storage.borrow();
+        let quantized_vectors = self.quantized_vectors.borrow();
+
+        let insert_point = |point_id| {
             check_process_stopped(stopped)?;
 
-            let vector = vector_storage.get_vector(block_point_id);
+            let vector = vector_storage.get_vector(point_id);
             let vector = vector.as_vec_ref().into();
             let raw_scorer = match quantized_vectors.as_ref() {
                 Some(quantized_storage) => quantized_storage.raw_scorer(
                     vector,
                     id_tracker.deleted_point_bitslice(),
-                    deleted_bitslice,
+                    vector_storage.deleted_vector_bitslice(),
                     stopped,
                 ),
                 None => {
                     new_raw_scorer(vector, &vector_storage, id_tracker.deleted_point_bitslice())
                 }
             }?;
-            let block_condition_checker = BuildConditionChecker {
-                filter_list: block_filter_list,
-                current_point: block_point_id,
-            };
-            let points_scorer =
-                FilteredScorer::new(raw_scorer.as_ref(), Some(&block_condition_checker));
-
-            graph_layers_builder.link_new_point(block_point_id, points_scorer);
+            let block_condition_checker =
+                block_filter_list.map(|filter_list| BuildConditionChecker {
+                    filter_list,
+                    current_point: point_id,
+                });
+            let points_scorer = FilteredScorer::new(
+                raw_scorer.as_ref(),
+                block_condition_checker
+                    .as_ref()
+                    .map(|checker| checker as &dyn FilterContext),
+            );
+
+            graph_layers_builder.link_new_point(point_id, points_scorer);
             Ok::<_, OperationError>(())
         };
 
-        let first_points = points_to_index
-            .len()
-            .min(SINGLE_THREADED_HNSW_BUILD_THRESHOLD);
+        let first_points = points.len().min(SINGLE_THREADED_HNSW_BUILD_THRESHOLD);
 
         // First index points in single thread so ensure warm start for parallel indexing process
-        for point_id in points_to_index[..first_points].iter().copied() {
-            insert_points(point_id)?;
+        for point_id in points[..first_points].iter().copied() {
+            insert_point(point_id)?;
         }
         // Once initial structure is built, index remaining points in parallel
         // So that each thread will insert points in different parts of the graph,
         // it is less likely that they will compete for the same locks
-        if points_to_index.len() > first_points {
+        if points.len() > first_points {
             pool.install(|| {
-                points_to_index
+                points
                     .into_par_iter()
                     .skip(first_points)
-                    .try_for_each(insert_points)
+                    .try_for_each(insert_point)
             })?;
         }
         Ok(())
@@ -634,168 +891,7 @@ impl<TGraphLinks: GraphLinks> VectorIndex for HNSWIndex<TGraphLinks> {
     }
 
     fn build_index(&mut self, stopped: &AtomicBool) -> OperationResult<()> {
-        // Build main index graph
-        let id_tracker = self.id_tracker.borrow();
-        let vector_storage = self.vector_storage.borrow();
-        let quantized_vectors = self.quantized_vectors.borrow();
-        let mut rng = thread_rng();
-
-        let total_vector_count = vector_storage.total_vector_count();
-        let deleted_bitslice = vector_storage.deleted_vector_bitslice();
-
-        debug!("building HNSW for {} vectors", total_vector_count);
-        let indexing_threshold = self.config.full_scan_threshold;
-        let mut graph_layers_builder = GraphLayersBuilder::new(
-            total_vector_count,
-            self.config.m,
-            self.config.m0,
-            self.config.ef_construct,
-            (total_vector_count
-                .checked_div(indexing_threshold)
-                .unwrap_or(0)
-                * 10)
-                .max(1),
-            HNSW_USE_HEURISTIC,
-        );
-
-        let pool = rayon::ThreadPoolBuilder::new()
-            .thread_name(|idx| format!("hnsw-build-{idx}"))
-            .num_threads(max_rayon_threads(self.config.max_indexing_threads))
-            .build()?;
-
-        for vector_id in id_tracker.iter_ids_excluding(deleted_bitslice) {
-            check_process_stopped(stopped)?;
-            let level = graph_layers_builder.get_random_layer(&mut rng);
-            graph_layers_builder.set_levels(vector_id, level);
-        }
-
-        let mut indexed_vectors = 0;
-
-        if self.config.m > 0 {
-            let mut ids_iterator = id_tracker.iter_ids_excluding(deleted_bitslice);
-
-            let first_few_ids: Vec<_> = ids_iterator
-                .by_ref()
-                .take(SINGLE_THREADED_HNSW_BUILD_THRESHOLD)
-                .collect();
-            let ids: Vec<_> = ids_iterator.collect();
-
-            indexed_vectors = ids.len() + first_few_ids.len();
-
-            let insert_point = |vector_id| {
-                check_process_stopped(stopped)?;
-                let vector = vector_storage.get_vector(vector_id);
-                let vector = vector.as_vec_ref().into();
-                let raw_scorer = if let Some(quantized_storage) = quantized_vectors.as_ref() {
-                    quantized_storage.raw_scorer(
-                        vector,
-                        id_tracker.deleted_point_bitslice(),
-                        vector_storage.deleted_vector_bitslice(),
-                        stopped,
-                    )
-                } else {
-                    new_raw_scorer(vector, &vector_storage, id_tracker.deleted_point_bitslice())
-                }?;
-                let points_scorer = FilteredScorer::new(raw_scorer.as_ref(), None);
-
-                graph_layers_builder.link_new_point(vector_id, points_scorer);
-                Ok::<_, OperationError>(())
-            };
-
-            for vector_id in first_few_ids {
-                insert_point(vector_id)?;
-            }
-
-            if !ids.is_empty() {
-                pool.install(|| ids.into_par_iter().try_for_each(insert_point))?;
-            }
-
-            debug!("finish main graph");
-        } else {
-            debug!("skip building main HNSW graph");
-        }
-
-        let visited_pool = VisitedPool::new();
-        let mut block_filter_list = visited_pool.get(total_vector_count);
-        let visits_iteration = block_filter_list.get_current_iteration_id();
-
-        let payload_index = self.payload_index.borrow();
-        let payload_m = self.config.payload_m.unwrap_or(self.config.m);
-
-        if payload_m > 0 {
-            // Calculate true average number of links per vertex in the HNSW graph
-            // to better estimate percolation threshold
-            let average_links_per_0_level =
-                graph_layers_builder.get_average_connectivity_on_level(0);
-            let average_links_per_0_level_int = (average_links_per_0_level as usize).max(1);
-
-            for (field, _) in payload_index.indexed_fields() {
-                debug!("building additional index for field {}", &field);
-
-                // It is expected, that graph will become disconnected less than
-                // $1/m$ points left.
-                // So blocks larger than $1/m$ are not needed.
-                // We add multiplier for the extra safety.
-                let percolation_multiplier = 4;
-                let max_block_size = if self.config.m > 0 {
-                    total_vector_count / average_links_per_0_level_int * percolation_multiplier
-                } else {
-                    usize::MAX
-                };
-                let min_block_size = indexing_threshold;
-
-                for payload_block in payload_index.payload_blocks(&field, min_block_size) {
-                    check_process_stopped(stopped)?;
-                    if payload_block.cardinality > max_block_size {
-                        continue;
-                    }
-                    // ToDo: reuse graph layer for same payload
-                    let mut additional_graph = GraphLayersBuilder::new_with_params(
-                        total_vector_count,
-                        payload_m,
-                        self.config.payload_m0.unwrap_or(self.config.m0),
-                        self.config.ef_construct,
-                        1,
-                        HNSW_USE_HEURISTIC,
-                        false,
-                    );
-                    self.build_filtered_graph(
-                        &pool,
-                        stopped,
-                        &mut additional_graph,
-                        payload_block.condition,
-                        &mut block_filter_list,
-                    )?;
-                    graph_layers_builder.merge_from_other(additional_graph);
-                }
-            }
-
-            let indexed_payload_vectors = block_filter_list.count_visits_since(visits_iteration);
-
-            debug_assert!(indexed_vectors >= indexed_payload_vectors || self.config.m == 0);
-            indexed_vectors = indexed_vectors.max(indexed_payload_vectors);
-            debug_assert!(indexed_payload_vectors <= total_vector_count);
-        } else {
-            debug!("skip building additional HNSW links");
-        }
-
-        self.config.indexed_vector_count.replace(indexed_vectors);
-
-        let graph_links_path = GraphLayers::<TGraphLinks>::get_links_path(&self.path);
-        self.graph = Some(graph_layers_builder.into_graph_layers(Some(&graph_links_path))?);
-
-        #[cfg(debug_assertions)]
-        {
-            let graph = self.graph.as_ref().unwrap();
-            for (idx, deleted) in deleted_bitslice.iter().enumerate() {
-                if *deleted {
-                    debug_assert!(graph.links.links(idx as PointOffsetType, 0).is_empty());
-                }
-            }
-        }
-
-        debug!("finish additional payload field indexing");
-        self.save()
+        self.build_index_with_reused_graph(None, stopped)
     }
 
     fn get_telemetry_data(&self) -> VectorIndexSearchesTelemetry {
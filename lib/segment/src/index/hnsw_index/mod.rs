@@ -7,6 +7,7 @@ pub mod graph_layers_builder;
 pub mod graph_links;
 pub mod hnsw;
 pub mod point_scorer;
+pub mod reused_graph;
 mod search_context;
 
 #[cfg(test)]
@@ -21,6 +21,17 @@ impl<'a> FilteredScorer<'a> {
         }
     }
 
+    /// Use given buffer for scores, so its memory is reused instead of a new allocation
+    pub fn with_buffer(mut self, points_buffer: Vec<ScoredPointOffset>) -> Self {
+        self.points_buffer = points_buffer;
+        self
+    }
+
+    /// Take the scores buffer back, e.g. to reuse it in the next query
+    pub fn into_buffer(self) -> Vec<ScoredPointOffset> {
+        self.points_buffer
+    }
+
     pub fn check_vector(&self, point_id: PointOffsetType) -> bool {
         match self.filter_context {
             None => self.raw_scorer.check_vector(point_id),
@@ -0,0 +1,111 @@
+use bitvec::prelude::{BitSlice, BitVec};
+use common::types::PointOffsetType;
+
+use crate::index::hnsw_index::config::HnswGraphConfig;
+use crate::index::hnsw_index::graph_layers::{GraphLayers, LayersContainer};
+use crate::index::hnsw_index::graph_links::GraphLinks;
+
+/// Max share of the links of the reused graph, which may be lost because of removed points.
+/// Graph, which lost more links, is degraded and is built from scratch instead.
+const MAX_DROPPED_LINKS_RATIO: f64 = 0.1;
+
+/// HNSW graph of an existing index, transferred into an index of another segment
+///
+/// Points of the reused graph are inserted into the new graph with their links as is, so only
+/// the points, which were not indexed before, need to be searched and linked.
+/// It makes building of a large segment with a few new points much cheaper than a full rebuild.
+pub struct ReusedGraph {
+    m: usize,
+    m0: usize,
+    ef_construct: usize,
+    /// New id of the point and its links on each level, in terms of new ids
+    points: Vec<(PointOffsetType, LayersContainer)>,
+    total_links: usize,
+    dropped_links: usize,
+}
+
+impl ReusedGraph {
+    /// Remap points of the `graph` into new ids
+    ///
+    /// `old_to_new` maps ids of the `graph` into ids of the new segment.
+    /// Points without a new id are not transferred, links to them are dropped.
+    pub fn new<TGraphLinks: GraphLinks>(
+        graph: &GraphLayers<TGraphLinks>,
+        old_to_new: &[Option<PointOffsetType>],
+    ) -> Self {
+        let mut points = Vec::new();
+        let mut total_links = 0;
+        let mut dropped_links = 0;
+
+        for (old_id, new_id) in old_to_new.iter().enumerate().take(graph.num_points()) {
+            let Some(new_id) = *new_id else {
+                continue;
+            };
+            let old_id = old_id as PointOffsetType;
+            if graph.links.links(old_id, 0).is_empty() {
+                // Point is not indexed in the old graph
+                continue;
+            }
+
+            let mut layers = LayersContainer::with_capacity(graph.point_level(old_id) + 1);
+            for level in 0..=graph.point_level(old_id) {
+                let old_links = graph.links.links(old_id, level);
+                let links: Vec<_> = old_links
+                    .iter()
+                    .filter_map(|&link| old_to_new.get(link as usize).copied().flatten())
+                    .collect();
+                total_links += old_links.len();
+                dropped_links += old_links.len() - links.len();
+                layers.push(links);
+            }
+            points.push((new_id, layers));
+        }
+
+        Self {
+            m: graph.m,
+            m0: graph.m0,
+            ef_construct: graph.ef_construct,
+            points,
+            total_links,
+            dropped_links,
+        }
+    }
+
+    pub fn num_points(&self) -> usize {
+        self.points.len()
+    }
+
+    /// Graph was built with the same parameters, as the new one is going to be
+    pub(super) fn is_compatible(&self, config: &HnswGraphConfig) -> bool {
+        self.m == config.m && self.m0 == config.m0 && self.ef_construct == config.ef_construct
+    }
+
+    /// Remove points, which are not `available` in the new segment, and all links to them
+    pub fn retain_available(&mut self, available: &BitSlice) {
+        let is_available = |id: PointOffsetType| available.get(id as usize).map_or(false, |x| *x);
+        self.points.retain(|(point_id, _)| is_available(*point_id));
+
+        // Links may only point to the reused points, so that levels of the linked points match
+        let mut is_reused = BitVec::repeat(false, available.len());
+        for (point_id, _) in &self.points {
+            is_reused.set(*point_id as usize, true);
+        }
+
+        for (_, layers) in &mut self.points {
+            for links in layers.iter_mut() {
+                let links_count = links.len();
+                links.retain(|&link| is_reused.get(link as usize).map_or(false, |x| *x));
+                self.dropped_links += links_count - links.len();
+            }
+        }
+    }
+
+    /// Too many links are lost, graph should be built from scratch
+    pub fn is_degraded(&self) -> bool {
+        self.dropped_links as f64 > self.total_links as f64 * MAX_DROPPED_LINKS_RATIO
+    }
+
+    pub(super) fn into_points(self) -> Vec<(PointOffsetType, LayersContainer)> {
+        self.points
+    }
+}
@@ -2,15 +2,19 @@ use std::collections::BinaryHeap;
 use std::iter::FromIterator;
 
 use common::fixed_length_priority_queue::FixedLengthPriorityQueue;
-use common::types::{ScoreType, ScoredPointOffset};
+use common::types::{PointOffsetType, ScoreType, ScoredPointOffset};
 use num_traits::float::FloatCore;
 
+use crate::index::query_arena::QueryArena;
+
 /// Structure that holds context of the search
 pub struct SearchContext {
     /// Overall nearest points found so far
     pub nearest: FixedLengthPriorityQueue<ScoredPointOffset>,
     /// Current candidates to process
     pub candidates: BinaryHeap<ScoredPointOffset>,
+    /// Buffer for links of the currently processed candidate
+    pub links: Vec<PointOffsetType>,
 }
 
 impl SearchContext {
@@ -20,9 +24,34 @@ impl SearchContext {
         SearchContext {
             nearest,
             candidates: BinaryHeap::from_iter([entry_point]),
+            links: Vec::new(),
+        }
+    }
+
+    /// Same as [`Self::new`], but candidates and links buffers are taken from the `arena`
+    pub fn new_in(entry_point: ScoredPointOffset, ef: usize, arena: &mut QueryArena) -> Self {
+        let mut nearest = FixedLengthPriorityQueue::new(ef);
+        nearest.push(entry_point);
+        // Heapify of an empty vector is free, capacity is preserved
+        let mut candidates = BinaryHeap::from(QueryArena::take(&mut arena.candidates));
+        candidates.push(entry_point);
+        SearchContext {
+            nearest,
+            candidates,
+            links: QueryArena::take(&mut arena.links),
         }
     }
 
+    /// Return buffers back to the `arena`, consuming the context into the found nearest points
+    pub fn release_into(
+        self,
+        arena: &mut QueryArena,
+    ) -> FixedLengthPriorityQueue<ScoredPointOffset> {
+        arena.candidates = self.candidates.into_vec();
+        arena.links = self.links;
+        self.nearest
+    }
+
     pub fn lower_bound(&self) -> ScoreType {
         match self.nearest.top() {
             None => ScoreType::min_value(),
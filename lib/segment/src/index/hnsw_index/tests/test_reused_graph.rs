@@ -0,0 +1,186 @@
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+use std::sync::atomic::AtomicBool;
+
+use bitvec::prelude::BitVec;
+use common::types::PointOffsetType;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use tempfile::Builder;
+
+use crate::data_types::vectors::{only_default_vector, DEFAULT_VECTOR_NAME};
+use crate::entry::entry_point::SegmentEntry;
+use crate::fixtures::index_fixtures::random_vector;
+use crate::index::hnsw_index::graph_links::{GraphLinks, GraphLinksRam};
+use crate::index::hnsw_index::hnsw::HNSWIndex;
+use crate::index::VectorIndex;
+use crate::segment::Segment;
+use crate::segment_constructor::build_segment;
+use crate::types::{
+    Distance, HnswConfig, Indexes, SearchParams, SegmentConfig, SeqNumberType, VectorDataConfig,
+    VectorStorageType,
+};
+
+const DIM: usize = 16;
+
+fn open_hnsw_index(path: &Path, segment: &Segment) -> HNSWIndex<GraphLinksRam> {
+    HNSWIndex::<GraphLinksRam>::open(
+        path,
+        segment.id_tracker.clone(),
+        segment.vector_data[DEFAULT_VECTOR_NAME]
+            .vector_storage
+            .clone(),
+        Default::default(),
+        segment.payload_index.clone(),
+        HnswConfig {
+            m: 16,
+            ef_construct: 64,
+            full_scan_threshold: 1,
+            max_indexing_threads: 2,
+            on_disk: Some(false),
+            payload_m: None,
+        },
+    )
+    .unwrap()
+}
+
+fn upsert_random_points(segment: &mut Segment, ids: std::ops::Range<u64>, rng: &mut StdRng) {
+    for n in ids {
+        let vector = random_vector(rng, DIM);
+        segment
+            .upsert_point(n as SeqNumberType, n.into(), only_default_vector(&vector))
+            .unwrap();
+    }
+}
+
+#[test]
+fn test_reused_graph() {
+    let stopped = AtomicBool::new(false);
+    let num_vectors: u64 = 1_000;
+    let mut rng = StdRng::seed_from_u64(42);
+
+    let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+    let config = SegmentConfig {
+        vector_data: HashMap::from([(
+            DEFAULT_VECTOR_NAME.to_owned(),
+            VectorDataConfig {
+                size: DIM,
+                distance: Distance::Cosine,
+                storage_type: VectorStorageType::Memory,
+                index: Indexes::Plain {},
+                quantization_config: None,
+                multivector_config: None,
+                datatype: None,
+            },
+        )]),
+        payload_storage_type: Default::default(),
+        sparse_vector_data: Default::default(),
+    };
+    let mut segment = build_segment(dir.path(), &config, true).unwrap();
+    upsert_random_points(&mut segment, 0..num_vectors, &mut rng);
+
+    let hnsw_dir = Builder::new().prefix("hnsw_dir").tempdir().unwrap();
+    let mut hnsw_index = open_hnsw_index(hnsw_dir.path(), &segment);
+    hnsw_index.build_index(&stopped).unwrap();
+
+    let identity: Vec<_> = (0..num_vectors as PointOffsetType).map(Some).collect();
+
+    // Graph reused as is has the same links
+    let reused_graph = hnsw_index.reusable_graph(&identity).unwrap();
+    assert_eq!(reused_graph.num_points(), num_vectors as usize);
+
+    let reused_dir = Builder::new().prefix("reused_hnsw_dir").tempdir().unwrap();
+    let mut reused_index = open_hnsw_index(reused_dir.path(), &segment);
+    reused_index
+        .build_index_with_reused_graph(Some(reused_graph), &stopped)
+        .unwrap();
+    assert_eq!(reused_index.indexed_vector_count(), num_vectors as usize);
+
+    let graph = hnsw_index.graph().unwrap();
+    let reused = reused_index.graph().unwrap();
+    for point_id in 0..num_vectors as PointOffsetType {
+        assert_eq!(graph.point_level(point_id), reused.point_level(point_id));
+        for level in 0..=graph.point_level(point_id) {
+            assert_eq!(
+                graph.links.links(point_id, level),
+                reused.links.links(point_id, level),
+            );
+        }
+    }
+
+    // Graph, which lost too many points, is not worth reusing
+    let mut available = BitVec::repeat(false, num_vectors as usize);
+    for point_id in 0..num_vectors as usize {
+        available.set(point_id, point_id % 3 != 0);
+    }
+    let mut degraded_graph = hnsw_index.reusable_graph(&identity).unwrap();
+    degraded_graph.retain_available(&available);
+    assert!(degraded_graph.is_degraded());
+
+    // New points are linked into the reused graph, removed ones are unlinked
+    for n in 0..num_vectors / 20 {
+        let point_id = rng.gen_range(0..num_vectors);
+        segment
+            .delete_point(2 * num_vectors + n, point_id.into())
+            .unwrap();
+    }
+    upsert_random_points(&mut segment, num_vectors..num_vectors + 200, &mut rng);
+
+    let extended_dir = Builder::new()
+        .prefix("extended_hnsw_dir")
+        .tempdir()
+        .unwrap();
+    let mut extended_index = open_hnsw_index(extended_dir.path(), &segment);
+    let reused_graph = hnsw_index.reusable_graph(&identity).unwrap();
+    extended_index
+        .build_index_with_reused_graph(Some(reused_graph), &stopped)
+        .unwrap();
+    assert_eq!(
+        extended_index.indexed_vector_count(),
+        segment.available_point_count()
+    );
+
+    let top = 10;
+    let attempts = 20;
+    let mut hits = 0;
+    for _ in 0..attempts {
+        let query = random_vector(&mut rng, DIM).into();
+
+        let exact_result = extended_index
+            .search(
+                &[&query],
+                None,
+                top,
+                Some(&SearchParams {
+                    exact: true,
+                    ..Default::default()
+                }),
+                &false.into(),
+            )
+            .unwrap();
+        let index_result = extended_index
+            .search(
+                &[&query],
+                None,
+                top,
+                Some(&SearchParams {
+                    hnsw_ef: Some(64),
+                    ..Default::default()
+                }),
+                &false.into(),
+            )
+            .unwrap();
+
+        let expected_ids: HashSet<_> = exact_result[0].iter().map(|point| point.idx).collect();
+        hits += index_result[0]
+            .iter()
+            .filter(|point| expected_ids.contains(&point.idx))
+            .count();
+    }
+
+    let recall = hits as f64 / (top * attempts) as f64;
+    assert!(
+        recall > 0.9,
+        "recall of the extended graph is too low: {recall}"
+    );
+}
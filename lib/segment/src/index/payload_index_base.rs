@@ -11,6 +11,7 @@ use crate::index::field_index::{CardinalityEstimation, PayloadBlockCondition};
 use crate::payload_storage::FilterContext;
 use crate::types::{
     Filter, Payload, PayloadFieldSchema, PayloadKeyType, PayloadKeyTypeRef, PayloadSchemaType,
+    PayloadSelector,
 };
 
 pub trait PayloadIndex {
@@ -70,6 +71,30 @@ pub trait PayloadIndex {
     /// Get payload for point
     fn payload(&self, point_id: PointOffsetType) -> OperationResult<Payload>;
 
+    /// Get payloads for multiple points at once, in the order of `point_ids`
+    fn payload_batch(&self, point_ids: &[PointOffsetType]) -> OperationResult<Vec<Payload>> {
+        point_ids
+            .iter()
+            .map(|&point_id| self.payload(point_id))
+            .collect()
+    }
+
+    /// Get payloads for multiple points at once, keeping only the fields selected by `selector`
+    fn payload_batch_selected(
+        &self,
+        point_ids: &[PointOffsetType],
+        selector: &PayloadSelector,
+    ) -> OperationResult<Vec<Payload>> {
+        Ok(self
+            .payload_batch(point_ids)?
+            .into_iter()
+            .map(|payload| selector.process(payload))
+            .collect())
+    }
+
+    /// Hint, that payloads of the points are going to be read soon
+    fn prefetch_payloads(&self, _point_ids: &[PointOffsetType]) {}
+
     /// Delete payload by key
     fn delete(
         &mut self,
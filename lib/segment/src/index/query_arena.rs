@@ -0,0 +1,138 @@
+//! Scratch buffers, reused by consequent queries to avoid allocator traffic on the search path
+
+use std::ops::{Deref, DerefMut};
+
+use common::types::{PointOffsetType, ScoredPointOffset};
+use lazy_static::lazy_static;
+use parking_lot::Mutex;
+
+/// Buffers larger than this number of elements are not preserved between queries.
+///
+/// Prevents a single query with huge `ef` from holding the memory forever.
+const MAX_RETAINED_BUFFER_LEN: usize = 64 * 1024;
+
+lazy_static! {
+    /// Max number of arenas to preserve in memory
+    ///
+    /// Same as for visited lists, it represents the expected number of concurrent requests.
+    static ref POOL_KEEP_LIMIT: usize = num_cpus::get().clamp(16, 128);
+}
+
+/// Query-scoped temporaries of a graph search.
+///
+/// All buffers hold plain `Copy` values, so resetting the arena is just setting lengths to zero,
+/// while the allocated capacity stays available for the next query.
+#[derive(Debug, Default)]
+pub struct QueryArena {
+    /// Backing storage of the candidates heap
+    pub(crate) candidates: Vec<ScoredPointOffset>,
+    /// Links of the currently processed point
+    pub(crate) links: Vec<PointOffsetType>,
+    /// Scores of the currently processed links
+    pub(crate) scores: Vec<ScoredPointOffset>,
+}
+
+impl QueryArena {
+    /// Take the buffer out of the arena, it is empty but keeps the capacity
+    pub(crate) fn take<T>(buffer: &mut Vec<T>) -> Vec<T> {
+        let mut buffer = std::mem::take(buffer);
+        buffer.clear();
+        buffer
+    }
+
+    fn reset(&mut self) {
+        Self::reset_buffer(&mut self.candidates);
+        Self::reset_buffer(&mut self.links);
+        Self::reset_buffer(&mut self.scores);
+    }
+
+    fn reset_buffer<T>(buffer: &mut Vec<T>) {
+        buffer.clear();
+        if buffer.capacity() > MAX_RETAINED_BUFFER_LEN {
+            buffer.shrink_to(MAX_RETAINED_BUFFER_LEN);
+        }
+    }
+}
+
+/// Owner of the `QueryArena`, which is returned back to the `QueryArenaPool` on drop
+#[derive(Debug)]
+pub struct QueryArenaHandle<'a> {
+    pool: &'a QueryArenaPool,
+    arena: QueryArena,
+}
+
+impl<'a> Deref for QueryArenaHandle<'a> {
+    type Target = QueryArena;
+
+    fn deref(&self) -> &Self::Target {
+        &self.arena
+    }
+}
+
+impl<'a> DerefMut for QueryArenaHandle<'a> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.arena
+    }
+}
+
+impl<'a> Drop for QueryArenaHandle<'a> {
+    fn drop(&mut self) {
+        self.pool.return_back(std::mem::take(&mut self.arena));
+    }
+}
+
+/// Keeps a list of `QueryArena` which could be requested and released from multiple threads
+///
+/// If there are more requests than arenas - creates a new one, but only keeps max defined amount.
+#[derive(Debug)]
+pub struct QueryArenaPool {
+    pool: Mutex<Vec<QueryArena>>,
+}
+
+impl QueryArenaPool {
+    pub fn new() -> Self {
+        QueryArenaPool {
+            pool: Mutex::new(Vec::with_capacity(*POOL_KEEP_LIMIT)),
+        }
+    }
+
+    pub fn get(&self) -> QueryArenaHandle {
+        let arena = self.pool.lock().pop().unwrap_or_default();
+        QueryArenaHandle { pool: self, arena }
+    }
+
+    fn return_back(&self, mut arena: QueryArena) {
+        arena.reset();
+        let mut pool = self.pool.lock();
+        if pool.len() < *POOL_KEEP_LIMIT {
+            pool.push(arena);
+        }
+    }
+}
+
+impl Default for QueryArenaPool {
+    fn default() -> Self {
+        QueryArenaPool::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_arena_is_reused_empty() {
+        let pool = QueryArenaPool::new();
+        {
+            let mut arena = pool.get();
+            arena.links.extend(0..100);
+            arena.scores.push(ScoredPointOffset { idx: 1, score: 0.5 });
+        }
+
+        let arena = pool.get();
+        assert!(arena.links.is_empty());
+        assert!(arena.scores.is_empty());
+        // Memory of the previous query is preserved
+        assert!(arena.links.capacity() >= 100);
+    }
+}
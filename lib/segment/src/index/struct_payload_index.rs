@@ -6,12 +6,14 @@ use std::sync::Arc;
 
 use atomic_refcell::AtomicRefCell;
 use common::types::PointOffsetType;
+use itertools::Itertools;
 use log::debug;
 use parking_lot::RwLock;
 use rocksdb::DB;
 use schemars::_serde_json::Value;
 
 use crate::common::arc_atomic_ref_cell_iterator::ArcAtomicRefCellIterator;
+use crate::common::checksums::Verification;
 use crate::common::operation_error::{OperationError, OperationResult};
 use crate::common::rocksdb_wrapper::open_db_with_existing_cf;
 use crate::common::utils::{IndexesMap, JsonPathPayload, MultiValue};
@@ -33,11 +35,14 @@ use crate::telemetry::PayloadIndexTelemetry;
 use crate::types::{
     infer_collection_value_type, infer_value_type, Condition, FieldCondition, Filter,
     IsEmptyCondition, IsNullCondition, Payload, PayloadContainer, PayloadField, PayloadFieldSchema,
-    PayloadKeyType, PayloadKeyTypeRef, PayloadSchemaType,
+    PayloadKeyType, PayloadKeyTypeRef, PayloadSchemaType, PayloadSelector,
 };
 
 pub const PAYLOAD_FIELD_INDEX_PATH: &str = "fields";
 
+/// Number of candidate points, payloads of which are prefetched at once during filtering
+pub const PAYLOAD_PREFETCH_BATCH: usize = 64;
+
 /// `PayloadIndex` implementation, which actually uses index structures for providing faster search
 pub struct StructPayloadIndex {
     /// Payload storage
@@ -222,6 +227,57 @@ impl StructPayloadIndex {
         )
     }
 
+    /// Whether checking of the filter may read payloads, because some of its conditions
+    /// are on fields without an index
+    pub fn filter_reads_payload(&self, filter: &Filter) -> bool {
+        let condition_reads_payload = |condition: &Condition| match condition {
+            Condition::Field(FieldCondition { key, .. })
+            | Condition::IsEmpty(IsEmptyCondition {
+                is_empty: PayloadField { key },
+            })
+            | Condition::IsNull(IsNullCondition {
+                is_null: PayloadField { key },
+            }) => !self.field_indexes.contains_key(key),
+            Condition::HasId(_) => false,
+            // Nested conditions are checked against the payload
+            Condition::Nested(_) => true,
+            Condition::Filter(filter) => self.filter_reads_payload(filter),
+        };
+        [&filter.must, &filter.should, &filter.must_not]
+            .into_iter()
+            .flatten()
+            .flatten()
+            .any(condition_reads_payload)
+    }
+
+    /// Check candidate points against the filter, keeping the order of the candidates.
+    ///
+    /// If the filter needs payloads, they are prefetched for batches of candidates,
+    /// so that on-disk storages read the whole batch at once instead of point by point.
+    fn check_candidates(
+        &self,
+        query: &Filter,
+        context: &StructFilterContext,
+        candidates: impl Iterator<Item = PointOffsetType>,
+    ) -> Vec<PointOffsetType> {
+        if !self.filter_reads_payload(query) {
+            return candidates
+                .filter(|&point_id| context.check(point_id))
+                .collect();
+        }
+        let mut matched = Vec::new();
+        for batch in &candidates.chunks(PAYLOAD_PREFETCH_BATCH) {
+            let batch: Vec<_> = batch.collect();
+            self.prefetch_payloads(&batch);
+            matched.extend(
+                batch
+                    .into_iter()
+                    .filter(|&point_id| context.check(point_id)),
+            );
+        }
+        matched
+    }
+
     fn condition_cardinality(
         &self,
         condition: &Condition,
@@ -313,6 +369,11 @@ impl StructPayloadIndex {
         }
     }
 
+    /// Compare payloads of the storage with their checksums
+    pub fn verify_payload_checksums(&self) -> OperationResult<Verification> {
+        self.payload.borrow().verify_checksums()
+    }
+
     pub fn get_telemetry_data(&self) -> Vec<PayloadIndexTelemetry> {
         self.field_indexes
             .iter()
@@ -404,10 +465,7 @@ impl PayloadIndex for StructPayloadIndex {
 
             let struct_filtered_context = self.struct_filtered_context(query);
             // Worst case: query expected to return few matches, but index can't be used
-            let matched_points =
-                full_scan_iterator.filter(move |i| struct_filtered_context.check(*i));
-
-            matched_points.collect()
+            self.check_candidates(query, &struct_filtered_context, full_scan_iterator)
         } else {
             let points_iterator_ref = self.id_tracker.borrow();
             let struct_filtered_context = self.struct_filtered_context(query);
@@ -418,7 +476,7 @@ impl PayloadIndex for StructPayloadIndex {
                 .visited_pool
                 .get(points_iterator_ref.total_point_count());
 
-            let preselected: Vec<PointOffsetType> = query_cardinality
+            let preselected = query_cardinality
                 .primary_clauses
                 .iter()
                 .flat_map(|clause| {
@@ -433,10 +491,8 @@ impl PayloadIndex for StructPayloadIndex {
                         PrimaryCondition::IsNull(_) => points_iterator_ref.iter_ids(),  /* no fast index for IsNull too */
                     }
                 })
-                .filter(|&id| !visited_list.check_and_update_visited(id))
-                .filter(move |&i| struct_filtered_context.check(i))
-                .collect();
-            preselected
+                .filter(|&id| !visited_list.check_and_update_visited(id));
+            self.check_candidates(query, &struct_filtered_context, preselected)
         }
     }
 
@@ -473,6 +529,25 @@ impl PayloadIndex for StructPayloadIndex {
         }
     }
 
+    fn assign_all(&mut self, point_id: PointOffsetType, payload: &Payload) -> OperationResult<()> {
+        for (field, field_index) in &mut self.field_indexes {
+            match &payload.get_value_opt(field) {
+                Some(field_value) => {
+                    for index in field_index {
+                        index.add_point(point_id, field_value)?;
+                    }
+                }
+                None => {
+                    for index in field_index {
+                        index.remove_point(point_id)?;
+                    }
+                }
+            }
+        }
+        // Storage may keep partial updates separately, full overwrite must discard them
+        self.payload.borrow_mut().assign_all(point_id, payload)
+    }
+
     fn assign(&mut self, point_id: PointOffsetType, payload: &Payload) -> OperationResult<()> {
         for (field, field_index) in &mut self.field_indexes {
             let field_value_opt = &payload.get_value_opt(field);
@@ -489,6 +564,24 @@ impl PayloadIndex for StructPayloadIndex {
         self.payload.borrow().payload(point_id)
     }
 
+    fn payload_batch(&self, point_ids: &[PointOffsetType]) -> OperationResult<Vec<Payload>> {
+        self.payload.borrow().payload_batch(point_ids)
+    }
+
+    fn prefetch_payloads(&self, point_ids: &[PointOffsetType]) {
+        self.payload.borrow().prefetch(point_ids)
+    }
+
+    fn payload_batch_selected(
+        &self,
+        point_ids: &[PointOffsetType],
+        selector: &PayloadSelector,
+    ) -> OperationResult<Vec<Payload>> {
+        self.payload
+            .borrow()
+            .payload_batch_selected(point_ids, selector)
+    }
+
     fn delete(
         &mut self,
         point_id: PointOffsetType,
@@ -550,6 +643,8 @@ impl PayloadIndex for StructPayloadIndex {
     }
 
     fn files(&self) -> Vec<PathBuf> {
-        vec![self.config_path()]
+        let mut files = vec![self.config_path()];
+        files.extend(self.payload.borrow().files());
+        files
     }
 }
@@ -0,0 +1,769 @@
+use std::collections::hash_map::Entry;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::fs::{create_dir_all, remove_dir_all, File};
+use std::mem::size_of;
+use std::path::{Path, PathBuf};
+
+use common::types::PointOffsetType;
+use memory::mmap_ops::{create_and_ensure_length, open_write_mmap};
+use parking_lot::Mutex;
+use serde_json::Value;
+
+use crate::common::checksums::{checksum, Verification};
+use crate::common::mmap_type::MmapSlice;
+use crate::common::operation_error::{OperationError, OperationResult};
+use crate::common::Flusher;
+#[cfg(target_os = "linux")]
+use crate::payload_storage::async_payload_reader::{ReadRequest, UringPayloadReader};
+#[cfg(not(target_os = "linux"))]
+use crate::payload_storage::async_payload_reader_mock::{ReadRequest, UringPayloadReader};
+use crate::payload_storage::payload_projection::read_selected_cbor;
+use crate::payload_storage::PayloadStorage;
+use crate::types::{Payload, PayloadKeyTypeRef, PayloadSelector};
+use crate::vector_storage::common::get_async_scorer;
+
+/// Values are stored in blocks of this size, so released space can be reused by other values
+const BLOCK_SIZE: usize = 128;
+
+#[cfg(debug_assertions)]
+const PAGE_SIZE: usize = 1024 * 1024; // 1Mb
+#[cfg(not(debug_assertions))]
+const PAGE_SIZE: usize = 32 * 1024 * 1024; // 32Mb
+
+const INITIAL_TRACKER_CAPACITY: usize = 1024;
+
+const TRACKER_FILE: &str = "tracker.dat";
+const CHECKSUMS_FILE: &str = "checksums.dat";
+const PAGE_FILE_PREFIX: &str = "page_";
+const PAGE_FILE_SUFFIX: &str = ".dat";
+
+/// Location of the serialized payload of a single point
+#[repr(C)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+struct ValuePointer {
+    page_id: u32,
+    block_offset: u32,
+    /// Length of the serialized payload in bytes, zero if the point has no payload
+    length: u32,
+}
+
+impl ValuePointer {
+    fn is_empty(&self) -> bool {
+        self.length == 0
+    }
+
+    fn blocks(&self) -> u32 {
+        blocks_for(self.length as usize)
+    }
+}
+
+/// Position of a value in the pages
+#[derive(Debug, Clone, Copy)]
+struct Region {
+    page_id: u32,
+    block_offset: u32,
+}
+
+/// Payload storage, which keeps serialized payloads in memory-mapped pages and does not use RocksDB.
+///
+/// Payloads are written into fixed-size blocks of append-only pages,
+/// the tracker maps each point to the blocks of its payload.
+/// Blocks released by updated or deleted payloads are reused by later writes,
+/// so the storage does not grow under constant overwrites and never needs a compaction.
+pub struct AppendLogPayloadStorage {
+    path: PathBuf,
+    /// Point offset -> location of its payload
+    tracker: MmapSlice<ValuePointer>,
+    /// Point offset -> checksum of its serialized payload, zero if unknown
+    checksums: MmapSlice<u64>,
+    pages: Vec<MmapSlice<u8>>,
+    /// Released regions: (page id, block offset) -> number of blocks
+    free_by_position: BTreeMap<(u32, u32), u32>,
+    /// Released regions: (number of blocks, page id, block offset)
+    free_by_size: BTreeSet<(u32, u32, u32)>,
+    /// First block of the last page, which is not used yet
+    tail_block: u32,
+    /// Context for io_uring-based batched reads, if enabled and supported
+    uring_reader: Mutex<Option<UringPayloadReader>>,
+}
+
+impl AppendLogPayloadStorage {
+    pub fn open(path: &Path) -> OperationResult<Self> {
+        Self::open_with_async_io(path, get_async_scorer())
+    }
+
+    /// Open the storage, reading batches of payloads with io_uring if `with_async_io` is set
+    ///
+    /// Falls back to reading from memory-mapped pages, if io_uring is not supported.
+    pub fn open_with_async_io(path: &Path, with_async_io: bool) -> OperationResult<Self> {
+        create_dir_all(path)?;
+
+        let tracker_path = path.join(TRACKER_FILE);
+        if !tracker_path.exists() {
+            create_and_ensure_length(
+                &tracker_path,
+                INITIAL_TRACKER_CAPACITY * size_of::<ValuePointer>(),
+            )?;
+        }
+        let tracker = unsafe { MmapSlice::try_from(open_write_mmap(&tracker_path)?)? };
+
+        // Storages, created before checksums were introduced, get them on open
+        let checksums_path = path.join(CHECKSUMS_FILE);
+        let init_checksums = !checksums_path.exists();
+        create_and_ensure_length(&checksums_path, tracker.len() * size_of::<u64>())?;
+        let checksums = unsafe { MmapSlice::try_from(open_write_mmap(&checksums_path)?)? };
+
+        let mut pages = Vec::new();
+        loop {
+            let page_path = page_path(path, pages.len() as u32);
+            if !page_path.exists() {
+                break;
+            }
+            pages.push(unsafe { MmapSlice::try_from(open_write_mmap(&page_path)?)? });
+        }
+
+        let uring_reader = if with_async_io {
+            UringPayloadReader::new()
+                .map_err(|err| {
+                    log::warn!("io_uring is not available for payload reads, using mmap: {err}");
+                })
+                .ok()
+        } else {
+            None
+        };
+
+        let mut storage = Self {
+            path: path.to_owned(),
+            tracker,
+            checksums,
+            pages,
+            free_by_position: BTreeMap::new(),
+            free_by_size: BTreeSet::new(),
+            tail_block: 0,
+            uring_reader: Mutex::new(uring_reader),
+        };
+        storage.init_free_regions()?;
+        if init_checksums {
+            for point_id in 0..storage.tracker.len() {
+                let checksum = storage
+                    .payload_data(point_id as PointOffsetType)
+                    .map_or(0, checksum);
+                storage.checksums[point_id] = checksum;
+            }
+        }
+        Ok(storage)
+    }
+
+    /// Free space is not persisted, it is restored from the gaps between the stored values
+    fn init_free_regions(&mut self) -> OperationResult<()> {
+        let mut used: Vec<_> = self
+            .tracker
+            .iter()
+            .filter(|pointer| !pointer.is_empty())
+            .map(|pointer| (pointer.page_id, pointer.block_offset, pointer.blocks()))
+            .collect();
+        used.sort_unstable();
+
+        let last_page_id = self.pages.len().saturating_sub(1) as u32;
+        self.tail_block = self
+            .pages
+            .first()
+            .map_or(0, |_| self.page_blocks(last_page_id));
+
+        let mut page_id = 0;
+        let mut block_offset = 0;
+        for (value_page_id, value_block_offset, value_blocks) in used {
+            if value_page_id > last_page_id || self.pages.is_empty() {
+                return Err(OperationError::service_error(format!(
+                    "Payload storage {} is corrupted: page {value_page_id} is missing",
+                    self.path.display(),
+                )));
+            }
+            while page_id < value_page_id {
+                self.release(
+                    page_id,
+                    block_offset,
+                    self.page_blocks(page_id) - block_offset,
+                );
+                page_id += 1;
+                block_offset = 0;
+            }
+            // Pointers may overlap after a crash, until the overwritten ones are restored from WAL
+            self.release(
+                page_id,
+                block_offset,
+                value_block_offset.saturating_sub(block_offset),
+            );
+            block_offset = block_offset.max(value_block_offset + value_blocks);
+        }
+        while page_id <= last_page_id && !self.pages.is_empty() {
+            self.release(
+                page_id,
+                block_offset,
+                self.page_blocks(page_id) - block_offset,
+            );
+            page_id += 1;
+            block_offset = 0;
+        }
+        Ok(())
+    }
+
+    fn page_blocks(&self, page_id: u32) -> u32 {
+        (self.pages[page_id as usize].len() / BLOCK_SIZE) as u32
+    }
+
+    fn is_last_page(&self, page_id: u32) -> bool {
+        page_id as usize + 1 == self.pages.len()
+    }
+
+    fn take_free_region(&mut self, page_id: u32, block_offset: u32) -> Option<u32> {
+        let blocks = self.free_by_position.remove(&(page_id, block_offset))?;
+        self.free_by_size.remove(&(blocks, page_id, block_offset));
+        Some(blocks)
+    }
+
+    /// Mark blocks as unused, merging them with adjacent unused blocks
+    fn release(&mut self, page_id: u32, mut block_offset: u32, mut blocks: u32) {
+        if blocks == 0 {
+            return;
+        }
+
+        let previous = self
+            .free_by_position
+            .range(..(page_id, block_offset))
+            .next_back()
+            .map(|(&position, &blocks)| (position, blocks));
+        if let Some(((previous_page_id, previous_offset), previous_blocks)) = previous {
+            if previous_page_id == page_id && previous_offset + previous_blocks == block_offset {
+                self.take_free_region(previous_page_id, previous_offset);
+                block_offset = previous_offset;
+                blocks += previous_blocks;
+            }
+        }
+        if let Some(next_blocks) = self.take_free_region(page_id, block_offset + blocks) {
+            blocks += next_blocks;
+        }
+
+        if self.is_last_page(page_id) && block_offset + blocks >= self.tail_block {
+            self.tail_block = self.tail_block.min(block_offset);
+            return;
+        }
+
+        self.free_by_position
+            .insert((page_id, block_offset), blocks);
+        self.free_by_size.insert((blocks, page_id, block_offset));
+    }
+
+    /// Find space for a value of the given size: the smallest released region it fits into,
+    /// or the tail of the last page, or a new page.
+    fn allocate(&mut self, blocks: u32) -> OperationResult<Region> {
+        let best_fit = self.free_by_size.range((blocks, 0, 0)..).next().copied();
+        if let Some((region_blocks, page_id, block_offset)) = best_fit {
+            self.take_free_region(page_id, block_offset);
+            if region_blocks > blocks {
+                self.release(page_id, block_offset + blocks, region_blocks - blocks);
+            }
+            return Ok(Region {
+                page_id,
+                block_offset,
+            });
+        }
+
+        let fits_last_page = self.pages.len().checked_sub(1).map_or(false, |page_id| {
+            self.tail_block + blocks <= self.page_blocks(page_id as u32)
+        });
+        if !fits_last_page {
+            self.add_page(blocks)?;
+        }
+
+        let region = Region {
+            page_id: (self.pages.len() - 1) as u32,
+            block_offset: self.tail_block,
+        };
+        self.tail_block += blocks;
+        Ok(region)
+    }
+
+    fn add_page(&mut self, min_blocks: u32) -> OperationResult<()> {
+        let previous_tail = self
+            .pages
+            .len()
+            .checked_sub(1)
+            .map(|page_id| (page_id as u32, self.tail_block));
+
+        // Values larger than a page get a dedicated page
+        let page_size = PAGE_SIZE.max(min_blocks as usize * BLOCK_SIZE);
+        let page_path = page_path(&self.path, self.pages.len() as u32);
+        create_and_ensure_length(&page_path, page_size)?;
+        self.pages
+            .push(unsafe { MmapSlice::try_from(open_write_mmap(&page_path)?)? });
+        self.tail_block = 0;
+
+        // Unused end of the previous page is not a tail anymore
+        if let Some((page_id, tail_block)) = previous_tail {
+            self.release(page_id, tail_block, self.page_blocks(page_id) - tail_block);
+        }
+        Ok(())
+    }
+
+    fn ensure_tracker_capacity(&mut self, point_id: PointOffsetType) -> OperationResult<()> {
+        let required = point_id as usize + 1;
+        if required <= self.tracker.len() {
+            return Ok(());
+        }
+        let capacity = required.next_power_of_two();
+        let tracker_path = self.path.join(TRACKER_FILE);
+        // Mapping is shared, so the new one sees all the changes of the current one
+        create_and_ensure_length(&tracker_path, capacity * size_of::<ValuePointer>())?;
+        self.tracker = unsafe { MmapSlice::try_from(open_write_mmap(&tracker_path)?)? };
+        let checksums_path = self.path.join(CHECKSUMS_FILE);
+        create_and_ensure_length(&checksums_path, capacity * size_of::<u64>())?;
+        self.checksums = unsafe { MmapSlice::try_from(open_write_mmap(&checksums_path)?)? };
+        Ok(())
+    }
+
+    fn pointer(&self, point_id: PointOffsetType) -> Option<ValuePointer> {
+        self.tracker
+            .get(point_id as usize)
+            .copied()
+            .filter(|pointer| !pointer.is_empty())
+    }
+
+    pub fn read_payload(&self, point_id: PointOffsetType) -> OperationResult<Option<Payload>> {
+        let Some(data) = self.payload_data(point_id) else {
+            return Ok(None);
+        };
+        Ok(Some(serde_cbor::from_slice(data)?))
+    }
+
+    pub fn has_async_reader(&self) -> bool {
+        self.uring_reader.lock().is_some()
+    }
+
+    /// Read payloads of the points in the order of `point_ids`
+    ///
+    /// With io_uring, reads of the whole batch are in flight at the same time,
+    /// so disk latency is paid once per batch rather than once per point.
+    pub fn read_payload_batch_with<F>(
+        &self,
+        point_ids: &[PointOffsetType],
+        mut decode: F,
+    ) -> OperationResult<Vec<Option<Payload>>>
+    where
+        F: FnMut(&[u8]) -> OperationResult<Payload>,
+    {
+        if point_ids.len() > 1 {
+            if let Some(reader) = self.uring_reader.lock().as_mut() {
+                match self.read_batch_async(reader, point_ids, &mut decode) {
+                    Ok(payloads) => return Ok(payloads),
+                    Err(err) => {
+                        log::warn!("Failed to read payloads with io_uring, using mmap: {err}")
+                    }
+                }
+            }
+        }
+        point_ids
+            .iter()
+            .map(|&point_id| self.payload_data(point_id).map(&mut decode).transpose())
+            .collect()
+    }
+
+    fn read_batch_async<F>(
+        &self,
+        reader: &mut UringPayloadReader,
+        point_ids: &[PointOffsetType],
+        mut decode: F,
+    ) -> OperationResult<Vec<Option<Payload>>>
+    where
+        F: FnMut(&[u8]) -> OperationResult<Payload>,
+    {
+        // Position in `point_ids` and location of each payload to read
+        let mut pointers = Vec::with_capacity(point_ids.len());
+        let mut files: HashMap<u32, File> = HashMap::new();
+        for (index, &point_id) in point_ids.iter().enumerate() {
+            let Some(pointer) = self.pointer(point_id) else {
+                continue;
+            };
+            if let Entry::Vacant(entry) = files.entry(pointer.page_id) {
+                entry.insert(File::open(page_path(&self.path, pointer.page_id))?);
+            }
+            pointers.push((index, pointer));
+        }
+
+        let requests: Vec<_> = pointers
+            .iter()
+            .map(|(_, pointer)| ReadRequest {
+                file: &files[&pointer.page_id],
+                offset: (pointer.block_offset as usize * BLOCK_SIZE) as u64,
+                length: pointer.length as usize,
+            })
+            .collect();
+
+        let mut payloads = vec![None; point_ids.len()];
+        reader.read_batch(&requests, |request_index, data| {
+            let (index, _) = pointers[request_index];
+            payloads[index] = Some(decode(data)?);
+            Ok(())
+        })?;
+        Ok(payloads)
+    }
+
+    fn payload_data(&self, point_id: PointOffsetType) -> Option<&[u8]> {
+        let pointer = self.pointer(point_id)?;
+        let start = pointer.block_offset as usize * BLOCK_SIZE;
+        Some(&self.pages[pointer.page_id as usize][start..start + pointer.length as usize])
+    }
+
+    pub fn update_storage(
+        &mut self,
+        point_id: PointOffsetType,
+        payload: &Payload,
+    ) -> OperationResult<()> {
+        let data = serde_cbor::to_vec(payload).unwrap();
+        let length = u32::try_from(data.len()).map_err(|_| {
+            OperationError::service_error(format!(
+                "Payload of point {point_id} is too large: {} bytes",
+                data.len(),
+            ))
+        })?;
+        self.ensure_tracker_capacity(point_id)?;
+
+        let blocks = blocks_for(data.len());
+        let previous = self.pointer(point_id);
+        let region = match previous {
+            // Overwrite in place, if the new value fits into the same blocks
+            Some(previous) if previous.blocks() == blocks => Region {
+                page_id: previous.page_id,
+                block_offset: previous.block_offset,
+            },
+            _ => self.allocate(blocks)?,
+        };
+
+        let start = region.block_offset as usize * BLOCK_SIZE;
+        self.pages[region.page_id as usize][start..start + data.len()].copy_from_slice(&data);
+
+        self.tracker[point_id as usize] = ValuePointer {
+            page_id: region.page_id,
+            block_offset: region.block_offset,
+            length,
+        };
+        self.checksums[point_id as usize] = checksum(&data);
+
+        if let Some(previous) = previous {
+            if previous.blocks() != blocks {
+                self.release(previous.page_id, previous.block_offset, previous.blocks());
+            }
+        }
+        Ok(())
+    }
+
+    pub fn remove_from_storage(&mut self, point_id: PointOffsetType) {
+        if let Some(previous) = self.pointer(point_id) {
+            self.tracker[point_id as usize] = ValuePointer::default();
+            self.checksums[point_id as usize] = 0;
+            self.release(previous.page_id, previous.block_offset, previous.blocks());
+        }
+    }
+
+    /// Compare stored payloads with their checksums, points without a known checksum are skipped
+    pub fn verify(&self) -> Verification {
+        let mut verification = Verification::default();
+        for point_id in 0..self.tracker.len() {
+            let Some(data) = self.payload_data(point_id as PointOffsetType) else {
+                continue;
+            };
+            let expected = self.checksums[point_id];
+            if expected != 0 && expected != checksum(data) {
+                verification.corrupted.push(point_id);
+            }
+            verification.bytes_read += data.len();
+        }
+        verification
+    }
+
+    pub fn iter<F>(&self, mut callback: F) -> OperationResult<()>
+    where
+        F: FnMut(PointOffsetType, &Payload) -> OperationResult<bool>,
+    {
+        for point_id in 0..self.tracker.len() as PointOffsetType {
+            let Some(payload) = self.read_payload(point_id)? else {
+                continue;
+            };
+            if !callback(point_id, &payload)? {
+                return Ok(());
+            }
+        }
+        Ok(())
+    }
+}
+
+impl PayloadStorage for AppendLogPayloadStorage {
+    fn assign_all(&mut self, point_id: PointOffsetType, payload: &Payload) -> OperationResult<()> {
+        self.update_storage(point_id, payload)
+    }
+
+    fn assign(&mut self, point_id: PointOffsetType, payload: &Payload) -> OperationResult<()> {
+        match self.read_payload(point_id)? {
+            Some(mut point_payload) => {
+                point_payload.merge(payload);
+                self.update_storage(point_id, &point_payload)
+            }
+            None => self.update_storage(point_id, payload),
+        }
+    }
+
+    fn payload(&self, point_id: PointOffsetType) -> OperationResult<Payload> {
+        Ok(self.read_payload(point_id)?.unwrap_or_default())
+    }
+
+    fn payload_batch(&self, point_ids: &[PointOffsetType]) -> OperationResult<Vec<Payload>> {
+        Ok(self
+            .read_payload_batch_with(point_ids, |data| Ok(serde_cbor::from_slice(data)?))?
+            .into_iter()
+            .map(Option::unwrap_or_default)
+            .collect())
+    }
+
+    fn payload_batch_selected(
+        &self,
+        point_ids: &[PointOffsetType],
+        selector: &PayloadSelector,
+    ) -> OperationResult<Vec<Payload>> {
+        Ok(self
+            .read_payload_batch_with(point_ids, |data| Ok(read_selected_cbor(data, selector)?))?
+            .into_iter()
+            .map(|payload| selector.process(payload.unwrap_or_default()))
+            .collect())
+    }
+
+    /// Payloads are read with io_uring into the page cache, so that following reads
+    /// from the memory-mapped pages don't wait for the disk one by one.
+    fn prefetch(&self, point_ids: &[PointOffsetType]) {
+        if let Some(reader) = self.uring_reader.lock().as_mut() {
+            if let Err(err) = self.read_batch_async(reader, point_ids, |_| Ok(Payload::default())) {
+                log::debug!("Failed to prefetch payloads: {err}");
+            }
+        }
+    }
+
+    fn delete(
+        &mut self,
+        point_id: PointOffsetType,
+        key: PayloadKeyTypeRef,
+    ) -> OperationResult<Vec<Value>> {
+        match self.read_payload(point_id)? {
+            Some(mut payload) => {
+                let res = payload.remove(key);
+                if !res.is_empty() {
+                    self.update_storage(point_id, &payload)?;
+                }
+                Ok(res)
+            }
+            None => Ok(vec![]),
+        }
+    }
+
+    fn drop(&mut self, point_id: PointOffsetType) -> OperationResult<Option<Payload>> {
+        let payload = self.read_payload(point_id)?;
+        self.remove_from_storage(point_id);
+        Ok(payload)
+    }
+
+    fn wipe(&mut self) -> OperationResult<()> {
+        let path = self.path.clone();
+        self.pages.clear();
+        remove_dir_all(&path)?;
+        *self = Self::open(&path)?;
+        Ok(())
+    }
+
+    fn flusher(&self) -> Flusher {
+        let pages_flushers: Vec<_> = self.pages.iter().map(|page| page.flusher()).collect();
+        let checksums_flusher = self.checksums.flusher();
+        let tracker_flusher = self.tracker.flusher();
+        Box::new(move || {
+            // Values must be persisted before the pointers to them
+            for flusher in pages_flushers {
+                flusher()?;
+            }
+            checksums_flusher()?;
+            tracker_flusher()
+        })
+    }
+
+    fn files(&self) -> Vec<PathBuf> {
+        let mut files = vec![self.path.join(TRACKER_FILE), self.path.join(CHECKSUMS_FILE)];
+        files.extend((0..self.pages.len() as u32).map(|page_id| page_path(&self.path, page_id)));
+        files
+    }
+
+    fn verify_checksums(&self) -> OperationResult<Verification> {
+        Ok(self.verify())
+    }
+}
+
+fn blocks_for(length: usize) -> u32 {
+    length.div_ceil(BLOCK_SIZE) as u32
+}
+
+fn page_path(path: &Path, page_id: u32) -> PathBuf {
+    path.join(format!("{PAGE_FILE_PREFIX}{page_id}{PAGE_FILE_SUFFIX}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use tempfile::Builder;
+
+    use super::*;
+
+    fn payload(value: Value) -> Payload {
+        serde_json::from_value(value).unwrap()
+    }
+
+    #[test]
+    fn test_append_log_payload_storage() {
+        let dir = Builder::new().prefix("storage_dir").tempdir().unwrap();
+
+        {
+            let mut storage = AppendLogPayloadStorage::open(dir.path()).unwrap();
+            storage
+                .assign_all(100, &payload(json!({"name": "John Doe", "age": 52})))
+                .unwrap();
+            storage.assign(100, &payload(json!({"age": 53}))).unwrap();
+            storage
+                .assign(5000, &payload(json!({"city": "Berlin"})))
+                .unwrap();
+            storage
+                .assign(1, &payload(json!({"city": "Tokyo"})))
+                .unwrap();
+            storage.delete(100, "name").unwrap();
+            assert_eq!(
+                storage.drop(1).unwrap(),
+                Some(payload(json!({"city": "Tokyo"})))
+            );
+            storage.flusher()().unwrap();
+        }
+
+        let mut storage = AppendLogPayloadStorage::open(dir.path()).unwrap();
+        assert_eq!(storage.payload(100).unwrap(), payload(json!({"age": 53})));
+        assert_eq!(
+            storage.payload(5000).unwrap(),
+            payload(json!({"city": "Berlin"})),
+        );
+        assert_eq!(storage.payload(1).unwrap(), Payload::default());
+        assert_eq!(storage.payload(1_000_000).unwrap(), Payload::default());
+
+        let mut stored = vec![];
+        storage
+            .iter(|point_id, _| {
+                stored.push(point_id);
+                Ok(true)
+            })
+            .unwrap();
+        assert_eq!(stored, vec![100, 5000]);
+
+        storage.wipe().unwrap();
+        assert_eq!(storage.payload(100).unwrap(), Payload::default());
+    }
+
+    #[test]
+    fn test_released_space_is_reused() {
+        let dir = Builder::new().prefix("storage_dir").tempdir().unwrap();
+        let mut storage = AppendLogPayloadStorage::open(dir.path()).unwrap();
+
+        let text = |len: usize| payload(json!({ "text": "a".repeat(len) }));
+        for round in 0..50 {
+            for point_id in 0..100 {
+                storage
+                    .assign_all(point_id, &text(1000 + (round * point_id as usize) % 3000))
+                    .unwrap();
+            }
+        }
+        // 100 values of 4Kb at most fit into a single page
+        assert_eq!(storage.pages.len(), 1);
+
+        // Large values get a dedicated page
+        let large = text(PAGE_SIZE * 2);
+        storage.assign_all(7, &large).unwrap();
+        assert_eq!(storage.payload(7).unwrap(), large);
+        storage.flusher()().unwrap();
+        drop(storage);
+
+        let mut storage = AppendLogPayloadStorage::open(dir.path()).unwrap();
+        assert_eq!(storage.payload(7).unwrap(), large);
+        // Released regions are restored on load
+        storage.assign_all(7, &text(10)).unwrap();
+        storage.assign_all(100, &text(PAGE_SIZE)).unwrap();
+        assert_eq!(storage.pages.len(), 2);
+        assert_eq!(storage.payload(7).unwrap(), text(10));
+    }
+
+    #[test]
+    fn test_payload_batch() {
+        // io_uring reader falls back to mmap reads, if it is not supported by the platform
+        for with_async_io in [false, true] {
+            let dir = Builder::new().prefix("storage_dir").tempdir().unwrap();
+            let mut storage =
+                AppendLogPayloadStorage::open_with_async_io(dir.path(), with_async_io).unwrap();
+            for point_id in 0..100 {
+                let text = "a".repeat(point_id as usize * 10);
+                storage
+                    .assign_all(point_id, &payload(json!({"id": point_id, "text": text})))
+                    .unwrap();
+            }
+            storage.drop(50).unwrap();
+
+            // More points than reads in flight, unsorted, repeated and missing ones
+            let point_ids: Vec<PointOffsetType> = [99, 3, 50, 3, 1000, 0]
+                .into_iter()
+                .chain((0..100).rev())
+                .collect();
+            let expected: Vec<_> = point_ids
+                .iter()
+                .map(|&point_id| storage.payload(point_id).unwrap())
+                .collect();
+            assert_eq!(storage.payload_batch(&point_ids).unwrap(), expected);
+
+            let selector = PayloadSelector::new_include(vec!["id".to_string()]);
+            let selected = storage
+                .payload_batch_selected(&point_ids, &selector)
+                .unwrap();
+            assert_eq!(selected[0], payload(json!({"id": 99})));
+            assert_eq!(selected[2], Payload::default());
+            assert_eq!(selected[4], Payload::default());
+
+            // Prefetch is only a hint, it does not change stored values
+            storage.prefetch(&point_ids);
+            assert_eq!(storage.payload_batch(&point_ids).unwrap(), expected);
+        }
+    }
+
+    #[test]
+    fn test_verify_payload_checksums() {
+        let dir = Builder::new().prefix("storage_dir").tempdir().unwrap();
+
+        {
+            let mut storage = AppendLogPayloadStorage::open(dir.path()).unwrap();
+            storage.assign(1, &payload(json!({"a": 1}))).unwrap();
+            storage.assign(2, &payload(json!({"b": "x"}))).unwrap();
+            storage.assign(3, &payload(json!({"c": [1, 2]}))).unwrap();
+            storage.drop(3).unwrap();
+            assert!(storage.verify_checksums().unwrap().is_valid());
+
+            // Corrupt the stored value of point 2
+            let pointer = storage.pointer(2).unwrap();
+            let start = pointer.block_offset as usize * BLOCK_SIZE;
+            storage.pages[pointer.page_id as usize][start] ^= 1;
+            assert_eq!(storage.verify_checksums().unwrap().corrupted, vec![2]);
+            storage.flusher()().unwrap();
+        }
+
+        // Storages without checksums compute them on open
+        std::fs::remove_file(dir.path().join(CHECKSUMS_FILE)).unwrap();
+        let storage = AppendLogPayloadStorage::open(dir.path()).unwrap();
+        let verification = storage.verify_checksums().unwrap();
+        assert!(verification.is_valid());
+        assert!(verification.bytes_read > 0);
+    }
+}
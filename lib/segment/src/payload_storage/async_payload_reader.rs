@@ -0,0 +1,132 @@
+use std::fs::File;
+use std::os::fd::AsRawFd;
+
+use io_uring::{opcode, types, IoUring};
+
+use crate::common::operation_error::{OperationError, OperationResult};
+
+/// Number of reads, which are in flight at the same time
+const DISK_PARALLELISM: usize = 16;
+
+/// Location of a single value to read
+pub struct ReadRequest<'a> {
+    pub file: &'a File,
+    pub offset: u64,
+    pub length: usize,
+}
+
+/// Reads batches of variable-length values with io_uring,
+/// so that disk latency of the values is overlapped instead of paid one by one.
+pub struct UringPayloadReader {
+    io_uring: Option<IoUring>,
+    /// Buffers of the reads in flight
+    buffers: Vec<Vec<u8>>,
+}
+
+impl UringPayloadReader {
+    pub fn new() -> OperationResult<Self> {
+        Ok(Self {
+            io_uring: Some(IoUring::new(DISK_PARALLELISM as _)?),
+            buffers: vec![vec![]; DISK_PARALLELISM],
+        })
+    }
+
+    /// Read all requested values and call `callback` with the index of the request and its data.
+    ///
+    /// Callback is called in the order of completion, not in the order of requests.
+    pub fn read_batch(
+        &mut self,
+        requests: &[ReadRequest],
+        mut callback: impl FnMut(usize, &[u8]) -> OperationResult<()>,
+    ) -> OperationResult<()> {
+        // Take `io_uring`, so that if we return an error in the middle of the batch,
+        // it is dropped together with the reads in flight, and the next batch starts with a new one.
+        let mut io_uring = match self.io_uring.take() {
+            Some(io_uring) => io_uring,
+            None => IoUring::new(DISK_PARALLELISM as _)?,
+        };
+
+        // Index of the request, which is read into the buffer
+        let mut in_flight: Vec<Option<usize>> = vec![None; self.buffers.len()];
+        let mut unused_buffer_ids: Vec<usize> = (0..self.buffers.len()).collect();
+
+        for (index, request) in requests.iter().enumerate() {
+            if unused_buffer_ids.is_empty() {
+                self.complete(
+                    &mut io_uring,
+                    requests,
+                    &mut in_flight,
+                    &mut unused_buffer_ids,
+                    &mut callback,
+                )?;
+            }
+            let buffer_id = unused_buffer_ids.pop().unwrap();
+            let buffer = &mut self.buffers[buffer_id];
+            buffer.resize(request.length, 0);
+            in_flight[buffer_id] = Some(index);
+
+            let read_e = opcode::Read::new(
+                types::Fd(request.file.as_raw_fd()),
+                buffer.as_mut_ptr(),
+                buffer.len() as _,
+            )
+            .offset(request.offset)
+            .build()
+            .user_data(buffer_id as _);
+
+            unsafe {
+                io_uring.submission().push(&read_e).map_err(|err| {
+                    OperationError::service_error(format!("Failed using io-uring: {err}"))
+                })?;
+            }
+        }
+
+        while unused_buffer_ids.len() < self.buffers.len() {
+            self.complete(
+                &mut io_uring,
+                requests,
+                &mut in_flight,
+                &mut unused_buffer_ids,
+                &mut callback,
+            )?;
+        }
+
+        self.io_uring = Some(io_uring);
+        Ok(())
+    }
+
+    /// Submit pending reads, wait for at least one of them and pass completed ones to `callback`
+    fn complete(
+        &self,
+        io_uring: &mut IoUring,
+        requests: &[ReadRequest],
+        in_flight: &mut [Option<usize>],
+        unused_buffer_ids: &mut Vec<usize>,
+        callback: &mut impl FnMut(usize, &[u8]) -> OperationResult<()>,
+    ) -> OperationResult<()> {
+        io_uring.submit_and_wait(1)?;
+
+        for entry in io_uring.completion() {
+            let buffer_id = entry.user_data() as usize;
+            let index = in_flight[buffer_id].take().ok_or_else(|| {
+                OperationError::service_error(format!(
+                    "io_uring completed unknown read {buffer_id}"
+                ))
+            })?;
+            let result = entry.result();
+            if result < 0 {
+                return Err(OperationError::service_error(format!(
+                    "io_uring operation failed with {result} error",
+                )));
+            } else if result as usize != requests[index].length {
+                return Err(OperationError::service_error(format!(
+                    "io_uring operation returned {result} bytes instead of {}",
+                    requests[index].length,
+                )));
+            }
+            callback(index, &self.buffers[buffer_id])?;
+            unused_buffer_ids.push(buffer_id);
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,31 @@
+use std::fs::File;
+
+use crate::common::operation_error::{OperationError, OperationResult};
+
+// This is a mock implementation of the async_payload_reader module for those platforms that don't support io_uring.
+#[allow(dead_code)]
+pub struct ReadRequest<'a> {
+    pub file: &'a File,
+    pub offset: u64,
+    pub length: usize,
+}
+
+#[allow(dead_code)]
+pub struct UringPayloadReader;
+
+#[allow(dead_code)]
+impl UringPayloadReader {
+    pub fn new() -> OperationResult<Self> {
+        Err(OperationError::service_error(
+            "io_uring is not supported on this platform",
+        ))
+    }
+
+    pub fn read_batch(
+        &mut self,
+        _requests: &[ReadRequest],
+        _callback: impl FnMut(usize, &[u8]) -> OperationResult<()>,
+    ) -> OperationResult<()> {
+        unreachable!("io_uring reader can't be created on this platform")
+    }
+}
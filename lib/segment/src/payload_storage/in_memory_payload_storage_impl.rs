@@ -6,8 +6,9 @@ use serde_json::Value;
 use crate::common::operation_error::OperationResult;
 use crate::common::Flusher;
 use crate::payload_storage::in_memory_payload_storage::InMemoryPayloadStorage;
+use crate::payload_storage::payload_projection::copy_selected;
 use crate::payload_storage::PayloadStorage;
-use crate::types::{Payload, PayloadKeyTypeRef};
+use crate::types::{Payload, PayloadKeyTypeRef, PayloadSelector};
 
 impl PayloadStorage for InMemoryPayloadStorage {
     fn assign(&mut self, point_id: PointOffsetType, payload: &Payload) -> OperationResult<()> {
@@ -27,6 +28,20 @@ impl PayloadStorage for InMemoryPayloadStorage {
         }
     }
 
+    fn payload_batch_selected(
+        &self,
+        point_ids: &[PointOffsetType],
+        selector: &PayloadSelector,
+    ) -> OperationResult<Vec<Payload>> {
+        Ok(point_ids
+            .iter()
+            .map(|point_id| match self.payload.get(point_id) {
+                Some(payload) => selector.process(copy_selected(payload, selector)),
+                None => Default::default(),
+            })
+            .collect())
+    }
+
     fn delete(
         &mut self,
         point_id: PointOffsetType,
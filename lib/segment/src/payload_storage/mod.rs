@@ -1,7 +1,12 @@
+pub mod append_log_payload_storage;
+#[cfg(target_os = "linux")]
+mod async_payload_reader;
+mod async_payload_reader_mock;
 pub mod condition_checker;
 pub mod in_memory_payload_storage;
 pub mod in_memory_payload_storage_impl;
 pub mod on_disk_payload_storage;
+pub mod payload_projection;
 mod payload_storage_base;
 pub mod payload_storage_enum;
 pub mod query_checker;
@@ -1,3 +1,4 @@
+use std::collections::HashMap;
 use std::sync::Arc;
 
 use common::types::PointOffsetType;
@@ -6,26 +7,61 @@ use rocksdb::DB;
 use serde_json::Value;
 
 use crate::common::operation_error::{OperationError, OperationResult};
-use crate::common::rocksdb_wrapper::{DatabaseColumnWrapper, DB_PAYLOAD_CF};
+use crate::common::rocksdb_wrapper::{DatabaseColumnWrapper, DB_PAYLOAD_CF, DB_PAYLOAD_DELTA_CF};
 use crate::common::Flusher;
+use crate::payload_storage::payload_projection::read_selected_cbor;
 use crate::payload_storage::PayloadStorage;
-use crate::types::{Payload, PayloadKeyTypeRef};
+use crate::types::{Payload, PayloadKeyTypeRef, PayloadSelector};
 
 /// On-disk implementation of `PayloadStorage`.
 /// Persists all changes to disk using `store`, does not keep payload in memory
+///
+/// Partial updates (`assign`) are not merged into the stored payload.
+/// Instead, they are accumulated in a separate delta record of the point, which is applied on read.
+/// This way frequent updates of small fields don't rewrite large payloads.
+/// Delta is consolidated into the base record on full overwrite or key removal,
+/// and when the segment is rebuilt by the optimizer.
 pub struct OnDiskPayloadStorage {
     db_wrapper: DatabaseColumnWrapper,
+    delta_wrapper: DatabaseColumnWrapper,
+}
+
+/// Add `update` to accumulated `delta`.
+///
+/// Unlike `Payload::merge`, `null` values are kept: they must remove the key from the base record.
+fn extend_delta(delta: &mut Payload, update: &Payload) {
+    for (key, value) in &update.0 {
+        delta.0.insert(key.to_owned(), value.to_owned());
+    }
+}
+
+fn apply_delta(base: Option<Payload>, delta: Option<Payload>) -> Option<Payload> {
+    match (base, delta) {
+        (base, None) => base,
+        (base, Some(delta)) => {
+            let mut payload = base.unwrap_or_default();
+            payload.merge(&delta);
+            Some(payload)
+        }
+    }
 }
 
 impl OnDiskPayloadStorage {
     pub fn open(database: Arc<RwLock<DB>>) -> OperationResult<Self> {
-        let db_wrapper = DatabaseColumnWrapper::new(database, DB_PAYLOAD_CF);
-        Ok(OnDiskPayloadStorage { db_wrapper })
+        let db_wrapper = DatabaseColumnWrapper::new(database.clone(), DB_PAYLOAD_CF);
+        let delta_wrapper = DatabaseColumnWrapper::new(database, DB_PAYLOAD_DELTA_CF);
+        // Segments created before delta updates were introduced don't have delta column
+        delta_wrapper.create_column_family_if_not_exists()?;
+        Ok(OnDiskPayloadStorage {
+            db_wrapper,
+            delta_wrapper,
+        })
     }
 
     pub fn remove_from_storage(&self, point_id: PointOffsetType) -> OperationResult<()> {
-        self.db_wrapper
-            .remove(serde_cbor::to_vec(&point_id).unwrap())
+        let key = serde_cbor::to_vec(&point_id).unwrap();
+        self.delta_wrapper.remove(&key)?;
+        self.db_wrapper.remove(key)
     }
 
     pub fn update_storage(
@@ -33,29 +69,111 @@ impl OnDiskPayloadStorage {
         point_id: PointOffsetType,
         payload: &Payload,
     ) -> OperationResult<()> {
-        self.db_wrapper.put(
-            serde_cbor::to_vec(&point_id).unwrap(),
-            serde_cbor::to_vec(payload).unwrap(),
-        )
+        let key = serde_cbor::to_vec(&point_id).unwrap();
+        self.delta_wrapper.remove(&key)?;
+        self.db_wrapper
+            .put(key, serde_cbor::to_vec(payload).unwrap())
     }
 
-    pub fn read_payload(&self, point_id: PointOffsetType) -> OperationResult<Option<Payload>> {
+    /// Accumulate partial update of the point in its delta record, leaving base record untouched
+    pub fn update_delta(
+        &self,
+        point_id: PointOffsetType,
+        payload: &Payload,
+    ) -> OperationResult<()> {
         let key = serde_cbor::to_vec(&point_id).unwrap();
-        self.db_wrapper
-            .get_pinned(&key, |raw| serde_cbor::from_slice(raw))?
+        let mut delta = Self::read_record(&self.delta_wrapper, &key)?.unwrap_or_default();
+        extend_delta(&mut delta, payload);
+        self.delta_wrapper
+            .put(key, serde_cbor::to_vec(&delta).unwrap())
+    }
+
+    fn read_record(
+        wrapper: &DatabaseColumnWrapper,
+        key: &[u8],
+    ) -> OperationResult<Option<Payload>> {
+        wrapper
+            .get_pinned(key, |raw| serde_cbor::from_slice(raw))?
             .transpose()
             .map_err(OperationError::from)
     }
 
+    pub fn read_payload(&self, point_id: PointOffsetType) -> OperationResult<Option<Payload>> {
+        let key = serde_cbor::to_vec(&point_id).unwrap();
+        let base = Self::read_record(&self.db_wrapper, &key)?;
+        let delta = Self::read_record(&self.delta_wrapper, &key)?;
+        Ok(apply_delta(base, delta))
+    }
+
+    pub fn read_payload_batch(
+        &self,
+        point_ids: &[PointOffsetType],
+    ) -> OperationResult<Vec<Option<Payload>>> {
+        self.read_payload_batch_with(point_ids, |raw| serde_cbor::from_slice(raw))
+    }
+
+    /// Read payloads of the points, deserializing only the top-level fields kept by `selector`
+    ///
+    /// Nested paths of the selector are not applied to the result.
+    pub fn read_selected_payload_batch(
+        &self,
+        point_ids: &[PointOffsetType],
+        selector: &PayloadSelector,
+    ) -> OperationResult<Vec<Option<Payload>>> {
+        self.read_payload_batch_with(point_ids, |raw| read_selected_cbor(raw, selector))
+    }
+
+    fn read_payload_batch_with<F>(
+        &self,
+        point_ids: &[PointOffsetType],
+        mut decode: F,
+    ) -> OperationResult<Vec<Option<Payload>>>
+    where
+        F: FnMut(&[u8]) -> Result<Payload, serde_cbor::Error>,
+    {
+        let keys: Vec<_> = point_ids
+            .iter()
+            .map(|point_id| serde_cbor::to_vec(point_id).unwrap())
+            .collect();
+        let bases = self.db_wrapper.multi_get_pinned(&keys, &mut decode)?;
+        let deltas = self.delta_wrapper.multi_get_pinned(&keys, &mut decode)?;
+        bases
+            .into_iter()
+            .zip(deltas)
+            .map(|(base, delta)| -> OperationResult<_> {
+                Ok(apply_delta(base.transpose()?, delta.transpose()?))
+            })
+            .collect()
+    }
+
     pub fn iter<F>(&self, mut callback: F) -> OperationResult<()>
     where
         F: FnMut(PointOffsetType, &Payload) -> OperationResult<bool>,
     {
+        // Deltas are small and rare compared to base records, so it is fine to collect them first
+        let mut deltas = HashMap::new();
+        for (key, val) in self.delta_wrapper.lock_db().iter()? {
+            let point_id: PointOffsetType = serde_cbor::from_slice(&key)?;
+            let delta: Payload = serde_cbor::from_slice(&val)?;
+            deltas.insert(point_id, delta);
+        }
+
         for (key, val) in self.db_wrapper.lock_db().iter()? {
-            let do_continue = callback(
-                serde_cbor::from_slice(&key)?,
-                &serde_cbor::from_slice(&val)?,
-            )?;
+            let point_id: PointOffsetType = serde_cbor::from_slice(&key)?;
+            let mut payload: Payload = serde_cbor::from_slice(&val)?;
+            if let Some(delta) = deltas.remove(&point_id) {
+                payload.merge(&delta);
+            }
+            let do_continue = callback(point_id, &payload)?;
+            if !do_continue {
+                return Ok(());
+            }
+        }
+
+        // Points, which only have partial updates
+        for (point_id, delta) in deltas {
+            let payload = apply_delta(None, Some(delta)).unwrap_or_default();
+            let do_continue = callback(point_id, &payload)?;
             if !do_continue {
                 return Ok(());
             }
@@ -70,15 +188,7 @@ impl PayloadStorage for OnDiskPayloadStorage {
     }
 
     fn assign(&mut self, point_id: PointOffsetType, payload: &Payload) -> OperationResult<()> {
-        let stored_payload = self.read_payload(point_id)?;
-        match stored_payload {
-            Some(mut point_payload) => {
-                point_payload.merge(payload);
-                self.update_storage(point_id, &point_payload)?
-            }
-            None => self.update_storage(point_id, payload)?,
-        }
-        Ok(())
+        self.update_delta(point_id, payload)
     }
 
     fn payload(&self, point_id: PointOffsetType) -> OperationResult<Payload> {
@@ -89,6 +199,40 @@ impl PayloadStorage for OnDiskPayloadStorage {
         }
     }
 
+    fn payload_batch(&self, point_ids: &[PointOffsetType]) -> OperationResult<Vec<Payload>> {
+        Ok(self
+            .read_payload_batch(point_ids)?
+            .into_iter()
+            .map(Option::unwrap_or_default)
+            .collect())
+    }
+
+    fn payload_batch_selected(
+        &self,
+        point_ids: &[PointOffsetType],
+        selector: &PayloadSelector,
+    ) -> OperationResult<Vec<Payload>> {
+        Ok(self
+            .read_selected_payload_batch(point_ids, selector)?
+            .into_iter()
+            .map(|payload| selector.process(payload.unwrap_or_default()))
+            .collect())
+    }
+
+    /// Without io_uring RocksDB reads the batch one by one, so there is nothing to gain
+    #[cfg(feature = "rocksdb-io-uring")]
+    fn prefetch(&self, point_ids: &[PointOffsetType]) {
+        let keys: Vec<_> = point_ids
+            .iter()
+            .map(|point_id| serde_cbor::to_vec(point_id).unwrap())
+            .collect();
+        for wrapper in [&self.db_wrapper, &self.delta_wrapper] {
+            if let Err(err) = wrapper.multi_get_pinned(&keys, |_| ()) {
+                log::debug!("Failed to prefetch payloads: {err}");
+            }
+        }
+    }
+
     fn delete(
         &mut self,
         point_id: PointOffsetType,
@@ -115,10 +259,94 @@ impl PayloadStorage for OnDiskPayloadStorage {
     }
 
     fn wipe(&mut self) -> OperationResult<()> {
+        self.delta_wrapper.recreate_column_family()?;
         self.db_wrapper.recreate_column_family()
     }
 
     fn flusher(&self) -> Flusher {
-        self.db_wrapper.flusher()
+        let delta_flusher = self.delta_wrapper.flusher();
+        let base_flusher = self.db_wrapper.flusher();
+        Box::new(move || {
+            delta_flusher()?;
+            base_flusher()
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::Builder;
+
+    use super::*;
+    use crate::common::rocksdb_wrapper::{open_db, DB_VECTOR_CF};
+
+    #[test]
+    fn test_on_disk_storage_delta_updates() {
+        let dir = Builder::new().prefix("storage_dir").tempdir().unwrap();
+        let db = open_db(dir.path(), &[DB_VECTOR_CF]).unwrap();
+
+        let base: Payload =
+            serde_json::from_str(r#"{"text": "long document", "status": "new", "views": 0}"#)
+                .unwrap();
+
+        {
+            let mut storage = OnDiskPayloadStorage::open(db.clone()).unwrap();
+            storage.assign_all(1, &base).unwrap();
+
+            let update: Payload = serde_json::from_str(r#"{"views": 1}"#).unwrap();
+            storage.assign(1, &update).unwrap();
+            let update: Payload = serde_json::from_str(r#"{"views": 2, "status": null}"#).unwrap();
+            storage.assign(1, &update).unwrap();
+
+            // Partial update of a point without payload
+            let update: Payload = serde_json::from_str(r#"{"views": 5}"#).unwrap();
+            storage.assign(2, &update).unwrap();
+
+            let expected: Payload =
+                serde_json::from_str(r#"{"text": "long document", "views": 2}"#).unwrap();
+            assert_eq!(storage.payload(1).unwrap(), expected);
+            assert_eq!(storage.payload(2).unwrap(), update);
+            assert_eq!(
+                storage.payload_batch(&[1, 2]).unwrap(),
+                vec![expected.clone(), update.clone()],
+            );
+
+            // Selected fields are read from both base and delta records
+            let selector =
+                PayloadSelector::new_include(vec!["status".to_string(), "views".to_string()]);
+            let selected: Payload = serde_json::from_str(r#"{"views": 2}"#).unwrap();
+            assert_eq!(
+                storage.payload_batch_selected(&[1, 2], &selector).unwrap(),
+                vec![selected, update.clone()],
+            );
+
+            let mut iterated = vec![];
+            storage
+                .iter(|point_id, payload| {
+                    iterated.push((point_id, payload.clone()));
+                    Ok(true)
+                })
+                .unwrap();
+            iterated.sort_by_key(|(point_id, _)| *point_id);
+            assert_eq!(iterated, vec![(1, expected), (2, update)]);
+
+            // Base record is not rewritten by partial updates
+            let key = serde_cbor::to_vec(&1u32).unwrap();
+            let stored_base = OnDiskPayloadStorage::read_record(&storage.db_wrapper, &key).unwrap();
+            assert_eq!(stored_base, Some(base));
+
+            // Key removal consolidates delta into the base record
+            storage.delete(1, "text").unwrap();
+            let stored_delta =
+                OnDiskPayloadStorage::read_record(&storage.delta_wrapper, &key).unwrap();
+            assert_eq!(stored_delta, None);
+            storage.flusher()().unwrap();
+        }
+
+        let storage = OnDiskPayloadStorage::open(db).unwrap();
+        let expected: Payload = serde_json::from_str(r#"{"views": 2}"#).unwrap();
+        assert_eq!(storage.payload(1).unwrap(), expected);
+        let expected: Payload = serde_json::from_str(r#"{"views": 5}"#).unwrap();
+        assert_eq!(storage.payload(2).unwrap(), expected);
     }
 }
@@ -0,0 +1,112 @@
+//! Reading of the payload fields, selected by [`PayloadSelector`], without materializing the
+//! rest of the payload.
+//!
+//! Top-level fields, which are not selected, are skipped while the serialized payload is parsed,
+//! so they are never allocated. The selector is applied to the remaining fields afterwards, to
+//! take care of nested paths.
+
+use std::fmt;
+
+use serde::de::{DeserializeSeed, IgnoredAny, MapAccess, Visitor};
+use serde::Deserializer;
+use serde_json::{Map, Value};
+
+use crate::types::{Payload, PayloadSelector};
+
+/// Deserialize only the top-level fields of the payload, which are selected by `selector`
+struct SelectedFields<'a>(&'a PayloadSelector);
+
+impl<'de, 'a> DeserializeSeed<'de> for SelectedFields<'a> {
+    type Value = Payload;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Payload, D::Error> {
+        deserializer.deserialize_map(self)
+    }
+}
+
+impl<'de, 'a> Visitor<'de> for SelectedFields<'a> {
+    type Value = Payload;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a payload object")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Payload, A::Error> {
+        let mut fields = Map::new();
+        while let Some(key) = map.next_key::<String>()? {
+            if self.0.is_selected(&key) {
+                let value: Value = map.next_value()?;
+                fields.insert(key, value);
+            } else {
+                map.next_value::<IgnoredAny>()?;
+            }
+        }
+        Ok(Payload(fields))
+    }
+}
+
+/// Read selected fields of the CBOR-serialized payload
+///
+/// Nested paths of the selector are not applied, use [`PayloadSelector::process`] on the result.
+pub fn read_selected_cbor(
+    data: &[u8],
+    selector: &PayloadSelector,
+) -> Result<Payload, serde_cbor::Error> {
+    let mut deserializer = serde_cbor::Deserializer::from_slice(data);
+    let payload = SelectedFields(selector).deserialize(&mut deserializer)?;
+    deserializer.end()?;
+    Ok(payload)
+}
+
+/// Copy selected fields of the payload, without cloning the rest of it
+///
+/// Nested paths of the selector are not applied, use [`PayloadSelector::process`] on the result.
+pub fn copy_selected(payload: &Payload, selector: &PayloadSelector) -> Payload {
+    Payload(
+        payload
+            .0
+            .iter()
+            .filter(|(key, _)| selector.is_selected(key))
+            .map(|(key, value)| (key.clone(), value.clone()))
+            .collect(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_read_selected_cbor() {
+        let payload: Payload = json!({
+            "title": "small",
+            "body": "a very large text, which is never deserialized",
+            "meta": {"author": "someone", "tags": ["a", "b"]},
+        })
+        .into();
+        let data = serde_cbor::to_vec(&payload).unwrap();
+
+        let selectors = [
+            PayloadSelector::new_include(vec!["title".to_string()]),
+            PayloadSelector::new_include(vec!["meta.tags".to_string(), "missing".to_string()]),
+            PayloadSelector::new_exclude(vec!["body".to_string()]),
+            PayloadSelector::new_exclude(vec!["meta.author".to_string()]),
+        ];
+        for selector in selectors {
+            let expected = selector.process(payload.clone());
+            let selected = read_selected_cbor(&data, &selector).unwrap();
+            assert_eq!(selector.process(selected), expected);
+            let copied = copy_selected(&payload, &selector);
+            assert_eq!(selector.process(copied), expected);
+        }
+
+        let selected = read_selected_cbor(
+            &data,
+            &PayloadSelector::new_include(vec!["title".to_string()]),
+        )
+        .unwrap();
+        assert_eq!(selected, json!({"title": "small"}).into());
+    }
+}
@@ -1,9 +1,12 @@
+use std::path::PathBuf;
+
 use common::types::PointOffsetType;
 use serde_json::Value;
 
+use crate::common::checksums::Verification;
 use crate::common::operation_error::OperationResult;
 use crate::common::Flusher;
-use crate::types::{Filter, Payload, PayloadKeyTypeRef};
+use crate::types::{Filter, Payload, PayloadKeyTypeRef, PayloadSelector};
 
 /// Trait for payload data storage. Should allow filter checks
 pub trait PayloadStorage {
@@ -20,6 +23,37 @@ pub trait PayloadStorage {
     /// Get payload for point
     fn payload(&self, point_id: PointOffsetType) -> OperationResult<Payload>;
 
+    /// Get payloads for multiple points at once, in the order of `point_ids`.
+    ///
+    /// Storages which read payload from disk should override it to read the whole batch at once.
+    fn payload_batch(&self, point_ids: &[PointOffsetType]) -> OperationResult<Vec<Payload>> {
+        point_ids
+            .iter()
+            .map(|&point_id| self.payload(point_id))
+            .collect()
+    }
+
+    /// Get payloads for multiple points at once, keeping only the fields selected by `selector`.
+    ///
+    /// Storages should override it to avoid reading and cloning the fields which are not selected.
+    fn payload_batch_selected(
+        &self,
+        point_ids: &[PointOffsetType],
+        selector: &PayloadSelector,
+    ) -> OperationResult<Vec<Payload>> {
+        Ok(self
+            .payload_batch(point_ids)?
+            .into_iter()
+            .map(|payload| selector.process(payload))
+            .collect())
+    }
+
+    /// Hint, that payloads of the points are going to be read soon.
+    ///
+    /// Storages which read payload from disk may read the whole batch at once,
+    /// to overlap disk latency of the points. Errors are not reported, reads will retry anyway.
+    fn prefetch(&self, _point_ids: &[PointOffsetType]) {}
+
     /// Delete payload by key
     fn delete(
         &mut self,
@@ -35,6 +69,19 @@ pub trait PayloadStorage {
 
     /// Return function that forces persistence of current storage state.
     fn flusher(&self) -> Flusher;
+
+    /// Files, which are used by the storage besides the segment database
+    fn files(&self) -> Vec<PathBuf> {
+        vec![]
+    }
+
+    /// Compare stored payloads with their checksums, to detect corruption of the data on disk
+    ///
+    /// Storages without own checksums report no corruption. RocksDB verifies checksums of its
+    /// blocks on every read by itself.
+    fn verify_checksums(&self) -> OperationResult<Verification> {
+        Ok(Verification::default())
+    }
 }
 
 pub trait ConditionChecker {
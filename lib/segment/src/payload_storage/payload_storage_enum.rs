@@ -1,18 +1,23 @@
+use std::path::PathBuf;
+
 use common::types::PointOffsetType;
 use serde_json::Value;
 
+use crate::common::checksums::Verification;
 use crate::common::operation_error::OperationResult;
 use crate::common::Flusher;
+use crate::payload_storage::append_log_payload_storage::AppendLogPayloadStorage;
 use crate::payload_storage::in_memory_payload_storage::InMemoryPayloadStorage;
 use crate::payload_storage::on_disk_payload_storage::OnDiskPayloadStorage;
 use crate::payload_storage::simple_payload_storage::SimplePayloadStorage;
 use crate::payload_storage::PayloadStorage;
-use crate::types::{Payload, PayloadKeyTypeRef};
+use crate::types::{Payload, PayloadKeyTypeRef, PayloadSelector};
 
 pub enum PayloadStorageEnum {
     InMemoryPayloadStorage(InMemoryPayloadStorage),
     SimplePayloadStorage(SimplePayloadStorage),
     OnDiskPayloadStorage(OnDiskPayloadStorage),
+    AppendLogPayloadStorage(AppendLogPayloadStorage),
 }
 
 impl From<InMemoryPayloadStorage> for PayloadStorageEnum {
@@ -33,6 +38,12 @@ impl From<OnDiskPayloadStorage> for PayloadStorageEnum {
     }
 }
 
+impl From<AppendLogPayloadStorage> for PayloadStorageEnum {
+    fn from(a: AppendLogPayloadStorage) -> Self {
+        PayloadStorageEnum::AppendLogPayloadStorage(a)
+    }
+}
+
 impl PayloadStorageEnum {
     pub fn iter<F>(&self, callback: F) -> OperationResult<()>
     where
@@ -42,16 +53,27 @@ impl PayloadStorageEnum {
             PayloadStorageEnum::InMemoryPayloadStorage(s) => s.iter(callback),
             PayloadStorageEnum::SimplePayloadStorage(s) => s.iter(callback),
             PayloadStorageEnum::OnDiskPayloadStorage(s) => s.iter(callback),
+            PayloadStorageEnum::AppendLogPayloadStorage(s) => s.iter(callback),
         }
     }
 }
 
 impl PayloadStorage for PayloadStorageEnum {
+    fn assign_all(&mut self, point_id: PointOffsetType, payload: &Payload) -> OperationResult<()> {
+        match self {
+            PayloadStorageEnum::InMemoryPayloadStorage(s) => s.assign_all(point_id, payload),
+            PayloadStorageEnum::SimplePayloadStorage(s) => s.assign_all(point_id, payload),
+            PayloadStorageEnum::OnDiskPayloadStorage(s) => s.assign_all(point_id, payload),
+            PayloadStorageEnum::AppendLogPayloadStorage(s) => s.assign_all(point_id, payload),
+        }
+    }
+
     fn assign(&mut self, point_id: PointOffsetType, payload: &Payload) -> OperationResult<()> {
         match self {
             PayloadStorageEnum::InMemoryPayloadStorage(s) => s.assign(point_id, payload),
             PayloadStorageEnum::SimplePayloadStorage(s) => s.assign(point_id, payload),
             PayloadStorageEnum::OnDiskPayloadStorage(s) => s.assign(point_id, payload),
+            PayloadStorageEnum::AppendLogPayloadStorage(s) => s.assign(point_id, payload),
         }
     }
 
@@ -60,6 +82,46 @@ impl PayloadStorage for PayloadStorageEnum {
             PayloadStorageEnum::InMemoryPayloadStorage(s) => s.payload(point_id),
             PayloadStorageEnum::SimplePayloadStorage(s) => s.payload(point_id),
             PayloadStorageEnum::OnDiskPayloadStorage(s) => s.payload(point_id),
+            PayloadStorageEnum::AppendLogPayloadStorage(s) => s.payload(point_id),
+        }
+    }
+
+    fn payload_batch(&self, point_ids: &[PointOffsetType]) -> OperationResult<Vec<Payload>> {
+        match self {
+            PayloadStorageEnum::InMemoryPayloadStorage(s) => s.payload_batch(point_ids),
+            PayloadStorageEnum::SimplePayloadStorage(s) => s.payload_batch(point_ids),
+            PayloadStorageEnum::OnDiskPayloadStorage(s) => s.payload_batch(point_ids),
+            PayloadStorageEnum::AppendLogPayloadStorage(s) => s.payload_batch(point_ids),
+        }
+    }
+
+    fn payload_batch_selected(
+        &self,
+        point_ids: &[PointOffsetType],
+        selector: &PayloadSelector,
+    ) -> OperationResult<Vec<Payload>> {
+        match self {
+            PayloadStorageEnum::InMemoryPayloadStorage(s) => {
+                s.payload_batch_selected(point_ids, selector)
+            }
+            PayloadStorageEnum::SimplePayloadStorage(s) => {
+                s.payload_batch_selected(point_ids, selector)
+            }
+            PayloadStorageEnum::OnDiskPayloadStorage(s) => {
+                s.payload_batch_selected(point_ids, selector)
+            }
+            PayloadStorageEnum::AppendLogPayloadStorage(s) => {
+                s.payload_batch_selected(point_ids, selector)
+            }
+        }
+    }
+
+    fn prefetch(&self, point_ids: &[PointOffsetType]) {
+        match self {
+            PayloadStorageEnum::InMemoryPayloadStorage(s) => s.prefetch(point_ids),
+            PayloadStorageEnum::SimplePayloadStorage(s) => s.prefetch(point_ids),
+            PayloadStorageEnum::OnDiskPayloadStorage(s) => s.prefetch(point_ids),
+            PayloadStorageEnum::AppendLogPayloadStorage(s) => s.prefetch(point_ids),
         }
     }
 
@@ -72,6 +134,7 @@ impl PayloadStorage for PayloadStorageEnum {
             PayloadStorageEnum::InMemoryPayloadStorage(s) => s.delete(point_id, key),
             PayloadStorageEnum::SimplePayloadStorage(s) => s.delete(point_id, key),
             PayloadStorageEnum::OnDiskPayloadStorage(s) => s.delete(point_id, key),
+            PayloadStorageEnum::AppendLogPayloadStorage(s) => s.delete(point_id, key),
         }
     }
 
@@ -80,6 +143,7 @@ impl PayloadStorage for PayloadStorageEnum {
             PayloadStorageEnum::InMemoryPayloadStorage(s) => s.drop(point_id),
             PayloadStorageEnum::SimplePayloadStorage(s) => s.drop(point_id),
             PayloadStorageEnum::OnDiskPayloadStorage(s) => s.drop(point_id),
+            PayloadStorageEnum::AppendLogPayloadStorage(s) => s.drop(point_id),
         }
     }
 
@@ -88,6 +152,7 @@ impl PayloadStorage for PayloadStorageEnum {
             PayloadStorageEnum::InMemoryPayloadStorage(s) => s.wipe(),
             PayloadStorageEnum::SimplePayloadStorage(s) => s.wipe(),
             PayloadStorageEnum::OnDiskPayloadStorage(s) => s.wipe(),
+            PayloadStorageEnum::AppendLogPayloadStorage(s) => s.wipe(),
         }
     }
 
@@ -96,6 +161,25 @@ impl PayloadStorage for PayloadStorageEnum {
             PayloadStorageEnum::InMemoryPayloadStorage(s) => s.flusher(),
             PayloadStorageEnum::SimplePayloadStorage(s) => s.flusher(),
             PayloadStorageEnum::OnDiskPayloadStorage(s) => s.flusher(),
+            PayloadStorageEnum::AppendLogPayloadStorage(s) => s.flusher(),
+        }
+    }
+
+    fn files(&self) -> Vec<PathBuf> {
+        match self {
+            PayloadStorageEnum::InMemoryPayloadStorage(s) => s.files(),
+            PayloadStorageEnum::SimplePayloadStorage(s) => s.files(),
+            PayloadStorageEnum::OnDiskPayloadStorage(s) => s.files(),
+            PayloadStorageEnum::AppendLogPayloadStorage(s) => s.files(),
+        }
+    }
+
+    fn verify_checksums(&self) -> OperationResult<Verification> {
+        match self {
+            PayloadStorageEnum::InMemoryPayloadStorage(s) => s.verify_checksums(),
+            PayloadStorageEnum::SimplePayloadStorage(s) => s.verify_checksums(),
+            PayloadStorageEnum::OnDiskPayloadStorage(s) => s.verify_checksums(),
+            PayloadStorageEnum::AppendLogPayloadStorage(s) => s.verify_checksums(),
         }
     }
 }
@@ -177,6 +261,11 @@ mod tests {
                 serde_json::from_str(r#"{ "hobby": "vector search" }"#).unwrap();
             storage.assign(100, &partial_payload).unwrap();
 
+            let batch = storage.payload_batch(&[100, 101]).unwrap();
+            assert_eq!(batch.len(), 2);
+            assert_eq!(batch[0], storage.payload(100).unwrap());
+            assert_eq!(batch[1], Payload::default());
+
             storage.delete(100, "location.city").unwrap();
             storage.delete(100, "location").unwrap();
 
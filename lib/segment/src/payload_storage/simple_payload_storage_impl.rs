@@ -5,9 +5,10 @@ use serde_json::Value;
 
 use crate::common::operation_error::OperationResult;
 use crate::common::Flusher;
+use crate::payload_storage::payload_projection::copy_selected;
 use crate::payload_storage::simple_payload_storage::SimplePayloadStorage;
 use crate::payload_storage::PayloadStorage;
-use crate::types::{Payload, PayloadKeyTypeRef};
+use crate::types::{Payload, PayloadKeyTypeRef, PayloadSelector};
 
 impl PayloadStorage for SimplePayloadStorage {
     fn assign(&mut self, point_id: PointOffsetType, payload: &Payload) -> OperationResult<()> {
@@ -30,6 +31,20 @@ impl PayloadStorage for SimplePayloadStorage {
         }
     }
 
+    fn payload_batch_selected(
+        &self,
+        point_ids: &[PointOffsetType],
+        selector: &PayloadSelector,
+    ) -> OperationResult<Vec<Payload>> {
+        Ok(point_ids
+            .iter()
+            .map(|point_id| match self.payload.get(point_id) {
+                Some(payload) => selector.process(copy_selected(payload, selector)),
+                None => Default::default(),
+            })
+            .collect())
+    }
+
     fn delete(
         &mut self,
         point_id: PointOffsetType,
@@ -9,13 +9,15 @@ use std::thread::{self, JoinHandle};
 use atomic_refcell::AtomicRefCell;
 use common::types::{PointOffsetType, ScoredPointOffset};
 use io::file_operations::{atomic_save_json, read_json};
+use itertools::Itertools;
 use memory::mmap_ops;
 use parking_lot::{Mutex, RwLock};
+use rand::seq::IteratorRandom;
 use rocksdb::DB;
-use sparse::common::sparse_vector::SparseVector;
 use tar::Builder;
 use uuid::Uuid;
 
+use crate::common::checksums::{self, Verification};
 use crate::common::operation_error::OperationError::TypeInferenceError;
 use crate::common::operation_error::{
     get_service_error, OperationError, OperationResult, SegmentFailedState,
@@ -25,18 +27,20 @@ use crate::common::{
     check_named_vectors, check_query_vectors, check_stopped, check_vector, check_vector_name,
 };
 use crate::data_types::named_vectors::NamedVectors;
-use crate::data_types::vectors::{QueryVector, Vector};
+use crate::data_types::order_by::{Direction, OrderBy};
+use crate::data_types::vectors::{QueryVector, Vector, VectorStruct};
 use crate::entry::entry_point::SegmentEntry;
 use crate::id_tracker::IdTrackerSS;
 use crate::index::field_index::CardinalityEstimation;
-use crate::index::struct_payload_index::StructPayloadIndex;
+use crate::index::struct_payload_index::{StructPayloadIndex, PAYLOAD_PREFETCH_BATCH};
 use crate::index::{PayloadIndex, VectorIndex, VectorIndexEnum};
 use crate::spaces::tools::peek_top_smallest_iterable;
 use crate::telemetry::SegmentTelemetry;
 use crate::types::{
-    Filter, Payload, PayloadFieldSchema, PayloadIndexInfo, PayloadKeyType, PayloadKeyTypeRef,
-    PayloadSchemaType, PointIdType, ScoredPoint, SearchParams, SegmentConfig, SegmentInfo,
-    SegmentState, SegmentType, SeqNumberType, VectorDataInfo, WithPayload, WithVector,
+    Filter, FloatPayloadType, Payload, PayloadFieldSchema, PayloadIndexInfo, PayloadKeyType,
+    PayloadKeyTypeRef, PayloadSchemaType, PointIdType, RetrievedPoint, ScoredPoint, SearchParams,
+    SegmentConfig, SegmentInfo, SegmentState, SegmentType, SeqNumberType, VectorDataInfo,
+    WithPayload, WithVector,
 };
 use crate::utils;
 use crate::utils::fs::find_symlink;
@@ -206,13 +210,7 @@ impl Segment {
             let mut vector_index = vector_data.vector_index.borrow_mut();
             match vector_opt {
                 None => {
-                    let dim = vector_storage.vector_dim();
-                    let vector: Vector = match *vector_storage {
-                        VectorStorageEnum::DenseSimple(_)
-                        | VectorStorageEnum::Memmap(_)
-                        | VectorStorageEnum::AppendableMemmap(_) => vec![1.0; dim].into(),
-                        VectorStorageEnum::SparseSimple(_) => SparseVector::default().into(),
-                    };
+                    let vector = vector_storage.default_vector();
                     vector_storage.insert_vector(new_index, vector.to_vec_ref())?;
                     vector_storage.delete_vector(new_index)?;
                     vector_index.update_vector(new_index, vector.to_vec_ref())?;
@@ -368,6 +366,19 @@ impl Segment {
         }
     }
 
+    /// Total size of the files in the segment directory, including indexes and payload storage
+    ///
+    /// Files, which are removed while the directory is walked, are not counted.
+    fn disk_usage_bytes(&self) -> usize {
+        walkdir::WalkDir::new(&self.current_path)
+            .into_iter()
+            .filter_map(Result::ok)
+            .filter_map(|entry| entry.metadata().ok())
+            .filter(|metadata| metadata.is_file())
+            .map(|metadata| metadata.len() as usize)
+            .sum()
+    }
+
     pub fn save_state(state: &SegmentState, current_path: &Path) -> OperationResult<()> {
         let state_path = current_path.join(SEGMENT_STATE_FILE);
         Ok(atomic_save_json(&state_path, state)?)
@@ -451,6 +462,48 @@ impl Segment {
         self.payload_index.borrow().payload(point_offset)
     }
 
+    /// Retrieve payload by internal ID, if requested by `with_payload`
+    fn payload_by_offset_with(
+        &self,
+        point_offset: PointOffsetType,
+        with_payload: &WithPayload,
+    ) -> OperationResult<Option<Payload>> {
+        if !with_payload.enable {
+            return Ok(None);
+        }
+        let payload = match &with_payload.payload_selector {
+            Some(selector) => self
+                .payload_index
+                .borrow()
+                .payload_batch_selected(&[point_offset], selector)?
+                .pop()
+                .unwrap_or_default(),
+            None => self.payload_by_offset(point_offset)?,
+        };
+        Ok(Some(payload))
+    }
+
+    /// Retrieve vectors by internal ID, selected by `with_vector`
+    fn vectors_by_offset_with(
+        &self,
+        point_offset: PointOffsetType,
+        with_vector: &WithVector,
+    ) -> OperationResult<Option<VectorStruct>> {
+        match with_vector {
+            WithVector::Bool(false) => Ok(None),
+            WithVector::Bool(true) => Ok(Some(self.all_vectors_by_offset(point_offset)?.into())),
+            WithVector::Selector(vectors) => {
+                let mut result = NamedVectors::default();
+                for vector_name in vectors {
+                    if let Some(vector) = self.vector_by_offset(vector_name, point_offset)? {
+                        result.insert(vector_name.clone(), vector);
+                    }
+                }
+                Ok(Some(result.into()))
+            }
+        }
+    }
+
     pub fn save_current_state(&self) -> OperationResult<()> {
         Self::save_state(&self.get_state(), &self.current_path)
     }
@@ -577,34 +630,8 @@ impl Segment {
                         "Corrupter id_tracker, no version for point {point_id}"
                     ))
                 })?;
-                let payload = if with_payload.enable {
-                    let initial_payload = self.payload_by_offset(point_offset)?;
-                    let processed_payload = if let Some(i) = &with_payload.payload_selector {
-                        i.process(initial_payload)
-                    } else {
-                        initial_payload
-                    };
-                    Some(processed_payload)
-                } else {
-                    None
-                };
-                let vector = match with_vector {
-                    WithVector::Bool(false) => None,
-                    WithVector::Bool(true) => {
-                        Some(self.all_vectors_by_offset(point_offset)?.into())
-                    }
-                    WithVector::Selector(vectors) => {
-                        let mut result = NamedVectors::default();
-                        for vector_name in vectors {
-                            if let Some(vector) =
-                                self.vector_by_offset(vector_name, point_offset)?
-                            {
-                                result.insert(vector_name.clone(), vector);
-                            }
-                        }
-                        Some(result.into())
-                    }
-                };
+                let payload = self.payload_by_offset_with(point_offset, with_payload)?;
+                let vector = self.vectors_by_offset_with(point_offset, with_vector)?;
 
                 Ok(ScoredPoint {
                     id: point_id,
@@ -657,13 +684,34 @@ impl Segment {
     ) -> Vec<PointIdType> {
         let payload_index = self.payload_index.borrow();
         let filter_context = payload_index.filter_context(condition);
-        self.id_tracker
-            .borrow()
-            .iter_from(offset)
-            .filter(move |(_, internal_id)| filter_context.check(*internal_id))
-            .map(|(external_id, _)| external_id)
-            .take(limit.unwrap_or(usize::MAX))
-            .collect()
+        let id_tracker = self.id_tracker.borrow();
+        let limit = limit.unwrap_or(usize::MAX);
+        if !payload_index.filter_reads_payload(condition) {
+            return id_tracker
+                .iter_from(offset)
+                .filter(move |(_, internal_id)| filter_context.check(*internal_id))
+                .map(|(external_id, _)| external_id)
+                .take(limit)
+                .collect();
+        }
+
+        // Conditions are checked against payloads, which might be on disk:
+        // read payloads of a batch of candidates at once, instead of waiting for each of them
+        let mut page = Vec::new();
+        for batch in &id_tracker.iter_from(offset).chunks(PAYLOAD_PREFETCH_BATCH) {
+            let batch: Vec<_> = batch.collect();
+            let internal_ids: Vec<_> = batch.iter().map(|(_, internal_id)| *internal_id).collect();
+            payload_index.prefetch_payloads(&internal_ids);
+            for (external_id, internal_id) in batch {
+                if filter_context.check(internal_id) {
+                    page.push(external_id);
+                    if page.len() >= limit {
+                        return page;
+                    }
+                }
+            }
+        }
+        page
     }
 
     /// Check consistency of the segment's data and repair it if possible.
@@ -719,12 +767,16 @@ impl Segment {
         self.id_tracker.borrow().total_point_count()
     }
 
-    pub fn prefault_mmap_pages(&self) {
-        let tasks: Vec<_> = self
-            .vector_data
+    /// Tasks, which read mmap-ed vector storages and HNSW graphs of the segment into page cache
+    pub fn prefault_mmap_pages_tasks(&self) -> Vec<mmap_ops::PrefaultMmapPages> {
+        self.vector_data
             .values()
             .flat_map(|data| data.prefault_mmap_pages())
-            .collect();
+            .collect()
+    }
+
+    pub fn prefault_mmap_pages(&self) {
+        let tasks = self.prefault_mmap_pages_tasks();
 
         let _ = thread::Builder::new()
             .name(format!(
@@ -733,6 +785,32 @@ impl Segment {
             ))
             .spawn(move || tasks.iter().for_each(mmap_ops::PrefaultMmapPages::exec));
     }
+
+    /// Files of the segment, which are not changed anymore and are protected by block checksums
+    ///
+    /// Only vector storages are covered, HNSW graphs, quantized vectors and payload indexes can be
+    /// rebuilt from them and are not checksummed.
+    pub fn checksummed_files(&self) -> Vec<PathBuf> {
+        self.vector_data
+            .values()
+            .flat_map(|data| data.vector_storage.borrow().immutable_files())
+            .collect()
+    }
+
+    /// Write checksums of the immutable files
+    ///
+    /// Must only be called once the segment is built, when the files are known to be intact.
+    /// Checksums, calculated later, would accept the corruption, which happened in the meantime.
+    pub fn write_checksums(&self) -> OperationResult<()> {
+        for path in self.checksummed_files() {
+            checksums::write_checksums(&path)?;
+        }
+        Ok(())
+    }
+
+    pub fn verify_payload_checksums(&self) -> OperationResult<Verification> {
+        self.payload_index.borrow().verify_payload_checksums()
+    }
 }
 
 /// This is a basic implementation of `SegmentEntry`,
@@ -815,7 +893,7 @@ impl SegmentEntry for Segment {
     ) -> OperationResult<bool> {
         debug_assert!(self.is_appendable());
         check_named_vectors(&vectors, &self.segment_config)?;
-        vectors.preprocess(|name| self.segment_config.distance(name).unwrap());
+        vectors.preprocess(|name| self.segment_config.preprocessing_distance(name));
         let stored_internal_point = self.id_tracker.borrow().internal_id(point_id);
         self.handle_version_and_failure(op_num, stored_internal_point, |segment| {
             if let Some(existing_internal_id) = stored_internal_point {
@@ -868,7 +946,7 @@ impl SegmentEntry for Segment {
         mut vectors: NamedVectors,
     ) -> OperationResult<bool> {
         check_named_vectors(&vectors, &self.segment_config)?;
-        vectors.preprocess(|name| self.segment_config.distance(name).unwrap());
+        vectors.preprocess(|name| self.segment_config.preprocessing_distance(name));
         let internal_id = self.id_tracker.borrow().internal_id(point_id);
         match internal_id {
             None => Err(OperationError::PointIdError {
@@ -1012,6 +1090,53 @@ impl SegmentEntry for Segment {
         self.payload_by_offset(internal_id)
     }
 
+    fn payload_batch(&self, point_ids: &[PointIdType]) -> OperationResult<Vec<Payload>> {
+        let internal_ids = point_ids
+            .iter()
+            .map(|&point_id| self.lookup_internal_id(point_id))
+            .collect::<OperationResult<Vec<_>>>()?;
+        self.payload_index.borrow().payload_batch(&internal_ids)
+    }
+
+    fn retrieve(
+        &self,
+        point_ids: &[PointIdType],
+        with_payload: &WithPayload,
+        with_vector: &WithVector,
+    ) -> OperationResult<Vec<RetrievedPoint>> {
+        let mut points = point_ids
+            .iter()
+            .map(|&point_id| Ok((self.lookup_internal_id(point_id)?, point_id)))
+            .collect::<OperationResult<Vec<_>>>()?;
+        // Read storages in the order of internal offsets, so that access is sequential
+        points.sort_unstable_by_key(|(point_offset, _)| *point_offset);
+
+        let mut payloads = if with_payload.enable {
+            let point_offsets: Vec<_> = points.iter().map(|(offset, _)| *offset).collect();
+            // Fields, which are not selected, are skipped while reading from the storage
+            let payload_index = self.payload_index.borrow();
+            let payloads = match &with_payload.payload_selector {
+                Some(selector) => payload_index.payload_batch_selected(&point_offsets, selector)?,
+                None => payload_index.payload_batch(&point_offsets)?,
+            };
+            Some(payloads.into_iter())
+        } else {
+            None
+        };
+
+        points
+            .into_iter()
+            .map(|(point_offset, point_id)| {
+                let payload = payloads.as_mut().and_then(|payloads| payloads.next());
+                Ok(RetrievedPoint {
+                    id: point_id,
+                    payload,
+                    vector: self.vectors_by_offset_with(point_offset, with_vector)?,
+                })
+            })
+            .collect()
+    }
+
     fn iter_points(&self) -> Box<dyn Iterator<Item = PointIdType> + '_> {
         // Sorry for that, but I didn't find any way easier.
         // If you try simply return iterator - it won't work because AtomicRef should exist
@@ -1078,6 +1203,66 @@ impl SegmentEntry for Segment {
         }
     }
 
+    fn read_ordered_filtered<'a>(
+        &'a self,
+        limit: Option<usize>,
+        filter: Option<&'a Filter>,
+        order_by: &'a OrderBy,
+    ) -> OperationResult<Vec<(FloatPayloadType, PointIdType)>> {
+        let payload_index = self.payload_index.borrow();
+        let range = order_by.value_range();
+        let values = payload_index
+            .field_indexes
+            .get(&order_by.key)
+            .and_then(|indexes| indexes.iter().find_map(|index| index.ordered_values(&range)))
+            .ok_or_else(|| OperationError::ValidationError {
+                description: format!(
+                    "No numeric index for `order_by` key `{}`, create an integer or float index to order by it",
+                    order_by.key,
+                ),
+            })?;
+        let values: Box<dyn DoubleEndedIterator<Item = (FloatPayloadType, PointOffsetType)> + '_> =
+            match order_by.direction() {
+                Direction::Asc => values,
+                Direction::Desc => Box::new(values.rev()),
+            };
+
+        let filter_context = filter.map(|filter| payload_index.filter_context(filter));
+        let id_tracker = self.id_tracker.borrow();
+        // Points with multiple values are returned once, by their first value in the order
+        let mut seen = HashSet::new();
+        Ok(values
+            .filter(|(_, internal_id)| {
+                filter_context
+                    .as_ref()
+                    .map_or(true, |context| context.check(*internal_id))
+            })
+            .filter(|(_, internal_id)| seen.insert(*internal_id))
+            .filter_map(|(value, internal_id)| Some((value, id_tracker.external_id(internal_id)?)))
+            .take(limit.unwrap_or(usize::MAX))
+            .collect())
+    }
+
+    fn read_random_filtered<'a>(
+        &'a self,
+        limit: usize,
+        filter: Option<&'a Filter>,
+    ) -> Vec<PointIdType> {
+        let mut rng = rand::thread_rng();
+        match filter {
+            None => self
+                .id_tracker
+                .borrow()
+                .iter_from(None)
+                .map(|x| x.0)
+                .choose_multiple(&mut rng, limit),
+            Some(_) => self
+                .read_filtered(None, None, filter)
+                .into_iter()
+                .choose_multiple(&mut rng, limit),
+        }
+    }
+
     fn read_range(&self, from: Option<PointIdType>, to: Option<PointIdType>) -> Vec<PointIdType> {
         let id_tracker = self.id_tracker.borrow();
         let iterator = id_tracker.iter_from(from).map(|x| x.0);
@@ -1174,8 +1359,8 @@ impl SegmentEntry for Segment {
             num_indexed_vectors,
             num_points: self.available_point_count(),
             num_deleted_vectors: self.deleted_point_count(),
-            ram_usage_bytes: 0,  // ToDo: Implement
-            disk_usage_bytes: 0, // ToDo: Implement
+            ram_usage_bytes: 0, // ToDo: Implement
+            disk_usage_bytes: self.disk_usage_bytes(),
             is_appendable: self.appendable_flag,
             index_schema: schema,
             vector_data: vector_data_info,
@@ -1190,6 +1375,14 @@ impl SegmentEntry for Segment {
         self.appendable_flag
     }
 
+    fn is_dirty(&self) -> bool {
+        match (self.version, *self.persisted_version.lock()) {
+            (None, _) => false,
+            (Some(version), Some(persisted_version)) => version != persisted_version,
+            (Some(_), None) => true,
+        }
+    }
+
     fn flush(&self, sync: bool) -> OperationResult<SeqNumberType> {
         let current_persisted_version: Option<SeqNumberType> = *self.persisted_version.lock();
         if !sync && self.is_background_flushing() {
@@ -1473,6 +1666,15 @@ impl SegmentEntry for Segment {
 
         let files = Path::new(SNAPSHOT_PATH).join(SNAPSHOT_FILES_PATH);
 
+        for file in self.id_tracker.borrow().files() {
+            utils::tar::append_file_relative_to_base(
+                &mut builder,
+                &self.current_path,
+                &file,
+                &files,
+            )?;
+        }
+
         for vector_data in self.vector_data.values() {
             for file in vector_data.vector_index.borrow().files() {
                 utils::tar::append_file_relative_to_base(
@@ -1576,7 +1778,10 @@ mod tests {
     use crate::common::operation_error::OperationError::PointIdError;
     use crate::data_types::vectors::{only_default_vector, DEFAULT_VECTOR_NAME};
     use crate::segment_constructor::{build_segment, load_segment};
-    use crate::types::{Distance, Indexes, SegmentConfig, VectorDataConfig, VectorStorageType};
+    use crate::types::{
+        Condition, Distance, FieldCondition, Indexes, PayloadStorageType, Range, SegmentConfig,
+        VectorDataConfig, VectorStorageType,
+    };
 
     // no longer valid since users are now allowed to store arbitrary json objects.
     // TODO(gvelo): add tests for invalid payload types on indexed fields.
@@ -1625,6 +1830,8 @@ mod tests {
                     storage_type: VectorStorageType::Memory,
                     index: Indexes::Plain {},
                     quantization_config: None,
+                    multivector_config: None,
+                    datatype: None,
                 },
             )]),
             sparse_vector_data: Default::default(),
@@ -1698,6 +1905,8 @@ mod tests {
                     storage_type: VectorStorageType::Memory,
                     index: Indexes::Plain {},
                     quantization_config: None,
+                    multivector_config: None,
+                    datatype: None,
                 },
             )]),
             sparse_vector_data: Default::default(),
@@ -1790,6 +1999,8 @@ mod tests {
                     storage_type: VectorStorageType::Memory,
                     index: Indexes::Plain {},
                     quantization_config: None,
+                    multivector_config: None,
+                    datatype: None,
                 },
             )]),
             sparse_vector_data: Default::default(),
@@ -1882,6 +2093,8 @@ mod tests {
                     storage_type: VectorStorageType::Memory,
                     index: Indexes::Plain {},
                     quantization_config: None,
+                    multivector_config: None,
+                    datatype: None,
                 },
             )]),
             sparse_vector_data: Default::default(),
@@ -1914,6 +2127,8 @@ mod tests {
                     storage_type: VectorStorageType::Memory,
                     index: Indexes::Plain {},
                     quantization_config: None,
+                    multivector_config: None,
+                    datatype: None,
                 },
             )]),
             sparse_vector_data: Default::default(),
@@ -2009,6 +2224,8 @@ mod tests {
                     storage_type: VectorStorageType::Memory,
                     index: Indexes::Plain {},
                     quantization_config: None,
+                    multivector_config: None,
+                    datatype: None,
                 },
             )]),
             sparse_vector_data: Default::default(),
@@ -2048,6 +2265,191 @@ mod tests {
         // assert_eq!(segment_info.num_vectors, 1);
     }
 
+    #[test]
+    fn test_disk_usage() {
+        let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+        let config = SegmentConfig {
+            vector_data: HashMap::from([(
+                DEFAULT_VECTOR_NAME.to_owned(),
+                VectorDataConfig {
+                    size: 4,
+                    distance: Distance::Dot,
+                    storage_type: VectorStorageType::Memory,
+                    index: Indexes::Plain {},
+                    quantization_config: None,
+                    multivector_config: None,
+                    datatype: None,
+                },
+            )]),
+            sparse_vector_data: Default::default(),
+            payload_storage_type: Default::default(),
+        };
+        let mut segment = build_segment(dir.path(), &config, true).unwrap();
+        let empty_disk_usage = segment.info().disk_usage_bytes;
+        assert!(empty_disk_usage > 0);
+
+        for i in 0..1000 {
+            segment
+                .upsert_point(i, i.into(), only_default_vector(&[i as f32; 4]))
+                .unwrap();
+        }
+        segment.flush(true).unwrap();
+
+        // Stored points are counted
+        assert!(segment.info().disk_usage_bytes > empty_disk_usage);
+    }
+
+    #[test]
+    fn test_retrieve_batch() {
+        let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+        let config = SegmentConfig {
+            vector_data: HashMap::from([(
+                DEFAULT_VECTOR_NAME.to_owned(),
+                VectorDataConfig {
+                    size: 1,
+                    distance: Distance::Dot,
+                    storage_type: VectorStorageType::Memory,
+                    index: Indexes::Plain {},
+                    quantization_config: None,
+                    multivector_config: None,
+                    datatype: None,
+                },
+            )]),
+            sparse_vector_data: Default::default(),
+            payload_storage_type: PayloadStorageType::OnDisk,
+        };
+        let mut segment = build_segment(dir.path(), &config, true).unwrap();
+
+        for i in 0..10u64 {
+            segment
+                .upsert_point(2 * i, i.into(), only_default_vector(&[i as f32]))
+                .unwrap();
+            let payload: Payload = serde_json::from_str(&format!(r#"{{"num": {i}}}"#)).unwrap();
+            segment
+                .set_full_payload(2 * i + 1, i.into(), &payload)
+                .unwrap();
+        }
+
+        let with_payload = WithPayload {
+            enable: true,
+            payload_selector: None,
+        };
+        let point_ids: Vec<PointIdType> = vec![7.into(), 2.into(), 5.into()];
+        let mut points = segment
+            .retrieve(&point_ids, &with_payload, &WithVector::Bool(true))
+            .unwrap();
+        points.sort_by_key(|point| point.id);
+
+        assert_eq!(points.len(), 3);
+        for (point, expected) in points.iter().zip([2u64, 5, 7]) {
+            assert_eq!(point.id, expected.into());
+            let expected_payload: Payload =
+                serde_json::from_str(&format!(r#"{{"num": {expected}}}"#)).unwrap();
+            assert_eq!(point.payload.as_ref(), Some(&expected_payload));
+            assert_eq!(point.vector, Some(vec![expected as f32].into()));
+        }
+
+        let points = segment
+            .retrieve(
+                &point_ids,
+                &WithPayload::default(),
+                &WithVector::Bool(false),
+            )
+            .unwrap();
+        assert!(points
+            .iter()
+            .all(|point| point.payload.is_none() && point.vector.is_none()));
+
+        // Missing points are reported as errors
+        let result = segment.retrieve(&[100.into()], &with_payload, &WithVector::Bool(false));
+        assert!(matches!(result, Err(PointIdError { .. })));
+    }
+
+    #[test]
+    fn test_read_ordered_filtered() {
+        let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+        let config = SegmentConfig {
+            vector_data: HashMap::from([(
+                DEFAULT_VECTOR_NAME.to_owned(),
+                VectorDataConfig {
+                    size: 1,
+                    distance: Distance::Dot,
+                    storage_type: VectorStorageType::Memory,
+                    index: Indexes::Plain {},
+                    quantization_config: None,
+                    multivector_config: None,
+                    datatype: None,
+                },
+            )]),
+            sparse_vector_data: Default::default(),
+            payload_storage_type: PayloadStorageType::InMemory,
+        };
+        let mut segment = build_segment(dir.path(), &config, true).unwrap();
+
+        let order_by = OrderBy {
+            key: "num".to_string(),
+            direction: None,
+            start_from: None,
+        };
+        // Ordering requires a numeric index
+        assert!(segment
+            .read_ordered_filtered(None, None, &order_by)
+            .is_err());
+        segment
+            .create_field_index(0, "num", Some(&PayloadSchemaType::Integer.into()))
+            .unwrap();
+
+        // Values are in reverse order of ids, point 0 has two values
+        for i in 0..10u64 {
+            segment
+                .upsert_point(1 + 2 * i, i.into(), only_default_vector(&[i as f32]))
+                .unwrap();
+            let payload: Payload =
+                serde_json::from_str(&format!(r#"{{"num": {}}}"#, 100 - i)).unwrap();
+            segment
+                .set_full_payload(2 + 2 * i, i.into(), &payload)
+                .unwrap();
+        }
+        let payload: Payload = serde_json::from_str(r#"{"num": [100, 50]}"#).unwrap();
+        segment.set_full_payload(30, 0.into(), &payload).unwrap();
+        segment.delete_point(31, 5.into()).unwrap();
+
+        let ids = |points: Vec<(FloatPayloadType, PointIdType)>| {
+            points.into_iter().map(|(_, id)| id).collect::<Vec<_>>()
+        };
+
+        let points = segment
+            .read_ordered_filtered(Some(4), None, &order_by)
+            .unwrap();
+        assert_eq!(points[0], (50.0, 0.into()));
+        assert_eq!(ids(points), vec![0.into(), 9.into(), 8.into(), 7.into()]);
+
+        let order_by = OrderBy {
+            direction: Some(Direction::Desc),
+            start_from: Some(96.0),
+            ..order_by
+        };
+        let points = segment
+            .read_ordered_filtered(None, None, &order_by)
+            .unwrap();
+        assert_eq!(
+            ids(points),
+            vec![4.into(), 6.into(), 7.into(), 8.into(), 9.into(), 0.into()],
+        );
+
+        let filter = Filter::new_must(Condition::Field(FieldCondition::new_range(
+            "num".to_string(),
+            Range {
+                gt: Some(92.0),
+                ..Default::default()
+            },
+        )));
+        let points = segment
+            .read_ordered_filtered(Some(2), Some(&filter), &order_by)
+            .unwrap();
+        assert_eq!(ids(points), vec![4.into(), 6.into()]);
+    }
+
     #[test]
     fn test_point_vector_count_multivec() {
         let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
@@ -2062,6 +2464,8 @@ mod tests {
                         storage_type: VectorStorageType::Memory,
                         index: Indexes::Plain {},
                         quantization_config: None,
+                        multivector_config: None,
+                        datatype: None,
                     },
                 ),
                 (
@@ -2072,6 +2476,8 @@ mod tests {
                         storage_type: VectorStorageType::Memory,
                         index: Indexes::Plain {},
                         quantization_config: None,
+                        multivector_config: None,
+                        datatype: None,
                     },
                 ),
             ]),
@@ -2168,6 +2574,8 @@ mod tests {
                         storage_type: VectorStorageType::Memory,
                         index: Indexes::Plain {},
                         quantization_config: None,
+                        multivector_config: None,
+                        datatype: None,
                     },
                 ),
                 (
@@ -2178,6 +2586,8 @@ mod tests {
                         storage_type: VectorStorageType::Memory,
                         index: Indexes::Plain {},
                         quantization_config: None,
+                        multivector_config: None,
+                        datatype: None,
                     },
                 ),
             ]),
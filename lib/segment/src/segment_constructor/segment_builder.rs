@@ -3,12 +3,16 @@ use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 use std::sync::atomic::AtomicBool;
 
+use common::types::PointOffsetType;
+
 use super::get_vector_storage_path;
 use crate::common::error_logging::LogError;
 use crate::common::operation_error::{check_process_stopped, OperationError, OperationResult};
 use crate::entry::entry_point::SegmentEntry;
+use crate::id_tracker::immutable_id_tracker::ImmutableIdTracker;
 use crate::index::hnsw_index::max_rayon_threads;
-use crate::index::{PayloadIndex, VectorIndex};
+use crate::index::hnsw_index::reused_graph::ReusedGraph;
+use crate::index::{PayloadIndex, VectorIndex, VectorIndexEnum};
 use crate::segment::Segment;
 use crate::segment_constructor::{build_segment, load_segment};
 use crate::types::{Indexes, PayloadFieldSchema, PayloadKeyType, SegmentConfig};
@@ -21,6 +25,8 @@ pub struct SegmentBuilder {
     pub destination_path: PathBuf,
     pub temp_path: PathBuf,
     pub indexed_fields: HashMap<PayloadKeyType, PayloadFieldSchema>,
+    /// The largest HNSW graph of the source segments for each vector, to be reused by new index
+    reused_graphs: HashMap<String, ReusedGraph>,
 }
 
 impl SegmentBuilder {
@@ -39,6 +45,7 @@ impl SegmentBuilder {
             destination_path,
             temp_path,
             indexed_fields: Default::default(),
+            reused_graphs: Default::default(),
         })
     }
 
@@ -87,25 +94,31 @@ impl SegmentBuilder {
             .collect();
         let mut payload_index = self_segment.payload_index.borrow_mut();
 
-        if vector_storages.len() != other_vector_storages.len() {
-            return Err(OperationError::service_error(
-                format!("Self and other segments have different vector names count. Self count: {}, other count: {}", vector_storages.len(), other_vector_storages.len()),
-            ));
+        // Other segment might miss vectors, which were added to the collection after it was
+        // created, but it can't have vectors unknown to this segment
+        if let Some(vector_name) = other_vector_storages
+            .keys()
+            .find(|vector_name| !vector_storages.contains_key(*vector_name))
+        {
+            return Err(OperationError::service_error(format!(
+                "Cannot update from other segment because of unknown vector name {vector_name}"
+            )));
         }
 
         let mut new_internal_range = None;
         for (vector_name, vector_storage) in &mut vector_storages {
             check_process_stopped(stopped)?;
-            let other_vector_storage = other_vector_storages.get(vector_name).ok_or_else(|| {
-                OperationError::service_error(format!(
-                    "Cannot update from other segment because if missing vector name {vector_name}"
-                ))
-            })?;
-            let internal_range = vector_storage.update_from(
-                other_vector_storage,
-                &mut other_id_tracker.iter_ids(),
-                stopped,
-            )?;
+            let internal_range = match other_vector_storages.get(vector_name) {
+                Some(other_vector_storage) => vector_storage.update_from(
+                    other_vector_storage,
+                    &mut other_id_tracker.iter_ids(),
+                    stopped,
+                )?,
+                // None of the points of other segment have this vector, store placeholders
+                None => {
+                    vector_storage.append_deleted(other_id_tracker.iter_ids().count(), stopped)?
+                }
+            };
             match new_internal_range.clone() {
                 Some(new_internal_range) => {
                     if new_internal_range != internal_range {
@@ -119,9 +132,13 @@ impl SegmentBuilder {
         }
 
         if let Some(new_internal_range) = new_internal_range {
-            let internal_id_iter = new_internal_range.zip(other_id_tracker.iter_ids());
+            let internal_ids: Vec<_> = new_internal_range
+                .zip(other_id_tracker.iter_ids())
+                .collect();
+
+            Self::update_reused_graphs(&mut self.reused_graphs, other, &internal_ids);
 
-            for (new_internal_id, old_internal_id) in internal_id_iter {
+            for (new_internal_id, old_internal_id) in internal_ids {
                 check_process_stopped(stopped)?;
 
                 let external_id =
@@ -140,7 +157,7 @@ impl SegmentBuilder {
                         // New point, just insert
                         id_tracker.set_link(external_id, new_internal_id)?;
                         id_tracker.set_internal_version(new_internal_id, other_version)?;
-                        payload_index.assign(
+                        payload_index.assign_all(
                             new_internal_id,
                             &other_payload_index.payload(old_internal_id)?,
                         )?;
@@ -156,7 +173,7 @@ impl SegmentBuilder {
                             id_tracker.set_link(external_id, new_internal_id)?;
                             id_tracker.set_internal_version(new_internal_id, other_version)?;
                             payload_index.drop(existing_internal_id)?;
-                            payload_index.assign(
+                            payload_index.assign_all(
                                 new_internal_id,
                                 &other_payload_index.payload(old_internal_id)?,
                             )?;
@@ -194,11 +211,28 @@ impl SegmentBuilder {
 
             Self::update_quantization(&mut segment, stopped)?;
 
-            for vector_data in segment.vector_data.values_mut() {
-                vector_data.vector_index.borrow_mut().build_index(stopped)?;
+            for (vector_name, vector_data) in &segment.vector_data {
+                let reused_graph = self.reused_graphs.remove(vector_name);
+                match &mut *vector_data.vector_index.borrow_mut() {
+                    VectorIndexEnum::HnswRam(index) => {
+                        index.build_index_with_reused_graph(reused_graph, stopped)?
+                    }
+                    VectorIndexEnum::HnswMmap(index) => {
+                        index.build_index_with_reused_graph(reused_graph, stopped)?
+                    }
+                    vector_index => vector_index.build_index(stopped)?,
+                }
             }
 
             segment.flush(true)?;
+            // Files of the built segment are not changed anymore, protect them from silent corruption
+            segment.write_checksums()?;
+
+            // Points are never inserted into non-appendable segments,
+            // so id mappings and versions can be served from memory-mapped files
+            if !segment.appendable_flag {
+                ImmutableIdTracker::build(&*segment.id_tracker.borrow(), &segment.current_path)?;
+            }
             drop(segment);
             // Now segment is evicted from RAM
         }
@@ -216,6 +250,46 @@ impl SegmentBuilder {
         Ok(loaded_segment)
     }
 
+    /// Keep the largest HNSW graph of the source segments, so that the new index could reuse it
+    ///
+    /// `internal_ids` are pairs of the new and the old internal ids of the transferred points.
+    fn update_reused_graphs(
+        reused_graphs: &mut HashMap<String, ReusedGraph>,
+        other: &Segment,
+        internal_ids: &[(PointOffsetType, PointOffsetType)],
+    ) {
+        let old_points_count = internal_ids
+            .iter()
+            .map(|&(_, old_internal_id)| old_internal_id as usize + 1)
+            .max()
+            .unwrap_or(0);
+        let mut old_to_new = vec![None; old_points_count];
+        for &(new_internal_id, old_internal_id) in internal_ids {
+            old_to_new[old_internal_id as usize] = Some(new_internal_id);
+        }
+
+        for (vector_name, vector_data) in &other.vector_data {
+            let is_larger = |points_count: usize| {
+                reused_graphs
+                    .get(vector_name)
+                    .map_or(true, |graph| graph.num_points() < points_count)
+            };
+
+            let vector_index = vector_data.vector_index.borrow();
+            if !is_larger(vector_index.indexed_vector_count()) {
+                continue;
+            }
+            let reused_graph = match &*vector_index {
+                VectorIndexEnum::HnswRam(index) => index.reusable_graph(&old_to_new),
+                VectorIndexEnum::HnswMmap(index) => index.reusable_graph(&old_to_new),
+                _ => None,
+            };
+            if let Some(reused_graph) = reused_graph.filter(|graph| is_larger(graph.num_points())) {
+                reused_graphs.insert(vector_name.clone(), reused_graph);
+            }
+        }
+    }
+
     fn update_quantization(segment: &mut Segment, stopped: &AtomicBool) -> OperationResult<()> {
         let config = segment.config().clone();
 
@@ -15,8 +15,9 @@ use crate::common::operation_error::{OperationError, OperationResult};
 use crate::common::rocksdb_wrapper::{open_db, DB_VECTOR_CF};
 use crate::common::version::StorageVersion;
 use crate::data_types::vectors::DEFAULT_VECTOR_NAME;
+use crate::id_tracker::immutable_id_tracker::ImmutableIdTracker;
 use crate::id_tracker::simple_id_tracker::SimpleIdTracker;
-use crate::id_tracker::IdTracker;
+use crate::id_tracker::{IdTracker, IdTrackerSS};
 use crate::index::hnsw_index::graph_links::{GraphLinksMmap, GraphLinksRam};
 use crate::index::hnsw_index::hnsw::HNSWIndex;
 use crate::index::plain_payload_index::PlainIndex;
@@ -24,6 +25,7 @@ use crate::index::sparse_index::sparse_index_config::SparseIndexType;
 use crate::index::sparse_index::sparse_vector_index::SparseVectorIndex;
 use crate::index::struct_payload_index::StructPayloadIndex;
 use crate::index::VectorIndexEnum;
+use crate::payload_storage::append_log_payload_storage::AppendLogPayloadStorage;
 use crate::payload_storage::on_disk_payload_storage::OnDiskPayloadStorage;
 use crate::payload_storage::simple_payload_storage::SimplePayloadStorage;
 use crate::segment::{Segment, SegmentVersion, VectorData, SEGMENT_STATE_FILE};
@@ -31,14 +33,22 @@ use crate::types::{
     Distance, Indexes, PayloadStorageType, SegmentConfig, SegmentState, SegmentType, SeqNumberType,
     VectorStorageType,
 };
-use crate::vector_storage::appendable_mmap_vector_storage::open_appendable_memmap_vector_storage;
+use crate::vector_storage::appendable_mmap_vector_storage::{
+    open_appendable_memmap_byte_vector_storage, open_appendable_memmap_half_vector_storage,
+    open_appendable_memmap_vector_storage,
+};
 use crate::vector_storage::memmap_vector_storage::open_memmap_vector_storage;
 use crate::vector_storage::quantized::quantized_vectors::QuantizedVectors;
-use crate::vector_storage::simple_dense_vector_storage::open_simple_vector_storage;
+use crate::vector_storage::simple_dense_vector_storage::{
+    open_simple_dense_byte_vector_storage, open_simple_dense_half_vector_storage,
+    open_simple_vector_storage,
+};
+use crate::vector_storage::simple_multi_dense_vector_storage::open_simple_multi_dense_vector_storage;
 use crate::vector_storage::simple_sparse_vector_storage::open_simple_sparse_vector_storage;
 use crate::vector_storage::VectorStorage;
 
 pub const PAYLOAD_INDEX_PATH: &str = "payload_index";
+pub const PAYLOAD_STORAGE_PATH: &str = "payload_storage";
 pub const VECTOR_STORAGE_PATH: &str = "vector_storage";
 pub const VECTOR_INDEX_PATH: &str = "vector_index";
 
@@ -87,9 +97,17 @@ fn create_segment(
     let payload_storage = match config.payload_storage_type {
         PayloadStorageType::InMemory => sp(SimplePayloadStorage::open(database.clone())?.into()),
         PayloadStorageType::OnDisk => sp(OnDiskPayloadStorage::open(database.clone())?.into()),
+        PayloadStorageType::AppendLog => {
+            sp(AppendLogPayloadStorage::open(&segment_path.join(PAYLOAD_STORAGE_PATH))?.into())
+        }
     };
 
-    let id_tracker = sp(SimpleIdTracker::open(database.clone())?);
+    // Immutable id tracker is only created for non-appendable segments during optimization
+    let id_tracker: Arc<AtomicRefCell<IdTrackerSS>> = if ImmutableIdTracker::exists(segment_path) {
+        sp(ImmutableIdTracker::open(segment_path)?)
+    } else {
+        sp(SimpleIdTracker::open(database.clone())?)
+    };
 
     let appendable_flag = config
         .vector_data
@@ -117,9 +135,60 @@ fn create_segment(
         let vector_index_path = get_vector_index_path(segment_path, vector_name);
 
         // Select suitable vector storage type based on configuration
-        let vector_storage = match vector_config.storage_type {
+        let vector_storage = match (vector_config.storage_type, vector_config.multivector_config) {
+            // Multi-vectors are in memory, regardless of the storage type
+            (_, Some(multivector_config)) => {
+                let db_column_name = get_vector_name_with_prefix(DB_VECTOR_CF, vector_name);
+                open_simple_multi_dense_vector_storage(
+                    database.clone(),
+                    &db_column_name,
+                    vector_config.size,
+                    vector_config.distance,
+                    multivector_config,
+                )?
+            }
+            // Vectors of the uint8 datatype
+            (VectorStorageType::Memory, None) if vector_config.is_byte_datatype() => {
+                let db_column_name = get_vector_name_with_prefix(DB_VECTOR_CF, vector_name);
+                open_simple_dense_byte_vector_storage(
+                    database.clone(),
+                    &db_column_name,
+                    vector_config.size,
+                    vector_config.distance,
+                )?
+            }
+            // On disk vectors of the uint8 datatype are always in appendable chunked mmap
+            (VectorStorageType::Mmap | VectorStorageType::ChunkedMmap, None)
+                if vector_config.is_byte_datatype() =>
+            {
+                open_appendable_memmap_byte_vector_storage(
+                    &vector_storage_path,
+                    vector_config.size,
+                    vector_config.distance,
+                )?
+            }
+            // Vectors of the float16 datatype
+            (VectorStorageType::Memory, None) if vector_config.is_half_datatype() => {
+                let db_column_name = get_vector_name_with_prefix(DB_VECTOR_CF, vector_name);
+                open_simple_dense_half_vector_storage(
+                    database.clone(),
+                    &db_column_name,
+                    vector_config.size,
+                    vector_config.distance,
+                )?
+            }
+            // On disk vectors of the float16 datatype are always in appendable chunked mmap
+            (VectorStorageType::Mmap | VectorStorageType::ChunkedMmap, None)
+                if vector_config.is_half_datatype() =>
+            {
+                open_appendable_memmap_half_vector_storage(
+                    &vector_storage_path,
+                    vector_config.size,
+                    vector_config.distance,
+                )?
+            }
             // In memory
-            VectorStorageType::Memory => {
+            (VectorStorageType::Memory, None) => {
                 let db_column_name = get_vector_name_with_prefix(DB_VECTOR_CF, vector_name);
                 open_simple_vector_storage(
                     database.clone(),
@@ -129,13 +198,13 @@ fn create_segment(
                 )?
             }
             // Mmap on disk, not appendable
-            VectorStorageType::Mmap => open_memmap_vector_storage(
+            (VectorStorageType::Mmap, None) => open_memmap_vector_storage(
                 &vector_storage_path,
                 vector_config.size,
                 vector_config.distance,
             )?,
             // Chunked mmap on disk, appendable
-            VectorStorageType::ChunkedMmap => open_appendable_memmap_vector_storage(
+            (VectorStorageType::ChunkedMmap, None) => open_appendable_memmap_vector_storage(
                 &vector_storage_path,
                 vector_config.size,
                 vector_config.distance,
@@ -1,6 +1,8 @@
 use common::types::ScoreType;
 
-use crate::data_types::vectors::{DenseVector, VectorElementType};
+use crate::data_types::vectors::{
+    DenseVector, VectorElementType, VectorElementTypeByte, VectorElementTypeHalf,
+};
 use crate::types::Distance;
 
 /// Defines how to compare vectors
@@ -10,6 +12,14 @@ pub trait Metric {
     /// Greater the value - closer the vectors
     fn similarity(v1: &[VectorElementType], v2: &[VectorElementType]) -> ScoreType;
 
+    /// Similarity of the vectors of the `uint8` datatype, scored the same way as [`Self::similarity`]
+    ///
+    /// Byte vectors are not preprocessed, so cosine similarity is normalized on scoring.
+    fn similarity_bytes(v1: &[VectorElementTypeByte], v2: &[VectorElementTypeByte]) -> ScoreType;
+
+    /// Similarity of the vectors of the `float16` datatype, scored the same way as [`Self::similarity`]
+    fn similarity_half(v1: &[VectorElementTypeHalf], v2: &[VectorElementTypeHalf]) -> ScoreType;
+
     /// Necessary vector transformations performed before adding it to the collection (like normalization)
     /// If no transformation is needed - returns the same vector
     fn preprocess(vector: DenseVector) -> DenseVector;
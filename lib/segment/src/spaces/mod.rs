@@ -8,5 +8,8 @@ pub mod simple_sse;
 #[cfg(target_arch = "x86_64")]
 pub mod simple_avx;
 
+#[cfg(all(target_arch = "x86_64", feature = "avx512"))]
+pub mod simple_avx512;
+
 #[cfg(target_arch = "aarch64")]
 pub mod simple_neon;
@@ -3,13 +3,20 @@ use common::types::ScoreType;
 use super::metric::Metric;
 #[cfg(target_arch = "x86_64")]
 use super::simple_avx::*;
+#[cfg(all(target_arch = "x86_64", feature = "avx512"))]
+use super::simple_avx512::*;
 #[cfg(all(target_arch = "aarch64", target_feature = "neon"))]
 use super::simple_neon::*;
 #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
 use super::simple_sse::*;
-use crate::data_types::vectors::{DenseVector, VectorElementType};
+use crate::data_types::vectors::{
+    DenseVector, VectorElementType, VectorElementTypeByte, VectorElementTypeHalf,
+};
 use crate::types::Distance;
 
+#[cfg(all(target_arch = "x86_64", feature = "avx512"))]
+const MIN_DIM_SIZE_AVX512: usize = 64;
+
 #[cfg(target_arch = "x86_64")]
 const MIN_DIM_SIZE_AVX: usize = 32;
 
@@ -38,6 +45,13 @@ impl Metric for EuclidMetric {
     }
 
     fn similarity(v1: &[VectorElementType], v2: &[VectorElementType]) -> ScoreType {
+        #[cfg(all(target_arch = "x86_64", feature = "avx512"))]
+        {
+            if is_x86_feature_detected!("avx512f") && v1.len() >= MIN_DIM_SIZE_AVX512 {
+                return unsafe { euclid_similarity_avx512(v1, v2) };
+            }
+        }
+
         #[cfg(target_arch = "x86_64")]
         {
             if is_x86_feature_detected!("avx")
@@ -65,6 +79,32 @@ impl Metric for EuclidMetric {
         euclid_similarity(v1, v2)
     }
 
+    fn similarity_bytes(v1: &[VectorElementTypeByte], v2: &[VectorElementTypeByte]) -> ScoreType {
+        euclid_similarity_bytes(v1, v2)
+    }
+
+    fn similarity_half(v1: &[VectorElementTypeHalf], v2: &[VectorElementTypeHalf]) -> ScoreType {
+        #[cfg(all(target_arch = "x86_64", feature = "avx512"))]
+        {
+            if is_x86_feature_detected!("avx512f") && v1.len() >= MIN_DIM_SIZE_AVX512 {
+                return unsafe { euclid_similarity_half_avx512(v1, v2) };
+            }
+        }
+
+        #[cfg(target_arch = "x86_64")]
+        {
+            if is_x86_feature_detected!("avx")
+                && is_x86_feature_detected!("fma")
+                && is_x86_feature_detected!("f16c")
+                && v1.len() >= MIN_DIM_SIZE_SIMD
+            {
+                return unsafe { euclid_similarity_half_avx(v1, v2) };
+            }
+        }
+
+        euclid_similarity_half(v1, v2)
+    }
+
     fn preprocess(vector: DenseVector) -> DenseVector {
         vector
     }
@@ -80,6 +120,13 @@ impl Metric for ManhattanMetric {
     }
 
     fn similarity(v1: &[VectorElementType], v2: &[VectorElementType]) -> ScoreType {
+        #[cfg(all(target_arch = "x86_64", feature = "avx512"))]
+        {
+            if is_x86_feature_detected!("avx512f") && v1.len() >= MIN_DIM_SIZE_AVX512 {
+                return unsafe { manhattan_similarity_avx512(v1, v2) };
+            }
+        }
+
         #[cfg(target_arch = "x86_64")]
         {
             if is_x86_feature_detected!("avx")
@@ -107,6 +154,32 @@ impl Metric for ManhattanMetric {
         manhattan_similarity(v1, v2)
     }
 
+    fn similarity_bytes(v1: &[VectorElementTypeByte], v2: &[VectorElementTypeByte]) -> ScoreType {
+        manhattan_similarity_bytes(v1, v2)
+    }
+
+    fn similarity_half(v1: &[VectorElementTypeHalf], v2: &[VectorElementTypeHalf]) -> ScoreType {
+        #[cfg(all(target_arch = "x86_64", feature = "avx512"))]
+        {
+            if is_x86_feature_detected!("avx512f") && v1.len() >= MIN_DIM_SIZE_AVX512 {
+                return unsafe { manhattan_similarity_half_avx512(v1, v2) };
+            }
+        }
+
+        #[cfg(target_arch = "x86_64")]
+        {
+            if is_x86_feature_detected!("avx")
+                && is_x86_feature_detected!("fma")
+                && is_x86_feature_detected!("f16c")
+                && v1.len() >= MIN_DIM_SIZE_SIMD
+            {
+                return unsafe { manhattan_similarity_half_avx(v1, v2) };
+            }
+        }
+
+        manhattan_similarity_half(v1, v2)
+    }
+
     fn preprocess(vector: DenseVector) -> DenseVector {
         vector
     }
@@ -122,6 +195,13 @@ impl Metric for DotProductMetric {
     }
 
     fn similarity(v1: &[VectorElementType], v2: &[VectorElementType]) -> ScoreType {
+        #[cfg(all(target_arch = "x86_64", feature = "avx512"))]
+        {
+            if is_x86_feature_detected!("avx512f") && v1.len() >= MIN_DIM_SIZE_AVX512 {
+                return unsafe { dot_similarity_avx512(v1, v2) };
+            }
+        }
+
         #[cfg(target_arch = "x86_64")]
         {
             if is_x86_feature_detected!("avx")
@@ -149,6 +229,63 @@ impl Metric for DotProductMetric {
         dot_similarity(v1, v2)
     }
 
+    fn similarity_bytes(v1: &[VectorElementTypeByte], v2: &[VectorElementTypeByte]) -> ScoreType {
+        #[cfg(all(target_arch = "x86_64", feature = "avx512"))]
+        {
+            if is_x86_feature_detected!("avx512bw")
+                && is_x86_feature_detected!("avx512vnni")
+                && v1.len() >= MIN_DIM_SIZE_AVX512
+            {
+                return unsafe { dot_similarity_bytes_avx512vnni(v1, v2) };
+            }
+        }
+
+        #[cfg(target_arch = "x86_64")]
+        {
+            if is_x86_feature_detected!("avx2") && v1.len() >= MIN_DIM_SIZE_AVX {
+                return unsafe { dot_similarity_bytes_avx(v1, v2) };
+            }
+        }
+
+        #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+        {
+            if is_x86_feature_detected!("sse2") && v1.len() >= MIN_DIM_SIZE_SIMD {
+                return unsafe { dot_similarity_bytes_sse(v1, v2) };
+            }
+        }
+
+        #[cfg(all(target_arch = "aarch64", target_feature = "neon"))]
+        {
+            if std::arch::is_aarch64_feature_detected!("neon") && v1.len() >= MIN_DIM_SIZE_SIMD {
+                return unsafe { dot_similarity_bytes_neon(v1, v2) };
+            }
+        }
+
+        dot_similarity_bytes(v1, v2)
+    }
+
+    fn similarity_half(v1: &[VectorElementTypeHalf], v2: &[VectorElementTypeHalf]) -> ScoreType {
+        #[cfg(all(target_arch = "x86_64", feature = "avx512"))]
+        {
+            if is_x86_feature_detected!("avx512f") && v1.len() >= MIN_DIM_SIZE_AVX512 {
+                return unsafe { dot_similarity_half_avx512(v1, v2) };
+            }
+        }
+
+        #[cfg(target_arch = "x86_64")]
+        {
+            if is_x86_feature_detected!("avx")
+                && is_x86_feature_detected!("fma")
+                && is_x86_feature_detected!("f16c")
+                && v1.len() >= MIN_DIM_SIZE_SIMD
+            {
+                return unsafe { dot_similarity_half_avx(v1, v2) };
+            }
+        }
+
+        dot_similarity_half(v1, v2)
+    }
+
     fn preprocess(vector: DenseVector) -> DenseVector {
         vector
     }
@@ -164,6 +301,13 @@ impl Metric for CosineMetric {
     }
 
     fn similarity(v1: &[VectorElementType], v2: &[VectorElementType]) -> ScoreType {
+        #[cfg(all(target_arch = "x86_64", feature = "avx512"))]
+        {
+            if is_x86_feature_detected!("avx512f") && v1.len() >= MIN_DIM_SIZE_AVX512 {
+                return unsafe { dot_similarity_avx512(v1, v2) };
+            }
+        }
+
         #[cfg(target_arch = "x86_64")]
         {
             if is_x86_feature_detected!("avx")
@@ -191,7 +335,72 @@ impl Metric for CosineMetric {
         dot_similarity(v1, v2)
     }
 
+    fn similarity_bytes(v1: &[VectorElementTypeByte], v2: &[VectorElementTypeByte]) -> ScoreType {
+        #[cfg(all(target_arch = "x86_64", feature = "avx512"))]
+        {
+            if is_x86_feature_detected!("avx512bw")
+                && is_x86_feature_detected!("avx512vnni")
+                && v1.len() >= MIN_DIM_SIZE_AVX512
+            {
+                return unsafe { cosine_similarity_bytes_avx512vnni(v1, v2) };
+            }
+        }
+
+        #[cfg(target_arch = "x86_64")]
+        {
+            if is_x86_feature_detected!("avx2") && v1.len() >= MIN_DIM_SIZE_AVX {
+                return unsafe { cosine_similarity_bytes_avx(v1, v2) };
+            }
+        }
+
+        #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+        {
+            if is_x86_feature_detected!("sse2") && v1.len() >= MIN_DIM_SIZE_SIMD {
+                return unsafe { cosine_similarity_bytes_sse(v1, v2) };
+            }
+        }
+
+        #[cfg(all(target_arch = "aarch64", target_feature = "neon"))]
+        {
+            if std::arch::is_aarch64_feature_detected!("neon") && v1.len() >= MIN_DIM_SIZE_SIMD {
+                return unsafe { cosine_similarity_bytes_neon(v1, v2) };
+            }
+        }
+
+        cosine_similarity_bytes(v1, v2)
+    }
+
+    /// Vectors of the `float16` datatype are normalized before conversion, same as float vectors
+    fn similarity_half(v1: &[VectorElementTypeHalf], v2: &[VectorElementTypeHalf]) -> ScoreType {
+        #[cfg(all(target_arch = "x86_64", feature = "avx512"))]
+        {
+            if is_x86_feature_detected!("avx512f") && v1.len() >= MIN_DIM_SIZE_AVX512 {
+                return unsafe { dot_similarity_half_avx512(v1, v2) };
+            }
+        }
+
+        #[cfg(target_arch = "x86_64")]
+        {
+            if is_x86_feature_detected!("avx")
+                && is_x86_feature_detected!("fma")
+                && is_x86_feature_detected!("f16c")
+                && v1.len() >= MIN_DIM_SIZE_SIMD
+            {
+                return unsafe { dot_similarity_half_avx(v1, v2) };
+            }
+        }
+
+        dot_similarity_half(v1, v2)
+    }
+
     fn preprocess(vector: DenseVector) -> DenseVector {
+        #[cfg(all(target_arch = "x86_64", feature = "avx512"))]
+        {
+            if is_x86_feature_detected!("avx512f") && vector.len() >= MIN_DIM_SIZE_AVX512 {
+                return unsafe { cosine_preprocess_avx512(vector) };
+            }
+        }
+
         #[cfg(target_arch = "x86_64")]
         {
             if is_x86_feature_detected!("avx")
@@ -252,13 +461,145 @@ pub fn dot_similarity(v1: &[VectorElementType], v2: &[VectorElementType]) -> Sco
     v1.iter().zip(v2).map(|(a, b)| a * b).sum()
 }
 
+pub fn euclid_similarity_bytes(
+    v1: &[VectorElementTypeByte],
+    v2: &[VectorElementTypeByte],
+) -> ScoreType {
+    -(v1.iter()
+        .zip(v2)
+        .map(|(&a, &b)| u64::from(a.abs_diff(b)).pow(2))
+        .sum::<u64>() as ScoreType)
+}
+
+pub fn manhattan_similarity_bytes(
+    v1: &[VectorElementTypeByte],
+    v2: &[VectorElementTypeByte],
+) -> ScoreType {
+    -(v1.iter()
+        .zip(v2)
+        .map(|(&a, &b)| u64::from(a.abs_diff(b)))
+        .sum::<u64>() as ScoreType)
+}
+
+pub fn dot_similarity_bytes(
+    v1: &[VectorElementTypeByte],
+    v2: &[VectorElementTypeByte],
+) -> ScoreType {
+    v1.iter()
+        .zip(v2)
+        .map(|(&a, &b)| u64::from(a) * u64::from(b))
+        .sum::<u64>() as ScoreType
+}
+
+pub fn cosine_similarity_bytes(
+    v1: &[VectorElementTypeByte],
+    v2: &[VectorElementTypeByte],
+) -> ScoreType {
+    let (mut dot, mut norm1, mut norm2) = (0u64, 0u64, 0u64);
+    for (&a, &b) in v1.iter().zip(v2) {
+        let (a, b) = (u64::from(a), u64::from(b));
+        dot += a * b;
+        norm1 += a * a;
+        norm2 += b * b;
+    }
+    cosine_bytes_score(dot, norm1, norm2)
+}
+
+pub fn euclid_similarity_half(
+    v1: &[VectorElementTypeHalf],
+    v2: &[VectorElementTypeHalf],
+) -> ScoreType {
+    -v1.iter()
+        .zip(v2)
+        .map(|(a, b)| (a.to_f32() - b.to_f32()).powi(2))
+        .sum::<ScoreType>()
+}
+
+pub fn manhattan_similarity_half(
+    v1: &[VectorElementTypeHalf],
+    v2: &[VectorElementTypeHalf],
+) -> ScoreType {
+    -v1.iter()
+        .zip(v2)
+        .map(|(a, b)| (a.to_f32() - b.to_f32()).abs())
+        .sum::<ScoreType>()
+}
+
+pub fn dot_similarity_half(
+    v1: &[VectorElementTypeHalf],
+    v2: &[VectorElementTypeHalf],
+) -> ScoreType {
+    v1.iter()
+        .zip(v2)
+        .map(|(a, b)| a.to_f32() * b.to_f32())
+        .sum()
+}
+
+/// Cosine similarity from the dot product and the squared norms of the byte vectors
+///
+/// Similarity with a zero vector is 0, same as for the normalized float vectors.
+pub(crate) fn cosine_bytes_score(dot: u64, norm1: u64, norm2: u64) -> ScoreType {
+    if norm1 == 0 || norm2 == 0 {
+        return 0.0;
+    }
+    (dot as f64 / (norm1 as f64 * norm2 as f64).sqrt()) as ScoreType
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::data_types::vectors::dense_vector_to_half;
 
     #[test]
     fn test_cosine_preprocessing() {
         let res = CosineMetric::preprocess(vec![0.0, 0.0, 0.0, 0.0]);
         assert_eq!(res, vec![0.0, 0.0, 0.0, 0.0]);
     }
+
+    #[test]
+    fn test_similarity_bytes() {
+        let v1: Vec<u8> = (0..40).map(|i| i * 3).collect();
+        let v2: Vec<u8> = (0..40).map(|i| 255 - i * 2).collect();
+        let f1: Vec<f32> = v1.iter().map(|&x| f32::from(x)).collect();
+        let f2: Vec<f32> = v2.iter().map(|&x| f32::from(x)).collect();
+
+        assert_eq!(
+            DotProductMetric::similarity_bytes(&v1, &v2),
+            dot_similarity(&f1, &f2)
+        );
+        assert_eq!(
+            EuclidMetric::similarity_bytes(&v1, &v2),
+            euclid_similarity(&f1, &f2)
+        );
+        assert_eq!(
+            ManhattanMetric::similarity_bytes(&v1, &v2),
+            manhattan_similarity(&f1, &f2)
+        );
+
+        let cosine = dot_similarity(&cosine_preprocess(f1), &cosine_preprocess(f2));
+        assert!((CosineMetric::similarity_bytes(&v1, &v2) - cosine).abs() < 1e-6);
+        assert_eq!(CosineMetric::similarity_bytes(&v1, &[0; 40]), 0.0);
+    }
+
+    #[test]
+    fn test_similarity_half() {
+        let f1: Vec<f32> = (0..40).map(|i| i as f32 / 4.).collect();
+        let f2: Vec<f32> = (0..40).map(|i| 10. - i as f32 / 2.).collect();
+        // Values are exactly representable as float16
+        let v1 = dense_vector_to_half(&f1);
+        let v2 = dense_vector_to_half(&f2);
+
+        assert_eq!(
+            DotProductMetric::similarity_half(&v1, &v2),
+            dot_similarity(&f1, &f2)
+        );
+        assert_eq!(
+            EuclidMetric::similarity_half(&v1, &v2),
+            euclid_similarity(&f1, &f2)
+        );
+        assert_eq!(
+            ManhattanMetric::similarity_half(&v1, &v2),
+            manhattan_similarity(&f1, &f2)
+        );
+    }
 }
@@ -2,7 +2,10 @@ use std::arch::x86_64::*;
 
 use common::types::ScoreType;
 
-use crate::data_types::vectors::{DenseVector, VectorElementType};
+use crate::data_types::vectors::{
+    DenseVector, VectorElementType, VectorElementTypeByte, VectorElementTypeHalf,
+};
+use crate::spaces::simple::cosine_bytes_score;
 
 #[target_feature(enable = "avx")]
 #[target_feature(enable = "fma")]
@@ -199,6 +202,195 @@ pub(crate) unsafe fn dot_similarity_avx(
     result
 }
 
+#[target_feature(enable = "avx2")]
+unsafe fn hsum256_epi32_avx2(x: __m256i) -> u64 {
+    let mut lanes = [0u32; 8];
+    _mm256_storeu_si256(lanes.as_mut_ptr() as *mut __m256i, x);
+    lanes.iter().map(|&lane| u64::from(lane)).sum()
+}
+
+/// Products of 16 bytes, accumulated into the 32-bit lanes
+///
+/// Bytes are widened to 16 bits, so the signed multiplication of `_mm256_madd_epi16` is exact.
+#[target_feature(enable = "avx2")]
+unsafe fn madd_bytes_avx2(sum: __m256i, ptr1: *const u8, ptr2: *const u8) -> __m256i {
+    let a = _mm256_cvtepu8_epi16(_mm_loadu_si128(ptr1 as *const __m128i));
+    let b = _mm256_cvtepu8_epi16(_mm_loadu_si128(ptr2 as *const __m128i));
+    _mm256_add_epi32(sum, _mm256_madd_epi16(a, b))
+}
+
+#[target_feature(enable = "avx2")]
+pub(crate) unsafe fn dot_similarity_bytes_avx(
+    v1: &[VectorElementTypeByte],
+    v2: &[VectorElementTypeByte],
+) -> ScoreType {
+    let n = v1.len();
+    let m = n - (n % 32);
+    let mut ptr1: *const u8 = v1.as_ptr();
+    let mut ptr2: *const u8 = v2.as_ptr();
+    let mut sum256_1: __m256i = _mm256_setzero_si256();
+    let mut sum256_2: __m256i = _mm256_setzero_si256();
+    let mut i: usize = 0;
+    while i < m {
+        sum256_1 = madd_bytes_avx2(sum256_1, ptr1, ptr2);
+        sum256_2 = madd_bytes_avx2(sum256_2, ptr1.add(16), ptr2.add(16));
+        ptr1 = ptr1.add(32);
+        ptr2 = ptr2.add(32);
+        i += 32;
+    }
+
+    let mut result = hsum256_epi32_avx2(sum256_1) + hsum256_epi32_avx2(sum256_2);
+    for i in 0..n - m {
+        result += u64::from(*ptr1.add(i)) * u64::from(*ptr2.add(i));
+    }
+    result as ScoreType
+}
+
+#[target_feature(enable = "avx2")]
+pub(crate) unsafe fn cosine_similarity_bytes_avx(
+    v1: &[VectorElementTypeByte],
+    v2: &[VectorElementTypeByte],
+) -> ScoreType {
+    let n = v1.len();
+    let m = n - (n % 16);
+    let mut ptr1: *const u8 = v1.as_ptr();
+    let mut ptr2: *const u8 = v2.as_ptr();
+    let mut dot256: __m256i = _mm256_setzero_si256();
+    let mut norm256_1: __m256i = _mm256_setzero_si256();
+    let mut norm256_2: __m256i = _mm256_setzero_si256();
+    let mut i: usize = 0;
+    while i < m {
+        dot256 = madd_bytes_avx2(dot256, ptr1, ptr2);
+        norm256_1 = madd_bytes_avx2(norm256_1, ptr1, ptr1);
+        norm256_2 = madd_bytes_avx2(norm256_2, ptr2, ptr2);
+        ptr1 = ptr1.add(16);
+        ptr2 = ptr2.add(16);
+        i += 16;
+    }
+
+    let mut dot = hsum256_epi32_avx2(dot256);
+    let mut norm1 = hsum256_epi32_avx2(norm256_1);
+    let mut norm2 = hsum256_epi32_avx2(norm256_2);
+    for i in 0..n - m {
+        let (a, b) = (u64::from(*ptr1.add(i)), u64::from(*ptr2.add(i)));
+        dot += a * b;
+        norm1 += a * a;
+        norm2 += b * b;
+    }
+    cosine_bytes_score(dot, norm1, norm2)
+}
+
+/// Load 8 half-precision floats, converted to single-precision
+#[target_feature(enable = "avx")]
+#[target_feature(enable = "f16c")]
+unsafe fn load_half_avx(ptr: *const VectorElementTypeHalf) -> __m256 {
+    // `f16` is a transparent wrapper of the `u16` bits
+    _mm256_cvtph_ps(_mm_loadu_si128(ptr as *const __m128i))
+}
+
+#[target_feature(enable = "avx")]
+#[target_feature(enable = "fma")]
+#[target_feature(enable = "f16c")]
+pub(crate) unsafe fn dot_similarity_half_avx(
+    v1: &[VectorElementTypeHalf],
+    v2: &[VectorElementTypeHalf],
+) -> ScoreType {
+    let n = v1.len();
+    let m = n - (n % 16);
+    let mut ptr1: *const VectorElementTypeHalf = v1.as_ptr();
+    let mut ptr2: *const VectorElementTypeHalf = v2.as_ptr();
+    let mut sum256_1: __m256 = _mm256_setzero_ps();
+    let mut sum256_2: __m256 = _mm256_setzero_ps();
+    let mut i: usize = 0;
+    while i < m {
+        sum256_1 = _mm256_fmadd_ps(load_half_avx(ptr1), load_half_avx(ptr2), sum256_1);
+        sum256_2 = _mm256_fmadd_ps(
+            load_half_avx(ptr1.add(8)),
+            load_half_avx(ptr2.add(8)),
+            sum256_2,
+        );
+        ptr1 = ptr1.add(16);
+        ptr2 = ptr2.add(16);
+        i += 16;
+    }
+
+    let mut result = hsum256_ps_avx(sum256_1) + hsum256_ps_avx(sum256_2);
+    for i in 0..n - m {
+        result += (*ptr1.add(i)).to_f32() * (*ptr2.add(i)).to_f32();
+    }
+    result
+}
+
+#[target_feature(enable = "avx")]
+#[target_feature(enable = "fma")]
+#[target_feature(enable = "f16c")]
+pub(crate) unsafe fn euclid_similarity_half_avx(
+    v1: &[VectorElementTypeHalf],
+    v2: &[VectorElementTypeHalf],
+) -> ScoreType {
+    let n = v1.len();
+    let m = n - (n % 16);
+    let mut ptr1: *const VectorElementTypeHalf = v1.as_ptr();
+    let mut ptr2: *const VectorElementTypeHalf = v2.as_ptr();
+    let mut sum256_1: __m256 = _mm256_setzero_ps();
+    let mut sum256_2: __m256 = _mm256_setzero_ps();
+    let mut i: usize = 0;
+    while i < m {
+        let sub256_1: __m256 = _mm256_sub_ps(load_half_avx(ptr1), load_half_avx(ptr2));
+        sum256_1 = _mm256_fmadd_ps(sub256_1, sub256_1, sum256_1);
+
+        let sub256_2: __m256 =
+            _mm256_sub_ps(load_half_avx(ptr1.add(8)), load_half_avx(ptr2.add(8)));
+        sum256_2 = _mm256_fmadd_ps(sub256_2, sub256_2, sum256_2);
+
+        ptr1 = ptr1.add(16);
+        ptr2 = ptr2.add(16);
+        i += 16;
+    }
+
+    let mut result = hsum256_ps_avx(sum256_1) + hsum256_ps_avx(sum256_2);
+    for i in 0..n - m {
+        result += ((*ptr1.add(i)).to_f32() - (*ptr2.add(i)).to_f32()).powi(2);
+    }
+    -result
+}
+
+#[target_feature(enable = "avx")]
+#[target_feature(enable = "fma")]
+#[target_feature(enable = "f16c")]
+pub(crate) unsafe fn manhattan_similarity_half_avx(
+    v1: &[VectorElementTypeHalf],
+    v2: &[VectorElementTypeHalf],
+) -> ScoreType {
+    let mask: __m256 = _mm256_set1_ps(-0.0f32); // 1 << 31 used to clear sign bit to mimic abs
+
+    let n = v1.len();
+    let m = n - (n % 16);
+    let mut ptr1: *const VectorElementTypeHalf = v1.as_ptr();
+    let mut ptr2: *const VectorElementTypeHalf = v2.as_ptr();
+    let mut sum256_1: __m256 = _mm256_setzero_ps();
+    let mut sum256_2: __m256 = _mm256_setzero_ps();
+    let mut i: usize = 0;
+    while i < m {
+        let sub256_1: __m256 = _mm256_sub_ps(load_half_avx(ptr1), load_half_avx(ptr2));
+        sum256_1 = _mm256_add_ps(_mm256_andnot_ps(mask, sub256_1), sum256_1);
+
+        let sub256_2: __m256 =
+            _mm256_sub_ps(load_half_avx(ptr1.add(8)), load_half_avx(ptr2.add(8)));
+        sum256_2 = _mm256_add_ps(_mm256_andnot_ps(mask, sub256_2), sum256_2);
+
+        ptr1 = ptr1.add(16);
+        ptr2 = ptr2.add(16);
+        i += 16;
+    }
+
+    let mut result = hsum256_ps_avx(sum256_1) + hsum256_ps_avx(sum256_2);
+    for i in 0..n - m {
+        result += ((*ptr1.add(i)).to_f32() - (*ptr2.add(i)).to_f32()).abs();
+    }
+    -result
+}
+
 #[cfg(test)]
 mod tests {
     #[test]
@@ -241,4 +433,55 @@ mod tests {
             println!("avx test skipped");
         }
     }
+
+    #[test]
+    fn test_spaces_bytes_avx() {
+        use super::*;
+        use crate::spaces::simple::*;
+
+        if is_x86_feature_detected!("avx2") {
+            let v1: Vec<u8> = (0..85).map(|i| (i * 5 % 256) as u8).collect();
+            let v2: Vec<u8> = (0..85).map(|i| (255 - i * 3) as u8).collect();
+
+            let dot_simd = unsafe { dot_similarity_bytes_avx(&v1, &v2) };
+            let dot = dot_similarity_bytes(&v1, &v2);
+            assert_eq!(dot_simd, dot);
+
+            let cosine_simd = unsafe { cosine_similarity_bytes_avx(&v1, &v2) };
+            let cosine = cosine_similarity_bytes(&v1, &v2);
+            assert_eq!(cosine_simd, cosine);
+        } else {
+            println!("avx2 test skipped");
+        }
+    }
+
+    #[test]
+    fn test_spaces_half_avx() {
+        use super::*;
+        use crate::data_types::vectors::dense_vector_to_half;
+        use crate::spaces::simple::*;
+
+        if is_x86_feature_detected!("avx")
+            && is_x86_feature_detected!("fma")
+            && is_x86_feature_detected!("f16c")
+        {
+            let v1 = dense_vector_to_half(&(0..85).map(|i| i as f32 / 10.).collect::<Vec<_>>());
+            let v2 = dense_vector_to_half(&(0..85).map(|i| 5. - i as f32 / 7.).collect::<Vec<_>>());
+
+            // Summation order differs, so results are not bit-exact
+            let dot_simd = unsafe { dot_similarity_half_avx(&v1, &v2) };
+            let dot = dot_similarity_half(&v1, &v2);
+            assert!((dot_simd - dot).abs() < 1e-3);
+
+            let euclid_simd = unsafe { euclid_similarity_half_avx(&v1, &v2) };
+            let euclid = euclid_similarity_half(&v1, &v2);
+            assert!((euclid_simd - euclid).abs() < 1e-2);
+
+            let manhattan_simd = unsafe { manhattan_similarity_half_avx(&v1, &v2) };
+            let manhattan = manhattan_similarity_half(&v1, &v2);
+            assert!((manhattan_simd - manhattan).abs() < 1e-3);
+        } else {
+            println!("f16c test skipped");
+        }
+    }
 }
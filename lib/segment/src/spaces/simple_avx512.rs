@@ -0,0 +1,466 @@
+use std::arch::x86_64::*;
+
+use common::types::ScoreType;
+
+use crate::data_types::vectors::{
+    DenseVector, VectorElementType, VectorElementTypeByte, VectorElementTypeHalf,
+};
+use crate::spaces::simple::cosine_bytes_score;
+
+#[target_feature(enable = "avx512f")]
+pub(crate) unsafe fn euclid_similarity_avx512(
+    v1: &[VectorElementType],
+    v2: &[VectorElementType],
+) -> ScoreType {
+    let n = v1.len();
+    let m = n - (n % 64);
+    let mut ptr1: *const f32 = v1.as_ptr();
+    let mut ptr2: *const f32 = v2.as_ptr();
+    let mut sum512_1: __m512 = _mm512_setzero_ps();
+    let mut sum512_2: __m512 = _mm512_setzero_ps();
+    let mut sum512_3: __m512 = _mm512_setzero_ps();
+    let mut sum512_4: __m512 = _mm512_setzero_ps();
+    let mut i: usize = 0;
+    while i < m {
+        let sub512_1: __m512 = _mm512_sub_ps(_mm512_loadu_ps(ptr1), _mm512_loadu_ps(ptr2));
+        sum512_1 = _mm512_fmadd_ps(sub512_1, sub512_1, sum512_1);
+
+        let sub512_2: __m512 =
+            _mm512_sub_ps(_mm512_loadu_ps(ptr1.add(16)), _mm512_loadu_ps(ptr2.add(16)));
+        sum512_2 = _mm512_fmadd_ps(sub512_2, sub512_2, sum512_2);
+
+        let sub512_3: __m512 =
+            _mm512_sub_ps(_mm512_loadu_ps(ptr1.add(32)), _mm512_loadu_ps(ptr2.add(32)));
+        sum512_3 = _mm512_fmadd_ps(sub512_3, sub512_3, sum512_3);
+
+        let sub512_4: __m512 =
+            _mm512_sub_ps(_mm512_loadu_ps(ptr1.add(48)), _mm512_loadu_ps(ptr2.add(48)));
+        sum512_4 = _mm512_fmadd_ps(sub512_4, sub512_4, sum512_4);
+
+        ptr1 = ptr1.add(64);
+        ptr2 = ptr2.add(64);
+        i += 64;
+    }
+
+    let mut result = _mm512_reduce_add_ps(sum512_1)
+        + _mm512_reduce_add_ps(sum512_2)
+        + _mm512_reduce_add_ps(sum512_3)
+        + _mm512_reduce_add_ps(sum512_4);
+    for i in 0..n - m {
+        result += (*ptr1.add(i) - *ptr2.add(i)).powi(2);
+    }
+    -result
+}
+
+#[target_feature(enable = "avx512f")]
+pub(crate) unsafe fn manhattan_similarity_avx512(
+    v1: &[VectorElementType],
+    v2: &[VectorElementType],
+) -> ScoreType {
+    let n = v1.len();
+    let m = n - (n % 64);
+    let mut ptr1: *const f32 = v1.as_ptr();
+    let mut ptr2: *const f32 = v2.as_ptr();
+    let mut sum512_1: __m512 = _mm512_setzero_ps();
+    let mut sum512_2: __m512 = _mm512_setzero_ps();
+    let mut sum512_3: __m512 = _mm512_setzero_ps();
+    let mut sum512_4: __m512 = _mm512_setzero_ps();
+    let mut i: usize = 0;
+    while i < m {
+        let sub512_1: __m512 = _mm512_sub_ps(_mm512_loadu_ps(ptr1), _mm512_loadu_ps(ptr2));
+        sum512_1 = _mm512_add_ps(_mm512_abs_ps(sub512_1), sum512_1);
+
+        let sub512_2: __m512 =
+            _mm512_sub_ps(_mm512_loadu_ps(ptr1.add(16)), _mm512_loadu_ps(ptr2.add(16)));
+        sum512_2 = _mm512_add_ps(_mm512_abs_ps(sub512_2), sum512_2);
+
+        let sub512_3: __m512 =
+            _mm512_sub_ps(_mm512_loadu_ps(ptr1.add(32)), _mm512_loadu_ps(ptr2.add(32)));
+        sum512_3 = _mm512_add_ps(_mm512_abs_ps(sub512_3), sum512_3);
+
+        let sub512_4: __m512 =
+            _mm512_sub_ps(_mm512_loadu_ps(ptr1.add(48)), _mm512_loadu_ps(ptr2.add(48)));
+        sum512_4 = _mm512_add_ps(_mm512_abs_ps(sub512_4), sum512_4);
+
+        ptr1 = ptr1.add(64);
+        ptr2 = ptr2.add(64);
+        i += 64;
+    }
+
+    let mut result = _mm512_reduce_add_ps(sum512_1)
+        + _mm512_reduce_add_ps(sum512_2)
+        + _mm512_reduce_add_ps(sum512_3)
+        + _mm512_reduce_add_ps(sum512_4);
+    for i in 0..n - m {
+        result += (*ptr1.add(i) - *ptr2.add(i)).abs();
+    }
+    -result
+}
+
+#[target_feature(enable = "avx512f")]
+pub(crate) unsafe fn cosine_preprocess_avx512(vector: DenseVector) -> DenseVector {
+    let n = vector.len();
+    let m = n - (n % 64);
+    let mut ptr: *const f32 = vector.as_ptr();
+    let mut sum512_1: __m512 = _mm512_setzero_ps();
+    let mut sum512_2: __m512 = _mm512_setzero_ps();
+    let mut sum512_3: __m512 = _mm512_setzero_ps();
+    let mut sum512_4: __m512 = _mm512_setzero_ps();
+    let mut i: usize = 0;
+    while i < m {
+        let m512_1 = _mm512_loadu_ps(ptr);
+        sum512_1 = _mm512_fmadd_ps(m512_1, m512_1, sum512_1);
+
+        let m512_2 = _mm512_loadu_ps(ptr.add(16));
+        sum512_2 = _mm512_fmadd_ps(m512_2, m512_2, sum512_2);
+
+        let m512_3 = _mm512_loadu_ps(ptr.add(32));
+        sum512_3 = _mm512_fmadd_ps(m512_3, m512_3, sum512_3);
+
+        let m512_4 = _mm512_loadu_ps(ptr.add(48));
+        sum512_4 = _mm512_fmadd_ps(m512_4, m512_4, sum512_4);
+
+        ptr = ptr.add(64);
+        i += 64;
+    }
+
+    let mut length = _mm512_reduce_add_ps(sum512_1)
+        + _mm512_reduce_add_ps(sum512_2)
+        + _mm512_reduce_add_ps(sum512_3)
+        + _mm512_reduce_add_ps(sum512_4);
+    for i in 0..n - m {
+        length += (*ptr.add(i)).powi(2);
+    }
+    if length < f32::EPSILON {
+        return vector;
+    }
+    length = length.sqrt();
+    vector.into_iter().map(|x| x / length).collect()
+}
+
+#[target_feature(enable = "avx512f")]
+pub(crate) unsafe fn dot_similarity_avx512(
+    v1: &[VectorElementType],
+    v2: &[VectorElementType],
+) -> ScoreType {
+    let n = v1.len();
+    let m = n - (n % 64);
+    let mut ptr1: *const f32 = v1.as_ptr();
+    let mut ptr2: *const f32 = v2.as_ptr();
+    let mut sum512_1: __m512 = _mm512_setzero_ps();
+    let mut sum512_2: __m512 = _mm512_setzero_ps();
+    let mut sum512_3: __m512 = _mm512_setzero_ps();
+    let mut sum512_4: __m512 = _mm512_setzero_ps();
+    let mut i: usize = 0;
+    while i < m {
+        sum512_1 = _mm512_fmadd_ps(_mm512_loadu_ps(ptr1), _mm512_loadu_ps(ptr2), sum512_1);
+        sum512_2 = _mm512_fmadd_ps(
+            _mm512_loadu_ps(ptr1.add(16)),
+            _mm512_loadu_ps(ptr2.add(16)),
+            sum512_2,
+        );
+        sum512_3 = _mm512_fmadd_ps(
+            _mm512_loadu_ps(ptr1.add(32)),
+            _mm512_loadu_ps(ptr2.add(32)),
+            sum512_3,
+        );
+        sum512_4 = _mm512_fmadd_ps(
+            _mm512_loadu_ps(ptr1.add(48)),
+            _mm512_loadu_ps(ptr2.add(48)),
+            sum512_4,
+        );
+
+        ptr1 = ptr1.add(64);
+        ptr2 = ptr2.add(64);
+        i += 64;
+    }
+
+    let mut result = _mm512_reduce_add_ps(sum512_1)
+        + _mm512_reduce_add_ps(sum512_2)
+        + _mm512_reduce_add_ps(sum512_3)
+        + _mm512_reduce_add_ps(sum512_4);
+
+    for i in 0..n - m {
+        result += (*ptr1.add(i)) * (*ptr2.add(i));
+    }
+    result
+}
+
+/// Sum of the 32-bit lanes, which hold non-negative sums of byte products
+#[target_feature(enable = "avx512f")]
+unsafe fn hsum512_epu32(x: __m512i) -> u64 {
+    let lanes: [u32; 16] = std::mem::transmute(x);
+    lanes.iter().map(|&lane| u64::from(lane)).sum()
+}
+
+/// Products of 32 bytes, accumulated into the 32-bit lanes with a single VNNI instruction
+///
+/// Bytes are widened to 16 bits, so the signed multiplication of `vpdpwssd` is exact.
+#[target_feature(enable = "avx512f")]
+#[target_feature(enable = "avx512bw")]
+#[target_feature(enable = "avx512vnni")]
+unsafe fn dpwssd_bytes_avx512(sum: __m512i, ptr1: *const u8, ptr2: *const u8) -> __m512i {
+    let a = _mm512_cvtepu8_epi16(_mm256_loadu_si256(ptr1 as *const __m256i));
+    let b = _mm512_cvtepu8_epi16(_mm256_loadu_si256(ptr2 as *const __m256i));
+    _mm512_dpwssd_epi32(sum, a, b)
+}
+
+#[target_feature(enable = "avx512f")]
+#[target_feature(enable = "avx512bw")]
+#[target_feature(enable = "avx512vnni")]
+pub(crate) unsafe fn dot_similarity_bytes_avx512vnni(
+    v1: &[VectorElementTypeByte],
+    v2: &[VectorElementTypeByte],
+) -> ScoreType {
+    let n = v1.len();
+    let m = n - (n % 64);
+    let mut ptr1: *const u8 = v1.as_ptr();
+    let mut ptr2: *const u8 = v2.as_ptr();
+    let mut sum512_1: __m512i = _mm512_setzero_si512();
+    let mut sum512_2: __m512i = _mm512_setzero_si512();
+    let mut i: usize = 0;
+    while i < m {
+        sum512_1 = dpwssd_bytes_avx512(sum512_1, ptr1, ptr2);
+        sum512_2 = dpwssd_bytes_avx512(sum512_2, ptr1.add(32), ptr2.add(32));
+        ptr1 = ptr1.add(64);
+        ptr2 = ptr2.add(64);
+        i += 64;
+    }
+
+    let mut result = hsum512_epu32(sum512_1) + hsum512_epu32(sum512_2);
+    for i in 0..n - m {
+        result += u64::from(*ptr1.add(i)) * u64::from(*ptr2.add(i));
+    }
+    result as ScoreType
+}
+
+#[target_feature(enable = "avx512f")]
+#[target_feature(enable = "avx512bw")]
+#[target_feature(enable = "avx512vnni")]
+pub(crate) unsafe fn cosine_similarity_bytes_avx512vnni(
+    v1: &[VectorElementTypeByte],
+    v2: &[VectorElementTypeByte],
+) -> ScoreType {
+    let n = v1.len();
+    let m = n - (n % 32);
+    let mut ptr1: *const u8 = v1.as_ptr();
+    let mut ptr2: *const u8 = v2.as_ptr();
+    let mut dot512: __m512i = _mm512_setzero_si512();
+    let mut norm512_1: __m512i = _mm512_setzero_si512();
+    let mut norm512_2: __m512i = _mm512_setzero_si512();
+    let mut i: usize = 0;
+    while i < m {
+        dot512 = dpwssd_bytes_avx512(dot512, ptr1, ptr2);
+        norm512_1 = dpwssd_bytes_avx512(norm512_1, ptr1, ptr1);
+        norm512_2 = dpwssd_bytes_avx512(norm512_2, ptr2, ptr2);
+        ptr1 = ptr1.add(32);
+        ptr2 = ptr2.add(32);
+        i += 32;
+    }
+
+    let mut dot = hsum512_epu32(dot512);
+    let mut norm1 = hsum512_epu32(norm512_1);
+    let mut norm2 = hsum512_epu32(norm512_2);
+    for i in 0..n - m {
+        let (a, b) = (u64::from(*ptr1.add(i)), u64::from(*ptr2.add(i)));
+        dot += a * b;
+        norm1 += a * a;
+        norm2 += b * b;
+    }
+    cosine_bytes_score(dot, norm1, norm2)
+}
+
+/// Load 16 half-precision floats, converted to single-precision
+#[target_feature(enable = "avx512f")]
+unsafe fn load_half_avx512(ptr: *const VectorElementTypeHalf) -> __m512 {
+    // `f16` is a transparent wrapper of the `u16` bits
+    _mm512_cvtph_ps(_mm256_loadu_si256(ptr as *const __m256i))
+}
+
+#[target_feature(enable = "avx512f")]
+pub(crate) unsafe fn dot_similarity_half_avx512(
+    v1: &[VectorElementTypeHalf],
+    v2: &[VectorElementTypeHalf],
+) -> ScoreType {
+    let n = v1.len();
+    let m = n - (n % 32);
+    let mut ptr1: *const VectorElementTypeHalf = v1.as_ptr();
+    let mut ptr2: *const VectorElementTypeHalf = v2.as_ptr();
+    let mut sum512_1: __m512 = _mm512_setzero_ps();
+    let mut sum512_2: __m512 = _mm512_setzero_ps();
+    let mut i: usize = 0;
+    while i < m {
+        sum512_1 = _mm512_fmadd_ps(load_half_avx512(ptr1), load_half_avx512(ptr2), sum512_1);
+        sum512_2 = _mm512_fmadd_ps(
+            load_half_avx512(ptr1.add(16)),
+            load_half_avx512(ptr2.add(16)),
+            sum512_2,
+        );
+        ptr1 = ptr1.add(32);
+        ptr2 = ptr2.add(32);
+        i += 32;
+    }
+
+    let mut result = _mm512_reduce_add_ps(sum512_1) + _mm512_reduce_add_ps(sum512_2);
+    for i in 0..n - m {
+        result += (*ptr1.add(i)).to_f32() * (*ptr2.add(i)).to_f32();
+    }
+    result
+}
+
+#[target_feature(enable = "avx512f")]
+pub(crate) unsafe fn euclid_similarity_half_avx512(
+    v1: &[VectorElementTypeHalf],
+    v2: &[VectorElementTypeHalf],
+) -> ScoreType {
+    let n = v1.len();
+    let m = n - (n % 32);
+    let mut ptr1: *const VectorElementTypeHalf = v1.as_ptr();
+    let mut ptr2: *const VectorElementTypeHalf = v2.as_ptr();
+    let mut sum512_1: __m512 = _mm512_setzero_ps();
+    let mut sum512_2: __m512 = _mm512_setzero_ps();
+    let mut i: usize = 0;
+    while i < m {
+        let sub512_1: __m512 = _mm512_sub_ps(load_half_avx512(ptr1), load_half_avx512(ptr2));
+        sum512_1 = _mm512_fmadd_ps(sub512_1, sub512_1, sum512_1);
+
+        let sub512_2: __m512 = _mm512_sub_ps(
+            load_half_avx512(ptr1.add(16)),
+            load_half_avx512(ptr2.add(16)),
+        );
+        sum512_2 = _mm512_fmadd_ps(sub512_2, sub512_2, sum512_2);
+
+        ptr1 = ptr1.add(32);
+        ptr2 = ptr2.add(32);
+        i += 32;
+    }
+
+    let mut result = _mm512_reduce_add_ps(sum512_1) + _mm512_reduce_add_ps(sum512_2);
+    for i in 0..n - m {
+        result += ((*ptr1.add(i)).to_f32() - (*ptr2.add(i)).to_f32()).powi(2);
+    }
+    -result
+}
+
+#[target_feature(enable = "avx512f")]
+pub(crate) unsafe fn manhattan_similarity_half_avx512(
+    v1: &[VectorElementTypeHalf],
+    v2: &[VectorElementTypeHalf],
+) -> ScoreType {
+    let n = v1.len();
+    let m = n - (n % 32);
+    let mut ptr1: *const VectorElementTypeHalf = v1.as_ptr();
+    let mut ptr2: *const VectorElementTypeHalf = v2.as_ptr();
+    let mut sum512_1: __m512 = _mm512_setzero_ps();
+    let mut sum512_2: __m512 = _mm512_setzero_ps();
+    let mut i: usize = 0;
+    while i < m {
+        let sub512_1: __m512 = _mm512_sub_ps(load_half_avx512(ptr1), load_half_avx512(ptr2));
+        sum512_1 = _mm512_add_ps(_mm512_abs_ps(sub512_1), sum512_1);
+
+        let sub512_2: __m512 = _mm512_sub_ps(
+            load_half_avx512(ptr1.add(16)),
+            load_half_avx512(ptr2.add(16)),
+        );
+        sum512_2 = _mm512_add_ps(_mm512_abs_ps(sub512_2), sum512_2);
+
+        ptr1 = ptr1.add(32);
+        ptr2 = ptr2.add(32);
+        i += 32;
+    }
+
+    let mut result = _mm512_reduce_add_ps(sum512_1) + _mm512_reduce_add_ps(sum512_2);
+    for i in 0..n - m {
+        result += ((*ptr1.add(i)).to_f32() - (*ptr2.add(i)).to_f32()).abs();
+    }
+    -result
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn test_spaces_avx512() {
+        use super::*;
+        use crate::spaces::simple::*;
+
+        if is_x86_feature_detected!("avx512f") {
+            let v1: Vec<f32> = (0..150).map(|i| (i % 23) as f32 + 10.).collect();
+            let v2: Vec<f32> = (0..150).map(|i| (i % 17) as f32 + 40.).collect();
+
+            let euclid_simd = unsafe { euclid_similarity_avx512(&v1, &v2) };
+            let euclid = euclid_similarity(&v1, &v2);
+            assert_eq!(euclid_simd, euclid);
+
+            let manhattan_simd = unsafe { manhattan_similarity_avx512(&v1, &v2) };
+            let manhattan = manhattan_similarity(&v1, &v2);
+            assert_eq!(manhattan_simd, manhattan);
+
+            let dot_simd = unsafe { dot_similarity_avx512(&v1, &v2) };
+            let dot = dot_similarity(&v1, &v2);
+            assert_eq!(dot_simd, dot);
+
+            let cosine_simd = unsafe { cosine_preprocess_avx512(v1.clone()) };
+            let cosine = cosine_preprocess(v1);
+            assert_eq!(cosine_simd, cosine);
+        } else {
+            println!("avx512 test skipped");
+        }
+    }
+
+    #[test]
+    fn test_spaces_bytes_avx512() {
+        use super::*;
+        use crate::spaces::simple::*;
+
+        if is_x86_feature_detected!("avx512f")
+            && is_x86_feature_detected!("avx512bw")
+            && is_x86_feature_detected!("avx512vnni")
+        {
+            let v1: Vec<u8> = (0..150).map(|i| (i * 5 % 256) as u8).collect();
+            let v2: Vec<u8> = (0..150).map(|i| (255 - i) as u8).collect();
+
+            let dot_simd = unsafe { dot_similarity_bytes_avx512vnni(&v1, &v2) };
+            let dot = dot_similarity_bytes(&v1, &v2);
+            assert_eq!(dot_simd, dot);
+
+            let cosine_simd = unsafe { cosine_similarity_bytes_avx512vnni(&v1, &v2) };
+            let cosine = cosine_similarity_bytes(&v1, &v2);
+            assert_eq!(cosine_simd, cosine);
+
+            // Largest products do not overflow the 32-bit lanes
+            let max = vec![u8::MAX; 4096];
+            let dot_simd = unsafe { dot_similarity_bytes_avx512vnni(&max, &max) };
+            assert_eq!(dot_simd, dot_similarity_bytes(&max, &max));
+        } else {
+            println!("avx512vnni test skipped");
+        }
+    }
+
+    #[test]
+    fn test_spaces_half_avx512() {
+        use super::*;
+        use crate::data_types::vectors::dense_vector_to_half;
+        use crate::spaces::simple::*;
+
+        if is_x86_feature_detected!("avx512f") {
+            let v1 = dense_vector_to_half(&(0..150).map(|i| i as f32 / 10.).collect::<Vec<_>>());
+            let v2 =
+                dense_vector_to_half(&(0..150).map(|i| 5. - i as f32 / 7.).collect::<Vec<_>>());
+
+            // Summation order differs, so results are not bit-exact
+            let dot_simd = unsafe { dot_similarity_half_avx512(&v1, &v2) };
+            let dot = dot_similarity_half(&v1, &v2);
+            assert!((dot_simd - dot).abs() < 1e-2);
+
+            let euclid_simd = unsafe { euclid_similarity_half_avx512(&v1, &v2) };
+            let euclid = euclid_similarity_half(&v1, &v2);
+            assert!((euclid_simd - euclid).abs() < 1e-1);
+
+            let manhattan_simd = unsafe { manhattan_similarity_half_avx512(&v1, &v2) };
+            let manhattan = manhattan_similarity_half(&v1, &v2);
+            assert!((manhattan_simd - manhattan).abs() < 1e-2);
+        } else {
+            println!("avx512 test skipped");
+        }
+    }
+}
@@ -6,7 +6,9 @@ use common::types::ScoreType;
 
 use crate::data_types::vectors::DenseVector;
 #[cfg(target_feature = "neon")]
-use crate::data_types::vectors::VectorElementType;
+use crate::data_types::vectors::{VectorElementType, VectorElementTypeByte};
+#[cfg(target_feature = "neon")]
+use crate::spaces::simple::cosine_bytes_score;
 
 #[cfg(target_feature = "neon")]
 pub(crate) unsafe fn euclid_similarity_neon(
@@ -155,6 +157,74 @@ pub(crate) unsafe fn dot_similarity_neon(
     result
 }
 
+/// Products of 16 bytes, accumulated pairwise into the 32-bit lanes
+#[cfg(target_feature = "neon")]
+unsafe fn madd_bytes_neon(sum: uint32x4_t, a: uint8x16_t, b: uint8x16_t) -> uint32x4_t {
+    let sum = vpadalq_u16(sum, vmull_u8(vget_low_u8(a), vget_low_u8(b)));
+    vpadalq_u16(sum, vmull_u8(vget_high_u8(a), vget_high_u8(b)))
+}
+
+#[cfg(target_feature = "neon")]
+pub(crate) unsafe fn dot_similarity_bytes_neon(
+    v1: &[VectorElementTypeByte],
+    v2: &[VectorElementTypeByte],
+) -> ScoreType {
+    let n = v1.len();
+    let m = n - (n % 16);
+    let mut ptr1: *const u8 = v1.as_ptr();
+    let mut ptr2: *const u8 = v2.as_ptr();
+    let mut sum = vdupq_n_u32(0);
+
+    let mut i: usize = 0;
+    while i < m {
+        sum = madd_bytes_neon(sum, vld1q_u8(ptr1), vld1q_u8(ptr2));
+        ptr1 = ptr1.add(16);
+        ptr2 = ptr2.add(16);
+        i += 16;
+    }
+    let mut result = vaddlvq_u32(sum);
+    for i in 0..n - m {
+        result += u64::from(*ptr1.add(i)) * u64::from(*ptr2.add(i));
+    }
+    result as ScoreType
+}
+
+#[cfg(target_feature = "neon")]
+pub(crate) unsafe fn cosine_similarity_bytes_neon(
+    v1: &[VectorElementTypeByte],
+    v2: &[VectorElementTypeByte],
+) -> ScoreType {
+    let n = v1.len();
+    let m = n - (n % 16);
+    let mut ptr1: *const u8 = v1.as_ptr();
+    let mut ptr2: *const u8 = v2.as_ptr();
+    let mut dot_sum = vdupq_n_u32(0);
+    let mut norm_sum1 = vdupq_n_u32(0);
+    let mut norm_sum2 = vdupq_n_u32(0);
+
+    let mut i: usize = 0;
+    while i < m {
+        let a = vld1q_u8(ptr1);
+        let b = vld1q_u8(ptr2);
+        dot_sum = madd_bytes_neon(dot_sum, a, b);
+        norm_sum1 = madd_bytes_neon(norm_sum1, a, a);
+        norm_sum2 = madd_bytes_neon(norm_sum2, b, b);
+        ptr1 = ptr1.add(16);
+        ptr2 = ptr2.add(16);
+        i += 16;
+    }
+    let mut dot = vaddlvq_u32(dot_sum);
+    let mut norm1 = vaddlvq_u32(norm_sum1);
+    let mut norm2 = vaddlvq_u32(norm_sum2);
+    for i in 0..n - m {
+        let (a, b) = (u64::from(*ptr1.add(i)), u64::from(*ptr2.add(i)));
+        dot += a * b;
+        norm1 += a * a;
+        norm2 += b * b;
+    }
+    cosine_bytes_score(dot, norm1, norm2)
+}
+
 #[cfg(test)]
 mod tests {
     #[cfg(target_feature = "neon")]
@@ -192,4 +262,26 @@ mod tests {
             println!("neon test skipped");
         }
     }
+
+    #[cfg(target_feature = "neon")]
+    #[test]
+    fn test_spaces_bytes_neon() {
+        use super::*;
+        use crate::spaces::simple::*;
+
+        if std::arch::is_aarch64_feature_detected!("neon") {
+            let v1: Vec<u8> = (0..53).map(|i| (i * 5 % 256) as u8).collect();
+            let v2: Vec<u8> = (0..53).map(|i| (255 - i * 3) as u8).collect();
+
+            let dot_simd = unsafe { dot_similarity_bytes_neon(&v1, &v2) };
+            let dot = dot_similarity_bytes(&v1, &v2);
+            assert_eq!(dot_simd, dot);
+
+            let cosine_simd = unsafe { cosine_similarity_bytes_neon(&v1, &v2) };
+            let cosine = cosine_similarity_bytes(&v1, &v2);
+            assert_eq!(cosine_simd, cosine);
+        } else {
+            println!("neon test skipped");
+        }
+    }
 }
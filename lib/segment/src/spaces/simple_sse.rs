@@ -5,7 +5,8 @@ use std::arch::x86_64::*;
 
 use common::types::ScoreType;
 
-use crate::data_types::vectors::{DenseVector, VectorElementType};
+use crate::data_types::vectors::{DenseVector, VectorElementType, VectorElementTypeByte};
+use crate::spaces::simple::cosine_bytes_score;
 
 #[target_feature(enable = "sse")]
 unsafe fn hsum128_ps_sse(x: __m128) -> f32 {
@@ -190,6 +191,89 @@ pub(crate) unsafe fn dot_similarity_sse(
     result
 }
 
+#[target_feature(enable = "sse2")]
+unsafe fn hsum128_epi32_sse(x: __m128i) -> u64 {
+    let mut lanes = [0u32; 4];
+    _mm_storeu_si128(lanes.as_mut_ptr() as *mut __m128i, x);
+    lanes.iter().map(|&lane| u64::from(lane)).sum()
+}
+
+/// Products of 16 bytes, accumulated into the 32-bit lanes
+///
+/// Bytes are widened to 16 bits, so the signed multiplication of `_mm_madd_epi16` is exact.
+#[target_feature(enable = "sse2")]
+unsafe fn madd_bytes_sse(sum: __m128i, a: __m128i, b: __m128i) -> __m128i {
+    let zero = _mm_setzero_si128();
+    let lo = _mm_madd_epi16(_mm_unpacklo_epi8(a, zero), _mm_unpacklo_epi8(b, zero));
+    let hi = _mm_madd_epi16(_mm_unpackhi_epi8(a, zero), _mm_unpackhi_epi8(b, zero));
+    _mm_add_epi32(sum, _mm_add_epi32(lo, hi))
+}
+
+#[target_feature(enable = "sse2")]
+pub(crate) unsafe fn dot_similarity_bytes_sse(
+    v1: &[VectorElementTypeByte],
+    v2: &[VectorElementTypeByte],
+) -> ScoreType {
+    let n = v1.len();
+    let m = n - (n % 16);
+    let mut ptr1: *const u8 = v1.as_ptr();
+    let mut ptr2: *const u8 = v2.as_ptr();
+    let mut sum128: __m128i = _mm_setzero_si128();
+    let mut i: usize = 0;
+    while i < m {
+        sum128 = madd_bytes_sse(
+            sum128,
+            _mm_loadu_si128(ptr1 as *const __m128i),
+            _mm_loadu_si128(ptr2 as *const __m128i),
+        );
+        ptr1 = ptr1.add(16);
+        ptr2 = ptr2.add(16);
+        i += 16;
+    }
+
+    let mut result = hsum128_epi32_sse(sum128);
+    for i in 0..n - m {
+        result += u64::from(*ptr1.add(i)) * u64::from(*ptr2.add(i));
+    }
+    result as ScoreType
+}
+
+#[target_feature(enable = "sse2")]
+pub(crate) unsafe fn cosine_similarity_bytes_sse(
+    v1: &[VectorElementTypeByte],
+    v2: &[VectorElementTypeByte],
+) -> ScoreType {
+    let n = v1.len();
+    let m = n - (n % 16);
+    let mut ptr1: *const u8 = v1.as_ptr();
+    let mut ptr2: *const u8 = v2.as_ptr();
+    let mut dot128: __m128i = _mm_setzero_si128();
+    let mut norm128_1: __m128i = _mm_setzero_si128();
+    let mut norm128_2: __m128i = _mm_setzero_si128();
+    let mut i: usize = 0;
+    while i < m {
+        let a = _mm_loadu_si128(ptr1 as *const __m128i);
+        let b = _mm_loadu_si128(ptr2 as *const __m128i);
+        dot128 = madd_bytes_sse(dot128, a, b);
+        norm128_1 = madd_bytes_sse(norm128_1, a, a);
+        norm128_2 = madd_bytes_sse(norm128_2, b, b);
+        ptr1 = ptr1.add(16);
+        ptr2 = ptr2.add(16);
+        i += 16;
+    }
+
+    let mut dot = hsum128_epi32_sse(dot128);
+    let mut norm1 = hsum128_epi32_sse(norm128_1);
+    let mut norm2 = hsum128_epi32_sse(norm128_2);
+    for i in 0..n - m {
+        let (a, b) = (u64::from(*ptr1.add(i)), u64::from(*ptr2.add(i)));
+        dot += a * b;
+        norm1 += a * a;
+        norm2 += b * b;
+    }
+    cosine_bytes_score(dot, norm1, norm2)
+}
+
 #[cfg(test)]
 mod tests {
     #[test]
@@ -230,4 +314,25 @@ mod tests {
             println!("sse test skipped");
         }
     }
+
+    #[test]
+    fn test_spaces_bytes_sse() {
+        use super::*;
+        use crate::spaces::simple::*;
+
+        if is_x86_feature_detected!("sse2") {
+            let v1: Vec<u8> = (0..53).map(|i| (i * 5 % 256) as u8).collect();
+            let v2: Vec<u8> = (0..53).map(|i| (255 - i * 3) as u8).collect();
+
+            let dot_simd = unsafe { dot_similarity_bytes_sse(&v1, &v2) };
+            let dot = dot_similarity_bytes(&v1, &v2);
+            assert_eq!(dot_simd, dot);
+
+            let cosine_simd = unsafe { cosine_similarity_bytes_sse(&v1, &v2) };
+            let cosine = cosine_similarity_bytes(&v1, &v2);
+            assert_eq!(cosine_simd, cosine);
+        } else {
+            println!("sse2 test skipped");
+        }
+    }
 }
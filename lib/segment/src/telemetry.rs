@@ -138,6 +138,8 @@ impl Anonymize for VectorDataConfig {
             storage_type: self.storage_type,
             index: self.index.clone(),
             quantization_config: None,
+            multivector_config: self.multivector_config,
+            datatype: self.datatype,
         }
     }
 }
@@ -34,6 +34,27 @@ use crate::vector_storage::simple_sparse_vector_storage::SPARSE_VECTOR_DISTANCE;
 
 pub type PayloadKeyType = String;
 pub type PayloadKeyTypeRef<'a> = &'a str;
+
+/// Prefix of the payload fields, which are reserved for internal use
+///
+/// Reserved fields are written by updates internally and are never returned with the payload
+/// of the point. Clients can't write, delete, filter or index them.
+pub const RESERVED_PAYLOAD_KEY_PREFIX: &str = "__qdrant_";
+
+/// Whether the payload `key` refers to a reserved field, or to a path inside of it
+pub fn is_reserved_payload_key(key: &str) -> bool {
+    key.starts_with(RESERVED_PAYLOAD_KEY_PREFIX)
+}
+
+/// Rejects conditions on the reserved payload fields
+pub fn validate_payload_key(key: &str) -> Result<(), ValidationErrors> {
+    if is_reserved_payload_key(key) {
+        let mut errors = ValidationErrors::new();
+        errors.add("key", ValidationError::new("payload key is reserved"));
+        return Err(errors);
+    }
+    Ok(())
+}
 /// Sequential number of modification, applied to segment
 pub type SeqNumberType = u64;
 pub type TagType = u64;
@@ -218,6 +239,17 @@ impl PartialEq for ScoredPoint {
     }
 }
 
+/// Point data, retrieved from a segment by id
+#[derive(Clone, Debug, PartialEq)]
+pub struct RetrievedPoint {
+    /// Point id
+    pub id: PointIdType,
+    /// Payload - values assigned to the point
+    pub payload: Option<Payload>,
+    /// Vector of the point
+    pub vector: Option<VectorStruct>,
+}
+
 /// Type of segment
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, Copy, PartialEq, Eq)]
 #[serde(rename_all = "snake_case")]
@@ -529,6 +561,15 @@ impl QuantizationConfig {
     pub fn mismatch_requires_rebuild(&self, other: &Self) -> bool {
         self != other
     }
+
+    /// Override whether quantized vectors are kept in RAM, regardless of the main storage
+    pub fn set_always_ram(&mut self, always_ram: bool) {
+        match self {
+            QuantizationConfig::Scalar(scalar) => scalar.scalar.always_ram = Some(always_ram),
+            QuantizationConfig::Product(product) => product.product.always_ram = Some(always_ram),
+            QuantizationConfig::Binary(binary) => binary.binary.always_ram = Some(always_ram),
+        }
+    }
 }
 
 impl Validate for QuantizationConfig {
@@ -606,11 +647,16 @@ pub enum PayloadStorageType {
     InMemory,
     // Store payload on disk only, read each time it is requested
     OnDisk,
+    // Store payload in memory-mapped pages, without RocksDB
+    AppendLog,
 }
 
 impl PayloadStorageType {
     pub fn is_on_disk(&self) -> bool {
-        matches!(self, PayloadStorageType::OnDisk)
+        matches!(
+            self,
+            PayloadStorageType::OnDisk | PayloadStorageType::AppendLog
+        )
     }
 }
 
@@ -635,6 +681,10 @@ impl SegmentConfig {
     pub fn quantization_config(&self, vector_name: &str) -> Option<&QuantizationConfig> {
         self.vector_data
             .get(vector_name)
+            // Multi-vectors and vectors of the uint8 and float16 datatypes are not quantized
+            .filter(|v| {
+                v.multivector_config.is_none() && !v.is_byte_datatype() && !v.is_half_datatype()
+            })
             .and_then(|v| v.quantization_config.as_ref())
     }
 
@@ -652,6 +702,22 @@ impl SegmentConfig {
         }
     }
 
+    /// Distance to preprocess the vectors of the given name with, before they are stored
+    ///
+    /// Vectors of the `uint8` datatype are stored as is, so `None` is returned for them.
+    pub fn preprocessing_distance(&self, vector_name: &str) -> Option<Distance> {
+        match self.vector_data.get(vector_name) {
+            Some(config) if config.is_byte_datatype() => None,
+            _ => self.distance(vector_name),
+        }
+    }
+
+    /// Check if the segment has a storage for the dense or sparse vector with the given name
+    pub fn has_vector(&self, vector_name: &str) -> bool {
+        self.vector_data.contains_key(vector_name)
+            || self.sparse_vector_data.contains_key(vector_name)
+    }
+
     /// Check if any vector storages are indexed
     pub fn is_any_vector_indexed(&self) -> bool {
         self.vector_data
@@ -728,6 +794,17 @@ pub struct VectorDataConfig {
     pub index: Indexes,
     /// Vector specific quantization config that overrides collection config
     pub quantization_config: Option<QuantizationConfig>,
+    /// If set - each point stores a list of vectors of `size` dimensions
+    ///
+    /// Multi-vectors are always kept in memory, regardless of the storage type.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub multivector_config: Option<MultiVectorConfig>,
+    /// Type of the elements of the stored vectors, `float32` if not set
+    ///
+    /// Vectors of the `uint8` and `float16` datatypes are kept in appendable chunked mmap files, if
+    /// stored on disk.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub datatype: Option<VectorStorageDatatype>,
 }
 
 impl VectorDataConfig {
@@ -740,12 +817,62 @@ impl VectorDataConfig {
             Indexes::Hnsw(_) => false,
         };
         let is_storage_appendable = match self.storage_type {
+            _ if self.multivector_config.is_some() => true,
+            // Storages of the uint8 and float16 datatypes are in memory or chunked mmap
+            _ if self.is_byte_datatype() || self.is_half_datatype() => true,
             VectorStorageType::Memory => true,
             VectorStorageType::Mmap => false,
             VectorStorageType::ChunkedMmap => true,
         };
         is_index_appendable && is_storage_appendable
     }
+
+    /// Whether vectors are stored with the `uint8` datatype
+    pub fn is_byte_datatype(&self) -> bool {
+        self.datatype == Some(VectorStorageDatatype::Uint8)
+    }
+
+    /// Whether vectors are stored with the `float16` datatype
+    pub fn is_half_datatype(&self) -> bool {
+        self.datatype == Some(VectorStorageDatatype::Float16)
+    }
+}
+
+/// Type of the elements of the stored vectors
+#[derive(Debug, Default, Deserialize, Serialize, JsonSchema, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum VectorStorageDatatype {
+    /// 4 bytes per dimension
+    #[default]
+    Float32,
+    /// 1 byte per dimension, values are integers in range `[0, 255]`
+    ///
+    /// Embeddings are expected to be quantized by the client. Vectors are not normalized, cosine
+    /// similarity is normalized on scoring instead.
+    Uint8,
+    /// 2 bytes per dimension, half-precision floats
+    ///
+    /// Values are rounded to the nearest representable value, absolute values must not exceed
+    /// 65504. Vectors are converted to `float32` on scoring.
+    Float16,
+}
+
+/// Config of the multi-vectors: variable number of vectors per point under a single vector name
+#[derive(Debug, Default, Deserialize, Serialize, JsonSchema, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub struct MultiVectorConfig {
+    /// How to compare a multi-vector with another one
+    pub comparator: MultiVectorComparator,
+}
+
+/// Comparator of the multi-vectors
+#[derive(Debug, Default, Deserialize, Serialize, JsonSchema, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum MultiVectorComparator {
+    /// Late interaction: for each vector of the query take the highest similarity to any vector
+    /// of the point, and sum these similarities up
+    #[default]
+    MaxSim,
 }
 
 /// Config of single sparse vector data storage
@@ -1638,6 +1765,8 @@ pub fn validate_field_condition(field_condition: &FieldCondition) -> Result<(),
         Err(ValidationError::new(
             "At least one field condition must be specified",
         ))
+    } else if is_reserved_payload_key(&field_condition.key) {
+        Err(ValidationError::new("payload key is reserved"))
     } else {
         Ok(())
     }
@@ -1763,9 +1892,14 @@ impl Condition {
 impl Validate for Condition {
     fn validate(&self) -> Result<(), ValidationErrors> {
         match self {
-            Condition::HasId(_) | Condition::IsEmpty(_) | Condition::IsNull(_) => Ok(()),
+            Condition::HasId(_) => Ok(()),
+            Condition::IsEmpty(condition) => validate_payload_key(&condition.is_empty.key),
+            Condition::IsNull(condition) => validate_payload_key(&condition.is_null.key),
             Condition::Field(field_condition) => field_condition.validate(),
-            Condition::Nested(nested_condition) => nested_condition.validate(),
+            Condition::Nested(nested_condition) => {
+                validate_payload_key(&nested_condition.nested.key)?;
+                nested_condition.validate()
+            }
             Condition::Filter(filter) => filter.validate(),
         }
     }
@@ -1920,25 +2054,26 @@ impl PayloadSelector {
         })
     }
 
-    /// Process payload selector
-    pub fn process(&self, x: Payload) -> Payload {
+    /// Check if the value at the given path of the payload is kept by the selector
+    ///
+    /// A top-level field of the payload is selected, if its key is selected.
+    pub fn is_selected(&self, path: &str) -> bool {
         match self {
-            PayloadSelector::Include(selector) => filter_json_values(&x.0, |key, _| {
-                selector
-                    .include
-                    .iter()
-                    .any(|pattern| check_include_pattern(pattern, key))
-            })
-            .into(),
-            PayloadSelector::Exclude(selector) => filter_json_values(&x.0, |key, _| {
-                selector
-                    .exclude
-                    .iter()
-                    .all(|pattern| !check_exclude_pattern(pattern, key))
-            })
-            .into(),
+            PayloadSelector::Include(selector) => selector
+                .include
+                .iter()
+                .any(|pattern| check_include_pattern(pattern, path)),
+            PayloadSelector::Exclude(selector) => selector
+                .exclude
+                .iter()
+                .all(|pattern| !check_exclude_pattern(pattern, path)),
         }
     }
+
+    /// Process payload selector
+    pub fn process(&self, x: Payload) -> Payload {
+        filter_json_values(&x.0, |path, _| self.is_selected(path)).into()
+    }
 }
 
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, Default, PartialEq, Eq)]
@@ -2,4 +2,5 @@ pub mod fmt;
 pub mod fs;
 pub mod mem;
 pub mod path;
+pub mod resource_usage;
 pub mod tar;
@@ -0,0 +1,197 @@
+//! Resource usage of the threads and of the process, read from `/proc` on Linux
+//!
+//! Other platforms don't report any usage, all values are zero there.
+
+use std::cell::Cell;
+use std::ops::{Add, AddAssign};
+use std::time::Duration;
+
+/// Resources used by a thread since its start
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct ThreadUsage {
+    /// Time spent on CPU
+    pub cpu_time: Duration,
+    /// Bytes fetched from the storage
+    pub read_bytes: u64,
+    /// Bytes sent to the storage
+    pub write_bytes: u64,
+}
+
+impl ThreadUsage {
+    /// Usage of the current thread
+    pub fn current() -> Self {
+        #[cfg(target_os = "linux")]
+        {
+            linux::current_thread_usage()
+        }
+        #[cfg(not(target_os = "linux"))]
+        {
+            Self::default()
+        }
+    }
+
+    /// Usage between the `earlier` measurement and this one
+    pub fn since(self, earlier: Self) -> Self {
+        Self {
+            cpu_time: self.cpu_time.saturating_sub(earlier.cpu_time),
+            read_bytes: self.read_bytes.saturating_sub(earlier.read_bytes),
+            write_bytes: self.write_bytes.saturating_sub(earlier.write_bytes),
+        }
+    }
+}
+
+impl Add for ThreadUsage {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self {
+            cpu_time: self.cpu_time + other.cpu_time,
+            read_bytes: self.read_bytes + other.read_bytes,
+            write_bytes: self.write_bytes + other.write_bytes,
+        }
+    }
+}
+
+impl AddAssign for ThreadUsage {
+    fn add_assign(&mut self, other: Self) {
+        *self = *self + other;
+    }
+}
+
+thread_local! {
+    static HELPERS_USAGE: Cell<ThreadUsage> = Cell::new(ThreadUsage::default());
+}
+
+/// Attribute usage of the helper threads, like a dedicated index building pool, to the current
+/// thread, which is waiting for them
+pub fn add_helpers_usage(usage: ThreadUsage) {
+    HELPERS_USAGE.with(|helpers_usage| helpers_usage.set(helpers_usage.get() + usage));
+}
+
+/// Usage of the current thread, including the usage of its helper threads
+pub fn current_usage_with_helpers() -> ThreadUsage {
+    ThreadUsage::current() + HELPERS_USAGE.with(Cell::get)
+}
+
+/// Usage of all threads of the dedicated pool since their start
+pub fn pool_usage(pool: &rayon::ThreadPool) -> ThreadUsage {
+    pool.broadcast(|_| ThreadUsage::current())
+        .into_iter()
+        .fold(ThreadUsage::default(), Add::add)
+}
+
+/// Resident memory of the process in bytes, if it's available on this platform
+pub fn process_rss_bytes() -> Option<u64> {
+    #[cfg(target_os = "linux")]
+    {
+        linux::process_rss_bytes()
+    }
+    #[cfg(not(target_os = "linux"))]
+    {
+        None
+    }
+}
+
+#[cfg(target_os = "linux")]
+mod linux {
+    use std::fs;
+    use std::time::Duration;
+
+    use super::ThreadUsage;
+
+    pub fn current_thread_usage() -> ThreadUsage {
+        let cpu_time = fs::read_to_string("/proc/thread-self/schedstat")
+            .ok()
+            .and_then(|schedstat| parse_schedstat_cpu_time(&schedstat))
+            .unwrap_or_default();
+        let (read_bytes, write_bytes) = fs::read_to_string("/proc/thread-self/io")
+            .map(|io| parse_io_bytes(&io))
+            .unwrap_or_default();
+
+        ThreadUsage {
+            cpu_time,
+            read_bytes,
+            write_bytes,
+        }
+    }
+
+    pub fn process_rss_bytes() -> Option<u64> {
+        let status = fs::read_to_string("/proc/self/status").ok()?;
+        parse_status_rss_bytes(&status)
+    }
+
+    /// First field of `schedstat` is the time spent on CPU in nanoseconds
+    pub(super) fn parse_schedstat_cpu_time(schedstat: &str) -> Option<Duration> {
+        let nanos = schedstat.split_whitespace().next()?.parse().ok()?;
+        Some(Duration::from_nanos(nanos))
+    }
+
+    /// Bytes actually read from and written to the storage, `(read_bytes, write_bytes)`
+    pub(super) fn parse_io_bytes(io: &str) -> (u64, u64) {
+        let field = |name: &str| {
+            io.lines()
+                .find_map(|line| line.strip_prefix(name)?.trim().parse().ok())
+                .unwrap_or(0)
+        };
+        (field("read_bytes:"), field("write_bytes:"))
+    }
+
+    pub(super) fn parse_status_rss_bytes(status: &str) -> Option<u64> {
+        let kilobytes: u64 = status
+            .lines()
+            .find_map(|line| line.strip_prefix("VmRSS:"))?
+            .trim()
+            .strip_suffix("kB")?
+            .trim()
+            .parse()
+            .ok()?;
+        Some(kilobytes * 1024)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_usage_since() {
+        let earlier = ThreadUsage {
+            cpu_time: Duration::from_millis(100),
+            read_bytes: 10,
+            write_bytes: 20,
+        };
+        let later = ThreadUsage {
+            cpu_time: Duration::from_millis(250),
+            read_bytes: 15,
+            write_bytes: 20,
+        };
+
+        assert_eq!(
+            later.since(earlier),
+            ThreadUsage {
+                cpu_time: Duration::from_millis(150),
+                read_bytes: 5,
+                write_bytes: 0,
+            },
+        );
+        assert_eq!(earlier.since(later), ThreadUsage::default());
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_parse_proc_files() {
+        use super::linux::*;
+
+        assert_eq!(
+            parse_schedstat_cpu_time("1500000000 2000 31\n"),
+            Some(Duration::from_millis(1500)),
+        );
+
+        let io = "rchar: 100\nwchar: 200\nsyscr: 3\nsyscw: 4\nread_bytes: 4096\n\
+                  write_bytes: 8192\ncancelled_write_bytes: 0\n";
+        assert_eq!(parse_io_bytes(io), (4096, 8192));
+
+        let status = "Name:\tqdrant\nVmHWM:\t  2048 kB\nVmRSS:\t  1024 kB\nThreads:\t4\n";
+        assert_eq!(parse_status_rss_bytes(status), Some(1024 * 1024));
+    }
+}
@@ -1,3 +1,4 @@
+use std::borrow::Cow;
 use std::fs::create_dir_all;
 use std::ops::Range;
 use std::path::{Path, PathBuf};
@@ -8,11 +9,14 @@ use atomic_refcell::AtomicRefCell;
 use bitvec::prelude::BitSlice;
 use common::types::PointOffsetType;
 
-use super::DenseVectorStorage;
+use super::{DenseByteVectorStorage, DenseHalfVectorStorage, DenseVectorStorage};
 use crate::common::operation_error::{check_process_stopped, OperationResult};
 use crate::common::Flusher;
 use crate::data_types::named_vectors::CowVector;
-use crate::data_types::vectors::{VectorElementType, VectorRef};
+use crate::data_types::primitive::PrimitiveVectorElement;
+use crate::data_types::vectors::{
+    VectorElementType, VectorElementTypeByte, VectorElementTypeHalf, VectorRef,
+};
 use crate::types::Distance;
 use crate::vector_storage::chunked_mmap_vectors::ChunkedMmapVectors;
 use crate::vector_storage::dynamic_mmap_flags::DynamicMmapFlags;
@@ -21,8 +25,11 @@ use crate::vector_storage::{VectorStorage, VectorStorageEnum};
 const VECTORS_DIR_PATH: &str = "vectors";
 const DELETED_DIR_PATH: &str = "deleted";
 
-pub struct AppendableMmapVectorStorage {
-    vectors: ChunkedMmapVectors,
+/// Appendable on-disk storage of the dense vectors
+///
+/// Vectors are stored with the `T` element type and are converted to and from float vectors.
+pub struct AppendableMmapVectorStorage<T: PrimitiveVectorElement = VectorElementType> {
+    vectors: ChunkedMmapVectors<T>,
     deleted: DynamicMmapFlags,
     distance: Distance,
     deleted_count: usize,
@@ -33,12 +40,48 @@ pub fn open_appendable_memmap_vector_storage(
     dim: usize,
     distance: Distance,
 ) -> OperationResult<Arc<AtomicRefCell<VectorStorageEnum>>> {
+    let storage = open_appendable_memmap_vector_storage_impl(path, dim, distance)?;
+
+    Ok(Arc::new(AtomicRefCell::new(
+        VectorStorageEnum::AppendableMemmap(Box::new(storage)),
+    )))
+}
+
+pub fn open_appendable_memmap_byte_vector_storage(
+    path: &Path,
+    dim: usize,
+    distance: Distance,
+) -> OperationResult<Arc<AtomicRefCell<VectorStorageEnum>>> {
+    let storage = open_appendable_memmap_vector_storage_impl(path, dim, distance)?;
+
+    Ok(Arc::new(AtomicRefCell::new(
+        VectorStorageEnum::AppendableMemmapByte(Box::new(storage)),
+    )))
+}
+
+pub fn open_appendable_memmap_half_vector_storage(
+    path: &Path,
+    dim: usize,
+    distance: Distance,
+) -> OperationResult<Arc<AtomicRefCell<VectorStorageEnum>>> {
+    let storage = open_appendable_memmap_vector_storage_impl(path, dim, distance)?;
+
+    Ok(Arc::new(AtomicRefCell::new(
+        VectorStorageEnum::AppendableMemmapHalf(Box::new(storage)),
+    )))
+}
+
+fn open_appendable_memmap_vector_storage_impl<T: PrimitiveVectorElement>(
+    path: &Path,
+    dim: usize,
+    distance: Distance,
+) -> OperationResult<AppendableMmapVectorStorage<T>> {
     create_dir_all(path)?;
 
     let vectors_path = path.join(VECTORS_DIR_PATH);
     let deleted_path = path.join(DELETED_DIR_PATH);
 
-    let vectors: ChunkedMmapVectors = ChunkedMmapVectors::open(&vectors_path, dim)?;
+    let vectors: ChunkedMmapVectors<T> = ChunkedMmapVectors::open(&vectors_path, dim)?;
 
     let num_vectors = vectors.len();
 
@@ -52,19 +95,15 @@ pub fn open_appendable_memmap_vector_storage(
         }
     }
 
-    let storage = AppendableMmapVectorStorage {
+    Ok(AppendableMmapVectorStorage {
         vectors,
         deleted,
         distance,
         deleted_count,
-    };
-
-    Ok(Arc::new(AtomicRefCell::new(
-        VectorStorageEnum::AppendableMemmap(Box::new(storage)),
-    )))
+    })
 }
 
-impl AppendableMmapVectorStorage {
+impl<T: PrimitiveVectorElement> AppendableMmapVectorStorage<T> {
     /// Set deleted flag for given key. Returns previous deleted state.
     #[inline]
     fn set_deleted(&mut self, key: PointOffsetType, deleted: bool) -> OperationResult<bool> {
@@ -85,13 +124,25 @@ impl AppendableMmapVectorStorage {
     }
 }
 
-impl DenseVectorStorage for AppendableMmapVectorStorage {
+impl DenseVectorStorage for AppendableMmapVectorStorage<VectorElementType> {
     fn get_dense(&self, key: PointOffsetType) -> &[VectorElementType] {
         self.vectors.get(key)
     }
 }
 
-impl VectorStorage for AppendableMmapVectorStorage {
+impl DenseByteVectorStorage for AppendableMmapVectorStorage<VectorElementTypeByte> {
+    fn get_dense_bytes(&self, key: PointOffsetType) -> &[VectorElementTypeByte] {
+        self.vectors.get(key)
+    }
+}
+
+impl DenseHalfVectorStorage for AppendableMmapVectorStorage<VectorElementTypeHalf> {
+    fn get_dense_half(&self, key: PointOffsetType) -> &[VectorElementTypeHalf] {
+        self.vectors.get(key)
+    }
+}
+
+impl<T: PrimitiveVectorElement> VectorStorage for AppendableMmapVectorStorage<T> {
     fn vector_dim(&self) -> usize {
         self.vectors.dim()
     }
@@ -109,11 +160,13 @@ impl VectorStorage for AppendableMmapVectorStorage {
     }
 
     fn get_vector(&self, key: PointOffsetType) -> CowVector {
-        self.get_dense(key).into()
+        let vector = self.vectors.get(key);
+        CowVector::Dense(T::slice_to_float_cow(Cow::Borrowed(vector)))
     }
 
     fn insert_vector(&mut self, key: PointOffsetType, vector: VectorRef) -> OperationResult<()> {
-        self.vectors.insert(key, vector.try_into()?)?;
+        let vector = T::slice_from_float_cow(Cow::Borrowed(vector.try_into()?));
+        self.vectors.insert(key, &vector)?;
         self.set_deleted(key, false)?;
         Ok(())
     }
@@ -130,8 +183,9 @@ impl VectorStorage for AppendableMmapVectorStorage {
             // Do not perform preprocessing - vectors should be already processed
             let other_deleted = other.is_deleted_vector(point_id);
             let other_vector = other.get_vector(point_id);
-            let other_vector = other_vector.as_vec_ref().try_into()?;
-            let new_id = self.vectors.push(other_vector)?;
+            let other_vector =
+                T::slice_from_float_cow(Cow::Borrowed(other_vector.as_vec_ref().try_into()?));
+            let new_id = self.vectors.push(&other_vector)?;
             self.set_deleted(new_id, other_deleted)?;
         }
         let end_index = self.vectors.len() as PointOffsetType;
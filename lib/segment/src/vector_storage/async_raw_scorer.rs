@@ -283,6 +283,7 @@ impl<'a> AsyncRawScorerBuilder<'a> {
                     Vector::Sparse(_sparse_vector) => Err(OperationError::service_error(
                         "sparse vectors are not supported for async scorer",
                     )), // TODO(sparse) add support?
+                    Vector::MultiDense(_) => Err(OperationError::WrongMulti),
                 }
             }
             QueryVector::Recommend(reco_query) => {
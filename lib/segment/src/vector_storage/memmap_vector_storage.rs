@@ -85,6 +85,51 @@ impl MemmapVectorStorage {
             .map(|x| x.has_async_reader())
             .unwrap_or(false)
     }
+
+    /// Append `count` vectors, which are marked as deleted right away
+    ///
+    /// Used for points, which don't have this vector, to keep the offsets aligned with the
+    /// other storages of the segment.
+    pub fn append_deleted(
+        &mut self,
+        count: usize,
+        stopped: &AtomicBool,
+    ) -> OperationResult<Range<PointOffsetType>> {
+        let dim = self.vector_dim();
+        let start_index = self.mmap_store.as_ref().unwrap().num_vectors as PointOffsetType;
+        let end_index = start_index + count as PointOffsetType;
+
+        let with_async_io = self
+            .mmap_store
+            .take()
+            .map(|x| x.has_async_reader())
+            .unwrap_or(get_async_scorer());
+
+        let placeholder: DenseVector = vec![1.0; dim];
+        let raw_bites = mmap_ops::transmute_to_u8_slice(&placeholder);
+        let mut vectors_file = open_append(&self.vectors_path)?;
+        for _ in 0..count {
+            check_process_stopped(stopped)?;
+            vectors_file.write_all(raw_bites)?;
+        }
+        vectors_file.flush()?;
+        drop(vectors_file);
+
+        self.mmap_store.replace(MmapVectors::open(
+            &self.vectors_path,
+            &self.deleted_path,
+            dim,
+            with_async_io,
+        )?);
+
+        let store = self.mmap_store.as_mut().unwrap();
+        for id in start_index..end_index {
+            check_process_stopped(stopped)?;
+            store.delete(id);
+        }
+
+        Ok(start_index..end_index)
+    }
 }
 
 impl DenseVectorStorage for MemmapVectorStorage {
@@ -184,6 +229,12 @@ impl VectorStorage for MemmapVectorStorage {
         vec![self.vectors_path.clone(), self.deleted_path.clone()]
     }
 
+    /// Vectors are only written by `update_from` while the storage is built,
+    /// deletion flags are changed afterwards
+    fn immutable_files(&self) -> Vec<PathBuf> {
+        vec![self.vectors_path.clone()]
+    }
+
     fn delete_vector(&mut self, key: PointOffsetType) -> OperationResult<bool> {
         Ok(self.mmap_store.as_mut().unwrap().delete(key))
     }
@@ -10,6 +10,7 @@ mod mmap_vectors;
 pub mod quantized;
 pub mod raw_scorer;
 pub mod simple_dense_vector_storage;
+pub mod simple_multi_dense_vector_storage;
 mod vector_storage_base;
 
 #[cfg(test)]
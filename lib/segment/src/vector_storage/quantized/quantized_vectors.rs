@@ -117,6 +117,17 @@ impl QuantizedVectors {
                 Self::create_impl(v.as_ref(), quantization_config, path, max_threads, stopped)
             }
             VectorStorageEnum::SparseSimple(_) => Err(OperationError::WrongSparse),
+            VectorStorageEnum::MultiDenseSimple(_) => Err(OperationError::WrongMulti),
+            VectorStorageEnum::DenseByteSimple(_) | VectorStorageEnum::AppendableMemmapByte(_) => {
+                Err(OperationError::service_error(
+                    "vectors of the uint8 datatype can't be quantized",
+                ))
+            }
+            VectorStorageEnum::DenseHalfSimple(_) | VectorStorageEnum::AppendableMemmapHalf(_) => {
+                Err(OperationError::service_error(
+                    "vectors of the float16 datatype can't be quantized",
+                ))
+            }
         }
     }
 
@@ -0,0 +1,62 @@
+use std::marker::PhantomData;
+
+use common::types::{PointOffsetType, ScoreType};
+
+use crate::data_types::vectors::VectorElementTypeByte;
+use crate::spaces::metric::Metric;
+use crate::vector_storage::query::Query;
+use crate::vector_storage::query_scorer::QueryScorer;
+use crate::vector_storage::DenseByteVectorStorage;
+
+pub struct ByteCustomQueryScorer<
+    'a,
+    TMetric: Metric,
+    TVectorStorage: DenseByteVectorStorage,
+    TQuery: Query<Vec<VectorElementTypeByte>>,
+> {
+    vector_storage: &'a TVectorStorage,
+    query: TQuery,
+    metric: PhantomData<TMetric>,
+}
+
+impl<
+        'a,
+        TMetric: Metric,
+        TVectorStorage: DenseByteVectorStorage,
+        TQuery: Query<Vec<VectorElementTypeByte>>,
+    > ByteCustomQueryScorer<'a, TMetric, TVectorStorage, TQuery>
+{
+    /// Vectors of the query are expected to be converted to bytes already
+    pub fn new(query: TQuery, vector_storage: &'a TVectorStorage) -> Self {
+        Self {
+            query,
+            vector_storage,
+            metric: PhantomData,
+        }
+    }
+}
+
+impl<
+        'a,
+        TMetric: Metric,
+        TVectorStorage: DenseByteVectorStorage,
+        TQuery: Query<Vec<VectorElementTypeByte>>,
+    > QueryScorer<[VectorElementTypeByte]>
+    for ByteCustomQueryScorer<'a, TMetric, TVectorStorage, TQuery>
+{
+    #[inline]
+    fn score_stored(&self, idx: PointOffsetType) -> ScoreType {
+        let stored = self.vector_storage.get_dense_bytes(idx);
+        self.score(stored)
+    }
+
+    #[inline]
+    fn score(&self, against: &[VectorElementTypeByte]) -> ScoreType {
+        self.query
+            .score_by(|example| TMetric::similarity_bytes(example, against))
+    }
+
+    fn score_internal(&self, _point_a: PointOffsetType, _point_b: PointOffsetType) -> ScoreType {
+        unimplemented!("Custom scorer can compare against multiple vectors, not just one")
+    }
+}
@@ -0,0 +1,47 @@
+use std::marker::PhantomData;
+
+use common::types::{PointOffsetType, ScoreType};
+
+use crate::data_types::vectors::{dense_vector_to_bytes, DenseVector, VectorElementTypeByte};
+use crate::spaces::metric::Metric;
+use crate::vector_storage::query_scorer::QueryScorer;
+use crate::vector_storage::DenseByteVectorStorage;
+
+/// Scorer of the vectors of the `uint8` datatype, query is converted to bytes as well
+pub struct ByteMetricQueryScorer<'a, TMetric: Metric, TVectorStorage: DenseByteVectorStorage> {
+    vector_storage: &'a TVectorStorage,
+    query: Vec<VectorElementTypeByte>,
+    metric: PhantomData<TMetric>,
+}
+
+impl<'a, TMetric: Metric, TVectorStorage: DenseByteVectorStorage>
+    ByteMetricQueryScorer<'a, TMetric, TVectorStorage>
+{
+    pub fn new(query: DenseVector, vector_storage: &'a TVectorStorage) -> Self {
+        Self {
+            query: dense_vector_to_bytes(&query),
+            vector_storage,
+            metric: PhantomData,
+        }
+    }
+}
+
+impl<'a, TMetric: Metric, TVectorStorage: DenseByteVectorStorage>
+    QueryScorer<[VectorElementTypeByte]> for ByteMetricQueryScorer<'a, TMetric, TVectorStorage>
+{
+    #[inline]
+    fn score_stored(&self, idx: PointOffsetType) -> ScoreType {
+        TMetric::similarity_bytes(&self.query, self.vector_storage.get_dense_bytes(idx))
+    }
+
+    #[inline]
+    fn score(&self, v2: &[VectorElementTypeByte]) -> ScoreType {
+        TMetric::similarity_bytes(&self.query, v2)
+    }
+
+    fn score_internal(&self, point_a: PointOffsetType, point_b: PointOffsetType) -> ScoreType {
+        let v1 = self.vector_storage.get_dense_bytes(point_a);
+        let v2 = self.vector_storage.get_dense_bytes(point_b);
+        TMetric::similarity_bytes(v1, v2)
+    }
+}
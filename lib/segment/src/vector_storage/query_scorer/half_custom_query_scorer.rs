@@ -0,0 +1,62 @@
+use std::marker::PhantomData;
+
+use common::types::{PointOffsetType, ScoreType};
+
+use crate::data_types::vectors::VectorElementTypeHalf;
+use crate::spaces::metric::Metric;
+use crate::vector_storage::query::Query;
+use crate::vector_storage::query_scorer::QueryScorer;
+use crate::vector_storage::DenseHalfVectorStorage;
+
+pub struct HalfCustomQueryScorer<
+    'a,
+    TMetric: Metric,
+    TVectorStorage: DenseHalfVectorStorage,
+    TQuery: Query<Vec<VectorElementTypeHalf>>,
+> {
+    vector_storage: &'a TVectorStorage,
+    query: TQuery,
+    metric: PhantomData<TMetric>,
+}
+
+impl<
+        'a,
+        TMetric: Metric,
+        TVectorStorage: DenseHalfVectorStorage,
+        TQuery: Query<Vec<VectorElementTypeHalf>>,
+    > HalfCustomQueryScorer<'a, TMetric, TVectorStorage, TQuery>
+{
+    /// Vectors of the query are expected to be preprocessed and converted to `float16` already
+    pub fn new(query: TQuery, vector_storage: &'a TVectorStorage) -> Self {
+        Self {
+            query,
+            vector_storage,
+            metric: PhantomData,
+        }
+    }
+}
+
+impl<
+        'a,
+        TMetric: Metric,
+        TVectorStorage: DenseHalfVectorStorage,
+        TQuery: Query<Vec<VectorElementTypeHalf>>,
+    > QueryScorer<[VectorElementTypeHalf]>
+    for HalfCustomQueryScorer<'a, TMetric, TVectorStorage, TQuery>
+{
+    #[inline]
+    fn score_stored(&self, idx: PointOffsetType) -> ScoreType {
+        let stored = self.vector_storage.get_dense_half(idx);
+        self.score(stored)
+    }
+
+    #[inline]
+    fn score(&self, against: &[VectorElementTypeHalf]) -> ScoreType {
+        self.query
+            .score_by(|example| TMetric::similarity_half(example, against))
+    }
+
+    fn score_internal(&self, _point_a: PointOffsetType, _point_b: PointOffsetType) -> ScoreType {
+        unimplemented!("Custom scorer can compare against multiple vectors, not just one")
+    }
+}
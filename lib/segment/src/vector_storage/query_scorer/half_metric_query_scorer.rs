@@ -0,0 +1,48 @@
+use std::marker::PhantomData;
+
+use common::types::{PointOffsetType, ScoreType};
+
+use crate::data_types::vectors::{dense_vector_to_half, DenseVector, VectorElementTypeHalf};
+use crate::spaces::metric::Metric;
+use crate::vector_storage::query_scorer::QueryScorer;
+use crate::vector_storage::DenseHalfVectorStorage;
+
+/// Scorer of the vectors of the `float16` datatype, query is preprocessed and converted to
+/// `float16` as well
+pub struct HalfMetricQueryScorer<'a, TMetric: Metric, TVectorStorage: DenseHalfVectorStorage> {
+    vector_storage: &'a TVectorStorage,
+    query: Vec<VectorElementTypeHalf>,
+    metric: PhantomData<TMetric>,
+}
+
+impl<'a, TMetric: Metric, TVectorStorage: DenseHalfVectorStorage>
+    HalfMetricQueryScorer<'a, TMetric, TVectorStorage>
+{
+    pub fn new(query: DenseVector, vector_storage: &'a TVectorStorage) -> Self {
+        Self {
+            query: dense_vector_to_half(&TMetric::preprocess(query)),
+            vector_storage,
+            metric: PhantomData,
+        }
+    }
+}
+
+impl<'a, TMetric: Metric, TVectorStorage: DenseHalfVectorStorage>
+    QueryScorer<[VectorElementTypeHalf]> for HalfMetricQueryScorer<'a, TMetric, TVectorStorage>
+{
+    #[inline]
+    fn score_stored(&self, idx: PointOffsetType) -> ScoreType {
+        TMetric::similarity_half(&self.query, self.vector_storage.get_dense_half(idx))
+    }
+
+    #[inline]
+    fn score(&self, v2: &[VectorElementTypeHalf]) -> ScoreType {
+        TMetric::similarity_half(&self.query, v2)
+    }
+
+    fn score_internal(&self, point_a: PointOffsetType, point_b: PointOffsetType) -> ScoreType {
+        let v1 = self.vector_storage.get_dense_half(point_a);
+        let v2 = self.vector_storage.get_dense_half(point_b);
+        TMetric::similarity_half(v1, v2)
+    }
+}
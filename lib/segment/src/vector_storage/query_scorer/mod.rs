@@ -1,7 +1,13 @@
 use common::types::{PointOffsetType, ScoreType};
 
+pub mod byte_custom_query_scorer;
+pub mod byte_metric_query_scorer;
 pub mod custom_query_scorer;
+pub mod half_custom_query_scorer;
+pub mod half_metric_query_scorer;
 pub mod metric_query_scorer;
+pub mod multi_custom_query_scorer;
+pub mod multi_metric_query_scorer;
 pub mod sparse_custom_query_scorer;
 
 pub trait QueryScorer<TVector: ?Sized> {
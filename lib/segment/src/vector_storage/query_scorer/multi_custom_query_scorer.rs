@@ -0,0 +1,66 @@
+use std::marker::PhantomData;
+
+use common::types::{PointOffsetType, ScoreType};
+
+use super::multi_metric_query_scorer::score_multi;
+use crate::data_types::vectors::{DenseVector, MultiDenseVector};
+use crate::spaces::metric::Metric;
+use crate::vector_storage::query::{Query, TransformInto};
+use crate::vector_storage::query_scorer::QueryScorer;
+use crate::vector_storage::MultiDenseVectorStorage;
+
+pub struct MultiCustomQueryScorer<
+    'a,
+    TMetric: Metric,
+    TVectorStorage: MultiDenseVectorStorage,
+    TQuery: Query<MultiDenseVector>,
+> {
+    vector_storage: &'a TVectorStorage,
+    query: TQuery,
+    metric: PhantomData<TMetric>,
+}
+
+impl<
+        'a,
+        TMetric: Metric,
+        TVectorStorage: MultiDenseVectorStorage,
+        TQuery: Query<MultiDenseVector> + TransformInto<TQuery, MultiDenseVector, MultiDenseVector>,
+    > MultiCustomQueryScorer<'a, TMetric, TVectorStorage, TQuery>
+{
+    pub fn new(query: TQuery, vector_storage: &'a TVectorStorage) -> Self {
+        let query = query
+            .transform(|vector| Ok(vector.into_iter().map(TMetric::preprocess).collect()))
+            .unwrap();
+
+        Self {
+            query,
+            vector_storage,
+            metric: PhantomData,
+        }
+    }
+}
+
+impl<
+        'a,
+        TMetric: Metric,
+        TVectorStorage: MultiDenseVectorStorage,
+        TQuery: Query<MultiDenseVector>,
+    > QueryScorer<[DenseVector]> for MultiCustomQueryScorer<'a, TMetric, TVectorStorage, TQuery>
+{
+    #[inline]
+    fn score_stored(&self, idx: PointOffsetType) -> ScoreType {
+        let stored = self.vector_storage.get_multi(idx);
+        self.score(stored)
+    }
+
+    #[inline]
+    fn score(&self, against: &[DenseVector]) -> ScoreType {
+        let config = self.vector_storage.multi_vector_config();
+        self.query
+            .score_by(|example| score_multi::<TMetric>(config, example, against))
+    }
+
+    fn score_internal(&self, _point_a: PointOffsetType, _point_b: PointOffsetType) -> ScoreType {
+        unimplemented!("Custom scorer can compare against multiple vectors, not just one")
+    }
+}
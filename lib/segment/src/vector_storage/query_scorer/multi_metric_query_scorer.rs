@@ -0,0 +1,86 @@
+use std::marker::PhantomData;
+
+use common::types::{PointOffsetType, ScoreType};
+
+use crate::data_types::vectors::{DenseVector, MultiDenseVector};
+use crate::spaces::metric::Metric;
+use crate::types::{MultiVectorComparator, MultiVectorConfig};
+use crate::vector_storage::query_scorer::QueryScorer;
+use crate::vector_storage::MultiDenseVectorStorage;
+
+pub struct MultiMetricQueryScorer<'a, TMetric: Metric, TVectorStorage: MultiDenseVectorStorage> {
+    vector_storage: &'a TVectorStorage,
+    query: MultiDenseVector,
+    metric: PhantomData<TMetric>,
+}
+
+impl<'a, TMetric: Metric, TVectorStorage: MultiDenseVectorStorage>
+    MultiMetricQueryScorer<'a, TMetric, TVectorStorage>
+{
+    pub fn new(query: MultiDenseVector, vector_storage: &'a TVectorStorage) -> Self {
+        Self {
+            query: query.into_iter().map(TMetric::preprocess).collect(),
+            vector_storage,
+            metric: PhantomData,
+        }
+    }
+}
+
+/// Similarity of two multi-vectors, according to the comparator
+pub fn score_multi<TMetric: Metric>(
+    multi_vector_config: &MultiVectorConfig,
+    query: &[DenseVector],
+    against: &[DenseVector],
+) -> ScoreType {
+    match multi_vector_config.comparator {
+        MultiVectorComparator::MaxSim => query
+            .iter()
+            .map(|query| {
+                against
+                    .iter()
+                    .map(|vector| TMetric::similarity(query, vector))
+                    .fold(ScoreType::NEG_INFINITY, ScoreType::max)
+            })
+            .sum(),
+    }
+}
+
+impl<'a, TMetric: Metric, TVectorStorage: MultiDenseVectorStorage> QueryScorer<[DenseVector]>
+    for MultiMetricQueryScorer<'a, TMetric, TVectorStorage>
+{
+    #[inline]
+    fn score_stored(&self, idx: PointOffsetType) -> ScoreType {
+        self.score(self.vector_storage.get_multi(idx))
+    }
+
+    #[inline]
+    fn score(&self, v2: &[DenseVector]) -> ScoreType {
+        score_multi::<TMetric>(self.vector_storage.multi_vector_config(), &self.query, v2)
+    }
+
+    fn score_internal(&self, point_a: PointOffsetType, point_b: PointOffsetType) -> ScoreType {
+        let v1 = self.vector_storage.get_multi(point_a);
+        let v2 = self.vector_storage.get_multi(point_b);
+        score_multi::<TMetric>(self.vector_storage.multi_vector_config(), v1, v2)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::spaces::simple::DotProductMetric;
+
+    #[test]
+    fn test_score_max_sim() {
+        let config = MultiVectorConfig::default();
+        let query = vec![vec![1.0, 0.0], vec![0.0, 1.0]];
+        let against = vec![vec![0.5, 0.1], vec![0.2, 0.3], vec![-1.0, 0.0]];
+        // Best match of the first query vector is 0.5, of the second one is 0.3
+        let score = score_multi::<DotProductMetric>(&config, &query, &against);
+        assert!((score - 0.8).abs() < 1e-6);
+
+        let single = vec![vec![0.5, 0.1]];
+        let score = score_multi::<DotProductMetric>(&config, &query, &single);
+        assert!((score - 0.6).abs() < 1e-6);
+    }
+}
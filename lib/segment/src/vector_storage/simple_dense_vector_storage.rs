@@ -1,3 +1,4 @@
+use std::borrow::Cow;
 use std::mem::size_of;
 use std::ops::Range;
 use std::sync::atomic::AtomicBool;
@@ -13,22 +14,29 @@ use serde::{Deserialize, Serialize};
 
 use super::chunked_vectors::ChunkedVectors;
 use super::vector_storage_base::VectorStorage;
-use super::{DenseVectorStorage, VectorStorageEnum};
+use super::{
+    DenseByteVectorStorage, DenseHalfVectorStorage, DenseVectorStorage, VectorStorageEnum,
+};
 use crate::common::operation_error::{check_process_stopped, OperationError, OperationResult};
 use crate::common::rocksdb_wrapper::DatabaseColumnWrapper;
 use crate::common::Flusher;
 use crate::data_types::named_vectors::CowVector;
-use crate::data_types::vectors::{VectorElementType, VectorRef};
+use crate::data_types::primitive::PrimitiveVectorElement;
+use crate::data_types::vectors::{
+    VectorElementType, VectorElementTypeByte, VectorElementTypeHalf, VectorRef,
+};
 use crate::types::Distance;
 use crate::vector_storage::bitvec::bitvec_set_deleted;
 
 /// In-memory vector storage with on-update persistence using `store`
-pub struct SimpleDenseVectorStorage {
+///
+/// Vectors are stored with the `T` element type and are converted to and from float vectors.
+pub struct SimpleDenseVectorStorage<T: PrimitiveVectorElement = VectorElementType> {
     dim: usize,
     distance: Distance,
-    vectors: ChunkedVectors<VectorElementType>,
+    vectors: ChunkedVectors<T>,
     db_wrapper: DatabaseColumnWrapper,
-    update_buffer: StoredRecord,
+    update_buffer: StoredRecord<T>,
     /// BitVec for deleted flags. Grows dynamically upto last set flag.
     deleted: BitVec,
     /// Current number of deleted vectors.
@@ -36,9 +44,10 @@ pub struct SimpleDenseVectorStorage {
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone)]
-struct StoredRecord {
+#[serde(bound = "")]
+struct StoredRecord<T: PrimitiveVectorElement> {
     pub deleted: bool,
-    pub vector: Vec<VectorElementType>,
+    pub vector: Vec<T>,
 }
 
 pub fn open_simple_vector_storage(
@@ -47,6 +56,45 @@ pub fn open_simple_vector_storage(
     dim: usize,
     distance: Distance,
 ) -> OperationResult<Arc<AtomicRefCell<VectorStorageEnum>>> {
+    let storage = open_simple_vector_storage_impl(database, database_column_name, dim, distance)?;
+
+    Ok(Arc::new(AtomicRefCell::new(
+        VectorStorageEnum::DenseSimple(storage),
+    )))
+}
+
+pub fn open_simple_dense_byte_vector_storage(
+    database: Arc<RwLock<DB>>,
+    database_column_name: &str,
+    dim: usize,
+    distance: Distance,
+) -> OperationResult<Arc<AtomicRefCell<VectorStorageEnum>>> {
+    let storage = open_simple_vector_storage_impl(database, database_column_name, dim, distance)?;
+
+    Ok(Arc::new(AtomicRefCell::new(
+        VectorStorageEnum::DenseByteSimple(storage),
+    )))
+}
+
+pub fn open_simple_dense_half_vector_storage(
+    database: Arc<RwLock<DB>>,
+    database_column_name: &str,
+    dim: usize,
+    distance: Distance,
+) -> OperationResult<Arc<AtomicRefCell<VectorStorageEnum>>> {
+    let storage = open_simple_vector_storage_impl(database, database_column_name, dim, distance)?;
+
+    Ok(Arc::new(AtomicRefCell::new(
+        VectorStorageEnum::DenseHalfSimple(storage),
+    )))
+}
+
+fn open_simple_vector_storage_impl<T: PrimitiveVectorElement>(
+    database: Arc<RwLock<DB>>,
+    database_column_name: &str,
+    dim: usize,
+    distance: Distance,
+) -> OperationResult<SimpleDenseVectorStorage<T>> {
     let mut vectors = ChunkedVectors::new(dim);
     let (mut deleted, mut deleted_count) = (BitVec::new(), 0);
 
@@ -55,7 +103,7 @@ pub fn open_simple_vector_storage(
     for (key, value) in db_wrapper.lock_db().iter()? {
         let point_id: PointOffsetType = bincode::deserialize(&key)
             .map_err(|_| OperationError::service_error("cannot deserialize point id from db"))?;
-        let stored_record: StoredRecord = bincode::deserialize(&value)
+        let stored_record: StoredRecord<T> = bincode::deserialize(&value)
             .map_err(|_| OperationError::service_error("cannot deserialize record from db"))?;
 
         // Propagate deleted flag
@@ -69,26 +117,24 @@ pub fn open_simple_vector_storage(
     debug!("Segment vectors: {}", vectors.len());
     debug!(
         "Estimated segment size {} MB",
-        vectors.len() * dim * size_of::<VectorElementType>() / 1024 / 1024
+        vectors.len() * dim * size_of::<T>() / 1024 / 1024
     );
 
-    Ok(Arc::new(AtomicRefCell::new(
-        VectorStorageEnum::DenseSimple(SimpleDenseVectorStorage {
-            dim,
-            distance,
-            vectors,
-            db_wrapper,
-            update_buffer: StoredRecord {
-                deleted: false,
-                vector: vec![0.; dim],
-            },
-            deleted,
-            deleted_count,
-        }),
-    )))
+    Ok(SimpleDenseVectorStorage {
+        dim,
+        distance,
+        vectors,
+        db_wrapper,
+        update_buffer: StoredRecord {
+            deleted: false,
+            vector: vec![T::default(); dim],
+        },
+        deleted,
+        deleted_count,
+    })
 }
 
-impl SimpleDenseVectorStorage {
+impl<T: PrimitiveVectorElement> SimpleDenseVectorStorage<T> {
     /// Set deleted flag for given key. Returns previous deleted state.
     #[inline]
     fn set_deleted(&mut self, key: PointOffsetType, deleted: bool) -> bool {
@@ -110,7 +156,7 @@ impl SimpleDenseVectorStorage {
         &mut self,
         key: PointOffsetType,
         deleted: bool,
-        vector: Option<&[VectorElementType]>,
+        vector: Option<&[T]>,
     ) -> OperationResult<()> {
         // Write vector state to buffer record
         let record = &mut self.update_buffer;
@@ -129,13 +175,25 @@ impl SimpleDenseVectorStorage {
     }
 }
 
-impl DenseVectorStorage for SimpleDenseVectorStorage {
+impl DenseVectorStorage for SimpleDenseVectorStorage<VectorElementType> {
     fn get_dense(&self, key: PointOffsetType) -> &[VectorElementType] {
         self.vectors.get(key)
     }
 }
 
-impl VectorStorage for SimpleDenseVectorStorage {
+impl DenseByteVectorStorage for SimpleDenseVectorStorage<VectorElementTypeByte> {
+    fn get_dense_bytes(&self, key: PointOffsetType) -> &[VectorElementTypeByte] {
+        self.vectors.get(key)
+    }
+}
+
+impl DenseHalfVectorStorage for SimpleDenseVectorStorage<VectorElementTypeHalf> {
+    fn get_dense_half(&self, key: PointOffsetType) -> &[VectorElementTypeHalf] {
+        self.vectors.get(key)
+    }
+}
+
+impl<T: PrimitiveVectorElement> VectorStorage for SimpleDenseVectorStorage<T> {
     fn vector_dim(&self) -> usize {
         self.dim
     }
@@ -153,14 +211,15 @@ impl VectorStorage for SimpleDenseVectorStorage {
     }
 
     fn get_vector(&self, key: PointOffsetType) -> CowVector {
-        self.get_dense(key).into()
+        let vector = self.vectors.get(key);
+        CowVector::Dense(T::slice_to_float_cow(Cow::Borrowed(vector)))
     }
 
     fn insert_vector(&mut self, key: PointOffsetType, vector: VectorRef) -> OperationResult<()> {
-        let vector = vector.try_into()?;
-        self.vectors.insert(key, vector)?;
+        let vector = T::slice_from_float_cow(Cow::Borrowed(vector.try_into()?));
+        self.vectors.insert(key, &vector)?;
         self.set_deleted(key, false);
-        self.update_stored(key, false, Some(vector))?;
+        self.update_stored(key, false, Some(&vector))?;
         Ok(())
     }
 
@@ -175,11 +234,12 @@ impl VectorStorage for SimpleDenseVectorStorage {
             check_process_stopped(stopped)?;
             // Do not perform preprocessing - vectors should be already processed
             let other_vector = other.get_vector(point_id);
-            let other_vector = other_vector.as_vec_ref().try_into()?;
+            let other_vector =
+                T::slice_from_float_cow(Cow::Borrowed(other_vector.as_vec_ref().try_into()?));
             let other_deleted = other.is_deleted_vector(point_id);
-            let new_id = self.vectors.push(other_vector)?;
+            let new_id = self.vectors.push(&other_vector)?;
             self.set_deleted(new_id, other_deleted);
-            self.update_stored(new_id, other_deleted, Some(other_vector))?;
+            self.update_stored(new_id, other_deleted, Some(&other_vector))?;
         }
         let end_index = self.vectors.len() as PointOffsetType;
         Ok(start_index..end_index)
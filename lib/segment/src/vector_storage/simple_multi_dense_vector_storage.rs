@@ -0,0 +1,222 @@
+use std::ops::Range;
+use std::sync::atomic::AtomicBool;
+use std::sync::Arc;
+
+use atomic_refcell::AtomicRefCell;
+use bitvec::prelude::{BitSlice, BitVec};
+use common::types::PointOffsetType;
+use log::debug;
+use parking_lot::RwLock;
+use rocksdb::DB;
+use serde::{Deserialize, Serialize};
+
+use super::{MultiDenseVectorStorage, VectorStorage, VectorStorageEnum};
+use crate::common::operation_error::{check_process_stopped, OperationError, OperationResult};
+use crate::common::rocksdb_wrapper::DatabaseColumnWrapper;
+use crate::common::Flusher;
+use crate::data_types::named_vectors::CowVector;
+use crate::data_types::vectors::{DenseVector, MultiDenseVector, VectorRef};
+use crate::types::{Distance, MultiVectorConfig};
+use crate::vector_storage::bitvec::bitvec_set_deleted;
+
+/// In-memory storage of the multi-vectors with on-update persistence using `store`
+///
+/// Each point has a variable number of vectors of the same dimensionality.
+pub struct SimpleMultiDenseVectorStorage {
+    dim: usize,
+    distance: Distance,
+    multi_vector_config: MultiVectorConfig,
+    vectors: Vec<MultiDenseVector>,
+    db_wrapper: DatabaseColumnWrapper,
+    /// BitVec for deleted flags. Grows dynamically upto last set flag.
+    deleted: BitVec,
+    /// Current number of deleted vectors.
+    deleted_count: usize,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+struct StoredRecord {
+    pub deleted: bool,
+    pub vector: MultiDenseVector,
+}
+
+#[derive(Debug, Serialize)]
+struct StoredRecordRef<'a> {
+    pub deleted: bool,
+    pub vector: &'a [DenseVector],
+}
+
+pub fn open_simple_multi_dense_vector_storage(
+    database: Arc<RwLock<DB>>,
+    database_column_name: &str,
+    dim: usize,
+    distance: Distance,
+    multi_vector_config: MultiVectorConfig,
+) -> OperationResult<Arc<AtomicRefCell<VectorStorageEnum>>> {
+    let mut vectors: Vec<MultiDenseVector> = Vec::new();
+    let (mut deleted, mut deleted_count) = (BitVec::new(), 0);
+
+    let db_wrapper = DatabaseColumnWrapper::new(database, database_column_name);
+
+    for (key, value) in db_wrapper.lock_db().iter()? {
+        let point_id: PointOffsetType = bincode::deserialize(&key)
+            .map_err(|_| OperationError::service_error("cannot deserialize point id from db"))?;
+        let stored_record: StoredRecord = bincode::deserialize(&value)
+            .map_err(|_| OperationError::service_error("cannot deserialize record from db"))?;
+
+        // Propagate deleted flag
+        if stored_record.deleted {
+            bitvec_set_deleted(&mut deleted, point_id, true);
+            deleted_count += 1;
+        }
+        if point_id as usize >= vectors.len() {
+            vectors.resize_with(point_id as usize + 1, Vec::new);
+        }
+        vectors[point_id as usize] = stored_record.vector;
+    }
+
+    debug!("Segment multi-vectors: {}", vectors.len());
+
+    Ok(Arc::new(AtomicRefCell::new(
+        VectorStorageEnum::MultiDenseSimple(SimpleMultiDenseVectorStorage {
+            dim,
+            distance,
+            multi_vector_config,
+            vectors,
+            db_wrapper,
+            deleted,
+            deleted_count,
+        }),
+    )))
+}
+
+impl SimpleMultiDenseVectorStorage {
+    /// Set deleted flag for given key. Returns previous deleted state.
+    #[inline]
+    fn set_deleted(&mut self, key: PointOffsetType, deleted: bool) -> bool {
+        if key as usize >= self.vectors.len() {
+            return false;
+        }
+        let was_deleted = bitvec_set_deleted(&mut self.deleted, key, deleted);
+        if was_deleted != deleted {
+            if !was_deleted {
+                self.deleted_count += 1;
+            } else {
+                self.deleted_count -= 1;
+            }
+        }
+        was_deleted
+    }
+
+    fn update_stored(&mut self, key: PointOffsetType, deleted: bool) -> OperationResult<()> {
+        let record = StoredRecordRef {
+            deleted,
+            vector: &self.vectors[key as usize],
+        };
+
+        // Store updated record
+        self.db_wrapper.put(
+            bincode::serialize(&key).unwrap(),
+            bincode::serialize(&record).unwrap(),
+        )?;
+
+        Ok(())
+    }
+
+    fn set_vector(&mut self, key: PointOffsetType, vector: &[DenseVector]) {
+        if key as usize >= self.vectors.len() {
+            self.vectors.resize_with(key as usize + 1, Vec::new);
+        }
+        self.vectors[key as usize] = vector.to_vec();
+    }
+}
+
+impl MultiDenseVectorStorage for SimpleMultiDenseVectorStorage {
+    fn get_multi(&self, key: PointOffsetType) -> &[DenseVector] {
+        &self.vectors[key as usize]
+    }
+
+    fn multi_vector_config(&self) -> &MultiVectorConfig {
+        &self.multi_vector_config
+    }
+}
+
+impl VectorStorage for SimpleMultiDenseVectorStorage {
+    fn vector_dim(&self) -> usize {
+        self.dim
+    }
+
+    fn distance(&self) -> Distance {
+        self.distance
+    }
+
+    fn is_on_disk(&self) -> bool {
+        false
+    }
+
+    fn total_vector_count(&self) -> usize {
+        self.vectors.len()
+    }
+
+    fn get_vector(&self, key: PointOffsetType) -> CowVector {
+        self.get_multi(key).into()
+    }
+
+    fn insert_vector(&mut self, key: PointOffsetType, vector: VectorRef) -> OperationResult<()> {
+        let vector: &[DenseVector] = vector.try_into()?;
+        self.set_vector(key, vector);
+        self.set_deleted(key, false);
+        self.update_stored(key, false)?;
+        Ok(())
+    }
+
+    fn update_from(
+        &mut self,
+        other: &VectorStorageEnum,
+        other_ids: &mut dyn Iterator<Item = PointOffsetType>,
+        stopped: &AtomicBool,
+    ) -> OperationResult<Range<PointOffsetType>> {
+        let start_index = self.vectors.len() as PointOffsetType;
+        for point_id in other_ids {
+            check_process_stopped(stopped)?;
+            // Do not perform preprocessing - vectors should be already processed
+            let other_vector = other.get_vector(point_id);
+            let other_vector = other_vector.as_vec_ref().try_into()?;
+            let other_deleted = other.is_deleted_vector(point_id);
+            let new_id = self.vectors.len() as PointOffsetType;
+            self.set_vector(new_id, other_vector);
+            self.set_deleted(new_id, other_deleted);
+            self.update_stored(new_id, other_deleted)?;
+        }
+        let end_index = self.vectors.len() as PointOffsetType;
+        Ok(start_index..end_index)
+    }
+
+    fn flusher(&self) -> Flusher {
+        self.db_wrapper.flusher()
+    }
+
+    fn files(&self) -> Vec<std::path::PathBuf> {
+        vec![]
+    }
+
+    fn delete_vector(&mut self, key: PointOffsetType) -> OperationResult<bool> {
+        let is_deleted = !self.set_deleted(key, true);
+        if is_deleted {
+            self.update_stored(key, true)?;
+        }
+        Ok(is_deleted)
+    }
+
+    fn is_deleted_vector(&self, key: PointOffsetType) -> bool {
+        self.deleted.get(key as usize).map(|b| *b).unwrap_or(false)
+    }
+
+    fn deleted_vector_count(&self) -> usize {
+        self.deleted_count
+    }
+
+    fn deleted_vector_bitslice(&self) -> &BitSlice {
+        self.deleted.as_bitslice()
+    }
+}
@@ -8,12 +8,15 @@ use sparse::common::sparse_vector::SparseVector;
 
 use super::memmap_vector_storage::MemmapVectorStorage;
 use super::simple_dense_vector_storage::SimpleDenseVectorStorage;
-use crate::common::operation_error::OperationResult;
+use crate::common::operation_error::{check_process_stopped, OperationResult};
 use crate::common::Flusher;
 use crate::data_types::named_vectors::CowVector;
-use crate::data_types::vectors::{VectorElementType, VectorRef};
-use crate::types::Distance;
+use crate::data_types::vectors::{
+    DenseVector, Vector, VectorElementType, VectorElementTypeByte, VectorElementTypeHalf, VectorRef,
+};
+use crate::types::{Distance, MultiVectorConfig};
 use crate::vector_storage::appendable_mmap_vector_storage::AppendableMmapVectorStorage;
+use crate::vector_storage::simple_multi_dense_vector_storage::SimpleMultiDenseVectorStorage;
 use crate::vector_storage::simple_sparse_vector_storage::SimpleSparseVectorStorage;
 
 /// Trait for vector storage
@@ -65,6 +68,11 @@ pub trait VectorStorage {
 
     fn files(&self) -> Vec<PathBuf>;
 
+    /// Files, which are never changed after the storage is built, so they could be checksummed
+    fn immutable_files(&self) -> Vec<PathBuf> {
+        Vec::new()
+    }
+
     /// Flag the vector by the given key as deleted
     ///
     /// Returns true if the vector was not deleted before and is now deleted
@@ -100,15 +108,80 @@ pub trait DenseVectorStorage: VectorStorage {
     fn get_dense(&self, key: PointOffsetType) -> &[VectorElementType];
 }
 
+/// Storage of the vectors of the `uint8` datatype
+pub trait DenseByteVectorStorage: VectorStorage {
+    fn get_dense_bytes(&self, key: PointOffsetType) -> &[VectorElementTypeByte];
+}
+
+/// Storage of the vectors of the `float16` datatype
+pub trait DenseHalfVectorStorage: VectorStorage {
+    fn get_dense_half(&self, key: PointOffsetType) -> &[VectorElementTypeHalf];
+}
+
 pub trait SparseVectorStorage: VectorStorage {
     fn get_sparse(&self, key: PointOffsetType) -> OperationResult<SparseVector>;
 }
 
+pub trait MultiDenseVectorStorage: VectorStorage {
+    fn get_multi(&self, key: PointOffsetType) -> &[DenseVector];
+
+    fn multi_vector_config(&self) -> &MultiVectorConfig;
+}
+
 pub enum VectorStorageEnum {
     DenseSimple(SimpleDenseVectorStorage),
     Memmap(Box<MemmapVectorStorage>),
     AppendableMemmap(Box<AppendableMmapVectorStorage>),
     SparseSimple(SimpleSparseVectorStorage),
+    MultiDenseSimple(SimpleMultiDenseVectorStorage),
+    DenseByteSimple(SimpleDenseVectorStorage<VectorElementTypeByte>),
+    AppendableMemmapByte(Box<AppendableMmapVectorStorage<VectorElementTypeByte>>),
+    DenseHalfSimple(SimpleDenseVectorStorage<VectorElementTypeHalf>),
+    AppendableMemmapHalf(Box<AppendableMmapVectorStorage<VectorElementTypeHalf>>),
+}
+
+impl VectorStorageEnum {
+    /// Placeholder, which is stored in place of the absent vector of the point
+    ///
+    /// Keeps offsets of the storage aligned with the point offsets, placeholder is expected to be
+    /// marked as deleted right away.
+    pub fn default_vector(&self) -> Vector {
+        match self {
+            VectorStorageEnum::DenseSimple(_)
+            | VectorStorageEnum::Memmap(_)
+            | VectorStorageEnum::AppendableMemmap(_)
+            | VectorStorageEnum::DenseByteSimple(_)
+            | VectorStorageEnum::AppendableMemmapByte(_)
+            | VectorStorageEnum::DenseHalfSimple(_)
+            | VectorStorageEnum::AppendableMemmapHalf(_) => vec![1.0; self.vector_dim()].into(),
+            VectorStorageEnum::SparseSimple(_) => SparseVector::default().into(),
+            VectorStorageEnum::MultiDenseSimple(_) => vec![vec![1.0; self.vector_dim()]].into(),
+        }
+    }
+
+    /// Append `count` placeholders, which are marked as deleted right away
+    ///
+    /// Used for points, which don't have this vector, to keep the offsets aligned with the
+    /// other storages of the segment.
+    pub fn append_deleted(
+        &mut self,
+        count: usize,
+        stopped: &AtomicBool,
+    ) -> OperationResult<Range<PointOffsetType>> {
+        if let VectorStorageEnum::Memmap(storage) = self {
+            return storage.append_deleted(count, stopped);
+        }
+
+        let placeholder = self.default_vector();
+        let start = self.total_vector_count() as PointOffsetType;
+        let end = start + count as PointOffsetType;
+        for key in start..end {
+            check_process_stopped(stopped)?;
+            self.insert_vector(key, placeholder.to_vec_ref())?;
+            self.delete_vector(key)?;
+        }
+        Ok(start..end)
+    }
 }
 
 impl VectorStorage for VectorStorageEnum {
@@ -118,6 +191,11 @@ impl VectorStorage for VectorStorageEnum {
             VectorStorageEnum::Memmap(v) => v.vector_dim(),
             VectorStorageEnum::AppendableMemmap(v) => v.vector_dim(),
             VectorStorageEnum::SparseSimple(v) => v.vector_dim(),
+            VectorStorageEnum::MultiDenseSimple(v) => v.vector_dim(),
+            VectorStorageEnum::DenseByteSimple(v) => v.vector_dim(),
+            VectorStorageEnum::AppendableMemmapByte(v) => v.vector_dim(),
+            VectorStorageEnum::DenseHalfSimple(v) => v.vector_dim(),
+            VectorStorageEnum::AppendableMemmapHalf(v) => v.vector_dim(),
         }
     }
 
@@ -127,6 +205,11 @@ impl VectorStorage for VectorStorageEnum {
             VectorStorageEnum::Memmap(v) => v.distance(),
             VectorStorageEnum::AppendableMemmap(v) => v.distance(),
             VectorStorageEnum::SparseSimple(v) => v.distance(),
+            VectorStorageEnum::MultiDenseSimple(v) => v.distance(),
+            VectorStorageEnum::DenseByteSimple(v) => v.distance(),
+            VectorStorageEnum::AppendableMemmapByte(v) => v.distance(),
+            VectorStorageEnum::DenseHalfSimple(v) => v.distance(),
+            VectorStorageEnum::AppendableMemmapHalf(v) => v.distance(),
         }
     }
 
@@ -136,6 +219,11 @@ impl VectorStorage for VectorStorageEnum {
             VectorStorageEnum::Memmap(v) => v.is_on_disk(),
             VectorStorageEnum::AppendableMemmap(v) => v.is_on_disk(),
             VectorStorageEnum::SparseSimple(v) => v.is_on_disk(),
+            VectorStorageEnum::MultiDenseSimple(v) => v.is_on_disk(),
+            VectorStorageEnum::DenseByteSimple(v) => v.is_on_disk(),
+            VectorStorageEnum::AppendableMemmapByte(v) => v.is_on_disk(),
+            VectorStorageEnum::DenseHalfSimple(v) => v.is_on_disk(),
+            VectorStorageEnum::AppendableMemmapHalf(v) => v.is_on_disk(),
         }
     }
 
@@ -145,6 +233,11 @@ impl VectorStorage for VectorStorageEnum {
             VectorStorageEnum::Memmap(v) => v.total_vector_count(),
             VectorStorageEnum::AppendableMemmap(v) => v.total_vector_count(),
             VectorStorageEnum::SparseSimple(v) => v.total_vector_count(),
+            VectorStorageEnum::MultiDenseSimple(v) => v.total_vector_count(),
+            VectorStorageEnum::DenseByteSimple(v) => v.total_vector_count(),
+            VectorStorageEnum::AppendableMemmapByte(v) => v.total_vector_count(),
+            VectorStorageEnum::DenseHalfSimple(v) => v.total_vector_count(),
+            VectorStorageEnum::AppendableMemmapHalf(v) => v.total_vector_count(),
         }
     }
 
@@ -154,6 +247,11 @@ impl VectorStorage for VectorStorageEnum {
             VectorStorageEnum::Memmap(v) => v.get_vector(key),
             VectorStorageEnum::AppendableMemmap(v) => v.get_vector(key),
             VectorStorageEnum::SparseSimple(v) => v.get_vector(key),
+            VectorStorageEnum::MultiDenseSimple(v) => v.get_vector(key),
+            VectorStorageEnum::DenseByteSimple(v) => v.get_vector(key),
+            VectorStorageEnum::AppendableMemmapByte(v) => v.get_vector(key),
+            VectorStorageEnum::DenseHalfSimple(v) => v.get_vector(key),
+            VectorStorageEnum::AppendableMemmapHalf(v) => v.get_vector(key),
         }
     }
 
@@ -163,6 +261,11 @@ impl VectorStorage for VectorStorageEnum {
             VectorStorageEnum::Memmap(v) => v.get_vector_opt(key),
             VectorStorageEnum::AppendableMemmap(v) => v.get_vector_opt(key),
             VectorStorageEnum::SparseSimple(v) => v.get_vector_opt(key),
+            VectorStorageEnum::MultiDenseSimple(v) => v.get_vector_opt(key),
+            VectorStorageEnum::DenseByteSimple(v) => v.get_vector_opt(key),
+            VectorStorageEnum::AppendableMemmapByte(v) => v.get_vector_opt(key),
+            VectorStorageEnum::DenseHalfSimple(v) => v.get_vector_opt(key),
+            VectorStorageEnum::AppendableMemmapHalf(v) => v.get_vector_opt(key),
         }
     }
 
@@ -172,6 +275,11 @@ impl VectorStorage for VectorStorageEnum {
             VectorStorageEnum::Memmap(v) => v.insert_vector(key, vector),
             VectorStorageEnum::AppendableMemmap(v) => v.insert_vector(key, vector),
             VectorStorageEnum::SparseSimple(v) => v.insert_vector(key, vector),
+            VectorStorageEnum::MultiDenseSimple(v) => v.insert_vector(key, vector),
+            VectorStorageEnum::DenseByteSimple(v) => v.insert_vector(key, vector),
+            VectorStorageEnum::AppendableMemmapByte(v) => v.insert_vector(key, vector),
+            VectorStorageEnum::DenseHalfSimple(v) => v.insert_vector(key, vector),
+            VectorStorageEnum::AppendableMemmapHalf(v) => v.insert_vector(key, vector),
         }
     }
 
@@ -186,6 +294,11 @@ impl VectorStorage for VectorStorageEnum {
             VectorStorageEnum::Memmap(v) => v.update_from(other, other_ids, stopped),
             VectorStorageEnum::AppendableMemmap(v) => v.update_from(other, other_ids, stopped),
             VectorStorageEnum::SparseSimple(v) => v.update_from(other, other_ids, stopped),
+            VectorStorageEnum::MultiDenseSimple(v) => v.update_from(other, other_ids, stopped),
+            VectorStorageEnum::DenseByteSimple(v) => v.update_from(other, other_ids, stopped),
+            VectorStorageEnum::AppendableMemmapByte(v) => v.update_from(other, other_ids, stopped),
+            VectorStorageEnum::DenseHalfSimple(v) => v.update_from(other, other_ids, stopped),
+            VectorStorageEnum::AppendableMemmapHalf(v) => v.update_from(other, other_ids, stopped),
         }
     }
 
@@ -195,6 +308,11 @@ impl VectorStorage for VectorStorageEnum {
             VectorStorageEnum::Memmap(v) => v.flusher(),
             VectorStorageEnum::AppendableMemmap(v) => v.flusher(),
             VectorStorageEnum::SparseSimple(v) => v.flusher(),
+            VectorStorageEnum::MultiDenseSimple(v) => v.flusher(),
+            VectorStorageEnum::DenseByteSimple(v) => v.flusher(),
+            VectorStorageEnum::AppendableMemmapByte(v) => v.flusher(),
+            VectorStorageEnum::DenseHalfSimple(v) => v.flusher(),
+            VectorStorageEnum::AppendableMemmapHalf(v) => v.flusher(),
         }
     }
 
@@ -204,6 +322,25 @@ impl VectorStorage for VectorStorageEnum {
             VectorStorageEnum::Memmap(v) => v.files(),
             VectorStorageEnum::AppendableMemmap(v) => v.files(),
             VectorStorageEnum::SparseSimple(v) => v.files(),
+            VectorStorageEnum::MultiDenseSimple(v) => v.files(),
+            VectorStorageEnum::DenseByteSimple(v) => v.files(),
+            VectorStorageEnum::AppendableMemmapByte(v) => v.files(),
+            VectorStorageEnum::DenseHalfSimple(v) => v.files(),
+            VectorStorageEnum::AppendableMemmapHalf(v) => v.files(),
+        }
+    }
+
+    fn immutable_files(&self) -> Vec<PathBuf> {
+        match self {
+            VectorStorageEnum::DenseSimple(v) => v.immutable_files(),
+            VectorStorageEnum::Memmap(v) => v.immutable_files(),
+            VectorStorageEnum::AppendableMemmap(v) => v.immutable_files(),
+            VectorStorageEnum::SparseSimple(v) => v.immutable_files(),
+            VectorStorageEnum::MultiDenseSimple(v) => v.immutable_files(),
+            VectorStorageEnum::DenseByteSimple(v) => v.immutable_files(),
+            VectorStorageEnum::AppendableMemmapByte(v) => v.immutable_files(),
+            VectorStorageEnum::DenseHalfSimple(v) => v.immutable_files(),
+            VectorStorageEnum::AppendableMemmapHalf(v) => v.immutable_files(),
         }
     }
 
@@ -213,6 +350,11 @@ impl VectorStorage for VectorStorageEnum {
             VectorStorageEnum::Memmap(v) => v.delete_vector(key),
             VectorStorageEnum::AppendableMemmap(v) => v.delete_vector(key),
             VectorStorageEnum::SparseSimple(v) => v.delete_vector(key),
+            VectorStorageEnum::MultiDenseSimple(v) => v.delete_vector(key),
+            VectorStorageEnum::DenseByteSimple(v) => v.delete_vector(key),
+            VectorStorageEnum::AppendableMemmapByte(v) => v.delete_vector(key),
+            VectorStorageEnum::DenseHalfSimple(v) => v.delete_vector(key),
+            VectorStorageEnum::AppendableMemmapHalf(v) => v.delete_vector(key),
         }
     }
 
@@ -222,6 +364,11 @@ impl VectorStorage for VectorStorageEnum {
             VectorStorageEnum::Memmap(v) => v.is_deleted_vector(key),
             VectorStorageEnum::AppendableMemmap(v) => v.is_deleted_vector(key),
             VectorStorageEnum::SparseSimple(v) => v.is_deleted_vector(key),
+            VectorStorageEnum::MultiDenseSimple(v) => v.is_deleted_vector(key),
+            VectorStorageEnum::DenseByteSimple(v) => v.is_deleted_vector(key),
+            VectorStorageEnum::AppendableMemmapByte(v) => v.is_deleted_vector(key),
+            VectorStorageEnum::DenseHalfSimple(v) => v.is_deleted_vector(key),
+            VectorStorageEnum::AppendableMemmapHalf(v) => v.is_deleted_vector(key),
         }
     }
 
@@ -231,6 +378,11 @@ impl VectorStorage for VectorStorageEnum {
             VectorStorageEnum::Memmap(v) => v.deleted_vector_count(),
             VectorStorageEnum::AppendableMemmap(v) => v.deleted_vector_count(),
             VectorStorageEnum::SparseSimple(v) => v.deleted_vector_count(),
+            VectorStorageEnum::MultiDenseSimple(v) => v.deleted_vector_count(),
+            VectorStorageEnum::DenseByteSimple(v) => v.deleted_vector_count(),
+            VectorStorageEnum::AppendableMemmapByte(v) => v.deleted_vector_count(),
+            VectorStorageEnum::DenseHalfSimple(v) => v.deleted_vector_count(),
+            VectorStorageEnum::AppendableMemmapHalf(v) => v.deleted_vector_count(),
         }
     }
 
@@ -240,6 +392,11 @@ impl VectorStorage for VectorStorageEnum {
             VectorStorageEnum::Memmap(v) => v.deleted_vector_bitslice(),
             VectorStorageEnum::AppendableMemmap(v) => v.deleted_vector_bitslice(),
             VectorStorageEnum::SparseSimple(v) => v.deleted_vector_bitslice(),
+            VectorStorageEnum::MultiDenseSimple(v) => v.deleted_vector_bitslice(),
+            VectorStorageEnum::DenseByteSimple(v) => v.deleted_vector_bitslice(),
+            VectorStorageEnum::AppendableMemmapByte(v) => v.deleted_vector_bitslice(),
+            VectorStorageEnum::DenseHalfSimple(v) => v.deleted_vector_bitslice(),
+            VectorStorageEnum::AppendableMemmapHalf(v) => v.deleted_vector_bitslice(),
         }
     }
 }
@@ -0,0 +1,163 @@
+use std::collections::{HashMap, HashSet};
+use std::sync::atomic::AtomicBool;
+
+use rand::{thread_rng, Rng};
+use segment::data_types::vectors::{only_default_vector, DenseVector, DEFAULT_VECTOR_NAME};
+use segment::entry::entry_point::SegmentEntry;
+use segment::index::hnsw_index::graph_links::GraphLinksRam;
+use segment::index::hnsw_index::hnsw::HNSWIndex;
+use segment::index::VectorIndex;
+use segment::segment_constructor::build_segment;
+use segment::spaces::metric::Metric;
+use segment::spaces::simple::CosineMetric;
+use segment::types::{
+    Distance, HnswConfig, Indexes, SearchParams, SegmentConfig, SeqNumberType, VectorDataConfig,
+    VectorStorageDatatype, VectorStorageType,
+};
+use segment::vector_storage::VectorStorage;
+use tempfile::Builder;
+
+fn random_byte_vector<R: Rng + ?Sized>(rnd: &mut R, dim: usize) -> DenseVector {
+    (0..dim).map(|_| f32::from(rnd.gen::<u8>())).collect()
+}
+
+fn check_byte_vectors(storage_type: VectorStorageType) {
+    let stopped = AtomicBool::new(false);
+
+    let dim = 40;
+    let num_vectors: u64 = 1_000;
+    let top = 10;
+    let attempts = 20;
+
+    let mut rnd = thread_rng();
+
+    let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+    let hnsw_dir = Builder::new().prefix("hnsw_dir").tempdir().unwrap();
+
+    let config = SegmentConfig {
+        vector_data: HashMap::from([(
+            DEFAULT_VECTOR_NAME.to_owned(),
+            VectorDataConfig {
+                size: dim,
+                distance: Distance::Cosine,
+                storage_type,
+                index: Indexes::Plain {},
+                quantization_config: None,
+                multivector_config: None,
+                datatype: Some(VectorStorageDatatype::Uint8),
+            },
+        )]),
+        sparse_vector_data: Default::default(),
+        payload_storage_type: Default::default(),
+    };
+
+    let mut segment = build_segment(dir.path(), &config, true).unwrap();
+    let mut vectors = Vec::new();
+    for n in 0..num_vectors {
+        let vector = random_byte_vector(&mut rnd, dim);
+        segment
+            .upsert_point(n as SeqNumberType, n.into(), only_default_vector(&vector))
+            .unwrap();
+        vectors.push(vector);
+    }
+
+    // Vectors are stored as is, without normalization
+    let stored = segment
+        .vector(DEFAULT_VECTOR_NAME, 7u64.into())
+        .unwrap()
+        .unwrap();
+    assert_eq!(stored, vectors[7].clone().into());
+
+    // Values out of the byte range are rejected
+    let result = segment.upsert_point(
+        num_vectors,
+        num_vectors.into(),
+        only_default_vector(&vec![256.0; dim]),
+    );
+    assert!(result.is_err());
+
+    let vector_storage = segment.vector_data[DEFAULT_VECTOR_NAME]
+        .vector_storage
+        .clone();
+    assert_eq!(vector_storage.borrow().total_vector_count(), vectors.len());
+    assert_eq!(
+        vector_storage.borrow().is_on_disk(),
+        storage_type != VectorStorageType::Memory,
+    );
+
+    let mut hnsw_index = HNSWIndex::<GraphLinksRam>::open(
+        hnsw_dir.path(),
+        segment.id_tracker.clone(),
+        vector_storage,
+        segment.vector_data[DEFAULT_VECTOR_NAME]
+            .quantized_vectors
+            .clone(),
+        segment.payload_index.clone(),
+        HnswConfig {
+            m: 16,
+            ef_construct: 100,
+            full_scan_threshold: 1,
+            max_indexing_threads: 2,
+            on_disk: Some(false),
+            payload_m: None,
+        },
+    )
+    .unwrap();
+    hnsw_index.build_index(&stopped).unwrap();
+
+    let mut hits = 0;
+    for _ in 0..attempts {
+        let query = random_byte_vector(&mut rnd, dim);
+
+        let plain_result = segment.vector_data[DEFAULT_VECTOR_NAME]
+            .vector_index
+            .borrow()
+            .search(&[&query.clone().into()], None, top, None, &false.into())
+            .unwrap();
+        let plain_result = &plain_result[0];
+
+        // Cosine similarity of the byte vectors matches the one of the float vectors
+        let best = &plain_result[0];
+        let expected = CosineMetric::similarity(
+            &CosineMetric::preprocess(query.clone()),
+            &CosineMetric::preprocess(vectors[best.idx as usize].clone()),
+        );
+        assert!((best.score - expected).abs() < 1e-5);
+
+        let index_result = hnsw_index
+            .search(
+                &[&query.into()],
+                None,
+                top,
+                Some(&SearchParams {
+                    hnsw_ef: Some(64),
+                    ..Default::default()
+                }),
+                &false.into(),
+            )
+            .unwrap();
+
+        let expected_ids: HashSet<_> = plain_result.iter().map(|point| point.idx).collect();
+        hits += index_result[0]
+            .iter()
+            .filter(|point| expected_ids.contains(&point.idx))
+            .count();
+    }
+
+    let recall = hits as f64 / (top * attempts) as f64;
+    assert!(
+        recall > 0.9,
+        "recall of the HNSW search is too low: {recall}"
+    );
+}
+
+#[test]
+fn byte_vectors_in_memory_test() {
+    check_byte_vectors(VectorStorageType::Memory);
+}
+
+#[test]
+fn byte_vectors_on_disk_test() {
+    check_byte_vectors(VectorStorageType::Mmap);
+    check_byte_vectors(VectorStorageType::ChunkedMmap);
+}
@@ -0,0 +1,162 @@
+use std::collections::HashMap;
+
+use rand::thread_rng;
+use segment::data_types::vectors::{only_default_vector, Vector, DEFAULT_VECTOR_NAME};
+use segment::entry::entry_point::SegmentEntry;
+use segment::fixtures::index_fixtures::random_vector;
+use segment::index::VectorIndex;
+use segment::segment::Segment;
+use segment::segment_constructor::{build_segment, load_segment};
+use segment::spaces::metric::Metric;
+use segment::spaces::simple::CosineMetric;
+use segment::types::{
+    Distance, Indexes, SegmentConfig, SeqNumberType, VectorDataConfig, VectorStorageDatatype,
+    VectorStorageType,
+};
+use tempfile::Builder;
+
+fn half_segment_config(dim: usize, storage_type: VectorStorageType) -> SegmentConfig {
+    SegmentConfig {
+        vector_data: HashMap::from([(
+            DEFAULT_VECTOR_NAME.to_owned(),
+            VectorDataConfig {
+                size: dim,
+                distance: Distance::Cosine,
+                storage_type,
+                index: Indexes::Plain {},
+                quantization_config: None,
+                multivector_config: None,
+                datatype: Some(VectorStorageDatatype::Float16),
+            },
+        )]),
+        sparse_vector_data: Default::default(),
+        payload_storage_type: Default::default(),
+    }
+}
+
+fn assert_vectors_close(stored: &Vector, expected: &[f32]) {
+    let Vector::Dense(stored) = stored else {
+        panic!("dense vector expected, got {stored:?}");
+    };
+    assert_eq!(stored.len(), expected.len());
+    for (stored, expected) in stored.iter().zip(expected) {
+        // Relative precision of float16 is 2^-11
+        assert!(
+            (stored - expected).abs() <= expected.abs() / 1024.0 + 1e-6,
+            "{stored} != {expected}",
+        );
+    }
+}
+
+fn check_half_vectors(storage_type: VectorStorageType) {
+    let dim = 40;
+    let num_vectors: u64 = 500;
+    let top = 10;
+
+    let mut rnd = thread_rng();
+
+    let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+    let config = half_segment_config(dim, storage_type);
+
+    let mut segment = build_segment(dir.path(), &config, true).unwrap();
+    let mut vectors = Vec::new();
+    for n in 0..num_vectors {
+        let vector = CosineMetric::preprocess(random_vector(&mut rnd, dim));
+        segment
+            .upsert_point(n as SeqNumberType, n.into(), only_default_vector(&vector))
+            .unwrap();
+        vectors.push(vector);
+    }
+
+    let stored = segment
+        .vector(DEFAULT_VECTOR_NAME, 7u64.into())
+        .unwrap()
+        .unwrap();
+    assert_vectors_close(&stored, &vectors[7]);
+
+    // Values out of the float16 range are rejected
+    let mut out_of_range = vectors[0].clone();
+    out_of_range[0] = 1e5;
+    let result = segment.upsert_point(
+        num_vectors,
+        num_vectors.into(),
+        only_default_vector(&out_of_range),
+    );
+    assert!(result.is_err());
+
+    let query = random_vector(&mut rnd, dim);
+    let result = segment.vector_data[DEFAULT_VECTOR_NAME]
+        .vector_index
+        .borrow()
+        .search(&[&query.clone().into()], None, top, None, &false.into())
+        .unwrap();
+
+    // Scores are close to the ones of the float vectors
+    let query = CosineMetric::preprocess(query);
+    for scored in &result[0] {
+        let expected = CosineMetric::similarity(&query, &vectors[scored.idx as usize]);
+        assert!((scored.score - expected).abs() < 1e-2);
+    }
+
+    // Vectors are preserved through snapshots
+    segment.flush(true).unwrap();
+    let snapshot_dir = Builder::new().prefix("snapshot_dir").tempdir().unwrap();
+    let temp_dir = Builder::new().prefix("temp_dir").tempdir().unwrap();
+    let archive = segment
+        .take_snapshot(temp_dir.path(), snapshot_dir.path())
+        .unwrap();
+    let segment_id = segment
+        .current_path
+        .file_stem()
+        .and_then(|f| f.to_str())
+        .unwrap();
+    Segment::restore_snapshot(&archive, segment_id).unwrap();
+
+    let restored_segment = load_segment(&snapshot_dir.path().join(segment_id))
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        restored_segment.config().vector_data[DEFAULT_VECTOR_NAME].datatype,
+        Some(VectorStorageDatatype::Float16),
+    );
+    assert_eq!(restored_segment.available_point_count(), vectors.len(),);
+    for id in segment.iter_points() {
+        assert_eq!(
+            segment.vector(DEFAULT_VECTOR_NAME, id).unwrap(),
+            restored_segment.vector(DEFAULT_VECTOR_NAME, id).unwrap(),
+        );
+    }
+}
+
+#[test]
+fn half_vectors_in_memory_test() {
+    check_half_vectors(VectorStorageType::Memory);
+}
+
+#[test]
+fn half_vectors_on_disk_test() {
+    check_half_vectors(VectorStorageType::Mmap);
+    check_half_vectors(VectorStorageType::ChunkedMmap);
+}
+
+#[test]
+fn half_vectors_range_test() {
+    let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+    let config = half_segment_config(4, VectorStorageType::Memory);
+    let mut segment = build_segment(dir.path(), &config, true).unwrap();
+
+    // Largest finite float16 value is accepted
+    segment
+        .upsert_point(
+            0,
+            0u64.into(),
+            only_default_vector(&[65504.0, 0.0, 0.0, 0.0]),
+        )
+        .unwrap();
+    let stored = segment
+        .vector(DEFAULT_VECTOR_NAME, 0u64.into())
+        .unwrap()
+        .unwrap();
+    // Cosine vectors are normalized before they are stored
+    assert_vectors_close(&stored, &[1.0, 0.0, 0.0, 0.0]);
+}
@@ -1,12 +1,14 @@
 #![cfg(test)]
 
 pub mod batch_search_test;
+pub mod byte_vectors_test;
 pub mod disbalanced_vectors_test;
 pub mod exact_search_test;
 pub mod fail_recovery_test;
 pub mod filtering_context_check;
 pub mod filtrable_hnsw_test;
 pub mod fixtures;
+pub mod half_vectors_test;
 pub mod hnsw_discover_test;
 pub mod hnsw_quantized_search_test;
 pub mod nested_filtering_test;
@@ -1,22 +1,30 @@
 use std::collections::BTreeMap;
+use std::hash::{Hash, Hasher};
 
-use collection::config::{CollectionConfig, ShardingMethod};
+use collection::collection::read_only::Freeze;
+use collection::collection::stored_queries::StoredQuery;
+use collection::config::{
+    CollectionConfig, PayloadStorageBackend, ShardingMethod, StoragePolicy, StrictModeConfig,
+};
 use collection::operations::config_diff::{
     CollectionParamsDiff, HnswConfigDiff, OptimizersConfigDiff, QuantizationConfigDiff,
     WalConfigDiff,
 };
+use collection::operations::feature_flags::FeatureFlags;
 use collection::operations::types::{
-    SparseVectorParams, SparseVectorsConfig, VectorsConfig, VectorsConfigDiff,
+    NewVectorsConfig, SparseVectorParams, SparseVectorsConfig, VectorsConfig, VectorsConfigDiff,
 };
 use collection::shards::replica_set::ReplicaState;
 use collection::shards::shard::{PeerId, ShardId, ShardsPlacement};
 use collection::shards::transfer::{ShardTransfer, ShardTransferKey};
 use collection::shards::{replica_set, CollectionId};
+use common::validation::validate_collection_name;
 use schemars::JsonSchema;
-use segment::types::{PayloadFieldSchema, PayloadKeyType, QuantizationConfig, ShardKey};
+use segment::types::{Filter, PayloadFieldSchema, PayloadKeyType, QuantizationConfig, ShardKey};
 use serde::{Deserialize, Serialize};
 use validator::Validate;
 
+use crate::content_manager::collection_templates::CollectionTemplate;
 use crate::content_manager::shard_distribution::ShardDistributionProposal;
 
 // *Operation wrapper structure is only required for better OpenAPI generation
@@ -65,6 +73,22 @@ pub struct RenameAliasOperation {
     pub rename_alias: RenameAlias,
 }
 
+/// Delete collection, once all alias changes are applied.
+/// Allows to switch aliases to a new collection and delete the old one at once.
+#[derive(Debug, Deserialize, Serialize, JsonSchema, PartialEq, Eq, Hash, Clone)]
+#[serde(rename_all = "snake_case")]
+pub struct DropCollection {
+    pub collection_name: String,
+}
+
+/// Delete collection, once all alias changes are applied.
+/// Allows to switch aliases to a new collection and delete the old one at once.
+#[derive(Debug, Deserialize, Serialize, JsonSchema, PartialEq, Eq, Hash, Clone)]
+#[serde(rename_all = "snake_case")]
+pub struct DropCollectionOperation {
+    pub drop_collection: DropCollection,
+}
+
 /// Group of all the possible operations related to collection aliases
 #[derive(Debug, Deserialize, Serialize, JsonSchema, PartialEq, Eq, Hash, Clone)]
 #[serde(rename_all = "snake_case")]
@@ -73,6 +97,7 @@ pub enum AliasOperations {
     CreateAlias(CreateAliasOperation),
     DeleteAlias(DeleteAliasOperation),
     RenameAlias(RenameAliasOperation),
+    DropCollection(DropCollectionOperation),
 }
 
 impl From<CreateAlias> for AliasOperations {
@@ -93,11 +118,57 @@ impl From<RenameAlias> for AliasOperations {
     }
 }
 
+impl From<DropCollection> for AliasOperations {
+    fn from(drop_collection: DropCollection) -> Self {
+        AliasOperations::DropCollection(DropCollectionOperation { drop_collection })
+    }
+}
+
 /// Operation for creating new collection and (optionally) specify index params
-#[derive(Debug, Deserialize, Serialize, JsonSchema, PartialEq, Eq, Hash, Clone)]
+#[derive(Debug, Deserialize, Serialize, JsonSchema, PartialEq, Clone)]
 #[serde(rename_all = "snake_case")]
 pub struct InitFrom {
     pub collection: CollectionId,
+    /// Copy only points, which satisfy this conditions. If none - all points are copied.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub filter: Option<Filter>,
+}
+
+// Filter can't contain NaN values, as they are not representable in JSON
+impl Eq for InitFrom {}
+
+impl Hash for InitFrom {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        // Filter is not hashable, equal values still have equal hashes
+        self.collection.hash(state);
+    }
+}
+
+/// Create a copy of the collection, performed on the server side
+///
+/// Configuration of the new collection is the same as of the source one, except for the specified overrides.
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, PartialEq, Clone)]
+#[serde(rename_all = "snake_case")]
+pub struct CloneCollection {
+    /// Name of the new collection
+    #[validate(length(min = 1, max = 255), custom = "validate_collection_name")]
+    pub target: CollectionId,
+    /// Copy only points, which satisfy this conditions. If none - all points are copied.
+    #[serde(default)]
+    #[validate]
+    pub filter: Option<Filter>,
+    /// Custom params for HNSW index of the new collection. If none - same as of the source collection.
+    #[serde(default)]
+    #[validate]
+    pub hnsw_config: Option<HnswConfigDiff>,
+    /// Custom params for Optimizers of the new collection. If none - same as of the source collection.
+    #[serde(default, alias = "optimizer_config")]
+    #[validate]
+    pub optimizers_config: Option<OptimizersConfigDiff>,
+    /// Quantization parameters of the new collection. If none - same as of the source collection, if `Disabled` - the new collection is not quantized.
+    #[serde(default, alias = "quantization")]
+    #[validate]
+    pub quantization_config: Option<QuantizationConfigDiff>,
 }
 
 /// Operation for creating new collection and (optionally) specify index params
@@ -123,8 +194,13 @@ pub struct CreateCollection {
     /// Sharding method
     /// Default is Auto - points are distributed across all available shards
     /// Custom - points are distributed across shards according to shard key
+    /// PayloadKey - points are distributed across shards according to the value of `sharding_payload_key`
     #[serde(default)]
     pub sharding_method: Option<ShardingMethod>,
+    /// Top-level payload field with a keyword or integer value, which selects the shard of the point.
+    /// Required for the `payload_key` sharding method, not allowed for others.
+    #[serde(default)]
+    pub sharding_payload_key: Option<PayloadKeyType>,
     /// Number of shards replicas.
     /// Default is 1
     /// Minimum is 1
@@ -144,6 +220,10 @@ pub struct CreateCollection {
     /// Note: those payload values that are involved in filtering and are indexed - remain in RAM.
     #[serde(default)]
     pub on_disk_payload: Option<bool>,
+    /// Storage engine of the on-disk payload. Only used if `on_disk_payload` is enabled.
+    /// If none - RocksDB is used.
+    #[serde(default)]
+    pub payload_storage_backend: Option<PayloadStorageBackend>,
     /// Custom params for HNSW index. If none - values from service configuration file are used.
     #[validate]
     pub hnsw_config: Option<HnswConfigDiff>,
@@ -164,6 +244,42 @@ pub struct CreateCollection {
     /// Sparse vector data config.
     #[validate]
     pub sparse_vectors: Option<BTreeMap<String, SparseVectorParams>>,
+    /// Number of threads in a dedicated search thread pool of the collection, can't exceed the number of CPUs.
+    /// Isolates searches of this collection from the load on other collections.
+    /// If none - the shared search thread pool of the service is used.
+    #[serde(default)]
+    #[validate(range(min = 1))]
+    pub search_threads: Option<u32>,
+    /// Name of the payload field, which holds the expiration time of the point as unix timestamp in seconds.
+    /// If set - expired points are deleted automatically in background.
+    /// The field is indexed automatically, points are not expired while it has no index.
+    /// If none - points never expire.
+    #[serde(default)]
+    pub expiration_field: Option<PayloadKeyType>,
+    /// Strict mode restrictions for the requests to the collection.
+    /// If none - requests are not restricted.
+    #[serde(default)]
+    #[validate]
+    pub strict_mode_config: Option<StrictModeConfig>,
+    /// Number of seconds to retain deleted points, hidden from all reads.
+    /// During this time deleted points could be restored with the `undelete` operation.
+    /// If none - points are deleted immediately.
+    #[serde(default)]
+    pub soft_delete_window_sec: Option<u64>,
+    /// Placement of the collection data on disk or in RAM.
+    /// Takes precedence over the individual `on_disk`, `always_ram` and `on_disk_payload` settings.
+    /// If none - placement is defined by the individual settings.
+    #[serde(default)]
+    pub storage_policy: Option<StoragePolicy>,
+    /// Name of the collection template to take the configuration and payload indexes from.
+    /// Parameters of this request take precedence over the template ones.
+    /// If none - the collection is created from this request only.
+    #[serde(default)]
+    pub from_template: Option<String>,
+    /// Flags of the experimental features of the collection.
+    /// If none - flags of the node configuration are used.
+    #[serde(default)]
+    pub feature_flags: Option<FeatureFlags>,
 }
 
 /// Operation for creating new collection and (optionally) specify index params
@@ -221,6 +337,19 @@ pub struct UpdateCollection {
     /// Map of sparse vector data parameters to update for each sparse vector.
     #[validate]
     pub sparse_vectors: Option<SparseVectorsConfig>,
+    /// Strict mode restrictions to update. Unspecified fields are left unchanged.
+    /// If none - strict mode configuration is left unchanged.
+    #[serde(default)]
+    #[validate]
+    pub strict_mode_config: Option<StrictModeConfig>,
+    /// Named vectors to add to the collection. Existing points don't have the new vectors until
+    /// they are updated. Not available for collections with a single unnamed vector.
+    #[serde(default)]
+    #[validate]
+    pub add_vectors: Option<NewVectorsConfig>,
+    /// Flags of the experimental features to update. Unspecified flags are left unchanged.
+    #[serde(default)]
+    pub feature_flags: Option<FeatureFlags>,
 }
 
 /// Operation for updating parameters of the existing collection
@@ -243,6 +372,9 @@ impl UpdateCollectionOperation {
                 optimizers_config: None,
                 quantization_config: None,
                 sparse_vectors: None,
+                strict_mode_config: None,
+                add_vectors: None,
+                feature_flags: None,
             },
             shard_replica_changes: None,
         }
@@ -306,6 +438,23 @@ pub enum ShardTransferOperations {
     },
 }
 
+#[derive(Debug, Deserialize, Serialize, PartialEq, Eq, Hash, Clone)]
+pub enum ShardSplitOperations {
+    Start {
+        shard_id: ShardId,
+    },
+    /// Peer has filled its replicas of the new shards, the split is finished once all active
+    /// replicas of the shard are copied
+    Copied {
+        shard_id: ShardId,
+        peer_id: PeerId,
+    },
+    Abort {
+        shard_id: ShardId,
+        reason: String,
+    },
+}
+
 /// Sets the state of shard replica
 #[derive(Debug, Deserialize, Serialize, PartialEq, Eq, Hash, Clone)]
 pub struct SetShardReplicaState {
@@ -349,6 +498,49 @@ pub struct DropPayloadIndex {
     pub field_name: PayloadKeyType,
 }
 
+#[derive(Debug, Deserialize, Serialize, PartialEq, Clone)]
+pub struct SetStoredQuery {
+    pub collection_name: String,
+    pub query_name: String,
+    pub query: StoredQuery,
+}
+
+// Stored query can't contain NaN values, as they are not representable in JSON
+impl Eq for SetStoredQuery {}
+
+impl Hash for SetStoredQuery {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        // Stored query is not hashable, equal values still have equal hashes
+        self.collection_name.hash(state);
+        self.query_name.hash(state);
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, PartialEq, Eq, Hash, Clone)]
+pub struct DeleteStoredQuery {
+    pub collection_name: String,
+    pub query_name: String,
+}
+
+/// Freeze the collection or its shard read-only, or unfreeze it if `freeze` is not set
+#[derive(Debug, Deserialize, Serialize, PartialEq, Eq, Hash, Clone)]
+pub struct SetReadOnly {
+    pub collection_name: String,
+    pub shard_id: Option<ShardId>,
+    pub freeze: Option<Freeze>,
+}
+
+#[derive(Debug, Deserialize, Serialize, PartialEq, Eq, Hash, Clone)]
+pub struct SetCollectionTemplate {
+    pub template_name: String,
+    pub template: CollectionTemplate,
+}
+
+#[derive(Debug, Deserialize, Serialize, PartialEq, Eq, Hash, Clone)]
+pub struct DeleteCollectionTemplate {
+    pub template_name: String,
+}
+
 /// Enumeration of all possible collection update operations
 #[derive(Debug, Deserialize, Serialize, PartialEq, Eq, Hash, Clone)]
 #[serde(rename_all = "snake_case")]
@@ -358,11 +550,17 @@ pub enum CollectionMetaOperations {
     DeleteCollection(DeleteCollectionOperation),
     ChangeAliases(ChangeAliasesOperation),
     TransferShard(CollectionId, ShardTransferOperations),
+    SplitShard(CollectionId, ShardSplitOperations),
     SetShardReplicaState(SetShardReplicaState),
     CreateShardKey(CreateShardKey),
     DropShardKey(DropShardKey),
     CreatePayloadIndex(CreatePayloadIndex),
     DropPayloadIndex(DropPayloadIndex),
+    SetStoredQuery(SetStoredQuery),
+    DeleteStoredQuery(DeleteStoredQuery),
+    SetReadOnly(SetReadOnly),
+    SetCollectionTemplate(SetCollectionTemplate),
+    DeleteCollectionTemplate(DeleteCollectionTemplate),
     Nop { token: usize }, // Empty operation
 }
 
@@ -374,15 +572,24 @@ impl From<CollectionConfig> for CreateCollection {
             vectors: value.params.vectors,
             shard_number: Some(value.params.shard_number.get()),
             sharding_method: value.params.sharding_method,
+            sharding_payload_key: value.params.sharding_payload_key,
             replication_factor: Some(value.params.replication_factor.get()),
             write_consistency_factor: Some(value.params.write_consistency_factor.get()),
             on_disk_payload: Some(value.params.on_disk_payload),
+            payload_storage_backend: value.params.payload_storage_backend,
             hnsw_config: Some(value.hnsw_config.into()),
             wal_config: Some(value.wal_config.into()),
             optimizers_config: Some(value.optimizer_config.into()),
             init_from: None,
             quantization_config: value.quantization_config,
             sparse_vectors: value.params.sparse_vectors,
+            search_threads: value.params.search_threads.map(|threads| threads.get()),
+            expiration_field: value.params.expiration_field,
+            strict_mode_config: value.strict_mode_config,
+            soft_delete_window_sec: value.params.soft_delete_window_sec,
+            storage_policy: value.params.storage_policy,
+            from_template: None,
+            feature_flags: value.feature_flags,
         }
     }
 }
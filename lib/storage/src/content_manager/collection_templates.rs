@@ -0,0 +1,216 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use io::file_operations::{atomic_save_json, read_json};
+use schemars::JsonSchema;
+use segment::types::{PayloadFieldSchema, PayloadKeyType};
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+use crate::content_manager::collection_meta_ops::CreateCollection;
+use crate::content_manager::errors::StorageError;
+
+pub const COLLECTION_TEMPLATES_CONFIG_FILE: &str = "data.json";
+
+/// Configuration preset for new collections
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, PartialEq, Eq, Hash, Clone)]
+#[serde(rename_all = "snake_case")]
+pub struct CollectionTemplate {
+    /// Configuration of the collections created from the template.
+    /// Parameters of the create collection request take precedence over the template ones.
+    #[validate]
+    pub config: CreateCollection,
+    /// Payload indexes to create in the collections created from the template
+    #[serde(default)]
+    pub payload_indexes: BTreeMap<PayloadKeyType, PayloadFieldSchema>,
+}
+
+impl CollectionTemplate {
+    /// Check that the template could be used to create collections, before it is stored
+    pub fn check(&self) -> Result<(), StorageError> {
+        if self.config.init_from.is_some() {
+            return Err(StorageError::bad_input(
+                "Collection template can't initialize collections from another collection",
+            ));
+        }
+        if self.config.from_template.is_some() {
+            return Err(StorageError::bad_input(
+                "Collection template can't be based on another template",
+            ));
+        }
+        Ok(())
+    }
+
+    /// Complete the create collection request with the parameters of the template
+    ///
+    /// Each parameter set in the request replaces the template one as a whole.
+    pub fn apply(&self, request: CreateCollection) -> CreateCollection {
+        let template = self.config.clone();
+        CreateCollection {
+            vectors: if request.vectors.vectors_num() == 0 {
+                template.vectors
+            } else {
+                request.vectors
+            },
+            shard_number: request.shard_number.or(template.shard_number),
+            sharding_method: request.sharding_method.or(template.sharding_method),
+            sharding_payload_key: request
+                .sharding_payload_key
+                .or(template.sharding_payload_key),
+            replication_factor: request.replication_factor.or(template.replication_factor),
+            write_consistency_factor: request
+                .write_consistency_factor
+                .or(template.write_consistency_factor),
+            on_disk_payload: request.on_disk_payload.or(template.on_disk_payload),
+            payload_storage_backend: request
+                .payload_storage_backend
+                .or(template.payload_storage_backend),
+            hnsw_config: request.hnsw_config.or(template.hnsw_config),
+            wal_config: request.wal_config.or(template.wal_config),
+            optimizers_config: request.optimizers_config.or(template.optimizers_config),
+            init_from: request.init_from,
+            quantization_config: request.quantization_config.or(template.quantization_config),
+            sparse_vectors: request.sparse_vectors.or(template.sparse_vectors),
+            search_threads: request.search_threads.or(template.search_threads),
+            expiration_field: request.expiration_field.or(template.expiration_field),
+            strict_mode_config: request.strict_mode_config.or(template.strict_mode_config),
+            soft_delete_window_sec: request
+                .soft_delete_window_sec
+                .or(template.soft_delete_window_sec),
+            storage_policy: request.storage_policy.or(template.storage_policy),
+            from_template: request.from_template,
+            feature_flags: request.feature_flags.or(template.feature_flags),
+        }
+    }
+}
+
+/// Collection templates by their names
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq, Eq, Default)]
+pub struct CollectionTemplates {
+    pub templates: BTreeMap<String, CollectionTemplate>,
+}
+
+impl CollectionTemplates {
+    pub fn load(path: &Path) -> Result<Self, StorageError> {
+        Ok(read_json(path)?)
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), StorageError> {
+        Ok(atomic_save_json(path, self)?)
+    }
+}
+
+/// Persists collection templates of the cluster. The data is assumed to be relatively small.
+/// - Reads are served from memory.
+/// - Writes are durably saved.
+#[derive(Debug)]
+pub struct CollectionTemplatesPersistence {
+    data_path: PathBuf,
+    templates: CollectionTemplates,
+}
+
+impl CollectionTemplatesPersistence {
+    pub fn get_config_path(path: &Path) -> PathBuf {
+        path.join(COLLECTION_TEMPLATES_CONFIG_FILE)
+    }
+
+    pub fn open(dir_path: PathBuf) -> Result<Self, StorageError> {
+        if !dir_path.exists() {
+            fs::create_dir_all(&dir_path)?;
+        }
+        let data_path = Self::get_config_path(&dir_path);
+        let templates = if data_path.exists() {
+            CollectionTemplates::load(&data_path)?
+        } else {
+            let templates = CollectionTemplates::default();
+            templates.save(&data_path)?;
+            templates
+        };
+        Ok(CollectionTemplatesPersistence {
+            data_path,
+            templates,
+        })
+    }
+
+    pub fn get(&self, name: &str) -> Option<&CollectionTemplate> {
+        self.templates.templates.get(name)
+    }
+
+    pub fn insert(
+        &mut self,
+        name: String,
+        template: CollectionTemplate,
+    ) -> Result<(), StorageError> {
+        self.templates.templates.insert(name, template);
+        self.templates.save(&self.data_path)?;
+        Ok(())
+    }
+
+    pub fn remove(&mut self, name: &str) -> Result<Option<CollectionTemplate>, StorageError> {
+        let output = self.templates.templates.remove(name);
+
+        if output.is_some() {
+            self.templates.save(&self.data_path)?;
+        }
+
+        Ok(output)
+    }
+
+    pub fn state(&self) -> &CollectionTemplates {
+        &self.templates
+    }
+
+    pub fn apply_state(&mut self, templates: CollectionTemplates) -> Result<(), StorageError> {
+        self.templates = templates;
+        self.templates.save(&self.data_path)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use collection::operations::config_diff::HnswConfigDiff;
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_apply_template() {
+        let template: CollectionTemplate = serde_json::from_value(json!({
+            "config": {
+                "vectors": { "size": 4, "distance": "Dot" },
+                "shard_number": 2,
+                "hnsw_config": { "m": 32 },
+                "on_disk_payload": true,
+            },
+            "payload_indexes": { "tenant": "keyword" },
+        }))
+        .unwrap();
+        template.check().unwrap();
+
+        let request: CreateCollection = serde_json::from_value(json!({
+            "shard_number": 3,
+            "hnsw_config": { "ef_construct": 50 },
+            "from_template": "tenant",
+        }))
+        .unwrap();
+
+        let config = template.apply(request);
+        assert_eq!(config.vectors, template.config.vectors);
+        assert_eq!(config.on_disk_payload, Some(true));
+        assert_eq!(config.from_template.as_deref(), Some("tenant"));
+        // Request parameters take precedence as a whole
+        assert_eq!(config.shard_number, Some(3));
+        assert_eq!(
+            config.hnsw_config,
+            Some(HnswConfigDiff {
+                ef_construct: Some(50),
+                ..Default::default()
+            }),
+        );
+
+        // Applying the template again changes nothing
+        assert_eq!(template.apply(config.clone()), config);
+    }
+}
@@ -1,4 +1,5 @@
 use std::cmp;
+use std::collections::HashMap;
 use std::fs::{create_dir_all, File};
 use std::io::BufWriter;
 use std::path::{Path, PathBuf};
@@ -6,6 +7,7 @@ use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 
 use atomicwrites::{AllowOverwrite, AtomicFile};
+use collection::operations::types::NodeType;
 use collection::shards::shard::PeerId;
 use http::Uri;
 use parking_lot::RwLock;
@@ -39,6 +41,9 @@ pub struct Persistent {
     /// Last known cluster topology
     #[serde(with = "serialize_peer_addresses")]
     pub peer_address_by_id: Arc<RwLock<PeerAddressById>>,
+    /// Roles of the peers, which are set through the API
+    #[serde(default)]
+    pub peer_roles: HashMap<PeerId, NodeType>,
     pub this_peer_id: PeerId,
     #[serde(skip)]
     pub path: PathBuf,
@@ -60,8 +65,10 @@ impl Persistent {
         &mut self,
         meta: &SnapshotMetadata,
         address_by_id: PeerAddressById,
+        peer_roles: HashMap<PeerId, NodeType>,
     ) -> Result<(), StorageError> {
         *self.peer_address_by_id.write() = address_by_id;
+        self.peer_roles = peer_roles;
         self.state.conf_state = meta.get_conf_state().clone();
         self.state.hard_state.term = cmp::max(self.state.hard_state.term, meta.term);
         self.state.hard_state.commit = meta.index;
@@ -150,6 +157,11 @@ impl Persistent {
         self.save()
     }
 
+    pub fn set_peer_role(&mut self, peer_id: PeerId, role: NodeType) -> Result<(), StorageError> {
+        self.peer_roles.insert(peer_id, role);
+        self.save()
+    }
+
     pub fn last_applied_entry(&self) -> Option<u64> {
         self.apply_progress_queue.get_last_applied()
     }
@@ -188,6 +200,7 @@ impl Persistent {
             },
             apply_progress_queue: Default::default(),
             peer_address_by_id: Default::default(),
+            peer_roles: Default::default(),
             this_peer_id,
             path,
             latest_snapshot_meta: Default::default(),
@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::error::Error;
 use std::fmt::Display;
 use std::future::Future;
@@ -10,6 +10,7 @@ use anyhow::{anyhow, Context};
 use chrono::Utc;
 use collection::collection_state;
 use collection::common::is_ready::IsReady;
+use collection::operations::types::NodeType;
 use collection::shards::shard::PeerId;
 use collection::shards::CollectionId;
 use common::defaults;
@@ -24,6 +25,7 @@ use tokio::time::error::Elapsed;
 use tonic::transport::Uri;
 
 use super::alias_mapping::AliasMapping;
+use super::collection_templates::CollectionTemplates;
 use super::consensus_ops::{ConsensusOperations, SnapshotStatus};
 use super::errors::StorageError;
 use super::CollectionContainer;
@@ -47,12 +49,16 @@ pub struct SnapshotData {
     pub collections_data: CollectionsSnapshot,
     #[serde(with = "crate::serialize_peer_addresses")]
     pub address_by_id: PeerAddressById,
+    #[serde(default)]
+    pub peer_roles: HashMap<PeerId, NodeType>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, Default)]
 pub struct CollectionsSnapshot {
     pub collections: HashMap<CollectionId, collection_state::State>,
     pub aliases: AliasMapping,
+    #[serde(default)]
+    pub templates: CollectionTemplates,
 }
 
 impl TryFrom<&[u8]> for SnapshotData {
@@ -101,6 +107,13 @@ impl<C: CollectionContainer> ConsensusManager<C> {
         propose_sender: OperationSender,
         storage_path: &str,
     ) -> Self {
+        // Role set through the API takes precedence over the configured one
+        if let Some(role) = persistent_state
+            .peer_roles
+            .get(&persistent_state.this_peer_id)
+        {
+            toc.set_node_type(*role);
+        }
         Self {
             persistent: RwLock::new(persistent_state),
             is_leader_established: Arc::new(IsReady::default()),
@@ -198,6 +211,7 @@ impl<C: CollectionContainer> ConsensusManager<C> {
                     peer_id,
                     PeerInfo {
                         uri: uri.to_string(),
+                        role: persistent.peer_roles.get(&peer_id).copied(),
                     },
                 )
             })
@@ -448,6 +462,10 @@ impl<C: CollectionContainer> ConsensusManager<C> {
                 Ok(false)
             }
 
+            ConsensusOperations::SetPeerRole { peer_id, role } => {
+                self.set_peer_role(peer_id, role).map(|()| true)
+            }
+
             ConsensusOperations::RequestSnapshot | ConsensusOperations::ReportSnapshot { .. } => {
                 unreachable!()
             }
@@ -471,9 +489,11 @@ impl<C: CollectionContainer> ConsensusManager<C> {
         let data: SnapshotData = snapshot.get_data().try_into()?;
         self.toc.apply_collections_snapshot(data.collections_data)?;
         self.wal.lock().clear()?;
-        self.persistent
-            .write()
-            .update_from_snapshot(meta, data.address_by_id)?;
+        let mut persistent = self.persistent.write();
+        if let Some(role) = data.peer_roles.get(&persistent.this_peer_id) {
+            self.toc.set_node_type(*role);
+        }
+        persistent.update_from_snapshot(meta, data.address_by_id, data.peer_roles)?;
 
         Ok(Ok(()))
     }
@@ -519,7 +539,28 @@ impl<C: CollectionContainer> ConsensusManager<C> {
         // plus we need to make additional removing in the `channel_pool`.
         // So we handle `remove_peer` inside the `toc` and persist changes in the `persistent` after that.
         self.toc.remove_peer(peer_id)?;
-        self.persistent.read().save()
+        let mut persistent = self.persistent.write();
+        persistent.peer_roles.remove(&peer_id);
+        persistent.save()
+    }
+
+    fn set_peer_role(&self, peer_id: PeerId, role: NodeType) -> Result<(), StorageError> {
+        let mut persistent = self.persistent.write();
+        if peer_id == persistent.this_peer_id {
+            self.toc.set_node_type(role);
+        }
+        persistent.set_peer_role(peer_id, role)
+    }
+
+    /// Peers, which are set to host listener replicas only
+    pub fn listener_peers(&self) -> HashSet<PeerId> {
+        self.persistent
+            .read()
+            .peer_roles
+            .iter()
+            .filter(|(_, role)| **role == NodeType::Listener)
+            .map(|(peer_id, _)| *peer_id)
+            .collect()
     }
 
     async fn await_receiver(
@@ -770,6 +811,7 @@ impl<C: CollectionContainer> Storage for ConsensusManager<C> {
             let snapshot = SnapshotData {
                 collections_data,
                 address_by_id: persistent.peer_address_by_id(),
+                peer_roles: persistent.peer_roles.clone(),
             };
             Ok(raft::eraftpb::Snapshot {
                 data: serde_cbor::to_vec(&snapshot).map_err(raft_error_other)?,
@@ -984,6 +1026,8 @@ mod tests {
         fn sync_local_state(&self) -> Result<(), crate::content_manager::errors::StorageError> {
             Ok(())
         }
+
+        fn set_node_type(&self, _node_type: collection::operations::types::NodeType) {}
     }
 
     fn setup_storages(
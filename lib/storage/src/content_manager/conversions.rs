@@ -1,12 +1,14 @@
-use collection::operations::conversions::sharding_method_from_proto;
+use collection::operations::conversions::{
+    payload_storage_backend_from_proto, sharding_method_from_proto,
+};
 use collection::operations::types::SparseVectorsConfig;
 use tonic::Status;
 
 use crate::content_manager::collection_meta_ops::{
-    AliasOperations, ChangeAliasesOperation, CollectionMetaOperations, CreateAlias,
-    CreateAliasOperation, CreateCollection, CreateCollectionOperation, DeleteAlias,
-    DeleteAliasOperation, DeleteCollectionOperation, InitFrom, RenameAlias, RenameAliasOperation,
-    UpdateCollection, UpdateCollectionOperation,
+    AliasOperations, ChangeAliasesOperation, CloneCollection, CollectionMetaOperations,
+    CreateAlias, CreateAliasOperation, CreateCollection, CreateCollectionOperation, DeleteAlias,
+    DeleteAliasOperation, DeleteCollectionOperation, DropCollection, DropCollectionOperation,
+    InitFrom, RenameAlias, RenameAliasOperation, UpdateCollection, UpdateCollectionOperation,
 };
 use crate::content_manager::errors::StorageError;
 
@@ -18,6 +20,7 @@ pub fn error_to_status(error: StorageError) -> tonic::Status {
         StorageError::BadRequest { .. } => tonic::Code::InvalidArgument,
         StorageError::Locked { .. } => tonic::Code::FailedPrecondition,
         StorageError::Timeout { .. } => tonic::Code::DeadlineExceeded,
+        StorageError::Overloaded { .. } => tonic::Code::ResourceExhausted,
     };
     tonic::Status::new(error_code, format!("{error}"))
 }
@@ -38,15 +41,20 @@ impl TryFrom<api::grpc::qdrant::CreateCollection> for CollectionMetaOperations {
                     .sparse_vectors_config
                     .map(|config| config.map.into_iter().map(|(k, v)| (k, v.into())).collect()),
                 hnsw_config: value.hnsw_config.map(|v| v.into()),
-                wal_config: value.wal_config.map(|v| v.into()),
+                wal_config: value.wal_config.map(TryInto::try_into).transpose()?,
                 optimizers_config: value.optimizers_config.map(|v| v.into()),
                 shard_number: value.shard_number,
                 on_disk_payload: value.on_disk_payload,
+                payload_storage_backend: value
+                    .payload_storage_backend
+                    .map(payload_storage_backend_from_proto)
+                    .transpose()?,
                 replication_factor: value.replication_factor,
                 write_consistency_factor: value.write_consistency_factor,
-                init_from: value
-                    .init_from_collection
-                    .map(|v| InitFrom { collection: v }),
+                init_from: value.init_from_collection.map(|v| InitFrom {
+                    collection: v,
+                    filter: None,
+                }),
                 quantization_config: value
                     .quantization_config
                     .map(TryInto::try_into)
@@ -55,6 +63,14 @@ impl TryFrom<api::grpc::qdrant::CreateCollection> for CollectionMetaOperations {
                     .sharding_method
                     .map(sharding_method_from_proto)
                     .transpose()?,
+                sharding_payload_key: value.sharding_payload_key,
+                search_threads: value.search_threads,
+                expiration_field: value.expiration_field,
+                strict_mode_config: value.strict_mode_config.map(Into::into),
+                soft_delete_window_sec: value.soft_delete_window_sec,
+                storage_policy: value.storage_policy.map(TryInto::try_into).transpose()?,
+                from_template: value.from_template,
+                feature_flags: value.feature_flags.map(Into::into),
             },
         )))
     }
@@ -84,11 +100,34 @@ impl TryFrom<api::grpc::qdrant::UpdateCollection> for CollectionMetaOperations {
                         config.map.into_iter().map(|(k, v)| (k, v.into())).collect(),
                     )
                 }),
+                strict_mode_config: value.strict_mode_config.map(Into::into),
+                add_vectors: value
+                    .new_vectors_config
+                    .map(TryInto::try_into)
+                    .transpose()?,
+                feature_flags: value.feature_flags.map(Into::into),
             },
         )))
     }
 }
 
+impl TryFrom<api::grpc::qdrant::CloneCollection> for CloneCollection {
+    type Error = Status;
+
+    fn try_from(value: api::grpc::qdrant::CloneCollection) -> Result<Self, Self::Error> {
+        Ok(Self {
+            target: value.target,
+            filter: value.filter.map(TryInto::try_into).transpose()?,
+            hnsw_config: value.hnsw_config.map(Into::into),
+            optimizers_config: value.optimizers_config.map(Into::into),
+            quantization_config: value
+                .quantization_config
+                .map(TryInto::try_into)
+                .transpose()?,
+        })
+    }
+}
+
 impl TryFrom<api::grpc::qdrant::DeleteCollection> for CollectionMetaOperations {
     type Error = Status;
 
@@ -131,6 +170,16 @@ impl From<api::grpc::qdrant::RenameAlias> for AliasOperations {
     }
 }
 
+impl From<api::grpc::qdrant::DropCollection> for AliasOperations {
+    fn from(value: api::grpc::qdrant::DropCollection) -> Self {
+        Self::DropCollection(DropCollectionOperation {
+            drop_collection: DropCollection {
+                collection_name: value.collection_name,
+            },
+        })
+    }
+}
+
 impl TryFrom<api::grpc::qdrant::AliasOperations> for AliasOperations {
     type Error = Status;
 
@@ -145,6 +194,9 @@ impl TryFrom<api::grpc::qdrant::AliasOperations> for AliasOperations {
             Some(api::grpc::qdrant::alias_operations::Action::RenameAlias(rename)) => {
                 Ok(rename.into())
             }
+            Some(api::grpc::qdrant::alias_operations::Action::DropCollection(drop)) => {
+                Ok(drop.into())
+            }
             _ => Err(Status::invalid_argument("Malformed AliasOperation type")),
         }
     }
@@ -8,10 +8,11 @@ use collection::operations::point_ops::{
 use collection::operations::shard_selector_internal::ShardSelectorInternal;
 use collection::operations::types::{CollectionError, CollectionResult, ScrollRequestInternal};
 use collection::operations::{CollectionUpdateOperations, CreateIndex, FieldIndexOperations};
+use collection::shards::replica_set::read_priority::ReadPriority;
 use collection::shards::replica_set::ReplicaState;
 use collection::shards::shard::{PeerId, ShardId};
 use collection::shards::CollectionId;
-use segment::types::{WithPayloadInterface, WithVector};
+use segment::types::{Filter, WithPayloadInterface, WithVector};
 use tokio::sync::RwLock;
 
 use crate::content_manager::collections_ops::Collections;
@@ -73,6 +74,7 @@ async fn replicate_shard_data(
     source_collection_name: &CollectionId,
     target_collection_name: &CollectionId,
     shard_id: ShardId,
+    filter: Option<&Filter>,
 ) -> CollectionResult<()> {
     let mut offset = None;
     let limit = MIGRATION_BATCH_SIZE;
@@ -81,9 +83,15 @@ async fn replicate_shard_data(
         let request = ScrollRequestInternal {
             offset,
             limit: Some(limit),
-            filter: None,
+            filter: filter.cloned(),
             with_payload: Some(WithPayloadInterface::Bool(true)),
             with_vector: WithVector::Bool(true),
+            as_of: None,
+            session_id: None,
+            session_ttl_sec: None,
+            order_by: None,
+            sample: None,
+            with_version: false,
         };
 
         let collections_read = collections.read().await;
@@ -92,7 +100,14 @@ async fn replicate_shard_data(
             handle_get_collection(collections_read.get(source_collection_name))?;
         let _updates_guard = source_collection.lock_updates().await;
         let scroll_result = source_collection
-            .scroll_by(request, None, &ShardSelectorInternal::ShardId(shard_id))
+            .scroll_by(
+                request,
+                None,
+                &ShardSelectorInternal::ShardId(shard_id),
+                None,
+                ReadPriority::Normal,
+                None,
+            )
             .await?;
 
         offset = scroll_result.next_page_offset;
@@ -108,6 +123,7 @@ async fn replicate_shard_data(
                 id: point.id,
                 vector: point.vector.unwrap(),
                 payload: point.payload,
+                if_version: None,
             })
             .collect();
 
@@ -148,10 +164,13 @@ async fn wait_all_shards_active(
 
 /// Spawns a task which will retrieve data from appropriate local shards of the `source` collection
 /// into target collection.
+///
+/// If `filter` is specified, only points matching it are transferred.
 pub async fn populate_collection(
     collections: Arc<RwLock<Collections>>,
     source_collection: &CollectionId,
     target_collection: &CollectionId,
+    filter: Option<&Filter>,
     this_peer_id: PeerId,
 ) -> CollectionResult<()> {
     let collections_read = collections.read().await;
@@ -174,6 +193,7 @@ pub async fn populate_collection(
             source_collection,
             target_collection,
             shard_id,
+            filter,
         )
         .await?;
     }
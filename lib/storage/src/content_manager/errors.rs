@@ -24,6 +24,8 @@ pub enum StorageError {
     Locked { description: String },
     #[error("Timeout: {description}")]
     Timeout { description: String },
+    #[error("Service overloaded: {description}")]
+    Overloaded { description: String },
 }
 
 impl StorageError {
@@ -91,6 +93,15 @@ impl StorageError {
             CollectionError::Timeout { .. } => StorageError::Timeout {
                 description: overriding_description,
             },
+            CollectionError::Overloaded { .. } => StorageError::Overloaded {
+                description: overriding_description,
+            },
+            CollectionError::ReadOnly { .. } => StorageError::Locked {
+                description: overriding_description,
+            },
+            CollectionError::BatchTooLarge { .. } => StorageError::BadRequest {
+                description: overriding_description,
+            },
         }
     }
 }
@@ -132,6 +143,11 @@ impl From<CollectionError> for StorageError {
             CollectionError::Timeout { .. } => StorageError::Timeout {
                 description: format!("{err}"),
             },
+            CollectionError::Overloaded { description } => StorageError::Overloaded { description },
+            CollectionError::ReadOnly { description } => StorageError::Locked { description },
+            CollectionError::BatchTooLarge { .. } => StorageError::BadRequest {
+                description: format!("{err}"),
+            },
         }
     }
 }
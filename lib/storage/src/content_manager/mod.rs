@@ -1,3 +1,4 @@
+use collection::operations::types::NodeType;
 use collection::shards::shard::PeerId;
 
 use self::collection_meta_ops::CollectionMetaOperations;
@@ -6,6 +7,7 @@ use self::errors::StorageError;
 
 pub mod alias_mapping;
 pub mod collection_meta_ops;
+pub mod collection_templates;
 mod collections_ops;
 pub mod consensus;
 pub mod consensus_manager;
@@ -17,17 +19,18 @@ pub mod snapshots;
 pub mod toc;
 
 pub mod consensus_ops {
+    use collection::operations::types::NodeType;
     use collection::shards::replica_set::ReplicaState;
     use collection::shards::replica_set::ReplicaState::Initializing;
-    use collection::shards::shard::PeerId;
+    use collection::shards::shard::{PeerId, ShardId};
     use collection::shards::transfer::ShardTransfer;
     use collection::shards::{replica_set, CollectionId};
     use raft::eraftpb::Entry as RaftEntry;
     use serde::{Deserialize, Serialize};
 
     use crate::content_manager::collection_meta_ops::{
-        CollectionMetaOperations, SetShardReplicaState, ShardTransferOperations, UpdateCollection,
-        UpdateCollectionOperation,
+        CollectionMetaOperations, SetShardReplicaState, ShardSplitOperations,
+        ShardTransferOperations, UpdateCollection, UpdateCollectionOperation,
     };
 
     /// Operation that should pass consensus
@@ -44,6 +47,11 @@ pub mod consensus_ops {
             peer_id: PeerId,
             status: SnapshotStatus,
         },
+        /// Change the role of the peer in the cluster
+        SetPeerRole {
+            peer_id: PeerId,
+            role: NodeType,
+        },
     }
 
     impl TryFrom<&RaftEntry> for ConsensusOperations {
@@ -76,6 +84,31 @@ pub mod consensus_ops {
             )))
         }
 
+        pub fn shard_split_copied(
+            collection_id: CollectionId,
+            shard_id: ShardId,
+            peer_id: PeerId,
+        ) -> Self {
+            ConsensusOperations::CollectionMeta(Box::new(CollectionMetaOperations::SplitShard(
+                collection_id,
+                ShardSplitOperations::Copied { shard_id, peer_id },
+            )))
+        }
+
+        pub fn abort_shard_split(
+            collection_id: CollectionId,
+            shard_id: ShardId,
+            reason: &str,
+        ) -> Self {
+            ConsensusOperations::CollectionMeta(Box::new(CollectionMetaOperations::SplitShard(
+                collection_id,
+                ShardSplitOperations::Abort {
+                    shard_id,
+                    reason: reason.to_string(),
+                },
+            )))
+        }
+
         pub fn set_replica_state(
             collection_name: CollectionId,
             shard_id: u32,
@@ -109,6 +142,9 @@ pub mod consensus_ops {
                     hnsw_config: None,
                     quantization_config: None,
                     sparse_vectors: None,
+                    strict_mode_config: None,
+                    add_vectors: None,
+                    feature_flags: None,
                 },
             );
             operation
@@ -193,4 +229,7 @@ pub trait CollectionContainer {
     fn remove_peer(&self, peer_id: PeerId) -> Result<(), StorageError>;
 
     fn sync_local_state(&self) -> Result<(), StorageError>;
+
+    /// Change the role of this peer
+    fn set_node_type(&self, node_type: NodeType);
 }
@@ -101,6 +101,17 @@ impl ShardDistributionProposal {
         Self { distribution }
     }
 
+    /// Place a replica of every shard on each of the peers
+    pub fn add_to_all_shards(&mut self, peers: &[PeerId]) {
+        for (_shard_id, shard_peers) in &mut self.distribution {
+            for peer_id in peers {
+                if !shard_peers.contains(peer_id) {
+                    shard_peers.push(*peer_id);
+                }
+            }
+        }
+    }
+
     pub fn local_shards_for(&self, peer_id: PeerId) -> Vec<ShardId> {
         self.distribution
             .iter()
@@ -170,6 +181,23 @@ mod tests {
         assert_eq!(shard_counts.iter().max(), Some(&2));
     }
 
+    #[test]
+    fn test_add_to_all_shards() {
+        let mut distribution = ShardDistributionProposal::new(
+            NonZeroU32::new(3).unwrap(),
+            NonZeroU32::new(1).unwrap(),
+            &[1, 2],
+        );
+        distribution.add_to_all_shards(&[2, 10]);
+
+        assert_eq!(distribution.distribution.len(), 3);
+        for (_shard_id, peers) in &distribution.distribution {
+            assert!(peers.contains(&10));
+            let unique: HashSet<_> = peers.iter().collect();
+            assert_eq!(unique.len(), peers.len(), "peer must not be duplicated");
+        }
+    }
+
     #[test]
     fn test_distribution_is_spread() {
         let known_peers = vec![1, 2, 3, 4];
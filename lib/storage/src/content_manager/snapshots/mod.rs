@@ -5,7 +5,8 @@ use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 
 use collection::operations::snapshot_ops::{
-    get_snapshot_description, list_snapshots_in_directory, SnapshotDescription,
+    get_snapshot_description, list_snapshots_in_directory, SnapshotCreationPriority,
+    SnapshotDescription,
 };
 use serde::{Deserialize, Serialize};
 use tar::Builder as TarBuilder;
@@ -28,7 +29,7 @@ pub async fn get_full_snapshot_path(
     toc: &TableOfContent,
     snapshot_name: &str,
 ) -> Result<PathBuf, StorageError> {
-    let snapshot_path = Path::new(toc.snapshots_path()).join(snapshot_name);
+    let snapshot_path = Path::new(&toc.snapshots_path()).join(snapshot_name);
     if !snapshot_path.exists() {
         return Err(StorageError::NotFound {
             description: format!("Full storage snapshot {snapshot_name} not found"),
@@ -91,17 +92,15 @@ async fn _do_delete_collection_snapshot(
     snapshot_name: &str,
 ) -> Result<bool, StorageError> {
     let collection = dispatcher.get_collection(collection_name).await?;
-    let file_name = collection.get_snapshot_path(snapshot_name).await?;
-    log::info!("Deleting collection snapshot {:?}", file_name);
-    tokio::fs::remove_file(file_name).await?;
+    collection.delete_snapshot(snapshot_name).await?;
     Ok(true)
 }
 
 pub async fn do_list_full_snapshots(
     toc: &TableOfContent,
 ) -> Result<Vec<SnapshotDescription>, StorageError> {
-    let snapshots_path = Path::new(toc.snapshots_path());
-    Ok(list_snapshots_in_directory(snapshots_path).await?)
+    let snapshots_path = toc.snapshots_path();
+    Ok(list_snapshots_in_directory(Path::new(&snapshots_path)).await?)
 }
 
 pub async fn do_create_full_snapshot(
@@ -122,12 +121,21 @@ async fn _do_create_full_snapshot(
 ) -> Result<SnapshotDescription, StorageError> {
     let dispatcher = dispatcher.clone();
 
-    let snapshot_dir = Path::new(dispatcher.snapshots_path()).to_path_buf();
+    // Full snapshot packs snapshots of the collections, which must be local files
+    if !dispatcher.toc().snapshot_storage().is_local() {
+        return Err(StorageError::bad_input(
+            "Full snapshots are not supported with the object snapshots storage",
+        ));
+    }
+
+    let snapshot_dir = PathBuf::from(dispatcher.snapshots_path());
 
     let all_collections = dispatcher.all_collections().await;
     let mut created_snapshots: Vec<(&str, SnapshotDescription)> = vec![];
     for collection_name in &all_collections {
-        let snapshot_details = dispatcher.create_snapshot(collection_name).await?;
+        let snapshot_details = dispatcher
+            .create_snapshot(collection_name, SnapshotCreationPriority::Normal)
+            .await?;
         created_snapshots.push((collection_name, snapshot_details));
     }
     let current_time = chrono::Utc::now().format("%Y-%m-%d-%H-%M-%S").to_string();
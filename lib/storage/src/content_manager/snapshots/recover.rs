@@ -77,45 +77,77 @@ async fn _do_recover_from_snapshot(
 
     let is_distributed = toc.is_distributed();
 
-    let download_dir = toc.snapshots_download_tempdir()?;
-
-    log::debug!(
-        "Downloading snapshot from {location} to {}",
-        download_dir.path().display(),
-    );
-
-    let (snapshot_path, snapshot_temp_path) =
-        download_snapshot(client, location, download_dir.path()).await?;
-
-    log::debug!("Snapshot downloaded to {}", snapshot_path.display());
-
     let temp_storage_path = toc.optional_temp_or_storage_temp_path()?;
 
     let tmp_collection_dir = tempfile::Builder::new()
         .prefix(&format!("col-{collection_name}-recovery-"))
         .tempdir_in(temp_storage_path)?;
 
-    log::debug!(
-        "Recovering collection {collection_name} from snapshot {}",
-        snapshot_path.display(),
-    );
+    let tmp_collection_dir_clone = tmp_collection_dir.path().to_path_buf();
 
-    log::debug!(
-        "Unpacking snapshot to {}",
-        tmp_collection_dir.path().display(),
-    );
+    if location.scheme() == "s3" {
+        // Stream the snapshot from the object storage, without downloading it first
+        let key = toc.snapshot_storage().location_key(&location)?;
+        let reader = toc.snapshot_storage().object_reader(&key).await?;
 
-    let tmp_collection_dir_clone = tmp_collection_dir.path().to_path_buf();
-    let restoring = tokio::task::spawn_blocking(move || {
-        // Unpack snapshot collection to the target folder
-        Collection::restore_snapshot(
-            &snapshot_path,
-            &tmp_collection_dir_clone,
-            this_peer_id,
-            is_distributed,
-        )
-    });
-    restoring.await??;
+        log::debug!(
+            "Recovering collection {collection_name} from snapshot {location}, unpacking to {}",
+            tmp_collection_dir.path().display(),
+        );
+
+        let restoring = tokio::task::spawn_blocking(move || {
+            Collection::restore_snapshot_from_reader(
+                reader,
+                location.as_str(),
+                &tmp_collection_dir_clone,
+                this_peer_id,
+                is_distributed,
+            )
+        });
+        restoring.await??;
+    } else {
+        let download_dir = toc.snapshots_download_tempdir()?;
+
+        log::debug!(
+            "Downloading snapshot from {location} to {}",
+            download_dir.path().display(),
+        );
+
+        let (snapshot_path, snapshot_temp_path) =
+            download_snapshot(client, location, download_dir.path()).await?;
+
+        log::debug!("Snapshot downloaded to {}", snapshot_path.display());
+
+        log::debug!(
+            "Recovering collection {collection_name} from snapshot {}",
+            snapshot_path.display(),
+        );
+
+        log::debug!(
+            "Unpacking snapshot to {}",
+            tmp_collection_dir.path().display(),
+        );
+
+        let restoring = tokio::task::spawn_blocking(move || {
+            // Unpack snapshot collection to the target folder
+            Collection::restore_snapshot(
+                &snapshot_path,
+                &tmp_collection_dir_clone,
+                this_peer_id,
+                is_distributed,
+            )
+        });
+        restoring.await??;
+
+        // Remove snapshot after unpacking if downloaded
+        if let Some(path) = snapshot_temp_path {
+            if let Err(err) = path.close() {
+                log::error!(
+                    "Failed to remove downloaded collection snapshot after recovery: {err}"
+                );
+            }
+        }
+    }
 
     let snapshot_config = CollectionConfig::load(tmp_collection_dir.path())?;
     snapshot_config.validate_and_warn();
@@ -301,12 +333,5 @@ async fn _do_recover_from_snapshot(
     // Remove tmp collection dir
     tokio::fs::remove_dir_all(&tmp_collection_dir).await?;
 
-    // Remove snapshot after recovery if downloaded
-    if let Some(path) = snapshot_temp_path {
-        if let Err(err) = path.close() {
-            log::error!("Failed to remove downloaded collection snapshot after recovery: {err}");
-        }
-    }
-
     Ok(true)
 }
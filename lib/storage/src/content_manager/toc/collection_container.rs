@@ -3,6 +3,7 @@ use std::sync::Arc;
 
 use collection::collection::Collection;
 use collection::collection_state;
+use collection::operations::types::NodeType;
 use collection::shards::collection_shard_distribution::CollectionShardDistribution;
 use collection::shards::replica_set::ReplicaState;
 use collection::shards::shard::PeerId;
@@ -73,6 +74,12 @@ impl CollectionContainer for TableOfContent {
                 Self::on_transfer_failure_callback(self.consensus_proposal_sender.clone());
             let transfer_success_callback =
                 Self::on_transfer_success_callback(self.consensus_proposal_sender.clone());
+            let split_copied_callback = Self::on_shard_split_copied_callback(
+                self.consensus_proposal_sender.clone(),
+                self.this_peer_id,
+            );
+            let split_failure_callback =
+                Self::on_shard_split_failure_callback(self.consensus_proposal_sender.clone());
 
             for collection in collections.values() {
                 let finish_shard_initialize = Self::change_peer_state_callback(
@@ -103,10 +110,25 @@ impl CollectionContainer for TableOfContent {
                         convert_from_listener_to_active_callback,
                     )
                     .await?;
+
+                collection
+                    .sync_shard_splits(
+                        split_copied_callback.clone(),
+                        split_failure_callback.clone(),
+                    )
+                    .await?;
             }
             Ok(())
         })
     }
+
+    fn set_node_type(&self, node_type: NodeType) {
+        let node_role = &self.shared_storage_config.node_type;
+        if node_role.get() != node_type {
+            log::info!("Changing role of this peer to {node_type:?}");
+            node_role.set(node_type);
+        }
+    }
 }
 
 impl TableOfContent {
@@ -122,6 +144,7 @@ impl TableOfContent {
         consensus_manager::CollectionsSnapshot {
             collections,
             aliases: self.alias_persistence.read().await.state().clone(),
+            templates: self.template_persistence.read().await.state().clone(),
         }
     }
 
@@ -147,9 +170,7 @@ impl TableOfContent {
                         &collection_path,
                         &snapshots_path,
                         &state.config,
-                        self.storage_config
-                            .to_shared_storage_config(self.is_distributed())
-                            .into(),
+                        self.shared_storage_config.clone(),
                         shard_distribution,
                         self.channel_service.clone(),
                         Self::change_peer_state_callback(
@@ -239,6 +260,12 @@ impl TableOfContent {
                 .await
                 .apply_state(data.aliases)?;
 
+            // Apply collection templates
+            self.template_persistence
+                .write()
+                .await
+                .apply_state(data.templates)?;
+
             Ok(())
         })
     }
@@ -297,4 +324,49 @@ impl TableOfContent {
             }
         })
     }
+
+    fn on_shard_split_copied_callback(
+        proposal_sender: Option<OperationSender>,
+        this_peer_id: PeerId,
+    ) -> collection::collection::OnShardSplitCopied {
+        Arc::new(move |shard_id, collection_name| {
+            if let Some(proposal_sender) = &proposal_sender {
+                let operation = ConsensusOperations::shard_split_copied(
+                    collection_name.clone(),
+                    shard_id,
+                    this_peer_id,
+                );
+                if let Err(send_error) = proposal_sender.send(operation) {
+                    log::error!(
+                        "Can't send proposal to complete split of shard {} of collection {}. Error: {}",
+                        shard_id,
+                        collection_name,
+                        send_error
+                    );
+                }
+            }
+        })
+    }
+
+    fn on_shard_split_failure_callback(
+        proposal_sender: Option<OperationSender>,
+    ) -> collection::collection::OnShardSplitFailure {
+        Arc::new(move |shard_id, collection_name, reason| {
+            if let Some(proposal_sender) = &proposal_sender {
+                let operation = ConsensusOperations::abort_shard_split(
+                    collection_name.clone(),
+                    shard_id,
+                    reason,
+                );
+                if let Err(send_error) = proposal_sender.send(operation) {
+                    log::error!(
+                        "Can't send proposal to abort split of shard {} of collection {}. Error: {}",
+                        shard_id,
+                        collection_name,
+                        send_error
+                    );
+                }
+            }
+        })
+    }
 }
@@ -1,11 +1,15 @@
-use std::collections::HashSet;
+use std::collections::{BTreeMap, HashSet};
 use std::path::Path;
 
+use collection::collection::Collection;
 use collection::collection_state;
 use collection::config::ShardingMethod;
+use collection::operations::feature_flags::FeatureFlags;
 use collection::shards::collection_shard_distribution::CollectionShardDistribution;
 use collection::shards::replica_set::ReplicaState;
 use collection::shards::{transfer, CollectionId};
+use segment::common::cpu::get_num_cpus;
+use segment::types::{PayloadFieldSchema, PayloadKeyType};
 use uuid::Uuid;
 
 use super::TableOfContent;
@@ -31,16 +35,21 @@ impl TableOfContent {
         match operation {
             CollectionMetaOperations::CreateCollection(mut operation) => {
                 log::info!("Creating collection {}", operation.collection_name);
+                let payload_indexes = self
+                    .apply_collection_template(&mut operation.create_collection)
+                    .await?;
                 let distribution = match operation.take_distribution() {
                     None => match operation
                         .create_collection
                         .sharding_method
                         .unwrap_or_default()
                     {
-                        ShardingMethod::Auto => CollectionShardDistribution::all_local(
-                            operation.create_collection.shard_number,
-                            self.this_peer_id,
-                        ),
+                        ShardingMethod::Auto | ShardingMethod::PayloadKey => {
+                            CollectionShardDistribution::all_local(
+                                operation.create_collection.shard_number,
+                                self.this_peer_id,
+                            )
+                        }
                         ShardingMethod::Custom => ShardDistributionProposal::empty().into(),
                     },
                     Some(distribution) => distribution.into(),
@@ -49,6 +58,7 @@ impl TableOfContent {
                     &operation.collection_name,
                     operation.create_collection,
                     distribution,
+                    payload_indexes,
                 )
                 .await
             }
@@ -71,6 +81,13 @@ impl TableOfContent {
                     .await
                     .map(|()| true)
             }
+            CollectionMetaOperations::SplitShard(collection, operation) => {
+                log::debug!("Split shard {:?} of {}", operation, collection);
+
+                self.handle_shard_split(collection, operation)
+                    .await
+                    .map(|()| true)
+            }
             CollectionMetaOperations::SetShardReplicaState(operation) => {
                 log::debug!("Set shard replica state {:?}", operation);
                 self.set_shard_replica_state(operation).await.map(|()| true)
@@ -96,9 +113,115 @@ impl TableOfContent {
                     .await
                     .map(|()| true)
             }
+            CollectionMetaOperations::SetStoredQuery(set_stored_query) => {
+                log::debug!("Set stored query {:?}", set_stored_query);
+                self.set_stored_query(set_stored_query).await.map(|()| true)
+            }
+            CollectionMetaOperations::DeleteStoredQuery(delete_stored_query) => {
+                log::debug!("Delete stored query {:?}", delete_stored_query);
+                self.delete_stored_query(delete_stored_query)
+                    .await
+                    .map(|()| true)
+            }
+            CollectionMetaOperations::SetReadOnly(set_read_only) => {
+                log::debug!("Set read-only {:?}", set_read_only);
+                self.set_read_only(set_read_only).await.map(|()| true)
+            }
+            CollectionMetaOperations::SetCollectionTemplate(set_template) => {
+                log::debug!("Set collection template {}", set_template.template_name);
+                self.set_collection_template(set_template)
+                    .await
+                    .map(|()| true)
+            }
+            CollectionMetaOperations::DeleteCollectionTemplate(delete_template) => {
+                log::debug!(
+                    "Delete collection template {}",
+                    delete_template.template_name
+                );
+                self.delete_collection_template(delete_template).await
+            }
         }
     }
 
+    /// Complete the create collection request with the parameters of its template
+    ///
+    /// Returns payload indexes of the template, which have to be created in the new collection.
+    /// Applying the same template again changes nothing.
+    pub async fn apply_collection_template(
+        &self,
+        operation: &mut CreateCollection,
+    ) -> Result<BTreeMap<PayloadKeyType, PayloadFieldSchema>, StorageError> {
+        let Some(template_name) = &operation.from_template else {
+            return Ok(BTreeMap::new());
+        };
+        let template = self.get_collection_template(template_name).await?;
+        *operation = template.apply(operation.clone());
+        Ok(template.payload_indexes)
+    }
+
+    /// Check that the stored query is applicable, before it is submitted
+    pub async fn check_stored_query(&self, operation: &SetStoredQuery) -> Result<(), StorageError> {
+        self.get_collection(&operation.collection_name)
+            .await?
+            .check_stored_query(&operation.query)
+            .await?;
+        Ok(())
+    }
+
+    /// Check that the collection update is applicable, before it is submitted
+    pub async fn check_collection_update(
+        &self,
+        operation: &UpdateCollectionOperation,
+    ) -> Result<(), StorageError> {
+        let collection = self.get_collection(&operation.collection_name).await?;
+        let update = &operation.update_collection;
+        collection
+            .check_config_update(
+                update.params.as_ref(),
+                update.hnsw_config.as_ref(),
+                update.optimizers_config.as_ref(),
+                update.vectors.as_ref(),
+                update.sparse_vectors.as_ref(),
+                update.add_vectors.as_ref(),
+                update.feature_flags.as_ref(),
+            )
+            .await?;
+        Ok(())
+    }
+
+    /// Check that the experimental features, selected by the new collection, are enabled
+    ///
+    /// Flags of the node configuration may differ between peers, so they are checked only by the
+    /// peer, which receives the request, before it is distributed across the cluster.
+    pub async fn check_collection_create(
+        &self,
+        operation: &CreateCollectionOperation,
+    ) -> Result<(), StorageError> {
+        let mut create_collection = operation.create_collection.clone();
+        self.apply_collection_template(&mut create_collection)
+            .await?;
+        let feature_flags = FeatureFlags::resolve(
+            create_collection.feature_flags.as_ref(),
+            &self.storage_config.feature_flags,
+        );
+        feature_flags.check_payload_storage_backend(create_collection.payload_storage_backend)?;
+        feature_flags.check_vectors(
+            create_collection
+                .vectors
+                .params_iter()
+                .map(|(_, params)| params),
+        )?;
+        if let Some(search_threads) = create_collection.search_threads {
+            let max_search_threads = get_num_cpus();
+            if search_threads as usize > max_search_threads {
+                return Err(StorageError::bad_input(format!(
+                    "`search_threads` can't exceed the number of CPUs ({max_search_threads})"
+                )));
+            }
+        }
+        Ok(())
+    }
+
     async fn update_collection(
         &self,
         mut operation: UpdateCollectionOperation,
@@ -111,6 +234,9 @@ impl TableOfContent {
             optimizers_config,
             quantization_config,
             sparse_vectors,
+            strict_mode_config,
+            add_vectors,
+            feature_flags,
         } = operation.update_collection;
         let collection = self.get_collection(&operation.collection_name).await?;
         let mut recreate_optimizers = false;
@@ -121,6 +247,8 @@ impl TableOfContent {
         }
         if let Some(diff) = params {
             collection.update_params_from_diff(diff).await?;
+            collection.create_soft_delete_index().await?;
+            collection.create_expiration_index().await?;
             recreate_optimizers = true;
         }
         if let Some(diff) = hnsw_config {
@@ -141,6 +269,16 @@ impl TableOfContent {
             collection.update_sparse_vectors_from_other(&diff).await?;
             recreate_optimizers = true;
         }
+        if let Some(diff) = strict_mode_config {
+            collection.update_strict_mode_config(diff).await?;
+        }
+        if let Some(diff) = feature_flags {
+            collection.update_feature_flags(diff).await?;
+        }
+        if let Some(new_vectors) = add_vectors {
+            collection.add_vectors(&new_vectors).await?;
+            recreate_optimizers = true;
+        }
         if let Some(changes) = replica_changes {
             collection.handle_replica_changes(changes).await?;
         }
@@ -162,80 +300,138 @@ impl TableOfContent {
                 .await
                 .remove_collection(collection_name)?;
 
-            let path = self.get_collection_path(collection_name);
-            drop(removed);
-
-            // Move collection to ".deleted" folder to prevent accidental reuse
-            let uuid = Uuid::new_v4().to_string();
-            let removed_collections_path =
-                Path::new(&self.storage_config.storage_path).join(".deleted");
-            tokio::fs::create_dir_all(&removed_collections_path).await?;
-            let deleted_path = removed_collections_path
-                .join(collection_name)
-                .with_extension(uuid);
-            tokio::fs::rename(path, &deleted_path).await?;
-
-            // At this point collection is removed from memory and moved to ".deleted" folder.
-            // Next time we load service the collection will not appear in the list of collections.
-            // We can take our time to delete the collection from disk.
-            tokio::spawn(async move {
-                if let Err(error) = tokio::fs::remove_dir_all(&deleted_path).await {
-                    log::error!(
-                        "Can't delete collection {} from disk. Error: {}",
-                        deleted_path.display(),
-                        error
-                    );
-                }
-            });
+            self.delete_collection_files(collection_name, removed)
+                .await?;
             Ok(true)
         } else {
             Ok(false)
         }
     }
 
+    /// Delete files of the collection, which is already removed from the service
+    async fn delete_collection_files(
+        &self,
+        collection_name: &str,
+        removed: Collection,
+    ) -> Result<(), StorageError> {
+        let path = self.get_collection_path(collection_name);
+        drop(removed);
+
+        // Move collection to ".deleted" folder to prevent accidental reuse
+        let uuid = Uuid::new_v4().to_string();
+        let removed_collections_path =
+            Path::new(&self.storage_config.storage_path).join(".deleted");
+        tokio::fs::create_dir_all(&removed_collections_path).await?;
+        let deleted_path = removed_collections_path
+            .join(collection_name)
+            .with_extension(uuid);
+        tokio::fs::rename(path, &deleted_path).await?;
+
+        // At this point collection is removed from memory and moved to ".deleted" folder.
+        // Next time we load service the collection will not appear in the list of collections.
+        // We can take our time to delete the collection from disk.
+        tokio::spawn(async move {
+            if let Err(error) = tokio::fs::remove_dir_all(&deleted_path).await {
+                log::error!(
+                    "Can't delete collection {} from disk. Error: {}",
+                    deleted_path.display(),
+                    error
+                );
+            }
+        });
+        Ok(())
+    }
+
     /// performs several alias changes in an atomic fashion
+    ///
+    /// Either all changes are applied, or none of them. Collections are dropped only after all
+    /// alias changes are applied, so aliases never point to a partially deleted collection.
     async fn update_aliases(
         &self,
         operation: ChangeAliasesOperation,
     ) -> Result<bool, StorageError> {
         // Lock all collections for alias changes
         // Prevent search on partially switched collections
-        let collection_lock = self.collections.write().await;
+        let mut collection_lock = self.collections.write().await;
         let mut alias_lock = self.alias_persistence.write().await;
-        for action in operation.actions {
-            match action {
-                AliasOperations::CreateAlias(CreateAliasOperation {
-                    create_alias:
-                        CreateAlias {
-                            collection_name,
-                            alias_name,
-                        },
-                }) => {
-                    collection_lock
-                        .validate_collection_exists(&collection_name)
-                        .await?;
-                    collection_lock
-                        .validate_collection_not_exists(&alias_name)
-                        .await?;
-
-                    alias_lock.insert(alias_name, collection_name)?;
-                }
-                AliasOperations::DeleteAlias(DeleteAliasOperation {
-                    delete_alias: DeleteAlias { alias_name },
-                }) => {
-                    alias_lock.remove(&alias_name)?;
-                }
-                AliasOperations::RenameAlias(RenameAliasOperation {
-                    rename_alias:
-                        RenameAlias {
-                            old_alias_name,
-                            new_alias_name,
-                        },
-                }) => {
-                    alias_lock.rename_alias(&old_alias_name, new_alias_name)?;
-                }
-            };
+
+        let previous_aliases = alias_lock.state().clone();
+        let mut dropped_collections: Vec<CollectionId> = Vec::new();
+
+        let result: Result<(), StorageError> = async {
+            for action in operation.actions {
+                match action {
+                    AliasOperations::CreateAlias(CreateAliasOperation {
+                        create_alias:
+                            CreateAlias {
+                                collection_name,
+                                alias_name,
+                            },
+                    }) => {
+                        collection_lock
+                            .validate_collection_exists(&collection_name)
+                            .await?;
+                        collection_lock
+                            .validate_collection_not_exists(&alias_name)
+                            .await?;
+                        if dropped_collections.contains(&collection_name) {
+                            return Err(StorageError::bad_input(format!(
+                                "Can't create alias {alias_name} for collection {collection_name}, which is dropped",
+                            )));
+                        }
+
+                        alias_lock.insert(alias_name, collection_name)?;
+                    }
+                    AliasOperations::DeleteAlias(DeleteAliasOperation {
+                        delete_alias: DeleteAlias { alias_name },
+                    }) => {
+                        alias_lock.remove(&alias_name)?;
+                    }
+                    AliasOperations::RenameAlias(RenameAliasOperation {
+                        rename_alias:
+                            RenameAlias {
+                                old_alias_name,
+                                new_alias_name,
+                            },
+                    }) => {
+                        alias_lock.rename_alias(&old_alias_name, new_alias_name)?;
+                    }
+                    AliasOperations::DropCollection(DropCollectionOperation {
+                        drop_collection: DropCollection { collection_name },
+                    }) => {
+                        collection_lock
+                            .validate_collection_exists(&collection_name)
+                            .await?;
+                        if !dropped_collections.contains(&collection_name) {
+                            alias_lock.remove_collection(&collection_name)?;
+                            dropped_collections.push(collection_name);
+                        }
+                    }
+                };
+            }
+            Ok(())
+        }
+        .await;
+
+        if let Err(err) = result {
+            // Revert the changes, which are already applied
+            alias_lock.apply_state(previous_aliases)?;
+            return Err(err);
+        }
+
+        let removed: Vec<_> = dropped_collections
+            .into_iter()
+            .filter_map(|name| collection_lock.remove(&name).map(|removed| (name, removed)))
+            .collect();
+        drop(alias_lock);
+        drop(collection_lock);
+
+        for (collection_name, removed) in removed {
+            log::info!("Deleting collection {collection_name}, dropped by alias changes");
+            self.delete_collection_files(&collection_name, removed)
+                .await?;
         }
+
         Ok(true)
     }
 
@@ -262,7 +458,21 @@ impl TableOfContent {
                     transfers,
                     shards_key_mapping: _,
                     payload_index_schema: _,
+                    shard_splits,
+                    stored_queries: _,
+                    read_only: _,
                 } = collection.state().await;
+
+                if shard_splits
+                    .iter()
+                    .any(|split| !split.is_done() && split.source == transfer.shard_id)
+                {
+                    return Err(StorageError::bad_request(format!(
+                        "Shard {} cannot be transferred while it is being split",
+                        transfer.shard_id,
+                    )));
+                }
+
                 let all_peers: HashSet<_> = self
                     .channel_service
                     .id_to_address
@@ -382,6 +592,69 @@ impl TableOfContent {
         Ok(())
     }
 
+    async fn handle_shard_split(
+        &self,
+        collection_id: CollectionId,
+        split_operation: ShardSplitOperations,
+    ) -> Result<(), StorageError> {
+        let collection = self.get_collection(&collection_id).await?;
+        let proposal_sender = if let Some(proposal_sender) = self.consensus_proposal_sender.clone()
+        {
+            proposal_sender
+        } else {
+            return Err(StorageError::service_error(
+                "Can't handle shard split, this is a single node deployment",
+            ));
+        };
+
+        match split_operation {
+            ShardSplitOperations::Start { shard_id } => {
+                let on_finish = {
+                    let collection_id = collection_id.clone();
+                    let proposal_sender = proposal_sender.clone();
+                    let this_peer_id = self.this_peer_id;
+                    async move {
+                        let operation = ConsensusOperations::shard_split_copied(
+                            collection_id,
+                            shard_id,
+                            this_peer_id,
+                        );
+
+                        if let Err(error) = proposal_sender.send(operation) {
+                            log::error!("Can't report shard split progress to consensus: {}", error)
+                        };
+                    }
+                };
+
+                let on_failure = {
+                    let collection_id = collection_id.clone();
+                    async move {
+                        if let Err(error) =
+                            proposal_sender.send(ConsensusOperations::abort_shard_split(
+                                collection_id,
+                                shard_id,
+                                "copying failed",
+                            ))
+                        {
+                            log::error!("Can't report shard split progress to consensus: {}", error)
+                        };
+                    }
+                };
+
+                collection
+                    .start_shard_split(shard_id, on_finish, on_failure)
+                    .await?;
+            }
+            ShardSplitOperations::Copied { shard_id, peer_id } => {
+                collection.shard_split_copied(shard_id, peer_id).await?;
+            }
+            ShardSplitOperations::Abort { shard_id, reason } => {
+                collection.abort_shard_split(shard_id, &reason).await?;
+            }
+        }
+        Ok(())
+    }
+
     async fn set_shard_replica_state(
         &self,
         operation: SetShardReplicaState,
@@ -432,4 +705,48 @@ impl TableOfContent {
             .await?;
         Ok(())
     }
+
+    async fn set_stored_query(&self, operation: SetStoredQuery) -> Result<(), StorageError> {
+        self.get_collection(&operation.collection_name)
+            .await?
+            .set_stored_query(operation.query_name, operation.query)?;
+        Ok(())
+    }
+
+    async fn delete_stored_query(&self, operation: DeleteStoredQuery) -> Result<(), StorageError> {
+        self.get_collection(&operation.collection_name)
+            .await?
+            .delete_stored_query(&operation.query_name)?;
+        Ok(())
+    }
+
+    async fn set_read_only(&self, operation: SetReadOnly) -> Result<(), StorageError> {
+        self.get_collection(&operation.collection_name)
+            .await?
+            .set_read_only(operation.shard_id, operation.freeze)
+            .await?;
+        Ok(())
+    }
+
+    async fn set_collection_template(
+        &self,
+        operation: SetCollectionTemplate,
+    ) -> Result<(), StorageError> {
+        self.template_persistence
+            .write()
+            .await
+            .insert(operation.template_name, operation.template)
+    }
+
+    async fn delete_collection_template(
+        &self,
+        operation: DeleteCollectionTemplate,
+    ) -> Result<bool, StorageError> {
+        let removed = self
+            .template_persistence
+            .write()
+            .await
+            .remove(&operation.template_name)?;
+        Ok(removed.is_some())
+    }
 }
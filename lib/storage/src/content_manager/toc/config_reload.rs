@@ -0,0 +1,199 @@
+//! Storage settings, which could be changed without restart of the service
+
+use std::cmp::max;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use collection::config::WalConfig;
+use collection::optimizers_builder::OptimizersConfig;
+use segment::common::cpu::get_num_cpus;
+use segment::types::{HnswConfig, QuantizationConfig};
+use tokio::runtime::Handle;
+use tokio::sync::Semaphore;
+
+use super::TableOfContent;
+use crate::content_manager::errors::StorageError;
+use crate::types::StorageConfig;
+
+/// Configuration of new collections, used for parameters which are not specified on creation
+#[derive(Debug, Clone)]
+pub(super) struct CollectionDefaults {
+    pub on_disk_payload: bool,
+    pub wal: WalConfig,
+    pub optimizers: OptimizersConfig,
+    pub hnsw_index: HnswConfig,
+    pub quantization: Option<QuantizationConfig>,
+}
+
+impl From<&StorageConfig> for CollectionDefaults {
+    fn from(storage_config: &StorageConfig) -> Self {
+        Self {
+            on_disk_payload: storage_config.on_disk_payload,
+            wal: storage_config.wal.clone(),
+            optimizers: storage_config.optimizers.clone(),
+            hnsw_index: storage_config.hnsw_index.clone(),
+            quantization: storage_config.quantization.clone(),
+        }
+    }
+}
+
+/// Limit of parallel update requests, which could be changed without losing track of the running ones
+pub(super) struct UpdateRateLimiter {
+    semaphore: Arc<Semaphore>,
+    limit: usize,
+}
+
+impl UpdateRateLimiter {
+    fn new(limit: usize) -> Self {
+        Self {
+            semaphore: Arc::new(Semaphore::new(limit)),
+            limit,
+        }
+    }
+
+    pub fn semaphore(&self) -> Arc<Semaphore> {
+        self.semaphore.clone()
+    }
+
+    /// Change number of permits of the semaphore
+    ///
+    /// Permits, which are in use, can't be revoked. If the limit is decreased, excess permits are
+    /// taken away by a task on the `runtime` once they are released.
+    fn resize(&mut self, limit: usize, runtime: &Handle) {
+        if limit > self.limit {
+            self.semaphore.add_permits(limit - self.limit);
+        } else if limit < self.limit {
+            let excess = (self.limit - limit) as u32;
+            let semaphore = self.semaphore.clone();
+            runtime.spawn(async move {
+                // Semaphore is never closed
+                if let Ok(permits) = semaphore.acquire_many_owned(excess).await {
+                    permits.forget();
+                }
+            });
+        }
+        self.limit = limit;
+    }
+}
+
+pub(super) fn update_rate_limiter(
+    update_rate_limit: Option<usize>,
+    is_distributed: bool,
+) -> Option<UpdateRateLimiter> {
+    match update_rate_limit {
+        Some(limit) => Some(UpdateRateLimiter::new(limit)),
+        None => {
+            if is_distributed {
+                // Auto adjust the rate limit in distributed mode.
+                // Select number of working threads as a guess.
+                let limit = max(get_num_cpus(), 2);
+                log::debug!(
+                    "Auto adjusting update rate limit to {} parallel update requests",
+                    limit
+                );
+                Some(UpdateRateLimiter::new(limit))
+            } else {
+                None
+            }
+        }
+    }
+}
+
+impl TableOfContent {
+    /// Apply storage settings, which could be changed at runtime:
+    /// defaults of new collections and the update rate limit.
+    ///
+    /// Existing collections keep their configuration.
+    /// Updates, which are running or waiting for the rate limiter, are counted by the new limit.
+    pub fn reload_config(&self, storage_config: &StorageConfig) {
+        *self.collection_defaults.write() = CollectionDefaults::from(storage_config);
+
+        let new_limiter = update_rate_limiter(
+            storage_config.performance.update_rate_limit,
+            self.is_distributed(),
+        );
+        let mut update_rate_limiter = self.update_rate_limiter.write();
+        match (update_rate_limiter.as_mut(), new_limiter) {
+            (Some(limiter), Some(new_limiter)) => {
+                limiter.resize(new_limiter.limit, self.general_runtime.handle())
+            }
+            // Updates, which are running without a limit, are not counted by the new one
+            (_, new_limiter) => *update_rate_limiter = new_limiter,
+        }
+    }
+
+    /// Store new snapshots in `snapshots_path`
+    ///
+    /// Existing snapshots are not moved, they are not listed anymore.
+    /// Only local snapshots storage supports it, as keys of the objects in S3 are relative to the
+    /// snapshots path.
+    pub async fn reload_snapshots_path(&self, snapshots_path: &str) -> Result<(), StorageError> {
+        if !self.snapshot_storage().is_local() {
+            return Err(StorageError::bad_input(
+                "Snapshots path can't be changed without restart, if snapshots are stored in S3",
+            ));
+        }
+
+        let new_snapshots_path = PathBuf::from(snapshots_path);
+        create_snapshots_dir(&new_snapshots_path).await?;
+        // Collections, created from now on, use the new path
+        *self.snapshots_path.write() = snapshots_path.to_string();
+
+        let collections = self.collections.read().await;
+        for (collection_name, collection) in collections.iter() {
+            let collection_snapshots_path =
+                Self::collection_snapshots_path(&new_snapshots_path, collection_name);
+            create_snapshots_dir(&collection_snapshots_path).await?;
+            collection.set_snapshots_path(collection_snapshots_path);
+        }
+        Ok(())
+    }
+}
+
+async fn create_snapshots_dir(path: &Path) -> Result<(), StorageError> {
+    tokio::fs::create_dir_all(path).await.map_err(|err| {
+        StorageError::service_error(format!(
+            "Can't create snapshots directory {}: {err}",
+            path.display(),
+        ))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use tokio::runtime::Runtime;
+
+    use super::*;
+
+    #[test]
+    fn test_resize_update_rate_limiter() {
+        let runtime = Runtime::new().unwrap();
+        let mut limiter = UpdateRateLimiter::new(2);
+        let semaphore = limiter.semaphore();
+
+        runtime.block_on(async {
+            let running_updates = semaphore.clone().acquire_many_owned(2).await.unwrap();
+
+            // Running updates keep their permits, new ones are available at once
+            limiter.resize(3, runtime.handle());
+            assert_eq!(semaphore.available_permits(), 1);
+
+            // Excess permits are taken away, once running updates are finished
+            limiter.resize(1, runtime.handle());
+            drop(running_updates);
+            for _ in 0..100 {
+                if semaphore.available_permits() == 1 {
+                    break;
+                }
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+            assert_eq!(semaphore.available_permits(), 1);
+
+            // Same semaphore is used, so updates are limited by the new limit
+            let _permit = semaphore.clone().acquire_owned().await.unwrap();
+            assert_eq!(limiter.semaphore().available_permits(), 0);
+        });
+    }
+}
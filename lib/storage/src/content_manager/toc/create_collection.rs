@@ -13,6 +13,7 @@ use collection::shards::collection_shard_distribution::CollectionShardDistributi
 use collection::shards::replica_set::ReplicaState;
 use collection::shards::shard::{PeerId, ShardId};
 use collection::shards::CollectionId;
+use segment::types::{Filter, PayloadFieldSchema, PayloadKeyType};
 
 use super::TableOfContent;
 use crate::content_manager::collection_meta_ops::*;
@@ -27,6 +28,7 @@ impl TableOfContent {
         collection_name: &str,
         operation: CreateCollection,
         collection_shard_distribution: CollectionShardDistribution,
+        payload_indexes: BTreeMap<PayloadKeyType, PayloadFieldSchema>,
     ) -> Result<bool, StorageError> {
         // Collection operations require multiple file operations,
         // before collection can actually be registered in the service.
@@ -37,7 +39,9 @@ impl TableOfContent {
             vectors,
             shard_number,
             sharding_method,
+            sharding_payload_key,
             on_disk_payload,
+            payload_storage_backend,
             hnsw_config: hnsw_config_diff,
             wal_config: wal_config_diff,
             optimizers_config: optimizers_config_diff,
@@ -46,6 +50,13 @@ impl TableOfContent {
             init_from,
             quantization_config,
             sparse_vectors,
+            search_threads,
+            expiration_field,
+            strict_mode_config,
+            soft_delete_window_sec,
+            storage_policy,
+            from_template: _,
+            feature_flags,
         } = operation;
 
         self.collections
@@ -70,11 +81,25 @@ impl TableOfContent {
                 .await?;
         }
 
+        match (sharding_method.unwrap_or_default(), &sharding_payload_key) {
+            (ShardingMethod::PayloadKey, None) => {
+                return Err(StorageError::bad_input(
+                    "`sharding_payload_key` is required for the PayloadKey sharding method",
+                ));
+            }
+            (ShardingMethod::Auto | ShardingMethod::Custom, Some(_)) => {
+                return Err(StorageError::bad_input(
+                    "`sharding_payload_key` is only allowed for the PayloadKey sharding method",
+                ));
+            }
+            _ => {}
+        }
+
         let collection_path = self.create_collection_path(collection_name).await?;
         let snapshots_path = self.create_snapshots_path(collection_name).await?;
 
         let shard_number = match sharding_method.unwrap_or_default() {
-            ShardingMethod::Auto => {
+            ShardingMethod::Auto | ShardingMethod::PayloadKey => {
                 if let Some(shard_number) = shard_number {
                     debug_assert_eq!(
                         shard_number as usize,
@@ -106,6 +131,7 @@ impl TableOfContent {
         let write_consistency_factor = write_consistency_factor
             .unwrap_or_else(|| config::default_write_consistency_factor().get());
 
+        let defaults = self.collection_defaults.read().clone();
         let collection_params = CollectionParams {
             vectors,
             sparse_vectors,
@@ -113,7 +139,9 @@ impl TableOfContent {
                 description: "`shard_number` cannot be 0".to_string(),
             })?,
             sharding_method,
-            on_disk_payload: on_disk_payload.unwrap_or(self.storage_config.on_disk_payload),
+            sharding_payload_key,
+            on_disk_payload: on_disk_payload.unwrap_or(defaults.on_disk_payload),
+            payload_storage_backend,
             replication_factor: NonZeroU32::new(replication_factor).ok_or(
                 StorageError::BadInput {
                     description: "`replication_factor` cannot be 0".to_string(),
@@ -125,31 +153,49 @@ impl TableOfContent {
                 },
             )?,
             read_fan_out_factor: None,
+            allow_degraded_reads: None,
+            serve_reads_from_listeners: None,
+            hedge_updates: None,
+            time_travel_window_sec: None,
+            shard_deactivation_timeout_sec: None,
+            search_threads: search_threads
+                .map(|threads| {
+                    NonZeroU32::new(threads).ok_or(StorageError::BadInput {
+                        description: "`search_threads` cannot be 0".to_string(),
+                    })
+                })
+                .transpose()?,
+            expiration_field,
+            soft_delete_window_sec,
+            storage_policy,
+            default_write_ordering: None,
+            default_wait: None,
+            inference: None,
         };
+        if let Some(storage_policy) = &collection_params.storage_policy {
+            storage_policy.check_vector_names(&collection_params)?;
+        }
         let wal_config = match wal_config_diff {
-            None => self.storage_config.wal.clone(),
-            Some(diff) => diff.update(&self.storage_config.wal)?,
+            None => defaults.wal.clone(),
+            Some(diff) => diff.update(&defaults.wal)?,
         };
 
         let optimizers_config = match optimizers_config_diff {
-            None => self.storage_config.optimizers.clone(),
-            Some(diff) => diff.update(&self.storage_config.optimizers)?,
+            None => defaults.optimizers.clone(),
+            Some(diff) => diff.update(&defaults.optimizers)?,
         };
 
         let hnsw_config = match hnsw_config_diff {
-            None => self.storage_config.hnsw_index.clone(),
-            Some(diff) => diff.update(&self.storage_config.hnsw_index)?,
+            None => defaults.hnsw_index.clone(),
+            Some(diff) => diff.update(&defaults.hnsw_index)?,
         };
 
         let quantization_config = match quantization_config {
-            None => self.storage_config.quantization.clone(),
+            None => defaults.quantization.clone(),
             Some(diff) => Some(diff),
         };
 
-        let storage_config = self
-            .storage_config
-            .to_shared_storage_config(self.is_distributed())
-            .into();
+        let storage_config = self.shared_storage_config.clone();
 
         let collection_config = CollectionConfig {
             wal_config,
@@ -157,6 +203,8 @@ impl TableOfContent {
             optimizer_config: optimizers_config,
             hnsw_config,
             quantization_config,
+            strict_mode_config,
+            feature_flags,
         };
         let collection = Collection::new(
             collection_name.to_string(),
@@ -186,6 +234,16 @@ impl TableOfContent {
         )
         .await?;
 
+        collection.create_soft_delete_index().await?;
+        collection.create_expiration_index().await?;
+
+        // Payload indexes of the template
+        for (field_name, field_schema) in payload_indexes {
+            collection
+                .create_payload_index(field_name, field_schema)
+                .await?;
+        }
+
         let local_shards = collection.get_local_shards().await;
 
         {
@@ -205,8 +263,12 @@ impl TableOfContent {
         }
 
         if let Some(init_from) = init_from {
-            self.run_data_initialization(init_from.collection, collection_name.to_string())
-                .await;
+            self.run_data_initialization(
+                init_from.collection,
+                collection_name.to_string(),
+                init_from.filter,
+            )
+            .await;
         }
 
         Ok(true)
@@ -270,6 +332,7 @@ impl TableOfContent {
         &self,
         from_collection: CollectionId,
         to_collection: CollectionId,
+        filter: Option<Filter>,
     ) {
         let collections = self.collections.clone();
         let this_peer_id = self.this_peer_id;
@@ -294,6 +357,7 @@ impl TableOfContent {
                 collections,
                 &from_collection,
                 &to_collection,
+                filter.as_ref(),
                 this_peer_id,
             )
             .await
@@ -14,7 +14,10 @@ impl TableOfContent {
         self.lock_error_message.lock().clone()
     }
 
-    /// Returns an error if the write lock is set
+    /// Returns an error if the write lock is set, or there is not enough free disk space
+    ///
+    /// Not checked for deletes, see
+    /// [`collection::operations::CollectionUpdateOperations::is_write_operation`].
     pub fn check_write_lock(&self) -> Result<(), StorageError> {
         if self.is_write_locked.load(atomic::Ordering::Relaxed) {
             return Err(StorageError::Locked {
@@ -25,9 +28,23 @@ impl TableOfContent {
                     .unwrap_or_else(|| DEFAULT_WRITE_LOCK_ERROR_MESSAGE.to_string()),
             });
         }
+        if let Some(description) = self.disk_space_error.lock().clone() {
+            return Err(StorageError::Locked { description });
+        }
         Ok(())
     }
 
+    /// Reject updates with the given error, until the protection is removed with `None`
+    ///
+    /// Unlike the write lock, set by the user, it is managed by the disk space watchdog.
+    pub fn set_disk_space_protection(&self, error_message: Option<String>) {
+        *self.disk_space_error.lock() = error_message;
+    }
+
+    pub fn is_disk_space_protected(&self) -> bool {
+        self.disk_space_error.lock().is_some()
+    }
+
     pub fn set_locks(&self, is_write_locked: bool, error_message: Option<String>) {
         self.is_write_locked
             .store(is_write_locked, atomic::Ordering::Relaxed);
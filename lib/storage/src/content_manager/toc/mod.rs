@@ -1,13 +1,14 @@
 mod collection_container;
 mod collection_meta_ops;
+mod config_reload;
 mod create_collection;
 mod locks;
 mod point_ops;
+mod shutdown;
 mod snapshots;
 mod temp_directories;
 pub mod transfer;
 
-use std::cmp::max;
 use std::collections::{HashMap, HashSet};
 use std::fs::{create_dir_all, read_dir};
 use std::num::NonZeroU32;
@@ -21,6 +22,8 @@ use api::grpc::qdrant::WaitOnConsensusCommitRequest;
 use api::grpc::transport_channel_pool::AddTimeout;
 use collection::collection::{Collection, RequestShardTransfer};
 use collection::config::{default_replication_factor, CollectionConfig};
+use collection::events::EventNotifier;
+use collection::operations::shared_storage_config::SharedStorageConfig;
 use collection::operations::types::*;
 use collection::shards::channel_service::ChannelService;
 use collection::shards::replica_set;
@@ -29,16 +32,19 @@ use collection::shards::shard::{PeerId, ShardId};
 use collection::telemetry::CollectionTelemetry;
 use futures::future::try_join_all;
 use futures::Future;
-use segment::common::cpu::get_num_cpus;
 use tokio::runtime::Runtime;
-use tokio::sync::{Mutex, RwLock, RwLockReadGuard, Semaphore};
+use tokio::sync::{Mutex, RwLock, RwLockReadGuard};
 use tonic::codegen::InterceptedService;
 use tonic::transport::Channel;
 use tonic::Status;
 
+use self::config_reload::{update_rate_limiter, CollectionDefaults, UpdateRateLimiter};
 use self::transfer::ShardTransferDispatcher;
 use crate::content_manager::alias_mapping::AliasPersistence;
 use crate::content_manager::collection_meta_ops::CreateCollectionOperation;
+use crate::content_manager::collection_templates::{
+    CollectionTemplate, CollectionTemplates, CollectionTemplatesPersistence,
+};
 use crate::content_manager::collections_ops::{Checker, Collections};
 use crate::content_manager::consensus::operation_sender::OperationSender;
 use crate::content_manager::errors::StorageError;
@@ -47,6 +53,7 @@ use crate::types::{PeerAddressById, StorageConfig};
 use crate::ConsensusOperations;
 
 pub const ALIASES_PATH: &str = "aliases";
+pub const COLLECTION_TEMPLATES_PATH: &str = "collection_templates";
 pub const COLLECTIONS_DIR: &str = "collections";
 pub const FULL_SNAPSHOT_FILE_NAME: &str = "full-snapshot";
 
@@ -56,22 +63,32 @@ pub const FULL_SNAPSHOT_FILE_NAME: &str = "full-snapshot";
 pub struct TableOfContent {
     collections: Arc<RwLock<Collections>>,
     pub(super) storage_config: Arc<StorageConfig>,
+    /// Could be changed without restart, the value in `storage_config` is only the initial one
+    snapshots_path: parking_lot::RwLock<String>,
+    /// Configuration shared with all collections, includes the runtime role of this node
+    shared_storage_config: Arc<SharedStorageConfig>,
     search_runtime: Runtime,
     update_runtime: Runtime,
     general_runtime: Runtime,
     alias_persistence: RwLock<AliasPersistence>,
+    /// Configuration presets for new collections, shared by the cluster
+    template_persistence: RwLock<CollectionTemplatesPersistence>,
     pub this_peer_id: PeerId,
     channel_service: ChannelService,
     /// Backlink to the consensus, if none - single node mode
     consensus_proposal_sender: Option<OperationSender>,
     is_write_locked: AtomicBool,
     lock_error_message: parking_lot::Mutex<Option<String>>,
+    /// Reason of rejecting updates, if there is not enough free disk space
+    disk_space_error: parking_lot::Mutex<Option<String>>,
     /// Prevent DDoS of too many concurrent updates in distributed mode.
     /// One external update usually triggers multiple internal updates, which breaks internal
     /// timings. For example, the health check timing and consensus timing.
     ///
     /// If not defined - no rate limiting is applied.
-    update_rate_limiter: Option<Semaphore>,
+    update_rate_limiter: parking_lot::RwLock<Option<UpdateRateLimiter>>,
+    /// Configuration of new collections, could be changed without restart
+    collection_defaults: parking_lot::RwLock<CollectionDefaults>,
     /// A lock to prevent concurrent collection creation.
     /// Effectively, this lock ensures that `create_collection` is called sequentially.
     collection_create_lock: Mutex<()>,
@@ -90,6 +107,7 @@ impl TableOfContent {
         channel_service: ChannelService,
         this_peer_id: PeerId,
         consensus_proposal_sender: Option<OperationSender>,
+        events: EventNotifier,
     ) -> Self {
         let snapshots_path = Path::new(&storage_config.snapshots_path.clone()).to_owned();
         create_dir_all(&snapshots_path).expect("Can't create Snapshots directory");
@@ -103,6 +121,8 @@ impl TableOfContent {
             read_dir(&collections_path).expect("Can't read Collections directory");
         let mut collections: HashMap<String, Collection> = Default::default();
         let is_distributed = consensus_proposal_sender.is_some();
+        let shared_storage_config =
+            Arc::new(storage_config.to_shared_storage_config(is_distributed, events));
         for entry in collection_paths {
             let collection_path = entry
                 .expect("Can't access of one of the collection files")
@@ -128,14 +148,12 @@ impl TableOfContent {
                 panic!("Can't create a directory for snapshot of {collection_name}: {e}")
             });
             log::info!("Loading collection: {}", collection_name);
-            let collection = general_runtime.block_on(Collection::load(
+            let collection = match general_runtime.block_on(Collection::load(
                 collection_name.clone(),
                 this_peer_id,
                 &collection_path,
                 &collection_snapshots_path,
-                storage_config
-                    .to_shared_storage_config(is_distributed)
-                    .into(),
+                shared_storage_config.clone(),
                 channel_service.clone(),
                 Self::change_peer_state_callback(
                     consensus_proposal_sender.clone(),
@@ -153,45 +171,45 @@ impl TableOfContent {
                 ),
                 Some(search_runtime.handle().clone()),
                 Some(update_runtime.handle().clone()),
-            ));
+            )) {
+                Ok(collection) => collection,
+                Err(err) => {
+                    log::error!("Can't load collection {collection_name}: {err}");
+                    continue;
+                }
+            };
 
             collections.insert(collection_name, collection);
         }
         let alias_path = Path::new(&storage_config.storage_path).join(ALIASES_PATH);
         let alias_persistence =
             AliasPersistence::open(alias_path).expect("Can't open database by the provided config");
+        let templates_path =
+            Path::new(&storage_config.storage_path).join(COLLECTION_TEMPLATES_PATH);
+        let template_persistence = CollectionTemplatesPersistence::open(templates_path)
+            .expect("Can't open collection templates by the provided config");
 
-        let rate_limiter = match storage_config.performance.update_rate_limit {
-            Some(limit) => Some(Semaphore::new(limit)),
-            None => {
-                if consensus_proposal_sender.is_some() {
-                    // Auto adjust the rate limit in distributed mode.
-                    // Select number of working threads as a guess.
-                    let limit = max(get_num_cpus(), 2);
-                    log::debug!(
-                        "Auto adjusting update rate limit to {} parallel update requests",
-                        limit
-                    );
-                    Some(Semaphore::new(limit))
-                } else {
-                    None
-                }
-            }
-        };
+        let rate_limiter =
+            update_rate_limiter(storage_config.performance.update_rate_limit, is_distributed);
 
         TableOfContent {
             collections: Arc::new(RwLock::new(collections)),
             storage_config: Arc::new(storage_config.clone()),
+            snapshots_path: parking_lot::RwLock::new(storage_config.snapshots_path.clone()),
+            shared_storage_config,
             search_runtime,
             update_runtime,
             general_runtime,
             alias_persistence: RwLock::new(alias_persistence),
+            template_persistence: RwLock::new(template_persistence),
             this_peer_id,
             channel_service,
             consensus_proposal_sender,
             is_write_locked: AtomicBool::new(false),
             lock_error_message: parking_lot::Mutex::new(None),
-            update_rate_limiter: rate_limiter,
+            disk_space_error: parking_lot::Mutex::new(None),
+            update_rate_limiter: parking_lot::RwLock::new(rate_limiter),
+            collection_defaults: parking_lot::RwLock::new(CollectionDefaults::from(storage_config)),
             collection_create_lock: Default::default(),
             shard_transfer_dispatcher: Default::default(),
         }
@@ -302,10 +320,30 @@ impl TableOfContent {
         Ok(aliases)
     }
 
+    /// All collection templates by their names
+    pub async fn collection_templates(&self) -> CollectionTemplates {
+        self.template_persistence.read().await.state().clone()
+    }
+
+    pub async fn get_collection_template(
+        &self,
+        template_name: &str,
+    ) -> Result<CollectionTemplate, StorageError> {
+        self.template_persistence
+            .read()
+            .await
+            .get(template_name)
+            .cloned()
+            .ok_or_else(|| StorageError::NotFound {
+                description: format!("Collection template {template_name} not found"),
+            })
+    }
+
     pub async fn suggest_shard_distribution(
         &self,
         op: &CreateCollectionOperation,
         suggested_shard_number: NonZeroU32,
+        listener_peers: &HashSet<PeerId>,
     ) -> ShardDistributionProposal {
         let shard_number = op
             .create_collection
@@ -320,21 +358,32 @@ impl TableOfContent {
             .copied()
             .collect();
         known_peers_set.insert(self.this_peer_id());
-        let known_peers: Vec<_> = known_peers_set.into_iter().collect();
+
+        // Active replicas are placed on the query peers, listener peers get a replica of every shard
+        let (listener_peers, query_peers): (Vec<_>, Vec<_>) = known_peers_set
+            .into_iter()
+            .partition(|peer_id| listener_peers.contains(peer_id));
+        let peer_count = listener_peers.len() + query_peers.len();
         let replication_factor = op
             .create_collection
             .replication_factor
             .and_then(NonZeroU32::new)
             .unwrap_or_else(default_replication_factor);
 
-        let shard_distribution =
-            ShardDistributionProposal::new(shard_number, replication_factor, &known_peers);
+        let shard_distribution = if query_peers.is_empty() {
+            ShardDistributionProposal::new(shard_number, replication_factor, &listener_peers)
+        } else {
+            let mut shard_distribution =
+                ShardDistributionProposal::new(shard_number, replication_factor, &query_peers);
+            shard_distribution.add_to_all_shards(&listener_peers);
+            shard_distribution
+        };
 
         log::debug!(
             "Suggesting distribution for {} shards for collection '{}' among {} peers {:?}",
             shard_number,
             op.collection_name,
-            known_peers.len(),
+            peer_count,
             shard_distribution.distribution
         );
         shard_distribution
@@ -1,17 +1,30 @@
+use std::iter;
 use std::time::Duration;
 
+use collection::collection::access_tags::AccessTags;
+use collection::collection::usage::RequestKind;
 use collection::collection::Collection;
 use collection::grouping::group_by::GroupRequest;
 use collection::grouping::GroupBy;
-use collection::operations::consistency_params::ReadConsistency;
+use collection::hybrid_search::{fuse_results, HybridSearchRequest, SearchCandidates};
+use collection::multi_search::{
+    merge_results, CollectionSearchResult, MultiCollectionScoredPoint, MultiCollectionSearchRequest,
+};
+use collection::operations::consistency_params::{ReadConsistency, WriteConsistency};
+use collection::operations::consistency_token::ConsistencyToken;
 use collection::operations::point_ops::WriteOrdering;
 use collection::operations::shard_selector_internal::ShardSelectorInternal;
 use collection::operations::types::*;
+use collection::operations::update_priority::UpdatePriority;
 use collection::operations::CollectionUpdateOperations;
+use collection::search_matrix::{matrix_rows, SearchMatrixRequest, SearchMatrixRow};
+use collection::shards::replica_set::read_priority::ReadPriority;
+use collection::shards::replica_set::stale_reads::StaleReads;
 use collection::{discovery, recommendations};
 use futures::future::try_join_all;
 use segment::types::{ScoredPoint, ShardKey};
 
+use super::config_reload::UpdateRateLimiter;
 use super::TableOfContent;
 use crate::content_manager::errors::StorageError;
 
@@ -33,8 +46,13 @@ impl TableOfContent {
         read_consistency: Option<ReadConsistency>,
         shard_selector: ShardSelectorInternal,
         timeout: Option<Duration>,
+        access_tags: Option<&AccessTags>,
+        read_priority: ReadPriority,
+        stale_reads: Option<&StaleReads>,
     ) -> Result<Vec<ScoredPoint>, StorageError> {
         let collection = self.get_collection(collection_name).await?;
+        collection.check_strict_mode(iter::once(&request)).await?;
+        let _usage = collection.meter_requests(RequestKind::Search, iter::once(&shard_selector));
         recommendations::recommend_by(
             request,
             &collection,
@@ -42,6 +60,9 @@ impl TableOfContent {
             read_consistency,
             shard_selector,
             timeout,
+            access_tags,
+            read_priority,
+            stale_reads,
         )
         .await
         .map_err(|err| err.into())
@@ -63,14 +84,27 @@ impl TableOfContent {
         requests: Vec<(RecommendRequestInternal, ShardSelectorInternal)>,
         read_consistency: Option<ReadConsistency>,
         timeout: Option<Duration>,
+        access_tags: Option<&AccessTags>,
+        read_priority: ReadPriority,
+        stale_reads: Option<&StaleReads>,
     ) -> Result<Vec<Vec<ScoredPoint>>, StorageError> {
         let collection = self.get_collection(collection_name).await?;
+        collection
+            .check_strict_mode(requests.iter().map(|(request, _)| request))
+            .await?;
+        let _usage = collection.meter_requests(
+            RequestKind::Search,
+            requests.iter().map(|(_, shard_selector)| shard_selector),
+        );
         recommendations::recommend_batch_by(
             requests,
             &collection,
             |name| self.get_collection_opt(name),
             read_consistency,
             timeout,
+            access_tags,
+            read_priority,
+            stale_reads,
         )
         .await
         .map_err(|err| err.into())
@@ -86,6 +120,10 @@ impl TableOfContent {
     /// * `shard_selection` - which local shard to use
     /// * `timeout` - how long to wait for the response
     /// * `read_consistency` - consistency level
+    /// * `consistency_token` - token of the updates, which should be visible to the search
+    /// * `access_tags` - restrict the search to points with any of these tags
+    /// * `read_priority` - priority of the reads, low priority ones could be served by listeners
+    /// * `stale_reads` - marked if any of the reads is served by a degraded replica
     ///
     /// # Result
     ///
@@ -95,16 +133,366 @@ impl TableOfContent {
         collection_name: &str,
         request: CoreSearchRequestBatch,
         read_consistency: Option<ReadConsistency>,
+        consistency_token: Option<&ConsistencyToken>,
         shard_selection: ShardSelectorInternal,
         timeout: Option<Duration>,
+        access_tags: Option<&AccessTags>,
+        read_priority: ReadPriority,
+        stale_reads: Option<&StaleReads>,
     ) -> Result<Vec<Vec<ScoredPoint>>, StorageError> {
         let collection = self.get_collection(collection_name).await?;
         collection
-            .core_search_batch(request, read_consistency, shard_selection, timeout)
+            .check_strict_mode(request.searches.iter())
+            .await?;
+        let _usage = collection.meter_requests(
+            RequestKind::Search,
+            request.searches.iter().map(|_| &shard_selection),
+        );
+        collection
+            .core_search_batch(
+                request,
+                read_consistency,
+                consistency_token,
+                shard_selection,
+                timeout,
+                access_tags,
+                read_priority,
+                stale_reads,
+            )
+            .await
+            .map_err(|err| err.into())
+    }
+
+    /// Same as [`TableOfContent::core_search_batch`], but returns results of the healthy shards
+    /// together with the list of shards which failed, instead of failing the whole request
+    ///
+    /// # Result
+    ///
+    /// Points with search score and failed shards
+    pub async fn core_search_batch_partial(
+        &self,
+        collection_name: &str,
+        request: CoreSearchRequestBatch,
+        read_consistency: Option<ReadConsistency>,
+        consistency_token: Option<&ConsistencyToken>,
+        shard_selection: ShardSelectorInternal,
+        timeout: Option<Duration>,
+        access_tags: Option<&AccessTags>,
+        read_priority: ReadPriority,
+        stale_reads: Option<&StaleReads>,
+    ) -> Result<PartialSearchBatchResult, StorageError> {
+        let collection = self.get_collection(collection_name).await?;
+        collection
+            .check_strict_mode(request.searches.iter())
+            .await?;
+        let _usage = collection.meter_requests(
+            RequestKind::Search,
+            request.searches.iter().map(|_| &shard_selection),
+        );
+        collection
+            .core_search_batch_partial(
+                request,
+                read_consistency,
+                consistency_token,
+                shard_selection,
+                timeout,
+                access_tags,
+                read_priority,
+                stale_reads,
+            )
             .await
             .map_err(|err| err.into())
     }
 
+    /// Run the same searches in several collections, and merge the results of each search
+    ///
+    /// Collections are searched concurrently. Each of them returns `offset + limit` best points,
+    /// which are then normalized according to the request and merged.
+    ///
+    /// # Arguments
+    ///
+    /// * `request` - [`MultiCollectionSearchRequest`]
+    /// * `read_consistency` - consistency level, applied to each collection
+    /// * `timeout` - how long to wait for the response of each collection
+    ///
+    /// # Result
+    ///
+    /// Points with search score and the collection they are found in
+    pub async fn multi_collection_search(
+        &self,
+        request: MultiCollectionSearchRequest,
+        read_consistency: Option<ReadConsistency>,
+        timeout: Option<Duration>,
+        read_priority: ReadPriority,
+        stale_reads: Option<&StaleReads>,
+    ) -> Result<Vec<Vec<MultiCollectionScoredPoint>>, StorageError> {
+        request.check_collections()?;
+
+        let MultiCollectionSearchRequest {
+            collections,
+            searches,
+            score_normalization,
+        } = request;
+
+        let searches: Vec<CoreSearchRequest> = searches.into_iter().map(Into::into).collect();
+
+        // Each collection returns the first `offset + limit` points, the offset is applied to
+        // the merged results
+        let collection_searches = CoreSearchRequestBatch {
+            searches: searches
+                .iter()
+                .map(|search| CoreSearchRequest {
+                    limit: search.offset + search.limit,
+                    offset: 0,
+                    ..search.clone()
+                })
+                .collect(),
+        };
+
+        let collection_results = try_join_all(collections.iter().map(|collection_name| {
+            let collection_searches = collection_searches.clone();
+            async move {
+                let collection = self.get_collection(collection_name).await?;
+                collection.check_strict_mode(searches.iter()).await?;
+                let _usage = collection.meter_requests(
+                    RequestKind::Search,
+                    searches.iter().map(|_| &ShardSelectorInternal::All),
+                );
+
+                let mut orders = Vec::with_capacity(collection_searches.searches.len());
+                for search in &collection_searches.searches {
+                    orders.push(collection.query_order(&search.query).await?);
+                }
+
+                let results = collection
+                    .core_search_batch(
+                        collection_searches,
+                        read_consistency,
+                        None,
+                        ShardSelectorInternal::All,
+                        timeout,
+                        None,
+                        read_priority,
+                        stale_reads,
+                    )
+                    .await?;
+
+                Ok::<_, StorageError>((collection_name, orders, results))
+            }
+        }))
+        .await?;
+
+        // Results of the collections, grouped by search
+        let mut results_by_search: Vec<Vec<CollectionSearchResult>> =
+            searches.iter().map(|_| Vec::new()).collect();
+        for (collection_name, orders, results) in collection_results {
+            for ((search_results, order), points) in
+                results_by_search.iter_mut().zip(orders).zip(results)
+            {
+                search_results.push(CollectionSearchResult {
+                    collection: collection_name.clone(),
+                    order,
+                    points,
+                });
+            }
+        }
+
+        results_by_search
+            .into_iter()
+            .zip(&searches)
+            .map(|(results, search)| {
+                merge_results(results, score_normalization, search.offset, search.limit)
+                    .map_err(StorageError::from)
+            })
+            .collect()
+    }
+
+    /// Search for the closest points, paginated with a cursor
+    ///
+    /// # Arguments
+    ///
+    /// * `collection_name` - in what collection do we search
+    /// * `request` - [`CoreSearchRequest`], `offset` is not allowed
+    /// * `cursor` - cursor returned with the previous page, `None` for the first page
+    /// * `shard_selection` - which local shard to use
+    /// * `timeout` - how long to wait for the response
+    /// * `read_consistency` - consistency level
+    /// * `consistency_token` - token of the updates, which should be visible to the search
+    /// * `access_tags` - restrict the search to points with any of these tags
+    /// * `read_priority` - priority of the reads, low priority ones could be served by listeners
+    /// * `stale_reads` - marked if any of the reads is served by a degraded replica
+    ///
+    /// # Result
+    ///
+    /// Points with search score and the cursor of the next page
+    pub async fn search_page(
+        &self,
+        collection_name: &str,
+        request: CoreSearchRequest,
+        cursor: Option<&str>,
+        read_consistency: Option<ReadConsistency>,
+        consistency_token: Option<&ConsistencyToken>,
+        shard_selection: ShardSelectorInternal,
+        timeout: Option<Duration>,
+        access_tags: Option<&AccessTags>,
+        read_priority: ReadPriority,
+        stale_reads: Option<&StaleReads>,
+    ) -> Result<SearchPageResult, StorageError> {
+        let collection = self.get_collection(collection_name).await?;
+        collection.check_strict_mode(iter::once(&request)).await?;
+        let _usage = collection.meter_requests(RequestKind::Search, iter::once(&shard_selection));
+        collection
+            .search_page(
+                request,
+                cursor,
+                read_consistency,
+                consistency_token,
+                &shard_selection,
+                timeout,
+                access_tags,
+                read_priority,
+                stale_reads,
+            )
+            .await
+            .map_err(|err| err.into())
+    }
+
+    /// Search by a dense and a sparse vector, with the results fused into one list
+    ///
+    /// # Arguments
+    ///
+    /// * `collection_name` - in what collection do we search
+    /// * `request` - [`HybridSearchRequest`], `shard_key` and `consistency_token` are ignored
+    /// * `shard_selection` - which local shard to use
+    /// * `timeout` - how long to wait for the response
+    /// * `read_consistency` - consistency level
+    /// * `consistency_token` - token of the updates, which should be visible to the search
+    ///
+    /// # Result
+    ///
+    /// Points with the fused score
+    pub async fn hybrid_search(
+        &self,
+        collection_name: &str,
+        request: HybridSearchRequest,
+        read_consistency: Option<ReadConsistency>,
+        consistency_token: Option<&ConsistencyToken>,
+        shard_selection: ShardSelectorInternal,
+        timeout: Option<Duration>,
+        read_priority: ReadPriority,
+        stale_reads: Option<&StaleReads>,
+    ) -> Result<Vec<ScoredPoint>, StorageError> {
+        let collection = self.get_collection(collection_name).await?;
+        let [dense_search, sparse_search] = request.searches();
+        // Hybrid search is a single request, not a batch
+        collection
+            .check_strict_mode(iter::once(&dense_search))
+            .await?;
+        collection
+            .check_strict_mode(iter::once(&sparse_search))
+            .await?;
+        let _usage = collection.meter_requests(RequestKind::Search, iter::once(&shard_selection));
+
+        let dense_order = collection.query_order(&dense_search.query).await?;
+        let sparse_order = collection.query_order(&sparse_search.query).await?;
+
+        // Both searches cut their candidates across shards, before the fusion
+        let mut results = collection
+            .core_search_batch(
+                CoreSearchRequestBatch {
+                    searches: vec![dense_search, sparse_search],
+                },
+                read_consistency,
+                consistency_token,
+                shard_selection,
+                timeout,
+                None,
+                read_priority,
+                stale_reads,
+            )
+            .await?
+            .into_iter();
+        let dense = SearchCandidates {
+            order: dense_order,
+            points: results.next().unwrap_or_default(),
+        };
+        let sparse = SearchCandidates {
+            order: sparse_order,
+            points: results.next().unwrap_or_default(),
+        };
+
+        Ok(fuse_results(
+            dense,
+            sparse,
+            request.fusion,
+            request.offset.unwrap_or_default(),
+            request.limit,
+        ))
+    }
+
+    /// Nearest neighbors of each of the selected points among the other selected points
+    ///
+    /// # Arguments
+    ///
+    /// * `collection_name` - in what collection do we search
+    /// * `request` - [`SearchMatrixRequest`], `shard_key` and `consistency_token` are ignored
+    /// * `shard_selection` - which local shard to use
+    /// * `timeout` - how long to wait for the response
+    /// * `read_consistency` - consistency level
+    /// * `consistency_token` - token of the updates, which should be visible to the search
+    ///
+    /// # Result
+    ///
+    /// Row of the neighbors for each selected point, which has the vector
+    pub async fn search_matrix(
+        &self,
+        collection_name: &str,
+        request: SearchMatrixRequest,
+        read_consistency: Option<ReadConsistency>,
+        consistency_token: Option<&ConsistencyToken>,
+        shard_selection: ShardSelectorInternal,
+        timeout: Option<Duration>,
+        read_priority: ReadPriority,
+        stale_reads: Option<&StaleReads>,
+    ) -> Result<Vec<SearchMatrixRow>, StorageError> {
+        let collection = self.get_collection(collection_name).await?;
+        let select_request = request.select_request();
+        collection
+            .check_strict_mode(iter::once(&select_request))
+            .await?;
+        let _usage = collection.meter_requests(RequestKind::Search, iter::once(&shard_selection));
+
+        let points = collection
+            .scroll_by(
+                select_request,
+                read_consistency,
+                &shard_selection,
+                None,
+                read_priority,
+                stale_reads,
+            )
+            .await?
+            .points;
+
+        // All rows are searched in a single batch
+        let (ids, batch) = request.searches(&points);
+        collection.check_strict_mode(batch.searches.iter()).await?;
+        let results = collection
+            .core_search_batch(
+                batch,
+                read_consistency,
+                consistency_token,
+                shard_selection,
+                timeout,
+                None,
+                read_priority,
+                stale_reads,
+            )
+            .await?;
+
+        Ok(matrix_rows(ids, results, request.limit))
+    }
+
     /// Count points in the collection.
     ///
     /// # Arguments
@@ -123,10 +511,22 @@ impl TableOfContent {
         request: CountRequestInternal,
         read_consistency: Option<ReadConsistency>,
         shard_selection: ShardSelectorInternal,
+        access_tags: Option<&AccessTags>,
+        read_priority: ReadPriority,
+        stale_reads: Option<&StaleReads>,
     ) -> Result<CountResult, StorageError> {
         let collection = self.get_collection(collection_name).await?;
+        collection.check_strict_mode(iter::once(&request)).await?;
+        let _usage = collection.meter_requests(RequestKind::Read, iter::once(&shard_selection));
         collection
-            .count(request, read_consistency, &shard_selection)
+            .count(
+                request,
+                read_consistency,
+                &shard_selection,
+                access_tags,
+                read_priority,
+                stale_reads,
+            )
             .await
             .map_err(|err| err.into())
     }
@@ -148,10 +548,21 @@ impl TableOfContent {
         request: PointRequestInternal,
         read_consistency: Option<ReadConsistency>,
         shard_selection: ShardSelectorInternal,
+        access_tags: Option<&AccessTags>,
+        read_priority: ReadPriority,
+        stale_reads: Option<&StaleReads>,
     ) -> Result<Vec<Record>, StorageError> {
         let collection = self.get_collection(collection_name).await?;
+        let _usage = collection.meter_requests(RequestKind::Read, iter::once(&shard_selection));
         collection
-            .retrieve(request, read_consistency, &shard_selection)
+            .retrieve(
+                request,
+                read_consistency,
+                &shard_selection,
+                access_tags,
+                read_priority,
+                stale_reads,
+            )
             .await
             .map_err(|err| err.into())
     }
@@ -163,15 +574,23 @@ impl TableOfContent {
         read_consistency: Option<ReadConsistency>,
         shard_selection: ShardSelectorInternal,
         timeout: Option<Duration>,
+        access_tags: Option<&AccessTags>,
+        read_priority: ReadPriority,
+        stale_reads: Option<&StaleReads>,
     ) -> Result<GroupsResult, StorageError> {
         let collection = self.get_collection(collection_name).await?;
+        collection.check_strict_mode(iter::once(&request)).await?;
+        let _usage = collection.meter_requests(RequestKind::Search, iter::once(&shard_selection));
 
         let collection_by_name = |name| self.get_collection_opt(name);
 
         let group_by = GroupBy::new(request, &collection, collection_by_name)
             .set_read_consistency(read_consistency)
             .set_shard_selection(shard_selection)
-            .set_timeout(timeout);
+            .set_timeout(timeout)
+            .set_access_tags(access_tags.cloned())
+            .set_read_priority(read_priority)
+            .set_stale_reads(stale_reads.cloned());
 
         group_by
             .execute()
@@ -187,8 +606,13 @@ impl TableOfContent {
         read_consistency: Option<ReadConsistency>,
         shard_selector: ShardSelectorInternal,
         timeout: Option<Duration>,
+        access_tags: Option<&AccessTags>,
+        read_priority: ReadPriority,
+        stale_reads: Option<&StaleReads>,
     ) -> Result<Vec<ScoredPoint>, StorageError> {
         let collection = self.get_collection(collection_name).await?;
+        collection.check_strict_mode(iter::once(&request)).await?;
+        let _usage = collection.meter_requests(RequestKind::Search, iter::once(&shard_selector));
         discovery::discover(
             request,
             &collection,
@@ -196,6 +620,9 @@ impl TableOfContent {
             read_consistency,
             shard_selector,
             timeout,
+            access_tags,
+            read_priority,
+            stale_reads,
         )
         .await
         .map_err(|err| err.into())
@@ -207,8 +634,18 @@ impl TableOfContent {
         requests: Vec<(DiscoverRequestInternal, ShardSelectorInternal)>,
         read_consistency: Option<ReadConsistency>,
         timeout: Option<Duration>,
+        access_tags: Option<&AccessTags>,
+        read_priority: ReadPriority,
+        stale_reads: Option<&StaleReads>,
     ) -> Result<Vec<Vec<ScoredPoint>>, StorageError> {
         let collection = self.get_collection(collection_name).await?;
+        collection
+            .check_strict_mode(requests.iter().map(|(request, _)| request))
+            .await?;
+        let _usage = collection.meter_requests(
+            RequestKind::Search,
+            requests.iter().map(|(_, shard_selector)| shard_selector),
+        );
 
         discovery::discover_batch(
             requests,
@@ -216,6 +653,9 @@ impl TableOfContent {
             |name| self.get_collection_opt(name),
             read_consistency,
             timeout,
+            access_tags,
+            read_priority,
+            stale_reads,
         )
         .await
         .map_err(|err| err.into())
@@ -238,10 +678,38 @@ impl TableOfContent {
         request: ScrollRequestInternal,
         read_consistency: Option<ReadConsistency>,
         shard_selection: ShardSelectorInternal,
+        access_tags: Option<&AccessTags>,
+        read_priority: ReadPriority,
+        stale_reads: Option<&StaleReads>,
     ) -> Result<ScrollResult, StorageError> {
         let collection = self.get_collection(collection_name).await?;
+        collection.check_strict_mode(iter::once(&request)).await?;
+        let _usage = collection.meter_requests(RequestKind::Read, iter::once(&shard_selection));
         collection
-            .scroll_by(request, read_consistency, &shard_selection)
+            .scroll_by(
+                request,
+                read_consistency,
+                &shard_selection,
+                access_tags,
+                read_priority,
+                stale_reads,
+            )
+            .await
+            .map_err(|err| err.into())
+    }
+
+    /// Open a scroll session: read snapshot of the collection for the following scroll requests
+    pub async fn open_scroll_session(
+        &self,
+        collection_name: &str,
+        request: OpenScrollSession,
+        read_consistency: Option<ReadConsistency>,
+        shard_selection: ShardSelectorInternal,
+    ) -> Result<ScrollSessionInfo, StorageError> {
+        let collection = self.get_collection(collection_name).await?;
+        let _usage = collection.meter_requests(RequestKind::Read, iter::once(&shard_selection));
+        collection
+            .open_scroll_session(request, read_consistency, &shard_selection)
             .await
             .map_err(|err| err.into())
     }
@@ -252,6 +720,10 @@ impl TableOfContent {
         operation: CollectionUpdateOperations,
         wait: bool,
         ordering: WriteOrdering,
+        shard_deactivation_timeout: Option<Duration>,
+        write_consistency: Option<WriteConsistency>,
+        timeout: Option<Duration>,
+        priority: Option<UpdatePriority>,
     ) -> Result<UpdateResult, StorageError> {
         if shard_keys.is_empty() {
             return Err(StorageError::bad_input("Empty shard keys selection"));
@@ -260,25 +732,105 @@ impl TableOfContent {
         let updates: Vec<_> = shard_keys
             .into_iter()
             .map(|shard_key| {
-                collection.update_from_client(operation.clone(), wait, ordering, Some(shard_key))
+                collection.update_from_client(
+                    operation.clone(),
+                    wait,
+                    ordering,
+                    Some(shard_key),
+                    shard_deactivation_timeout,
+                    write_consistency,
+                    timeout,
+                    priority,
+                )
             })
             .collect();
 
-        let results = try_join_all(updates).await?;
+        let mut results = try_join_all(updates).await?.into_iter();
+        let mut result = results.next().unwrap();
+
+        // Acknowledgments of the replicas of all selected shard keys
+        for other in results {
+            if let Some(acknowledgments) = other.acknowledgments {
+                result
+                    .acknowledgments
+                    .get_or_insert_with(Vec::new)
+                    .extend(acknowledgments);
+            }
+        }
 
-        Ok(results.into_iter().next().unwrap())
+        Ok(result)
     }
 
+    /// Apply update operation to the collection
+    ///
+    /// `shard_deactivation_timeout` overrides the collection setting of how long to wait for the
+    /// deactivation of replicas, which failed to apply the operation.
+    ///
+    /// `write_consistency` overrides the collection setting of how many replicas must apply the
+    /// operation for it to succeed.
+    ///
+    /// `timeout` limits the time to wait for the replicas to apply the operation.
+    ///
+    /// `priority` of the operation, bulk operations yield the update queues to interactive ones.
+    ///
+    /// `wait` and `ordering`, which are not specified, fall back to the collection defaults.
+    /// Updates forwarded by peers don't use the defaults, as the first node already resolved them.
+    ///
+    /// Client updates can't modify reserved payload fields.
     pub async fn update(
         &self,
         collection_name: &str,
         operation: CollectionUpdateOperations,
-        wait: bool,
-        ordering: WriteOrdering,
+        wait: Option<bool>,
+        ordering: Option<WriteOrdering>,
+        shard_deactivation_timeout: Option<Duration>,
+        write_consistency: Option<WriteConsistency>,
+        timeout: Option<Duration>,
+        priority: Option<UpdatePriority>,
+        shard_selector: ShardSelectorInternal,
+    ) -> Result<UpdateResult, StorageError> {
+        // Operation is already checked on the first node
+        if !shard_selector.is_shard_id() {
+            operation.check_reserved_payload()?;
+        }
+        self.update_with_reserved_payload(
+            collection_name,
+            operation,
+            wait,
+            ordering,
+            shard_deactivation_timeout,
+            write_consistency,
+            timeout,
+            priority,
+            shard_selector,
+        )
+        .await
+    }
+
+    /// Apply update operation to the collection, which may modify reserved payload fields
+    ///
+    /// Used for the operations, which are built internally on behalf of the client, like restoring
+    /// of soft-deleted points. See [`TableOfContent::update`] for the parameters.
+    pub async fn update_with_reserved_payload(
+        &self,
+        collection_name: &str,
+        operation: CollectionUpdateOperations,
+        wait: Option<bool>,
+        ordering: Option<WriteOrdering>,
+        shard_deactivation_timeout: Option<Duration>,
+        write_consistency: Option<WriteConsistency>,
+        timeout: Option<Duration>,
+        priority: Option<UpdatePriority>,
         shard_selector: ShardSelectorInternal,
     ) -> Result<UpdateResult, StorageError> {
         let collection = self.get_collection(collection_name).await?;
 
+        let (wait, ordering) = if shard_selector.is_shard_id() {
+            (wait.unwrap_or(false), ordering.unwrap_or_default())
+        } else {
+            collection.resolve_update_params(wait, ordering).await
+        };
+
         // Ordered operation flow:
         //
         // ┌───────────────────┐
@@ -303,12 +855,17 @@ impl TableOfContent {
         // │ Updating node     │ <- update_from_peer
         // └───────────────────┘
 
-        let _rate_limit = match &self.update_rate_limiter {
+        let rate_limiter = self
+            .update_rate_limiter
+            .read()
+            .as_ref()
+            .map(UpdateRateLimiter::semaphore);
+        let _rate_limit = match rate_limiter {
             None => None,
             Some(rate_limiter) => {
                 // We only want to rate limit the first node in the chain
                 if !shard_selector.is_shard_id() {
-                    Some(rate_limiter.acquire().await)
+                    Some(rate_limiter.acquire_owned().await)
                 } else {
                     None
                 }
@@ -317,34 +874,97 @@ impl TableOfContent {
         if operation.is_write_operation() {
             self.check_write_lock()?;
         }
+        // Operation is already checked on the first node
+        if !shard_selector.is_shard_id() {
+            collection.check_strict_mode_update(&operation).await?;
+        }
+        // Updates forwarded by peers are not metered
+        let _usage = collection.meter_requests(RequestKind::Update, iter::once(&shard_selector));
         let res = match shard_selector {
             ShardSelectorInternal::Empty => {
                 collection
-                    .update_from_client(operation, wait, ordering, None)
+                    .update_from_client(
+                        operation,
+                        wait,
+                        ordering,
+                        None,
+                        shard_deactivation_timeout,
+                        write_consistency,
+                        timeout,
+                        priority,
+                    )
                     .await?
             }
             ShardSelectorInternal::All => {
                 let shard_keys = collection.get_shard_keys().await;
                 if shard_keys.is_empty() {
                     collection
-                        .update_from_client(operation, wait, ordering, None)
+                        .update_from_client(
+                            operation,
+                            wait,
+                            ordering,
+                            None,
+                            shard_deactivation_timeout,
+                            write_consistency,
+                            timeout,
+                            priority,
+                        )
                         .await?
                 } else {
-                    Self::_update_shard_keys(&collection, shard_keys, operation, wait, ordering)
-                        .await?
+                    Self::_update_shard_keys(
+                        &collection,
+                        shard_keys,
+                        operation,
+                        wait,
+                        ordering,
+                        shard_deactivation_timeout,
+                        write_consistency,
+                        timeout,
+                        priority,
+                    )
+                    .await?
                 }
             }
             ShardSelectorInternal::ShardKey(shard_key) => {
                 collection
-                    .update_from_client(operation, wait, ordering, Some(shard_key))
+                    .update_from_client(
+                        operation,
+                        wait,
+                        ordering,
+                        Some(shard_key),
+                        shard_deactivation_timeout,
+                        write_consistency,
+                        timeout,
+                        priority,
+                    )
                     .await?
             }
             ShardSelectorInternal::ShardKeys(shard_keys) => {
-                Self::_update_shard_keys(&collection, shard_keys, operation, wait, ordering).await?
+                Self::_update_shard_keys(
+                    &collection,
+                    shard_keys,
+                    operation,
+                    wait,
+                    ordering,
+                    shard_deactivation_timeout,
+                    write_consistency,
+                    timeout,
+                    priority,
+                )
+                .await?
             }
             ShardSelectorInternal::ShardId(shard_selection) => {
                 collection
-                    .update_from_peer(operation, shard_selection, wait, ordering)
+                    .update_from_peer(
+                        operation,
+                        shard_selection,
+                        wait,
+                        ordering,
+                        shard_deactivation_timeout,
+                        write_consistency,
+                        timeout,
+                        priority,
+                    )
                     .await?
             }
         };
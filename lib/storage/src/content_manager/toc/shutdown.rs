@@ -0,0 +1,43 @@
+//! Coordinated shutdown of the storage before the process exits
+
+use std::time::{Duration, Instant};
+
+use super::TableOfContent;
+
+const SHUTDOWN_LOCK_MESSAGE: &str = "Node is shutting down";
+
+impl TableOfContent {
+    /// Stop all local shards, so no data has to be recovered on the next start:
+    ///
+    /// 1. Stop accepting updates
+    /// 2. Apply updates, which are already queued
+    /// 3. Cancel running optimizations
+    /// 4. Flush segments and WAL
+    ///
+    /// Waiting for updates and optimizations is limited by `timeout`, data is flushed regardless.
+    pub async fn shutdown(&self, timeout: Duration) {
+        let started = Instant::now();
+        let deadline = started + timeout;
+
+        log::info!(
+            "Shutting down storage, deadline is {}s",
+            timeout.as_secs_f64()
+        );
+        self.set_locks(true, Some(SHUTDOWN_LOCK_MESSAGE.to_string()));
+
+        let collections = self.collections.read().await;
+        let collections_count = collections.len();
+        for (i, (collection_name, collection)) in collections.iter().enumerate() {
+            log::info!(
+                "Shutting down collection {collection_name} ({}/{collections_count})",
+                i + 1,
+            );
+            collection.shutdown(deadline).await;
+        }
+
+        log::info!(
+            "Storage is shut down in {:.1}s",
+            started.elapsed().as_secs_f64()
+        );
+    }
+}
@@ -1,6 +1,7 @@
 use std::path::{Path, PathBuf};
 
-use collection::operations::snapshot_ops::SnapshotDescription;
+use collection::common::snapshot_storage::SnapshotStorage;
+use collection::operations::snapshot_ops::{SnapshotCreationPriority, SnapshotDescription};
 use collection::shards::replica_set::ReplicaState;
 use collection::shards::shard::{PeerId, ShardId};
 use collection::shards::transfer::{ShardTransfer, ShardTransferMethod};
@@ -11,8 +12,12 @@ use crate::content_manager::consensus_ops::ConsensusOperations;
 use crate::content_manager::errors::StorageError;
 
 impl TableOfContent {
-    pub fn snapshots_path(&self) -> &str {
-        &self.storage_config.snapshots_path
+    pub fn snapshots_path(&self) -> String {
+        self.snapshots_path.read().clone()
+    }
+
+    pub fn snapshot_storage(&self) -> &SnapshotStorage {
+        &self.shared_storage_config.snapshot_storage
     }
 
     pub fn collection_snapshots_path(snapshots_path: &Path, collection_name: &str) -> PathBuf {
@@ -20,10 +25,7 @@ impl TableOfContent {
     }
 
     pub fn snapshots_path_for_collection(&self, collection_name: &str) -> PathBuf {
-        Self::collection_snapshots_path(
-            Path::new(&self.storage_config.snapshots_path),
-            collection_name,
-        )
+        Self::collection_snapshots_path(Path::new(&self.snapshots_path()), collection_name)
     }
 
     pub async fn create_snapshots_path(
@@ -45,13 +47,26 @@ impl TableOfContent {
     pub async fn create_snapshot(
         &self,
         collection_name: &str,
+        priority: SnapshotCreationPriority,
     ) -> Result<SnapshotDescription, StorageError> {
         let collection = self.get_collection(collection_name).await?;
         // We want to use temp dir inside the temp_path (storage if not specified), because it is possible, that
         // snapshot directory is mounted as network share and multiple writes to it could be slow
         let temp_dir = self.optional_temp_or_storage_temp_path()?;
         Ok(collection
-            .create_snapshot(&temp_dir, self.this_peer_id)
+            .create_snapshot(&temp_dir, self.this_peer_id, priority)
+            .await?)
+    }
+
+    /// Create a low priority snapshot, which can be told apart from the ones requested by users
+    pub async fn create_scheduled_snapshot(
+        &self,
+        collection_name: &str,
+    ) -> Result<SnapshotDescription, StorageError> {
+        let collection = self.get_collection(collection_name).await?;
+        let temp_dir = self.optional_temp_or_storage_temp_path()?;
+        Ok(collection
+            .create_scheduled_snapshot(&temp_dir, self.this_peer_id)
             .await?)
     }
 
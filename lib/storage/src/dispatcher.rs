@@ -48,6 +48,22 @@ impl Dispatcher {
         operation: CollectionMetaOperations,
         wait_timeout: Option<Duration>,
     ) -> Result<bool, StorageError> {
+        match &operation {
+            CollectionMetaOperations::CreateCollection(op) => {
+                self.toc.check_collection_create(op).await?;
+            }
+            CollectionMetaOperations::UpdateCollection(op) => {
+                self.toc.check_collection_update(op).await?;
+            }
+            CollectionMetaOperations::SetStoredQuery(op) => {
+                self.toc.check_stored_query(op).await?;
+            }
+            CollectionMetaOperations::SetCollectionTemplate(op) => {
+                op.template.check()?;
+            }
+            _ => {}
+        }
+
         // if distributed deployment is enabled
         if let Some(state) = self.consensus_state.as_ref() {
             // List of operations to await for collection to be operational
@@ -56,17 +72,27 @@ impl Dispatcher {
             let op = match operation {
                 CollectionMetaOperations::CreateCollection(mut op) => {
                     self.toc.check_write_lock()?;
+                    // Template may define the sharding of the collection
+                    self.toc
+                        .apply_collection_template(&mut op.create_collection)
+                        .await?;
                     if !op.is_distribution_set() {
                         match op.create_collection.sharding_method.unwrap_or_default() {
-                            ShardingMethod::Auto => {
-                                // Suggest even distribution of shards across nodes
-                                let number_of_peers = state.0.peer_count();
+                            ShardingMethod::Auto | ShardingMethod::PayloadKey => {
+                                // Suggest even distribution of shards across query nodes
+                                let listener_peers = state.0.listener_peers();
+                                let number_of_peers = state
+                                    .0
+                                    .peer_count()
+                                    .saturating_sub(listener_peers.len())
+                                    .max(1);
                                 let shard_distribution = self
                                     .toc
                                     .suggest_shard_distribution(
                                         &op,
                                         NonZeroU32::new(number_of_peers as u32)
                                             .expect("Peer count should be always >= 1"),
+                                        &listener_peers,
                                     )
                                     .await;
 
@@ -1,13 +1,21 @@
 use std::collections::HashMap;
 use std::num::NonZeroUsize;
+use std::path::Path;
 use std::time::Duration;
 
 use chrono::{DateTime, Utc};
+use collection::common::io_budget::IoBudget;
+use collection::common::search_admission::SearchAdmission;
+use collection::common::snapshot_storage::{SnapshotStorage, SnapshotsConfig};
 use collection::config::WalConfig;
+use collection::events::EventNotifier;
+use collection::operations::feature_flags::FeatureFlags;
 use collection::operations::shared_storage_config::SharedStorageConfig;
-use collection::operations::types::NodeType;
+use collection::operations::types::{NodeType, ReadLoadBalancing};
 use collection::optimizers_builder::OptimizersConfig;
+use collection::shards::scrubber::ScrubberConfig;
 use collection::shards::shard::PeerId;
+use collection::shards::transfer::throttle::ShardTransferConfig;
 use memory::madvise;
 use schemars::JsonSchema;
 use segment::common::anonymize::Anonymize;
@@ -18,7 +26,7 @@ use validator::Validate;
 
 pub type PeerAddressById = HashMap<PeerId, Uri>;
 
-#[derive(Debug, Deserialize, Serialize, Clone)]
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
 pub struct PerformanceConfig {
     pub max_search_threads: usize,
     #[serde(default = "default_max_optimization_threads")]
@@ -27,12 +35,131 @@ pub struct PerformanceConfig {
     pub update_rate_limit: Option<usize>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub search_timeout_sec: Option<usize>,
+    /// Strategy of choosing remote replicas for reads
+    #[serde(default)]
+    pub read_load_balancing: ReadLoadBalancing,
+    /// Disk throughput of all low priority tasks together, e.g. snapshots created with
+    /// `snapshot_priority=low`, in megabytes per second. If 0 - no limit
+    #[serde(default = "default_low_priority_io_limit_mb")]
+    pub low_priority_io_limit_mb: usize,
+    /// Max number of searches executed on the node at the same time. If not set - no limit
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_concurrent_searches: Option<usize>,
+    /// Max number of searches waiting for execution, once the concurrency limit is reached.
+    /// Searches, which do not fit into the queue, are rejected
+    #[serde(default = "default_search_queue_size")]
+    pub search_queue_size: usize,
+    /// Max time in milliseconds a search waits in the queue before it is rejected
+    #[serde(default = "default_search_queue_timeout_ms")]
+    pub search_queue_timeout_ms: u64,
 }
 
 const fn default_max_optimization_threads() -> usize {
     1
 }
 
+const fn default_low_priority_io_limit_mb() -> usize {
+    64
+}
+
+const fn default_search_queue_size() -> usize {
+    128
+}
+
+const fn default_search_queue_timeout_ms() -> u64 {
+    5000
+}
+
+/// Periodic creation of snapshots of all collections
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Eq, Default, Validate)]
+pub struct SnapshotScheduleConfig {
+    /// Interval between the snapshots of each collection. If not set - snapshots are not scheduled
+    #[serde(default)]
+    #[validate(range(min = 1))]
+    pub snapshot_interval_sec: Option<u64>,
+    /// After each scheduled snapshot, delete the oldest scheduled snapshots of the collection, so
+    /// only this many are kept. Manually created snapshots are neither counted nor deleted, and
+    /// neither are the bases of the kept incremental snapshots.
+    /// If not set - snapshots are not deleted
+    #[serde(default)]
+    #[validate(range(min = 1))]
+    pub max_snapshots_to_keep: Option<usize>,
+}
+
+/// Protection of the storage from running out of disk space
+///
+/// Once free space of the storage, snapshots or temporary files path is below `min_free_space_mb`,
+/// updates are rejected, until enough space is freed again. Deletes are still accepted.
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Eq, Default, Validate)]
+pub struct DiskWatchdogConfig {
+    /// Minimal free space of each storage path to accept updates.
+    /// If not set - disk space is not watched
+    #[serde(default)]
+    #[validate(range(min = 1))]
+    pub min_free_space_mb: Option<u64>,
+    /// Free space of each storage path to accept updates again, once they are rejected.
+    /// Must not be less than `min_free_space_mb`, which is used if not set
+    #[serde(default)]
+    pub resume_free_space_mb: Option<u64>,
+    /// Interval between the checks of the free space. Default: 10 seconds
+    #[serde(default)]
+    #[validate(range(min = 1))]
+    pub check_interval_sec: Option<u64>,
+    /// Also freeze the shards of this peer read-only in the cluster, while the space is low,
+    /// so updates of these shards are rejected by all peers
+    #[serde(default)]
+    pub freeze_shards: bool,
+}
+
+/// Periodic rollups of the usage of the tenants of each collection
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Eq, Validate)]
+pub struct UsageMeteringConfig {
+    /// Interval between the rollups, each closes the current metering period.
+    /// If not set - usage is accumulated since the start of the service
+    #[serde(default)]
+    #[validate(range(min = 1))]
+    pub rollup_interval_sec: Option<u64>,
+    /// Number of the last rollups kept in memory of each collection
+    #[serde(default = "default_rollups_to_keep")]
+    #[validate(range(min = 1))]
+    pub rollups_to_keep: usize,
+}
+
+impl Default for UsageMeteringConfig {
+    fn default() -> Self {
+        Self {
+            rollup_interval_sec: None,
+            rollups_to_keep: default_rollups_to_keep(),
+        }
+    }
+}
+
+/// Periodic check of the consistency of the shards of this peer
+///
+/// Versions of the segments are compared with the WAL of each local shard, and active replicas
+/// are checked not to miss updates, coordinated by this peer. Anomalies turn the status of the
+/// collection red, see [`collection::shards::consistency_check`].
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Eq, Validate)]
+pub struct ConsistencyCheckConfig {
+    /// Interval between the checks of all shards. If not set - shards are not checked
+    #[serde(default)]
+    #[validate(range(min = 1))]
+    pub check_interval_sec: Option<u64>,
+    /// Number of the latest updates, which an active replica may fail, before it is reported.
+    /// Failed replicas are deactivated through the consensus, so short lags are expected
+    #[serde(default = "default_max_missed_updates")]
+    pub max_missed_updates: u64,
+}
+
+impl Default for ConsistencyCheckConfig {
+    fn default() -> Self {
+        Self {
+            check_interval_sec: None,
+            max_missed_updates: default_max_missed_updates(),
+        }
+    }
+}
+
 /// Global configuration of the storage, loaded on the service launch, default stored in ./config
 #[derive(Clone, Debug, Deserialize, Validate)]
 pub struct StorageConfig {
@@ -72,10 +199,51 @@ pub struct StorageConfig {
     pub recovery_mode: Option<String>,
     #[serde(default)]
     pub update_concurrency: Option<NonZeroUsize>,
+    /// Collections, which data is read into memory in background right after the start
+    #[serde(default)]
+    pub warm_up_collections: Vec<String>,
+    /// Maximum time to wait for queued updates and running optimizations on shutdown, before
+    /// data is flushed to disk
+    #[serde(default = "default_shutdown_timeout_sec")]
+    pub shutdown_timeout_sec: u64,
+    /// Storage of collection snapshots: local `snapshots_path` or S3-compatible object storage
+    #[serde(default)]
+    pub snapshots_config: SnapshotsConfig,
+    /// Periodic snapshots of all collections, created with low priority
+    #[serde(default)]
+    #[validate]
+    pub snapshot_schedule: SnapshotScheduleConfig,
+    /// Throughput limits of each outgoing shard transfer
+    #[serde(default)]
+    #[validate]
+    pub shard_transfer: ShardTransferConfig,
+    /// Rejection of updates before the disk is full
+    #[serde(default)]
+    #[validate]
+    pub disk_watchdog: DiskWatchdogConfig,
+    /// Background verification of the checksums of the storage files
+    #[serde(default)]
+    #[validate]
+    pub scrubber: ScrubberConfig,
+    /// Metering of the usage of the tenants of the collections
+    #[serde(default)]
+    #[validate]
+    pub usage_metering: UsageMeteringConfig,
+    /// Periodic check of the consistency of the shards
+    #[serde(default)]
+    #[validate]
+    pub consistency_check: ConsistencyCheckConfig,
+    /// Flags of the experimental features, default for all collections of the node
+    #[serde(default)]
+    pub feature_flags: FeatureFlags,
 }
 
 impl StorageConfig {
-    pub fn to_shared_storage_config(&self, is_distributed: bool) -> SharedStorageConfig {
+    pub fn to_shared_storage_config(
+        &self,
+        is_distributed: bool,
+        events: EventNotifier,
+    ) -> SharedStorageConfig {
         SharedStorageConfig::new(
             self.update_queue_size,
             self.node_type,
@@ -86,6 +254,19 @@ impl StorageConfig {
                 .map(|x| Duration::from_secs(x as u64)),
             self.update_concurrency,
             is_distributed,
+            self.performance.read_load_balancing,
+            SnapshotStorage::new(&self.snapshots_config, Path::new(&self.snapshots_path))
+                .unwrap_or_else(|err| panic!("Invalid snapshots storage configuration: {err}")),
+            IoBudget::new(self.performance.low_priority_io_limit_mb),
+            SearchAdmission::new(
+                self.performance.max_concurrent_searches,
+                self.performance.search_queue_size,
+                Duration::from_millis(self.performance.search_queue_timeout_ms),
+            ),
+            self.shard_transfer,
+            self.scrubber,
+            self.feature_flags,
+            events,
         )
     }
 }
@@ -102,14 +283,36 @@ const fn default_mmap_advice() -> madvise::Advice {
     madvise::Advice::Random
 }
 
+const fn default_shutdown_timeout_sec() -> u64 {
+    30
+}
+
+const fn default_rollups_to_keep() -> usize {
+    24
+}
+
+const fn default_max_missed_updates() -> u64 {
+    10
+}
+
 /// Information of a peer in the cluster
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Clone)]
 pub struct PeerInfo {
     pub uri: String,
+    /// Role of the peer, if it is set through the API
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub role: Option<NodeType>,
     // ToDo: How long ago was the last communication? In milliseconds
     // pub last_responded_millis: usize
 }
 
+/// Request to change the role of a peer
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone)]
+pub struct SetPeerRole {
+    /// New role of the peer. Replicas on the peer are converted to the role, once it is applied
+    pub role: NodeType,
+}
+
 /// Summary information about the current raft state
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Clone)]
 pub struct RaftInfo {
@@ -200,6 +403,7 @@ impl Anonymize for PeerInfo {
     fn anonymize(&self) -> Self {
         PeerInfo {
             uri: self.uri.anonymize(),
+            role: self.role,
         }
     }
 }
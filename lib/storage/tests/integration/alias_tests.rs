@@ -39,7 +39,10 @@ fn test_alias_operation() {
             memmap_threshold: Some(100),
             indexing_threshold: Some(100),
             flush_interval_sec: 2,
+            flush_dirty_threshold: None,
+            flush_dirty_operations: None,
             max_optimization_threads: 2,
+            max_config_rebuild_segments: None,
         },
         wal: Default::default(),
         performance: PerformanceConfig {
@@ -47,6 +50,11 @@ fn test_alias_operation() {
             max_optimization_threads: 1,
             update_rate_limit: None,
             search_timeout_sec: None,
+            read_load_balancing: Default::default(),
+            low_priority_io_limit_mb: 0,
+            max_concurrent_searches: None,
+            search_queue_size: 128,
+            search_queue_timeout_ms: 5000,
         },
         hnsw_index: Default::default(),
         quantization: None,
@@ -58,6 +66,16 @@ fn test_alias_operation() {
         async_scorer: false,
         update_concurrency: Some(NonZeroUsize::new(2).unwrap()),
         // update_concurrency: None,
+        warm_up_collections: vec![],
+        shutdown_timeout_sec: 30,
+        snapshots_config: Default::default(),
+        snapshot_schedule: Default::default(),
+        shard_transfer: Default::default(),
+        disk_watchdog: Default::default(),
+        scrubber: Default::default(),
+        usage_metering: Default::default(),
+        consistency_check: Default::default(),
+        feature_flags: Default::default(),
     };
 
     let search_runtime = Runtime::new().unwrap();
@@ -78,6 +96,7 @@ fn test_alias_operation() {
         ChannelService::new(6333),
         0,
         Some(propose_operation_sender),
+        Default::default(),
     ));
     let dispatcher = Dispatcher::new(toc);
 
@@ -93,6 +112,10 @@ fn test_alias_operation() {
                             hnsw_config: None,
                             quantization_config: None,
                             on_disk: None,
+                            normalize: None,
+                            dimensionality_reduction: None,
+                            multivector_config: None,
+                            datatype: None,
                         }
                         .into(),
                         sparse_vectors: None,
@@ -101,11 +124,20 @@ fn test_alias_operation() {
                         optimizers_config: None,
                         shard_number: Some(1),
                         on_disk_payload: None,
+                        payload_storage_backend: None,
                         replication_factor: None,
                         write_consistency_factor: None,
                         init_from: None,
                         quantization_config: None,
                         sharding_method: None,
+                        sharding_payload_key: None,
+                        search_threads: None,
+                        expiration_field: None,
+                        strict_mode_config: None,
+                        soft_delete_window_sec: None,
+                        storage_policy: None,
+                        from_template: None,
+                        feature_flags: None,
                     },
                 )),
                 None,
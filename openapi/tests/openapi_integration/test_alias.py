@@ -181,3 +181,90 @@ def test_alias_operations():
         }
     )
     assert response.status_code == 404
+
+
+def get_aliases():
+    response = request_with_validation(
+        api='/aliases',
+        method="GET"
+    )
+    assert response.ok
+    return {
+        alias['alias_name']: alias['collection_name']
+        for alias in response.json()['result']['aliases']
+    }
+
+
+def test_alias_switch_with_drop_collection(on_disk_vectors):
+    second_collection_name = f'{collection_name}_2'
+    basic_collection_setup(collection_name=second_collection_name, on_disk_vectors=on_disk_vectors)
+
+    response = request_with_validation(
+        api='/collections/aliases',
+        method="POST",
+        body={
+            "actions": [
+                {"create_alias": {"alias_name": "test_alias", "collection_name": collection_name}},
+            ]
+        }
+    )
+    assert response.ok
+
+    # Changes are not applied partially
+    response = request_with_validation(
+        api='/collections/aliases',
+        method="POST",
+        body={
+            "actions": [
+                {"create_alias": {"alias_name": "test_alias", "collection_name": second_collection_name}},
+                {"drop_collection": {"collection_name": collection_name}},
+                {"create_alias": {"alias_name": "other_alias", "collection_name": collection_name}},
+            ]
+        }
+    )
+    assert response.status_code == 400
+    assert get_aliases() == {"test_alias": collection_name}
+
+    # Switch the alias and drop the previous collection at once
+    response = request_with_validation(
+        api='/collections/aliases',
+        method="POST",
+        body={
+            "actions": [
+                {"create_alias": {"alias_name": "test_alias", "collection_name": second_collection_name}},
+                {"drop_collection": {"collection_name": collection_name}},
+            ]
+        }
+    )
+    assert response.ok
+    assert get_aliases() == {"test_alias": second_collection_name}
+
+    response = request_with_validation(
+        api='/collections/{collection_name}',
+        method="GET",
+        path_params={'collection_name': collection_name},
+    )
+    assert response.status_code == 404
+
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/search',
+        method="POST",
+        path_params={'collection_name': "test_alias"},
+        body={
+            "vector": [0.2, 0.1, 0.9, 0.7],
+            "limit": 3
+        }
+    )
+    assert response.ok
+    assert len(response.json()['result']) == 3
+
+    response = request_with_validation(
+        api='/collections/aliases',
+        method="POST",
+        body={
+            "actions": [
+                {"delete_alias": {"alias_name": "test_alias"}},
+            ]
+        }
+    )
+    assert response.ok
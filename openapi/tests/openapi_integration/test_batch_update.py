@@ -357,3 +357,52 @@ def test_batch_update_payload():
             },
         ]
     )
+
+
+def test_all_or_nothing_batch_update():
+    response = request_with_validation(
+        api="/collections/{collection_name}/points/batch",
+        method="POST",
+        path_params={"collection_name": collection_name},
+        body={
+            "all_or_nothing": True,
+            "operations": [
+                {
+                    "upsert": {
+                        "points": [
+                            {
+                                "id": 100,
+                                "vector": [1.0, 2.0, 3.0, 4.0],
+                                "payload": {"city": "Berlin"},
+                            },
+                        ]
+                    }
+                },
+                {
+                    "set_payload": {
+                        "payload": {"count": 1},
+                        "points": [100],
+                    }
+                },
+                {"delete": {"points": [1]}},
+            ]
+        },
+        query_params={"wait": "true"},
+    )
+    assert response.ok
+
+    # All operations are applied as a single update
+    results = response.json()['result']
+    assert len(results) == 3
+    assert len({result['operation_id'] for result in results}) == 1
+
+    assert_points(
+        [
+            {
+                "id": 100,
+                "vector": None,
+                "payload": {"city": "Berlin", "count": 1},
+            },
+        ],
+        nonexisting_ids=[1],
+    )
@@ -0,0 +1,114 @@
+import time
+
+import pytest
+
+from .helpers.collection_setup import basic_collection_setup, drop_collection
+from .helpers.helpers import request_with_validation
+
+collection_name = 'test_collection_clone_source'
+target_collection_name = 'test_collection_clone_target'
+
+
+@pytest.fixture(autouse=True)
+def setup(on_disk_vectors):
+    basic_collection_setup(collection_name=collection_name, on_disk_vectors=on_disk_vectors)
+    yield
+    drop_collection(collection_name=collection_name)
+    drop_collection(collection_name=target_collection_name)
+
+
+def clone_collection(body):
+    response = request_with_validation(
+        api='/collections/{collection_name}/clone',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        body={"target": target_collection_name, **body},
+    )
+    assert response.ok
+
+
+def get_config(collection):
+    response = request_with_validation(
+        api='/collections/{collection_name}',
+        method="GET",
+        path_params={'collection_name': collection},
+    )
+    assert response.ok
+    return response.json()['result']['config']
+
+
+def scroll_all(collection):
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/scroll',
+        method="POST",
+        path_params={'collection_name': collection},
+        body={"limit": 100, "with_payload": True, "with_vector": True},
+    )
+    assert response.ok
+    return response.json()['result']['points']
+
+
+def wait_points_count(collection, expected_count, timeout_sec=10):
+    # Points are copied into the new collection in background
+    deadline = time.time() + timeout_sec
+    count = None
+    while time.time() < deadline:
+        response = request_with_validation(
+            api='/collections/{collection_name}/points/count',
+            method="POST",
+            path_params={'collection_name': collection},
+            body={"exact": True},
+        )
+        assert response.ok
+        count = response.json()['result']['count']
+        if count == expected_count:
+            return
+        time.sleep(0.2)
+    assert count == expected_count
+
+
+def test_clone_collection():
+    clone_collection({})
+
+    source_points = scroll_all(collection_name)
+    wait_points_count(target_collection_name, len(source_points))
+
+    assert scroll_all(target_collection_name) == source_points
+    assert get_config(target_collection_name)['params'] == get_config(collection_name)['params']
+
+
+def test_clone_collection_with_filter():
+    city_filter = {"must": [{"key": "city", "match": {"value": "Berlin"}}]}
+    clone_collection({"filter": city_filter})
+
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/scroll',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        body={"limit": 100, "with_payload": True, "with_vector": True, "filter": city_filter},
+    )
+    assert response.ok
+    source_points = response.json()['result']['points']
+    assert len(source_points) == 3
+
+    wait_points_count(target_collection_name, len(source_points))
+    assert scroll_all(target_collection_name) == source_points
+
+
+def test_clone_collection_disable_quantization():
+    response = request_with_validation(
+        api='/collections/{collection_name}',
+        method="PATCH",
+        path_params={'collection_name': collection_name},
+        body={"quantization_config": {"scalar": {"type": "int8", "always_ram": True}}},
+    )
+    assert response.ok
+    assert get_config(collection_name)['quantization_config'] is not None
+
+    clone_collection({"quantization_config": "Disabled"})
+
+    source_points = scroll_all(collection_name)
+    wait_points_count(target_collection_name, len(source_points))
+    assert scroll_all(target_collection_name) == source_points
+
+    assert get_config(target_collection_name)['quantization_config'] is None
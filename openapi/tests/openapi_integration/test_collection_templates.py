@@ -0,0 +1,123 @@
+import pytest
+
+from .helpers.collection_setup import drop_collection
+from .helpers.helpers import request_with_validation
+
+collection_name = 'test_collection_from_template'
+template_name = 'test_tenant_template'
+
+
+@pytest.fixture(autouse=True)
+def setup():
+    yield
+    drop_collection(collection_name=collection_name)
+    request_with_validation(
+        api='/collection_templates/{template_name}',
+        method="DELETE",
+        path_params={'template_name': template_name},
+    )
+
+
+def set_template():
+    response = request_with_validation(
+        api='/collection_templates/{template_name}',
+        method="PUT",
+        path_params={'template_name': template_name},
+        body={
+            "config": {
+                "vectors": {"size": 4, "distance": "Dot"},
+                "hnsw_config": {"m": 32},
+                "on_disk_payload": True,
+            },
+            "payload_indexes": {"tenant": "keyword"},
+        },
+    )
+    assert response.ok, response.text
+
+
+def test_collection_templates():
+    set_template()
+
+    response = request_with_validation(
+        api='/collection_templates',
+        method="GET",
+    )
+    assert response.ok, response.text
+    assert template_name in response.json()['result']['templates']
+
+    response = request_with_validation(
+        api='/collection_templates/{template_name}',
+        method="GET",
+        path_params={'template_name': template_name},
+    )
+    assert response.ok, response.text
+    assert response.json()['result']['payload_indexes'] == {'tenant': 'keyword'}
+
+    response = request_with_validation(
+        api='/collection_templates/{template_name}',
+        method="DELETE",
+        path_params={'template_name': template_name},
+    )
+    assert response.ok, response.text
+
+    response = request_with_validation(
+        api='/collection_templates/{template_name}',
+        method="GET",
+        path_params={'template_name': template_name},
+    )
+    assert response.status_code == 404, response.text
+
+
+def test_create_collection_from_template():
+    set_template()
+
+    drop_collection(collection_name=collection_name)
+    response = request_with_validation(
+        api='/collections/{collection_name}',
+        method="PUT",
+        path_params={'collection_name': collection_name},
+        body={
+            "from_template": template_name,
+            "hnsw_config": {"m": 8},
+        },
+    )
+    assert response.ok, response.text
+
+    response = request_with_validation(
+        api='/collections/{collection_name}',
+        method="GET",
+        path_params={'collection_name': collection_name},
+    )
+    assert response.ok, response.text
+    result = response.json()['result']
+
+    # Configuration of the template, unless set in the request
+    assert result['config']['params']['vectors']['size'] == 4
+    assert result['config']['params']['on_disk_payload']
+    assert result['config']['hnsw_config']['m'] == 8
+    assert result['payload_schema']['tenant']['data_type'] == 'keyword'
+
+
+def test_collection_templates_validation():
+    response = request_with_validation(
+        api='/collections/{collection_name}',
+        method="PUT",
+        path_params={'collection_name': collection_name},
+        body={
+            "from_template": 'missing_template',
+        },
+    )
+    assert response.status_code == 404, response.text
+
+    response = request_with_validation(
+        api='/collection_templates/{template_name}',
+        method="PUT",
+        path_params={'template_name': template_name},
+        body={
+            "config": {
+                "vectors": {"size": 4, "distance": "Dot"},
+                "init_from": {"collection": "other_collection"},
+            },
+        },
+    )
+    assert response.status_code == 400, response.text
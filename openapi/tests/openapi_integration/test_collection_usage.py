@@ -0,0 +1,55 @@
+import pytest
+
+from .helpers.collection_setup import basic_collection_setup, drop_collection
+from .helpers.helpers import request_with_validation
+
+collection_name = 'test_collection_usage'
+
+
+@pytest.fixture(autouse=True)
+def setup(on_disk_vectors):
+    basic_collection_setup(collection_name=collection_name, on_disk_vectors=on_disk_vectors)
+    yield
+    drop_collection(collection_name=collection_name)
+
+
+def get_usage():
+    response = request_with_validation(
+        api='/collections/{collection_name}/usage',
+        method="GET",
+        path_params={'collection_name': collection_name},
+    )
+    assert response.ok, response.text
+    return response.json()['result']
+
+
+def test_collection_usage():
+    usage = get_usage()
+    # Requests of the setup and the points of the collection, without a shard key
+    [tenant] = usage['tenants']
+    assert 'shard_key' not in tenant
+    assert tenant['points_count'] > 0
+    updates = tenant['requests']['update']['count']
+    searches = tenant['requests']['search']['count']
+
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/search',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        body={"vector": [0.2, 0.1, 0.9, 0.7], "limit": 3},
+    )
+    assert response.ok, response.text
+
+    response = request_with_validation(
+        api='/collections/{collection_name}/points',
+        method="PUT",
+        path_params={'collection_name': collection_name},
+        query_params={'wait': 'true'},
+        body={"points": [{"id": 100, "vector": [0.1, 0.2, 0.3, 0.4]}]},
+    )
+    assert response.ok, response.text
+
+    [tenant] = get_usage()['tenants']
+    assert tenant['requests']['search']['count'] == searches + 1
+    assert tenant['requests']['update']['count'] == updates + 1
+    assert usage['rollups'] == []
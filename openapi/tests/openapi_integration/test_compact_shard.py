@@ -0,0 +1,45 @@
+import pytest
+
+from .helpers.collection_setup import basic_collection_setup, drop_collection
+from .helpers.helpers import request_with_validation
+
+collection_name = 'test_collection_compact'
+
+
+@pytest.fixture(autouse=True)
+def setup(on_disk_vectors):
+    basic_collection_setup(collection_name=collection_name, on_disk_vectors=on_disk_vectors)
+    yield
+    drop_collection(collection_name=collection_name)
+
+
+def test_compact_shard():
+    response = request_with_validation(
+        api='/collections/{collection_name}/shards/{shard_id}/compact',
+        method="POST",
+        path_params={'shard_id': 0, 'collection_name': collection_name},
+        query_params={'wait': 'true'},
+    )
+    assert response.ok
+    result = response.json()['result']
+    assert result['segments_after'] <= result['segments_before']
+
+    # points are not affected by the compaction
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/count',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        body={'exact': True},
+    )
+    assert response.ok
+    assert response.json()['result']['count'] == 10
+
+
+def test_compact_unknown_shard():
+    response = request_with_validation(
+        api='/collections/{collection_name}/shards/{shard_id}/compact',
+        method="POST",
+        path_params={'shard_id': 100, 'collection_name': collection_name},
+        query_params={'wait': 'true'},
+    )
+    assert response.status_code == 404
@@ -0,0 +1,120 @@
+import pytest
+
+from .helpers.collection_setup import drop_collection
+from .helpers.helpers import request_with_validation
+
+collection_name = 'test_collection_conditional_updates'
+
+
+@pytest.fixture(autouse=True)
+def setup(on_disk_vectors):
+    drop_collection(collection_name=collection_name)
+
+    response = request_with_validation(
+        api='/collections/{collection_name}',
+        method="PUT",
+        path_params={'collection_name': collection_name},
+        body={
+            "vectors": {
+                "size": 2,
+                "distance": "Dot",
+                "on_disk": on_disk_vectors,
+            }
+        }
+    )
+    assert response.ok
+
+    response = upsert_points([{"id": 1, "vector": [1.0, 0.0], "payload": {"name": "first"}}])
+    assert response.ok
+    yield
+    drop_collection(collection_name=collection_name)
+
+
+def upsert_points(points):
+    return request_with_validation(
+        api='/collections/{collection_name}/points',
+        method="PUT",
+        path_params={'collection_name': collection_name},
+        query_params={'wait': 'true'},
+        body={"points": points},
+    )
+
+
+def set_payload(payload, points, if_version):
+    return request_with_validation(
+        api='/collections/{collection_name}/points/payload',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        query_params={'wait': 'true'},
+        body={"payload": payload, "points": points, "if_version": if_version},
+    )
+
+
+def get_points(point_ids, with_version=True):
+    response = request_with_validation(
+        api='/collections/{collection_name}/points',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        body={"ids": point_ids, "with_payload": True, "with_version": with_version},
+    )
+    assert response.ok
+    return response.json()['result']
+
+
+def get_point(point_id):
+    points = get_points([point_id])
+    assert len(points) == 1
+    return points[0]
+
+
+def test_version_is_returned_on_request():
+    assert 'version' not in get_points([1], with_version=False)[0]
+    assert get_point(1)['version'] > 0
+
+
+def test_set_payload_if_version():
+    version = get_point(1)['version']
+
+    response = set_payload({"name": "stale"}, [1], if_version=version + 100)
+    assert response.status_code == 400
+    assert "Version conflict" in response.json()['status']['error']
+    assert get_point(1)['payload'] == {"name": "first"}
+
+    response = set_payload({"name": "updated"}, [1], if_version=version)
+    assert response.ok
+    point = get_point(1)
+    assert point['payload'] == {"name": "updated"}
+    assert point['version'] > version
+
+
+def test_reserved_payload_fields():
+    response = set_payload({"__qdrant_version": 100}, [1], if_version=None)
+    assert response.status_code == 400
+
+    response = upsert_points([
+        {"id": 1, "vector": [0.0, 1.0], "payload": {"__qdrant_deleted_at": 100}},
+    ])
+    assert response.status_code == 400
+    assert get_point(1)['payload'] == {"name": "first"}
+
+
+def test_upsert_if_version():
+    version = get_point(1)['version']
+
+    # Version 0 only allows to insert new points
+    response = upsert_points([
+        {"id": 1, "vector": [0.0, 1.0], "payload": {"name": "replaced"}, "if_version": 0},
+        {"id": 2, "vector": [0.0, 1.0], "payload": {"name": "second"}, "if_version": 0},
+    ])
+    assert response.status_code == 400
+    assert get_point(1)['payload'] == {"name": "first"}
+    # Whole operation is rejected
+    assert get_points([2]) == []
+
+    response = upsert_points([
+        {"id": 1, "vector": [0.0, 1.0], "payload": {"name": "replaced"}, "if_version": version},
+    ])
+    assert response.ok
+    point = get_point(1)
+    assert point['payload'] == {"name": "replaced"}
+    assert point['version'] > version
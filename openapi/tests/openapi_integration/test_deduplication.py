@@ -0,0 +1,135 @@
+import pytest
+
+from .helpers.collection_setup import drop_collection
+from .helpers.helpers import request_with_validation
+
+collection_name = 'test_collection_deduplication'
+
+
+@pytest.fixture(autouse=True)
+def setup(on_disk_vectors):
+    response = request_with_validation(
+        api='/collections/{collection_name}',
+        method="DELETE",
+        path_params={'collection_name': collection_name},
+    )
+    assert response.ok
+
+    response = request_with_validation(
+        api='/collections/{collection_name}',
+        method="PUT",
+        path_params={'collection_name': collection_name},
+        body={
+            "vectors": {
+                "size": 2,
+                "distance": "Cosine",
+                "on_disk": on_disk_vectors,
+            }
+        }
+    )
+    assert response.ok
+
+    upsert_points([
+        {"id": 1, "vector": [1.0, 0.0], "payload": {"name": "first"}},
+        {"id": 2, "vector": [0.0, 1.0], "payload": {"name": "second"}},
+    ])
+    yield
+    drop_collection(collection_name=collection_name)
+
+
+def upsert_points(points, deduplication=None):
+    body = {"points": points}
+    if deduplication is not None:
+        body["deduplication"] = deduplication
+    response = request_with_validation(
+        api='/collections/{collection_name}/points',
+        method="PUT",
+        path_params={'collection_name': collection_name},
+        query_params={'wait': 'true'},
+        body=body,
+    )
+    assert response.ok
+
+
+def get_point(point_id):
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/{id}',
+        method="GET",
+        path_params={'collection_name': collection_name, 'id': point_id},
+    )
+    return response
+
+
+def count_points():
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/count',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        body={"exact": True},
+    )
+    assert response.ok
+    return response.json()['result']['count']
+
+
+def test_skip_duplicates():
+    upsert_points(
+        [
+            {"id": 3, "vector": [1.0, 0.01], "payload": {"name": "duplicate"}},
+            {"id": 4, "vector": [1.0, 1.0], "payload": {"name": "unique"}},
+        ],
+        deduplication={"threshold": 0.99, "action": "skip"},
+    )
+
+    assert count_points() == 3
+    assert not get_point(3).ok
+    assert get_point(4).ok
+
+
+def test_overwrite_duplicates():
+    upsert_points(
+        [{"id": 3, "vector": [1.0, 0.01], "payload": {"name": "duplicate"}}],
+        deduplication={"threshold": 0.99, "action": "overwrite"},
+    )
+
+    assert count_points() == 2
+    assert not get_point(3).ok
+    response = get_point(1)
+    assert response.ok
+    assert response.json()['result']['payload'] == {"name": "duplicate"}
+
+
+def test_tag_duplicates():
+    response = request_with_validation(
+        api='/collections/{collection_name}/points',
+        method="PUT",
+        path_params={'collection_name': collection_name},
+        query_params={'wait': 'true'},
+        body={
+            "batch": {
+                "ids": [3, 4],
+                "vectors": [[0.01, 1.0], [1.0, 1.0]],
+                "payloads": [{"name": "duplicate"}, {"name": "unique"}],
+            },
+            "deduplication": {"threshold": 0.99, "action": "tag"},
+        },
+    )
+    assert response.ok
+
+    assert count_points() == 4
+    response = get_point(3)
+    assert response.ok
+    assert response.json()['result']['payload'] == {"name": "duplicate", "duplicate_of": 2}
+    response = get_point(4)
+    assert response.ok
+    assert response.json()['result']['payload'] == {"name": "unique"}
+
+
+def test_update_of_existing_point_is_not_duplicate():
+    upsert_points(
+        [{"id": 1, "vector": [1.0, 0.0], "payload": {"name": "updated"}}],
+        deduplication={"threshold": 0.99, "action": "skip"},
+    )
+
+    response = get_point(1)
+    assert response.ok
+    assert response.json()['result']['payload'] == {"name": "updated"}
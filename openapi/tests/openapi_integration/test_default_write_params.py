@@ -0,0 +1,71 @@
+import pytest
+
+from .helpers.collection_setup import basic_collection_setup, drop_collection
+from .helpers.helpers import request_with_validation
+
+collection_name = 'test_collection_default_write_params'
+
+
+@pytest.fixture(autouse=True)
+def setup(on_disk_vectors):
+    basic_collection_setup(collection_name=collection_name, on_disk_vectors=on_disk_vectors)
+    yield
+    drop_collection(collection_name=collection_name)
+
+
+def get_params():
+    response = request_with_validation(
+        api='/collections/{collection_name}',
+        method="GET",
+        path_params={'collection_name': collection_name},
+    )
+    assert response.ok
+    return response.json()['result']['config']['params']
+
+
+def test_default_write_params():
+    params = get_params()
+    assert 'default_write_ordering' not in params
+    assert 'default_wait' not in params
+
+    response = request_with_validation(
+        api='/collections/{collection_name}',
+        method="PATCH",
+        path_params={'collection_name': collection_name},
+        body={
+            "params": {
+                "default_write_ordering": "strong",
+                "default_wait": True,
+            }
+        }
+    )
+    assert response.ok
+
+    params = get_params()
+    assert params['default_write_ordering'] == 'strong'
+    assert params['default_wait'] is True
+
+    # Update without `wait` waits for the changes, so the point is visible right away
+    response = request_with_validation(
+        api='/collections/{collection_name}/points',
+        method="PUT",
+        path_params={'collection_name': collection_name},
+        body={
+            "points": [
+                {"id": 100, "vector": [0.1, 0.2, 0.3, 0.4]},
+            ]
+        }
+    )
+    assert response.ok
+    assert response.json()['result']['status'] == 'completed'
+
+    # Request parameters take precedence over the collection defaults
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/delete',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        query_params={'wait': 'false', 'ordering': 'weak'},
+        body={"points": [100]}
+    )
+    assert response.ok
+    assert response.json()['result']['status'] == 'acknowledged'
@@ -1,3 +1,6 @@
+import time
+from concurrent.futures import ThreadPoolExecutor
+
 import pytest
 
 from .helpers.collection_setup import basic_collection_setup, drop_collection
@@ -60,3 +63,86 @@ def test_delete_points():
     assert response.ok
     assert response.json()['result']['points_count'] == 5
     assert response.json()['result']['vectors_count'] == 10
+
+
+def test_delete_by_filter_batched():
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/delete_by_filter',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        body={
+            "filter": {
+                "must": [
+                    {"key": "city", "match": {"value": "Berlin"}}
+                ]
+            },
+            "batch_size": 2,
+            "batch_delay_ms": 10,
+        }
+    )
+    assert response.ok
+    assert response.json()['result']['points'] == 3
+    assert response.json()['result']['batches'] == 2
+
+    response = request_with_validation(
+        api='/collections/{collection_name}',
+        method="GET",
+        path_params={'collection_name': collection_name},
+    )
+    assert response.ok
+    assert response.json()['result']['points_count'] == 7
+
+
+def test_delete_by_filter_progress():
+    def delete_by_filter():
+        return request_with_validation(
+            api='/collections/{collection_name}/points/delete_by_filter',
+            method="POST",
+            path_params={'collection_name': collection_name},
+            body={
+                "filter": {
+                    "must": [
+                        {"key": "city", "match": {"value": "Berlin"}}
+                    ]
+                },
+                "batch_size": 1,
+                "batch_delay_ms": 500,
+            }
+        )
+
+    with ThreadPoolExecutor(max_workers=1) as executor:
+        delete_future = executor.submit(delete_by_filter)
+
+        # Running update is listed with the number of the points deleted so far
+        progress = None
+        deadline = time.time() + 10
+        while progress is None and time.time() < deadline and not delete_future.done():
+            response = request_with_validation(
+                api='/operations',
+                method="GET",
+            )
+            assert response.ok
+            for operation in response.json()['result']:
+                if operation['kind'] == 'update_by_filter' and operation['collection_name'] == collection_name:
+                    progress = operation.get('progress')
+            time.sleep(0.1)
+
+        response = delete_future.result()
+
+    assert progress is not None
+    assert 1 <= progress['done'] <= 3
+
+    assert response.ok
+    assert response.json()['result']['points'] == 3
+    assert response.json()['result']['batches'] == 3
+
+    # Finished update is not listed anymore
+    response = request_with_validation(
+        api='/operations',
+        method="GET",
+    )
+    assert response.ok
+    assert all(
+        operation['kind'] != 'update_by_filter' or operation['collection_name'] != collection_name
+        for operation in response.json()['result']
+    )
@@ -0,0 +1,125 @@
+import pytest
+
+from .helpers.collection_setup import drop_collection
+from .helpers.helpers import request_with_validation
+
+collection_name = 'test_collection_dimensionality_reduction'
+
+
+@pytest.fixture(autouse=True)
+def setup(on_disk_vectors):
+    drop_collection(collection_name=collection_name)
+
+    response = request_with_validation(
+        api='/collections/{collection_name}',
+        method="PUT",
+        path_params={'collection_name': collection_name},
+        body={
+            "vectors": {
+                "reduced": {
+                    "size": 2,
+                    "distance": "Euclid",
+                    "dimensionality_reduction": {"input_size": 4},
+                    "on_disk": on_disk_vectors,
+                },
+            }
+        }
+    )
+    assert response.ok, response.text
+    yield
+    drop_collection(collection_name=collection_name)
+
+
+def upsert_points():
+    return request_with_validation(
+        api='/collections/{collection_name}/points',
+        method="PUT",
+        path_params={'collection_name': collection_name},
+        query_params={'wait': 'true'},
+        body={
+            "points": [
+                {"id": 1, "vector": {"reduced": [1.0, 2.0, 1.0, 2.0]}},
+                {"id": 2, "vector": {"reduced": [5.0, -3.0, 5.0, -3.0]}},
+            ]
+        }
+    )
+
+
+def train():
+    # Sample varies in two directions only, so they are kept by the reduction
+    sample = [[float(i), float(i % 7), float(i), float(i % 7)] for i in range(20)]
+    return request_with_validation(
+        api='/collections/{collection_name}/dimensionality_reduction',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        body={
+            "vector_name": "reduced",
+            "sample": sample,
+        }
+    )
+
+
+def test_vectors_are_reduced():
+    # Vectors can't be reduced until the transform is trained
+    response = upsert_points()
+    assert response.status_code == 400, response.text
+
+    response = train()
+    assert response.ok, response.text
+
+    response = request_with_validation(
+        api='/collections/{collection_name}',
+        method="GET",
+        path_params={'collection_name': collection_name},
+    )
+    assert response.ok
+    reduction = response.json()['result']['config']['params']['vectors']['reduced']['dimensionality_reduction']
+    assert reduction['input_size'] == 4
+    assert len(reduction['transform']['mean']) == 4
+    assert len(reduction['transform']['matrix']) == 8
+
+    response = upsert_points()
+    assert response.ok, response.text
+
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/{id}',
+        method="GET",
+        path_params={'collection_name': collection_name, 'id': 1},
+    )
+    assert response.ok
+    assert len(response.json()['result']['vector']['reduced']) == 2
+
+    # Query vectors are reduced as well
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/search',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        body={
+            "vector": {"name": "reduced", "vector": [5.0, -3.0, 5.0, -3.0]},
+            "limit": 2,
+        }
+    )
+    assert response.ok, response.text
+    result = response.json()['result']
+    assert result[0]['id'] == 2
+    assert result[0]['score'] == pytest.approx(0.0, abs=1e-3)
+
+    # Transform can't be replaced, as stored vectors are already reduced
+    response = train()
+    assert response.status_code == 400, response.text
+
+
+def test_dimensionality_reduction_validation():
+    response = request_with_validation(
+        api='/collections/{collection_name}',
+        method="PUT",
+        path_params={'collection_name': collection_name + '_invalid'},
+        body={
+            "vectors": {
+                "size": 4,
+                "distance": "Euclid",
+                "dimensionality_reduction": {"input_size": 4},
+            }
+        }
+    )
+    assert response.status_code == 422, response.text
@@ -0,0 +1,72 @@
+import pytest
+
+from .helpers.collection_setup import drop_collection
+from .helpers.helpers import request_with_validation
+
+collection_name = 'test_collection_feature_flags'
+
+reduced_vector = {
+    "size": 2,
+    "distance": "Euclid",
+    "dimensionality_reduction": {"input_size": 4},
+}
+
+
+@pytest.fixture(autouse=True)
+def setup():
+    drop_collection(collection_name=collection_name)
+    yield
+    drop_collection(collection_name=collection_name)
+
+
+def create_collection(body):
+    return request_with_validation(
+        api='/collections/{collection_name}',
+        method="PUT",
+        path_params={'collection_name': collection_name},
+        body=body,
+    )
+
+
+def update_collection(body):
+    return request_with_validation(
+        api='/collections/{collection_name}',
+        method="PATCH",
+        path_params={'collection_name': collection_name},
+        body=body,
+    )
+
+
+def test_disabled_feature_is_rejected():
+    response = create_collection({
+        "vectors": {"reduced": reduced_vector},
+        "feature_flags": {"dimensionality_reduction": False},
+    })
+    assert response.status_code == 400
+    assert "dimensionality_reduction" in response.json()["status"]["error"]
+
+
+def test_update_feature_flags():
+    response = create_collection({
+        "vectors": {"dense": {"size": 4, "distance": "Dot"}},
+        "feature_flags": {"dimensionality_reduction": False},
+    })
+    assert response.ok, response.text
+
+    response = request_with_validation(
+        api='/collections/{collection_name}',
+        method="GET",
+        path_params={'collection_name': collection_name},
+    )
+    assert response.ok, response.text
+    assert response.json()['result']['config']['feature_flags'] == {"dimensionality_reduction": False}
+
+    response = update_collection({"add_vectors": {"reduced": reduced_vector}})
+    assert response.status_code == 400
+
+    # Feature is enabled by the same update, which selects it
+    response = update_collection({
+        "add_vectors": {"reduced": reduced_vector},
+        "feature_flags": {"dimensionality_reduction": True},
+    })
+    assert response.ok, response.text
@@ -0,0 +1,102 @@
+import pytest
+
+from .helpers.collection_setup import drop_collection
+from .helpers.helpers import request_with_validation
+
+collection_name = 'test_collection_float16'
+
+
+@pytest.fixture(autouse=True, params=[False, True])
+def setup(request):
+    drop_collection(collection_name=collection_name)
+
+    response = request_with_validation(
+        api='/collections/{collection_name}',
+        method="PUT",
+        path_params={'collection_name': collection_name},
+        body={
+            "vectors": {
+                "size": 4,
+                "distance": "Dot",
+                "datatype": "float16",
+                "on_disk": request.param,
+            }
+        }
+    )
+    assert response.ok, response.text
+    yield
+    drop_collection(collection_name=collection_name)
+
+
+def upsert_points(points):
+    return request_with_validation(
+        api='/collections/{collection_name}/points',
+        method="PUT",
+        path_params={'collection_name': collection_name},
+        query_params={'wait': 'true'},
+        body={"points": points}
+    )
+
+
+def test_float16_vectors_search():
+    response = upsert_points([
+        {"id": 1, "vector": [1.0, 0.5, 0.0, 0.0]},
+        {"id": 2, "vector": [0.1, 0.2, 0.3, 0.4]},
+        {"id": 3, "vector": [-1.0, 0.0, 0.0, 2.0]},
+    ])
+    assert response.ok, response.text
+
+    response = request_with_validation(
+        api='/collections/{collection_name}',
+        method="GET",
+        path_params={'collection_name': collection_name},
+    )
+    assert response.ok, response.text
+    assert response.json()['result']['config']['params']['vectors']['datatype'] == 'float16'
+
+    # Values are rounded to the nearest float16
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/{id}',
+        method="GET",
+        path_params={'collection_name': collection_name, 'id': 2},
+    )
+    assert response.ok, response.text
+    assert response.json()['result']['vector'] == pytest.approx([0.1, 0.2, 0.3, 0.4], rel=1e-3)
+
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/search',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        body={
+            "vector": [1.0, 0.0, 0.0, 0.0],
+            "limit": 3,
+        }
+    )
+    assert response.ok, response.text
+    result = response.json()['result']
+    assert [point['id'] for point in result] == [1, 2, 3]
+    assert result[0]['score'] == pytest.approx(1.0)
+    assert result[1]['score'] == pytest.approx(0.1, rel=1e-3)
+    assert result[2]['score'] == pytest.approx(-1.0)
+
+
+def test_float16_vectors_validation():
+    # Values out of the float16 range
+    response = upsert_points([{"id": 1, "vector": [1e5, 0, 0, 0]}])
+    assert response.status_code == 400, response.text
+
+    # Vectors of the float16 datatype are not quantized
+    response = request_with_validation(
+        api='/collections/{collection_name}',
+        method="PUT",
+        path_params={'collection_name': collection_name + '_quantized'},
+        body={
+            "vectors": {
+                "size": 4,
+                "distance": "Dot",
+                "datatype": "float16",
+                "quantization_config": {"scalar": {"type": "int8"}},
+            }
+        }
+    )
+    assert response.status_code == 422, response.text
@@ -0,0 +1,89 @@
+import pytest
+
+from .helpers.collection_setup import basic_collection_setup, drop_collection
+from .helpers.helpers import request_with_validation
+
+collection_name = 'test_collection_hybrid_search'
+
+
+@pytest.fixture(autouse=True)
+def setup(on_disk_vectors):
+    basic_collection_setup(collection_name=collection_name, on_disk_vectors=on_disk_vectors)
+    yield
+    drop_collection(collection_name=collection_name)
+
+
+def hybrid_search(body):
+    return request_with_validation(
+        api='/collections/{collection_name}/points/search/hybrid',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        body=body,
+    )
+
+
+dense = [0.2, 0.1, 0.9, 0.7]
+sparse = {"name": "sparse-text", "vector": {"indices": [1, 2, 3], "values": [1.0, 1.0, 1.0]}}
+
+
+def test_hybrid_search_rrf():
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/search',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        body={"vector": dense, "limit": 3},
+    )
+    assert response.ok, response.text
+    dense_ids = [point['id'] for point in response.json()['result']]
+
+    response = hybrid_search({"dense": dense, "sparse": sparse, "limit": 4})
+    assert response.ok, response.text
+    result = response.json()['result']
+    ids = [point['id'] for point in result]
+    assert len(ids) == 4
+    # Only point 10 matches the sparse vector, it is ranked first there
+    assert 10 in ids
+    assert set(dense_ids) <= set(ids)
+    assert all(point['score'] <= 1 / 61 for point in result)
+
+    # Offset is applied to the fused results
+    response = hybrid_search({"dense": dense, "sparse": sparse, "limit": 2, "offset": 2})
+    assert response.ok, response.text
+    assert [point['id'] for point in response.json()['result']] == ids[2:]
+
+
+def test_hybrid_search_weighted_sum():
+    response = hybrid_search({
+        "dense": dense,
+        "sparse": sparse,
+        "fusion": {"weighted_sum": {"dense_weight": 0.5, "sparse_weight": 1.0}},
+        "limit": 2,
+        "with_payload": True,
+    })
+    assert response.ok, response.text
+    result = response.json()['result']
+    assert result[0]['id'] == 10
+    assert result[0]['score'] == 1.0
+    assert result[0]['payload'] == {"city": []}
+    assert result[1]['score'] == 0.5
+
+
+def test_hybrid_search_validation():
+    # Dense vector must not be sparse
+    response = hybrid_search({"dense": sparse, "sparse": sparse, "limit": 2})
+    assert response.status_code == 422
+
+    response = hybrid_search({
+        "dense": dense,
+        "sparse": sparse,
+        "fusion": {"weighted_sum": {"dense_weight": -1.0, "sparse_weight": 1.0}},
+        "limit": 2,
+    })
+    assert response.status_code == 422
+
+    response = hybrid_search({
+        "dense": dense,
+        "sparse": {"name": "missing", "vector": {"indices": [1], "values": [1.0]}},
+        "limit": 2,
+    })
+    assert response.status_code == 400
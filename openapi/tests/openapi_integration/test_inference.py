@@ -0,0 +1,77 @@
+import pytest
+
+from .helpers.collection_setup import basic_collection_setup, drop_collection
+from .helpers.helpers import request_with_validation
+
+collection_name = 'test_collection_inference'
+
+
+@pytest.fixture(autouse=True)
+def setup(on_disk_vectors):
+    basic_collection_setup(collection_name=collection_name, on_disk_vectors=on_disk_vectors)
+    yield
+    drop_collection(collection_name=collection_name)
+
+
+def set_inference(inference):
+    return request_with_validation(
+        api='/collections/{collection_name}',
+        method="PATCH",
+        path_params={'collection_name': collection_name},
+        body={"params": {"inference": inference}},
+    )
+
+
+def upsert_documents():
+    return request_with_validation(
+        api='/collections/{collection_name}/points/documents',
+        method="PUT",
+        path_params={'collection_name': collection_name},
+        query_params={'wait': 'true'},
+        body={
+            "points": [
+                {"id": 100, "document": {"text": "hello"}, "payload": {"lang": "en"}},
+                {"id": 101, "document": {"image": "https://example.com/cat.png"}},
+            ]
+        },
+    )
+
+
+def search_document():
+    return request_with_validation(
+        api='/collections/{collection_name}/points/search/document',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        body={"document": {"text": "hello"}, "limit": 3},
+    )
+
+
+def test_inference():
+    # Documents require the inference endpoint
+    response = upsert_documents()
+    assert response.status_code == 400
+    response = search_document()
+    assert response.status_code == 400
+
+    # Inferred vector must be in the collection
+    response = set_inference({"url": "http://127.0.0.1:1/embed", "vector_name": "missing"})
+    assert response.status_code == 400
+
+    # Nothing listens on the endpoint
+    response = set_inference({"url": "http://127.0.0.1:1/embed", "model": "test", "max_retries": 0})
+    assert response.ok, response.text
+
+    response = request_with_validation(
+        api='/collections/{collection_name}',
+        method="GET",
+        path_params={'collection_name': collection_name},
+    )
+    assert response.ok
+    inference = response.json()['result']['config']['params']['inference']
+    assert inference['url'] == "http://127.0.0.1:1/embed"
+    assert inference['model'] == "test"
+
+    response = upsert_documents()
+    assert response.status_code == 500
+    response = search_document()
+    assert response.status_code == 500
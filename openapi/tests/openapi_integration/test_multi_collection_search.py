@@ -0,0 +1,101 @@
+import pytest
+
+from .helpers.collection_setup import basic_collection_setup, drop_collection
+from .helpers.helpers import request_with_validation
+
+collection_name = 'test_collection_multi_search'
+collection_name2 = 'test_collection_multi_search2'
+
+
+@pytest.fixture(autouse=True, scope="module")
+def setup(on_disk_vectors):
+    basic_collection_setup(collection_name=collection_name, on_disk_vectors=on_disk_vectors)
+    basic_collection_setup(collection_name=collection_name2, on_disk_vectors=on_disk_vectors)
+    yield
+    drop_collection(collection_name=collection_name)
+    drop_collection(collection_name=collection_name2)
+
+
+def test_multi_collection_search():
+    response = request_with_validation(
+        api='/collections/search',
+        method="POST",
+        body={
+            "collections": [collection_name, collection_name2],
+            "searches": [
+                {
+                    "vector": [0.2, 0.1, 0.9, 0.7],
+                    "limit": 4,
+                },
+            ],
+        }
+    )
+    assert response.ok, response.text
+
+    result = response.json()['result']
+    assert len(result) == 1
+    points = result[0]
+    assert len(points) == 4
+
+    # Collections hold the same points, so each point is found in both
+    assert [point['collection'] for point in points] == [collection_name, collection_name2] * 2
+    assert points[0]['id'] == points[1]['id']
+    assert points[0]['score'] == points[0]['raw_score']
+    assert points[0]['score'] >= points[2]['score']
+
+
+def test_multi_collection_search_normalized():
+    response = request_with_validation(
+        api='/collections/search',
+        method="POST",
+        body={
+            "collections": [collection_name, collection_name2],
+            "searches": [
+                {
+                    "vector": [0.2, 0.1, 0.9, 0.7],
+                    "limit": 3,
+                },
+                {
+                    "vector": [0.2, 0.1, 0.9, 0.7],
+                    "limit": 2,
+                    "offset": 1,
+                },
+            ],
+            "score_normalization": "min_max",
+        }
+    )
+    assert response.ok, response.text
+
+    result = response.json()['result']
+    assert len(result) == 2
+
+    # Best point of each collection is normalized to 1
+    best = result[0][:2]
+    assert [point['score'] for point in best] == [1.0, 1.0]
+    assert {point['collection'] for point in best} == {collection_name, collection_name2}
+
+    # Offset is applied to the merged results
+    assert len(result[1]) == 2
+    assert result[1][0]['score'] == 1.0
+
+
+def test_multi_collection_search_validation():
+    response = request_with_validation(
+        api='/collections/search',
+        method="POST",
+        body={
+            "collections": [collection_name, collection_name],
+            "searches": [{"vector": [0.2, 0.1, 0.9, 0.7], "limit": 3}],
+        }
+    )
+    assert response.status_code == 400, response.text
+
+    response = request_with_validation(
+        api='/collections/search',
+        method="POST",
+        body={
+            "collections": [collection_name, 'missing_collection'],
+            "searches": [{"vector": [0.2, 0.1, 0.9, 0.7], "limit": 3}],
+        }
+    )
+    assert response.status_code == 404, response.text
@@ -0,0 +1,105 @@
+import pytest
+
+from .helpers.collection_setup import drop_collection
+from .helpers.helpers import request_with_validation
+
+collection_name = 'test_collection_multivector'
+
+
+@pytest.fixture(autouse=True)
+def setup():
+    drop_collection(collection_name=collection_name)
+
+    response = request_with_validation(
+        api='/collections/{collection_name}',
+        method="PUT",
+        path_params={'collection_name': collection_name},
+        body={
+            "vectors": {
+                "colbert": {
+                    "size": 2,
+                    "distance": "Dot",
+                    "multivector_config": {"comparator": "max_sim"},
+                },
+            }
+        }
+    )
+    assert response.ok, response.text
+    yield
+    drop_collection(collection_name=collection_name)
+
+
+def upsert_points(points):
+    return request_with_validation(
+        api='/collections/{collection_name}/points',
+        method="PUT",
+        path_params={'collection_name': collection_name},
+        query_params={'wait': 'true'},
+        body={"points": points}
+    )
+
+
+def test_multivector_search():
+    response = upsert_points([
+        {"id": 1, "vector": {"colbert": [[1.0, 0.0], [0.0, 1.0]]}},
+        {"id": 2, "vector": {"colbert": [[1.0, 0.0]]}},
+        {"id": 3, "vector": {"colbert": [[0.2, 0.1], [0.1, 0.2], [0.0, 0.0]]}},
+    ])
+    assert response.ok, response.text
+
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/search',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        body={
+            "vector": {"name": "colbert", "vector": [[1.0, 0.0], [0.0, 1.0]]},
+            "limit": 3,
+        }
+    )
+    assert response.ok, response.text
+    result = response.json()['result']
+    assert [point['id'] for point in result] == [1, 2, 3]
+    # Sum of the best similarities of each query vector
+    assert result[0]['score'] == pytest.approx(2.0)
+    assert result[1]['score'] == pytest.approx(1.0)
+    assert result[2]['score'] == pytest.approx(0.4)
+
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/{id}',
+        method="GET",
+        path_params={'collection_name': collection_name, 'id': 3},
+    )
+    assert response.ok, response.text
+    assert len(response.json()['result']['vector']['colbert']) == 3
+
+
+def test_multivector_validation():
+    # Vectors of a different size
+    response = upsert_points([{"id": 1, "vector": {"colbert": [[1.0, 0.0], [1.0]]}}])
+    assert response.status_code == 400, response.text
+
+    # Regular vector instead of a multi-vector
+    response = upsert_points([{"id": 1, "vector": {"colbert": [1.0, 0.0]}}])
+    assert response.status_code == 400, response.text
+
+    # Empty multi-vector
+    response = upsert_points([{"id": 1, "vector": {"colbert": []}}])
+    assert response.status_code == 400, response.text
+
+    # Multi-vectors are not quantized
+    response = request_with_validation(
+        api='/collections/{collection_name}',
+        method="PUT",
+        path_params={'collection_name': collection_name + '_quantized'},
+        body={
+            "vectors": {
+                "colbert": {
+                    "size": 2,
+                    "distance": "Dot",
+                    "multivector_config": {"comparator": "max_sim"},
+                    "quantization_config": {"scalar": {"type": "int8"}},
+                },
+            }
+        }
+    )
+    assert response.status_code == 422, response.text
@@ -0,0 +1,43 @@
+import pytest
+
+from .helpers.collection_setup import basic_collection_setup, drop_collection
+from .helpers.helpers import request_with_validation
+
+collection_name = 'test_collection_operations'
+
+
+@pytest.fixture(autouse=True)
+def setup(on_disk_vectors):
+    basic_collection_setup(collection_name=collection_name, on_disk_vectors=on_disk_vectors)
+    yield
+    drop_collection(collection_name=collection_name)
+
+
+def test_list_operations():
+    response = request_with_validation(
+        api='/operations',
+        method="GET",
+    )
+    assert response.ok, response.text
+
+    for operation in response.json()['result']:
+        assert operation['id'] > 0
+        assert operation['kind'] in (
+            'optimization', 'index_build', 'shard_transfer', 'snapshot_creation', 'update_by_filter',
+        )
+
+
+def test_unknown_operation():
+    response = request_with_validation(
+        api='/operations/{operation_id}',
+        method="GET",
+        path_params={'operation_id': 1234567890},
+    )
+    assert response.status_code == 404
+
+    response = request_with_validation(
+        api='/operations/{operation_id}/cancel',
+        method="POST",
+        path_params={'operation_id': 1234567890},
+    )
+    assert response.status_code == 404
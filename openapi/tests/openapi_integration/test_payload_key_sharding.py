@@ -0,0 +1,121 @@
+import pytest
+
+from .helpers.collection_setup import drop_collection
+from .helpers.helpers import request_with_validation
+
+collection_name = 'test_collection_payload_key_sharding'
+
+
+@pytest.fixture(autouse=True)
+def setup():
+    drop_collection(collection_name=collection_name)
+    response = request_with_validation(
+        api='/collections/{collection_name}',
+        method="PUT",
+        path_params={'collection_name': collection_name},
+        body={
+            "vectors": {
+                "size": 2,
+                "distance": "Dot",
+            },
+            "shard_number": 3,
+            "sharding_method": "payload_key",
+            "sharding_payload_key": "tenant",
+        }
+    )
+    assert response.ok
+    yield
+    drop_collection(collection_name=collection_name)
+
+
+def upsert_points(points):
+    return request_with_validation(
+        api='/collections/{collection_name}/points',
+        method="PUT",
+        path_params={'collection_name': collection_name},
+        query_params={'wait': 'true'},
+        body={"points": points},
+    )
+
+
+def test_payload_key_sharding():
+    response = upsert_points([
+        {"id": i, "vector": [1.0, float(i)], "payload": {"tenant": f"tenant_{i % 4}"}}
+        for i in range(20)
+    ])
+    assert response.ok
+
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/search',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        body={
+            "vector": [1.0, 1.0],
+            "limit": 10,
+            "filter": {"must": [{"key": "tenant", "match": {"value": "tenant_1"}}]},
+            "with_payload": True,
+        }
+    )
+    assert response.ok
+    result = response.json()['result']
+    assert sorted(point['id'] for point in result) == [1, 5, 9, 13, 17]
+    assert all(point['payload']['tenant'] == "tenant_1" for point in result)
+
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/count',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        body={
+            "filter": {"must": [{"key": "tenant", "match": {"any": ["tenant_0", "tenant_2"]}}]},
+        }
+    )
+    assert response.ok
+    assert response.json()['result']['count'] == 10
+
+    # Points are found by ID, whichever shard they are stored in
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/payload',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        query_params={'wait': 'true'},
+        body={"points": [1, 2], "payload": {"color": "red"}},
+    )
+    assert response.ok
+
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/delete',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        query_params={'wait': 'true'},
+        body={"points": [3]},
+    )
+    assert response.ok
+
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/count',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        body={},
+    )
+    assert response.ok
+    assert response.json()['result']['count'] == 19
+
+
+def test_payload_key_is_required():
+    response = upsert_points([{"id": 1, "vector": [1.0, 1.0], "payload": {"color": "red"}}])
+    assert response.status_code == 400
+    assert "sharding payload key" in response.json()['status']['error']
+
+
+def test_payload_key_change_is_rejected():
+    response = upsert_points([{"id": 1, "vector": [1.0, 1.0], "payload": {"tenant": "a"}}])
+    assert response.ok
+
+    for tenant in ["b", "c", "d", "e", "f", "g", "h"]:
+        response = upsert_points([{"id": 1, "vector": [1.0, 1.0], "payload": {"tenant": tenant}}])
+        # Values of the same shard could be changed, values of other shards are rejected
+        if not response.ok:
+            assert response.status_code == 400
+            break
+    else:
+        pytest.fail("All tenants are expected to be in the same shard")
@@ -275,3 +275,38 @@ def test_payload_operations():
     )
     assert response.ok
     assert len(response.json()['result']['points']) == 0
+
+
+def test_set_payload_by_filter_batched():
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/payload/set_by_filter',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        body={
+            "payload": {"batched": True},
+            "filter": {
+                "must": [
+                    {"key": "city", "match": {"value": "Berlin"}}
+                ]
+            },
+            "batch_size": 2,
+        }
+    )
+    assert response.ok
+    assert response.json()['result']['points'] == 3
+    assert response.json()['result']['batches'] == 2
+
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/scroll',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        body={
+            "filter": {
+                "must": [
+                    {"key": "batched", "match": {"value": True}}
+                ]
+            }
+        }
+    )
+    assert response.ok
+    assert sorted(point['id'] for point in response.json()['result']['points']) == [1, 2, 3]
@@ -0,0 +1,71 @@
+import time
+
+import pytest
+
+from .helpers.collection_setup import basic_collection_setup, drop_collection
+from .helpers.helpers import request_with_validation
+
+collection_name = 'test_collection_point_ttl'
+
+
+@pytest.fixture(autouse=True)
+def setup(on_disk_vectors):
+    basic_collection_setup(collection_name=collection_name, on_disk_vectors=on_disk_vectors)
+    yield
+    drop_collection(collection_name=collection_name)
+
+
+def upsert_with_ttl(ttl):
+    return request_with_validation(
+        api='/collections/{collection_name}/points',
+        method="PUT",
+        path_params={'collection_name': collection_name},
+        query_params={'wait': 'true'},
+        body={
+            "points": [
+                {"id": 100, "vector": [0.1, 0.2, 0.3, 0.4], "payload": {"city": "Berlin"}},
+            ],
+            "ttl": ttl,
+        },
+    )
+
+
+def test_upsert_with_ttl():
+    # Expiration field is not configured
+    response = upsert_with_ttl(60)
+    assert response.status_code == 400
+
+    response = request_with_validation(
+        api='/collections/{collection_name}',
+        method="PATCH",
+        path_params={'collection_name': collection_name},
+        body={
+            "params": {
+                "expiration_field": "expire_at",
+            }
+        }
+    )
+    assert response.ok, response.text
+
+    # Expired points are looked for by the index
+    response = request_with_validation(
+        api='/collections/{collection_name}',
+        method="GET",
+        path_params={'collection_name': collection_name},
+    )
+    assert response.ok, response.text
+    assert 'expire_at' in response.json()['result']['payload_schema']
+
+    before = int(time.time())
+    response = upsert_with_ttl(60)
+    assert response.ok, response.text
+
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/{id}',
+        method="GET",
+        path_params={'collection_name': collection_name, 'id': 100},
+    )
+    assert response.ok, response.text
+    payload = response.json()['result']['payload']
+    assert payload['city'] == "Berlin"
+    assert before + 60 <= payload['expire_at'] <= int(time.time()) + 60
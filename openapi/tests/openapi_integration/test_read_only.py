@@ -0,0 +1,91 @@
+import pytest
+
+from .helpers.collection_setup import basic_collection_setup, drop_collection
+from .helpers.helpers import request_with_validation
+
+collection_name = 'test_collection_read_only'
+
+
+@pytest.fixture(autouse=True)
+def setup(on_disk_vectors):
+    basic_collection_setup(collection_name=collection_name, on_disk_vectors=on_disk_vectors)
+    yield
+    drop_collection(collection_name=collection_name)
+
+
+def set_read_only(body):
+    response = request_with_validation(
+        api='/collections/{collection_name}/read_only',
+        method="PUT",
+        path_params={'collection_name': collection_name},
+        body=body,
+    )
+    assert response.ok, response.text
+
+
+def get_read_only():
+    response = request_with_validation(
+        api='/collections/{collection_name}/read_only',
+        method="GET",
+        path_params={'collection_name': collection_name},
+    )
+    assert response.ok, response.text
+    return response.json()['result']
+
+
+def upsert_point():
+    return request_with_validation(
+        api='/collections/{collection_name}/points',
+        method="PUT",
+        path_params={'collection_name': collection_name},
+        query_params={'wait': 'true'},
+        body={"points": [{"id": 100, "vector": [0.1, 0.2, 0.3, 0.4]}]},
+    )
+
+
+def test_read_only_collection():
+    assert get_read_only() == {}
+
+    set_read_only({"read_only": True, "reason": "migration"})
+    assert get_read_only() == {"collection": {"reason": "migration"}}
+
+    response = upsert_point()
+    assert response.status_code == 403
+    assert "migration" in response.json()['status']['error']
+
+    # Reads are still served
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/search',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        body={"vector": [0.2, 0.1, 0.9, 0.7], "limit": 3},
+    )
+    assert response.ok, response.text
+    assert len(response.json()['result']) == 3
+
+    set_read_only({"read_only": False})
+    assert get_read_only() == {}
+
+    response = upsert_point()
+    assert response.ok, response.text
+
+
+def test_read_only_shard():
+    set_read_only({"read_only": True, "shard_id": 0})
+    assert get_read_only() == {"shards": {"0": {}}}
+
+    response = upsert_point()
+    assert response.status_code == 403
+
+    set_read_only({"read_only": False, "shard_id": 0})
+    assert get_read_only() == {}
+
+
+def test_read_only_missing_shard():
+    response = request_with_validation(
+        api='/collections/{collection_name}/read_only',
+        method="PUT",
+        path_params={'collection_name': collection_name},
+        body={"read_only": True, "shard_id": 100},
+    )
+    assert response.status_code == 404
@@ -0,0 +1,104 @@
+import pytest
+
+from .helpers.collection_setup import basic_collection_setup, drop_collection
+from .helpers.helpers import request_with_validation
+
+collection_name = 'test_collection_rescore_search'
+
+
+@pytest.fixture(autouse=True)
+def setup(on_disk_vectors):
+    basic_collection_setup(collection_name=collection_name, on_disk_vectors=on_disk_vectors)
+    yield
+    drop_collection(collection_name)
+
+
+def rescore_search(body):
+    return request_with_validation(
+        api='/collections/{collection_name}/points/search/rescore',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        body=body,
+    )
+
+
+dense = [0.2, 0.1, 0.9, 0.7]
+sparse = {"name": "sparse-text", "vector": {"indices": [1, 2, 3], "values": [1.0, 1.0, 1.0]}}
+
+
+def test_rescore_search():
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/search',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        body={"vector": dense, "limit": 3},
+    )
+    assert response.ok, response.text
+    dense_result = response.json()['result']
+
+    # Prefetch by the same vector keeps the results of the plain search
+    response = rescore_search({
+        "prefetch": {"vector": dense, "limit": 10, "params": {"quantization": {"ignore": True}}},
+        "vector": dense,
+        "limit": 3,
+    })
+    assert response.ok, response.text
+    assert response.json()['result'] == dense_result
+
+
+def test_rescore_search_sparse_prefetch():
+    response = request_with_validation(
+        api='/collections/{collection_name}/points',
+        method="PUT",
+        path_params={'collection_name': collection_name},
+        query_params={'wait': 'true'},
+        body={
+            "points": [
+                {
+                    "id": 11,
+                    "vector": {
+                        "": [0.5, 0.5, 0.5, 0.5],
+                        "sparse-text": {"indices": [1], "values": [1.0]},
+                    },
+                    "payload": {"city": "Paris"},
+                },
+            ]
+        },
+    )
+    assert response.ok, response.text
+
+    # Points 10 and 11 match the sparse vector, only point 11 has the dense vector
+    response = rescore_search({
+        "prefetch": {"vector": sparse, "limit": 10},
+        "vector": dense,
+        "limit": 3,
+        "with_payload": True,
+    })
+    assert response.ok, response.text
+    result = response.json()['result']
+    assert [point['id'] for point in result] == [11]
+    assert result[0]['payload'] == {"city": "Paris"}
+    assert result[0]['score'] == pytest.approx(0.95, abs=1e-5)
+
+
+def test_rescore_search_filter():
+    response = rescore_search({
+        "prefetch": {"vector": dense, "limit": 10},
+        "vector": [0.1, 0.9, 0.1, 0.1],
+        "filter": {"must": [{"has_id": [1, 2, 3]}]},
+        "limit": 5,
+    })
+    assert response.ok, response.text
+    assert {point['id'] for point in response.json()['result']} <= {1, 2, 3}
+
+
+def test_rescore_search_validation():
+    response = rescore_search({"prefetch": {"vector": dense, "limit": 0}, "vector": dense, "limit": 3})
+    assert response.status_code == 422
+
+    response = rescore_search({
+        "prefetch": {"vector": {"name": "missing", "vector": dense}, "limit": 10},
+        "vector": dense,
+        "limit": 3,
+    })
+    assert response.status_code == 400
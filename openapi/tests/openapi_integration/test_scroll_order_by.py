@@ -0,0 +1,89 @@
+import pytest
+
+from .helpers.collection_setup import basic_collection_setup, drop_collection
+from .helpers.helpers import request_with_validation
+
+collection_name = 'test_collection_scroll_order_by'
+
+
+@pytest.fixture(autouse=True)
+def setup(on_disk_vectors):
+    basic_collection_setup(collection_name=collection_name, on_disk_vectors=on_disk_vectors)
+    yield
+    drop_collection(collection_name=collection_name)
+
+
+def scroll(body):
+    return request_with_validation(
+        api='/collections/{collection_name}/points/scroll',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        body=body,
+    )
+
+
+def test_scroll_order_by():
+    # Timestamps are in reverse order of ids
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/payload',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        query_params={'wait': 'true'},
+        body={
+            "payload": {"timestamp": 100},
+            "points": [1],
+        },
+    )
+    assert response.ok, response.text
+    for point_id in range(2, 9):
+        response = request_with_validation(
+            api='/collections/{collection_name}/points/payload',
+            method="POST",
+            path_params={'collection_name': collection_name},
+            query_params={'wait': 'true'},
+            body={
+                "payload": {"timestamp": 100 - point_id},
+                "points": [point_id],
+            },
+        )
+        assert response.ok, response.text
+
+    # Ordering requires a numeric index
+    response = scroll({"order_by": {"key": "timestamp"}})
+    assert response.status_code == 400
+
+    response = request_with_validation(
+        api='/collections/{collection_name}/index',
+        method="PUT",
+        path_params={'collection_name': collection_name},
+        query_params={'wait': 'true'},
+        body={
+            "field_name": "timestamp",
+            "field_schema": "integer"
+        }
+    )
+    assert response.ok, response.text
+
+    response = scroll({"limit": 3, "order_by": {"key": "timestamp", "direction": "desc"}})
+    assert response.ok, response.text
+    points = response.json()['result']['points']
+    assert [point['id'] for point in points] == [1, 2, 3]
+    assert [point['order_value'] for point in points] == [100, 98, 97]
+
+    # Next page starts from the value of the last point
+    response = scroll({
+        "limit": 3,
+        "order_by": {"key": "timestamp", "direction": "desc", "start_from": 96},
+    })
+    assert response.ok, response.text
+    assert [point['id'] for point in response.json()['result']['points']] == [4, 5, 6]
+
+    response = scroll({"limit": 2, "order_by": {"key": "timestamp"}})
+    assert response.ok, response.text
+    result = response.json()['result']
+    assert [point['id'] for point in result['points']] == [8, 7]
+    assert result['next_page_offset'] is None
+
+    # Offset can't be combined with ordering
+    response = scroll({"offset": 2, "order_by": {"key": "timestamp"}})
+    assert response.status_code == 400
@@ -0,0 +1,47 @@
+import pytest
+
+from .helpers.collection_setup import basic_collection_setup, drop_collection
+from .helpers.helpers import request_with_validation
+
+collection_name = 'test_collection_scroll_sample'
+
+
+@pytest.fixture(autouse=True)
+def setup(on_disk_vectors):
+    basic_collection_setup(collection_name=collection_name, on_disk_vectors=on_disk_vectors)
+    yield
+    drop_collection(collection_name=collection_name)
+
+
+def scroll(body):
+    return request_with_validation(
+        api='/collections/{collection_name}/points/scroll',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        body=body,
+    )
+
+
+def test_scroll_sample():
+    response = scroll({"sample": "random", "limit": 3})
+    assert response.ok, response.text
+    result = response.json()['result']
+    ids = [point['id'] for point in result['points']]
+    assert len(ids) == 3
+    assert len(set(ids)) == 3
+    assert result['next_page_offset'] is None
+
+    # All matching points are returned, if there are fewer of them than the limit
+    response = scroll({
+        "sample": "random",
+        "limit": 5,
+        "filter": {"must": [{"key": "value", "range": {"lte": 2}}]},
+    })
+    assert response.ok, response.text
+    ids = sorted(point['id'] for point in response.json()['result']['points'])
+    assert ids == [1, 2]
+
+
+def test_scroll_sample_with_offset():
+    response = scroll({"sample": "random", "limit": 3, "offset": 2})
+    assert not response.ok
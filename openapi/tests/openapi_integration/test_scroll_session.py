@@ -0,0 +1,148 @@
+import pytest
+
+from .helpers.collection_setup import basic_collection_setup, drop_collection
+from .helpers.helpers import request_with_validation
+
+collection_name = 'test_collection_scroll_session'
+
+
+@pytest.fixture(autouse=True)
+def setup(on_disk_vectors):
+    basic_collection_setup(collection_name=collection_name, on_disk_vectors=on_disk_vectors)
+    yield
+    drop_collection(collection_name=collection_name)
+
+
+def scroll(session_id, offset=None, **kwargs):
+    body = {"limit": 3, "session_id": session_id, **kwargs}
+    if offset is not None:
+        body["offset"] = offset
+    return request_with_validation(
+        api='/collections/{collection_name}/points/scroll',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        body=body,
+    )
+
+
+def test_scroll_session():
+    # Session is started by the first page only
+    response = scroll("unknown-session", offset=2)
+    assert response.status_code == 400
+
+    response = scroll("session")
+    assert response.ok, response.text
+    result = response.json()['result']
+    scrolled_ids = [point['id'] for point in result['points']]
+    # Session is pinned to the replicas, which serve its pages
+    session_id = result['session_id']
+    assert session_id.startswith("session~")
+
+    # Points, inserted after the session is started, are not visible in it
+    response = request_with_validation(
+        api='/collections/{collection_name}/points',
+        method="PUT",
+        path_params={'collection_name': collection_name},
+        query_params={'wait': 'true'},
+        body={"points": [{"id": 100, "vector": [0.1, 0.2, 0.3, 0.4]}]},
+    )
+    assert response.ok, response.text
+
+    offset = result['next_page_offset']
+    while offset is not None:
+        response = scroll(session_id, offset=offset)
+        assert response.ok, response.text
+        result = response.json()['result']
+        assert result['session_id'] == session_id
+        scrolled_ids += [point['id'] for point in result['points']]
+        offset = result['next_page_offset']
+
+    assert len(scrolled_ids) == len(set(scrolled_ids))
+    assert set(range(1, 9)).issubset(scrolled_ids)
+    assert 100 not in scrolled_ids
+
+
+def open_session(body):
+    return request_with_validation(
+        api='/collections/{collection_name}/points/scroll/session',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        body=body,
+    )
+
+
+def scroll_all(session_id, **kwargs):
+    points = []
+    offset = None
+    while True:
+        response = scroll(session_id, offset=offset, **kwargs)
+        assert response.ok, response.text
+        result = response.json()['result']
+        session_id = result['session_id']
+        points += result['points']
+        offset = result['next_page_offset']
+        if offset is None:
+            return points
+
+
+def test_open_scroll_session():
+    response = open_session({"ttl_sec": 600})
+    assert response.ok, response.text
+    session = response.json()['result']
+    assert session['ttl_sec'] == 600
+    session_id = session['session_id']
+
+    # Updates after the opening of the session are not visible in it
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/payload',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        query_params={'wait': 'true'},
+        body={"payload": {"city": "Paris"}, "points": [1]},
+    )
+    assert response.ok, response.text
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/delete',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        query_params={'wait': 'true'},
+        body={"points": [2]},
+    )
+    assert response.ok, response.text
+
+    points = scroll_all(session_id)
+    assert [point['id'] for point in points] == list(range(1, 11))
+    assert points[0]['payload']['city'] == "Berlin"
+
+    # Session is reused by the following scrolls, filters see the same snapshot
+    points = scroll_all(
+        session_id,
+        filter={"must": [{"key": "city", "match": {"value": "Berlin"}}]},
+    )
+    assert [point['id'] for point in points] == [1, 2, 3]
+
+    # Regular scroll sees the updates
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/scroll',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        body={"limit": 10},
+    )
+    assert response.ok, response.text
+    points = response.json()['result']['points']
+    assert 2 not in [point['id'] for point in points]
+    assert points[0]['payload']['city'] == "Paris"
+
+
+def test_scroll_session_validation():
+    response = open_session({"ttl_sec": 0})
+    assert response.status_code == 422, response.text
+
+    response = scroll("session", as_of=1)
+    assert response.status_code == 400, response.text
+
+    response = scroll("session", order_by="count")
+    assert response.status_code == 400, response.text
+
+    response = scroll("session~0:peer")
+    assert response.status_code == 400, response.text
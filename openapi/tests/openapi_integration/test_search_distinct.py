@@ -0,0 +1,71 @@
+import pytest
+
+from .helpers.collection_setup import basic_collection_setup, drop_collection
+from .helpers.helpers import request_with_validation
+
+collection_name = 'test_collection_search_distinct'
+
+
+@pytest.fixture(autouse=True)
+def setup(on_disk_vectors):
+    basic_collection_setup(collection_name=collection_name, on_disk_vectors=on_disk_vectors)
+    yield
+    drop_collection(collection_name=collection_name)
+
+
+def set_payload(payload, points):
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/payload',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        query_params={'wait': 'true'},
+        body={"payload": payload, "points": points},
+    )
+    assert response.ok, response.text
+
+
+def search(body):
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/search',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        body=body,
+    )
+    assert response.ok, response.text
+    return response.json()['result']
+
+
+def test_search_distinct():
+    set_payload({"doc": {"id": "a"}}, [1, 2, 3])
+    set_payload({"doc": {"id": "b"}}, [4, 5])
+
+    vector = [0.2, 0.1, 0.9, 0.7]
+    all_points = search({"vector": vector, "limit": 10})
+    best = {}
+    for point in all_points:
+        doc = {1: "a", 2: "a", 3: "a", 4: "b", 5: "b"}.get(point['id'], point['id'])
+        best.setdefault(doc, point['id'])
+
+    result = search({"vector": vector, "limit": 10, "distinct_by": {"key": "doc.id"}})
+    assert [point['id'] for point in result] == list(best.values())
+    # Values of the field are not returned, unless the payload is requested
+    assert all(point['payload'] is None for point in result)
+
+    result = search({
+        "vector": vector,
+        "limit": 2,
+        "with_payload": ["value"],
+        "distinct_by": {"key": "doc.id"},
+    })
+    assert [point['id'] for point in result] == list(best.values())[:2]
+    assert all(list(point['payload']) == ["value"] for point in result)
+
+
+def test_search_distinct_page():
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/search/page',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        body={"vector": [0.2, 0.1, 0.9, 0.7], "limit": 3, "distinct_by": {"key": "doc"}},
+    )
+    assert not response.ok
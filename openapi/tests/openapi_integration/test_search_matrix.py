@@ -0,0 +1,54 @@
+import pytest
+
+from .helpers.collection_setup import basic_collection_setup, drop_collection
+from .helpers.helpers import request_with_validation
+
+collection_name = 'test_collection_search_matrix'
+
+
+@pytest.fixture(autouse=True)
+def setup(on_disk_vectors):
+    basic_collection_setup(collection_name=collection_name, on_disk_vectors=on_disk_vectors)
+    yield
+    drop_collection(collection_name=collection_name)
+
+
+def search_matrix(body):
+    return request_with_validation(
+        api='/collections/{collection_name}/points/search/matrix',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        body=body,
+    )
+
+
+def test_search_matrix_by_ids():
+    response = search_matrix({"ids": [1, 2, 3, 4], "limit": 2})
+    assert response.ok, response.text
+    rows = response.json()['result']
+    assert sorted(row['id'] for row in rows) == [1, 2, 3, 4]
+    for row in rows:
+        neighbors = [neighbor['id'] for neighbor in row['neighbors']]
+        assert len(neighbors) == 2
+        assert row['id'] not in neighbors
+        assert set(neighbors) <= {1, 2, 3, 4}
+        scores = [neighbor['score'] for neighbor in row['neighbors']]
+        assert scores == sorted(scores, reverse=True)
+
+
+def test_search_matrix_sample():
+    response = search_matrix({
+        "sample": 3,
+        "filter": {"must": [{"key": "city", "match": {"value": "Berlin"}}]},
+    })
+    assert response.ok, response.text
+    rows = response.json()['result']
+    ids = {row['id'] for row in rows}
+    assert len(ids) == 3
+    for row in rows:
+        assert {neighbor['id'] for neighbor in row['neighbors']} == ids - {row['id']}
+
+
+def test_search_matrix_ids_and_sample():
+    response = search_matrix({"ids": [1, 2], "sample": 2})
+    assert not response.ok
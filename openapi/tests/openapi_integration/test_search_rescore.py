@@ -0,0 +1,61 @@
+import pytest
+
+from .helpers.collection_setup import basic_collection_setup, drop_collection
+from .helpers.helpers import request_with_validation
+
+collection_name = 'test_collection_search_rescore'
+
+
+@pytest.fixture(autouse=True)
+def setup(on_disk_vectors):
+    basic_collection_setup(collection_name=collection_name, on_disk_vectors=on_disk_vectors)
+    yield
+    drop_collection(collection_name=collection_name)
+
+
+def search(body):
+    return request_with_validation(
+        api='/collections/{collection_name}/points/search',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        body=body,
+    )
+
+
+def test_search_rescore():
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/payload',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        query_params={'wait': 'true'},
+        body={"payload": {"boost": 10}, "points": [6]},
+    )
+    assert response.ok, response.text
+
+    vector = [0.2, 0.1, 0.9, 0.7]
+    response = search({"vector": vector, "limit": 3})
+    assert response.ok, response.text
+    assert 6 not in [point['id'] for point in response.json()['result']]
+
+    # Boosted point is found among the candidates, and ranked first
+    response = search({
+        "vector": vector,
+        "limit": 3,
+        "rescore": {"formula": "score + log(boost + 1) * 2", "candidates": 8},
+    })
+    assert response.ok, response.text
+    result = response.json()['result']
+    assert len(result) == 3
+    assert result[0]['id'] == 6
+    scores = [point['score'] for point in result]
+    assert scores == sorted(scores, reverse=True)
+
+
+def test_search_rescore_invalid_formula():
+    response = search({
+        "vector": [0.2, 0.1, 0.9, 0.7],
+        "limit": 3,
+        "rescore": {"formula": "score + unknown(boost)"},
+    })
+    assert not response.ok
+    assert 'unknown function' in response.text
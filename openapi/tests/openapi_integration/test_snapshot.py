@@ -261,3 +261,24 @@ def test_snapshot_invalid_file_uri():
     )
     assert response.status_code == 400
     assert response.json()["status"]["error"] == "Bad request: Snapshot file \"/whatever.snapshot\" does not exist"
+
+
+def test_low_priority_snapshot():
+    response = request_with_validation(
+        api='/collections/{collection_name}/snapshots',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        query_params={'wait': 'true', 'snapshot_priority': 'low'},
+    )
+    assert response.ok
+    snapshot_name = response.json()['result']['name']
+    assert response.json()['result']['size'] > 0
+
+    response = request_with_validation(
+        api='/collections/{collection_name}/snapshots/{snapshot_name}',
+        method="DELETE",
+        path_params={'collection_name': collection_name,
+                     'snapshot_name': snapshot_name},
+        query_params={'wait': 'true'},
+    )
+    assert response.ok
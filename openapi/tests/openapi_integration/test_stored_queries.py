@@ -0,0 +1,74 @@
+import pytest
+
+from .helpers.collection_setup import basic_collection_setup, drop_collection
+from .helpers.helpers import request_with_validation
+
+collection_name = 'test_collection_stored_queries'
+
+
+@pytest.fixture(autouse=True)
+def setup(on_disk_vectors):
+    basic_collection_setup(collection_name=collection_name, on_disk_vectors=on_disk_vectors)
+    yield
+    drop_collection(collection_name=collection_name)
+
+
+def get_stored_queries():
+    response = request_with_validation(
+        api='/collections/{collection_name}/stored_queries',
+        method="GET",
+        path_params={'collection_name': collection_name},
+    )
+    assert response.ok, response.text
+    return response.json()['result']['queries']
+
+
+def test_stored_queries():
+    assert get_stored_queries() == {}
+
+    response = request_with_validation(
+        api='/collections/{collection_name}/stored_queries/{query_name}',
+        method="PUT",
+        path_params={'collection_name': collection_name, 'query_name': 'london'},
+        body={
+            "filter": {"must": [{"key": "city", "match": {"value": "London"}}]},
+            "vector": [0.2, 0.1, 0.9, 0.7],
+            "score_threshold": 0.5,
+        },
+    )
+    assert response.ok, response.text
+
+    queries = get_stored_queries()
+    assert list(queries) == ['london']
+    assert queries['london']['score_threshold'] == 0.5
+
+    response = request_with_validation(
+        api='/collections/{collection_name}/stored_queries/{query_name}',
+        method="DELETE",
+        path_params={'collection_name': collection_name, 'query_name': 'london'},
+    )
+    assert response.ok, response.text
+    assert get_stored_queries() == {}
+
+
+def test_invalid_stored_queries():
+    invalid_queries = [
+        # Neither filter nor vector
+        {},
+        # Vector without threshold
+        {"vector": [0.2, 0.1, 0.9, 0.7]},
+        # Wrong dimension
+        {"vector": [0.2, 0.1], "score_threshold": 0.5},
+        # Unknown vector name
+        {"vector": [0.2, 0.1, 0.9, 0.7], "using": "image", "score_threshold": 0.5},
+    ]
+    for query in invalid_queries:
+        response = request_with_validation(
+            api='/collections/{collection_name}/stored_queries/{query_name}',
+            method="PUT",
+            path_params={'collection_name': collection_name, 'query_name': 'invalid'},
+            body=query,
+        )
+        assert response.status_code == 400, query
+
+    assert get_stored_queries() == {}
@@ -0,0 +1,121 @@
+import time
+
+import pytest
+
+from .helpers.collection_setup import basic_collection_setup, drop_collection
+from .helpers.helpers import request_with_validation
+
+collection_name = 'test_collection_time_travel'
+
+
+@pytest.fixture(autouse=True)
+def setup(on_disk_vectors):
+    basic_collection_setup(collection_name=collection_name, on_disk_vectors=on_disk_vectors)
+    yield
+    drop_collection(collection_name=collection_name)
+
+
+def retrieve(ids, as_of=None):
+    body = {"ids": ids, "with_payload": True}
+    if as_of is not None:
+        body["as_of"] = as_of
+    response = request_with_validation(
+        api='/collections/{collection_name}/points',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        body=body,
+    )
+    assert response.ok, response.text
+    return {point['id']: point for point in response.json()['result']}
+
+
+def test_time_travel_reads():
+    # Not enabled by default
+    response = request_with_validation(
+        api='/collections/{collection_name}/points',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        body={"ids": [1], "as_of": int(time.time())},
+    )
+    assert response.status_code == 400
+
+    response = request_with_validation(
+        api='/collections/{collection_name}',
+        method="PATCH",
+        path_params={'collection_name': collection_name},
+        body={
+            "params": {
+                "time_travel_window_sec": 60,
+            }
+        }
+    )
+    assert response.ok, response.text
+
+    # Changes are recorded with a precision of seconds
+    time.sleep(1)
+    as_of = int(time.time())
+    before = retrieve([1, 2])
+
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/payload',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        query_params={'wait': 'true'},
+        body={"payload": {"value": 100}, "points": [1]},
+    )
+    assert response.ok, response.text
+
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/delete',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        query_params={'wait': 'true'},
+        body={"points": [2]},
+    )
+    assert response.ok, response.text
+
+    response = request_with_validation(
+        api='/collections/{collection_name}/points',
+        method="PUT",
+        path_params={'collection_name': collection_name},
+        query_params={'wait': 'true'},
+        body={"points": [{"id": 100, "vector": [0.1, 0.2, 0.3, 0.4]}]},
+    )
+    assert response.ok, response.text
+
+    current = retrieve([1, 2, 100])
+    assert current[1]['payload']['value'] == 100
+    assert 2 not in current
+    assert 100 in current
+
+    past = retrieve([1, 2, 100], as_of=as_of)
+    assert past[1]['payload'] == before[1]['payload']
+    assert past[1]['version'] == before[1]['version']
+    assert past[2]['payload'] == before[2]['payload']
+    assert 100 not in past
+
+    # Scroll filters points by their past state
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/scroll',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        body={
+            "filter": {"must": [{"key": "value", "range": {"lte": 2}}]},
+            "limit": 10,
+            "as_of": as_of,
+        },
+    )
+    assert response.ok, response.text
+    scrolled_ids = [point['id'] for point in response.json()['result']['points']]
+    assert 1 in scrolled_ids
+    assert 2 in scrolled_ids
+    assert 100 not in scrolled_ids
+
+    # Out of the window
+    response = request_with_validation(
+        api='/collections/{collection_name}/points',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        body={"ids": [1], "as_of": as_of - 3600},
+    )
+    assert response.status_code == 400
@@ -0,0 +1,104 @@
+import pytest
+
+from .helpers.collection_setup import drop_collection
+from .helpers.helpers import request_with_validation
+
+collection_name = 'test_collection_uint8'
+
+
+@pytest.fixture(autouse=True)
+def setup():
+    drop_collection(collection_name=collection_name)
+
+    response = request_with_validation(
+        api='/collections/{collection_name}',
+        method="PUT",
+        path_params={'collection_name': collection_name},
+        body={
+            "vectors": {
+                "size": 4,
+                "distance": "Cosine",
+                "datatype": "uint8",
+            }
+        }
+    )
+    assert response.ok, response.text
+    yield
+    drop_collection(collection_name=collection_name)
+
+
+def upsert_points(points):
+    return request_with_validation(
+        api='/collections/{collection_name}/points',
+        method="PUT",
+        path_params={'collection_name': collection_name},
+        query_params={'wait': 'true'},
+        body={"points": points}
+    )
+
+
+def test_uint8_vectors_search():
+    response = upsert_points([
+        {"id": 1, "vector": [255, 0, 0, 0]},
+        {"id": 2, "vector": [100, 100, 0, 0]},
+        {"id": 3, "vector": [0, 0, 10, 20]},
+    ])
+    assert response.ok, response.text
+
+    response = request_with_validation(
+        api='/collections/{collection_name}',
+        method="GET",
+        path_params={'collection_name': collection_name},
+    )
+    assert response.ok, response.text
+    assert response.json()['result']['config']['params']['vectors']['datatype'] == 'uint8'
+
+    # Vectors are stored as is, without normalization
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/{id}',
+        method="GET",
+        path_params={'collection_name': collection_name, 'id': 2},
+    )
+    assert response.ok, response.text
+    assert response.json()['result']['vector'] == [100, 100, 0, 0]
+
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/search',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        body={
+            "vector": [200, 0, 0, 0],
+            "limit": 3,
+        }
+    )
+    assert response.ok, response.text
+    result = response.json()['result']
+    assert [point['id'] for point in result] == [1, 2, 3]
+    assert result[0]['score'] == pytest.approx(1.0)
+    assert result[1]['score'] == pytest.approx(0.7071, abs=1e-4)
+    assert result[2]['score'] == pytest.approx(0.0)
+
+
+def test_uint8_vectors_validation():
+    # Values out of the byte range
+    response = upsert_points([{"id": 1, "vector": [256, 0, 0, 0]}])
+    assert response.status_code == 400, response.text
+
+    response = upsert_points([{"id": 1, "vector": [-1, 0, 0, 0]}])
+    assert response.status_code == 400, response.text
+
+    # Vectors of the uint8 datatype are not quantized
+    response = request_with_validation(
+        api='/collections/{collection_name}',
+        method="PUT",
+        path_params={'collection_name': collection_name + '_quantized'},
+        body={
+            "vectors": {
+                "size": 4,
+                "distance": "Dot",
+                "datatype": "uint8",
+                "quantization_config": {"scalar": {"type": "int8"}},
+            }
+        }
+    )
+    assert response.status_code == 422, response.text
@@ -0,0 +1,33 @@
+import pytest
+
+from .helpers.collection_setup import basic_collection_setup, drop_collection
+from .helpers.helpers import request_with_validation
+
+collection_name = 'test_collection_update_acknowledgments'
+
+
+@pytest.fixture(autouse=True)
+def setup(on_disk_vectors):
+    basic_collection_setup(collection_name=collection_name, on_disk_vectors=on_disk_vectors)
+    yield
+    drop_collection(collection_name=collection_name)
+
+
+def test_update_acknowledgments():
+    response = request_with_validation(
+        api='/collections/{collection_name}/points',
+        method="PUT",
+        path_params={'collection_name': collection_name},
+        query_params={'wait': 'true'},
+        body={
+            "points": [
+                {"id": 100, "vector": [0.1, 0.2, 0.3, 0.4]},
+            ]
+        }
+    )
+    assert response.ok, response.text
+
+    acknowledgments = response.json()['result']['acknowledgments']
+    assert len(acknowledgments) == 1
+    assert acknowledgments[0]['status'] == 'completed'
+    assert 'error' not in acknowledgments[0]
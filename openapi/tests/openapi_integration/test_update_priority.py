@@ -0,0 +1,67 @@
+import pytest
+
+from .helpers.collection_setup import basic_collection_setup, drop_collection
+from .helpers.helpers import request_with_validation
+
+collection_name = 'test_collection_update_priority'
+
+
+@pytest.fixture(autouse=True)
+def setup(on_disk_vectors):
+    basic_collection_setup(collection_name=collection_name, on_disk_vectors=on_disk_vectors)
+    yield
+    drop_collection(collection_name=collection_name)
+
+
+def upsert_points(priority, ids, wait='true'):
+    return request_with_validation(
+        api='/collections/{collection_name}/points',
+        method="PUT",
+        path_params={'collection_name': collection_name},
+        query_params={'wait': wait, 'priority': priority},
+        body={
+            "points": [
+                {"id": point_id, "vector": [0.1, 0.2, 0.3, 0.4]}
+                for point_id in ids
+            ]
+        }
+    )
+
+
+def test_update_priority():
+    # Bulk updates are applied, even if there are more of them than the bulk lane fits at once
+    for batch in range(20):
+        response = upsert_points('bulk', range(100 + batch * 10, 110 + batch * 10), wait='false')
+        assert response.ok, response.text
+
+    response = upsert_points('interactive', [1000])
+    assert response.ok, response.text
+    assert response.json()['result']['status'] == 'completed'
+
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/batch',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        query_params={'wait': 'true', 'priority': 'bulk'},
+        body={
+            "operations": [
+                {"delete": {"points": [1000]}},
+            ]
+        }
+    )
+    assert response.ok, response.text
+
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/count',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        body={"exact": True}
+    )
+    assert response.ok, response.text
+    # 200 points of the backfill, and 6 points of the collection setup
+    assert response.json()['result']['count'] == 206
+
+
+def test_update_priority_validation():
+    response = upsert_points('urgent', [1000])
+    assert response.status_code == 400, response.text
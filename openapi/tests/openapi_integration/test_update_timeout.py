@@ -0,0 +1,47 @@
+import pytest
+
+from .helpers.collection_setup import basic_collection_setup, drop_collection
+from .helpers.helpers import request_with_validation
+
+collection_name = 'test_collection_update_timeout'
+
+
+@pytest.fixture(autouse=True)
+def setup(on_disk_vectors):
+    basic_collection_setup(collection_name=collection_name, on_disk_vectors=on_disk_vectors)
+    yield
+    drop_collection(collection_name=collection_name)
+
+
+def upsert_points(timeout):
+    return request_with_validation(
+        api='/collections/{collection_name}/points',
+        method="PUT",
+        path_params={'collection_name': collection_name},
+        query_params={'wait': 'true', 'timeout': timeout},
+        body={
+            "points": [
+                {"id": 100, "vector": [0.1, 0.2, 0.3, 0.4]},
+            ]
+        }
+    )
+
+
+def test_update_timeout():
+    response = upsert_points(10)
+    assert response.ok, response.text
+    assert response.json()['result']['status'] == 'completed'
+
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/delete',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        query_params={'wait': 'true', 'timeout': 10},
+        body={"points": [100]}
+    )
+    assert response.ok, response.text
+
+
+def test_update_timeout_validation():
+    response = upsert_points(0)
+    assert response.status_code == 400, response.text
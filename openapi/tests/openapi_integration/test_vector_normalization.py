@@ -0,0 +1,94 @@
+import pytest
+
+from .helpers.collection_setup import drop_collection
+from .helpers.helpers import request_with_validation
+
+collection_name = 'test_collection_normalization'
+
+
+@pytest.fixture(autouse=True)
+def setup(on_disk_vectors):
+    response = request_with_validation(
+        api='/collections/{collection_name}',
+        method="DELETE",
+        path_params={'collection_name': collection_name},
+    )
+    assert response.ok
+
+    response = request_with_validation(
+        api='/collections/{collection_name}',
+        method="PUT",
+        path_params={'collection_name': collection_name},
+        body={
+            "vectors": {
+                "normalized": {
+                    "size": 2,
+                    "distance": "Dot",
+                    "normalize": True,
+                    "on_disk": on_disk_vectors,
+                },
+                "raw": {
+                    "size": 2,
+                    "distance": "Dot",
+                    "on_disk": on_disk_vectors,
+                },
+            }
+        }
+    )
+    assert response.ok
+
+    response = request_with_validation(
+        api='/collections/{collection_name}/points',
+        method="PUT",
+        path_params={'collection_name': collection_name},
+        query_params={'wait': 'true'},
+        body={
+            "points": [
+                {"id": 1, "vector": {"normalized": [3.0, 4.0], "raw": [3.0, 4.0]}},
+                {"id": 2, "vector": {"normalized": [0.0, 10.0], "raw": [0.0, 10.0]}},
+            ]
+        }
+    )
+    assert response.ok
+    yield
+    drop_collection(collection_name=collection_name)
+
+
+def test_vectors_are_normalized_on_upsert():
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/{id}',
+        method="GET",
+        path_params={'collection_name': collection_name, 'id': 1},
+    )
+    assert response.ok
+    vectors = response.json()['result']['vector']
+    assert vectors['normalized'] == pytest.approx([0.6, 0.8])
+    assert vectors['raw'] == pytest.approx([3.0, 4.0])
+
+    response = request_with_validation(
+        api='/collections/{collection_name}',
+        method="GET",
+        path_params={'collection_name': collection_name},
+    )
+    assert response.ok
+    params = response.json()['result']['config']['params']['vectors']
+    assert params['normalized']['normalize'] is True
+    assert 'normalize' not in params['raw']
+
+
+def test_query_is_normalized():
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/search',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        body={
+            "vector": {"name": "normalized", "vector": [30.0, 40.0]},
+            "limit": 2,
+        }
+    )
+    assert response.ok
+    result = response.json()['result']
+    assert result[0]['id'] == 1
+    # Dot product of normalized vectors is cosine similarity
+    assert result[0]['score'] == pytest.approx(1.0, abs=1e-5)
+    assert result[1]['score'] == pytest.approx(0.8, abs=1e-5)
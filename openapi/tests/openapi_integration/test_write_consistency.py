@@ -0,0 +1,45 @@
+import pytest
+
+from .helpers.collection_setup import basic_collection_setup, drop_collection
+from .helpers.helpers import request_with_validation
+
+collection_name = 'test_collection_write_consistency'
+
+
+@pytest.fixture(autouse=True)
+def setup(on_disk_vectors):
+    basic_collection_setup(collection_name=collection_name, on_disk_vectors=on_disk_vectors)
+    yield
+    drop_collection(collection_name=collection_name)
+
+
+@pytest.mark.parametrize("write_consistency", ["majority", "all", "1"])
+def test_write_consistency(write_consistency):
+    response = request_with_validation(
+        api='/collections/{collection_name}/points',
+        method="PUT",
+        path_params={'collection_name': collection_name},
+        query_params={'wait': 'true', 'write_consistency': write_consistency},
+        body={
+            "points": [
+                {"id": 100, "vector": [0.1, 0.2, 0.3, 0.4]},
+            ]
+        }
+    )
+    assert response.ok, response.text
+    assert response.json()['result']['status'] == 'completed'
+
+
+def test_write_consistency_validation():
+    response = request_with_validation(
+        api='/collections/{collection_name}/points',
+        method="PUT",
+        path_params={'collection_name': collection_name},
+        query_params={'wait': 'true', 'write_consistency': '0'},
+        body={
+            "points": [
+                {"id": 100, "vector": [0.1, 0.2, 0.3, 0.4]},
+            ]
+        }
+    )
+    assert response.status_code == 400, response.text
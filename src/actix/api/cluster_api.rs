@@ -1,11 +1,12 @@
 use actix_web::rt::time::Instant;
-use actix_web::{delete, get, post, web, Responder};
-use actix_web_validator::Query;
+use actix_web::{delete, get, post, put, web, Responder};
+use actix_web_validator::{Json, Query};
 use serde::Deserialize;
 use storage::content_manager::consensus_ops::ConsensusOperations;
 use storage::content_manager::errors::StorageError;
 use storage::content_manager::toc::TableOfContent;
 use storage::dispatcher::Dispatcher;
+use storage::types::SetPeerRole;
 use validator::Validate;
 
 use crate::actix::helpers::process_response;
@@ -68,9 +69,47 @@ async fn remove_peer(
     process_response(response, timing)
 }
 
+#[put("/cluster/peer/{peer_id}/role")]
+async fn set_peer_role(
+    dispatcher: web::Data<Dispatcher>,
+    peer_id: web::Path<u64>,
+    request: Json<SetPeerRole>,
+    Query(params): Query<QueryParams>,
+) -> impl Responder {
+    let timing = Instant::now();
+    let peer_id = peer_id.into_inner();
+
+    let response = match dispatcher.consensus_state() {
+        Some(consensus_state) => {
+            if !consensus_state.peer_address_by_id().contains_key(&peer_id) {
+                return process_response::<()>(
+                    Err(StorageError::NotFound {
+                        description: format!("Peer {peer_id} does not exist"),
+                    }),
+                    timing,
+                );
+            }
+            consensus_state
+                .propose_consensus_op_with_await(
+                    ConsensusOperations::SetPeerRole {
+                        peer_id,
+                        role: request.role,
+                    },
+                    params.timeout.map(std::time::Duration::from_secs),
+                )
+                .await
+        }
+        None => Err(StorageError::BadRequest {
+            description: "Distributed mode disabled.".to_string(),
+        }),
+    };
+    process_response(response, timing)
+}
+
 // Configure services
 pub fn config_cluster_api(cfg: &mut web::ServiceConfig) {
     cfg.service(cluster_status)
         .service(remove_peer)
+        .service(set_peer_role)
         .service(recover_current_peer);
 }
@@ -3,18 +3,27 @@ use std::time::Duration;
 use actix_web::rt::time::Instant;
 use actix_web::{delete, get, patch, post, put, web, Responder};
 use actix_web_validator::{Json, Path, Query};
+use collection::collection::read_only::SetReadOnlyRequest;
+use collection::collection::stored_queries::StoredQuery;
 use collection::operations::cluster_ops::ClusterOperations;
+use collection::operations::dimensionality_reduction::TrainDimensionalityReduction;
+use collection::shards::shard::ShardId;
+use futures::TryFutureExt as _;
 use serde::Deserialize;
 use storage::content_manager::collection_meta_ops::{
-    ChangeAliasesOperation, CollectionMetaOperations, CreateCollection, CreateCollectionOperation,
-    DeleteCollectionOperation, UpdateCollection, UpdateCollectionOperation,
+    ChangeAliasesOperation, CloneCollection, CollectionMetaOperations, CreateCollection,
+    CreateCollectionOperation, DeleteCollectionOperation, DeleteCollectionTemplate,
+    DeleteStoredQuery, SetCollectionTemplate, SetReadOnly, SetStoredQuery, UpdateCollection,
+    UpdateCollectionOperation,
 };
+use storage::content_manager::collection_templates::CollectionTemplate;
 use storage::content_manager::toc::TableOfContent;
 use storage::dispatcher::Dispatcher;
 use validator::Validate;
 
 use super::CollectionPath;
 use crate::actix::api::StrictCollectionPath;
+use crate::actix::helpers;
 use crate::actix::helpers::process_response;
 use crate::common::collections::*;
 
@@ -30,6 +39,25 @@ impl WaitTimeout {
     }
 }
 
+#[derive(Debug, Deserialize, Validate)]
+pub struct CompactionParam {
+    wait: Option<bool>,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+struct StoredQueryPath {
+    #[validate(length(min = 1, max = 255))]
+    name: String,
+    #[validate(length(min = 1, max = 255))]
+    query_name: String,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+struct CollectionTemplatePath {
+    #[validate(length(min = 1, max = 255))]
+    template_name: String,
+}
+
 #[get("/collections")]
 async fn get_collections(toc: web::Data<TableOfContent>) -> impl Responder {
     let timing = Instant::now();
@@ -168,6 +196,217 @@ async fn update_collection_cluster(
     process_response(response, timing)
 }
 
+#[post("/collections/{name}/dimensionality_reduction")]
+async fn train_dimensionality_reduction(
+    toc: web::Data<TableOfContent>,
+    dispatcher: web::Data<Dispatcher>,
+    collection: Path<CollectionPath>,
+    request: Json<TrainDimensionalityReduction>,
+    Query(query): Query<WaitTimeout>,
+) -> impl Responder {
+    let timing = Instant::now();
+    let response = do_train_dimensionality_reduction(
+        toc.get_ref(),
+        dispatcher.get_ref(),
+        &collection.name,
+        request.into_inner(),
+        query.timeout(),
+    )
+    .await;
+    process_response(response, timing)
+}
+
+#[post("/collections/{name}/clone")]
+async fn clone_collection(
+    toc: web::Data<TableOfContent>,
+    dispatcher: web::Data<Dispatcher>,
+    collection: Path<CollectionPath>,
+    operation: Json<CloneCollection>,
+    Query(query): Query<WaitTimeout>,
+) -> impl Responder {
+    let timing = Instant::now();
+    let response = do_clone_collection(
+        toc.get_ref(),
+        dispatcher.get_ref(),
+        &collection.name,
+        operation.into_inner(),
+        query.timeout(),
+    )
+    .await;
+    process_response(response, timing)
+}
+
+#[post("/collections/{name}/warmup")]
+async fn warm_up_collection(
+    toc: web::Data<TableOfContent>,
+    collection: Path<CollectionPath>,
+) -> impl Responder {
+    let timing = Instant::now();
+    let response = do_warm_up_collection(toc.get_ref(), &collection.name).await;
+    process_response(response, timing)
+}
+
+#[post("/collections/{collection}/shards/{shard}/compact")]
+async fn compact_shard(
+    toc: web::Data<TableOfContent>,
+    path: web::Path<(String, ShardId)>,
+    query: Query<CompactionParam>,
+) -> impl Responder {
+    let (collection, shard) = path.into_inner();
+    let future = do_compact_shard(toc.into_inner(), collection, shard).map_err(Into::into);
+
+    helpers::time_or_accept(future, query.wait.unwrap_or(true)).await
+}
+
+#[get("/collections/{name}/stored_queries")]
+async fn get_stored_queries(
+    toc: web::Data<TableOfContent>,
+    collection: Path<CollectionPath>,
+) -> impl Responder {
+    let timing = Instant::now();
+    let response = do_list_stored_queries(toc.get_ref(), &collection.name).await;
+    process_response(response, timing)
+}
+
+#[put("/collections/{name}/stored_queries/{query_name}")]
+async fn set_stored_query(
+    dispatcher: web::Data<Dispatcher>,
+    path: Path<StoredQueryPath>,
+    query: Json<StoredQuery>,
+    Query(wait_timeout): Query<WaitTimeout>,
+) -> impl Responder {
+    let timing = Instant::now();
+    let path = path.into_inner();
+    let response = dispatcher
+        .submit_collection_meta_op(
+            CollectionMetaOperations::SetStoredQuery(SetStoredQuery {
+                collection_name: path.name,
+                query_name: path.query_name,
+                query: query.into_inner(),
+            }),
+            wait_timeout.timeout(),
+        )
+        .await;
+    process_response(response, timing)
+}
+
+#[delete("/collections/{name}/stored_queries/{query_name}")]
+async fn delete_stored_query(
+    dispatcher: web::Data<Dispatcher>,
+    path: Path<StoredQueryPath>,
+    Query(query): Query<WaitTimeout>,
+) -> impl Responder {
+    let timing = Instant::now();
+    let path = path.into_inner();
+    let response = dispatcher
+        .submit_collection_meta_op(
+            CollectionMetaOperations::DeleteStoredQuery(DeleteStoredQuery {
+                collection_name: path.name,
+                query_name: path.query_name,
+            }),
+            query.timeout(),
+        )
+        .await;
+    process_response(response, timing)
+}
+
+#[get("/collections/{name}/read_only")]
+async fn get_read_only(
+    toc: web::Data<TableOfContent>,
+    collection: Path<CollectionPath>,
+) -> impl Responder {
+    let timing = Instant::now();
+    let response = do_get_read_only_state(toc.get_ref(), &collection.name).await;
+    process_response(response, timing)
+}
+
+#[put("/collections/{name}/read_only")]
+async fn set_read_only(
+    dispatcher: web::Data<Dispatcher>,
+    collection: Path<CollectionPath>,
+    request: Json<SetReadOnlyRequest>,
+    Query(wait_timeout): Query<WaitTimeout>,
+) -> impl Responder {
+    let timing = Instant::now();
+    let request = request.into_inner();
+    let response = dispatcher
+        .submit_collection_meta_op(
+            CollectionMetaOperations::SetReadOnly(SetReadOnly {
+                collection_name: collection.into_inner().name,
+                shard_id: request.shard_id,
+                freeze: request.freeze(),
+            }),
+            wait_timeout.timeout(),
+        )
+        .await;
+    process_response(response, timing)
+}
+
+#[get("/collections/{name}/usage")]
+async fn get_collection_usage(
+    toc: web::Data<TableOfContent>,
+    collection: Path<CollectionPath>,
+) -> impl Responder {
+    let timing = Instant::now();
+    let response = do_get_collection_usage(toc.get_ref(), &collection.name).await;
+    process_response(response, timing)
+}
+
+#[get("/collection_templates")]
+async fn get_collection_templates(toc: web::Data<TableOfContent>) -> impl Responder {
+    let timing = Instant::now();
+    let response = Ok(toc.collection_templates().await);
+    process_response(response, timing)
+}
+
+#[get("/collection_templates/{template_name}")]
+async fn get_collection_template(
+    toc: web::Data<TableOfContent>,
+    path: Path<CollectionTemplatePath>,
+) -> impl Responder {
+    let timing = Instant::now();
+    let response = toc.get_collection_template(&path.template_name).await;
+    process_response(response, timing)
+}
+
+#[put("/collection_templates/{template_name}")]
+async fn set_collection_template(
+    dispatcher: web::Data<Dispatcher>,
+    path: Path<CollectionTemplatePath>,
+    template: Json<CollectionTemplate>,
+    Query(wait_timeout): Query<WaitTimeout>,
+) -> impl Responder {
+    let timing = Instant::now();
+    let response = dispatcher
+        .submit_collection_meta_op(
+            CollectionMetaOperations::SetCollectionTemplate(SetCollectionTemplate {
+                template_name: path.into_inner().template_name,
+                template: template.into_inner(),
+            }),
+            wait_timeout.timeout(),
+        )
+        .await;
+    process_response(response, timing)
+}
+
+#[delete("/collection_templates/{template_name}")]
+async fn delete_collection_template(
+    dispatcher: web::Data<Dispatcher>,
+    path: Path<CollectionTemplatePath>,
+    Query(query): Query<WaitTimeout>,
+) -> impl Responder {
+    let timing = Instant::now();
+    let response = dispatcher
+        .submit_collection_meta_op(
+            CollectionMetaOperations::DeleteCollectionTemplate(DeleteCollectionTemplate {
+                template_name: path.into_inner().template_name,
+            }),
+            query.timeout(),
+        )
+        .await;
+    process_response(response, timing)
+}
+
 // Configure services
 pub fn config_collections_api(cfg: &mut web::ServiceConfig) {
     cfg.service(get_collections)
@@ -179,7 +418,21 @@ pub fn config_collections_api(cfg: &mut web::ServiceConfig) {
         .service(get_collection_aliases)
         .service(update_aliases)
         .service(get_cluster_info)
-        .service(update_collection_cluster);
+        .service(update_collection_cluster)
+        .service(clone_collection)
+        .service(train_dimensionality_reduction)
+        .service(warm_up_collection)
+        .service(compact_shard)
+        .service(get_stored_queries)
+        .service(set_stored_query)
+        .service(delete_stored_query)
+        .service(get_read_only)
+        .service(set_read_only)
+        .service(get_collection_usage)
+        .service(get_collection_templates)
+        .service(get_collection_template)
+        .service(set_collection_template)
+        .service(delete_collection_template);
 }
 
 #[cfg(test)]
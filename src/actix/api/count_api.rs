@@ -1,8 +1,12 @@
 use actix_web::rt::time::Instant;
+use actix_web::web::ReqData;
 use actix_web::{post, web, Responder};
 use actix_web_validator::{Json, Path, Query};
+use collection::collection::access_tags::AccessTags;
 use collection::operations::shard_selector_internal::ShardSelectorInternal;
 use collection::operations::types::CountRequest;
+use collection::shards::replica_set::read_priority::ReadPriority;
+use collection::shards::replica_set::stale_reads::StaleReads;
 use storage::content_manager::toc::TableOfContent;
 
 use super::CollectionPath;
@@ -16,6 +20,9 @@ async fn count_points(
     collection: Path<CollectionPath>,
     request: Json<CountRequest>,
     params: Query<ReadParams>,
+    access_tags: Option<ReqData<AccessTags>>,
+    read_priority: ReqData<ReadPriority>,
+    stale_reads: Option<ReqData<StaleReads>>,
 ) -> impl Responder {
     let timing = Instant::now();
 
@@ -35,6 +42,9 @@ async fn count_points(
         count_request,
         params.consistency,
         shard_selector,
+        access_tags.as_deref(),
+        read_priority.into_inner(),
+        stale_reads.as_deref(),
         // ToDo: use timeout from params
     )
     .await;
@@ -1,7 +1,11 @@
+use actix_web::web::ReqData;
 use actix_web::{post, web, Responder};
 use actix_web_validator::{Json, Path, Query};
+use collection::collection::access_tags::AccessTags;
 use collection::operations::shard_selector_internal::ShardSelectorInternal;
 use collection::operations::types::{DiscoverRequest, DiscoverRequestBatch};
+use collection::shards::replica_set::read_priority::ReadPriority;
+use collection::shards::replica_set::stale_reads::StaleReads;
 use storage::content_manager::toc::TableOfContent;
 use tokio::time::Instant;
 
@@ -16,6 +20,9 @@ async fn discover_points(
     collection: Path<CollectionPath>,
     request: Json<DiscoverRequest>,
     params: Query<ReadParams>,
+    access_tags: Option<ReqData<AccessTags>>,
+    read_priority: ReqData<ReadPriority>,
+    stale_reads: Option<ReqData<StaleReads>>,
 ) -> impl Responder {
     let timing = Instant::now();
 
@@ -36,6 +43,9 @@ async fn discover_points(
             params.consistency,
             shard_selection,
             params.timeout(),
+            access_tags.as_deref(),
+            read_priority.into_inner(),
+            stale_reads.as_deref(),
         )
         .await;
 
@@ -48,6 +58,9 @@ async fn discover_batch_points(
     collection: Path<CollectionPath>,
     request: Json<DiscoverRequestBatch>,
     params: Query<ReadParams>,
+    access_tags: Option<ReqData<AccessTags>>,
+    read_priority: ReqData<ReadPriority>,
+    stale_reads: Option<ReqData<StaleReads>>,
 ) -> impl Responder {
     let timing = Instant::now();
 
@@ -57,6 +70,9 @@ async fn discover_batch_points(
         request.into_inner(),
         params.consistency,
         params.timeout(),
+        access_tags.as_deref(),
+        read_priority.into_inner(),
+        stale_reads.as_deref(),
     )
     .await;
 
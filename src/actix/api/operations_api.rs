@@ -0,0 +1,61 @@
+use actix_web::rt::time::Instant;
+use actix_web::{get, post, web, Responder};
+use actix_web_validator::Query;
+use serde::Deserialize;
+use storage::content_manager::toc::TableOfContent;
+use storage::dispatcher::Dispatcher;
+use validator::Validate;
+
+use crate::actix::helpers::process_response;
+use crate::common::running_operations::{
+    do_cancel_operation, do_get_operation, do_list_operations,
+};
+
+#[derive(Debug, Deserialize, Validate)]
+struct CancelOperationParams {
+    #[serde(default)]
+    #[validate(range(min = 1))]
+    timeout: Option<u64>,
+}
+
+#[get("/operations")]
+async fn list_operations(toc: web::Data<TableOfContent>) -> impl Responder {
+    let timing = Instant::now();
+    let response = do_list_operations(toc.get_ref()).await;
+    process_response(response, timing)
+}
+
+#[get("/operations/{operation_id}")]
+async fn get_operation(
+    toc: web::Data<TableOfContent>,
+    operation_id: web::Path<u64>,
+) -> impl Responder {
+    let timing = Instant::now();
+    let response = do_get_operation(toc.get_ref(), operation_id.into_inner()).await;
+    process_response(response, timing)
+}
+
+#[post("/operations/{operation_id}/cancel")]
+async fn cancel_operation(
+    toc: web::Data<TableOfContent>,
+    dispatcher: web::Data<Dispatcher>,
+    operation_id: web::Path<u64>,
+    Query(params): Query<CancelOperationParams>,
+) -> impl Responder {
+    let timing = Instant::now();
+    let response = do_cancel_operation(
+        toc.get_ref(),
+        dispatcher.get_ref(),
+        operation_id.into_inner(),
+        params.timeout.map(std::time::Duration::from_secs),
+    )
+    .await;
+    process_response(response, timing)
+}
+
+// Configure services
+pub fn config_operations_api(cfg: &mut web::ServiceConfig) {
+    cfg.service(list_operations)
+        .service(get_operation)
+        .service(cancel_operation);
+}
@@ -15,6 +15,15 @@ pub struct ReadParams {
     pub timeout: Option<NonZeroU64>,
 }
 
+/// Query parameters of the search requests, which may tolerate failures of individual shards
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Deserialize, JsonSchema, Validate)]
+pub struct PartialResultsParams {
+    /// If set, results of the shards which responded are returned along with the list of
+    /// failed shards, instead of failing the whole request
+    #[serde(default)]
+    pub partial_results: bool,
+}
+
 impl ReadParams {
     pub fn timeout(&self) -> Option<Duration> {
         self.timeout.map(|num| Duration::from_secs(num.get()))
@@ -78,6 +87,16 @@ mod test {
         assert!(try_deserialize(&str("0")).is_err());
     }
 
+    #[test]
+    fn deserialize_partial_results() {
+        let params: PartialResultsParams = serde_urlencoded::from_str("").unwrap();
+        assert!(!params.partial_results);
+
+        let params: PartialResultsParams =
+            serde_urlencoded::from_str("consistency=all&partial_results=true").unwrap();
+        assert!(params.partial_results);
+    }
+
     fn test(value: &str, params: ReadParams) {
         test_str(&str(value), params);
     }
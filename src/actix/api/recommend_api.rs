@@ -1,13 +1,17 @@
 use std::time::Duration;
 
 use actix_web::rt::time::Instant;
+use actix_web::web::ReqData;
 use actix_web::{post, web, Responder};
 use actix_web_validator::{Json, Path, Query};
+use collection::collection::access_tags::AccessTags;
 use collection::operations::consistency_params::ReadConsistency;
 use collection::operations::shard_selector_internal::ShardSelectorInternal;
 use collection::operations::types::{
     RecommendGroupsRequest, RecommendRequest, RecommendRequestBatch,
 };
+use collection::shards::replica_set::read_priority::ReadPriority;
+use collection::shards::replica_set::stale_reads::StaleReads;
 use segment::types::ScoredPoint;
 use storage::content_manager::errors::StorageError;
 use storage::content_manager::toc::TableOfContent;
@@ -22,6 +26,9 @@ async fn recommend_points(
     collection: Path<CollectionPath>,
     request: Json<RecommendRequest>,
     params: Query<ReadParams>,
+    access_tags: Option<ReqData<AccessTags>>,
+    read_priority: ReqData<ReadPriority>,
+    stale_reads: Option<ReqData<StaleReads>>,
 ) -> impl Responder {
     let timing = Instant::now();
 
@@ -42,6 +49,9 @@ async fn recommend_points(
             params.consistency,
             shard_selection,
             params.timeout(),
+            access_tags.as_deref(),
+            read_priority.into_inner(),
+            stale_reads.as_deref(),
         )
         .await;
 
@@ -54,6 +64,9 @@ async fn do_recommend_batch_points(
     request: RecommendRequestBatch,
     read_consistency: Option<ReadConsistency>,
     timeout: Option<Duration>,
+    access_tags: Option<&AccessTags>,
+    read_priority: ReadPriority,
+    stale_reads: Option<&StaleReads>,
 ) -> Result<Vec<Vec<ScoredPoint>>, StorageError> {
     let requests = request
         .searches
@@ -68,8 +81,16 @@ async fn do_recommend_batch_points(
         })
         .collect();
 
-    toc.recommend_batch(collection_name, requests, read_consistency, timeout)
-        .await
+    toc.recommend_batch(
+        collection_name,
+        requests,
+        read_consistency,
+        timeout,
+        access_tags,
+        read_priority,
+        stale_reads,
+    )
+    .await
 }
 
 #[post("/collections/{name}/points/recommend/batch")]
@@ -78,6 +99,9 @@ async fn recommend_batch_points(
     collection: Path<CollectionPath>,
     request: Json<RecommendRequestBatch>,
     params: Query<ReadParams>,
+    access_tags: Option<ReqData<AccessTags>>,
+    read_priority: ReqData<ReadPriority>,
+    stale_reads: Option<ReqData<StaleReads>>,
 ) -> impl Responder {
     let timing = Instant::now();
 
@@ -87,6 +111,9 @@ async fn recommend_batch_points(
         request.into_inner(),
         params.consistency,
         params.timeout(),
+        access_tags.as_deref(),
+        read_priority.into_inner(),
+        stale_reads.as_deref(),
     )
     .await;
 
@@ -99,6 +126,9 @@ async fn recommend_point_groups(
     collection: Path<CollectionPath>,
     request: Json<RecommendGroupsRequest>,
     params: Query<ReadParams>,
+    access_tags: Option<ReqData<AccessTags>>,
+    read_priority: ReqData<ReadPriority>,
+    stale_reads: Option<ReqData<StaleReads>>,
 ) -> impl Responder {
     let timing = Instant::now();
 
@@ -119,6 +149,9 @@ async fn recommend_point_groups(
         params.consistency,
         shard_selection,
         params.timeout(),
+        access_tags.as_deref(),
+        read_priority.into_inner(),
+        stale_reads.as_deref(),
     )
     .await;
 
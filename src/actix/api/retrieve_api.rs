@@ -1,9 +1,15 @@
 use actix_web::rt::time::Instant;
+use actix_web::web::ReqData;
 use actix_web::{get, post, web, Responder};
 use actix_web_validator::{Json, Path, Query};
+use collection::collection::access_tags::AccessTags;
 use collection::operations::consistency_params::ReadConsistency;
 use collection::operations::shard_selector_internal::ShardSelectorInternal;
-use collection::operations::types::{PointRequest, PointRequestInternal, Record, ScrollRequest};
+use collection::operations::types::{
+    OpenScrollSession, PointRequest, PointRequestInternal, Record, ScrollRequest,
+};
+use collection::shards::replica_set::read_priority::ReadPriority;
+use collection::shards::replica_set::stale_reads::StaleReads;
 use segment::types::{PointIdType, WithPayloadInterface};
 use serde::Deserialize;
 use storage::content_manager::errors::StorageError;
@@ -27,18 +33,31 @@ async fn do_get_point(
     collection_name: &str,
     point_id: PointIdType,
     read_consistency: Option<ReadConsistency>,
+    access_tags: Option<&AccessTags>,
+    read_priority: ReadPriority,
+    stale_reads: Option<&StaleReads>,
 ) -> Result<Option<Record>, StorageError> {
     let request = PointRequestInternal {
         ids: vec![point_id],
         with_payload: Some(WithPayloadInterface::Bool(true)),
         with_vector: true.into(),
+        as_of: None,
+        with_version: false,
     };
 
     let shard_selection = ShardSelectorInternal::All;
 
-    toc.retrieve(collection_name, request, read_consistency, shard_selection)
-        .await
-        .map(|points| points.into_iter().next())
+    toc.retrieve(
+        collection_name,
+        request,
+        read_consistency,
+        shard_selection,
+        access_tags,
+        read_priority,
+        stale_reads,
+    )
+    .await
+    .map(|points| points.into_iter().next())
 }
 
 #[get("/collections/{name}/points/{id}")]
@@ -47,6 +66,9 @@ async fn get_point(
     collection: Path<CollectionPath>,
     point: Path<PointPath>,
     params: Query<ReadParams>,
+    access_tags: Option<ReqData<AccessTags>>,
+    read_priority: ReqData<ReadPriority>,
+    stale_reads: Option<ReqData<StaleReads>>,
 ) -> impl Responder {
     let timing = Instant::now();
 
@@ -68,6 +90,9 @@ async fn get_point(
         &collection.name,
         point_id,
         params.consistency,
+        access_tags.as_deref(),
+        read_priority.into_inner(),
+        stale_reads.as_deref(),
     )
     .await;
 
@@ -89,6 +114,9 @@ async fn get_points(
     collection: Path<CollectionPath>,
     request: Json<PointRequest>,
     params: Query<ReadParams>,
+    access_tags: Option<ReqData<AccessTags>>,
+    read_priority: ReqData<ReadPriority>,
+    stale_reads: Option<ReqData<StaleReads>>,
 ) -> impl Responder {
     let timing = Instant::now();
 
@@ -108,6 +136,9 @@ async fn get_points(
         point_request,
         params.consistency,
         shard_selection,
+        access_tags.as_deref(),
+        read_priority.into_inner(),
+        stale_reads.as_deref(),
     )
     .await;
     process_response(response, timing)
@@ -119,6 +150,9 @@ async fn scroll_points(
     collection: Path<CollectionPath>,
     request: Json<ScrollRequest>,
     params: Query<ReadParams>,
+    access_tags: Option<ReqData<AccessTags>>,
+    read_priority: ReqData<ReadPriority>,
+    stale_reads: Option<ReqData<StaleReads>>,
 ) -> impl Responder {
     let timing = Instant::now();
 
@@ -138,6 +172,30 @@ async fn scroll_points(
             scroll_request,
             params.consistency,
             shard_selection,
+            access_tags.as_deref(),
+            read_priority.into_inner(),
+            stale_reads.as_deref(),
+        )
+        .await;
+
+    process_response(response, timing)
+}
+
+#[post("/collections/{name}/points/scroll/session")]
+async fn open_scroll_session(
+    toc: web::Data<TableOfContent>,
+    collection: Path<CollectionPath>,
+    request: Json<OpenScrollSession>,
+    params: Query<ReadParams>,
+) -> impl Responder {
+    let timing = Instant::now();
+
+    let response = toc
+        .open_scroll_session(
+            &collection.name,
+            request.into_inner(),
+            params.consistency,
+            ShardSelectorInternal::All,
         )
         .await;
 
@@ -1,17 +1,32 @@
 use actix_web::rt::time::Instant;
+use actix_web::web::ReqData;
 use actix_web::{post, web, Responder};
 use actix_web_validator::{Json, Path, Query};
+use collection::collection::access_tags::AccessTags;
+use collection::distinct::CoreDistinct;
+use collection::hybrid_search::HybridSearchRequest;
+use collection::multi_search::MultiCollectionSearchRequest;
+use collection::operations::consistency_token::ConsistencyToken;
+use collection::operations::document_ops::SearchDocumentRequest;
 use collection::operations::shard_selector_internal::ShardSelectorInternal;
 use collection::operations::types::{
-    CoreSearchRequest, SearchGroupsRequest, SearchRequest, SearchRequestBatch,
+    CollectionError, SearchGroupsRequest, SearchPageRequest, SearchRequest, SearchRequestBatch,
 };
+use collection::rescore_search::RescoreSearchRequest;
+use collection::score_formula::rescored_core_search;
+use collection::search_matrix::SearchMatrixRequest;
+use collection::shards::replica_set::read_priority::ReadPriority;
+use collection::shards::replica_set::stale_reads::StaleReads;
+use storage::content_manager::errors::StorageError;
 use storage::content_manager::toc::TableOfContent;
 
-use super::read_params::ReadParams;
+use super::read_params::{PartialResultsParams, ReadParams};
 use super::CollectionPath;
 use crate::actix::helpers::process_response;
 use crate::common::points::{
-    do_core_search_points, do_search_batch_points, do_search_point_groups,
+    do_core_search_points, do_core_search_points_partial, do_hybrid_search_points,
+    do_multi_collection_search, do_search_batch_points, do_search_batch_points_partial,
+    do_search_matrix_points, do_search_page_points, do_search_point_groups, infer_search_request,
 };
 
 #[post("/collections/{name}/points/search")]
@@ -20,12 +35,19 @@ async fn search_points(
     collection: Path<CollectionPath>,
     request: Json<SearchRequest>,
     params: Query<ReadParams>,
+    partial_params: Query<PartialResultsParams>,
+    access_tags: Option<ReqData<AccessTags>>,
+    read_priority: ReqData<ReadPriority>,
+    stale_reads: Option<ReqData<StaleReads>>,
 ) -> impl Responder {
     let timing = Instant::now();
 
     let SearchRequest {
         search_request,
         shard_key,
+        consistency_token,
+        rescore,
+        distinct_by,
     } = request.into_inner();
 
     let shard_selection = match shard_key {
@@ -33,13 +55,278 @@ async fn search_points(
         Some(shard_keys) => shard_keys.into(),
     };
 
+    let consistency_token = match ConsistencyToken::merge_all([consistency_token.as_deref()]) {
+        Ok(consistency_token) => consistency_token,
+        Err(err) => return process_response::<()>(Err(err.into()), timing),
+    };
+
+    let mut core_request = match rescored_core_search(search_request, rescore) {
+        Ok(core_request) => core_request,
+        Err(err) => return process_response::<()>(Err(err.into()), timing),
+    };
+    core_request.distinct_by = distinct_by
+        .map(|distinct| CoreDistinct::new(distinct, core_request.offset, core_request.limit));
+
+    if partial_params.partial_results {
+        let response = do_core_search_points_partial(
+            toc.get_ref(),
+            &collection.name,
+            core_request,
+            params.consistency,
+            consistency_token.as_ref(),
+            shard_selection,
+            params.timeout(),
+            access_tags.as_deref(),
+            read_priority.into_inner(),
+            stale_reads.as_deref(),
+        )
+        .await;
+        return process_response(response, timing);
+    }
+
+    let response = do_core_search_points(
+        toc.get_ref(),
+        &collection.name,
+        core_request,
+        params.consistency,
+        consistency_token.as_ref(),
+        shard_selection,
+        params.timeout(),
+        access_tags.as_deref(),
+        read_priority.into_inner(),
+        stale_reads.as_deref(),
+    )
+    .await;
+
+    process_response(response, timing)
+}
+
+#[post("/collections/{name}/points/search/document")]
+async fn search_points_by_document(
+    toc: web::Data<TableOfContent>,
+    collection: Path<CollectionPath>,
+    request: Json<SearchDocumentRequest>,
+    params: Query<ReadParams>,
+) -> impl Responder {
+    let timing = Instant::now();
+
+    let request =
+        match infer_search_request(toc.get_ref(), &collection.name, request.into_inner()).await {
+            Ok(request) => request,
+            Err(err) => return process_response::<()>(Err(err), timing),
+        };
+
+    let SearchRequest {
+        search_request,
+        shard_key,
+        consistency_token,
+        rescore: _,
+        distinct_by: _,
+    } = request;
+
+    let shard_selection = match shard_key {
+        None => ShardSelectorInternal::All,
+        Some(shard_keys) => shard_keys.into(),
+    };
+
+    let consistency_token = match ConsistencyToken::merge_all([consistency_token.as_deref()]) {
+        Ok(consistency_token) => consistency_token,
+        Err(err) => return process_response::<()>(Err(err.into()), timing),
+    };
+
+    let response = do_core_search_points(
+        toc.get_ref(),
+        &collection.name,
+        search_request.into(),
+        params.consistency,
+        consistency_token.as_ref(),
+        shard_selection,
+        params.timeout(),
+        None,
+        ReadPriority::Normal,
+        None,
+    )
+    .await;
+
+    process_response(response, timing)
+}
+
+#[post("/collections/{name}/points/search/hybrid")]
+async fn hybrid_search_points(
+    toc: web::Data<TableOfContent>,
+    collection: Path<CollectionPath>,
+    request: Json<HybridSearchRequest>,
+    params: Query<ReadParams>,
+    read_priority: ReqData<ReadPriority>,
+    stale_reads: Option<ReqData<StaleReads>>,
+) -> impl Responder {
+    let timing = Instant::now();
+
+    let mut request = request.into_inner();
+
+    let shard_selection = match request.shard_key.take() {
+        None => ShardSelectorInternal::All,
+        Some(shard_keys) => shard_keys.into(),
+    };
+
+    let consistency_token =
+        match ConsistencyToken::merge_all([request.consistency_token.take().as_deref()]) {
+            Ok(consistency_token) => consistency_token,
+            Err(err) => return process_response::<()>(Err(err.into()), timing),
+        };
+
+    let response = do_hybrid_search_points(
+        toc.get_ref(),
+        &collection.name,
+        request,
+        params.consistency,
+        consistency_token.as_ref(),
+        shard_selection,
+        params.timeout(),
+        read_priority.into_inner(),
+        stale_reads.as_deref(),
+    )
+    .await;
+
+    process_response(response, timing)
+}
+
+#[post("/collections/{name}/points/search/matrix")]
+async fn search_matrix_points(
+    toc: web::Data<TableOfContent>,
+    collection: Path<CollectionPath>,
+    request: Json<SearchMatrixRequest>,
+    params: Query<ReadParams>,
+    read_priority: ReqData<ReadPriority>,
+    stale_reads: Option<ReqData<StaleReads>>,
+) -> impl Responder {
+    let timing = Instant::now();
+
+    let mut request = request.into_inner();
+
+    let shard_selection = match request.shard_key.take() {
+        None => ShardSelectorInternal::All,
+        Some(shard_keys) => shard_keys.into(),
+    };
+
+    let consistency_token =
+        match ConsistencyToken::merge_all([request.consistency_token.take().as_deref()]) {
+            Ok(consistency_token) => consistency_token,
+            Err(err) => return process_response::<()>(Err(err.into()), timing),
+        };
+
+    let response = do_search_matrix_points(
+        toc.get_ref(),
+        &collection.name,
+        request,
+        params.consistency,
+        consistency_token.as_ref(),
+        shard_selection,
+        params.timeout(),
+        read_priority.into_inner(),
+        stale_reads.as_deref(),
+    )
+    .await;
+
+    process_response(response, timing)
+}
+
+#[post("/collections/{name}/points/search/rescore")]
+async fn rescore_search_points(
+    toc: web::Data<TableOfContent>,
+    collection: Path<CollectionPath>,
+    request: Json<RescoreSearchRequest>,
+    params: Query<ReadParams>,
+) -> impl Responder {
+    let timing = Instant::now();
+
+    let mut request = request.into_inner();
+
+    let shard_selection = match request.shard_key.take() {
+        None => ShardSelectorInternal::All,
+        Some(shard_keys) => shard_keys.into(),
+    };
+
+    let consistency_token =
+        match ConsistencyToken::merge_all([request.consistency_token.take().as_deref()]) {
+            Ok(consistency_token) => consistency_token,
+            Err(err) => return process_response::<()>(Err(err.into()), timing),
+        };
+
     let response = do_core_search_points(
+        toc.get_ref(),
+        &collection.name,
+        request.into(),
+        params.consistency,
+        consistency_token.as_ref(),
+        shard_selection,
+        params.timeout(),
+        None,
+        ReadPriority::Normal,
+        None,
+    )
+    .await;
+
+    process_response(response, timing)
+}
+
+#[post("/collections/{name}/points/search/page")]
+async fn search_points_page(
+    toc: web::Data<TableOfContent>,
+    collection: Path<CollectionPath>,
+    request: Json<SearchPageRequest>,
+    params: Query<ReadParams>,
+    access_tags: Option<ReqData<AccessTags>>,
+    read_priority: ReqData<ReadPriority>,
+    stale_reads: Option<ReqData<StaleReads>>,
+) -> impl Responder {
+    let timing = Instant::now();
+
+    let SearchPageRequest {
+        search_request:
+            SearchRequest {
+                search_request,
+                shard_key,
+                consistency_token,
+                rescore,
+                distinct_by,
+            },
+        cursor,
+    } = request.into_inner();
+
+    // Cursor follows the order of the vector scores
+    if rescore.is_some() {
+        let err = StorageError::bad_input("Rescoring is not supported by the paginated search");
+        return process_response::<()>(Err(err), timing);
+    }
+    // Pages are deduplicated independently, so the same value could be returned on several pages
+    if distinct_by.is_some() {
+        let err = StorageError::bad_input("Deduplication is not supported by the paginated search");
+        return process_response::<()>(Err(err), timing);
+    }
+
+    let shard_selection = match shard_key {
+        None => ShardSelectorInternal::All,
+        Some(shard_keys) => shard_keys.into(),
+    };
+
+    let consistency_token = match ConsistencyToken::merge_all([consistency_token.as_deref()]) {
+        Ok(consistency_token) => consistency_token,
+        Err(err) => return process_response::<()>(Err(err.into()), timing),
+    };
+
+    let response = do_search_page_points(
         toc.get_ref(),
         &collection.name,
         search_request.into(),
+        cursor.as_deref(),
         params.consistency,
+        consistency_token.as_ref(),
         shard_selection,
         params.timeout(),
+        access_tags.as_deref(),
+        read_priority.into_inner(),
+        stale_reads.as_deref(),
     )
     .await;
 
@@ -52,10 +339,25 @@ async fn batch_search_points(
     collection: Path<CollectionPath>,
     request: Json<SearchRequestBatch>,
     params: Query<ReadParams>,
+    partial_params: Query<PartialResultsParams>,
+    access_tags: Option<ReqData<AccessTags>>,
+    read_priority: ReqData<ReadPriority>,
+    stale_reads: Option<ReqData<StaleReads>>,
 ) -> impl Responder {
     let timing = Instant::now();
 
     let request = request.into_inner();
+
+    let consistency_token = match ConsistencyToken::merge_all(
+        request
+            .searches
+            .iter()
+            .map(|req| req.consistency_token.as_deref()),
+    ) {
+        Ok(consistency_token) => consistency_token,
+        Err(err) => return process_response::<()>(Err(err.into()), timing),
+    };
+
     let requests = request
         .searches
         .into_iter()
@@ -63,23 +365,53 @@ async fn batch_search_points(
             let SearchRequest {
                 search_request,
                 shard_key,
+                consistency_token: _,
+                rescore,
+                distinct_by,
             } = req;
             let shard_selection = match shard_key {
                 None => ShardSelectorInternal::All,
                 Some(shard_keys) => shard_keys.into(),
             };
-            let core_request: CoreSearchRequest = search_request.into();
+            let mut core_request = rescored_core_search(search_request, rescore)?;
+            core_request.distinct_by = distinct_by.map(|distinct| {
+                CoreDistinct::new(distinct, core_request.offset, core_request.limit)
+            });
 
-            (core_request, shard_selection)
+            Ok((core_request, shard_selection))
         })
-        .collect();
+        .collect::<Result<_, CollectionError>>();
+    let requests = match requests {
+        Ok(requests) => requests,
+        Err(err) => return process_response::<()>(Err(err.into()), timing),
+    };
+
+    if partial_params.partial_results {
+        let response = do_search_batch_points_partial(
+            toc.get_ref(),
+            &collection.name,
+            requests,
+            params.consistency,
+            consistency_token.as_ref(),
+            params.timeout(),
+            access_tags.as_deref(),
+            read_priority.into_inner(),
+            stale_reads.as_deref(),
+        )
+        .await;
+        return process_response(response, timing);
+    }
 
     let response = do_search_batch_points(
         toc.get_ref(),
         &collection.name,
         requests,
         params.consistency,
+        consistency_token.as_ref(),
         params.timeout(),
+        access_tags.as_deref(),
+        read_priority.into_inner(),
+        stale_reads.as_deref(),
     )
     .await;
 
@@ -92,6 +424,9 @@ async fn search_point_groups(
     collection: Path<CollectionPath>,
     request: Json<SearchGroupsRequest>,
     params: Query<ReadParams>,
+    access_tags: Option<ReqData<AccessTags>>,
+    read_priority: ReqData<ReadPriority>,
+    stale_reads: Option<ReqData<StaleReads>>,
 ) -> impl Responder {
     let timing = Instant::now();
 
@@ -112,6 +447,32 @@ async fn search_point_groups(
         params.consistency,
         shard_selection,
         params.timeout(),
+        access_tags.as_deref(),
+        read_priority.into_inner(),
+        stale_reads.as_deref(),
+    )
+    .await;
+
+    process_response(response, timing)
+}
+
+#[post("/collections/search")]
+async fn multi_collection_search(
+    toc: web::Data<TableOfContent>,
+    request: Json<MultiCollectionSearchRequest>,
+    params: Query<ReadParams>,
+    read_priority: ReqData<ReadPriority>,
+    stale_reads: Option<ReqData<StaleReads>>,
+) -> impl Responder {
+    let timing = Instant::now();
+
+    let response = do_multi_collection_search(
+        toc.get_ref(),
+        request.into_inner(),
+        params.consistency,
+        params.timeout(),
+        read_priority.into_inner(),
+        stale_reads.as_deref(),
     )
     .await;
 
@@ -121,6 +482,12 @@ async fn search_point_groups(
 // Configure services
 pub fn config_search_api(cfg: &mut web::ServiceConfig) {
     cfg.service(search_points)
+        .service(search_points_by_document)
+        .service(hybrid_search_points)
+        .service(search_matrix_points)
+        .service(rescore_search_points)
+        .service(search_points_page)
         .service(batch_search_points)
-        .service(search_point_groups);
+        .service(search_point_groups)
+        .service(multi_collection_search);
 }
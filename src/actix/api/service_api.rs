@@ -13,6 +13,7 @@ use storage::content_manager::toc::TableOfContent;
 use tokio::sync::Mutex;
 
 use crate::actix::helpers::process_response;
+use crate::common::config_reload::ConfigReloader;
 use crate::common::health;
 use crate::common::helpers::LocksOption;
 use crate::common::metrics::MetricsData;
@@ -92,6 +93,13 @@ async fn get_locks(toc: web::Data<TableOfContent>) -> impl Responder {
     process_response(Ok(result), timing)
 }
 
+#[post("/config/reload")]
+async fn reload_config(config_reloader: web::Data<ConfigReloader>) -> impl Responder {
+    let timing = Instant::now();
+    let result = config_reloader.reload().await;
+    process_response(result, timing)
+}
+
 #[get("/stacktrace")]
 async fn get_stacktrace() -> impl Responder {
     let timing = Instant::now();
@@ -140,6 +148,7 @@ pub fn config_service_api(cfg: &mut web::ServiceConfig) {
         .service(metrics)
         .service(put_locks)
         .service(get_locks)
+        .service(reload_config)
         .service(get_stacktrace)
         .service(healthz)
         .service(livez)
@@ -2,11 +2,11 @@ use actix_files::NamedFile;
 use actix_multipart::form::tempfile::TempFile;
 use actix_multipart::form::MultipartForm;
 use actix_web::rt::time::Instant;
-use actix_web::{delete, get, post, put, web, Responder, Result};
+use actix_web::{delete, get, post, put, web, Either, HttpResponse, Responder, Result};
 use actix_web_validator as valid;
 use collection::common::file_utils::move_file;
 use collection::operations::snapshot_ops::{
-    ShardSnapshotRecover, SnapshotPriority, SnapshotRecover,
+    ShardSnapshotRecover, SnapshotCreationPriority, SnapshotPriority, SnapshotRecover,
 };
 use collection::shards::shard::ShardId;
 use futures::{FutureExt as _, TryFutureExt as _};
@@ -51,6 +51,12 @@ pub struct SnapshottingParam {
     pub wait: Option<bool>,
 }
 
+#[derive(Deserialize, Serialize, JsonSchema, Validate)]
+pub struct SnapshotCreationParam {
+    pub wait: Option<bool>,
+    pub snapshot_priority: Option<SnapshotCreationPriority>,
+}
+
 #[derive(MultipartForm)]
 pub struct SnapshottingForm {
     snapshot: TempFile,
@@ -103,7 +109,7 @@ pub async fn do_get_snapshot(
     toc: &TableOfContent,
     collection_name: &str,
     snapshot_name: &str,
-) -> Result<NamedFile> {
+) -> Result<Either<NamedFile, HttpResponse>> {
     let collection = toc
         .get_collection(collection_name)
         .await
@@ -114,7 +120,25 @@ pub async fn do_get_snapshot(
         .await
         .map_err(collection_into_actix_error)?;
 
-    Ok(NamedFile::open(file_name)?)
+    if collection.snapshot_storage().is_local() {
+        return Ok(Either::Left(NamedFile::open(file_name)?));
+    }
+
+    // Stream the snapshot from the object storage
+    let response = collection
+        .snapshot_storage()
+        .object_response(&file_name)
+        .await
+        .map_err(collection_into_actix_error)?;
+
+    let mut http_response = HttpResponse::Ok();
+    http_response.content_type("application/octet-stream");
+    if let Some(content_length) = response.content_length() {
+        http_response.no_chunking(content_length);
+    }
+    Ok(Either::Right(
+        http_response.streaming(response.bytes_stream()),
+    ))
 }
 
 #[get("/collections/{name}/snapshots")]
@@ -130,13 +154,14 @@ async fn list_snapshots(toc: web::Data<TableOfContent>, path: web::Path<String>)
 async fn create_snapshot(
     dispatcher: web::Data<Dispatcher>,
     path: web::Path<String>,
-    params: valid::Query<SnapshottingParam>,
+    params: valid::Query<SnapshotCreationParam>,
 ) -> impl Responder {
     let collection_name = path.into_inner();
     let wait = params.wait.unwrap_or(true);
+    let priority = params.snapshot_priority.unwrap_or_default();
 
     let timing = Instant::now();
-    let response = do_create_snapshot(dispatcher.get_ref(), &collection_name, wait).await;
+    let response = do_create_snapshot(dispatcher.get_ref(), &collection_name, priority, wait).await;
     match response {
         Err(_) => process_response(response, timing),
         Ok(_) if wait => process_response(response, timing),
@@ -1,8 +1,15 @@
+use std::time::Duration;
+
 use actix_web::rt::time::Instant;
 use actix_web::{delete, post, put, web, Responder};
 use actix_web_validator::{Json, Path, Query};
+use collection::operations::consistency_params::WriteConsistency;
+use collection::operations::document_ops::PointsDocuments;
 use collection::operations::payload_ops::{DeletePayload, SetPayload};
-use collection::operations::point_ops::{PointInsertOperations, PointsSelector, WriteOrdering};
+use collection::operations::point_ops::{
+    PointIdsList, PointInsertOperations, PointsSelector, WriteOrdering,
+};
+use collection::operations::update_priority::UpdatePriority;
 use collection::operations::vector_ops::{DeleteVectors, UpdateVectors};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
@@ -12,10 +19,14 @@ use validator::Validate;
 
 use super::CollectionPath;
 use crate::actix::helpers::process_response;
+use crate::common::filter_updates::{
+    do_delete_by_filter, do_set_payload_by_filter, DeleteByFilter, SetPayloadByFilter,
+};
 use crate::common::points::{
-    do_batch_update_points, do_clear_payload, do_create_index, do_delete_index, do_delete_payload,
-    do_delete_points, do_delete_vectors, do_overwrite_payload, do_set_payload, do_update_vectors,
-    do_upsert_points, CreateFieldIndex, UpdateOperations,
+    do_all_or_nothing_batch_update_points, do_batch_update_points, do_clear_payload,
+    do_create_index, do_delete_index, do_delete_payload, do_delete_points, do_delete_vectors,
+    do_overwrite_payload, do_set_payload, do_undelete_points, do_update_vectors,
+    do_upsert_documents, do_upsert_points, CreateFieldIndex, UpdateOperations,
 };
 
 #[derive(Deserialize, Validate)]
@@ -29,6 +40,20 @@ struct FieldPath {
 pub struct UpdateParam {
     pub wait: Option<bool>,
     pub ordering: Option<WriteOrdering>,
+    /// Max number of seconds to wait for the deactivation of replicas, which failed to apply the update.
+    /// Overrides the collection setting.
+    pub shard_deactivation_timeout: Option<u64>,
+    /// Number of replicas, which must apply the update for it to succeed.
+    /// Overrides the collection setting.
+    #[validate]
+    pub write_consistency: Option<WriteConsistency>,
+    /// Max number of seconds to wait for the replicas to apply the update.
+    /// Replicas, which didn't apply it in time, are reported as failed.
+    #[validate(range(min = 1))]
+    pub timeout: Option<u64>,
+    /// Priority of the update. Interactive updates are not queued behind bulk ones,
+    /// use `bulk` for backfills and imports. Default: `interactive`
+    pub priority: Option<UpdatePriority>,
 }
 
 #[put("/collections/{name}/points")]
@@ -40,8 +65,12 @@ async fn upsert_points(
 ) -> impl Responder {
     let timing = Instant::now();
     let operation = operation.into_inner();
-    let wait = params.wait.unwrap_or(false);
-    let ordering = params.ordering.unwrap_or_default();
+    let wait = params.wait;
+    let ordering = params.ordering;
+    let shard_deactivation_timeout = params.shard_deactivation_timeout.map(Duration::from_secs);
+    let write_consistency = params.write_consistency;
+    let timeout = params.timeout.map(Duration::from_secs);
+    let priority = params.priority;
 
     let response = do_upsert_points(
         toc.get_ref(),
@@ -50,6 +79,41 @@ async fn upsert_points(
         None,
         wait,
         ordering,
+        shard_deactivation_timeout,
+        write_consistency,
+        timeout,
+        priority,
+    )
+    .await;
+    process_response(response, timing)
+}
+
+#[put("/collections/{name}/points/documents")]
+async fn upsert_documents(
+    toc: web::Data<TableOfContent>,
+    collection: Path<CollectionPath>,
+    operation: Json<PointsDocuments>,
+    params: Query<UpdateParam>,
+) -> impl Responder {
+    let timing = Instant::now();
+    let operation = operation.into_inner();
+    let wait = params.wait;
+    let ordering = params.ordering;
+    let shard_deactivation_timeout = params.shard_deactivation_timeout.map(Duration::from_secs);
+    let write_consistency = params.write_consistency;
+    let timeout = params.timeout.map(Duration::from_secs);
+    let priority = params.priority;
+
+    let response = do_upsert_documents(
+        toc.get_ref(),
+        &collection.name,
+        operation,
+        wait,
+        ordering,
+        shard_deactivation_timeout,
+        write_consistency,
+        timeout,
+        priority,
     )
     .await;
     process_response(response, timing)
@@ -64,8 +128,12 @@ async fn delete_points(
 ) -> impl Responder {
     let timing = Instant::now();
     let operation = operation.into_inner();
-    let wait = params.wait.unwrap_or(false);
-    let ordering = params.ordering.unwrap_or_default();
+    let wait = params.wait;
+    let ordering = params.ordering;
+    let shard_deactivation_timeout = params.shard_deactivation_timeout.map(Duration::from_secs);
+    let write_consistency = params.write_consistency;
+    let timeout = params.timeout.map(Duration::from_secs);
+    let priority = params.priority;
 
     let response = do_delete_points(
         toc.get_ref(),
@@ -74,6 +142,72 @@ async fn delete_points(
         None,
         wait,
         ordering,
+        shard_deactivation_timeout,
+        write_consistency,
+        timeout,
+        priority,
+    )
+    .await;
+    process_response(response, timing)
+}
+
+/// Delete points matching the filter in batches, waiting for each batch to be applied
+#[post("/collections/{name}/points/delete_by_filter")]
+async fn delete_by_filter(
+    toc: web::Data<TableOfContent>,
+    collection: Path<CollectionPath>,
+    operation: Json<DeleteByFilter>,
+    params: Query<UpdateParam>,
+) -> impl Responder {
+    let timing = Instant::now();
+    let operation = operation.into_inner();
+    let ordering = params.ordering;
+    let shard_deactivation_timeout = params.shard_deactivation_timeout.map(Duration::from_secs);
+    let write_consistency = params.write_consistency;
+    let timeout = params.timeout.map(Duration::from_secs);
+    let priority = params.priority;
+
+    let response = do_delete_by_filter(
+        toc.get_ref(),
+        &collection.name,
+        operation,
+        ordering,
+        shard_deactivation_timeout,
+        write_consistency,
+        timeout,
+        priority,
+    )
+    .await;
+    process_response(response, timing)
+}
+
+#[post("/collections/{name}/points/undelete")]
+async fn undelete_points(
+    toc: web::Data<TableOfContent>,
+    collection: Path<CollectionPath>,
+    operation: Json<PointIdsList>,
+    params: Query<UpdateParam>,
+) -> impl Responder {
+    let timing = Instant::now();
+    let operation = operation.into_inner();
+    let wait = params.wait;
+    let ordering = params.ordering;
+    let shard_deactivation_timeout = params.shard_deactivation_timeout.map(Duration::from_secs);
+    let write_consistency = params.write_consistency;
+    let timeout = params.timeout.map(Duration::from_secs);
+    let priority = params.priority;
+
+    let response = do_undelete_points(
+        toc.get_ref(),
+        &collection.name,
+        operation,
+        None,
+        wait,
+        ordering,
+        shard_deactivation_timeout,
+        write_consistency,
+        timeout,
+        priority,
     )
     .await;
     process_response(response, timing)
@@ -88,8 +222,12 @@ async fn update_vectors(
 ) -> impl Responder {
     let timing = Instant::now();
     let operation = operation.into_inner();
-    let wait = params.wait.unwrap_or(false);
-    let ordering = params.ordering.unwrap_or_default();
+    let wait = params.wait;
+    let ordering = params.ordering;
+    let shard_deactivation_timeout = params.shard_deactivation_timeout.map(Duration::from_secs);
+    let write_consistency = params.write_consistency;
+    let timeout = params.timeout.map(Duration::from_secs);
+    let priority = params.priority;
 
     let response = do_update_vectors(
         toc.get_ref(),
@@ -98,6 +236,10 @@ async fn update_vectors(
         None,
         wait,
         ordering,
+        shard_deactivation_timeout,
+        write_consistency,
+        timeout,
+        priority,
     )
     .await;
     process_response(response, timing)
@@ -112,8 +254,12 @@ async fn delete_vectors(
 ) -> impl Responder {
     let timing = Instant::now();
     let operation = operation.into_inner();
-    let wait = params.wait.unwrap_or(false);
-    let ordering = params.ordering.unwrap_or_default();
+    let wait = params.wait;
+    let ordering = params.ordering;
+    let shard_deactivation_timeout = params.shard_deactivation_timeout.map(Duration::from_secs);
+    let write_consistency = params.write_consistency;
+    let timeout = params.timeout.map(Duration::from_secs);
+    let priority = params.priority;
 
     let response = do_delete_vectors(
         toc.get_ref(),
@@ -122,6 +268,10 @@ async fn delete_vectors(
         None,
         wait,
         ordering,
+        shard_deactivation_timeout,
+        write_consistency,
+        timeout,
+        priority,
     )
     .await;
     process_response(response, timing)
@@ -136,8 +286,12 @@ async fn set_payload(
 ) -> impl Responder {
     let timing = Instant::now();
     let operation = operation.into_inner();
-    let wait = params.wait.unwrap_or(false);
-    let ordering = params.ordering.unwrap_or_default();
+    let wait = params.wait;
+    let ordering = params.ordering;
+    let shard_deactivation_timeout = params.shard_deactivation_timeout.map(Duration::from_secs);
+    let write_consistency = params.write_consistency;
+    let timeout = params.timeout.map(Duration::from_secs);
+    let priority = params.priority;
 
     let response = do_set_payload(
         toc.get_ref(),
@@ -146,6 +300,10 @@ async fn set_payload(
         None,
         wait,
         ordering,
+        shard_deactivation_timeout,
+        write_consistency,
+        timeout,
+        priority,
     )
     .await;
     process_response(response, timing)
@@ -160,8 +318,12 @@ async fn overwrite_payload(
 ) -> impl Responder {
     let timing = Instant::now();
     let operation = operation.into_inner();
-    let wait = params.wait.unwrap_or(false);
-    let ordering = params.ordering.unwrap_or_default();
+    let wait = params.wait;
+    let ordering = params.ordering;
+    let shard_deactivation_timeout = params.shard_deactivation_timeout.map(Duration::from_secs);
+    let write_consistency = params.write_consistency;
+    let timeout = params.timeout.map(Duration::from_secs);
+    let priority = params.priority;
 
     let response = do_overwrite_payload(
         toc.get_ref(),
@@ -170,6 +332,40 @@ async fn overwrite_payload(
         None,
         wait,
         ordering,
+        shard_deactivation_timeout,
+        write_consistency,
+        timeout,
+        priority,
+    )
+    .await;
+    process_response(response, timing)
+}
+
+/// Set payload of the points matching the filter in batches, waiting for each batch to be applied
+#[post("/collections/{name}/points/payload/set_by_filter")]
+async fn set_payload_by_filter(
+    toc: web::Data<TableOfContent>,
+    collection: Path<CollectionPath>,
+    operation: Json<SetPayloadByFilter>,
+    params: Query<UpdateParam>,
+) -> impl Responder {
+    let timing = Instant::now();
+    let operation = operation.into_inner();
+    let ordering = params.ordering;
+    let shard_deactivation_timeout = params.shard_deactivation_timeout.map(Duration::from_secs);
+    let write_consistency = params.write_consistency;
+    let timeout = params.timeout.map(Duration::from_secs);
+    let priority = params.priority;
+
+    let response = do_set_payload_by_filter(
+        toc.get_ref(),
+        &collection.name,
+        operation,
+        ordering,
+        shard_deactivation_timeout,
+        write_consistency,
+        timeout,
+        priority,
     )
     .await;
     process_response(response, timing)
@@ -184,8 +380,12 @@ async fn delete_payload(
 ) -> impl Responder {
     let timing = Instant::now();
     let operation = operation.into_inner();
-    let wait = params.wait.unwrap_or(false);
-    let ordering = params.ordering.unwrap_or_default();
+    let wait = params.wait;
+    let ordering = params.ordering;
+    let shard_deactivation_timeout = params.shard_deactivation_timeout.map(Duration::from_secs);
+    let write_consistency = params.write_consistency;
+    let timeout = params.timeout.map(Duration::from_secs);
+    let priority = params.priority;
 
     let response = do_delete_payload(
         toc.get_ref(),
@@ -194,6 +394,10 @@ async fn delete_payload(
         None,
         wait,
         ordering,
+        shard_deactivation_timeout,
+        write_consistency,
+        timeout,
+        priority,
     )
     .await;
     process_response(response, timing)
@@ -208,8 +412,12 @@ async fn clear_payload(
 ) -> impl Responder {
     let timing = Instant::now();
     let operation = operation.into_inner();
-    let wait = params.wait.unwrap_or(false);
-    let ordering = params.ordering.unwrap_or_default();
+    let wait = params.wait;
+    let ordering = params.ordering;
+    let shard_deactivation_timeout = params.shard_deactivation_timeout.map(Duration::from_secs);
+    let write_consistency = params.write_consistency;
+    let timeout = params.timeout.map(Duration::from_secs);
+    let priority = params.priority;
 
     let response = do_clear_payload(
         toc.get_ref(),
@@ -218,6 +426,10 @@ async fn clear_payload(
         None,
         wait,
         ordering,
+        shard_deactivation_timeout,
+        write_consistency,
+        timeout,
+        priority,
     )
     .await;
     process_response(response, timing)
@@ -232,18 +444,45 @@ async fn update_batch(
 ) -> impl Responder {
     let timing = Instant::now();
     let operations = operations.into_inner();
-    let wait = params.wait.unwrap_or(false);
-    let ordering = params.ordering.unwrap_or_default();
+    let wait = params.wait;
+    let ordering = params.ordering;
+    let shard_deactivation_timeout = params.shard_deactivation_timeout.map(Duration::from_secs);
+    let write_consistency = params.write_consistency;
+    let timeout = params.timeout.map(Duration::from_secs);
+    let priority = params.priority;
 
-    let response = do_batch_update_points(
-        &toc,
-        &collection.name,
-        operations.operations,
-        None,
-        wait,
-        ordering,
-    )
-    .await;
+    let response = if operations.all_or_nothing {
+        // Operations are applied as one update, so they share the result
+        let operations_count = operations.operations.len();
+        do_all_or_nothing_batch_update_points(
+            &toc,
+            &collection.name,
+            operations.operations,
+            None,
+            wait,
+            ordering,
+            shard_deactivation_timeout,
+            write_consistency,
+            timeout,
+            priority,
+        )
+        .await
+        .map(|result| vec![result; operations_count])
+    } else {
+        do_batch_update_points(
+            &toc,
+            &collection.name,
+            operations.operations,
+            None,
+            wait,
+            ordering,
+            shard_deactivation_timeout,
+            write_consistency,
+            timeout,
+            priority,
+        )
+        .await
+    };
     process_response(response, timing)
 }
 #[put("/collections/{name}/index")]
@@ -255,8 +494,12 @@ async fn create_field_index(
 ) -> impl Responder {
     let timing = Instant::now();
     let operation = operation.into_inner();
-    let wait = params.wait.unwrap_or(false);
-    let ordering = params.ordering.unwrap_or_default();
+    let wait = params.wait;
+    let ordering = params.ordering;
+    let shard_deactivation_timeout = params.shard_deactivation_timeout.map(Duration::from_secs);
+    let write_consistency = params.write_consistency;
+    let timeout = params.timeout.map(Duration::from_secs);
+    let priority = params.priority;
 
     let response = do_create_index(
         dispatcher.get_ref(),
@@ -265,6 +508,10 @@ async fn create_field_index(
         None,
         wait,
         ordering,
+        shard_deactivation_timeout,
+        write_consistency,
+        timeout,
+        priority,
     )
     .await;
     process_response(response, timing)
@@ -278,8 +525,12 @@ async fn delete_field_index(
     params: Query<UpdateParam>,
 ) -> impl Responder {
     let timing = Instant::now();
-    let wait = params.wait.unwrap_or(false);
-    let ordering = params.ordering.unwrap_or_default();
+    let wait = params.wait;
+    let ordering = params.ordering;
+    let shard_deactivation_timeout = params.shard_deactivation_timeout.map(Duration::from_secs);
+    let write_consistency = params.write_consistency;
+    let timeout = params.timeout.map(Duration::from_secs);
+    let priority = params.priority;
 
     let response = do_delete_index(
         dispatcher.get_ref(),
@@ -288,6 +539,10 @@ async fn delete_field_index(
         None,
         wait,
         ordering,
+        shard_deactivation_timeout,
+        write_consistency,
+        timeout,
+        priority,
     )
     .await;
     process_response(response, timing)
@@ -296,10 +551,14 @@ async fn delete_field_index(
 // Configure services
 pub fn config_update_api(cfg: &mut web::ServiceConfig) {
     cfg.service(upsert_points)
+        .service(upsert_documents)
         .service(delete_points)
+        .service(delete_by_filter)
+        .service(undelete_points)
         .service(update_vectors)
         .service(delete_vectors)
         .service(set_payload)
+        .service(set_payload_by_filter)
         .service(overwrite_payload)
         .service(delete_payload)
         .service(clear_payload)
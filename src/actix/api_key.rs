@@ -4,19 +4,22 @@ use actix_web::body::{BoxBody, EitherBody};
 use actix_web::dev::{forward_ready, Service, ServiceRequest, ServiceResponse, Transform};
 use actix_web::http::header::Header;
 use actix_web::http::Method;
-use actix_web::{Error, HttpResponse};
+use actix_web::{Error, HttpMessage, HttpResponse};
 use actix_web_httpauth::headers::authorization::{Authorization, Bearer};
+use collection::collection::access_tags::AccessTags;
 use futures_util::future::LocalBoxFuture;
 
 use crate::common::auth::AuthKeys;
 
-const READ_ONLY_POST_PATTERNS: [&str; 11] = [
+const READ_ONLY_POST_PATTERNS: [&str; 13] = [
     "/collections/{name}/points",
     "/collections/{name}/points/count",
     "/collections/{name}/points/search",
     "/collections/{name}/points/scroll",
+    "/collections/{name}/points/scroll/session",
     "/collections/{name}/points/search/groups",
     "/collections/{name}/points/search/batch",
+    "/collections/{name}/points/search/page",
     "/collections/{name}/points/recommend",
     "/collections/{name}/points/recommend/groups",
     "/collections/{name}/points/recommend/batch",
@@ -24,6 +27,8 @@ const READ_ONLY_POST_PATTERNS: [&str; 11] = [
     "/collections/{name}/points/discover/batch",
 ];
 
+const READ_POINT_GET_PATTERN: &str = "/collections/{name}/points/{id}";
+
 pub struct ApiKey {
     auth_keys: Option<AuthKeys>,
     whitelist: Vec<WhitelistItem>,
@@ -145,6 +150,19 @@ where
             if is_allowed {
                 return Box::pin(self.service.call(req));
             }
+
+            // Access tag keys could only read points, which have any of their tags. The handlers
+            // pass the tags from the request extensions along with the read.
+            let access_tags = self
+                .auth_keys
+                .as_ref()
+                .and_then(|auth_keys| auth_keys.access_tags(&key));
+            if let Some(access_tags) = access_tags {
+                if is_points_read(&req) {
+                    req.extensions_mut().insert(AccessTags(access_tags));
+                    return Box::pin(self.service.call(req));
+                }
+            }
         }
 
         Box::pin(async {
@@ -155,6 +173,14 @@ where
     }
 }
 
+fn is_points_read(req: &ServiceRequest) -> bool {
+    match *req.method() {
+        Method::GET => req.match_pattern().as_deref() == Some(READ_POINT_GET_PATTERN),
+        Method::POST => is_read_only(req),
+        _ => false,
+    }
+}
+
 fn is_read_only(req: &ServiceRequest) -> bool {
     match *req.method() {
         Method::GET => true,
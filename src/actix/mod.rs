@@ -14,17 +14,24 @@ use ::api::grpc::models::{ApiResponse, ApiStatus, VersionInfo};
 use actix_cors::Cors;
 use actix_multipart::form::tempfile::TempFileConfig;
 use actix_multipart::form::MultipartFormConfig;
+use actix_web::dev::Service as _;
+use actix_web::http::header::{HeaderName, HeaderValue};
 use actix_web::middleware::{Compress, Condition, Logger};
-use actix_web::{error, get, web, App, HttpRequest, HttpResponse, HttpServer, Responder};
+use actix_web::{
+    error, get, web, App, HttpMessage, HttpRequest, HttpResponse, HttpServer, Responder,
+};
 use collection::operations::validation;
+use collection::shards::replica_set::read_priority::{ReadPriority, READ_PRIORITY_HEADER};
+use collection::shards::replica_set::stale_reads::{StaleReads, STALE_READ_HEADER};
 use storage::dispatcher::Dispatcher;
 
 use crate::actix::api::cluster_api::config_cluster_api;
 use crate::actix::api::collections_api::config_collections_api;
 use crate::actix::api::count_api::count_points;
 use crate::actix::api::discovery_api::config_discovery_api;
+use crate::actix::api::operations_api::config_operations_api;
 use crate::actix::api::recommend_api::config_recommend_api;
-use crate::actix::api::retrieve_api::{get_point, get_points, scroll_points};
+use crate::actix::api::retrieve_api::{get_point, get_points, open_scroll_session, scroll_points};
 use crate::actix::api::search_api::config_search_api;
 use crate::actix::api::service_api::config_service_api;
 use crate::actix::api::shards_api::config_shards_api;
@@ -32,6 +39,7 @@ use crate::actix::api::snapshot_api::config_snapshots_api;
 use crate::actix::api::update_api::config_update_api;
 use crate::actix::api_key::{ApiKey, WhitelistItem};
 use crate::common::auth::AuthKeys;
+use crate::common::config_reload::ConfigReloader;
 use crate::common::health;
 use crate::common::http_client::HttpClient;
 use crate::common::telemetry::TelemetryCollector;
@@ -50,6 +58,7 @@ pub fn init(
     dispatcher: Arc<Dispatcher>,
     telemetry_collector: Arc<tokio::sync::Mutex<TelemetryCollector>>,
     health_checker: Option<Arc<health::HealthChecker>>,
+    config_reloader: Arc<ConfigReloader>,
     settings: Settings,
 ) -> io::Result<()> {
     actix_web::rt::System::new().block_on(async {
@@ -63,6 +72,7 @@ pub fn init(
         let telemetry_collector_data = web::Data::from(telemetry_collector);
         let http_client = web::Data::new(HttpClient::from_settings(&settings)?);
         let health_checker = web::Data::new(health_checker);
+        let config_reloader = web::Data::from(config_reloader);
         let auth_keys = AuthKeys::try_create(&settings.service);
         let static_folder = settings
             .service
@@ -116,6 +126,35 @@ pub fn init(
                 .error_handler(|err, rec| validation_error_handler("JSON body", err, rec));
 
             let mut app = App::new()
+                // Marks responses, which are served by degraded replicas, the handlers pass the
+                // tracker along with the reads
+                .wrap_fn(|request, service| {
+                    let stale_reads = StaleReads::default();
+                    request.extensions_mut().insert(stale_reads.clone());
+                    let response = service.call(request);
+                    async move {
+                        let mut response = response.await?;
+                        if stale_reads.is_stale() {
+                            response.headers_mut().insert(
+                                HeaderName::from_static(STALE_READ_HEADER),
+                                HeaderValue::from_static("true"),
+                            );
+                        }
+                        Ok(response)
+                    }
+                })
+                // Sets the priority of the reads of the request, the handlers pass it along with
+                // the reads
+                .wrap_fn(|request, service| {
+                    let priority = request
+                        .headers()
+                        .get(READ_PRIORITY_HEADER)
+                        .map_or(ReadPriority::Normal, |value| {
+                            ReadPriority::from_header(value.as_bytes())
+                        });
+                    request.extensions_mut().insert(priority);
+                    service.call(request)
+                })
                 .wrap(Compress::default()) // Reads the `Accept-Encoding` header to negotiate which compression codec to use.
                 // api_key middleware
                 // note: the last call to `wrap()` or `wrap_fn()` is executed first
@@ -133,6 +172,7 @@ pub fn init(
                 .app_data(telemetry_collector_data.clone())
                 .app_data(http_client.clone())
                 .app_data(health_checker.clone())
+                .app_data(config_reloader.clone())
                 .app_data(validate_path_config)
                 .app_data(validate_query_config)
                 .app_data(validate_json_config)
@@ -148,9 +188,11 @@ pub fn init(
                 .configure(config_recommend_api)
                 .configure(config_discovery_api)
                 .configure(config_shards_api)
+                .configure(config_operations_api)
                 .service(get_point)
                 .service(get_points)
                 .service(scroll_points)
+                .service(open_scroll_session)
                 .service(count_points);
 
             if web_ui_available {
@@ -1,3 +1,5 @@
+use std::sync::Arc;
+
 use super::strings::ct_eq;
 use crate::settings::ServiceConfig;
 
@@ -9,6 +11,9 @@ pub struct AuthKeys {
 
     /// A key allowing Read operations
     read_only: Option<String>,
+
+    /// Keys allowing to read only points with any of the given access tags
+    access_tag_keys: Vec<(String, Arc<Vec<String>>)>,
 }
 
 impl AuthKeys {
@@ -16,14 +21,20 @@ impl AuthKeys {
     ///
     /// Returns None if no scheme is specified.
     pub fn try_create(service_config: &ServiceConfig) -> Option<Self> {
+        let access_tag_keys: Vec<_> = service_config
+            .access_tag_api_keys
+            .iter()
+            .map(|key| (key.key.clone(), Arc::new(key.access_tags.clone())))
+            .collect();
         match (
             service_config.api_key.clone(),
             service_config.read_only_api_key.clone(),
         ) {
-            (None, None) => None,
+            (None, None) if access_tag_keys.is_empty() => None,
             (read_write, read_only) => Some(Self {
                 read_write,
                 read_only,
+                access_tag_keys,
             }),
         }
     }
@@ -45,4 +56,14 @@ impl AuthKeys {
             .map(|rw_key| ct_eq(rw_key, key))
             .unwrap_or_default()
     }
+
+    /// Access tags, which the reads with a key are restricted to
+    ///
+    /// Returns None if the key is not an access tag key.
+    pub fn access_tags(&self, key: &str) -> Option<Arc<Vec<String>>> {
+        self.access_tag_keys
+            .iter()
+            .find(|(tag_key, _)| ct_eq(tag_key, key))
+            .map(|(_, tags)| tags.clone())
+    }
 }
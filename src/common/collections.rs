@@ -1,15 +1,22 @@
+use std::sync::Arc;
 use std::time::Duration;
 
 use api::grpc::models::{CollectionDescription, CollectionsResponse};
+use collection::collection::read_only::ReadOnlyState;
+use collection::collection::stored_queries::StoredQueries;
+use collection::collection::usage::CollectionUsage;
 use collection::config::ShardingMethod;
 use collection::operations::cluster_ops::{
     AbortTransferOperation, ClusterOperations, DropReplicaOperation, MoveShardOperation,
-    ReplicateShardOperation,
+    ReplicateShardOperation, SplitShardOperation,
 };
+use collection::operations::config_diff::DiffConfig as _;
+use collection::operations::dimensionality_reduction::TrainDimensionalityReduction;
 use collection::operations::shard_selector_internal::ShardSelectorInternal;
-use collection::operations::snapshot_ops::SnapshotDescription;
+use collection::operations::snapshot_ops::{SnapshotCreationPriority, SnapshotDescription};
 use collection::operations::types::{
     AliasDescription, CollectionClusterInfo, CollectionInfo, CollectionsAliasesResponse,
+    CompactionResult, WarmUpResult,
 };
 use collection::shards::replica_set;
 use collection::shards::shard::{PeerId, ShardId, ShardsPlacement};
@@ -18,7 +25,8 @@ use itertools::Itertools;
 use rand::prelude::SliceRandom;
 use storage::content_manager::collection_meta_ops::ShardTransferOperations::{Abort, Start};
 use storage::content_manager::collection_meta_ops::{
-    CollectionMetaOperations, CreateShardKey, DropShardKey, UpdateCollectionOperation,
+    CloneCollection, CollectionMetaOperations, CreateCollection, CreateCollectionOperation,
+    CreateShardKey, DropShardKey, InitFrom, ShardSplitOperations, UpdateCollectionOperation,
 };
 use storage::content_manager::errors::StorageError;
 use storage::content_manager::toc::TableOfContent;
@@ -103,6 +111,30 @@ pub async fn do_list_collection_aliases(
     Ok(CollectionsAliasesResponse { aliases })
 }
 
+pub async fn do_list_stored_queries(
+    toc: &TableOfContent,
+    collection_name: &str,
+) -> Result<StoredQueries, StorageError> {
+    let collection = toc.get_collection(collection_name).await?;
+    Ok(collection.stored_queries())
+}
+
+pub async fn do_get_read_only_state(
+    toc: &TableOfContent,
+    collection_name: &str,
+) -> Result<ReadOnlyState, StorageError> {
+    let collection = toc.get_collection(collection_name).await?;
+    Ok(collection.read_only_state())
+}
+
+pub async fn do_get_collection_usage(
+    toc: &TableOfContent,
+    collection_name: &str,
+) -> Result<CollectionUsage, StorageError> {
+    let collection = toc.get_collection(collection_name).await?;
+    Ok(collection.usage().await?)
+}
+
 pub async fn do_list_aliases(
     toc: &TableOfContent,
 ) -> Result<CollectionsAliasesResponse, StorageError> {
@@ -124,11 +156,13 @@ pub async fn do_list_snapshots(
 pub async fn do_create_snapshot(
     dispatcher: &Dispatcher,
     collection_name: &str,
+    priority: SnapshotCreationPriority,
     wait: bool,
 ) -> Result<SnapshotDescription, StorageError> {
     let collection = collection_name.to_string();
     let dispatcher = dispatcher.clone();
-    let snapshot = tokio::spawn(async move { dispatcher.create_snapshot(&collection).await });
+    let snapshot =
+        tokio::spawn(async move { dispatcher.create_snapshot(&collection, priority).await });
     if wait {
         Ok(snapshot.await??)
     } else {
@@ -148,6 +182,109 @@ pub async fn do_get_collection_cluster(
     Ok(collection.cluster_info(toc.this_peer_id).await?)
 }
 
+pub async fn do_warm_up_collection(
+    toc: &TableOfContent,
+    name: &str,
+) -> Result<WarmUpResult, StorageError> {
+    let collection = toc.get_collection(name).await?;
+    Ok(collection.warm_up().await?)
+}
+
+pub async fn do_compact_shard(
+    toc: Arc<TableOfContent>,
+    collection_name: String,
+    shard_id: ShardId,
+) -> Result<CompactionResult, StorageError> {
+    let collection = toc.get_collection(&collection_name).await?;
+    Ok(collection.compact_shard(shard_id).await?)
+}
+
+/// Create a new collection with the configuration of the `source` one and copy its points into it
+pub async fn do_clone_collection(
+    toc: &TableOfContent,
+    dispatcher: &Dispatcher,
+    source: &str,
+    operation: CloneCollection,
+    wait_timeout: Option<Duration>,
+) -> Result<bool, StorageError> {
+    let CloneCollection {
+        target,
+        filter,
+        hnsw_config,
+        optimizers_config,
+        quantization_config,
+    } = operation;
+
+    let (source_name, source_config) = {
+        let collection = toc.get_collection(source).await?;
+        (collection.name(), collection.state().await.config)
+    };
+
+    if source_config.params.sharding_method == Some(ShardingMethod::Custom) {
+        return Err(StorageError::bad_input(
+            "Cloning of collections with custom sharding is not supported",
+        ));
+    }
+
+    let hnsw_config = match hnsw_config {
+        Some(diff) => diff.update(&source_config.hnsw_config)?,
+        None => source_config.hnsw_config.clone(),
+    };
+    let optimizers_config = match optimizers_config {
+        Some(diff) => diff.update(&source_config.optimizer_config)?,
+        None => source_config.optimizer_config.clone(),
+    };
+    let quantization_config = match quantization_config {
+        Some(diff) => diff.into_config(),
+        None => source_config.quantization_config.clone(),
+    };
+
+    let create_collection = CreateCollection {
+        hnsw_config: Some(hnsw_config.into()),
+        optimizers_config: Some(optimizers_config.into()),
+        quantization_config,
+        init_from: Some(InitFrom {
+            collection: source_name,
+            filter,
+        }),
+        ..CreateCollection::from(source_config)
+    };
+
+    dispatcher
+        .submit_collection_meta_op(
+            CollectionMetaOperations::CreateCollection(CreateCollectionOperation::new(
+                target,
+                create_collection,
+            )),
+            wait_timeout,
+        )
+        .await
+}
+
+/// Train the dimensionality reduction of the vector, and store the transform in the collection config
+pub async fn do_train_dimensionality_reduction(
+    toc: &TableOfContent,
+    dispatcher: &Dispatcher,
+    collection_name: &str,
+    request: TrainDimensionalityReduction,
+    wait_timeout: Option<Duration>,
+) -> Result<bool, StorageError> {
+    let (collection_name, vectors) = {
+        let collection = toc.get_collection(collection_name).await?;
+        let vectors = collection.train_dimensionality_reduction(request).await?;
+        (collection.name(), vectors)
+    };
+
+    let mut operation = UpdateCollectionOperation::new_empty(collection_name);
+    operation.update_collection.vectors = Some(vectors);
+    dispatcher
+        .submit_collection_meta_op(
+            CollectionMetaOperations::UpdateCollection(operation),
+            wait_timeout,
+        )
+        .await
+}
+
 pub async fn do_update_collection_cluster(
     dispatcher: &Dispatcher,
     collection_name: String,
@@ -324,10 +461,10 @@ pub async fn do_update_collection_cluster(
             let state = collection.state().await;
 
             match state.config.params.sharding_method.unwrap_or_default() {
-                ShardingMethod::Auto => {
-                    return Err(StorageError::bad_request(
-                        "Shard Key cannot be created with Auto sharding method",
-                    ));
+                sharding_method @ (ShardingMethod::Auto | ShardingMethod::PayloadKey) => {
+                    return Err(StorageError::bad_request(format!(
+                        "Shard Key cannot be created with {sharding_method:?} sharding method",
+                    )));
                 }
                 ShardingMethod::Custom => {}
             }
@@ -392,10 +529,10 @@ pub async fn do_update_collection_cluster(
             let state = collection.state().await;
 
             match state.config.params.sharding_method.unwrap_or_default() {
-                ShardingMethod::Auto => {
-                    return Err(StorageError::bad_request(
-                        "Shard Key cannot be created with Auto sharding method",
-                    ));
+                sharding_method @ (ShardingMethod::Auto | ShardingMethod::PayloadKey) => {
+                    return Err(StorageError::bad_request(format!(
+                        "Shard Key cannot be created with {sharding_method:?} sharding method",
+                    )));
                 }
                 ShardingMethod::Custom => {}
             }
@@ -420,6 +557,43 @@ pub async fn do_update_collection_cluster(
                 )
                 .await
         }
+        ClusterOperations::SplitShard(SplitShardOperation { split_shard }) => {
+            // Validate that:
+            // - proper sharding method is used
+            // - shard exists
+
+            let state = collection.state().await;
+
+            match state.config.params.sharding_method.unwrap_or_default() {
+                ShardingMethod::Auto => {}
+                sharding_method @ (ShardingMethod::Custom | ShardingMethod::PayloadKey) => {
+                    return Err(StorageError::bad_request(format!(
+                        "Shards cannot be split with {sharding_method:?} sharding method",
+                    )));
+                }
+            }
+
+            if !state.shards.contains_key(&split_shard.shard_id) {
+                return Err(StorageError::BadRequest {
+                    description: format!(
+                        "Shard {} of {} does not exist",
+                        split_shard.shard_id, collection_name
+                    ),
+                });
+            }
+
+            dispatcher
+                .submit_collection_meta_op(
+                    CollectionMetaOperations::SplitShard(
+                        collection_name,
+                        ShardSplitOperations::Start {
+                            shard_id: split_shard.shard_id,
+                        },
+                    ),
+                    wait_timeout,
+                )
+                .await
+        }
     }
 }
 
@@ -0,0 +1,457 @@
+//! Re-applying of the service configuration without restart
+
+use std::sync::Arc;
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use storage::content_manager::errors::StorageError;
+use storage::content_manager::toc::TableOfContent;
+use tokio::sync::Mutex;
+use validator::Validate;
+
+use crate::settings::Settings;
+
+/// Changes log filters of the running service, accepts value of the `log_level` setting
+pub type LogLevelReloader = Box<dyn Fn(&str) -> anyhow::Result<()> + Send + Sync>;
+
+#[derive(Debug, Default, Deserialize, Serialize, JsonSchema)]
+pub struct ConfigReloadResult {
+    /// Changed settings, which are applied
+    pub applied: Vec<String>,
+    /// Changed settings, which are not applied, because they require restart of the service
+    pub requires_restart: Vec<String>,
+}
+
+/// Reads configuration files again and applies settings, which could be changed at runtime:
+///
+/// - log level
+/// - mmap advice and async scorer
+/// - update rate limit
+/// - snapshots path, if snapshots are stored locally
+/// - defaults of new collections: optimizers, WAL, HNSW index, quantization and on-disk payload
+///
+/// Changes of other settings are only reported.
+pub struct ConfigReloader {
+    config_path: Option<String>,
+    /// Settings, which are currently in effect
+    settings: Mutex<Settings>,
+    reload_log_level: LogLevelReloader,
+    toc: Arc<TableOfContent>,
+}
+
+impl ConfigReloader {
+    pub fn new(
+        config_path: Option<String>,
+        settings: Settings,
+        reload_log_level: LogLevelReloader,
+        toc: Arc<TableOfContent>,
+    ) -> Self {
+        Self {
+            config_path,
+            settings: Mutex::new(settings),
+            reload_log_level,
+            toc,
+        }
+    }
+
+    pub async fn reload(&self) -> Result<ConfigReloadResult, StorageError> {
+        let new = Settings::new(self.config_path.clone())
+            .map_err(|err| StorageError::bad_input(format!("Can't load configuration: {err}")))?;
+        new.validate()
+            .map_err(|err| StorageError::bad_input(format!("Invalid configuration: {err}")))?;
+
+        // Reloads are serialized, so settings are never applied out of order
+        let mut current = self.settings.lock().await;
+        let mut result = ConfigReloadResult::default();
+
+        if current.log_level != new.log_level {
+            (self.reload_log_level)(&new.log_level).map_err(|err| {
+                StorageError::bad_input(format!("Can't apply `log_level`: {err}"))
+            })?;
+            current.log_level = new.log_level.clone();
+            result.applied.push("log_level".to_string());
+        }
+
+        if is_changed(&current.storage.mmap_advice, &new.storage.mmap_advice) {
+            // Only affects storages, which are opened after this point
+            memory::madvise::set_global(new.storage.mmap_advice);
+            result.applied.push("storage.mmap_advice".to_string());
+        }
+        if current.storage.async_scorer != new.storage.async_scorer {
+            segment::vector_storage::common::set_async_scorer(new.storage.async_scorer);
+            result.applied.push("storage.async_scorer".to_string());
+        }
+
+        let toc_settings = [
+            (
+                "storage.performance.update_rate_limit",
+                is_changed(
+                    &current.storage.performance.update_rate_limit,
+                    &new.storage.performance.update_rate_limit,
+                ),
+            ),
+            (
+                "storage.on_disk_payload",
+                is_changed(
+                    &current.storage.on_disk_payload,
+                    &new.storage.on_disk_payload,
+                ),
+            ),
+            (
+                "storage.optimizers",
+                is_changed(&current.storage.optimizers, &new.storage.optimizers),
+            ),
+            (
+                "storage.wal",
+                is_changed(&current.storage.wal, &new.storage.wal),
+            ),
+            (
+                "storage.hnsw_index",
+                is_changed(&current.storage.hnsw_index, &new.storage.hnsw_index),
+            ),
+            (
+                "storage.quantization",
+                is_changed(&current.storage.quantization, &new.storage.quantization),
+            ),
+        ];
+        if toc_settings.iter().any(|(_, is_changed)| *is_changed) {
+            self.toc.reload_config(&new.storage);
+            result.applied.extend(changed_names(&toc_settings));
+        }
+
+        if current.storage.snapshots_path != new.storage.snapshots_path {
+            self.toc
+                .reload_snapshots_path(&new.storage.snapshots_path)
+                .await?;
+            current.storage.snapshots_path = new.storage.snapshots_path.clone();
+            result.applied.push("storage.snapshots_path".to_string());
+        }
+
+        current.storage.mmap_advice = new.storage.mmap_advice;
+        current.storage.async_scorer = new.storage.async_scorer;
+        current.storage.performance.update_rate_limit = new.storage.performance.update_rate_limit;
+        current.storage.on_disk_payload = new.storage.on_disk_payload;
+        current.storage.optimizers = new.storage.optimizers.clone();
+        current.storage.wal = new.storage.wal.clone();
+        current.storage.hnsw_index = new.storage.hnsw_index.clone();
+        current.storage.quantization = new.storage.quantization.clone();
+
+        // Not applied settings are compared with the startup values,
+        // so they are reported on every reload until the restart
+        let restart_settings = [
+            (
+                "storage.storage_path",
+                is_changed(&current.storage.storage_path, &new.storage.storage_path),
+            ),
+            (
+                "storage.snapshots_config",
+                is_changed(
+                    &current.storage.snapshots_config,
+                    &new.storage.snapshots_config,
+                ),
+            ),
+            (
+                "storage.snapshot_schedule",
+                is_changed(
+                    &current.storage.snapshot_schedule,
+                    &new.storage.snapshot_schedule,
+                ),
+            ),
+            (
+                "storage.disk_watchdog",
+                is_changed(&current.storage.disk_watchdog, &new.storage.disk_watchdog),
+            ),
+            (
+                "storage.shard_transfer",
+                is_changed(&current.storage.shard_transfer, &new.storage.shard_transfer),
+            ),
+            (
+                "storage.scrubber",
+                is_changed(&current.storage.scrubber, &new.storage.scrubber),
+            ),
+            (
+                "storage.usage_metering",
+                is_changed(&current.storage.usage_metering, &new.storage.usage_metering),
+            ),
+            (
+                "storage.feature_flags",
+                is_changed(&current.storage.feature_flags, &new.storage.feature_flags),
+            ),
+            (
+                "storage.consistency_check",
+                is_changed(
+                    &current.storage.consistency_check,
+                    &new.storage.consistency_check,
+                ),
+            ),
+            (
+                "storage.temp_path",
+                is_changed(&current.storage.temp_path, &new.storage.temp_path),
+            ),
+            (
+                "storage.performance.max_search_threads",
+                is_changed(
+                    &current.storage.performance.max_search_threads,
+                    &new.storage.performance.max_search_threads,
+                ),
+            ),
+            (
+                "storage.performance.max_optimization_threads",
+                is_changed(
+                    &current.storage.performance.max_optimization_threads,
+                    &new.storage.performance.max_optimization_threads,
+                ),
+            ),
+            (
+                "storage.performance.search_timeout_sec",
+                is_changed(
+                    &current.storage.performance.search_timeout_sec,
+                    &new.storage.performance.search_timeout_sec,
+                ),
+            ),
+            (
+                "storage.performance.low_priority_io_limit_mb",
+                is_changed(
+                    &current.storage.performance.low_priority_io_limit_mb,
+                    &new.storage.performance.low_priority_io_limit_mb,
+                ),
+            ),
+            (
+                "storage.performance.max_concurrent_searches",
+                is_changed(
+                    &current.storage.performance.max_concurrent_searches,
+                    &new.storage.performance.max_concurrent_searches,
+                ),
+            ),
+            (
+                "storage.performance.search_queue_size",
+                is_changed(
+                    &current.storage.performance.search_queue_size,
+                    &new.storage.performance.search_queue_size,
+                ),
+            ),
+            (
+                "storage.performance.search_queue_timeout_ms",
+                is_changed(
+                    &current.storage.performance.search_queue_timeout_ms,
+                    &new.storage.performance.search_queue_timeout_ms,
+                ),
+            ),
+            (
+                "storage.performance.read_load_balancing",
+                is_changed(
+                    &current.storage.performance.read_load_balancing,
+                    &new.storage.performance.read_load_balancing,
+                ),
+            ),
+            (
+                "storage.node_type",
+                is_changed(&current.storage.node_type, &new.storage.node_type),
+            ),
+            (
+                "storage.update_queue_size",
+                is_changed(
+                    &current.storage.update_queue_size,
+                    &new.storage.update_queue_size,
+                ),
+            ),
+            (
+                "storage.handle_collection_load_errors",
+                is_changed(
+                    &current.storage.handle_collection_load_errors,
+                    &new.storage.handle_collection_load_errors,
+                ),
+            ),
+            (
+                "storage.recovery_mode",
+                is_changed(&current.storage.recovery_mode, &new.storage.recovery_mode),
+            ),
+            (
+                "storage.update_concurrency",
+                is_changed(
+                    &current.storage.update_concurrency,
+                    &new.storage.update_concurrency,
+                ),
+            ),
+            (
+                "storage.warm_up_collections",
+                is_changed(
+                    &current.storage.warm_up_collections,
+                    &new.storage.warm_up_collections,
+                ),
+            ),
+            (
+                "storage.shutdown_timeout_sec",
+                is_changed(
+                    &current.storage.shutdown_timeout_sec,
+                    &new.storage.shutdown_timeout_sec,
+                ),
+            ),
+            ("service", is_changed(&current.service, &new.service)),
+            ("cluster", is_changed(&current.cluster, &new.cluster)),
+            ("tls", is_changed(&current.tls, &new.tls)),
+            (
+                "telemetry_disabled",
+                is_changed(&current.telemetry_disabled, &new.telemetry_disabled),
+            ),
+            ("webhooks", is_changed(&current.webhooks, &new.webhooks)),
+        ];
+        result.requires_restart = changed_names(&restart_settings).collect();
+
+        if !result.applied.is_empty() {
+            log::info!(
+                "Configuration reloaded, applied settings: {}",
+                result.applied.join(", "),
+            );
+        }
+        if !result.requires_restart.is_empty() {
+            log::warn!(
+                "Changed settings require restart to be applied: {}",
+                result.requires_restart.join(", "),
+            );
+        }
+
+        Ok(result)
+    }
+}
+
+fn is_changed<T: PartialEq>(current: &T, new: &T) -> bool {
+    current != new
+}
+
+fn changed_names<'a>(settings: &'a [(&str, bool)]) -> impl Iterator<Item = String> + 'a {
+    settings
+        .iter()
+        .filter(|(_, is_changed)| *is_changed)
+        .map(|(name, _)| name.to_string())
+}
+
+/// Reload configuration every time the process receives `SIGHUP`
+#[cfg(unix)]
+pub async fn reload_on_sighup(config_reloader: Arc<ConfigReloader>) {
+    use tokio::signal::unix::{signal, SignalKind};
+
+    let mut sighup = match signal(SignalKind::hangup()) {
+        Ok(sighup) => sighup,
+        Err(err) => {
+            log::error!("Can't listen for SIGHUP, configuration reload is disabled: {err}");
+            return;
+        }
+    };
+    while sighup.recv().await.is_some() {
+        log::info!("Received SIGHUP, reloading configuration");
+        if let Err(err) = config_reloader.reload().await {
+            log::error!("Failed to reload configuration: {err}");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use collection::shards::channel_service::ChannelService;
+    use tempfile::Builder;
+    use tokio::runtime::Runtime;
+
+    use super::*;
+
+    fn write_config(
+        config_path: &Path,
+        storage_path: &Path,
+        snapshots_path: &Path,
+        log_level: &str,
+        update_rate_limit: usize,
+    ) {
+        let config = format!(
+            "log_level: {log_level}\n\
+             storage:\n  \
+               storage_path: {}\n  \
+               snapshots_path: {}\n  \
+               performance:\n    \
+                 update_rate_limit: {update_rate_limit}\n",
+            storage_path.display(),
+            snapshots_path.display(),
+        );
+        std::fs::write(config_path, config).unwrap();
+    }
+
+    #[test]
+    fn test_reload_config() {
+        let dir = Builder::new().prefix("config_reload").tempdir().unwrap();
+        let config_path = dir.path().join("config.yaml");
+        write_config(
+            &config_path,
+            &dir.path().join("storage"),
+            &dir.path().join("snapshots"),
+            "INFO",
+            2,
+        );
+        let config_path = config_path.to_str().unwrap().to_string();
+
+        let settings = Settings::new(Some(config_path.clone())).unwrap();
+        let toc = Arc::new(TableOfContent::new(
+            &settings.storage,
+            Runtime::new().unwrap(),
+            Runtime::new().unwrap(),
+            Runtime::new().unwrap(),
+            ChannelService::new(settings.service.http_port),
+            0,
+            None,
+            Default::default(),
+        ));
+        let log_levels = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let config_reloader = ConfigReloader::new(
+            Some(config_path.clone()),
+            settings,
+            Box::new({
+                let log_levels = log_levels.clone();
+                move |log_level| {
+                    log_levels.lock().unwrap().push(log_level.to_string());
+                    Ok(())
+                }
+            }),
+            toc.clone(),
+        );
+        let runtime = Runtime::new().unwrap();
+
+        // Nothing is changed
+        let result = runtime.block_on(config_reloader.reload()).unwrap();
+        assert!(result.applied.is_empty());
+        assert!(result.requires_restart.is_empty());
+
+        let new_snapshots_path = dir.path().join("new_snapshots");
+        write_config(
+            Path::new(&config_path),
+            &dir.path().join("new_storage"),
+            &new_snapshots_path,
+            "DEBUG",
+            4,
+        );
+        let result = runtime.block_on(config_reloader.reload()).unwrap();
+        assert_eq!(
+            result.applied,
+            vec![
+                "log_level".to_string(),
+                "storage.performance.update_rate_limit".to_string(),
+                "storage.snapshots_path".to_string(),
+            ],
+        );
+        assert_eq!(
+            result.requires_restart,
+            vec!["storage.storage_path".to_string()],
+        );
+        assert_eq!(*log_levels.lock().unwrap(), vec!["DEBUG".to_string()]);
+        assert_eq!(toc.snapshots_path(), new_snapshots_path.to_str().unwrap());
+        assert!(new_snapshots_path.is_dir());
+
+        // Applied settings are not applied again, not applied ones are reported until the restart
+        let result = runtime.block_on(config_reloader.reload()).unwrap();
+        assert!(result.applied.is_empty());
+        assert_eq!(
+            result.requires_restart,
+            vec!["storage.storage_path".to_string()],
+        );
+        assert_eq!(log_levels.lock().unwrap().len(), 1);
+    }
+}
@@ -0,0 +1,61 @@
+//! Periodic check of the consistency of all shards of this peer
+//!
+//! Shards are checked one collection at a time. Anomalies are logged and reported in the optimizer
+//! status of the local shards, so the collection status turns red, see
+//! [`collection::shards::consistency_check`] for the checked invariants.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use storage::content_manager::toc::TableOfContent;
+use storage::types::ConsistencyCheckConfig;
+use tokio::time::MissedTickBehavior;
+
+pub struct ConsistencyChecker {
+    toc: Arc<TableOfContent>,
+    max_missed_updates: u64,
+}
+
+impl ConsistencyChecker {
+    /// Check all shards every `check_interval_sec`, does nothing if the interval is not set
+    pub async fn run(toc: Arc<TableOfContent>, config: ConsistencyCheckConfig) {
+        let Some(interval_sec) = config.check_interval_sec else {
+            return;
+        };
+        let checker = Self {
+            toc,
+            max_missed_updates: config.max_missed_updates,
+        };
+
+        let mut interval = tokio::time::interval(Duration::from_secs(interval_sec));
+        interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        // Shards are still loading right after the start
+        interval.tick().await;
+
+        loop {
+            interval.tick().await;
+            checker.check_all().await;
+        }
+    }
+
+    async fn check_all(&self) {
+        let mut anomalies_count = 0;
+        for collection_name in self.toc.all_collections().await {
+            // Collection might be deleted concurrently
+            let Ok(collection) = self.toc.get_collection(&collection_name).await else {
+                continue;
+            };
+            let anomalies = collection.check_consistency(self.max_missed_updates).await;
+            for (shard_id, anomaly) in &anomalies {
+                log::error!(
+                    "Consistency check of shard {shard_id} of collection {collection_name} \
+                     failed: {anomaly}",
+                );
+            }
+            anomalies_count += anomalies.len();
+        }
+        if anomalies_count == 0 {
+            log::debug!("Consistency check found no anomalies");
+        }
+    }
+}
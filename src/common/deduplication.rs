@@ -0,0 +1,170 @@
+//! Detection of near-duplicate points on insert
+//!
+//! Before the points are inserted, each of them is searched for among the existing points of the
+//! collection. The search is approximate and bounded: only the nearest couple of points are
+//! requested, with a small `ef`, so the check stays cheap even for large collections. Points with
+//! a match above the threshold are skipped, overwrite the match, or are tagged with its ID.
+
+use collection::operations::point_ops::{Deduplication, DuplicateAction, PointStruct};
+use collection::operations::shard_selector_internal::ShardSelectorInternal;
+use collection::operations::types::{CoreSearchRequest, CoreSearchRequestBatch, QueryEnum};
+use collection::shards::replica_set::read_priority::ReadPriority;
+use segment::data_types::vectors::{NamedVectorStruct, DEFAULT_VECTOR_NAME};
+use segment::types::{PointIdType, SearchParams};
+use storage::content_manager::errors::StorageError;
+use storage::content_manager::toc::TableOfContent;
+
+/// Size of the candidate list of the HNSW search, which looks for duplicates
+const DEDUPLICATION_HNSW_EF: usize = 64;
+
+/// Payload field with the ID of the existing point, set by the `tag` action
+pub const DUPLICATE_OF_KEY: &str = "duplicate_of";
+
+/// Find near-duplicates of `points` among the existing points and apply the configured action
+///
+/// Points without the compared vector are kept as they are.
+pub async fn deduplicate_points(
+    toc: &TableOfContent,
+    collection_name: &str,
+    points: Vec<PointStruct>,
+    deduplication: &Deduplication,
+    shard_selection: ShardSelectorInternal,
+) -> Result<Vec<PointStruct>, StorageError> {
+    let using = deduplication
+        .using
+        .clone()
+        .unwrap_or_else(|| DEFAULT_VECTOR_NAME.to_string());
+
+    // Indexes of the points, which are searched for
+    let mut searched = Vec::new();
+    let mut searches = Vec::new();
+    for (index, point) in points.iter().enumerate() {
+        let Some(vector) = point.vector.get(&using) else {
+            continue;
+        };
+        searched.push(index);
+        searches.push(CoreSearchRequest {
+            query: QueryEnum::Nearest(NamedVectorStruct::new_from_vector(
+                vector.to_vec(),
+                using.clone(),
+            )),
+            filter: None,
+            params: Some(SearchParams {
+                hnsw_ef: Some(DEDUPLICATION_HNSW_EF),
+                ..Default::default()
+            }),
+            // The point itself may already exist, it is an update then, not a duplicate
+            limit: 2,
+            offset: 0,
+            with_payload: None,
+            with_vector: None,
+            score_threshold: Some(deduplication.threshold),
+            prefetch: None,
+            rescore: None,
+            distinct_by: None,
+        });
+    }
+    if searches.is_empty() {
+        return Ok(points);
+    }
+
+    let results = toc
+        .core_search_batch(
+            collection_name,
+            CoreSearchRequestBatch { searches },
+            None,
+            None,
+            shard_selection,
+            None,
+            None,
+            ReadPriority::Normal,
+            None,
+        )
+        .await?;
+
+    let mut duplicates = vec![None; points.len()];
+    for (index, scored_points) in searched.into_iter().zip(results) {
+        duplicates[index] = scored_points
+            .into_iter()
+            .map(|scored_point| scored_point.id)
+            .find(|id| *id != points[index].id);
+    }
+
+    let found = duplicates.iter().flatten().count();
+    if found > 0 {
+        log::debug!(
+            "Found {found} duplicates of {} inserted points in collection {collection_name}, action: {:?}",
+            points.len(),
+            deduplication.action,
+        );
+    }
+
+    Ok(apply_duplicates(points, duplicates, deduplication.action))
+}
+
+/// Apply `action` to the points, which have an existing duplicate
+fn apply_duplicates(
+    points: Vec<PointStruct>,
+    duplicates: Vec<Option<PointIdType>>,
+    action: DuplicateAction,
+) -> Vec<PointStruct> {
+    points
+        .into_iter()
+        .zip(duplicates)
+        .filter_map(|(mut point, duplicate_of)| {
+            let Some(duplicate_of) = duplicate_of else {
+                return Some(point);
+            };
+            match action {
+                DuplicateAction::Skip => return None,
+                DuplicateAction::Overwrite => point.id = duplicate_of,
+                DuplicateAction::Tag => {
+                    point.payload.get_or_insert_with(Default::default).0.insert(
+                        DUPLICATE_OF_KEY.to_string(),
+                        serde_json::to_value(duplicate_of).unwrap_or_default(),
+                    );
+                }
+            }
+            Some(point)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use segment::data_types::vectors::VectorStruct;
+
+    use super::*;
+
+    fn points() -> Vec<PointStruct> {
+        (1..=3u64)
+            .map(|id| PointStruct {
+                id: id.into(),
+                vector: VectorStruct::Single(vec![id as f32; 4]),
+                payload: None,
+                if_version: None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_apply_duplicates() {
+        let duplicates = vec![None, Some(10.into()), None];
+
+        let skipped = apply_duplicates(points(), duplicates.clone(), DuplicateAction::Skip);
+        let ids: Vec<_> = skipped.iter().map(|point| point.id).collect();
+        assert_eq!(ids, vec![1.into(), 3.into()]);
+
+        let overwritten =
+            apply_duplicates(points(), duplicates.clone(), DuplicateAction::Overwrite);
+        let ids: Vec<_> = overwritten.iter().map(|point| point.id).collect();
+        assert_eq!(ids, vec![1.into(), 10.into(), 3.into()]);
+
+        let tagged = apply_duplicates(points(), duplicates, DuplicateAction::Tag);
+        assert!(tagged[0].payload.is_none());
+        assert_eq!(
+            tagged[1].payload.as_ref().unwrap().0[DUPLICATE_OF_KEY],
+            serde_json::json!(10),
+        );
+    }
+}
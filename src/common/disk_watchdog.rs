@@ -0,0 +1,237 @@
+//! Rejection of updates before the disk is full
+//!
+//! Running out of disk space in the middle of a WAL write or an optimization may leave the storage
+//! in a broken state. The watchdog periodically checks free space of the storage paths, and
+//! switches the storage to read-only, while any of them is below the minimum. Updates are accepted
+//! again once all paths have enough space above the resume threshold, so it doesn't flap around
+//! the minimum. Deletes are accepted all the time, as they are the way to free space.
+//!
+//! With `freeze_shards`, local shards are also frozen read-only through consensus, so updates of
+//! these shards are rejected by all peers, not only by this one. Only shards frozen by the
+//! watchdog are unfrozen once the space is freed, freezes set by users are kept.
+
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+
+use collection::collection::read_only::Freeze;
+use storage::content_manager::collection_meta_ops::{CollectionMetaOperations, SetReadOnly};
+use storage::content_manager::toc::TableOfContent;
+use storage::dispatcher::Dispatcher;
+use storage::types::{DiskWatchdogConfig, StorageConfig};
+use tokio::time::MissedTickBehavior;
+
+const DEFAULT_CHECK_INTERVAL_SEC: u64 = 10;
+
+const MB: u64 = 1024 * 1024;
+
+pub struct DiskWatchdog {
+    dispatcher: Arc<Dispatcher>,
+    /// Paths, where the collections store their data, snapshots path is taken from the storage
+    /// as it may be changed by config reload
+    paths: Vec<PathBuf>,
+    thresholds: Thresholds,
+    freeze_shards: bool,
+}
+
+/// Free space, required to accept updates
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Thresholds {
+    min_free_space_mb: u64,
+    resume_free_space_mb: u64,
+}
+
+impl Thresholds {
+    fn new(min_free_space_mb: u64, resume_free_space_mb: Option<u64>) -> Self {
+        Self {
+            min_free_space_mb,
+            resume_free_space_mb: resume_free_space_mb
+                .unwrap_or(min_free_space_mb)
+                .max(min_free_space_mb),
+        }
+    }
+
+    /// Once updates are rejected, more space is required to accept them again
+    fn required_mb(&self, is_protected: bool) -> u64 {
+        if is_protected {
+            self.resume_free_space_mb
+        } else {
+            self.min_free_space_mb
+        }
+    }
+
+    /// Whether updates should be rejected with the given free space of a watched path
+    fn is_low(&self, is_protected: bool, free_space_mb: u64) -> bool {
+        free_space_mb < self.required_mb(is_protected)
+    }
+}
+
+impl DiskWatchdog {
+    /// Watch free space of the storage paths, does nothing if the minimal free space is not set
+    pub async fn run(dispatcher: Arc<Dispatcher>, config: StorageConfig) {
+        let DiskWatchdogConfig {
+            min_free_space_mb,
+            resume_free_space_mb,
+            check_interval_sec,
+            freeze_shards,
+        } = config.disk_watchdog;
+        let Some(min_free_space_mb) = min_free_space_mb else {
+            return;
+        };
+
+        let mut paths = vec![PathBuf::from(&config.storage_path)];
+        if let Some(temp_path) = config.temp_path {
+            paths.push(PathBuf::from(temp_path));
+        }
+
+        let watchdog = Self {
+            dispatcher,
+            paths,
+            thresholds: Thresholds::new(min_free_space_mb, resume_free_space_mb),
+            freeze_shards,
+        };
+
+        let check_interval_sec = check_interval_sec.unwrap_or(DEFAULT_CHECK_INTERVAL_SEC);
+        let mut interval = tokio::time::interval(Duration::from_secs(check_interval_sec));
+        interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+        loop {
+            interval.tick().await;
+            watchdog.check().await;
+        }
+    }
+
+    fn toc(&self) -> &TableOfContent {
+        self.dispatcher.toc()
+    }
+
+    async fn check(&self) {
+        let is_protected = self.toc().is_disk_space_protected();
+        let threshold_mb = self.thresholds.required_mb(is_protected);
+
+        let snapshots_path = PathBuf::from(self.toc().snapshots_path());
+        for path in self.paths.iter().chain([&snapshots_path]) {
+            let free_space_mb = match available_space_mb(path.clone()).await {
+                Ok(free_space_mb) => free_space_mb,
+                Err(err) => {
+                    log::warn!("Can't check free disk space of {}: {err}", path.display());
+                    continue;
+                }
+            };
+
+            if self.thresholds.is_low(is_protected, free_space_mb) {
+                if !is_protected {
+                    log::error!(
+                        "Free disk space of {} is {free_space_mb} MB, below the minimum of {} MB, \
+                         updates are rejected until space is freed",
+                        path.display(),
+                        self.thresholds.min_free_space_mb,
+                    );
+                }
+                self.toc().set_disk_space_protection(Some(format!(
+                    "Not enough free disk space: {free_space_mb} MB left on {}, \
+                     at least {threshold_mb} MB is required to accept updates. \
+                     Free some disk space to resume updates",
+                    path.display(),
+                )));
+                // Repeated on each check, so new shards and failed freezes are covered
+                if self.freeze_shards {
+                    self.set_local_shards_read_only(true).await;
+                }
+                return;
+            }
+        }
+
+        if is_protected {
+            log::info!("Enough free disk space, updates are accepted again");
+            self.toc().set_disk_space_protection(None);
+        }
+        // Shards might remain frozen by the previous run of the service as well
+        if self.freeze_shards {
+            self.set_local_shards_read_only(false).await;
+        }
+    }
+
+    /// Freeze the local shards of all collections, or unfreeze the ones frozen by the watchdog
+    ///
+    /// Only shards, which state has to change, are submitted to consensus.
+    async fn set_local_shards_read_only(&self, read_only: bool) {
+        let toc = self.toc();
+        let reason = format!("Not enough free disk space on peer {}", toc.this_peer_id);
+
+        for collection_name in toc.all_collections().await {
+            let shard_ids: Vec<_> = {
+                // Collection might be deleted concurrently
+                let Ok(collection) = toc.get_collection(&collection_name).await else {
+                    continue;
+                };
+                let frozen = collection.read_only_state().shards;
+                collection
+                    .get_local_shards()
+                    .await
+                    .into_iter()
+                    .filter(|shard_id| match frozen.get(shard_id) {
+                        None => read_only,
+                        Some(freeze) => !read_only && freeze.reason.as_ref() == Some(&reason),
+                    })
+                    .collect()
+            };
+
+            for shard_id in shard_ids {
+                let operation = CollectionMetaOperations::SetReadOnly(SetReadOnly {
+                    collection_name: collection_name.clone(),
+                    shard_id: Some(shard_id),
+                    freeze: read_only.then(|| Freeze {
+                        reason: Some(reason.clone()),
+                        allow_deletes: true,
+                    }),
+                });
+                if let Err(err) = self
+                    .dispatcher
+                    .submit_collection_meta_op(operation, None)
+                    .await
+                {
+                    log::warn!(
+                        "Can't change read-only state of shard {shard_id} of collection \
+                         {collection_name}: {err}",
+                    );
+                }
+            }
+        }
+    }
+}
+
+async fn available_space_mb(path: PathBuf) -> std::io::Result<u64> {
+    tokio::task::spawn_blocking(move || fs4::available_space(path))
+        .await?
+        .map(|bytes| bytes / MB)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_thresholds() {
+        let thresholds = Thresholds::new(100, Some(200));
+        assert!(!thresholds.is_low(false, 150));
+        assert!(!thresholds.is_low(false, 100));
+        assert!(thresholds.is_low(false, 99));
+
+        // Updates are not accepted again right above the minimum
+        assert!(thresholds.is_low(true, 150));
+        assert!(thresholds.is_low(true, 199));
+        assert!(!thresholds.is_low(true, 200));
+    }
+
+    #[test]
+    fn test_resume_threshold_defaults() {
+        let thresholds = Thresholds::new(100, None);
+        assert_eq!(thresholds.required_mb(false), 100);
+        assert_eq!(thresholds.required_mb(true), 100);
+
+        // Resume threshold can't be below the minimum
+        let thresholds = Thresholds::new(100, Some(50));
+        assert_eq!(thresholds.required_mb(true), 100);
+    }
+}
@@ -0,0 +1,290 @@
+//! Updates of all points matching a filter, executed server-side in batches
+//!
+//! Unlike the regular update by filter, which is applied to all matching points at once, these
+//! operations scroll the matching point IDs page by page and apply the update to one page at a
+//! time. Each batch is awaited before the next one is started, and an optional delay between the
+//! batches gives way to the serving traffic, so even very large matches don't overload the
+//! update queue.
+//!
+//! While the update is running, it is listed in the running operations of the collection along
+//! with the number of the points updated so far.
+
+use std::time::{Duration, Instant};
+
+use collection::operations::consistency_params::WriteConsistency;
+use collection::operations::payload_ops::{PayloadOps, SetPayloadOp};
+use collection::operations::point_ops::{PointOperations, WriteOrdering};
+use collection::operations::running_operations::{OperationProgress, RunningOperationKind};
+use collection::operations::shard_key_selector::ShardKeySelector;
+use collection::operations::shard_selector_internal::ShardSelectorInternal;
+use collection::operations::types::ScrollRequestInternal;
+use collection::operations::update_priority::UpdatePriority;
+use collection::operations::CollectionUpdateOperations;
+use collection::shards::replica_set::read_priority::ReadPriority;
+use schemars::JsonSchema;
+use segment::types::{ExtendedPointId, Filter, Payload, WithPayloadInterface, WithVector};
+use serde::{Deserialize, Serialize};
+use storage::content_manager::errors::StorageError;
+use storage::content_manager::toc::TableOfContent;
+use validator::Validate;
+
+const DEFAULT_BATCH_SIZE: usize = 1000;
+
+/// Progress is logged after this many batches
+const PROGRESS_LOG_INTERVAL: usize = 10;
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Validate)]
+pub struct DeleteByFilter {
+    /// Delete all points, which satisfy this filter condition
+    #[validate]
+    pub filter: Filter,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub shard_key: Option<ShardKeySelector>,
+    /// Number of points deleted at once. Default: 1000
+    #[validate(range(min = 1))]
+    pub batch_size: Option<usize>,
+    /// Pause between the batches in milliseconds, to limit the impact on the serving traffic.
+    /// Default: no pause
+    pub batch_delay_ms: Option<u64>,
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Validate)]
+pub struct SetPayloadByFilter {
+    pub payload: Payload,
+    /// Assigns payload to each point, which satisfies this filter condition
+    #[validate]
+    pub filter: Filter,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub shard_key: Option<ShardKeySelector>,
+    /// Number of points updated at once. Default: 1000
+    #[validate(range(min = 1))]
+    pub batch_size: Option<usize>,
+    /// Pause between the batches in milliseconds, to limit the impact on the serving traffic.
+    /// Default: no pause
+    pub batch_delay_ms: Option<u64>,
+}
+
+#[derive(Debug, Default, Deserialize, Serialize, JsonSchema)]
+pub struct FilterUpdateResult {
+    /// Number of the updated points
+    pub points: usize,
+    /// Number of the applied batches
+    pub batches: usize,
+}
+
+struct BatchParams {
+    size: usize,
+    delay: Option<Duration>,
+}
+
+impl BatchParams {
+    fn new(batch_size: Option<usize>, batch_delay_ms: Option<u64>) -> Self {
+        Self {
+            size: batch_size.unwrap_or(DEFAULT_BATCH_SIZE),
+            delay: batch_delay_ms
+                .filter(|delay| *delay > 0)
+                .map(Duration::from_millis),
+        }
+    }
+}
+
+pub async fn do_delete_by_filter(
+    toc: &TableOfContent,
+    collection_name: &str,
+    operation: DeleteByFilter,
+    ordering: Option<WriteOrdering>,
+    shard_deactivation_timeout: Option<Duration>,
+    write_consistency: Option<WriteConsistency>,
+    timeout: Option<Duration>,
+    priority: Option<UpdatePriority>,
+) -> Result<FilterUpdateResult, StorageError> {
+    let DeleteByFilter {
+        filter,
+        shard_key,
+        batch_size,
+        batch_delay_ms,
+    } = operation;
+
+    update_by_filter_batched(
+        toc,
+        collection_name,
+        "delete",
+        filter,
+        shard_key,
+        BatchParams::new(batch_size, batch_delay_ms),
+        ordering,
+        shard_deactivation_timeout,
+        write_consistency,
+        timeout,
+        priority,
+        |ids| CollectionUpdateOperations::PointOperation(PointOperations::DeletePoints { ids }),
+    )
+    .await
+}
+
+pub async fn do_set_payload_by_filter(
+    toc: &TableOfContent,
+    collection_name: &str,
+    operation: SetPayloadByFilter,
+    ordering: Option<WriteOrdering>,
+    shard_deactivation_timeout: Option<Duration>,
+    write_consistency: Option<WriteConsistency>,
+    timeout: Option<Duration>,
+    priority: Option<UpdatePriority>,
+) -> Result<FilterUpdateResult, StorageError> {
+    let SetPayloadByFilter {
+        payload,
+        filter,
+        shard_key,
+        batch_size,
+        batch_delay_ms,
+    } = operation;
+
+    update_by_filter_batched(
+        toc,
+        collection_name,
+        "set payload",
+        filter,
+        shard_key,
+        BatchParams::new(batch_size, batch_delay_ms),
+        ordering,
+        shard_deactivation_timeout,
+        write_consistency,
+        timeout,
+        priority,
+        |ids| {
+            CollectionUpdateOperations::PayloadOperation(PayloadOps::SetPayload(SetPayloadOp {
+                payload: payload.clone(),
+                points: Some(ids),
+                filter: None,
+                if_version: None,
+            }))
+        },
+    )
+    .await
+}
+
+/// Scroll IDs of the points matching `filter` and apply the operation built by `operation`
+/// to each page of them
+///
+/// Pages are scrolled by offset, so points, which stop or start matching the filter because of
+/// the update, are neither updated twice nor cause an endless loop.
+#[allow(clippy::too_many_arguments)]
+async fn update_by_filter_batched(
+    toc: &TableOfContent,
+    collection_name: &str,
+    operation_name: &str,
+    filter: Filter,
+    shard_key: Option<ShardKeySelector>,
+    batch: BatchParams,
+    ordering: Option<WriteOrdering>,
+    shard_deactivation_timeout: Option<Duration>,
+    write_consistency: Option<WriteConsistency>,
+    timeout: Option<Duration>,
+    priority: Option<UpdatePriority>,
+    operation: impl Fn(Vec<ExtendedPointId>) -> CollectionUpdateOperations,
+) -> Result<FilterUpdateResult, StorageError> {
+    let (scroll_selector, update_selector) = match shard_key {
+        Some(shard_key) => (
+            ShardSelectorInternal::from(shard_key.clone()),
+            ShardSelectorInternal::from(shard_key),
+        ),
+        None => (ShardSelectorInternal::All, ShardSelectorInternal::Empty),
+    };
+
+    let running_operation = toc
+        .get_collection(collection_name)
+        .await?
+        .register_operation(
+            RunningOperationKind::UpdateByFilter,
+            format!("Batched {operation_name} by filter"),
+        );
+
+    let started = Instant::now();
+    let mut result = FilterUpdateResult::default();
+    let mut offset = None;
+
+    loop {
+        let request = ScrollRequestInternal {
+            offset,
+            limit: Some(batch.size),
+            filter: Some(filter.clone()),
+            with_payload: Some(WithPayloadInterface::Bool(false)),
+            with_vector: WithVector::Bool(false),
+            as_of: None,
+            session_id: None,
+            session_ttl_sec: None,
+            order_by: None,
+            sample: None,
+            with_version: false,
+        };
+        let page = toc
+            .scroll(
+                collection_name,
+                request,
+                None,
+                scroll_selector.clone(),
+                None,
+                ReadPriority::Normal,
+                None,
+            )
+            .await?;
+        offset = page.next_page_offset;
+
+        if page.points.is_empty() {
+            break;
+        }
+
+        let ids: Vec<_> = page.points.into_iter().map(|point| point.id).collect();
+        let points = ids.len();
+
+        // Wait for each batch, so the update queue is never flooded
+        toc.update(
+            collection_name,
+            operation(ids),
+            Some(true),
+            ordering,
+            shard_deactivation_timeout,
+            write_consistency,
+            timeout,
+            priority,
+            update_selector.clone(),
+        )
+        .await?;
+
+        result.points += points;
+        result.batches += 1;
+        running_operation.set_progress(OperationProgress {
+            done: result.points as u64,
+            total: None,
+        });
+
+        if result.batches % PROGRESS_LOG_INTERVAL == 0 {
+            log::info!(
+                "Batched {operation_name} by filter in collection {collection_name}: \
+                 {} points in {} batches updated in {:.1}s",
+                result.points,
+                result.batches,
+                started.elapsed().as_secs_f64(),
+            );
+        }
+
+        if offset.is_none() {
+            break;
+        }
+
+        if let Some(delay) = batch.delay {
+            tokio::time::sleep(delay).await;
+        }
+    }
+
+    log::info!(
+        "Batched {operation_name} by filter in collection {collection_name} finished: \
+         {} points in {} batches updated in {:.1}s",
+        result.points,
+        result.batches,
+        started.elapsed().as_secs_f64(),
+    );
+
+    Ok(result)
+}
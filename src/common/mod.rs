@@ -1,7 +1,13 @@
 #[allow(dead_code)] // May contain functions used in different binaries. Not actually dead
 pub mod collections;
 #[allow(dead_code)] // May contain functions used in different binaries. Not actually dead
+pub mod config_reload;
+pub mod consistency_checker;
+pub mod deduplication;
+pub mod disk_watchdog;
+#[allow(dead_code)] // May contain functions used in different binaries. Not actually dead
 pub mod error_reporting;
+pub mod filter_updates;
 #[allow(dead_code)]
 pub mod health;
 #[allow(dead_code)] // May contain functions used in different binaries. Not actually dead
@@ -10,6 +16,9 @@ pub mod http_client;
 pub mod metrics;
 #[allow(dead_code)] // May contain functions used in different binaries. Not actually dead
 pub mod points;
+pub mod running_operations;
+#[allow(dead_code)] // May contain functions used in different binaries. Not actually dead
+pub mod snapshot_scheduler;
 pub mod snapshots;
 #[allow(dead_code)] // May contain functions used in different binaries. Not actually dead
 pub mod stacktrace;
@@ -22,3 +31,5 @@ pub mod telemetry_reporting;
 pub mod auth;
 
 pub mod strings;
+pub mod usage_rollups;
+pub mod webhooks;
@@ -1,29 +1,45 @@
-use std::time::Duration;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
+use collection::collection::access_tags::AccessTags;
+use collection::collection::soft_delete::undelete_operation;
 use collection::common::batching::batch_requests;
-use collection::operations::consistency_params::ReadConsistency;
+use collection::common::inference;
+use collection::config::InferenceConfig;
+use collection::hybrid_search::HybridSearchRequest;
+use collection::multi_search::{MultiCollectionScoredPoint, MultiCollectionSearchRequest};
+use collection::operations::consistency_params::{ReadConsistency, WriteConsistency};
+use collection::operations::consistency_token::ConsistencyToken;
+use collection::operations::document_ops::{PointsDocuments, SearchDocumentRequest};
 use collection::operations::payload_ops::{
     DeletePayload, DeletePayloadOp, PayloadOps, SetPayload, SetPayloadOp,
 };
 use collection::operations::point_ops::{
-    FilterSelector, PointIdsList, PointInsertOperations, PointOperations, PointsSelector,
-    WriteOrdering,
+    FilterSelector, PointIdsList, PointInsertOperations, PointInsertOperationsInternal,
+    PointOperations, PointsSelector, WriteOrdering,
 };
 use collection::operations::shard_key_selector::ShardKeySelector;
 use collection::operations::shard_selector_internal::ShardSelectorInternal;
 use collection::operations::types::{
     CoreSearchRequest, CoreSearchRequestBatch, CountRequestInternal, CountResult,
-    DiscoverRequestBatch, DiscoverRequestInternal, GroupsResult, PointRequestInternal,
-    RecommendGroupsRequestInternal, Record, ScrollRequestInternal, ScrollResult,
-    SearchGroupsRequestInternal, UpdateResult,
+    DiscoverRequestBatch, DiscoverRequestInternal, GroupsResult, PartialSearchBatchResult,
+    PartialSearchResult, PointRequestInternal, RecommendGroupsRequestInternal, Record,
+    ScrollRequestInternal, ScrollResult, SearchGroupsRequestInternal, SearchPageResult,
+    SearchRequest, UpdateResult, UpdateStatus,
 };
+use collection::operations::update_priority::UpdatePriority;
 use collection::operations::vector_ops::{
     DeleteVectors, UpdateVectors, UpdateVectorsOp, VectorOperations,
 };
 use collection::operations::{CollectionUpdateOperations, CreateIndex, FieldIndexOperations};
+use collection::search_matrix::{SearchMatrixRequest, SearchMatrixRow};
+use collection::shards::replica_set::read_priority::ReadPriority;
+use collection::shards::replica_set::stale_reads::StaleReads;
 use collection::shards::shard::ShardId;
 use schemars::JsonSchema;
-use segment::types::{PayloadFieldSchema, PayloadKeyType, ScoredPoint};
+use segment::types::{
+    is_reserved_payload_key, PayloadFieldSchema, PayloadKeyType, ScoredPoint,
+    RESERVED_PAYLOAD_KEY_PREFIX,
+};
 use serde::{Deserialize, Serialize};
 use storage::content_manager::collection_meta_ops::{
     CollectionMetaOperations, CreatePayloadIndex, DropPayloadIndex,
@@ -33,6 +49,8 @@ use storage::content_manager::toc::TableOfContent;
 use storage::dispatcher::Dispatcher;
 use validator::Validate;
 
+use crate::common::deduplication::deduplicate_points;
+
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Validate)]
 pub struct CreateFieldIndex {
     pub field_name: PayloadKeyType,
@@ -43,49 +61,49 @@ pub struct CreateFieldIndex {
 #[derive(Deserialize, Serialize, JsonSchema, Validate)]
 pub struct UpsertOperation {
     #[validate]
-    upsert: PointInsertOperations,
+    pub upsert: PointInsertOperations,
 }
 
 #[derive(Deserialize, Serialize, JsonSchema, Validate)]
 pub struct DeleteOperation {
     #[validate]
-    delete: PointsSelector,
+    pub delete: PointsSelector,
 }
 
 #[derive(Deserialize, Serialize, JsonSchema, Validate)]
 pub struct SetPayloadOperation {
     #[validate]
-    set_payload: SetPayload,
+    pub set_payload: SetPayload,
 }
 
 #[derive(Deserialize, Serialize, JsonSchema, Validate)]
 pub struct OverwritePayloadOperation {
     #[validate]
-    overwrite_payload: SetPayload,
+    pub overwrite_payload: SetPayload,
 }
 
 #[derive(Deserialize, Serialize, JsonSchema, Validate)]
 pub struct DeletePayloadOperation {
     #[validate]
-    delete_payload: DeletePayload,
+    pub delete_payload: DeletePayload,
 }
 
 #[derive(Deserialize, Serialize, JsonSchema, Validate)]
 pub struct ClearPayloadOperation {
     #[validate]
-    clear_payload: PointsSelector,
+    pub clear_payload: PointsSelector,
 }
 
 #[derive(Deserialize, Serialize, JsonSchema, Validate)]
 pub struct UpdateVectorsOperation {
     #[validate]
-    update_vectors: UpdateVectors,
+    pub update_vectors: UpdateVectors,
 }
 
 #[derive(Deserialize, Serialize, JsonSchema, Validate)]
 pub struct DeleteVectorsOperation {
     #[validate]
-    delete_vectors: DeleteVectors,
+    pub delete_vectors: DeleteVectors,
 }
 
 #[derive(Deserialize, Serialize, JsonSchema)]
@@ -105,6 +123,13 @@ pub enum UpdateOperation {
 #[derive(Deserialize, Serialize, JsonSchema, Validate)]
 pub struct UpdateOperations {
     pub operations: Vec<UpdateOperation>,
+    /// If true, all operations are applied as a single update: in order, under one operation
+    /// number, without other updates in between. If an operation fails, changes of the previous
+    /// ones are rolled back within the shard. Reads are not isolated from the batch, and might
+    /// see it partially applied. A batch can change at most 10000 points. All operations must use
+    /// the same shard key. Default: false
+    #[serde(default)]
+    pub all_or_nothing: bool,
 }
 
 impl Validate for UpdateOperation {
@@ -158,10 +183,66 @@ pub async fn do_upsert_points(
     collection_name: &str,
     operation: PointInsertOperations,
     shard_selection: Option<ShardId>,
-    wait: bool,
-    ordering: WriteOrdering,
+    wait: Option<bool>,
+    ordering: Option<WriteOrdering>,
+    shard_deactivation_timeout: Option<Duration>,
+    write_consistency: Option<WriteConsistency>,
+    timeout: Option<Duration>,
+    priority: Option<UpdatePriority>,
 ) -> Result<UpdateResult, StorageError> {
-    let (shard_key, operation) = operation.decompose();
+    let deduplication = operation.deduplication().cloned();
+    let ttl = operation.ttl();
+    let (shard_key, mut operation) = operation.decompose();
+
+    if let Some(ttl) = ttl {
+        let expiration_field = toc
+            .get_collection(collection_name)
+            .await?
+            .expiration_field()
+            .await
+            .ok_or_else(|| {
+                StorageError::bad_request(
+                    "TTL of points requires `expiration_field` to be configured for the collection",
+                )
+            })?;
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+        operation.set_expiration(&expiration_field, now.saturating_add(ttl));
+    }
+
+    if let Some(deduplication) = deduplication {
+        // Duplicates are looked for in the same shards, where the points are inserted
+        let search_selector = match (shard_selection, &shard_key) {
+            (Some(shard_id), _) => ShardSelectorInternal::ShardId(shard_id),
+            (None, Some(shard_key)) => ShardSelectorInternal::from(shard_key.clone()),
+            (None, None) => ShardSelectorInternal::All,
+        };
+        let points = match operation {
+            PointInsertOperationsInternal::PointsBatch(batch) => batch.into_points(),
+            PointInsertOperationsInternal::PointsList(points) => points,
+        };
+        let points = deduplicate_points(
+            toc,
+            collection_name,
+            points,
+            &deduplication,
+            search_selector,
+        )
+        .await?;
+        if points.is_empty() {
+            // All points are skipped as duplicates, nothing to update
+            return Ok(UpdateResult {
+                operation_id: None,
+                status: UpdateStatus::Completed,
+                consistency_token: None,
+                acknowledgments: None,
+            });
+        }
+        operation = PointInsertOperationsInternal::PointsList(points);
+    }
+
     let collection_operation =
         CollectionUpdateOperations::PointOperation(PointOperations::UpsertPoints(operation));
 
@@ -172,18 +253,87 @@ pub async fn do_upsert_points(
         collection_operation,
         wait,
         ordering,
+        shard_deactivation_timeout,
+        write_consistency,
+        timeout,
+        priority,
         shard_selector,
     )
     .await
 }
 
+/// Inference endpoint of the collection, which converts documents into its vectors
+async fn get_inference_config(
+    toc: &TableOfContent,
+    collection_name: &str,
+) -> Result<InferenceConfig, StorageError> {
+    toc.get_collection(collection_name)
+        .await?
+        .inference_config()
+        .await
+        .ok_or_else(|| {
+            StorageError::bad_request(
+                "Documents require `inference` endpoint to be configured for the collection",
+            )
+        })
+}
+
+/// Upsert points, vectors of which are inferred from their documents
+pub async fn do_upsert_documents(
+    toc: &TableOfContent,
+    collection_name: &str,
+    operation: PointsDocuments,
+    wait: Option<bool>,
+    ordering: Option<WriteOrdering>,
+    shard_deactivation_timeout: Option<Duration>,
+    write_consistency: Option<WriteConsistency>,
+    timeout: Option<Duration>,
+    priority: Option<UpdatePriority>,
+) -> Result<UpdateResult, StorageError> {
+    let config = get_inference_config(toc, collection_name).await?;
+    let vectors = inference::infer(&config, &operation.documents()).await?;
+    let points = operation.into_points_list(config.vector_name(), vectors);
+
+    do_upsert_points(
+        toc,
+        collection_name,
+        PointInsertOperations::PointsList(points),
+        None,
+        wait,
+        ordering,
+        shard_deactivation_timeout,
+        write_consistency,
+        timeout,
+        priority,
+    )
+    .await
+}
+
+/// Convert the document search into the regular search by the inferred vector
+pub async fn infer_search_request(
+    toc: &TableOfContent,
+    collection_name: &str,
+    request: SearchDocumentRequest,
+) -> Result<SearchRequest, StorageError> {
+    let config = get_inference_config(toc, collection_name).await?;
+    let vector = inference::infer(&config, std::slice::from_ref(&request.document))
+        .await?
+        .pop()
+        .ok_or_else(|| StorageError::service_error("No vector inferred for the document"))?;
+    Ok(request.into_search_request(config.vector_name(), vector))
+}
+
 pub async fn do_delete_points(
     toc: &TableOfContent,
     collection_name: &str,
     points: PointsSelector,
     shard_selection: Option<ShardId>,
-    wait: bool,
-    ordering: WriteOrdering,
+    wait: Option<bool>,
+    ordering: Option<WriteOrdering>,
+    shard_deactivation_timeout: Option<Duration>,
+    write_consistency: Option<WriteConsistency>,
+    timeout: Option<Duration>,
+    priority: Option<UpdatePriority>,
 ) -> Result<UpdateResult, StorageError> {
     let (point_operation, shard_key) = match points {
         PointsSelector::PointIdsSelector(PointIdsList { points, shard_key }) => {
@@ -201,6 +351,42 @@ pub async fn do_delete_points(
         collection_operation,
         wait,
         ordering,
+        shard_deactivation_timeout,
+        write_consistency,
+        timeout,
+        priority,
+        shard_selector,
+    )
+    .await
+}
+
+/// Restore soft-deleted points, which are not yet deleted permanently
+pub async fn do_undelete_points(
+    toc: &TableOfContent,
+    collection_name: &str,
+    points: PointIdsList,
+    shard_selection: Option<ShardId>,
+    wait: Option<bool>,
+    ordering: Option<WriteOrdering>,
+    shard_deactivation_timeout: Option<Duration>,
+    write_consistency: Option<WriteConsistency>,
+    timeout: Option<Duration>,
+    priority: Option<UpdatePriority>,
+) -> Result<UpdateResult, StorageError> {
+    let PointIdsList { points, shard_key } = points;
+    let collection_operation = undelete_operation(points);
+    let shard_selector = get_shard_selector_for_update(shard_selection, shard_key);
+
+    // Undelete removes the reserved soft delete marker, which clients can't do directly
+    toc.update_with_reserved_payload(
+        collection_name,
+        collection_operation,
+        wait,
+        ordering,
+        shard_deactivation_timeout,
+        write_consistency,
+        timeout,
+        priority,
         shard_selector,
     )
     .await
@@ -211,8 +397,12 @@ pub async fn do_update_vectors(
     collection_name: &str,
     operation: UpdateVectors,
     shard_selection: Option<ShardId>,
-    wait: bool,
-    ordering: WriteOrdering,
+    wait: Option<bool>,
+    ordering: Option<WriteOrdering>,
+    shard_deactivation_timeout: Option<Duration>,
+    write_consistency: Option<WriteConsistency>,
+    timeout: Option<Duration>,
+    priority: Option<UpdatePriority>,
 ) -> Result<UpdateResult, StorageError> {
     let UpdateVectors { points, shard_key } = operation;
 
@@ -227,6 +417,10 @@ pub async fn do_update_vectors(
         collection_operation,
         wait,
         ordering,
+        shard_deactivation_timeout,
+        write_consistency,
+        timeout,
+        priority,
         shard_selector,
     )
     .await
@@ -237,8 +431,12 @@ pub async fn do_delete_vectors(
     collection_name: &str,
     operation: DeleteVectors,
     shard_selection: Option<ShardId>,
-    wait: bool,
-    ordering: WriteOrdering,
+    wait: Option<bool>,
+    ordering: Option<WriteOrdering>,
+    shard_deactivation_timeout: Option<Duration>,
+    write_consistency: Option<WriteConsistency>,
+    timeout: Option<Duration>,
+    priority: Option<UpdatePriority>,
 ) -> Result<UpdateResult, StorageError> {
     let DeleteVectors {
         vector,
@@ -263,6 +461,10 @@ pub async fn do_delete_vectors(
                 collection_operation,
                 wait,
                 ordering,
+                shard_deactivation_timeout,
+                write_consistency,
+                timeout,
+                priority,
                 shard_selector.clone(),
             )
             .await?,
@@ -278,6 +480,10 @@ pub async fn do_delete_vectors(
                 collection_operation,
                 wait,
                 ordering,
+                shard_deactivation_timeout,
+                write_consistency,
+                timeout,
+                priority,
                 shard_selector,
             )
             .await?,
@@ -292,14 +498,19 @@ pub async fn do_set_payload(
     collection_name: &str,
     operation: SetPayload,
     shard_selection: Option<ShardId>,
-    wait: bool,
-    ordering: WriteOrdering,
+    wait: Option<bool>,
+    ordering: Option<WriteOrdering>,
+    shard_deactivation_timeout: Option<Duration>,
+    write_consistency: Option<WriteConsistency>,
+    timeout: Option<Duration>,
+    priority: Option<UpdatePriority>,
 ) -> Result<UpdateResult, StorageError> {
     let SetPayload {
         points,
         payload,
         filter,
         shard_key,
+        if_version,
     } = operation;
 
     let collection_operation =
@@ -307,6 +518,7 @@ pub async fn do_set_payload(
             payload,
             points,
             filter,
+            if_version,
         }));
 
     let shard_selector = get_shard_selector_for_update(shard_selection, shard_key);
@@ -316,6 +528,10 @@ pub async fn do_set_payload(
         collection_operation,
         wait,
         ordering,
+        shard_deactivation_timeout,
+        write_consistency,
+        timeout,
+        priority,
         shard_selector,
     )
     .await
@@ -326,14 +542,19 @@ pub async fn do_overwrite_payload(
     collection_name: &str,
     operation: SetPayload,
     shard_selection: Option<ShardId>,
-    wait: bool,
-    ordering: WriteOrdering,
+    wait: Option<bool>,
+    ordering: Option<WriteOrdering>,
+    shard_deactivation_timeout: Option<Duration>,
+    write_consistency: Option<WriteConsistency>,
+    timeout: Option<Duration>,
+    priority: Option<UpdatePriority>,
 ) -> Result<UpdateResult, StorageError> {
     let SetPayload {
         points,
         payload,
         filter,
         shard_key,
+        if_version,
     } = operation;
 
     let collection_operation =
@@ -341,6 +562,7 @@ pub async fn do_overwrite_payload(
             payload,
             points,
             filter,
+            if_version,
         }));
 
     let shard_selector = get_shard_selector_for_update(shard_selection, shard_key);
@@ -350,6 +572,10 @@ pub async fn do_overwrite_payload(
         collection_operation,
         wait,
         ordering,
+        shard_deactivation_timeout,
+        write_consistency,
+        timeout,
+        priority,
         shard_selector,
     )
     .await
@@ -360,8 +586,12 @@ pub async fn do_delete_payload(
     collection_name: &str,
     operation: DeletePayload,
     shard_selection: Option<ShardId>,
-    wait: bool,
-    ordering: WriteOrdering,
+    wait: Option<bool>,
+    ordering: Option<WriteOrdering>,
+    shard_deactivation_timeout: Option<Duration>,
+    write_consistency: Option<WriteConsistency>,
+    timeout: Option<Duration>,
+    priority: Option<UpdatePriority>,
 ) -> Result<UpdateResult, StorageError> {
     let DeletePayload {
         keys,
@@ -384,6 +614,10 @@ pub async fn do_delete_payload(
         collection_operation,
         wait,
         ordering,
+        shard_deactivation_timeout,
+        write_consistency,
+        timeout,
+        priority,
         shard_selector,
     )
     .await
@@ -394,8 +628,12 @@ pub async fn do_clear_payload(
     collection_name: &str,
     points: PointsSelector,
     shard_selection: Option<ShardId>,
-    wait: bool,
-    ordering: WriteOrdering,
+    wait: Option<bool>,
+    ordering: Option<WriteOrdering>,
+    shard_deactivation_timeout: Option<Duration>,
+    write_consistency: Option<WriteConsistency>,
+    timeout: Option<Duration>,
+    priority: Option<UpdatePriority>,
 ) -> Result<UpdateResult, StorageError> {
     let (point_operation, shard_key) = match points {
         PointsSelector::PointIdsSelector(PointIdsList { points, shard_key }) => {
@@ -415,6 +653,10 @@ pub async fn do_clear_payload(
         collection_operation,
         wait,
         ordering,
+        shard_deactivation_timeout,
+        write_consistency,
+        timeout,
+        priority,
         shard_selector,
     )
     .await
@@ -425,8 +667,12 @@ pub async fn do_batch_update_points(
     collection_name: &str,
     operations: Vec<UpdateOperation>,
     shard_selection: Option<ShardId>,
-    wait: bool,
-    ordering: WriteOrdering,
+    wait: Option<bool>,
+    ordering: Option<WriteOrdering>,
+    shard_deactivation_timeout: Option<Duration>,
+    write_consistency: Option<WriteConsistency>,
+    timeout: Option<Duration>,
+    priority: Option<UpdatePriority>,
 ) -> Result<Vec<UpdateResult>, StorageError> {
     let mut results = Vec::with_capacity(operations.len());
     for operation in operations {
@@ -439,6 +685,10 @@ pub async fn do_batch_update_points(
                     shard_selection,
                     wait,
                     ordering,
+                    shard_deactivation_timeout,
+                    write_consistency,
+                    timeout,
+                    priority,
                 )
                 .await
             }
@@ -450,6 +700,10 @@ pub async fn do_batch_update_points(
                     shard_selection,
                     wait,
                     ordering,
+                    shard_deactivation_timeout,
+                    write_consistency,
+                    timeout,
+                    priority,
                 )
                 .await
             }
@@ -461,6 +715,10 @@ pub async fn do_batch_update_points(
                     shard_selection,
                     wait,
                     ordering,
+                    shard_deactivation_timeout,
+                    write_consistency,
+                    timeout,
+                    priority,
                 )
                 .await
             }
@@ -472,6 +730,10 @@ pub async fn do_batch_update_points(
                     shard_selection,
                     wait,
                     ordering,
+                    shard_deactivation_timeout,
+                    write_consistency,
+                    timeout,
+                    priority,
                 )
                 .await
             }
@@ -483,6 +745,10 @@ pub async fn do_batch_update_points(
                     shard_selection,
                     wait,
                     ordering,
+                    shard_deactivation_timeout,
+                    write_consistency,
+                    timeout,
+                    priority,
                 )
                 .await
             }
@@ -494,6 +760,10 @@ pub async fn do_batch_update_points(
                     shard_selection,
                     wait,
                     ordering,
+                    shard_deactivation_timeout,
+                    write_consistency,
+                    timeout,
+                    priority,
                 )
                 .await
             }
@@ -505,6 +775,10 @@ pub async fn do_batch_update_points(
                     shard_selection,
                     wait,
                     ordering,
+                    shard_deactivation_timeout,
+                    write_consistency,
+                    timeout,
+                    priority,
                 )
                 .await
             }
@@ -516,6 +790,10 @@ pub async fn do_batch_update_points(
                     shard_selection,
                     wait,
                     ordering,
+                    shard_deactivation_timeout,
+                    write_consistency,
+                    timeout,
+                    priority,
                 )
                 .await
             }
@@ -525,14 +803,207 @@ pub async fn do_batch_update_points(
     Ok(results)
 }
 
+fn decompose_set_payload(set_payload: SetPayload) -> (Option<ShardKeySelector>, SetPayloadOp) {
+    let SetPayload {
+        points,
+        payload,
+        filter,
+        shard_key,
+        if_version,
+    } = set_payload;
+    let operation = SetPayloadOp {
+        payload,
+        points,
+        filter,
+        if_version,
+    };
+    (shard_key, operation)
+}
+
+/// Convert the operation into the operations of the collection, and the shard key it targets
+fn collection_operations(
+    operation: UpdateOperation,
+) -> Result<(Option<ShardKeySelector>, Vec<CollectionUpdateOperations>), StorageError> {
+    let converted = match operation {
+        UpdateOperation::Upsert(UpsertOperation { upsert }) => {
+            if upsert.deduplication().is_some() {
+                return Err(StorageError::bad_request(
+                    "Deduplication is not supported in all-or-nothing batches",
+                ));
+            }
+            if upsert.ttl().is_some() {
+                return Err(StorageError::bad_request(
+                    "TTL of points is not supported in all-or-nothing batches",
+                ));
+            }
+            let (shard_key, operation) = upsert.decompose();
+            (
+                shard_key,
+                vec![CollectionUpdateOperations::PointOperation(
+                    PointOperations::UpsertPoints(operation),
+                )],
+            )
+        }
+        UpdateOperation::Delete(DeleteOperation { delete }) => {
+            let (operation, shard_key) = match delete {
+                PointsSelector::PointIdsSelector(PointIdsList { points, shard_key }) => {
+                    (PointOperations::DeletePoints { ids: points }, shard_key)
+                }
+                PointsSelector::FilterSelector(FilterSelector { filter, shard_key }) => {
+                    (PointOperations::DeletePointsByFilter(filter), shard_key)
+                }
+            };
+            (
+                shard_key,
+                vec![CollectionUpdateOperations::PointOperation(operation)],
+            )
+        }
+        UpdateOperation::SetPayload(SetPayloadOperation { set_payload }) => {
+            let (shard_key, operation) = decompose_set_payload(set_payload);
+            (
+                shard_key,
+                vec![CollectionUpdateOperations::PayloadOperation(
+                    PayloadOps::SetPayload(operation),
+                )],
+            )
+        }
+        UpdateOperation::OverwritePayload(OverwritePayloadOperation { overwrite_payload }) => {
+            let (shard_key, operation) = decompose_set_payload(overwrite_payload);
+            (
+                shard_key,
+                vec![CollectionUpdateOperations::PayloadOperation(
+                    PayloadOps::OverwritePayload(operation),
+                )],
+            )
+        }
+        UpdateOperation::DeletePayload(DeletePayloadOperation { delete_payload }) => {
+            let DeletePayload {
+                keys,
+                points,
+                filter,
+                shard_key,
+            } = delete_payload;
+            (
+                shard_key,
+                vec![CollectionUpdateOperations::PayloadOperation(
+                    PayloadOps::DeletePayload(DeletePayloadOp {
+                        keys,
+                        points,
+                        filter,
+                    }),
+                )],
+            )
+        }
+        UpdateOperation::ClearPayload(ClearPayloadOperation { clear_payload }) => {
+            let (operation, shard_key) = match clear_payload {
+                PointsSelector::PointIdsSelector(PointIdsList { points, shard_key }) => {
+                    (PayloadOps::ClearPayload { points }, shard_key)
+                }
+                PointsSelector::FilterSelector(FilterSelector { filter, shard_key }) => {
+                    (PayloadOps::ClearPayloadByFilter(filter), shard_key)
+                }
+            };
+            (
+                shard_key,
+                vec![CollectionUpdateOperations::PayloadOperation(operation)],
+            )
+        }
+        UpdateOperation::UpdateVectors(UpdateVectorsOperation { update_vectors }) => {
+            let UpdateVectors { points, shard_key } = update_vectors;
+            (
+                shard_key,
+                vec![CollectionUpdateOperations::VectorOperation(
+                    VectorOperations::UpdateVectors(UpdateVectorsOp { points }),
+                )],
+            )
+        }
+        UpdateOperation::DeleteVectors(DeleteVectorsOperation { delete_vectors }) => {
+            let DeleteVectors {
+                vector,
+                filter,
+                points,
+                shard_key,
+            } = delete_vectors;
+            let vector_names: Vec<_> = vector.into_iter().collect();
+            let mut operations = Vec::new();
+            if let Some(filter) = filter {
+                operations.push(CollectionUpdateOperations::VectorOperation(
+                    VectorOperations::DeleteVectorsByFilter(filter, vector_names.clone()),
+                ));
+            }
+            if let Some(points) = points {
+                operations.push(CollectionUpdateOperations::VectorOperation(
+                    VectorOperations::DeleteVectors(points.into(), vector_names),
+                ));
+            }
+            if operations.is_empty() {
+                return Err(StorageError::bad_request("No filter or points provided"));
+            }
+            (shard_key, operations)
+        }
+    };
+    Ok(converted)
+}
+
+/// Apply all operations as a single update of the collection
+///
+/// Each affected shard applies its part of the operations in order, under a single operation
+/// number. If an operation fails, the shard rolls back changes of the previous ones, while other
+/// shards apply their parts independently.
+pub async fn do_all_or_nothing_batch_update_points(
+    toc: &TableOfContent,
+    collection_name: &str,
+    operations: Vec<UpdateOperation>,
+    shard_selection: Option<ShardId>,
+    wait: Option<bool>,
+    ordering: Option<WriteOrdering>,
+    shard_deactivation_timeout: Option<Duration>,
+    write_consistency: Option<WriteConsistency>,
+    timeout: Option<Duration>,
+    priority: Option<UpdatePriority>,
+) -> Result<UpdateResult, StorageError> {
+    let mut batch_shard_key = None;
+    let mut batch = Vec::with_capacity(operations.len());
+    for (idx, operation) in operations.into_iter().enumerate() {
+        let (shard_key, operations) = collection_operations(operation)?;
+        if idx == 0 {
+            batch_shard_key = shard_key;
+        } else if shard_key != batch_shard_key {
+            return Err(StorageError::bad_request(
+                "All operations of an all-or-nothing batch must use the same shard key",
+            ));
+        }
+        batch.extend(operations);
+    }
+
+    let shard_selector = get_shard_selector_for_update(shard_selection, batch_shard_key);
+
+    toc.update(
+        collection_name,
+        CollectionUpdateOperations::Batch(batch),
+        wait,
+        ordering,
+        shard_deactivation_timeout,
+        write_consistency,
+        timeout,
+        priority,
+        shard_selector,
+    )
+    .await
+}
+
 pub async fn do_create_index_internal(
     toc: &TableOfContent,
     collection_name: &str,
     field_name: PayloadKeyType,
     field_schema: Option<PayloadFieldSchema>,
     shard_selection: Option<ShardId>,
-    wait: bool,
-    ordering: WriteOrdering,
+    wait: Option<bool>,
+    ordering: Option<WriteOrdering>,
+    shard_deactivation_timeout: Option<Duration>,
+    write_consistency: Option<WriteConsistency>,
+    timeout: Option<Duration>,
+    priority: Option<UpdatePriority>,
 ) -> Result<UpdateResult, StorageError> {
     let collection_operation = CollectionUpdateOperations::FieldIndexOperation(
         FieldIndexOperations::CreateIndex(CreateIndex {
@@ -552,6 +1023,10 @@ pub async fn do_create_index_internal(
         collection_operation,
         wait,
         ordering,
+        shard_deactivation_timeout,
+        write_consistency,
+        timeout,
+        priority,
         shard_selector,
     )
     .await
@@ -562,8 +1037,12 @@ pub async fn do_create_index(
     collection_name: &str,
     operation: CreateFieldIndex,
     shard_selection: Option<ShardId>,
-    wait: bool,
-    ordering: WriteOrdering,
+    wait: Option<bool>,
+    ordering: Option<WriteOrdering>,
+    shard_deactivation_timeout: Option<Duration>,
+    write_consistency: Option<WriteConsistency>,
+    timeout: Option<Duration>,
+    priority: Option<UpdatePriority>,
 ) -> Result<UpdateResult, StorageError> {
     let Some(field_schema) = operation.field_schema else {
         return Err(StorageError::bad_request(
@@ -571,6 +1050,12 @@ pub async fn do_create_index(
         ));
     };
 
+    if is_reserved_payload_key(&operation.field_name) {
+        return Err(StorageError::bad_request(format!(
+            "Payload fields starting with `{RESERVED_PAYLOAD_KEY_PREFIX}` are reserved",
+        )));
+    }
+
     let consensus_op = CollectionMetaOperations::CreatePayloadIndex(CreatePayloadIndex {
         collection_name: collection_name.to_string(),
         field_name: operation.field_name.clone(),
@@ -596,6 +1081,10 @@ pub async fn do_create_index(
         shard_selection,
         wait,
         ordering,
+        shard_deactivation_timeout,
+        write_consistency,
+        timeout,
+        priority,
     )
     .await
 }
@@ -605,8 +1094,12 @@ pub async fn do_delete_index_internal(
     collection_name: &str,
     index_name: String,
     shard_selection: Option<ShardId>,
-    wait: bool,
-    ordering: WriteOrdering,
+    wait: Option<bool>,
+    ordering: Option<WriteOrdering>,
+    shard_deactivation_timeout: Option<Duration>,
+    write_consistency: Option<WriteConsistency>,
+    timeout: Option<Duration>,
+    priority: Option<UpdatePriority>,
 ) -> Result<UpdateResult, StorageError> {
     let collection_operation = CollectionUpdateOperations::FieldIndexOperation(
         FieldIndexOperations::DeleteIndex(index_name),
@@ -623,6 +1116,10 @@ pub async fn do_delete_index_internal(
         collection_operation,
         wait,
         ordering,
+        shard_deactivation_timeout,
+        write_consistency,
+        timeout,
+        priority,
         shard_selector,
     )
     .await
@@ -633,9 +1130,19 @@ pub async fn do_delete_index(
     collection_name: &str,
     index_name: String,
     shard_selection: Option<ShardId>,
-    wait: bool,
-    ordering: WriteOrdering,
+    wait: Option<bool>,
+    ordering: Option<WriteOrdering>,
+    shard_deactivation_timeout: Option<Duration>,
+    write_consistency: Option<WriteConsistency>,
+    timeout: Option<Duration>,
+    priority: Option<UpdatePriority>,
 ) -> Result<UpdateResult, StorageError> {
+    if is_reserved_payload_key(&index_name) {
+        return Err(StorageError::bad_request(format!(
+            "Payload fields starting with `{RESERVED_PAYLOAD_KEY_PREFIX}` are reserved",
+        )));
+    }
+
     let consensus_op = CollectionMetaOperations::DropPayloadIndex(DropPayloadIndex {
         collection_name: collection_name.to_string(),
         field_name: index_name.clone(),
@@ -655,6 +1162,10 @@ pub async fn do_delete_index(
         shard_selection,
         wait,
         ordering,
+        shard_deactivation_timeout,
+        write_consistency,
+        timeout,
+        priority,
     )
     .await
 }
@@ -664,8 +1175,12 @@ pub async fn do_core_search_points(
     collection_name: &str,
     request: CoreSearchRequest,
     read_consistency: Option<ReadConsistency>,
+    consistency_token: Option<&ConsistencyToken>,
     shard_selection: ShardSelectorInternal,
     timeout: Option<Duration>,
+    access_tags: Option<&AccessTags>,
+    read_priority: ReadPriority,
+    stale_reads: Option<&StaleReads>,
 ) -> Result<Vec<ScoredPoint>, StorageError> {
     let batch_res = do_core_search_batch_points(
         toc,
@@ -674,8 +1189,12 @@ pub async fn do_core_search_points(
             searches: vec![request],
         },
         read_consistency,
+        consistency_token,
         shard_selection,
         timeout,
+        access_tags,
+        read_priority,
+        stale_reads,
     )
     .await?;
     batch_res
@@ -684,12 +1203,149 @@ pub async fn do_core_search_points(
         .ok_or_else(|| StorageError::service_error("Empty search result"))
 }
 
+/// Same as [`do_core_search_points`], but skips shards which failed and reports them in the result
+pub async fn do_core_search_points_partial(
+    toc: &TableOfContent,
+    collection_name: &str,
+    request: CoreSearchRequest,
+    read_consistency: Option<ReadConsistency>,
+    consistency_token: Option<&ConsistencyToken>,
+    shard_selection: ShardSelectorInternal,
+    timeout: Option<Duration>,
+    access_tags: Option<&AccessTags>,
+    read_priority: ReadPriority,
+    stale_reads: Option<&StaleReads>,
+) -> Result<PartialSearchResult, StorageError> {
+    let batch_res = toc
+        .core_search_batch_partial(
+            collection_name,
+            CoreSearchRequestBatch {
+                searches: vec![request],
+            },
+            read_consistency,
+            consistency_token,
+            shard_selection,
+            timeout,
+            access_tags,
+            read_priority,
+            stale_reads,
+        )
+        .await?;
+    let points = batch_res
+        .results
+        .into_iter()
+        .next()
+        .ok_or_else(|| StorageError::service_error("Empty search result"))?;
+    Ok(PartialSearchResult {
+        points,
+        failed_shards: batch_res.failed_shards,
+    })
+}
+
+pub async fn do_search_page_points(
+    toc: &TableOfContent,
+    collection_name: &str,
+    request: CoreSearchRequest,
+    cursor: Option<&str>,
+    read_consistency: Option<ReadConsistency>,
+    consistency_token: Option<&ConsistencyToken>,
+    shard_selection: ShardSelectorInternal,
+    timeout: Option<Duration>,
+    access_tags: Option<&AccessTags>,
+    read_priority: ReadPriority,
+    stale_reads: Option<&StaleReads>,
+) -> Result<SearchPageResult, StorageError> {
+    toc.search_page(
+        collection_name,
+        request,
+        cursor,
+        read_consistency,
+        consistency_token,
+        shard_selection,
+        timeout,
+        access_tags,
+        read_priority,
+        stale_reads,
+    )
+    .await
+}
+
+pub async fn do_hybrid_search_points(
+    toc: &TableOfContent,
+    collection_name: &str,
+    request: HybridSearchRequest,
+    read_consistency: Option<ReadConsistency>,
+    consistency_token: Option<&ConsistencyToken>,
+    shard_selection: ShardSelectorInternal,
+    timeout: Option<Duration>,
+    read_priority: ReadPriority,
+    stale_reads: Option<&StaleReads>,
+) -> Result<Vec<ScoredPoint>, StorageError> {
+    toc.hybrid_search(
+        collection_name,
+        request,
+        read_consistency,
+        consistency_token,
+        shard_selection,
+        timeout,
+        read_priority,
+        stale_reads,
+    )
+    .await
+}
+
+pub async fn do_search_matrix_points(
+    toc: &TableOfContent,
+    collection_name: &str,
+    request: SearchMatrixRequest,
+    read_consistency: Option<ReadConsistency>,
+    consistency_token: Option<&ConsistencyToken>,
+    shard_selection: ShardSelectorInternal,
+    timeout: Option<Duration>,
+    read_priority: ReadPriority,
+    stale_reads: Option<&StaleReads>,
+) -> Result<Vec<SearchMatrixRow>, StorageError> {
+    toc.search_matrix(
+        collection_name,
+        request,
+        read_consistency,
+        consistency_token,
+        shard_selection,
+        timeout,
+        read_priority,
+        stale_reads,
+    )
+    .await
+}
+
+pub async fn do_multi_collection_search(
+    toc: &TableOfContent,
+    request: MultiCollectionSearchRequest,
+    read_consistency: Option<ReadConsistency>,
+    timeout: Option<Duration>,
+    read_priority: ReadPriority,
+    stale_reads: Option<&StaleReads>,
+) -> Result<Vec<Vec<MultiCollectionScoredPoint>>, StorageError> {
+    toc.multi_collection_search(
+        request,
+        read_consistency,
+        timeout,
+        read_priority,
+        stale_reads,
+    )
+    .await
+}
+
 pub async fn do_search_batch_points(
     toc: &TableOfContent,
     collection_name: &str,
     requests: Vec<(CoreSearchRequest, ShardSelectorInternal)>,
     read_consistency: Option<ReadConsistency>,
+    consistency_token: Option<&ConsistencyToken>,
     timeout: Option<Duration>,
+    access_tags: Option<&AccessTags>,
+    read_priority: ReadPriority,
+    stale_reads: Option<&StaleReads>,
 ) -> Result<Vec<Vec<ScoredPoint>>, StorageError> {
     let requests = batch_requests::<
         (CoreSearchRequest, ShardSelectorInternal),
@@ -716,8 +1372,12 @@ pub async fn do_search_batch_points(
                 collection_name,
                 core_batch,
                 read_consistency,
+                consistency_token,
                 shard_selector,
                 timeout,
+                access_tags,
+                read_priority,
+                stale_reads,
             );
             res.push(req);
             Ok(())
@@ -729,20 +1389,95 @@ pub async fn do_search_batch_points(
     Ok(flatten_results)
 }
 
+/// Same as [`do_search_batch_points`], but skips shards which failed and reports them in the result
+pub async fn do_search_batch_points_partial(
+    toc: &TableOfContent,
+    collection_name: &str,
+    requests: Vec<(CoreSearchRequest, ShardSelectorInternal)>,
+    read_consistency: Option<ReadConsistency>,
+    consistency_token: Option<&ConsistencyToken>,
+    timeout: Option<Duration>,
+    access_tags: Option<&AccessTags>,
+    read_priority: ReadPriority,
+    stale_reads: Option<&StaleReads>,
+) -> Result<PartialSearchBatchResult, StorageError> {
+    let requests = batch_requests::<
+        (CoreSearchRequest, ShardSelectorInternal),
+        ShardSelectorInternal,
+        Vec<CoreSearchRequest>,
+        Vec<_>,
+    >(
+        requests,
+        |(_, shard_selector)| shard_selector,
+        |(request, _), core_reqs| {
+            core_reqs.push(request);
+            Ok(())
+        },
+        |shard_selector, core_requests, res| {
+            if core_requests.is_empty() {
+                return Ok(());
+            }
+
+            let core_batch = CoreSearchRequestBatch {
+                searches: core_requests,
+            };
+
+            let req = toc.core_search_batch_partial(
+                collection_name,
+                core_batch,
+                read_consistency,
+                consistency_token,
+                shard_selector,
+                timeout,
+                access_tags,
+                read_priority,
+                stale_reads,
+            );
+            res.push(req);
+            Ok(())
+        },
+    )?;
+
+    let results = futures::future::try_join_all(requests).await?;
+    let mut batch_result = PartialSearchBatchResult::default();
+    for result in results {
+        batch_result.results.extend(result.results);
+        // Same shard might be queried by several sub-batches, report it once
+        for failure in result.failed_shards {
+            if !batch_result
+                .failed_shards
+                .iter()
+                .any(|reported| reported.shard_id == failure.shard_id)
+            {
+                batch_result.failed_shards.push(failure);
+            }
+        }
+    }
+    Ok(batch_result)
+}
+
 pub async fn do_core_search_batch_points(
     toc: &TableOfContent,
     collection_name: &str,
     request: CoreSearchRequestBatch,
     read_consistency: Option<ReadConsistency>,
+    consistency_token: Option<&ConsistencyToken>,
     shard_selection: ShardSelectorInternal,
     timeout: Option<Duration>,
+    access_tags: Option<&AccessTags>,
+    read_priority: ReadPriority,
+    stale_reads: Option<&StaleReads>,
 ) -> Result<Vec<Vec<ScoredPoint>>, StorageError> {
     toc.core_search_batch(
         collection_name,
         request,
         read_consistency,
+        consistency_token,
         shard_selection,
         timeout,
+        access_tags,
+        read_priority,
+        stale_reads,
     )
     .await
 }
@@ -754,6 +1489,9 @@ pub async fn do_search_point_groups(
     read_consistency: Option<ReadConsistency>,
     shard_selection: ShardSelectorInternal,
     timeout: Option<Duration>,
+    access_tags: Option<&AccessTags>,
+    read_priority: ReadPriority,
+    stale_reads: Option<&StaleReads>,
 ) -> Result<GroupsResult, StorageError> {
     toc.group(
         collection_name,
@@ -761,6 +1499,9 @@ pub async fn do_search_point_groups(
         read_consistency,
         shard_selection,
         timeout,
+        access_tags,
+        read_priority,
+        stale_reads,
     )
     .await
 }
@@ -772,6 +1513,9 @@ pub async fn do_recommend_point_groups(
     read_consistency: Option<ReadConsistency>,
     shard_selection: ShardSelectorInternal,
     timeout: Option<Duration>,
+    access_tags: Option<&AccessTags>,
+    read_priority: ReadPriority,
+    stale_reads: Option<&StaleReads>,
 ) -> Result<GroupsResult, StorageError> {
     toc.group(
         collection_name,
@@ -779,6 +1523,9 @@ pub async fn do_recommend_point_groups(
         read_consistency,
         shard_selection,
         timeout,
+        access_tags,
+        read_priority,
+        stale_reads,
     )
     .await
 }
@@ -790,6 +1537,9 @@ pub async fn do_discover_points(
     read_consistency: Option<ReadConsistency>,
     shard_selector: ShardSelectorInternal,
     timeout: Option<Duration>,
+    access_tags: Option<&AccessTags>,
+    read_priority: ReadPriority,
+    stale_reads: Option<&StaleReads>,
 ) -> Result<Vec<ScoredPoint>, StorageError> {
     toc.discover(
         collection_name,
@@ -797,6 +1547,9 @@ pub async fn do_discover_points(
         read_consistency,
         shard_selector,
         timeout,
+        access_tags,
+        read_priority,
+        stale_reads,
     )
     .await
 }
@@ -807,6 +1560,9 @@ pub async fn do_discover_batch_points(
     request: DiscoverRequestBatch,
     read_consistency: Option<ReadConsistency>,
     timeout: Option<Duration>,
+    access_tags: Option<&AccessTags>,
+    read_priority: ReadPriority,
+    stale_reads: Option<&StaleReads>,
 ) -> Result<Vec<Vec<ScoredPoint>>, StorageError> {
     let requests = request
         .searches
@@ -821,8 +1577,16 @@ pub async fn do_discover_batch_points(
         })
         .collect();
 
-    toc.discover_batch(collection_name, requests, read_consistency, timeout)
-        .await
+    toc.discover_batch(
+        collection_name,
+        requests,
+        read_consistency,
+        timeout,
+        access_tags,
+        read_priority,
+        stale_reads,
+    )
+    .await
 }
 
 pub async fn do_count_points(
@@ -831,9 +1595,20 @@ pub async fn do_count_points(
     request: CountRequestInternal,
     read_consistency: Option<ReadConsistency>,
     shard_selection: ShardSelectorInternal,
+    access_tags: Option<&AccessTags>,
+    read_priority: ReadPriority,
+    stale_reads: Option<&StaleReads>,
 ) -> Result<CountResult, StorageError> {
-    toc.count(collection_name, request, read_consistency, shard_selection)
-        .await
+    toc.count(
+        collection_name,
+        request,
+        read_consistency,
+        shard_selection,
+        access_tags,
+        read_priority,
+        stale_reads,
+    )
+    .await
 }
 
 pub async fn do_get_points(
@@ -842,9 +1617,20 @@ pub async fn do_get_points(
     request: PointRequestInternal,
     read_consistency: Option<ReadConsistency>,
     shard_selection: ShardSelectorInternal,
+    access_tags: Option<&AccessTags>,
+    read_priority: ReadPriority,
+    stale_reads: Option<&StaleReads>,
 ) -> Result<Vec<Record>, StorageError> {
-    toc.retrieve(collection_name, request, read_consistency, shard_selection)
-        .await
+    toc.retrieve(
+        collection_name,
+        request,
+        read_consistency,
+        shard_selection,
+        access_tags,
+        read_priority,
+        stale_reads,
+    )
+    .await
 }
 
 pub async fn do_scroll_points(
@@ -853,7 +1639,18 @@ pub async fn do_scroll_points(
     request: ScrollRequestInternal,
     read_consistency: Option<ReadConsistency>,
     shard_selection: ShardSelectorInternal,
+    access_tags: Option<&AccessTags>,
+    read_priority: ReadPriority,
+    stale_reads: Option<&StaleReads>,
 ) -> Result<ScrollResult, StorageError> {
-    toc.scroll(collection_name, request, read_consistency, shard_selection)
-        .await
+    toc.scroll(
+        collection_name,
+        request,
+        read_consistency,
+        shard_selection,
+        access_tags,
+        read_priority,
+        stale_reads,
+    )
+    .await
 }
@@ -0,0 +1,74 @@
+use std::time::Duration;
+
+use collection::operations::running_operations::RunningOperation;
+use storage::content_manager::collection_meta_ops::CollectionMetaOperations;
+use storage::content_manager::collection_meta_ops::ShardTransferOperations::Abort;
+use storage::content_manager::errors::StorageError;
+use storage::content_manager::toc::TableOfContent;
+use storage::dispatcher::Dispatcher;
+
+/// Long running operations of all collections on this node
+pub async fn do_list_operations(
+    toc: &TableOfContent,
+) -> Result<Vec<RunningOperation>, StorageError> {
+    let mut operations = Vec::new();
+    for collection_name in toc.all_collections().await {
+        // Collection may be deleted in the meantime
+        let Ok(collection) = toc.get_collection(&collection_name).await else {
+            continue;
+        };
+        operations.extend(collection.running_operations().await);
+    }
+    operations.sort_by_key(|operation| operation.id);
+    Ok(operations)
+}
+
+pub async fn do_get_operation(
+    toc: &TableOfContent,
+    operation_id: u64,
+) -> Result<RunningOperation, StorageError> {
+    do_list_operations(toc)
+        .await?
+        .into_iter()
+        .find(|operation| operation.id == operation_id)
+        .ok_or_else(|| StorageError::NotFound {
+            description: format!("Operation {operation_id} is not running"),
+        })
+}
+
+/// Cancel the running operation, only shard transfers could be cancelled for now
+pub async fn do_cancel_operation(
+    toc: &TableOfContent,
+    dispatcher: &Dispatcher,
+    operation_id: u64,
+    wait_timeout: Option<Duration>,
+) -> Result<bool, StorageError> {
+    let operation = do_get_operation(toc, operation_id).await?;
+    if !operation.cancellable {
+        return Err(StorageError::bad_input(format!(
+            "Operation {operation_id} can't be cancelled",
+        )));
+    }
+
+    let transfer = toc
+        .get_collection(&operation.collection_name)
+        .await?
+        .running_transfer(operation_id)
+        .await
+        .ok_or_else(|| StorageError::NotFound {
+            description: format!("Operation {operation_id} is not running"),
+        })?;
+
+    dispatcher
+        .submit_collection_meta_op(
+            CollectionMetaOperations::TransferShard(
+                operation.collection_name,
+                Abort {
+                    transfer: transfer.key(),
+                    reason: "user request".to_string(),
+                },
+            ),
+            wait_timeout,
+        )
+        .await
+}
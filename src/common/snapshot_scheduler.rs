@@ -0,0 +1,211 @@
+//! Periodic snapshots of all collections
+//!
+//! Snapshots are created through the same path as the snapshots requested by the API, so they
+//! are consistent with concurrent optimizations. They are created with low priority, and only
+//! one collection is snapshotted at a time, to limit the impact on the serving traffic.
+//!
+//! Only the scheduled snapshots are pruned, the ones created by users are left alone.
+
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use std::time::Duration;
+
+use collection::collection::snapshots::SCHEDULED_SNAPSHOT_SUFFIX;
+use collection::operations::snapshot_ops::SnapshotDescription;
+use storage::content_manager::errors::StorageError;
+use storage::content_manager::toc::TableOfContent;
+use storage::types::SnapshotScheduleConfig;
+use tokio::time::MissedTickBehavior;
+
+pub struct SnapshotScheduler {
+    toc: Arc<TableOfContent>,
+    max_snapshots_to_keep: Option<usize>,
+}
+
+impl SnapshotScheduler {
+    /// Create snapshots of all collections every `snapshot_interval_sec`, does nothing if the
+    /// interval is not set
+    pub async fn run(toc: Arc<TableOfContent>, config: SnapshotScheduleConfig) {
+        let Some(interval_sec) = config.snapshot_interval_sec else {
+            return;
+        };
+        let scheduler = Self {
+            toc,
+            max_snapshots_to_keep: config.max_snapshots_to_keep,
+        };
+
+        let mut interval = tokio::time::interval(Duration::from_secs(interval_sec));
+        // If snapshots take longer than the interval, don't start the next round right away
+        interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        // First tick completes immediately, but there is no reason to snapshot right at the start
+        interval.tick().await;
+
+        loop {
+            interval.tick().await;
+            scheduler.snapshot_all().await;
+        }
+    }
+
+    async fn snapshot_all(&self) {
+        for collection_name in self.toc.all_collections().await {
+            if let Err(err) = self.snapshot_collection(&collection_name).await {
+                log::error!("Scheduled snapshot of collection {collection_name} failed: {err}");
+            }
+        }
+    }
+
+    async fn snapshot_collection(&self, collection_name: &str) -> Result<(), StorageError> {
+        let snapshot = self.toc.create_scheduled_snapshot(collection_name).await?;
+        log::info!(
+            "Scheduled snapshot {} of collection {collection_name} is created",
+            snapshot.name,
+        );
+
+        let Some(max_snapshots_to_keep) = self.max_snapshots_to_keep else {
+            return Ok(());
+        };
+        let collection = self.toc.get_collection(collection_name).await?;
+        let snapshots = collection.list_snapshots().await?;
+        let snapshot_bases = collection.incremental_snapshot_bases().await?;
+        for snapshot_name in outdated_snapshots(snapshots, max_snapshots_to_keep, &snapshot_bases) {
+            collection.delete_snapshot(&snapshot_name).await?;
+            log::info!(
+                "Outdated snapshot {snapshot_name} of collection {collection_name} is deleted"
+            );
+        }
+
+        Ok(())
+    }
+}
+
+/// Names of the oldest scheduled snapshots, which exceed `max_snapshots_to_keep`
+///
+/// Snapshots, created by users, are neither counted nor pruned. Outdated snapshots, which are
+/// bases of the kept incremental snapshots, directly or through other bases, are kept as well.
+/// `snapshot_bases` maps names of incremental snapshots to the names of their bases.
+fn outdated_snapshots(
+    snapshots: Vec<SnapshotDescription>,
+    max_snapshots_to_keep: usize,
+    snapshot_bases: &HashMap<String, String>,
+) -> Vec<String> {
+    let (mut scheduled, other): (Vec<_>, Vec<_>) = snapshots
+        .into_iter()
+        .partition(|snapshot| snapshot.name.ends_with(SCHEDULED_SNAPSHOT_SUFFIX));
+    if scheduled.len() <= max_snapshots_to_keep {
+        return Vec::new();
+    }
+    // Snapshots without creation time are considered the oldest
+    scheduled.sort_by_key(|snapshot| snapshot.creation_time);
+    let kept = scheduled.split_off(scheduled.len() - max_snapshots_to_keep);
+
+    let mut referenced = HashSet::new();
+    for snapshot in kept.iter().chain(&other) {
+        let mut name = &snapshot.name;
+        while let Some(base) = snapshot_bases.get(name) {
+            if !referenced.insert(base) {
+                break;
+            }
+            name = base;
+        }
+    }
+
+    scheduled
+        .into_iter()
+        .map(|snapshot| snapshot.name)
+        .filter(|name| !referenced.contains(name))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDateTime;
+
+    use super::*;
+
+    fn snapshot(name: &str, timestamp: Option<i64>) -> SnapshotDescription {
+        SnapshotDescription {
+            name: name.to_string(),
+            creation_time: timestamp
+                .map(|timestamp| NaiveDateTime::from_timestamp_opt(timestamp, 0).unwrap()),
+            size: 0,
+        }
+    }
+
+    fn scheduled(name: &str, timestamp: Option<i64>) -> SnapshotDescription {
+        snapshot(&format!("{name}{SCHEDULED_SNAPSHOT_SUFFIX}"), timestamp)
+    }
+
+    fn names(names: &[&str]) -> Vec<String> {
+        names
+            .iter()
+            .map(|name| format!("{name}{SCHEDULED_SNAPSHOT_SUFFIX}"))
+            .collect()
+    }
+
+    #[test]
+    fn test_outdated_snapshots() {
+        let snapshots = vec![
+            scheduled("c", Some(300)),
+            scheduled("a", Some(100)),
+            scheduled("unknown", None),
+            scheduled("b", Some(200)),
+        ];
+        let no_bases = HashMap::new();
+
+        assert_eq!(
+            outdated_snapshots(snapshots.clone(), 2, &no_bases),
+            names(&["unknown", "a"]),
+        );
+        assert!(outdated_snapshots(snapshots.clone(), 4, &no_bases).is_empty());
+        assert_eq!(
+            outdated_snapshots(snapshots, 3, &no_bases),
+            names(&["unknown"]),
+        );
+    }
+
+    #[test]
+    fn test_manual_snapshots_are_not_pruned() {
+        let snapshots = vec![
+            snapshot("manual-old.snapshot", Some(50)),
+            scheduled("a", Some(100)),
+            snapshot("manual-new.snapshot", Some(150)),
+            scheduled("b", Some(200)),
+            scheduled("c", Some(300)),
+        ];
+
+        // Only scheduled snapshots are counted and pruned
+        assert_eq!(
+            outdated_snapshots(snapshots.clone(), 2, &HashMap::new()),
+            names(&["a"]),
+        );
+        assert!(outdated_snapshots(snapshots, 3, &HashMap::new()).is_empty());
+    }
+
+    #[test]
+    fn test_bases_of_incremental_snapshots_are_not_pruned() {
+        let snapshots = vec![
+            scheduled("a", Some(100)),
+            scheduled("b", Some(200)),
+            scheduled("c", Some(300)),
+            snapshot("b-1-incremental.snapshot", Some(250)),
+            snapshot("b-2-incremental.snapshot", Some(260)),
+            scheduled("d", Some(400)),
+        ];
+        // Second increment is made on top of the first one, which is made on top of `b`
+        let snapshot_bases = HashMap::from([
+            (
+                "b-1-incremental.snapshot".to_string(),
+                format!("b{SCHEDULED_SNAPSHOT_SUFFIX}"),
+            ),
+            (
+                "b-2-incremental.snapshot".to_string(),
+                "b-1-incremental.snapshot".to_string(),
+            ),
+        ]);
+
+        assert_eq!(
+            outdated_snapshots(snapshots, 1, &snapshot_bases),
+            names(&["a", "c"]),
+        );
+    }
+}
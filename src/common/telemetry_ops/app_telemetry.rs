@@ -1,6 +1,7 @@
 use std::path::Path;
 
 use chrono::{DateTime, SubsecRound, Utc};
+use collection::operations::feature_flags::FeatureFlags;
 use schemars::JsonSchema;
 use segment::common::anonymize::Anonymize;
 use serde::{Deserialize, Serialize};
@@ -25,6 +26,8 @@ pub struct AppFeaturesTelemetry {
     pub web_feature: bool,
     pub service_debug_feature: bool,
     pub recovery_mode: bool,
+    /// Flags of the experimental features, default for all collections of this node
+    pub feature_flags: FeatureFlags,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, JsonSchema)]
@@ -66,6 +69,7 @@ impl AppBuildTelemetry {
                     web_feature: cfg!(feature = "web"),
                     service_debug_feature: cfg!(feature = "service_debug"),
                     recovery_mode: settings.storage.recovery_mode.is_some(),
+                    feature_flags: FeatureFlags::resolve(None, &settings.storage.feature_flags),
                 })
             } else {
                 None
@@ -134,6 +138,7 @@ impl Anonymize for AppFeaturesTelemetry {
             web_feature: self.web_feature,
             service_debug_feature: self.service_debug_feature,
             recovery_mode: self.recovery_mode,
+            feature_flags: self.feature_flags,
         }
     }
 }
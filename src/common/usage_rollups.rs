@@ -0,0 +1,54 @@
+//! Periodic rollups of the usage of the tenants of all collections
+//!
+//! Each rollup closes the current metering period of the collection, and measures the storage of
+//! its tenants at the end of it. Collections are rolled up at the same time, so their periods are
+//! aligned and could be summed up by the billing.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use storage::content_manager::toc::TableOfContent;
+use storage::types::UsageMeteringConfig;
+use tokio::time::MissedTickBehavior;
+
+pub struct UsageRollups {
+    toc: Arc<TableOfContent>,
+    rollups_to_keep: usize,
+}
+
+impl UsageRollups {
+    /// Roll up usage of all collections every `rollup_interval_sec`, does nothing if the interval
+    /// is not set
+    pub async fn run(toc: Arc<TableOfContent>, config: UsageMeteringConfig) {
+        let Some(interval_sec) = config.rollup_interval_sec else {
+            return;
+        };
+        let rollups = Self {
+            toc,
+            rollups_to_keep: config.rollups_to_keep,
+        };
+
+        let mut interval = tokio::time::interval(Duration::from_secs(interval_sec));
+        // Periods are kept aligned to the start of the service
+        interval.set_missed_tick_behavior(MissedTickBehavior::Skip);
+        // First tick completes immediately, the first period has just started
+        interval.tick().await;
+
+        loop {
+            interval.tick().await;
+            rollups.rollup_all().await;
+        }
+    }
+
+    async fn rollup_all(&self) {
+        for collection_name in self.toc.all_collections().await {
+            // Collection might be deleted concurrently
+            let Ok(collection) = self.toc.get_collection(&collection_name).await else {
+                continue;
+            };
+            if let Err(err) = collection.rollup_usage(self.rollups_to_keep).await {
+                log::error!("Usage rollup of collection {collection_name} failed: {err}");
+            }
+        }
+    }
+}
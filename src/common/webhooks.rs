@@ -0,0 +1,160 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use collection::events::CollectionEvent;
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use tokio::sync::broadcast;
+use tokio::sync::broadcast::error::RecvError;
+
+use crate::settings::WebhookConfig;
+
+/// Number of events which can be buffered before slow webhooks start to lose them
+pub const WEBHOOK_EVENTS_QUEUE_SIZE: usize = 1024;
+
+const SIGNATURE_HEADER: &str = "X-Qdrant-Signature";
+const RETRY_BASE_DELAY: Duration = Duration::from_millis(500);
+const RETRY_MAX_DELAY: Duration = Duration::from_secs(60);
+
+/// Delivers collection lifecycle events to the configured webhooks
+pub struct WebhookDispatcher {
+    client: reqwest::Client,
+    webhooks: Vec<Arc<WebhookConfig>>,
+}
+
+impl WebhookDispatcher {
+    fn new(webhooks: Vec<WebhookConfig>) -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            webhooks: webhooks.into_iter().map(Arc::new).collect(),
+        }
+    }
+
+    pub async fn run(
+        webhooks: Vec<WebhookConfig>,
+        mut receiver: broadcast::Receiver<CollectionEvent>,
+    ) {
+        let dispatcher = Self::new(webhooks);
+        loop {
+            match receiver.recv().await {
+                Ok(event) => dispatcher.dispatch(&event),
+                Err(RecvError::Lagged(skipped)) => {
+                    log::warn!("Webhook dispatcher is lagging, {skipped} events were dropped");
+                }
+                Err(RecvError::Closed) => break,
+            }
+        }
+    }
+
+    fn dispatch(&self, event: &CollectionEvent) {
+        let body = match serde_json::to_string(event) {
+            Ok(body) => body,
+            Err(err) => {
+                log::error!("Can't serialize collection event: {err}");
+                return;
+            }
+        };
+
+        let event_type = event.kind.event_type();
+        for webhook in &self.webhooks {
+            let is_subscribed = webhook
+                .events
+                .as_ref()
+                .map_or(true, |events| events.contains(&event_type));
+            if !is_subscribed {
+                continue;
+            }
+            // Deliver each webhook independently, so slow endpoints do not block others
+            tokio::spawn(Self::deliver(
+                self.client.clone(),
+                webhook.clone(),
+                body.clone(),
+            ));
+        }
+    }
+
+    async fn deliver(client: reqwest::Client, webhook: Arc<WebhookConfig>, body: String) {
+        let signature = webhook
+            .secret
+            .as_deref()
+            .map(|secret| sign(secret, body.as_bytes()));
+
+        let mut attempt = 0;
+        loop {
+            let mut request = client
+                .post(&webhook.url)
+                .timeout(Duration::from_millis(webhook.timeout_ms))
+                .header("Content-Type", "application/json")
+                .body(body.clone());
+            if let Some(signature) = &signature {
+                request = request.header(SIGNATURE_HEADER, format!("sha256={signature}"));
+            }
+
+            let error = match request.send().await {
+                Ok(response) if response.status().is_success() => return,
+                Ok(response) => format!("status {}", response.status()),
+                Err(err) => err.to_string(),
+            };
+
+            if attempt >= webhook.max_retries {
+                log::warn!(
+                    "Failed to deliver event to webhook {} after {} attempts: {error}",
+                    webhook.url,
+                    attempt + 1,
+                );
+                return;
+            }
+
+            log::debug!(
+                "Failed to deliver event to webhook {}: {error}",
+                webhook.url
+            );
+            tokio::time::sleep(retry_delay(attempt)).await;
+            attempt += 1;
+        }
+    }
+}
+
+/// Exponential backoff delay before the retry, capped at `RETRY_MAX_DELAY`
+fn retry_delay(attempt: usize) -> Duration {
+    u32::try_from(attempt)
+        .ok()
+        .and_then(|attempt| 2u32.checked_pow(attempt))
+        .and_then(|factor| RETRY_BASE_DELAY.checked_mul(factor))
+        .map_or(RETRY_MAX_DELAY, |delay| delay.min(RETRY_MAX_DELAY))
+}
+
+/// Hex-encoded HMAC-SHA256 signature of the body
+fn sign(secret: &str, body: &[u8]) -> String {
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts keys of any size");
+    mac.update(body);
+    mac.finalize()
+        .into_bytes()
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sign() {
+        // Test vector from RFC 4231, test case 2
+        assert_eq!(
+            sign("Jefe", b"what do ya want for nothing?"),
+            "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843",
+        );
+    }
+
+    #[test]
+    fn test_retry_delay() {
+        assert_eq!(retry_delay(0), RETRY_BASE_DELAY);
+        assert_eq!(retry_delay(3), RETRY_BASE_DELAY * 8);
+        assert_eq!(retry_delay(20), RETRY_MAX_DELAY);
+        assert_eq!(retry_delay(32), RETRY_MAX_DELAY);
+        assert_eq!(retry_delay(usize::MAX), RETRY_MAX_DELAY);
+    }
+}
@@ -1164,6 +1164,7 @@ mod tests {
             ChannelService::new(settings.service.http_port),
             persistent_state.this_peer_id(),
             Some(operation_sender.clone()),
+            Default::default(),
         );
         let toc_arc = Arc::new(toc);
         let storage_path = toc_arc.storage_path();
@@ -1224,6 +1225,10 @@ mod tests {
                                 hnsw_config: None,
                                 quantization_config: None,
                                 on_disk: None,
+                                normalize: None,
+                                dimensionality_reduction: None,
+                                multivector_config: None,
+                                datatype: None,
                             }
                             .into(),
                             sparse_vectors: None,
@@ -1232,11 +1237,20 @@ mod tests {
                             optimizers_config: None,
                             shard_number: Some(2),
                             on_disk_payload: None,
+                            payload_storage_backend: None,
                             replication_factor: None,
                             write_consistency_factor: None,
                             init_from: None,
                             quantization_config: None,
                             sharding_method: None,
+                            sharding_payload_key: None,
+                            search_threads: None,
+                            expiration_field: None,
+                            strict_mode_config: None,
+                            soft_delete_window_sec: None,
+                            storage_policy: None,
+                            from_template: None,
+                            feature_flags: None,
                         },
                     )),
                     None,
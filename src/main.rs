@@ -21,6 +21,7 @@ use std::time::Duration;
 use ::tonic::transport::Uri;
 use api::grpc::transport_channel_pool::TransportChannelPool;
 use clap::Parser;
+use collection::events::EventNotifier;
 use collection::shards::channel_service::ChannelService;
 use consensus::Consensus;
 use slog::Drain;
@@ -34,12 +35,21 @@ use storage::dispatcher::Dispatcher;
 #[cfg(not(target_env = "msvc"))]
 use tikv_jemallocator::Jemalloc;
 
+use crate::common::collections::do_warm_up_collection;
+#[cfg(unix)]
+use crate::common::config_reload::reload_on_sighup;
+use crate::common::config_reload::ConfigReloader;
+use crate::common::consistency_checker::ConsistencyChecker;
+use crate::common::disk_watchdog::DiskWatchdog;
 use crate::common::helpers::{
     create_general_purpose_runtime, create_search_runtime, create_update_runtime,
     load_tls_client_config,
 };
+use crate::common::snapshot_scheduler::SnapshotScheduler;
 use crate::common::telemetry::TelemetryCollector;
 use crate::common::telemetry_reporting::TelemetryReporter;
+use crate::common::usage_rollups::UsageRollups;
+use crate::common::webhooks::{WebhookDispatcher, WEBHOOK_EVENTS_QUEUE_SIZE};
 use crate::greeting::welcome;
 use crate::migrations::single_to_cluster::handle_existing_collections;
 use crate::settings::Settings;
@@ -125,13 +135,13 @@ fn main() -> anyhow::Result<()> {
 
     remove_started_file_indicator();
 
-    let settings = Settings::new(args.config_path)?;
+    let settings = Settings::new(args.config_path.clone())?;
 
     let reporting_enabled = !settings.telemetry_disabled && !args.disable_telemetry;
 
     let reporting_id = TelemetryCollector::generate_id();
 
-    tracing::setup(&settings.log_level)?;
+    let logger_handle = tracing::setup(&settings.log_level)?;
 
     setup_panic_hook(reporting_enabled, reporting_id.to_string());
 
@@ -194,6 +204,19 @@ fn main() -> anyhow::Result<()> {
         create_general_purpose_runtime().expect("Can't optimizer general purpose runtime.");
     let runtime_handle = general_runtime.handle().clone();
 
+    // Deliver collection lifecycle events to the configured webhooks
+    let event_notifier = if settings.webhooks.is_empty() {
+        EventNotifier::default()
+    } else {
+        let (events_sender, events_receiver) =
+            tokio::sync::broadcast::channel(WEBHOOK_EVENTS_QUEUE_SIZE);
+        runtime_handle.spawn(WebhookDispatcher::run(
+            settings.webhooks.clone(),
+            events_receiver,
+        ));
+        EventNotifier::new(events_sender)
+    };
+
     // Create a signal sender and receiver. It is used to communicate with the consensus thread.
     let (propose_sender, propose_receiver) = std::sync::mpsc::channel();
 
@@ -236,6 +259,7 @@ fn main() -> anyhow::Result<()> {
         channel_service.clone(),
         persistent_consensus_state.this_peer_id(),
         propose_operation_sender.clone(),
+        event_notifier,
     );
 
     toc.clear_all_tmp_directories()?;
@@ -250,6 +274,64 @@ fn main() -> anyhow::Result<()> {
     let toc_arc = Arc::new(toc);
     let storage_path = toc_arc.storage_path();
 
+    // Re-applies changeable settings on `SIGHUP` or API request
+    let config_reloader = Arc::new(ConfigReloader::new(
+        args.config_path.clone(),
+        settings.clone(),
+        Box::new(move |log_level| tracing::reload(&logger_handle, log_level)),
+        toc_arc.clone(),
+    ));
+    #[cfg(unix)]
+    runtime_handle.spawn(reload_on_sighup(config_reloader.clone()));
+
+    // Read data of selected collections into memory, so first requests are not slowed down
+    let warm_up_collections = settings.storage.warm_up_collections.clone();
+    if !warm_up_collections.is_empty() {
+        let toc_arc_clone = toc_arc.clone();
+        runtime_handle.spawn(async move {
+            for collection_name in warm_up_collections {
+                log::info!("Warming up collection {collection_name}");
+                match do_warm_up_collection(&toc_arc_clone, &collection_name).await {
+                    Ok(result) => log::info!(
+                        "Collection {collection_name} is warmed up: {} files, {} bytes",
+                        result.files,
+                        result.bytes,
+                    ),
+                    Err(err) => log::warn!("Can't warm up collection {collection_name}: {err}"),
+                }
+            }
+        });
+    }
+
+    if let Some(interval_sec) = settings.storage.snapshot_schedule.snapshot_interval_sec {
+        log::info!("Snapshots of all collections are scheduled every {interval_sec} seconds");
+        runtime_handle.spawn(SnapshotScheduler::run(
+            toc_arc.clone(),
+            settings.storage.snapshot_schedule.clone(),
+        ));
+    }
+
+    if let Some(interval_sec) = settings.storage.consistency_check.check_interval_sec {
+        log::info!("Consistency of all shards is checked every {interval_sec} seconds");
+        runtime_handle.spawn(ConsistencyChecker::run(
+            toc_arc.clone(),
+            settings.storage.consistency_check.clone(),
+        ));
+    }
+
+    if let Some(interval_sec) = settings.storage.usage_metering.rollup_interval_sec {
+        log::info!(
+            "Usage of the tenants of all collections is rolled up every {interval_sec} seconds"
+        );
+        runtime_handle.spawn(UsageRollups::run(
+            toc_arc.clone(),
+            settings.storage.usage_metering.clone(),
+        ));
+    }
+
+    // Storage is shut down once all API servers are stopped
+    let shutdown_runtime_handle = runtime_handle.clone();
+
     // Holder for all actively running threads of the service: web, gPRC, consensus, etc.
     let mut handles: Vec<JoinHandle<Result<(), Error>>> = vec![];
 
@@ -353,6 +435,16 @@ fn main() -> anyhow::Result<()> {
         (telemetry_collector, dispatcher_arc, None)
     };
 
+    if let Some(min_free_space_mb) = settings.storage.disk_watchdog.min_free_space_mb {
+        log::info!(
+            "Updates are rejected if there is less than {min_free_space_mb} MB of free disk space"
+        );
+        runtime_handle.spawn(DiskWatchdog::run(
+            dispatcher_arc.clone(),
+            settings.storage.clone(),
+        ));
+    }
+
     let tonic_telemetry_collector = telemetry_collector.tonic_telemetry_collector.clone();
 
     //
@@ -396,6 +488,7 @@ fn main() -> anyhow::Result<()> {
                         dispatcher_arc.clone(),
                         telemetry_collector,
                         health_checker,
+                        config_reloader,
                         settings,
                     ),
                 )
@@ -474,6 +567,10 @@ fn main() -> anyhow::Result<()> {
         );
         handle.join().expect("thread is not panicking")?;
     }
+
+    // No requests are served anymore, so queued updates can be applied and flushed
+    shutdown_runtime_handle
+        .block_on(toc_arc.shutdown(Duration::from_secs(settings.storage.shutdown_timeout_sec)));
     drop(toc_arc);
     drop(settings);
     Ok(())
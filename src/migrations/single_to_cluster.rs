@@ -40,6 +40,7 @@ pub async fn handle_existing_collections(
                 sparse_vectors: collection_state.config.params.sparse_vectors,
                 shard_number: Some(shards_number),
                 sharding_method,
+                sharding_payload_key: collection_state.config.params.sharding_payload_key,
                 replication_factor: Some(collection_state.config.params.replication_factor.get()),
                 write_consistency_factor: Some(
                     collection_state
@@ -49,18 +50,30 @@ pub async fn handle_existing_collections(
                         .get(),
                 ),
                 on_disk_payload: Some(collection_state.config.params.on_disk_payload),
+                payload_storage_backend: collection_state.config.params.payload_storage_backend,
                 hnsw_config: Some(collection_state.config.hnsw_config.into()),
                 wal_config: Some(collection_state.config.wal_config.into()),
                 optimizers_config: Some(collection_state.config.optimizer_config.into()),
                 init_from: None,
                 quantization_config: collection_state.config.quantization_config,
+                search_threads: collection_state
+                    .config
+                    .params
+                    .search_threads
+                    .map(|threads| threads.get()),
+                expiration_field: collection_state.config.params.expiration_field,
+                strict_mode_config: collection_state.config.strict_mode_config,
+                soft_delete_window_sec: collection_state.config.params.soft_delete_window_sec,
+                storage_policy: collection_state.config.params.storage_policy,
+                from_template: None,
+                feature_flags: collection_state.config.feature_flags,
             },
         );
 
         let mut consensus_operations = Vec::new();
 
         match sharding_method.unwrap_or_default() {
-            ShardingMethod::Auto => {
+            ShardingMethod::Auto | ShardingMethod::PayloadKey => {
                 collection_create_operation.set_distribution(ShardDistributionProposal {
                     distribution: collection_state
                         .shards
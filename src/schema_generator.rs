@@ -1,28 +1,43 @@
 use api::grpc::models::CollectionsResponse;
+use collection::collection::read_only::{ReadOnlyState, SetReadOnlyRequest};
+use collection::collection::stored_queries::{StoredQueries, StoredQuery};
+use collection::collection::usage::CollectionUsage;
+use collection::hybrid_search::HybridSearchRequest;
+use collection::multi_search::{MultiCollectionScoredPoint, MultiCollectionSearchRequest};
 use collection::operations::cluster_ops::ClusterOperations;
-use collection::operations::consistency_params::ReadConsistency;
+use collection::operations::consistency_params::{ReadConsistency, WriteConsistency};
+use collection::operations::dimensionality_reduction::TrainDimensionalityReduction;
+use collection::operations::document_ops::{PointsDocuments, SearchDocumentRequest};
 use collection::operations::payload_ops::{DeletePayload, SetPayload};
 use collection::operations::point_ops::{PointInsertOperations, PointsSelector, WriteOrdering};
+use collection::operations::running_operations::RunningOperation;
 use collection::operations::snapshot_ops::{
-    ShardSnapshotRecover, SnapshotDescription, SnapshotRecover,
+    ShardSnapshotRecover, SnapshotCreationPriority, SnapshotDescription, SnapshotRecover,
 };
 use collection::operations::types::{
     AliasDescription, CollectionClusterInfo, CollectionInfo, CollectionsAliasesResponse,
-    CountRequest, CountResult, DiscoverRequest, DiscoverRequestBatch, GroupsResult, PointGroup,
+    CompactionResult, CountRequest, CountResult, DiscoverRequest, DiscoverRequestBatch,
+    GroupsResult, OpenScrollSession, PartialSearchBatchResult, PartialSearchResult, PointGroup,
     PointRequest, RecommendGroupsRequest, RecommendRequest, RecommendRequestBatch, Record,
-    ScrollRequest, ScrollResult, SearchGroupsRequest, SearchRequest, SearchRequestBatch,
-    UpdateResult,
+    ScrollRequest, ScrollResult, ScrollSessionInfo, SearchGroupsRequest, SearchPageRequest,
+    SearchPageResult, SearchRequest, SearchRequestBatch, UpdateResult, WarmUpResult,
 };
+use collection::operations::update_priority::UpdatePriority;
 use collection::operations::vector_ops::{DeleteVectors, UpdateVectors};
+use collection::rescore_search::RescoreSearchRequest;
+use collection::search_matrix::{SearchMatrixRequest, SearchMatrixRow};
 use schemars::gen::SchemaSettings;
 use schemars::JsonSchema;
 use segment::types::ScoredPoint;
 use serde::{Deserialize, Serialize};
 use storage::content_manager::collection_meta_ops::{
-    ChangeAliasesOperation, CreateCollection, UpdateCollection,
+    ChangeAliasesOperation, CloneCollection, CreateCollection, UpdateCollection,
 };
-use storage::types::ClusterStatus;
+use storage::content_manager::collection_templates::{CollectionTemplate, CollectionTemplates};
+use storage::types::{ClusterStatus, SetPeerRole};
 
+use crate::common::config_reload::ConfigReloadResult;
+use crate::common::filter_updates::{DeleteByFilter, FilterUpdateResult, SetPayloadByFilter};
 use crate::common::helpers::LocksOption;
 use crate::common::points::{CreateFieldIndex, UpdateOperations};
 use crate::common::telemetry::TelemetryData;
@@ -78,6 +93,40 @@ struct AllDefinitions {
     b9: ShardSnapshotRecover,
     ba: DiscoverRequest,
     bb: DiscoverRequestBatch,
+    bc: WarmUpResult,
+    bd: CloneCollection,
+    be: ConfigReloadResult,
+    bf: PartialSearchResult,
+    bg: PartialSearchBatchResult,
+    bh: SearchPageRequest,
+    bi: SearchPageResult,
+    bj: SetPeerRole,
+    bk: DeleteByFilter,
+    bl: SetPayloadByFilter,
+    bm: FilterUpdateResult,
+    bn: CompactionResult,
+    bo: SnapshotCreationPriority,
+    bp: MultiCollectionSearchRequest,
+    bq: MultiCollectionScoredPoint,
+    br: StoredQuery,
+    bs: StoredQueries,
+    bt: CollectionTemplate,
+    bu: CollectionTemplates,
+    bv: WriteConsistency,
+    bw: TrainDimensionalityReduction,
+    bx: RunningOperation,
+    by: PointsDocuments,
+    bz: SearchDocumentRequest,
+    c0: HybridSearchRequest,
+    c1: RescoreSearchRequest,
+    c2: ReadOnlyState,
+    c3: SetReadOnlyRequest,
+    c4: CollectionUsage,
+    c5: SearchMatrixRequest,
+    c6: SearchMatrixRow,
+    c7: UpdatePriority,
+    c8: OpenScrollSession,
+    c9: ScrollSessionInfo,
 }
 
 fn save_schema<T: JsonSchema>() {
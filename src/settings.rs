@@ -3,6 +3,7 @@ use std::{env, io};
 use api::grpc::transport_channel_pool::{
     DEFAULT_CONNECT_TIMEOUT, DEFAULT_GRPC_TIMEOUT, DEFAULT_POOL_SIZE,
 };
+use collection::events::CollectionEventType;
 use collection::operations::validation;
 use config::{Config, ConfigError, Environment, File, FileFormat, Source};
 use segment::common::cpu::get_num_cpus;
@@ -12,7 +13,7 @@ use validator::Validate;
 
 const DEFAULT_CONFIG: &str = include_str!("../config/config.yaml");
 
-#[derive(Debug, Deserialize, Validate, Clone)]
+#[derive(Debug, Deserialize, Validate, Clone, PartialEq)]
 pub struct ServiceConfig {
     #[validate(length(min = 1))]
     pub host: String,
@@ -28,6 +29,9 @@ pub struct ServiceConfig {
     pub verify_https_client_certificate: bool,
     pub api_key: Option<String>,
     pub read_only_api_key: Option<String>,
+    /// API keys, which allow to read only points with any of the given access tags
+    #[serde(default)]
+    pub access_tag_api_keys: Vec<AccessTagApiKey>,
 
     /// Directory where static files are served from.
     /// For example, the Web-UI should be placed here.
@@ -40,7 +44,14 @@ pub struct ServiceConfig {
     pub enable_static_content: Option<bool>,
 }
 
-#[derive(Debug, Deserialize, Clone, Default, Validate)]
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct AccessTagApiKey {
+    pub key: String,
+    /// Points with any of these tags in the `access_tags` payload field are visible with the key
+    pub access_tags: Vec<String>,
+}
+
+#[derive(Debug, Deserialize, Clone, PartialEq, Default, Validate)]
 pub struct ClusterConfig {
     pub enabled: bool, // disabled by default
     #[serde(default = "default_timeout_ms")]
@@ -57,7 +68,7 @@ pub struct ClusterConfig {
     pub consensus: ConsensusConfig,
 }
 
-#[derive(Debug, Deserialize, Clone, Validate)]
+#[derive(Debug, Deserialize, Clone, PartialEq, Validate)]
 pub struct P2pConfig {
     #[serde(default)]
     pub port: Option<u16>,
@@ -78,7 +89,7 @@ impl Default for P2pConfig {
     }
 }
 
-#[derive(Debug, Deserialize, Clone, Validate)]
+#[derive(Debug, Deserialize, Clone, PartialEq, Validate)]
 pub struct ConsensusConfig {
     #[serde(default = "default_max_message_queue_size")]
     pub max_message_queue_size: usize, // controls the back-pressure at the Raft level
@@ -104,7 +115,7 @@ impl Default for ConsensusConfig {
     }
 }
 
-#[derive(Debug, Deserialize, Clone, Validate)]
+#[derive(Debug, Deserialize, Clone, PartialEq, Validate)]
 pub struct TlsConfig {
     pub cert: String,
     pub key: String,
@@ -114,6 +125,26 @@ pub struct TlsConfig {
     pub cert_ttl: Option<u64>,
 }
 
+#[derive(Debug, Deserialize, Clone, PartialEq, Validate)]
+pub struct WebhookConfig {
+    /// URL to send event notifications to with POST requests
+    #[validate(length(min = 1))]
+    pub url: String,
+    /// If set, each request is signed with HMAC-SHA256 of the body using this secret.
+    /// Signature is sent in `X-Qdrant-Signature` header.
+    #[serde(default)]
+    pub secret: Option<String>,
+    /// Types of events to send. If not set - all events are sent.
+    #[serde(default)]
+    pub events: Option<Vec<CollectionEventType>>,
+    /// Number of delivery retries after the first failed attempt
+    #[serde(default = "default_webhook_max_retries")]
+    pub max_retries: usize,
+    #[serde(default = "default_webhook_timeout_ms")]
+    #[validate(range(min = 1))]
+    pub timeout_ms: u64,
+}
+
 #[derive(Debug, Deserialize, Clone, Validate)]
 pub struct Settings {
     #[serde(default = "default_log_level")]
@@ -129,6 +160,10 @@ pub struct Settings {
     pub telemetry_disabled: bool,
     #[validate]
     pub tls: Option<TlsConfig>,
+    /// Endpoints to notify about collection lifecycle events
+    #[serde(default)]
+    #[validate]
+    pub webhooks: Vec<WebhookConfig>,
     /// A list of messages for errors that happened during loading the configuration. We collect
     /// them and store them here while loading because then our logger is not configured yet.
     /// We therefore need to log these messages later, after the logger is ready.
@@ -217,6 +252,14 @@ const fn default_message_timeout_tics() -> u64 {
     10
 }
 
+const fn default_webhook_max_retries() -> usize {
+    3
+}
+
+const fn default_webhook_timeout_ms() -> u64 {
+    5000
+}
+
 const fn default_tls_cert_ttl() -> Option<u64> {
     // Default one hour
     Some(3600)
@@ -3,12 +3,14 @@ use std::time::{Duration, Instant};
 
 use api::grpc::qdrant::collections_server::Collections;
 use api::grpc::qdrant::{
-    AliasDescription, ChangeAliases, CollectionClusterInfoRequest, CollectionClusterInfoResponse,
+    AliasDescription, CancelOperationRequest, CancelOperationResponse, ChangeAliases,
+    CloneCollection, CollectionClusterInfoRequest, CollectionClusterInfoResponse,
     CollectionOperationResponse, CreateCollection, CreateShardKeyRequest, CreateShardKeyResponse,
     DeleteCollection, DeleteShardKeyRequest, DeleteShardKeyResponse, GetCollectionInfoRequest,
     GetCollectionInfoResponse, ListAliasesRequest, ListAliasesResponse,
     ListCollectionAliasesRequest, ListCollectionsRequest, ListCollectionsResponse,
-    UpdateCollection, UpdateCollectionClusterSetupRequest, UpdateCollectionClusterSetupResponse,
+    ListOperationsRequest, ListOperationsResponse, UpdateCollection,
+    UpdateCollectionClusterSetupRequest, UpdateCollectionClusterSetupResponse,
 };
 use collection::operations::cluster_ops::{
     ClusterOperations, CreateShardingKeyOperation, DropShardingKeyOperation,
@@ -19,6 +21,7 @@ use tonic::{Request, Response, Status};
 
 use super::validate;
 use crate::common::collections::*;
+use crate::common::running_operations::{do_cancel_operation, do_list_operations};
 use crate::tonic::api::collections_common::get;
 
 pub struct CollectionsService {
@@ -140,6 +143,29 @@ impl Collections for CollectionsService {
         self.perform_operation(request).await
     }
 
+    async fn clone_collection(
+        &self,
+        request: Request<CloneCollection>,
+    ) -> Result<Response<CollectionOperationResponse>, Status> {
+        validate(request.get_ref())?;
+        let timing = Instant::now();
+        let operation = request.into_inner();
+        let wait_timeout = operation.wait_timeout();
+        let source = operation.collection_name.clone();
+        let result = do_clone_collection(
+            self.dispatcher.toc(),
+            self.dispatcher.as_ref(),
+            &source,
+            operation.try_into()?,
+            wait_timeout,
+        )
+        .await
+        .map_err(error_to_status)?;
+
+        let response = CollectionOperationResponse::from((timing, result));
+        Ok(Response::new(response))
+    }
+
     async fn delete(
         &self,
         request: Request<DeleteCollection>,
@@ -282,6 +308,48 @@ impl Collections for CollectionsService {
 
         Ok(Response::new(DeleteShardKeyResponse { result }))
     }
+
+    async fn list_operations(
+        &self,
+        request: Request<ListOperationsRequest>,
+    ) -> Result<Response<ListOperationsResponse>, Status> {
+        validate(request.get_ref())?;
+        let timing = Instant::now();
+        let operations = do_list_operations(self.dispatcher.toc())
+            .await
+            .map_err(error_to_status)?;
+
+        Ok(Response::new(ListOperationsResponse {
+            operations: operations.into_iter().map(From::from).collect(),
+            time: timing.elapsed().as_secs_f64(),
+        }))
+    }
+
+    async fn cancel_operation(
+        &self,
+        request: Request<CancelOperationRequest>,
+    ) -> Result<Response<CancelOperationResponse>, Status> {
+        validate(request.get_ref())?;
+        let timing = Instant::now();
+        let CancelOperationRequest {
+            operation_id,
+            timeout,
+        } = request.into_inner();
+
+        let result = do_cancel_operation(
+            self.dispatcher.toc(),
+            self.dispatcher.as_ref(),
+            operation_id,
+            timeout.map(Duration::from_secs),
+        )
+        .await
+        .map_err(error_to_status)?;
+
+        Ok(Response::new(CancelOperationResponse {
+            result,
+            time: timing.elapsed().as_secs_f64(),
+        }))
+    }
 }
 
 trait WithTimeout {
@@ -300,6 +368,7 @@ macro_rules! impl_with_timeout {
 
 impl_with_timeout!(CreateCollection);
 impl_with_timeout!(UpdateCollection);
+impl_with_timeout!(CloneCollection);
 impl_with_timeout!(DeleteCollection);
 impl_with_timeout!(ChangeAliases);
 impl_with_timeout!(UpdateCollectionClusterSetupRequest);
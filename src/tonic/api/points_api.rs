@@ -4,21 +4,26 @@ use std::time::Duration;
 use api::grpc::qdrant::points_server::Points;
 use api::grpc::qdrant::{
     ClearPayloadPoints, CountPoints, CountResponse, CreateFieldIndexCollection,
-    DeleteFieldIndexCollection, DeletePayloadPoints, DeletePointVectors, DeletePoints,
-    DiscoverBatchPoints, DiscoverBatchResponse, DiscoverPoints, DiscoverResponse, GetPoints,
-    GetResponse, PointsOperationResponse, RecommendBatchPoints, RecommendBatchResponse,
-    RecommendGroupsResponse, RecommendPointGroups, RecommendPoints, RecommendResponse,
-    ScrollPoints, ScrollResponse, SearchBatchPoints, SearchBatchResponse, SearchGroupsResponse,
-    SearchPointGroups, SearchPoints, SearchResponse, SetPayloadPoints, UpdateBatchPoints,
+    DeleteByFilterPoints, DeleteFieldIndexCollection, DeletePayloadPoints, DeletePointVectors,
+    DeletePoints, DiscoverBatchPoints, DiscoverBatchResponse, DiscoverPoints, DiscoverResponse,
+    FilterUpdateResponse, GetPoints, GetResponse, PointsOperationResponse, RecommendBatchPoints,
+    RecommendBatchResponse, RecommendGroupsResponse, RecommendPointGroups, RecommendPoints,
+    RecommendResponse, ScrollPoints, ScrollResponse, SearchBatchPoints, SearchBatchResponse,
+    SearchGroupsResponse, SearchPageResponse, SearchPointGroups, SearchPoints, SearchPointsPage,
+    SearchResponse, SetPayloadByFilterPoints, SetPayloadPoints, UndeletePoints, UpdateBatchPoints,
     UpdateBatchResponse, UpdatePointVectors, UpsertPoints,
 };
+use collection::collection::access_tags::AccessTags;
+use collection::operations::consistency_token::ConsistencyToken;
 use collection::operations::types::CoreSearchRequest;
+use collection::shards::replica_set::stale_reads::StaleReads;
+use storage::content_manager::conversions::error_to_status;
 use storage::dispatcher::Dispatcher;
 use tonic::{Request, Response, Status};
 
 use super::points_common::{
-    delete_vectors, discover, discover_batch, recommend_groups, search_groups, update_batch,
-    update_vectors,
+    delete_by_filter, delete_vectors, discover, discover_batch, recommend_groups, search_groups,
+    search_page, set_payload_by_filter, undelete, update_batch, update_vectors,
 };
 use super::validate;
 use crate::tonic::api::points_common::{
@@ -26,6 +31,7 @@ use crate::tonic::api::points_common::{
     delete, delete_field_index, delete_payload, get, overwrite_payload, recommend, recommend_batch,
     scroll, search, set_payload, upsert,
 };
+use crate::tonic::read_priority::extract_read_priority;
 
 pub struct PointsService {
     dispatcher: Arc<Dispatcher>,
@@ -57,7 +63,18 @@ impl Points for PointsService {
 
     async fn get(&self, request: Request<GetPoints>) -> Result<Response<GetResponse>, Status> {
         validate(request.get_ref())?;
-        get(self.dispatcher.as_ref(), request.into_inner(), None).await
+        let access_tags = request.extensions().get::<AccessTags>().cloned();
+        let read_priority = extract_read_priority(&request);
+        let stale_reads = request.extensions().get::<StaleReads>().cloned();
+        get(
+            self.dispatcher.as_ref(),
+            request.into_inner(),
+            None,
+            access_tags.as_ref(),
+            read_priority,
+            stale_reads.as_ref(),
+        )
+        .await
     }
 
     async fn update_vectors(
@@ -108,6 +125,30 @@ impl Points for PointsService {
         clear_payload(self.dispatcher.as_ref(), request.into_inner(), None).await
     }
 
+    async fn undelete(
+        &self,
+        request: Request<UndeletePoints>,
+    ) -> Result<Response<PointsOperationResponse>, Status> {
+        validate(request.get_ref())?;
+        undelete(self.dispatcher.as_ref(), request.into_inner(), None).await
+    }
+
+    async fn delete_by_filter(
+        &self,
+        request: Request<DeleteByFilterPoints>,
+    ) -> Result<Response<FilterUpdateResponse>, Status> {
+        validate(request.get_ref())?;
+        delete_by_filter(self.dispatcher.as_ref(), request.into_inner()).await
+    }
+
+    async fn set_payload_by_filter(
+        &self,
+        request: Request<SetPayloadByFilterPoints>,
+    ) -> Result<Response<FilterUpdateResponse>, Status> {
+        validate(request.get_ref())?;
+        set_payload_by_filter(self.dispatcher.as_ref(), request.into_inner()).await
+    }
+
     async fn update_batch(
         &self,
         request: Request<UpdateBatchPoints>,
@@ -137,7 +178,18 @@ impl Points for PointsService {
         request: Request<SearchPoints>,
     ) -> Result<Response<SearchResponse>, Status> {
         validate(request.get_ref())?;
-        search(self.dispatcher.as_ref(), request.into_inner(), None).await
+        let access_tags = request.extensions().get::<AccessTags>().cloned();
+        let read_priority = extract_read_priority(&request);
+        let stale_reads = request.extensions().get::<StaleReads>().cloned();
+        search(
+            self.dispatcher.as_ref(),
+            request.into_inner(),
+            None,
+            access_tags.as_ref(),
+            read_priority,
+            stale_reads.as_ref(),
+        )
+        .await
     }
 
     async fn search_batch(
@@ -145,15 +197,26 @@ impl Points for PointsService {
         request: Request<SearchBatchPoints>,
     ) -> Result<Response<SearchBatchResponse>, Status> {
         validate(request.get_ref())?;
+        let access_tags = request.extensions().get::<AccessTags>().cloned();
+        let read_priority = extract_read_priority(&request);
+        let stale_reads = request.extensions().get::<StaleReads>().cloned();
         let SearchBatchPoints {
             collection_name,
             search_points,
             read_consistency,
             timeout,
+            partial_results,
         } = request.into_inner();
 
         let timeout = timeout.map(Duration::from_secs);
 
+        let consistency_token = ConsistencyToken::merge_all(
+            search_points
+                .iter()
+                .map(|search_point| search_point.consistency_token.as_deref()),
+        )
+        .map_err(|err| error_to_status(err.into()))?;
+
         let mut requests = Vec::new();
 
         for mut search_point in search_points {
@@ -170,7 +233,12 @@ impl Points for PointsService {
             collection_name,
             requests,
             read_consistency,
+            consistency_token,
             timeout,
+            partial_results.unwrap_or_default(),
+            access_tags.as_ref(),
+            read_priority,
+            stale_reads.as_ref(),
         )
         .await
     }
@@ -180,7 +248,37 @@ impl Points for PointsService {
         request: Request<SearchPointGroups>,
     ) -> Result<Response<SearchGroupsResponse>, Status> {
         validate(request.get_ref())?;
-        search_groups(self.dispatcher.as_ref(), request.into_inner(), None).await
+        let access_tags = request.extensions().get::<AccessTags>().cloned();
+        let read_priority = extract_read_priority(&request);
+        let stale_reads = request.extensions().get::<StaleReads>().cloned();
+        search_groups(
+            self.dispatcher.as_ref(),
+            request.into_inner(),
+            None,
+            access_tags.as_ref(),
+            read_priority,
+            stale_reads.as_ref(),
+        )
+        .await
+    }
+
+    async fn search_page(
+        &self,
+        request: Request<SearchPointsPage>,
+    ) -> Result<Response<SearchPageResponse>, Status> {
+        validate(request.get_ref())?;
+        let access_tags = request.extensions().get::<AccessTags>().cloned();
+        let read_priority = extract_read_priority(&request);
+        let stale_reads = request.extensions().get::<StaleReads>().cloned();
+        search_page(
+            self.dispatcher.as_ref(),
+            request.into_inner(),
+            None,
+            access_tags.as_ref(),
+            read_priority,
+            stale_reads.as_ref(),
+        )
+        .await
     }
 
     async fn scroll(
@@ -188,7 +286,18 @@ impl Points for PointsService {
         request: Request<ScrollPoints>,
     ) -> Result<Response<ScrollResponse>, Status> {
         validate(request.get_ref())?;
-        scroll(self.dispatcher.as_ref(), request.into_inner(), None).await
+        let access_tags = request.extensions().get::<AccessTags>().cloned();
+        let read_priority = extract_read_priority(&request);
+        let stale_reads = request.extensions().get::<StaleReads>().cloned();
+        scroll(
+            self.dispatcher.as_ref(),
+            request.into_inner(),
+            None,
+            access_tags.as_ref(),
+            read_priority,
+            stale_reads.as_ref(),
+        )
+        .await
     }
 
     async fn recommend(
@@ -196,7 +305,17 @@ impl Points for PointsService {
         request: Request<RecommendPoints>,
     ) -> Result<Response<RecommendResponse>, Status> {
         validate(request.get_ref())?;
-        recommend(self.dispatcher.as_ref(), request.into_inner()).await
+        let access_tags = request.extensions().get::<AccessTags>().cloned();
+        let read_priority = extract_read_priority(&request);
+        let stale_reads = request.extensions().get::<StaleReads>().cloned();
+        recommend(
+            self.dispatcher.as_ref(),
+            request.into_inner(),
+            access_tags.as_ref(),
+            read_priority,
+            stale_reads.as_ref(),
+        )
+        .await
     }
 
     async fn recommend_batch(
@@ -204,6 +323,9 @@ impl Points for PointsService {
         request: Request<RecommendBatchPoints>,
     ) -> Result<Response<RecommendBatchResponse>, Status> {
         validate(request.get_ref())?;
+        let access_tags = request.extensions().get::<AccessTags>().cloned();
+        let read_priority = extract_read_priority(&request);
+        let stale_reads = request.extensions().get::<StaleReads>().cloned();
         let RecommendBatchPoints {
             collection_name,
             recommend_points,
@@ -216,6 +338,9 @@ impl Points for PointsService {
             recommend_points,
             read_consistency,
             timeout.map(Duration::from_secs),
+            access_tags.as_ref(),
+            read_priority,
+            stale_reads.as_ref(),
         )
         .await
     }
@@ -225,7 +350,17 @@ impl Points for PointsService {
         request: Request<RecommendPointGroups>,
     ) -> Result<Response<RecommendGroupsResponse>, Status> {
         validate(request.get_ref())?;
-        recommend_groups(self.dispatcher.as_ref(), request.into_inner()).await
+        let access_tags = request.extensions().get::<AccessTags>().cloned();
+        let read_priority = extract_read_priority(&request);
+        let stale_reads = request.extensions().get::<StaleReads>().cloned();
+        recommend_groups(
+            self.dispatcher.as_ref(),
+            request.into_inner(),
+            access_tags.as_ref(),
+            read_priority,
+            stale_reads.as_ref(),
+        )
+        .await
     }
 
     async fn discover(
@@ -233,7 +368,17 @@ impl Points for PointsService {
         request: Request<DiscoverPoints>,
     ) -> Result<Response<DiscoverResponse>, Status> {
         validate(request.get_ref())?;
-        discover(self.dispatcher.as_ref(), request.into_inner()).await
+        let access_tags = request.extensions().get::<AccessTags>().cloned();
+        let read_priority = extract_read_priority(&request);
+        let stale_reads = request.extensions().get::<StaleReads>().cloned();
+        discover(
+            self.dispatcher.as_ref(),
+            request.into_inner(),
+            access_tags.as_ref(),
+            read_priority,
+            stale_reads.as_ref(),
+        )
+        .await
     }
 
     async fn discover_batch(
@@ -241,6 +386,9 @@ impl Points for PointsService {
         request: Request<DiscoverBatchPoints>,
     ) -> Result<Response<DiscoverBatchResponse>, Status> {
         validate(request.get_ref())?;
+        let access_tags = request.extensions().get::<AccessTags>().cloned();
+        let read_priority = extract_read_priority(&request);
+        let stale_reads = request.extensions().get::<StaleReads>().cloned();
         let DiscoverBatchPoints {
             collection_name,
             discover_points,
@@ -253,6 +401,9 @@ impl Points for PointsService {
             discover_points,
             read_consistency,
             timeout.map(Duration::from_secs),
+            access_tags.as_ref(),
+            read_priority,
+            stale_reads.as_ref(),
         )
         .await
     }
@@ -262,6 +413,17 @@ impl Points for PointsService {
         request: Request<CountPoints>,
     ) -> Result<Response<CountResponse>, Status> {
         validate(request.get_ref())?;
-        count(self.dispatcher.as_ref(), request.into_inner(), None).await
+        let access_tags = request.extensions().get::<AccessTags>().cloned();
+        let read_priority = extract_read_priority(&request);
+        let stale_reads = request.extensions().get::<StaleReads>().cloned();
+        count(
+            self.dispatcher.as_ref(),
+            request.into_inner(),
+            None,
+            access_tags.as_ref(),
+            read_priority,
+            stale_reads.as_ref(),
+        )
+        .await
     }
 }
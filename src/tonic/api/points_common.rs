@@ -5,31 +5,38 @@ use api::grpc::qdrant::payload_index_params::IndexParams;
 use api::grpc::qdrant::points_update_operation::{ClearPayload, Operation, PointStructList};
 use api::grpc::qdrant::{
     points_update_operation, BatchResult, ClearPayloadPoints, CoreSearchPoints, CountPoints,
-    CountResponse, CreateFieldIndexCollection, DeleteFieldIndexCollection, DeletePayloadPoints,
-    DeletePointVectors, DeletePoints, DiscoverBatchResponse, DiscoverPoints, DiscoverResponse,
-    FieldType, GetPoints, GetResponse, PayloadIndexParams, PointsOperationResponse, PointsSelector,
+    CountResponse, CreateFieldIndexCollection, DeleteByFilterPoints, DeleteFieldIndexCollection,
+    DeletePayloadPoints, DeletePointVectors, DeletePoints, DiscoverBatchResponse, DiscoverPoints,
+    DiscoverResponse, FieldType, FilterUpdateResponse, GetPoints, GetResponse, PayloadIndexParams,
+    PointsOperationResponse, PointsSelector, PointsUpdateOperation,
     ReadConsistency as ReadConsistencyGrpc, RecommendBatchResponse, RecommendGroupsResponse,
     RecommendPointGroups, RecommendPoints, RecommendResponse, ScrollPoints, ScrollResponse,
-    SearchBatchResponse, SearchGroupsResponse, SearchPointGroups, SearchPoints, SearchResponse,
-    SetPayloadPoints, SyncPoints, UpdateBatchPoints, UpdateBatchResponse, UpdatePointVectors,
-    UpsertPoints,
+    SearchBatchResponse, SearchGroupsResponse, SearchPageResponse, SearchPointGroups, SearchPoints,
+    SearchPointsPage, SearchResponse, SetPayloadByFilterPoints, SetPayloadPoints, SyncPoints,
+    UndeletePoints, UpdateBatchPoints, UpdateBatchResponse, UpdatePointVectors, UpsertPoints,
 };
-use collection::operations::consistency_params::ReadConsistency;
+use collection::collection::access_tags::AccessTags;
+use collection::operations::consistency_params::{ReadConsistency, WriteConsistency};
+use collection::operations::consistency_token::ConsistencyToken;
 use collection::operations::conversions::{
-    try_discover_request_from_grpc, try_points_selector_from_grpc, write_ordering_from_proto,
+    try_discover_request_from_grpc, try_points_selector_from_grpc, update_priority_from_proto,
+    write_ordering_from_proto,
 };
 use collection::operations::payload_ops::DeletePayload;
 use collection::operations::point_ops::{
-    self, PointInsertOperations, PointOperations, PointSyncOperation, PointsList,
+    self, PointIdsList, PointInsertOperations, PointOperations, PointSyncOperation, PointsList,
 };
 use collection::operations::shard_key_selector::ShardKeySelector;
 use collection::operations::shard_selector_internal::ShardSelectorInternal;
 use collection::operations::types::{
-    default_exact_count, CoreSearchRequest, CoreSearchRequestBatch, PointRequestInternal,
-    QueryEnum, RecommendExample, ScrollRequestInternal,
+    default_exact_count, CoreSearchRequest, CoreSearchRequestBatch, PartialSearchBatchResult,
+    PartialSearchResult, PointRequestInternal, QueryEnum, RecommendExample, Sample,
+    ScrollRequestInternal,
 };
 use collection::operations::vector_ops::{DeleteVectors, PointVectors, UpdateVectors};
 use collection::operations::CollectionUpdateOperations;
+use collection::shards::replica_set::read_priority::ReadPriority;
+use collection::shards::replica_set::stale_reads::StaleReads;
 use collection::shards::shard::ShardId;
 use segment::types::{
     ExtendedPointId, Filter, PayloadFieldSchema, PayloadSchemaParams, PayloadSchemaType,
@@ -39,11 +46,20 @@ use storage::content_manager::toc::TableOfContent;
 use storage::dispatcher::Dispatcher;
 use tonic::{Response, Status};
 
+use crate::common::filter_updates::{
+    do_delete_by_filter, do_set_payload_by_filter, DeleteByFilter, FilterUpdateResult,
+    SetPayloadByFilter,
+};
 use crate::common::points::{
-    do_clear_payload, do_core_search_points, do_count_points, do_create_index,
-    do_create_index_internal, do_delete_index, do_delete_index_internal, do_delete_payload,
-    do_delete_points, do_delete_vectors, do_get_points, do_overwrite_payload, do_scroll_points,
-    do_search_batch_points, do_set_payload, do_update_vectors, do_upsert_points, CreateFieldIndex,
+    do_all_or_nothing_batch_update_points, do_clear_payload, do_core_search_points,
+    do_core_search_points_partial, do_count_points, do_create_index, do_create_index_internal,
+    do_delete_index, do_delete_index_internal, do_delete_payload, do_delete_points,
+    do_delete_vectors, do_get_points, do_overwrite_payload, do_scroll_points,
+    do_search_batch_points, do_search_batch_points_partial, do_search_page_points, do_set_payload,
+    do_undelete_points, do_update_vectors, do_upsert_points, ClearPayloadOperation,
+    CreateFieldIndex, DeleteOperation, DeletePayloadOperation, DeleteVectorsOperation,
+    OverwritePayloadOperation, SetPayloadOperation, UpdateOperation, UpdateVectorsOperation,
+    UpsertOperation,
 };
 
 fn extract_points_selector(
@@ -61,6 +77,25 @@ fn extract_points_selector(
     Ok((points, filter))
 }
 
+/// Build list of operation points
+fn convert_point_vectors(
+    points: Vec<api::grpc::qdrant::PointVectors>,
+) -> Result<Vec<PointVectors>, Status> {
+    let mut op_points = Vec::with_capacity(points.len());
+    for point in points {
+        let id = match point.id {
+            Some(id) => id.try_into()?,
+            None => return Err(Status::invalid_argument("id is expected")),
+        };
+        let vector = match point.vectors {
+            Some(vectors) => vectors.try_into()?,
+            None => return Err(Status::invalid_argument("vectors is expected")),
+        };
+        op_points.push(PointVectors { id, vector });
+    }
+    Ok(op_points)
+}
+
 pub fn points_operation_response(
     timing: Instant,
     update_result: collection::operations::types::UpdateResult,
@@ -71,6 +106,16 @@ pub fn points_operation_response(
     }
 }
 
+fn filter_update_response(timing: Instant, result: FilterUpdateResult) -> FilterUpdateResponse {
+    FilterUpdateResponse {
+        result: Some(api::grpc::qdrant::FilterUpdateResult {
+            points: result.points as u64,
+            batches: result.batches as u64,
+        }),
+        time: timing.elapsed().as_secs_f64(),
+    }
+}
+
 pub(crate) fn convert_shard_selector_for_read(
     shard_id_selector: Option<ShardId>,
     shard_key_selector: Option<api::grpc::qdrant::ShardKeySelector>,
@@ -99,7 +144,12 @@ pub async fn upsert(
         wait,
         points,
         ordering,
+        shard_deactivation_timeout,
+        write_consistency,
+        timeout,
         shard_key_selector,
+        ttl,
+        priority,
     } = upsert_points;
     let points = points
         .into_iter()
@@ -108,6 +158,8 @@ pub async fn upsert(
     let operation = PointInsertOperations::PointsList(PointsList {
         points,
         shard_key: shard_key_selector.map(ShardKeySelector::from),
+        deduplication: None,
+        ttl,
     });
     let timing = Instant::now();
     let result = do_upsert_points(
@@ -115,8 +167,12 @@ pub async fn upsert(
         &collection_name,
         operation,
         shard_selection,
-        wait.unwrap_or(false),
+        wait,
         write_ordering_from_proto(ordering)?,
+        shard_deactivation_timeout.map(Duration::from_secs),
+        WriteConsistency::try_from_optional(write_consistency)?,
+        timeout.map(Duration::from_secs),
+        update_priority_from_proto(priority)?,
     )
     .await
     .map_err(error_to_status)?;
@@ -137,6 +193,10 @@ pub async fn sync(
         from_id,
         to_id,
         ordering,
+        shard_deactivation_timeout,
+        write_consistency,
+        timeout,
+        priority,
     } = sync_points;
 
     let points = points
@@ -165,8 +225,12 @@ pub async fn sync(
         .update(
             &collection_name,
             collection_operation,
-            wait.unwrap_or(false),
+            wait,
             write_ordering_from_proto(ordering)?,
+            shard_deactivation_timeout.map(Duration::from_secs),
+            WriteConsistency::try_from_optional(write_consistency)?,
+            timeout.map(Duration::from_secs),
+            update_priority_from_proto(priority)?,
             shard_selector,
         )
         .await
@@ -186,7 +250,11 @@ pub async fn delete(
         wait,
         points,
         ordering,
+        shard_deactivation_timeout,
+        write_consistency,
+        timeout,
         shard_key_selector,
+        priority,
     } = delete_points;
 
     let points_selector = match points {
@@ -200,8 +268,12 @@ pub async fn delete(
         &collection_name,
         points_selector,
         shard_selection,
-        wait.unwrap_or(false),
+        wait,
         write_ordering_from_proto(ordering)?,
+        shard_deactivation_timeout.map(Duration::from_secs),
+        WriteConsistency::try_from_optional(write_consistency)?,
+        timeout.map(Duration::from_secs),
+        update_priority_from_proto(priority)?,
     )
     .await
     .map_err(error_to_status)?;
@@ -220,25 +292,15 @@ pub async fn update_vectors(
         wait,
         points,
         ordering,
+        shard_deactivation_timeout,
+        write_consistency,
+        timeout,
         shard_key_selector,
+        priority,
     } = update_point_vectors;
 
-    // Build list of operation points
-    let mut op_points = Vec::with_capacity(points.len());
-    for point in points {
-        let id = match point.id {
-            Some(id) => id.try_into()?,
-            None => return Err(Status::invalid_argument("id is expected")),
-        };
-        let vector = match point.vectors {
-            Some(vectors) => vectors.try_into()?,
-            None => return Err(Status::invalid_argument("vectors is expected")),
-        };
-        op_points.push(PointVectors { id, vector });
-    }
-
     let operation = UpdateVectors {
-        points: op_points,
+        points: convert_point_vectors(points)?,
         shard_key: shard_key_selector.map(ShardKeySelector::from),
     };
 
@@ -248,8 +310,12 @@ pub async fn update_vectors(
         &collection_name,
         operation,
         shard_selection,
-        wait.unwrap_or(false),
+        wait,
         write_ordering_from_proto(ordering)?,
+        shard_deactivation_timeout.map(Duration::from_secs),
+        WriteConsistency::try_from_optional(write_consistency)?,
+        timeout.map(Duration::from_secs),
+        update_priority_from_proto(priority)?,
     )
     .await
     .map_err(error_to_status)?;
@@ -269,7 +335,11 @@ pub async fn delete_vectors(
         points_selector,
         vectors,
         ordering,
+        shard_deactivation_timeout,
+        write_consistency,
+        timeout,
         shard_key_selector,
+        priority,
     } = delete_point_vectors;
 
     let (points, filter) = extract_points_selector(points_selector)?;
@@ -291,8 +361,12 @@ pub async fn delete_vectors(
         &collection_name,
         operation,
         shard_selection,
-        wait.unwrap_or(false),
+        wait,
         write_ordering_from_proto(ordering)?,
+        shard_deactivation_timeout.map(Duration::from_secs),
+        WriteConsistency::try_from_optional(write_consistency)?,
+        timeout.map(Duration::from_secs),
+        update_priority_from_proto(priority)?,
     )
     .await
     .map_err(error_to_status)?;
@@ -312,7 +386,12 @@ pub async fn set_payload(
         payload,
         points_selector,
         ordering,
+        shard_deactivation_timeout,
+        write_consistency,
+        timeout,
         shard_key_selector,
+        if_version,
+        priority,
     } = set_payload_points;
 
     let (points, filter) = extract_points_selector(points_selector)?;
@@ -321,6 +400,7 @@ pub async fn set_payload(
         points,
         filter,
         shard_key: shard_key_selector.map(ShardKeySelector::from),
+        if_version,
     };
 
     let timing = Instant::now();
@@ -329,8 +409,12 @@ pub async fn set_payload(
         &collection_name,
         operation,
         shard_selection,
-        wait.unwrap_or(false),
+        wait,
         write_ordering_from_proto(ordering)?,
+        shard_deactivation_timeout.map(Duration::from_secs),
+        WriteConsistency::try_from_optional(write_consistency)?,
+        timeout.map(Duration::from_secs),
+        update_priority_from_proto(priority)?,
     )
     .await
     .map_err(error_to_status)?;
@@ -350,7 +434,12 @@ pub async fn overwrite_payload(
         payload,
         points_selector,
         ordering,
+        shard_deactivation_timeout,
+        write_consistency,
+        timeout,
         shard_key_selector,
+        if_version,
+        priority,
     } = set_payload_points;
 
     let (points, filter) = extract_points_selector(points_selector)?;
@@ -359,6 +448,7 @@ pub async fn overwrite_payload(
         points,
         filter,
         shard_key: shard_key_selector.map(ShardKeySelector::from),
+        if_version,
     };
 
     let timing = Instant::now();
@@ -367,8 +457,12 @@ pub async fn overwrite_payload(
         &collection_name,
         operation,
         shard_selection,
-        wait.unwrap_or(false),
+        wait,
         write_ordering_from_proto(ordering)?,
+        shard_deactivation_timeout.map(Duration::from_secs),
+        WriteConsistency::try_from_optional(write_consistency)?,
+        timeout.map(Duration::from_secs),
+        update_priority_from_proto(priority)?,
     )
     .await
     .map_err(error_to_status)?;
@@ -388,7 +482,11 @@ pub async fn delete_payload(
         keys,
         points_selector,
         ordering,
+        shard_deactivation_timeout,
+        write_consistency,
+        timeout,
         shard_key_selector,
+        priority,
     } = delete_payload_points;
 
     let (points, filter) = extract_points_selector(points_selector)?;
@@ -405,8 +503,12 @@ pub async fn delete_payload(
         &collection_name,
         operation,
         shard_selection,
-        wait.unwrap_or(false),
+        wait,
         write_ordering_from_proto(ordering)?,
+        shard_deactivation_timeout.map(Duration::from_secs),
+        WriteConsistency::try_from_optional(write_consistency)?,
+        timeout.map(Duration::from_secs),
+        update_priority_from_proto(priority)?,
     )
     .await
     .map_err(error_to_status)?;
@@ -425,7 +527,11 @@ pub async fn clear_payload(
         wait,
         points,
         ordering,
+        shard_deactivation_timeout,
+        write_consistency,
+        timeout,
         shard_key_selector,
+        priority,
     } = clear_payload_points;
 
     let points_selector = match points {
@@ -439,8 +545,312 @@ pub async fn clear_payload(
         &collection_name,
         points_selector,
         shard_selection,
-        wait.unwrap_or(false),
+        wait,
+        write_ordering_from_proto(ordering)?,
+        shard_deactivation_timeout.map(Duration::from_secs),
+        WriteConsistency::try_from_optional(write_consistency)?,
+        timeout.map(Duration::from_secs),
+        update_priority_from_proto(priority)?,
+    )
+    .await
+    .map_err(error_to_status)?;
+
+    let response = points_operation_response(timing, result);
+    Ok(Response::new(response))
+}
+
+pub async fn undelete(
+    toc: &TableOfContent,
+    undelete_points: UndeletePoints,
+    shard_selection: Option<ShardId>,
+) -> Result<Response<PointsOperationResponse>, Status> {
+    let UndeletePoints {
+        collection_name,
+        wait,
+        ids,
+        ordering,
+        shard_deactivation_timeout,
+        write_consistency,
+        timeout,
+        shard_key_selector,
+        priority,
+    } = undelete_points;
+
+    let points = PointIdsList {
+        points: ids
+            .into_iter()
+            .map(|p| p.try_into())
+            .collect::<Result<_, _>>()?,
+        shard_key: shard_key_selector.map(ShardKeySelector::from),
+    };
+
+    let timing = Instant::now();
+    let result = do_undelete_points(
+        toc,
+        &collection_name,
+        points,
+        shard_selection,
+        wait,
+        write_ordering_from_proto(ordering)?,
+        shard_deactivation_timeout.map(Duration::from_secs),
+        WriteConsistency::try_from_optional(write_consistency)?,
+        timeout.map(Duration::from_secs),
+        update_priority_from_proto(priority)?,
+    )
+    .await
+    .map_err(error_to_status)?;
+
+    let response = points_operation_response(timing, result);
+    Ok(Response::new(response))
+}
+
+pub async fn delete_by_filter(
+    toc: &TableOfContent,
+    delete_by_filter_points: DeleteByFilterPoints,
+) -> Result<Response<FilterUpdateResponse>, Status> {
+    let DeleteByFilterPoints {
+        collection_name,
+        filter,
+        shard_key_selector,
+        batch_size,
+        batch_delay_ms,
+        ordering,
+        shard_deactivation_timeout,
+        write_consistency,
+        timeout,
+        priority,
+    } = delete_by_filter_points;
+
+    let Some(filter) = filter else {
+        return Err(Status::invalid_argument("filter is expected"));
+    };
+    let operation = DeleteByFilter {
+        filter: filter.try_into()?,
+        shard_key: shard_key_selector.map(ShardKeySelector::from),
+        batch_size: batch_size.map(|batch_size| batch_size as usize),
+        batch_delay_ms,
+    };
+
+    let timing = Instant::now();
+    let result = do_delete_by_filter(
+        toc,
+        &collection_name,
+        operation,
+        write_ordering_from_proto(ordering)?,
+        shard_deactivation_timeout.map(Duration::from_secs),
+        WriteConsistency::try_from_optional(write_consistency)?,
+        timeout.map(Duration::from_secs),
+        update_priority_from_proto(priority)?,
+    )
+    .await
+    .map_err(error_to_status)?;
+
+    Ok(Response::new(filter_update_response(timing, result)))
+}
+
+pub async fn set_payload_by_filter(
+    toc: &TableOfContent,
+    set_payload_by_filter_points: SetPayloadByFilterPoints,
+) -> Result<Response<FilterUpdateResponse>, Status> {
+    let SetPayloadByFilterPoints {
+        collection_name,
+        payload,
+        filter,
+        shard_key_selector,
+        batch_size,
+        batch_delay_ms,
+        ordering,
+        shard_deactivation_timeout,
+        write_consistency,
+        timeout,
+        priority,
+    } = set_payload_by_filter_points;
+
+    let Some(filter) = filter else {
+        return Err(Status::invalid_argument("filter is expected"));
+    };
+    let operation = SetPayloadByFilter {
+        payload: proto_to_payloads(payload)?,
+        filter: filter.try_into()?,
+        shard_key: shard_key_selector.map(ShardKeySelector::from),
+        batch_size: batch_size.map(|batch_size| batch_size as usize),
+        batch_delay_ms,
+    };
+
+    let timing = Instant::now();
+    let result = do_set_payload_by_filter(
+        toc,
+        &collection_name,
+        operation,
+        write_ordering_from_proto(ordering)?,
+        shard_deactivation_timeout.map(Duration::from_secs),
+        WriteConsistency::try_from_optional(write_consistency)?,
+        timeout.map(Duration::from_secs),
+        update_priority_from_proto(priority)?,
+    )
+    .await
+    .map_err(error_to_status)?;
+
+    Ok(Response::new(filter_update_response(timing, result)))
+}
+
+/// Convert the operation of the batch into the same operation of the REST API
+fn try_update_operation_from_grpc(
+    operation: PointsUpdateOperation,
+) -> Result<UpdateOperation, Status> {
+    let operation = operation
+        .operation
+        .ok_or(Status::invalid_argument("Operation is missing"))?;
+    let converted = match operation {
+        Operation::Upsert(PointStructList {
+            points,
+            shard_key_selector,
+        }) => UpdateOperation::Upsert(UpsertOperation {
+            upsert: PointInsertOperations::PointsList(PointsList {
+                points: points
+                    .into_iter()
+                    .map(|point| point.try_into())
+                    .collect::<Result<_, _>>()?,
+                shard_key: shard_key_selector.map(ShardKeySelector::from),
+                deduplication: None,
+                ttl: None,
+            }),
+        }),
+        Operation::DeleteDeprecated(points) => UpdateOperation::Delete(DeleteOperation {
+            delete: try_points_selector_from_grpc(points, None)?,
+        }),
+        Operation::DeletePoints(points_update_operation::DeletePoints {
+            points,
+            shard_key_selector,
+        }) => {
+            let points = points.ok_or(Status::invalid_argument("PointSelector is missing"))?;
+            UpdateOperation::Delete(DeleteOperation {
+                delete: try_points_selector_from_grpc(points, shard_key_selector)?,
+            })
+        }
+        Operation::SetPayload(set_payload) => UpdateOperation::SetPayload(SetPayloadOperation {
+            set_payload: try_set_payload_from_grpc(set_payload)?,
+        }),
+        Operation::OverwritePayload(set_payload) => {
+            UpdateOperation::OverwritePayload(OverwritePayloadOperation {
+                overwrite_payload: try_set_payload_from_grpc(set_payload)?,
+            })
+        }
+        Operation::DeletePayload(points_update_operation::DeletePayload {
+            keys,
+            points_selector,
+            shard_key_selector,
+        }) => {
+            let (points, filter) = extract_points_selector(points_selector)?;
+            UpdateOperation::DeletePayload(DeletePayloadOperation {
+                delete_payload: DeletePayload {
+                    keys,
+                    points,
+                    filter,
+                    shard_key: shard_key_selector.map(ShardKeySelector::from),
+                },
+            })
+        }
+        Operation::ClearPayload(ClearPayload {
+            points,
+            shard_key_selector,
+        }) => {
+            let points = points.ok_or(Status::invalid_argument("PointSelector is missing"))?;
+            UpdateOperation::ClearPayload(ClearPayloadOperation {
+                clear_payload: try_points_selector_from_grpc(points, shard_key_selector)?,
+            })
+        }
+        Operation::ClearPayloadDeprecated(selector) => {
+            UpdateOperation::ClearPayload(ClearPayloadOperation {
+                clear_payload: try_points_selector_from_grpc(selector, None)?,
+            })
+        }
+        Operation::UpdateVectors(points_update_operation::UpdateVectors {
+            points,
+            shard_key_selector,
+        }) => UpdateOperation::UpdateVectors(UpdateVectorsOperation {
+            update_vectors: UpdateVectors {
+                points: convert_point_vectors(points)?,
+                shard_key: shard_key_selector.map(ShardKeySelector::from),
+            },
+        }),
+        Operation::DeleteVectors(points_update_operation::DeleteVectors {
+            points_selector,
+            vectors,
+            shard_key_selector,
+        }) => {
+            let (points, filter) = extract_points_selector(points_selector)?;
+            let vector_names = match vectors {
+                Some(vectors) => vectors.names,
+                None => return Err(Status::invalid_argument("vectors is expected")),
+            };
+            UpdateOperation::DeleteVectors(DeleteVectorsOperation {
+                delete_vectors: DeleteVectors {
+                    points,
+                    filter,
+                    vector: vector_names.into_iter().collect(),
+                    shard_key: shard_key_selector.map(ShardKeySelector::from),
+                },
+            })
+        }
+    };
+    Ok(converted)
+}
+
+fn try_set_payload_from_grpc(
+    set_payload: points_update_operation::SetPayload,
+) -> Result<collection::operations::payload_ops::SetPayload, Status> {
+    let points_update_operation::SetPayload {
+        payload,
+        points_selector,
+        shard_key_selector,
+        if_version,
+    } = set_payload;
+    let (points, filter) = extract_points_selector(points_selector)?;
+    Ok(collection::operations::payload_ops::SetPayload {
+        payload: proto_to_payloads(payload)?,
+        points,
+        filter,
+        shard_key: shard_key_selector.map(ShardKeySelector::from),
+        if_version,
+    })
+}
+
+/// Apply all operations of the batch as a single update
+pub async fn update_batch_all_or_nothing(
+    toc: &TableOfContent,
+    update_batch_points: UpdateBatchPoints,
+    shard_selection: Option<ShardId>,
+) -> Result<Response<PointsOperationResponse>, Status> {
+    let UpdateBatchPoints {
+        collection_name,
+        wait,
+        operations,
+        ordering,
+        shard_deactivation_timeout,
+        write_consistency,
+        timeout,
+        all_or_nothing: _,
+        priority,
+    } = update_batch_points;
+
+    let operations = operations
+        .into_iter()
+        .map(try_update_operation_from_grpc)
+        .collect::<Result<_, _>>()?;
+
+    let timing = Instant::now();
+    let result = do_all_or_nothing_batch_update_points(
+        toc,
+        &collection_name,
+        operations,
+        shard_selection,
+        wait,
         write_ordering_from_proto(ordering)?,
+        shard_deactivation_timeout.map(Duration::from_secs),
+        WriteConsistency::try_from_optional(write_consistency)?,
+        timeout.map(Duration::from_secs),
+        update_priority_from_proto(priority)?,
     )
     .await
     .map_err(error_to_status)?;
@@ -454,11 +864,29 @@ pub async fn update_batch(
     update_batch_points: UpdateBatchPoints,
     shard_selection: Option<ShardId>,
 ) -> Result<Response<UpdateBatchResponse>, Status> {
+    if update_batch_points.all_or_nothing.unwrap_or_default() {
+        // Operations are applied as one update, so they share the result
+        let operations_count = update_batch_points.operations.len();
+        let PointsOperationResponse { result, time } =
+            update_batch_all_or_nothing(toc, update_batch_points, shard_selection)
+                .await?
+                .into_inner();
+        return Ok(Response::new(UpdateBatchResponse {
+            result: vec![result.unwrap(); operations_count],
+            time,
+        }));
+    }
+
     let UpdateBatchPoints {
         collection_name,
         wait,
         operations,
         ordering,
+        shard_deactivation_timeout,
+        write_consistency,
+        timeout,
+        all_or_nothing: _,
+        priority,
     } = update_batch_points;
 
     let timing = Instant::now();
@@ -469,6 +897,7 @@ pub async fn update_batch(
             .ok_or(Status::invalid_argument("Operation is missing"))?;
         let collection_name = collection_name.clone();
         let ordering = ordering.clone();
+        let write_consistency = write_consistency.clone();
         let result = match operation {
             points_update_operation::Operation::Upsert(PointStructList {
                 points,
@@ -481,7 +910,12 @@ pub async fn update_batch(
                         points,
                         wait,
                         ordering,
+                        shard_deactivation_timeout,
+                        write_consistency,
+                        timeout,
+                        priority,
                         shard_key_selector,
+                        ttl: None,
                     },
                     shard_selection,
                 )
@@ -495,6 +929,10 @@ pub async fn update_batch(
                         wait,
                         points: Some(points),
                         ordering,
+                        shard_deactivation_timeout,
+                        write_consistency,
+                        timeout,
+                        priority,
                         shard_key_selector: None,
                     },
                     shard_selection,
@@ -506,6 +944,7 @@ pub async fn update_batch(
                     payload,
                     points_selector,
                     shard_key_selector,
+                    if_version,
                 },
             ) => {
                 set_payload(
@@ -516,7 +955,12 @@ pub async fn update_batch(
                         payload,
                         points_selector,
                         ordering,
+                        shard_deactivation_timeout,
+                        write_consistency,
+                        timeout,
+                        priority,
                         shard_key_selector,
+                        if_version,
                     },
                     shard_selection,
                 )
@@ -527,6 +971,7 @@ pub async fn update_batch(
                     payload,
                     points_selector,
                     shard_key_selector,
+                    if_version,
                 },
             ) => {
                 overwrite_payload(
@@ -537,7 +982,12 @@ pub async fn update_batch(
                         payload,
                         points_selector,
                         ordering,
+                        shard_deactivation_timeout,
+                        write_consistency,
+                        timeout,
+                        priority,
                         shard_key_selector,
+                        if_version,
                     },
                     shard_selection,
                 )
@@ -558,6 +1008,10 @@ pub async fn update_batch(
                         keys,
                         points_selector,
                         ordering,
+                        shard_deactivation_timeout,
+                        write_consistency,
+                        timeout,
+                        priority,
                         shard_key_selector,
                     },
                     shard_selection,
@@ -575,6 +1029,10 @@ pub async fn update_batch(
                         wait,
                         points,
                         ordering,
+                        shard_deactivation_timeout,
+                        write_consistency,
+                        timeout,
+                        priority,
                         shard_key_selector,
                     },
                     shard_selection,
@@ -594,6 +1052,10 @@ pub async fn update_batch(
                         wait,
                         points,
                         ordering,
+                        shard_deactivation_timeout,
+                        write_consistency,
+                        timeout,
+                        priority,
                         shard_key_selector,
                     },
                     shard_selection,
@@ -615,6 +1077,10 @@ pub async fn update_batch(
                         points_selector,
                         vectors,
                         ordering,
+                        shard_deactivation_timeout,
+                        write_consistency,
+                        timeout,
+                        priority,
                         shard_key_selector,
                     },
                     shard_selection,
@@ -629,6 +1095,10 @@ pub async fn update_batch(
                         wait,
                         points: Some(selector),
                         ordering,
+                        shard_deactivation_timeout,
+                        write_consistency,
+                        timeout,
+                        priority,
                         shard_key_selector: None,
                     },
                     shard_selection,
@@ -646,6 +1116,10 @@ pub async fn update_batch(
                         wait,
                         points,
                         ordering,
+                        shard_deactivation_timeout,
+                        write_consistency,
+                        timeout,
+                        priority,
                         shard_key_selector,
                     },
                     shard_selection,
@@ -715,6 +1189,10 @@ pub async fn create_field_index(
         field_type,
         field_index_params,
         ordering,
+        shard_deactivation_timeout,
+        write_consistency,
+        timeout,
+        priority,
     } = create_field_index_collection;
 
     let field_schema = convert_field_type(field_type, field_index_params)?;
@@ -730,8 +1208,12 @@ pub async fn create_field_index(
         &collection_name,
         operation,
         shard_selection,
-        wait.unwrap_or(false),
+        wait,
         write_ordering_from_proto(ordering)?,
+        shard_deactivation_timeout.map(Duration::from_secs),
+        WriteConsistency::try_from_optional(write_consistency)?,
+        timeout.map(Duration::from_secs),
+        update_priority_from_proto(priority)?,
     )
     .await
     .map_err(error_to_status)?;
@@ -752,6 +1234,10 @@ pub async fn create_field_index_internal(
         field_type,
         field_index_params,
         ordering,
+        shard_deactivation_timeout,
+        write_consistency,
+        timeout,
+        priority,
     } = create_field_index_collection;
 
     let field_schema = convert_field_type(field_type, field_index_params)?;
@@ -763,8 +1249,12 @@ pub async fn create_field_index_internal(
         field_name,
         field_schema,
         shard_selection,
-        wait.unwrap_or(false),
+        wait,
         write_ordering_from_proto(ordering)?,
+        shard_deactivation_timeout.map(Duration::from_secs),
+        WriteConsistency::try_from_optional(write_consistency)?,
+        timeout.map(Duration::from_secs),
+        update_priority_from_proto(priority)?,
     )
     .await
     .map_err(error_to_status)?;
@@ -783,6 +1273,10 @@ pub async fn delete_field_index(
         wait,
         field_name,
         ordering,
+        shard_deactivation_timeout,
+        write_consistency,
+        timeout,
+        priority,
     } = delete_field_index_collection;
 
     let timing = Instant::now();
@@ -791,8 +1285,12 @@ pub async fn delete_field_index(
         &collection_name,
         field_name,
         shard_selection,
-        wait.unwrap_or(false),
+        wait,
         write_ordering_from_proto(ordering)?,
+        shard_deactivation_timeout.map(Duration::from_secs),
+        WriteConsistency::try_from_optional(write_consistency)?,
+        timeout.map(Duration::from_secs),
+        update_priority_from_proto(priority)?,
     )
     .await
     .map_err(error_to_status)?;
@@ -811,6 +1309,10 @@ pub async fn delete_field_index_internal(
         wait,
         field_name,
         ordering,
+        shard_deactivation_timeout,
+        write_consistency,
+        timeout,
+        priority,
     } = delete_field_index_collection;
 
     let timing = Instant::now();
@@ -819,8 +1321,12 @@ pub async fn delete_field_index_internal(
         &collection_name,
         field_name,
         shard_selection,
-        wait.unwrap_or(false),
+        wait,
         write_ordering_from_proto(ordering)?,
+        shard_deactivation_timeout.map(Duration::from_secs),
+        WriteConsistency::try_from_optional(write_consistency)?,
+        timeout.map(Duration::from_secs),
+        update_priority_from_proto(priority)?,
     )
     .await
     .map_err(error_to_status)?;
@@ -833,6 +1339,9 @@ pub async fn search(
     toc: &TableOfContent,
     search_points: SearchPoints,
     shard_selection: Option<ShardId>,
+    access_tags: Option<&AccessTags>,
+    read_priority: ReadPriority,
+    stale_reads: Option<&StaleReads>,
 ) -> Result<Response<SearchResponse>, Status> {
     let SearchPoints {
         collection_name,
@@ -849,10 +1358,17 @@ pub async fn search(
         timeout,
         shard_key_selector,
         sparse_indices,
+        consistency_token,
+        vectors_count,
+        partial_results,
     } = search_points;
 
-    let vector_struct =
-        api::grpc::conversions::into_named_vector_struct(vector_name, vector, sparse_indices)?;
+    let vector_struct = api::grpc::conversions::into_named_vector_struct(
+        vector_name,
+        vector,
+        sparse_indices,
+        vectors_count,
+    )?;
 
     let shard_selector = convert_shard_selector_for_read(shard_selection, shard_key_selector);
 
@@ -869,27 +1385,121 @@ pub async fn search(
                 .unwrap_or_default(),
         ),
         score_threshold,
+        prefetch: None,
+        rescore: None,
+        distinct_by: None,
     };
 
     let read_consistency = ReadConsistency::try_from_optional(read_consistency)?;
+    let consistency_token = ConsistencyToken::merge_all([consistency_token.as_deref()])
+        .map_err(|err| error_to_status(err.into()))?;
+
+    let timing = Instant::now();
+    let PartialSearchResult {
+        points: scored_points,
+        failed_shards,
+    } = if partial_results.unwrap_or_default() {
+        do_core_search_points_partial(
+            toc,
+            &collection_name,
+            search_request,
+            read_consistency,
+            consistency_token.as_ref(),
+            shard_selector,
+            timeout.map(Duration::from_secs),
+            access_tags,
+            read_priority,
+            stale_reads,
+        )
+        .await
+        .map_err(error_to_status)?
+    } else {
+        let points = do_core_search_points(
+            toc,
+            &collection_name,
+            search_request,
+            read_consistency,
+            consistency_token.as_ref(),
+            shard_selector,
+            timeout.map(Duration::from_secs),
+            access_tags,
+            read_priority,
+            stale_reads,
+        )
+        .await
+        .map_err(error_to_status)?;
+        PartialSearchResult {
+            points,
+            failed_shards: vec![],
+        }
+    };
+
+    let response = SearchResponse {
+        result: scored_points
+            .into_iter()
+            .map(|point| point.into())
+            .collect(),
+        time: timing.elapsed().as_secs_f64(),
+        failed_shards: failed_shards.into_iter().map(Into::into).collect(),
+    };
+
+    Ok(Response::new(response))
+}
+
+pub async fn search_page(
+    toc: &TableOfContent,
+    search_points_page: SearchPointsPage,
+    shard_selection: Option<ShardId>,
+    access_tags: Option<&AccessTags>,
+    read_priority: ReadPriority,
+    stale_reads: Option<&StaleReads>,
+) -> Result<Response<SearchPageResponse>, Status> {
+    let SearchPointsPage {
+        search_points,
+        cursor,
+    } = search_points_page;
+
+    let Some(mut search_points) = search_points else {
+        return Err(Status::invalid_argument("search_points is expected"));
+    };
+    // Pages of the partial results would silently miss points of the failed shards
+    if search_points.partial_results.unwrap_or_default() {
+        return Err(Status::invalid_argument(
+            "Partial results are not supported by the paginated search",
+        ));
+    }
+
+    let collection_name = std::mem::take(&mut search_points.collection_name);
+    let shard_selector =
+        convert_shard_selector_for_read(shard_selection, search_points.shard_key_selector.take());
+    let read_consistency =
+        ReadConsistency::try_from_optional(search_points.read_consistency.take())?;
+    let timeout = search_points.timeout.take().map(Duration::from_secs);
+    let consistency_token =
+        ConsistencyToken::merge_all([search_points.consistency_token.take().as_deref()])
+            .map_err(|err| error_to_status(err.into()))?;
+    let search_request = CoreSearchRequest::try_from(search_points)?;
 
     let timing = Instant::now();
-    let scored_points = do_core_search_points(
+    let page = do_search_page_points(
         toc,
         &collection_name,
         search_request,
+        cursor.as_deref(),
         read_consistency,
+        consistency_token.as_ref(),
         shard_selector,
-        timeout.map(Duration::from_secs),
+        timeout,
+        access_tags,
+        read_priority,
+        stale_reads,
     )
     .await
     .map_err(error_to_status)?;
 
-    let response = SearchResponse {
-        result: scored_points
-            .into_iter()
-            .map(|point| point.into())
-            .collect(),
+    let response = SearchPageResponse {
+        result: page.points.into_iter().map(|point| point.into()).collect(),
+        next_page_cursor: page.next_page_cursor,
         time: timing.elapsed().as_secs_f64(),
     };
 
@@ -901,16 +1511,53 @@ pub async fn core_search_batch(
     collection_name: String,
     requests: Vec<(CoreSearchRequest, ShardSelectorInternal)>,
     read_consistency: Option<ReadConsistencyGrpc>,
+    consistency_token: Option<ConsistencyToken>,
     timeout: Option<Duration>,
+    partial_results: bool,
+    access_tags: Option<&AccessTags>,
+    read_priority: ReadPriority,
+    stale_reads: Option<&StaleReads>,
 ) -> Result<Response<SearchBatchResponse>, Status> {
     let read_consistency = ReadConsistency::try_from_optional(read_consistency)?;
 
     let timing = Instant::now();
 
-    let scored_points =
-        do_search_batch_points(toc, &collection_name, requests, read_consistency, timeout)
-            .await
-            .map_err(error_to_status)?;
+    let PartialSearchBatchResult {
+        results: scored_points,
+        failed_shards,
+    } = if partial_results {
+        do_search_batch_points_partial(
+            toc,
+            &collection_name,
+            requests,
+            read_consistency,
+            consistency_token.as_ref(),
+            timeout,
+            access_tags,
+            read_priority,
+            stale_reads,
+        )
+        .await
+        .map_err(error_to_status)?
+    } else {
+        let results = do_search_batch_points(
+            toc,
+            &collection_name,
+            requests,
+            read_consistency,
+            consistency_token.as_ref(),
+            timeout,
+            access_tags,
+            read_priority,
+            stale_reads,
+        )
+        .await
+        .map_err(error_to_status)?;
+        PartialSearchBatchResult {
+            results,
+            failed_shards: vec![],
+        }
+    };
 
     let response = SearchBatchResponse {
         result: scored_points
@@ -920,6 +1567,7 @@ pub async fn core_search_batch(
             })
             .collect(),
         time: timing.elapsed().as_secs_f64(),
+        failed_shards: failed_shards.into_iter().map(Into::into).collect(),
     };
 
     Ok(Response::new(response))
@@ -959,8 +1607,12 @@ pub async fn core_search_list(
             &collection_name,
             request,
             read_consistency,
+            None,
             shard_selection,
             timeout,
+            None,
+            ReadPriority::Normal,
+            None,
         )
         .await
         .map_err(error_to_status)?;
@@ -973,6 +1625,7 @@ pub async fn core_search_list(
             })
             .collect(),
         time: timing.elapsed().as_secs_f64(),
+        failed_shards: vec![],
     };
 
     Ok(Response::new(response))
@@ -982,6 +1635,9 @@ pub async fn search_groups(
     toc: &TableOfContent,
     search_point_groups: SearchPointGroups,
     shard_selection: Option<ShardId>,
+    access_tags: Option<&AccessTags>,
+    read_priority: ReadPriority,
+    stale_reads: Option<&StaleReads>,
 ) -> Result<Response<SearchGroupsResponse>, Status> {
     let search_groups_request = search_point_groups.clone().try_into()?;
 
@@ -1005,6 +1661,9 @@ pub async fn search_groups(
         read_consistency,
         shard_selector,
         timeout.map(Duration::from_secs),
+        access_tags,
+        read_priority,
+        stale_reads,
     )
     .await
     .map_err(error_to_status)?;
@@ -1020,6 +1679,9 @@ pub async fn search_groups(
 pub async fn recommend(
     toc: &TableOfContent,
     recommend_points: RecommendPoints,
+    access_tags: Option<&AccessTags>,
+    read_priority: ReadPriority,
+    stale_reads: Option<&StaleReads>,
 ) -> Result<Response<RecommendResponse>, Status> {
     // TODO(luis): check if we can make this into a From impl
     let RecommendPoints {
@@ -1056,10 +1718,7 @@ pub async fn recommend(
         .into_iter()
         .map(TryInto::try_into)
         .collect::<Result<Vec<RecommendExample>, Status>>()?;
-    let negative_vectors = negative_vectors
-        .into_iter()
-        .map(|v| RecommendExample::Dense(v.data))
-        .collect();
+    let negative_vectors = negative_vectors.into_iter().map(Into::into).collect();
     let negative = [negative_ids, negative_vectors].concat();
 
     let request = collection::operations::types::RecommendRequestInternal {
@@ -1093,6 +1752,9 @@ pub async fn recommend(
             read_consistency,
             shard_selector,
             timeout,
+            access_tags,
+            read_priority,
+            stale_reads,
         )
         .await
         .map_err(error_to_status)?;
@@ -1114,6 +1776,9 @@ pub async fn recommend_batch(
     recommend_points: Vec<RecommendPoints>,
     read_consistency: Option<ReadConsistencyGrpc>,
     timeout: Option<Duration>,
+    access_tags: Option<&AccessTags>,
+    read_priority: ReadPriority,
+    stale_reads: Option<&StaleReads>,
 ) -> Result<Response<RecommendBatchResponse>, Status> {
     let mut requests = Vec::with_capacity(recommend_points.len());
 
@@ -1129,7 +1794,15 @@ pub async fn recommend_batch(
 
     let timing = Instant::now();
     let scored_points = toc
-        .recommend_batch(&collection_name, requests, read_consistency, timeout)
+        .recommend_batch(
+            &collection_name,
+            requests,
+            read_consistency,
+            timeout,
+            access_tags,
+            read_priority,
+            stale_reads,
+        )
         .await
         .map_err(error_to_status)?;
 
@@ -1149,6 +1822,9 @@ pub async fn recommend_batch(
 pub async fn recommend_groups(
     toc: &TableOfContent,
     recommend_point_groups: RecommendPointGroups,
+    access_tags: Option<&AccessTags>,
+    read_priority: ReadPriority,
+    stale_reads: Option<&StaleReads>,
 ) -> Result<Response<RecommendGroupsResponse>, Status> {
     let recommend_groups_request = recommend_point_groups.clone().try_into()?;
 
@@ -1172,6 +1848,9 @@ pub async fn recommend_groups(
         read_consistency,
         shard_selector,
         timeout.map(Duration::from_secs),
+        access_tags,
+        read_priority,
+        stale_reads,
     )
     .await
     .map_err(error_to_status)?;
@@ -1187,6 +1866,9 @@ pub async fn recommend_groups(
 pub async fn discover(
     toc: &TableOfContent,
     discover_points: DiscoverPoints,
+    access_tags: Option<&AccessTags>,
+    read_priority: ReadPriority,
+    stale_reads: Option<&StaleReads>,
 ) -> Result<Response<DiscoverResponse>, Status> {
     let (request, collection_name, read_consistency, timeout, shard_key_selector) =
         try_discover_request_from_grpc(discover_points)?;
@@ -1202,6 +1884,9 @@ pub async fn discover(
             read_consistency,
             shard_selector,
             timeout,
+            access_tags,
+            read_priority,
+            stale_reads,
         )
         .await
         .map_err(error_to_status)?;
@@ -1223,6 +1908,9 @@ pub async fn discover_batch(
     discover_points: Vec<DiscoverPoints>,
     read_consistency: Option<ReadConsistencyGrpc>,
     timeout: Option<Duration>,
+    access_tags: Option<&AccessTags>,
+    read_priority: ReadPriority,
+    stale_reads: Option<&StaleReads>,
 ) -> Result<Response<DiscoverBatchResponse>, Status> {
     let mut requests = Vec::with_capacity(discover_points.len());
 
@@ -1237,7 +1925,15 @@ pub async fn discover_batch(
 
     let timing = Instant::now();
     let scored_points = toc
-        .discover_batch(&collection_name, requests, read_consistency, timeout)
+        .discover_batch(
+            &collection_name,
+            requests,
+            read_consistency,
+            timeout,
+            access_tags,
+            read_priority,
+            stale_reads,
+        )
         .await
         .map_err(error_to_status)?;
 
@@ -1257,6 +1953,9 @@ pub async fn scroll(
     toc: &TableOfContent,
     scroll_points: ScrollPoints,
     shard_selection: Option<ShardId>,
+    access_tags: Option<&AccessTags>,
+    read_priority: ReadPriority,
+    stale_reads: Option<&StaleReads>,
 ) -> Result<Response<ScrollResponse>, Status> {
     let ScrollPoints {
         collection_name,
@@ -1267,6 +1966,12 @@ pub async fn scroll(
         with_vectors,
         read_consistency,
         shard_key_selector,
+        as_of,
+        session_id,
+        order_by,
+        sample,
+        session_ttl_sec,
+        with_version,
     } = scroll_points;
 
     let scroll_request = ScrollRequestInternal {
@@ -1277,6 +1982,12 @@ pub async fn scroll(
         with_vector: with_vectors
             .map(|selector| selector.into())
             .unwrap_or_default(),
+        as_of,
+        session_id,
+        session_ttl_sec,
+        order_by: order_by.map(|o| o.try_into()).transpose()?,
+        sample: sample.map(Sample::try_from).transpose()?,
+        with_version: with_version.unwrap_or_default(),
     };
 
     let read_consistency = ReadConsistency::try_from_optional(read_consistency)?;
@@ -1290,6 +2001,9 @@ pub async fn scroll(
         scroll_request,
         read_consistency,
         shard_selector,
+        access_tags,
+        read_priority,
+        stale_reads,
     )
     .await
     .map_err(error_to_status)?;
@@ -1302,6 +2016,7 @@ pub async fn scroll(
             .map(|point| point.into())
             .collect(),
         time: timing.elapsed().as_secs_f64(),
+        session_id: scrolled_points.session_id,
     };
 
     Ok(Response::new(response))
@@ -1311,6 +2026,9 @@ pub async fn count(
     toc: &TableOfContent,
     count_points: CountPoints,
     shard_selection: Option<ShardId>,
+    access_tags: Option<&AccessTags>,
+    read_priority: ReadPriority,
+    stale_reads: Option<&StaleReads>,
 ) -> Result<Response<CountResponse>, Status> {
     let CountPoints {
         collection_name,
@@ -1336,6 +2054,9 @@ pub async fn count(
         count_request,
         read_consistency,
         shard_selector,
+        access_tags,
+        read_priority,
+        stale_reads,
     )
     .await
     .map_err(error_to_status)?;
@@ -1352,6 +2073,9 @@ pub async fn get(
     toc: &TableOfContent,
     get_points: GetPoints,
     shard_selection: Option<ShardId>,
+    access_tags: Option<&AccessTags>,
+    read_priority: ReadPriority,
+    stale_reads: Option<&StaleReads>,
 ) -> Result<Response<GetResponse>, Status> {
     let GetPoints {
         collection_name,
@@ -1360,6 +2084,8 @@ pub async fn get(
         with_vectors,
         read_consistency,
         shard_key_selector,
+        as_of,
+        with_version,
     } = get_points;
 
     let point_request = PointRequestInternal {
@@ -1371,6 +2097,8 @@ pub async fn get(
         with_vector: with_vectors
             .map(|selector| selector.into())
             .unwrap_or_default(),
+        as_of,
+        with_version: with_version.unwrap_or_default(),
     };
 
     let read_consistency = ReadConsistency::try_from_optional(read_consistency)?;
@@ -1385,6 +2113,9 @@ pub async fn get(
         point_request,
         read_consistency,
         shard_selector,
+        access_tags,
+        read_priority,
+        stale_reads,
     )
     .await
     .map_err(error_to_status)?;
@@ -9,8 +9,9 @@ use api::grpc::qdrant::{
     GetResponse, PointsOperationResponse, RecommendPointsInternal, RecommendResponse,
     ScrollPointsInternal, ScrollResponse, SearchBatchPointsInternal, SearchBatchResponse,
     SearchPointsInternal, SearchResponse, SetPayloadPointsInternal, SyncPointsInternal,
-    UpdateVectorsInternal, UpsertPointsInternal,
+    UpdateBatchInternal, UpdateVectorsInternal, UpsertPointsInternal,
 };
+use collection::shards::replica_set::read_priority::ReadPriority;
 use storage::content_manager::toc::TableOfContent;
 use tonic::{Request, Response, Status};
 
@@ -19,7 +20,7 @@ use super::validate_and_log;
 use crate::tonic::api::points_common::{
     clear_payload, count, create_field_index_internal, delete, delete_field_index_internal,
     delete_payload, delete_vectors, get, overwrite_payload, recommend, scroll, set_payload, sync,
-    update_vectors, upsert,
+    update_batch_all_or_nothing, update_vectors, upsert,
 };
 
 /// This API is intended for P2P communication within a distributed deployment.
@@ -179,6 +180,22 @@ impl PointsInternal for PointsInternalService {
             .await
     }
 
+    async fn update_batch(
+        &self,
+        request: Request<UpdateBatchInternal>,
+    ) -> Result<Response<PointsOperationResponse>, Status> {
+        validate_and_log(request.get_ref());
+        let UpdateBatchInternal {
+            update_batch,
+            shard_id,
+        } = request.into_inner();
+
+        let update_batch =
+            update_batch.ok_or_else(|| Status::invalid_argument("UpdateBatchPoints is missing"))?;
+
+        update_batch_all_or_nothing(self.toc.as_ref(), update_batch, shard_id).await
+    }
+
     async fn search(
         &self,
         _request: Request<SearchPointsInternal>,
@@ -246,7 +263,14 @@ impl PointsInternal for PointsInternalService {
 
         recommend_points.read_consistency = None; // *Have* to be `None`!
 
-        recommend(self.toc.as_ref(), recommend_points).await
+        recommend(
+            self.toc.as_ref(),
+            recommend_points,
+            None,
+            ReadPriority::Normal,
+            None,
+        )
+        .await
     }
 
     async fn scroll(
@@ -264,7 +288,15 @@ impl PointsInternal for PointsInternalService {
 
         scroll_points.read_consistency = None; // *Have* to be `None`!
 
-        scroll(self.toc.as_ref(), scroll_points, shard_id).await
+        scroll(
+            self.toc.as_ref(),
+            scroll_points,
+            shard_id,
+            None,
+            ReadPriority::Normal,
+            None,
+        )
+        .await
     }
 
     async fn get(
@@ -282,7 +314,15 @@ impl PointsInternal for PointsInternalService {
 
         get_points.read_consistency = None; // *Have* to be `None`!
 
-        get(self.toc.as_ref(), get_points, shard_id).await
+        get(
+            self.toc.as_ref(),
+            get_points,
+            shard_id,
+            None,
+            ReadPriority::Normal,
+            None,
+        )
+        .await
     }
 
     async fn count(
@@ -297,7 +337,15 @@ impl PointsInternal for PointsInternalService {
 
         let count_points =
             count_points.ok_or_else(|| Status::invalid_argument("CountPoints is missing"))?;
-        count(self.toc.as_ref(), count_points, shard_id).await
+        count(
+            self.toc.as_ref(),
+            count_points,
+            shard_id,
+            None,
+            ReadPriority::Normal,
+            None,
+        )
+        .await
     }
 
     async fn sync(
@@ -10,6 +10,7 @@ use api::grpc::qdrant::{
     ListShardSnapshotsRequest, ListSnapshotsRequest, ListSnapshotsResponse,
     RecoverShardSnapshotRequest, RecoverSnapshotResponse,
 };
+use collection::operations::snapshot_ops::SnapshotCreationPriority;
 use storage::content_manager::conversions::error_to_status;
 use storage::content_manager::snapshots::{
     do_create_full_snapshot, do_delete_collection_snapshot, do_delete_full_snapshot,
@@ -44,9 +45,14 @@ impl Snapshots for SnapshotsService {
         let collection_name = request.into_inner().collection_name;
         let timing = Instant::now();
         let dispatcher = self.dispatcher.clone();
-        let response = do_create_snapshot(&dispatcher, &collection_name, true)
-            .await
-            .map_err(error_to_status)?;
+        let response = do_create_snapshot(
+            &dispatcher,
+            &collection_name,
+            SnapshotCreationPriority::Normal,
+            true,
+        )
+        .await
+        .map_err(error_to_status)?;
         Ok(Response::new(CreateSnapshotResponse {
             snapshot_description: Some(response.into()),
             time: timing.elapsed().as_secs_f64(),
@@ -1,6 +1,7 @@
 use std::task::{Context, Poll};
 
 use actix_web_httpauth::headers::authorization::{Bearer, Scheme};
+use collection::collection::access_tags::AccessTags;
 use futures_util::future::BoxFuture;
 use reqwest::header::HeaderValue;
 use reqwest::StatusCode;
@@ -12,14 +13,16 @@ use tower_layer::Layer;
 use crate::common::auth::AuthKeys;
 use crate::common::strings::ct_eq;
 
-const READ_ONLY_RPC_PATHS: [&str; 13] = [
+const READ_ONLY_RPC_PATHS: [&str; 15] = [
     "/qdrant.Collections/List",
     "/qdrant.Collections/Get",
+    "/qdrant.Collections/ListOperations",
     "/qdrant.Points/Scroll",
     "/qdrant.Points/Get",
     "/qdrant.Points/Count",
     "/qdrant.Points/Search",
     "/qdrant.Points/SearchGroups",
+    "/qdrant.Points/SearchPage",
     "/qdrant.Points/SearchBatch",
     "/qdrant.Points/Recommend",
     "/qdrant.Points/RecommendGroups",
@@ -57,7 +60,7 @@ where
 
     fn call(
         &mut self,
-        request: tonic::codegen::http::Request<tonic::transport::Body>,
+        mut request: tonic::codegen::http::Request<tonic::transport::Body>,
     ) -> Self::Future {
         // Grab API key from request
         let key =
@@ -77,6 +80,15 @@ where
             if is_allowed {
                 return Box::pin(self.service.call(request));
             }
+
+            // Access tag keys could only read points, which have any of their tags. The handlers
+            // pass the tags from the request extensions along with the read.
+            if let Some(access_tags) = self.auth_keys.access_tags(&key) {
+                if is_points_read(&request) {
+                    request.extensions_mut().insert(AccessTags(access_tags));
+                    return Box::pin(self.service.call(request));
+                }
+            }
         }
 
         let mut response = Self::Response::new(BoxBody::default());
@@ -110,6 +122,10 @@ impl<S> Layer<S> for ApiKeyMiddlewareLayer {
     }
 }
 
+fn is_points_read<R>(req: &tonic::codegen::http::Request<R>) -> bool {
+    req.uri().path().starts_with("/qdrant.Points/") && is_read_only(req)
+}
+
 fn is_read_only<R>(req: &tonic::codegen::http::Request<R>) -> bool {
     let uri_path = req.uri().path();
     READ_ONLY_RPC_PATHS
@@ -1,6 +1,8 @@
 mod api;
 mod api_key;
 mod logging;
+mod read_priority;
+mod stale_reads;
 mod tonic_telemetry;
 
 use std::io;
@@ -196,6 +198,8 @@ pub fn init(
             .option_layer({
                 AuthKeys::try_create(&settings.service).map(api_key::ApiKeyMiddlewareLayer::new)
             })
+            .layer(stale_reads::StaleReadsMiddlewareLayer::new())
+            .layer(read_priority::ReadPriorityMiddlewareLayer::new())
             .into_inner();
 
         server
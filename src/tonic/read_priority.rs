@@ -0,0 +1,75 @@
+use std::task::{Context, Poll};
+
+use collection::shards::replica_set::read_priority::{ReadPriority, READ_PRIORITY_HEADER};
+use futures_util::future::BoxFuture;
+use tonic::body::BoxBody;
+use tonic::codegen::http::Response;
+use tower::Service;
+use tower_layer::Layer;
+
+/// Sets the priority of the reads of the request from the read priority header
+///
+/// The priority is stored in the request extensions, the handlers pass it along with the reads.
+#[derive(Clone)]
+pub struct ReadPriorityMiddleware<T> {
+    inner: T,
+}
+
+#[derive(Clone)]
+pub struct ReadPriorityMiddlewareLayer;
+
+impl ReadPriorityMiddlewareLayer {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl<S> Service<tonic::codegen::http::Request<tonic::transport::Body>> for ReadPriorityMiddleware<S>
+where
+    S: Service<tonic::codegen::http::Request<tonic::transport::Body>, Response = Response<BoxBody>>
+        + Clone,
+    S::Future: Send + 'static,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = BoxFuture<'static, Result<S::Response, S::Error>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(
+        &mut self,
+        mut request: tonic::codegen::http::Request<tonic::transport::Body>,
+    ) -> Self::Future {
+        let clone = self.inner.clone();
+        let mut inner = std::mem::replace(&mut self.inner, clone);
+
+        let priority = request
+            .headers()
+            .get(READ_PRIORITY_HEADER)
+            .map_or(ReadPriority::Normal, |value| {
+                ReadPriority::from_header(value.as_bytes())
+            });
+
+        request.extensions_mut().insert(priority);
+        Box::pin(inner.call(request))
+    }
+}
+
+/// Priority of the reads of the request, set by [`ReadPriorityMiddleware`]
+pub fn extract_read_priority<T>(request: &tonic::Request<T>) -> ReadPriority {
+    request
+        .extensions()
+        .get::<ReadPriority>()
+        .copied()
+        .unwrap_or_default()
+}
+
+impl<S> Layer<S> for ReadPriorityMiddlewareLayer {
+    type Service = ReadPriorityMiddleware<S>;
+
+    fn layer(&self, service: S) -> Self::Service {
+        ReadPriorityMiddleware { inner: service }
+    }
+}
@@ -0,0 +1,73 @@
+use std::task::{Context, Poll};
+
+use collection::shards::replica_set::stale_reads::{StaleReads, STALE_READ_HEADER};
+use futures_util::future::BoxFuture;
+use tonic::body::BoxBody;
+use tonic::codegen::http::header::{HeaderName, HeaderValue};
+use tonic::codegen::http::Response;
+use tower::Service;
+use tower_layer::Layer;
+
+/// Marks responses, which are served by degraded replicas, with the stale read header
+///
+/// [`StaleReads`] of the request are stored in the request extensions, the handlers pass them
+/// along with the reads.
+#[derive(Clone)]
+pub struct StaleReadsMiddleware<T> {
+    inner: T,
+}
+
+#[derive(Clone)]
+pub struct StaleReadsMiddlewareLayer;
+
+impl StaleReadsMiddlewareLayer {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl<S> Service<tonic::codegen::http::Request<tonic::transport::Body>> for StaleReadsMiddleware<S>
+where
+    S: Service<tonic::codegen::http::Request<tonic::transport::Body>, Response = Response<BoxBody>>
+        + Clone,
+    S::Future: Send + 'static,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = BoxFuture<'static, Result<S::Response, S::Error>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(
+        &mut self,
+        mut request: tonic::codegen::http::Request<tonic::transport::Body>,
+    ) -> Self::Future {
+        let clone = self.inner.clone();
+        let mut inner = std::mem::replace(&mut self.inner, clone);
+
+        let stale_reads = StaleReads::default();
+        request.extensions_mut().insert(stale_reads.clone());
+
+        let future = inner.call(request);
+        Box::pin(async move {
+            let mut response = future.await?;
+            if stale_reads.is_stale() {
+                response.headers_mut().insert(
+                    HeaderName::from_static(STALE_READ_HEADER),
+                    HeaderValue::from_static("true"),
+                );
+            }
+            Ok(response)
+        })
+    }
+}
+
+impl<S> Layer<S> for StaleReadsMiddlewareLayer {
+    type Service = StaleReadsMiddleware<S>;
+
+    fn layer(&self, service: S) -> Self::Service {
+        StaleReadsMiddleware { inner: service }
+    }
+}
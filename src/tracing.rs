@@ -3,7 +3,7 @@ use std::str::FromStr as _;
 
 use colored::control::ShouldColorize;
 use tracing_subscriber::prelude::*;
-use tracing_subscriber::{filter, fmt};
+use tracing_subscriber::{filter, fmt, reload, Registry};
 
 const DEFAULT_LOG_LEVEL: log::LevelFilter = log::LevelFilter::Info;
 
@@ -16,9 +16,10 @@ const DEFAULT_FILTERS: &[(&str, log::LevelFilter)] = &[
     ("raft", log::LevelFilter::Warn),
 ];
 
-pub fn setup(user_filters: &str) -> anyhow::Result<()> {
-    tracing_log::LogTracer::init()?;
+/// Handle to change log filters of the running service
+pub type LoggerHandle = reload::Handle<filter::EnvFilter, Registry>;
 
+fn env_filter(user_filters: &str) -> filter::EnvFilter {
     let mut filters = DEFAULT_LOG_LEVEL.to_string();
 
     let user_log_level = user_filters
@@ -33,16 +34,22 @@ pub fn setup(user_filters: &str) -> anyhow::Result<()> {
 
     write!(&mut filters, ",{user_filters}").unwrap(); // Writing into `String` never fails
 
+    filter::EnvFilter::builder()
+        .with_regex(false)
+        .parse_lossy(filters)
+}
+
+pub fn setup(user_filters: &str) -> anyhow::Result<LoggerHandle> {
+    tracing_log::LogTracer::init()?;
+
+    let (filter_layer, logger_handle) = reload::Layer::new(env_filter(user_filters));
+
     let reg = tracing_subscriber::registry().with(
         fmt::layer()
             // Only use ANSI if we should colorize
             .with_ansi(ShouldColorize::from_env().should_colorize())
             .with_span_events(fmt::format::FmtSpan::NEW)
-            .with_filter(
-                filter::EnvFilter::builder()
-                    .with_regex(false)
-                    .parse_lossy(filters),
-            ),
+            .with_filter(filter_layer),
     );
 
     // Use `console` or `console-subscriber` feature to enable `console-subscriber`
@@ -70,5 +77,11 @@ pub fn setup(user_filters: &str) -> anyhow::Result<()> {
 
     tracing::subscriber::set_global_default(reg)?;
 
+    Ok(logger_handle)
+}
+
+/// Replace log filters of the running service, same format as in `setup`
+pub fn reload(logger_handle: &LoggerHandle, user_filters: &str) -> anyhow::Result<()> {
+    logger_handle.reload(env_filter(user_filters))?;
     Ok(())
 }
@@ -0,0 +1,77 @@
+import pathlib
+
+from .fixtures import upsert_random_points, create_collection
+from .utils import *
+
+N_PEERS = 3
+N_SHARDS = 3
+N_REPLICA = 2
+COLLECTION_NAME = "test_collection"
+
+
+def check_collection_shard_count(peer_api_uri: str, collection_name: str, expected_shard_count: int) -> bool:
+    collection_cluster_info = get_collection_cluster_info(peer_api_uri, collection_name)
+    return collection_cluster_info['shard_count'] == expected_shard_count
+
+
+def get_points_count(peer_api_uri: str, collection_name: str) -> int:
+    r = requests.post(
+        f"{peer_api_uri}/collections/{collection_name}/points/count", json={
+            "exact": True
+        }
+    )
+    assert_http_ok(r)
+    return r.json()["result"]['count']
+
+
+# Split one shard into two new shards
+#
+# Test that the shard is replaced on all peers, and no points are lost or duplicated
+def test_shard_split(tmp_path: pathlib.Path):
+    assert_project_root()
+
+    peer_api_uris, peer_dirs, bootstrap_uri = start_cluster(tmp_path, N_PEERS)
+
+    create_collection(peer_api_uris[0], shard_number=N_SHARDS, replication_factor=N_REPLICA)
+    wait_collection_exists_and_active_on_all_peers(
+        collection_name=COLLECTION_NAME,
+        peer_api_uris=peer_api_uris
+    )
+
+    upsert_random_points(peer_api_uris[0], 1000)
+
+    r = requests.post(
+        f"{peer_api_uris[0]}/collections/{COLLECTION_NAME}/cluster", json={
+            "split_shard": {
+                "shard_id": 0
+            }
+        })
+    assert_http_ok(r)
+
+    # Split is finished, once the shard is replaced with two new shards
+    for uri in peer_api_uris:
+        wait_for(check_collection_shard_count, uri, COLLECTION_NAME, N_SHARDS + 1)
+        wait_for_all_replicas_active(uri, COLLECTION_NAME)
+
+    collection_cluster_info = get_collection_cluster_info(peer_api_uris[0], COLLECTION_NAME)
+    shard_ids = {shard['shard_id'] for shard in collection_cluster_info['local_shards']}
+    shard_ids |= {shard['shard_id'] for shard in collection_cluster_info['remote_shards']}
+    assert 0 not in shard_ids
+
+    # Point counts must be consistent across nodes
+    for uri in peer_api_uris:
+        assert get_points_count(uri, COLLECTION_NAME) == 1000
+
+    # Updates are routed to the new shards
+    upsert_random_points(peer_api_uris[1], 100, offset=1000)
+    for uri in peer_api_uris:
+        assert get_points_count(uri, COLLECTION_NAME) == 1100
+
+    # Shard, which was split, does not exist anymore
+    r = requests.post(
+        f"{peer_api_uris[0]}/collections/{COLLECTION_NAME}/cluster", json={
+            "split_shard": {
+                "shard_id": 0
+            }
+        })
+    assert r.status_code == 400